@@ -1,22 +1,91 @@
-use sqlx::postgres::PgPoolOptions;
-use sqlx::{Executor, PgPool};
+use serde::Serialize;
+use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
+use sqlx::{Executor, PgPool, Postgres, Transaction};
+use std::str::FromStr;
+use std::time::Duration;
+
+/// Connection pool tuning for one service. Defaults match sqlx's.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PoolSettings {
+    pub max_connections: u32,
+    /// Connections kept open even when idle.
+    pub min_connections: u32,
+    /// How long a query waits for a free connection before failing.
+    pub acquire_timeout: Duration,
+    /// Idle connections above `min_connections` are closed after this long;
+    /// `None` keeps them open.
+    pub idle_timeout: Option<Duration>,
+    /// Prepared statements cached per connection (0 disables the cache).
+    pub statement_cache_capacity: usize,
+}
+
+impl PoolSettings {
+    pub fn with_max_connections(max_connections: u32) -> Self {
+        Self {
+            max_connections,
+            ..Self::default()
+        }
+    }
+}
+
+impl Default for PoolSettings {
+    fn default() -> Self {
+        Self {
+            max_connections: 10,
+            min_connections: 0,
+            acquire_timeout: Duration::from_secs(30),
+            idle_timeout: Some(Duration::from_secs(600)),
+            statement_cache_capacity: 100,
+        }
+    }
+}
+
+/// Create a database connection pool with default settings and `max_connections`.
+pub async fn create_pool(database_url: &str, max_connections: u32) -> Result<PgPool, sqlx::Error> {
+    create_pool_with(
+        database_url,
+        &PoolSettings::with_max_connections(max_connections),
+    )
+    .await
+}
 
 /// Create a database connection pool.
 /// Sets statement_timeout = 10s on every connection to prevent slow queries
 /// from exhausting the pool.
-pub async fn create_pool(database_url: &str, max_connections: u32) -> Result<PgPool, sqlx::Error> {
+pub async fn create_pool_with(
+    database_url: &str,
+    settings: &PoolSettings,
+) -> Result<PgPool, sqlx::Error> {
+    let connect_options = PgConnectOptions::from_str(database_url)?
+        .statement_cache_capacity(settings.statement_cache_capacity);
     PgPoolOptions::new()
-        .max_connections(max_connections)
+        .max_connections(settings.max_connections)
+        .min_connections(settings.min_connections)
+        .acquire_timeout(settings.acquire_timeout)
+        .idle_timeout(settings.idle_timeout)
         .after_connect(|conn, _meta| {
             Box::pin(async move {
                 conn.execute("SET statement_timeout = '10s'").await?;
                 Ok(())
             })
         })
-        .connect(database_url)
+        .connect_with(connect_options)
         .await
 }
 
+/// Begin a transaction whose statements are cancelled after `timeout`.
+/// `SET LOCAL` scopes the override to this transaction, so the connection
+/// returns to the pool with the default 10s timeout.
+pub async fn begin_with_statement_timeout(
+    pool: &PgPool,
+    timeout: Duration,
+) -> Result<Transaction<'static, Postgres>, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    tx.execute(format!("SET LOCAL statement_timeout = '{}ms'", timeout.as_millis()).as_str())
+        .await?;
+    Ok(tx)
+}
+
 /// Run database migrations using a dedicated connection without statement_timeout,
 /// since migrations (index builds, bulk inserts) can legitimately exceed 10s.
 pub async fn run_migrations(database_url: &str) -> Result<(), sqlx::Error> {
@@ -24,9 +93,139 @@ pub async fn run_migrations(database_url: &str) -> Result<(), sqlx::Error> {
         .max_connections(1)
         .connect(database_url)
         .await?;
-    sqlx::migrate!("../../migrations")
+    atlas_migrations::MIGRATOR
         .run(&pool)
         .await
         .map_err(|e| sqlx::Error::Migrate(Box::new(e)))?;
     Ok(())
 }
+
+/// How the database schema compares with the migrations built into this binary.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MigrationInfo {
+    /// Newest embedded migration: the schema this build expects.
+    pub expected_version: i64,
+    /// Newest migration the database has applied, `None` before the first run.
+    pub applied_version: Option<i64>,
+    /// Every version recorded in `_sqlx_migrations`, oldest first.
+    pub applied: Vec<i64>,
+    /// Embedded migrations the database has not applied yet.
+    pub pending: Vec<i64>,
+    /// Applied migrations this build does not know, e.g. after a newer release migrated the database.
+    pub unknown: Vec<i64>,
+    /// Applied migrations whose SQL differs from the embedded file.
+    pub checksum_mismatches: Vec<i64>,
+    /// Migrations that started but did not complete.
+    pub failed: Vec<i64>,
+    /// True when the database is at exactly the schema this build expects.
+    pub compatible: bool,
+}
+
+impl MigrationInfo {
+    /// Compare embedded `(version, checksum)` pairs with applied
+    /// `(version, checksum, success)` rows.
+    pub fn compare(embedded: &[(i64, &[u8])], applied: &[(i64, Vec<u8>, bool)]) -> Self {
+        let expected_version = embedded.iter().map(|(v, _)| *v).max().unwrap_or(0);
+        let pending: Vec<i64> = embedded
+            .iter()
+            .filter(|(v, _)| !applied.iter().any(|(a, _, ok)| a == v && *ok))
+            .map(|(v, _)| *v)
+            .collect();
+        let unknown: Vec<i64> = applied
+            .iter()
+            .filter(|(a, _, _)| !embedded.iter().any(|(v, _)| v == a))
+            .map(|(a, _, _)| *a)
+            .collect();
+        let checksum_mismatches: Vec<i64> = applied
+            .iter()
+            .filter(|(a, checksum, _)| {
+                embedded
+                    .iter()
+                    .any(|(v, expected)| v == a && *expected != checksum.as_slice())
+            })
+            .map(|(a, _, _)| *a)
+            .collect();
+        let failed: Vec<i64> = applied
+            .iter()
+            .filter(|(_, _, ok)| !ok)
+            .map(|(a, _, _)| *a)
+            .collect();
+        let compatible = pending.is_empty()
+            && unknown.is_empty()
+            && checksum_mismatches.is_empty()
+            && failed.is_empty();
+
+        Self {
+            expected_version,
+            applied_version: applied.iter().map(|(a, _, _)| *a).max(),
+            applied: applied.iter().map(|(a, _, _)| *a).collect(),
+            pending,
+            unknown,
+            checksum_mismatches,
+            failed,
+            compatible,
+        }
+    }
+}
+
+/// Read `_sqlx_migrations` and compare it with the embedded migrations.
+/// A database that was never migrated reports every migration as pending.
+pub async fn migration_info(pool: &PgPool) -> Result<MigrationInfo, sqlx::Error> {
+    let tracked: bool = sqlx::query_scalar("SELECT to_regclass('_sqlx_migrations') IS NOT NULL")
+        .fetch_one(pool)
+        .await?;
+    let applied: Vec<(i64, Vec<u8>, bool)> = if tracked {
+        sqlx::query_as("SELECT version, checksum, success FROM _sqlx_migrations ORDER BY version")
+            .fetch_all(pool)
+            .await?
+    } else {
+        Vec::new()
+    };
+    let embedded: Vec<(i64, &[u8])> = atlas_migrations::embedded()
+        .map(|m| (m.version, m.checksum))
+        .collect();
+    Ok(MigrationInfo::compare(&embedded, &applied))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn migration_info_is_compatible_when_everything_matches() {
+        let info = MigrationInfo::compare(
+            &[(1, b"a"), (2, b"b")],
+            &[(1, b"a".to_vec(), true), (2, b"b".to_vec(), true)],
+        );
+        assert!(info.compatible);
+        assert_eq!(info.expected_version, 2);
+        assert_eq!(info.applied_version, Some(2));
+        assert_eq!(info.applied, vec![1, 2]);
+    }
+
+    #[test]
+    fn migration_info_reports_each_kind_of_drift() {
+        let info = MigrationInfo::compare(
+            &[(1, b"a"), (2, b"b"), (3, b"c")],
+            &[
+                (1, b"edited".to_vec(), true),
+                (2, b"b".to_vec(), false),
+                (4, b"d".to_vec(), true),
+            ],
+        );
+        assert!(!info.compatible);
+        assert_eq!(info.pending, vec![2, 3]);
+        assert_eq!(info.unknown, vec![4]);
+        assert_eq!(info.checksum_mismatches, vec![1]);
+        assert_eq!(info.failed, vec![2]);
+        assert_eq!(info.applied_version, Some(4));
+    }
+
+    #[test]
+    fn migration_info_treats_an_empty_database_as_fully_pending() {
+        let info = MigrationInfo::compare(&[(1, b"a")], &[]);
+        assert_eq!(info.applied_version, None);
+        assert_eq!(info.pending, vec![1]);
+        assert!(!info.compatible);
+    }
+}
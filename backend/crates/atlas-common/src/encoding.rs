@@ -0,0 +1,115 @@
+//! Hex string form of addresses and hashes.
+//!
+//! Every address and hash is stored and served as `0x` followed by lowercase
+//! hex (42 characters for an address, 66 for a 32-byte hash). These helpers
+//! encode the raw bytes directly instead of going through alloy's `Debug` or
+//! `Display` impls, whose output (checksummed or not) is not part of alloy's
+//! API and could change on an upgrade.
+
+use alloy::primitives::{Address, B256};
+
+use crate::AtlasError;
+
+/// `0x`-prefixed lowercase hex of an address.
+pub fn address_to_hex(address: &Address) -> String {
+    format!("0x{}", hex::encode(address.as_slice()))
+}
+
+/// `0x`-prefixed lowercase hex of a 32-byte hash or topic.
+pub fn b256_to_hex(value: &B256) -> String {
+    format!("0x{}", hex::encode(value.as_slice()))
+}
+
+/// Address held in the low 20 bytes of an indexed event topic.
+pub fn topic_to_address_hex(topic: &B256) -> String {
+    address_to_hex(&Address::from_word(*topic))
+}
+
+/// Parse an address given with or without `0x`, in any letter case.
+pub fn parse_address(value: &str) -> Result<Address, AtlasError> {
+    value
+        .trim()
+        .parse()
+        .map_err(|_| AtlasError::InvalidInput("Invalid address".to_string()))
+}
+
+/// Parse a 32-byte hash given with or without `0x`, in any letter case.
+pub fn parse_b256(value: &str) -> Result<B256, AtlasError> {
+    value
+        .trim()
+        .parse()
+        .map_err(|_| AtlasError::InvalidInput("Invalid hash".to_string()))
+}
+
+/// Raw bytes of a `0x`-prefixed hex string, for binding to `BYTEA` columns.
+pub fn hex_to_bytes(value: &str) -> Result<Vec<u8>, AtlasError> {
+    let digits = value.strip_prefix("0x").unwrap_or(value);
+    hex::decode(digits).map_err(|_| AtlasError::InvalidInput(format!("Invalid hex {value:?}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::{address, b256};
+
+    const ADDRESS: Address = address!("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed");
+    const HASH: B256 = b256!("0xDDF252AD1BE2C89B69C2B068FC378DAA952BA7F163C4A11628F55A4DF523B3EF");
+
+    #[test]
+    fn addresses_are_lowercase_and_prefixed() {
+        assert_eq!(
+            address_to_hex(&ADDRESS),
+            "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed"
+        );
+        assert_eq!(
+            address_to_hex(&Address::ZERO),
+            "0x0000000000000000000000000000000000000000"
+        );
+    }
+
+    #[test]
+    fn hashes_are_lowercase_and_prefixed() {
+        assert_eq!(
+            b256_to_hex(&HASH),
+            "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"
+        );
+        assert_eq!(b256_to_hex(&B256::ZERO).len(), 66);
+    }
+
+    #[test]
+    fn topic_addresses_use_the_low_20_bytes() {
+        assert_eq!(
+            topic_to_address_hex(&ADDRESS.into_word()),
+            "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed"
+        );
+    }
+
+    #[test]
+    fn parsing_round_trips() {
+        for input in [
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+            "5aaeb6053f3e94c9b9a09f33669435e7ef1beaed",
+            " 0x5AAEB6053F3E94C9B9A09F33669435E7EF1BEAED ",
+        ] {
+            assert_eq!(parse_address(input).unwrap(), ADDRESS, "{input}");
+        }
+        assert_eq!(parse_b256(&b256_to_hex(&HASH)).unwrap(), HASH);
+    }
+
+    #[test]
+    fn hex_strings_decode_to_bytes() {
+        assert_eq!(
+            hex_to_bytes(&address_to_hex(&ADDRESS)).unwrap(),
+            ADDRESS.to_vec()
+        );
+        assert_eq!(hex_to_bytes("0xABcd").unwrap(), vec![0xab, 0xcd]);
+        assert!(hex_to_bytes("0xabc").is_err());
+    }
+
+    #[test]
+    fn parsing_rejects_wrong_lengths_and_non_hex() {
+        assert!(parse_address("0x1234").is_err());
+        assert!(parse_address("0xzzaeb6053f3e94c9b9a09f33669435e7ef1beaed").is_err());
+        assert!(parse_b256(&address_to_hex(&ADDRESS)).is_err());
+    }
+}
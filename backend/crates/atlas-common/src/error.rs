@@ -20,6 +20,9 @@ pub enum AtlasError {
     #[error("Unauthorized: {0}")]
     Unauthorized(String),
 
+    #[error("Forbidden: {0}")]
+    Forbidden(String),
+
     #[error("Metadata fetch error: {0}")]
     MetadataFetch(String),
 
@@ -38,11 +41,23 @@ pub enum AtlasError {
     #[error("Bytecode mismatch: {0}")]
     BytecodeMismatch(String),
 
+    #[error("Payload too large: {0}")]
+    PayloadTooLarge(String),
+
+    #[error("Limit exceeded: {0}")]
+    LimitExceeded(String),
+
     #[error("Too many requests: {message} (retry after {retry_after_seconds}s)")]
     TooManyRequests {
         message: String,
         retry_after_seconds: u64,
     },
+
+    #[error("Service unavailable: {message} (retry after {retry_after_seconds}s)")]
+    ServiceUnavailable {
+        message: String,
+        retry_after_seconds: u64,
+    },
 }
 
 impl AtlasError {
@@ -51,12 +66,15 @@ impl AtlasError {
             AtlasError::NotFound(_) => 404,
             AtlasError::InvalidInput(_) | AtlasError::Validation(_) => 400,
             AtlasError::Unauthorized(_) => 401,
+            AtlasError::Forbidden(_) => 403,
             AtlasError::Database(_) | AtlasError::Internal(_) => 500,
             AtlasError::Rpc(_) | AtlasError::MetadataFetch(_) => 502,
             AtlasError::Config(_) => 500,
             AtlasError::Verification(_) | AtlasError::BytecodeMismatch(_) => 400,
-            AtlasError::Compilation(_) => 422,
+            AtlasError::Compilation(_) | AtlasError::LimitExceeded(_) => 422,
+            AtlasError::PayloadTooLarge(_) => 413,
             AtlasError::TooManyRequests { .. } => 429,
+            AtlasError::ServiceUnavailable { .. } => 503,
         }
     }
 }
@@ -1,5 +1,8 @@
 pub mod db;
+pub mod encoding;
 pub mod error;
+pub mod numeric;
+pub mod storage;
 pub mod types;
 
 pub use error::AtlasError;
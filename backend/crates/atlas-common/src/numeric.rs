@@ -0,0 +1,123 @@
+//! How numeric values are written in API responses.
+//!
+//! JavaScript parses JSON numbers as doubles, so integers past 2^53 lose
+//! precision. The policy:
+//!
+//! - Amounts of wei, tokens or gas (balances, values, supplies, fees, gas
+//!   used and prices, volumes) are decimal strings. `BigDecimal` always
+//!   serializes as a string; fields with machine-sized types use
+//!   [`quantity`] or [`option_quantity`].
+//! - Heights, timestamps, indices, counts, decimals and ratios stay numbers.
+//!
+//! Machine-sized quantities were served as numbers before this policy, and
+//! still are unless the response is written inside
+//! [`with_numbers_as_strings`], as `/api/v1` responses are.
+
+use serde::{Serialize, Serializer};
+use std::fmt::Display;
+use std::future::Future;
+
+tokio::task_local! {
+    static NUMBERS_AS_STRINGS: bool;
+}
+
+/// Run `future` with [`quantity`] fields written as strings.
+pub async fn with_numbers_as_strings<F: Future>(future: F) -> F::Output {
+    NUMBERS_AS_STRINGS.scope(true, future).await
+}
+
+/// Whether quantities are written as strings in the current task.
+pub fn numbers_as_strings() -> bool {
+    NUMBERS_AS_STRINGS.try_with(|mode| *mode).unwrap_or(false)
+}
+
+/// Run `f` with the given mode, for serializing outside the task that chose
+/// it, such as in a response body stream.
+pub fn with_mode<R>(as_strings: bool, f: impl FnOnce() -> R) -> R {
+    NUMBERS_AS_STRINGS.sync_scope(as_strings, f)
+}
+
+/// `serialize_with` for an integer or float quantity: a decimal string in
+/// string mode, otherwise the number itself.
+pub fn quantity<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: Display + Serialize,
+    S: Serializer,
+{
+    if numbers_as_strings() {
+        serializer.collect_str(value)
+    } else {
+        value.serialize(serializer)
+    }
+}
+
+/// [`quantity`] for an optional field; `None` stays `null`.
+pub fn option_quantity<T, S>(value: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: Display + Serialize,
+    S: Serializer,
+{
+    match value {
+        Some(value) => quantity(value, serializer),
+        None => serializer.serialize_none(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bigdecimal::BigDecimal;
+    use serde_json::json;
+
+    #[derive(Serialize)]
+    struct Amounts {
+        #[serde(serialize_with = "quantity")]
+        gas_used: i64,
+        #[serde(serialize_with = "quantity")]
+        volume: f64,
+        #[serde(serialize_with = "option_quantity")]
+        supply: Option<i64>,
+        #[serde(serialize_with = "option_quantity")]
+        missing: Option<i64>,
+        value: BigDecimal,
+    }
+
+    fn amounts() -> Amounts {
+        Amounts {
+            gas_used: 9_007_199_254_740_993,
+            volume: 1.5,
+            supply: Some(42),
+            missing: None,
+            value: "123456789012345678901234567890".parse().unwrap(),
+        }
+    }
+
+    #[test]
+    fn quantities_are_numbers_by_default() {
+        assert!(!numbers_as_strings());
+        assert_eq!(
+            serde_json::to_value(amounts()).unwrap(),
+            json!({
+                "gas_used": 9_007_199_254_740_993_i64, "volume": 1.5, "supply": 42,
+                "missing": null, "value": "123456789012345678901234567890"
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn quantities_are_strings_in_string_mode() {
+        let value =
+            with_numbers_as_strings(async { serde_json::to_value(amounts()).unwrap() }).await;
+        assert_eq!(
+            value,
+            json!({
+                "gas_used": "9007199254740993", "volume": "1.5", "supply": "42",
+                "missing": null, "value": "123456789012345678901234567890"
+            })
+        );
+        assert_eq!(
+            with_mode(true, || serde_json::to_value(amounts()).unwrap())["supply"],
+            "42"
+        );
+    }
+}
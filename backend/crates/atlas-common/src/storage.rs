@@ -0,0 +1,215 @@
+//! Hot and cold storage tiers for the largest block-partitioned tables.
+//!
+//! Old partitions of `event_logs` and `erc20_transfers` can be moved out of
+//! the main database (into archive tables in another schema, or into another
+//! Postgres database) to keep the hot tables small. Readers ask
+//! [`TieredStorage::segments`] where a block range lives and query each
+//! [`Segment`] in turn, stitching the rows together.
+
+use sqlx::PgPool;
+
+use crate::AtlasError;
+
+/// Tables whose old rows may live in the cold tier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TieredTable {
+    EventLogs,
+    Erc20Transfers,
+}
+
+impl TieredTable {
+    pub fn name(self) -> &'static str {
+        match self {
+            TieredTable::EventLogs => "event_logs",
+            TieredTable::Erc20Transfers => "erc20_transfers",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tier {
+    Hot,
+    Cold,
+}
+
+/// Inclusive range of block numbers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockRange {
+    pub from: i64,
+    pub to: i64,
+}
+
+impl BlockRange {
+    pub fn new(from: i64, to: i64) -> Self {
+        Self { from, to }
+    }
+
+    /// Range holding the single block `number`.
+    pub fn block(number: i64) -> Self {
+        Self::new(number, number)
+    }
+
+    /// Every block up to and including `to`.
+    pub fn up_to(to: i64) -> Self {
+        Self::new(0, to)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.from > self.to
+    }
+}
+
+/// Part of a block range that is read from one place.
+#[derive(Debug, Clone)]
+pub struct Segment {
+    pub tier: Tier,
+    pub pool: PgPool,
+    /// Table to select from, schema-qualified for the cold tier
+    pub table: String,
+    pub blocks: BlockRange,
+}
+
+/// Where each block range of a [`TieredTable`] is stored.
+pub trait TieredStorage: Send + Sync {
+    /// Segments covering `blocks` of `table`, oldest blocks first. An empty
+    /// range has no segments.
+    fn segments(&self, table: TieredTable, blocks: BlockRange) -> Vec<Segment>;
+
+    /// The segment holding block `number` of `table`.
+    fn segment_for_block(&self, table: TieredTable, number: i64) -> Segment {
+        self.segments(table, BlockRange::block(number))
+            .pop()
+            .expect("a single block lies in exactly one segment")
+    }
+}
+
+/// Every block in the main database's tables.
+pub struct HotStorage {
+    pool: PgPool,
+}
+
+impl HotStorage {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+impl TieredStorage for HotStorage {
+    fn segments(&self, table: TieredTable, blocks: BlockRange) -> Vec<Segment> {
+        if blocks.is_empty() {
+            return Vec::new();
+        }
+        vec![Segment {
+            tier: Tier::Hot,
+            pool: self.pool.clone(),
+            table: table.name().to_string(),
+            blocks,
+        }]
+    }
+}
+
+/// Blocks below `boundary` in `schema`'s tables on the cold pool, the rest
+/// in the main database.
+pub struct ColdBelowBlock {
+    hot: PgPool,
+    cold: PgPool,
+    schema: String,
+    boundary: i64,
+}
+
+impl ColdBelowBlock {
+    /// `schema` names the schema of the archived tables and must be a plain
+    /// SQL identifier.
+    pub fn new(hot: PgPool, cold: PgPool, schema: &str, boundary: i64) -> Result<Self, AtlasError> {
+        if !is_identifier(schema) {
+            return Err(AtlasError::Config(format!(
+                "cold storage schema {schema:?} must be a lowercase SQL identifier"
+            )));
+        }
+        if boundary <= 0 {
+            return Err(AtlasError::Config(
+                "cold storage boundary block must be greater than 0".to_string(),
+            ));
+        }
+        Ok(Self {
+            hot,
+            cold,
+            schema: schema.to_string(),
+            boundary,
+        })
+    }
+}
+
+impl TieredStorage for ColdBelowBlock {
+    fn segments(&self, table: TieredTable, blocks: BlockRange) -> Vec<Segment> {
+        let (cold, hot) = split_at(blocks, self.boundary);
+        let cold = cold.map(|blocks| Segment {
+            tier: Tier::Cold,
+            pool: self.cold.clone(),
+            table: format!("{}.{}", self.schema, table.name()),
+            blocks,
+        });
+        let hot = hot.map(|blocks| Segment {
+            tier: Tier::Hot,
+            pool: self.hot.clone(),
+            table: table.name().to_string(),
+            blocks,
+        });
+        cold.into_iter().chain(hot).collect()
+    }
+}
+
+/// Split `blocks` into the part below `boundary` and the part from it on.
+fn split_at(blocks: BlockRange, boundary: i64) -> (Option<BlockRange>, Option<BlockRange>) {
+    let below = BlockRange::new(blocks.from, blocks.to.min(boundary - 1));
+    let above = BlockRange::new(blocks.from.max(boundary), blocks.to);
+    (
+        (!below.is_empty()).then_some(below),
+        (!above.is_empty()).then_some(above),
+    )
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_lowercase() || c == '_')
+        && chars.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+        && name.len() <= 63
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_at_divides_ranges_around_the_boundary() {
+        let boundary = 100;
+        assert_eq!(
+            split_at(BlockRange::new(10, 50), boundary),
+            (Some(BlockRange::new(10, 50)), None)
+        );
+        assert_eq!(
+            split_at(BlockRange::new(100, 150), boundary),
+            (None, Some(BlockRange::new(100, 150)))
+        );
+        assert_eq!(
+            split_at(BlockRange::new(90, 110), boundary),
+            (
+                Some(BlockRange::new(90, 99)),
+                Some(BlockRange::new(100, 110))
+            )
+        );
+        assert_eq!(split_at(BlockRange::new(5, 4), boundary), (None, None));
+    }
+
+    #[test]
+    fn is_identifier_accepts_plain_schema_names() {
+        assert!(is_identifier("archive"));
+        assert!(is_identifier("_cold_2024"));
+        assert!(!is_identifier(""));
+        assert!(!is_identifier("2024"));
+        assert!(!is_identifier("Archive"));
+        assert!(!is_identifier("archive; DROP TABLE blocks"));
+    }
+}
@@ -10,7 +10,9 @@ pub struct Block {
     pub hash: String,
     pub parent_hash: String,
     pub timestamp: i64,
+    #[serde(serialize_with = "crate::numeric::quantity")]
     pub gas_used: i64,
+    #[serde(serialize_with = "crate::numeric::quantity")]
     pub gas_limit: i64,
     pub base_fee_per_gas: Option<String>,
     pub transaction_count: i32,
@@ -39,6 +41,7 @@ pub struct Transaction {
     pub to_address: Option<String>,
     pub value: BigDecimal,
     pub gas_price: BigDecimal,
+    #[serde(serialize_with = "crate::numeric::quantity")]
     pub gas_used: i64,
     pub input_data: Vec<u8>,
     pub status: bool,
@@ -61,6 +64,7 @@ pub struct NftContract {
     pub address: String,
     pub name: Option<String>,
     pub symbol: Option<String>,
+    #[serde(serialize_with = "crate::numeric::option_quantity")]
     pub total_supply: Option<i64>,
     pub first_seen_block: i64,
 }
@@ -147,6 +151,8 @@ pub struct Erc20Balance {
 /// ERC-20 holder with balance for API responses
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Erc20Holder {
+    /// 1-based position by balance, highest first
+    pub rank: i64,
     pub address: String,
     pub balance: BigDecimal,
     pub percentage: Option<f64>,
@@ -185,6 +191,124 @@ pub struct EventSignature {
 // Proxy Contract Types
 // =====================
 
+/// Curated address label
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AddressLabel {
+    pub address: String,
+    pub name: String,
+    pub tags: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Publicly submitted label awaiting (or past) moderation
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct LabelSuggestion {
+    pub id: i64,
+    pub address: String,
+    pub name: String,
+    pub tags: Vec<String>,
+    pub note: Option<String>,
+    pub submitter_ip: String,
+    pub status: String,
+    pub review_note: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub reviewed_at: Option<DateTime<Utc>>,
+}
+
+/// Private note and tags an API key holder attached to an address
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AddressNote {
+    pub address: String,
+    pub note: Option<String>,
+    pub tags: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Named API query an API key holder saved for reuse
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct SavedFilter {
+    pub id: i64,
+    pub name: String,
+    pub description: Option<String>,
+    /// API path, e.g. `/api/tokens/0x…/transfers`
+    pub method: String,
+    /// Query parameters of `method`
+    pub params: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Alert rule over indexed transfers or logs; unset conditions match anything
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AlertRule {
+    pub id: i64,
+    pub name: String,
+    /// `transfer` (ERC-20 transfers) or `log` (event logs)
+    pub kind: String,
+    /// Token (transfers) or emitting contract (logs)
+    pub contract: Option<String>,
+    /// Event signature hash (topic0); logs only
+    pub event: Option<String>,
+    /// Smallest raw token amount; transfers only
+    pub min_value: Option<BigDecimal>,
+    /// Sender or recipient (transfers), transaction sender or target (logs)
+    pub counterparty: Option<String>,
+    /// 4-byte selector of the transaction's called method
+    pub method: Option<String>,
+    pub webhook_url: String,
+    pub enabled: bool,
+    /// Last block the rule has been evaluated against
+    pub last_block: i64,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Match of an [`AlertRule`] and the state of its delivery
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AlertNotification {
+    pub id: i64,
+    pub rule_id: i64,
+    pub block_number: i64,
+    pub tx_hash: String,
+    pub log_index: i32,
+    pub payload: serde_json::Value,
+    /// `pending`, `delivered` or `failed`
+    pub status: String,
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub delivered_at: Option<DateTime<Utc>>,
+}
+
+/// Finding of the data-quality checks, one row per kind and subject
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct DataQualityIssue {
+    pub id: i64,
+    /// `negative_balance`, `missing_receipt` or `transfer_balance_mismatch`
+    pub kind: String,
+    /// `address/contract` for balance issues, the block number for receipts
+    pub subject: String,
+    pub block_number: Option<i64>,
+    pub address: Option<String>,
+    pub contract_address: Option<String>,
+    pub details: serde_json::Value,
+    pub first_seen_at: DateTime<Utc>,
+    pub last_seen_at: DateTime<Utc>,
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+/// Tag-scoped curator API key (the key itself is never stored)
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct LabelApiKey {
+    pub id: i64,
+    pub name: String,
+    pub tags: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
 /// Proxy contract relationship as stored in the database
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct ProxyContract {
@@ -260,12 +384,36 @@ pub struct FullContractAbi {
     pub license_type: Option<String>,
     pub is_multi_file: bool,
     pub source_files: Option<serde_json::Value>,
+    pub match_strategy: Option<String>,
+    pub immutable_values: Option<serde_json::Value>,
 }
 
 /// SQL column list for the `blocks` table, matching the field order in [`Block`].
 pub const BLOCK_COLUMNS: &str =
     "number, hash, parent_hash, timestamp, gas_used, gas_limit, base_fee_per_gas::text AS base_fee_per_gas, transaction_count, indexed_at";
 
+/// SQL column list for the `event_logs` table, matching the field order in
+/// [`EventLog`]. Hashes, addresses and topics are stored as bytes and read
+/// back as `0x`-prefixed hex.
+pub const EVENT_LOG_COLUMNS: &str = "id, '0x' || encode(tx_hash, 'hex') AS tx_hash, log_index, \
+    '0x' || encode(address, 'hex') AS address, '0x' || encode(topic0, 'hex') AS topic0, \
+    '0x' || encode(topic1, 'hex') AS topic1, '0x' || encode(topic2, 'hex') AS topic2, \
+    '0x' || encode(topic3, 'hex') AS topic3, data, block_number, decoded";
+
+/// How a paginated endpoint reports `total`.
+///
+/// Each endpoint picks a default; clients can override it with `?count=`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CountMode {
+    /// Exact `COUNT(*)` (bounded by the count statement timeout).
+    Exact,
+    /// Planner row estimate, falling back to an exact count for small result sets.
+    Estimated,
+    /// Omit `total`; clients page with `has_more` instead.
+    None,
+}
+
 /// Pagination parameters
 #[derive(Debug, Clone, Deserialize)]
 pub struct Pagination {
@@ -273,6 +421,14 @@ pub struct Pagination {
     pub page: u32,
     #[serde(default = "default_limit")]
     pub limit: u32,
+    /// Optional override of the endpoint's default [`CountMode`].
+    #[serde(default)]
+    pub count: Option<CountMode>,
+    /// Only list rows at or below this block so later pages see the same
+    /// snapshot as the first. Endpoints that support it echo the anchor they
+    /// used (the indexed head when omitted) as `as_of_block`.
+    #[serde(default)]
+    pub as_of_block: Option<i64>,
 }
 
 fn default_page() -> u32 {
@@ -290,6 +446,10 @@ impl Pagination {
     pub fn limit(&self) -> i64 {
         self.limit.min(100) as i64
     }
+
+    pub fn count_mode(&self, default: CountMode) -> CountMode {
+        self.count.unwrap_or(default)
+    }
 }
 
 /// Paginated response wrapper
@@ -298,19 +458,138 @@ pub struct PaginatedResponse<T> {
     pub data: Vec<T>,
     pub page: u32,
     pub limit: u32,
-    pub total: i64,
-    pub total_pages: u32,
+    /// Omitted when the endpoint ran in [`CountMode::None`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_pages: Option<u32>,
+    /// True when `total` is a planner estimate rather than an exact count.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub total_is_estimate: bool,
+    /// Whether another page exists. Only set by endpoints that probe with `LIMIT + 1`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub has_more: Option<bool>,
+    /// Block height the page is anchored to; pass it back as `as_of_block`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub as_of_block: Option<i64>,
+}
+
+fn total_pages(total: i64, limit: u32) -> u32 {
+    ((total as f64) / (limit as f64)).ceil() as u32
 }
 
 impl<T> PaginatedResponse<T> {
     pub fn new(data: Vec<T>, page: u32, limit: u32, total: i64) -> Self {
-        let total_pages = ((total as f64) / (limit as f64)).ceil() as u32;
+        Self {
+            data,
+            page,
+            limit,
+            total: Some(total),
+            total_pages: Some(total_pages(total, limit)),
+            total_is_estimate: false,
+            has_more: None,
+            as_of_block: None,
+        }
+    }
+
+    /// Build a response from a page fetched with `LIMIT limit + 1`.
+    ///
+    /// The extra row (if any) is dropped and turned into `has_more`. When the
+    /// probe shows this is the last page, the exact total is known for free and
+    /// replaces any estimate; otherwise an estimate is raised to at least the
+    /// number of rows seen so far.
+    pub fn from_probe(
+        mut data: Vec<T>,
+        page: u32,
+        limit: u32,
+        offset: i64,
+        total: Option<i64>,
+        total_is_estimate: bool,
+    ) -> Self {
+        let has_more = data.len() > limit as usize;
+        data.truncate(limit as usize);
+        let seen = offset + data.len() as i64;
+
+        let (total, total_is_estimate) = match total {
+            Some(_) if !has_more && (offset == 0 || !data.is_empty()) => (Some(seen), false),
+            Some(total) if has_more => (Some(total.max(seen + 1)), total_is_estimate),
+            other => (other, total_is_estimate),
+        };
+
         Self {
             data,
             page,
             limit,
             total,
-            total_pages,
+            total_pages: total.map(|total| total_pages(total, limit)),
+            total_is_estimate,
+            has_more: Some(has_more),
+            as_of_block: None,
         }
     }
+
+    /// Record the block height the page was anchored to.
+    pub fn anchored_at(mut self, as_of_block: Option<i64>) -> Self {
+        self.as_of_block = as_of_block;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn as_of_block_is_only_serialized_when_anchored() {
+        let plain = serde_json::to_value(PaginatedResponse::new(vec![1], 1, 20, 1)).unwrap();
+        assert!(plain.get("as_of_block").is_none());
+
+        let anchored = PaginatedResponse::new(vec![1], 1, 20, 1).anchored_at(Some(42));
+        assert_eq!(serde_json::to_value(anchored).unwrap()["as_of_block"], 42);
+    }
+
+    #[test]
+    fn from_probe_trims_extra_row_and_sets_has_more() {
+        let response = PaginatedResponse::from_probe(vec![1, 2, 3], 1, 2, 0, None, false);
+        assert_eq!(response.data, vec![1, 2]);
+        assert_eq!(response.has_more, Some(true));
+        assert_eq!(response.total, None);
+        assert_eq!(response.total_pages, None);
+    }
+
+    #[test]
+    fn from_probe_replaces_estimate_with_exact_total_on_last_page() {
+        let response = PaginatedResponse::from_probe(vec![1], 3, 10, 20, Some(500), true);
+        assert_eq!(response.has_more, Some(false));
+        assert_eq!(response.total, Some(21));
+        assert_eq!(response.total_pages, Some(3));
+        assert!(!response.total_is_estimate);
+    }
+
+    #[test]
+    fn from_probe_raises_low_estimate_to_rows_seen() {
+        let response = PaginatedResponse::from_probe(vec![1, 2, 3], 2, 2, 2, Some(1), true);
+        assert_eq!(response.total, Some(5));
+        assert!(response.total_is_estimate);
+    }
+
+    #[test]
+    fn from_probe_keeps_estimate_for_empty_page_past_the_end() {
+        let response = PaginatedResponse::<i32>::from_probe(vec![], 9, 10, 80, Some(50), true);
+        assert_eq!(response.has_more, Some(false));
+        assert_eq!(response.total, Some(50));
+        assert!(response.total_is_estimate);
+    }
+
+    #[test]
+    fn count_mode_deserializes_lowercase() {
+        let pagination: Pagination = serde_json::from_str(r#"{"count":"none"}"#).unwrap();
+        assert_eq!(pagination.count_mode(CountMode::Exact), CountMode::None);
+
+        let pagination: Pagination = serde_json::from_str("{}").unwrap();
+        assert_eq!(
+            pagination.count_mode(CountMode::Estimated),
+            CountMode::Estimated
+        );
+    }
 }
@@ -0,0 +1,203 @@
+use clap::{Args, Parser};
+
+fn database_url_from_env() -> String {
+    std::env::var("DATABASE_URL").unwrap_or_default()
+}
+
+/// Atlas metadata fetcher — token and NFT metadata for an Atlas index
+#[derive(Parser)]
+#[command(
+    name = "atlas-metadata",
+    version,
+    about = "Fetches ERC-20, NFT contract and NFT token metadata for an Atlas index"
+)]
+pub struct Cli {
+    #[arg(
+        long = "config",
+        env = "ATLAS_CONFIG",
+        value_name = "FILE",
+        help = "TOML file with settings for any --atlas.* flag; flags and env vars take precedence"
+    )]
+    pub config: Option<std::path::PathBuf>,
+
+    #[arg(
+        long = "print-config",
+        help = "Print the resolved configuration as TOML, then exit"
+    )]
+    pub print_config: bool,
+
+    #[command(flatten)]
+    pub db: DatabaseArgs,
+    #[command(flatten)]
+    pub rpc: RpcArgs,
+    #[command(flatten)]
+    pub metadata: MetadataArgs,
+    #[command(flatten)]
+    pub log: LogArgs,
+}
+
+// ── Sections ──────────────────────────────────────────────────────────────────
+
+#[derive(Args, Clone)]
+#[command(next_help_heading = "Database")]
+pub struct DatabaseArgs {
+    #[arg(skip = database_url_from_env())]
+    pub url: String,
+
+    #[arg(
+        long = "atlas.db.max-connections",
+        env = "METADATA_DB_MAX_CONNECTIONS",
+        default_value = "10",
+        value_name = "N",
+        help = "Max database connections"
+    )]
+    pub max_connections: u32,
+}
+
+#[derive(Args, Clone)]
+#[command(next_help_heading = "RPC")]
+pub struct RpcArgs {
+    #[arg(
+        long = "atlas.rpc.url",
+        env = "RPC_URL",
+        value_name = "URL",
+        help = "Ethereum JSON-RPC endpoint"
+    )]
+    pub url: String,
+}
+
+#[derive(Args, Clone)]
+#[command(next_help_heading = "Metadata")]
+pub struct MetadataArgs {
+    #[arg(
+        long = "atlas.metadata.workers",
+        env = "METADATA_FETCH_WORKERS",
+        default_value = "4",
+        value_name = "N",
+        help = "Concurrent metadata fetches"
+    )]
+    pub workers: u32,
+
+    #[arg(
+        long = "atlas.metadata.retry-attempts",
+        env = "METADATA_RETRY_ATTEMPTS",
+        default_value = "3",
+        value_name = "N",
+        help = "Max retry attempts for metadata fetches"
+    )]
+    pub retry_attempts: u32,
+
+    #[arg(
+        long = "atlas.metadata.ipfs-gateway",
+        env = "IPFS_GATEWAY",
+        default_value = "https://ipfs.io/ipfs/",
+        value_name = "URL",
+        help = "IPFS gateway for NFT metadata"
+    )]
+    pub ipfs_gateway: String,
+
+    #[arg(
+        long = "atlas.metadata.priority-window-blocks",
+        env = "METADATA_PRIORITY_WINDOW_BLOCKS",
+        default_value = "10000",
+        value_name = "BLOCKS",
+        help = "Fetch contract metadata busiest first, by transfers in this many recent blocks plus holders (0 = unordered)"
+    )]
+    pub priority_window_blocks: u64,
+
+    #[arg(
+        long = "atlas.metadata.nft-contract-workers",
+        env = "METADATA_NFT_CONTRACT_WORKERS",
+        default_value = "0",
+        value_name = "N",
+        help = "Concurrent NFT contract metadata fetches (0 = workers)"
+    )]
+    pub nft_contract_workers: u32,
+
+    #[arg(
+        long = "atlas.metadata.erc20-contract-workers",
+        env = "METADATA_ERC20_CONTRACT_WORKERS",
+        default_value = "0",
+        value_name = "N",
+        help = "Concurrent ERC-20 contract metadata fetches (0 = workers)"
+    )]
+    pub erc20_contract_workers: u32,
+
+    #[arg(
+        long = "atlas.metadata.nft-token-workers",
+        env = "METADATA_NFT_TOKEN_WORKERS",
+        default_value = "0",
+        value_name = "N",
+        help = "Concurrent NFT token metadata fetches (0 = workers)"
+    )]
+    pub nft_token_workers: u32,
+
+    #[arg(
+        long = "atlas.metadata.health-host",
+        env = "METADATA_HEALTH_HOST",
+        default_value = "127.0.0.1",
+        value_name = "HOST",
+        help = "Bind address for /health and /metrics"
+    )]
+    pub health_host: String,
+
+    #[arg(
+        long = "atlas.metadata.health-port",
+        env = "METADATA_HEALTH_PORT",
+        default_value = "3001",
+        value_name = "PORT",
+        help = "Port for /health and /metrics"
+    )]
+    pub health_port: u16,
+}
+
+#[derive(Args, Clone)]
+#[command(next_help_heading = "Logging")]
+pub struct LogArgs {
+    #[arg(
+        long = "atlas.log.level",
+        env = "RUST_LOG",
+        default_value = "atlas_metadata=info,atlas_server=info,sqlx=warn",
+        value_name = "FILTER",
+        help = "Log filter directives (RUST_LOG syntax)"
+    )]
+    pub level: String,
+
+    #[arg(
+        long = "atlas.log.format",
+        env = "LOG_FORMAT",
+        default_value = "text",
+        value_parser = ["text", "json"],
+        value_name = "FORMAT",
+        help = "Log output format"
+    )]
+    pub format: String,
+
+    #[arg(
+        long = "atlas.log.otlp-endpoint",
+        env = "OTEL_EXPORTER_OTLP_ENDPOINT",
+        value_name = "URL",
+        help = "OTLP/HTTP collector to export traces to (unset = no export)"
+    )]
+    pub otlp_endpoint: Option<String>,
+
+    #[arg(
+        long = "atlas.log.otel-service-name",
+        env = "OTEL_SERVICE_NAME",
+        default_value = "atlas-metadata",
+        value_name = "NAME",
+        help = "Service name attached to exported traces"
+    )]
+    pub otel_service_name: String,
+}
+
+impl From<LogArgs> for atlas_server::cli::LogArgs {
+    fn from(args: LogArgs) -> Self {
+        Self {
+            level: args.level,
+            format: args.format,
+            otlp_endpoint: args.otlp_endpoint,
+            otel_service_name: args.otel_service_name,
+        }
+    }
+}
@@ -0,0 +1,135 @@
+//! `/health` and `/metrics` for the metadata fetcher.
+//!
+//! `/health` reports whether the fetcher loop is running, how often it has
+//! restarted, and how much metadata is still waiting. It answers 503 when
+//! the database is unreachable, so orchestrators can restart the process.
+
+use axum::{extract::State, http::StatusCode, routing::get, Json, Router};
+use metrics_exporter_prometheus::PrometheusHandle;
+use serde::Serialize;
+use sqlx::PgPool;
+use std::sync::{Arc, Mutex};
+
+/// What the fetcher supervisor reports about the fetcher loop.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct FetcherStatus {
+    pub running: bool,
+    pub restarts: u64,
+    pub last_error: Option<String>,
+}
+
+#[derive(Clone)]
+pub struct HealthState {
+    pub pool: PgPool,
+    pub prometheus_handle: PrometheusHandle,
+    pub fetcher: Arc<Mutex<FetcherStatus>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Backlog {
+    pub nft_contracts: i64,
+    pub erc20_contracts: i64,
+    pub nft_tokens: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct HealthResponse {
+    pub status: &'static str,
+    pub fetcher: FetcherStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub backlog: Option<Backlog>,
+}
+
+pub fn router(state: HealthState) -> Router {
+    Router::new()
+        .route("/health", get(health))
+        .route("/metrics", get(metrics))
+        .with_state(state)
+}
+
+async fn health(State(state): State<HealthState>) -> (StatusCode, Json<HealthResponse>) {
+    let fetcher = state.fetcher.lock().unwrap().clone();
+    match backlog(&state.pool).await {
+        Ok(backlog) => (
+            StatusCode::OK,
+            Json(HealthResponse {
+                status: "ok",
+                fetcher,
+                backlog: Some(backlog),
+            }),
+        ),
+        Err(e) => {
+            tracing::warn!(error = %e, "health check could not reach the database");
+            (
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(HealthResponse {
+                    status: "database_unavailable",
+                    fetcher,
+                    backlog: None,
+                }),
+            )
+        }
+    }
+}
+
+async fn metrics(State(state): State<HealthState>) -> String {
+    state.prometheus_handle.render()
+}
+
+async fn backlog(pool: &PgPool) -> Result<Backlog, sqlx::Error> {
+    let (nft_contracts, erc20_contracts, nft_tokens): (i64, i64, i64) = sqlx::query_as(
+        "SELECT
+            (SELECT COUNT(*) FROM nft_contracts WHERE metadata_fetched = false),
+            (SELECT COUNT(*) FROM erc20_contracts WHERE metadata_fetched = false),
+            (SELECT COUNT(*) FROM nft_tokens
+             WHERE metadata_status IN ('pending', 'retryable_error'))",
+    )
+    .fetch_one(pool)
+    .await?;
+    Ok(Backlog {
+        nft_contracts,
+        erc20_contracts,
+        nft_tokens,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::http::Request;
+    use sqlx::postgres::PgPoolOptions;
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn health_reports_503_without_database() {
+        let pool = PgPoolOptions::new()
+            .acquire_timeout(std::time::Duration::from_millis(200))
+            .connect_lazy("postgres://test@127.0.0.1:1/test")
+            .expect("lazy pool");
+        let fetcher = Arc::new(Mutex::new(FetcherStatus {
+            running: true,
+            restarts: 2,
+            last_error: Some("rpc down".to_string()),
+        }));
+        let app = router(HealthState {
+            pool,
+            prometheus_handle: atlas_server::metrics::install_prometheus_recorder(),
+            fetcher,
+        });
+
+        let response = app
+            .oneshot(Request::get("/health").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["status"], "database_unavailable");
+        assert_eq!(json["fetcher"]["restarts"], 2);
+        assert_eq!(json["fetcher"]["last_error"], "rpc down");
+        assert!(json.get("backlog").is_none());
+    }
+}
@@ -0,0 +1,235 @@
+//! Standalone token and NFT metadata fetcher.
+//!
+//! Runs the same `MetadataFetcher` as `atlas-server`, against the same
+//! database, in its own process so a metadata backlog or crash never
+//! restarts block indexing and the fetcher can be scaled on its own. Start
+//! `atlas-server` with `METADATA_EXTERNAL=true` so only this process fetches.
+//! The schema is owned by `atlas-server`, which must have run its migrations.
+
+use anyhow::{bail, Result};
+use clap::{CommandFactory, FromArgMatches};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use atlas_server::indexer::{MetadataConfig, MetadataFetcher};
+use atlas_server::metrics::Metrics;
+use atlas_server::runtime_settings::{RuntimeSettings, RuntimeValues};
+use atlas_server::{config_file, metrics, telemetry};
+
+mod cli;
+mod health;
+
+use health::{FetcherStatus, HealthState};
+
+/// Retry delays for the fetcher loop, as in `atlas-server`.
+const RETRY_DELAYS: &[u64] = &[5, 10, 20, 30, 60];
+const MAX_RETRY_DELAY: u64 = 60;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    dotenvy::dotenv().ok();
+
+    // A config file only fills in settings the environment leaves unset
+    let args: Vec<_> = std::env::args_os().collect();
+    let loaded = config_file::config_path(&args)
+        .map(|path| config_file::load(&path, &cli::Cli::command()))
+        .transpose()?;
+
+    let matches = cli::Cli::command().get_matches_from(args);
+    let cli = cli::Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+
+    if cli.print_config {
+        validate(&cli)?;
+        print!(
+            "{}",
+            config_file::render(&cli::Cli::command(), &matches, loaded.as_ref())
+        );
+        return Ok(());
+    }
+    run(cli, loaded.map(|loaded| loaded.path)).await
+}
+
+fn validate(cli: &cli::Cli) -> Result<()> {
+    if cli.db.url.is_empty() {
+        bail!("DATABASE_URL must be set");
+    }
+    if cli.metadata.workers == 0 {
+        bail!("atlas.metadata.workers must be greater than 0");
+    }
+    Ok(())
+}
+
+async fn run(cli: cli::Cli, config_file: Option<std::path::PathBuf>) -> Result<()> {
+    validate(&cli)?;
+    let log_level = cli.log.level.clone();
+    let telemetry = telemetry::init(&cli.log.into())?;
+    tracing::info!("Starting Atlas metadata fetcher");
+
+    let prometheus_handle = metrics::install_prometheus_recorder();
+    let metrics = Metrics::new();
+
+    let pool = atlas_common::db::create_pool(&cli.db.url, cli.db.max_connections).await?;
+    let settings = RuntimeSettings::new(
+        RuntimeValues {
+            metadata_fetch_workers: cli.metadata.workers,
+            log_level,
+            ..RuntimeValues::default()
+        },
+        Some(telemetry.log_filter()),
+    );
+    #[cfg(unix)]
+    {
+        let settings = settings.clone();
+        tokio::spawn(async move {
+            if let Err(e) = atlas_server::runtime_settings::reload_on_sighup(
+                settings,
+                config_file,
+                cli::Cli::command(),
+            )
+            .await
+            {
+                tracing::error!("SIGHUP handler terminated with error: {}", e);
+            }
+        });
+    }
+    #[cfg(not(unix))]
+    let _ = config_file;
+
+    let status = Arc::new(Mutex::new(FetcherStatus::default()));
+    let config = MetadataConfig {
+        rpc_url: cli.rpc.url,
+        ipfs_gateway: cli.metadata.ipfs_gateway,
+        retry_attempts: cli.metadata.retry_attempts,
+        priority_window_blocks: cli.metadata.priority_window_blocks,
+        nft_contract_workers: cli.metadata.nft_contract_workers,
+        erc20_contract_workers: cli.metadata.erc20_contract_workers,
+        nft_token_workers: cli.metadata.nft_token_workers,
+    };
+    tokio::spawn(supervise(
+        pool.clone(),
+        config,
+        metrics,
+        settings,
+        status.clone(),
+    ));
+
+    let app = health::router(HealthState {
+        pool,
+        prometheus_handle,
+        fetcher: status,
+    });
+    let addr = format!("{}:{}", cli.metadata.health_host, cli.metadata.health_port);
+    tracing::info!("Health endpoint listening on {}", addr);
+    let listener = tokio::net::TcpListener::bind(&addr).await?;
+    axum::serve(listener, app)
+        .with_graceful_shutdown(shutdown_signal())
+        .await?;
+    Ok(())
+}
+
+/// Run the fetcher forever, restarting it with backoff after each failure.
+async fn supervise(
+    pool: sqlx::PgPool,
+    config: MetadataConfig,
+    metrics: Metrics,
+    settings: RuntimeSettings,
+    status: Arc<Mutex<FetcherStatus>>,
+) {
+    let mut retry_count = 0;
+    loop {
+        status.lock().unwrap().running = true;
+        let result = match MetadataFetcher::new(
+            pool.clone(),
+            config.clone(),
+            metrics.clone(),
+            settings.clone(),
+        ) {
+            Ok(fetcher) => fetcher.run().await,
+            Err(e) => Err(e),
+        };
+        let Err(e) = result else {
+            retry_count = 0;
+            continue;
+        };
+
+        let delay = RETRY_DELAYS
+            .get(retry_count)
+            .copied()
+            .unwrap_or(MAX_RETRY_DELAY);
+        tracing::error!(
+            error = %e,
+            restart_in_secs = delay,
+            attempt = retry_count + 1,
+            "metadata fetcher failed"
+        );
+        {
+            let mut status = status.lock().unwrap();
+            status.running = false;
+            status.restarts += 1;
+            status.last_error = Some(format!("{e:#}"));
+        }
+        tokio::time::sleep(Duration::from_secs(delay)).await;
+        retry_count += 1;
+    }
+}
+
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        tokio::signal::ctrl_c()
+            .await
+            .expect("failed to install Ctrl+C handler");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("failed to install SIGTERM handler")
+            .recv()
+            .await;
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+
+    tracing::info!("Shutdown signal received, stopping metadata fetcher");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    #[test]
+    fn cli_defaults_match_the_server() {
+        let cli =
+            cli::Cli::try_parse_from(["atlas-metadata", "--atlas.rpc.url", "http://node:8545"])
+                .unwrap();
+        assert_eq!(cli.metadata.workers, 4);
+        assert_eq!(cli.metadata.retry_attempts, 3);
+        assert_eq!(cli.metadata.ipfs_gateway, "https://ipfs.io/ipfs/");
+        assert_eq!(cli.metadata.priority_window_blocks, 10000);
+        assert_eq!(cli.metadata.nft_token_workers, 0);
+        assert_eq!(cli.metadata.health_port, 3001);
+        assert_eq!(cli.log.otel_service_name, "atlas-metadata");
+    }
+
+    #[test]
+    fn validate_rejects_zero_workers() {
+        let mut cli = cli::Cli::try_parse_from([
+            "atlas-metadata",
+            "--atlas.rpc.url",
+            "http://node:8545",
+            "--atlas.metadata.workers",
+            "0",
+        ])
+        .unwrap();
+        cli.db.url = "postgres://localhost/atlas".to_string();
+        let err = validate(&cli).unwrap_err();
+        assert!(err.to_string().contains("workers"));
+    }
+}
@@ -0,0 +1,19 @@
+-- Labels inferred from on-chain relationships to curated `address_labels`.
+-- Kept apart from curated labels so they can be rebuilt, filtered, or
+-- discarded without touching hand-maintained data.
+CREATE TABLE IF NOT EXISTS derived_address_labels (
+    address VARCHAR(42) NOT NULL,
+    tag VARCHAR(128) NOT NULL,
+    provenance VARCHAR(16) NOT NULL,
+    source_address VARCHAR(42) NOT NULL,
+    source_tag VARCHAR(100) NOT NULL,
+    evidence_tx_hash VARCHAR(66) NOT NULL,
+    evidence_block BIGINT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (address, tag),
+    CONSTRAINT derived_address_labels_provenance_check
+        CHECK (provenance IN ('deployer', 'funder'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_derived_address_labels_tag ON derived_address_labels(tag);
+CREATE INDEX IF NOT EXISTS idx_derived_address_labels_source ON derived_address_labels(source_address);
@@ -0,0 +1,19 @@
+-- Moderation queue for publicly submitted address labels. Approved rows are
+-- copied into `address_labels`; the queue keeps the history either way.
+CREATE TABLE IF NOT EXISTS label_suggestions (
+    id BIGSERIAL PRIMARY KEY,
+    address VARCHAR(42) NOT NULL,
+    name VARCHAR(255) NOT NULL,
+    tags TEXT[] NOT NULL DEFAULT '{}',
+    note TEXT,
+    submitter_ip VARCHAR(45) NOT NULL,
+    status VARCHAR(16) NOT NULL DEFAULT 'pending',
+    review_note TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    reviewed_at TIMESTAMPTZ,
+    CONSTRAINT label_suggestions_status_check
+        CHECK (status IN ('pending', 'approved', 'rejected'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_label_suggestions_status ON label_suggestions(status, id);
+CREATE INDEX IF NOT EXISTS idx_label_suggestions_submitter ON label_suggestions(submitter_ip, created_at);
@@ -0,0 +1,12 @@
+-- Curator API keys for label management, each limited to a set of tags.
+-- Only a keccak256 hash of the key is stored; the key itself is shown once
+-- when it is issued.
+CREATE TABLE IF NOT EXISTS label_api_keys (
+    id BIGSERIAL PRIMARY KEY,
+    name VARCHAR(100) NOT NULL,
+    key_hash VARCHAR(66) NOT NULL UNIQUE,
+    tags TEXT[] NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    revoked_at TIMESTAMPTZ,
+    CONSTRAINT label_api_keys_tags_not_empty CHECK (cardinality(tags) > 0)
+);
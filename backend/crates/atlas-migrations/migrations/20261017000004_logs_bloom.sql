@@ -0,0 +1,6 @@
+-- Logs bloom per block (header) and per transaction (receipt), used to rule
+-- out "does this block/tx involve address X" checks without touching
+-- event_logs. An empty value is an all-zero bloom (no logs); NULL marks rows
+-- indexed before this column existed.
+ALTER TABLE blocks ADD COLUMN IF NOT EXISTS logs_bloom BYTEA;
+ALTER TABLE transactions ADD COLUMN IF NOT EXISTS logs_bloom BYTEA;
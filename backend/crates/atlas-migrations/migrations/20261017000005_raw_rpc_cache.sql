@@ -0,0 +1,11 @@
+-- Verbatim node JSON for indexed blocks and transactions, fetched on first
+-- request to the /raw endpoints. `block_hash` ties an entry to the indexed
+-- block so entries from a reorged-out block are refetched.
+CREATE TABLE IF NOT EXISTS raw_rpc_cache (
+    kind VARCHAR(16) NOT NULL CHECK (kind IN ('block', 'transaction')),
+    key VARCHAR(66) NOT NULL,
+    block_hash VARCHAR(66) NOT NULL,
+    payload JSONB NOT NULL,
+    fetched_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (kind, key)
+);
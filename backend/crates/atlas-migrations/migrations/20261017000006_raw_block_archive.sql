@@ -0,0 +1,19 @@
+-- Optional archive of raw block + receipts JSON (RAW_ARCHIVE_ENABLED), kept
+-- across re-indexing so history can be re-derived without the RPC node.
+CREATE TABLE IF NOT EXISTS raw_block_archive (
+    block_number BIGINT PRIMARY KEY,
+    block_hash VARCHAR(66) NOT NULL,
+    payload BYTEA NOT NULL,
+    archived_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- lz4 compresses RPC JSON faster and smaller than the default pglz; it needs
+-- a server built with lz4, so keep pglz when it is unavailable.
+DO $$
+BEGIN
+    ALTER TABLE raw_block_archive ALTER COLUMN payload SET COMPRESSION lz4;
+EXCEPTION
+    WHEN feature_not_supported OR invalid_parameter_value THEN
+        RAISE NOTICE 'lz4 unavailable, raw_block_archive uses pglz compression';
+END
+$$;
@@ -0,0 +1,31 @@
+-- Trait attributes parsed from fetched NFT metadata, for trait filters and
+-- rarity counts. Kept in sync by the metadata fetcher.
+CREATE TABLE IF NOT EXISTS nft_token_attributes (
+    contract_address VARCHAR(42) NOT NULL,
+    token_id NUMERIC(78, 0) NOT NULL,
+    trait_type TEXT NOT NULL,
+    value TEXT NOT NULL,
+    PRIMARY KEY (contract_address, token_id, trait_type, value),
+    FOREIGN KEY (contract_address, token_id)
+        REFERENCES nft_tokens(contract_address, token_id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_nft_token_attributes_trait
+    ON nft_token_attributes (contract_address, trait_type, value);
+
+-- Backfill from metadata that was fetched before this table existed, using
+-- the same rules as nft_metadata::extract_attributes.
+INSERT INTO nft_token_attributes (contract_address, token_id, trait_type, value)
+SELECT t.contract_address, t.token_id, btrim(a.attr->>'trait_type'), btrim(a.attr->>'value')
+FROM nft_tokens t
+CROSS JOIN LATERAL jsonb_array_elements(
+    CASE WHEN jsonb_typeof(t.metadata->'attributes') = 'array'
+         THEN t.metadata->'attributes' ELSE '[]'::jsonb END
+) WITH ORDINALITY AS a(attr, ord)
+WHERE a.ord <= 100
+  AND jsonb_typeof(a.attr) = 'object'
+  AND jsonb_typeof(a.attr->'trait_type') = 'string'
+  AND jsonb_typeof(a.attr->'value') IN ('string', 'number', 'boolean')
+  AND length(btrim(a.attr->>'trait_type')) BETWEEN 1 AND 256
+  AND length(btrim(a.attr->>'value')) BETWEEN 1 AND 256
+ON CONFLICT DO NOTHING;
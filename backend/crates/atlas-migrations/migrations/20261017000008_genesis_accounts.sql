@@ -0,0 +1,9 @@
+-- Accounts from the chain's genesis alloc (GENESIS_FILE): pre-funded
+-- balances and predeploys that no transaction ever touches.
+CREATE TABLE IF NOT EXISTS genesis_accounts (
+    address VARCHAR(42) PRIMARY KEY,
+    balance NUMERIC(78, 0) NOT NULL DEFAULT 0,
+    nonce BIGINT NOT NULL DEFAULT 0,
+    code_size INTEGER NOT NULL DEFAULT 0,
+    storage_slots INTEGER NOT NULL DEFAULT 0
+);
@@ -0,0 +1,13 @@
+-- L1 data fee reported in rollup (OP Stack) receipts, on top of the L2
+-- execution fee (gas_used * gas_price). NULL on L1 chains, for deposit
+-- transactions and for rows indexed before these columns existed; replaying
+-- the raw block archive fills them in. l1_fee_scalar is the pre-Ecotone
+-- decimal scalar, the base/blob scalars replace it from Ecotone on.
+ALTER TABLE transactions
+    ADD COLUMN IF NOT EXISTS l1_fee NUMERIC(78, 0),
+    ADD COLUMN IF NOT EXISTS l1_gas_used BIGINT,
+    ADD COLUMN IF NOT EXISTS l1_gas_price NUMERIC(78, 0),
+    ADD COLUMN IF NOT EXISTS l1_blob_base_fee NUMERIC(78, 0),
+    ADD COLUMN IF NOT EXISTS l1_fee_scalar TEXT,
+    ADD COLUMN IF NOT EXISTS l1_base_fee_scalar BIGINT,
+    ADD COLUMN IF NOT EXISTS l1_blob_base_fee_scalar BIGINT;
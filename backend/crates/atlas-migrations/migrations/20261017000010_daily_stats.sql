@@ -0,0 +1,14 @@
+-- Per-day chain summary (UTC days of block timestamps), maintained by the
+-- daily stats aggregation job and served by /api/stats/daily.
+CREATE TABLE IF NOT EXISTS daily_stats (
+    day DATE PRIMARY KEY,
+    block_count BIGINT NOT NULL,
+    tx_count BIGINT NOT NULL,
+    active_addresses BIGINT NOT NULL,
+    new_contracts BIGINT NOT NULL,
+    gas_used NUMERIC(78, 0) NOT NULL,
+    avg_gas_price NUMERIC(78, 0),
+    new_tokens BIGINT NOT NULL,
+    nft_mints BIGINT NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
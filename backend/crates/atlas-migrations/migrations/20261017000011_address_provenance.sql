@@ -0,0 +1,34 @@
+-- Address provenance: the first successful transaction that sent the
+-- address native value (its funder) and, for contracts, the transaction that
+-- created it (its deployer). Kept by the indexer as the earliest evidence
+-- seen; backfilled here from already indexed transactions.
+ALTER TABLE addresses
+    ADD COLUMN IF NOT EXISTS funded_by VARCHAR(42),
+    ADD COLUMN IF NOT EXISTS funded_block BIGINT,
+    ADD COLUMN IF NOT EXISTS funding_tx_hash VARCHAR(66),
+    ADD COLUMN IF NOT EXISTS deployer VARCHAR(42),
+    ADD COLUMN IF NOT EXISTS creation_block BIGINT,
+    ADD COLUMN IF NOT EXISTS creation_tx_hash VARCHAR(66);
+
+CREATE INDEX IF NOT EXISTS idx_addresses_funded_by ON addresses(funded_by) WHERE funded_by IS NOT NULL;
+CREATE INDEX IF NOT EXISTS idx_addresses_deployer ON addresses(deployer) WHERE deployer IS NOT NULL;
+
+UPDATE addresses a
+SET deployer = c.from_address, creation_block = c.block_number, creation_tx_hash = c.hash
+FROM (
+    SELECT DISTINCT ON (contract_created) contract_created, from_address, block_number, hash
+    FROM transactions
+    WHERE contract_created IS NOT NULL AND status
+    ORDER BY contract_created, block_number, block_index
+) c
+WHERE a.address = c.contract_created AND a.creation_block IS NULL;
+
+UPDATE addresses a
+SET funded_by = f.from_address, funded_block = f.block_number, funding_tx_hash = f.hash
+FROM (
+    SELECT DISTINCT ON (to_address) to_address, from_address, block_number, hash
+    FROM transactions
+    WHERE to_address IS NOT NULL AND value > 0 AND status
+    ORDER BY to_address, block_number, block_index
+) f
+WHERE a.address = f.to_address AND a.funded_block IS NULL;
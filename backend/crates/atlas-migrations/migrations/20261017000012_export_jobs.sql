@@ -0,0 +1,25 @@
+-- Bulk export jobs queued by POST /api/export and run by the export worker.
+-- `id` is a random token: knowing it is what grants access to the file.
+CREATE TABLE IF NOT EXISTS export_jobs (
+    id VARCHAR(32) PRIMARY KEY,
+    entity TEXT NOT NULL
+        CHECK (entity IN ('transactions', 'erc20_transfers', 'nft_transfers', 'logs')),
+    format TEXT NOT NULL CHECK (format IN ('csv', 'jsonl')),
+    address VARCHAR(42),
+    from_block BIGINT,
+    to_block BIGINT,
+    status TEXT NOT NULL DEFAULT 'queued'
+        CHECK (status IN ('queued', 'running', 'completed', 'failed')),
+    row_count BIGINT,
+    file_size BIGINT,
+    error TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    started_at TIMESTAMPTZ,
+    completed_at TIMESTAMPTZ,
+    expires_at TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_export_jobs_pending ON export_jobs (created_at)
+    WHERE status IN ('queued', 'running');
+CREATE INDEX IF NOT EXISTS idx_export_jobs_expires ON export_jobs (expires_at)
+    WHERE expires_at IS NOT NULL;
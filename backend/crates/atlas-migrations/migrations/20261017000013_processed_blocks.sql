@@ -0,0 +1,12 @@
+-- Blocks whose writes have been applied. write_batch claims each block here
+-- inside its transaction, so replaying a block (retried mini-batch,
+-- overlapping backfill, gap fill) never re-applies tx_count, balance or
+-- total supply deltas.
+CREATE TABLE IF NOT EXISTS processed_blocks (
+    number BIGINT PRIMARY KEY
+);
+
+-- Everything already indexed has had its deltas applied.
+INSERT INTO processed_blocks (number)
+SELECT number FROM blocks
+ON CONFLICT (number) DO NOTHING;
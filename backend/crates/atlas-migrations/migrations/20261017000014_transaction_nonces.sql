@@ -0,0 +1,8 @@
+-- Sender nonce of each transaction, for /api/addresses/{address}/nonces.
+-- Rows indexed before this column existed stay NULL until their block is
+-- written again (reindex, gap fill or archive replay).
+ALTER TABLE transactions ADD COLUMN IF NOT EXISTS nonce BIGINT;
+
+CREATE INDEX IF NOT EXISTS idx_transactions_from_nonce
+    ON transactions (from_address, nonce)
+    WHERE nonce IS NOT NULL;
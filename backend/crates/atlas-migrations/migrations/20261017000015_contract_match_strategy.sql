@@ -0,0 +1,3 @@
+-- How a verified contract's compiled bytecode matched the deployed code:
+-- 'exact', 'immutables' or 'metadata'. NULL for earlier verifications.
+ALTER TABLE contract_abis ADD COLUMN IF NOT EXISTS match_strategy TEXT;
@@ -0,0 +1,3 @@
+-- Values of a verified contract's `immutable` variables, read from its
+-- deployed bytecode: [{id, name, type, value, decoded}]. NULL when it has none.
+ALTER TABLE contract_abis ADD COLUMN IF NOT EXISTS immutable_values JSONB;
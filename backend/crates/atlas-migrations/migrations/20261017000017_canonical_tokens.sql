@@ -0,0 +1,22 @@
+-- Admin verdicts on ERC-20 tokens: 'verified' marks the legitimate token,
+-- 'flagged' a known scam or impersonation. Kept across reindexes.
+CREATE TABLE IF NOT EXISTS token_curation (
+    address VARCHAR(42) PRIMARY KEY,
+    status TEXT NOT NULL CHECK (status IN ('verified', 'flagged')),
+    note TEXT,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- The token each shared symbol resolves to, maintained by the canonical token
+-- job. Only symbols used by more than one token have a row.
+CREATE TABLE IF NOT EXISTS canonical_tokens (
+    symbol_key TEXT PRIMARY KEY,
+    address VARCHAR(42) NOT NULL,
+    source TEXT NOT NULL CHECK (source IN ('admin', 'heuristic')),
+    holder_count BIGINT NOT NULL,
+    transfer_count BIGINT NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_erc20_contracts_symbol_key
+    ON erc20_contracts (lower(symbol)) WHERE symbol IS NOT NULL;
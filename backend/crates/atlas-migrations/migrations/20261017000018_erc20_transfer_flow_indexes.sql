@@ -0,0 +1,7 @@
+-- Per-address transfer history by time, for /api/addresses/{address}/token-flows.
+-- They cover the same leading columns as idx_erc20_transfers_from/_to, which
+-- stay for the block-ordered transfer lists.
+CREATE INDEX IF NOT EXISTS idx_erc20_transfers_from_timestamp
+    ON erc20_transfers (from_address, timestamp);
+CREATE INDEX IF NOT EXISTS idx_erc20_transfers_to_timestamp
+    ON erc20_transfers (to_address, timestamp);
@@ -0,0 +1,5 @@
+-- solc output recorded at verification for source navigation: the runtime
+-- bytecode's compressed source map, and {path: {id, ast}} for each compiled
+-- source file. NULL for Yul and for contracts verified before this.
+ALTER TABLE contract_abis ADD COLUMN IF NOT EXISTS deployed_source_map TEXT;
+ALTER TABLE contract_abis ADD COLUMN IF NOT EXISTS source_asts JSONB;
@@ -0,0 +1,13 @@
+-- Private address notes and tags, one set per API key. Kept apart from the
+-- curated address_labels and never shown to other callers.
+CREATE TABLE IF NOT EXISTS address_notes (
+    api_key_id BIGINT NOT NULL REFERENCES label_api_keys(id) ON DELETE CASCADE,
+    address VARCHAR(42) NOT NULL,
+    note TEXT,
+    tags TEXT[] NOT NULL DEFAULT '{}',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (api_key_id, address)
+);
+
+CREATE INDEX IF NOT EXISTS idx_address_notes_tags ON address_notes USING GIN (tags);
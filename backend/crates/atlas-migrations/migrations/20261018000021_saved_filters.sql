@@ -0,0 +1,13 @@
+-- Named API queries saved per API key, stored as batch calls (an API path
+-- and its query parameters) so any session can run them again.
+CREATE TABLE IF NOT EXISTS saved_filters (
+    id BIGSERIAL PRIMARY KEY,
+    api_key_id BIGINT NOT NULL REFERENCES label_api_keys(id) ON DELETE CASCADE,
+    name VARCHAR(100) NOT NULL,
+    description TEXT,
+    method TEXT NOT NULL,
+    params JSONB NOT NULL DEFAULT '{}',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (api_key_id, name)
+);
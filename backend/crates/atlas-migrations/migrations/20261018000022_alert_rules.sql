@@ -0,0 +1,42 @@
+-- Alert rules over indexed ERC-20 transfers and event logs, owned by an API
+-- key, and the notifications they produced. Rules are evaluated behind a
+-- per-rule block cursor (last_block) as new blocks are indexed.
+CREATE TABLE IF NOT EXISTS alert_rules (
+    id BIGSERIAL PRIMARY KEY,
+    api_key_id BIGINT NOT NULL REFERENCES label_api_keys(id) ON DELETE CASCADE,
+    name VARCHAR(100) NOT NULL,
+    kind TEXT NOT NULL CHECK (kind IN ('transfer', 'log')),
+    contract VARCHAR(42),
+    event VARCHAR(66),
+    min_value NUMERIC(78, 0),
+    counterparty VARCHAR(42),
+    method VARCHAR(10),
+    webhook_url TEXT NOT NULL,
+    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    last_block BIGINT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (api_key_id, name),
+    CONSTRAINT alert_rules_scoped CHECK (contract IS NOT NULL OR counterparty IS NOT NULL)
+);
+
+CREATE TABLE IF NOT EXISTS alert_notifications (
+    id BIGSERIAL PRIMARY KEY,
+    rule_id BIGINT NOT NULL REFERENCES alert_rules(id) ON DELETE CASCADE,
+    block_number BIGINT NOT NULL,
+    tx_hash VARCHAR(66) NOT NULL,
+    log_index INTEGER NOT NULL,
+    payload JSONB NOT NULL,
+    status TEXT NOT NULL DEFAULT 'pending' CHECK (status IN ('pending', 'delivered', 'failed')),
+    attempts INTEGER NOT NULL DEFAULT 0,
+    last_error TEXT,
+    next_attempt_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    delivered_at TIMESTAMPTZ,
+    UNIQUE (rule_id, tx_hash, log_index, block_number)
+);
+
+CREATE INDEX IF NOT EXISTS idx_alert_notifications_pending
+    ON alert_notifications (next_attempt_at) WHERE status = 'pending';
+CREATE INDEX IF NOT EXISTS idx_alert_notifications_rule
+    ON alert_notifications (rule_id, id DESC);
@@ -0,0 +1,39 @@
+-- Current ERC-20 allowance per (owner, token, spender): the value of the
+-- latest Approval event. Spends through transferFrom are reflected only when
+-- the token emits a fresh Approval for them.
+CREATE TABLE IF NOT EXISTS erc20_approvals (
+    owner VARCHAR(42) NOT NULL,
+    contract_address VARCHAR(42) NOT NULL,
+    spender VARCHAR(42) NOT NULL,
+    value NUMERIC(78, 0) NOT NULL,
+    tx_hash VARCHAR(66) NOT NULL,
+    log_index INTEGER NOT NULL,
+    block_number BIGINT NOT NULL,
+    PRIMARY KEY (owner, contract_address, spender)
+);
+
+CREATE INDEX IF NOT EXISTS idx_erc20_approvals_spender ON erc20_approvals (spender);
+
+-- Last call to a spender, for approval risk reports.
+CREATE INDEX IF NOT EXISTS idx_transactions_to_block
+    ON transactions (to_address, block_number DESC);
+
+-- Backfill from already indexed logs. ERC-721 Approval shares the topic but
+-- indexes its token id as a fourth topic.
+INSERT INTO erc20_approvals (owner, contract_address, spender, value, tx_hash, log_index, block_number)
+SELECT DISTINCT ON (l.address, l.topic1, l.topic2)
+    '0x' || substring(l.topic1 FROM 27),
+    l.address,
+    '0x' || substring(l.topic2 FROM 27),
+    (SELECT SUM(get_byte(l.data, i)::numeric * power(256::numeric, 31 - i))
+     FROM generate_series(0, 31) AS i)::numeric(78, 0),
+    l.tx_hash,
+    l.log_index,
+    l.block_number
+FROM event_logs l
+WHERE l.topic0 = '0x8c5be1e5ebec7d5bd14f71427d1e84f3dd0314c0f7b2291e5b200ac8c7c3b925'
+  AND l.topic2 IS NOT NULL
+  AND l.topic3 IS NULL
+  AND length(l.data) >= 32
+ORDER BY l.address, l.topic1, l.topic2, l.block_number DESC, l.log_index DESC
+ON CONFLICT (owner, contract_address, spender) DO NOTHING;
@@ -0,0 +1,18 @@
+-- Periodically refreshed holder ranking per ERC-20 token, so holder pages are
+-- a primary-key range read instead of a sort over every balance of the token.
+CREATE TABLE IF NOT EXISTS erc20_holder_ranks (
+    contract_address VARCHAR(42) NOT NULL,
+    rank BIGINT NOT NULL,
+    address VARCHAR(42) NOT NULL,
+    balance NUMERIC(78, 0) NOT NULL,
+    PRIMARY KEY (contract_address, rank)
+);
+
+-- One row per ranked token: its holder count and the indexed block the
+-- ranking was built at.
+CREATE TABLE IF NOT EXISTS erc20_holder_rank_state (
+    contract_address VARCHAR(42) PRIMARY KEY,
+    holders BIGINT NOT NULL,
+    ranked_block BIGINT NOT NULL,
+    ranked_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,3 @@
+-- The indexer now deletes ERC-20 balance rows that reach zero; drop the ones
+-- left over from before. A missing row reads as a zero balance everywhere.
+DELETE FROM erc20_balances WHERE balance = 0;
@@ -0,0 +1,24 @@
+-- Findings of the data-quality job. One row per (kind, subject); a finding
+-- seen again is reopened, and one no longer detected gets resolved_at.
+CREATE TABLE IF NOT EXISTS data_quality_issues (
+    id BIGSERIAL PRIMARY KEY,
+    -- negative_balance | missing_receipt | transfer_balance_mismatch
+    kind VARCHAR(32) NOT NULL,
+    -- Block number for missing_receipt, 'address/contract' otherwise
+    subject VARCHAR(128) NOT NULL,
+    block_number BIGINT,
+    address VARCHAR(42),
+    contract_address VARCHAR(42),
+    details JSONB NOT NULL DEFAULT '{}',
+    first_seen_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_seen_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    resolved_at TIMESTAMPTZ,
+    UNIQUE (kind, subject)
+);
+
+CREATE INDEX IF NOT EXISTS idx_data_quality_issues_open
+    ON data_quality_issues (kind, id DESC) WHERE resolved_at IS NULL;
+
+-- Negative balances are rare; keep finding them cheap.
+CREATE INDEX IF NOT EXISTS idx_erc20_balances_negative
+    ON erc20_balances (contract_address) WHERE balance < 0;
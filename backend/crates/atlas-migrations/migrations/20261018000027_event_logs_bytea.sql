@@ -0,0 +1,19 @@
+-- Store event_logs' hash and address columns as raw bytes (32/20) instead of
+-- 0x-prefixed hex text (66/42 characters). event_logs is the largest table,
+-- and its tx_hash, address and topic0 indexes roughly halve in size.
+--
+-- The API still speaks hex: queries select these columns through
+-- atlas_common::EVENT_LOG_COLUMNS ('0x' || encode(col, 'hex')) and bind
+-- filter values as bytes.
+--
+-- This rewrites every partition of event_logs and rebuilds its indexes, and
+-- holds an exclusive lock meanwhile; on a large index run it in a
+-- maintenance window. A cold tier's event_logs tables (COLD_STORAGE_SCHEMA)
+-- are not touched and need the same ALTER before they are read again.
+ALTER TABLE event_logs
+    ALTER COLUMN tx_hash TYPE BYTEA USING decode(substr(tx_hash, 3), 'hex'),
+    ALTER COLUMN address TYPE BYTEA USING decode(substr(address, 3), 'hex'),
+    ALTER COLUMN topic0 TYPE BYTEA USING decode(substr(topic0, 3), 'hex'),
+    ALTER COLUMN topic1 TYPE BYTEA USING decode(substr(topic1, 3), 'hex'),
+    ALTER COLUMN topic2 TYPE BYTEA USING decode(substr(topic2, 3), 'hex'),
+    ALTER COLUMN topic3 TYPE BYTEA USING decode(substr(topic3, 3), 'hex');
@@ -0,0 +1,22 @@
+-- Daily per-pair totals of ERC-20 transfers whose raw rows were dropped by
+-- the retention job (TRANSFER_RETENTION_BLOCKS). Balances are kept as running
+-- sums and are unaffected; transfer counts and transfer/balance checks add
+-- these totals to the raw rows still in erc20_transfers.
+CREATE TABLE IF NOT EXISTS erc20_transfer_summaries (
+    day DATE NOT NULL,
+    contract_address VARCHAR(42) NOT NULL,
+    from_address VARCHAR(42) NOT NULL,
+    to_address VARCHAR(42) NOT NULL,
+    transfer_count BIGINT NOT NULL,
+    value NUMERIC(78, 0) NOT NULL,
+    first_block BIGINT NOT NULL,
+    last_block BIGINT NOT NULL,
+    PRIMARY KEY (day, contract_address, from_address, to_address)
+);
+
+CREATE INDEX IF NOT EXISTS idx_erc20_transfer_summaries_contract
+    ON erc20_transfer_summaries (contract_address);
+CREATE INDEX IF NOT EXISTS idx_erc20_transfer_summaries_from
+    ON erc20_transfer_summaries (from_address);
+CREATE INDEX IF NOT EXISTS idx_erc20_transfer_summaries_to
+    ON erc20_transfer_summaries (to_address);
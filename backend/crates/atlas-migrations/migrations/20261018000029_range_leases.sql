@@ -0,0 +1,18 @@
+-- Historical block ranges indexed in parallel by backfill workers (see
+-- BACKFILL_SHARDS). The head-following indexer splits a fresh index's history
+-- into these rows once; workers in any instance lease one at a time, record
+-- their progress in next_block and renew leased_until as they go, so a range
+-- whose worker died is taken over once its lease expires.
+CREATE TABLE IF NOT EXISTS range_leases (
+    start_block BIGINT PRIMARY KEY,
+    -- Exclusive.
+    end_block BIGINT NOT NULL,
+    -- First block of the range not yet written.
+    next_block BIGINT NOT NULL,
+    holder TEXT,
+    leased_until TIMESTAMPTZ,
+    completed_at TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_range_leases_open
+    ON range_leases (start_block) WHERE completed_at IS NULL;
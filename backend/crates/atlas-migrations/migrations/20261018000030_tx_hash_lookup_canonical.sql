@@ -0,0 +1,18 @@
+-- A transaction hash can be indexed in more than one block when a reorg
+-- moves it: keep a row per (hash, block), record the block hash it was seen
+-- in, and flag the one on the canonical chain. The indexer clears the flag
+-- when a block number is rewritten with a different hash or the hash shows
+-- up in another block.
+ALTER TABLE tx_hash_lookup
+    ADD COLUMN IF NOT EXISTS block_hash VARCHAR(66),
+    ADD COLUMN IF NOT EXISTS canonical BOOLEAN NOT NULL DEFAULT TRUE;
+
+ALTER TABLE tx_hash_lookup DROP CONSTRAINT IF EXISTS tx_hash_lookup_pkey;
+ALTER TABLE tx_hash_lookup ADD PRIMARY KEY (hash, block_number);
+
+CREATE INDEX IF NOT EXISTS idx_tx_hash_lookup_block ON tx_hash_lookup (block_number);
+
+UPDATE tx_hash_lookup l
+SET block_hash = b.hash
+FROM blocks b
+WHERE b.number = l.block_number AND l.block_hash IS NULL;
@@ -0,0 +1,25 @@
+-- Verifications registered before deployment: a compiled contract keyed by
+-- the keccak256 of its runtime bytecode. Contracts created later with exactly
+-- that code are copied into contract_abis as verified.
+CREATE TABLE IF NOT EXISTS pending_verifications (
+    code_hash VARCHAR(66) PRIMARY KEY,
+    abi JSONB NOT NULL,
+    source_code TEXT,
+    compiler_version VARCHAR(64) NOT NULL,
+    optimization_used BOOLEAN,
+    runs INTEGER,
+    contract_name VARCHAR(255) NOT NULL,
+    evm_version VARCHAR(32),
+    license_type VARCHAR(64),
+    is_multi_file BOOLEAN NOT NULL DEFAULT FALSE,
+    source_files JSONB,
+    deployed_source_map TEXT,
+    source_asts JSONB,
+    matched_contracts BIGINT NOT NULL DEFAULT 0,
+    registered_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_matched_at TIMESTAMPTZ
+);
+
+-- The matcher walks newly created contracts in creation order.
+CREATE INDEX IF NOT EXISTS idx_addresses_creation_block
+    ON addresses (creation_block) WHERE creation_block IS NOT NULL;
@@ -0,0 +1,11 @@
+-- solc standard-JSON output keyed by compiler version and the keccak256 of
+-- the exact input, so verifying another copy of a contract skips solc.
+CREATE TABLE IF NOT EXISTS compilation_cache (
+    compiler_version VARCHAR(64) NOT NULL,
+    input_hash VARCHAR(66) NOT NULL,
+    output JSONB NOT NULL,
+    hits BIGINT NOT NULL DEFAULT 0,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_used_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (compiler_version, input_hash)
+);
@@ -0,0 +1,9 @@
+-- Fees paid per day, in wei: gas used at the effective gas price, the part of
+-- it covered by the block base fee, and L1 data fees on OP Stack chains.
+ALTER TABLE daily_stats
+    ADD COLUMN IF NOT EXISTS execution_fees NUMERIC(78, 0) NOT NULL DEFAULT 0,
+    ADD COLUMN IF NOT EXISTS base_fees NUMERIC(78, 0) NOT NULL DEFAULT 0,
+    ADD COLUMN IF NOT EXISTS l1_fees NUMERIC(78, 0) NOT NULL DEFAULT 0;
+
+-- Recompute every day so rows written before this migration get their fees.
+DELETE FROM indexer_state WHERE key = 'daily_stats_next_day';
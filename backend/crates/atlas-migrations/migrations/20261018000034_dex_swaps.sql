@@ -0,0 +1,35 @@
+-- Swaps decoded by the dex_swaps block processor. Amounts are signed changes
+-- of the pool's balances: positive for the token paid in, negative for the
+-- token paid out.
+CREATE TABLE IF NOT EXISTS dex_swaps (
+    block_number BIGINT NOT NULL,
+    log_index INTEGER NOT NULL,
+    tx_hash VARCHAR(66) NOT NULL,
+    pool VARCHAR(42) NOT NULL,
+    protocol VARCHAR(16) NOT NULL,
+    sender VARCHAR(42) NOT NULL,
+    recipient VARCHAR(42) NOT NULL,
+    amount0 NUMERIC(78, 0) NOT NULL,
+    amount1 NUMERIC(78, 0) NOT NULL,
+    timestamp BIGINT NOT NULL,
+    PRIMARY KEY (block_number, log_index)
+);
+
+CREATE INDEX IF NOT EXISTS idx_dex_swaps_pool
+    ON dex_swaps (pool, block_number DESC, log_index DESC);
+
+-- Pools seen in swaps. token0/token1 are read by the pool resolver and stay
+-- NULL when the pool does not answer token0()/token1().
+CREATE TABLE IF NOT EXISTS dex_pools (
+    address VARCHAR(42) PRIMARY KEY,
+    protocol VARCHAR(16) NOT NULL,
+    token0 VARCHAR(42),
+    token1 VARCHAR(42),
+    first_swap_block BIGINT NOT NULL,
+    resolved_at TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_dex_pools_unresolved
+    ON dex_pools (first_swap_block) WHERE resolved_at IS NULL;
+CREATE INDEX IF NOT EXISTS idx_dex_pools_token0 ON dex_pools (token0);
+CREATE INDEX IF NOT EXISTS idx_dex_pools_token1 ON dex_pools (token1);
@@ -0,0 +1,35 @@
+-- Owner management events of Safe (Gnosis Safe) multisigs, decoded by the
+-- safes block processor. `owners` holds every owner for `setup` and the one
+-- added or removed for `added_owner`/`removed_owner`; `threshold` is set for
+-- `setup` and `changed_threshold`. Current owners are these events replayed
+-- in log order.
+CREATE TABLE IF NOT EXISTS safe_events (
+    block_number BIGINT NOT NULL,
+    log_index INTEGER NOT NULL,
+    tx_hash VARCHAR(66) NOT NULL,
+    safe VARCHAR(42) NOT NULL,
+    kind VARCHAR(20) NOT NULL,
+    owners VARCHAR(42)[] NOT NULL DEFAULT '{}',
+    threshold BIGINT,
+    timestamp BIGINT NOT NULL,
+    PRIMARY KEY (block_number, log_index)
+);
+
+CREATE INDEX IF NOT EXISTS idx_safe_events_safe
+    ON safe_events (safe, block_number, log_index);
+
+-- Safe transactions executed through execTransaction.
+CREATE TABLE IF NOT EXISTS safe_executions (
+    block_number BIGINT NOT NULL,
+    log_index INTEGER NOT NULL,
+    tx_hash VARCHAR(66) NOT NULL,
+    safe VARCHAR(42) NOT NULL,
+    safe_tx_hash VARCHAR(66) NOT NULL,
+    success BOOLEAN NOT NULL,
+    payment NUMERIC(78, 0) NOT NULL,
+    timestamp BIGINT NOT NULL,
+    PRIMARY KEY (block_number, log_index)
+);
+
+CREATE INDEX IF NOT EXISTS idx_safe_executions_safe
+    ON safe_executions (safe, block_number DESC, log_index DESC);
@@ -0,0 +1,18 @@
+-- Events of the staking/sequencer registry, decoded by the validators block
+-- processor. `kind` is register, exit, stake, unstake, slash, jail or unjail;
+-- `amount` is the stake moved, when the event carries one. Stakes and status
+-- are derived from these rows when read.
+CREATE TABLE IF NOT EXISTS validator_events (
+    block_number BIGINT NOT NULL,
+    log_index INTEGER NOT NULL,
+    tx_hash VARCHAR(66) NOT NULL,
+    validator VARCHAR(42) NOT NULL,
+    event TEXT NOT NULL,
+    kind VARCHAR(16) NOT NULL,
+    amount NUMERIC(78, 0),
+    timestamp BIGINT NOT NULL,
+    PRIMARY KEY (block_number, log_index)
+);
+
+CREATE INDEX IF NOT EXISTS idx_validator_events_validator
+    ON validator_events (validator, block_number DESC, log_index DESC);
@@ -0,0 +1,9 @@
+-- Default EIP-2981 royalty of each NFT collection, read by the metadata
+-- fetcher: royaltyInfo for token 0 at a sale price of 10,000, so the amount
+-- is in basis points. NULL when the collection doesn't support EIP-2981.
+ALTER TABLE nft_contracts ADD COLUMN IF NOT EXISTS royalty_receiver VARCHAR(42);
+ALTER TABLE nft_contracts ADD COLUMN IF NOT EXISTS royalty_bps INTEGER;
+
+-- Fetch collections again so existing ones get their royalty too. Name,
+-- symbol and supply are only overwritten by values the contract returns.
+UPDATE nft_contracts SET metadata_fetched = false WHERE metadata_fetched;
@@ -0,0 +1,24 @@
+-- ERC-721 sales decoded by the nft_sales block processor from marketplace
+-- order events, one row per token sold. `log_index` is the order event's;
+-- seller and buyer come from the token's transfer in the same transaction.
+-- `currency` is NULL for the native coin, and `price` is in its raw units.
+CREATE TABLE IF NOT EXISTS nft_sales (
+    block_number BIGINT NOT NULL,
+    log_index INTEGER NOT NULL,
+    tx_hash VARCHAR(66) NOT NULL,
+    marketplace VARCHAR(16) NOT NULL,
+    exchange VARCHAR(42) NOT NULL,
+    contract_address VARCHAR(42) NOT NULL,
+    token_id NUMERIC(78, 0) NOT NULL,
+    seller VARCHAR(42) NOT NULL,
+    buyer VARCHAR(42) NOT NULL,
+    currency VARCHAR(42),
+    price NUMERIC(78, 0) NOT NULL,
+    timestamp BIGINT NOT NULL,
+    PRIMARY KEY (block_number, log_index, contract_address, token_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_nft_sales_collection
+    ON nft_sales (contract_address, block_number DESC, log_index DESC);
+CREATE INDEX IF NOT EXISTS idx_nft_sales_token
+    ON nft_sales (contract_address, token_id, block_number DESC, log_index DESC);
@@ -0,0 +1,46 @@
+-- One row per known address for the addresses list: `addresses` merged with
+-- the ERC-20 and NFT contract tables, which the list used to union on every
+-- request. Rows are refreshed from those tables by whatever writes them (see
+-- indexer/address_directory.rs).
+CREATE TABLE IF NOT EXISTS address_directory (
+    address VARCHAR(42) PRIMARY KEY,
+    is_contract BOOLEAN NOT NULL,
+    first_seen_block BIGINT NOT NULL,
+    tx_count INTEGER NOT NULL,
+    -- 'eoa', 'contract', 'erc20' or 'nft'
+    address_type VARCHAR(8) NOT NULL,
+    name TEXT,
+    symbol TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_address_directory_activity
+    ON address_directory (tx_count DESC, first_seen_block DESC, address);
+CREATE INDEX IF NOT EXISTS idx_address_directory_type_activity
+    ON address_directory (address_type, tx_count DESC, first_seen_block DESC, address);
+CREATE INDEX IF NOT EXISTS idx_address_directory_first_seen
+    ON address_directory (first_seen_block);
+
+INSERT INTO address_directory (address, is_contract, first_seen_block, tx_count, address_type, name, symbol)
+SELECT s.address,
+       COALESCE(a.is_contract, true),
+       COALESCE(a.first_seen_block, e.first_seen_block, n.first_seen_block),
+       COALESCE(a.tx_count, 0),
+       CASE
+           WHEN e.address IS NOT NULL THEN 'erc20'
+           WHEN n.address IS NOT NULL THEN 'nft'
+           WHEN a.is_contract THEN 'contract'
+           ELSE 'eoa'
+       END,
+       COALESCE(e.name, n.name),
+       COALESCE(e.symbol, n.symbol)
+FROM (
+    SELECT address FROM addresses
+    UNION
+    SELECT address FROM erc20_contracts
+    UNION
+    SELECT address FROM nft_contracts
+) s
+LEFT JOIN addresses a ON a.address = s.address
+LEFT JOIN erc20_contracts e ON e.address = s.address
+LEFT JOIN nft_contracts n ON n.address = s.address
+ON CONFLICT (address) DO NOTHING;
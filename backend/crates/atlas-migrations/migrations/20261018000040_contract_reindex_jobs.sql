@@ -0,0 +1,21 @@
+-- Per-contract reindex jobs queued by POST /api/admin/contracts/{address}/reindex
+-- and run by the contract reindex worker (see indexer/contract_reindex.rs).
+CREATE TABLE IF NOT EXISTS contract_reindex_jobs (
+    id BIGSERIAL PRIMARY KEY,
+    address VARCHAR(42) NOT NULL,
+    status TEXT NOT NULL DEFAULT 'queued'
+        CHECK (status IN ('queued', 'running', 'completed', 'failed')),
+    -- Rows rebuilt, set when the job completes
+    erc20_transfers BIGINT,
+    nft_transfers BIGINT,
+    error TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    started_at TIMESTAMPTZ,
+    completed_at TIMESTAMPTZ
+);
+
+-- At most one open job per contract: a second request returns the first.
+CREATE UNIQUE INDEX IF NOT EXISTS idx_contract_reindex_jobs_open
+    ON contract_reindex_jobs (address) WHERE status IN ('queued', 'running');
+CREATE INDEX IF NOT EXISTS idx_contract_reindex_jobs_address
+    ON contract_reindex_jobs (address, id DESC);
@@ -0,0 +1,15 @@
+-- Verifications removed from contract_abis, kept so an admin can audit or
+-- restore them. `action` is what removed or brought back the verification:
+-- an admin unverify, a re-verification with an exact match replacing it, or
+-- an admin restore. `verification` is the contract_abis row as it was.
+CREATE TABLE IF NOT EXISTS verification_history (
+    id BIGSERIAL PRIMARY KEY,
+    address VARCHAR(42) NOT NULL,
+    action TEXT NOT NULL CHECK (action IN ('unverified', 'replaced', 'restored')),
+    reason TEXT,
+    verification JSONB NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_verification_history_address
+    ON verification_history (address, id DESC);
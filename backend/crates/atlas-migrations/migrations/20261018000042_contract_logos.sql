@@ -0,0 +1,12 @@
+-- Admin-set logos for ERC-20 tokens and NFT collections: either an external
+-- URL or an uploaded image served by GET /api/logos/{address}. `logo_url` is
+-- what token and collection responses return: the external URL, or the
+-- upload's versioned path.
+CREATE TABLE IF NOT EXISTS contract_logos (
+    address VARCHAR(42) PRIMARY KEY,
+    logo_url TEXT NOT NULL,
+    image BYTEA,
+    content_type TEXT,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CHECK ((image IS NULL) = (content_type IS NULL))
+);
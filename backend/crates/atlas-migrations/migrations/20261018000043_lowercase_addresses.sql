@@ -0,0 +1,56 @@
+-- Addresses are stored as lowercase `0x` hex, so handlers lowercase user
+-- input and compare columns directly, without LOWER(). Rows written by the
+-- indexer always were lowercase; only the tables filled from admin or user
+-- input (labels, notes, curation, alerts, verification) can hold older
+-- mixed-case rows, so only those are scanned, never the indexed chain data.
+--
+-- lowercase_legacy_addresses() lowercases them and returns how many values
+-- changed; it is kept so an import can be normalized again later. These
+-- tables are small, so rows are updated one at a time: a row whose lowercased
+-- value would collide with an existing key is left as is and reported, to be
+-- merged by hand, without holding back the rest.
+CREATE OR REPLACE FUNCTION lowercase_legacy_addresses() RETURNS BIGINT AS $$
+DECLARE
+    col RECORD;
+    row_id TID;
+    total BIGINT := 0;
+BEGIN
+    FOR col IN
+        SELECT c.table_name, c.column_name
+        FROM (VALUES
+            ('address_labels', 'address'),
+            ('address_notes', 'address'),
+            ('alert_rules', 'contract'),
+            ('alert_rules', 'counterparty'),
+            ('contract_abis', 'address'),
+            ('contract_logos', 'address'),
+            ('derived_address_labels', 'address'),
+            ('derived_address_labels', 'source_address'),
+            ('export_jobs', 'address'),
+            ('label_suggestions', 'address'),
+            ('token_curation', 'address'),
+            ('verification_history', 'address')
+        ) AS c(table_name, column_name)
+        WHERE to_regclass(c.table_name) IS NOT NULL
+    LOOP
+        FOR row_id IN EXECUTE format(
+            'SELECT ctid FROM %I WHERE %I <> lower(%I)',
+            col.table_name, col.column_name, col.column_name
+        )
+        LOOP
+            BEGIN
+                EXECUTE format(
+                    'UPDATE %I SET %I = lower(%I) WHERE ctid = $1',
+                    col.table_name, col.column_name, col.column_name
+                ) USING row_id;
+                total := total + 1;
+            EXCEPTION WHEN unique_violation OR foreign_key_violation THEN
+                RAISE WARNING 'mixed-case %.% left as is: %', col.table_name, col.column_name, SQLERRM;
+            END;
+        END LOOP;
+    END LOOP;
+    RETURN total;
+END
+$$ LANGUAGE plpgsql;
+
+SELECT lowercase_legacy_addresses();
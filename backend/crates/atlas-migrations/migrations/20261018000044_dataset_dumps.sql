@@ -0,0 +1,12 @@
+-- Public daily dataset dumps: one row per CSV file the dataset worker wrote
+-- to `<DATASET_DIR>/<day>/<dataset>.csv`. GET /api/datasets lists them.
+CREATE TABLE IF NOT EXISTS dataset_dumps (
+    day DATE NOT NULL,
+    dataset VARCHAR(32) NOT NULL,
+    from_block BIGINT NOT NULL,
+    to_block BIGINT NOT NULL,
+    row_count BIGINT NOT NULL,
+    file_size BIGINT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (day, dataset)
+);
@@ -0,0 +1,9 @@
+-- processed_blocks records the hash each block number was written with, so
+-- a block rewritten with another hash (a reorg, or a gap fill that fetched
+-- a different block) replaces the old block's deltas instead of being
+-- skipped as a replay.
+ALTER TABLE processed_blocks ADD COLUMN IF NOT EXISTS hash VARCHAR(66);
+
+UPDATE processed_blocks p SET hash = b.hash
+FROM blocks b
+WHERE b.number = p.number AND p.hash IS NULL;
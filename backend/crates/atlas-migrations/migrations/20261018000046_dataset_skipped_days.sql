@@ -0,0 +1,7 @@
+-- Days the dataset worker stopped waiting for: still missing blocks a day
+-- after they ended. Later days are dumped meanwhile, and a skipped day is
+-- dumped (and its row deleted) once gap-fill has completed it.
+CREATE TABLE IF NOT EXISTS dataset_skipped_days (
+    day DATE PRIMARY KEY,
+    skipped_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,62 @@
+//! Versioned SQL migrations for the Atlas database.
+//!
+//! The files in `migrations/` are embedded into the binary at compile time,
+//! each with the SHA-384 checksum sqlx records when it applies them. Comparing
+//! the embedded set with `_sqlx_migrations` tells whether a database matches
+//! the release that reads it (see `atlas_common::db::migration_info`).
+
+use sqlx::migrate::Migrator;
+
+/// Every migration in `migrations/`, ordered by version.
+pub static MIGRATOR: Migrator = sqlx::migrate!();
+
+/// A migration built into this binary.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EmbeddedMigration {
+    pub version: i64,
+    pub description: &'static str,
+    pub checksum: &'static [u8],
+}
+
+impl EmbeddedMigration {
+    /// The checksum as lowercase hex, as shown by `sqlx migrate info`.
+    pub fn checksum_hex(&self) -> String {
+        hex::encode(self.checksum)
+    }
+}
+
+/// The embedded migrations, oldest first.
+pub fn embedded() -> impl Iterator<Item = EmbeddedMigration> {
+    MIGRATOR.iter().map(|migration| EmbeddedMigration {
+        version: migration.version,
+        description: &migration.description,
+        checksum: &migration.checksum,
+    })
+}
+
+/// Version of the newest embedded migration: the schema this build expects.
+pub fn latest_version() -> i64 {
+    MIGRATOR.iter().map(|m| m.version).max().unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn embedded_versions_are_unique_and_ordered() {
+        let versions: Vec<i64> = embedded().map(|m| m.version).collect();
+        assert!(!versions.is_empty());
+        assert!(versions.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(latest_version(), *versions.last().unwrap());
+    }
+
+    #[test]
+    fn embedded_migrations_carry_sha384_checksums() {
+        for migration in embedded() {
+            assert_eq!(migration.checksum.len(), 48, "{}", migration.version);
+            assert_eq!(migration.checksum_hex().len(), 96);
+            assert!(!migration.description.is_empty());
+        }
+    }
+}
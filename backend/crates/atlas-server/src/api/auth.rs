@@ -0,0 +1,211 @@
+use alloy::primitives::keccak256;
+use axum::http::{header::AUTHORIZATION, HeaderMap};
+
+use crate::api::AppState;
+use crate::labels::TagScope;
+use atlas_common::encoding::b256_to_hex;
+use atlas_common::AtlasError;
+
+/// Prefix of issued curator keys, so leaked keys are recognisable.
+const CURATOR_KEY_PREFIX: &str = "atlas_lk_";
+
+/// Require `Authorization: Bearer <ADMIN_API_KEY>` on an admin endpoint.
+///
+/// Admin-only routes are only mounted when a key is configured, but this
+/// still rejects every request if the key is missing so a routing mistake
+/// cannot expose them.
+pub fn require_admin(state: &AppState, headers: &HeaderMap) -> Result<(), AtlasError> {
+    if is_admin(state, headers) {
+        return Ok(());
+    }
+    if state.admin_api_key.is_none() {
+        return Err(AtlasError::Unauthorized(
+            "Admin API is disabled".to_string(),
+        ));
+    }
+    Err(AtlasError::Unauthorized(
+        "Invalid or missing admin API key".to_string(),
+    ))
+}
+
+fn is_admin(state: &AppState, headers: &HeaderMap) -> bool {
+    match (state.admin_api_key.as_deref(), bearer_token(headers)) {
+        (Some(expected), Some(token)) => constant_time_eq(token.as_bytes(), expected.as_bytes()),
+        _ => false,
+    }
+}
+
+/// Caller allowed to modify curated labels.
+pub enum LabelEditor {
+    Admin,
+    /// A curator key from `label_api_keys`, limited to its tags.
+    Curator {
+        name: String,
+        scope: TagScope,
+    },
+}
+
+impl LabelEditor {
+    /// Tag scope to enforce, or `None` for unrestricted (admin) access.
+    pub fn scope(&self) -> Option<&TagScope> {
+        match self {
+            LabelEditor::Admin => None,
+            LabelEditor::Curator { scope, .. } => Some(scope),
+        }
+    }
+
+    pub fn check_write(
+        &self,
+        existing: Option<&[String]>,
+        new_tags: &[String],
+    ) -> Result<(), AtlasError> {
+        self.check(|scope| scope.check_write(existing, new_tags))
+    }
+
+    pub fn check_delete(&self, existing: &[String]) -> Result<(), AtlasError> {
+        self.check(|scope| scope.check_delete(existing))
+    }
+
+    /// Curators may only moderate suggestions carrying one of their tags.
+    pub fn check_review(&self, suggested: &[String]) -> Result<(), AtlasError> {
+        self.check(|scope| {
+            if scope.covers_any(suggested) {
+                Ok(())
+            } else {
+                Err("Suggestion has none of the key's tags".to_string())
+            }
+        })
+    }
+
+    fn check(&self, rule: impl FnOnce(&TagScope) -> Result<(), String>) -> Result<(), AtlasError> {
+        match self {
+            LabelEditor::Admin => Ok(()),
+            LabelEditor::Curator { name, scope } => rule(scope).map_err(|reason| {
+                AtlasError::Forbidden(format!("Curator key '{name}' not permitted: {reason}"))
+            }),
+        }
+    }
+}
+
+/// Authenticate a label-management request with either the admin key or an
+/// active curator key.
+pub async fn authorize_label_editor(
+    state: &AppState,
+    headers: &HeaderMap,
+) -> Result<LabelEditor, AtlasError> {
+    if is_admin(state, headers) {
+        return Ok(LabelEditor::Admin);
+    }
+
+    let (_, name, tags) = active_curator_key(state, headers).await?;
+    Ok(LabelEditor::Curator {
+        name,
+        scope: TagScope::new(tags),
+    })
+}
+
+/// Authenticate a request with an active issued key and return the key's id.
+/// Per-key data such as address notes is owned by this id; the admin key has
+/// none.
+pub async fn authorize_api_key(state: &AppState, headers: &HeaderMap) -> Result<i64, AtlasError> {
+    let (id, _, _) = active_curator_key(state, headers).await?;
+    Ok(id)
+}
+
+/// Id, name and tags of the unrevoked curator key sent as the bearer token.
+async fn active_curator_key(
+    state: &AppState,
+    headers: &HeaderMap,
+) -> Result<(i64, String, Vec<String>), AtlasError> {
+    let unauthorized = || AtlasError::Unauthorized("Invalid or missing API key".to_string());
+    let token = bearer_token(headers).ok_or_else(unauthorized)?;
+    if !token.starts_with(CURATOR_KEY_PREFIX) {
+        return Err(unauthorized());
+    }
+
+    let key: Option<(i64, String, Vec<String>)> = sqlx::query_as(
+        "SELECT id, name, tags FROM label_api_keys WHERE key_hash = $1 AND revoked_at IS NULL",
+    )
+    .bind(hash_curator_key(token))
+    .fetch_optional(&state.pool)
+    .await?;
+    key.ok_or_else(unauthorized)
+}
+
+/// Generate a new curator key and its storage hash.
+pub fn generate_curator_key() -> (String, String) {
+    let key = format!(
+        "{CURATOR_KEY_PREFIX}{}",
+        hex::encode(rand::random::<[u8; 32]>())
+    );
+    let hash = hash_curator_key(&key);
+    (key, hash)
+}
+
+fn hash_curator_key(key: &str) -> String {
+    b256_to_hex(&keccak256(key.as_bytes()))
+}
+
+fn bearer_token(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(AUTHORIZATION)?
+        .to_str()
+        .ok()?
+        .strip_prefix("Bearer ")
+        .map(str::trim)
+}
+
+/// Compare secrets without short-circuiting on the first differing byte.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    fn headers(value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(AUTHORIZATION, HeaderValue::from_str(value).unwrap());
+        headers
+    }
+
+    #[test]
+    fn bearer_token_requires_bearer_scheme() {
+        assert_eq!(bearer_token(&headers("Bearer secret")), Some("secret"));
+        assert_eq!(bearer_token(&headers("Basic secret")), None);
+        assert_eq!(bearer_token(&HeaderMap::new()), None);
+    }
+
+    #[test]
+    fn constant_time_eq_matches_only_identical_input() {
+        assert!(constant_time_eq(b"secret", b"secret"));
+        assert!(!constant_time_eq(b"secret", b"secreT"));
+        assert!(!constant_time_eq(b"secret", b"secret2"));
+    }
+
+    #[test]
+    fn generated_curator_keys_are_prefixed_and_hash_stably() {
+        let (key, hash) = generate_curator_key();
+        assert!(key.starts_with(CURATOR_KEY_PREFIX));
+        assert_eq!(hash, hash_curator_key(&key));
+        assert_ne!(key, generate_curator_key().0);
+    }
+
+    #[test]
+    fn curator_scope_violation_is_forbidden() {
+        let editor = LabelEditor::Curator {
+            name: "bridge-team".to_string(),
+            scope: TagScope::new(vec!["bridge".to_string()]),
+        };
+        let err = editor.check_write(None, &["cex".to_string()]).unwrap_err();
+        assert_eq!(err.status_code(), 403);
+        assert!(LabelEditor::Admin
+            .check_write(None, &["cex".to_string()])
+            .is_ok());
+    }
+}
@@ -0,0 +1,89 @@
+use axum::body::{Body, Bytes, HttpBody};
+use futures::StreamExt;
+
+use atlas_common::AtlasError;
+
+/// Request body size limits per route group, in bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BodyLimits {
+    /// Every API route without its own limit (label imports allow 20 MiB).
+    pub api: usize,
+    /// Contract verification, whose standard JSON inputs can be megabytes.
+    pub verify: usize,
+}
+
+impl Default for BodyLimits {
+    fn default() -> Self {
+        Self {
+            api: 2 * 1024 * 1024,
+            verify: 50 * 1024 * 1024,
+        }
+    }
+}
+
+/// Read a request body of at most `max_bytes`.
+///
+/// An oversized `Content-Length` is refused before anything is read, and a
+/// streamed body is cut off as soon as it passes the limit, so a handler can
+/// take a large upload without buffering more than it accepts. Either way the
+/// client gets a 413 that says what the limit is.
+pub async fn read_limited(body: Body, max_bytes: usize) -> Result<Bytes, AtlasError> {
+    let too_large =
+        || AtlasError::PayloadTooLarge(format!("request body exceeds the {max_bytes} byte limit"));
+    if body.size_hint().lower() > max_bytes as u64 {
+        return Err(too_large());
+    }
+
+    let mut stream = body.into_data_stream();
+    let mut buf = Vec::new();
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk
+            .map_err(|e| AtlasError::InvalidInput(format!("failed to read request body: {e}")))?;
+        if buf.len() + chunk.len() > max_bytes {
+            return Err(too_large());
+        }
+        buf.extend_from_slice(&chunk);
+    }
+    Ok(buf.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn streamed(chunks: &[&'static [u8]]) -> Body {
+        let chunks: Vec<Result<Bytes, std::io::Error>> = chunks
+            .iter()
+            .map(|chunk| Ok(Bytes::from_static(chunk)))
+            .collect();
+        Body::from_stream(futures::stream::iter(chunks))
+    }
+
+    #[tokio::test]
+    async fn reads_bodies_up_to_the_limit() {
+        let bytes = read_limited(Body::from(vec![b'x'; 16]), 16).await.unwrap();
+        assert_eq!(bytes.len(), 16);
+
+        let bytes = read_limited(streamed(&[b"0123", b"4567"]), 8)
+            .await
+            .unwrap();
+        assert_eq!(&bytes[..], b"01234567");
+    }
+
+    #[tokio::test]
+    async fn rejects_a_known_size_over_the_limit() {
+        let err = read_limited(Body::from(vec![b'x'; 17]), 16)
+            .await
+            .unwrap_err();
+        assert_eq!(err.status_code(), 413);
+        assert!(err.to_string().contains("16 byte limit"));
+    }
+
+    #[tokio::test]
+    async fn cuts_off_a_stream_once_it_passes_the_limit() {
+        let err = read_limited(streamed(&[b"0123", b"4567", b"8"]), 8)
+            .await
+            .unwrap_err();
+        assert_eq!(err.status_code(), 413);
+    }
+}
@@ -0,0 +1,284 @@
+use axum::{
+    extract::{Request, State},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::api::error::ApiError;
+use crate::api::AppState;
+use atlas_common::AtlasError;
+
+/// Postgres SQLSTATE raised when `statement_timeout` cancels a query.
+const QUERY_CANCELED_SQLSTATE: &str = "57014";
+
+/// Response extension set by [`ApiError`] when a handler failed because the
+/// database is overloaded or unreachable. The breaker middleware counts these.
+#[derive(Clone, Copy, Debug)]
+pub struct DatabaseFailure;
+
+/// Whether a database error signals pool/server pressure (as opposed to a
+/// query-specific problem such as a missing row or constraint violation).
+pub fn is_database_overload(err: &sqlx::Error) -> bool {
+    match err {
+        sqlx::Error::PoolTimedOut | sqlx::Error::PoolClosed | sqlx::Error::Io(_) => true,
+        sqlx::Error::Database(db_err) => db_err.code().as_deref() == Some(QUERY_CANCELED_SQLSTATE),
+        _ => false,
+    }
+}
+
+#[derive(Default)]
+struct BreakerState {
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+    /// When the half-open probe request was let through.
+    probe_started: Option<Instant>,
+}
+
+/// Consecutive-failure circuit breaker guarding the API database pool.
+///
+/// After `failure_threshold` consecutive DB failures (statement timeouts, pool
+/// acquire timeouts, lost connections) the breaker opens and API requests are
+/// rejected with 503 for `cooldown`. Once the cooldown elapses a single probe
+/// request is let through (half-open) while the rest are still rejected: its
+/// success closes the breaker, its failure re-opens it. A probe that never
+/// reports back (client gone, non-database 5xx) is replaced by the next
+/// request after another `cooldown`. A threshold of 0 disables the breaker.
+pub struct CircuitBreaker {
+    failure_threshold: u32,
+    cooldown: Duration,
+    state: Mutex<BreakerState>,
+}
+
+impl Default for CircuitBreaker {
+    /// A disabled breaker that never opens.
+    fn default() -> Self {
+        Self::new(0, Duration::ZERO)
+    }
+}
+
+impl CircuitBreaker {
+    pub fn new(failure_threshold: u32, cooldown: Duration) -> Self {
+        Self {
+            failure_threshold,
+            cooldown,
+            state: Mutex::new(BreakerState::default()),
+        }
+    }
+
+    /// Returns the remaining open time when requests should be rejected.
+    pub fn check(&self) -> Result<(), Duration> {
+        self.check_at(Instant::now())
+    }
+
+    fn check_at(&self, now: Instant) -> Result<(), Duration> {
+        if self.failure_threshold == 0 {
+            return Ok(());
+        }
+        let mut state = self.state.lock().expect("circuit breaker lock poisoned");
+        let Some(opened_at) = state.opened_at else {
+            return Ok(());
+        };
+        let elapsed = now.saturating_duration_since(opened_at);
+        if elapsed < self.cooldown {
+            return Err(self.cooldown - elapsed);
+        }
+        if let Some(probe_started) = state.probe_started {
+            let probing = now.saturating_duration_since(probe_started);
+            if probing < self.cooldown {
+                return Err(self.cooldown - probing);
+            }
+        }
+        state.probe_started = Some(now);
+        Ok(())
+    }
+
+    pub fn record_success(&self) {
+        if self.failure_threshold == 0 {
+            return;
+        }
+        let mut state = self.state.lock().expect("circuit breaker lock poisoned");
+        if state.opened_at.is_some() {
+            tracing::info!("database circuit breaker closed");
+        }
+        *state = BreakerState::default();
+    }
+
+    pub fn record_failure(&self) {
+        self.record_failure_at(Instant::now());
+    }
+
+    fn record_failure_at(&self, now: Instant) {
+        if self.failure_threshold == 0 {
+            return;
+        }
+        let mut state = self.state.lock().expect("circuit breaker lock poisoned");
+        state.consecutive_failures = state.consecutive_failures.saturating_add(1);
+        if state.consecutive_failures >= self.failure_threshold {
+            if state.opened_at.is_none() {
+                tracing::warn!(
+                    failures = state.consecutive_failures,
+                    cooldown_secs = self.cooldown.as_secs(),
+                    "database circuit breaker opened"
+                );
+            }
+            state.opened_at = Some(now);
+            state.probe_started = None;
+        }
+    }
+}
+
+/// Whether a handler's response counts against the breaker: only failures
+/// [`ApiError`] flagged as database overload, not e.g. a request timeout
+/// spent outside the database.
+fn is_database_failure(response: &Response) -> bool {
+    response.extensions().get::<DatabaseFailure>().is_some()
+}
+
+fn is_exempt_path(path: &str) -> bool {
+    // Probes and metrics must keep answering while the breaker is open,
+    // otherwise an orchestrator would restart an otherwise healthy process.
+    path.starts_with("/health") || path == "/metrics"
+}
+
+/// Axum middleware that short-circuits requests while the DB breaker is open
+/// and feeds handler outcomes back into it.
+pub async fn circuit_breaker_middleware(
+    State(state): State<Arc<AppState>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if is_exempt_path(request.uri().path()) {
+        return next.run(request).await;
+    }
+
+    let breaker = &state.db_circuit_breaker;
+    if let Err(remaining) = breaker.check() {
+        return ApiError(AtlasError::ServiceUnavailable {
+            message: "Database temporarily unavailable".to_string(),
+            retry_after_seconds: remaining.as_secs().max(1),
+        })
+        .into_response();
+    }
+
+    let response = next.run(request).await;
+    if is_database_failure(&response) {
+        breaker.record_failure();
+    } else if !response.status().is_server_error() {
+        breaker.record_success();
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn breaker_opens_after_threshold_consecutive_failures() {
+        let breaker = CircuitBreaker::new(3, Duration::from_secs(30));
+        let now = Instant::now();
+
+        breaker.record_failure_at(now);
+        breaker.record_failure_at(now);
+        assert!(breaker.check_at(now).is_ok());
+
+        breaker.record_failure_at(now);
+        let remaining = breaker.check_at(now + Duration::from_secs(10)).unwrap_err();
+        assert_eq!(remaining, Duration::from_secs(20));
+    }
+
+    #[test]
+    fn success_resets_failure_count() {
+        let breaker = CircuitBreaker::new(2, Duration::from_secs(30));
+        let now = Instant::now();
+
+        breaker.record_failure_at(now);
+        breaker.record_success();
+        breaker.record_failure_at(now);
+
+        assert!(breaker.check_at(now).is_ok());
+    }
+
+    #[test]
+    fn breaker_half_opens_after_cooldown_and_reopens_on_failure() {
+        let breaker = CircuitBreaker::new(1, Duration::from_secs(5));
+        let now = Instant::now();
+
+        breaker.record_failure_at(now);
+        assert!(breaker.check_at(now).is_err());
+
+        let later = now + Duration::from_secs(6);
+        assert!(breaker.check_at(later).is_ok());
+
+        breaker.record_failure_at(later);
+        assert!(breaker.check_at(later + Duration::from_secs(1)).is_err());
+    }
+
+    #[test]
+    fn half_open_breaker_admits_a_single_probe() {
+        let breaker = CircuitBreaker::new(1, Duration::from_secs(5));
+        let now = Instant::now();
+        breaker.record_failure_at(now);
+
+        let later = now + Duration::from_secs(6);
+        assert!(breaker.check_at(later).is_ok());
+        let remaining = breaker
+            .check_at(later + Duration::from_secs(1))
+            .unwrap_err();
+        assert_eq!(remaining, Duration::from_secs(4));
+
+        breaker.record_success();
+        assert!(breaker.check_at(later + Duration::from_secs(1)).is_ok());
+        assert!(breaker.check_at(later + Duration::from_secs(1)).is_ok());
+    }
+
+    #[test]
+    fn lost_probe_is_replaced_after_cooldown() {
+        let breaker = CircuitBreaker::new(1, Duration::from_secs(5));
+        let now = Instant::now();
+        breaker.record_failure_at(now);
+
+        let probe = now + Duration::from_secs(6);
+        assert!(breaker.check_at(probe).is_ok());
+        assert!(breaker.check_at(probe + Duration::from_secs(4)).is_err());
+        assert!(breaker.check_at(probe + Duration::from_secs(5)).is_ok());
+    }
+
+    #[test]
+    fn only_database_failures_count() {
+        let timeout = axum::http::StatusCode::REQUEST_TIMEOUT.into_response();
+        assert!(!is_database_failure(&timeout));
+
+        let not_found = ApiError(AtlasError::Database(sqlx::Error::RowNotFound)).into_response();
+        assert!(!is_database_failure(&not_found));
+
+        let overloaded = ApiError(AtlasError::Database(sqlx::Error::PoolTimedOut)).into_response();
+        assert!(is_database_failure(&overloaded));
+    }
+
+    #[test]
+    fn zero_threshold_disables_breaker() {
+        let breaker = CircuitBreaker::default();
+        for _ in 0..10 {
+            breaker.record_failure();
+        }
+        assert!(breaker.check().is_ok());
+    }
+
+    #[test]
+    fn overload_classification_ignores_query_specific_errors() {
+        assert!(is_database_overload(&sqlx::Error::PoolTimedOut));
+        assert!(is_database_overload(&sqlx::Error::PoolClosed));
+        assert!(!is_database_overload(&sqlx::Error::RowNotFound));
+    }
+
+    #[test]
+    fn health_and_metrics_paths_are_exempt() {
+        assert!(is_exempt_path("/health/live"));
+        assert!(is_exempt_path("/health/ready"));
+        assert!(is_exempt_path("/metrics"));
+        assert!(!is_exempt_path("/api/blocks"));
+    }
+}
@@ -6,6 +6,7 @@ use axum::{
 use serde_json::json;
 use std::ops::Deref;
 
+use crate::api::circuit_breaker::{is_database_overload, DatabaseFailure};
 use atlas_common::AtlasError;
 
 /// Newtype wrapper for AtlasError to implement IntoResponse
@@ -81,10 +82,14 @@ impl IntoResponse for ApiError {
             AtlasError::InvalidInput(msg) => msg.clone(),
             AtlasError::Validation(msg) => msg.clone(),
             AtlasError::Unauthorized(msg) => msg.clone(),
+            AtlasError::Forbidden(msg) => msg.clone(),
             AtlasError::Verification(msg) => msg.clone(),
             AtlasError::BytecodeMismatch(msg) => msg.clone(),
             AtlasError::Compilation(msg) => msg.clone(),
+            AtlasError::PayloadTooLarge(msg) => msg.clone(),
+            AtlasError::LimitExceeded(msg) => msg.clone(),
             AtlasError::TooManyRequests { message, .. } => message.clone(),
+            AtlasError::ServiceUnavailable { message, .. } => message.clone(),
             // Opaque: log full detail, return generic message
             AtlasError::Database(inner) => {
                 tracing::error!(error = %inner, "Database error");
@@ -108,11 +113,25 @@ impl IntoResponse for ApiError {
             }
         };
 
-        let body = match &self.0 {
+        let retry_after_seconds = match &self.0 {
             AtlasError::TooManyRequests {
                 retry_after_seconds,
                 ..
-            } => Json(json!({
+            }
+            | AtlasError::ServiceUnavailable {
+                retry_after_seconds,
+                ..
+            } => Some(*retry_after_seconds),
+            _ => None,
+        };
+
+        let body = match (&self.0, retry_after_seconds) {
+            (AtlasError::ServiceUnavailable { .. }, Some(retry_after_seconds)) => Json(json!({
+                "error": client_message,
+                "code": "database_unavailable",
+                "retry_after_seconds": retry_after_seconds,
+            })),
+            (_, Some(retry_after_seconds)) => Json(json!({
                 "error": client_message,
                 "retry_after_seconds": retry_after_seconds,
             })),
@@ -120,15 +139,14 @@ impl IntoResponse for ApiError {
         };
 
         let mut response = (status, body).into_response();
-        if let AtlasError::TooManyRequests {
-            retry_after_seconds,
-            ..
-        } = &self.0
-        {
+        if let Some(retry_after_seconds) = retry_after_seconds {
             if let Ok(header_value) = HeaderValue::from_str(&retry_after_seconds.to_string()) {
                 response.headers_mut().insert(RETRY_AFTER, header_value);
             }
         }
+        if matches!(&self.0, AtlasError::Database(inner) if is_database_overload(inner)) {
+            response.extensions_mut().insert(DatabaseFailure);
+        }
         response
     }
 }
@@ -181,4 +199,31 @@ mod tests {
         assert_eq!(value["error"], "Faucet cooldown active");
         assert_eq!(value["retry_after_seconds"], 42);
     }
+
+    #[tokio::test]
+    async fn service_unavailable_returns_structured_503() {
+        let response = ApiError(AtlasError::ServiceUnavailable {
+            message: "Database temporarily unavailable".to_string(),
+            retry_after_seconds: 12,
+        })
+        .into_response();
+
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(response.headers().get(RETRY_AFTER).unwrap(), "12");
+
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(value["error"], "Database temporarily unavailable");
+        assert_eq!(value["code"], "database_unavailable");
+        assert_eq!(value["retry_after_seconds"], 12);
+    }
+
+    #[test]
+    fn pool_timeouts_mark_response_as_database_failure() {
+        let response = ApiError(AtlasError::Database(sqlx::Error::PoolTimedOut)).into_response();
+        assert!(response.extensions().get::<DatabaseFailure>().is_some());
+
+        let response = ApiError(AtlasError::Database(sqlx::Error::RowNotFound)).into_response();
+        assert!(response.extensions().get::<DatabaseFailure>().is_none());
+    }
 }
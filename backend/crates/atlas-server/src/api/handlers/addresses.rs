@@ -1,15 +1,37 @@
 use axum::{
     extract::{Path, Query, State},
+    http::HeaderMap,
+    response::{IntoResponse, Response},
     Json,
 };
+use bigdecimal::BigDecimal;
 use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
+use std::collections::HashMap;
+use std::str::FromStr;
 use std::sync::Arc;
 
 use crate::api::error::ApiResult;
-use crate::api::handlers::has_complete_erc20_supply_history;
+use crate::api::handlers::display::{self, decorate, token_decimals, Display, DisplayQuery};
+use crate::api::handlers::tokens::TokenStanding;
+use crate::api::handlers::transactions::decorate_transactions;
+use crate::api::handlers::{
+    anchor_bound, count_rows, fetch_count, get_table_count, has_complete_erc20_supply_history,
+    require_hot_tier, segments_newest_first, snapshot_anchor, stitch_segments, PageTotal,
+};
+use crate::api::ndjson;
 use crate::api::AppState;
-use atlas_common::{Address, AtlasError, NftToken, PaginatedResponse, Pagination, Transaction};
+use crate::ordering::{ChainRow, Direction};
+use atlas_common::encoding::parse_address;
+use atlas_common::storage::{BlockRange, Segment, Tier, TieredTable};
+use atlas_common::{
+    Address, AtlasError, CountMode, NftToken, PaginatedResponse, Pagination, Transaction,
+};
+
+/// Exact counts over `from_address = $1 OR to_address = $1` are the slowest
+/// queries in the API for busy addresses, so address history defaults to
+/// estimated totals. Clients can still ask for `?count=exact` or `?count=none`.
+const ADDRESS_HISTORY_COUNT_MODE: CountMode = CountMode::Estimated;
 
 /// Merged address response that combines data from addresses, nft_contracts, and erc20_contracts tables
 #[derive(Debug, Clone, Serialize)]
@@ -31,6 +53,63 @@ pub struct AddressDetailResponse {
     /// Total supply (for NFT or ERC-20 contracts)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub total_supply: Option<String>,
+    /// Allocation in the chain's genesis, when the genesis state was imported
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub genesis: Option<GenesisAllocation>,
+    /// First successful transaction that sent this address native value
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub funded_by: Option<AddressProvenance>,
+    /// Transaction that created this contract
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_by: Option<AddressProvenance>,
+}
+
+/// Counterparty (funder or deployer) and transaction an address came from
+#[derive(Debug, Clone, Serialize)]
+pub struct AddressProvenance {
+    pub address: String,
+    pub block_number: i64,
+    pub tx_hash: String,
+}
+
+/// Internal row type for the provenance columns of `addresses`
+#[derive(sqlx::FromRow)]
+struct ProvenanceRow {
+    funded_by: Option<String>,
+    funded_block: Option<i64>,
+    funding_tx_hash: Option<String>,
+    deployer: Option<String>,
+    creation_block: Option<i64>,
+    creation_tx_hash: Option<String>,
+}
+
+impl AddressProvenance {
+    fn from_columns(
+        address: Option<String>,
+        block_number: Option<i64>,
+        tx_hash: Option<String>,
+    ) -> Option<Self> {
+        Some(Self {
+            address: address?,
+            block_number: block_number?,
+            tx_hash: tx_hash?,
+        })
+    }
+}
+
+/// An account's genesis `alloc` entry
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct GenesisAllocation {
+    /// Wei
+    pub balance: String,
+    pub nonce: i64,
+    /// Bytes of predeployed code; 0 for plain accounts
+    pub code_size: i32,
+    pub storage_slots: i32,
+    /// `balance` in native units, with `?include=display`
+    #[sqlx(skip)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display: Option<Display>,
 }
 
 /// Address list item with address type info
@@ -92,59 +171,6 @@ pub async fn list_addresses(
     let limit = filters.limit.min(100);
     let offset = (page.saturating_sub(1) * limit) as i64;
 
-    // Build the combined query using UNION ALL
-    // We use a CTE to combine all sources and then filter/paginate
-    let base_query = r#"
-        WITH all_addresses AS (
-            -- Regular addresses (EOAs and contracts not in token tables)
-            SELECT
-                a.address,
-                a.is_contract,
-                a.first_seen_block,
-                a.tx_count,
-                CASE
-                    WHEN e.address IS NOT NULL THEN 'erc20'
-                    WHEN n.address IS NOT NULL THEN 'nft'
-                    WHEN a.is_contract THEN 'contract'
-                    ELSE 'eoa'
-                END as address_type,
-                COALESCE(e.name, n.name) as name,
-                COALESCE(e.symbol, n.symbol) as symbol
-            FROM addresses a
-            LEFT JOIN erc20_contracts e ON a.address = e.address
-            LEFT JOIN nft_contracts n ON a.address = n.address
-
-            UNION ALL
-
-            -- ERC-20 contracts not in addresses table
-            SELECT
-                e.address,
-                true as is_contract,
-                e.first_seen_block,
-                0 as tx_count,
-                'erc20' as address_type,
-                e.name,
-                e.symbol
-            FROM erc20_contracts e
-            WHERE NOT EXISTS (SELECT 1 FROM addresses a WHERE a.address = e.address)
-
-            UNION ALL
-
-            -- NFT contracts not in addresses table
-            SELECT
-                n.address,
-                true as is_contract,
-                n.first_seen_block,
-                0 as tx_count,
-                'nft' as address_type,
-                n.name,
-                n.symbol
-            FROM nft_contracts n
-            WHERE NOT EXISTS (SELECT 1 FROM addresses a WHERE a.address = n.address)
-        )
-        SELECT * FROM all_addresses
-    "#;
-
     // Build WHERE conditions (validated; numeric/boolean to avoid injection)
     let mut conditions: Vec<String> = Vec::new();
 
@@ -177,35 +203,33 @@ pub async fn list_addresses(
         format!("WHERE {}", conditions.join(" AND "))
     };
 
-    // Count total
-    let count_query = format!(
-        "{} {} ",
-        base_query.replace(
-            "SELECT * FROM all_addresses",
-            "SELECT COUNT(*) FROM all_addresses"
-        ),
-        where_clause
-    );
-    let total: (i64,) = sqlx::query_as(&count_query).fetch_one(&state.pool).await?;
+    // Unfiltered, the table estimate is the total (exact for small tables)
+    let total = if conditions.is_empty() {
+        get_table_count(&state.pool, "address_directory").await?
+    } else {
+        let count_query = format!("SELECT COUNT(*) FROM address_directory {where_clause}");
+        fetch_count(&state.pool, sqlx::query_as(&count_query)).await?
+    };
 
     // Fetch addresses sorted by tx_count (most active first), then by first_seen_block
     let query = format!(
-        "{} {}
-         ORDER BY tx_count DESC, first_seen_block DESC
+        "SELECT address, first_seen_block, tx_count, address_type, name, symbol
+         FROM address_directory
+         {}
+         ORDER BY tx_count DESC, first_seen_block DESC, address
          LIMIT {} OFFSET {}",
-        base_query, where_clause, limit, offset
+        where_clause, limit, offset
     );
 
     let addresses: Vec<AddressListItem> = sqlx::query_as(&query).fetch_all(&state.pool).await?;
 
-    Ok(Json(PaginatedResponse::new(
-        addresses, page, limit, total.0,
-    )))
+    Ok(Json(PaginatedResponse::new(addresses, page, limit, total)))
 }
 
 pub async fn get_address(
     State(state): State<Arc<AppState>>,
     Path(address): Path<String>,
+    Query(options): Query<DisplayQuery>,
 ) -> ApiResult<Json<AddressDetailResponse>> {
     let address = normalize_address(&address);
 
@@ -250,10 +274,33 @@ pub async fn get_address(
         None => None,
     };
 
+    let mut genesis: Option<GenesisAllocation> = sqlx::query_as(
+        "SELECT balance::text AS balance, nonce, code_size, storage_slots
+         FROM genesis_accounts
+         WHERE address = $1",
+    )
+    .bind(&address)
+    .fetch_optional(&state.pool)
+    .await?;
+    if let Some(genesis) = genesis.as_mut().filter(|_| options.enabled()) {
+        genesis.display = BigDecimal::from_str(&genesis.balance)
+            .ok()
+            .map(|balance| state.native_currency.amount(&balance, None, None));
+    }
+
+    let provenance: Option<ProvenanceRow> = sqlx::query_as(
+        "SELECT funded_by, funded_block, funding_tx_hash, deployer, creation_block, creation_tx_hash
+         FROM addresses
+         WHERE address = $1",
+    )
+    .bind(&address)
+    .fetch_optional(&state.pool)
+    .await?;
+
     // Merge the data
-    match (base_addr, nft_contract, erc20_contract) {
+    let mut response = match (base_addr, nft_contract, erc20_contract) {
         // Found in addresses table and is an NFT contract
-        (Some(addr), Some(nft), None) => Ok(Json(AddressDetailResponse {
+        (Some(addr), Some(nft), None) => AddressDetailResponse {
             address: addr.address,
             first_seen_block: addr.first_seen_block,
             tx_count: addr.tx_count,
@@ -262,9 +309,12 @@ pub async fn get_address(
             symbol: nft.symbol,
             decimals: None,
             total_supply: nft.total_supply.map(|s| s.to_string()),
-        })),
+            genesis: None,
+            funded_by: None,
+            created_by: None,
+        },
         // Found in addresses table and is an ERC-20 contract
-        (Some(addr), None, Some(erc20)) => Ok(Json(AddressDetailResponse {
+        (Some(addr), None, Some(erc20)) => AddressDetailResponse {
             address: addr.address,
             first_seen_block: addr.first_seen_block,
             tx_count: addr.tx_count,
@@ -273,9 +323,12 @@ pub async fn get_address(
             symbol: erc20.symbol,
             decimals: Some(erc20.decimals),
             total_supply: erc20.total_supply.map(|s| s.to_string()),
-        })),
+            genesis: None,
+            funded_by: None,
+            created_by: None,
+        },
         // Found only in addresses table (regular address or contract)
-        (Some(addr), None, None) => Ok(Json(AddressDetailResponse {
+        (Some(addr), None, None) => AddressDetailResponse {
             address: addr.address,
             first_seen_block: addr.first_seen_block,
             tx_count: addr.tx_count,
@@ -284,9 +337,12 @@ pub async fn get_address(
             symbol: None,
             decimals: None,
             total_supply: None,
-        })),
+            genesis: None,
+            funded_by: None,
+            created_by: None,
+        },
         // Found only in NFT contracts table (not in addresses)
-        (None, Some(nft), None) => Ok(Json(AddressDetailResponse {
+        (None, Some(nft), None) => AddressDetailResponse {
             address: nft.address,
             first_seen_block: nft.first_seen_block,
             tx_count: 0,
@@ -295,9 +351,12 @@ pub async fn get_address(
             symbol: nft.symbol,
             decimals: None,
             total_supply: nft.total_supply.map(|s| s.to_string()),
-        })),
+            genesis: None,
+            funded_by: None,
+            created_by: None,
+        },
         // Found only in ERC-20 contracts table (not in addresses)
-        (None, None, Some(erc20)) => Ok(Json(AddressDetailResponse {
+        (None, None, Some(erc20)) => AddressDetailResponse {
             address: erc20.address,
             first_seen_block: erc20.first_seen_block,
             tx_count: 0,
@@ -306,9 +365,12 @@ pub async fn get_address(
             symbol: erc20.symbol,
             decimals: Some(erc20.decimals),
             total_supply: erc20.total_supply.map(|s| s.to_string()),
-        })),
+            genesis: None,
+            funded_by: None,
+            created_by: None,
+        },
         // Edge case: found in both NFT and ERC-20 (shouldn't happen, prefer ERC-20)
-        (base, _, Some(erc20)) => Ok(Json(AddressDetailResponse {
+        (base, _, Some(erc20)) => AddressDetailResponse {
             address: erc20.address.clone(),
             first_seen_block: base
                 .as_ref()
@@ -320,12 +382,24 @@ pub async fn get_address(
             symbol: erc20.symbol,
             decimals: Some(erc20.decimals),
             total_supply: erc20.total_supply.map(|s| s.to_string()),
-        })),
+            genesis: None,
+            funded_by: None,
+            created_by: None,
+        },
         // Not found anywhere
         (None, None, None) => {
-            Err(AtlasError::NotFound(format!("Address {} not found", address)).into())
+            return Err(AtlasError::NotFound(format!("Address {} not found", address)).into())
         }
+    };
+
+    response.genesis = genesis;
+    if let Some(row) = provenance {
+        response.funded_by =
+            AddressProvenance::from_columns(row.funded_by, row.funded_block, row.funding_tx_hash);
+        response.created_by =
+            AddressProvenance::from_columns(row.deployer, row.creation_block, row.creation_tx_hash);
     }
+    Ok(Json(response))
 }
 
 /// Internal row type for NFT contracts query
@@ -349,39 +423,238 @@ struct Erc20ContractRow {
     first_seen_block: i64,
 }
 
+/// Badge data shown next to an address wherever it appears: label, contract
+/// kind, verification, proxy and token metadata from one query
+#[derive(Debug, Clone, Serialize)]
+pub struct AddressMeta {
+    pub address: String,
+    /// Address type: "eoa", "contract", "nft", "erc20"
+    pub address_type: String,
+    pub is_contract: bool,
+    /// Curated label
+    pub label: Option<AddressMetaLabel>,
+    /// Set when the contract's source is verified
+    pub verification: Option<AddressMetaVerification>,
+    /// Set when the contract is a known proxy
+    pub proxy: Option<AddressMetaProxy>,
+    /// Set for NFT and ERC-20 contracts
+    pub token: Option<AddressMetaToken>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AddressMetaLabel {
+    pub name: String,
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AddressMetaVerification {
+    pub contract_name: Option<String>,
+    /// "exact", "immutables" or "metadata"; null for older verifications
+    pub match_strategy: Option<String>,
+    pub verified_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AddressMetaProxy {
+    pub proxy_type: String,
+    pub implementation_address: String,
+    pub implementation_verified: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AddressMetaToken {
+    pub name: Option<String>,
+    pub symbol: Option<String>,
+    /// ERC-20 only
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub decimals: Option<i16>,
+    /// ERC-20 only
+    #[serde(flatten)]
+    pub standing: Option<TokenStanding>,
+}
+
+/// Internal row type for [`ADDRESS_META_SQL`]
+#[derive(sqlx::FromRow)]
+struct AddressMetaRow {
+    address: String,
+    is_contract: bool,
+    label_name: Option<String>,
+    label_tags: Option<Vec<String>>,
+    verified_at: Option<chrono::DateTime<chrono::Utc>>,
+    contract_name: Option<String>,
+    match_strategy: Option<String>,
+    proxy_type: Option<String>,
+    implementation_address: Option<String>,
+    implementation_verified: bool,
+    is_erc20: bool,
+    is_nft: bool,
+    token_name: Option<String>,
+    token_symbol: Option<String>,
+    decimals: Option<i16>,
+    token_verified: bool,
+    token_flagged: bool,
+    duplicate_of: Option<String>,
+}
+
+/// Everything [`AddressMeta`] needs in one round-trip. ERC-20 metadata wins
+/// over NFT metadata, as in [`get_address`].
+const ADDRESS_META_SQL: &str = "
+    SELECT q.address,
+           COALESCE(a.is_contract, false) OR v.address IS NOT NULL OR p.proxy_address IS NOT NULL
+               OR e.address IS NOT NULL OR n.address IS NOT NULL AS is_contract,
+           l.name AS label_name, l.tags AS label_tags,
+           v.verified_at, v.contract_name, v.match_strategy,
+           p.proxy_type, p.implementation_address,
+           iv.address IS NOT NULL AS implementation_verified,
+           e.address IS NOT NULL AS is_erc20,
+           n.address IS NOT NULL AS is_nft,
+           COALESCE(e.name, n.name) AS token_name,
+           COALESCE(e.symbol, n.symbol) AS token_symbol,
+           e.decimals,
+           COALESCE(cur.status = 'verified', false) AS token_verified,
+           COALESCE(cur.status = 'flagged', false) AS token_flagged,
+           CASE WHEN ct.address <> e.address THEN ct.address END AS duplicate_of
+    FROM (SELECT $1::varchar AS address) q
+    LEFT JOIN addresses a ON a.address = q.address
+    LEFT JOIN address_labels l ON l.address = q.address
+    LEFT JOIN contract_abis v ON v.address = q.address
+    LEFT JOIN proxy_contracts p ON p.proxy_address = q.address
+    LEFT JOIN contract_abis iv ON iv.address = p.implementation_address
+    LEFT JOIN erc20_contracts e ON e.address = q.address
+    LEFT JOIN nft_contracts n ON n.address = q.address
+    LEFT JOIN token_curation cur ON cur.address = e.address
+    LEFT JOIN canonical_tokens ct ON ct.symbol_key = lower(e.symbol)";
+
+impl From<AddressMetaRow> for AddressMeta {
+    fn from(row: AddressMetaRow) -> Self {
+        let address_type = if row.is_erc20 {
+            "erc20"
+        } else if row.is_nft {
+            "nft"
+        } else if row.is_contract {
+            "contract"
+        } else {
+            "eoa"
+        };
+        Self {
+            address: row.address,
+            address_type: address_type.to_string(),
+            is_contract: row.is_contract,
+            label: row.label_name.map(|name| AddressMetaLabel {
+                name,
+                tags: row.label_tags.unwrap_or_default(),
+            }),
+            verification: row.verified_at.map(|verified_at| AddressMetaVerification {
+                contract_name: row.contract_name,
+                match_strategy: row.match_strategy,
+                verified_at,
+            }),
+            proxy: row.proxy_type.zip(row.implementation_address).map(
+                |(proxy_type, implementation_address)| AddressMetaProxy {
+                    proxy_type,
+                    implementation_address,
+                    implementation_verified: row.implementation_verified,
+                },
+            ),
+            token: (row.is_erc20 || row.is_nft).then(|| AddressMetaToken {
+                name: row.token_name,
+                symbol: row.token_symbol,
+                decimals: row.decimals,
+                standing: row.is_erc20.then_some(TokenStanding {
+                    verified: row.token_verified,
+                    flagged: row.token_flagged,
+                    duplicate_of: row.duplicate_of,
+                }),
+            }),
+        }
+    }
+}
+
+/// GET /api/addresses/{address}/meta - Badge data for an address. Unknown
+/// addresses are plain EOAs rather than 404s, so clients can fetch it for
+/// any address they render.
+pub async fn get_address_meta(
+    State(state): State<Arc<AppState>>,
+    Path(address): Path<String>,
+) -> ApiResult<Json<AddressMeta>> {
+    parse_address(&address)?;
+    let address = normalize_address(&address);
+
+    let row: AddressMetaRow = sqlx::query_as(ADDRESS_META_SQL)
+        .bind(&address)
+        .fetch_one(&state.pool)
+        .await?;
+    Ok(Json(row.into()))
+}
+
+/// Transactions from or to `$1` up to block `$2`, newest first
+fn address_transactions_sql() -> String {
+    format!(
+        "SELECT hash, block_number, block_index, from_address, to_address, value, gas_price, gas_used, input_data, status, contract_created, timestamp
+         FROM transactions
+         WHERE (from_address = $1 OR to_address = $1) AND block_number <= $2
+         ORDER BY {}",
+        ChainRow::Transaction.order_by("", Direction::Desc)
+    )
+}
+
+/// GET /api/addresses/{address}/transactions - A page of the address's
+/// transactions, or all of them up to the anchor as NDJSON (see [`ndjson`])
 pub async fn get_address_transactions(
     State(state): State<Arc<AppState>>,
     Path(address): Path<String>,
     Query(pagination): Query<Pagination>,
-) -> ApiResult<Json<PaginatedResponse<Transaction>>> {
+    Query(options): Query<DisplayQuery>,
+    headers: HeaderMap,
+) -> ApiResult<Response> {
     let address = normalize_address(&address);
+    let anchor = snapshot_anchor(&state, pagination.as_of_block).await?;
+
+    if ndjson::requested(&headers) {
+        let mut tx = ndjson::begin(&state.pool).await?;
+        sqlx::query(&ndjson::declare(&address_transactions_sql()))
+            .bind(&address)
+            .bind(anchor_bound(anchor))
+            .execute(&mut *tx)
+            .await?;
+        return Ok(ndjson::stream::<Transaction>(tx));
+    }
+
+    let bound = anchor_bound(anchor).to_string();
 
-    let total: (i64,) = sqlx::query_as(
-        "SELECT COUNT(*) FROM transactions WHERE from_address = $1 OR to_address = $1",
+    let total = count_rows(
+        &state.pool,
+        pagination.count_mode(ADDRESS_HISTORY_COUNT_MODE),
+        "SELECT 1 FROM transactions
+         WHERE (from_address = $1 OR to_address = $1) AND block_number <= $2::bigint",
+        &[&address, &bound],
     )
-    .bind(&address)
-    .fetch_one(&state.pool)
     .await?;
 
-    let transactions: Vec<Transaction> = sqlx::query_as(
-        "SELECT hash, block_number, block_index, from_address, to_address, value, gas_price, gas_used, input_data, status, contract_created, timestamp
-         FROM transactions
-         WHERE from_address = $1 OR to_address = $1
-         ORDER BY block_number DESC, block_index DESC
-         LIMIT $2 OFFSET $3"
-    )
+    let transactions: Vec<Transaction> = sqlx::query_as(&format!(
+        "{} LIMIT $3 OFFSET $4",
+        address_transactions_sql()
+    ))
     .bind(&address)
-    .bind(pagination.limit())
+    .bind(anchor_bound(anchor))
+    .bind(pagination.limit() + 1)
     .bind(pagination.offset())
     .fetch_all(&state.pool)
     .await?;
 
-    Ok(Json(PaginatedResponse::new(
-        transactions,
-        pagination.page,
-        pagination.limit,
-        total.0,
-    )))
+    Ok(Json(
+        PaginatedResponse::from_probe(
+            decorate_transactions(&state, transactions, &options),
+            pagination.page,
+            pagination.limit() as u32,
+            pagination.offset(),
+            total.total,
+            total.is_estimate,
+        )
+        .anchored_at(anchor),
+    )
+    .into_response())
 }
 
 pub async fn get_address_nfts(
@@ -402,7 +675,7 @@ pub async fn get_address_nfts(
                 metadata, image_url, name, last_transfer_block
          FROM nft_tokens
          WHERE owner = $1
-         ORDER BY last_transfer_block DESC
+         ORDER BY last_transfer_block DESC, contract_address, token_id
          LIMIT $2 OFFSET $3"
     )
     .bind(&address)
@@ -419,8 +692,387 @@ pub async fn get_address_nfts(
     )))
 }
 
-/// Unified transfer type combining ERC-20 and NFT transfers
+/// Contract created by an address, with its verification status
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct DeployedContract {
+    pub address: String,
+    pub block_number: i64,
+    pub tx_hash: String,
+    pub verified: bool,
+    /// Contract name from verified source
+    pub contract_name: Option<String>,
+    pub verified_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// GET /api/addresses/{address}/deployed-contracts - Contracts this address
+/// created with a deployment transaction, newest first. Contracts created
+/// internally by a factory are not listed: that needs call traces.
+pub async fn get_address_deployed_contracts(
+    State(state): State<Arc<AppState>>,
+    Path(address): Path<String>,
+    Query(pagination): Query<Pagination>,
+) -> ApiResult<Json<PaginatedResponse<DeployedContract>>> {
+    let address = normalize_address(&address);
+
+    let total: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM addresses WHERE deployer = $1")
+        .bind(&address)
+        .fetch_one(&state.pool)
+        .await?;
+
+    let contracts: Vec<DeployedContract> = sqlx::query_as(
+        "SELECT a.address, a.creation_block AS block_number, a.creation_tx_hash AS tx_hash,
+                c.address IS NOT NULL AS verified, c.contract_name, c.verified_at
+         FROM addresses a
+         LEFT JOIN contract_abis c ON c.address = a.address
+         WHERE a.deployer = $1
+         ORDER BY a.creation_block DESC, a.address
+         LIMIT $2 OFFSET $3",
+    )
+    .bind(&address)
+    .bind(pagination.limit())
+    .bind(pagination.offset())
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(Json(PaginatedResponse::new(
+        contracts,
+        pagination.page,
+        pagination.limit,
+        total.0,
+    )))
+}
+
+/// Most nonce gaps returned by [`get_address_nonces`]
+const MAX_NONCE_GAPS: i64 = 100;
+
+/// Nonce sequence of the transactions an address has sent
 #[derive(Debug, Clone, Serialize)]
+pub struct AddressNonces {
+    pub address: String,
+    /// Sent transactions with a stored nonce
+    pub nonce_count: i64,
+    /// Sent transactions indexed before nonces were stored
+    pub unknown_nonce_count: i64,
+    pub lowest_nonce: Option<i64>,
+    pub highest_nonce: Option<i64>,
+    /// Nonce the address's next transaction has to use
+    pub next_nonce: Option<i64>,
+    /// Missing nonces between `lowest_nonce` and `highest_nonce`, at most 100
+    pub gaps: Vec<NonceGap>,
+    /// Sent transactions by nonce, highest first
+    pub transactions: PaginatedResponse<NonceTransaction>,
+}
+
+/// Inclusive range of nonces with no indexed transaction
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct NonceGap {
+    pub from: i64,
+    pub to: i64,
+}
+
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct NonceTransaction {
+    pub nonce: i64,
+    pub hash: String,
+    pub block_number: i64,
+    pub status: bool,
+    pub timestamp: i64,
+}
+
+/// GET /api/addresses/{address}/nonces - Nonce sequence of the address's
+/// sent transactions and the gaps in it. Included nonces are always
+/// contiguous on chain, so a gap means blocks that are not indexed yet
+/// (see `/api/status`), not a stuck transaction; a transaction that is not
+/// confirming usually uses a nonce other than `next_nonce`.
+pub async fn get_address_nonces(
+    State(state): State<Arc<AppState>>,
+    Path(address): Path<String>,
+    Query(pagination): Query<Pagination>,
+) -> ApiResult<Json<AddressNonces>> {
+    let address = normalize_address(&address);
+
+    let (nonce_count, unknown_nonce_count, lowest_nonce, highest_nonce): (
+        i64,
+        i64,
+        Option<i64>,
+        Option<i64>,
+    ) = sqlx::query_as(
+        "SELECT COUNT(nonce), COUNT(*) - COUNT(nonce), MIN(nonce), MAX(nonce)
+         FROM transactions
+         WHERE from_address = $1",
+    )
+    .bind(&address)
+    .fetch_one(&state.pool)
+    .await?;
+
+    let gaps: Vec<NonceGap> = sqlx::query_as(
+        "SELECT nonce + 1 AS \"from\", next_nonce - 1 AS \"to\"
+         FROM (
+             SELECT nonce, LEAD(nonce) OVER (ORDER BY nonce) AS next_nonce
+             FROM transactions
+             WHERE from_address = $1 AND nonce IS NOT NULL
+         ) s
+         WHERE next_nonce > nonce + 1
+         ORDER BY nonce
+         LIMIT $2",
+    )
+    .bind(&address)
+    .bind(MAX_NONCE_GAPS)
+    .fetch_all(&state.pool)
+    .await?;
+
+    let transactions: Vec<NonceTransaction> = sqlx::query_as(
+        "SELECT nonce, hash, block_number, status, timestamp
+         FROM transactions
+         WHERE from_address = $1 AND nonce IS NOT NULL
+         ORDER BY nonce DESC, block_number DESC, hash
+         LIMIT $2 OFFSET $3",
+    )
+    .bind(&address)
+    .bind(pagination.limit())
+    .bind(pagination.offset())
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(Json(AddressNonces {
+        address,
+        nonce_count,
+        unknown_nonce_count,
+        lowest_nonce,
+        highest_nonce,
+        next_nonce: highest_nonce.map(|nonce| nonce + 1),
+        gaps,
+        transactions: PaginatedResponse::new(
+            transactions,
+            pagination.page,
+            pagination.limit,
+            nonce_count,
+        ),
+    }))
+}
+
+/// Most tokens returned by [`get_address_token_flows`]
+const MAX_TOKEN_FLOWS: usize = 100;
+
+/// Lookback for token flows, anchored to the latest indexed block timestamp
+#[derive(Debug, Deserialize, Default, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum FlowPeriod {
+    #[serde(rename = "24h")]
+    Day,
+    #[serde(rename = "7d")]
+    Week,
+    #[default]
+    #[serde(rename = "30d")]
+    Month,
+    #[serde(rename = "90d")]
+    Quarter,
+    #[serde(rename = "1y")]
+    Year,
+    #[serde(rename = "all")]
+    All,
+}
+
+impl FlowPeriod {
+    /// Seconds covered, `None` for the whole history
+    pub fn duration_secs(self) -> Option<i64> {
+        match self {
+            FlowPeriod::Day => Some(86_400),
+            FlowPeriod::Week => Some(7 * 86_400),
+            FlowPeriod::Month => Some(30 * 86_400),
+            FlowPeriod::Quarter => Some(90 * 86_400),
+            FlowPeriod::Year => Some(365 * 86_400),
+            FlowPeriod::All => None,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TokenFlowQuery {
+    #[serde(default)]
+    pub period: FlowPeriod,
+}
+
+/// ERC-20 volume in and out of an address over a period, per token
+#[derive(Debug, Clone, Serialize)]
+pub struct AddressTokenFlows {
+    pub address: String,
+    pub period: FlowPeriod,
+    /// First block timestamp counted; `null` for `all` or before any block is indexed
+    pub from_timestamp: Option<i64>,
+    /// Latest indexed block timestamp the period ends at
+    pub to_timestamp: Option<i64>,
+    /// Tokens by the larger of inbound and outbound volume, at most 100
+    pub tokens: Vec<TokenFlow>,
+}
+
+/// Raw token amounts as decimal strings; `net` is inbound minus outbound and
+/// negative when more left the address than arrived.
+#[derive(Debug, Clone, Serialize)]
+pub struct TokenFlow {
+    pub contract_address: String,
+    pub name: Option<String>,
+    pub symbol: Option<String>,
+    pub decimals: Option<i16>,
+    pub inbound: String,
+    pub outbound: String,
+    pub net: String,
+    pub inbound_transfers: i64,
+    pub outbound_transfers: i64,
+}
+
+/// GET /api/addresses/{address}/token-flows?period=30d - Inbound, outbound
+/// and net ERC-20 volume per token. A transfer to itself counts both ways.
+/// Transfers aggregated by the retention job count from the start of their
+/// UTC day.
+pub async fn get_address_token_flows(
+    State(state): State<Arc<AppState>>,
+    Path(address): Path<String>,
+    Query(query): Query<TokenFlowQuery>,
+) -> ApiResult<Json<AddressTokenFlows>> {
+    let address = normalize_address(&address);
+
+    let (to_timestamp,): (Option<i64>,) = sqlx::query_as("SELECT MAX(timestamp) FROM blocks")
+        .fetch_one(&state.pool)
+        .await?;
+    let from_timestamp = to_timestamp
+        .zip(query.period.duration_secs())
+        .map(|(latest, secs)| latest - secs);
+
+    // Sums are taken in every tier and over the daily summaries, then
+    // merged here; token details come from the hot tables.
+    let mut sums: HashMap<String, FlowSums> = HashMap::new();
+    let segments = state
+        .storage
+        .segments(TieredTable::Erc20Transfers, BlockRange::up_to(i64::MAX));
+    for segment in &segments {
+        let rows: Vec<FlowSums> = sqlx::query_as(&token_flow_sums_sql(&format!(
+            "SELECT contract_address, value AS inbound, 0::numeric AS outbound,
+                    1 AS inbound_transfers, 0 AS outbound_transfers
+             FROM {table}
+             WHERE to_address = $1 AND ($2::bigint IS NULL OR timestamp >= $2)
+               AND block_number BETWEEN $3 AND $4
+             UNION ALL
+             SELECT contract_address, 0::numeric, value, 0, 1
+             FROM {table}
+             WHERE from_address = $1 AND ($2::bigint IS NULL OR timestamp >= $2)
+               AND block_number BETWEEN $3 AND $4",
+            table = segment.table
+        )))
+        .bind(&address)
+        .bind(from_timestamp)
+        .bind(segment.blocks.from)
+        .bind(segment.blocks.to)
+        .fetch_all(&segment.pool)
+        .await?;
+        add_flow_sums(&mut sums, rows);
+    }
+    let rows: Vec<FlowSums> = sqlx::query_as(&token_flow_sums_sql(
+        "SELECT contract_address, value AS inbound, 0::numeric AS outbound,
+                transfer_count AS inbound_transfers, 0 AS outbound_transfers
+         FROM erc20_transfer_summaries
+         WHERE to_address = $1 AND ($2::bigint IS NULL OR day >= DATE '1970-01-01' + ($2 / 86400)::int)
+         UNION ALL
+         SELECT contract_address, 0::numeric, value, 0, transfer_count
+         FROM erc20_transfer_summaries
+         WHERE from_address = $1 AND ($2::bigint IS NULL OR day >= DATE '1970-01-01' + ($2 / 86400)::int)",
+    ))
+    .bind(&address)
+    .bind(from_timestamp)
+    .fetch_all(&state.pool)
+    .await?;
+    add_flow_sums(&mut sums, rows);
+
+    let mut sums: Vec<FlowSums> = sums.into_values().collect();
+    sums.sort_by(|a, b| {
+        (&b.inbound)
+            .max(&b.outbound)
+            .cmp((&a.inbound).max(&a.outbound))
+            .then_with(|| a.contract_address.cmp(&b.contract_address))
+    });
+    sums.truncate(MAX_TOKEN_FLOWS);
+
+    let contracts: Vec<String> = sums.iter().map(|s| s.contract_address.clone()).collect();
+    let mut details: HashMap<String, (Option<String>, Option<String>, i16)> = sqlx::query_as(
+        "SELECT address, name, symbol, decimals FROM erc20_contracts WHERE address = ANY($1)",
+    )
+    .bind(&contracts)
+    .fetch_all(&state.pool)
+    .await?
+    .into_iter()
+    .map(|(address, name, symbol, decimals): (String, _, _, _)| (address, (name, symbol, decimals)))
+    .collect();
+    let tokens = sums
+        .into_iter()
+        .map(|sums| {
+            let (name, symbol, decimals) = match details.remove(&sums.contract_address) {
+                Some((name, symbol, decimals)) => (name, symbol, Some(decimals)),
+                None => (None, None, None),
+            };
+            TokenFlow {
+                net: (&sums.inbound - &sums.outbound).to_plain_string(),
+                inbound: sums.inbound.to_plain_string(),
+                outbound: sums.outbound.to_plain_string(),
+                contract_address: sums.contract_address,
+                name,
+                symbol,
+                decimals,
+                inbound_transfers: sums.inbound_transfers,
+                outbound_transfers: sums.outbound_transfers,
+            }
+        })
+        .collect();
+
+    Ok(Json(AddressTokenFlows {
+        address,
+        period: query.period,
+        from_timestamp,
+        to_timestamp,
+        tokens,
+    }))
+}
+
+/// Per-token totals of one source of [`get_address_token_flows`].
+#[derive(Debug, sqlx::FromRow)]
+struct FlowSums {
+    contract_address: String,
+    inbound: BigDecimal,
+    outbound: BigDecimal,
+    inbound_transfers: i64,
+    outbound_transfers: i64,
+}
+
+/// [`FlowSums`] per token of `flows`, rows of `contract_address`, `inbound`,
+/// `outbound`, `inbound_transfers` and `outbound_transfers`.
+fn token_flow_sums_sql(flows: &str) -> String {
+    format!(
+        "SELECT f.contract_address,
+                SUM(f.inbound) AS inbound,
+                SUM(f.outbound) AS outbound,
+                SUM(f.inbound_transfers)::bigint AS inbound_transfers,
+                SUM(f.outbound_transfers)::bigint AS outbound_transfers
+         FROM ({flows}) f
+         GROUP BY f.contract_address"
+    )
+}
+
+fn add_flow_sums(sums: &mut HashMap<String, FlowSums>, rows: Vec<FlowSums>) {
+    for row in rows {
+        match sums.get_mut(&row.contract_address) {
+            Some(total) => {
+                total.inbound += row.inbound;
+                total.outbound += row.outbound;
+                total.inbound_transfers += row.inbound_transfers;
+                total.outbound_transfers += row.outbound_transfers;
+            }
+            None => {
+                sums.insert(row.contract_address.clone(), row);
+            }
+        }
+    }
+}
+
+/// Unified transfer type combining ERC-20 and NFT transfers
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
 pub struct Transfer {
     pub tx_hash: String,
     pub log_index: i32,
@@ -450,171 +1102,343 @@ pub struct TransferFilters {
     /// Filter by transfer type: "erc20", "nft", or both if not specified
     #[serde(default)]
     pub transfer_type: Option<String>,
+    #[serde(default)]
+    pub count: Option<CountMode>,
+    /// See [`Pagination::as_of_block`].
+    #[serde(default)]
+    pub as_of_block: Option<i64>,
 }
 
+/// GET /api/addresses/{address}/transfers - A page of ERC-20 and/or NFT
+/// transfers, or all of them up to the anchor as NDJSON (see [`ndjson`]),
+/// without display values
 pub async fn get_address_transfers(
     State(state): State<Arc<AppState>>,
     Path(address): Path<String>,
     Query(filters): Query<TransferFilters>,
-) -> ApiResult<Json<PaginatedResponse<Transfer>>> {
+    Query(options): Query<DisplayQuery>,
+    headers: HeaderMap,
+) -> ApiResult<Response> {
     let address = normalize_address(&address);
     let page = filters.page;
     let limit = filters.limit.min(100);
     let offset = (page.saturating_sub(1) * limit) as i64;
+    let anchor = snapshot_anchor(&state, filters.as_of_block).await?;
+
+    let kinds = TransferKind::requested(filters.transfer_type.as_deref());
 
-    // Build query based on filter
-    let (count_query, data_query) = match filters.transfer_type.as_deref() {
-        Some("erc20") => {
-            let count = r#"
-                SELECT COUNT(*) FROM erc20_transfers
-                WHERE from_address = $1 OR to_address = $1
-            "#;
-            let data = r#"
-                SELECT
-                    t.tx_hash,
-                    t.log_index,
-                    t.contract_address,
-                    t.from_address,
-                    t.to_address,
-                    t.value::text as value,
-                    t.block_number,
-                    t.timestamp,
-                    'erc20' as transfer_type,
-                    c.name as token_name,
-                    c.symbol as token_symbol
-                FROM erc20_transfers t
-                LEFT JOIN erc20_contracts c ON t.contract_address = c.address
-                WHERE t.from_address = $1 OR t.to_address = $1
-                ORDER BY t.block_number DESC, t.log_index DESC
-                LIMIT $2 OFFSET $3
-            "#;
-            (count.to_string(), data.to_string())
+    if ndjson::requested(&headers) {
+        // One cursor can only read from one database.
+        if kinds.contains(&TransferKind::Erc20) {
+            require_hot_tier(
+                &state,
+                TieredTable::Erc20Transfers,
+                BlockRange::up_to(anchor_bound(anchor)),
+                "NDJSON ERC-20 transfer listings",
+            )?;
         }
-        Some("nft") => {
-            let count = r#"
-                SELECT COUNT(*) FROM nft_transfers
-                WHERE from_address = $1 OR to_address = $1
-            "#;
-            let data = r#"
-                SELECT
-                    t.tx_hash,
-                    t.log_index,
-                    t.contract_address,
-                    t.from_address,
-                    t.to_address,
-                    t.token_id::text as value,
-                    t.block_number,
-                    t.timestamp,
-                    'nft' as transfer_type,
-                    c.name as token_name,
-                    c.symbol as token_symbol
-                FROM nft_transfers t
-                LEFT JOIN nft_contracts c ON t.contract_address = c.address
-                WHERE t.from_address = $1 OR t.to_address = $1
-                ORDER BY t.block_number DESC, t.log_index DESC
-                LIMIT $2 OFFSET $3
-            "#;
-            (count.to_string(), data.to_string())
+        let selects: Vec<String> = kinds
+            .iter()
+            .map(|kind| kind.select_sql(kind.hot_table(), true))
+            .collect();
+        let mut tx = ndjson::begin(&state.pool).await?;
+        // LIMIT NULL is LIMIT ALL
+        sqlx::query(&ndjson::declare(&transfers_page_sql(&selects)))
+            .bind(&address)
+            .bind(0i64)
+            .bind(anchor_bound(anchor))
+            .bind(None::<i64>)
+            .bind(0i64)
+            .execute(&mut *tx)
+            .await?;
+        return Ok(ndjson::stream::<Transfer>(tx));
+    }
+
+    // NFT transfers are all in the hot tables, so only ERC-20 transfers
+    // split the listing into segments.
+    let segments = if kinds.contains(&TransferKind::Erc20) {
+        segments_newest_first(&state, TieredTable::Erc20Transfers, anchor_bound(anchor))
+    } else {
+        vec![Segment {
+            tier: Tier::Hot,
+            pool: state.pool.clone(),
+            table: TransferKind::Nft.hot_table().to_string(),
+            blocks: BlockRange::up_to(anchor_bound(anchor)),
+        }]
+    };
+
+    let mode = filters.count.unwrap_or(ADDRESS_HISTORY_COUNT_MODE);
+    let mut total = PageTotal {
+        total: (mode != CountMode::None).then_some(0),
+        is_estimate: false,
+    };
+    for segment in &segments {
+        let (from, to) = (
+            segment.blocks.from.to_string(),
+            segment.blocks.to.to_string(),
+        );
+        for part in TransferPart::of(&state, segment, kinds) {
+            let count = count_rows(
+                &part.pool,
+                mode,
+                &part.kind.count_sql(&part.table),
+                &[&address, &from, &to],
+            )
+            .await?;
+            total.total = total.total.zip(count.total).map(|(a, b)| a + b);
+            total.is_estimate |= count.is_estimate;
         }
-        _ => {
-            // Both types - use UNION ALL
-            let count = r#"
-                SELECT (
-                    SELECT COUNT(*) FROM erc20_transfers WHERE from_address = $1 OR to_address = $1
-                ) + (
-                    SELECT COUNT(*) FROM nft_transfers WHERE from_address = $1 OR to_address = $1
+    }
+
+    let fetch = |segment: Segment, limit: i64, offset: i64| {
+        let (state, address) = (&state, &address);
+        async move {
+            let parts = TransferPart::of(state, &segment, kinds);
+            if parts.len() == 1 || parts.iter().all(|part| part.hot) {
+                return fetch_transfers(&parts, &segment, address, limit, offset).await;
+            }
+            // Parts in different databases: take enough of each to cover the
+            // page and merge them in the listing's order.
+            let mut rows = Vec::new();
+            for part in &parts {
+                rows.extend(
+                    fetch_transfers(
+                        std::slice::from_ref(part),
+                        &segment,
+                        address,
+                        offset + limit,
+                        0,
+                    )
+                    .await?,
+                );
+            }
+            rows.sort_by(|a, b| {
+                (b.block_number, b.log_index, &b.tx_hash)
+                    .cmp(&(a.block_number, a.log_index, &a.tx_hash))
+                    .then_with(|| a.transfer_type.cmp(&b.transfer_type))
+            });
+            Ok(rows
+                .into_iter()
+                .skip(offset as usize)
+                .take(limit as usize)
+                .collect())
+        }
+    };
+    let count = |segment: Segment| {
+        let (state, address) = (&state, &address);
+        async move {
+            let mut total = 0;
+            for part in TransferPart::of(state, &segment, kinds) {
+                let sql = format!(
+                    "SELECT COUNT(*) FROM ({}) counted",
+                    part.kind.count_sql(&part.table)
+                );
+                total += fetch_count(
+                    &part.pool,
+                    sqlx::query_as(&sql)
+                        .bind(address)
+                        .bind(segment.blocks.from.to_string())
+                        .bind(segment.blocks.to.to_string()),
                 )
-            "#;
-            let data = r#"
-                SELECT * FROM (
-                    SELECT
-                        t.tx_hash,
-                        t.log_index,
-                        t.contract_address,
-                        t.from_address,
-                        t.to_address,
-                        t.value::text as value,
-                        t.block_number,
-                        t.timestamp,
-                        'erc20' as transfer_type,
-                        c.name as token_name,
-                        c.symbol as token_symbol
-                    FROM erc20_transfers t
-                    LEFT JOIN erc20_contracts c ON t.contract_address = c.address
-                    WHERE t.from_address = $1 OR t.to_address = $1
-
-                    UNION ALL
-
-                    SELECT
-                        t.tx_hash,
-                        t.log_index,
-                        t.contract_address,
-                        t.from_address,
-                        t.to_address,
-                        t.token_id::text as value,
-                        t.block_number,
-                        t.timestamp,
-                        'nft' as transfer_type,
-                        c.name as token_name,
-                        c.symbol as token_symbol
-                    FROM nft_transfers t
-                    LEFT JOIN nft_contracts c ON t.contract_address = c.address
-                    WHERE t.from_address = $1 OR t.to_address = $1
-                ) combined
-                ORDER BY block_number DESC, log_index DESC
-                LIMIT $2 OFFSET $3
-            "#;
-            (count.to_string(), data.to_string())
+                .await?;
+            }
+            Ok(total)
         }
     };
+    let mut transfers: Vec<Transfer> =
+        stitch_segments(&segments, offset, limit as i64 + 1, fetch, count).await?;
+    fill_token_names(&state.pool, &mut transfers).await?;
 
-    let total: (i64,) = sqlx::query_as(&count_query)
-        .bind(&address)
-        .fetch_one(&state.pool)
-        .await?;
+    let decimals = if options.enabled() {
+        let contracts: Vec<String> = transfers
+            .iter()
+            .filter(|t| t.transfer_type == "erc20")
+            .map(|t| t.contract_address.clone())
+            .collect();
+        token_decimals(&state.pool, &contracts).await?
+    } else {
+        Default::default()
+    };
+    // NFT values are token ids, so they only get an age.
+    let transfers = decorate(transfers, options.enabled(), |t| Display {
+        value: (t.transfer_type == "erc20")
+            .then(|| BigDecimal::from_str(&t.value).ok())
+            .flatten()
+            .zip(decimals.get(&t.contract_address))
+            .map(|(value, decimals)| display::format_units(&value, *decimals)),
+        fee: None,
+        symbol: None,
+        age_seconds: Some(display::age_seconds(t.timestamp)),
+    });
+
+    Ok(Json(
+        PaginatedResponse::from_probe(
+            transfers,
+            page,
+            limit,
+            offset,
+            total.total,
+            total.is_estimate,
+        )
+        .anchored_at(anchor),
+    )
+    .into_response())
+}
 
-    #[derive(sqlx::FromRow)]
-    struct TransferRow {
-        tx_hash: String,
-        log_index: i32,
-        contract_address: String,
-        from_address: String,
-        to_address: String,
-        value: String,
-        block_number: i64,
-        timestamp: i64,
-        transfer_type: String,
-        token_name: Option<String>,
-        token_symbol: Option<String>,
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TransferKind {
+    Erc20,
+    Nft,
+}
+
+impl TransferKind {
+    /// Kinds listed for a `transfer_type` filter: both when it is unset.
+    fn requested(transfer_type: Option<&str>) -> &'static [TransferKind] {
+        match transfer_type {
+            Some("erc20") => &[TransferKind::Erc20],
+            Some("nft") => &[TransferKind::Nft],
+            _ => &[TransferKind::Erc20, TransferKind::Nft],
+        }
     }
 
-    let rows: Vec<TransferRow> = sqlx::query_as(&data_query)
-        .bind(&address)
-        .bind(limit as i64)
-        .bind(offset)
-        .fetch_all(&state.pool)
-        .await?;
+    fn hot_table(self) -> &'static str {
+        match self {
+            TransferKind::Erc20 => "erc20_transfers",
+            TransferKind::Nft => "nft_transfers",
+        }
+    }
 
-    let transfers: Vec<Transfer> = rows
-        .into_iter()
-        .map(|r| Transfer {
-            tx_hash: r.tx_hash,
-            log_index: r.log_index,
-            contract_address: r.contract_address,
-            from_address: r.from_address,
-            to_address: r.to_address,
-            value: r.value,
-            block_number: r.block_number,
-            timestamp: r.timestamp,
-            transfer_type: r.transfer_type,
-            token_name: r.token_name,
-            token_symbol: r.token_symbol,
-        })
+    /// [`Transfer`] rows of `table` for address `$1` in blocks `$2..=$3`.
+    /// Token names need the contracts table, which only the hot tier has.
+    fn select_sql(self, table: &str, join_contracts: bool) -> String {
+        let (value, transfer_type, contracts) = match self {
+            TransferKind::Erc20 => ("t.value", "erc20", "erc20_contracts"),
+            TransferKind::Nft => ("t.token_id", "nft", "nft_contracts"),
+        };
+        let (names, join) = if join_contracts {
+            (
+                "c.name as token_name, c.symbol as token_symbol",
+                format!("LEFT JOIN {contracts} c ON t.contract_address = c.address"),
+            )
+        } else {
+            (
+                "NULL::text as token_name, NULL::text as token_symbol",
+                String::new(),
+            )
+        };
+        format!(
+            "SELECT t.tx_hash, t.log_index, t.contract_address, t.from_address, t.to_address,
+                    {value}::text as value, t.block_number, t.timestamp,
+                    '{transfer_type}' as transfer_type, {names}
+             FROM {table} t
+             {join}
+             WHERE (t.from_address = $1 OR t.to_address = $1)
+               AND t.block_number BETWEEN $2::bigint AND $3::bigint"
+        )
+    }
+
+    /// Filter for [`count_rows`] over `table`, bound like [`Self::select_sql`].
+    fn count_sql(self, table: &str) -> String {
+        format!(
+            "SELECT 1 FROM {table}
+             WHERE (from_address = $1 OR to_address = $1)
+               AND block_number BETWEEN $2::bigint AND $3::bigint"
+        )
+    }
+}
+
+/// Where one kind of transfer in a segment is read from.
+struct TransferPart {
+    kind: TransferKind,
+    pool: PgPool,
+    table: String,
+    hot: bool,
+}
+
+impl TransferPart {
+    fn of(state: &AppState, segment: &Segment, kinds: &[TransferKind]) -> Vec<TransferPart> {
+        kinds
+            .iter()
+            .map(|&kind| match kind {
+                TransferKind::Erc20 => TransferPart {
+                    kind,
+                    pool: segment.pool.clone(),
+                    table: segment.table.clone(),
+                    hot: segment.tier == Tier::Hot,
+                },
+                TransferKind::Nft => TransferPart {
+                    kind,
+                    pool: state.pool.clone(),
+                    table: kind.hot_table().to_string(),
+                    hot: true,
+                },
+            })
+            .collect()
+    }
+}
+
+/// Newest-first listing over `selects`, bound like
+/// [`TransferKind::select_sql`] then `LIMIT $4 OFFSET $5`.
+fn transfers_page_sql(selects: &[String]) -> String {
+    format!(
+        "SELECT * FROM ({}) combined
+         ORDER BY {}, transfer_type
+         LIMIT $4 OFFSET $5",
+        selects.join(" UNION ALL "),
+        ChainRow::Log.order_by("", Direction::Desc)
+    )
+}
+
+/// A page of `segment`'s transfers from `parts`, which share a database.
+async fn fetch_transfers(
+    parts: &[TransferPart],
+    segment: &Segment,
+    address: &str,
+    limit: i64,
+    offset: i64,
+) -> Result<Vec<Transfer>, sqlx::Error> {
+    let selects: Vec<String> = parts
+        .iter()
+        .map(|part| part.kind.select_sql(&part.table, part.hot))
         .collect();
+    sqlx::query_as(&transfers_page_sql(&selects))
+        .bind(address)
+        .bind(segment.blocks.from)
+        .bind(segment.blocks.to)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&parts[0].pool)
+        .await
+}
 
-    Ok(Json(PaginatedResponse::new(
-        transfers, page, limit, total.0,
-    )))
+/// Fill in the token names of ERC-20 transfers read from the cold tier.
+async fn fill_token_names(pool: &PgPool, transfers: &mut [Transfer]) -> Result<(), sqlx::Error> {
+    let unnamed = |t: &Transfer| {
+        t.transfer_type == "erc20" && t.token_name.is_none() && t.token_symbol.is_none()
+    };
+    let contracts: Vec<String> = transfers
+        .iter()
+        .filter(|t| unnamed(t))
+        .map(|t| t.contract_address.clone())
+        .collect();
+    if contracts.is_empty() {
+        return Ok(());
+    }
+    let names: HashMap<String, (Option<String>, Option<String>)> =
+        sqlx::query_as("SELECT address, name, symbol FROM erc20_contracts WHERE address = ANY($1)")
+            .bind(&contracts)
+            .fetch_all(pool)
+            .await?
+            .into_iter()
+            .map(|(address, name, symbol): (String, _, _)| (address, (name, symbol)))
+            .collect();
+    for transfer in transfers.iter_mut().filter(|t| unnamed(t)) {
+        if let Some((name, symbol)) = names.get(&transfer.contract_address) {
+            transfer.token_name = name.clone();
+            transfer.token_symbol = symbol.clone();
+        }
+    }
+    Ok(())
 }
 
 fn normalize_address(address: &str) -> String {
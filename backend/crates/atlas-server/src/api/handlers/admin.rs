@@ -0,0 +1,269 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use crate::api::auth::require_admin;
+use crate::api::error::ApiResult;
+use crate::api::latency::{EndpointLatency, SlowRequest};
+use crate::api::AppState;
+use crate::db_pools::PoolStats;
+use crate::indexer::contract_reindex::{ContractReindexJob, CONTRACT_REINDEX_JOB_COLUMNS};
+use crate::indexer::data_quality::ISSUE_KINDS;
+use crate::labels::normalize_label_address;
+use crate::runtime_settings::{RuntimeUpdate, RuntimeValues};
+use crate::state_keys::DATA_QUALITY_LAST_BLOCK_KEY;
+use atlas_common::{AtlasError, DataQualityIssue, PaginatedResponse, Pagination};
+
+#[derive(Serialize)]
+pub struct DbStats {
+    pub pools: Vec<PoolStats>,
+}
+
+/// GET /api/admin/db-stats — connection pool usage and settings (admin)
+pub async fn get_db_stats(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+) -> ApiResult<Json<DbStats>> {
+    require_admin(&state, &headers)?;
+    Ok(Json(DbStats {
+        pools: state.db_pools.stats(),
+    }))
+}
+
+/// Most endpoints `/api/admin/slow-queries` lists.
+const MAX_SLOW_ENDPOINTS: usize = 100;
+
+#[derive(Debug, Deserialize)]
+pub struct SlowQueriesQuery {
+    /// Endpoints to list, slowest p95 first; default 20
+    pub limit: Option<usize>,
+}
+
+#[derive(Serialize)]
+pub struct SlowQueries {
+    /// Slow request threshold; 0 when slow request logging is off
+    pub threshold_ms: u64,
+    pub endpoints: Vec<EndpointLatency>,
+    pub slow_requests: Vec<SlowRequest>,
+}
+
+/// GET /api/admin/slow-queries — slowest endpoints and recent slow requests (admin)
+pub async fn get_slow_queries(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Query(query): Query<SlowQueriesQuery>,
+) -> ApiResult<Json<SlowQueries>> {
+    require_admin(&state, &headers)?;
+    let limit = query.limit.unwrap_or(20).clamp(1, MAX_SLOW_ENDPOINTS);
+    Ok(Json(SlowQueries {
+        threshold_ms: u64::try_from(state.latency.slow_threshold().as_millis()).unwrap_or(u64::MAX),
+        endpoints: state.latency.endpoints(limit),
+        slow_requests: state.latency.slow_requests(),
+    }))
+}
+
+/// GET /api/admin/runtime-settings — settings that change without a restart (admin)
+pub async fn get_runtime_settings(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+) -> ApiResult<Json<RuntimeValues>> {
+    require_admin(&state, &headers)?;
+    Ok(Json(state.runtime_settings.current()))
+}
+
+/// PATCH /api/admin/runtime-settings — change runtime settings until the next restart (admin)
+pub async fn update_runtime_settings(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Json(update): Json<RuntimeUpdate>,
+) -> ApiResult<Json<RuntimeValues>> {
+    require_admin(&state, &headers)?;
+    let values = state
+        .runtime_settings
+        .update(update)
+        .map_err(|e| AtlasError::InvalidInput(format!("{e:#}")))?;
+    Ok(Json(values))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DataQualityQuery {
+    pub kind: Option<String>,
+    /// `open` (default), `resolved` or `all`
+    pub status: Option<String>,
+}
+
+impl DataQualityQuery {
+    /// SQL condition on `resolved_at` for the requested status.
+    fn status_filter(&self) -> Result<&'static str, AtlasError> {
+        match self.status.as_deref().unwrap_or("open") {
+            "open" => Ok("resolved_at IS NULL"),
+            "resolved" => Ok("resolved_at IS NOT NULL"),
+            "all" => Ok("TRUE"),
+            other => Err(AtlasError::InvalidInput(format!(
+                "status must be open, resolved or all, got {other:?}"
+            ))),
+        }
+    }
+
+    fn kind(&self) -> Result<Option<&str>, AtlasError> {
+        match self.kind.as_deref() {
+            Some(kind) if !ISSUE_KINDS.contains(&kind) => Err(AtlasError::InvalidInput(format!(
+                "kind must be one of {}",
+                ISSUE_KINDS.join(", ")
+            ))),
+            kind => Ok(kind),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct DataQualityReport {
+    /// Last block the per-block checks have covered; `null` before the first cycle
+    pub checked_through_block: Option<i64>,
+    /// Unresolved issues per kind, every kind included
+    pub open: BTreeMap<String, i64>,
+    pub issues: PaginatedResponse<DataQualityIssue>,
+}
+
+/// GET /api/admin/data-quality — findings of the data-quality checks (admin)
+pub async fn get_data_quality(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Query(query): Query<DataQualityQuery>,
+    Query(pagination): Query<Pagination>,
+) -> ApiResult<Json<DataQualityReport>> {
+    require_admin(&state, &headers)?;
+    let status = query.status_filter()?;
+    let kind = query.kind()?;
+
+    let checked_through_block: Option<String> =
+        sqlx::query_scalar("SELECT value FROM indexer_state WHERE key = $1")
+            .bind(DATA_QUALITY_LAST_BLOCK_KEY)
+            .fetch_optional(&state.pool)
+            .await?;
+
+    let mut open: BTreeMap<String, i64> = ISSUE_KINDS
+        .iter()
+        .map(|kind| (kind.to_string(), 0))
+        .collect();
+    let counts: Vec<(String, i64)> = sqlx::query_as(
+        "SELECT kind, COUNT(*) FROM data_quality_issues WHERE resolved_at IS NULL GROUP BY kind",
+    )
+    .fetch_all(&state.pool)
+    .await?;
+    open.extend(counts);
+
+    let (total,): (i64,) = sqlx::query_as(&format!(
+        "SELECT COUNT(*) FROM data_quality_issues
+         WHERE {status} AND ($1::text IS NULL OR kind = $1)"
+    ))
+    .bind(kind)
+    .fetch_one(&state.pool)
+    .await?;
+    let issues: Vec<DataQualityIssue> = sqlx::query_as(&format!(
+        "SELECT id, kind, subject, block_number, address, contract_address, details,
+                first_seen_at, last_seen_at, resolved_at
+         FROM data_quality_issues
+         WHERE {status} AND ($1::text IS NULL OR kind = $1)
+         ORDER BY id DESC
+         LIMIT $2 OFFSET $3"
+    ))
+    .bind(kind)
+    .bind(pagination.limit())
+    .bind(pagination.offset())
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(Json(DataQualityReport {
+        checked_through_block: checked_through_block.and_then(|value| value.parse().ok()),
+        open,
+        issues: PaginatedResponse::new(issues, pagination.page, pagination.limit() as u32, total),
+    }))
+}
+
+/// POST /api/admin/contracts/{address}/reindex — queue a rebuild of one
+/// contract's transfers, balances and supply from its logs (admin). Returns
+/// the contract's open job if one is already queued or running.
+pub async fn reindex_contract(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Path(address): Path<String>,
+) -> ApiResult<(StatusCode, Json<ContractReindexJob>)> {
+    require_admin(&state, &headers)?;
+    let address = normalize_label_address(&address)?;
+
+    let queued: Option<ContractReindexJob> = sqlx::query_as(&format!(
+        "INSERT INTO contract_reindex_jobs (address) VALUES ($1)
+         ON CONFLICT (address) WHERE status IN ('queued', 'running') DO NOTHING
+         RETURNING {CONTRACT_REINDEX_JOB_COLUMNS}"
+    ))
+    .bind(&address)
+    .fetch_optional(&state.pool)
+    .await?;
+    let job = match queued {
+        Some(job) => job,
+        None => latest_reindex_job(&state, &address).await?,
+    };
+    Ok((StatusCode::ACCEPTED, Json(job)))
+}
+
+/// GET /api/admin/contracts/{address}/reindex — the contract's latest reindex job (admin)
+pub async fn get_contract_reindex(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Path(address): Path<String>,
+) -> ApiResult<Json<ContractReindexJob>> {
+    require_admin(&state, &headers)?;
+    let address = normalize_label_address(&address)?;
+    Ok(Json(latest_reindex_job(&state, &address).await?))
+}
+
+async fn latest_reindex_job(
+    state: &AppState,
+    address: &str,
+) -> Result<ContractReindexJob, AtlasError> {
+    sqlx::query_as(&format!(
+        "SELECT {CONTRACT_REINDEX_JOB_COLUMNS} FROM contract_reindex_jobs
+         WHERE address = $1 ORDER BY id DESC LIMIT 1"
+    ))
+    .bind(address)
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or_else(|| AtlasError::NotFound(format!("No reindex job for {address}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn query(kind: Option<&str>, status: Option<&str>) -> DataQualityQuery {
+        DataQualityQuery {
+            kind: kind.map(str::to_string),
+            status: status.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn status_defaults_to_open() {
+        assert_eq!(
+            query(None, None).status_filter().unwrap(),
+            "resolved_at IS NULL"
+        );
+        assert_eq!(query(None, Some("all")).status_filter().unwrap(), "TRUE");
+        assert!(query(None, Some("closed")).status_filter().is_err());
+    }
+
+    #[test]
+    fn kind_must_be_known() {
+        assert_eq!(
+            query(Some("missing_receipt"), None).kind().unwrap(),
+            Some("missing_receipt")
+        );
+        assert!(query(Some("; DROP TABLE"), None).kind().is_err());
+    }
+}
@@ -0,0 +1,423 @@
+//! Alert rules: conditions over indexed transfers and logs
+//!
+//! A rule matches ERC-20 transfers (`kind: "transfer"`) or event logs
+//! (`kind: "log"`) by contract, counterparty, called method and either a
+//! minimum amount (transfers) or event signature (logs). Matches are queued
+//! and POSTed to the rule's webhook by the
+//! [`AlertEngine`](crate::indexer::AlertEngine). Rules belong to a key issued
+//! through `/api/labels/keys`.
+
+use axum::{
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use bigdecimal::BigDecimal;
+use serde::Deserialize;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use crate::api::auth::authorize_api_key;
+use crate::api::error::ApiResult;
+use crate::api::AppState;
+use crate::labels::{normalize_label_address, validate_label_name};
+use atlas_common::{AlertNotification, AlertRule, AtlasError, PaginatedResponse, Pagination};
+
+/// Rules one key may create.
+const MAX_RULES_PER_KEY: i64 = 50;
+const MAX_WEBHOOK_URL_LEN: usize = 2048;
+
+const RULE_COLUMNS: &str = "id, name, kind, contract, event, min_value, counterparty, method, \
+     webhook_url, enabled, last_block, created_at, updated_at";
+
+#[derive(Debug, Deserialize)]
+pub struct AlertRuleRequest {
+    pub name: String,
+    /// `transfer` or `log`
+    pub kind: String,
+    pub contract: Option<String>,
+    /// Event signature hash (topic0); logs only
+    pub event: Option<String>,
+    /// Smallest raw token amount as a decimal string; transfers only
+    pub min_value: Option<String>,
+    pub counterparty: Option<String>,
+    /// 4-byte method selector, e.g. `0xa9059cbb`
+    pub method: Option<String>,
+    pub webhook_url: String,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// Validated [`AlertRuleRequest`].
+struct RuleFields {
+    name: String,
+    kind: String,
+    contract: Option<String>,
+    event: Option<String>,
+    min_value: Option<BigDecimal>,
+    counterparty: Option<String>,
+    method: Option<String>,
+    webhook_url: String,
+    enabled: bool,
+}
+
+/// POST /api/alerts — create a rule for the calling key
+///
+/// The rule fires for blocks indexed after it was created.
+pub async fn create_alert(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Json(request): Json<AlertRuleRequest>,
+) -> ApiResult<(StatusCode, Json<AlertRule>)> {
+    let key_id = authorize_api_key(&state, &headers).await?;
+    let fields = validate_rule(request)?;
+
+    let mut tx = state.pool.begin().await?;
+    // Serialize creates per key so concurrent requests can't overshoot the cap.
+    sqlx::query("SELECT id FROM label_api_keys WHERE id = $1 FOR UPDATE")
+        .bind(key_id)
+        .execute(&mut *tx)
+        .await?;
+    let (rules,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM alert_rules WHERE api_key_id = $1")
+        .bind(key_id)
+        .fetch_one(&mut *tx)
+        .await?;
+    if rules >= MAX_RULES_PER_KEY {
+        return Err(AtlasError::InvalidInput(format!(
+            "An API key can create at most {MAX_RULES_PER_KEY} alert rules"
+        ))
+        .into());
+    }
+
+    let rule: AlertRule = sqlx::query_as(&format!(
+        "INSERT INTO alert_rules (api_key_id, name, kind, contract, event, min_value,
+                                  counterparty, method, webhook_url, enabled, last_block)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10,
+                 COALESCE((SELECT MAX(number) FROM blocks), -1))
+         ON CONFLICT (api_key_id, name) DO NOTHING
+         RETURNING {RULE_COLUMNS}"
+    ))
+    .bind(key_id)
+    .bind(&fields.name)
+    .bind(&fields.kind)
+    .bind(&fields.contract)
+    .bind(&fields.event)
+    .bind(&fields.min_value)
+    .bind(&fields.counterparty)
+    .bind(&fields.method)
+    .bind(&fields.webhook_url)
+    .bind(fields.enabled)
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| duplicate_name(&fields.name))?;
+    tx.commit().await?;
+
+    Ok((StatusCode::CREATED, Json(rule)))
+}
+
+/// GET /api/alerts — the calling key's rules by name
+pub async fn list_alerts(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+) -> ApiResult<Json<Vec<AlertRule>>> {
+    let key_id = authorize_api_key(&state, &headers).await?;
+    let rules: Vec<AlertRule> = sqlx::query_as(&format!(
+        "SELECT {RULE_COLUMNS} FROM alert_rules WHERE api_key_id = $1 ORDER BY name"
+    ))
+    .bind(key_id)
+    .fetch_all(&state.pool)
+    .await?;
+    Ok(Json(rules))
+}
+
+/// GET /api/alerts/{id} — one of the calling key's rules
+pub async fn get_alert(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Path(id): Path<i64>,
+) -> ApiResult<Json<AlertRule>> {
+    let key_id = authorize_api_key(&state, &headers).await?;
+    Ok(Json(fetch_rule(&state, key_id, id).await?))
+}
+
+/// PUT /api/alerts/{id} — replace one of the calling key's rules
+///
+/// The rule keeps its position; already queued notifications are unaffected.
+pub async fn update_alert(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Path(id): Path<i64>,
+    Json(request): Json<AlertRuleRequest>,
+) -> ApiResult<Json<AlertRule>> {
+    let key_id = authorize_api_key(&state, &headers).await?;
+    let fields = validate_rule(request)?;
+
+    let result = sqlx::query_as(&format!(
+        "UPDATE alert_rules
+         SET name = $3, kind = $4, contract = $5, event = $6, min_value = $7,
+             counterparty = $8, method = $9, webhook_url = $10, enabled = $11,
+             updated_at = NOW()
+         WHERE id = $1 AND api_key_id = $2
+         RETURNING {RULE_COLUMNS}"
+    ))
+    .bind(id)
+    .bind(key_id)
+    .bind(&fields.name)
+    .bind(&fields.kind)
+    .bind(&fields.contract)
+    .bind(&fields.event)
+    .bind(&fields.min_value)
+    .bind(&fields.counterparty)
+    .bind(&fields.method)
+    .bind(&fields.webhook_url)
+    .bind(fields.enabled)
+    .fetch_optional(&state.pool)
+    .await;
+    let rule: AlertRule = match result {
+        Ok(rule) => rule.ok_or_else(|| not_found(id))?,
+        Err(sqlx::Error::Database(error)) if error.is_unique_violation() => {
+            return Err(duplicate_name(&fields.name).into())
+        }
+        Err(error) => return Err(error.into()),
+    };
+    Ok(Json(rule))
+}
+
+/// DELETE /api/alerts/{id} — remove a rule and its notifications
+pub async fn delete_alert(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Path(id): Path<i64>,
+) -> ApiResult<StatusCode> {
+    let key_id = authorize_api_key(&state, &headers).await?;
+    let deleted = sqlx::query("DELETE FROM alert_rules WHERE id = $1 AND api_key_id = $2")
+        .bind(id)
+        .bind(key_id)
+        .execute(&state.pool)
+        .await?
+        .rows_affected();
+    if deleted == 0 {
+        return Err(not_found(id).into());
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// GET /api/alerts/{id}/notifications — a rule's matches, newest first
+pub async fn list_alert_notifications(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Path(id): Path<i64>,
+    Query(pagination): Query<Pagination>,
+) -> ApiResult<Json<PaginatedResponse<AlertNotification>>> {
+    let key_id = authorize_api_key(&state, &headers).await?;
+    fetch_rule(&state, key_id, id).await?;
+
+    let (total,): (i64,) =
+        sqlx::query_as("SELECT COUNT(*) FROM alert_notifications WHERE rule_id = $1")
+            .bind(id)
+            .fetch_one(&state.pool)
+            .await?;
+    let notifications: Vec<AlertNotification> = sqlx::query_as(
+        "SELECT id, rule_id, block_number, tx_hash, log_index, payload, status, attempts,
+                last_error, created_at, delivered_at
+         FROM alert_notifications
+         WHERE rule_id = $1
+         ORDER BY id DESC
+         LIMIT $2 OFFSET $3",
+    )
+    .bind(id)
+    .bind(pagination.limit())
+    .bind(pagination.offset())
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(Json(PaginatedResponse::new(
+        notifications,
+        pagination.page,
+        pagination.limit() as u32,
+        total,
+    )))
+}
+
+async fn fetch_rule(state: &AppState, key_id: i64, id: i64) -> Result<AlertRule, AtlasError> {
+    sqlx::query_as(&format!(
+        "SELECT {RULE_COLUMNS} FROM alert_rules WHERE id = $1 AND api_key_id = $2"
+    ))
+    .bind(id)
+    .bind(key_id)
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or_else(|| not_found(id))
+}
+
+fn validate_rule(request: AlertRuleRequest) -> Result<RuleFields, AtlasError> {
+    let name = validate_label_name(&request.name)?;
+    let kind = request.kind.trim().to_ascii_lowercase();
+    if kind != "transfer" && kind != "log" {
+        return Err(AtlasError::InvalidInput(
+            "kind must be 'transfer' or 'log'".to_string(),
+        ));
+    }
+
+    let contract = non_empty(request.contract)
+        .map(|a| normalize_label_address(&a))
+        .transpose()?;
+    let counterparty = non_empty(request.counterparty)
+        .map(|a| normalize_label_address(&a))
+        .transpose()?;
+    if contract.is_none() && counterparty.is_none() {
+        return Err(AtlasError::InvalidInput(
+            "A rule needs a contract or a counterparty".to_string(),
+        ));
+    }
+
+    let event = non_empty(request.event)
+        .map(|e| hex_field(&e, 32, "event"))
+        .transpose()?;
+    if event.is_some() && kind != "log" {
+        return Err(AtlasError::InvalidInput(
+            "event only applies to log rules".to_string(),
+        ));
+    }
+
+    let min_value = non_empty(request.min_value)
+        .map(|v| {
+            if !v.bytes().all(|b| b.is_ascii_digit()) || v.len() > 78 {
+                return Err(AtlasError::InvalidInput(
+                    "min_value must be a non-negative integer".to_string(),
+                ));
+            }
+            BigDecimal::from_str(&v)
+                .map_err(|_| AtlasError::InvalidInput("Invalid min_value".to_string()))
+        })
+        .transpose()?;
+    if min_value.is_some() && kind != "transfer" {
+        return Err(AtlasError::InvalidInput(
+            "min_value only applies to transfer rules".to_string(),
+        ));
+    }
+
+    let method = non_empty(request.method)
+        .map(|m| hex_field(&m, 4, "method"))
+        .transpose()?;
+
+    let webhook_url = request.webhook_url.trim().to_string();
+    let parsed = reqwest::Url::parse(&webhook_url)
+        .map_err(|_| AtlasError::InvalidInput("Invalid webhook_url".to_string()))?;
+    if !matches!(parsed.scheme(), "http" | "https")
+        || parsed.host_str().is_none()
+        || webhook_url.len() > MAX_WEBHOOK_URL_LEN
+    {
+        return Err(AtlasError::InvalidInput(format!(
+            "webhook_url must be an http(s) URL of at most {MAX_WEBHOOK_URL_LEN} characters"
+        )));
+    }
+
+    Ok(RuleFields {
+        name,
+        kind,
+        contract,
+        event,
+        min_value,
+        counterparty,
+        method,
+        webhook_url,
+        enabled: request.enabled,
+    })
+}
+
+fn non_empty(value: Option<String>) -> Option<String> {
+    value
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+/// Lowercase `0x`-prefixed hex of exactly `bytes` bytes.
+fn hex_field(value: &str, bytes: usize, field: &str) -> Result<String, AtlasError> {
+    let value = value.to_ascii_lowercase();
+    match value.strip_prefix("0x") {
+        Some(hex) if hex.len() == bytes * 2 && hex.bytes().all(|b| b.is_ascii_hexdigit()) => {
+            Ok(value)
+        }
+        _ => Err(AtlasError::InvalidInput(format!(
+            "{field} must be {bytes} bytes of 0x-prefixed hex"
+        ))),
+    }
+}
+
+fn not_found(id: i64) -> AtlasError {
+    AtlasError::NotFound(format!("Alert rule {id} not found"))
+}
+
+fn duplicate_name(name: &str) -> AtlasError {
+    AtlasError::InvalidInput(format!("An alert rule named '{name}' already exists"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(json: serde_json::Value) -> AlertRuleRequest {
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn validate_rule_normalizes_and_checks_conditions() {
+        let fields = validate_rule(request(serde_json::json!({
+            "name": " Whale moves ",
+            "kind": "Transfer",
+            "contract": "0x00000000000000000000000000000000000000AA",
+            "min_value": "1000000",
+            "method": "0xA9059CBB",
+            "webhook_url": "https://hooks.example.com/atlas",
+        })))
+        .unwrap();
+        assert_eq!(fields.name, "Whale moves");
+        assert_eq!(fields.kind, "transfer");
+        assert_eq!(
+            fields.contract.as_deref(),
+            Some("0x00000000000000000000000000000000000000aa")
+        );
+        assert_eq!(fields.min_value, Some(BigDecimal::from(1_000_000)));
+        assert_eq!(fields.method.as_deref(), Some("0xa9059cbb"));
+        assert!(fields.enabled);
+
+        let base = serde_json::json!({
+            "name": "rule",
+            "kind": "log",
+            "contract": "0x00000000000000000000000000000000000000aa",
+            "webhook_url": "https://hooks.example.com/atlas",
+        });
+        let with = |key: &str, value: serde_json::Value| {
+            let mut json = base.clone();
+            json[key] = value;
+            validate_rule(request(json))
+        };
+        assert!(with("event", format!("0x{}", "ab".repeat(32)).into()).is_ok());
+        assert!(with("event", "0x1234".into()).is_err());
+        assert!(with("min_value", "5".into()).is_err());
+        assert!(with("method", "0x123".into()).is_err());
+        assert!(with("kind", "block".into()).is_err());
+        assert!(with("contract", serde_json::Value::Null).is_err());
+        assert!(with("webhook_url", "ftp://hooks.example.com".into()).is_err());
+        assert!(with("webhook_url", "not a url".into()).is_err());
+    }
+
+    #[test]
+    fn min_value_must_be_a_plain_integer() {
+        for value in ["-1", "1.5", "1e18", "0x10"] {
+            let json = serde_json::json!({
+                "name": "rule",
+                "kind": "transfer",
+                "counterparty": "0x00000000000000000000000000000000000000aa",
+                "min_value": value,
+                "webhook_url": "http://hooks.example.com",
+            });
+            assert!(validate_rule(request(json)).is_err(), "{value}");
+        }
+    }
+}
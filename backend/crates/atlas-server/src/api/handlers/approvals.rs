@@ -0,0 +1,339 @@
+//! Approval risk report: an owner's active ERC-20 allowances by spender
+//!
+//! Allowances come from `erc20_approvals`, the latest `Approval` event per
+//! (owner, token, spender) kept by the indexer. Each spender is scored from
+//! its labels (curated and derived), whether it is a contract and when it was
+//! last called, and each allowance from its size and the token's curation
+//! status. The report backs a "revoke" helper: the riskiest spenders come
+//! first.
+
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use bigdecimal::BigDecimal;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use crate::api::error::ApiResult;
+use crate::api::handlers::proxy::normalize_address;
+use crate::api::AppState;
+use crate::ordering::{ChainRow, Direction};
+
+/// Allowances read per report; the rest are reported as truncated.
+const MAX_ALLOWANCES: i64 = 1000;
+/// Label tags that mark a spender as malicious. Derived labels count when
+/// their source tag is one of these.
+const MALICIOUS_TAGS: &[&str] = &["phishing", "scam", "spam", "drainer", "exploit", "hack"];
+/// A contract spender not called for this long is flagged `inactive`.
+const INACTIVE_AFTER_SECS: i64 = 180 * 24 * 3600;
+
+#[derive(Debug, Serialize)]
+pub struct ApprovalRisks {
+    pub owner: String,
+    pub spenders: Vec<SpenderRisk>,
+    /// More than the reported allowances are active
+    pub truncated: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SpenderRisk {
+    pub spender: String,
+    /// Curated label name
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// Unknown when the spender never appeared in an indexed transaction
+    pub is_contract: Option<bool>,
+    /// `high`, `medium` or `low`
+    pub risk: &'static str,
+    /// Why the risk is what it is; see the API docs for the flag list
+    pub flags: Vec<String>,
+    /// Latest indexed transaction sent to the spender
+    pub last_active_block: Option<i64>,
+    pub last_active_timestamp: Option<i64>,
+    pub allowances: Vec<ActiveAllowance>,
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct ActiveAllowance {
+    #[serde(skip)]
+    pub spender: String,
+    pub contract_address: String,
+    pub name: Option<String>,
+    pub symbol: Option<String>,
+    pub decimals: Option<i16>,
+    /// Raw allowance as of the latest Approval event
+    pub value: String,
+    #[sqlx(skip)]
+    pub unlimited: bool,
+    /// Token marked as a scam or impersonation by an admin
+    pub token_flagged: bool,
+    /// Approval that set the allowance
+    pub block_number: i64,
+    pub tx_hash: String,
+    pub timestamp: Option<i64>,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct SpenderInfo {
+    spender: String,
+    label: Option<String>,
+    tags: Vec<String>,
+    derived_tags: Vec<String>,
+    is_contract: Option<bool>,
+    last_active_block: Option<i64>,
+    last_active_timestamp: Option<i64>,
+}
+
+/// GET /api/addresses/{address}/approval-risks - Active ERC-20 allowances
+/// granted by the address, grouped by spender, riskiest first
+pub async fn get_approval_risks(
+    State(state): State<Arc<AppState>>,
+    Path(address): Path<String>,
+) -> ApiResult<Json<ApprovalRisks>> {
+    let owner = normalize_address(&address);
+
+    let mut allowances: Vec<ActiveAllowance> = sqlx::query_as(&format!(
+        "SELECT a.spender, a.contract_address, c.name, c.symbol, c.decimals, a.value::text AS value,
+                COALESCE(cur.status = 'flagged', false) AS token_flagged,
+                a.block_number, a.tx_hash, b.timestamp
+         FROM erc20_approvals a
+         LEFT JOIN erc20_contracts c ON c.address = a.contract_address
+         LEFT JOIN token_curation cur ON cur.address = a.contract_address
+         LEFT JOIN blocks b ON b.number = a.block_number
+         WHERE a.owner = $1 AND a.value > 0
+         ORDER BY {}
+         LIMIT $2",
+        ChainRow::Log.order_by("a", Direction::Desc)
+    ))
+    .bind(&owner)
+    .bind(MAX_ALLOWANCES + 1)
+    .fetch_all(&state.pool)
+    .await?;
+    let truncated = allowances.len() as i64 > MAX_ALLOWANCES;
+    allowances.truncate(MAX_ALLOWANCES as usize);
+
+    let threshold = unlimited_threshold();
+    for allowance in &mut allowances {
+        allowance.unlimited =
+            BigDecimal::from_str(&allowance.value).is_ok_and(|value| value >= threshold);
+    }
+
+    let mut spenders: Vec<String> = allowances.iter().map(|a| a.spender.clone()).collect();
+    spenders.sort();
+    spenders.dedup();
+    let malicious: Vec<String> = MALICIOUS_TAGS.iter().map(|t| t.to_string()).collect();
+    let infos: Vec<SpenderInfo> = sqlx::query_as(
+        "SELECT s.spender, l.name AS label, COALESCE(l.tags, '{}') AS tags,
+                ARRAY(SELECT d.tag FROM derived_address_labels d
+                      WHERE d.address = s.spender AND d.source_tag = ANY($2)
+                      ORDER BY d.tag) AS derived_tags,
+                ad.is_contract,
+                last.block_number AS last_active_block,
+                last.timestamp AS last_active_timestamp
+         FROM unnest($1::text[]) AS s(spender)
+         LEFT JOIN address_labels l ON l.address = s.spender
+         LEFT JOIN addresses ad ON ad.address = s.spender
+         LEFT JOIN LATERAL (
+             SELECT t.block_number, t.timestamp FROM transactions t
+             WHERE t.to_address = s.spender
+             ORDER BY t.block_number DESC
+             LIMIT 1
+         ) last ON true",
+    )
+    .bind(&spenders)
+    .bind(&malicious)
+    .fetch_all(&state.pool)
+    .await?;
+
+    let now = state
+        .head_tracker
+        .latest()
+        .await
+        .map(|block| block.timestamp);
+    let mut grouped: HashMap<String, Vec<ActiveAllowance>> = HashMap::new();
+    for allowance in allowances {
+        grouped
+            .entry(allowance.spender.clone())
+            .or_default()
+            .push(allowance);
+    }
+
+    let mut spenders: Vec<SpenderRisk> = infos
+        .into_iter()
+        .map(|info| {
+            let allowances = grouped.remove(&info.spender).unwrap_or_default();
+            let (risk, flags) = assess(&info, &allowances, now);
+            SpenderRisk {
+                spender: info.spender,
+                label: info.label,
+                is_contract: info.is_contract,
+                risk,
+                flags,
+                last_active_block: info.last_active_block,
+                last_active_timestamp: info.last_active_timestamp,
+                allowances,
+            }
+        })
+        .collect();
+    spenders.sort_by(|a, b| {
+        risk_rank(b.risk)
+            .cmp(&risk_rank(a.risk))
+            .then_with(|| latest_approval(b).cmp(&latest_approval(a)))
+            .then_with(|| a.spender.cmp(&b.spender))
+    });
+
+    Ok(Json(ApprovalRisks {
+        owner,
+        spenders,
+        truncated,
+    }))
+}
+
+/// Allowances of at least 2^128 are treated as unlimited: no real supply
+/// comes close, and wallets approve `2^256 - 1` or similar sentinels.
+fn unlimited_threshold() -> BigDecimal {
+    BigDecimal::from(u128::MAX) + 1
+}
+
+/// Score a spender: malicious labels make it `high`; unlimited allowances,
+/// flagged tokens, EOA spenders and dormant contracts make it `medium`.
+/// `now` is the latest block timestamp.
+fn assess(
+    info: &SpenderInfo,
+    allowances: &[ActiveAllowance],
+    now: Option<i64>,
+) -> (&'static str, Vec<String>) {
+    let mut flags: Vec<String> = info
+        .tags
+        .iter()
+        .filter(|tag| MALICIOUS_TAGS.contains(&tag.as_str()))
+        .chain(&info.derived_tags)
+        .cloned()
+        .collect();
+    let malicious = !flags.is_empty();
+
+    if allowances.iter().any(|a| a.unlimited) {
+        flags.push("unlimited".to_string());
+    }
+    if allowances.iter().any(|a| a.token_flagged) {
+        flags.push("flagged-token".to_string());
+    }
+    match info.is_contract {
+        Some(false) => flags.push("eoa".to_string()),
+        Some(true) => {
+            let dormant = match (info.last_active_timestamp, now) {
+                (Some(last), Some(now)) => now - last > INACTIVE_AFTER_SECS,
+                (None, _) => true,
+                (Some(_), None) => false,
+            };
+            if dormant {
+                flags.push("inactive".to_string());
+            }
+        }
+        None => {}
+    }
+
+    let risk = if malicious {
+        "high"
+    } else if flags.is_empty() {
+        "low"
+    } else {
+        "medium"
+    };
+    (risk, flags)
+}
+
+fn risk_rank(risk: &str) -> u8 {
+    match risk {
+        "high" => 2,
+        "medium" => 1,
+        _ => 0,
+    }
+}
+
+fn latest_approval(spender: &SpenderRisk) -> i64 {
+    spender
+        .allowances
+        .iter()
+        .map(|a| a.block_number)
+        .max()
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn info(tags: &[&str], is_contract: Option<bool>, last_active: Option<i64>) -> SpenderInfo {
+        SpenderInfo {
+            spender: "0xspender".to_string(),
+            label: None,
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            derived_tags: Vec::new(),
+            is_contract,
+            last_active_block: last_active.map(|_| 1),
+            last_active_timestamp: last_active,
+        }
+    }
+
+    fn allowance(value: &str, token_flagged: bool) -> ActiveAllowance {
+        ActiveAllowance {
+            spender: "0xspender".to_string(),
+            contract_address: "0xtoken".to_string(),
+            name: None,
+            symbol: None,
+            decimals: None,
+            value: value.to_string(),
+            unlimited: BigDecimal::from_str(value).unwrap() >= unlimited_threshold(),
+            token_flagged,
+            block_number: 1,
+            tx_hash: "0x".to_string(),
+            timestamp: None,
+        }
+    }
+
+    #[test]
+    fn unlimited_threshold_is_two_to_the_128() {
+        assert_eq!(
+            unlimited_threshold().to_string(),
+            "340282366920938463463374607431768211456"
+        );
+    }
+
+    #[test]
+    fn assess_scores_spenders() {
+        let now = Some(1_000_000_000);
+        let active = Some(1_000_000_000 - 60);
+        let max = "115792089237316195423570985008687907853269984665640564039457584007913129639935";
+
+        let (risk, flags) = assess(
+            &info(&["dex"], Some(true), active),
+            &[allowance("1000", false)],
+            now,
+        );
+        assert_eq!((risk, flags), ("low", vec![]));
+
+        let (risk, flags) = assess(
+            &info(&["dex"], Some(true), active),
+            &[allowance(max, false), allowance("5", true)],
+            now,
+        );
+        assert_eq!(risk, "medium");
+        assert_eq!(flags, vec!["unlimited", "flagged-token"]);
+
+        let (risk, flags) = assess(&info(&[], Some(false), None), &[allowance("1", false)], now);
+        assert_eq!((risk, flags), ("medium", vec!["eoa".to_string()]));
+
+        let (risk, flags) = assess(&info(&[], Some(true), None), &[allowance("1", false)], now);
+        assert_eq!((risk, flags), ("medium", vec!["inactive".to_string()]));
+
+        let mut phishing = info(&["phishing"], None, None);
+        phishing.derived_tags = vec!["funded-by:scam".to_string()];
+        let (risk, flags) = assess(&phishing, &[allowance("1", false)], now);
+        assert_eq!(risk, "high");
+        assert_eq!(flags, vec!["phishing", "funded-by:scam"]);
+    }
+}
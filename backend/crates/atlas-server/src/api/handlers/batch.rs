@@ -0,0 +1,261 @@
+//! POST /api/batch — several read-only API calls in one round trip.
+//!
+//! Each call names an API path and its query parameters. Calls are
+//! dispatched through the API router itself, so they see the same handlers,
+//! concurrency limits and circuit breaker as standalone requests, and the
+//! results come back in request order.
+
+use axum::{
+    body::Body,
+    http::{header, Method, Request, StatusCode},
+    Json, Router,
+};
+use futures::stream::{self, StreamExt};
+use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
+use serde::{Deserialize, Serialize};
+use tower::ServiceExt;
+
+use crate::api::error::ApiResult;
+use atlas_common::AtlasError;
+
+/// Most calls accepted in one batch.
+pub const MAX_CALLS: usize = 20;
+/// Calls of one batch executed at the same time.
+const CONCURRENCY: usize = 4;
+/// Largest response body kept for a single call.
+const MAX_RESULT_BYTES: usize = 4 * 1024 * 1024;
+
+#[derive(Debug, Deserialize)]
+pub struct BatchCall {
+    /// Echoed back on the result; any JSON value
+    #[serde(default)]
+    pub id: serde_json::Value,
+    /// API path, e.g. `/api/blocks/42`
+    pub method: String,
+    /// Query parameters; strings, numbers and booleans
+    #[serde(default)]
+    pub params: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BatchResult {
+    pub id: serde_json::Value,
+    /// HTTP status the call would have had on its own
+    pub status: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// POST /api/batch - Run `calls` as GET requests against `api`
+pub async fn execute(api: Router, calls: Vec<BatchCall>) -> ApiResult<Json<Vec<BatchResult>>> {
+    if calls.is_empty() {
+        return Err(
+            AtlasError::InvalidInput("Batch must contain at least one call".to_string()).into(),
+        );
+    }
+    if calls.len() > MAX_CALLS {
+        return Err(AtlasError::InvalidInput(format!(
+            "Batch has {} calls; at most {MAX_CALLS} are allowed",
+            calls.len()
+        ))
+        .into());
+    }
+
+    let results = stream::iter(calls)
+        .map(|call| run_call(api.clone(), call))
+        .buffered(CONCURRENCY)
+        .collect()
+        .await;
+    Ok(Json(results))
+}
+
+async fn run_call(api: Router, call: BatchCall) -> BatchResult {
+    let uri = match call_uri(&call) {
+        Ok(uri) => uri,
+        Err(message) => {
+            return BatchResult {
+                id: call.id,
+                status: StatusCode::BAD_REQUEST.as_u16(),
+                result: None,
+                error: Some(message),
+            }
+        }
+    };
+
+    let request = Request::builder()
+        .method(Method::GET)
+        .uri(uri)
+        .header(header::ACCEPT, "application/json")
+        .body(Body::empty())
+        .expect("batch call request");
+    let response = match api.oneshot(request).await {
+        Ok(response) => response,
+        Err(never) => match never {},
+    };
+
+    let status = response.status();
+    let body = match axum::body::to_bytes(response.into_body(), MAX_RESULT_BYTES).await {
+        Ok(body) => body,
+        Err(_) => {
+            return BatchResult {
+                id: call.id,
+                status: StatusCode::PAYLOAD_TOO_LARGE.as_u16(),
+                result: None,
+                error: Some(format!(
+                    "Response exceeds the {MAX_RESULT_BYTES} byte batch result limit"
+                )),
+            }
+        }
+    };
+    let value = serde_json::from_slice(&body)
+        .unwrap_or_else(|_| serde_json::Value::String(String::from_utf8_lossy(&body).into_owned()));
+
+    if status.is_success() {
+        BatchResult {
+            id: call.id,
+            status: status.as_u16(),
+            result: Some(value),
+            error: None,
+        }
+    } else {
+        let error = match value {
+            serde_json::Value::Object(mut body) => match body.remove("error") {
+                Some(serde_json::Value::String(message)) => message,
+                _ => status.to_string(),
+            },
+            serde_json::Value::String(message) if !message.is_empty() => message,
+            _ => status.to_string(),
+        };
+        BatchResult {
+            id: call.id,
+            status: status.as_u16(),
+            result: None,
+            error: Some(error),
+        }
+    }
+}
+
+/// Request URI for `call`: its path with `params` as the query string.
+pub(crate) fn call_uri(call: &BatchCall) -> Result<String, String> {
+    let path = call.method.as_str();
+    if !path.starts_with("/api/") || path.contains(['?', '#']) {
+        return Err(format!(
+            "method must be an API path such as /api/blocks/1, got {path:?}"
+        ));
+    }
+    if path == "/api/batch" {
+        return Err("Batches cannot be nested".to_string());
+    }
+
+    let mut query = Vec::with_capacity(call.params.len());
+    for (name, value) in &call.params {
+        let value = match value {
+            serde_json::Value::String(value) => value.clone(),
+            serde_json::Value::Number(value) => value.to_string(),
+            serde_json::Value::Bool(value) => value.to_string(),
+            _ => return Err(format!("params.{name} must be a string, number or boolean")),
+        };
+        query.push(format!(
+            "{}={}",
+            utf8_percent_encode(name, NON_ALPHANUMERIC),
+            utf8_percent_encode(&value, NON_ALPHANUMERIC)
+        ));
+    }
+
+    if query.is_empty() {
+        Ok(path.to_string())
+    } else {
+        Ok(format!("{path}?{}", query.join("&")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{extract::Query, routing::get};
+    use std::collections::BTreeMap;
+
+    fn call(method: &str, params: serde_json::Value) -> BatchCall {
+        serde_json::from_value(serde_json::json!({
+            "id": method,
+            "method": method,
+            "params": params,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn call_uri_encodes_params_as_the_query_string() {
+        assert_eq!(
+            call_uri(&call(
+                "/api/blocks",
+                serde_json::json!({"page": 2, "limit": 5})
+            )),
+            Ok("/api/blocks?limit=5&page=2".to_string())
+        );
+        assert_eq!(
+            call_uri(&call("/api/search", serde_json::json!({"q": "a b&c"}))),
+            Ok("/api/search?q=a%20b%26c".to_string())
+        );
+        assert!(call_uri(&call("/api/logs", serde_json::json!({"topic0": ["0x1"]}))).is_err());
+        assert!(call_uri(&call("/health", serde_json::json!({}))).is_err());
+        assert!(call_uri(&call("/api/blocks?page=2", serde_json::json!({}))).is_err());
+        assert!(call_uri(&call("/api/batch", serde_json::json!({}))).is_err());
+    }
+
+    #[tokio::test]
+    async fn execute_returns_results_and_errors_in_call_order() {
+        let api = Router::new()
+            .route(
+                "/api/echo",
+                get(|Query(params): Query<BTreeMap<String, String>>| async move { Json(params) }),
+            )
+            .route(
+                "/api/missing",
+                get(|| async {
+                    crate::api::error::ApiError(AtlasError::NotFound("Nothing here".to_string()))
+                }),
+            );
+
+        let Json(results) = execute(
+            api,
+            vec![
+                call("/api/echo", serde_json::json!({"page": 1})),
+                call("/api/missing", serde_json::json!({})),
+                call("/api/unknown", serde_json::json!({})),
+                call("/metrics", serde_json::json!({})),
+            ],
+        )
+        .await
+        .unwrap_or_else(|_| panic!("batch rejected"));
+
+        let summary: Vec<_> = results
+            .iter()
+            .map(|r| (r.id.clone(), r.status, r.result.clone(), r.error.clone()))
+            .collect();
+        assert_eq!(
+            summary[0],
+            (
+                serde_json::json!("/api/echo"),
+                200,
+                Some(serde_json::json!({"page": "1"})),
+                None
+            )
+        );
+        assert_eq!(summary[1].1, 404);
+        assert_eq!(summary[1].3.as_deref(), Some("Nothing here"));
+        assert_eq!(summary[2].1, 404);
+        assert_eq!(summary[3].1, 400);
+    }
+
+    #[tokio::test]
+    async fn execute_rejects_empty_and_oversized_batches() {
+        assert!(execute(Router::new(), Vec::new()).await.is_err());
+        let calls = (0..=MAX_CALLS)
+            .map(|_| call("/api/blocks", serde_json::json!({})))
+            .collect();
+        assert!(execute(Router::new(), calls).await.is_err());
+    }
+}
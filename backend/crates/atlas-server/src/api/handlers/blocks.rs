@@ -1,12 +1,22 @@
 use axum::{
     extract::{Path, Query, State},
+    http::HeaderMap,
+    response::{IntoResponse, Response},
     Json,
 };
 use serde::Serialize;
 use std::sync::Arc;
 
 use crate::api::error::ApiResult;
+use crate::api::handlers::display::{DisplayQuery, WithDisplay};
+use crate::api::handlers::snapshot_anchor;
+use crate::api::handlers::transactions::{
+    decorate_transaction_response, decorate_transactions, TransactionResponse,
+    TRANSACTION_RESPONSE_COLUMNS,
+};
+use crate::api::ndjson;
 use crate::api::AppState;
+use crate::ordering::{ChainRow, Direction};
 use atlas_common::{
     AtlasError, Block, BlockDaStatus, PaginatedResponse, Pagination, Transaction, BLOCK_COLUMNS,
 };
@@ -25,12 +35,11 @@ pub async fn list_blocks(
     State(state): State<Arc<AppState>>,
     Query(pagination): Query<Pagination>,
 ) -> ApiResult<Json<PaginatedResponse<BlockResponse>>> {
-    // Use MAX(number) + 1 instead of COUNT(*) - blocks are sequential so this is accurate
-    // This is ~6500x faster than COUNT(*) on large tables
-    let total: (Option<i64>,) = sqlx::query_as("SELECT MAX(number) + 1 FROM blocks")
-        .fetch_one(&state.pool)
-        .await?;
-    let total_count = total.0.unwrap_or(0);
+    // Use the anchor (cached head height unless `as_of_block` is given) + 1 instead
+    // of COUNT(*) - blocks are sequential so this is accurate, and the head tracker
+    // keeps it off the database entirely.
+    let anchor = snapshot_anchor(&state, pagination.as_of_block).await?;
+    let total_count = anchor.map_or(0, |height| height + 1);
 
     // Convert page-based navigation to a keyset cursor using block numbers.
     // Blocks are sequential so: cursor = max_block - (page - 1) * limit
@@ -69,12 +78,10 @@ pub async fn list_blocks(
         })
         .collect();
 
-    Ok(Json(PaginatedResponse::new(
-        responses,
-        pagination.page,
-        pagination.limit,
-        total_count,
-    )))
+    Ok(Json(
+        PaginatedResponse::new(responses, pagination.page, pagination.limit, total_count)
+            .anchored_at(anchor),
+    ))
 }
 
 pub async fn get_block(
@@ -102,33 +109,81 @@ pub async fn get_block(
     Ok(Json(BlockResponse { block, da_status }))
 }
 
+/// Transactions of block `$1` in execution order
+fn block_transactions_sql() -> String {
+    format!(
+        "SELECT hash, block_number, block_index, from_address, to_address, value, gas_price, gas_used, input_data, status, contract_created, timestamp
+         FROM transactions
+         WHERE block_number = $1
+         ORDER BY {}",
+        ChainRow::Transaction.order_by("", Direction::Asc)
+    )
+}
+
+/// GET /api/blocks/{number}/transactions - A page of the block's
+/// transactions, or all of them as NDJSON (see [`ndjson`])
 pub async fn get_block_transactions(
     State(state): State<Arc<AppState>>,
     Path(number): Path<i64>,
     Query(pagination): Query<Pagination>,
-) -> ApiResult<Json<PaginatedResponse<Transaction>>> {
+    Query(options): Query<DisplayQuery>,
+    headers: HeaderMap,
+) -> ApiResult<Response> {
+    if ndjson::requested(&headers) {
+        let mut tx = ndjson::begin(&state.pool).await?;
+        sqlx::query(&ndjson::declare(&block_transactions_sql()))
+            .bind(number)
+            .execute(&mut *tx)
+            .await?;
+        return Ok(ndjson::stream::<Transaction>(tx));
+    }
+
     let total: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM transactions WHERE block_number = $1")
         .bind(number)
         .fetch_one(&state.pool)
         .await?;
 
-    let transactions: Vec<Transaction> = sqlx::query_as(
-        "SELECT hash, block_number, block_index, from_address, to_address, value, gas_price, gas_used, input_data, status, contract_created, timestamp
-         FROM transactions
-         WHERE block_number = $1
-         ORDER BY block_index ASC
-         LIMIT $2 OFFSET $3",
-    )
-    .bind(number)
-    .bind(pagination.limit())
-    .bind(pagination.offset())
-    .fetch_all(&state.pool)
-    .await?;
+    let transactions: Vec<Transaction> =
+        sqlx::query_as(&format!("{} LIMIT $2 OFFSET $3", block_transactions_sql()))
+            .bind(number)
+            .bind(pagination.limit())
+            .bind(pagination.offset())
+            .fetch_all(&state.pool)
+            .await?;
 
     Ok(Json(PaginatedResponse::new(
-        transactions,
+        decorate_transactions(&state, transactions, &options),
         pagination.page,
         pagination.limit,
         total.0,
+    ))
+    .into_response())
+}
+
+/// GET /api/blocks/{number}/transactions/{index} - Transaction at a position in a block
+pub async fn get_block_transaction_by_index(
+    State(state): State<Arc<AppState>>,
+    Path((number, index)): Path<(i64, i32)>,
+    Query(options): Query<DisplayQuery>,
+) -> ApiResult<Json<WithDisplay<TransactionResponse>>> {
+    let transaction: TransactionResponse = sqlx::query_as(&format!(
+        "SELECT {} FROM transactions WHERE block_number = $1 AND block_index = $2",
+        TRANSACTION_RESPONSE_COLUMNS
+    ))
+    .bind(number)
+    .bind(index)
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or_else(|| {
+        AtlasError::NotFound(format!(
+            "Transaction {} in block {} not found",
+            index, number
+        ))
+    })?;
+
+    Ok(Json(decorate_transaction_response(
+        &state,
+        transaction,
+        &options,
     )))
 }
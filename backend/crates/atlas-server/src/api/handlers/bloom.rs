@@ -0,0 +1,194 @@
+//! Fast "does this block/transaction involve X" checks backed by logs blooms.
+//!
+//! An address is involved when it sent or received a transaction, created a
+//! contract, emitted a log or appears as an indexed topic (e.g. a Transfer
+//! recipient). Transaction fields are checked exactly; logs are checked
+//! against the stored bloom, which can only rule them out. Rows indexed
+//! before blooms were stored fall back to an exact `event_logs` lookup.
+
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use crate::api::error::ApiResult;
+use crate::api::AppState;
+use crate::bloom::{self, BloomFilter};
+use atlas_common::encoding::{address_to_hex, hex_to_bytes, parse_address, parse_b256};
+use atlas_common::storage::TieredTable;
+use atlas_common::AtlasError;
+
+#[derive(Debug, Deserialize)]
+pub struct BloomCheckParams {
+    pub address: Option<String>,
+    pub topic: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BloomMatch {
+    /// Definitely not involved.
+    None,
+    /// The bloom matched; confirm with the logs endpoints.
+    Possible,
+    /// Known to be involved.
+    Confirmed,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BloomCheck {
+    pub result: BloomMatch,
+}
+
+/// GET /api/blocks/{number}/bloom?address=&topic= — check block involvement
+pub async fn check_block(
+    State(state): State<Arc<AppState>>,
+    Path(number): Path<i64>,
+    Query(params): Query<BloomCheckParams>,
+) -> ApiResult<Json<BloomCheck>> {
+    let filter = parse_filter(&params)?;
+
+    let (stored,): (Option<Vec<u8>>,) =
+        sqlx::query_as("SELECT logs_bloom FROM blocks WHERE number = $1")
+            .bind(number)
+            .fetch_optional(&state.pool)
+            .await?
+            .ok_or_else(|| AtlasError::NotFound(format!("Block {} not found", number)))?;
+
+    if filter.topic.is_none() {
+        if let Some(address) = filter.address {
+            let (in_txs,): (bool,) = sqlx::query_as(
+                "SELECT EXISTS (
+                    SELECT 1 FROM transactions
+                    WHERE block_number = $1
+                      AND $2 IN (from_address, to_address, contract_created)
+                 )",
+            )
+            .bind(number)
+            .bind(address_to_hex(&address))
+            .fetch_one(&state.pool)
+            .await?;
+            if in_txs {
+                return Ok(Json(BloomCheck {
+                    result: BloomMatch::Confirmed,
+                }));
+            }
+        }
+    }
+
+    let result = match stored.as_deref().and_then(bloom::expand) {
+        Some(bloom) => bloom_result(&filter, &bloom),
+        None => logs_result(&state, &filter, number, None).await?,
+    };
+    Ok(Json(BloomCheck { result }))
+}
+
+/// GET /api/transactions/{hash}/bloom?address=&topic= — check transaction involvement
+pub async fn check_transaction(
+    State(state): State<Arc<AppState>>,
+    Path(hash): Path<String>,
+    Query(params): Query<BloomCheckParams>,
+) -> ApiResult<Json<BloomCheck>> {
+    let filter = parse_filter(&params)?;
+    let hash = normalize_hash(&hash);
+
+    type TxRow = (i64, String, Option<String>, Option<String>, Option<Vec<u8>>);
+    let (block_number, from, to, created, stored): TxRow = sqlx::query_as(
+        "SELECT block_number, from_address, to_address, contract_created, logs_bloom
+         FROM transactions
+         WHERE hash = $1",
+    )
+    .bind(&hash)
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or_else(|| AtlasError::NotFound(format!("Transaction {} not found", hash)))?;
+
+    if filter.topic.is_none() {
+        if let Some(address) = filter.address {
+            let address = address_to_hex(&address);
+            if [Some(from), to, created].contains(&Some(address)) {
+                return Ok(Json(BloomCheck {
+                    result: BloomMatch::Confirmed,
+                }));
+            }
+        }
+    }
+
+    let result = match stored.as_deref().and_then(bloom::expand) {
+        Some(bloom) => bloom_result(&filter, &bloom),
+        None => logs_result(&state, &filter, block_number, Some(&hash)).await?,
+    };
+    Ok(Json(BloomCheck { result }))
+}
+
+fn parse_filter(params: &BloomCheckParams) -> Result<BloomFilter, AtlasError> {
+    let filter = BloomFilter {
+        address: params.address.as_deref().map(parse_address).transpose()?,
+        topic: params
+            .topic
+            .as_deref()
+            .map(|t| {
+                parse_b256(t).map_err(|_| AtlasError::InvalidInput("Invalid topic".to_string()))
+            })
+            .transpose()?,
+    };
+    if filter.is_empty() {
+        return Err(AtlasError::InvalidInput(
+            "Provide an address and/or topic to check".to_string(),
+        ));
+    }
+    Ok(filter)
+}
+
+fn bloom_result(filter: &BloomFilter, bloom: &alloy::primitives::Bloom) -> BloomMatch {
+    if filter.may_match(bloom) {
+        BloomMatch::Possible
+    } else {
+        BloomMatch::None
+    }
+}
+
+/// Exact answer from `event_logs`, in the block's tier, for rows indexed
+/// without a bloom.
+async fn logs_result(
+    state: &AppState,
+    filter: &BloomFilter,
+    block_number: i64,
+    tx_hash: Option<&str>,
+) -> Result<BloomMatch, AtlasError> {
+    let segment = state
+        .storage
+        .segment_for_block(TieredTable::EventLogs, block_number);
+    let (found,): (bool,) = sqlx::query_as(&format!(
+        "SELECT EXISTS (
+            SELECT 1 FROM {}
+            WHERE block_number = $1
+              AND ($2::bytea IS NULL OR tx_hash = $2)
+              AND ($3::bytea IS NULL OR address = $3 OR $4 IN (topic0, topic1, topic2, topic3))
+              AND ($5::bytea IS NULL OR $5 IN (topic0, topic1, topic2, topic3))
+         )",
+        segment.table
+    ))
+    .bind(block_number)
+    .bind(tx_hash.map(hex_to_bytes).transpose()?)
+    .bind(filter.address.map(|a| a.to_vec()))
+    .bind(filter.address.map(|a| a.into_word().to_vec()))
+    .bind(filter.topic.map(|t| t.to_vec()))
+    .fetch_one(&segment.pool)
+    .await?;
+    Ok(if found {
+        BloomMatch::Confirmed
+    } else {
+        BloomMatch::None
+    })
+}
+
+fn normalize_hash(hash: &str) -> String {
+    if hash.starts_with("0x") {
+        hash.to_lowercase()
+    } else {
+        format!("0x{}", hash.to_lowercase())
+    }
+}
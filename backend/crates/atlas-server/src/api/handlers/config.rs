@@ -2,6 +2,7 @@ use axum::{extract::State, Json};
 use serde::Serialize;
 use std::sync::Arc;
 
+use crate::api::handlers::display::NativeCurrency;
 use crate::api::AppState;
 
 #[derive(Serialize)]
@@ -21,6 +22,7 @@ pub struct FaucetConfig {
 #[derive(Serialize)]
 pub struct BrandingConfig {
     pub chain_name: String,
+    pub native_currency: NativeCurrency,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub logo_url: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -46,6 +48,7 @@ pub struct BrandingConfig {
 pub async fn get_config(State(state): State<Arc<AppState>>) -> Json<BrandingConfig> {
     Json(BrandingConfig {
         chain_name: state.chain_name.clone(),
+        native_currency: state.native_currency.clone(),
         logo_url: state.chain_logo_url.clone(),
         logo_url_light: state.chain_logo_url_light.clone(),
         logo_url_dark: state.chain_logo_url_dark.clone(),
@@ -73,6 +76,7 @@ mod tests {
     fn branding_config_skips_none_fields() {
         let config = BrandingConfig {
             chain_name: "TestChain".to_string(),
+            native_currency: Default::default(),
             logo_url: None,
             logo_url_light: None,
             logo_url_dark: None,
@@ -108,6 +112,7 @@ mod tests {
     fn branding_config_includes_all_fields_when_set() {
         let config = BrandingConfig {
             chain_name: "MyChain".to_string(),
+            native_currency: Default::default(),
             logo_url: Some("/branding/logo.svg".to_string()),
             logo_url_light: Some("/branding/logo-light.svg".to_string()),
             logo_url_dark: Some("/branding/logo-dark.svg".to_string()),
@@ -0,0 +1,184 @@
+//! Event definitions of a verified contract, ready to paste into tooling.
+//!
+//! For proxies the implementation's events are listed alongside the proxy's
+//! own, since the proxy address is what emits them.
+
+use alloy::json_abi::Event;
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use serde::Serialize;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use crate::api::error::ApiResult;
+use crate::api::handlers::proxy::{normalize_address, resolve_proxy};
+use crate::api::AppState;
+use atlas_common::encoding::b256_to_hex;
+use atlas_common::AtlasError;
+
+#[derive(Debug, Serialize)]
+pub struct ContractEventsResponse {
+    pub address: String,
+    pub implementation_address: Option<String>,
+    pub events: Vec<ContractEvent>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ContractEvent {
+    pub name: String,
+    /// Canonical signature, e.g. `Transfer(address,address,uint256)`.
+    pub signature: String,
+    /// Human-readable ABI fragment as used by ethers and Foundry's `cast`.
+    pub human_readable: String,
+    /// `keccak256(signature)`; `null` for anonymous events, which have no topic0.
+    pub topic0: Option<String>,
+    pub anonymous: bool,
+    /// `"contract"` for the queried contract's ABI, `"implementation"` for a proxy's implementation.
+    pub source: &'static str,
+    /// The event's JSON ABI entry.
+    pub abi: serde_json::Value,
+    /// `eth_getLogs` filter matching this event on the queried address.
+    pub filter: LogFilter,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LogFilter {
+    pub address: String,
+    pub topics: Vec<String>,
+}
+
+/// GET /api/contracts/:address/events - Event definitions from the verified ABI (and a proxy's implementation)
+pub async fn get_contract_events(
+    State(state): State<Arc<AppState>>,
+    Path(address): Path<String>,
+) -> ApiResult<Json<ContractEventsResponse>> {
+    let address = normalize_address(&address);
+    let proxy = resolve_proxy(&state, &address).await?;
+    let implementation_address = proxy.map(|p| p.implementation_address);
+
+    let contract_abi = verified_abi(&state, &address).await?;
+    let implementation_abi = match &implementation_address {
+        Some(implementation) => verified_abi(&state, implementation).await?,
+        None => None,
+    };
+    if contract_abi.is_none() && implementation_abi.is_none() {
+        return Err(AtlasError::NotFound(format!("No verified ABI for {address}")).into());
+    }
+
+    // Implementation first, matching the combined ABI's precedence.
+    let mut seen = HashSet::new();
+    let events = [
+        (implementation_abi.as_ref(), "implementation"),
+        (contract_abi.as_ref(), "contract"),
+    ]
+    .into_iter()
+    .filter_map(|(abi, source)| abi.map(|abi| (abi, source)))
+    .flat_map(|(abi, source)| abi_events(abi).map(move |(event, item)| (event, item, source)))
+    .filter(|(event, _, _)| seen.insert((event.signature(), event.anonymous)))
+    .map(|(event, abi, source)| contract_event(&address, event, abi, source))
+    .collect();
+
+    Ok(Json(ContractEventsResponse {
+        address,
+        implementation_address,
+        events,
+    }))
+}
+
+async fn verified_abi(
+    state: &AppState,
+    address: &str,
+) -> Result<Option<serde_json::Value>, sqlx::Error> {
+    sqlx::query_scalar("SELECT abi FROM contract_abis WHERE address = $1")
+        .bind(address)
+        .fetch_optional(&state.pool)
+        .await
+}
+
+/// Event entries of a JSON ABI; entries that do not parse are skipped.
+fn abi_events(abi: &serde_json::Value) -> impl Iterator<Item = (Event, serde_json::Value)> + '_ {
+    abi.as_array()
+        .into_iter()
+        .flatten()
+        .filter(|item| item.get("type").and_then(|t| t.as_str()) == Some("event"))
+        .filter_map(|item| {
+            serde_json::from_value::<Event>(item.clone())
+                .ok()
+                .map(|event| (event, item.clone()))
+        })
+}
+
+fn contract_event(
+    address: &str,
+    event: Event,
+    abi: serde_json::Value,
+    source: &'static str,
+) -> ContractEvent {
+    let topic0 = (!event.anonymous).then(|| b256_to_hex(&event.selector()));
+    ContractEvent {
+        signature: event.signature(),
+        human_readable: event.full_signature(),
+        filter: LogFilter {
+            address: address.to_string(),
+            topics: topic0.iter().cloned().collect(),
+        },
+        topic0,
+        anonymous: event.anonymous,
+        name: event.name,
+        source,
+        abi,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn transfer_abi() -> serde_json::Value {
+        json!([
+            {"type": "function", "name": "transfer", "inputs": [], "outputs": [], "stateMutability": "nonpayable"},
+            {"type": "event", "name": "Transfer", "anonymous": false, "inputs": [
+                {"name": "from", "type": "address", "indexed": true},
+                {"name": "to", "type": "address", "indexed": true},
+                {"name": "value", "type": "uint256", "indexed": false}
+            ]},
+            {"type": "event", "name": "Broken", "inputs": "not-an-array"}
+        ])
+    }
+
+    #[test]
+    fn abi_events_skips_functions_and_malformed_entries() {
+        let abi = transfer_abi();
+        let events: Vec<_> = abi_events(&abi).map(|(event, _)| event.name).collect();
+        assert_eq!(events, vec!["Transfer"]);
+    }
+
+    #[test]
+    fn contract_event_has_topic0_and_filter() {
+        let abi = transfer_abi();
+        let (event, item) = abi_events(&abi).next().unwrap();
+        let event = contract_event("0xabc", event, item, "contract");
+
+        assert_eq!(event.signature, "Transfer(address,address,uint256)");
+        assert_eq!(
+            event.human_readable,
+            "event Transfer(address indexed from, address indexed to, uint256 value)"
+        );
+        let topic0 = "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef";
+        assert_eq!(event.topic0.as_deref(), Some(topic0));
+        assert_eq!(event.filter.address, "0xabc");
+        assert_eq!(event.filter.topics, vec![topic0]);
+    }
+
+    #[test]
+    fn anonymous_event_has_no_topic0() {
+        let abi = json!([{"type": "event", "name": "Anon", "anonymous": true, "inputs": []}]);
+        let (event, item) = abi_events(&abi).next().unwrap();
+        let event = contract_event("0xabc", event, item, "contract");
+        assert_eq!(event.topic0, None);
+        assert!(event.filter.topics.is_empty());
+    }
+}
@@ -4,13 +4,34 @@
 //! against on-chain bytecode. On success, stores ABI + source in `contract_abis`.
 //!
 //! GET /api/contracts/:address — returns verification status, ABI, and source.
+//!
+//! GET /api/contracts/:address/files[/*path] — lists the verified source files,
+//! or returns one of them as text, for the code viewer.
+//!
+//! GET /api/contracts/:address/ast — solc's AST of each source file and the
+//! runtime bytecode's source map, recorded at verification.
+//!
+//! POST /api/contracts/pending-verifications — compile a contract before it is
+//! deployed and register it by runtime code hash; contracts later created with
+//! that exact code are verified by `indexer::PendingVerificationMatcher`.
+//!
+//! GET /api/contracts/pending-verifications/:code_hash — a registration and
+//! how many contracts it has verified.
+//!
+//! A verified contract can be verified again only with an exact match, which
+//! replaces the old verification. Admins can unverify a contract and restore
+//! the verification they removed; every removed verification is kept in
+//! `verification_history`.
 
 use axum::{
+    body::Body,
     extract::{Path, State},
-    http::StatusCode,
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
     Json,
 };
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::env::consts::{ARCH, OS};
 use std::io::Write;
 use std::path::PathBuf;
@@ -18,8 +39,14 @@ use std::sync::Arc;
 use std::time::Duration;
 use tokio::fs;
 
+use crate::api::auth::require_admin;
+use crate::api::body_limits::read_limited;
 use crate::api::error::ApiResult;
 use crate::api::AppState;
+use alloy::dyn_abi::{DynSolType, DynSolValue, Specifier};
+use alloy::json_abi::JsonAbi;
+use alloy::primitives::{keccak256, I256, U256};
+use atlas_common::encoding::address_to_hex;
 use atlas_common::{AtlasError, FullContractAbi};
 
 // ── Request / Response types ──────────────────────────────────────────────────
@@ -41,25 +68,124 @@ pub struct VerifyRequest {
     /// EVM version, e.g. "paris" (default: compiler default)
     pub evm_version: Option<String>,
     pub license_type: Option<String>,
+    /// Strategies the match may use (default: all). `["exact"]` requires a
+    /// byte-for-byte match.
+    pub match_strategies: Option<Vec<MatchStrategy>>,
+}
+
+/// A verification registered ahead of deployment: the usual verification
+/// fields plus, optionally, the code hash the caller's build produced.
+#[derive(Debug, Deserialize)]
+pub struct PendingVerificationRequest {
+    #[serde(flatten)]
+    pub verify: VerifyRequest,
+    /// keccak256 of the runtime bytecode (`0x` hex); rejected unless the
+    /// submitted source compiles to it
+    pub code_hash: Option<String>,
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct PendingVerificationResponse {
+    pub code_hash: String,
+    pub contract_name: String,
+    pub compiler_version: String,
+    /// Contracts verified from this registration so far
+    pub matched_contracts: i64,
+    pub registered_at: chrono::DateTime<chrono::Utc>,
+    pub last_matched_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
 #[derive(Debug, Serialize)]
 pub struct VerifyResponse {
     pub verified: bool,
     pub abi: serde_json::Value,
+    pub match_strategy: MatchStrategy,
+    pub immutable_values: Vec<ImmutableValue>,
+}
+
+/// The value the constructor assigned to an `immutable` variable, read from
+/// the deployed bytecode.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImmutableValue {
+    /// Solidity AST id of the declaration (the key in `immutableReferences`)
+    pub id: u64,
+    pub name: Option<String>,
+    #[serde(rename = "type")]
+    pub type_name: Option<String>,
+    /// The raw 32-byte word as hex
+    pub value: String,
+    /// `value` rendered for its type (addresses, bools, integers)
+    pub decoded: Option<String>,
+}
+
+/// A constructor argument decoded from the stored `constructor_args` with the
+/// verified ABI's constructor inputs.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ConstructorArgument {
+    /// Empty for unnamed parameters
+    pub name: String,
+    #[serde(rename = "type")]
+    pub type_name: String,
+    /// Integers as decimal strings, addresses and bytes as `0x` hex, arrays
+    /// and structs as JSON arrays
+    pub value: serde_json::Value,
+}
+
+/// How compiled bytecode matched the deployed bytecode. Strategies are tried
+/// strictest first and the first one that matches is reported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MatchStrategy {
+    /// Byte-for-byte identical, metadata included.
+    Exact,
+    /// Identical once the compiler's immutable slots are patched with the
+    /// values the constructor wrote into the deployed code.
+    Immutables,
+    /// Identical apart from the trailing CBOR metadata (source hashes,
+    /// compiler tag), after patching immutables.
+    Metadata,
+}
+
+impl MatchStrategy {
+    const ALL: [MatchStrategy; 3] = [
+        MatchStrategy::Exact,
+        MatchStrategy::Immutables,
+        MatchStrategy::Metadata,
+    ];
+
+    fn as_str(self) -> &'static str {
+        match self {
+            MatchStrategy::Exact => "exact",
+            MatchStrategy::Immutables => "immutables",
+            MatchStrategy::Metadata => "metadata",
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 struct ImmutableReference {
+    id: u64,
     start: usize,
     length: usize,
 }
 
+/// Name and type of an `immutable` declaration, from the Solidity AST.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ImmutableDeclaration {
+    name: String,
+    type_name: Option<String>,
+}
+
 #[derive(Debug)]
 struct CompiledContract {
     bytecode: Vec<u8>,
     abi: serde_json::Value,
     immutable_references: Vec<ImmutableReference>,
+    immutable_declarations: HashMap<u64, ImmutableDeclaration>,
+    /// solc's `evm.deployedBytecode.sourceMap`
+    source_map: Option<String>,
+    /// See [`extract_source_asts`]
+    source_asts: Option<serde_json::Map<String, serde_json::Value>>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -106,10 +232,54 @@ pub struct ContractDetailResponse {
     pub is_multi_file: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub source_files: Option<serde_json::Value>,
+    /// See [`MatchStrategy`]; unset for contracts verified before it was recorded
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub match_strategy: Option<String>,
+    /// See [`ImmutableValue`]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub immutable_values: Option<serde_json::Value>,
+    /// See [`ConstructorArgument`]; unset when no arguments were stored or
+    /// they don't decode against the ABI's constructor
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub constructor_arguments: Option<Vec<ConstructorArgument>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub verified_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
+#[derive(Debug, Serialize)]
+pub struct ContractAstResponse {
+    pub address: String,
+    pub contract_name: Option<String>,
+    /// solc's compressed source map of the runtime bytecode
+    /// (`s:l:f:j:m;...`); `f` is a source `id` below
+    pub source_map: Option<String>,
+    /// `{path: {id, ast}}` for every compiled source file
+    pub sources: serde_json::Value,
+}
+
+#[derive(sqlx::FromRow)]
+struct StoredSourcesRow {
+    source_code: Option<String>,
+    contract_name: Option<String>,
+    is_multi_file: bool,
+    source_files: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ContractFilesResponse {
+    pub address: String,
+    pub is_multi_file: bool,
+    /// Sorted by path
+    pub files: Vec<ContractFileEntry>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ContractFileEntry {
+    pub path: String,
+    /// Content length in bytes
+    pub size: usize,
+}
+
 // ── Handlers ──────────────────────────────────────────────────────────────────
 
 /// GET /api/contracts/:address
@@ -122,7 +292,7 @@ pub async fn get_contract(
     let row: Option<FullContractAbi> = sqlx::query_as(
         "SELECT address, abi, source_code, compiler_version, optimization_used, runs,
                 verified_at, contract_name, constructor_args, evm_version, license_type,
-                is_multi_file, source_files
+                is_multi_file, source_files, match_strategy, immutable_values
          FROM contract_abis
          WHERE address = $1",
     )
@@ -144,9 +314,16 @@ pub async fn get_contract(
             license_type: None,
             is_multi_file: false,
             source_files: None,
+            match_strategy: None,
+            immutable_values: None,
+            constructor_arguments: None,
             verified_at: None,
         })),
         Some(c) => Ok(Json(ContractDetailResponse {
+            constructor_arguments: c
+                .constructor_args
+                .as_deref()
+                .and_then(|args| decode_constructor_arguments(&c.abi, args)),
             verified: true,
             address: Some(c.address),
             abi: Some(c.abi),
@@ -159,24 +336,170 @@ pub async fn get_contract(
             license_type: c.license_type,
             is_multi_file: c.is_multi_file,
             source_files: c.source_files,
+            match_strategy: c.match_strategy,
+            immutable_values: c.immutable_values,
             verified_at: Some(c.verified_at),
         })),
     }
 }
 
+/// GET /api/contracts/:address/files
+pub async fn list_contract_files(
+    State(state): State<Arc<AppState>>,
+    Path(address): Path<String>,
+) -> ApiResult<Json<ContractFilesResponse>> {
+    let address = normalize_address(&address);
+    let (is_multi_file, files) = verified_source_files(&state, &address).await?;
+
+    Ok(Json(ContractFilesResponse {
+        address,
+        is_multi_file,
+        files: files
+            .into_iter()
+            .map(|(path, content)| ContractFileEntry {
+                path,
+                size: content.len(),
+            })
+            .collect(),
+    }))
+}
+
+/// GET /api/contracts/:address/files/*path
+pub async fn get_contract_file(
+    State(state): State<Arc<AppState>>,
+    Path((address, path)): Path<(String, String)>,
+) -> ApiResult<Response> {
+    let address = normalize_address(&address);
+    let (_, mut files) = verified_source_files(&state, &address).await?;
+    let content = files.remove(&path).ok_or_else(|| {
+        AtlasError::NotFound(format!("Contract {address} has no source file {path}"))
+    })?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, source_content_type(&path)),
+            (header::X_CONTENT_TYPE_OPTIONS, "nosniff"),
+        ],
+        content,
+    )
+        .into_response())
+}
+
+/// GET /api/contracts/:address/ast
+pub async fn get_contract_ast(
+    State(state): State<Arc<AppState>>,
+    Path(address): Path<String>,
+) -> ApiResult<Json<ContractAstResponse>> {
+    let address = normalize_address(&address);
+
+    let row: Option<(Option<String>, Option<String>, Option<serde_json::Value>)> = sqlx::query_as(
+        "SELECT contract_name, deployed_source_map, source_asts
+             FROM contract_abis
+             WHERE address = $1",
+    )
+    .bind(&address)
+    .fetch_optional(&state.pool)
+    .await?;
+
+    let (contract_name, source_map, sources) =
+        row.ok_or_else(|| AtlasError::NotFound(format!("Contract {address} is not verified")))?;
+    let sources = sources.ok_or_else(|| {
+        AtlasError::NotFound(format!(
+            "No AST recorded for contract {address}; it is Yul or was verified before ASTs were stored"
+        ))
+    })?;
+
+    Ok(Json(ContractAstResponse {
+        address,
+        contract_name,
+        source_map,
+        sources,
+    }))
+}
+
+/// Source files of a verified contract keyed by path. Single-file
+/// verifications have no `source_files`, so their source is listed as
+/// `<ContractName>.sol`.
+async fn verified_source_files(
+    state: &AppState,
+    address: &str,
+) -> Result<(bool, BTreeMap<String, String>), AtlasError> {
+    let row: Option<StoredSourcesRow> = sqlx::query_as(
+        "SELECT source_code, contract_name, is_multi_file, source_files
+         FROM contract_abis
+         WHERE address = $1",
+    )
+    .bind(address)
+    .fetch_optional(&state.pool)
+    .await?;
+
+    let row =
+        row.ok_or_else(|| AtlasError::NotFound(format!("Contract {address} is not verified")))?;
+    Ok((
+        row.is_multi_file,
+        source_files_by_path(row.source_code, row.contract_name, row.source_files),
+    ))
+}
+
+fn source_files_by_path(
+    source_code: Option<String>,
+    contract_name: Option<String>,
+    source_files: Option<serde_json::Value>,
+) -> BTreeMap<String, String> {
+    if let Some(serde_json::Value::Object(files)) = source_files {
+        return files
+            .into_iter()
+            .filter_map(|(path, content)| match content {
+                serde_json::Value::String(content) => Some((path, content)),
+                _ => None,
+            })
+            .collect();
+    }
+
+    source_code
+        .map(|source| (single_file_source_path(contract_name.as_deref()), source))
+        .into_iter()
+        .collect()
+}
+
+/// Path a single-file verification's source is listed and stored under.
+fn single_file_source_path(contract_name: Option<&str>) -> String {
+    format!("{}.sol", contract_name.unwrap_or("Contract"))
+}
+
+/// `Content-Type` for a source file, by extension. Sources are served as
+/// text so browsers display rather than download or execute them.
+fn source_content_type(path: &str) -> &'static str {
+    match path
+        .rsplit_once('.')
+        .map(|(_, ext)| ext.to_ascii_lowercase())
+    {
+        Some(ext) if ext == "json" => "application/json",
+        _ => "text/plain; charset=utf-8",
+    }
+}
+
 /// POST /api/contracts/:address/verify
 pub async fn verify_contract(
     State(state): State<Arc<AppState>>,
     Path(address): Path<String>,
-    Json(req): Json<VerifyRequest>,
+    body: Body,
 ) -> ApiResult<(StatusCode, Json<VerifyResponse>)> {
     let address = normalize_address(&address);
 
+    // Standard JSON inputs can be tens of megabytes; stream the body under
+    // the verification limit rather than the API-wide extractor limit.
+    let body = read_limited(body, state.body_limits.verify).await?;
+    let req: VerifyRequest = serde_json::from_slice(&body)
+        .map_err(|e| AtlasError::InvalidInput(format!("invalid verification request: {e}")))?;
+    drop(body);
+
     // Validate compiler version format: v<major>.<minor>.<patch>+commit.<hex>
     validate_compiler_version(&req.compiler_version)?;
 
     // Validate that exactly one supported source input is provided
     let input_kind = detect_input_kind(&req)?;
+    check_source_size(&req, state.verify_max_source_bytes)?;
 
     // Ensure the address is a known contract
     let is_contract: Option<(bool,)> =
@@ -195,16 +518,30 @@ pub async fn verify_contract(
         None => return Err(AtlasError::NotFound(format!("address {address} not found")).into()),
     }
 
-    // Fast-path already verified contracts; the insert conflict guard below is
-    // still kept to handle races between concurrent verification requests.
+    // A verified contract can only be re-verified by an exact match, which
+    // replaces the existing verification. The insert conflict guard below
+    // still handles races between concurrent first verifications.
     let already_verified: Option<(String,)> =
         sqlx::query_as("SELECT address FROM contract_abis WHERE address = $1")
             .bind(&address)
             .fetch_optional(&state.pool)
             .await?;
-    if already_verified.is_some() {
-        return Err(AtlasError::Verification(format!("{address} is already verified")).into());
+    let reverify = already_verified.is_some();
+    let strategies = req
+        .match_strategies
+        .as_deref()
+        .unwrap_or(&MatchStrategy::ALL);
+    if reverify && !strategies.contains(&MatchStrategy::Exact) {
+        return Err(AtlasError::Verification(format!(
+            "{address} is already verified; re-verification requires an exact match"
+        ))
+        .into());
     }
+    let strategies = if reverify {
+        &[MatchStrategy::Exact][..]
+    } else {
+        strategies
+    };
 
     // Fetch deployed bytecode from the RPC node
     let deployed_hex = fetch_deployed_bytecode(&state.rpc_url, &address).await?;
@@ -214,53 +551,55 @@ pub async fn verify_contract(
         );
     }
 
-    // Get the solc binary (download if not cached)
-    let solc_path = get_solc_binary(&req.compiler_version, &state.solc_cache_dir).await?;
-
-    // Compile the submitted source
-    let compiled_contract = compile_source(&solc_path, &req).await?;
-
-    // Strip CBOR metadata from both sides before comparing
-    let deployed_bytes = decode_hex_bytecode(&deployed_hex)?;
-    let deployed_stripped = strip_metadata(&deployed_bytes);
-    let compiled_stripped = strip_metadata(&compiled_contract.bytecode);
-    let deployed_cmp = normalize_bytecode_for_comparison(
-        deployed_stripped,
-        &compiled_contract.immutable_references,
-    )?;
-    let compiled_cmp = normalize_bytecode_for_comparison(
-        compiled_stripped,
-        &compiled_contract.immutable_references,
-    )?;
+    // Compile the submitted source, downloading solc if needed
+    let compiled_contract = compile_source(&state, &req).await?;
 
     // eth_getCode returns deployed runtime bytecode, so constructor args are not
     // part of the bytecode comparison. We still parse and persist them as metadata.
-    if deployed_cmp != compiled_cmp {
-        return Err(AtlasError::BytecodeMismatch(
-            "compiled bytecode does not match on-chain bytecode".to_string(),
-        )
+    let deployed_bytes = decode_hex_bytecode(&deployed_hex)?;
+    let Some(match_strategy) = match_bytecode(&deployed_bytes, &compiled_contract, strategies)?
+    else {
+        let tried: Vec<&str> = strategies.iter().map(|s| s.as_str()).collect();
+        return Err(AtlasError::BytecodeMismatch(format!(
+            "compiled bytecode does not match on-chain bytecode (tried: {})",
+            tried.join(", ")
+        ))
         .into());
-    }
+    };
 
+    let immutable_values = resolve_immutable_values(&deployed_bytes, &compiled_contract);
     let constructor_bytes = parse_constructor_args(req.constructor_args.as_deref())?;
     let abi = compiled_contract.abi;
     let verification_settings = extract_verification_settings(&req, input_kind)?;
     let stored_sources = extract_stored_contract_sources(&req, input_kind)?;
+    // Single files are compiled as `contract.sol`; store their AST under the
+    // path the files endpoint lists.
+    let source_asts = compiled_contract.source_asts.map(|mut asts| {
+        if input_kind == VerifyInputKind::SingleFile {
+            if let Some(ast) = asts.remove(SINGLE_FILE_SOURCE_PATH) {
+                asts.insert(single_file_source_path(Some(&req.contract_name)), ast);
+            }
+        }
+        serde_json::Value::Object(asts)
+    });
 
-    // Store verification metadata, but keep existing rows immutable so
-    // re-verification is rejected consistently.
     let constructor_args_bytes: Option<Vec<u8>> = if constructor_bytes.is_empty() {
         None
     } else {
         Some(constructor_bytes)
     };
 
+    let mut tx = state.pool.begin().await?;
+    if reverify {
+        archive_verification(&mut tx, &address, "replaced", None).await?;
+    }
     let insert_result = sqlx::query(
         "INSERT INTO contract_abis
             (address, abi, source_code, compiler_version, optimization_used, runs,
              contract_name, constructor_args, evm_version, license_type,
-             is_multi_file, source_files, verified_at)
-         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, NOW())
+             is_multi_file, source_files, match_strategy, immutable_values,
+             deployed_source_map, source_asts, verified_at)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, NOW())
          ON CONFLICT (address) DO NOTHING",
     )
     .bind(&address)
@@ -275,24 +614,295 @@ pub async fn verify_contract(
     .bind(&req.license_type)
     .bind(stored_sources.is_multi_file)
     .bind(&stored_sources.source_files)
-    .execute(&state.pool)
+    .bind(match_strategy.as_str())
+    .bind((!immutable_values.is_empty()).then_some(sqlx::types::Json(&immutable_values)))
+    .bind(&compiled_contract.source_map)
+    .bind(&source_asts)
+    .execute(&mut *tx)
     .await?;
 
     if insert_result.rows_affected() == 0 {
         return Err(AtlasError::Verification(format!("{address} is already verified")).into());
     }
+    tx.commit().await?;
 
     Ok((
         StatusCode::OK,
         Json(VerifyResponse {
             verified: true,
             abi,
+            match_strategy,
+            immutable_values,
         }),
     ))
 }
 
+const PENDING_VERIFICATION_COLUMNS: &str =
+    "code_hash, contract_name, compiler_version, matched_contracts, registered_at, last_matched_at";
+
+/// POST /api/contracts/pending-verifications
+///
+/// Returns 201 for a new registration and 200 with the existing one when the
+/// code hash is already registered, so CI pipelines can re-run it.
+pub async fn register_pending_verification(
+    State(state): State<Arc<AppState>>,
+    body: Body,
+) -> ApiResult<(StatusCode, Json<PendingVerificationResponse>)> {
+    let body = read_limited(body, state.body_limits.verify).await?;
+    let PendingVerificationRequest {
+        verify: req,
+        code_hash,
+    } = serde_json::from_slice(&body)
+        .map_err(|e| AtlasError::InvalidInput(format!("invalid verification request: {e}")))?;
+    drop(body);
+
+    validate_compiler_version(&req.compiler_version)?;
+    let input_kind = detect_input_kind(&req)?;
+    check_source_size(&req, state.verify_max_source_bytes)?;
+
+    let compiled_contract = compile_source(&state, &req).await?;
+    if !compiled_contract.immutable_references.is_empty() {
+        return Err(AtlasError::Verification(format!(
+            "{} has immutable variables, so its deployed code differs from the compiled code; verify it after deployment",
+            req.contract_name
+        ))
+        .into());
+    }
+
+    let compiled_hash = format!("{:#x}", keccak256(&compiled_contract.bytecode));
+    if let Some(code_hash) = code_hash {
+        if !code_hash.trim().eq_ignore_ascii_case(&compiled_hash) {
+            return Err(AtlasError::BytecodeMismatch(format!(
+                "source compiles to runtime code hash {compiled_hash}, not {}",
+                code_hash.trim()
+            ))
+            .into());
+        }
+    }
+
+    let verification_settings = extract_verification_settings(&req, input_kind)?;
+    let stored_sources = extract_stored_contract_sources(&req, input_kind)?;
+    let source_asts = compiled_contract.source_asts.map(|mut asts| {
+        if input_kind == VerifyInputKind::SingleFile {
+            if let Some(ast) = asts.remove(SINGLE_FILE_SOURCE_PATH) {
+                asts.insert(single_file_source_path(Some(&req.contract_name)), ast);
+            }
+        }
+        serde_json::Value::Object(asts)
+    });
+
+    let inserted: Option<PendingVerificationResponse> = sqlx::query_as(&format!(
+        "INSERT INTO pending_verifications
+            (code_hash, abi, source_code, compiler_version, optimization_used, runs,
+             contract_name, evm_version, license_type, is_multi_file, source_files,
+             deployed_source_map, source_asts)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+         ON CONFLICT (code_hash) DO NOTHING
+         RETURNING {PENDING_VERIFICATION_COLUMNS}"
+    ))
+    .bind(&compiled_hash)
+    .bind(&compiled_contract.abi)
+    .bind(&stored_sources.source_code)
+    .bind(&req.compiler_version)
+    .bind(verification_settings.optimization_enabled)
+    .bind(verification_settings.optimization_runs)
+    .bind(&req.contract_name)
+    .bind(&verification_settings.evm_version)
+    .bind(&req.license_type)
+    .bind(stored_sources.is_multi_file)
+    .bind(&stored_sources.source_files)
+    .bind(&compiled_contract.source_map)
+    .bind(&source_asts)
+    .fetch_optional(&state.pool)
+    .await?;
+
+    match inserted {
+        Some(registration) => Ok((StatusCode::CREATED, Json(registration))),
+        None => Ok((
+            StatusCode::OK,
+            Json(fetch_pending_verification(&state, &compiled_hash).await?),
+        )),
+    }
+}
+
+/// GET /api/contracts/pending-verifications/:code_hash
+pub async fn get_pending_verification(
+    State(state): State<Arc<AppState>>,
+    Path(code_hash): Path<String>,
+) -> ApiResult<Json<PendingVerificationResponse>> {
+    let code_hash = code_hash.trim().to_lowercase();
+    Ok(Json(fetch_pending_verification(&state, &code_hash).await?))
+}
+
+async fn fetch_pending_verification(
+    state: &AppState,
+    code_hash: &str,
+) -> Result<PendingVerificationResponse, AtlasError> {
+    sqlx::query_as(&format!(
+        "SELECT {PENDING_VERIFICATION_COLUMNS} FROM pending_verifications WHERE code_hash = $1"
+    ))
+    .bind(code_hash)
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or_else(|| AtlasError::NotFound(format!("no pending verification for {code_hash}")))
+}
+
+// ── Verification history (admin) ──────────────────────────────────────────────
+
+/// A verification removed from or restored to `contract_abis`.
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct VerificationHistoryEntry {
+    pub id: i64,
+    pub address: String,
+    /// `unverified`, `replaced` or `restored`
+    pub action: String,
+    pub reason: Option<String>,
+    pub contract_name: Option<String>,
+    pub compiler_version: Option<String>,
+    pub match_strategy: Option<String>,
+    /// When the affected verification was made
+    pub verified_at: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+const VERIFICATION_HISTORY_COLUMNS: &str = "id, address, action, reason,
+    verification->>'contract_name' AS contract_name,
+    verification->>'compiler_version' AS compiler_version,
+    verification->>'match_strategy' AS match_strategy,
+    verification->>'verified_at' AS verified_at,
+    created_at";
+
+const MAX_UNVERIFY_REASON_LEN: usize = 500;
+
+#[derive(Debug, Default, Deserialize)]
+pub struct UnverifyRequest {
+    pub reason: Option<String>,
+}
+
+/// Move a contract's verification into `verification_history`. Returns
+/// `None` if the contract was not verified.
+async fn archive_verification(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    address: &str,
+    action: &str,
+    reason: Option<&str>,
+) -> Result<Option<VerificationHistoryEntry>, AtlasError> {
+    Ok(sqlx::query_as(&format!(
+        "WITH removed AS (DELETE FROM contract_abis WHERE address = $1 RETURNING *)
+         INSERT INTO verification_history (address, action, reason, verification)
+         SELECT r.address, $2, $3, to_jsonb(r) FROM removed r
+         RETURNING {VERIFICATION_HISTORY_COLUMNS}"
+    ))
+    .bind(address)
+    .bind(action)
+    .bind(reason)
+    .fetch_optional(&mut **tx)
+    .await?)
+}
+
+/// POST /api/admin/contracts/:address/unverify — remove a contract's
+/// verification, keeping it in the history (admin)
+pub async fn unverify_contract(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Path(address): Path<String>,
+    request: Option<Json<UnverifyRequest>>,
+) -> ApiResult<Json<VerificationHistoryEntry>> {
+    require_admin(&state, &headers)?;
+    let address = normalize_address(&address);
+    let request = request.map(|Json(r)| r).unwrap_or_default();
+    let reason = request
+        .reason
+        .as_deref()
+        .map(str::trim)
+        .filter(|reason| !reason.is_empty());
+    if reason.is_some_and(|reason| reason.len() > MAX_UNVERIFY_REASON_LEN) {
+        return Err(AtlasError::InvalidInput(format!(
+            "reason must be at most {MAX_UNVERIFY_REASON_LEN} bytes"
+        ))
+        .into());
+    }
+
+    let mut tx = state.pool.begin().await?;
+    let entry = archive_verification(&mut tx, &address, "unverified", reason)
+        .await?
+        .ok_or_else(|| AtlasError::NotFound(format!("{address} is not verified")))?;
+    tx.commit().await?;
+    Ok(Json(entry))
+}
+
+/// POST /api/admin/contracts/:address/reverify — restore the verification
+/// most recently removed by an unverify (admin)
+pub async fn reverify_contract(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Path(address): Path<String>,
+) -> ApiResult<Json<VerificationHistoryEntry>> {
+    require_admin(&state, &headers)?;
+    let address = normalize_address(&address);
+
+    let mut tx = state.pool.begin().await?;
+    let verification: serde_json::Value = sqlx::query_scalar(
+        "SELECT verification FROM verification_history
+         WHERE address = $1 AND action = 'unverified'
+         ORDER BY id DESC LIMIT 1",
+    )
+    .bind(&address)
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| {
+        AtlasError::NotFound(format!("{address} has no removed verification to restore"))
+    })?;
+
+    let restored = sqlx::query(
+        "INSERT INTO contract_abis
+         SELECT * FROM jsonb_populate_record(NULL::contract_abis, $1)
+         ON CONFLICT (address) DO NOTHING",
+    )
+    .bind(&verification)
+    .execute(&mut *tx)
+    .await?;
+    if restored.rows_affected() == 0 {
+        return Err(AtlasError::Verification(format!("{address} is already verified")).into());
+    }
+
+    let entry: VerificationHistoryEntry = sqlx::query_as(&format!(
+        "INSERT INTO verification_history (address, action, verification)
+         VALUES ($1, 'restored', $2)
+         RETURNING {VERIFICATION_HISTORY_COLUMNS}"
+    ))
+    .bind(&address)
+    .bind(&verification)
+    .fetch_one(&mut *tx)
+    .await?;
+    tx.commit().await?;
+    Ok(Json(entry))
+}
+
+/// GET /api/admin/contracts/:address/verification-history — removed and
+/// restored verifications, newest first (admin)
+pub async fn get_verification_history(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Path(address): Path<String>,
+) -> ApiResult<Json<Vec<VerificationHistoryEntry>>> {
+    require_admin(&state, &headers)?;
+    let address = normalize_address(&address);
+    let entries: Vec<VerificationHistoryEntry> = sqlx::query_as(&format!(
+        "SELECT {VERIFICATION_HISTORY_COLUMNS} FROM verification_history
+         WHERE address = $1 ORDER BY id DESC"
+    ))
+    .bind(&address)
+    .fetch_all(&state.pool)
+    .await?;
+    Ok(Json(entries))
+}
+
 // ── Helpers ───────────────────────────────────────────────────────────────────
 
+/// Source path a single-file submission is compiled under.
+const SINGLE_FILE_SOURCE_PATH: &str = "contract.sol";
+
 fn normalize_address(address: &str) -> String {
     if address.starts_with("0x") {
         address.to_lowercase()
@@ -442,19 +1052,17 @@ fn solc_binary_target(os: &str, arch: &str, version: &str) -> Result<&'static st
     }
 }
 
-/// Compile source and return the deployed bytecode bytes for the specified contract.
+/// Run solc on a standard-JSON input and return its output.
 async fn compile_standard_json(
     solc_path: &PathBuf,
-    req: &VerifyRequest,
+    input_str: &str,
     dir: tempfile::TempDir,
 ) -> Result<serde_json::Value, AtlasError> {
-    let input = build_solc_input(req, true)?;
-    let input_str = serde_json::to_string(&input)
-        .map_err(|e| AtlasError::Internal(format!("failed to serialize solc input: {e}")))?;
-
     let mut child = tokio::process::Command::new(solc_path)
         .arg("--standard-json")
         .current_dir(dir.path())
+        // A verification request that times out must not leave solc running.
+        .kill_on_drop(true)
         .stdin(std::process::Stdio::piped())
         .stdout(std::process::Stdio::piped())
         .stderr(std::process::Stdio::piped())
@@ -490,13 +1098,52 @@ async fn compile_standard_json(
 }
 
 /// Compile submitted source and return runtime bytecode, ABI, and immutable refs.
+///
+/// solc's output is cached in `compilation_cache` by compiler version and the
+/// keccak256 of the serialized input, so verifying a clone of an already
+/// compiled contract neither downloads solc nor runs it.
 async fn compile_source(
-    solc_path: &PathBuf,
+    state: &AppState,
     req: &VerifyRequest,
 ) -> Result<CompiledContract, AtlasError> {
-    let dir = tempfile::tempdir()
-        .map_err(|e| AtlasError::Internal(format!("failed to create temp dir: {e}")))?;
-    let json = compile_standard_json(solc_path, req, dir).await?;
+    let input = build_solc_input(req, true)?;
+    let input_str = serde_json::to_string(&input)
+        .map_err(|e| AtlasError::Internal(format!("failed to serialize solc input: {e}")))?;
+    let input_hash = format!("{:#x}", keccak256(input_str.as_bytes()));
+
+    let cached: Option<(serde_json::Value,)> = sqlx::query_as(
+        "UPDATE compilation_cache SET hits = hits + 1, last_used_at = NOW()
+         WHERE compiler_version = $1 AND input_hash = $2
+         RETURNING output",
+    )
+    .bind(&req.compiler_version)
+    .bind(&input_hash)
+    .fetch_optional(&state.pool)
+    .await?;
+
+    let json = match cached {
+        Some((output,)) => {
+            tracing::debug!(input_hash, "reusing cached solc output");
+            output
+        }
+        None => {
+            let solc_path = get_solc_binary(&req.compiler_version, &state.solc_cache_dir).await?;
+            let dir = tempfile::tempdir()
+                .map_err(|e| AtlasError::Internal(format!("failed to create temp dir: {e}")))?;
+            let output = compile_standard_json(&solc_path, &input_str, dir).await?;
+            sqlx::query(
+                "INSERT INTO compilation_cache (compiler_version, input_hash, output)
+                 VALUES ($1, $2, $3)
+                 ON CONFLICT (compiler_version, input_hash) DO NOTHING",
+            )
+            .bind(&req.compiler_version)
+            .bind(&input_hash)
+            .bind(&output)
+            .execute(&state.pool)
+            .await?;
+            output
+        }
+    };
     extract_compiled_contract(&json, &req.contract_name)
 }
 
@@ -580,10 +1227,6 @@ fn build_single_file_standard_json_input(
         .ok_or_else(|| AtlasError::InvalidInput("source_code is required".to_string()))?;
     let runs = req.optimization_runs.unwrap_or(200);
     let optimization_enabled = req.optimization_enabled.unwrap_or(false);
-    let mut contract_outputs = vec![serde_json::json!("abi")];
-    if include_deployed_bytecode {
-        contract_outputs.push(serde_json::json!("evm.deployedBytecode"));
-    }
 
     let mut settings = serde_json::Map::from_iter([
         (
@@ -595,9 +1238,7 @@ fn build_single_file_standard_json_input(
         ),
         (
             "outputSelection".to_string(),
-            serde_json::json!({
-                "*": { "*": contract_outputs }
-            }),
+            build_output_selection(include_deployed_bytecode, true),
         ),
     ]);
     if let Some(evm_version) = req.evm_version.as_deref() {
@@ -610,7 +1251,7 @@ fn build_single_file_standard_json_input(
     Ok(serde_json::json!({
         "language": "Solidity",
         "sources": {
-            "contract.sol": {
+            SINGLE_FILE_SOURCE_PATH: {
                 "content": source_code,
             }
         },
@@ -626,6 +1267,8 @@ fn build_provided_standard_json_input(
     let root = input.as_object_mut().ok_or_else(|| {
         AtlasError::InvalidInput("standard_json_input must be a JSON object".to_string())
     })?;
+    // Yul objects have no ABI or AST, and solc rejects requests for them.
+    let is_yul = root.get("language").and_then(|v| v.as_str()) == Some("Yul");
 
     let settings = root
         .entry("settings")
@@ -635,21 +1278,31 @@ fn build_provided_standard_json_input(
     })?;
     settings_obj.insert(
         "outputSelection".to_string(),
-        build_output_selection(include_deployed_bytecode),
+        build_output_selection(include_deployed_bytecode, !is_yul),
     );
 
     Ok(input)
 }
 
-fn build_output_selection(include_deployed_bytecode: bool) -> serde_json::Value {
-    let mut contract_outputs = vec![serde_json::json!("abi")];
+/// The AST is only requested alongside the bytecode, to name the immutables.
+fn build_output_selection(include_deployed_bytecode: bool, is_solidity: bool) -> serde_json::Value {
+    let mut contract_outputs = Vec::new();
+    if is_solidity {
+        contract_outputs.push(serde_json::json!("abi"));
+    }
     if include_deployed_bytecode {
         contract_outputs.push(serde_json::json!("evm.deployedBytecode"));
     }
 
-    serde_json::json!({
-        "*": { "*": contract_outputs }
-    })
+    if include_deployed_bytecode && is_solidity {
+        serde_json::json!({
+            "*": { "": ["ast"], "*": contract_outputs }
+        })
+    } else {
+        serde_json::json!({
+            "*": { "*": contract_outputs }
+        })
+    }
 }
 
 fn parse_standard_json_input(req: &VerifyRequest) -> Result<serde_json::Value, AtlasError> {
@@ -663,6 +1316,20 @@ fn parse_standard_json_input(req: &VerifyRequest) -> Result<serde_json::Value, A
     })
 }
 
+fn check_source_size(req: &VerifyRequest, max_bytes: usize) -> Result<(), AtlasError> {
+    let (field, len) = match (&req.source_code, &req.standard_json_input) {
+        (Some(source), _) => ("source_code", source.len()),
+        (None, Some(input)) => ("standard_json_input", input.len()),
+        (None, None) => return Ok(()),
+    };
+    if len > max_bytes {
+        return Err(AtlasError::PayloadTooLarge(format!(
+            "{field} is {len} bytes, over the {max_bytes} byte limit for verification"
+        )));
+    }
+    Ok(())
+}
+
 fn detect_input_kind(req: &VerifyRequest) -> Result<VerifyInputKind, AtlasError> {
     match (&req.source_code, &req.standard_json_input) {
         (Some(_), None) => Ok(VerifyInputKind::SingleFile),
@@ -826,20 +1493,30 @@ fn extract_compiled_contract(
                 )));
             }
 
+            // Yul objects have no ABI.
             let abi = contract
                 .get("abi")
                 .cloned()
-                .ok_or_else(|| AtlasError::Compilation("no abi in solc output".to_string()))?;
+                .unwrap_or_else(|| serde_json::json!([]));
             let immutable_references = contract
                 .pointer("/evm/deployedBytecode/immutableReferences")
                 .map(extract_immutable_references)
                 .transpose()?
                 .unwrap_or_default();
+            let immutable_declarations = find_immutable_declarations(json, &immutable_references);
+            let source_map = contract
+                .pointer("/evm/deployedBytecode/sourceMap")
+                .and_then(|v| v.as_str())
+                .filter(|map| !map.is_empty())
+                .map(str::to_string);
 
             return Ok(CompiledContract {
                 bytecode: decode_hex_bytecode(&format!("0x{bytecode}"))?,
                 abi,
                 immutable_references,
+                immutable_declarations,
+                source_map,
+                source_asts: extract_source_asts(json),
             });
         }
     }
@@ -849,10 +1526,12 @@ fn extract_compiled_contract(
     )))
 }
 
-/// Strip CBOR-encoded metadata suffix from EVM bytecode.
+/// Strip the CBOR-encoded metadata suffix from EVM bytecode.
 ///
-/// Solc appends a CBOR blob; the last 2 bytes encode its length (big-endian u16).
-/// We strip `metadata_len + 2` bytes from the end.
+/// Solc appends a CBOR map; the last 2 bytes encode its length (big-endian
+/// u16). Bytecode without one (raw Yul, `--no-cbor-metadata`, other tooling)
+/// is returned unchanged rather than losing its tail to a length that is
+/// really just the last two opcodes.
 pub fn strip_metadata(bytecode: &[u8]) -> &[u8] {
     if bytecode.len() < 2 {
         return bytecode;
@@ -860,32 +1539,70 @@ pub fn strip_metadata(bytecode: &[u8]) -> &[u8] {
     let meta_len =
         u16::from_be_bytes([bytecode[bytecode.len() - 2], bytecode[bytecode.len() - 1]]) as usize;
     let total_strip = meta_len + 2;
-    if total_strip > bytecode.len() {
+    if meta_len == 0 || total_strip > bytecode.len() {
         return bytecode;
     }
-    &bytecode[..bytecode.len() - total_strip]
+    let code_len = bytecode.len() - total_strip;
+    // Major type 5 (map) with fewer than 24 entries: 0xa0..=0xb7
+    if !(0xa0..=0xb7).contains(&bytecode[code_len]) {
+        return bytecode;
+    }
+    &bytecode[..code_len]
 }
 
-fn normalize_bytecode_for_comparison(
-    bytecode: &[u8],
+/// Match compiled against deployed bytecode with the first of `strategies`
+/// (in [`MatchStrategy`] order) that succeeds.
+fn match_bytecode(
+    deployed: &[u8],
+    compiled: &CompiledContract,
+    strategies: &[MatchStrategy],
+) -> Result<Option<MatchStrategy>, AtlasError> {
+    let patched = patch_immutables(&compiled.bytecode, deployed, &compiled.immutable_references)?;
+    for strategy in MatchStrategy::ALL {
+        if !strategies.contains(&strategy) {
+            continue;
+        }
+        let matched = match strategy {
+            MatchStrategy::Exact => deployed == compiled.bytecode.as_slice(),
+            MatchStrategy::Immutables => patched.as_deref() == Some(deployed),
+            MatchStrategy::Metadata => patched
+                .as_deref()
+                .is_some_and(|patched| strip_metadata(patched) == strip_metadata(deployed)),
+        };
+        if matched {
+            return Ok(Some(strategy));
+        }
+    }
+    Ok(None)
+}
+
+/// Copy the deployed values of the compiler's immutable slots into the
+/// compiled bytecode, where solc leaves them zeroed. `None` when the deployed
+/// code is too short to hold a slot, i.e. cannot match.
+fn patch_immutables(
+    compiled: &[u8],
+    deployed: &[u8],
     immutable_references: &[ImmutableReference],
-) -> Result<Vec<u8>, AtlasError> {
-    let mut normalized = bytecode.to_vec();
+) -> Result<Option<Vec<u8>>, AtlasError> {
+    let mut patched = compiled.to_vec();
 
     for reference in immutable_references {
         let end = reference.start.saturating_add(reference.length);
-        if end > normalized.len() {
+        if end > patched.len() {
             return Err(AtlasError::Compilation(format!(
                 "immutable reference out of bounds: start={}, length={}, bytecode_len={}",
                 reference.start,
                 reference.length,
-                normalized.len()
+                patched.len()
             )));
         }
-        normalized[reference.start..end].fill(0);
+        if end > deployed.len() {
+            return Ok(None);
+        }
+        patched[reference.start..end].copy_from_slice(&deployed[reference.start..end]);
     }
 
-    Ok(normalized)
+    Ok(Some(patched))
 }
 
 fn extract_immutable_references(
@@ -898,7 +1615,12 @@ fn extract_immutable_references(
         ));
     };
 
-    for entries in map.values() {
+    for (id, entries) in map {
+        let id = id.parse::<u64>().map_err(|_| {
+            AtlasError::Compilation(format!(
+                "invalid immutableReferences id {id:?} in solc output"
+            ))
+        })?;
         let Some(entries) = entries.as_array() else {
             return Err(AtlasError::Compilation(
                 "invalid immutableReferences entry in solc output".to_string(),
@@ -918,13 +1640,191 @@ fn extract_immutable_references(
                         "missing immutable reference length in solc output".to_string(),
                     )
                 })? as usize;
-            refs.push(ImmutableReference { start, length });
+            refs.push(ImmutableReference { id, start, length });
         }
     }
 
     Ok(refs)
 }
 
+/// `{path: {id, ast}}` from solc's `sources` output; the ids are the file
+/// indexes the source map refers to. `None` for Yul, which has no AST.
+fn extract_source_asts(
+    json: &serde_json::Value,
+) -> Option<serde_json::Map<String, serde_json::Value>> {
+    let asts: serde_json::Map<String, serde_json::Value> = json
+        .get("sources")?
+        .as_object()?
+        .iter()
+        .filter_map(|(path, source)| {
+            let ast = source.get("ast")?;
+            Some((
+                path.clone(),
+                serde_json::json!({ "id": source.get("id"), "ast": ast }),
+            ))
+        })
+        .collect();
+    (!asts.is_empty()).then_some(asts)
+}
+
+/// Find the AST declarations of the referenced immutables in solc's `sources`
+/// output. Missing ones (Yul, or no AST in the output) are simply left out.
+fn find_immutable_declarations(
+    json: &serde_json::Value,
+    references: &[ImmutableReference],
+) -> HashMap<u64, ImmutableDeclaration> {
+    let wanted: HashSet<u64> = references.iter().map(|r| r.id).collect();
+    let mut found = HashMap::new();
+    if wanted.is_empty() {
+        return found;
+    }
+
+    let mut stack: Vec<&serde_json::Value> = json
+        .get("sources")
+        .and_then(|s| s.as_object())
+        .into_iter()
+        .flat_map(|sources| sources.values())
+        .filter_map(|source| source.get("ast"))
+        .collect();
+    while let Some(node) = stack.pop() {
+        match node {
+            serde_json::Value::Object(obj) => {
+                if obj.get("nodeType").and_then(|v| v.as_str()) == Some("VariableDeclaration") {
+                    let id = obj.get("id").and_then(|v| v.as_u64());
+                    let name = obj.get("name").and_then(|v| v.as_str());
+                    if let (Some(id), Some(name)) = (id, name) {
+                        if wanted.contains(&id) {
+                            let type_name = node
+                                .pointer("/typeDescriptions/typeString")
+                                .and_then(|v| v.as_str())
+                                .map(str::to_string);
+                            found.insert(
+                                id,
+                                ImmutableDeclaration {
+                                    name: name.to_string(),
+                                    type_name,
+                                },
+                            );
+                            continue;
+                        }
+                    }
+                }
+                stack.extend(obj.values());
+            }
+            serde_json::Value::Array(items) => stack.extend(items),
+            _ => {}
+        }
+    }
+    found
+}
+
+/// Read each immutable's value out of the deployed bytecode. Every reference
+/// to one variable holds the same word, so the first is used.
+fn resolve_immutable_values(deployed: &[u8], compiled: &CompiledContract) -> Vec<ImmutableValue> {
+    let mut first_refs: BTreeMap<u64, &ImmutableReference> = BTreeMap::new();
+    for reference in &compiled.immutable_references {
+        first_refs.entry(reference.id).or_insert(reference);
+    }
+
+    first_refs
+        .into_iter()
+        .filter_map(|(id, reference)| {
+            let word = deployed.get(reference.start..reference.start + reference.length)?;
+            let declaration = compiled.immutable_declarations.get(&id);
+            let type_name = declaration.and_then(|d| d.type_name.clone());
+            Some(ImmutableValue {
+                id,
+                name: declaration.map(|d| d.name.clone()),
+                decoded: type_name
+                    .as_deref()
+                    .and_then(|t| decode_immutable_word(t, word)),
+                type_name,
+                value: format!("0x{}", hex::encode(word)),
+            })
+        })
+        .collect()
+}
+
+/// Render a 32-byte immutable word for the common value types.
+fn decode_immutable_word(type_name: &str, word: &[u8]) -> Option<String> {
+    if word.len() != 32 {
+        return None;
+    }
+    if type_name == "address"
+        || type_name == "address payable"
+        || type_name.starts_with("contract ")
+    {
+        return Some(format!("0x{}", hex::encode(&word[12..])));
+    }
+    if type_name == "bool" {
+        return Some((word[31] != 0).to_string());
+    }
+    if type_name.starts_with("uint") {
+        return Some(U256::from_be_slice(word).to_string());
+    }
+    if type_name.starts_with("int") {
+        return Some(I256::from_raw(U256::from_be_slice(word)).to_string());
+    }
+    None
+}
+
+/// Decode ABI-encoded constructor arguments with the constructor in `abi`.
+/// `None` when the ABI has no constructor or the bytes don't match its inputs.
+fn decode_constructor_arguments(
+    abi: &serde_json::Value,
+    args: &[u8],
+) -> Option<Vec<ConstructorArgument>> {
+    let abi: JsonAbi = serde_json::from_value(abi.clone()).ok()?;
+    let inputs = abi.constructor?.inputs;
+    let types = inputs
+        .iter()
+        .map(|param| param.resolve())
+        .collect::<Result<Vec<_>, _>>()
+        .ok()?;
+    let values = match DynSolType::Tuple(types.clone())
+        .abi_decode_params(args)
+        .ok()?
+    {
+        DynSolValue::Tuple(values) => values,
+        _ => return None,
+    };
+
+    Some(
+        inputs
+            .into_iter()
+            .zip(types)
+            .zip(values)
+            .map(|((param, ty), value)| ConstructorArgument {
+                name: param.name,
+                type_name: ty.sol_type_name().into_owned(),
+                value: abi_value_to_json(&value),
+            })
+            .collect(),
+    )
+}
+
+pub(crate) fn abi_value_to_json(value: &DynSolValue) -> serde_json::Value {
+    match value {
+        DynSolValue::Bool(value) => serde_json::Value::Bool(*value),
+        DynSolValue::Int(value, _) => serde_json::Value::String(value.to_string()),
+        DynSolValue::Uint(value, _) => serde_json::Value::String(value.to_string()),
+        DynSolValue::FixedBytes(word, size) => {
+            serde_json::Value::String(format!("0x{}", hex::encode(&word[..*size])))
+        }
+        DynSolValue::Address(address) => serde_json::Value::String(address_to_hex(address)),
+        DynSolValue::Function(function) => {
+            serde_json::Value::String(format!("0x{}", hex::encode(function)))
+        }
+        DynSolValue::Bytes(bytes) => serde_json::Value::String(format!("0x{}", hex::encode(bytes))),
+        DynSolValue::String(value) => serde_json::Value::String(value.clone()),
+        DynSolValue::Array(items) => items.iter().map(abi_value_to_json).collect(),
+        other => other
+            .as_fixed_seq()
+            .map(|items| items.iter().map(abi_value_to_json).collect())
+            .unwrap_or(serde_json::Value::Null),
+    }
+}
+
 /// Decode a hex-encoded bytecode string (with or without 0x prefix) to bytes.
 fn decode_hex_bytecode(hex_str: &str) -> Result<Vec<u8>, AtlasError> {
     let stripped = hex_str.trim_start_matches("0x");
@@ -980,6 +1880,43 @@ async fn fetch_deployed_bytecode(rpc_url: &str, address: &str) -> Result<String,
 mod tests {
     use super::*;
 
+    #[test]
+    fn source_files_by_path_prefers_stored_files() {
+        let files = source_files_by_path(
+            Some("{\"language\":\"Solidity\"}".to_string()),
+            Some("Token".to_string()),
+            Some(serde_json::json!({
+                "src/Token.sol": "contract Token {}",
+                "@openzeppelin/contracts/token/ERC20/ERC20.sol": "contract ERC20 {}",
+            })),
+        );
+        assert_eq!(
+            files.keys().collect::<Vec<_>>(),
+            [
+                "@openzeppelin/contracts/token/ERC20/ERC20.sol",
+                "src/Token.sol"
+            ]
+        );
+
+        let files = source_files_by_path(
+            Some("contract Token {}".to_string()),
+            Some("Token".to_string()),
+            None,
+        );
+        assert_eq!(files["Token.sol"], "contract Token {}");
+        assert!(source_files_by_path(None, None, None).is_empty());
+    }
+
+    #[test]
+    fn source_content_type_serves_text() {
+        assert_eq!(
+            source_content_type("src/Token.sol"),
+            "text/plain; charset=utf-8"
+        );
+        assert_eq!(source_content_type("foundry.JSON"), "application/json");
+        assert_eq!(source_content_type("LICENSE"), "text/plain; charset=utf-8");
+    }
+
     #[test]
     fn strip_metadata_removes_cbor_suffix() {
         // Simulate bytecode with a 3-byte CBOR blob + 2-byte length header
@@ -1067,30 +2004,200 @@ mod tests {
     }
 
     #[test]
-    fn normalize_bytecode_for_comparison_zeroes_immutable_ranges() {
-        let bytecode = vec![0xaa, 0xbb, 0x01, 0x02, 0x03, 0xcc];
-        let normalized = normalize_bytecode_for_comparison(
-            &bytecode,
+    fn strip_metadata_keeps_bytecode_without_cbor_metadata() {
+        // Ends in PUSH1 0x03 like plain Yul output; 0x6001 is not a CBOR map
+        let bytecode: Vec<u8> = vec![0x60, 0x01, 0x60, 0x00, 0x03];
+        assert_eq!(strip_metadata(&bytecode), bytecode.as_slice());
+        // A zero length is never solc metadata either
+        let bytecode: Vec<u8> = vec![0xDE, 0xAD, 0x00, 0x00];
+        assert_eq!(strip_metadata(&bytecode), bytecode.as_slice());
+    }
+
+    #[test]
+    fn patch_immutables_copies_deployed_values() {
+        let compiled = vec![0xaa, 0xbb, 0x00, 0x00, 0x00, 0xcc];
+        let deployed = vec![0xaa, 0xbb, 0x01, 0x02, 0x03, 0xcc];
+        let patched = patch_immutables(
+            &compiled,
+            &deployed,
             &[ImmutableReference {
+                id: 1,
                 start: 2,
                 length: 3,
             }],
         )
         .unwrap();
-        assert_eq!(normalized, vec![0xaa, 0xbb, 0x00, 0x00, 0x00, 0xcc]);
+        assert_eq!(patched, Some(deployed));
     }
 
     #[test]
-    fn normalize_bytecode_for_comparison_rejects_out_of_bounds_ranges() {
-        let err = normalize_bytecode_for_comparison(
-            &[0xaa, 0xbb],
-            &[ImmutableReference {
-                start: 1,
-                length: 4,
-            }],
-        )
-        .unwrap_err();
+    fn patch_immutables_rejects_out_of_bounds_ranges() {
+        let reference = ImmutableReference {
+            id: 1,
+            start: 1,
+            length: 4,
+        };
+        let err = patch_immutables(&[0xaa, 0xbb], &[0xaa; 8], std::slice::from_ref(&reference))
+            .unwrap_err();
         assert!(matches!(err, AtlasError::Compilation(_)));
+
+        // Deployed code too short to hold the slot just cannot match
+        let patched = patch_immutables(&[0xaa; 8], &[0xaa, 0xbb], &[reference]).unwrap();
+        assert_eq!(patched, None);
+    }
+
+    fn compiled(
+        bytecode: Vec<u8>,
+        immutable_references: Vec<ImmutableReference>,
+    ) -> CompiledContract {
+        CompiledContract {
+            bytecode,
+            abi: serde_json::json!([]),
+            immutable_references,
+            immutable_declarations: HashMap::new(),
+            source_map: None,
+            source_asts: None,
+        }
+    }
+
+    #[test]
+    fn match_bytecode_reports_the_strictest_strategy_that_matches() {
+        let metadata = |hash: u8| vec![0xa1, 0x64, hash, 0x00, 0x03];
+        let code = [vec![0x60, 0x80, 0x00, 0x00, 0x56], metadata(0x11)].concat();
+        let slot = vec![ImmutableReference {
+            id: 1,
+            start: 2,
+            length: 2,
+        }];
+
+        let exact = compiled(code.clone(), vec![]);
+        assert_eq!(
+            match_bytecode(&code, &exact, &MatchStrategy::ALL).unwrap(),
+            Some(MatchStrategy::Exact)
+        );
+
+        let deployed = [vec![0x60, 0x80, 0xbe, 0xef, 0x56], metadata(0x11)].concat();
+        let with_slot = compiled(code, slot);
+        assert_eq!(
+            match_bytecode(&deployed, &with_slot, &MatchStrategy::ALL).unwrap(),
+            Some(MatchStrategy::Immutables)
+        );
+
+        let rebuilt = [vec![0x60, 0x80, 0xbe, 0xef, 0x56], metadata(0x22)].concat();
+        assert_eq!(
+            match_bytecode(&rebuilt, &with_slot, &MatchStrategy::ALL).unwrap(),
+            Some(MatchStrategy::Metadata)
+        );
+
+        let different = [vec![0x60, 0x40, 0xbe, 0xef, 0x56], metadata(0x11)].concat();
+        assert_eq!(
+            match_bytecode(&different, &with_slot, &MatchStrategy::ALL).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn match_bytecode_only_uses_the_allowed_strategies() {
+        let compiled_code = compiled(vec![0x60, 0x00, 0xa1, 0x64, 0x11, 0x00, 0x03], vec![]);
+        let deployed = vec![0x60, 0x00, 0xa1, 0x64, 0x22, 0x00, 0x03];
+        assert_eq!(
+            match_bytecode(&deployed, &compiled_code, &[MatchStrategy::Exact]).unwrap(),
+            None
+        );
+        assert_eq!(
+            match_bytecode(&deployed, &compiled_code, &[MatchStrategy::Metadata]).unwrap(),
+            Some(MatchStrategy::Metadata)
+        );
+    }
+
+    #[test]
+    fn match_bytecode_compares_yul_output_without_metadata_in_full() {
+        // No CBOR suffix: the trailing bytes are code and must still match
+        let compiled_code = compiled(vec![0x60, 0x2a, 0x60, 0x00, 0x52, 0x60, 0x20], vec![]);
+        let deployed = vec![0x60, 0x2a, 0x60, 0x00, 0x52, 0x60, 0x40];
+        assert_eq!(
+            match_bytecode(&deployed, &compiled_code, &MatchStrategy::ALL).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn build_provided_standard_json_input_skips_abi_for_yul() {
+        let req = VerifyRequest {
+            source_code: None,
+            standard_json_input: Some(
+                serde_json::json!({
+                    "language": "Yul",
+                    "sources": { "A.yul": { "content": "object \"A\" { code { } }" } }
+                })
+                .to_string(),
+            ),
+            compiler_version: "v0.8.20+commit.a1b79de6".to_string(),
+            optimization_enabled: None,
+            optimization_runs: None,
+            contract_name: "A".to_string(),
+            constructor_args: None,
+            evm_version: None,
+            license_type: None,
+            match_strategies: None,
+        };
+
+        let input = build_provided_standard_json_input(&req, true).unwrap();
+        assert_eq!(
+            input.pointer("/settings/outputSelection/*/*").unwrap(),
+            &serde_json::json!(["evm.deployedBytecode"])
+        );
+    }
+
+    #[test]
+    fn extract_compiled_contract_defaults_missing_abi_for_yul_objects() {
+        let output = serde_json::json!({
+            "contracts": {
+                "A.yul": {
+                    "A": { "evm": { "deployedBytecode": { "object": "602a60005260206000f3" } } }
+                }
+            }
+        });
+        let contract = extract_compiled_contract(&output, "A").unwrap();
+        assert_eq!(contract.abi, serde_json::json!([]));
+        assert_eq!(contract.bytecode.len(), 10);
+    }
+
+    #[test]
+    fn extract_compiled_contract_keeps_source_map_and_asts() {
+        let output = serde_json::json!({
+            "sources": {
+                "src/A.sol": { "id": 0, "ast": { "nodeType": "SourceUnit", "id": 12 } },
+                "src/B.sol": { "id": 1, "ast": { "nodeType": "SourceUnit", "id": 30 } }
+            },
+            "contracts": {
+                "src/A.sol": {
+                    "A": { "abi": [], "evm": { "deployedBytecode": {
+                        "object": "6080",
+                        "sourceMap": "25:60:0:-:0;;;;"
+                    } } }
+                }
+            }
+        });
+        let contract = extract_compiled_contract(&output, "A").unwrap();
+        assert_eq!(contract.source_map.as_deref(), Some("25:60:0:-:0;;;;"));
+        let asts = contract.source_asts.unwrap();
+        assert_eq!(asts.len(), 2);
+        assert_eq!(
+            asts["src/B.sol"],
+            serde_json::json!({ "id": 1, "ast": { "nodeType": "SourceUnit", "id": 30 } })
+        );
+
+        // Yul output has neither.
+        let output = serde_json::json!({
+            "sources": { "A.yul": { "id": 0 } },
+            "contracts": {
+                "A.yul": { "A": { "evm": { "deployedBytecode": { "object": "6080", "sourceMap": "" } } } }
+            }
+        });
+        let contract = extract_compiled_contract(&output, "A").unwrap();
+        assert_eq!(contract.source_map, None);
+        assert_eq!(contract.source_asts, None);
     }
 
     #[test]
@@ -1105,14 +2212,17 @@ mod tests {
             refs,
             vec![
                 ImmutableReference {
+                    id: 3,
                     start: 5,
                     length: 32,
                 },
                 ImmutableReference {
+                    id: 8,
                     start: 80,
                     length: 32,
                 },
                 ImmutableReference {
+                    id: 8,
                     start: 144,
                     length: 20,
                 },
@@ -1120,6 +2230,169 @@ mod tests {
         );
     }
 
+    #[test]
+    fn resolve_immutable_values_names_and_decodes_each_variable() {
+        let output = serde_json::json!({
+            "sources": {
+                "A.sol": {
+                    "ast": {
+                        "nodeType": "SourceUnit",
+                        "nodes": [{
+                            "nodeType": "ContractDefinition",
+                            "nodes": [
+                                {
+                                    "nodeType": "VariableDeclaration",
+                                    "id": 3,
+                                    "name": "owner",
+                                    "typeDescriptions": { "typeString": "address" }
+                                },
+                                {
+                                    "nodeType": "VariableDeclaration",
+                                    "id": 8,
+                                    "name": "cap",
+                                    "typeDescriptions": { "typeString": "uint256" }
+                                }
+                            ]
+                        }]
+                    }
+                }
+            }
+        });
+        let refs = vec![
+            ImmutableReference {
+                id: 8,
+                start: 32,
+                length: 32,
+            },
+            ImmutableReference {
+                id: 3,
+                start: 0,
+                length: 32,
+            },
+            ImmutableReference {
+                id: 8,
+                start: 64,
+                length: 32,
+            },
+        ];
+        let contract = CompiledContract {
+            bytecode: vec![0; 96],
+            abi: serde_json::json!([]),
+            immutable_declarations: find_immutable_declarations(&output, &refs),
+            immutable_references: refs,
+            source_map: None,
+            source_asts: None,
+        };
+        let mut deployed = vec![0u8; 96];
+        deployed[12..32].copy_from_slice(&[0xab; 20]);
+        deployed[63] = 100;
+        deployed[95] = 100;
+
+        let values = resolve_immutable_values(&deployed, &contract);
+
+        assert_eq!(values.len(), 2);
+        assert_eq!(values[0].id, 3);
+        assert_eq!(values[0].name.as_deref(), Some("owner"));
+        assert_eq!(
+            values[0].decoded.as_deref(),
+            Some("0xabababababababababababababababababababab")
+        );
+        assert_eq!(values[1].name.as_deref(), Some("cap"));
+        assert_eq!(values[1].type_name.as_deref(), Some("uint256"));
+        assert_eq!(values[1].decoded.as_deref(), Some("100"));
+        assert_eq!(values[1].value, format!("0x{}64", "0".repeat(62)));
+    }
+
+    #[test]
+    fn decode_constructor_arguments_names_and_renders_each_input() {
+        use alloy::primitives::Address;
+
+        let abi = serde_json::json!([
+            {"type": "constructor", "stateMutability": "nonpayable", "inputs": [
+                {"name": "owner", "type": "address", "internalType": "address"},
+                {"name": "supply", "type": "uint256", "internalType": "uint256"},
+                {"name": "name", "type": "string", "internalType": "string"},
+                {"name": "", "type": "bytes4[]", "internalType": "bytes4[]"}
+            ]},
+            {"type": "function", "name": "owner", "stateMutability": "view", "inputs": [],
+             "outputs": [{"name": "", "type": "address", "internalType": "address"}]}
+        ]);
+        let owner = Address::repeat_byte(0xab);
+        let args = DynSolValue::Tuple(vec![
+            DynSolValue::Address(owner),
+            DynSolValue::Uint(U256::from(1_000_000u64), 256),
+            DynSolValue::String("Atlas".to_string()),
+            DynSolValue::Array(vec![DynSolValue::FixedBytes(
+                alloy::primitives::B256::right_padding_from(&[0xde, 0xad, 0xbe, 0xef]),
+                4,
+            )]),
+        ])
+        .abi_encode_params();
+
+        let decoded = decode_constructor_arguments(&abi, &args).unwrap();
+        let rendered: Vec<_> = decoded
+            .iter()
+            .map(|arg| (arg.name.as_str(), arg.type_name.as_str(), arg.value.clone()))
+            .collect();
+        assert_eq!(
+            rendered,
+            vec![
+                (
+                    "owner",
+                    "address",
+                    serde_json::json!(address_to_hex(&owner))
+                ),
+                ("supply", "uint256", serde_json::json!("1000000")),
+                ("name", "string", serde_json::json!("Atlas")),
+                ("", "bytes4[]", serde_json::json!(["0xdeadbeef"])),
+            ]
+        );
+
+        assert_eq!(decode_constructor_arguments(&abi, &args[..40]), None);
+        assert_eq!(
+            decode_constructor_arguments(&serde_json::json!([]), &args),
+            None
+        );
+    }
+
+    #[test]
+    fn decode_immutable_word_handles_signed_and_unknown_types() {
+        let minus_one = [0xffu8; 32];
+        assert_eq!(
+            decode_immutable_word("int256", &minus_one).as_deref(),
+            Some("-1")
+        );
+        assert_eq!(
+            decode_immutable_word("bool", &minus_one).as_deref(),
+            Some("true")
+        );
+        assert_eq!(decode_immutable_word("bytes32", &minus_one), None);
+    }
+
+    #[test]
+    fn check_source_size_names_the_field_and_limit() {
+        let req = VerifyRequest {
+            source_code: None,
+            standard_json_input: Some("x".repeat(11)),
+            compiler_version: "v0.8.20+commit.a1b79de6".to_string(),
+            optimization_enabled: None,
+            optimization_runs: None,
+            contract_name: "C".to_string(),
+            constructor_args: None,
+            evm_version: None,
+            license_type: None,
+            match_strategies: None,
+        };
+
+        assert!(check_source_size(&req, 11).is_ok());
+        let err = check_source_size(&req, 10).unwrap_err();
+        assert_eq!(err.status_code(), 413);
+        assert_eq!(
+            err.to_string(),
+            "Payload too large: standard_json_input is 11 bytes, over the 10 byte limit for verification"
+        );
+    }
+
     #[test]
     fn build_standard_json_input_omits_evm_version_when_not_provided() {
         let req = VerifyRequest {
@@ -1132,6 +2405,7 @@ mod tests {
             constructor_args: None,
             evm_version: None,
             license_type: None,
+            match_strategies: None,
         };
 
         let input = build_single_file_standard_json_input(&req, true).unwrap();
@@ -1167,6 +2441,7 @@ mod tests {
             constructor_args: None,
             evm_version: None,
             license_type: None,
+            match_strategies: None,
         };
 
         let input = build_provided_standard_json_input(&req, true).unwrap();
@@ -1189,6 +2464,10 @@ mod tests {
                 serde_json::json!("evm.deployedBytecode"),
             ]
         );
+        assert_eq!(
+            input.pointer("/settings/outputSelection/*/"),
+            Some(&serde_json::json!(["ast"]))
+        );
     }
 
     #[test]
@@ -1212,6 +2491,7 @@ mod tests {
             constructor_args: None,
             evm_version: None,
             license_type: None,
+            match_strategies: None,
         };
 
         let stored = extract_stored_contract_sources(&req, VerifyInputKind::StandardJson).unwrap();
@@ -1248,6 +2528,7 @@ mod tests {
             constructor_args: None,
             evm_version: None,
             license_type: None,
+            match_strategies: None,
         };
 
         let stored = extract_stored_contract_sources(&req, VerifyInputKind::StandardJson).unwrap();
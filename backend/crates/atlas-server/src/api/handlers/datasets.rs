@@ -0,0 +1,157 @@
+//! Public daily dataset dumps; see [`crate::datasets`] for how they are written.
+
+use axum::{
+    extract::{Path, State},
+    response::Response,
+    Json,
+};
+use chrono::NaiveDate;
+use serde::Serialize;
+use std::sync::Arc;
+
+use crate::api::error::ApiResult;
+use crate::api::handlers::export::attachment;
+use crate::api::AppState;
+use crate::datasets::{Dataset, DatasetDump, DATASET_DUMP_COLUMNS};
+use atlas_common::AtlasError;
+
+#[derive(Serialize)]
+pub struct DatasetManifest {
+    /// `csv`, with a header line
+    pub format: &'static str,
+    /// Dumped days, newest first
+    pub days: Vec<DatasetDay>,
+}
+
+#[derive(Serialize)]
+pub struct DatasetDay {
+    pub day: NaiveDate,
+    pub from_block: i64,
+    pub to_block: i64,
+    /// `erc20_transfers` is missing on days whose raw transfers were already
+    /// aggregated by transfer retention
+    pub files: Vec<DatasetFile>,
+}
+
+#[derive(Serialize)]
+pub struct DatasetFile {
+    pub dataset: String,
+    pub url: String,
+    pub row_count: i64,
+    /// File size in bytes
+    pub file_size: i64,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// GET /api/datasets - Manifest of the daily dataset dumps
+pub async fn list_datasets(State(state): State<Arc<AppState>>) -> ApiResult<Json<DatasetManifest>> {
+    let dumps: Vec<DatasetDump> = sqlx::query_as(&format!(
+        "SELECT {DATASET_DUMP_COLUMNS} FROM dataset_dumps ORDER BY day DESC, dataset ASC"
+    ))
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(Json(DatasetManifest {
+        format: "csv",
+        days: group_days(dumps),
+    }))
+}
+
+/// Group dumps, ordered by day, into one entry per day.
+fn group_days(dumps: Vec<DatasetDump>) -> Vec<DatasetDay> {
+    let mut days: Vec<DatasetDay> = Vec::new();
+    for dump in dumps {
+        let file = DatasetFile {
+            url: format!("/api/datasets/{}/{}.csv", dump.day, dump.dataset),
+            dataset: dump.dataset,
+            row_count: dump.row_count,
+            file_size: dump.file_size,
+            created_at: dump.created_at,
+        };
+        match days.last_mut() {
+            Some(day) if day.day == dump.day => day.files.push(file),
+            _ => days.push(DatasetDay {
+                day: dump.day,
+                from_block: dump.from_block,
+                to_block: dump.to_block,
+                files: vec![file],
+            }),
+        }
+    }
+    days
+}
+
+/// GET /api/datasets/{day}/{file} - Download one dump, e.g. `2026-01-31/blocks.csv`
+pub async fn download_dataset(
+    State(state): State<Arc<AppState>>,
+    Path((day, file)): Path<(String, String)>,
+) -> ApiResult<Response> {
+    // Both parts are parsed, so the path never leaves the dataset directory.
+    let not_found = || AtlasError::NotFound(format!("Dataset {day}/{file} not found"));
+    let day: NaiveDate = day.parse().map_err(|_| not_found())?;
+    let dataset = file
+        .strip_suffix(".csv")
+        .and_then(Dataset::parse)
+        .ok_or_else(not_found)?;
+
+    let recorded: Option<(i64,)> =
+        sqlx::query_as("SELECT row_count FROM dataset_dumps WHERE day = $1 AND dataset = $2")
+            .bind(day)
+            .bind(dataset.as_str())
+            .fetch_optional(&state.pool)
+            .await?;
+    if recorded.is_none() {
+        return Err(not_found().into());
+    }
+
+    let dir = state
+        .dataset_dir
+        .as_deref()
+        .ok_or_else(|| AtlasError::Config("dataset dumps are disabled".to_string()))?;
+    let path = std::path::Path::new(dir)
+        .join(day.to_string())
+        .join(dataset.file_name());
+    let file = match tokio::fs::File::open(path).await {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Err(not_found().into()),
+        Err(e) => return Err(AtlasError::Internal(e.to_string()).into()),
+    };
+    Ok(attachment(
+        file,
+        "text/csv; charset=utf-8",
+        &format!("atlas-{}-{day}.csv", dataset.as_str()),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dump(day: &str, dataset: &str) -> DatasetDump {
+        DatasetDump {
+            day: day.parse().unwrap(),
+            dataset: dataset.to_string(),
+            from_block: 1,
+            to_block: 9,
+            row_count: 3,
+            file_size: 100,
+            created_at: chrono::Utc::now(),
+        }
+    }
+
+    #[test]
+    fn dumps_are_grouped_by_day() {
+        let days = group_days(vec![
+            dump("2026-01-02", "blocks"),
+            dump("2026-01-02", "transactions"),
+            dump("2026-01-01", "blocks"),
+        ]);
+        assert_eq!(days.len(), 2);
+        assert_eq!(days[0].files.len(), 2);
+        assert_eq!(
+            days[0].files[1].url,
+            "/api/datasets/2026-01-02/transactions.csv"
+        );
+        assert_eq!(days[1].day.to_string(), "2026-01-01");
+    }
+}
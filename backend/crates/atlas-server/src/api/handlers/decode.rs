@@ -0,0 +1,243 @@
+//! Decoding of raw event logs for external tools.
+//!
+//! A log is decoded with the verified ABI of its emitting contract when an
+//! address is given (a known proxy's implementation ABI first), and otherwise
+//! with the `event_signatures` table. A signature row without an ABI entry
+//! does not say which parameters are indexed; the leading parameters are
+//! assumed to fill the topics, as in the standard token events.
+
+use alloy::dyn_abi::{DecodedEvent, EventExt};
+use alloy::json_abi::Event;
+use alloy::primitives::B256;
+use axum::{extract::State, Json};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use crate::api::error::ApiResult;
+use crate::api::handlers::contracts::abi_value_to_json;
+use crate::api::handlers::proxy::normalize_address;
+use crate::api::AppState;
+use atlas_common::encoding::{b256_to_hex, hex_to_bytes, parse_address, parse_b256};
+use atlas_common::AtlasError;
+
+#[derive(Debug, Deserialize)]
+pub struct DecodeLogRequest {
+    pub topics: Vec<String>,
+    #[serde(default)]
+    pub data: String,
+    /// Emitting contract, whose verified ABI takes precedence
+    pub address: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DecodedLog {
+    pub name: String,
+    /// Canonical signature, e.g. `Transfer(address,address,uint256)`
+    pub signature: String,
+    pub topic0: String,
+    /// `abi` (verified contract), `implementation` (a proxy's implementation)
+    /// or `signature` (the `event_signatures` table)
+    pub source: &'static str,
+    pub params: Vec<DecodedParam>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DecodedParam {
+    /// `null` when the signature does not name it
+    pub name: Option<String>,
+    #[serde(rename = "type")]
+    pub type_name: String,
+    pub indexed: bool,
+    /// Numbers are decimal strings; indexed strings, bytes and arrays are their topic hash
+    pub value: serde_json::Value,
+}
+
+/// POST /api/decode/log - Decode an event log from its topics and data
+pub async fn decode_log(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<DecodeLogRequest>,
+) -> ApiResult<Json<DecodedLog>> {
+    let topics = request
+        .topics
+        .iter()
+        .map(|topic| parse_b256(topic))
+        .collect::<Result<Vec<B256>, _>>()?;
+    let Some(topic0) = topics.first().copied() else {
+        return Err(AtlasError::InvalidInput("topics must include topic0".to_string()).into());
+    };
+    if topics.len() > 4 {
+        return Err(AtlasError::InvalidInput("a log has at most 4 topics".to_string()).into());
+    }
+    let data = hex_to_bytes(&request.data)?;
+
+    let mut candidates: Vec<(Event, &'static str)> = Vec::new();
+    if let Some(address) = &request.address {
+        parse_address(address)?;
+        let address = normalize_address(address);
+        let implementation: Option<String> = sqlx::query_scalar(
+            "SELECT implementation_address FROM proxy_contracts WHERE proxy_address = $1",
+        )
+        .bind(&address)
+        .fetch_optional(&state.pool)
+        .await?;
+        for (contract, source) in [(implementation, "implementation"), (Some(address), "abi")] {
+            let Some(contract) = contract else { continue };
+            let abi: Option<serde_json::Value> =
+                sqlx::query_scalar("SELECT abi FROM contract_abis WHERE address = $1")
+                    .bind(&contract)
+                    .fetch_optional(&state.pool)
+                    .await?;
+            candidates.extend(
+                abi.iter()
+                    .flat_map(abi_events)
+                    .filter(|event| !event.anonymous && event.selector() == topic0)
+                    .map(|event| (event, source)),
+            );
+        }
+    }
+
+    let signature: Option<(String, Option<serde_json::Value>)> =
+        sqlx::query_as("SELECT full_signature, abi FROM event_signatures WHERE signature = $1")
+            .bind(b256_to_hex(&topic0))
+            .fetch_optional(&state.pool)
+            .await?;
+    if let Some((full_signature, abi)) = signature {
+        let event = abi
+            .and_then(|abi| serde_json::from_value::<Event>(abi).ok())
+            .or_else(|| signature_event(&full_signature, topics.len() - 1));
+        candidates.extend(event.map(|event| (event, "signature")));
+    }
+
+    candidates
+        .into_iter()
+        .find_map(|(event, source)| {
+            let decoded = event.decode_log_parts(topics.iter().copied(), &data).ok()?;
+            Some(decoded_log(event, decoded, topic0, source))
+        })
+        .map(Json)
+        .ok_or_else(|| {
+            AtlasError::NotFound(format!(
+                "No known event decodes a log with topic0 {}",
+                b256_to_hex(&topic0)
+            ))
+            .into()
+        })
+}
+
+/// Event entries of a JSON ABI; entries that do not parse are skipped.
+fn abi_events(abi: &serde_json::Value) -> impl Iterator<Item = Event> + '_ {
+    abi.as_array()
+        .into_iter()
+        .flatten()
+        .filter(|item| item.get("type").and_then(|t| t.as_str()) == Some("event"))
+        .filter_map(|item| serde_json::from_value(item.clone()).ok())
+}
+
+/// An event from a bare signature, with its first `indexed` parameters indexed.
+fn signature_event(signature: &str, indexed: usize) -> Option<Event> {
+    let mut event = Event::parse(signature).ok()?;
+    if indexed > event.inputs.len() {
+        return None;
+    }
+    for input in event.inputs.iter_mut().take(indexed) {
+        input.indexed = true;
+    }
+    Some(event)
+}
+
+fn decoded_log(
+    event: Event,
+    decoded: DecodedEvent,
+    topic0: B256,
+    source: &'static str,
+) -> DecodedLog {
+    let mut indexed = decoded.indexed.iter();
+    let mut body = decoded.body.iter();
+    let params = event
+        .inputs
+        .iter()
+        .map(|input| {
+            let value = if input.indexed {
+                indexed.next()
+            } else {
+                body.next()
+            };
+            DecodedParam {
+                name: (!input.name.is_empty()).then(|| input.name.clone()),
+                type_name: input.selector_type().into_owned(),
+                indexed: input.indexed,
+                value: value.map(abi_value_to_json).unwrap_or_default(),
+            }
+        })
+        .collect();
+
+    DecodedLog {
+        signature: event.signature(),
+        name: event.name,
+        topic0: b256_to_hex(&topic0),
+        source,
+        params,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::{address, U256};
+
+    const TRANSFER: &str = "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef";
+
+    fn transfer_topics() -> Vec<B256> {
+        vec![
+            TRANSFER.parse().unwrap(),
+            address!("0x1111111111111111111111111111111111111111").into_word(),
+            address!("0x2222222222222222222222222222222222222222").into_word(),
+        ]
+    }
+
+    #[test]
+    fn signature_event_indexes_leading_params() {
+        let event = signature_event("Transfer(address,address,uint256)", 2).unwrap();
+        assert_eq!(
+            event.inputs.iter().map(|i| i.indexed).collect::<Vec<_>>(),
+            [true, true, false]
+        );
+        assert!(signature_event("Transfer(address,address,uint256)", 4).is_none());
+    }
+
+    #[test]
+    fn decoded_log_pairs_values_with_params() {
+        let event = signature_event("Transfer(address,address,uint256)", 2).unwrap();
+        let data = U256::from(1000).to_be_bytes::<32>();
+        let topics = transfer_topics();
+        let decoded = event.decode_log_parts(topics.clone(), &data).unwrap();
+        let log = decoded_log(event, decoded, topics[0], "signature");
+
+        assert_eq!(log.name, "Transfer");
+        assert_eq!(log.signature, "Transfer(address,address,uint256)");
+        assert_eq!(log.params.len(), 3);
+        assert_eq!(
+            log.params[0].value,
+            "0x1111111111111111111111111111111111111111"
+        );
+        assert!(log.params[1].indexed);
+        assert_eq!(log.params[2].type_name, "uint256");
+        assert_eq!(log.params[2].value, "1000");
+        assert_eq!(log.params[2].name, None);
+    }
+
+    #[test]
+    fn shared_signature_follows_the_topic_count() {
+        // ERC-721 Transfer has the ERC-20 signature with the token ID indexed too.
+        let mut topics = transfer_topics();
+        topics.push(U256::from(7).into());
+        let erc20 = signature_event("Transfer(address,address,uint256)", 2).unwrap();
+        assert!(erc20.decode_log_parts(topics.clone(), &[]).is_err());
+
+        let erc721 = signature_event("Transfer(address,address,uint256)", 3).unwrap();
+        let decoded = erc721.decode_log_parts(topics.clone(), &[]).unwrap();
+        let log = decoded_log(erc721, decoded, topics[0], "signature");
+        assert_eq!(log.params[2].value, "7");
+        assert!(log.params[2].indexed);
+    }
+}
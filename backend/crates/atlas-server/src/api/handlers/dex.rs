@@ -0,0 +1,177 @@
+//! DEX swaps decoded by the `dex_swaps` block processor, and volume per
+//! token pair.
+//!
+//! Both endpoints are empty unless `BLOCK_PROCESSORS` includes `dex_swaps`.
+//! Pool tokens come from `dex_pools` and are `null` until the pool resolver
+//! has read them.
+
+use axum::{
+    extract::{Query, State},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use crate::api::error::ApiResult;
+use crate::api::handlers::stats::{resolve_block_range, Window};
+use crate::api::AppState;
+use crate::ordering::{ChainRow, Direction};
+use atlas_common::encoding::{address_to_hex, parse_address};
+use atlas_common::{PaginatedResponse, Pagination};
+
+/// Pairs listed by default and at most by `/api/dex/pairs`.
+const PAIRS_DEFAULT_LIMIT: u32 = 20;
+const PAIRS_MAX_LIMIT: u32 = 100;
+
+#[derive(Deserialize)]
+pub struct DexSwapsQuery {
+    /// Only swaps in this pool
+    pub pool: Option<String>,
+    /// Only swaps in pools trading this token
+    pub token: Option<String>,
+    #[serde(flatten)]
+    pub pagination: Pagination,
+}
+
+/// A swap. Amounts are signed changes of the pool's balances in raw token
+/// units: positive for the token paid in, negative for the token paid out.
+#[derive(Serialize, sqlx::FromRow)]
+pub struct DexSwap {
+    pub block_number: i64,
+    pub log_index: i32,
+    pub tx_hash: String,
+    pub timestamp: i64,
+    pub pool: String,
+    /// `uniswap_v2` or `uniswap_v3`, including forks emitting the same events
+    pub protocol: String,
+    pub token0: Option<String>,
+    pub token1: Option<String>,
+    pub sender: String,
+    pub recipient: String,
+    pub amount0: String,
+    pub amount1: String,
+}
+
+#[derive(Deserialize)]
+pub struct DexPairsQuery {
+    #[serde(default)]
+    pub window: Window,
+    /// An explicit block range replaces `window`
+    pub from_block: Option<i64>,
+    pub to_block: Option<i64>,
+    pub limit: Option<u32>,
+}
+
+#[derive(Serialize)]
+pub struct DexPairStats {
+    /// Range covered; `null` when the window holds no blocks
+    pub from_block: Option<i64>,
+    pub to_block: Option<i64>,
+    pub pairs: Vec<DexPair>,
+}
+
+/// Swap volume of one token pair, over all of its pools. Volumes are the
+/// summed absolute amounts in raw token units.
+#[derive(Serialize, sqlx::FromRow)]
+pub struct DexPair {
+    pub token0: String,
+    pub token1: String,
+    pub token0_symbol: Option<String>,
+    pub token1_symbol: Option<String>,
+    pub token0_decimals: Option<i16>,
+    pub token1_decimals: Option<i16>,
+    pub pools: i64,
+    pub swaps: i64,
+    pub volume0: String,
+    pub volume1: String,
+}
+
+/// GET /api/dex/swaps?pool=&token= - Decoded swaps, newest first
+pub async fn list_swaps(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<DexSwapsQuery>,
+) -> ApiResult<Json<PaginatedResponse<DexSwap>>> {
+    let pool = params.pool.as_deref().map(parse_address).transpose()?;
+    let token = params.token.as_deref().map(parse_address).transpose()?;
+    let pagination = params.pagination;
+
+    let swaps: Vec<DexSwap> = sqlx::query_as(&format!(
+        "SELECT s.block_number, s.log_index, s.tx_hash, s.timestamp, s.pool, s.protocol,
+                p.token0, p.token1, s.sender, s.recipient,
+                s.amount0::text AS amount0, s.amount1::text AS amount1
+         FROM dex_swaps s
+         LEFT JOIN dex_pools p ON p.address = s.pool
+         WHERE ($1::text IS NULL OR s.pool = $1)
+           AND ($2::text IS NULL OR s.pool IN (
+                SELECT address FROM dex_pools WHERE token0 = $2 OR token1 = $2))
+         ORDER BY {}
+         LIMIT $3 OFFSET $4",
+        ChainRow::Log.order_by("s", Direction::Desc)
+    ))
+    .bind(pool.as_ref().map(address_to_hex))
+    .bind(token.as_ref().map(address_to_hex))
+    .bind(pagination.limit() + 1)
+    .bind(pagination.offset())
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(Json(PaginatedResponse::from_probe(
+        swaps,
+        pagination.page,
+        pagination.limit() as u32,
+        pagination.offset(),
+        None,
+        false,
+    )))
+}
+
+/// GET /api/dex/pairs?window=24h or ?from_block=N&to_block=M
+///
+/// Token pairs by swap count over a block range, chosen as for the stats
+/// endpoints. Pools whose tokens are unknown are left out.
+pub async fn get_pair_stats(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<DexPairsQuery>,
+) -> ApiResult<Json<DexPairStats>> {
+    let (from_block, to_block) =
+        resolve_block_range(&state, params.window, params.from_block, params.to_block).await?;
+    let (Some(lo), Some(hi)) = (from_block, to_block) else {
+        return Ok(Json(DexPairStats {
+            from_block,
+            to_block,
+            pairs: Vec::new(),
+        }));
+    };
+    let limit = params
+        .limit
+        .unwrap_or(PAIRS_DEFAULT_LIMIT)
+        .clamp(1, PAIRS_MAX_LIMIT);
+
+    let pairs: Vec<DexPair> = sqlx::query_as(
+        "SELECT p.token0, p.token1,
+                e0.symbol AS token0_symbol, e1.symbol AS token1_symbol,
+                e0.decimals AS token0_decimals, e1.decimals AS token1_decimals,
+                COUNT(DISTINCT s.pool) AS pools, COUNT(*) AS swaps,
+                SUM(ABS(s.amount0))::text AS volume0, SUM(ABS(s.amount1))::text AS volume1
+         FROM dex_swaps s
+         JOIN dex_pools p ON p.address = s.pool
+         LEFT JOIN erc20_contracts e0 ON e0.address = p.token0
+         LEFT JOIN erc20_contracts e1 ON e1.address = p.token1
+         WHERE s.block_number BETWEEN $1 AND $2
+           AND p.token0 IS NOT NULL AND p.token1 IS NOT NULL
+         GROUP BY p.token0, p.token1, e0.symbol, e1.symbol, e0.decimals, e1.decimals
+         ORDER BY swaps DESC, p.token0, p.token1
+         LIMIT $3",
+    )
+    .bind(lo)
+    .bind(hi)
+    .bind(limit as i64)
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(Json(DexPairStats {
+        from_block,
+        to_block,
+        pairs,
+    }))
+}
@@ -0,0 +1,238 @@
+//! Opt-in display fields (`?include=display`) for token and native amounts.
+//!
+//! Raw amounts stay integers in the token's smallest unit; the `display`
+//! object adds the decimal-adjusted amount as an exact string (never a float)
+//! and, for transfers, the age in seconds. Native amounts (transaction values
+//! and fees, genesis balances) use the chain's configured [`NativeCurrency`]
+//! and carry its symbol. Responses without the option are unchanged.
+
+use bigdecimal::num_bigint::Sign;
+use bigdecimal::BigDecimal;
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::collections::HashMap;
+
+#[derive(Debug, Default, Deserialize)]
+pub struct DisplayQuery {
+    /// Comma-separated list of optional response sections.
+    #[serde(default)]
+    pub include: Option<String>,
+}
+
+impl DisplayQuery {
+    pub fn enabled(&self) -> bool {
+        self.include
+            .as_deref()
+            .is_some_and(|include| include.split(',').any(|part| part.trim() == "display"))
+    }
+}
+
+/// The chain's native currency, which not every chain calls 18-decimal ETH.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct NativeCurrency {
+    pub symbol: String,
+    pub decimals: u8,
+}
+
+impl Default for NativeCurrency {
+    fn default() -> Self {
+        Self {
+            symbol: "ETH".to_string(),
+            decimals: 18,
+        }
+    }
+}
+
+impl NativeCurrency {
+    /// Display fields for a native `value`, with the fee when given.
+    pub fn amount(
+        &self,
+        value: &BigDecimal,
+        fee: Option<&BigDecimal>,
+        timestamp: Option<i64>,
+    ) -> Display {
+        let decimals = i16::from(self.decimals);
+        Display {
+            value: Some(format_units(value, decimals)),
+            fee: fee.map(|fee| format_units(fee, decimals)),
+            symbol: Some(self.symbol.clone()),
+            age_seconds: timestamp.map(age_seconds),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Display {
+    /// Amount divided by `10^decimals`; omitted when the token's decimals are unknown.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+    /// Total fee of a transaction, in the same units as `value`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fee: Option<String>,
+    /// Native currency symbol, on native amounts only.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub symbol: Option<String>,
+    /// Seconds between the transfer's block timestamp and the response.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub age_seconds: Option<i64>,
+}
+
+/// A response item with its optional `display` object.
+#[derive(Debug, Clone, Serialize)]
+pub struct WithDisplay<T> {
+    #[serde(flatten)]
+    pub item: T,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display: Option<Display>,
+}
+
+/// Wrap `items`, computing `display` only when it was requested.
+pub fn decorate<T>(
+    items: Vec<T>,
+    enabled: bool,
+    display: impl Fn(&T) -> Display,
+) -> Vec<WithDisplay<T>> {
+    items
+        .into_iter()
+        .map(|item| WithDisplay {
+            display: enabled.then(|| display(&item)),
+            item,
+        })
+        .collect()
+}
+
+/// Display fields for a token amount, with the age when `timestamp` is given.
+pub fn amount(value: &BigDecimal, decimals: Option<i16>, timestamp: Option<i64>) -> Display {
+    Display {
+        value: decimals.map(|decimals| format_units(value, decimals)),
+        fee: None,
+        symbol: None,
+        age_seconds: timestamp.map(age_seconds),
+    }
+}
+
+/// Seconds since a block `timestamp`, clamped at zero for clock skew.
+pub fn age_seconds(timestamp: i64) -> i64 {
+    (chrono::Utc::now().timestamp() - timestamp).max(0)
+}
+
+/// Decimals of the given ERC-20 contracts, keyed by address.
+pub async fn token_decimals(
+    pool: &PgPool,
+    contracts: &[String],
+) -> Result<HashMap<String, i16>, sqlx::Error> {
+    let rows: Vec<(String, i16)> =
+        sqlx::query_as("SELECT address, decimals FROM erc20_contracts WHERE address = ANY($1)")
+            .bind(contracts)
+            .fetch_all(pool)
+            .await?;
+    Ok(rows.into_iter().collect())
+}
+
+/// `value / 10^decimals` as a plain decimal string with trailing zeros
+/// trimmed, computed on the digits so nothing is rounded.
+pub fn format_units(value: &BigDecimal, decimals: i16) -> String {
+    let (digits, scale) = value.as_bigint_and_exponent();
+    let scale = scale + i64::from(decimals.max(0));
+    let mut out = digits.magnitude().to_string();
+
+    if scale <= 0 {
+        if out != "0" {
+            out.push_str(&"0".repeat(scale.unsigned_abs() as usize));
+        }
+    } else {
+        let scale = scale as usize;
+        if out.len() <= scale {
+            out = format!("{}{}", "0".repeat(scale + 1 - out.len()), out);
+        }
+        out.insert(out.len() - scale, '.');
+        let trimmed = out.trim_end_matches('0').trim_end_matches('.').len();
+        out.truncate(trimmed);
+    }
+
+    if digits.sign() == Sign::Minus {
+        out.insert(0, '-');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn units(value: &str, decimals: i16) -> String {
+        format_units(&BigDecimal::from_str(value).unwrap(), decimals)
+    }
+
+    #[test]
+    fn format_units_shifts_the_decimal_point() {
+        assert_eq!(units("1500000000000000000", 18), "1.5");
+        assert_eq!(units("1000000", 6), "1");
+        assert_eq!(units("1", 18), "0.000000000000000001");
+        assert_eq!(units("123456789", 0), "123456789");
+        assert_eq!(units("0", 18), "0");
+        assert_eq!(units("-2500", 3), "-2.5");
+    }
+
+    #[test]
+    fn format_units_is_exact_for_values_beyond_f64() {
+        assert_eq!(
+            units(
+                "115792089237316195423570985008687907853269984665640564039457584007913129639935",
+                18
+            ),
+            "115792089237316195423570985008687907853269984665640564039457.584007913129639935"
+        );
+    }
+
+    #[test]
+    fn format_units_handles_scaled_and_exponent_inputs() {
+        assert_eq!(units("1e3", 2), "10");
+        assert_eq!(units("12.50", 1), "1.25");
+    }
+
+    #[test]
+    fn native_amounts_use_the_configured_decimals_and_symbol() {
+        let currency = NativeCurrency {
+            symbol: "TIA".to_string(),
+            decimals: 6,
+        };
+        let value = BigDecimal::from_str("2500000").unwrap();
+        let fee = BigDecimal::from_str("21000").unwrap();
+        let display = serde_json::to_value(currency.amount(&value, Some(&fee), None)).unwrap();
+        assert_eq!(
+            display,
+            serde_json::json!({"value": "2.5", "fee": "0.021", "symbol": "TIA"})
+        );
+    }
+
+    #[test]
+    fn include_accepts_comma_separated_sections() {
+        let query = |include: &str| DisplayQuery {
+            include: Some(include.to_string()),
+        };
+        assert!(query("display").enabled());
+        assert!(query("other, display").enabled());
+        assert!(!query("displays").enabled());
+        assert!(!DisplayQuery::default().enabled());
+    }
+
+    #[test]
+    fn decorate_omits_display_unless_enabled() {
+        let items = || vec![serde_json::json!({"value": "1000"})];
+        let display = |_: &serde_json::Value| Display {
+            value: Some("1".into()),
+            fee: None,
+            symbol: None,
+            age_seconds: None,
+        };
+
+        let plain = serde_json::to_value(decorate(items(), false, display)).unwrap();
+        assert_eq!(plain[0], serde_json::json!({"value": "1000"}));
+
+        let decorated = serde_json::to_value(decorate(items(), true, display)).unwrap();
+        assert_eq!(decorated[0]["value"], "1000");
+        assert_eq!(decorated[0]["display"]["value"], "1");
+    }
+}
@@ -0,0 +1,145 @@
+//! Etherscan response conformance
+//!
+//! Foundry, Hardhat and ethers match on Etherscan's exact envelopes rather than
+//! on HTTP status codes: client errors are `200` with `status: "0"` and
+//! `message: "NOTOK"`, empty lists are `status: "0"` with
+//! `message: "No transactions found"`, and `module=proxy` answers in a JSON-RPC
+//! envelope. Every action builds its response through this module so the shapes
+//! are defined in one place; `tests/golden/etherscan` pins them per action.
+
+use axum::Json;
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::api::error::ApiResult;
+use atlas_common::AtlasError;
+
+pub const NOTOK: &str = "NOTOK";
+pub const NO_TRANSACTIONS_FOUND: &str = "No transactions found";
+pub const NOT_VERIFIED: &str = "Contract source code not verified";
+pub const NO_DATA_FOUND: &str = "No data found";
+
+/// JSON-RPC error code for bad proxy parameters.
+const INVALID_PARAMS: i64 = -32602;
+
+/// Etherscan API response wrapper
+#[derive(Debug, Serialize)]
+pub struct EtherscanResponse<T> {
+    pub status: String,
+    pub message: String,
+    pub result: T,
+}
+
+impl<T> EtherscanResponse<T> {
+    pub fn ok(result: T) -> Self {
+        Self {
+            status: "1".to_string(),
+            message: "OK".to_string(),
+            result,
+        }
+    }
+
+    pub fn error(message: impl Into<String>, result: T) -> Self {
+        Self {
+            status: "0".to_string(),
+            message: message.into(),
+            result,
+        }
+    }
+}
+
+impl<T> EtherscanResponse<Vec<T>> {
+    /// List result; Etherscan reports an empty list as `status: "0"`.
+    pub fn list(result: Vec<T>) -> Self {
+        if result.is_empty() {
+            Self::error(NO_TRANSACTIONS_FOUND, result)
+        } else {
+            Self::ok(result)
+        }
+    }
+}
+
+/// `NOTOK` response carrying an `Error! ...` string as its result.
+pub fn notok(message: &str) -> Value {
+    json!(EtherscanResponse::error(NOTOK, format!("Error! {message}")))
+}
+
+pub fn unknown_module() -> Value {
+    notok("Missing Or invalid Module name")
+}
+
+pub fn unknown_action() -> Value {
+    notok("Missing Or invalid Action name")
+}
+
+/// Successful `module=proxy` response.
+pub fn rpc_result<T: Serialize>(result: T) -> Value {
+    json!({ "jsonrpc": "2.0", "id": 1, "result": result })
+}
+
+fn rpc_error(code: i64, message: &str) -> Value {
+    json!({ "jsonrpc": "2.0", "id": 1, "error": { "code": code, "message": message } })
+}
+
+/// Render caller errors in the module's envelope. Server-side failures keep
+/// their HTTP status so clients back off and retry.
+pub fn conform(result: ApiResult<Json<Value>>, proxy: bool) -> ApiResult<Json<Value>> {
+    let err = match result {
+        Ok(body) => return Ok(body),
+        Err(err) => err,
+    };
+    let message = match &err.0 {
+        AtlasError::InvalidInput(msg) | AtlasError::Validation(msg) | AtlasError::NotFound(msg) => {
+            msg
+        }
+        _ => return Err(err),
+    };
+    Ok(Json(if proxy {
+        rpc_error(INVALID_PARAMS, message)
+    } else {
+        notok(message)
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::error::ApiError;
+
+    #[test]
+    fn empty_lists_report_no_transactions_found() {
+        let empty = json!(EtherscanResponse::list(Vec::<u8>::new()));
+        assert_eq!(
+            empty,
+            json!({"status": "0", "message": "No transactions found", "result": []})
+        );
+        let full = json!(EtherscanResponse::list(vec!["1"]));
+        assert_eq!(
+            full,
+            json!({"status": "1", "message": "OK", "result": ["1"]})
+        );
+    }
+
+    #[test]
+    fn caller_errors_become_notok_or_rpc_errors() {
+        let err = || Err(AtlasError::InvalidInput("Invalid address format".into()).into());
+        assert_eq!(
+            conform(err(), false).map_err(|e| e.0).unwrap().0,
+            json!({"status": "0", "message": "NOTOK", "result": "Error! Invalid address format"})
+        );
+        assert_eq!(
+            conform(err(), true).map_err(|e| e.0).unwrap().0,
+            json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "error": {"code": -32602, "message": "Invalid address format"}
+            })
+        );
+    }
+
+    #[test]
+    fn server_errors_keep_their_status() {
+        let result = conform(Err(AtlasError::Rpc("down".into()).into()), false);
+        assert!(matches!(result, Err(ApiError(AtlasError::Rpc(_)))));
+    }
+}
@@ -1,60 +1,54 @@
 //! Etherscan-compatible API endpoints
 //!
 //! Implements the Etherscan API format for compatibility with tooling like Hardhat and Foundry.
-//! Response format: { "status": "1", "message": "OK", "result": ... }
+//! Response format: { "status": "1", "message": "OK", "result": ... }; see
+//! [`conformance`] for error, empty-list and proxy shapes.
+
+pub(crate) mod conformance;
 
 use alloy::providers::{Provider, ProviderBuilder};
 use axum::{
-    extract::{Query, State},
+    extract::{rejection::QueryRejection, Query, State},
     Json,
 };
 use bigdecimal::BigDecimal;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use crate::api::error::ApiResult;
+use crate::api::handlers::{canonical_tx_block, segments_newest_first, stitch_segments};
 use crate::api::AppState;
-use atlas_common::{AtlasError, ContractAbi, Transaction};
-
-/// Etherscan API response wrapper
-#[derive(Debug, Serialize)]
-pub struct EtherscanResponse<T> {
-    pub status: String,
-    pub message: String,
-    pub result: T,
-}
-
-impl<T> EtherscanResponse<T> {
-    pub fn ok(result: T) -> Self {
-        Self {
-            status: "1".to_string(),
-            message: "OK".to_string(),
-            result,
-        }
-    }
+use crate::ordering::{ChainRow, Direction};
+use atlas_common::storage::{Segment, TieredTable};
+use atlas_common::{AtlasError, ContractAbi, Erc20Transfer, Transaction};
+use conformance::{
+    notok, rpc_result, unknown_action, unknown_module, NOTOK, NOT_VERIFIED, NO_DATA_FOUND,
+};
 
-    pub fn error(message: impl Into<String>, result: T) -> Self {
-        Self {
-            status: "0".to_string(),
-            message: message.into(),
-            result,
-        }
-    }
-}
+pub use conformance::EtherscanResponse;
 
 /// Query parameters for Etherscan API
 #[derive(Debug, Deserialize)]
 pub struct EtherscanQuery {
+    #[serde(default)]
     pub module: String,
+    #[serde(default)]
     pub action: String,
     /// Single address for account queries
     pub address: Option<String>,
     /// Contract address for token queries
     pub contractaddress: Option<String>,
+    /// Comma-separated contract addresses for `getcontractcreation`
+    pub contractaddresses: Option<String>,
     /// Transaction hash
     pub txhash: Option<String>,
     /// Block number
     pub blockno: Option<String>,
+    /// Block number for proxy actions (hex or decimal)
+    pub tag: Option<String>,
+    /// Transaction position in a block (hex or decimal)
+    pub index: Option<String>,
     /// Start block for range queries
     #[serde(rename = "startblock")]
     pub _startblock: Option<i64>,
@@ -75,19 +69,22 @@ pub struct EtherscanQuery {
 /// Main Etherscan API router (GET requests)
 pub async fn etherscan_api(
     State(state): State<Arc<AppState>>,
-    Query(query): Query<EtherscanQuery>,
+    query: Result<Query<EtherscanQuery>, QueryRejection>,
 ) -> ApiResult<Json<serde_json::Value>> {
-    match query.module.as_str() {
+    let Query(query) = match query {
+        Ok(query) => query,
+        Err(rejection) => return Ok(Json(notok(&rejection.body_text()))),
+    };
+    let proxy = query.module == "proxy";
+    let result = match query.module.as_str() {
         "account" => handle_account_module(state, query).await,
         "contract" => handle_contract_module(state, query).await,
         "transaction" => handle_transaction_module(state, query).await,
         "block" => handle_block_module(state, query).await,
         "proxy" => handle_proxy_module(state, query).await,
-        _ => Ok(Json(serde_json::to_value(EtherscanResponse::error(
-            format!("Unknown module: {}", query.module),
-            serde_json::Value::Null,
-        ))?)),
-    }
+        _ => Ok(Json(unknown_module())),
+    };
+    conformance::conform(result, proxy)
 }
 
 /// Handle account module requests
@@ -102,10 +99,7 @@ async fn handle_account_module(
         "txlistinternal" => get_internal_tx_list(state, query).await,
         "tokentx" => get_token_tx_list(state, query).await,
         "tokenbalance" => get_token_balance(state, query).await,
-        _ => Ok(Json(serde_json::to_value(EtherscanResponse::error(
-            format!("Unknown action: {}", query.action),
-            serde_json::Value::Null,
-        ))?)),
+        _ => Ok(Json(unknown_action())),
     }
 }
 
@@ -117,10 +111,8 @@ async fn handle_contract_module(
     match query.action.as_str() {
         "getabi" => get_contract_abi(state, query).await,
         "getsourcecode" => get_source_code(state, query).await,
-        _ => Ok(Json(serde_json::to_value(EtherscanResponse::error(
-            format!("Unknown action: {}", query.action),
-            serde_json::Value::Null,
-        ))?)),
+        "getcontractcreation" => get_contract_creation(state, query).await,
+        _ => Ok(Json(unknown_action())),
     }
 }
 
@@ -131,10 +123,7 @@ async fn handle_transaction_module(
 ) -> ApiResult<Json<serde_json::Value>> {
     match query.action.as_str() {
         "gettxreceiptstatus" => get_tx_receipt_status(state, query).await,
-        _ => Ok(Json(serde_json::to_value(EtherscanResponse::error(
-            format!("Unknown action: {}", query.action),
-            serde_json::Value::Null,
-        ))?)),
+        _ => Ok(Json(unknown_action())),
     }
 }
 
@@ -145,10 +134,7 @@ async fn handle_block_module(
 ) -> ApiResult<Json<serde_json::Value>> {
     match query.action.as_str() {
         "getblockreward" => get_block_reward(state, query).await,
-        _ => Ok(Json(serde_json::to_value(EtherscanResponse::error(
-            format!("Unknown action: {}", query.action),
-            serde_json::Value::Null,
-        ))?)),
+        _ => Ok(Json(unknown_action())),
     }
 }
 
@@ -157,6 +143,10 @@ async fn handle_proxy_module(
     state: Arc<AppState>,
     query: EtherscanQuery,
 ) -> ApiResult<Json<serde_json::Value>> {
+    if query.action == "eth_getTransactionByBlockNumberAndIndex" {
+        return get_tx_by_block_number_and_index(state, query).await;
+    }
+
     let provider = ProviderBuilder::new().connect_http(
         state
             .rpc_url
@@ -170,29 +160,21 @@ async fn handle_proxy_module(
                 .get_block_number()
                 .await
                 .map_err(|e| AtlasError::Rpc(e.to_string()))?;
-            Ok(Json(serde_json::to_value(EtherscanResponse::ok(format!(
-                "0x{:x}",
-                block_number
-            )))?))
+            Ok(Json(rpc_result(format!("0x{:x}", block_number))))
         }
         "eth_getBlockByNumber" => {
+            // Etherscan takes `tag`; `blockno` is accepted for older clients.
             let block_no = query
-                .blockno
+                .tag
                 .as_ref()
-                .ok_or_else(|| AtlasError::InvalidInput("blockno required".to_string()))?;
-            let block_num: u64 = if let Some(stripped) = block_no.strip_prefix("0x") {
-                u64::from_str_radix(stripped, 16)
-                    .map_err(|_| AtlasError::InvalidInput("Invalid block number".to_string()))?
-            } else {
-                block_no
-                    .parse()
-                    .map_err(|_| AtlasError::InvalidInput("Invalid block number".to_string()))?
-            };
+                .or(query.blockno.as_ref())
+                .ok_or_else(|| AtlasError::InvalidInput("tag required".to_string()))?;
+            let block_num = parse_quantity(block_no, "block number")?;
             let block = provider
                 .get_block_by_number(alloy::rpc::types::BlockNumberOrTag::Number(block_num))
                 .await
                 .map_err(|e| AtlasError::Rpc(e.to_string()))?;
-            Ok(Json(serde_json::to_value(EtherscanResponse::ok(block))?))
+            Ok(Json(rpc_result(block)))
         }
         "eth_getTransactionByHash" => {
             let hash = query
@@ -206,15 +188,86 @@ async fn handle_proxy_module(
                 .get_transaction_by_hash(hash_bytes)
                 .await
                 .map_err(|e| AtlasError::Rpc(e.to_string()))?;
-            Ok(Json(serde_json::to_value(EtherscanResponse::ok(tx))?))
+            Ok(Json(rpc_result(tx)))
         }
-        _ => Ok(Json(serde_json::to_value(EtherscanResponse::error(
-            format!("Unknown proxy action: {}", query.action),
-            serde_json::Value::Null,
-        ))?)),
+        _ => Ok(Json(unknown_action())),
     }
 }
 
+/// Transaction in JSON-RPC format, built from indexed columns. Fields the
+/// indexer does not store (nonce, gas limit, signature, type) are omitted.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RpcTransaction {
+    block_hash: String,
+    block_number: String,
+    from: String,
+    gas_price: String,
+    hash: String,
+    input: String,
+    to: Option<String>,
+    transaction_index: String,
+    value: String,
+}
+
+#[derive(sqlx::FromRow)]
+struct IndexedTransaction {
+    #[sqlx(flatten)]
+    transaction: Transaction,
+    block_hash: String,
+}
+
+/// `eth_getTransactionByBlockNumberAndIndex` served from the database, so it
+/// needs no RPC. The result is null when no such transaction is indexed.
+async fn get_tx_by_block_number_and_index(
+    state: Arc<AppState>,
+    query: EtherscanQuery,
+) -> ApiResult<Json<serde_json::Value>> {
+    let tag = query
+        .tag
+        .as_ref()
+        .ok_or_else(|| AtlasError::InvalidInput("tag required".to_string()))?;
+    let index = query
+        .index
+        .as_ref()
+        .ok_or_else(|| AtlasError::InvalidInput("index required".to_string()))?;
+    let block_number = i64::try_from(parse_quantity(tag, "block number")?)
+        .map_err(|_| AtlasError::InvalidInput("Invalid block number".to_string()))?;
+    let index = i32::try_from(parse_quantity(index, "transaction index")?)
+        .map_err(|_| AtlasError::InvalidInput("Invalid transaction index".to_string()))?;
+
+    let row: Option<IndexedTransaction> = sqlx::query_as(
+        "SELECT t.hash, t.block_number, t.block_index, t.from_address, t.to_address, t.value, t.gas_price,
+                t.gas_used, t.input_data, t.status, t.contract_created, t.timestamp, b.hash AS block_hash
+         FROM transactions t
+         JOIN blocks b ON b.number = t.block_number
+         WHERE t.block_number = $1 AND t.block_index = $2",
+    )
+    .bind(block_number)
+    .bind(index)
+    .fetch_optional(&state.pool)
+    .await?;
+
+    let result = row.map(
+        |IndexedTransaction {
+             transaction: tx,
+             block_hash,
+         }| RpcTransaction {
+            block_hash,
+            block_number: format!("0x{:x}", tx.block_number),
+            from: tx.from_address,
+            gas_price: quantity_hex(&tx.gas_price),
+            hash: tx.hash,
+            input: format!("0x{}", hex::encode(&tx.input_data)),
+            to: tx.to_address,
+            transaction_index: format!("0x{:x}", tx.block_index),
+            value: quantity_hex(&tx.value),
+        },
+    );
+
+    Ok(Json(rpc_result(result)))
+}
+
 // =====================
 // Account Module Actions
 // =====================
@@ -227,7 +280,7 @@ async fn get_balance(
         .address
         .as_ref()
         .ok_or_else(|| AtlasError::InvalidInput("address required".to_string()))?;
-    let address = normalize_address(address);
+    let address = parse_address(address)?;
 
     // Get balance from RPC
     let provider = ProviderBuilder::new().connect_http(
@@ -239,7 +292,7 @@ async fn get_balance(
 
     let addr: alloy::primitives::Address = address
         .parse()
-        .map_err(|_| AtlasError::InvalidInput("Invalid address".to_string()))?;
+        .map_err(|_| AtlasError::InvalidInput("Invalid address format".to_string()))?;
 
     let balance = provider
         .get_balance(addr)
@@ -278,7 +331,7 @@ async fn get_balance_multi(
     let mut results = Vec::new();
 
     for addr_str in addresses {
-        let addr_str = normalize_address(addr_str.trim());
+        let addr_str = parse_address(addr_str.trim())?;
         let addr: alloy::primitives::Address = addr_str
             .parse()
             .map_err(|_| AtlasError::InvalidInput(format!("Invalid address: {}", addr_str)))?;
@@ -329,21 +382,25 @@ async fn get_tx_list(
         .address
         .as_ref()
         .ok_or_else(|| AtlasError::InvalidInput("address required".to_string()))?;
-    let address = normalize_address(address);
+    let address = parse_address(address)?;
 
     let page = query.page.unwrap_or(1);
     let limit = query.offset.unwrap_or(10).min(100) as i64;
     let offset = ((page.saturating_sub(1)) as i64) * limit;
     let sort = query.sort.as_deref().unwrap_or("desc");
-    let order = if sort == "asc" { "ASC" } else { "DESC" };
+    let direction = if sort == "asc" {
+        Direction::Asc
+    } else {
+        Direction::Desc
+    };
 
     let sql = format!(
         "SELECT hash, block_number, block_index, from_address, to_address, value, gas_price, gas_used, input_data, status, contract_created, timestamp
          FROM transactions
          WHERE from_address = $1 OR to_address = $1
-         ORDER BY block_number {}, block_index {}
+         ORDER BY {}
          LIMIT $2 OFFSET $3",
-        order, order
+        ChainRow::Transaction.order_by("", direction)
     );
 
     let transactions: Vec<Transaction> = sqlx::query_as(&sql)
@@ -353,15 +410,12 @@ async fn get_tx_list(
         .fetch_all(&state.pool)
         .await?;
 
-    // Get current block for confirmations
-    let current_block: (i64,) = sqlx::query_as("SELECT COALESCE(MAX(number), 0) FROM blocks")
-        .fetch_one(&state.pool)
-        .await?;
+    let head = state.confirmations_height().await?;
 
     let result: Vec<EtherscanTransaction> = transactions
         .into_iter()
         .map(|tx| {
-            let confirmations = current_block.0.saturating_sub(tx.block_number);
+            let confirmations = confirmations(head, tx.block_number);
             EtherscanTransaction {
                 block_number: tx.block_number.to_string(),
                 time_stamp: tx.timestamp.to_string(),
@@ -371,9 +425,9 @@ async fn get_tx_list(
                 transaction_index: tx.block_index.to_string(),
                 from: tx.from_address,
                 to: tx.to_address.unwrap_or_default(),
-                value: tx.value.to_string(),
+                value: decimal_string(&tx.value),
                 gas: tx.gas_used.to_string(),
-                gas_price: tx.gas_price.to_string(),
+                gas_price: decimal_string(&tx.gas_price),
                 is_error: if tx.status { "0" } else { "1" }.to_string(),
                 txreceipt_status: if tx.status { "1" } else { "0" }.to_string(),
                 input: format!("0x{}", hex::encode(&tx.input_data)),
@@ -385,7 +439,7 @@ async fn get_tx_list(
         })
         .collect();
 
-    Ok(Json(serde_json::to_value(EtherscanResponse::ok(result))?))
+    Ok(Json(serde_json::to_value(EtherscanResponse::list(result))?))
 }
 
 async fn get_internal_tx_list(
@@ -393,7 +447,7 @@ async fn get_internal_tx_list(
     _query: EtherscanQuery,
 ) -> ApiResult<Json<serde_json::Value>> {
     // Internal transactions require trace support - return empty for now
-    Ok(Json(serde_json::to_value(EtherscanResponse::ok(Vec::<
+    Ok(Json(serde_json::to_value(EtherscanResponse::list(Vec::<
         serde_json::Value,
     >::new(
     )))?))
@@ -424,19 +478,10 @@ struct EtherscanTokenTransfer {
     confirmations: String,
 }
 
-/// Token transfer with contract info from JOIN
+/// Name, symbol and decimals of a transfer's token
 #[derive(Debug, sqlx::FromRow)]
-struct TokenTransferRow {
-    #[allow(dead_code)]
-    id: i64,
-    tx_hash: String,
-    log_index: i32,
-    contract_address: String,
-    from_address: String,
-    to_address: String,
-    value: BigDecimal,
-    block_number: i64,
-    timestamp: i64,
+struct TokenInfo {
+    address: String,
     name: Option<String>,
     symbol: Option<String>,
     decimals: i16,
@@ -450,35 +495,76 @@ async fn get_token_tx_list(
         .address
         .as_ref()
         .ok_or_else(|| AtlasError::InvalidInput("address required".to_string()))?;
-    let address = normalize_address(address);
+    let address = parse_address(address)?;
 
     let page = query.page.unwrap_or(1);
     let limit = query.offset.unwrap_or(10).min(100) as i64;
     let offset = ((page.saturating_sub(1)) as i64) * limit;
 
-    let transfers: Vec<TokenTransferRow> = sqlx::query_as(
-        "SELECT t.id, t.tx_hash, t.log_index, t.contract_address, t.from_address, t.to_address, t.value, t.block_number, t.timestamp,
-                c.name, c.symbol, COALESCE(c.decimals, 18) as decimals
-         FROM erc20_transfers t
-         LEFT JOIN erc20_contracts c ON t.contract_address = c.address
-         WHERE t.from_address = $1 OR t.to_address = $1
-         ORDER BY t.block_number DESC, t.log_index DESC
-         LIMIT $2 OFFSET $3",
+    // Segments may live in another database, so tokens are looked up
+    // separately rather than joined.
+    let segments = segments_newest_first(&state, TieredTable::Erc20Transfers, i64::MAX);
+    let fetch = |segment: Segment, limit: i64, offset: i64| {
+        let address = &address;
+        async move {
+            let sql = format!(
+                "SELECT id, tx_hash, log_index, contract_address, from_address, to_address, value, block_number, timestamp
+                 FROM {}
+                 WHERE (from_address = $1 OR to_address = $1) AND block_number BETWEEN $2 AND $3
+                 ORDER BY {}
+                 LIMIT $4 OFFSET $5",
+                segment.table,
+                ChainRow::Log.order_by("", Direction::Desc)
+            );
+            sqlx::query_as::<_, Erc20Transfer>(&sql)
+                .bind(address)
+                .bind(segment.blocks.from)
+                .bind(segment.blocks.to)
+                .bind(limit)
+                .bind(offset)
+                .fetch_all(&segment.pool)
+                .await
+        }
+    };
+    let count = |segment: Segment| {
+        let address = &address;
+        async move {
+            let sql = format!(
+                "SELECT COUNT(*) FROM {}
+                 WHERE (from_address = $1 OR to_address = $1) AND block_number BETWEEN $2 AND $3",
+                segment.table
+            );
+            sqlx::query_scalar(&sql)
+                .bind(address)
+                .bind(segment.blocks.from)
+                .bind(segment.blocks.to)
+                .fetch_one(&segment.pool)
+                .await
+        }
+    };
+    let transfers = stitch_segments(&segments, offset, limit, fetch, count).await?;
+
+    let contracts: Vec<String> = transfers
+        .iter()
+        .map(|t| t.contract_address.clone())
+        .collect();
+    let tokens: HashMap<String, TokenInfo> = sqlx::query_as::<_, TokenInfo>(
+        "SELECT address, name, symbol, decimals FROM erc20_contracts WHERE address = ANY($1)",
     )
-    .bind(&address)
-    .bind(limit)
-    .bind(offset)
+    .bind(&contracts)
     .fetch_all(&state.pool)
-    .await?;
+    .await?
+    .into_iter()
+    .map(|token| (token.address.clone(), token))
+    .collect();
 
-    let current_block: (i64,) = sqlx::query_as("SELECT COALESCE(MAX(number), 0) FROM blocks")
-        .fetch_one(&state.pool)
-        .await?;
+    let head = state.confirmations_height().await?;
 
     let result: Vec<EtherscanTokenTransfer> = transfers
         .into_iter()
         .map(|transfer| {
-            let confirmations = current_block.0.saturating_sub(transfer.block_number);
+            let confirmations = confirmations(head, transfer.block_number);
+            let token = tokens.get(&transfer.contract_address);
             EtherscanTokenTransfer {
                 block_number: transfer.block_number.to_string(),
                 time_stamp: transfer.timestamp.to_string(),
@@ -488,10 +574,10 @@ async fn get_token_tx_list(
                 from: transfer.from_address,
                 contract_address: transfer.contract_address,
                 to: transfer.to_address,
-                value: transfer.value.to_string(),
-                token_name: transfer.name.unwrap_or_default(),
-                token_symbol: transfer.symbol.unwrap_or_default(),
-                token_decimal: transfer.decimals.to_string(),
+                value: decimal_string(&transfer.value),
+                token_name: token.and_then(|t| t.name.clone()).unwrap_or_default(),
+                token_symbol: token.and_then(|t| t.symbol.clone()).unwrap_or_default(),
+                token_decimal: token.map_or(18, |t| t.decimals).to_string(),
                 transaction_index: transfer.log_index.to_string(),
                 gas: "0".to_string(),
                 gas_price: "0".to_string(),
@@ -503,7 +589,7 @@ async fn get_token_tx_list(
         })
         .collect();
 
-    Ok(Json(serde_json::to_value(EtherscanResponse::ok(result))?))
+    Ok(Json(serde_json::to_value(EtherscanResponse::list(result))?))
 }
 
 async fn get_token_balance(
@@ -519,8 +605,8 @@ async fn get_token_balance(
         .as_ref()
         .ok_or_else(|| AtlasError::InvalidInput("contractaddress required".to_string()))?;
 
-    let address = normalize_address(address);
-    let contract_address = normalize_address(contract_address);
+    let address = parse_address(address)?;
+    let contract_address = parse_address(contract_address)?;
 
     let balance: Option<(BigDecimal,)> = sqlx::query_as(
         "SELECT balance FROM erc20_balances
@@ -532,7 +618,7 @@ async fn get_token_balance(
     .await?;
 
     let balance_str = balance
-        .map(|(b,)| b.to_string())
+        .map(|(b,)| decimal_string(&b))
         .unwrap_or_else(|| "0".to_string());
 
     Ok(Json(serde_json::to_value(EtherscanResponse::ok(
@@ -552,7 +638,7 @@ async fn get_contract_abi(
         .address
         .as_ref()
         .ok_or_else(|| AtlasError::InvalidInput("address required".to_string()))?;
-    let address = normalize_address(address);
+    let address = parse_address(address)?;
 
     let abi: Option<ContractAbi> = sqlx::query_as(
         "SELECT address, abi, source_code, compiler_version, optimization_used, runs, verified_at
@@ -570,8 +656,8 @@ async fn get_contract_abi(
             Ok(Json(serde_json::to_value(EtherscanResponse::ok(abi_str))?))
         }
         None => Ok(Json(serde_json::to_value(EtherscanResponse::error(
-            "Contract source code not verified",
-            "".to_string(),
+            NOTOK,
+            NOT_VERIFIED,
         ))?)),
     }
 }
@@ -605,7 +691,7 @@ async fn get_source_code(
         .address
         .as_ref()
         .ok_or_else(|| AtlasError::InvalidInput("address required".to_string()))?;
-    let address = normalize_address(address);
+    let address = parse_address(address)?;
 
     let contract: Option<ContractAbi> = sqlx::query_as(
         "SELECT address, abi, source_code, compiler_version, optimization_used, runs, verified_at
@@ -642,9 +728,9 @@ async fn get_source_code(
                 .to_string(),
                 runs: c.runs.unwrap_or(200).to_string(),
                 constructor_arguments: "".to_string(),
-                evm_version: "".to_string(),
+                evm_version: "Default".to_string(),
                 library: "".to_string(),
-                license_type: "".to_string(),
+                license_type: "Unknown".to_string(),
                 proxy: if proxy.is_some() { "1" } else { "0" }.to_string(),
                 implementation: proxy
                     .as_ref()
@@ -656,13 +742,94 @@ async fn get_source_code(
                 result,
             ]))?))
         }
-        None => Ok(Json(serde_json::to_value(EtherscanResponse::error(
-            "Contract source code not verified",
-            Vec::<SourceCodeResult>::new(),
-        ))?)),
+        // Etherscan answers unverified contracts with OK and a placeholder
+        // row; Foundry and Hardhat detect it by the ABI text.
+        None => Ok(Json(serde_json::to_value(EtherscanResponse::ok(vec![
+            SourceCodeResult {
+                source_code: "".to_string(),
+                abi: NOT_VERIFIED.to_string(),
+                contract_name: "".to_string(),
+                compiler_version: "".to_string(),
+                optimization_used: "".to_string(),
+                runs: "".to_string(),
+                constructor_arguments: "".to_string(),
+                evm_version: "Default".to_string(),
+                library: "".to_string(),
+                license_type: "Unknown".to_string(),
+                proxy: "0".to_string(),
+                implementation: "".to_string(),
+                swarm_source: "".to_string(),
+            },
+        ]))?)),
     }
 }
 
+/// Contract creation row in Etherscan format
+#[derive(Debug, Serialize, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+struct ContractCreation {
+    contract_address: String,
+    contract_creator: String,
+    tx_hash: String,
+    block_number: String,
+    timestamp: String,
+    /// Always empty: only contracts deployed by a transaction are recorded
+    contract_factory: String,
+    creation_bytecode: String,
+}
+
+async fn get_contract_creation(
+    state: Arc<AppState>,
+    query: EtherscanQuery,
+) -> ApiResult<Json<serde_json::Value>> {
+    let addresses_str = query
+        .contractaddresses
+        .as_ref()
+        .ok_or_else(|| AtlasError::InvalidInput("contractaddresses required".to_string()))?;
+    let addresses = addresses_str
+        .split(',')
+        .map(|address| parse_address(address.trim()))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if addresses.len() > 5 {
+        return Err(AtlasError::Validation(
+            "Maximum 5 contract addresses allowed per request".into(),
+        )
+        .into());
+    }
+
+    let creations: Vec<ContractCreation> = sqlx::query_as(
+        "SELECT a.address AS contract_address,
+                a.deployer AS contract_creator,
+                a.creation_tx_hash AS tx_hash,
+                a.creation_block::text AS block_number,
+                COALESCE(b.timestamp::text, '') AS timestamp,
+                '' AS contract_factory,
+                COALESCE('0x' || encode(t.input_data, 'hex'), '') AS creation_bytecode
+         FROM unnest($1::text[]) WITH ORDINALITY AS req(address, position)
+         JOIN addresses a ON a.address = req.address
+         LEFT JOIN blocks b ON b.number = a.creation_block
+         LEFT JOIN transactions t
+           ON t.hash = a.creation_tx_hash AND t.block_number = a.creation_block
+         WHERE a.deployer IS NOT NULL AND a.creation_tx_hash IS NOT NULL
+           AND a.creation_block IS NOT NULL
+         ORDER BY req.position",
+    )
+    .bind(&addresses)
+    .fetch_all(&state.pool)
+    .await?;
+
+    if creations.is_empty() {
+        return Ok(Json(serde_json::to_value(EtherscanResponse::error(
+            NO_DATA_FOUND,
+            serde_json::Value::Null,
+        ))?));
+    }
+    Ok(Json(serde_json::to_value(EtherscanResponse::ok(
+        creations,
+    ))?))
+}
+
 // =====================
 // Transaction Module Actions
 // =====================
@@ -675,19 +842,25 @@ async fn get_tx_receipt_status(
         .txhash
         .as_ref()
         .ok_or_else(|| AtlasError::InvalidInput("txhash required".to_string()))?;
-    let txhash = normalize_hash(txhash);
-
-    let status: Option<(bool,)> = sqlx::query_as("SELECT status FROM transactions WHERE hash = $1")
-        .bind(&txhash)
-        .fetch_optional(&state.pool)
-        .await?;
+    let txhash = parse_hash(txhash)?;
+
+    let status: Option<(bool,)> = match canonical_tx_block(&state.pool, &txhash).await? {
+        Some(block_number) => {
+            sqlx::query_as("SELECT status FROM transactions WHERE hash = $1 AND block_number = $2")
+                .bind(&txhash)
+                .bind(block_number)
+                .fetch_optional(&state.pool)
+                .await?
+        }
+        None => None,
+    };
 
     match status {
         Some((success,)) => Ok(Json(serde_json::to_value(EtherscanResponse::ok(
             serde_json::json!({ "status": if success { "1" } else { "0" } }),
         ))?)),
-        None => Ok(Json(serde_json::to_value(EtherscanResponse::error(
-            "Transaction not found",
+        // Etherscan reports unknown and pre-Byzantium transactions alike.
+        None => Ok(Json(serde_json::to_value(EtherscanResponse::ok(
             serde_json::json!({ "status": "" }),
         ))?)),
     }
@@ -729,25 +902,82 @@ async fn get_block_reward(
                 }),
             ))?))
         }
-        None => Ok(Json(serde_json::to_value(EtherscanResponse::error(
-            "Block not found",
-            serde_json::Value::Null,
-        ))?)),
+        None => Err(AtlasError::NotFound("Block not found".to_string()).into()),
     }
 }
 
-fn normalize_address(address: &str) -> String {
-    if address.starts_with("0x") {
-        address.to_lowercase()
-    } else {
-        format!("0x{}", address.to_lowercase())
-    }
+/// Parse a JSON-RPC quantity given as hex (`0x`-prefixed) or decimal.
+fn parse_quantity(value: &str, what: &str) -> Result<u64, AtlasError> {
+    let parsed = match value.strip_prefix("0x") {
+        Some(stripped) => u64::from_str_radix(stripped, 16),
+        None => value.parse(),
+    };
+    parsed.map_err(|_| AtlasError::InvalidInput(format!("Invalid {}", what)))
 }
 
-fn normalize_hash(hash: &str) -> String {
-    if hash.starts_with("0x") {
-        hash.to_lowercase()
-    } else {
-        format!("0x{}", hash.to_lowercase())
+/// Decimal string of a stored integer amount. `BigDecimal`'s `Display` may use
+/// exponent notation (`100e+16`), which Etherscan clients cannot parse.
+fn decimal_string(value: &BigDecimal) -> String {
+    value.with_scale(0).to_plain_string()
+}
+
+/// Hex JSON-RPC quantity of a stored wei amount.
+fn quantity_hex(value: &BigDecimal) -> String {
+    decimal_string(value)
+        .parse::<alloy::primitives::U256>()
+        .map(|v| format!("{:#x}", v))
+        .unwrap_or_else(|_| "0x0".to_string())
+}
+
+/// Blocks on top of `block_number` up to `head`. Blocks past the head
+/// (e.g. not yet finalized) and an unknown head count as unconfirmed.
+fn confirmations(head: Option<i64>, block_number: i64) -> i64 {
+    head.map_or(0, |head| (head - block_number).max(0))
+}
+
+/// Lowercase `0x`-prefixed address, with or without the prefix on input.
+fn parse_address(address: &str) -> Result<String, AtlasError> {
+    parse_hex(address, 40)
+        .ok_or_else(|| AtlasError::InvalidInput("Invalid address format".to_string()))
+}
+
+/// Lowercase `0x`-prefixed transaction hash, with or without the prefix on input.
+fn parse_hash(hash: &str) -> Result<String, AtlasError> {
+    parse_hex(hash, 64).ok_or_else(|| AtlasError::InvalidInput("Invalid txhash format".to_string()))
+}
+
+fn parse_hex(value: &str, digits: usize) -> Option<String> {
+    let hex = value.strip_prefix("0x").unwrap_or(value);
+    (hex.len() == digits && hex.bytes().all(|b| b.is_ascii_hexdigit()))
+        .then(|| format!("0x{}", hex.to_lowercase()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn confirmations_never_go_negative() {
+        assert_eq!(confirmations(Some(100), 90), 10);
+        assert_eq!(confirmations(Some(100), 100), 0);
+        assert_eq!(confirmations(Some(100), 105), 0);
+        assert_eq!(confirmations(None, 90), 0);
+    }
+
+    #[test]
+    fn parse_address_normalizes_and_rejects_malformed_input() {
+        let lower = format!("0x{}", "ab".repeat(20));
+        assert_eq!(parse_address(&"AB".repeat(20)).unwrap(), lower);
+        assert_eq!(parse_address(&lower).unwrap(), lower);
+        assert!(parse_address("0x1234").is_err());
+        assert!(parse_address(&"zz".repeat(20)).is_err());
+        assert!(parse_hash(&lower).is_err());
+    }
+
+    #[test]
+    fn decimal_string_never_uses_exponent_notation() {
+        let wei: BigDecimal = "100e+16".parse().unwrap();
+        assert_eq!(decimal_string(&wei), "1000000000000000000");
+        assert_eq!(decimal_string(&"2500000.0".parse().unwrap()), "2500000");
     }
 }
@@ -0,0 +1,223 @@
+//! Bulk export jobs; see [`crate::export`] for how they run.
+
+use axum::{
+    body::Body,
+    extract::{Path, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tokio::io::AsyncReadExt;
+
+use crate::api::error::ApiResult;
+use crate::api::handlers::require_hot_tier;
+use crate::api::AppState;
+use crate::export::{
+    new_job_id, ExportEntity, ExportFormat, ExportJob, ExportSpec, EXPORT_JOB_COLUMNS,
+    MAX_PENDING_EXPORTS,
+};
+use crate::labels::normalize_label_address;
+use atlas_common::storage::BlockRange;
+use atlas_common::AtlasError;
+
+const DOWNLOAD_CHUNK_SIZE: usize = 64 * 1024;
+
+#[derive(Debug, Deserialize)]
+pub struct ExportRequest {
+    /// `transactions`, `erc20_transfers`, `nft_transfers` or `logs`
+    pub entity: String,
+    /// `csv` (default) or `jsonl`
+    pub format: Option<String>,
+    pub address: Option<String>,
+    pub from_block: Option<i64>,
+    pub to_block: Option<i64>,
+}
+
+impl ExportRequest {
+    fn spec(self) -> Result<ExportSpec, AtlasError> {
+        let entity = ExportEntity::parse(&self.entity).ok_or_else(|| {
+            AtlasError::InvalidInput(
+                "entity must be one of transactions, erc20_transfers, nft_transfers, logs"
+                    .to_string(),
+            )
+        })?;
+        let format = match self.format.as_deref() {
+            None => ExportFormat::Csv,
+            Some(format) => ExportFormat::parse(format).ok_or_else(|| {
+                AtlasError::InvalidInput("format must be csv or jsonl".to_string())
+            })?,
+        };
+        let address = self
+            .address
+            .as_deref()
+            .map(normalize_label_address)
+            .transpose()?;
+        if self.from_block.is_some_and(|b| b < 0) || self.to_block.is_some_and(|b| b < 0) {
+            return Err(AtlasError::InvalidInput(
+                "Block numbers must not be negative".to_string(),
+            ));
+        }
+        if let (Some(from), Some(to)) = (self.from_block, self.to_block) {
+            if from > to {
+                return Err(AtlasError::InvalidInput(
+                    "from_block must not be after to_block".to_string(),
+                ));
+            }
+        }
+        Ok(ExportSpec {
+            entity,
+            format,
+            address,
+            from_block: self.from_block,
+            to_block: self.to_block,
+        })
+    }
+}
+
+/// Export job status. `download_url` is set once the job has completed.
+#[derive(Serialize)]
+pub struct ExportJobResponse {
+    #[serde(flatten)]
+    pub job: ExportJob,
+    pub download_url: Option<String>,
+}
+
+impl From<ExportJob> for ExportJobResponse {
+    fn from(job: ExportJob) -> Self {
+        let download_url =
+            (job.status == "completed").then(|| format!("/api/export/{}/download", job.id));
+        Self { job, download_url }
+    }
+}
+
+/// POST /api/export - Queue an export job
+pub async fn create_export(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<ExportRequest>,
+) -> ApiResult<(StatusCode, Json<ExportJobResponse>)> {
+    let spec = request.spec()?;
+    if let Some(table) = spec.entity.tiered_table() {
+        let blocks = BlockRange::new(
+            spec.from_block.unwrap_or(0),
+            spec.to_block.unwrap_or(i64::MAX),
+        );
+        require_hot_tier(
+            &state,
+            table,
+            blocks,
+            &format!("{} exports", spec.entity.as_str()),
+        )?;
+    }
+
+    let (pending,): (i64,) =
+        sqlx::query_as("SELECT COUNT(*) FROM export_jobs WHERE status IN ('queued', 'running')")
+            .fetch_one(&state.pool)
+            .await?;
+    if pending >= MAX_PENDING_EXPORTS {
+        return Err(AtlasError::TooManyRequests {
+            message: "Too many exports in progress, try again later".to_string(),
+            retry_after_seconds: 60,
+        }
+        .into());
+    }
+
+    let job: ExportJob = sqlx::query_as(&format!(
+        "INSERT INTO export_jobs (id, entity, format, address, from_block, to_block)
+         VALUES ($1, $2, $3, $4, $5, $6)
+         RETURNING {EXPORT_JOB_COLUMNS}"
+    ))
+    .bind(new_job_id())
+    .bind(spec.entity.as_str())
+    .bind(spec.format.as_str())
+    .bind(&spec.address)
+    .bind(spec.from_block)
+    .bind(spec.to_block)
+    .fetch_one(&state.pool)
+    .await?;
+
+    Ok((StatusCode::ACCEPTED, Json(job.into())))
+}
+
+/// GET /api/export/{id} - Export job status
+pub async fn get_export(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> ApiResult<Json<ExportJobResponse>> {
+    Ok(Json(find_job(&state, &id).await?.into()))
+}
+
+/// GET /api/export/{id}/download - Stream a completed export's file
+pub async fn download_export(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> ApiResult<Response> {
+    let job = find_job(&state, &id).await?;
+    if job.status != "completed" {
+        return Err(AtlasError::NotFound(format!("Export {} has no file yet", id)).into());
+    }
+    let format = job
+        .spec()
+        .map_err(|e| AtlasError::Internal(e.to_string()))?
+        .format;
+    let dir = state
+        .export_dir
+        .as_deref()
+        .ok_or_else(|| AtlasError::Config("exports are disabled".to_string()))?;
+    let file = match tokio::fs::File::open(std::path::Path::new(dir).join(job.file_name())).await {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            return Err(AtlasError::NotFound(format!("Export {} file not found", id)).into())
+        }
+        Err(e) => return Err(AtlasError::Internal(e.to_string()).into()),
+    };
+    Ok(attachment(
+        file,
+        format.content_type(),
+        &format!("atlas-{}-{}", job.entity, job.file_name()),
+    ))
+}
+
+/// Stream `file` as a download named `file_name`.
+pub(crate) fn attachment(
+    mut file: tokio::fs::File,
+    content_type: &'static str,
+    file_name: &str,
+) -> Response {
+    let stream = async_stream::try_stream! {
+        let mut buf = vec![0u8; DOWNLOAD_CHUNK_SIZE];
+        loop {
+            let n = file.read(&mut buf).await?;
+            if n == 0 {
+                break;
+            }
+            yield axum::body::Bytes::copy_from_slice(&buf[..n]);
+        }
+    };
+    let stream: std::pin::Pin<
+        Box<dyn futures::Stream<Item = std::io::Result<axum::body::Bytes>> + Send>,
+    > = Box::pin(stream);
+
+    (
+        [
+            (header::CONTENT_TYPE, content_type.to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{file_name}\""),
+            ),
+        ],
+        Body::from_stream(stream),
+    )
+        .into_response()
+}
+
+async fn find_job(state: &AppState, id: &str) -> Result<ExportJob, AtlasError> {
+    sqlx::query_as(&format!(
+        "SELECT {EXPORT_JOB_COLUMNS} FROM export_jobs WHERE id = $1"
+    ))
+    .bind(id)
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or_else(|| AtlasError::NotFound(format!("Export {} not found", id)))
+}
@@ -43,7 +43,7 @@ pub async fn request_faucet(
     Ok(Json(faucet.request_faucet(recipient, client_ip).await?))
 }
 
-fn extract_client_ip(headers: &HeaderMap) -> Result<String, AtlasError> {
+pub(crate) fn extract_client_ip(headers: &HeaderMap) -> Result<String, AtlasError> {
     // Prefer X-Real-IP — set by nginx to $remote_addr (trustworthy, not spoofable)
     if let Some(value) = headers.get("x-real-ip") {
         let real_ip = value
@@ -71,7 +71,7 @@ fn extract_client_ip(headers: &HeaderMap) -> Result<String, AtlasError> {
     }
 
     Err(AtlasError::InvalidInput(
-        "Client IP is required for faucet requests".to_string(),
+        "Client IP is required".to_string(),
     ))
 }
 
@@ -166,7 +166,7 @@ mod tests {
             .build_recorder()
             .handle();
         Arc::new(AppState {
-            pool,
+            pool: pool.clone(),
             block_events_tx: tx,
             da_events_tx: da_tx,
             head_tracker,
@@ -177,6 +177,7 @@ mod tests {
             faucet_cooldown_minutes: None,
             chain_id: 1,
             chain_name: "Test Chain".to_string(),
+            native_currency: Default::default(),
             chain_logo_url: None,
             chain_logo_url_light: None,
             chain_logo_url_dark: None,
@@ -188,6 +189,22 @@ mod tests {
             metrics: crate::metrics::Metrics::new(),
             prometheus_handle,
             solc_cache_dir: "/tmp/solc-cache".to_string(),
+            verify_timeout: std::time::Duration::from_secs(300),
+            verify_max_source_bytes: 20 * 1024 * 1024,
+            export_dir: None,
+            dataset_dir: None,
+            db_circuit_breaker: crate::api::circuit_breaker::CircuitBreaker::default(),
+            latency: Default::default(),
+            admin_api_key: None,
+            db_pools: Arc::new(crate::db_pools::DbPools::new(Vec::new())),
+            concurrency_limits: Default::default(),
+            body_limits: Default::default(),
+            log_limits: Default::default(),
+            row_limits: Default::default(),
+            storage: Arc::new(atlas_common::storage::HotStorage::new(pool)),
+            confirmations_head: Default::default(),
+            block_stall_secs: 0,
+            runtime_settings: Default::default(),
         })
     }
 
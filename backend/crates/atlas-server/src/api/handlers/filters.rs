@@ -0,0 +1,262 @@
+//! Saved filters: named API queries kept per API key
+//!
+//! A saved filter is a batch call (an API path plus its query parameters,
+//! see [`batch`](super::batch)) under a name unique to the key that saved
+//! it, e.g. "USDC transfers" → `/api/tokens/0x…/transfers`. Frontend sessions
+//! and alerting rules load the filter and run it directly or through
+//! `/api/batch`. Like address notes, filters belong to a key issued through
+//! `/api/labels/keys`.
+
+use axum::{
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::api::auth::authorize_api_key;
+use crate::api::error::ApiResult;
+use crate::api::handlers::batch::{call_uri, BatchCall};
+use crate::api::AppState;
+use crate::labels::validate_label_name;
+use atlas_common::{AtlasError, SavedFilter};
+
+const MAX_DESCRIPTION_LEN: usize = 1000;
+/// Filters one key may save.
+const MAX_FILTERS_PER_KEY: i64 = 100;
+
+const FILTER_COLUMNS: &str = "id, name, description, method, params, created_at, updated_at";
+
+#[derive(Debug, Deserialize)]
+pub struct FilterRequest {
+    pub name: String,
+    pub description: Option<String>,
+    /// API path, e.g. `/api/tokens/0x…/transfers`
+    pub method: String,
+    /// Query parameters; strings, numbers and booleans
+    #[serde(default)]
+    pub params: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Validated [`FilterRequest`].
+struct FilterFields {
+    name: String,
+    description: Option<String>,
+    method: String,
+    params: serde_json::Value,
+}
+
+/// POST /api/me/filters — save a named filter for the calling key
+pub async fn create_filter(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Json(request): Json<FilterRequest>,
+) -> ApiResult<(StatusCode, Json<SavedFilter>)> {
+    let key_id = authorize_api_key(&state, &headers).await?;
+    let fields = validate_filter(request)?;
+
+    let mut tx = state.pool.begin().await?;
+    // Serialize saves per key so concurrent requests can't overshoot the cap.
+    sqlx::query("SELECT id FROM label_api_keys WHERE id = $1 FOR UPDATE")
+        .bind(key_id)
+        .execute(&mut *tx)
+        .await?;
+    let (saved,): (i64,) =
+        sqlx::query_as("SELECT COUNT(*) FROM saved_filters WHERE api_key_id = $1")
+            .bind(key_id)
+            .fetch_one(&mut *tx)
+            .await?;
+    if saved >= MAX_FILTERS_PER_KEY {
+        return Err(AtlasError::InvalidInput(format!(
+            "An API key can save at most {MAX_FILTERS_PER_KEY} filters"
+        ))
+        .into());
+    }
+
+    let filter: SavedFilter = sqlx::query_as(&format!(
+        "INSERT INTO saved_filters (api_key_id, name, description, method, params)
+         VALUES ($1, $2, $3, $4, $5)
+         ON CONFLICT (api_key_id, name) DO NOTHING
+         RETURNING {FILTER_COLUMNS}"
+    ))
+    .bind(key_id)
+    .bind(&fields.name)
+    .bind(&fields.description)
+    .bind(&fields.method)
+    .bind(&fields.params)
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| duplicate_name(&fields.name))?;
+    tx.commit().await?;
+
+    Ok((StatusCode::CREATED, Json(filter)))
+}
+
+/// GET /api/me/filters — the calling key's filters by name
+pub async fn list_filters(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+) -> ApiResult<Json<Vec<SavedFilter>>> {
+    let key_id = authorize_api_key(&state, &headers).await?;
+    let filters: Vec<SavedFilter> = sqlx::query_as(&format!(
+        "SELECT {FILTER_COLUMNS} FROM saved_filters WHERE api_key_id = $1 ORDER BY name"
+    ))
+    .bind(key_id)
+    .fetch_all(&state.pool)
+    .await?;
+    Ok(Json(filters))
+}
+
+/// GET /api/me/filters/{id} — one of the calling key's filters
+pub async fn get_filter(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Path(id): Path<i64>,
+) -> ApiResult<Json<SavedFilter>> {
+    let key_id = authorize_api_key(&state, &headers).await?;
+    let filter: SavedFilter = sqlx::query_as(&format!(
+        "SELECT {FILTER_COLUMNS} FROM saved_filters WHERE id = $1 AND api_key_id = $2"
+    ))
+    .bind(id)
+    .bind(key_id)
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or_else(|| not_found(id))?;
+    Ok(Json(filter))
+}
+
+/// PUT /api/me/filters/{id} — replace one of the calling key's filters
+pub async fn update_filter(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Path(id): Path<i64>,
+    Json(request): Json<FilterRequest>,
+) -> ApiResult<Json<SavedFilter>> {
+    let key_id = authorize_api_key(&state, &headers).await?;
+    let fields = validate_filter(request)?;
+
+    let result = sqlx::query_as(&format!(
+        "UPDATE saved_filters
+         SET name = $3, description = $4, method = $5, params = $6, updated_at = NOW()
+         WHERE id = $1 AND api_key_id = $2
+         RETURNING {FILTER_COLUMNS}"
+    ))
+    .bind(id)
+    .bind(key_id)
+    .bind(&fields.name)
+    .bind(&fields.description)
+    .bind(&fields.method)
+    .bind(&fields.params)
+    .fetch_optional(&state.pool)
+    .await;
+    let filter: SavedFilter = match result {
+        Ok(filter) => filter.ok_or_else(|| not_found(id))?,
+        Err(sqlx::Error::Database(error)) if error.is_unique_violation() => {
+            return Err(duplicate_name(&fields.name).into())
+        }
+        Err(error) => return Err(error.into()),
+    };
+    Ok(Json(filter))
+}
+
+/// DELETE /api/me/filters/{id} — remove one of the calling key's filters
+pub async fn delete_filter(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Path(id): Path<i64>,
+) -> ApiResult<StatusCode> {
+    let key_id = authorize_api_key(&state, &headers).await?;
+    let deleted = sqlx::query("DELETE FROM saved_filters WHERE id = $1 AND api_key_id = $2")
+        .bind(id)
+        .bind(key_id)
+        .execute(&state.pool)
+        .await?
+        .rows_affected();
+    if deleted == 0 {
+        return Err(not_found(id).into());
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Check the name and description, and that the query is one
+/// `/api/batch` would run.
+fn validate_filter(request: FilterRequest) -> Result<FilterFields, AtlasError> {
+    let name = validate_label_name(&request.name)?;
+    let description = request
+        .description
+        .map(|d| d.trim().to_string())
+        .filter(|d| !d.is_empty());
+    if description
+        .as_ref()
+        .is_some_and(|d| d.chars().count() > MAX_DESCRIPTION_LEN)
+    {
+        return Err(AtlasError::InvalidInput(format!(
+            "Description must be at most {MAX_DESCRIPTION_LEN} characters"
+        )));
+    }
+
+    let call = BatchCall {
+        id: serde_json::Value::Null,
+        method: request.method.trim().to_string(),
+        params: request.params,
+    };
+    call_uri(&call).map_err(AtlasError::InvalidInput)?;
+
+    Ok(FilterFields {
+        name,
+        description,
+        method: call.method,
+        params: serde_json::Value::Object(call.params),
+    })
+}
+
+fn not_found(id: i64) -> AtlasError {
+    AtlasError::NotFound(format!("Saved filter {id} not found"))
+}
+
+fn duplicate_name(name: &str) -> AtlasError {
+    AtlasError::InvalidInput(format!("A saved filter named '{name}' already exists"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(json: serde_json::Value) -> FilterRequest {
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn validate_filter_accepts_only_runnable_queries() {
+        let fields = validate_filter(request(serde_json::json!({
+            "name": " USDC transfers ",
+            "description": "  ",
+            "method": "/api/tokens/0x1/transfers",
+            "params": {"limit": 50},
+        })))
+        .unwrap();
+        assert_eq!(fields.name, "USDC transfers");
+        assert_eq!(fields.description, None);
+        assert_eq!(fields.params, serde_json::json!({"limit": 50}));
+
+        for method in ["/health", "/api/batch", "/api/blocks?page=2"] {
+            assert!(validate_filter(request(serde_json::json!({
+                "name": "bad",
+                "method": method,
+            })))
+            .is_err());
+        }
+        assert!(validate_filter(request(serde_json::json!({
+            "name": "bad",
+            "method": "/api/logs",
+            "params": {"topic0": ["0x1"]},
+        })))
+        .is_err());
+        assert!(validate_filter(request(serde_json::json!({
+            "name": "",
+            "method": "/api/blocks",
+        })))
+        .is_err());
+    }
+}
@@ -0,0 +1,238 @@
+//! Fee suggestions for wallets and dapps.
+//!
+//! Estimates come from the newest indexed blocks while the indexer is close to
+//! the chain head. When it is further behind, as during a backfill, they come
+//! from the node's `eth_feeHistory` instead, and from the indexed blocks again
+//! if that call fails. Every response says which source it used and how far
+//! the index lags, so clients can tell a fresh estimate from a stale one.
+
+use axum::{extract::State, Json};
+use bigdecimal::BigDecimal;
+use serde::Serialize;
+use serde_json::Value;
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::raw::rpc_call_with_timeout;
+use crate::api::error::ApiResult;
+use crate::api::AppState;
+use atlas_common::AtlasError;
+
+/// Recent blocks the estimate is drawn from.
+const ORACLE_BLOCKS: i64 = 20;
+/// Priority fee percentiles for the slow, standard and fast suggestions.
+const PERCENTILES: [f64; 3] = [25.0, 50.0, 75.0];
+/// Lag above which the indexed blocks are considered too old to price fees.
+const MAX_INDEXED_LAG_BLOCKS: i64 = 20;
+/// Kept short so a slow node leaves time for the indexed fallback.
+const FEE_HISTORY_TIMEOUT: Duration = Duration::from_secs(3);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GasOracleSource {
+    Indexed,
+    Rpc,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub struct PriorityFees {
+    pub slow: String,
+    pub standard: String,
+    pub fast: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct GasOracle {
+    pub source: GasOracleSource,
+    /// Newest block the estimate covers
+    pub block_number: Option<i64>,
+    pub indexed_block: Option<i64>,
+    /// `null` when the chain head is not known to this instance
+    pub chain_head: Option<i64>,
+    pub lag_blocks: Option<i64>,
+    /// Wei; from `eth_feeHistory` this is the next block's base fee
+    pub base_fee_per_gas: Option<String>,
+    /// Wei per gas at the 25th, 50th and 75th percentile
+    pub priority_fee_per_gas: PriorityFees,
+}
+
+/// GET /api/stats/gas-oracle — base fee and priority fee suggestions
+pub async fn get_gas_oracle(State(state): State<Arc<AppState>>) -> ApiResult<Json<GasOracle>> {
+    let indexed_block = state.latest_block_height().await?;
+    let chain_head = state.head_tracker.chain_height();
+    let lag_blocks = chain_head
+        .zip(indexed_block)
+        .map(|(head, indexed)| (head - indexed).max(0));
+
+    let mut oracle = GasOracle {
+        source: GasOracleSource::Indexed,
+        block_number: None,
+        indexed_block,
+        chain_head,
+        lag_blocks,
+        base_fee_per_gas: None,
+        priority_fee_per_gas: PriorityFees {
+            slow: "0".to_string(),
+            standard: "0".to_string(),
+            fast: "0".to_string(),
+        },
+    };
+
+    let stale = indexed_block.is_none() || lag_blocks.is_some_and(|l| l > MAX_INDEXED_LAG_BLOCKS);
+    if stale && !state.rpc_url.is_empty() {
+        match fee_history(&state.rpc_url).await {
+            Ok((block_number, base_fee, fees)) => {
+                oracle.source = GasOracleSource::Rpc;
+                oracle.block_number = Some(block_number);
+                oracle.base_fee_per_gas = Some(base_fee);
+                oracle.priority_fee_per_gas = fees;
+                return Ok(Json(oracle));
+            }
+            Err(e) => {
+                tracing::warn!(error = %e, "eth_feeHistory failed, estimating fees from indexed blocks")
+            }
+        }
+    }
+
+    let Some(head) = indexed_block else {
+        return Ok(Json(oracle));
+    };
+    let (base_fee, slow, standard, fast): (
+        Option<BigDecimal>,
+        Option<BigDecimal>,
+        Option<BigDecimal>,
+        Option<BigDecimal>,
+    ) = sqlx::query_as(
+        "WITH fees AS (
+             SELECT t.gas_price - COALESCE(b.base_fee_per_gas, 0) AS tip
+             FROM transactions t
+             JOIN blocks b ON b.number = t.block_number
+             WHERE t.block_number > $1 - $2 AND t.block_number <= $1
+               AND t.gas_price > 0
+         )
+         SELECT (SELECT base_fee_per_gas FROM blocks WHERE number = $1),
+                percentile_disc($3) WITHIN GROUP (ORDER BY GREATEST(tip, 0)),
+                percentile_disc($4) WITHIN GROUP (ORDER BY GREATEST(tip, 0)),
+                percentile_disc($5) WITHIN GROUP (ORDER BY GREATEST(tip, 0))
+         FROM fees",
+    )
+    .bind(head)
+    .bind(ORACLE_BLOCKS)
+    .bind(PERCENTILES[0] / 100.0)
+    .bind(PERCENTILES[1] / 100.0)
+    .bind(PERCENTILES[2] / 100.0)
+    .fetch_one(&state.pool)
+    .await?;
+
+    let wei = |v: Option<BigDecimal>| v.map_or_else(|| "0".to_string(), |v| v.to_string());
+    oracle.block_number = Some(head);
+    oracle.base_fee_per_gas = base_fee.map(|v| v.to_string());
+    oracle.priority_fee_per_gas = PriorityFees {
+        slow: wei(slow),
+        standard: wei(standard),
+        fast: wei(fast),
+    };
+    Ok(Json(oracle))
+}
+
+async fn fee_history(rpc_url: &str) -> Result<(i64, String, PriorityFees), AtlasError> {
+    let result = rpc_call_with_timeout(
+        rpc_url,
+        "eth_feeHistory",
+        serde_json::json!([format!("{ORACLE_BLOCKS:#x}"), "latest", PERCENTILES]),
+        FEE_HISTORY_TIMEOUT,
+    )
+    .await?;
+    parse_fee_history(&result)
+}
+
+fn hex_quantity(value: &Value) -> Option<u128> {
+    u128::from_str_radix(value.as_str()?.strip_prefix("0x")?, 16).ok()
+}
+
+/// The newest block covered, the next block's base fee and the mean reward
+/// per percentile from an `eth_feeHistory` result.
+fn parse_fee_history(result: &Value) -> Result<(i64, String, PriorityFees), AtlasError> {
+    let invalid = |what: &str| AtlasError::Rpc(format!("eth_feeHistory returned invalid {what}"));
+
+    let oldest = hex_quantity(&result["oldestBlock"]).ok_or_else(|| invalid("oldestBlock"))?;
+    let base_fees = result["baseFeePerGas"]
+        .as_array()
+        .filter(|fees| !fees.is_empty())
+        .ok_or_else(|| invalid("baseFeePerGas"))?;
+    let next_base_fee =
+        hex_quantity(base_fees.last().unwrap()).ok_or_else(|| invalid("baseFeePerGas"))?;
+    // One more base fee than blocks: the last is for the block after the range.
+    let newest = oldest + base_fees.len().saturating_sub(2) as u128;
+
+    let mut sums = [0u128; PERCENTILES.len()];
+    let rewards = result["reward"]
+        .as_array()
+        .map(Vec::as_slice)
+        .unwrap_or(&[]);
+    for block in rewards {
+        let block = block.as_array().ok_or_else(|| invalid("reward"))?;
+        for (sum, reward) in sums.iter_mut().zip(block) {
+            *sum += hex_quantity(reward).ok_or_else(|| invalid("reward"))?;
+        }
+    }
+    let mean = |sum: u128| (sum / rewards.len().max(1) as u128).to_string();
+
+    Ok((
+        i64::try_from(newest).map_err(|_| invalid("oldestBlock"))?,
+        next_base_fee.to_string(),
+        PriorityFees {
+            slow: mean(sums[0]),
+            standard: mean(sums[1]),
+            fast: mean(sums[2]),
+        },
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_fee_history_averages_rewards_per_percentile() {
+        let result = serde_json::json!({
+            "oldestBlock": "0x64",
+            "baseFeePerGas": ["0x10", "0x12", "0x14"],
+            "gasUsedRatio": [0.5, 0.6],
+            "reward": [["0x1", "0x2", "0x3"], ["0x3", "0x6", "0x9"]]
+        });
+        let (block, base_fee, fees) = parse_fee_history(&result).unwrap();
+        assert_eq!(block, 101);
+        assert_eq!(base_fee, "20");
+        assert_eq!(
+            fees,
+            PriorityFees {
+                slow: "2".to_string(),
+                standard: "4".to_string(),
+                fast: "6".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn parse_fee_history_without_rewards_suggests_zero_tips() {
+        let result = serde_json::json!({
+            "oldestBlock": "0x0",
+            "baseFeePerGas": ["0x0", "0x0"],
+        });
+        let (block, _, fees) = parse_fee_history(&result).unwrap();
+        assert_eq!(block, 0);
+        assert_eq!(fees.standard, "0");
+    }
+
+    #[test]
+    fn parse_fee_history_rejects_malformed_results() {
+        assert!(parse_fee_history(&serde_json::json!({"baseFeePerGas": ["0x1"]})).is_err());
+        assert!(parse_fee_history(&serde_json::json!({
+            "oldestBlock": "0x1",
+            "baseFeePerGas": ["0x1", "0x1"],
+            "reward": [["nope"]]
+        }))
+        .is_err());
+    }
+}
@@ -5,7 +5,7 @@ use std::sync::Arc;
 
 use crate::api::AppState;
 
-const MAX_INDEXER_AGE_MINUTES: i64 = 5;
+pub(super) const MAX_INDEXER_AGE_MINUTES: i64 = 5;
 
 #[derive(Serialize)]
 struct HealthResponse {
@@ -109,7 +109,7 @@ mod tests {
             .handle();
 
         Arc::new(AppState {
-            pool,
+            pool: pool.clone(),
             block_events_tx: block_tx,
             da_events_tx: da_tx,
             head_tracker,
@@ -120,6 +120,7 @@ mod tests {
             faucet_cooldown_minutes: None,
             chain_id: 1,
             chain_name: "Test Chain".to_string(),
+            native_currency: Default::default(),
             chain_logo_url: None,
             chain_logo_url_light: None,
             chain_logo_url_dark: None,
@@ -131,6 +132,22 @@ mod tests {
             metrics: Metrics::new(),
             prometheus_handle,
             solc_cache_dir: "/tmp/solc-cache".to_string(),
+            verify_timeout: std::time::Duration::from_secs(300),
+            verify_max_source_bytes: 20 * 1024 * 1024,
+            export_dir: None,
+            dataset_dir: None,
+            db_circuit_breaker: crate::api::circuit_breaker::CircuitBreaker::default(),
+            latency: Default::default(),
+            admin_api_key: None,
+            db_pools: Arc::new(crate::db_pools::DbPools::new(Vec::new())),
+            concurrency_limits: Default::default(),
+            body_limits: Default::default(),
+            log_limits: Default::default(),
+            row_limits: Default::default(),
+            storage: Arc::new(atlas_common::storage::HotStorage::new(pool)),
+            confirmations_head: Default::default(),
+            block_stall_secs: 0,
+            runtime_settings: Default::default(),
         })
     }
 
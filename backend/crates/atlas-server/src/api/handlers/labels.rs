@@ -0,0 +1,626 @@
+//! Address label suggestions, moderation, editing and bulk import/export
+//!
+//! Anyone may submit a label suggestion; it lands in `label_suggestions` as
+//! `pending` and only reaches the curated `address_labels` table once an
+//! editor approves it. Editors are the admin (authenticated with
+//! `ADMIN_API_KEY`) or holders of a curator key from `label_api_keys`, which
+//! limits them to labels carrying the key's tags (see [`TagScope`]). Editors
+//! can also edit and bulk import labels; the curated set can be exported by
+//! anyone.
+
+use axum::{
+    extract::{Path, Query, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+use crate::api::auth::{authorize_label_editor, generate_curator_key, require_admin};
+use crate::api::error::ApiResult;
+use crate::api::handlers::faucet::extract_client_ip;
+use crate::api::AppState;
+use crate::labels::{
+    normalize_label_address, normalize_tags, parse_import, validate_label_name, write_csv,
+    ExportFormat, ImportFormat, ImportRowError, LabelRecord, TagMapping, TagScope,
+};
+use atlas_common::{
+    AddressLabel, AtlasError, LabelApiKey, LabelSuggestion, PaginatedResponse, Pagination,
+};
+
+const MAX_NOTE_LEN: usize = 1000;
+
+/// Suggestions accepted per client IP within [`SUGGESTION_WINDOW_SECS`].
+const SUGGESTIONS_PER_WINDOW: i64 = 10;
+const SUGGESTION_WINDOW_SECS: i64 = 3600;
+
+/// Row errors echoed back in an import report; the rest are only counted.
+const MAX_REPORTED_IMPORT_ERRORS: usize = 100;
+
+const SUGGESTION_COLUMNS: &str =
+    "id, address, name, tags, note, submitter_ip, status, review_note, created_at, reviewed_at";
+
+#[derive(Debug, Deserialize)]
+pub struct LabelSuggestionRequest {
+    pub address: String,
+    pub name: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LabelSuggestionReceipt {
+    pub id: i64,
+    pub status: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SuggestionFilters {
+    #[serde(default = "default_status")]
+    pub status: String,
+    #[serde(default = "default_page")]
+    pub page: u32,
+    #[serde(default = "default_limit")]
+    pub limit: u32,
+}
+
+fn default_status() -> String {
+    "pending".to_string()
+}
+
+fn default_page() -> u32 {
+    1
+}
+
+fn default_limit() -> u32 {
+    20
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct ReviewRequest {
+    pub note: Option<String>,
+}
+
+/// POST /api/labels/suggest — queue a label suggestion for moderation
+pub async fn suggest_label(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Json(request): Json<LabelSuggestionRequest>,
+) -> ApiResult<(StatusCode, Json<LabelSuggestionReceipt>)> {
+    let address = normalize_label_address(&request.address)?;
+    let name = validate_label_name(&request.name)?;
+    let tags = normalize_tags(&request.tags)?;
+    let note = request
+        .note
+        .map(|n| n.trim().to_string())
+        .filter(|n| !n.is_empty());
+    if note
+        .as_ref()
+        .is_some_and(|n| n.chars().count() > MAX_NOTE_LEN)
+    {
+        return Err(AtlasError::InvalidInput(format!(
+            "Note must be at most {MAX_NOTE_LEN} characters"
+        ))
+        .into());
+    }
+    let client_ip = extract_client_ip(&headers)?;
+
+    let (recent, retry_after): (i64, Option<i64>) = sqlx::query_as(
+        "SELECT COUNT(*),
+                EXTRACT(EPOCH FROM MIN(created_at) + make_interval(secs => $2) - NOW())::BIGINT
+         FROM label_suggestions
+         WHERE submitter_ip = $1 AND created_at > NOW() - make_interval(secs => $2)",
+    )
+    .bind(&client_ip)
+    .bind(SUGGESTION_WINDOW_SECS as f64)
+    .fetch_one(&state.pool)
+    .await?;
+    if recent >= SUGGESTIONS_PER_WINDOW {
+        return Err(AtlasError::TooManyRequests {
+            message: "Too many label suggestions, try again later".to_string(),
+            retry_after_seconds: retry_after.unwrap_or(SUGGESTION_WINDOW_SECS).max(1) as u64,
+        }
+        .into());
+    }
+
+    let (id, status): (i64, String) = sqlx::query_as(
+        "INSERT INTO label_suggestions (address, name, tags, note, submitter_ip)
+         VALUES ($1, $2, $3, $4, $5)
+         RETURNING id, status",
+    )
+    .bind(&address)
+    .bind(&name)
+    .bind(&tags)
+    .bind(&note)
+    .bind(&client_ip)
+    .fetch_one(&state.pool)
+    .await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(LabelSuggestionReceipt { id, status }),
+    ))
+}
+
+/// GET /api/labels/suggestions — moderation queue (editors; curators see their tags only)
+pub async fn list_label_suggestions(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Query(filters): Query<SuggestionFilters>,
+) -> ApiResult<Json<PaginatedResponse<LabelSuggestion>>> {
+    let editor = authorize_label_editor(&state, &headers).await?;
+    let scope = editor.scope().map(TagScope::tags);
+
+    if !matches!(filters.status.as_str(), "pending" | "approved" | "rejected") {
+        return Err(AtlasError::InvalidInput(
+            "status must be one of: pending, approved, rejected".to_string(),
+        )
+        .into());
+    }
+
+    let pagination = Pagination {
+        page: filters.page,
+        limit: filters.limit,
+        count: None,
+        as_of_block: None,
+    };
+    let (total,): (i64,) = sqlx::query_as(
+        "SELECT COUNT(*) FROM label_suggestions
+         WHERE status = $1 AND ($2::text[] IS NULL OR tags && $2)",
+    )
+    .bind(&filters.status)
+    .bind(scope)
+    .fetch_one(&state.pool)
+    .await?;
+
+    // Pending items are reviewed oldest-first; reviewed history newest-first.
+    let order = if filters.status == "pending" {
+        "ASC"
+    } else {
+        "DESC"
+    };
+    let suggestions: Vec<LabelSuggestion> = sqlx::query_as(&format!(
+        "SELECT {SUGGESTION_COLUMNS} FROM label_suggestions
+         WHERE status = $1 AND ($2::text[] IS NULL OR tags && $2)
+         ORDER BY id {order}
+         LIMIT $3 OFFSET $4"
+    ))
+    .bind(&filters.status)
+    .bind(scope)
+    .bind(pagination.limit())
+    .bind(pagination.offset())
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(Json(PaginatedResponse::new(
+        suggestions,
+        pagination.page,
+        pagination.limit,
+        total,
+    )))
+}
+
+/// POST /api/labels/suggestions/{id}/approve — publish a suggestion as a curated label (editors)
+pub async fn approve_label_suggestion(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Path(id): Path<i64>,
+    review: Option<Json<ReviewRequest>>,
+) -> ApiResult<Json<AddressLabel>> {
+    let editor = authorize_label_editor(&state, &headers).await?;
+    let review = review.map(|Json(r)| r).unwrap_or_default();
+
+    let mut tx = state.pool.begin().await?;
+
+    let suggestion: LabelSuggestion = sqlx::query_as(&format!(
+        "SELECT {SUGGESTION_COLUMNS} FROM label_suggestions WHERE id = $1 FOR UPDATE"
+    ))
+    .bind(id)
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| AtlasError::NotFound(format!("Label suggestion {id} not found")))?;
+    ensure_pending(&suggestion)?;
+    let existing = lock_label_tags(&mut tx, &suggestion.address).await?;
+    editor.check_write(existing.as_deref(), &suggestion.tags)?;
+
+    let label = upsert_label(
+        &mut tx,
+        &suggestion.address,
+        &suggestion.name,
+        &suggestion.tags,
+    )
+    .await?;
+
+    mark_reviewed(&mut tx, id, "approved", review.note).await?;
+    tx.commit().await?;
+
+    Ok(Json(label))
+}
+
+/// POST /api/labels/suggestions/{id}/reject — discard a suggestion (editors)
+pub async fn reject_label_suggestion(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Path(id): Path<i64>,
+    review: Option<Json<ReviewRequest>>,
+) -> ApiResult<Json<LabelSuggestion>> {
+    let editor = authorize_label_editor(&state, &headers).await?;
+    let review = review.map(|Json(r)| r).unwrap_or_default();
+
+    let mut tx = state.pool.begin().await?;
+
+    let suggestion: LabelSuggestion = sqlx::query_as(&format!(
+        "SELECT {SUGGESTION_COLUMNS} FROM label_suggestions WHERE id = $1 FOR UPDATE"
+    ))
+    .bind(id)
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| AtlasError::NotFound(format!("Label suggestion {id} not found")))?;
+    ensure_pending(&suggestion)?;
+    editor.check_review(&suggestion.tags)?;
+
+    let rejected = mark_reviewed(&mut tx, id, "rejected", review.note).await?;
+    tx.commit().await?;
+
+    Ok(Json(rejected))
+}
+
+fn ensure_pending(suggestion: &LabelSuggestion) -> Result<(), AtlasError> {
+    if suggestion.status != "pending" {
+        return Err(AtlasError::Validation(format!(
+            "Label suggestion {} is already {}",
+            suggestion.id, suggestion.status
+        )));
+    }
+    Ok(())
+}
+
+/// Current tags of a curated label, row-locked until the transaction ends.
+async fn lock_label_tags(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    address: &str,
+) -> Result<Option<Vec<String>>, sqlx::Error> {
+    let row: Option<(Vec<String>,)> =
+        sqlx::query_as("SELECT tags FROM address_labels WHERE address = $1 FOR UPDATE")
+            .bind(address)
+            .fetch_optional(&mut **tx)
+            .await?;
+    Ok(row.map(|(tags,)| tags))
+}
+
+async fn upsert_label(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    address: &str,
+    name: &str,
+    tags: &[String],
+) -> Result<AddressLabel, sqlx::Error> {
+    sqlx::query_as(
+        "INSERT INTO address_labels (address, name, tags)
+         VALUES ($1, $2, $3)
+         ON CONFLICT (address) DO UPDATE
+         SET name = EXCLUDED.name, tags = EXCLUDED.tags, updated_at = NOW()
+         RETURNING address, name, tags, created_at, updated_at",
+    )
+    .bind(address)
+    .bind(name)
+    .bind(tags)
+    .fetch_one(&mut **tx)
+    .await
+}
+
+async fn mark_reviewed(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    id: i64,
+    status: &str,
+    note: Option<String>,
+) -> Result<LabelSuggestion, sqlx::Error> {
+    sqlx::query_as(&format!(
+        "UPDATE label_suggestions
+         SET status = $2, review_note = $3, reviewed_at = NOW()
+         WHERE id = $1
+         RETURNING {SUGGESTION_COLUMNS}"
+    ))
+    .bind(id)
+    .bind(status)
+    .bind(note.map(|n| n.trim().to_string()).filter(|n| !n.is_empty()))
+    .fetch_one(&mut **tx)
+    .await
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ExportParams {
+    #[serde(default)]
+    pub format: ExportFormat,
+    pub tag: Option<String>,
+}
+
+/// GET /api/labels/export — download curated labels as CSV or JSON
+pub async fn export_labels(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<ExportParams>,
+) -> ApiResult<Response> {
+    let tag = params
+        .tag
+        .map(|t| t.trim().to_lowercase())
+        .filter(|t| !t.is_empty());
+    let rows: Vec<(String, String, Vec<String>)> = sqlx::query_as(
+        "SELECT address, name, tags FROM address_labels
+         WHERE $1::text IS NULL OR $1 = ANY(tags)
+         ORDER BY address",
+    )
+    .bind(&tag)
+    .fetch_all(&state.pool)
+    .await?;
+    let labels: Vec<LabelRecord> = rows
+        .into_iter()
+        .map(|(address, name, tags)| LabelRecord {
+            address,
+            name,
+            tags,
+        })
+        .collect();
+
+    let (content_type, extension, body) = match params.format {
+        ExportFormat::Csv => ("text/csv; charset=utf-8", "csv", write_csv(&labels)),
+        ExportFormat::Json => ("application/json", "json", serde_json::to_string(&labels)?),
+    };
+    Ok((
+        [
+            (header::CONTENT_TYPE, content_type.to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"labels.{extension}\""),
+            ),
+        ],
+        body,
+    )
+        .into_response())
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ImportParams {
+    pub format: ImportFormat,
+    #[serde(default)]
+    pub dry_run: bool,
+    /// Comma-separated `source:target` tag rewrites; empty target drops the tag.
+    pub tag_map: Option<String>,
+    /// Comma-separated tags added to every imported label.
+    pub add_tags: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImportReport {
+    pub dry_run: bool,
+    pub total_rows: usize,
+    pub valid_rows: usize,
+    pub invalid_rows: usize,
+    /// Labels for addresses that had none (would be created on dry run).
+    pub created: usize,
+    /// Existing labels replaced by the import (would be replaced on dry run).
+    pub updated: usize,
+    /// Tag usage across valid rows after mapping.
+    pub tags: BTreeMap<String, usize>,
+    /// First [`MAX_REPORTED_IMPORT_ERRORS`] row errors.
+    pub errors: Vec<ImportRowError>,
+}
+
+/// POST /api/labels/import — bulk upsert labels from a CSV/JSON dump (editors)
+///
+/// The request body is the raw file. With `dry_run=true` nothing is written
+/// and the report describes what the import would do. Rows a curator key may
+/// not write are reported as row errors.
+pub async fn import_labels(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Query(params): Query<ImportParams>,
+    body: String,
+) -> ApiResult<Json<ImportReport>> {
+    let editor = authorize_label_editor(&state, &headers).await?;
+
+    let mapping = TagMapping::parse(params.tag_map.as_deref(), params.add_tags.as_deref())?;
+    let mut parsed = parse_import(params.format, &body, &mapping)?;
+
+    // Existing rows stay locked until the upsert so scope checks cannot race
+    // a concurrent edit.
+    let mut tx = state.pool.begin().await?;
+    let candidates: Vec<&str> = parsed.records.iter().map(|r| r.address.as_str()).collect();
+    let existing: Vec<(String, Vec<String>)> = sqlx::query_as(
+        "SELECT address, tags FROM address_labels WHERE address = ANY($1) FOR UPDATE",
+    )
+    .bind(&candidates)
+    .fetch_all(&mut *tx)
+    .await?;
+    let existing: HashMap<String, Vec<String>> = existing.into_iter().collect();
+
+    if let Some(scope) = editor.scope() {
+        parsed.reject_records(|record| {
+            scope.check_write(
+                existing.get(&record.address).map(Vec::as_slice),
+                &record.tags,
+            )
+        });
+    }
+    let addresses: Vec<&str> = parsed.records.iter().map(|r| r.address.as_str()).collect();
+
+    let mut tags = BTreeMap::new();
+    for tag in parsed.records.iter().flat_map(|r| &r.tags) {
+        *tags.entry(tag.clone()).or_insert(0) += 1;
+    }
+
+    if !params.dry_run && !parsed.records.is_empty() {
+        let names: Vec<&str> = parsed.records.iter().map(|r| r.name.as_str()).collect();
+        // Tags travel as unit-separator-joined strings: Postgres has no
+        // ragged arrays, and validated tags never contain U+001F.
+        let joined_tags: Vec<String> = parsed
+            .records
+            .iter()
+            .map(|r| r.tags.join("\u{1f}"))
+            .collect();
+        sqlx::query(
+            "INSERT INTO address_labels (address, name, tags)
+             SELECT r.address, r.name, string_to_array(r.tags, chr(31))
+             FROM unnest($1::text[], $2::text[], $3::text[]) AS r(address, name, tags)
+             ON CONFLICT (address) DO UPDATE
+             SET name = EXCLUDED.name, tags = EXCLUDED.tags, updated_at = NOW()",
+        )
+        .bind(&addresses)
+        .bind(&names)
+        .bind(&joined_tags)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+    }
+
+    let updated = addresses
+        .iter()
+        .filter(|a| existing.contains_key(**a))
+        .count();
+    Ok(Json(ImportReport {
+        dry_run: params.dry_run,
+        total_rows: parsed.total_rows,
+        valid_rows: parsed.records.len(),
+        invalid_rows: parsed.errors.len(),
+        created: parsed.records.len() - updated,
+        updated,
+        tags,
+        errors: parsed
+            .errors
+            .into_iter()
+            .take(MAX_REPORTED_IMPORT_ERRORS)
+            .collect(),
+    }))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LabelRequest {
+    pub address: String,
+    pub name: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+/// POST /api/labels — create or replace a curated label (editors)
+pub async fn save_label(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Json(request): Json<LabelRequest>,
+) -> ApiResult<Json<AddressLabel>> {
+    let editor = authorize_label_editor(&state, &headers).await?;
+    let address = normalize_label_address(&request.address)?;
+    let name = validate_label_name(&request.name)?;
+    let tags = normalize_tags(&request.tags)?;
+
+    let mut tx = state.pool.begin().await?;
+    let existing = lock_label_tags(&mut tx, &address).await?;
+    editor.check_write(existing.as_deref(), &tags)?;
+    let label = upsert_label(&mut tx, &address, &name, &tags).await?;
+    tx.commit().await?;
+
+    Ok(Json(label))
+}
+
+/// DELETE /api/labels/{address} — remove a curated label (editors)
+pub async fn delete_label(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Path(address): Path<String>,
+) -> ApiResult<StatusCode> {
+    let editor = authorize_label_editor(&state, &headers).await?;
+    let address = normalize_label_address(&address)?;
+
+    let mut tx = state.pool.begin().await?;
+    let existing = lock_label_tags(&mut tx, &address)
+        .await?
+        .ok_or_else(|| AtlasError::NotFound(format!("No label for {address}")))?;
+    editor.check_delete(&existing)?;
+    sqlx::query("DELETE FROM address_labels WHERE address = $1")
+        .bind(&address)
+        .execute(&mut *tx)
+        .await?;
+    tx.commit().await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateApiKeyRequest {
+    pub name: String,
+    pub tags: Vec<String>,
+}
+
+/// Newly issued curator key; `key` is only ever returned here.
+#[derive(Debug, Serialize)]
+pub struct IssuedApiKey {
+    pub key: String,
+    #[serde(flatten)]
+    pub info: LabelApiKey,
+}
+
+const API_KEY_COLUMNS: &str = "id, name, tags, created_at, revoked_at";
+
+/// POST /api/labels/keys — issue a tag-scoped curator key (admin)
+pub async fn create_label_api_key(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Json(request): Json<CreateApiKeyRequest>,
+) -> ApiResult<(StatusCode, Json<IssuedApiKey>)> {
+    require_admin(&state, &headers)?;
+    let name = validate_label_name(&request.name)?;
+    let tags = normalize_tags(&request.tags)?;
+    if tags.is_empty() {
+        return Err(
+            AtlasError::InvalidInput("A curator key needs at least one tag".to_string()).into(),
+        );
+    }
+
+    let (key, key_hash) = generate_curator_key();
+    let info: LabelApiKey = sqlx::query_as(&format!(
+        "INSERT INTO label_api_keys (name, key_hash, tags)
+         VALUES ($1, $2, $3)
+         RETURNING {API_KEY_COLUMNS}"
+    ))
+    .bind(&name)
+    .bind(&key_hash)
+    .bind(&tags)
+    .fetch_one(&state.pool)
+    .await?;
+
+    Ok((StatusCode::CREATED, Json(IssuedApiKey { key, info })))
+}
+
+/// GET /api/labels/keys — list curator keys, including revoked ones (admin)
+pub async fn list_label_api_keys(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+) -> ApiResult<Json<Vec<LabelApiKey>>> {
+    require_admin(&state, &headers)?;
+    let keys: Vec<LabelApiKey> = sqlx::query_as(&format!(
+        "SELECT {API_KEY_COLUMNS} FROM label_api_keys ORDER BY id"
+    ))
+    .fetch_all(&state.pool)
+    .await?;
+    Ok(Json(keys))
+}
+
+/// DELETE /api/labels/keys/{id} — revoke a curator key (admin)
+pub async fn revoke_label_api_key(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Path(id): Path<i64>,
+) -> ApiResult<Json<LabelApiKey>> {
+    require_admin(&state, &headers)?;
+    let key: LabelApiKey = sqlx::query_as(&format!(
+        "UPDATE label_api_keys
+         SET revoked_at = COALESCE(revoked_at, NOW())
+         WHERE id = $1
+         RETURNING {API_KEY_COLUMNS}"
+    ))
+    .bind(id)
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or_else(|| AtlasError::NotFound(format!("Label API key {id} not found")))?;
+    Ok(Json(key))
+}
@@ -0,0 +1,209 @@
+//! Logos of ERC-20 tokens and NFT collections, set by admins so the explorer
+//! does not show placeholder icons.
+//!
+//! A logo is either an external URL or an image uploaded to
+//! `PUT /api/admin/logos/:address/image`, stored in `contract_logos` and
+//! served by `GET /api/logos/:address`. Token and collection responses
+//! carry it as `logo_url`.
+
+use axum::{
+    body::Body,
+    extract::{Path, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use crate::api::auth::require_admin;
+use crate::api::body_limits::read_limited;
+use crate::api::error::ApiResult;
+use crate::api::AppState;
+use crate::labels::normalize_label_address;
+use atlas_common::AtlasError;
+
+/// Largest uploaded logo.
+pub const MAX_LOGO_BYTES: usize = 256 * 1024;
+
+const MAX_LOGO_URL_LEN: usize = 2048;
+
+/// Image types accepted for upload.
+const LOGO_CONTENT_TYPES: [&str; 5] = [
+    "image/png",
+    "image/jpeg",
+    "image/webp",
+    "image/gif",
+    "image/svg+xml",
+];
+
+#[derive(Debug, Deserialize)]
+pub struct LogoUrlRequest {
+    pub url: String,
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct ContractLogo {
+    pub address: String,
+    pub logo_url: String,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// GET /api/logos/:address - An uploaded logo image
+pub async fn get_logo(
+    State(state): State<Arc<AppState>>,
+    Path(address): Path<String>,
+) -> ApiResult<Response> {
+    let address = normalize_label_address(&address)?;
+    let (image, content_type): (Vec<u8>, String) = sqlx::query_as(
+        "SELECT image, content_type FROM contract_logos
+         WHERE address = $1 AND image IS NOT NULL",
+    )
+    .bind(&address)
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or_else(|| AtlasError::NotFound(format!("No uploaded logo for {address}")))?;
+
+    // SVGs can carry scripts; the policy keeps one opened directly inert.
+    Ok((
+        [
+            (header::CONTENT_TYPE, content_type),
+            (header::CACHE_CONTROL, "public, max-age=86400".to_string()),
+            (header::X_CONTENT_TYPE_OPTIONS, "nosniff".to_string()),
+            (
+                header::CONTENT_SECURITY_POLICY,
+                "default-src 'none'; style-src 'unsafe-inline'; sandbox".to_string(),
+            ),
+        ],
+        image,
+    )
+        .into_response())
+}
+
+/// PUT /api/admin/logos/:address - Use an external image URL as the logo (admin)
+pub async fn set_logo_url(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Path(address): Path<String>,
+    Json(request): Json<LogoUrlRequest>,
+) -> ApiResult<Json<ContractLogo>> {
+    require_admin(&state, &headers)?;
+    let address = indexed_contract(&state, &address).await?;
+    let url = request.url.trim();
+    if url.len() > MAX_LOGO_URL_LEN
+        || !(url.starts_with("https://") || url.starts_with("http://") || url.starts_with('/'))
+    {
+        return Err(AtlasError::InvalidInput(format!(
+            "url must be an http(s) URL or a path, at most {MAX_LOGO_URL_LEN} bytes"
+        ))
+        .into());
+    }
+
+    let logo = sqlx::query_as(
+        "INSERT INTO contract_logos (address, logo_url, updated_at)
+         VALUES ($1, $2, NOW())
+         ON CONFLICT (address) DO UPDATE SET
+             logo_url = EXCLUDED.logo_url, image = NULL, content_type = NULL,
+             updated_at = EXCLUDED.updated_at
+         RETURNING address, logo_url, updated_at",
+    )
+    .bind(&address)
+    .bind(url)
+    .fetch_one(&state.pool)
+    .await?;
+    Ok(Json(logo))
+}
+
+/// PUT /api/admin/logos/:address/image - Upload the logo image, sent as the
+/// raw body with its `Content-Type` (admin)
+pub async fn upload_logo(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Path(address): Path<String>,
+    body: Body,
+) -> ApiResult<Json<ContractLogo>> {
+    require_admin(&state, &headers)?;
+    let address = indexed_contract(&state, &address).await?;
+    let content_type = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| {
+            value
+                .split(';')
+                .next()
+                .unwrap_or_default()
+                .trim()
+                .to_lowercase()
+        })
+        .filter(|value| LOGO_CONTENT_TYPES.contains(&value.as_str()))
+        .ok_or_else(|| {
+            AtlasError::InvalidInput(format!(
+                "Content-Type must be one of {}",
+                LOGO_CONTENT_TYPES.join(", ")
+            ))
+        })?;
+    let image = read_limited(body, MAX_LOGO_BYTES).await?;
+    if image.is_empty() {
+        return Err(AtlasError::InvalidInput("logo image is empty".to_string()).into());
+    }
+
+    // The version changes with every upload, so a new image is not served
+    // from cache.
+    let logo_url = format!(
+        "/api/logos/{address}?v={}",
+        chrono::Utc::now().timestamp_millis()
+    );
+    let logo = sqlx::query_as(
+        "INSERT INTO contract_logos (address, logo_url, image, content_type, updated_at)
+         VALUES ($1, $2, $3, $4, NOW())
+         ON CONFLICT (address) DO UPDATE SET
+             logo_url = EXCLUDED.logo_url, image = EXCLUDED.image,
+             content_type = EXCLUDED.content_type, updated_at = EXCLUDED.updated_at
+         RETURNING address, logo_url, updated_at",
+    )
+    .bind(&address)
+    .bind(&logo_url)
+    .bind(&image[..])
+    .bind(&content_type)
+    .fetch_one(&state.pool)
+    .await?;
+    Ok(Json(logo))
+}
+
+/// DELETE /api/admin/logos/:address - Remove a contract's logo (admin)
+pub async fn delete_logo(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Path(address): Path<String>,
+) -> ApiResult<StatusCode> {
+    require_admin(&state, &headers)?;
+    let address = normalize_label_address(&address)?;
+    let deleted = sqlx::query("DELETE FROM contract_logos WHERE address = $1")
+        .bind(&address)
+        .execute(&state.pool)
+        .await?
+        .rows_affected();
+    if deleted == 0 {
+        return Err(AtlasError::NotFound(format!("No logo for {address}")).into());
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// The normalized address of an indexed ERC-20 token or NFT collection; a
+/// 404 for any other address.
+async fn indexed_contract(state: &AppState, address: &str) -> Result<String, AtlasError> {
+    let address = normalize_label_address(address)?;
+    let indexed: bool = sqlx::query_scalar(
+        "SELECT EXISTS (SELECT 1 FROM erc20_contracts WHERE address = $1)
+             OR EXISTS (SELECT 1 FROM nft_contracts WHERE address = $1)",
+    )
+    .bind(&address)
+    .fetch_one(&state.pool)
+    .await?;
+    if !indexed {
+        return Err(AtlasError::NotFound(format!(
+            "{address} is not an indexed token or collection"
+        )));
+    }
+    Ok(address)
+}
@@ -6,8 +6,14 @@ use serde::Deserialize;
 use std::sync::Arc;
 
 use crate::api::error::ApiResult;
+use crate::api::handlers::{
+    segments_newest_first, stitch_segments, sum_segment_counts, transaction_segment,
+};
 use crate::api::AppState;
-use atlas_common::{EventLog, PaginatedResponse, Pagination};
+use crate::ordering::{ChainRow, Direction};
+use atlas_common::encoding::hex_to_bytes;
+use atlas_common::storage::{Segment, TieredTable};
+use atlas_common::{EventLog, PaginatedResponse, Pagination, EVENT_LOG_COLUMNS};
 
 /// Pagination for transaction log endpoints.
 #[derive(Debug, Deserialize)]
@@ -63,23 +69,34 @@ pub async fn get_transaction_logs(
     Query(query): Query<TransactionLogsQuery>,
 ) -> ApiResult<Json<PaginatedResponse<EventLog>>> {
     let hash = normalize_hash(&hash);
+    let (segment, block_number) =
+        transaction_segment(&state, TieredTable::EventLogs, &hash).await?;
+    let hash = hex_to_bytes(&hash)?;
+
+    let total: (i64,) = sqlx::query_as(&format!(
+        "SELECT COUNT(*) FROM {}
+         WHERE tx_hash = $1 AND ($2::bigint IS NULL OR block_number = $2)",
+        segment.table
+    ))
+    .bind(&hash)
+    .bind(block_number)
+    .fetch_one(&segment.pool)
+    .await?;
 
-    let total: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM event_logs WHERE tx_hash = $1")
-        .bind(&hash)
-        .fetch_one(&state.pool)
-        .await?;
-
-    let logs: Vec<EventLog> = sqlx::query_as(
-        "SELECT id, tx_hash, log_index, address, topic0, topic1, topic2, topic3, data, block_number, decoded
-         FROM event_logs
-         WHERE tx_hash = $1
-         ORDER BY log_index ASC
-         LIMIT $2 OFFSET $3",
-    )
+    let logs: Vec<EventLog> = sqlx::query_as(&format!(
+        "SELECT {EVENT_LOG_COLUMNS}
+         FROM {}
+         WHERE tx_hash = $1 AND ($2::bigint IS NULL OR block_number = $2)
+         ORDER BY {}
+         LIMIT $3 OFFSET $4",
+        segment.table,
+        ChainRow::Log.order_by("", Direction::Asc)
+    ))
     .bind(&hash)
+    .bind(block_number)
     .bind(query.limit())
     .bind(query.offset())
-    .fetch_all(&state.pool)
+    .fetch_all(&segment.pool)
     .await?;
 
     Ok(Json(PaginatedResponse::new(
@@ -90,60 +107,135 @@ pub async fn get_transaction_logs(
     )))
 }
 
+/// Filters for the logs of a block
+#[derive(Debug, Deserialize)]
+pub struct BlockLogsFilter {
+    /// Filter by event signature (topic0)
+    pub topic0: Option<String>,
+    /// Filter by emitting contract
+    pub address: Option<String>,
+}
+
+/// GET /api/blocks/:number/logs - Logs in a block in execution order, with
+/// event names, optionally filtered by topic0 and/or emitting contract
+pub async fn get_block_logs(
+    State(state): State<Arc<AppState>>,
+    Path(number): Path<i64>,
+    Query(filter): Query<BlockLogsFilter>,
+    Query(pagination): Query<Pagination>,
+) -> ApiResult<Json<PaginatedResponse<EnrichedEventLog>>> {
+    let topic0 = filter
+        .topic0
+        .as_deref()
+        .map(|topic| hex_to_bytes(&normalize_hash(topic)))
+        .transpose()?;
+    let address = filter
+        .address
+        .as_deref()
+        .map(|address| hex_to_bytes(&normalize_address(address)))
+        .transpose()?;
+    let segment = state
+        .storage
+        .segment_for_block(TieredTable::EventLogs, number);
+
+    let total: (i64,) = sqlx::query_as(&format!(
+        "SELECT COUNT(*) FROM {}
+         WHERE block_number = $1
+           AND ($2::bytea IS NULL OR topic0 = $2)
+           AND ($3::bytea IS NULL OR address = $3)",
+        segment.table
+    ))
+    .bind(number)
+    .bind(&topic0)
+    .bind(&address)
+    .fetch_one(&segment.pool)
+    .await?;
+
+    let logs: Vec<EventLog> = sqlx::query_as(&format!(
+        "SELECT {EVENT_LOG_COLUMNS}
+         FROM {}
+         WHERE block_number = $1
+           AND ($2::bytea IS NULL OR topic0 = $2)
+           AND ($3::bytea IS NULL OR address = $3)
+         ORDER BY {}
+         LIMIT $4 OFFSET $5",
+        segment.table,
+        ChainRow::Log.order_by("", Direction::Asc)
+    ))
+    .bind(number)
+    .bind(&topic0)
+    .bind(&address)
+    .bind(pagination.limit())
+    .bind(pagination.offset())
+    .fetch_all(&segment.pool)
+    .await?;
+
+    Ok(Json(PaginatedResponse::new(
+        enrich_logs(&state, logs).await?,
+        pagination.page,
+        pagination.limit() as u32,
+        total.0,
+    )))
+}
+
 /// GET /api/addresses/:address/logs - Get logs emitted by a contract
 pub async fn get_address_logs(
     State(state): State<Arc<AppState>>,
     Path(address): Path<String>,
     Query(query): Query<LogsQuery>,
 ) -> ApiResult<Json<PaginatedResponse<EventLog>>> {
-    let address = normalize_address(&address);
-
-    let (total, logs) = if let Some(topic0) = &query.topic0 {
-        let topic0 = normalize_hash(topic0);
-
-        let total: (i64,) =
-            sqlx::query_as("SELECT COUNT(*) FROM event_logs WHERE address = $1 AND topic0 = $2")
-                .bind(&address)
-                .bind(&topic0)
-                .fetch_one(&state.pool)
-                .await?;
-
-        let logs: Vec<EventLog> = sqlx::query_as(
-            "SELECT id, tx_hash, log_index, address, topic0, topic1, topic2, topic3, data, block_number, decoded
-             FROM event_logs
-             WHERE address = $1 AND topic0 = $2
-             ORDER BY block_number DESC, log_index DESC
-             LIMIT $3 OFFSET $4",
-        )
-        .bind(&address)
-        .bind(&topic0)
-        .bind(query.limit())
-        .bind(query.offset())
-        .fetch_all(&state.pool)
-        .await?;
-
-        (total.0, logs)
-    } else {
-        let total: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM event_logs WHERE address = $1")
-            .bind(&address)
-            .fetch_one(&state.pool)
-            .await?;
-
-        let logs: Vec<EventLog> = sqlx::query_as(
-            "SELECT id, tx_hash, log_index, address, topic0, topic1, topic2, topic3, data, block_number, decoded
-             FROM event_logs
-             WHERE address = $1
-             ORDER BY block_number DESC, log_index DESC
-             LIMIT $2 OFFSET $3",
-        )
-        .bind(&address)
-        .bind(query.limit())
-        .bind(query.offset())
-        .fetch_all(&state.pool)
-        .await?;
-
-        (total.0, logs)
+    let address = hex_to_bytes(&normalize_address(&address))?;
+    let topic0 = query
+        .topic0
+        .as_deref()
+        .map(|topic0| hex_to_bytes(&normalize_hash(topic0)))
+        .transpose()?;
+    let segments = segments_newest_first(&state, TieredTable::EventLogs, i64::MAX);
+
+    let count = |segment: Segment| {
+        let (address, topic0) = (&address, &topic0);
+        async move {
+            let sql = format!(
+                "SELECT COUNT(*) FROM {}
+                 WHERE address = $1 AND ($2::bytea IS NULL OR topic0 = $2)
+                   AND block_number BETWEEN $3 AND $4",
+                segment.table
+            );
+            sqlx::query_scalar(&sql)
+                .bind(address)
+                .bind(topic0)
+                .bind(segment.blocks.from)
+                .bind(segment.blocks.to)
+                .fetch_one(&segment.pool)
+                .await
+        }
+    };
+    let fetch = |segment: Segment, limit: i64, offset: i64| {
+        let (address, topic0) = (&address, &topic0);
+        async move {
+            let sql = format!(
+                "SELECT {EVENT_LOG_COLUMNS}
+                 FROM {}
+                 WHERE address = $1 AND ($2::bytea IS NULL OR topic0 = $2)
+                   AND block_number BETWEEN $3 AND $4
+                 ORDER BY {}
+                 LIMIT $5 OFFSET $6",
+                segment.table,
+                ChainRow::Log.order_by("", Direction::Desc)
+            );
+            sqlx::query_as::<_, EventLog>(&sql)
+                .bind(address)
+                .bind(topic0)
+                .bind(segment.blocks.from)
+                .bind(segment.blocks.to)
+                .bind(limit)
+                .bind(offset)
+                .fetch_all(&segment.pool)
+                .await
+        }
     };
+    let total = sum_segment_counts(&segments, &count).await?;
+    let logs = stitch_segments(&segments, query.offset(), query.limit(), fetch, &count).await?;
 
     Ok(Json(PaginatedResponse::new(
         logs,
@@ -169,25 +261,49 @@ pub async fn get_transaction_logs_decoded(
     Query(query): Query<TransactionLogsQuery>,
 ) -> ApiResult<Json<PaginatedResponse<EnrichedEventLog>>> {
     let hash = normalize_hash(&hash);
+    let (segment, block_number) =
+        transaction_segment(&state, TieredTable::EventLogs, &hash).await?;
+    let hash = hex_to_bytes(&hash)?;
+
+    let total: (i64,) = sqlx::query_as(&format!(
+        "SELECT COUNT(*) FROM {}
+         WHERE tx_hash = $1 AND ($2::bigint IS NULL OR block_number = $2)",
+        segment.table
+    ))
+    .bind(&hash)
+    .bind(block_number)
+    .fetch_one(&segment.pool)
+    .await?;
 
-    let total: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM event_logs WHERE tx_hash = $1")
-        .bind(&hash)
-        .fetch_one(&state.pool)
-        .await?;
-
-    let logs: Vec<EventLog> = sqlx::query_as(
-        "SELECT id, tx_hash, log_index, address, topic0, topic1, topic2, topic3, data, block_number, decoded
-         FROM event_logs
-         WHERE tx_hash = $1
-         ORDER BY log_index ASC
-         LIMIT $2 OFFSET $3",
-    )
+    let logs: Vec<EventLog> = sqlx::query_as(&format!(
+        "SELECT {EVENT_LOG_COLUMNS}
+         FROM {}
+         WHERE tx_hash = $1 AND ($2::bigint IS NULL OR block_number = $2)
+         ORDER BY {}
+         LIMIT $3 OFFSET $4",
+        segment.table,
+        ChainRow::Log.order_by("", Direction::Asc)
+    ))
     .bind(&hash)
+    .bind(block_number)
     .bind(query.limit())
     .bind(query.offset())
-    .fetch_all(&state.pool)
+    .fetch_all(&segment.pool)
     .await?;
 
+    Ok(Json(PaginatedResponse::new(
+        enrich_logs(&state, logs).await?,
+        query.page,
+        query.clamped_limit(),
+        total.0,
+    )))
+}
+
+/// Attach event names from `event_signatures` to logs.
+async fn enrich_logs(
+    state: &AppState,
+    logs: Vec<EventLog>,
+) -> Result<Vec<EnrichedEventLog>, sqlx::Error> {
     // Collect unique topic0 values for signature lookup
     let topic0s: Vec<String> = logs.iter().map(|l| l.topic0.clone()).collect();
 
@@ -219,12 +335,7 @@ pub async fn get_transaction_logs_decoded(
         })
         .collect();
 
-    Ok(Json(PaginatedResponse::new(
-        enriched,
-        query.page,
-        query.clamped_limit(),
-        total.0,
-    )))
+    Ok(enriched)
 }
 
 fn default_page() -> u32 {
@@ -270,6 +381,6 @@ mod tests {
         let response =
             PaginatedResponse::new(Vec::<()>::new(), query.page, query.clamped_limit(), 250);
         assert_eq!(response.limit, 100);
-        assert_eq!(response.total_pages, 3);
+        assert_eq!(response.total_pages, Some(3));
     }
 }
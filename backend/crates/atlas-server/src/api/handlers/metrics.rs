@@ -37,7 +37,7 @@ mod tests {
         let recorder_metrics = Metrics::new();
         recorder_metrics.set_indexer_head_block(42);
         let state = Arc::new(AppState {
-            pool,
+            pool: pool.clone(),
             block_events_tx: block_tx,
             da_events_tx: da_tx,
             head_tracker: Arc::new(HeadTracker::empty(10)),
@@ -48,6 +48,7 @@ mod tests {
             faucet_cooldown_minutes: None,
             chain_id: 1,
             chain_name: "Test Chain".to_string(),
+            native_currency: Default::default(),
             chain_logo_url: None,
             chain_logo_url_light: None,
             chain_logo_url_dark: None,
@@ -59,6 +60,22 @@ mod tests {
             metrics: recorder_metrics,
             prometheus_handle,
             solc_cache_dir: "/tmp/solc-cache".to_string(),
+            verify_timeout: std::time::Duration::from_secs(300),
+            verify_max_source_bytes: 20 * 1024 * 1024,
+            export_dir: None,
+            dataset_dir: None,
+            db_circuit_breaker: crate::api::circuit_breaker::CircuitBreaker::default(),
+            latency: Default::default(),
+            admin_api_key: None,
+            db_pools: Arc::new(crate::db_pools::DbPools::new(Vec::new())),
+            concurrency_limits: Default::default(),
+            body_limits: Default::default(),
+            log_limits: Default::default(),
+            row_limits: Default::default(),
+            storage: Arc::new(atlas_common::storage::HotStorage::new(pool)),
+            confirmations_head: Default::default(),
+            block_stall_secs: 0,
+            runtime_settings: Default::default(),
         });
 
         let body = super::metrics(State(state)).await;
@@ -1,24 +1,52 @@
 pub mod addresses;
+pub mod admin;
+pub mod alerts;
+pub mod approvals;
+pub mod batch;
 pub mod blocks;
+pub mod bloom;
 pub mod config;
+pub mod contract_events;
 pub mod contracts;
+pub mod datasets;
+pub mod decode;
+pub mod dex;
+pub mod display;
 pub mod etherscan;
+pub mod export;
 pub mod faucet;
+pub mod filters;
+pub mod gas_oracle;
 pub mod health;
+pub mod labels;
+pub mod logos;
 pub mod logs;
 pub mod metrics;
 pub mod nfts;
+pub mod notes;
+pub mod overview;
 pub mod proxy;
+pub mod raw;
+pub mod rpc_logs;
+pub mod safes;
 pub mod search;
 pub mod sse;
 pub mod stats;
 pub mod status;
 pub mod tokens;
 pub mod transactions;
+pub mod validators;
 
-use atlas_common::{Block, BLOCK_COLUMNS};
-use sqlx::PgPool;
+use atlas_common::db::begin_with_statement_timeout;
+use atlas_common::storage::{BlockRange, Segment, Tier, TieredTable};
+use atlas_common::{AtlasError, Block, CountMode, BLOCK_COLUMNS};
+use sqlx::postgres::PgArguments;
+use sqlx::query::QueryAs;
+use sqlx::{PgPool, Postgres};
+use std::future::Future;
+use std::time::Duration;
 
+use crate::api::AppState;
 use crate::state_keys::ERC20_SUPPLY_HISTORY_COMPLETE_KEY;
 
 pub async fn get_latest_block(pool: &PgPool) -> Result<Option<Block>, sqlx::Error> {
@@ -30,6 +58,124 @@ pub async fn get_latest_block(pool: &PgPool) -> Result<Option<Block>, sqlx::Erro
     .await
 }
 
+/// Block of the canonical transaction with `hash`, from `tx_hash_lookup`. A
+/// hash whose only block was reorged out has none; a hash without lookup rows
+/// (written outside the indexer) is looked up in `transactions`.
+pub async fn canonical_tx_block(pool: &PgPool, hash: &str) -> Result<Option<i64>, sqlx::Error> {
+    sqlx::query_scalar(
+        "SELECT block_number FROM tx_hash_lookup WHERE hash = $1 AND canonical
+         UNION ALL
+         SELECT block_number FROM transactions
+         WHERE hash = $1 AND NOT EXISTS (SELECT 1 FROM tx_hash_lookup WHERE hash = $1)
+         ORDER BY block_number DESC
+         LIMIT 1",
+    )
+    .bind(hash)
+    .fetch_optional(pool)
+    .await
+}
+
+/// Segment of `table` holding the canonical transaction `hash`, and its
+/// block. Rows of a transaction without a known block are looked for in the
+/// newest segment.
+pub async fn transaction_segment(
+    state: &AppState,
+    table: TieredTable,
+    hash: &str,
+) -> Result<(Segment, Option<i64>), sqlx::Error> {
+    let block_number = canonical_tx_block(&state.pool, hash).await?;
+    let segment = state
+        .storage
+        .segment_for_block(table, block_number.unwrap_or(i64::MAX));
+    Ok((segment, block_number))
+}
+
+/// Segments of `table` holding blocks up to `bound`, newest first: the order
+/// newest-first listings read them in.
+pub fn segments_newest_first(state: &AppState, table: TieredTable, bound: i64) -> Vec<Segment> {
+    state
+        .storage
+        .segments(table, BlockRange::up_to(bound))
+        .into_iter()
+        .rev()
+        .collect()
+}
+
+/// Refuse `blocks` of `table` that reach into the cold tier, for readers
+/// that join hot-only tables and so cannot be stitched. `what` names the
+/// result in the error.
+pub fn require_hot_tier(
+    state: &AppState,
+    table: TieredTable,
+    blocks: BlockRange,
+    what: &str,
+) -> Result<(), AtlasError> {
+    let cold_end = state
+        .storage
+        .segments(table, blocks)
+        .iter()
+        .filter(|segment| segment.tier == Tier::Cold)
+        .map(|segment| segment.blocks.to)
+        .max();
+    match cold_end {
+        Some(end) => Err(AtlasError::InvalidInput(format!(
+            "{what} only cover blocks from {}; older blocks are in cold storage",
+            end + 1
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// Sum of `count(segment)` over `segments`.
+pub async fn sum_segment_counts<C, CF>(
+    segments: &[Segment],
+    mut count: C,
+) -> Result<i64, sqlx::Error>
+where
+    C: FnMut(Segment) -> CF,
+    CF: Future<Output = Result<i64, sqlx::Error>>,
+{
+    let mut total = 0;
+    for segment in segments {
+        total += count(segment.clone()).await?;
+    }
+    Ok(total)
+}
+
+/// Rows `offset..offset + limit` of a listing stitched from `segments`, read
+/// in order. `fetch(segment, limit, offset)` reads a page of one segment's
+/// rows; `count(segment)` is only run for a segment the page starts past, to
+/// carry the offset over to the next one.
+pub async fn stitch_segments<T, F, FF, C, CF>(
+    segments: &[Segment],
+    mut offset: i64,
+    limit: i64,
+    mut fetch: F,
+    mut count: C,
+) -> Result<Vec<T>, sqlx::Error>
+where
+    F: FnMut(Segment, i64, i64) -> FF,
+    FF: Future<Output = Result<Vec<T>, sqlx::Error>>,
+    C: FnMut(Segment) -> CF,
+    CF: Future<Output = Result<i64, sqlx::Error>>,
+{
+    let mut rows = Vec::new();
+    for segment in segments {
+        let remaining = limit - rows.len() as i64;
+        if remaining <= 0 {
+            break;
+        }
+        let mut page = fetch(segment.clone(), remaining, offset).await?;
+        if page.is_empty() && offset > 0 {
+            offset = (offset - count(segment.clone()).await?).max(0);
+            continue;
+        }
+        rows.append(&mut page);
+        offset = 0;
+    }
+    Ok(rows)
+}
+
 pub async fn has_complete_erc20_supply_history(pool: &PgPool) -> Result<bool, sqlx::Error> {
     let value: Option<(String,)> =
         sqlx::query_as("SELECT value FROM indexer_state WHERE key = $1 LIMIT 1")
@@ -42,6 +188,120 @@ pub async fn has_complete_erc20_supply_history(pool: &PgPool) -> Result<bool, sq
         Some("true")
     ))
 }
+/// Block height a newest-first list is anchored to: the requested
+/// `as_of_block` (capped at the indexed head), or the current indexed head. Rows above it are excluded, so
+/// paging with the returned anchor never shifts as new blocks are indexed.
+pub async fn snapshot_anchor(
+    state: &AppState,
+    as_of_block: Option<i64>,
+) -> Result<Option<i64>, AtlasError> {
+    if as_of_block.is_some_and(|block| block < 0) {
+        return Err(AtlasError::InvalidInput(
+            "as_of_block must not be negative".to_string(),
+        ));
+    }
+    let head = state.latest_block_height().await?;
+    Ok(match (as_of_block, head) {
+        (Some(block), Some(head)) => Some(block.min(head)),
+        (requested, head) => requested.or(head),
+    })
+}
+
+/// Upper block bound to bind for an anchor (no bound while nothing is indexed).
+pub fn anchor_bound(anchor: Option<i64>) -> i64 {
+    anchor.unwrap_or(i64::MAX)
+}
+
+/// Statement timeout for pagination `COUNT(*)` queries. Counts over OR-filtered
+/// or unindexed predicates are the usual pool hogs, so they get a tighter budget
+/// than the 10s pool-wide default.
+pub const COUNT_QUERY_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Run a `SELECT COUNT(*)` style query under [`COUNT_QUERY_TIMEOUT`].
+pub async fn fetch_count<'q>(
+    pool: &PgPool,
+    query: QueryAs<'q, Postgres, (i64,), PgArguments>,
+) -> Result<i64, sqlx::Error> {
+    let mut tx = begin_with_statement_timeout(pool, COUNT_QUERY_TIMEOUT).await?;
+    let (count,) = query.fetch_one(&mut *tx).await?;
+    tx.commit().await?;
+    Ok(count)
+}
+
+/// Below this planner estimate, [`CountMode::Estimated`] runs the exact count
+/// anyway: it is cheap and avoids showing "~12" for a handful of rows.
+const EXACT_COUNT_BELOW_ESTIMATE: i64 = 10_000;
+
+/// Total for a paginated endpoint, as resolved by [`count_rows`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PageTotal {
+    pub total: Option<i64>,
+    pub is_estimate: bool,
+}
+
+/// Count the rows matched by `filter_sql` according to `mode`.
+///
+/// `filter_sql` is a row-producing query (e.g. `SELECT 1 FROM t WHERE ...`)
+/// whose `$n` placeholders are bound, in order, from `binds`.
+pub async fn count_rows(
+    pool: &PgPool,
+    mode: CountMode,
+    filter_sql: &str,
+    binds: &[&str],
+) -> Result<PageTotal, sqlx::Error> {
+    let exact_sql = format!("SELECT COUNT(*) FROM ({filter_sql}) counted");
+    let exact = || async {
+        let mut query = sqlx::query_as(&exact_sql);
+        for bind in binds {
+            query = query.bind(*bind);
+        }
+        fetch_count(pool, query).await
+    };
+
+    match mode {
+        CountMode::None => Ok(PageTotal {
+            total: None,
+            is_estimate: false,
+        }),
+        CountMode::Exact => Ok(PageTotal {
+            total: Some(exact().await?),
+            is_estimate: false,
+        }),
+        CountMode::Estimated => {
+            let estimate = estimate_rows(pool, filter_sql, binds).await?;
+            if estimate < EXACT_COUNT_BELOW_ESTIMATE {
+                Ok(PageTotal {
+                    total: Some(exact().await?),
+                    is_estimate: false,
+                })
+            } else {
+                Ok(PageTotal {
+                    total: Some(estimate),
+                    is_estimate: true,
+                })
+            }
+        }
+    }
+}
+
+/// Planner row estimate for `sql` via `EXPLAIN` — never executes the query.
+async fn estimate_rows(pool: &PgPool, sql: &str, binds: &[&str]) -> Result<i64, sqlx::Error> {
+    let explain_sql = format!("EXPLAIN (FORMAT JSON) {sql}");
+    let mut query = sqlx::query_as::<_, (serde_json::Value,)>(&explain_sql);
+    for bind in binds {
+        query = query.bind(*bind);
+    }
+    let (plan,) = query.fetch_one(pool).await?;
+    Ok(plan_rows(&plan))
+}
+
+fn plan_rows(plan: &serde_json::Value) -> i64 {
+    plan[0]["Plan"]["Plan Rows"]
+        .as_f64()
+        .map(|rows| rows.max(0.0) as i64)
+        .unwrap_or(0)
+}
+
 fn exact_count_sql(table_name: &str) -> Result<&'static str, sqlx::Error> {
     match table_name {
         "transactions" => Ok("SELECT COUNT(*) FROM transactions"),
@@ -93,10 +353,7 @@ pub async fn get_table_count(pool: &PgPool, table_name: &str) -> Result<i64, sql
         Ok(approx)
     } else {
         // Exact count for small tables
-        let exact: (i64,) = sqlx::query_as(exact_count_sql(table_name)?)
-            .fetch_one(pool)
-            .await?;
-        Ok(exact.0)
+        fetch_count(pool, sqlx::query_as(exact_count_sql(table_name)?)).await
     }
 }
 
@@ -122,6 +379,18 @@ mod tests {
         assert!(err.to_string().contains("unsupported table"));
     }
 
+    #[test]
+    fn plan_rows_reads_top_level_estimate() {
+        let plan =
+            serde_json::json!([{ "Plan": { "Node Type": "Append", "Plan Rows": 123456.0 } }]);
+        assert_eq!(plan_rows(&plan), 123_456);
+    }
+
+    #[test]
+    fn plan_rows_defaults_to_zero_for_unexpected_shape() {
+        assert_eq!(plan_rows(&serde_json::json!({})), 0);
+    }
+
     #[test]
     fn should_use_approximate_count_above_threshold() {
         assert!(should_use_approximate_count(100_001));
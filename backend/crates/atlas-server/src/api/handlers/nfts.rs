@@ -3,26 +3,48 @@ use axum::{
     extract::{Path, Query, State},
     Json,
 };
+use serde::Serialize;
 use std::sync::Arc;
 
 use crate::api::error::ApiResult;
+use crate::api::handlers::{anchor_bound, snapshot_anchor};
 use crate::api::AppState;
+use crate::indexer::address_directory;
+use crate::ordering::{ChainRow, Direction};
 use atlas_common::{AtlasError, NftContract, NftToken, NftTransfer, PaginatedResponse, Pagination};
 
+/// NFT collection with its admin-set logo, see [`crate::api::handlers::logos`]
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct Collection {
+    #[serde(flatten)]
+    #[sqlx(flatten)]
+    pub contract: NftContract,
+    pub logo_url: Option<String>,
+}
+
+/// `nft_contracts` columns (aliased `c`) for [`Collection`]; use with
+/// [`COLLECTION_LOGO_JOIN`].
+pub(crate) const COLLECTION_COLUMNS: &str =
+    "c.address, c.name, c.symbol, c.total_supply, c.first_seen_block, logo.logo_url";
+
+pub(crate) const COLLECTION_LOGO_JOIN: &str =
+    "LEFT JOIN contract_logos logo ON logo.address = c.address";
+
 pub async fn list_collections(
     State(state): State<Arc<AppState>>,
     Query(pagination): Query<Pagination>,
-) -> ApiResult<Json<PaginatedResponse<NftContract>>> {
+) -> ApiResult<Json<PaginatedResponse<Collection>>> {
     let total: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM nft_contracts")
         .fetch_one(&state.pool)
         .await?;
 
-    let collections: Vec<NftContract> = sqlx::query_as(
-        "SELECT address, name, symbol, total_supply, first_seen_block
-         FROM nft_contracts
-         ORDER BY first_seen_block DESC
-         LIMIT $1 OFFSET $2",
-    )
+    let collections: Vec<Collection> = sqlx::query_as(&format!(
+        "SELECT {COLLECTION_COLUMNS}
+         FROM nft_contracts c
+         {COLLECTION_LOGO_JOIN}
+         ORDER BY c.first_seen_block DESC, c.address
+         LIMIT $1 OFFSET $2"
+    ))
     .bind(pagination.limit())
     .bind(pagination.offset())
     .fetch_all(&state.pool)
@@ -36,24 +58,52 @@ pub async fn list_collections(
     )))
 }
 
+/// A collection with its default royalty.
+#[derive(Serialize)]
+pub struct CollectionDetail {
+    #[serde(flatten)]
+    pub collection: Collection,
+    /// `null` for collections without EIP-2981 support, or not fetched yet
+    pub royalty: Option<Royalty>,
+}
+
+/// Default EIP-2981 royalty, read by the metadata fetcher.
+#[derive(Serialize)]
+pub struct Royalty {
+    /// `null` when royalties are paid to the zero address
+    pub receiver: Option<String>,
+    /// Share of the sale price in basis points (250 = 2.5%)
+    pub bps: i32,
+}
+
+#[derive(sqlx::FromRow)]
+struct CollectionRow {
+    #[sqlx(flatten)]
+    collection: Collection,
+    royalty_receiver: Option<String>,
+    royalty_bps: Option<i32>,
+}
+
 pub async fn get_collection(
     State(state): State<Arc<AppState>>,
     Path(address): Path<String>,
-) -> ApiResult<Json<NftContract>> {
+) -> ApiResult<Json<CollectionDetail>> {
     let address = normalize_address(&address);
 
-    let mut collection: NftContract = sqlx::query_as(
-        "SELECT address, name, symbol, total_supply, first_seen_block
-         FROM nft_contracts
-         WHERE address = $1",
-    )
+    let row: CollectionRow = sqlx::query_as(&format!(
+        "SELECT {COLLECTION_COLUMNS}, c.royalty_receiver, c.royalty_bps
+         FROM nft_contracts c
+         {COLLECTION_LOGO_JOIN}
+         WHERE c.address = $1"
+    ))
     .bind(&address)
     .fetch_optional(&state.pool)
     .await?
     .ok_or_else(|| AtlasError::NotFound(format!("Collection {} not found", address)))?;
+    let mut collection = row.collection;
 
     // Fetch name/symbol on-demand if not already fetched
-    if collection.name.is_none() && collection.symbol.is_none() {
+    if collection.contract.name.is_none() && collection.contract.symbol.is_none() {
         if let Ok((name, symbol)) = fetch_collection_metadata(&state.rpc_url, &address).await {
             // Update the database
             sqlx::query("UPDATE nft_contracts SET name = $1, symbol = $2 WHERE address = $3")
@@ -62,13 +112,20 @@ pub async fn get_collection(
                 .bind(&address)
                 .execute(&state.pool)
                 .await?;
+            address_directory::refresh(&state.pool, &[&address]).await?;
 
-            collection.name = name;
-            collection.symbol = symbol;
+            collection.contract.name = name;
+            collection.contract.symbol = symbol;
         }
     }
 
-    Ok(Json(collection))
+    Ok(Json(CollectionDetail {
+        collection,
+        royalty: row.royalty_bps.map(|bps| Royalty {
+            receiver: row.royalty_receiver,
+            bps,
+        }),
+    }))
 }
 
 /// Fetch NFT collection name and symbol from contract
@@ -115,29 +172,68 @@ async fn fetch_collection_metadata(
     Ok((name, symbol))
 }
 
+/// Most `trait[...]` filters accepted on one request.
+const MAX_TRAIT_FILTERS: usize = 20;
+
+/// Values listed per trait type in the rarity response; the rest are only counted.
+const MAX_VALUES_PER_TRAIT: usize = 200;
+
+/// GET /api/nfts/collections/:address/tokens - Tokens of a collection,
+/// optionally filtered by `trait[<trait_type>]=<value>`.
+///
+/// Different trait types must all match; repeating a trait type matches any
+/// of its values.
 pub async fn list_collection_tokens(
     State(state): State<Arc<AppState>>,
     Path(address): Path<String>,
     Query(pagination): Query<Pagination>,
+    Query(params): Query<Vec<(String, String)>>,
 ) -> ApiResult<Json<PaginatedResponse<NftToken>>> {
     let address = normalize_address(&address);
+    let filters = parse_trait_filters(&params)?;
+    let (trait_types, values): (Vec<&str>, Vec<&str>) = filters
+        .iter()
+        .map(|(trait_type, value)| (trait_type.as_str(), value.as_str()))
+        .unzip();
+    let mut distinct_trait_types = trait_types.clone();
+    distinct_trait_types.sort_unstable();
+    distinct_trait_types.dedup();
+    let required_matches = distinct_trait_types.len() as i64;
+
+    // A token matches when it has a listed value for every filtered trait type.
+    let trait_filter = "($2 = 0 OR (
+            SELECT COUNT(DISTINCT a.trait_type)
+            FROM nft_token_attributes a
+            WHERE a.contract_address = t.contract_address
+              AND a.token_id = t.token_id
+              AND (a.trait_type, a.value) IN (
+                  SELECT * FROM unnest($3::text[], $4::text[])
+              )
+         ) = $2)";
+
+    let total: (i64,) = sqlx::query_as(&format!(
+        "SELECT COUNT(*) FROM nft_tokens t WHERE t.contract_address = $1 AND {trait_filter}"
+    ))
+    .bind(&address)
+    .bind(required_matches)
+    .bind(&trait_types)
+    .bind(&values)
+    .fetch_one(&state.pool)
+    .await?;
 
-    let total: (i64,) =
-        sqlx::query_as("SELECT COUNT(*) FROM nft_tokens WHERE contract_address = $1")
-            .bind(&address)
-            .fetch_one(&state.pool)
-            .await?;
-
-    let tokens: Vec<NftToken> = sqlx::query_as(
+    let tokens: Vec<NftToken> = sqlx::query_as(&format!(
         "SELECT contract_address, token_id, owner, token_uri, metadata_status, metadata_retry_count,
                 next_retry_at, last_metadata_error, last_metadata_attempted_at, metadata_updated_at,
                 metadata, image_url, name, last_transfer_block
-         FROM nft_tokens
-         WHERE contract_address = $1
+         FROM nft_tokens t
+         WHERE t.contract_address = $1 AND {trait_filter}
          ORDER BY token_id ASC
-         LIMIT $2 OFFSET $3"
-    )
+         LIMIT $5 OFFSET $6"
+    ))
     .bind(&address)
+    .bind(required_matches)
+    .bind(&trait_types)
+    .bind(&values)
     .bind(pagination.limit())
     .bind(pagination.offset())
     .fetch_all(&state.pool)
@@ -151,10 +247,168 @@ pub async fn list_collection_tokens(
     )))
 }
 
+/// `(trait_type, value)` pairs from `trait[<trait_type>]=<value>` query params.
+fn parse_trait_filters(params: &[(String, String)]) -> Result<Vec<(String, String)>, AtlasError> {
+    let mut filters: Vec<(String, String)> = Vec::new();
+    for (key, value) in params {
+        let Some(trait_type) = key
+            .strip_prefix("trait[")
+            .and_then(|rest| rest.strip_suffix(']'))
+        else {
+            continue;
+        };
+        let (trait_type, value) = (trait_type.trim(), value.trim());
+        if trait_type.is_empty() || value.is_empty() {
+            return Err(AtlasError::InvalidInput(format!(
+                "Invalid trait filter {key}={value}: trait type and value must be non-empty"
+            )));
+        }
+        let pair = (trait_type.to_string(), value.to_string());
+        if !filters.contains(&pair) {
+            filters.push(pair);
+        }
+    }
+    if filters.len() > MAX_TRAIT_FILTERS {
+        return Err(AtlasError::InvalidInput(format!(
+            "At most {MAX_TRAIT_FILTERS} trait filters are allowed"
+        )));
+    }
+    Ok(filters)
+}
+
+#[derive(Debug, Serialize)]
+pub struct CollectionTraits {
+    pub address: String,
+    /// Tokens in the collection; the denominator for `frequency`.
+    pub total_tokens: i64,
+    pub traits: Vec<TraitSummary>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TraitSummary {
+    pub trait_type: String,
+    /// Distinct values of this trait, including any beyond the listed ones.
+    pub value_count: usize,
+    /// Most common values first.
+    pub values: Vec<TraitValueCount>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TraitValueCount {
+    pub value: String,
+    pub count: i64,
+    /// `count / total_tokens`; lower is rarer.
+    pub frequency: f64,
+}
+
+/// GET /api/nfts/collections/:address/traits - Trait values and their rarity across a collection
+pub async fn get_collection_traits(
+    State(state): State<Arc<AppState>>,
+    Path(address): Path<String>,
+) -> ApiResult<Json<CollectionTraits>> {
+    let address = normalize_address(&address);
+
+    let exists: bool =
+        sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM nft_contracts WHERE address = $1)")
+            .bind(&address)
+            .fetch_one(&state.pool)
+            .await?;
+    if !exists {
+        return Err(AtlasError::NotFound(format!("Collection {} not found", address)).into());
+    }
+
+    let total_tokens: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM nft_tokens WHERE contract_address = $1")
+            .bind(&address)
+            .fetch_one(&state.pool)
+            .await?;
+
+    let rows: Vec<(String, String, i64)> = sqlx::query_as(
+        "SELECT trait_type, value, COUNT(*)
+         FROM nft_token_attributes
+         WHERE contract_address = $1
+         GROUP BY trait_type, value
+         ORDER BY trait_type ASC, COUNT(*) DESC, value ASC",
+    )
+    .bind(&address)
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(Json(CollectionTraits {
+        address,
+        total_tokens,
+        traits: summarize_traits(rows, total_tokens),
+    }))
+}
+
+/// Group `(trait_type, value, count)` rows, already ordered by trait type and
+/// descending count, into per-trait summaries.
+fn summarize_traits(rows: Vec<(String, String, i64)>, total_tokens: i64) -> Vec<TraitSummary> {
+    let mut traits: Vec<TraitSummary> = Vec::new();
+    for (trait_type, value, count) in rows {
+        if traits.last().map(|t| &t.trait_type) != Some(&trait_type) {
+            traits.push(TraitSummary {
+                trait_type,
+                value_count: 0,
+                values: Vec::new(),
+            });
+        }
+        let summary = traits.last_mut().expect("pushed above");
+        summary.value_count += 1;
+        if summary.values.len() < MAX_VALUES_PER_TRAIT {
+            summary.values.push(TraitValueCount {
+                value,
+                count,
+                frequency: if total_tokens > 0 {
+                    count as f64 / total_tokens as f64
+                } else {
+                    0.0
+                },
+            });
+        }
+    }
+    traits
+}
+
+/// A token with its latest marketplace sale.
+#[derive(Serialize)]
+pub struct TokenDetail {
+    #[serde(flatten)]
+    pub token: NftToken,
+    /// `null` until a sale is decoded by the `nft_sales` block processor
+    pub last_sale: Option<NftSale>,
+}
+
+/// An ERC-721 sale decoded by the `nft_sales` block processor.
+#[derive(Serialize, sqlx::FromRow)]
+pub struct NftSale {
+    pub block_number: i64,
+    pub log_index: i32,
+    pub tx_hash: String,
+    pub timestamp: i64,
+    /// Protocol of the order event, e.g. `seaport`
+    pub marketplace: String,
+    /// Contract that emitted the order event
+    pub exchange: String,
+    pub token_id: String,
+    pub seller: String,
+    pub buyer: String,
+    /// ERC-20 paid, `null` for the native coin
+    pub currency: Option<String>,
+    pub currency_symbol: Option<String>,
+    pub currency_decimals: Option<i16>,
+    /// Price in the currency's raw units, fees and royalties included
+    pub price: String,
+}
+
+const SALE_COLUMNS: &str = "s.block_number, s.log_index, s.tx_hash, s.timestamp, s.marketplace,
+    s.exchange, s.token_id::text AS token_id, s.seller, s.buyer, s.currency,
+    c.symbol AS currency_symbol, c.decimals AS currency_decimals, s.price::text AS price";
+
 pub async fn get_token(
     State(state): State<Arc<AppState>>,
     Path((address, token_id)): Path<(String, String)>,
-) -> ApiResult<Json<NftToken>> {
+) -> ApiResult<Json<TokenDetail>> {
     let address = normalize_address(&address);
 
     let token: NftToken = sqlx::query_as(
@@ -170,7 +424,21 @@ pub async fn get_token(
     .await?
     .ok_or_else(|| AtlasError::NotFound(format!("Token {}:{} not found", address, token_id)))?;
 
-    Ok(Json(token))
+    let last_sale: Option<NftSale> = sqlx::query_as(&format!(
+        "SELECT {SALE_COLUMNS}
+         FROM nft_sales s
+         LEFT JOIN erc20_contracts c ON c.address = s.currency
+         WHERE s.contract_address = $1 AND s.token_id = $2::numeric
+         ORDER BY {order}
+         LIMIT 1",
+        order = ChainRow::Log.order_by("s", Direction::Desc)
+    ))
+    .bind(&address)
+    .bind(&token_id)
+    .fetch_optional(&state.pool)
+    .await?;
+
+    Ok(Json(TokenDetail { token, last_sale }))
 }
 
 /// Decode an ABI-encoded string
@@ -203,32 +471,74 @@ pub async fn get_collection_transfers(
     Query(pagination): Query<Pagination>,
 ) -> ApiResult<Json<PaginatedResponse<NftTransfer>>> {
     let address = normalize_address(&address);
+    let anchor = snapshot_anchor(&state, pagination.as_of_block).await?;
 
-    let total: (i64,) =
-        sqlx::query_as("SELECT COUNT(*) FROM nft_transfers WHERE contract_address = $1")
-            .bind(&address)
-            .fetch_one(&state.pool)
-            .await?;
+    let total: (i64,) = sqlx::query_as(
+        "SELECT COUNT(*) FROM nft_transfers WHERE contract_address = $1 AND block_number <= $2",
+    )
+    .bind(&address)
+    .bind(anchor_bound(anchor))
+    .fetch_one(&state.pool)
+    .await?;
 
-    let transfers: Vec<NftTransfer> = sqlx::query_as(
+    let transfers: Vec<NftTransfer> = sqlx::query_as(&format!(
         "SELECT id, tx_hash, log_index, contract_address, token_id, from_address, to_address, block_number, timestamp
          FROM nft_transfers
-         WHERE contract_address = $1
-         ORDER BY block_number DESC, log_index DESC
-         LIMIT $2 OFFSET $3"
+         WHERE contract_address = $1 AND block_number <= $4
+         ORDER BY {}
+         LIMIT $2 OFFSET $3",
+        ChainRow::Log.order_by("", Direction::Desc)
+    ))
+    .bind(&address)
+    .bind(pagination.limit())
+    .bind(pagination.offset())
+    .bind(anchor_bound(anchor))
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(Json(
+        PaginatedResponse::new(transfers, pagination.page, pagination.limit, total.0)
+            .anchored_at(anchor),
+    ))
+}
+
+/// GET /api/nfts/collections/{address}/sales - Marketplace sales of a collection, newest first
+pub async fn get_collection_sales(
+    State(state): State<Arc<AppState>>,
+    Path(address): Path<String>,
+    Query(pagination): Query<Pagination>,
+) -> ApiResult<Json<PaginatedResponse<NftSale>>> {
+    let address = normalize_address(&address);
+    let anchor = snapshot_anchor(&state, pagination.as_of_block).await?;
+
+    let total: (i64,) = sqlx::query_as(
+        "SELECT COUNT(*) FROM nft_sales WHERE contract_address = $1 AND block_number <= $2",
     )
     .bind(&address)
+    .bind(anchor_bound(anchor))
+    .fetch_one(&state.pool)
+    .await?;
+
+    let sales: Vec<NftSale> = sqlx::query_as(&format!(
+        "SELECT {SALE_COLUMNS}
+         FROM nft_sales s
+         LEFT JOIN erc20_contracts c ON c.address = s.currency
+         WHERE s.contract_address = $1 AND s.block_number <= $4
+         ORDER BY {order}, s.token_id
+         LIMIT $2 OFFSET $3",
+        order = ChainRow::Log.order_by("s", Direction::Desc)
+    ))
+    .bind(&address)
     .bind(pagination.limit())
     .bind(pagination.offset())
+    .bind(anchor_bound(anchor))
     .fetch_all(&state.pool)
     .await?;
 
-    Ok(Json(PaginatedResponse::new(
-        transfers,
-        pagination.page,
-        pagination.limit,
-        total.0,
-    )))
+    Ok(Json(
+        PaginatedResponse::new(sales, pagination.page, pagination.limit, total.0)
+            .anchored_at(anchor),
+    ))
 }
 
 /// GET /api/nfts/collections/{address}/tokens/{token_id}/transfers - Get transfers for a specific token
@@ -238,35 +548,38 @@ pub async fn get_token_transfers(
     Query(pagination): Query<Pagination>,
 ) -> ApiResult<Json<PaginatedResponse<NftTransfer>>> {
     let address = normalize_address(&address);
+    let anchor = snapshot_anchor(&state, pagination.as_of_block).await?;
 
     let total: (i64,) = sqlx::query_as(
-        "SELECT COUNT(*) FROM nft_transfers WHERE contract_address = $1 AND token_id = $2::numeric",
+        "SELECT COUNT(*) FROM nft_transfers
+         WHERE contract_address = $1 AND token_id = $2::numeric AND block_number <= $3",
     )
     .bind(&address)
     .bind(&token_id)
+    .bind(anchor_bound(anchor))
     .fetch_one(&state.pool)
     .await?;
 
-    let transfers: Vec<NftTransfer> = sqlx::query_as(
+    let transfers: Vec<NftTransfer> = sqlx::query_as(&format!(
         "SELECT id, tx_hash, log_index, contract_address, token_id, from_address, to_address, block_number, timestamp
          FROM nft_transfers
-         WHERE contract_address = $1 AND token_id = $2::numeric
-         ORDER BY block_number DESC, log_index DESC
-         LIMIT $3 OFFSET $4"
-    )
+         WHERE contract_address = $1 AND token_id = $2::numeric AND block_number <= $5
+         ORDER BY {}
+         LIMIT $3 OFFSET $4",
+        ChainRow::Log.order_by("", Direction::Desc)
+    ))
     .bind(&address)
     .bind(&token_id)
     .bind(pagination.limit())
     .bind(pagination.offset())
+    .bind(anchor_bound(anchor))
     .fetch_all(&state.pool)
     .await?;
 
-    Ok(Json(PaginatedResponse::new(
-        transfers,
-        pagination.page,
-        pagination.limit,
-        total.0,
-    )))
+    Ok(Json(
+        PaginatedResponse::new(transfers, pagination.page, pagination.limit, total.0)
+            .anchored_at(anchor),
+    ))
 }
 
 fn normalize_address(address: &str) -> String {
@@ -276,3 +589,64 @@ fn normalize_address(address: &str) -> String {
         format!("0x{}", address.to_lowercase())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn parses_trait_filters_and_ignores_other_params() {
+        let filters = parse_trait_filters(&params(&[
+            ("page", "2"),
+            ("trait[Background]", "Gold"),
+            ("trait[Background]", "Blue"),
+            ("trait[Eyes]", " Laser "),
+            ("trait[Eyes]", "Laser"),
+        ]))
+        .unwrap();
+
+        assert_eq!(
+            filters,
+            vec![
+                ("Background".to_string(), "Gold".to_string()),
+                ("Background".to_string(), "Blue".to_string()),
+                ("Eyes".to_string(), "Laser".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_empty_or_too_many_trait_filters() {
+        assert!(parse_trait_filters(&params(&[("trait[]", "Gold")])).is_err());
+        assert!(parse_trait_filters(&params(&[("trait[Eyes]", "")])).is_err());
+
+        let many: Vec<(String, String)> = (0..=MAX_TRAIT_FILTERS)
+            .map(|i| ("trait[Level]".to_string(), i.to_string()))
+            .collect();
+        assert!(parse_trait_filters(&many).is_err());
+    }
+
+    #[test]
+    fn summarizes_trait_rarity() {
+        let rows = vec![
+            ("Background".to_string(), "Blue".to_string(), 3),
+            ("Background".to_string(), "Gold".to_string(), 1),
+            ("Eyes".to_string(), "Laser".to_string(), 2),
+        ];
+        let traits = summarize_traits(rows, 4);
+
+        assert_eq!(traits.len(), 2);
+        assert_eq!(traits[0].trait_type, "Background");
+        assert_eq!(traits[0].value_count, 2);
+        assert_eq!(traits[0].values[1].value, "Gold");
+        assert_eq!(traits[0].values[1].frequency, 0.25);
+        assert_eq!(traits[1].values[0].count, 2);
+    }
+}
@@ -0,0 +1,210 @@
+//! Private address notes and tags, kept per API key
+//!
+//! Holders of a key issued through `/api/labels/keys` can annotate the
+//! addresses they track. Notes live in `address_notes`, apart from the
+//! curated `address_labels`, and are only ever returned to the key that wrote
+//! them. Revoking the key hides its notes; deleting it removes them.
+
+use axum::{
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::api::auth::authorize_api_key;
+use crate::api::error::ApiResult;
+use crate::api::AppState;
+use crate::labels::{normalize_label_address, normalize_tags};
+use atlas_common::{AddressNote, AtlasError, PaginatedResponse, Pagination};
+
+const MAX_NOTE_LEN: usize = 2000;
+/// Addresses one key may annotate.
+const MAX_NOTES_PER_KEY: i64 = 10_000;
+
+const NOTE_COLUMNS: &str = "address, note, tags, created_at, updated_at";
+
+#[derive(Debug, Deserialize)]
+pub struct NoteRequest {
+    pub address: String,
+    pub note: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NoteFilters {
+    /// Only notes carrying this private tag
+    pub tag: Option<String>,
+}
+
+/// POST /api/me/notes — create or replace the caller's note on an address
+pub async fn save_note(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Json(request): Json<NoteRequest>,
+) -> ApiResult<Json<AddressNote>> {
+    let key_id = authorize_api_key(&state, &headers).await?;
+    let address = normalize_label_address(&request.address)?;
+    let note = validate_note(request.note)?;
+    let tags = normalize_tags(&request.tags)?;
+    if note.is_none() && tags.is_empty() {
+        return Err(AtlasError::InvalidInput(
+            "A note needs text or at least one tag; use DELETE to remove it".to_string(),
+        )
+        .into());
+    }
+
+    let mut tx = state.pool.begin().await?;
+    // Serialize saves per key so concurrent requests can't overshoot the cap.
+    sqlx::query("SELECT id FROM label_api_keys WHERE id = $1 FOR UPDATE")
+        .bind(key_id)
+        .execute(&mut *tx)
+        .await?;
+    let (others,): (i64,) = sqlx::query_as(
+        "SELECT COUNT(*) FROM address_notes WHERE api_key_id = $1 AND address <> $2",
+    )
+    .bind(key_id)
+    .bind(&address)
+    .fetch_one(&mut *tx)
+    .await?;
+    if others >= MAX_NOTES_PER_KEY {
+        return Err(AtlasError::InvalidInput(format!(
+            "An API key can keep notes on at most {MAX_NOTES_PER_KEY} addresses"
+        ))
+        .into());
+    }
+
+    let saved: AddressNote = sqlx::query_as(&format!(
+        "INSERT INTO address_notes (api_key_id, address, note, tags)
+         VALUES ($1, $2, $3, $4)
+         ON CONFLICT (api_key_id, address) DO UPDATE
+         SET note = EXCLUDED.note, tags = EXCLUDED.tags, updated_at = NOW()
+         RETURNING {NOTE_COLUMNS}"
+    ))
+    .bind(key_id)
+    .bind(&address)
+    .bind(&note)
+    .bind(&tags)
+    .fetch_one(&mut *tx)
+    .await?;
+    tx.commit().await?;
+
+    Ok(Json(saved))
+}
+
+/// GET /api/me/notes — the caller's notes, most recently updated first
+pub async fn list_notes(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Query(filters): Query<NoteFilters>,
+    Query(pagination): Query<Pagination>,
+) -> ApiResult<Json<PaginatedResponse<AddressNote>>> {
+    let key_id = authorize_api_key(&state, &headers).await?;
+    let tag = filters
+        .tag
+        .map(|tag| normalize_tags(&[tag]))
+        .transpose()?
+        .and_then(|tags| tags.into_iter().next());
+
+    let (total,): (i64,) = sqlx::query_as(
+        "SELECT COUNT(*) FROM address_notes
+         WHERE api_key_id = $1 AND ($2::text IS NULL OR $2 = ANY(tags))",
+    )
+    .bind(key_id)
+    .bind(&tag)
+    .fetch_one(&state.pool)
+    .await?;
+
+    let notes: Vec<AddressNote> = sqlx::query_as(&format!(
+        "SELECT {NOTE_COLUMNS} FROM address_notes
+         WHERE api_key_id = $1 AND ($2::text IS NULL OR $2 = ANY(tags))
+         ORDER BY updated_at DESC, address
+         LIMIT $3 OFFSET $4"
+    ))
+    .bind(key_id)
+    .bind(&tag)
+    .bind(pagination.limit())
+    .bind(pagination.offset())
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(Json(PaginatedResponse::new(
+        notes,
+        pagination.page,
+        pagination.limit() as u32,
+        total,
+    )))
+}
+
+/// GET /api/me/notes/{address} — the caller's note on one address
+pub async fn get_note(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Path(address): Path<String>,
+) -> ApiResult<Json<AddressNote>> {
+    let key_id = authorize_api_key(&state, &headers).await?;
+    let address = normalize_label_address(&address)?;
+
+    let note: AddressNote = sqlx::query_as(&format!(
+        "SELECT {NOTE_COLUMNS} FROM address_notes WHERE api_key_id = $1 AND address = $2"
+    ))
+    .bind(key_id)
+    .bind(&address)
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or_else(|| AtlasError::NotFound(format!("No note for {address}")))?;
+    Ok(Json(note))
+}
+
+/// DELETE /api/me/notes/{address} — remove the caller's note on an address
+pub async fn delete_note(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Path(address): Path<String>,
+) -> ApiResult<StatusCode> {
+    let key_id = authorize_api_key(&state, &headers).await?;
+    let address = normalize_label_address(&address)?;
+
+    let deleted = sqlx::query("DELETE FROM address_notes WHERE api_key_id = $1 AND address = $2")
+        .bind(key_id)
+        .bind(&address)
+        .execute(&state.pool)
+        .await?
+        .rows_affected();
+    if deleted == 0 {
+        return Err(AtlasError::NotFound(format!("No note for {address}")).into());
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Trimmed note text; blank notes are `None`.
+fn validate_note(note: Option<String>) -> Result<Option<String>, AtlasError> {
+    let note = note.map(|n| n.trim().to_string()).filter(|n| !n.is_empty());
+    if note
+        .as_ref()
+        .is_some_and(|n| n.chars().count() > MAX_NOTE_LEN)
+    {
+        return Err(AtlasError::InvalidInput(format!(
+            "Note must be at most {MAX_NOTE_LEN} characters"
+        )));
+    }
+    Ok(note)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_note_trims_and_limits_length() {
+        assert_eq!(
+            validate_note(Some("  cold wallet ".to_string())).unwrap(),
+            Some("cold wallet".to_string())
+        );
+        assert_eq!(validate_note(Some("   ".to_string())).unwrap(), None);
+        assert_eq!(validate_note(None).unwrap(), None);
+        assert!(validate_note(Some("x".repeat(MAX_NOTE_LEN + 1))).is_err());
+    }
+}
@@ -0,0 +1,171 @@
+//! Homepage summary: everything the explorer landing page shows, in one
+//! request.
+//!
+//! Blocks, TPS and gas price come from the head tracker's replay buffer when
+//! it holds enough blocks (the database is only hit right after startup or a
+//! reindex), totals use the approximate table counts, and indexer lag uses the
+//! chain head cached by the in-process indexer.
+
+use axum::{
+    extract::{Query, State},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use crate::api::error::ApiResult;
+use crate::api::handlers::get_table_count;
+use crate::api::AppState;
+use crate::ordering::{ChainRow, Direction};
+use atlas_common::{Block, Transaction, BLOCK_COLUMNS};
+
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 25;
+
+#[derive(Deserialize)]
+pub struct OverviewQuery {
+    #[serde(default = "default_limit")]
+    pub limit: u32,
+}
+
+fn default_limit() -> u32 {
+    DEFAULT_LIMIT
+}
+
+impl OverviewQuery {
+    fn limit(&self) -> usize {
+        self.limit.clamp(1, MAX_LIMIT) as usize
+    }
+}
+
+#[derive(Serialize)]
+pub struct Overview {
+    pub latest_blocks: Vec<Block>,
+    pub latest_transactions: Vec<Transaction>,
+    /// Transactions per second over the blocks in the replay buffer.
+    pub tps: Option<f64>,
+    /// Base fee of the latest block in wei (`null` on chains without EIP-1559).
+    pub gas_price: Option<String>,
+    pub total_transactions: i64,
+    pub total_addresses: i64,
+    pub block_height: i64,
+    /// Chain head last seen by the indexer (`null` when it runs in another process).
+    pub chain_height: Option<i64>,
+    pub indexer_lag_blocks: Option<i64>,
+}
+
+/// GET /api/overview?limit=10 - Homepage summary in one payload.
+pub async fn get_overview(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<OverviewQuery>,
+) -> ApiResult<Json<Overview>> {
+    let limit = query.limit();
+
+    // Oldest first, like the replay buffer.
+    let mut window = state
+        .head_tracker
+        .replay_after(None)
+        .await
+        .blocks_after_cursor;
+    if window.len() < limit {
+        window = sqlx::query_as(&format!(
+            "SELECT {} FROM blocks ORDER BY number DESC LIMIT $1",
+            BLOCK_COLUMNS
+        ))
+        .bind(limit as i64)
+        .fetch_all(&state.pool)
+        .await?;
+        window.reverse();
+    }
+
+    let latest_transactions: Vec<Transaction> = sqlx::query_as(&format!(
+        "SELECT hash, block_number, block_index, from_address, to_address, value, gas_price, gas_used, input_data, status, contract_created, timestamp
+         FROM transactions
+         ORDER BY {}
+         LIMIT $1",
+        ChainRow::Transaction.order_by("", Direction::Desc)
+    ))
+    .bind(limit as i64)
+    .fetch_all(&state.pool)
+    .await?;
+
+    let total_transactions = get_table_count(&state.pool, "transactions").await?;
+    let total_addresses = get_table_count(&state.pool, "addresses").await?;
+
+    let tps = tps(&window);
+    let gas_price = window.last().and_then(|b| b.base_fee_per_gas.clone());
+    let block_height = state
+        .latest_block_height()
+        .await?
+        .or(window.last().map(|b| b.number))
+        .unwrap_or(0);
+    let chain_height = state.head_tracker.chain_height();
+    let indexer_lag_blocks = chain_height.map(|head| (head - block_height).max(0));
+
+    let latest_blocks = window.into_iter().rev().take(limit).collect();
+
+    Ok(Json(Overview {
+        latest_blocks,
+        latest_transactions,
+        tps,
+        gas_price,
+        total_transactions,
+        total_addresses,
+        block_height,
+        chain_height,
+        indexer_lag_blocks,
+    }))
+}
+
+/// Transactions per second across `blocks` (sorted oldest first). The first
+/// block's transactions happened before the window starts, so only the blocks
+/// after it count. `None` without at least two blocks spanning some time.
+fn tps(blocks: &[Block]) -> Option<f64> {
+    let (first, last) = (blocks.first()?, blocks.last()?);
+    let span = last.timestamp - first.timestamp;
+    if span <= 0 {
+        return None;
+    }
+    let txs: i64 = blocks[1..].iter().map(|b| b.transaction_count as i64).sum();
+    Some(txs as f64 / span as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn block(number: i64, timestamp: i64, transaction_count: i32) -> Block {
+        Block {
+            number,
+            hash: format!("0x{number:064x}"),
+            parent_hash: format!("0x{:064x}", number - 1),
+            timestamp,
+            gas_used: 0,
+            gas_limit: 30_000_000,
+            base_fee_per_gas: None,
+            transaction_count,
+            indexed_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn tps_excludes_first_block_transactions() {
+        let blocks = [block(1, 100, 50), block(2, 102, 4), block(3, 104, 6)];
+        assert_eq!(tps(&blocks), Some(2.5));
+    }
+
+    #[test]
+    fn tps_needs_a_time_span() {
+        assert_eq!(tps(&[]), None);
+        assert_eq!(tps(&[block(1, 100, 3)]), None);
+        assert_eq!(tps(&[block(1, 100, 3), block(2, 100, 3)]), None);
+    }
+
+    #[test]
+    fn limit_is_clamped() {
+        assert_eq!(OverviewQuery { limit: 0 }.limit(), 1);
+        assert_eq!(OverviewQuery { limit: 1000 }.limit(), MAX_LIMIT as usize);
+        assert_eq!(OverviewQuery { limit: 5 }.limit(), 5);
+    }
+}
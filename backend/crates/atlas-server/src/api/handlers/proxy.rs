@@ -8,12 +8,20 @@ use axum::{
     extract::{Path, State},
     Json,
 };
+use futures::stream::{self, StreamExt, TryStreamExt};
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
 use crate::api::error::ApiResult;
 use crate::api::AppState;
+use atlas_common::encoding::{address_to_hex, parse_address};
 use atlas_common::{AtlasError, ContractAbi, ProxyContract};
 
+/// Most addresses accepted by one ABI bundle request.
+pub const MAX_BUNDLE_ADDRESSES: usize = 100;
+/// Addresses of one bundle resolved at the same time (each may call the RPC).
+const BUNDLE_CONCURRENCY: usize = 4;
+
 // EIP-1967 implementation slot: keccak256("eip1967.proxy.implementation") - 1
 const EIP1967_IMPL_SLOT: &str =
     "0x360894a13ba1a3210667c828492db98dca3e2076cc3735a920a3ca505d382bbc";
@@ -65,7 +73,7 @@ async fn read_address_slot(
 
 /// Detect a proxy pattern for `address` via RPC and, if found, persist it in `proxy_contracts`.
 /// Returns the cached or newly detected `ProxyContract`, or `None` if not a proxy.
-async fn resolve_proxy(
+pub(crate) async fn resolve_proxy(
     state: &AppState,
     address: &str,
 ) -> Result<Option<ProxyContract>, AtlasError> {
@@ -281,6 +289,80 @@ pub struct CombinedAbiResponse {
     pub implementation_abi: Option<serde_json::Value>,
 }
 
+#[derive(Debug, serde::Deserialize)]
+pub struct AbiBundleRequest {
+    pub addresses: Vec<String>,
+}
+
+/// POST /api/abis/bundle - Combined ABIs of several contracts, keyed by
+/// lowercase address. Proxies get their implementation's ABI merged in, as
+/// with `combined-abi`; addresses without a verified ABI map to `null`.
+pub async fn get_abi_bundle(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<AbiBundleRequest>,
+) -> ApiResult<Json<BTreeMap<String, Option<serde_json::Value>>>> {
+    if request.addresses.is_empty() {
+        return Err(AtlasError::InvalidInput(
+            "addresses must contain at least one address".to_string(),
+        )
+        .into());
+    }
+    if request.addresses.len() > MAX_BUNDLE_ADDRESSES {
+        return Err(AtlasError::InvalidInput(format!(
+            "Bundle has {} addresses; at most {MAX_BUNDLE_ADDRESSES} are allowed",
+            request.addresses.len()
+        ))
+        .into());
+    }
+    let mut addresses = request
+        .addresses
+        .iter()
+        .map(|address| Ok(address_to_hex(&parse_address(address)?)))
+        .collect::<Result<Vec<_>, AtlasError>>()?;
+    addresses.sort();
+    addresses.dedup();
+
+    let bundle = stream::iter(addresses)
+        .map(|address| {
+            let state = &state;
+            async move {
+                let abi = combined_abi(state, &address).await?;
+                Ok::<_, AtlasError>((address, abi))
+            }
+        })
+        .buffered(BUNDLE_CONCURRENCY)
+        .try_collect()
+        .await?;
+    Ok(Json(bundle))
+}
+
+/// ABI of `address` merged with its implementation's if it is a proxy.
+async fn combined_abi(
+    state: &AppState,
+    address: &str,
+) -> Result<Option<serde_json::Value>, AtlasError> {
+    let proxy = resolve_proxy(state, address).await?;
+    let mut addresses = vec![address.to_string()];
+    if let Some(proxy) = &proxy {
+        addresses.push(proxy.implementation_address.clone());
+    }
+    let abis: Vec<(String, serde_json::Value)> =
+        sqlx::query_as("SELECT address, abi FROM contract_abis WHERE address = ANY($1)")
+            .bind(&addresses)
+            .fetch_all(&state.pool)
+            .await?;
+    let abi_of = |address: &str| {
+        abis.iter()
+            .find(|(abi_address, _)| abi_address == address)
+            .map(|(_, abi)| abi)
+    };
+
+    Ok(match &proxy {
+        Some(proxy) => merge_abis(abi_of(address), abi_of(&proxy.implementation_address)),
+        None => abi_of(address).cloned(),
+    })
+}
+
 /// Merge proxy and implementation ABIs
 fn merge_abis(
     proxy_abi: Option<&serde_json::Value>,
@@ -336,7 +418,7 @@ pub async fn list_proxies(
     let proxies: Vec<ProxyContract> = sqlx::query_as(
         "SELECT proxy_address, implementation_address, proxy_type, admin_address, detected_at_block, last_checked_block, updated_at
          FROM proxy_contracts
-         ORDER BY detected_at_block DESC
+         ORDER BY detected_at_block DESC, proxy_address
          LIMIT $1 OFFSET $2",
     )
     .bind(pagination.limit())
@@ -352,7 +434,7 @@ pub async fn list_proxies(
     )))
 }
 
-fn normalize_address(address: &str) -> String {
+pub(crate) fn normalize_address(address: &str) -> String {
     if address.starts_with("0x") {
         address.to_lowercase()
     } else {
@@ -0,0 +1,225 @@
+//! Raw node JSON for indexed blocks and transactions.
+//!
+//! Responses are the node's JSON-RPC results verbatim (not re-serialized
+//! through alloy types), so fields the indexer ignores or decodes differently
+//! stay visible for debugging. The first request fetches from the RPC and
+//! stores the result in `raw_rpc_cache`; later requests are served from the
+//! database. An entry is only cached when the node's block hash matches the
+//! indexed one, and a cached entry whose block hash no longer matches (after
+//! a reorg was re-indexed) is refetched.
+
+use axum::{
+    extract::{Path, State},
+    http::HeaderName,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde_json::Value;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::api::error::ApiResult;
+use crate::api::handlers::canonical_tx_block;
+use crate::api::AppState;
+use atlas_common::AtlasError;
+
+const RPC_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// `HIT` when served from `raw_rpc_cache`, `MISS` when fetched from the node.
+const CACHE_HEADER: HeaderName = HeaderName::from_static("x-atlas-cache");
+
+/// GET /api/blocks/{number}/raw — node JSON for `eth_getBlockByNumber`
+pub async fn get_raw_block(
+    State(state): State<Arc<AppState>>,
+    Path(number): Path<i64>,
+) -> ApiResult<Response> {
+    let (block_hash,): (String,) = sqlx::query_as("SELECT hash FROM blocks WHERE number = $1")
+        .bind(number)
+        .fetch_optional(&state.pool)
+        .await?
+        .ok_or_else(|| AtlasError::NotFound(format!("Block {} not found", number)))?;
+    let key = number.to_string();
+
+    if let Some(payload) = cached(&state, "block", &key, &block_hash).await? {
+        return Ok(respond(payload, true));
+    }
+
+    let block = rpc_call(
+        &state.rpc_url,
+        "eth_getBlockByNumber",
+        serde_json::json!([format!("0x{:x}", number), false]),
+    )
+    .await?;
+    if block.get("hash").and_then(Value::as_str) == Some(block_hash.as_str()) {
+        store(&state, "block", &key, &block_hash, &block).await?;
+    }
+    Ok(respond(block, false))
+}
+
+/// GET /api/transactions/{hash}/raw — node JSON for `eth_getTransactionByHash`
+/// and `eth_getTransactionReceipt`, as `{transaction, receipt}`
+pub async fn get_raw_transaction(
+    State(state): State<Arc<AppState>>,
+    Path(hash): Path<String>,
+) -> ApiResult<Response> {
+    let hash = normalize_hash(&hash);
+    let not_found = || AtlasError::NotFound(format!("Transaction {} not found", hash));
+    let block_number = canonical_tx_block(&state.pool, &hash)
+        .await?
+        .ok_or_else(not_found)?;
+    let (block_hash,): (String,) = sqlx::query_as("SELECT hash FROM blocks WHERE number = $1")
+        .bind(block_number)
+        .fetch_optional(&state.pool)
+        .await?
+        .ok_or_else(not_found)?;
+
+    if let Some(payload) = cached(&state, "transaction", &hash, &block_hash).await? {
+        return Ok(respond(payload, true));
+    }
+
+    let params = serde_json::json!([hash]);
+    let (transaction, receipt) = tokio::try_join!(
+        rpc_call(&state.rpc_url, "eth_getTransactionByHash", params.clone()),
+        rpc_call(&state.rpc_url, "eth_getTransactionReceipt", params),
+    )?;
+    let canonical = [&transaction, &receipt]
+        .iter()
+        .all(|v| v.get("blockHash").and_then(Value::as_str) == Some(block_hash.as_str()));
+    let payload = serde_json::json!({ "transaction": transaction, "receipt": receipt });
+    if canonical {
+        store(&state, "transaction", &hash, &block_hash, &payload).await?;
+    }
+    Ok(respond(payload, false))
+}
+
+async fn cached(
+    state: &AppState,
+    kind: &str,
+    key: &str,
+    block_hash: &str,
+) -> Result<Option<Value>, AtlasError> {
+    let row: Option<(Value,)> = sqlx::query_as(
+        "SELECT payload FROM raw_rpc_cache WHERE kind = $1 AND key = $2 AND block_hash = $3",
+    )
+    .bind(kind)
+    .bind(key)
+    .bind(block_hash)
+    .fetch_optional(&state.pool)
+    .await?;
+    Ok(row.map(|(payload,)| payload))
+}
+
+async fn store(
+    state: &AppState,
+    kind: &str,
+    key: &str,
+    block_hash: &str,
+    payload: &Value,
+) -> Result<(), AtlasError> {
+    sqlx::query(
+        "INSERT INTO raw_rpc_cache (kind, key, block_hash, payload)
+         VALUES ($1, $2, $3, $4)
+         ON CONFLICT (kind, key) DO UPDATE
+         SET block_hash = EXCLUDED.block_hash, payload = EXCLUDED.payload, fetched_at = NOW()",
+    )
+    .bind(kind)
+    .bind(key)
+    .bind(block_hash)
+    .bind(payload)
+    .execute(&state.pool)
+    .await?;
+    Ok(())
+}
+
+fn respond(payload: Value, hit: bool) -> Response {
+    (
+        [(CACHE_HEADER, if hit { "HIT" } else { "MISS" })],
+        Json(payload),
+    )
+        .into_response()
+}
+
+/// Single JSON-RPC call returning the raw `result`. A `null` result (node
+/// pruned or not yet synced) is reported as not found.
+async fn rpc_call(rpc_url: &str, method: &str, params: Value) -> Result<Value, AtlasError> {
+    rpc_call_with_timeout(rpc_url, method, params, RPC_TIMEOUT).await
+}
+
+/// [`rpc_call`] giving up after `timeout`.
+pub(crate) async fn rpc_call_with_timeout(
+    rpc_url: &str,
+    method: &str,
+    params: Value,
+    timeout: Duration,
+) -> Result<Value, AtlasError> {
+    let body = serde_json::json!({
+        "jsonrpc": "2.0",
+        "method": method,
+        "params": params,
+        "id": 1
+    });
+
+    let client = reqwest::Client::builder()
+        .timeout(timeout)
+        .build()
+        .map_err(|e| AtlasError::Internal(e.to_string()))?;
+
+    let resp: Value = client
+        .post(rpc_url)
+        .json(&body)
+        .send()
+        .await
+        .map_err(|e| AtlasError::Rpc(format!("{method} failed: {e}")))?
+        .json()
+        .await
+        .map_err(|e| AtlasError::Rpc(format!("failed to parse {method} response: {e}")))?;
+
+    rpc_result(method, resp)
+}
+
+fn rpc_result(method: &str, mut resp: Value) -> Result<Value, AtlasError> {
+    if let Some(error) = resp.get("error") {
+        return Err(AtlasError::Rpc(format!("{method} returned error: {error}")));
+    }
+    match resp.get_mut("result").map(Value::take) {
+        Some(Value::Null) => Err(AtlasError::NotFound(format!(
+            "{method} returned no result from the node"
+        ))),
+        Some(result) => Ok(result),
+        None => Err(AtlasError::Rpc(format!("{method} returned no result"))),
+    }
+}
+
+fn normalize_hash(hash: &str) -> String {
+    if hash.starts_with("0x") {
+        hash.to_lowercase()
+    } else {
+        format!("0x{}", hash.to_lowercase())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rpc_result_distinguishes_errors_and_missing_results() {
+        let ok = rpc_result(
+            "eth_getBlockByNumber",
+            serde_json::json!({"result": {"hash": "0x01", "extra": 1}}),
+        )
+        .unwrap();
+        assert_eq!(ok["extra"], 1);
+
+        let err = rpc_result(
+            "eth_getBlockByNumber",
+            serde_json::json!({"error": {"code": -32000, "message": "boom"}}),
+        )
+        .unwrap_err();
+        assert_eq!(err.status_code(), 502);
+
+        let missing =
+            rpc_result("eth_getBlockByNumber", serde_json::json!({"result": null})).unwrap_err();
+        assert_eq!(missing.status_code(), 404);
+    }
+}
@@ -0,0 +1,524 @@
+//! `eth_getLogs` served from the indexed `event_logs` table.
+//!
+//! Takes the same filter object as the JSON-RPC method and returns logs in
+//! the same shape, so indexer-style consumers can point at the explorer
+//! instead of the node. Unlike most nodes it refuses a block range or result
+//! set over [`LogLimits`] up front, with an error that says how to narrow it.
+//! Blocks above the indexed head are not served; `latest` means the indexed
+//! head.
+
+use alloy::primitives::{Address, B256};
+use axum::{extract::State, Json};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::api::error::ApiResult;
+use crate::api::handlers::status::latest_indexed_block;
+use crate::api::AppState;
+use crate::ordering::{ChainRow, Direction};
+use atlas_common::encoding::{address_to_hex, b256_to_hex, hex_to_bytes};
+use atlas_common::storage::{BlockRange, Segment, Tier, TieredTable};
+use atlas_common::AtlasError;
+
+/// Most addresses or topic alternatives accepted in one filter position.
+const MAX_FILTER_VALUES: usize = 100;
+
+/// Range and result caps for `/api/rpc/logs`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LogLimits {
+    /// Most blocks one request may span.
+    pub max_block_range: u64,
+    /// Most logs one request may return.
+    pub max_results: usize,
+}
+
+impl Default for LogLimits {
+    fn default() -> Self {
+        Self {
+            max_block_range: 10_000,
+            max_results: 10_000,
+        }
+    }
+}
+
+/// `eth_getLogs` filter object.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogFilterRequest {
+    pub from_block: Option<String>,
+    pub to_block: Option<String>,
+    pub block_hash: Option<String>,
+    pub address: Option<OneOrMany>,
+    /// Per position: `null` matches anything, a list matches any of its topics.
+    #[serde(default)]
+    pub topics: Vec<Option<OneOrMany>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum OneOrMany {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl OneOrMany {
+    fn into_vec(self) -> Vec<String> {
+        match self {
+            OneOrMany::One(value) => vec![value],
+            OneOrMany::Many(values) => values,
+        }
+    }
+}
+
+/// Log in `eth_getLogs` result format.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcLog {
+    pub address: String,
+    pub topics: Vec<String>,
+    pub data: String,
+    pub block_number: String,
+    /// `null` when the block row is missing
+    pub block_hash: Option<String>,
+    pub transaction_hash: String,
+    /// `null` when the transaction row is missing
+    pub transaction_index: Option<String>,
+    pub log_index: String,
+    pub removed: bool,
+}
+
+#[derive(sqlx::FromRow)]
+struct LogRow {
+    address: String,
+    topic0: String,
+    topic1: Option<String>,
+    topic2: Option<String>,
+    topic3: Option<String>,
+    data: Vec<u8>,
+    block_number: i64,
+    block_hash: Option<String>,
+    tx_hash: String,
+    tx_index: Option<i32>,
+    log_index: i32,
+}
+
+impl From<LogRow> for RpcLog {
+    fn from(row: LogRow) -> Self {
+        let topics = [Some(row.topic0), row.topic1, row.topic2, row.topic3]
+            .into_iter()
+            .flatten()
+            .collect();
+        Self {
+            address: row.address,
+            topics,
+            data: format!("0x{}", hex::encode(row.data)),
+            block_number: format!("{:#x}", row.block_number),
+            block_hash: row.block_hash,
+            transaction_hash: row.tx_hash,
+            transaction_index: row.tx_index.map(|i| format!("{:#x}", i)),
+            log_index: format!("{:#x}", row.log_index),
+            removed: false,
+        }
+    }
+}
+
+/// Validated filter: an inclusive block range and the values each column
+/// must be one of (`None` = any).
+#[derive(Debug, PartialEq)]
+struct LogQuery {
+    from_block: u64,
+    to_block: u64,
+    addresses: Option<Vec<String>>,
+    topics: [Option<Vec<String>>; 4],
+}
+
+/// POST /api/rpc/logs - `eth_getLogs` over indexed logs
+pub async fn get_logs(
+    State(state): State<Arc<AppState>>,
+    Json(filter): Json<LogFilterRequest>,
+) -> ApiResult<Json<Vec<RpcLog>>> {
+    let Some((head, _)) = latest_indexed_block(&state).await? else {
+        return Ok(Json(Vec::new()));
+    };
+    let head = head as u64;
+
+    let block_hash_number = match filter.block_hash.as_deref() {
+        Some(hash) => {
+            let hash = parse_hash(hash, "blockHash")?;
+            let (number,): (i64,) = sqlx::query_as("SELECT number FROM blocks WHERE hash = $1")
+                .bind(&hash)
+                .fetch_optional(&state.pool)
+                .await?
+                .ok_or_else(|| AtlasError::NotFound(format!("Block {} not found", hash)))?;
+            Some(number as u64)
+        }
+        None => None,
+    };
+
+    let query = build_query(filter, head, block_hash_number, &state.log_limits)?;
+    if query.from_block > head {
+        return Ok(Json(Vec::new()));
+    }
+
+    // Segments come back oldest first, so appending keeps block order.
+    let limit = state.log_limits.max_results + 1;
+    let mut rows: Vec<LogRow> = Vec::new();
+    let blocks = BlockRange::new(query.from_block as i64, query.to_block as i64);
+    for segment in state.storage.segments(TieredTable::EventLogs, blocks) {
+        if rows.len() >= limit {
+            break;
+        }
+        let mut segment_rows = fetch_segment_logs(&segment, &query, limit - rows.len()).await?;
+        if segment.tier == Tier::Cold {
+            fill_block_and_tx_fields(&state.pool, &mut segment_rows).await?;
+        }
+        rows.append(&mut segment_rows);
+    }
+
+    if rows.len() > state.log_limits.max_results {
+        return Err(AtlasError::LimitExceeded(format!(
+            "Query returns more than {} logs; narrow the block range or filter by address or topics",
+            state.log_limits.max_results
+        ))
+        .into());
+    }
+    Ok(Json(rows.into_iter().map(RpcLog::from).collect()))
+}
+
+/// Up to `limit` logs of `segment` matching `query`. Block hashes and
+/// transaction indexes are joined in only on the hot tier, which shares a
+/// database with `blocks` and `transactions`.
+async fn fetch_segment_logs(
+    segment: &Segment,
+    query: &LogQuery,
+    limit: usize,
+) -> Result<Vec<LogRow>, AtlasError> {
+    let (columns, joins) = match segment.tier {
+        Tier::Hot => (
+            "b.hash AS block_hash, t.block_index AS tx_index",
+            "LEFT JOIN blocks b ON b.number = l.block_number
+             LEFT JOIN transactions t
+               ON t.hash = '0x' || encode(l.tx_hash, 'hex') AND t.block_number = l.block_number",
+        ),
+        Tier::Cold => ("NULL::text AS block_hash, NULL::int AS tx_index", ""),
+    };
+    let sql = format!(
+        "SELECT '0x' || encode(l.address, 'hex') AS address,
+                '0x' || encode(l.topic0, 'hex') AS topic0,
+                '0x' || encode(l.topic1, 'hex') AS topic1,
+                '0x' || encode(l.topic2, 'hex') AS topic2,
+                '0x' || encode(l.topic3, 'hex') AS topic3,
+                l.data, l.block_number, '0x' || encode(l.tx_hash, 'hex') AS tx_hash,
+                {columns}, l.log_index
+         FROM {table} l
+         {joins}
+         WHERE l.block_number BETWEEN $1 AND $2
+           AND ($3::bytea[] IS NULL OR l.address = ANY($3))
+           AND ($4::bytea[] IS NULL OR l.topic0 = ANY($4))
+           AND ($5::bytea[] IS NULL OR l.topic1 = ANY($5))
+           AND ($6::bytea[] IS NULL OR l.topic2 = ANY($6))
+           AND ($7::bytea[] IS NULL OR l.topic3 = ANY($7))
+         ORDER BY {order}
+         LIMIT $8",
+        table = segment.table,
+        order = ChainRow::Log.order_by("l", Direction::Asc),
+    );
+    let rows = sqlx::query_as(&sql)
+        .bind(segment.blocks.from)
+        .bind(segment.blocks.to)
+        .bind(as_bytes(&query.addresses)?)
+        .bind(as_bytes(&query.topics[0])?)
+        .bind(as_bytes(&query.topics[1])?)
+        .bind(as_bytes(&query.topics[2])?)
+        .bind(as_bytes(&query.topics[3])?)
+        .bind(limit as i64)
+        .fetch_all(&segment.pool)
+        .await?;
+    Ok(rows)
+}
+
+/// Filter values as bound to the `BYTEA` columns of `event_logs`.
+fn as_bytes(values: &Option<Vec<String>>) -> Result<Option<Vec<Vec<u8>>>, AtlasError> {
+    values
+        .as_ref()
+        .map(|values| values.iter().map(|value| hex_to_bytes(value)).collect())
+        .transpose()
+}
+
+/// Look up the block hash and transaction index of cold-tier `rows` in the
+/// main database.
+async fn fill_block_and_tx_fields(pool: &PgPool, rows: &mut [LogRow]) -> Result<(), AtlasError> {
+    if rows.is_empty() {
+        return Ok(());
+    }
+    let block_numbers: Vec<i64> = rows.iter().map(|row| row.block_number).collect();
+    let tx_hashes: Vec<&str> = rows.iter().map(|row| row.tx_hash.as_str()).collect();
+
+    let block_hashes: HashMap<i64, String> =
+        sqlx::query_as("SELECT number, hash FROM blocks WHERE number = ANY($1)")
+            .bind(&block_numbers)
+            .fetch_all(pool)
+            .await?
+            .into_iter()
+            .collect();
+    let tx_indexes: HashMap<(String, i64), i32> = sqlx::query_as::<_, (String, i64, i32)>(
+        "SELECT hash, block_number, block_index FROM transactions
+         WHERE hash = ANY($1) AND block_number = ANY($2)",
+    )
+    .bind(&tx_hashes)
+    .bind(&block_numbers)
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .map(|(hash, block_number, index)| ((hash, block_number), index))
+    .collect();
+
+    for row in rows {
+        row.block_hash = block_hashes.get(&row.block_number).cloned();
+        row.tx_index = tx_indexes
+            .get(&(row.tx_hash.clone(), row.block_number))
+            .copied();
+    }
+    Ok(())
+}
+
+/// Validate `filter` against the indexed `head` and `limits`. A range that
+/// ends above the head is cut at the head.
+fn build_query(
+    filter: LogFilterRequest,
+    head: u64,
+    block_hash_number: Option<u64>,
+    limits: &LogLimits,
+) -> Result<LogQuery, AtlasError> {
+    let (from_block, to_block) = match block_hash_number {
+        Some(number) => {
+            if filter.from_block.is_some() || filter.to_block.is_some() {
+                return Err(AtlasError::InvalidInput(
+                    "blockHash cannot be combined with fromBlock or toBlock".to_string(),
+                ));
+            }
+            (number, number)
+        }
+        None => {
+            let from = parse_block_tag(filter.from_block.as_deref(), head, "fromBlock")?;
+            let to = parse_block_tag(filter.to_block.as_deref(), head, "toBlock")?;
+            if from > to {
+                return Err(AtlasError::InvalidInput(format!(
+                    "fromBlock ({from}) is after toBlock ({to})"
+                )));
+            }
+            (from, to.min(head))
+        }
+    };
+
+    let span = to_block.saturating_sub(from_block) + 1;
+    if from_block <= to_block && span > limits.max_block_range {
+        return Err(AtlasError::LimitExceeded(format!(
+            "Block range spans {span} blocks, over the limit of {}; split it into smaller ranges",
+            limits.max_block_range
+        )));
+    }
+
+    let addresses = filter
+        .address
+        .map(|address| {
+            let values = address.into_vec();
+            check_value_count(values.len(), "address")?;
+            values
+                .iter()
+                .map(|value| {
+                    value
+                        .parse::<Address>()
+                        .map(|a| address_to_hex(&a))
+                        .map_err(|_| AtlasError::InvalidInput(format!("Invalid address {value}")))
+                })
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .transpose()?;
+
+    if filter.topics.len() > 4 {
+        return Err(AtlasError::InvalidInput(format!(
+            "topics has {} positions; logs have at most 4",
+            filter.topics.len()
+        )));
+    }
+    let mut topics: [Option<Vec<String>>; 4] = Default::default();
+    for (position, topic) in filter.topics.into_iter().enumerate() {
+        let Some(topic) = topic else { continue };
+        let values = topic.into_vec();
+        // An empty list at a position matches anything, like `null`.
+        if values.is_empty() {
+            continue;
+        }
+        check_value_count(values.len(), "topics")?;
+        topics[position] = Some(
+            values
+                .iter()
+                .map(|value| parse_hash(value, &format!("topics[{position}]")))
+                .collect::<Result<_, _>>()?,
+        );
+    }
+
+    Ok(LogQuery {
+        from_block,
+        to_block,
+        addresses,
+        topics,
+    })
+}
+
+/// Block number of a tag or hex/decimal quantity; tags past `earliest` all
+/// mean the indexed head.
+fn parse_block_tag(value: Option<&str>, head: u64, what: &str) -> Result<u64, AtlasError> {
+    match value {
+        None | Some("latest" | "pending" | "safe" | "finalized") => Ok(head),
+        Some("earliest") => Ok(0),
+        Some(value) => {
+            let parsed = match value.strip_prefix("0x") {
+                Some(hex) => u64::from_str_radix(hex, 16),
+                None => value.parse(),
+            };
+            parsed.map_err(|_| {
+                AtlasError::InvalidInput(format!(
+                    "Invalid {what} {value}; expected a hex block number or latest, earliest, pending, safe or finalized"
+                ))
+            })
+        }
+    }
+}
+
+fn parse_hash(value: &str, what: &str) -> Result<String, AtlasError> {
+    value
+        .parse::<B256>()
+        .map(|hash| b256_to_hex(&hash))
+        .map_err(|_| {
+            AtlasError::InvalidInput(format!("Invalid {what} {value}; expected 32 bytes of hex"))
+        })
+}
+
+fn check_value_count(count: usize, what: &str) -> Result<(), AtlasError> {
+    if count > MAX_FILTER_VALUES {
+        return Err(AtlasError::LimitExceeded(format!(
+            "{what} lists {count} values, over the limit of {MAX_FILTER_VALUES}"
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOPIC: &str = "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef";
+
+    fn filter(json: serde_json::Value) -> LogFilterRequest {
+        serde_json::from_value(json).unwrap()
+    }
+
+    fn build(json: serde_json::Value) -> Result<LogQuery, AtlasError> {
+        build_query(filter(json), 1_000, None, &LogLimits::default())
+    }
+
+    #[test]
+    fn defaults_to_the_indexed_head() {
+        let query = build(serde_json::json!({})).unwrap();
+        assert_eq!((query.from_block, query.to_block), (1_000, 1_000));
+        assert_eq!(query.addresses, None);
+        assert_eq!(query.topics, [None, None, None, None]);
+    }
+
+    #[test]
+    fn parses_tags_quantities_and_caps_at_the_head() {
+        let query =
+            build(serde_json::json!({ "fromBlock": "earliest", "toBlock": "0x3e8" })).unwrap();
+        assert_eq!((query.from_block, query.to_block), (0, 1_000));
+
+        let query = build(serde_json::json!({ "fromBlock": "990", "toBlock": "0xffff" })).unwrap();
+        assert_eq!((query.from_block, query.to_block), (990, 1_000));
+
+        let err = build(serde_json::json!({ "fromBlock": "recent" })).unwrap_err();
+        assert!(err.to_string().contains("Invalid fromBlock recent"));
+    }
+
+    #[test]
+    fn enforces_the_block_range() {
+        let limits = LogLimits {
+            max_block_range: 100,
+            max_results: 10,
+        };
+        let ok = build_query(
+            filter(serde_json::json!({ "fromBlock": "0x1", "toBlock": "0x64" })),
+            1_000,
+            None,
+            &limits,
+        );
+        assert!(ok.is_ok());
+        let err = build_query(
+            filter(serde_json::json!({ "fromBlock": "0x1", "toBlock": "0x65" })),
+            1_000,
+            None,
+            &limits,
+        )
+        .unwrap_err();
+        assert_eq!(err.status_code(), 422);
+        assert!(err
+            .to_string()
+            .contains("spans 101 blocks, over the limit of 100"));
+
+        let err = build(serde_json::json!({ "fromBlock": "0x10", "toBlock": "0x5" })).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("fromBlock (16) is after toBlock (5)"));
+    }
+
+    #[test]
+    fn block_hash_excludes_a_range() {
+        let query = build_query(
+            filter(serde_json::json!({})),
+            1_000,
+            Some(42),
+            &LogLimits::default(),
+        )
+        .unwrap();
+        assert_eq!((query.from_block, query.to_block), (42, 42));
+
+        let err = build_query(
+            filter(serde_json::json!({ "fromBlock": "0x1" })),
+            1_000,
+            Some(42),
+            &LogLimits::default(),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("cannot be combined"));
+    }
+
+    #[test]
+    fn normalizes_addresses_and_topic_positions() {
+        let query = build(serde_json::json!({
+            "address": "0xABCDEFabcdefABCDEFabcdefABCDEFabcdefABCD",
+            "topics": [TOPIC.to_uppercase().replace("0X", "0x"), null, [TOPIC, TOPIC], []],
+        }))
+        .unwrap();
+        assert_eq!(
+            query.addresses,
+            Some(vec![
+                "0xabcdefabcdefabcdefabcdefabcdefabcdefabcd".to_string()
+            ])
+        );
+        assert_eq!(query.topics[0], Some(vec![TOPIC.to_string()]));
+        assert_eq!(query.topics[1], None);
+        assert_eq!(query.topics[2].as_ref().unwrap().len(), 2);
+        assert_eq!(query.topics[3], None);
+
+        assert!(build(serde_json::json!({ "address": ["0x1234"] })).is_err());
+        assert!(build(serde_json::json!({ "topics": ["0x1234"] })).is_err());
+        assert!(
+            build(serde_json::json!({ "topics": [null, null, null, null, null] }))
+                .unwrap_err()
+                .to_string()
+                .contains("at most 4")
+        );
+    }
+}
@@ -0,0 +1,229 @@
+//! Safe multisigs decoded by the `safes` block processor: owners, threshold
+//! and executed transactions.
+//!
+//! Both endpoints return 404 unless `BLOCK_PROCESSORS` includes `safes` and
+//! the address has emitted a Safe event. Owners and threshold are the owner
+//! events replayed in order, so a Safe set up before the indexed range only
+//! shows the changes made since.
+
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use serde::Serialize;
+use std::sync::Arc;
+
+use crate::api::error::ApiResult;
+use crate::api::AppState;
+use crate::ordering::{ChainRow, Direction};
+use atlas_common::encoding::{address_to_hex, parse_address};
+use atlas_common::{AtlasError, PaginatedResponse, Pagination};
+
+/// Executions listed in the Safe detail; the rest are paged.
+const RECENT_EXECUTIONS: i64 = 10;
+
+#[derive(Serialize)]
+pub struct SafeDetail {
+    pub address: String,
+    /// Block and transaction of the `SafeSetup` event; `null` when the Safe
+    /// was set up before the indexed range
+    pub setup_block: Option<i64>,
+    pub setup_tx_hash: Option<String>,
+    /// Current owners, in the order they were added
+    pub owners: Vec<String>,
+    /// Signatures required; `null` until a setup or threshold change is seen
+    pub threshold: Option<i64>,
+    pub executions: i64,
+    pub failed_executions: i64,
+    /// Newest executions first
+    pub recent_executions: Vec<SafeExecution>,
+}
+
+/// A Safe transaction run through `execTransaction`.
+#[derive(Serialize, sqlx::FromRow)]
+pub struct SafeExecution {
+    pub block_number: i64,
+    pub log_index: i32,
+    /// Hash of the chain transaction that executed it
+    pub tx_hash: String,
+    /// Hash the owners signed
+    pub safe_tx_hash: String,
+    pub success: bool,
+    /// Gas refund paid by the Safe, in wei or the gas token's units
+    pub payment: String,
+    pub timestamp: i64,
+}
+
+#[derive(sqlx::FromRow)]
+struct SafeEventRow {
+    block_number: i64,
+    tx_hash: String,
+    kind: String,
+    owners: Vec<String>,
+    threshold: Option<i64>,
+}
+
+/// Owners and threshold after `events`, given in log order.
+fn replay(events: &[SafeEventRow]) -> (Vec<String>, Option<i64>) {
+    let mut owners: Vec<String> = Vec::new();
+    let mut threshold = None;
+    for event in events {
+        match event.kind.as_str() {
+            "setup" => {
+                owners = event.owners.clone();
+                threshold = event.threshold;
+            }
+            "added_owner" => {
+                for owner in &event.owners {
+                    if !owners.contains(owner) {
+                        owners.push(owner.clone());
+                    }
+                }
+            }
+            "removed_owner" => owners.retain(|owner| !event.owners.contains(owner)),
+            "changed_threshold" => threshold = event.threshold,
+            _ => {}
+        }
+    }
+    (owners, threshold)
+}
+
+/// GET /api/safes/{address} - Owners, threshold and recent executions
+pub async fn get_safe(
+    State(state): State<Arc<AppState>>,
+    Path(address): Path<String>,
+) -> ApiResult<Json<SafeDetail>> {
+    let address = address_to_hex(&parse_address(&address)?);
+
+    let events: Vec<SafeEventRow> = sqlx::query_as(&format!(
+        "SELECT block_number, tx_hash, kind, owners::text[] AS owners, threshold
+         FROM safe_events
+         WHERE safe = $1
+         ORDER BY {}",
+        ChainRow::Log.order_by("", Direction::Asc)
+    ))
+    .bind(&address)
+    .fetch_all(&state.pool)
+    .await?;
+
+    let (executions, failed_executions): (i64, i64) = sqlx::query_as(
+        "SELECT COUNT(*), COUNT(*) FILTER (WHERE NOT success)
+         FROM safe_executions WHERE safe = $1",
+    )
+    .bind(&address)
+    .fetch_one(&state.pool)
+    .await?;
+
+    if events.is_empty() && executions == 0 {
+        return Err(AtlasError::NotFound(format!("Safe {address} not found")).into());
+    }
+
+    let recent_executions = fetch_executions(&state, &address, RECENT_EXECUTIONS, 0).await?;
+    let setup = events.iter().rev().find(|event| event.kind == "setup");
+    let (owners, threshold) = replay(&events);
+
+    Ok(Json(SafeDetail {
+        setup_block: setup.map(|event| event.block_number),
+        setup_tx_hash: setup.map(|event| event.tx_hash.clone()),
+        address,
+        owners,
+        threshold,
+        executions,
+        failed_executions,
+        recent_executions,
+    }))
+}
+
+/// GET /api/safes/{address}/executions - Executed Safe transactions, newest first
+pub async fn list_executions(
+    State(state): State<Arc<AppState>>,
+    Path(address): Path<String>,
+    Query(pagination): Query<Pagination>,
+) -> ApiResult<Json<PaginatedResponse<SafeExecution>>> {
+    let address = address_to_hex(&parse_address(&address)?);
+
+    let is_safe: bool = sqlx::query_scalar(
+        "SELECT EXISTS (SELECT 1 FROM safe_events WHERE safe = $1)
+             OR EXISTS (SELECT 1 FROM safe_executions WHERE safe = $1)",
+    )
+    .bind(&address)
+    .fetch_one(&state.pool)
+    .await?;
+    if !is_safe {
+        return Err(AtlasError::NotFound(format!("Safe {address} not found")).into());
+    }
+
+    let executions = fetch_executions(
+        &state,
+        &address,
+        pagination.limit() + 1,
+        pagination.offset(),
+    )
+    .await?;
+
+    Ok(Json(PaginatedResponse::from_probe(
+        executions,
+        pagination.page,
+        pagination.limit() as u32,
+        pagination.offset(),
+        None,
+        false,
+    )))
+}
+
+async fn fetch_executions(
+    state: &AppState,
+    safe: &str,
+    limit: i64,
+    offset: i64,
+) -> ApiResult<Vec<SafeExecution>> {
+    Ok(sqlx::query_as(&format!(
+        "SELECT block_number, log_index, tx_hash, safe_tx_hash, success,
+                payment::text AS payment, timestamp
+         FROM safe_executions
+         WHERE safe = $1
+         ORDER BY {}
+         LIMIT $2 OFFSET $3",
+        ChainRow::Log.order_by("", Direction::Desc)
+    ))
+    .bind(safe)
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(&state.pool)
+    .await?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(kind: &str, owners: &[&str], threshold: Option<i64>) -> SafeEventRow {
+        SafeEventRow {
+            block_number: 1,
+            tx_hash: String::new(),
+            kind: kind.to_string(),
+            owners: owners.iter().map(|o| o.to_string()).collect(),
+            threshold,
+        }
+    }
+
+    #[test]
+    fn replay_applies_owner_changes_in_order() {
+        let events = [
+            event("setup", &["0xa", "0xb"], Some(1)),
+            event("added_owner", &["0xc"], None),
+            event("removed_owner", &["0xa"], None),
+            event("changed_threshold", &[], Some(2)),
+        ];
+        assert_eq!(
+            replay(&events),
+            (vec!["0xb".to_string(), "0xc".to_string()], Some(2))
+        );
+
+        // Without a setup only the changes are known
+        assert_eq!(
+            replay(&[event("added_owner", &["0xc"], None)]),
+            (vec!["0xc".to_string()], None)
+        );
+    }
+}
@@ -1,3 +1,10 @@
+//! Omnibox search across blocks, transactions, addresses, tokens, NFTs and
+//! labels.
+//!
+//! Exact lookups (block number or hash, tx hash, address) return at most one
+//! hit each; text searches over names are paginated per result type. With
+//! `suggest=true` the dropdown gets a few cheap hits per type instead.
+
 use axum::{
     extract::{Query, State},
     Json,
@@ -6,12 +13,92 @@ use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
 use crate::api::error::ApiResult;
+use crate::api::handlers::nfts::{Collection, COLLECTION_COLUMNS, COLLECTION_LOGO_JOIN};
+use crate::api::handlers::tokens::{
+    RankedToken, RANKED_TOKEN_COLUMNS, TOKEN_STANDING_JOINS, TOKEN_STANDING_ORDER,
+};
 use crate::api::AppState;
-use atlas_common::{Address, Block, Erc20Contract, NftContract, Transaction, BLOCK_COLUMNS};
+use atlas_common::{Address, AtlasError, Block, Transaction, BLOCK_COLUMNS};
+
+/// Per-type page size ceiling for text searches.
+const MAX_LIMIT: u32 = 50;
+
+/// Hits per type in suggest mode.
+const SUGGEST_LIMIT: u32 = 3;
 
 #[derive(Deserialize)]
 pub struct SearchQuery {
     pub q: String,
+    /// Comma-separated result types to search; all types when omitted.
+    #[serde(rename = "type", default)]
+    pub types: Option<String>,
+    #[serde(default = "default_page")]
+    pub page: u32,
+    /// Page size for each text-searched type; per-type defaults when omitted.
+    #[serde(default)]
+    pub limit: Option<u32>,
+    /// Lightweight mode for search-as-you-type.
+    #[serde(default)]
+    pub suggest: bool,
+}
+
+fn default_page() -> u32 {
+    1
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SearchType {
+    Block,
+    Tx,
+    Address,
+    Token,
+    Nft,
+    Label,
+}
+
+impl SearchType {
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "block" => Some(SearchType::Block),
+            "tx" | "transaction" => Some(SearchType::Tx),
+            "address" => Some(SearchType::Address),
+            "token" => Some(SearchType::Token),
+            "nft" => Some(SearchType::Nft),
+            "label" => Some(SearchType::Label),
+            _ => None,
+        }
+    }
+
+    /// Page size of a text-searched type when `limit` is not given.
+    fn default_limit(self) -> u32 {
+        match self {
+            SearchType::Nft => 5,
+            _ => 10,
+        }
+    }
+}
+
+/// Parse the `type` parameter; `None` means every type.
+fn parse_types(types: Option<&str>) -> Result<Option<Vec<SearchType>>, AtlasError> {
+    let Some(types) = types.map(str::trim).filter(|types| !types.is_empty()) else {
+        return Ok(None);
+    };
+    let mut parsed = Vec::new();
+    for value in types
+        .split(',')
+        .map(|value| value.trim().to_ascii_lowercase())
+    {
+        let search_type = SearchType::parse(&value).ok_or_else(|| {
+            AtlasError::InvalidInput(format!(
+                "Unknown search type '{value}', expected one of block, tx, address, token, nft, label"
+            ))
+        })?;
+        if !parsed.contains(&search_type) {
+            parsed.push(search_type);
+        }
+    }
+    Ok(Some(parsed))
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
@@ -22,6 +109,13 @@ pub struct NftTokenResult {
     pub image_url: Option<String>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct LabelResult {
+    pub address: String,
+    pub name: String,
+    pub tags: Vec<String>,
+}
+
 #[derive(Serialize)]
 #[serde(tag = "type")]
 pub enum SearchResult {
@@ -32,17 +126,70 @@ pub enum SearchResult {
     #[serde(rename = "address")]
     Address(Address),
     #[serde(rename = "nft_collection")]
-    NftCollection(NftContract),
+    NftCollection(Collection),
     #[serde(rename = "nft")]
     Nft(NftTokenResult),
     #[serde(rename = "erc20_token")]
-    Erc20Token(Erc20Contract),
+    Erc20Token(RankedToken),
+    #[serde(rename = "label")]
+    Label(LabelResult),
+}
+
+/// Paging state of one text-searched type.
+#[derive(Debug, Serialize)]
+pub struct TypePage {
+    #[serde(rename = "type")]
+    pub search_type: SearchType,
+    pub page: u32,
+    pub limit: u32,
+    pub has_more: bool,
 }
 
 #[derive(Serialize)]
 pub struct SearchResponse {
     pub results: Vec<SearchResult>,
     pub query: String,
+    /// One entry per text-searched type; empty when no text search ran and
+    /// in suggest mode.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub pages: Vec<TypePage>,
+}
+
+/// Page window for a text search; fetches one extra row to detect `has_more`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Window {
+    limit: u32,
+    offset: i64,
+}
+
+impl Window {
+    fn new(search_type: SearchType, params: &SearchQuery) -> Self {
+        if params.suggest {
+            return Window {
+                limit: SUGGEST_LIMIT,
+                offset: 0,
+            };
+        }
+        let limit = params
+            .limit
+            .unwrap_or(search_type.default_limit())
+            .clamp(1, MAX_LIMIT);
+        Window {
+            limit,
+            offset: i64::from(params.page.max(1) - 1) * i64::from(limit),
+        }
+    }
+
+    fn fetch_limit(self) -> i64 {
+        i64::from(self.limit) + 1
+    }
+
+    /// Drop the probe row; returns whether there was one.
+    fn trim<T>(self, rows: &mut Vec<T>) -> bool {
+        let has_more = rows.len() > self.limit as usize;
+        rows.truncate(self.limit as usize);
+        has_more
+    }
 }
 
 pub async fn search(
@@ -50,20 +197,29 @@ pub async fn search(
     Query(params): Query<SearchQuery>,
 ) -> ApiResult<Json<SearchResponse>> {
     let query = params.q.trim();
+    let filter = parse_types(params.types.as_deref())?;
+    let wanted = |search_type| {
+        filter
+            .as_ref()
+            .is_none_or(|types| types.contains(&search_type))
+    };
     let mut results = Vec::new();
+    let mut pages = Vec::new();
 
     if query.is_empty() {
         return Ok(Json(SearchResponse {
             results,
             query: query.to_string(),
+            pages,
         }));
     }
 
-    // Detect query type and run appropriate searches in parallel
+    // Exact matches have a single hit, so they only appear on the first page.
+    let first_page = params.suggest || params.page <= 1;
     let is_hex = query.starts_with("0x") || query.chars().all(|c| c.is_ascii_hexdigit());
     let block_num = query.parse::<i64>().ok();
 
-    if is_hex {
+    if first_page && is_hex {
         let hex_query = if query.starts_with("0x") {
             query.to_lowercase()
         } else {
@@ -72,7 +228,7 @@ pub async fn search(
 
         match hex_query.len() {
             // 42 chars = address (0x + 40 hex)
-            42 => {
+            42 if wanted(SearchType::Address) => {
                 if let Some(addr) = search_address(&state, &hex_query).await? {
                     results.push(SearchResult::Address(addr));
                 }
@@ -81,8 +237,20 @@ pub async fn search(
             66 => {
                 // Run tx and block search in parallel
                 let (tx_result, block_result) = tokio::join!(
-                    search_transaction(&state, &hex_query),
-                    search_block_by_hash(&state, &hex_query)
+                    async {
+                        if wanted(SearchType::Tx) {
+                            search_transaction(&state, &hex_query).await
+                        } else {
+                            Ok(None)
+                        }
+                    },
+                    async {
+                        if wanted(SearchType::Block) {
+                            search_block_by_hash(&state, &hex_query).await
+                        } else {
+                            Ok(None)
+                        }
+                    }
                 );
 
                 if let Some(tx) = tx_result? {
@@ -94,44 +262,94 @@ pub async fn search(
             }
             _ => {}
         }
-    } else if let Some(num) = block_num {
+    } else if let Some(num) = block_num.filter(|_| first_page && wanted(SearchType::Block)) {
         // Block number search
         if let Some(block) = search_block_by_number(&state, num).await? {
             results.push(SearchResult::Block(block));
         }
     }
 
-    // Text search for tokens/collections if no hex/number results and query is long
-    // enough to benefit from the pg_trgm GIN indexes (trigrams require >= 3 chars).
-    if results.is_empty() && query.len() >= 3 {
-        // Run NFT collection, NFT token, and ERC-20 searches in parallel
-        let (nft_collection_results, nft_token_results, erc20_results) = tokio::join!(
-            search_nft_collections(&state, query),
-            search_nft_tokens(&state, query),
-            search_erc20_tokens(&state, query)
+    // Text search for names when the caller asked for those types or nothing
+    // matched exactly, and the query is long enough to benefit from the
+    // pg_trgm GIN indexes (trigrams require >= 3 chars).
+    if (filter.is_some() || results.is_empty()) && query.len() >= 3 {
+        let token_window = Window::new(SearchType::Token, &params);
+        let nft_window = Window::new(SearchType::Nft, &params);
+        let label_window = Window::new(SearchType::Label, &params);
+        // Token names are the most expensive scan; suggestions skip them.
+        let search_nft_names = wanted(SearchType::Nft) && !params.suggest;
+
+        let (nft_collection_results, nft_token_results, erc20_results, label_results) = tokio::join!(
+            async {
+                if wanted(SearchType::Nft) {
+                    search_nft_collections(&state, query, nft_window).await
+                } else {
+                    Ok(Vec::new())
+                }
+            },
+            async {
+                if search_nft_names {
+                    search_nft_tokens(&state, query, nft_window).await
+                } else {
+                    Ok(Vec::new())
+                }
+            },
+            async {
+                if wanted(SearchType::Token) {
+                    search_erc20_tokens(&state, query, token_window).await
+                } else {
+                    Ok(Vec::new())
+                }
+            },
+            async {
+                if wanted(SearchType::Label) {
+                    search_labels(&state, query, label_window).await
+                } else {
+                    Ok(Vec::new())
+                }
+            }
+        );
+        let (mut nft_collections, mut nft_tokens, mut erc20_tokens, mut labels) = (
+            nft_collection_results?,
+            nft_token_results?,
+            erc20_results?,
+            label_results?,
         );
 
-        for nft in nft_collection_results? {
-            results.push(SearchResult::NftCollection(nft));
-        }
-        for token in nft_token_results? {
-            results.push(SearchResult::Nft(token));
-        }
-        for token in erc20_results? {
-            results.push(SearchResult::Erc20Token(token));
+        let nft_more = nft_window.trim(&mut nft_collections) | nft_window.trim(&mut nft_tokens);
+        let token_more = token_window.trim(&mut erc20_tokens);
+        let label_more = label_window.trim(&mut labels);
+        if !params.suggest {
+            for (search_type, window, has_more) in [
+                (SearchType::Token, token_window, token_more),
+                (SearchType::Nft, nft_window, nft_more),
+                (SearchType::Label, label_window, label_more),
+            ] {
+                if wanted(search_type) {
+                    pages.push(TypePage {
+                        search_type,
+                        page: params.page.max(1),
+                        limit: window.limit,
+                        has_more,
+                    });
+                }
+            }
         }
+
+        results.extend(nft_collections.into_iter().map(SearchResult::NftCollection));
+        results.extend(nft_tokens.into_iter().map(SearchResult::Nft));
+        results.extend(erc20_tokens.into_iter().map(SearchResult::Erc20Token));
+        results.extend(labels.into_iter().map(SearchResult::Label));
     }
 
     Ok(Json(SearchResponse {
         results,
         query: query.to_string(),
+        pages,
     }))
 }
 
-async fn search_address(
-    state: &AppState,
-    address: &str,
-) -> Result<Option<Address>, atlas_common::AtlasError> {
+async fn search_address(state: &AppState, address: &str) -> Result<Option<Address>, AtlasError> {
     // Address is already lowercased by caller
     sqlx::query_as(
         "SELECT address, is_contract, first_seen_block, tx_count
@@ -147,13 +365,15 @@ async fn search_address(
 async fn search_transaction(
     state: &AppState,
     hash: &str,
-) -> Result<Option<Transaction>, atlas_common::AtlasError> {
+) -> Result<Option<Transaction>, AtlasError> {
     // Use tx_hash_lookup table for O(1) lookup, then fetch full tx with partition key
     sqlx::query_as(
         "SELECT t.hash, t.block_number, t.block_index, t.from_address, t.to_address, t.value, t.gas_price, t.gas_used, t.input_data, t.status, t.contract_created, t.timestamp
          FROM tx_hash_lookup l
          JOIN transactions t ON t.hash = l.hash AND t.block_number = l.block_number
-         WHERE l.hash = $1"
+         WHERE l.hash = $1 AND l.canonical
+         ORDER BY l.block_number DESC
+         LIMIT 1"
     )
     .bind(hash)
     .fetch_optional(&state.pool)
@@ -161,10 +381,7 @@ async fn search_transaction(
     .map_err(Into::into)
 }
 
-async fn search_block_by_hash(
-    state: &AppState,
-    hash: &str,
-) -> Result<Option<Block>, atlas_common::AtlasError> {
+async fn search_block_by_hash(state: &AppState, hash: &str) -> Result<Option<Block>, AtlasError> {
     // Hash is already lowercased by caller
     sqlx::query_as(&format!(
         "SELECT {} FROM blocks WHERE hash = $1",
@@ -179,7 +396,7 @@ async fn search_block_by_hash(
 async fn search_block_by_number(
     state: &AppState,
     number: i64,
-) -> Result<Option<Block>, atlas_common::AtlasError> {
+) -> Result<Option<Block>, AtlasError> {
     sqlx::query_as(&format!(
         "SELECT {} FROM blocks WHERE number = $1",
         BLOCK_COLUMNS
@@ -200,16 +417,20 @@ fn like_escape(input: &str) -> String {
 async fn search_nft_collections(
     state: &AppState,
     query: &str,
-) -> Result<Vec<NftContract>, atlas_common::AtlasError> {
+    window: Window,
+) -> Result<Vec<Collection>, AtlasError> {
     let pattern = format!("%{}%", like_escape(query));
-    sqlx::query_as(
-        "SELECT address, name, symbol, total_supply, first_seen_block
-         FROM nft_contracts
-         WHERE name ILIKE $1 OR symbol ILIKE $1
-         ORDER BY total_supply DESC NULLS LAST
-         LIMIT 10",
-    )
+    sqlx::query_as(&format!(
+        "SELECT {COLLECTION_COLUMNS}
+         FROM nft_contracts c
+         {COLLECTION_LOGO_JOIN}
+         WHERE c.name ILIKE $1 OR c.symbol ILIKE $1
+         ORDER BY c.total_supply DESC NULLS LAST, c.address
+         LIMIT $2 OFFSET $3"
+    ))
     .bind(&pattern)
+    .bind(window.fetch_limit())
+    .bind(window.offset)
     .fetch_all(&state.pool)
     .await
     .map_err(Into::into)
@@ -218,16 +439,19 @@ async fn search_nft_collections(
 async fn search_nft_tokens(
     state: &AppState,
     query: &str,
-) -> Result<Vec<NftTokenResult>, atlas_common::AtlasError> {
+    window: Window,
+) -> Result<Vec<NftTokenResult>, AtlasError> {
     let pattern = format!("%{}%", like_escape(query));
     sqlx::query_as(
         "SELECT contract_address, token_id::text AS token_id, name, image_url
          FROM nft_tokens
          WHERE name ILIKE $1
-         ORDER BY last_transfer_block DESC NULLS LAST
-         LIMIT 5",
+         ORDER BY last_transfer_block DESC NULLS LAST, contract_address, token_id
+         LIMIT $2 OFFSET $3",
     )
     .bind(&pattern)
+    .bind(window.fetch_limit())
+    .bind(window.offset)
     .fetch_all(&state.pool)
     .await
     .map_err(Into::into)
@@ -236,17 +460,111 @@ async fn search_nft_tokens(
 async fn search_erc20_tokens(
     state: &AppState,
     query: &str,
-) -> Result<Vec<Erc20Contract>, atlas_common::AtlasError> {
+    window: Window,
+) -> Result<Vec<RankedToken>, AtlasError> {
+    let pattern = format!("%{}%", like_escape(query));
+    // Legitimate tokens first, then exact symbol matches, so the real USDC
+    // leads a page of look-alikes.
+    sqlx::query_as(&format!(
+        "SELECT {RANKED_TOKEN_COLUMNS}
+         FROM erc20_contracts c
+         {TOKEN_STANDING_JOINS}
+         WHERE c.name ILIKE $1 OR c.symbol ILIKE $1
+         ORDER BY {TOKEN_STANDING_ORDER}, lower(c.symbol) = lower($4) DESC,
+                  c.first_seen_block DESC, c.address
+         LIMIT $2 OFFSET $3"
+    ))
+    .bind(&pattern)
+    .bind(window.fetch_limit())
+    .bind(window.offset)
+    .bind(query)
+    .fetch_all(&state.pool)
+    .await
+    .map_err(Into::into)
+}
+
+async fn search_labels(
+    state: &AppState,
+    query: &str,
+    window: Window,
+) -> Result<Vec<LabelResult>, AtlasError> {
     let pattern = format!("%{}%", like_escape(query));
     sqlx::query_as(
-        "SELECT address, name, symbol, decimals, total_supply, first_seen_block
-         FROM erc20_contracts
-         WHERE name ILIKE $1 OR symbol ILIKE $1
-         ORDER BY first_seen_block DESC
-         LIMIT 10",
+        "SELECT address, name, tags
+         FROM address_labels
+         WHERE name ILIKE $1 OR EXISTS (SELECT 1 FROM unnest(tags) AS tag WHERE tag ILIKE $1)
+         ORDER BY lower(name) = lower($4) DESC, name ASC, address ASC
+         LIMIT $2 OFFSET $3",
     )
     .bind(&pattern)
+    .bind(window.fetch_limit())
+    .bind(window.offset)
+    .bind(query)
     .fetch_all(&state.pool)
     .await
     .map_err(Into::into)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params(query: &str) -> SearchQuery {
+        let uri = format!("/api/search?{query}").parse().unwrap();
+        Query::<SearchQuery>::try_from_uri(&uri).unwrap().0
+    }
+
+    #[test]
+    fn parses_type_filter() {
+        assert_eq!(parse_types(None).unwrap(), None);
+        assert_eq!(parse_types(Some(" ")).unwrap(), None);
+        assert_eq!(
+            parse_types(Some("token, TX,token")).unwrap(),
+            Some(vec![SearchType::Token, SearchType::Tx])
+        );
+        assert!(parse_types(Some("token,bogus")).is_err());
+    }
+
+    #[test]
+    fn window_uses_type_defaults_and_clamps_limit() {
+        let defaults = params("q=abc&page=3");
+        assert_eq!(
+            Window::new(SearchType::Nft, &defaults),
+            Window {
+                limit: 5,
+                offset: 10
+            }
+        );
+        assert_eq!(Window::new(SearchType::Token, &defaults).limit, 10);
+
+        let large = params("q=abc&limit=500&page=0");
+        assert_eq!(
+            Window::new(SearchType::Label, &large),
+            Window {
+                limit: MAX_LIMIT,
+                offset: 0
+            }
+        );
+
+        let suggest = params("q=abc&suggest=true&page=4&limit=20");
+        assert_eq!(
+            Window::new(SearchType::Token, &suggest),
+            Window {
+                limit: SUGGEST_LIMIT,
+                offset: 0
+            }
+        );
+    }
+
+    #[test]
+    fn window_trim_reports_probe_row() {
+        let window = Window {
+            limit: 2,
+            offset: 0,
+        };
+        let mut rows = vec![1, 2, 3];
+        assert!(window.trim(&mut rows));
+        assert_eq!(rows, vec![1, 2]);
+        assert!(!window.trim(&mut rows));
+    }
+}
@@ -1,14 +1,46 @@
+use atlas_common::storage::{BlockRange, TieredTable};
+use atlas_common::AtlasError;
 use axum::{
     extract::{Query, State},
     Json,
 };
-use chrono::Utc;
+use chrono::{Days, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
 use crate::api::error::ApiResult;
+use crate::api::handlers::require_hot_tier;
 use crate::api::AppState;
 
+/// `UserOperationEvent(bytes32 indexed userOpHash, address indexed sender,
+/// address indexed paymaster, uint256 nonce, bool success, uint256
+/// actualGasCost, uint256 actualGasUsed)`, the same in EntryPoint v0.6–v0.8.
+const USER_OPERATION_EVENT_TOPIC: &str =
+    "0x49628fd1471006c1482da88028e9ce4dbb080b815c9b0344d39e5a8e6ec1419f";
+/// Canonical ERC-4337 EntryPoint deployments (v0.6, v0.7, v0.8). Only their
+/// events count, so any contract emitting a look-alike event is ignored.
+const ENTRY_POINTS: [&str; 3] = [
+    "0x5ff137d4b0fdcd49dca30c7cf57e578a026d2789",
+    "0x0000000071727de22e5e9d8baf0edac6f37da032",
+    "0x4337084d9e255ff0702461cf8895ce9e3b5ff108",
+];
+const ZERO_ADDRESS: &str = "0x0000000000000000000000000000000000000000";
+/// Paymasters and bundlers listed by `/api/stats/aa`.
+const AA_TOP_N: i64 = 25;
+
+/// Longest gaps listed by `/api/stats/block-times`.
+const BLOCK_TIMES_LONGEST_GAPS: i64 = 10;
+
+/// Blocks returned by `/api/stats/fees/blocks` when no range is given.
+const BLOCK_FEES_DEFAULT_BLOCKS: i64 = 100;
+/// Longest range `/api/stats/fees/blocks` serves in one request.
+const BLOCK_FEES_MAX_BLOCKS: i64 = 1_000;
+
+/// Days returned by `/api/stats/daily` when `from` is omitted.
+const DAILY_STATS_DEFAULT_DAYS: u64 = 30;
+/// Longest range `/api/stats/daily` serves in one request.
+const DAILY_STATS_MAX_DAYS: i64 = 366;
+
 /// Time window for chart queries.
 #[derive(Deserialize, Default, Clone, Copy)]
 pub enum Window {
@@ -65,6 +97,7 @@ pub struct WindowQuery {
 pub struct BlockChartPoint {
     pub bucket: String,
     pub tx_count: i64,
+    #[serde(serialize_with = "atlas_common::numeric::quantity")]
     pub avg_gas_used: f64,
 }
 
@@ -77,9 +110,211 @@ pub struct DailyTxPoint {
 #[derive(Serialize)]
 pub struct GasPricePoint {
     pub bucket: String,
+    #[serde(serialize_with = "atlas_common::numeric::option_quantity")]
     pub avg_gas_price: Option<f64>,
 }
 
+#[derive(Deserialize)]
+pub struct BlockTimesQuery {
+    #[serde(default)]
+    pub window: Window,
+    /// An explicit block range replaces `window`
+    pub from_block: Option<i64>,
+    pub to_block: Option<i64>,
+    /// Gap counted as a stall; defaults to `BLOCK_STALL_SECS`
+    pub stall_secs: Option<u64>,
+}
+
+/// Time between consecutive blocks over a block range, in seconds. Gaps are
+/// only measured between blocks `n - 1` and `n` that are both in the range.
+#[derive(Serialize)]
+pub struct BlockTimeStats {
+    /// Range covered; `null` when the window holds no blocks
+    pub from_block: Option<i64>,
+    pub to_block: Option<i64>,
+    pub blocks: i64,
+    pub avg_secs: Option<f64>,
+    pub p50_secs: Option<f64>,
+    pub p95_secs: Option<f64>,
+    pub max_secs: Option<i64>,
+    /// `null` when no threshold was given or configured
+    pub stall_threshold_secs: Option<u64>,
+    /// Gaps of at least `stall_threshold_secs`
+    pub stalls: Option<i64>,
+    pub longest_gaps: Vec<BlockGap>,
+}
+
+#[derive(sqlx::FromRow)]
+struct BlockTimeSummary {
+    blocks: i64,
+    avg_secs: Option<f64>,
+    p50_secs: Option<f64>,
+    p95_secs: Option<f64>,
+    max_secs: Option<i64>,
+    stalls: i64,
+}
+
+/// A block and how long after its parent it was produced.
+#[derive(Serialize, sqlx::FromRow)]
+pub struct BlockGap {
+    pub block_number: i64,
+    pub timestamp: i64,
+    pub gap_secs: i64,
+}
+
+#[derive(Deserialize)]
+pub struct AaStatsQuery {
+    #[serde(default)]
+    pub window: Window,
+    /// An explicit block range replaces `window`
+    pub from_block: Option<i64>,
+    pub to_block: Option<i64>,
+}
+
+/// Account abstraction activity over a block range. Gas costs are wei as
+/// decimal strings; success rates are fractions (`null` without user ops).
+#[derive(Serialize)]
+pub struct AaStats {
+    /// Range covered; `null` when the window holds no blocks
+    pub from_block: Option<i64>,
+    pub to_block: Option<i64>,
+    #[serde(flatten)]
+    pub totals: AaTotals,
+    /// Top paymasters by gas cost sponsored
+    pub paymasters: Vec<AaPaymaster>,
+    /// Top bundlers by user operations submitted
+    pub bundlers: Vec<AaBundler>,
+}
+
+#[derive(Serialize, sqlx::FromRow)]
+pub struct AaTotals {
+    pub user_ops: i64,
+    pub successful_user_ops: i64,
+    #[sqlx(skip)]
+    pub success_rate: Option<f64>,
+    /// User operations paid for by a paymaster
+    pub sponsored_user_ops: i64,
+    /// Distinct smart accounts that sent user operations
+    pub senders: i64,
+    /// Transactions carrying user operations (`handleOps` calls)
+    pub bundles: i64,
+    pub total_gas_cost: String,
+    pub sponsored_gas_cost: String,
+}
+
+impl Default for AaTotals {
+    fn default() -> Self {
+        Self {
+            user_ops: 0,
+            successful_user_ops: 0,
+            success_rate: None,
+            sponsored_user_ops: 0,
+            senders: 0,
+            bundles: 0,
+            total_gas_cost: "0".to_string(),
+            sponsored_gas_cost: "0".to_string(),
+        }
+    }
+}
+
+#[derive(Serialize, sqlx::FromRow)]
+pub struct AaPaymaster {
+    pub address: String,
+    pub user_ops: i64,
+    pub successful_user_ops: i64,
+    #[sqlx(skip)]
+    pub success_rate: Option<f64>,
+    pub senders: i64,
+    /// Wei the paymaster paid the EntryPoint for its user operations
+    pub gas_cost: String,
+}
+
+#[derive(Serialize, sqlx::FromRow)]
+pub struct AaBundler {
+    pub address: String,
+    pub bundles: i64,
+    pub user_ops: i64,
+    pub successful_user_ops: i64,
+    #[sqlx(skip)]
+    pub success_rate: Option<f64>,
+    /// Wei the bundler was compensated with for its user operations
+    pub gas_cost: String,
+}
+
+#[derive(Deserialize)]
+pub struct DailyStatsQuery {
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+}
+
+/// One `daily_stats` row. Wei and gas amounts are decimal strings.
+#[derive(Serialize, sqlx::FromRow)]
+pub struct DailyStats {
+    pub day: NaiveDate,
+    pub block_count: i64,
+    pub tx_count: i64,
+    pub active_addresses: i64,
+    pub new_contracts: i64,
+    pub gas_used: String,
+    pub avg_gas_price: Option<String>,
+    pub new_tokens: i64,
+    pub nft_mints: i64,
+}
+
+/// Fees paid over a day or block, in wei as decimal strings.
+/// `total_fees` is what senders paid: `execution_fees` plus `l1_fees`.
+/// `base_fees` is the part of `execution_fees` covered by the base fee.
+#[derive(Serialize, sqlx::FromRow)]
+pub struct Fees {
+    pub tx_count: i64,
+    pub execution_fees: String,
+    pub base_fees: String,
+    pub l1_fees: String,
+    pub total_fees: String,
+}
+
+impl Fees {
+    fn zero() -> Self {
+        Self {
+            tx_count: 0,
+            execution_fees: "0".to_string(),
+            base_fees: "0".to_string(),
+            l1_fees: "0".to_string(),
+            total_fees: "0".to_string(),
+        }
+    }
+}
+
+#[derive(Serialize, sqlx::FromRow)]
+pub struct DailyFees {
+    pub day: NaiveDate,
+    #[sqlx(flatten)]
+    #[serde(flatten)]
+    pub fees: Fees,
+}
+
+#[derive(Serialize)]
+pub struct FeeStats {
+    /// Sums over `days`
+    pub totals: Fees,
+    pub days: Vec<DailyFees>,
+}
+
+#[derive(Deserialize)]
+pub struct BlockFeesQuery {
+    pub from_block: Option<i64>,
+    pub to_block: Option<i64>,
+}
+
+#[derive(Serialize, sqlx::FromRow)]
+pub struct BlockFees {
+    pub block_number: i64,
+    pub timestamp: i64,
+    #[sqlx(flatten)]
+    #[serde(flatten)]
+    pub fees: Fees,
+}
+
 fn resolve_avg_gas_price(
     tx_avg_gas_price: Option<f64>,
     block_avg_base_fee_per_gas: Option<f64>,
@@ -250,10 +485,482 @@ pub async fn get_gas_price_chart(
     Ok(Json(points))
 }
 
+/// GET /api/stats/block-times?window=24h&stall_secs=N or ?from_block=N&to_block=M
+///
+/// Block time percentiles, the longest gaps and the number of stalls. The
+/// window is anchored to the latest indexed block timestamp like the charts;
+/// an explicit block range is used as given.
+pub async fn get_block_times(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<BlockTimesQuery>,
+) -> ApiResult<Json<BlockTimeStats>> {
+    let stall_threshold_secs = params
+        .stall_secs
+        .or((state.block_stall_secs > 0).then_some(state.block_stall_secs));
+    let (from_block, to_block) =
+        resolve_block_range(&state, params.window, params.from_block, params.to_block).await?;
+    let (Some(lo), Some(hi)) = (from_block, to_block) else {
+        return Ok(Json(BlockTimeStats {
+            from_block,
+            to_block,
+            blocks: 0,
+            avg_secs: None,
+            p50_secs: None,
+            p95_secs: None,
+            max_secs: None,
+            stall_threshold_secs,
+            stalls: stall_threshold_secs.map(|_| 0),
+            longest_gaps: Vec::new(),
+        }));
+    };
+
+    let gaps = r#"
+        WITH gaps AS (
+            SELECT
+                number,
+                timestamp,
+                timestamp - LAG(timestamp) OVER (ORDER BY number) AS gap,
+                number - LAG(number) OVER (ORDER BY number)       AS step
+            FROM blocks
+            WHERE number BETWEEN $1 AND $2
+        )
+    "#;
+
+    let summary: BlockTimeSummary = sqlx::query_as(&format!(
+        r#"{gaps}
+        SELECT
+            COUNT(*)::bigint                                                          AS blocks,
+            (AVG(gap) FILTER (WHERE step = 1))::float8                                AS avg_secs,
+            percentile_cont(0.5) WITHIN GROUP (ORDER BY gap) FILTER (WHERE step = 1)  AS p50_secs,
+            percentile_cont(0.95) WITHIN GROUP (ORDER BY gap) FILTER (WHERE step = 1) AS p95_secs,
+            MAX(gap) FILTER (WHERE step = 1)                                          AS max_secs,
+            COUNT(*) FILTER (WHERE step = 1 AND gap >= $3)::bigint                    AS stalls
+        FROM gaps
+        "#
+    ))
+    .bind(lo)
+    .bind(hi)
+    .bind(stall_threshold_secs.unwrap_or(0) as i64)
+    .fetch_one(&state.pool)
+    .await?;
+
+    let longest_gaps: Vec<BlockGap> = sqlx::query_as(&format!(
+        r#"{gaps}
+        SELECT number AS block_number, timestamp, gap AS gap_secs
+        FROM gaps
+        WHERE step = 1
+        ORDER BY gap DESC, number DESC
+        LIMIT $3
+        "#
+    ))
+    .bind(lo)
+    .bind(hi)
+    .bind(BLOCK_TIMES_LONGEST_GAPS)
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(Json(BlockTimeStats {
+        from_block,
+        to_block,
+        blocks: summary.blocks,
+        avg_secs: summary.avg_secs,
+        p50_secs: summary.p50_secs,
+        p95_secs: summary.p95_secs,
+        max_secs: summary.max_secs,
+        stall_threshold_secs,
+        stalls: stall_threshold_secs.map(|_| summary.stalls),
+        longest_gaps,
+    }))
+}
+
+/// Blocks covered by `window`, anchored to the latest indexed block
+/// timestamp, or the explicit range when either bound is given.
+pub(crate) async fn resolve_block_range(
+    state: &AppState,
+    window: Window,
+    from_block: Option<i64>,
+    to_block: Option<i64>,
+) -> Result<(Option<i64>, Option<i64>), AtlasError> {
+    match (from_block, to_block) {
+        (None, None) => Ok(sqlx::query_as(
+            "WITH latest AS (SELECT MAX(timestamp) AS max_ts FROM blocks)
+             SELECT MIN(number), MAX(number)
+             FROM blocks, latest
+             WHERE blocks.timestamp >= latest.max_ts - $1",
+        )
+        .bind(window.duration_secs())
+        .fetch_one(&state.pool)
+        .await?),
+        (from, to) => {
+            let (from, to) = (from.unwrap_or(0), to.unwrap_or(i64::MAX));
+            if from < 0 || to < 0 {
+                return Err(AtlasError::InvalidInput(
+                    "Block numbers must not be negative".to_string(),
+                ));
+            }
+            if from > to {
+                return Err(AtlasError::InvalidInput(
+                    "from_block must not be after to_block".to_string(),
+                ));
+            }
+            Ok((Some(from), Some(to)))
+        }
+    }
+}
+
+/// GET /api/stats/aa?window=24h or ?from_block=N&to_block=M
+///
+/// ERC-4337 user operation totals, paymaster spend and bundler activity,
+/// aggregated from the `UserOperationEvent` logs of the canonical EntryPoints.
+/// The window is anchored to the latest indexed block timestamp like the
+/// charts; an explicit block range is used as given.
+pub async fn get_aa_stats(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<AaStatsQuery>,
+) -> ApiResult<Json<AaStats>> {
+    let (from_block, to_block) =
+        resolve_block_range(&state, params.window, params.from_block, params.to_block).await?;
+
+    let (Some(lo), Some(hi)) = (from_block, to_block) else {
+        return Ok(Json(AaStats {
+            from_block,
+            to_block,
+            totals: AaTotals::default(),
+            paymasters: Vec::new(),
+            bundlers: Vec::new(),
+        }));
+    };
+    // The bundler comes from `transactions`, which the cold tier lacks.
+    require_hot_tier(
+        &state,
+        TieredTable::EventLogs,
+        BlockRange::new(lo, hi),
+        "AA stats",
+    )?;
+    let ops = user_ops_sql();
+    let entry_points: Vec<&str> = ENTRY_POINTS.to_vec();
+
+    let mut totals: AaTotals = sqlx::query_as(&format!(
+        "{ops}
+         SELECT COUNT(*) AS user_ops,
+                COUNT(*) FILTER (WHERE success) AS successful_user_ops,
+                COUNT(*) FILTER (WHERE paymaster <> $5) AS sponsored_user_ops,
+                COUNT(DISTINCT sender) AS senders,
+                COUNT(DISTINCT tx_hash) AS bundles,
+                COALESCE(SUM(gas_cost), 0)::text AS total_gas_cost,
+                COALESCE(SUM(gas_cost) FILTER (WHERE paymaster <> $5), 0)::text AS sponsored_gas_cost
+         FROM ops"
+    ))
+    .bind(lo)
+    .bind(hi)
+    .bind(USER_OPERATION_EVENT_TOPIC)
+    .bind(&entry_points)
+    .bind(ZERO_ADDRESS)
+    .fetch_one(&state.pool)
+    .await?;
+    totals.success_rate = success_rate(totals.successful_user_ops, totals.user_ops);
+
+    let mut paymasters: Vec<AaPaymaster> = sqlx::query_as(&format!(
+        "{ops}
+         SELECT paymaster AS address,
+                COUNT(*) AS user_ops,
+                COUNT(*) FILTER (WHERE success) AS successful_user_ops,
+                COUNT(DISTINCT sender) AS senders,
+                SUM(gas_cost)::text AS gas_cost
+         FROM ops
+         WHERE paymaster <> $5
+         GROUP BY paymaster
+         ORDER BY SUM(gas_cost) DESC, paymaster
+         LIMIT $6"
+    ))
+    .bind(lo)
+    .bind(hi)
+    .bind(USER_OPERATION_EVENT_TOPIC)
+    .bind(&entry_points)
+    .bind(ZERO_ADDRESS)
+    .bind(AA_TOP_N)
+    .fetch_all(&state.pool)
+    .await?;
+    for paymaster in &mut paymasters {
+        paymaster.success_rate = success_rate(paymaster.successful_user_ops, paymaster.user_ops);
+    }
+
+    let mut bundlers: Vec<AaBundler> = sqlx::query_as(&format!(
+        "{ops}
+         SELECT bundler AS address,
+                COUNT(DISTINCT tx_hash) AS bundles,
+                COUNT(*) AS user_ops,
+                COUNT(*) FILTER (WHERE success) AS successful_user_ops,
+                SUM(gas_cost)::text AS gas_cost
+         FROM ops
+         GROUP BY bundler
+         ORDER BY COUNT(*) DESC, bundler
+         LIMIT $5"
+    ))
+    .bind(lo)
+    .bind(hi)
+    .bind(USER_OPERATION_EVENT_TOPIC)
+    .bind(&entry_points)
+    .bind(AA_TOP_N)
+    .fetch_all(&state.pool)
+    .await?;
+    for bundler in &mut bundlers {
+        bundler.success_rate = success_rate(bundler.successful_user_ops, bundler.user_ops);
+    }
+
+    Ok(Json(AaStats {
+        from_block,
+        to_block,
+        totals,
+        paymasters,
+        bundlers,
+    }))
+}
+
+/// `ops` CTE: one row per user operation in blocks `$1..=$2`, from
+/// `UserOperationEvent` logs (`$3`) of the EntryPoints in `$4`. The bundler is
+/// the sender of the `handleOps` transaction.
+fn user_ops_sql() -> String {
+    format!(
+        "WITH ops AS (
+             SELECT '0x' || encode(l.tx_hash, 'hex') AS tx_hash,
+                    '0x' || encode(substring(l.topic2 FROM 13), 'hex') AS sender,
+                    '0x' || encode(substring(l.topic3 FROM 13), 'hex') AS paymaster,
+                    get_byte(l.data, 63) <> 0 AS success,
+                    {gas_cost} AS gas_cost,
+                    t.from_address AS bundler
+             FROM event_logs l
+             JOIN transactions t
+               ON t.hash = '0x' || encode(l.tx_hash, 'hex') AND t.block_number = l.block_number
+             WHERE l.block_number BETWEEN $1 AND $2
+               AND l.topic0 = decode(substr($3, 3), 'hex')
+               AND l.address = ANY(ARRAY(SELECT decode(substr(a, 3), 'hex') FROM unnest($4::text[]) a))
+               AND length(l.data) >= 128
+         )",
+        gas_cost = uint256_word_sql("l.data", 64),
+    )
+}
+
+/// SQL `numeric` for the big-endian uint256 at `offset` in a `bytea` column,
+/// assembled from four 64-bit chunks (each made unsigned).
+fn uint256_word_sql(column: &str, offset: usize) -> String {
+    const CHUNK_SCALES: [&str; 4] = [
+        "6277101735386680763835789423207666416102355444464034512896",
+        "340282366920938463463374607431768211456",
+        "18446744073709551616",
+        "1",
+    ];
+    let chunks: Vec<String> = CHUNK_SCALES
+        .iter()
+        .enumerate()
+        .map(|(i, scale)| {
+            let start = offset + i * 8;
+            format!(
+                "(('x' || encode(substring({column} from {} for 8), 'hex'))::bit(64)::bigint::numeric
+                  + CASE WHEN get_byte({column}, {start}) >= 128 THEN 18446744073709551616 ELSE 0 END) * {scale}",
+                start + 1
+            )
+        })
+        .collect();
+    format!("({})", chunks.join(" + "))
+}
+
+fn success_rate(successful: i64, total: i64) -> Option<f64> {
+    (total > 0).then(|| successful as f64 / total as f64)
+}
+
+/// GET /api/stats/daily?from=YYYY-MM-DD&to=YYYY-MM-DD
+///
+/// Returns precomputed per-day chain summaries (UTC days, oldest first) from
+/// `daily_stats`. `to` defaults to the latest aggregated day and `from` to
+/// 30 days before it; days the aggregation has not reached are omitted.
+pub async fn get_daily_stats(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<DailyStatsQuery>,
+) -> ApiResult<Json<Vec<DailyStats>>> {
+    let latest = match params.to {
+        Some(_) => None,
+        None => {
+            sqlx::query_scalar("SELECT MAX(day) FROM daily_stats")
+                .fetch_one(&state.pool)
+                .await?
+        }
+    };
+    let Some((from, to)) = daily_stats_range(params.from, params.to, latest)? else {
+        return Ok(Json(Vec::new()));
+    };
+
+    let rows: Vec<DailyStats> = sqlx::query_as(
+        "SELECT day, block_count, tx_count, active_addresses, new_contracts,
+                gas_used::text AS gas_used, avg_gas_price::text AS avg_gas_price,
+                new_tokens, nft_mints
+         FROM daily_stats
+         WHERE day BETWEEN $1 AND $2
+         ORDER BY day ASC",
+    )
+    .bind(from)
+    .bind(to)
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(Json(rows))
+}
+
+/// GET /api/stats/fees?from=YYYY-MM-DD&to=YYYY-MM-DD
+///
+/// Fees paid per day from `daily_stats`, with totals over the range. The
+/// range defaults and limits match `/api/stats/daily`.
+pub async fn get_fee_stats(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<DailyStatsQuery>,
+) -> ApiResult<Json<FeeStats>> {
+    let latest = match params.to {
+        Some(_) => None,
+        None => {
+            sqlx::query_scalar("SELECT MAX(day) FROM daily_stats")
+                .fetch_one(&state.pool)
+                .await?
+        }
+    };
+    let Some((from, to)) = daily_stats_range(params.from, params.to, latest)? else {
+        return Ok(Json(FeeStats {
+            totals: Fees::zero(),
+            days: Vec::new(),
+        }));
+    };
+
+    let days: Vec<DailyFees> = sqlx::query_as(
+        "SELECT day, tx_count,
+                execution_fees::text AS execution_fees, base_fees::text AS base_fees,
+                l1_fees::text AS l1_fees, (execution_fees + l1_fees)::text AS total_fees
+         FROM daily_stats
+         WHERE day BETWEEN $1 AND $2
+         ORDER BY day ASC",
+    )
+    .bind(from)
+    .bind(to)
+    .fetch_all(&state.pool)
+    .await?;
+    let totals: Fees = sqlx::query_as(
+        "SELECT COALESCE(SUM(tx_count), 0)::bigint AS tx_count,
+                COALESCE(SUM(execution_fees), 0)::text AS execution_fees,
+                COALESCE(SUM(base_fees), 0)::text AS base_fees,
+                COALESCE(SUM(l1_fees), 0)::text AS l1_fees,
+                COALESCE(SUM(execution_fees + l1_fees), 0)::text AS total_fees
+         FROM daily_stats
+         WHERE day BETWEEN $1 AND $2",
+    )
+    .bind(from)
+    .bind(to)
+    .fetch_one(&state.pool)
+    .await?;
+
+    Ok(Json(FeeStats { totals, days }))
+}
+
+/// GET /api/stats/fees/blocks?from_block=N&to_block=M
+///
+/// Fees paid per block, computed from `transactions`, newest first.
+/// `to_block` defaults to the latest indexed block and `from_block` to 99
+/// blocks before it.
+pub async fn get_block_fees(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<BlockFeesQuery>,
+) -> ApiResult<Json<Vec<BlockFees>>> {
+    let to = match params.to_block {
+        Some(to) => to,
+        None => match state.latest_block_height().await? {
+            Some(head) => head,
+            None => return Ok(Json(Vec::new())),
+        },
+    };
+    let from = params
+        .from_block
+        .unwrap_or((to - BLOCK_FEES_DEFAULT_BLOCKS + 1).max(0));
+    if from < 0 || to < 0 {
+        return Err(
+            AtlasError::InvalidInput("Block numbers must not be negative".to_string()).into(),
+        );
+    }
+    if from > to {
+        return Err(
+            AtlasError::InvalidInput("from_block must not be after to_block".to_string()).into(),
+        );
+    }
+    if to - from >= BLOCK_FEES_MAX_BLOCKS {
+        return Err(AtlasError::InvalidInput(format!(
+            "Block range must not exceed {BLOCK_FEES_MAX_BLOCKS} blocks"
+        ))
+        .into());
+    }
+
+    let rows: Vec<BlockFees> = sqlx::query_as(
+        "SELECT b.number AS block_number, b.timestamp,
+                COUNT(t.hash)::bigint AS tx_count,
+                COALESCE(SUM(t.gas_used * t.gas_price), 0)::text AS execution_fees,
+                COALESCE(b.gas_used * b.base_fee_per_gas, 0)::text AS base_fees,
+                COALESCE(SUM(t.l1_fee), 0)::text AS l1_fees,
+                (COALESCE(SUM(t.gas_used * t.gas_price), 0) + COALESCE(SUM(t.l1_fee), 0))::text
+                    AS total_fees
+         FROM blocks b
+         LEFT JOIN transactions t ON t.block_number = b.number
+         WHERE b.number BETWEEN $1 AND $2
+         GROUP BY b.number, b.timestamp, b.gas_used, b.base_fee_per_gas
+         ORDER BY b.number DESC",
+    )
+    .bind(from)
+    .bind(to)
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(Json(rows))
+}
+
+/// Resolve the requested day range; `None` when there is nothing to return.
+fn daily_stats_range(
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+    latest: Option<NaiveDate>,
+) -> Result<Option<(NaiveDate, NaiveDate)>, AtlasError> {
+    let Some(to) = to.or(latest) else {
+        return Ok(None);
+    };
+    let from = from.unwrap_or_else(|| {
+        to.checked_sub_days(Days::new(DAILY_STATS_DEFAULT_DAYS - 1))
+            .unwrap_or(NaiveDate::MIN)
+    });
+
+    if from > to {
+        return Err(AtlasError::InvalidInput(
+            "from must not be after to".to_string(),
+        ));
+    }
+    if (to - from).num_days() >= DAILY_STATS_MAX_DAYS {
+        return Err(AtlasError::InvalidInput(format!(
+            "Date range must not exceed {DAILY_STATS_MAX_DAYS} days"
+        )));
+    }
+    Ok(Some((from, to)))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn user_operation_event_topic_matches_signature() {
+        let topic = alloy::primitives::keccak256(
+            "UserOperationEvent(bytes32,address,address,uint256,bool,uint256,uint256)",
+        );
+        assert_eq!(format!("{topic:?}"), USER_OPERATION_EVENT_TOPIC);
+    }
+
+    #[test]
+    fn success_rate_is_none_without_user_ops() {
+        assert_eq!(success_rate(0, 0), None);
+        assert_eq!(success_rate(3, 4), Some(0.75));
+    }
+
     #[test]
     fn window_duration_secs() {
         assert_eq!(Window::OneHour.duration_secs(), 3_600);
@@ -300,4 +1007,34 @@ mod tests {
     fn resolve_avg_gas_price_returns_none_when_bucket_is_empty() {
         assert_eq!(resolve_avg_gas_price(None, None), None);
     }
+
+    fn date(s: &str) -> NaiveDate {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn daily_stats_range_defaults_to_last_30_aggregated_days() {
+        assert_eq!(
+            daily_stats_range(None, None, Some(date("2030-01-31"))).unwrap(),
+            Some((date("2030-01-02"), date("2030-01-31")))
+        );
+        assert_eq!(
+            daily_stats_range(Some(date("2030-01-20")), None, Some(date("2030-01-31"))).unwrap(),
+            Some((date("2030-01-20"), date("2030-01-31")))
+        );
+        assert_eq!(daily_stats_range(None, None, None).unwrap(), None);
+    }
+
+    #[test]
+    fn daily_stats_range_rejects_inverted_or_oversized_ranges() {
+        assert!(
+            daily_stats_range(Some(date("2030-02-01")), Some(date("2030-01-01")), None).is_err()
+        );
+        assert!(
+            daily_stats_range(Some(date("2030-01-01")), Some(date("2031-01-01")), None).is_ok()
+        );
+        assert!(
+            daily_stats_range(Some(date("2030-01-01")), Some(date("2031-01-02")), None).is_err()
+        );
+    }
 }
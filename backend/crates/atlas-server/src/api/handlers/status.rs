@@ -1,10 +1,23 @@
 use axum::{extract::State, Json};
 use serde::Serialize;
+use serde_json::Value;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use crate::api::error::ApiResult;
+use crate::api::handlers::display::NativeCurrency;
 use crate::api::handlers::get_table_count;
+use crate::api::handlers::health::MAX_INDEXER_AGE_MINUTES;
+use crate::api::handlers::raw::rpc_call_with_timeout;
 use crate::api::AppState;
+use atlas_common::db::MigrationInfo;
+
+/// Give up on the RPC probe after this long; the node counts as down.
+const RPC_PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+/// Database or RPC round trips slower than this are degraded.
+const SLOW_MS: u64 = 1000;
+/// Indexer lag behind the chain head beyond which it is degraded.
+const MAX_LAG_BLOCKS: i64 = 20;
 
 #[derive(Serialize)]
 pub struct HeightResponse {
@@ -16,10 +29,64 @@ pub struct HeightResponse {
 pub struct ChainStatus {
     pub chain_id: String,
     pub chain_name: String,
+    pub native_currency: NativeCurrency,
     pub block_height: i64,
     pub total_transactions: i64,
     pub total_addresses: i64,
     pub indexed_at: String,
+    /// Release of this server build.
+    pub version: &'static str,
+    /// Applied migrations compared with the ones this build ships.
+    pub schema: MigrationInfo,
+    /// What users of the explorer currently get from each component.
+    pub health: ServiceHealth,
+}
+
+/// Component health, ordered from best to worst.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthLevel {
+    Ok,
+    Degraded,
+    Down,
+}
+
+#[derive(Serialize)]
+pub struct ServiceHealth {
+    /// Worst of `api`, `indexer` and `rpc`
+    pub status: HealthLevel,
+    pub api: ApiHealth,
+    pub indexer: IndexerHealth,
+    pub rpc: RpcHealth,
+}
+
+#[derive(Serialize)]
+pub struct ApiHealth {
+    pub status: HealthLevel,
+    /// Round trip of a trivial database query
+    pub db_latency_ms: u64,
+}
+
+#[derive(Serialize)]
+pub struct IndexerHealth {
+    pub status: HealthLevel,
+    /// Blocks between the chain head and the indexed head; `null` when the
+    /// chain head is unknown
+    pub lag_blocks: Option<i64>,
+    /// Seconds since the indexed head was written; `null` before the first block
+    pub last_indexed_secs_ago: Option<i64>,
+}
+
+#[derive(Serialize)]
+pub struct RpcHealth {
+    pub status: HealthLevel,
+    /// Round trip of `eth_blockNumber`
+    pub latency_ms: Option<u64>,
+    pub head_block: Option<i64>,
+    /// `eth_syncing` reported sync progress; `null` when it did not answer
+    pub syncing: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
 }
 
 pub(super) async fn latest_indexed_block(
@@ -47,6 +114,130 @@ async fn latest_height_and_indexed_at(state: &AppState) -> Result<(i64, String),
     Ok((0, String::new()))
 }
 
+/// Probe the chain RPC with `eth_blockNumber` and `eth_syncing`.
+async fn probe_rpc(rpc_url: &str) -> RpcHealth {
+    let mut health = RpcHealth {
+        status: HealthLevel::Down,
+        latency_ms: None,
+        head_block: None,
+        syncing: None,
+        error: None,
+    };
+    if rpc_url.is_empty() {
+        health.error = Some("no RPC URL configured".to_string());
+        return health;
+    }
+
+    let started = Instant::now();
+    let (head, syncing) = tokio::join!(
+        async {
+            let head = rpc_call_with_timeout(
+                rpc_url,
+                "eth_blockNumber",
+                serde_json::json!([]),
+                RPC_PROBE_TIMEOUT,
+            )
+            .await;
+            (head, started.elapsed())
+        },
+        rpc_call_with_timeout(
+            rpc_url,
+            "eth_syncing",
+            serde_json::json!([]),
+            RPC_PROBE_TIMEOUT
+        ),
+    );
+    let (head, latency) = head;
+    let head = head.and_then(|head| {
+        head.as_str()
+            .and_then(|hex| i64::from_str_radix(hex.trim_start_matches("0x"), 16).ok())
+            .ok_or_else(|| {
+                atlas_common::AtlasError::Rpc(format!("eth_blockNumber returned {head}"))
+            })
+    });
+    match head {
+        Ok(head) => {
+            health.latency_ms = Some(latency.as_millis() as u64);
+            health.head_block = Some(head);
+        }
+        Err(e) => {
+            health.error = Some(e.to_string());
+            return health;
+        }
+    }
+    // `false` when in sync, a progress object while syncing.
+    health.syncing = syncing
+        .ok()
+        .map(|syncing| !matches!(syncing, Value::Bool(false)));
+    health.status = rpc_level(health.latency_ms, health.syncing);
+    health
+}
+
+/// A reachable node is degraded while syncing or when slow.
+fn rpc_level(latency_ms: Option<u64>, syncing: Option<bool>) -> HealthLevel {
+    match latency_ms {
+        None => HealthLevel::Down,
+        Some(ms) if ms > SLOW_MS || syncing == Some(true) => HealthLevel::Degraded,
+        Some(_) => HealthLevel::Ok,
+    }
+}
+
+/// The indexer is down before its first block and when it has not written
+/// one for a while although the chain moved on, and degraded when lagging.
+fn indexer_level(lag_blocks: Option<i64>, last_indexed_secs_ago: Option<i64>) -> HealthLevel {
+    match (lag_blocks, last_indexed_secs_ago) {
+        (_, None) => HealthLevel::Down,
+        (lag, Some(age)) if age > MAX_INDEXER_AGE_MINUTES * 60 && lag != Some(0) => {
+            HealthLevel::Down
+        }
+        (Some(lag), _) if lag > MAX_LAG_BLOCKS => HealthLevel::Degraded,
+        _ => HealthLevel::Ok,
+    }
+}
+
+async fn service_health(state: &AppState) -> Result<ServiceHealth, sqlx::Error> {
+    let (rpc, db_latency, latest) = tokio::join!(
+        probe_rpc(&state.rpc_url),
+        async {
+            let started = Instant::now();
+            sqlx::query("SELECT 1")
+                .execute(&state.pool)
+                .await
+                .map(|_| started.elapsed())
+        },
+        latest_indexed_block(state),
+    );
+    let db_latency_ms = db_latency?.as_millis() as u64;
+    let latest = latest?;
+
+    let chain_head = rpc.head_block.or_else(|| state.head_tracker.chain_height());
+    let lag_blocks = chain_head
+        .zip(latest.map(|(number, _)| number))
+        .map(|(head, indexed)| (head - indexed).max(0));
+    let last_indexed_secs_ago =
+        latest.map(|(_, indexed_at)| (chrono::Utc::now() - indexed_at).num_seconds().max(0));
+
+    let api = ApiHealth {
+        status: if db_latency_ms > SLOW_MS {
+            HealthLevel::Degraded
+        } else {
+            HealthLevel::Ok
+        },
+        db_latency_ms,
+    };
+    let indexer = IndexerHealth {
+        status: indexer_level(lag_blocks, last_indexed_secs_ago),
+        lag_blocks,
+        last_indexed_secs_ago,
+    };
+    Ok(ServiceHealth {
+        status: api.status.max(indexer.status).max(rpc.status),
+        api,
+        indexer,
+        rpc,
+    })
+}
+
 /// GET /api/height - Lightweight endpoint for current block height.
 /// Returns in <1ms, optimized for frequent polling.
 pub async fn get_height(State(state): State<Arc<AppState>>) -> ApiResult<Json<HeightResponse>> {
@@ -63,14 +254,20 @@ pub async fn get_status(State(state): State<Arc<AppState>>) -> ApiResult<Json<Ch
     let (block_height, indexed_at) = latest_height_and_indexed_at(&state).await?;
     let total_transactions = get_table_count(&state.pool, "transactions").await?;
     let total_addresses = get_table_count(&state.pool, "addresses").await?;
+    let schema = atlas_common::db::migration_info(&state.pool).await?;
+    let health = service_health(&state).await?;
 
     Ok(Json(ChainStatus {
         chain_id: state.chain_id.to_string(),
         chain_name: state.chain_name.clone(),
+        native_currency: state.native_currency.clone(),
         block_height,
         total_transactions,
         total_addresses,
         indexed_at,
+        version: env!("CARGO_PKG_VERSION"),
+        schema,
+        health,
     }))
 }
 
@@ -105,7 +302,7 @@ mod tests {
             .build_recorder()
             .handle();
         State(Arc::new(AppState {
-            pool,
+            pool: pool.clone(),
             block_events_tx: block_tx,
             da_events_tx: da_tx,
             head_tracker,
@@ -116,6 +313,7 @@ mod tests {
             faucet_cooldown_minutes: None,
             chain_id: 1,
             chain_name: "Test Chain".to_string(),
+            native_currency: Default::default(),
             chain_logo_url: None,
             chain_logo_url_light: None,
             chain_logo_url_dark: None,
@@ -127,6 +325,22 @@ mod tests {
             metrics: crate::metrics::Metrics::new(),
             prometheus_handle,
             solc_cache_dir: "/tmp/solc-cache".to_string(),
+            verify_timeout: std::time::Duration::from_secs(300),
+            verify_max_source_bytes: 20 * 1024 * 1024,
+            export_dir: None,
+            dataset_dir: None,
+            db_circuit_breaker: crate::api::circuit_breaker::CircuitBreaker::default(),
+            latency: Default::default(),
+            admin_api_key: None,
+            db_pools: Arc::new(crate::db_pools::DbPools::new(Vec::new())),
+            concurrency_limits: Default::default(),
+            body_limits: Default::default(),
+            log_limits: Default::default(),
+            row_limits: Default::default(),
+            storage: Arc::new(atlas_common::storage::HotStorage::new(pool)),
+            confirmations_head: Default::default(),
+            block_stall_secs: 0,
+            runtime_settings: Default::default(),
         }))
     }
 
@@ -156,4 +370,35 @@ mod tests {
 
         assert_eq!(status.block_height, 12);
     }
+
+    #[test]
+    fn rpc_is_degraded_while_syncing_or_slow() {
+        assert_eq!(rpc_level(None, None), HealthLevel::Down);
+        assert_eq!(rpc_level(Some(20), Some(false)), HealthLevel::Ok);
+        assert_eq!(rpc_level(Some(20), None), HealthLevel::Ok);
+        assert_eq!(rpc_level(Some(20), Some(true)), HealthLevel::Degraded);
+        assert_eq!(
+            rpc_level(Some(SLOW_MS + 1), Some(false)),
+            HealthLevel::Degraded
+        );
+    }
+
+    #[test]
+    fn indexer_is_down_when_stale_and_degraded_when_lagging() {
+        let stale = MAX_INDEXER_AGE_MINUTES * 60 + 1;
+        assert_eq!(indexer_level(Some(0), None), HealthLevel::Down);
+        assert_eq!(indexer_level(Some(3), Some(stale)), HealthLevel::Down);
+        assert_eq!(indexer_level(None, Some(stale)), HealthLevel::Down);
+        // A stalled chain leaves a caught-up indexer idle
+        assert_eq!(indexer_level(Some(0), Some(stale)), HealthLevel::Ok);
+        assert_eq!(
+            indexer_level(Some(MAX_LAG_BLOCKS + 1), Some(2)),
+            HealthLevel::Degraded
+        );
+        assert_eq!(
+            indexer_level(Some(MAX_LAG_BLOCKS), Some(2)),
+            HealthLevel::Ok
+        );
+        assert_eq!(indexer_level(None, Some(2)), HealthLevel::Ok);
+    }
 }
@@ -1,35 +1,91 @@
 use axum::{
     extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
     Json,
 };
-use chrono::Utc;
+use chrono::{TimeZone, Utc};
+use futures::future::BoxFuture;
+use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
+use std::collections::HashMap;
 use std::sync::Arc;
 
+use crate::api::auth::require_admin;
 use crate::api::error::ApiResult;
-use crate::api::handlers::has_complete_erc20_supply_history;
+use crate::api::handlers::display::{self, decorate, token_decimals, DisplayQuery, WithDisplay};
 use crate::api::handlers::stats::WindowQuery;
+use crate::api::handlers::{
+    anchor_bound, has_complete_erc20_supply_history, segments_newest_first, snapshot_anchor,
+    stitch_segments, sum_segment_counts,
+};
 use crate::api::AppState;
+use crate::indexer::canonical_tokens::resolve_symbols;
+use crate::ordering::{ChainRow, Direction};
+use atlas_common::storage::{BlockRange, Segment, TieredTable};
 use atlas_common::{
-    AtlasError, Erc20Balance, Erc20Contract, Erc20Holder, Erc20Transfer, PaginatedResponse,
-    Pagination,
+    AtlasError, Erc20Contract, Erc20Holder, Erc20Transfer, PaginatedResponse, Pagination,
 };
 
+/// How a token stands among tokens sharing its symbol; see
+/// [`crate::indexer::canonical_tokens`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize, sqlx::FromRow)]
+pub struct TokenStanding {
+    /// Marked legitimate by an admin
+    pub verified: bool,
+    /// Marked as a scam or impersonation by an admin
+    pub flagged: bool,
+    /// The canonical token for this symbol, when it is another token
+    pub duplicate_of: Option<String>,
+}
+
+/// ERC-20 token with its [`TokenStanding`] and logo
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct RankedToken {
+    #[serde(flatten)]
+    #[sqlx(flatten)]
+    pub contract: Erc20Contract,
+    #[serde(flatten)]
+    #[sqlx(flatten)]
+    pub standing: TokenStanding,
+    /// Admin-set logo, see [`crate::api::handlers::logos`]
+    pub logo_url: Option<String>,
+}
+
+/// `erc20_contracts` columns (aliased `c`) for [`RankedToken`]; use with
+/// [`TOKEN_STANDING_JOINS`].
+pub(crate) const RANKED_TOKEN_COLUMNS: &str =
+    "c.address, c.name, c.symbol, c.decimals, c.total_supply, c.first_seen_block,
+     COALESCE(cur.status = 'verified', false) AS verified,
+     COALESCE(cur.status = 'flagged', false) AS flagged,
+     CASE WHEN ct.address <> c.address THEN ct.address END AS duplicate_of,
+     logo.logo_url";
+
+pub(crate) const TOKEN_STANDING_JOINS: &str =
+    "LEFT JOIN token_curation cur ON cur.address = c.address
+     LEFT JOIN canonical_tokens ct ON ct.symbol_key = lower(c.symbol)
+     LEFT JOIN contract_logos logo ON logo.address = c.address";
+
+/// Leading `ORDER BY` terms: verified tokens first, flagged tokens and
+/// look-alikes of a canonical token last.
+pub(crate) const TOKEN_STANDING_ORDER: &str = "COALESCE(cur.status = 'verified', false) DESC,
+     (cur.status = 'flagged' OR ct.address <> c.address) IS TRUE ASC";
+
 /// GET /api/tokens - List all ERC-20 tokens
 pub async fn list_tokens(
     State(state): State<Arc<AppState>>,
     Query(pagination): Query<Pagination>,
-) -> ApiResult<Json<PaginatedResponse<Erc20Contract>>> {
+) -> ApiResult<Json<PaginatedResponse<RankedToken>>> {
     let total: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM erc20_contracts")
         .fetch_one(&state.pool)
         .await?;
 
-    let tokens: Vec<Erc20Contract> = sqlx::query_as(
-        "SELECT address, name, symbol, decimals, total_supply, first_seen_block
-         FROM erc20_contracts
-         ORDER BY first_seen_block DESC
-         LIMIT $1 OFFSET $2",
-    )
+    let tokens: Vec<RankedToken> = sqlx::query_as(&format!(
+        "SELECT {RANKED_TOKEN_COLUMNS}
+         FROM erc20_contracts c
+         {TOKEN_STANDING_JOINS}
+         ORDER BY {TOKEN_STANDING_ORDER}, c.first_seen_block DESC, c.address
+         LIMIT $1 OFFSET $2"
+    ))
     .bind(pagination.limit())
     .bind(pagination.offset())
     .fetch_all(&state.pool)
@@ -48,6 +104,9 @@ pub async fn list_tokens(
 pub struct TokenDetailResponse {
     #[serde(flatten)]
     pub contract: Erc20Contract,
+    #[serde(flatten)]
+    pub standing: TokenStanding,
+    pub logo_url: Option<String>,
     pub holder_count: i64,
     pub transfer_count: i64,
 }
@@ -75,11 +134,16 @@ pub async fn get_token(
 ) -> ApiResult<Json<TokenDetailResponse>> {
     let address = normalize_address(&address);
 
-    let mut contract: Erc20Contract = sqlx::query_as(
-        "SELECT address, name, symbol, decimals, total_supply, first_seen_block
-         FROM erc20_contracts
-         WHERE address = $1",
-    )
+    let RankedToken {
+        mut contract,
+        standing,
+        logo_url,
+    } = sqlx::query_as(&format!(
+        "SELECT {RANKED_TOKEN_COLUMNS}
+         FROM erc20_contracts c
+         {TOKEN_STANDING_JOINS}
+         WHERE c.address = $1"
+    ))
     .bind(&address)
     .fetch_optional(&state.pool)
     .await?
@@ -92,11 +156,19 @@ pub async fn get_token(
     .fetch_one(&state.pool)
     .await?;
 
-    let transfer_count: (i64,) =
-        sqlx::query_as("SELECT COUNT(*) FROM erc20_transfers WHERE contract_address = $1")
-            .bind(&address)
-            .fetch_one(&state.pool)
-            .await?;
+    // Raw transfers in every tier plus the ones folded into daily summaries
+    // by the retention job.
+    let segments = state
+        .storage
+        .segments(TieredTable::Erc20Transfers, BlockRange::up_to(i64::MAX));
+    let raw_count = sum_segment_counts(&segments, count_token_transfers(&address)).await?;
+    let summarized_count: i64 = sqlx::query_scalar(
+        "SELECT COALESCE(SUM(transfer_count), 0)::bigint FROM erc20_transfer_summaries
+         WHERE contract_address = $1",
+    )
+    .bind(&address)
+    .fetch_one(&state.pool)
+    .await?;
 
     if has_complete_erc20_supply_history(&state.pool).await? {
         contract.total_supply = Some(get_indexed_total_supply(&state.pool, &address).await?);
@@ -104,8 +176,10 @@ pub async fn get_token(
 
     Ok(Json(TokenDetailResponse {
         contract,
+        standing,
+        logo_url,
         holder_count: holder_count.0,
-        transfer_count: transfer_count.0,
+        transfer_count: raw_count + summarized_count,
     }))
 }
 
@@ -114,7 +188,8 @@ pub async fn get_token_holders(
     State(state): State<Arc<AppState>>,
     Path(address): Path<String>,
     Query(pagination): Query<Pagination>,
-) -> ApiResult<Json<PaginatedResponse<Erc20Holder>>> {
+    Query(options): Query<DisplayQuery>,
+) -> ApiResult<Json<PaginatedResponse<WithDisplay<Erc20Holder>>>> {
     let address = normalize_address(&address);
 
     // Verify token exists
@@ -127,13 +202,6 @@ pub async fn get_token_holders(
         return Err(AtlasError::NotFound(format!("Token {} not found", address)).into());
     }
 
-    let total: (i64,) = sqlx::query_as(
-        "SELECT COUNT(*) FROM erc20_balances WHERE contract_address = $1 AND balance > 0",
-    )
-    .bind(&address)
-    .fetch_one(&state.pool)
-    .await?;
-
     let total_supply = if has_complete_erc20_supply_history(&state.pool).await? {
         Some(get_indexed_total_supply(&state.pool, &address).await?)
     } else {
@@ -145,26 +213,59 @@ pub async fn get_token_holders(
         stored.and_then(|(supply,)| supply)
     };
 
-    let balances: Vec<Erc20Balance> = sqlx::query_as(
-        "SELECT address, contract_address, balance, last_updated_block
-         FROM erc20_balances
-         WHERE contract_address = $1 AND balance > 0
-         ORDER BY balance DESC
-         LIMIT $2 OFFSET $3",
-    )
-    .bind(&address)
-    .bind(pagination.limit())
-    .bind(pagination.offset())
-    .fetch_all(&state.pool)
-    .await?;
+    // Served from the periodic ranking once the token has one; tokens not yet
+    // ranked fall back to sorting live balances.
+    let ranked: Option<(i64,)> =
+        sqlx::query_as("SELECT holders FROM erc20_holder_rank_state WHERE contract_address = $1")
+            .bind(&address)
+            .fetch_optional(&state.pool)
+            .await?;
+    let (total, balances): (i64, Vec<(i64, String, bigdecimal::BigDecimal)>) = match ranked {
+        Some((holders,)) => {
+            let rows = sqlx::query_as(
+                "SELECT rank, address, balance
+                 FROM erc20_holder_ranks
+                 WHERE contract_address = $1 AND rank > $2
+                 ORDER BY rank
+                 LIMIT $3",
+            )
+            .bind(&address)
+            .bind(pagination.offset())
+            .bind(pagination.limit())
+            .fetch_all(&state.pool)
+            .await?;
+            (holders, rows)
+        }
+        None => {
+            let (total,): (i64,) = sqlx::query_as(
+                "SELECT COUNT(*) FROM erc20_balances WHERE contract_address = $1 AND balance > 0",
+            )
+            .bind(&address)
+            .fetch_one(&state.pool)
+            .await?;
+            let rows = sqlx::query_as(
+                "SELECT ROW_NUMBER() OVER (ORDER BY balance DESC, address), address, balance
+                 FROM erc20_balances
+                 WHERE contract_address = $1 AND balance > 0
+                 ORDER BY balance DESC, address
+                 LIMIT $3 OFFSET $2",
+            )
+            .bind(&address)
+            .bind(pagination.offset())
+            .bind(pagination.limit())
+            .fetch_all(&state.pool)
+            .await?;
+            (total, rows)
+        }
+    };
 
     // Convert to Erc20Holder with percentage
     let holders: Vec<Erc20Holder> = balances
         .into_iter()
-        .map(|b| {
+        .map(|(rank, holder, balance)| {
             let percentage = total_supply.as_ref().and_then(|ts| {
                 use bigdecimal::ToPrimitive;
-                let balance_f = b.balance.to_f64()?;
+                let balance_f = balance.to_f64()?;
                 let supply_f = ts.to_f64()?;
                 if supply_f > 0.0 {
                     Some((balance_f / supply_f) * 100.0)
@@ -173,18 +274,30 @@ pub async fn get_token_holders(
                 }
             });
             Erc20Holder {
-                address: b.address,
-                balance: b.balance,
+                rank,
+                address: holder,
+                balance,
                 percentage,
             }
         })
         .collect();
 
+    let decimals = if options.enabled() {
+        token_decimals(&state.pool, std::slice::from_ref(&address))
+            .await?
+            .remove(&address)
+    } else {
+        None
+    };
+    let holders = decorate(holders, options.enabled(), |h| {
+        display::amount(&h.balance, decimals, None)
+    });
+
     Ok(Json(PaginatedResponse::new(
         holders,
         pagination.page,
         pagination.limit,
-        total.0,
+        total,
     )))
 }
 
@@ -193,34 +306,81 @@ pub async fn get_token_transfers(
     State(state): State<Arc<AppState>>,
     Path(address): Path<String>,
     Query(pagination): Query<Pagination>,
-) -> ApiResult<Json<PaginatedResponse<Erc20Transfer>>> {
+    Query(options): Query<DisplayQuery>,
+) -> ApiResult<Json<PaginatedResponse<WithDisplay<Erc20Transfer>>>> {
     let address = normalize_address(&address);
-
-    let total: (i64,) =
-        sqlx::query_as("SELECT COUNT(*) FROM erc20_transfers WHERE contract_address = $1")
-            .bind(&address)
-            .fetch_one(&state.pool)
-            .await?;
-
-    let transfers: Vec<Erc20Transfer> = sqlx::query_as(
-        "SELECT id, tx_hash, log_index, contract_address, from_address, to_address, value, block_number, timestamp
-         FROM erc20_transfers
-         WHERE contract_address = $1
-         ORDER BY block_number DESC, log_index DESC
-         LIMIT $2 OFFSET $3",
+    let anchor = snapshot_anchor(&state, pagination.as_of_block).await?;
+
+    let segments = segments_newest_first(&state, TieredTable::Erc20Transfers, anchor_bound(anchor));
+    let count = count_token_transfers(&address);
+    let total = sum_segment_counts(&segments, &count).await?;
+    let fetch = |segment: Segment, limit: i64, offset: i64| {
+        let address = &address;
+        async move {
+            let sql = format!(
+                "SELECT id, tx_hash, log_index, contract_address, from_address, to_address, value, block_number, timestamp
+                 FROM {}
+                 WHERE contract_address = $1 AND block_number BETWEEN $2 AND $3
+                 ORDER BY {}
+                 LIMIT $4 OFFSET $5",
+                segment.table,
+                ChainRow::Log.order_by("", Direction::Desc)
+            );
+            sqlx::query_as::<_, Erc20Transfer>(&sql)
+                .bind(address)
+                .bind(segment.blocks.from)
+                .bind(segment.blocks.to)
+                .bind(limit)
+                .bind(offset)
+                .fetch_all(&segment.pool)
+                .await
+        }
+    };
+    let transfers = stitch_segments(
+        &segments,
+        pagination.offset(),
+        pagination.limit(),
+        fetch,
+        &count,
     )
-    .bind(&address)
-    .bind(pagination.limit())
-    .bind(pagination.offset())
-    .fetch_all(&state.pool)
     .await?;
 
-    Ok(Json(PaginatedResponse::new(
-        transfers,
-        pagination.page,
-        pagination.limit,
-        total.0,
-    )))
+    let decimals = if options.enabled() {
+        token_decimals(&state.pool, std::slice::from_ref(&address))
+            .await?
+            .remove(&address)
+    } else {
+        None
+    };
+    let transfers = decorate(transfers, options.enabled(), |t| {
+        display::amount(&t.value, decimals, Some(t.timestamp))
+    });
+
+    Ok(Json(
+        PaginatedResponse::new(transfers, pagination.page, pagination.limit, total)
+            .anchored_at(anchor),
+    ))
+}
+
+/// Counts one segment's transfers of the token at `address`.
+fn count_token_transfers<'a>(
+    address: &'a str,
+) -> impl Fn(Segment) -> BoxFuture<'a, Result<i64, sqlx::Error>> + 'a {
+    move |segment| {
+        Box::pin(async move {
+            let sql = format!(
+                "SELECT COUNT(*) FROM {}
+                 WHERE contract_address = $1 AND block_number BETWEEN $2 AND $3",
+                segment.table
+            );
+            sqlx::query_scalar(&sql)
+                .bind(address)
+                .bind(segment.blocks.from)
+                .bind(segment.blocks.to)
+                .fetch_one(&segment.pool)
+                .await
+        })
+    }
 }
 
 /// GET /api/addresses/:address/tokens - Get ERC-20 balances for address
@@ -228,7 +388,8 @@ pub async fn get_address_tokens(
     State(state): State<Arc<AppState>>,
     Path(address): Path<String>,
     Query(pagination): Query<Pagination>,
-) -> ApiResult<Json<PaginatedResponse<AddressTokenBalance>>> {
+    Query(options): Query<DisplayQuery>,
+) -> ApiResult<Json<PaginatedResponse<WithDisplay<AddressTokenBalance>>>> {
     let address = normalize_address(&address);
 
     let total: (i64,) = sqlx::query_as(
@@ -239,21 +400,29 @@ pub async fn get_address_tokens(
     .fetch_one(&state.pool)
     .await?;
 
-    let balances: Vec<AddressTokenBalance> = sqlx::query_as(
+    let balances: Vec<AddressTokenBalance> = sqlx::query_as(&format!(
         "SELECT b.address, b.contract_address, b.balance, b.last_updated_block,
-                c.name, c.symbol, c.decimals
+                c.name, c.symbol, c.decimals,
+                COALESCE(cur.status = 'verified', false) AS verified,
+                COALESCE(cur.status = 'flagged', false) AS flagged,
+                CASE WHEN ct.address <> c.address THEN ct.address END AS duplicate_of
          FROM erc20_balances b
          JOIN erc20_contracts c ON b.contract_address = c.address
+         {TOKEN_STANDING_JOINS}
          WHERE b.address = $1 AND b.balance > 0
-         ORDER BY b.balance DESC
-         LIMIT $2 OFFSET $3",
-    )
+         ORDER BY b.balance DESC, b.contract_address
+         LIMIT $2 OFFSET $3"
+    ))
     .bind(&address)
     .bind(pagination.limit())
     .bind(pagination.offset())
     .fetch_all(&state.pool)
     .await?;
 
+    let balances = decorate(balances, options.enabled(), |b| {
+        display::amount(&b.balance, Some(b.decimals), None)
+    });
+
     Ok(Json(PaginatedResponse::new(
         balances,
         pagination.page,
@@ -272,6 +441,9 @@ pub struct AddressTokenBalance {
     pub name: Option<String>,
     pub symbol: Option<String>,
     pub decimals: i16,
+    #[serde(flatten)]
+    #[sqlx(flatten)]
+    pub standing: TokenStanding,
 }
 
 /// Chart point returned by GET /api/tokens/:address/chart
@@ -279,6 +451,7 @@ pub struct AddressTokenBalance {
 pub struct TokenChartPoint {
     pub bucket: String,
     pub transfer_count: i64,
+    #[serde(serialize_with = "atlas_common::numeric::quantity")]
     pub volume: f64,
 }
 
@@ -287,6 +460,8 @@ pub struct TokenChartPoint {
 /// Returns transfer count and volume (in human-readable token units) per time
 /// bucket for the given token contract. Anchored to the latest transfer
 /// timestamp so charts show data even when the indexer is catching up.
+/// Transfers folded into daily summaries by the retention job count in the
+/// bucket holding the start of their day.
 pub async fn get_token_chart(
     State(state): State<Arc<AppState>>,
     Path(address): Path<String>,
@@ -305,42 +480,49 @@ pub async fn get_token_chart(
             .map(|(d,)| d)
             .unwrap_or(18);
 
-    let rows: Vec<(chrono::DateTime<Utc>, i64, bigdecimal::BigDecimal)> = sqlx::query_as(
-        r#"
-        WITH latest AS (SELECT MAX(timestamp) AS max_ts FROM blocks),
-        bounds AS (
-            SELECT
-                max_ts - $3 AS start_ts,
-                max_ts      AS end_ts
-            FROM latest
-        ),
-        agg AS (
-            SELECT
-                (b.start_ts + (((erc20_transfers.timestamp - b.start_ts) / $2) * $2))::bigint AS bucket_ts,
-                COUNT(*)::bigint                                                                AS transfer_count,
-                COALESCE(SUM(value), 0)                                                         AS volume
-            FROM erc20_transfers
-            CROSS JOIN bounds b
-            WHERE contract_address = $1
-              AND erc20_transfers.timestamp >= b.start_ts
-              AND erc20_transfers.timestamp <= b.end_ts
-            GROUP BY 1
-        )
-        SELECT
-            to_timestamp(gs::float8)                        AS bucket,
-            COALESCE(a.transfer_count, 0)::bigint           AS transfer_count,
-            COALESCE(a.volume, 0::numeric)                  AS volume
-        FROM bounds b
-        CROSS JOIN generate_series(b.start_ts, b.end_ts - $2, $2::bigint) AS gs
-        LEFT JOIN agg a ON a.bucket_ts = gs
-        ORDER BY gs ASC
-        "#,
-    )
+    let (latest,): (Option<i64>,) = sqlx::query_as("SELECT MAX(timestamp) FROM blocks")
+        .fetch_one(&state.pool)
+        .await?;
+    let Some(end_ts) = latest else {
+        return Ok(Json(Vec::new()));
+    };
+    let start_ts = end_ts - window.duration_secs();
+
+    // Buckets are summed over every tier, then over the daily summaries.
+    let mut buckets: HashMap<i64, (i64, bigdecimal::BigDecimal)> = HashMap::new();
+    let segments = state
+        .storage
+        .segments(TieredTable::Erc20Transfers, BlockRange::up_to(i64::MAX));
+    for segment in &segments {
+        let rows: Vec<(i64, i64, bigdecimal::BigDecimal)> =
+            sqlx::query_as(&token_chart_buckets_sql(&format!(
+                "SELECT timestamp AS ts, 1::bigint AS transfer_count, value
+                 FROM {}
+                 WHERE contract_address = $1 AND block_number BETWEEN $5 AND $6",
+                segment.table
+            )))
+            .bind(&address)
+            .bind(start_ts)
+            .bind(bucket_secs)
+            .bind(end_ts)
+            .bind(segment.blocks.from)
+            .bind(segment.blocks.to)
+            .fetch_all(&segment.pool)
+            .await?;
+        add_chart_buckets(&mut buckets, rows);
+    }
+    let rows: Vec<(i64, i64, bigdecimal::BigDecimal)> = sqlx::query_as(&token_chart_buckets_sql(
+        "SELECT (day - DATE '1970-01-01')::bigint * 86400 AS ts, transfer_count, value
+         FROM erc20_transfer_summaries
+         WHERE contract_address = $1",
+    ))
     .bind(&address)
+    .bind(start_ts)
     .bind(bucket_secs)
-    .bind(window.duration_secs())
+    .bind(end_ts)
     .fetch_all(&state.pool)
     .await?;
+    add_chart_buckets(&mut buckets, rows);
 
     let divisor = if decimals <= 18 {
         bigdecimal::BigDecimal::from(10_i64.pow(decimals as u32))
@@ -349,13 +531,18 @@ pub async fn get_token_chart(
             .parse::<bigdecimal::BigDecimal>()
             .unwrap_or(bigdecimal::BigDecimal::from(1))
     };
-    let points = rows
-        .into_iter()
-        .map(|(bucket, transfer_count, sum_value)| {
+    let points = (start_ts..=end_ts - bucket_secs)
+        .step_by(bucket_secs as usize)
+        .map(|bucket_ts| {
             use bigdecimal::ToPrimitive;
+            let (transfer_count, sum_value) = buckets.remove(&bucket_ts).unwrap_or_default();
             let volume = (&sum_value / &divisor).to_f64().unwrap_or(0.0);
             TokenChartPoint {
-                bucket: bucket.to_rfc3339(),
+                bucket: Utc
+                    .timestamp_opt(bucket_ts, 0)
+                    .single()
+                    .unwrap_or_default()
+                    .to_rfc3339(),
                 transfer_count,
                 volume,
             }
@@ -365,6 +552,160 @@ pub async fn get_token_chart(
     Ok(Json(points))
 }
 
+/// Per-bucket transfer count and raw volume of `transfers` (rows of `ts`,
+/// `transfer_count` and `value` for the token `$1`) between `$2` and `$4`,
+/// in buckets of `$3` seconds from `$2`.
+fn token_chart_buckets_sql(transfers: &str) -> String {
+    format!(
+        "SELECT ($2 + ((t.ts - $2) / $3) * $3)::bigint AS bucket_ts,
+                SUM(t.transfer_count)::bigint AS transfer_count,
+                COALESCE(SUM(t.value), 0) AS volume
+         FROM ({transfers}) t
+         WHERE t.ts >= $2 AND t.ts <= $4
+         GROUP BY 1"
+    )
+}
+
+fn add_chart_buckets(
+    buckets: &mut HashMap<i64, (i64, bigdecimal::BigDecimal)>,
+    rows: Vec<(i64, i64, bigdecimal::BigDecimal)>,
+) {
+    for (bucket_ts, transfer_count, volume) in rows {
+        let bucket = buckets.entry(bucket_ts).or_default();
+        bucket.0 += transfer_count;
+        bucket.1 += volume;
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TokenCurationRequest {
+    /// `verified` or `flagged`
+    pub status: String,
+    pub note: Option<String>,
+}
+
+/// Admin verdict on a token, with the token's name and symbol
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct TokenCuration {
+    pub address: String,
+    pub name: Option<String>,
+    pub symbol: Option<String>,
+    pub status: String,
+    pub note: Option<String>,
+    pub updated_at: chrono::DateTime<Utc>,
+}
+
+const MAX_CURATION_NOTE_LEN: usize = 500;
+
+const TOKEN_CURATION_SELECT: &str =
+    "SELECT cur.address, c.name, c.symbol, cur.status, cur.note, cur.updated_at
+     FROM token_curation cur
+     LEFT JOIN erc20_contracts c ON c.address = cur.address";
+
+/// GET /api/admin/tokens/curation - List admin token verdicts (admin)
+pub async fn list_token_curation(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+) -> ApiResult<Json<Vec<TokenCuration>>> {
+    require_admin(&state, &headers)?;
+    let entries: Vec<TokenCuration> = sqlx::query_as(&format!(
+        "{TOKEN_CURATION_SELECT} ORDER BY cur.updated_at DESC, cur.address"
+    ))
+    .fetch_all(&state.pool)
+    .await?;
+    Ok(Json(entries))
+}
+
+/// PUT /api/admin/tokens/:address/curation - Mark a token verified or flagged (admin)
+pub async fn set_token_curation(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Path(address): Path<String>,
+    Json(request): Json<TokenCurationRequest>,
+) -> ApiResult<Json<TokenCuration>> {
+    require_admin(&state, &headers)?;
+    let address = normalize_address(&address);
+    if request.status != "verified" && request.status != "flagged" {
+        return Err(
+            AtlasError::InvalidInput("status must be verified or flagged".to_string()).into(),
+        );
+    }
+    let note = request
+        .note
+        .as_deref()
+        .map(str::trim)
+        .filter(|note| !note.is_empty());
+    if note.is_some_and(|note| note.len() > MAX_CURATION_NOTE_LEN) {
+        return Err(AtlasError::InvalidInput(format!(
+            "note must be at most {MAX_CURATION_NOTE_LEN} bytes"
+        ))
+        .into());
+    }
+
+    let symbol = token_symbol(&state.pool, &address).await?;
+    sqlx::query(
+        "INSERT INTO token_curation (address, status, note, updated_at)
+         VALUES ($1, $2, $3, NOW())
+         ON CONFLICT (address) DO UPDATE SET
+             status = EXCLUDED.status, note = EXCLUDED.note, updated_at = EXCLUDED.updated_at",
+    )
+    .bind(&address)
+    .bind(&request.status)
+    .bind(note)
+    .execute(&state.pool)
+    .await?;
+    if let Some(symbol) = symbol.as_deref() {
+        resolve_symbols(&state.pool, Some(symbol)).await?;
+    }
+
+    let entry: TokenCuration =
+        sqlx::query_as(&format!("{TOKEN_CURATION_SELECT} WHERE cur.address = $1"))
+            .bind(&address)
+            .fetch_one(&state.pool)
+            .await?;
+    Ok(Json(entry))
+}
+
+/// DELETE /api/admin/tokens/:address/curation - Clear a token's verdict (admin)
+pub async fn delete_token_curation(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Path(address): Path<String>,
+) -> ApiResult<StatusCode> {
+    require_admin(&state, &headers)?;
+    let address = normalize_address(&address);
+
+    let deleted = sqlx::query("DELETE FROM token_curation WHERE address = $1")
+        .bind(&address)
+        .execute(&state.pool)
+        .await?
+        .rows_affected();
+    if deleted == 0 {
+        return Err(AtlasError::NotFound(format!("No curation for {address}")).into());
+    }
+    let symbol: Option<(Option<String>,)> =
+        sqlx::query_as("SELECT symbol FROM erc20_contracts WHERE address = $1")
+            .bind(&address)
+            .fetch_optional(&state.pool)
+            .await?;
+    if let Some((Some(symbol),)) = symbol {
+        resolve_symbols(&state.pool, Some(&symbol)).await?;
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Symbol of an indexed token; a 404 for addresses that are not ERC-20 tokens.
+async fn token_symbol(pool: &PgPool, address: &str) -> Result<Option<String>, AtlasError> {
+    let row: Option<(Option<String>,)> =
+        sqlx::query_as("SELECT symbol FROM erc20_contracts WHERE address = $1")
+            .bind(address)
+            .fetch_optional(pool)
+            .await?;
+    row.map(|(symbol,)| symbol)
+        .ok_or_else(|| AtlasError::NotFound(format!("Token {} not found", address)))
+}
+
 fn normalize_address(address: &str) -> String {
     if address.starts_with("0x") {
         address.to_lowercase()
@@ -2,58 +2,239 @@ use axum::{
     extract::{Path, Query, State},
     Json,
 };
+use bigdecimal::BigDecimal;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
 use std::sync::Arc;
 
-use super::get_table_count;
+use super::display::{self, decorate, token_decimals, DisplayQuery, WithDisplay};
+use super::{
+    anchor_bound, canonical_tx_block, get_table_count, snapshot_anchor, transaction_segment,
+};
 use crate::api::error::ApiResult;
 use crate::api::AppState;
+use crate::indexer::calldata_index::{self, PREFIX_BYTES, PREFIX_EXPRESSION};
+use crate::ordering::{ChainRow, Direction};
+use atlas_common::storage::TieredTable;
 use atlas_common::{
     AtlasError, Erc20Transfer, NftTransfer, PaginatedResponse, Pagination, Transaction,
 };
 
+/// Transaction detail with its fee breakdown.
+#[derive(Serialize, sqlx::FromRow)]
+pub struct TransactionResponse {
+    #[serde(flatten)]
+    #[sqlx(flatten)]
+    pub transaction: Transaction,
+    #[sqlx(flatten)]
+    pub fee: FeeBreakdown,
+}
+
+/// Columns of a `TransactionResponse` selected from `transactions`.
+pub(crate) const TRANSACTION_RESPONSE_COLUMNS: &str =
+    "hash, block_number, block_index, from_address, to_address, value, gas_price, gas_used, input_data, status, contract_created, timestamp,
+     (gas_used * gas_price)::text AS execution_fee,
+     l1_fee::text AS l1_fee,
+     (gas_used * gas_price + COALESCE(l1_fee, 0))::text AS total_fee,
+     l1_gas_used, l1_gas_price::text AS l1_gas_price, l1_blob_base_fee::text AS l1_blob_base_fee,
+     l1_fee_scalar, l1_base_fee_scalar, l1_blob_base_fee_scalar";
+
+/// Fee paid by a transaction, in wei. On rollups that report it in receipts
+/// (OP Stack) the total includes the L1 data fee; the `l1_*` fields are null
+/// elsewhere.
+#[derive(Serialize, sqlx::FromRow)]
+pub struct FeeBreakdown {
+    /// `gas_used * gas_price`
+    pub execution_fee: String,
+    pub l1_fee: Option<String>,
+    pub total_fee: String,
+    #[serde(serialize_with = "atlas_common::numeric::option_quantity")]
+    pub l1_gas_used: Option<i64>,
+    pub l1_gas_price: Option<String>,
+    pub l1_blob_base_fee: Option<String>,
+    /// Pre-Ecotone fee scalar (decimal)
+    pub l1_fee_scalar: Option<String>,
+    pub l1_base_fee_scalar: Option<i64>,
+    pub l1_blob_base_fee_scalar: Option<i64>,
+}
+
+/// Filters of `GET /api/transactions`.
+#[derive(Debug, Default, Deserialize)]
+pub struct TransactionFilter {
+    /// Hex calldata the input must start with: a 4-byte selector, optionally
+    /// followed by up to 32 bytes of arguments.
+    pub input_prefix: Option<String>,
+}
+
+/// Wrap transactions with their native `value` and execution fee for
+/// `?include=display`.
+pub(crate) fn decorate_transactions(
+    state: &AppState,
+    transactions: Vec<Transaction>,
+    options: &DisplayQuery,
+) -> Vec<WithDisplay<Transaction>> {
+    decorate(transactions, options.enabled(), |tx| {
+        let fee = &tx.gas_price * BigDecimal::from(tx.gas_used);
+        state
+            .native_currency
+            .amount(&tx.value, Some(&fee), Some(tx.timestamp))
+    })
+}
+
 pub async fn list_transactions(
     State(state): State<Arc<AppState>>,
     Query(pagination): Query<Pagination>,
-) -> ApiResult<Json<PaginatedResponse<Transaction>>> {
+    Query(filter): Query<TransactionFilter>,
+    Query(options): Query<DisplayQuery>,
+) -> ApiResult<Json<PaginatedResponse<WithDisplay<Transaction>>>> {
+    if let Some(prefix) = filter.input_prefix.as_deref() {
+        return list_transactions_by_input_prefix(&state, &pagination, &options, prefix).await;
+    }
+
     // Use optimized count (approximate for large tables, exact for small)
     let total = get_table_count(&state.pool, "transactions").await?;
+    let anchor = snapshot_anchor(&state, pagination.as_of_block).await?;
 
-    let transactions: Vec<Transaction> = sqlx::query_as(
+    let transactions: Vec<Transaction> = sqlx::query_as(&format!(
         "SELECT hash, block_number, block_index, from_address, to_address, value, gas_price, gas_used, input_data, status, contract_created, timestamp
          FROM transactions
-         ORDER BY block_number DESC, block_index DESC
-         LIMIT $1 OFFSET $2"
-    )
+         WHERE block_number <= $3
+         ORDER BY {}
+         LIMIT $1 OFFSET $2",
+        ChainRow::Transaction.order_by("", Direction::Desc)
+    ))
     .bind(pagination.limit())
     .bind(pagination.offset())
+    .bind(anchor_bound(anchor))
     .fetch_all(&state.pool)
     .await?;
 
-    Ok(Json(PaginatedResponse::new(
-        transactions,
-        pagination.page,
-        pagination.limit,
-        total,
-    )))
+    Ok(Json(
+        PaginatedResponse::new(
+            decorate_transactions(&state, transactions, &options),
+            pagination.page,
+            pagination.limit,
+            total,
+        )
+        .anchored_at(anchor),
+    ))
+}
+
+/// Transactions whose calldata starts with `prefix`, served from the
+/// opt-in calldata prefix index. The total is not counted: `has_more` says
+/// whether another page follows.
+async fn list_transactions_by_input_prefix(
+    state: &AppState,
+    pagination: &Pagination,
+    options: &DisplayQuery,
+    prefix: &str,
+) -> ApiResult<Json<PaginatedResponse<WithDisplay<Transaction>>>> {
+    let prefix = parse_input_prefix(prefix)?;
+    if !calldata_index::is_ready(&state.pool).await? {
+        return Err(AtlasError::LimitExceeded(
+            "input_prefix requires the calldata prefix index, which is disabled or still building (CALLDATA_PREFIX_INDEX)"
+                .to_string(),
+        )
+        .into());
+    }
+    let anchor = snapshot_anchor(state, pagination.as_of_block).await?;
+    let (lower, upper) = calldata_index::prefix_range(&prefix);
+
+    let transactions: Vec<Transaction> = sqlx::query_as(&format!(
+        "SELECT hash, block_number, block_index, from_address, to_address, value, gas_price, gas_used, input_data, status, contract_created, timestamp
+         FROM transactions
+         WHERE {PREFIX_EXPRESSION} BETWEEN $1 AND $2 AND block_number <= $5
+         ORDER BY {}
+         LIMIT $3 OFFSET $4",
+        ChainRow::Transaction.order_by("", Direction::Desc)
+    ))
+    .bind(lower)
+    .bind(upper)
+    .bind(pagination.limit() + 1)
+    .bind(pagination.offset())
+    .bind(anchor_bound(anchor))
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(Json(
+        PaginatedResponse::from_probe(
+            decorate_transactions(state, transactions, options),
+            pagination.page,
+            pagination.limit() as u32,
+            pagination.offset(),
+            None,
+            false,
+        )
+        .anchored_at(anchor),
+    ))
+}
+
+/// Decode an `input_prefix`: `0x`-prefixed hex of a selector plus at most
+/// one argument word.
+fn parse_input_prefix(prefix: &str) -> Result<Vec<u8>, AtlasError> {
+    let invalid = || {
+        AtlasError::InvalidInput(format!(
+            "input_prefix must be 0x-prefixed hex of 4 to {PREFIX_BYTES} bytes"
+        ))
+    };
+    let bytes = prefix
+        .strip_prefix("0x")
+        .and_then(|hex| hex::decode(hex).ok())
+        .ok_or_else(invalid)?;
+    if !(4..=PREFIX_BYTES).contains(&bytes.len()) {
+        return Err(invalid());
+    }
+    Ok(bytes)
 }
 
 pub async fn get_transaction(
     State(state): State<Arc<AppState>>,
     Path(hash): Path<String>,
-) -> ApiResult<Json<Transaction>> {
+    Query(options): Query<DisplayQuery>,
+) -> ApiResult<Json<WithDisplay<TransactionResponse>>> {
     let hash = normalize_hash(&hash);
+    let not_found = || AtlasError::NotFound(format!("Transaction {} not found", hash));
 
-    let transaction: Transaction = sqlx::query_as(
-        "SELECT hash, block_number, block_index, from_address, to_address, value, gas_price, gas_used, input_data, status, contract_created, timestamp
-         FROM transactions
-         WHERE hash = $1"
-    )
+    // The same hash can be indexed in a block that was later reorged out.
+    let block_number = canonical_tx_block(&state.pool, &hash)
+        .await?
+        .ok_or_else(not_found)?;
+    let transaction: TransactionResponse = sqlx::query_as(&format!(
+        "SELECT {} FROM transactions WHERE hash = $1 AND block_number = $2",
+        TRANSACTION_RESPONSE_COLUMNS
+    ))
     .bind(&hash)
+    .bind(block_number)
     .fetch_optional(&state.pool)
     .await?
-    .ok_or_else(|| AtlasError::NotFound(format!("Transaction {} not found", hash)))?;
+    .ok_or_else(not_found)?;
+
+    Ok(Json(decorate_transaction_response(
+        &state,
+        transaction,
+        &options,
+    )))
+}
 
-    Ok(Json(transaction))
+/// Wrap a transaction detail with its native `value` and total fee (which
+/// includes the L1 data fee where there is one) for `?include=display`.
+pub(crate) fn decorate_transaction_response(
+    state: &AppState,
+    transaction: TransactionResponse,
+    options: &DisplayQuery,
+) -> WithDisplay<TransactionResponse> {
+    let display = options.enabled().then(|| {
+        let fee = BigDecimal::from_str(&transaction.fee.total_fee).ok();
+        state.native_currency.amount(
+            &transaction.transaction.value,
+            fee.as_ref(),
+            Some(transaction.transaction.timestamp),
+        )
+    });
+    WithDisplay {
+        item: transaction,
+        display,
+    }
 }
 
 /// GET /api/transactions/{hash}/erc20-transfers - Get all ERC-20 transfers in a transaction
@@ -61,27 +242,55 @@ pub async fn get_transaction_erc20_transfers(
     State(state): State<Arc<AppState>>,
     Path(hash): Path<String>,
     Query(pagination): Query<Pagination>,
-) -> ApiResult<Json<PaginatedResponse<Erc20Transfer>>> {
+    Query(options): Query<DisplayQuery>,
+) -> ApiResult<Json<PaginatedResponse<WithDisplay<Erc20Transfer>>>> {
     let hash = normalize_hash(&hash);
+    let (segment, block_number) =
+        transaction_segment(&state, TieredTable::Erc20Transfers, &hash).await?;
 
-    let total: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM erc20_transfers WHERE tx_hash = $1")
-        .bind(&hash)
-        .fetch_one(&state.pool)
-        .await?;
+    let total: (i64,) = sqlx::query_as(&format!(
+        "SELECT COUNT(*) FROM {}
+         WHERE tx_hash = $1 AND ($2::bigint IS NULL OR block_number = $2)",
+        segment.table
+    ))
+    .bind(&hash)
+    .bind(block_number)
+    .fetch_one(&segment.pool)
+    .await?;
 
-    let transfers: Vec<Erc20Transfer> = sqlx::query_as(
+    let transfers: Vec<Erc20Transfer> = sqlx::query_as(&format!(
         "SELECT id, tx_hash, log_index, contract_address, from_address, to_address, value, block_number, timestamp
-         FROM erc20_transfers
-         WHERE tx_hash = $1
-         ORDER BY log_index ASC
-         LIMIT $2 OFFSET $3"
-    )
+         FROM {}
+         WHERE tx_hash = $1 AND ($2::bigint IS NULL OR block_number = $2)
+         ORDER BY {}
+         LIMIT $3 OFFSET $4",
+        segment.table,
+        ChainRow::Log.order_by("", Direction::Asc)
+    ))
     .bind(&hash)
+    .bind(block_number)
     .bind(pagination.limit())
     .bind(pagination.offset())
-    .fetch_all(&state.pool)
+    .fetch_all(&segment.pool)
     .await?;
 
+    let decimals = if options.enabled() {
+        let contracts: Vec<String> = transfers
+            .iter()
+            .map(|t| t.contract_address.clone())
+            .collect();
+        token_decimals(&state.pool, &contracts).await?
+    } else {
+        Default::default()
+    };
+    let transfers = decorate(transfers, options.enabled(), |t| {
+        display::amount(
+            &t.value,
+            decimals.get(&t.contract_address).copied(),
+            Some(t.timestamp),
+        )
+    });
+
     Ok(Json(PaginatedResponse::new(
         transfers,
         pagination.page,
@@ -103,13 +312,14 @@ pub async fn get_transaction_nft_transfers(
         .fetch_one(&state.pool)
         .await?;
 
-    let transfers: Vec<NftTransfer> = sqlx::query_as(
+    let transfers: Vec<NftTransfer> = sqlx::query_as(&format!(
         "SELECT id, tx_hash, log_index, contract_address, token_id, from_address, to_address, block_number, timestamp
          FROM nft_transfers
          WHERE tx_hash = $1
-         ORDER BY log_index ASC
-         LIMIT $2 OFFSET $3"
-    )
+         ORDER BY {}
+         LIMIT $2 OFFSET $3",
+        ChainRow::Log.order_by("", Direction::Asc)
+    ))
     .bind(&hash)
     .bind(pagination.limit())
     .bind(pagination.offset())
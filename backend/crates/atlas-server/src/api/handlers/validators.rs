@@ -0,0 +1,202 @@
+//! Validators and sequencers of the staking registry, from the events the
+//! `validators` block processor decodes.
+//!
+//! Stakes are summed from the events' amounts: `register` and `stake` add,
+//! `unstake`, `slash` and `exit` subtract. The status is set by the latest
+//! `register`/`unjail` (active), `jail` (jailed) or `exit` (exited) event,
+//! and is `active` for a validator seen only through stake changes.
+
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use crate::api::error::ApiResult;
+use crate::api::AppState;
+use crate::ordering::{ChainRow, Direction};
+use atlas_common::encoding::{address_to_hex, parse_address};
+use atlas_common::{AtlasError, PaginatedResponse, Pagination};
+
+/// Events listed in the validator detail; the rest are paged.
+const RECENT_EVENTS: i64 = 10;
+
+/// Stake and status per validator, optionally for one validator ($1) or one
+/// status ($2), largest stake first.
+const VALIDATORS_SQL: &str = "
+    SELECT * FROM (
+        SELECT validator AS address,
+               CASE (array_agg(kind ORDER BY block_number DESC, log_index DESC)
+                         FILTER (WHERE kind IN ('register', 'unjail', 'jail', 'exit')))[1]
+                   WHEN 'jail' THEN 'jailed'
+                   WHEN 'exit' THEN 'exited'
+                   ELSE 'active'
+               END AS status,
+               GREATEST(COALESCE(SUM(CASE
+                   WHEN kind IN ('register', 'stake') THEN amount
+                   WHEN kind IN ('unstake', 'slash', 'exit') THEN -amount
+               END), 0), 0)::text AS stake,
+               MIN(block_number) AS first_block,
+               MAX(block_number) AS last_block,
+               COUNT(*) AS events
+        FROM validator_events
+        WHERE $1::text IS NULL OR validator = $1
+        GROUP BY validator
+    ) v
+    WHERE $2::text IS NULL OR status = $2
+    ORDER BY stake::numeric DESC, address
+    LIMIT $3 OFFSET $4";
+
+#[derive(Deserialize, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum ValidatorStatus {
+    Active,
+    Jailed,
+    Exited,
+}
+
+impl ValidatorStatus {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Active => "active",
+            Self::Jailed => "jailed",
+            Self::Exited => "exited",
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct ValidatorsQuery {
+    pub status: Option<ValidatorStatus>,
+    #[serde(flatten)]
+    pub pagination: Pagination,
+}
+
+#[derive(Serialize, sqlx::FromRow)]
+pub struct Validator {
+    pub address: String,
+    /// `active`, `jailed` or `exited`
+    pub status: String,
+    /// Current stake in the registry's raw units
+    pub stake: String,
+    pub first_block: i64,
+    pub last_block: i64,
+    pub events: i64,
+}
+
+#[derive(Serialize)]
+pub struct ValidatorDetail {
+    #[serde(flatten)]
+    pub validator: Validator,
+    /// Newest first
+    pub recent_events: Vec<ValidatorEvent>,
+}
+
+#[derive(Serialize, sqlx::FromRow)]
+pub struct ValidatorEvent {
+    pub block_number: i64,
+    pub log_index: i32,
+    pub tx_hash: String,
+    /// Event name in the registry's ABI
+    pub event: String,
+    /// `register`, `exit`, `stake`, `unstake`, `slash`, `jail` or `unjail`
+    pub kind: String,
+    pub amount: Option<String>,
+    pub timestamp: i64,
+}
+
+/// GET /api/validators?status= - Validators by stake, largest first
+pub async fn list_validators(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<ValidatorsQuery>,
+) -> ApiResult<Json<PaginatedResponse<Validator>>> {
+    let pagination = params.pagination;
+    let validators: Vec<Validator> = sqlx::query_as(VALIDATORS_SQL)
+        .bind(None::<String>)
+        .bind(params.status.map(ValidatorStatus::as_str))
+        .bind(pagination.limit() + 1)
+        .bind(pagination.offset())
+        .fetch_all(&state.pool)
+        .await?;
+
+    Ok(Json(PaginatedResponse::from_probe(
+        validators,
+        pagination.page,
+        pagination.limit() as u32,
+        pagination.offset(),
+        None,
+        false,
+    )))
+}
+
+/// GET /api/validators/{address} - Stake, status and recent registry events
+pub async fn get_validator(
+    State(state): State<Arc<AppState>>,
+    Path(address): Path<String>,
+) -> ApiResult<Json<ValidatorDetail>> {
+    let address = address_to_hex(&parse_address(&address)?);
+    let validator: Validator = sqlx::query_as(VALIDATORS_SQL)
+        .bind(&address)
+        .bind(None::<String>)
+        .bind(1i64)
+        .bind(0i64)
+        .fetch_optional(&state.pool)
+        .await?
+        .ok_or_else(|| AtlasError::NotFound(format!("Validator {address} not found")))?;
+    let recent_events = fetch_events(&state, &address, RECENT_EVENTS, 0).await?;
+
+    Ok(Json(ValidatorDetail {
+        validator,
+        recent_events,
+    }))
+}
+
+/// GET /api/validators/{address}/events - Registry events, newest first
+pub async fn list_validator_events(
+    State(state): State<Arc<AppState>>,
+    Path(address): Path<String>,
+    Query(pagination): Query<Pagination>,
+) -> ApiResult<Json<PaginatedResponse<ValidatorEvent>>> {
+    let address = address_to_hex(&parse_address(&address)?);
+    let events = fetch_events(
+        &state,
+        &address,
+        pagination.limit() + 1,
+        pagination.offset(),
+    )
+    .await?;
+    if events.is_empty() && pagination.offset() == 0 {
+        return Err(AtlasError::NotFound(format!("Validator {address} not found")).into());
+    }
+
+    Ok(Json(PaginatedResponse::from_probe(
+        events,
+        pagination.page,
+        pagination.limit() as u32,
+        pagination.offset(),
+        None,
+        false,
+    )))
+}
+
+async fn fetch_events(
+    state: &AppState,
+    validator: &str,
+    limit: i64,
+    offset: i64,
+) -> ApiResult<Vec<ValidatorEvent>> {
+    Ok(sqlx::query_as(&format!(
+        "SELECT block_number, log_index, tx_hash, event, kind, amount::text AS amount, timestamp
+         FROM validator_events
+         WHERE validator = $1
+         ORDER BY {}
+         LIMIT $2 OFFSET $3",
+        ChainRow::Log.order_by("", Direction::Desc)
+    ))
+    .bind(validator)
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(&state.pool)
+    .await?)
+}
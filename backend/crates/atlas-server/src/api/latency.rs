@@ -0,0 +1,568 @@
+//! Per-route latency percentiles and slow request logging.
+//!
+//! [`latency_middleware`] times every API request by its route pattern and
+//! keeps the latest [`LATENCY_WINDOW`] durations per route, from which
+//! `/api/admin/slow-queries` reports p50/p95/p99. A request taking at least
+//! the slow threshold is logged with the SQL it ran: sqlx reports each
+//! statement as a `sqlx::query` event, which [`query_capture_layer`] collects
+//! for the request being handled. Statements carry `$n` placeholders rather
+//! than bound values, and [`sanitize_sql`] masks any literal left in the
+//! text, so logged SQL holds no request parameters. Queries run on spawned
+//! tasks are not captured.
+
+use axum::{
+    extract::{MatchedPath, Request, State},
+    middleware::Next,
+    response::Response,
+};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::cell::RefCell;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::field::{Field, Visit};
+use tracing::subscriber::Interest;
+use tracing::{Event, Subscriber};
+use tracing_subscriber::filter::dynamic_filter_fn;
+use tracing_subscriber::layer::{Context, Layer};
+use tracing_subscriber::registry::LookupSpan;
+
+use crate::api::request_id::RequestId;
+use crate::api::AppState;
+
+/// Durations kept per route for percentiles.
+pub const LATENCY_WINDOW: usize = 1024;
+
+/// Slow requests kept for `/api/admin/slow-queries`, newest first.
+pub const SLOW_REQUEST_HISTORY: usize = 100;
+
+/// Statements kept per slow request; the rest are only counted.
+const MAX_CAPTURED_QUERIES: usize = 50;
+
+/// Longest statement kept, after sanitizing.
+const MAX_SQL_LEN: usize = 2000;
+
+/// Default slow request threshold.
+pub const DEFAULT_SLOW_REQUEST_MS: u64 = 1000;
+
+tokio::task_local! {
+    static CAPTURED_QUERIES: RefCell<Vec<CapturedQuery>>;
+}
+
+/// A statement run while handling a request, as sqlx reported it.
+#[derive(Debug, Clone)]
+struct CapturedQuery {
+    sql: String,
+    duration: Duration,
+    rows: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SlowQuery {
+    pub sql: String,
+    pub duration_ms: f64,
+    pub rows: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SlowRequest {
+    pub request_id: Option<String>,
+    pub method: String,
+    pub route: String,
+    pub status: u16,
+    pub duration_ms: f64,
+    pub at: DateTime<Utc>,
+    /// Statements run, including those not listed in `queries`
+    pub query_count: usize,
+    pub queries: Vec<SlowQuery>,
+}
+
+/// Latency percentiles of one route over its latest requests.
+#[derive(Debug, Clone, Serialize)]
+pub struct EndpointLatency {
+    pub method: String,
+    pub route: String,
+    /// Requests since startup
+    pub requests: u64,
+    /// Requests at or over the slow threshold since startup
+    pub slow_requests: u64,
+    /// Requests the percentiles are computed from
+    pub window: usize,
+    pub p50_ms: f64,
+    pub p95_ms: f64,
+    pub p99_ms: f64,
+    pub max_ms: f64,
+}
+
+#[derive(Default)]
+struct RouteLatency {
+    requests: u64,
+    slow_requests: u64,
+    /// Ring buffer of the latest durations, in microseconds
+    samples: Vec<u64>,
+    next: usize,
+}
+
+impl RouteLatency {
+    fn record(&mut self, duration: Duration, slow: bool) {
+        self.requests += 1;
+        self.slow_requests += u64::from(slow);
+        let micros = u64::try_from(duration.as_micros()).unwrap_or(u64::MAX);
+        if self.samples.len() < LATENCY_WINDOW {
+            self.samples.push(micros);
+        } else {
+            self.samples[self.next] = micros;
+        }
+        self.next = (self.next + 1) % LATENCY_WINDOW;
+    }
+}
+
+#[derive(Default)]
+struct TrackerState {
+    routes: HashMap<(String, String), RouteLatency>,
+    slow_requests: VecDeque<SlowRequest>,
+}
+
+/// Request latencies by route and the latest slow requests.
+///
+/// A `slow_threshold` of zero turns slow request logging and SQL capture off;
+/// latencies are still tracked.
+pub struct LatencyTracker {
+    slow_threshold: Duration,
+    state: Mutex<TrackerState>,
+}
+
+impl Default for LatencyTracker {
+    fn default() -> Self {
+        Self::new(Duration::from_millis(DEFAULT_SLOW_REQUEST_MS))
+    }
+}
+
+impl LatencyTracker {
+    pub fn new(slow_threshold: Duration) -> Self {
+        Self {
+            slow_threshold,
+            state: Mutex::new(TrackerState::default()),
+        }
+    }
+
+    pub fn slow_threshold(&self) -> Duration {
+        self.slow_threshold
+    }
+
+    fn is_slow(&self, duration: Duration) -> bool {
+        !self.slow_threshold.is_zero() && duration >= self.slow_threshold
+    }
+
+    fn record(&self, method: &str, route: &str, duration: Duration) {
+        let slow = self.is_slow(duration);
+        let mut state = self.state.lock().expect("latency tracker lock poisoned");
+        state
+            .routes
+            .entry((method.to_string(), route.to_string()))
+            .or_default()
+            .record(duration, slow);
+    }
+
+    fn record_slow(&self, request: SlowRequest) {
+        let mut state = self.state.lock().expect("latency tracker lock poisoned");
+        if state.slow_requests.len() == SLOW_REQUEST_HISTORY {
+            state.slow_requests.pop_back();
+        }
+        state.slow_requests.push_front(request);
+    }
+
+    /// Routes with the highest p95 first, at most `limit` of them.
+    pub fn endpoints(&self, limit: usize) -> Vec<EndpointLatency> {
+        let state = self.state.lock().expect("latency tracker lock poisoned");
+        let mut endpoints: Vec<EndpointLatency> = state
+            .routes
+            .iter()
+            .map(|((method, route), latency)| {
+                let mut samples = latency.samples.clone();
+                samples.sort_unstable();
+                EndpointLatency {
+                    method: method.clone(),
+                    route: route.clone(),
+                    requests: latency.requests,
+                    slow_requests: latency.slow_requests,
+                    window: samples.len(),
+                    p50_ms: percentile_ms(&samples, 50),
+                    p95_ms: percentile_ms(&samples, 95),
+                    p99_ms: percentile_ms(&samples, 99),
+                    max_ms: samples.last().map_or(0.0, |max| micros_to_ms(*max)),
+                }
+            })
+            .collect();
+        drop(state);
+        endpoints.sort_by(|a, b| {
+            b.p95_ms
+                .total_cmp(&a.p95_ms)
+                .then_with(|| a.route.cmp(&b.route))
+                .then_with(|| a.method.cmp(&b.method))
+        });
+        endpoints.truncate(limit);
+        endpoints
+    }
+
+    /// The latest slow requests, newest first.
+    pub fn slow_requests(&self) -> Vec<SlowRequest> {
+        let state = self.state.lock().expect("latency tracker lock poisoned");
+        state.slow_requests.iter().cloned().collect()
+    }
+}
+
+/// Nearest-rank percentile of sorted microsecond samples, in milliseconds.
+fn percentile_ms(sorted: &[u64], percentile: usize) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let rank = (percentile * sorted.len()).div_ceil(100).max(1);
+    micros_to_ms(sorted[rank - 1])
+}
+
+fn micros_to_ms(micros: u64) -> f64 {
+    micros as f64 / 1000.0
+}
+
+fn duration_ms(duration: Duration) -> f64 {
+    (duration.as_secs_f64() * 1_000_000.0).round() / 1000.0
+}
+
+/// Time the request by route and log it when slow, with its SQL.
+///
+/// Placed after routing so `MatchedPath` is available.
+pub async fn latency_middleware(
+    State(state): State<Arc<AppState>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let method = request.method().to_string();
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| "unknown".to_string());
+    let request_id = request
+        .extensions()
+        .get::<RequestId>()
+        .map(|id| id.0.clone());
+    let tracker = &state.latency;
+
+    let start = Instant::now();
+    let (response, queries) = if tracker.slow_threshold.is_zero() {
+        (next.run(request).await, Vec::new())
+    } else {
+        CAPTURED_QUERIES
+            .scope(RefCell::new(Vec::new()), async {
+                let response = next.run(request).await;
+                (response, CAPTURED_QUERIES.with(|queries| queries.take()))
+            })
+            .await
+    };
+    let duration = start.elapsed();
+
+    tracker.record(&method, &route, duration);
+    if tracker.is_slow(duration) {
+        let slow = slow_request(
+            request_id,
+            method,
+            route,
+            response.status().as_u16(),
+            duration,
+            queries,
+        );
+        log_slow_request(&slow);
+        tracker.record_slow(slow);
+    }
+    response
+}
+
+fn slow_request(
+    request_id: Option<String>,
+    method: String,
+    route: String,
+    status: u16,
+    duration: Duration,
+    queries: Vec<CapturedQuery>,
+) -> SlowRequest {
+    let query_count = queries.len();
+    let queries = queries
+        .into_iter()
+        .take(MAX_CAPTURED_QUERIES)
+        .map(|query| SlowQuery {
+            sql: sanitize_sql(&query.sql),
+            duration_ms: duration_ms(query.duration),
+            rows: query.rows,
+        })
+        .collect();
+    SlowRequest {
+        request_id,
+        method,
+        route,
+        status,
+        duration_ms: duration_ms(duration),
+        at: Utc::now(),
+        query_count,
+        queries,
+    }
+}
+
+fn log_slow_request(request: &SlowRequest) {
+    let queries = request
+        .queries
+        .iter()
+        .map(|query| format!("[{:.1}ms] {}", query.duration_ms, query.sql))
+        .collect::<Vec<_>>()
+        .join(" | ");
+    tracing::warn!(
+        request_id = request.request_id.as_deref().unwrap_or_default(),
+        method = %request.method,
+        route = %request.route,
+        status = request.status,
+        duration_ms = request.duration_ms,
+        query_count = request.query_count,
+        queries = %queries,
+        "slow request"
+    );
+}
+
+/// Mask string and numeric literals with `?` and collapse whitespace.
+/// `$n` placeholders and identifiers such as `erc20_transfers` are kept.
+pub fn sanitize_sql(sql: &str) -> String {
+    let mut out = String::with_capacity(sql.len().min(MAX_SQL_LEN));
+    let mut chars = sql.chars().peekable();
+    // Whether the previous character continues an identifier or placeholder,
+    // so the digits after it are not a literal.
+    let mut in_word = false;
+    while let Some(c) = chars.next() {
+        if c == '\'' {
+            // '' inside a literal is an escaped quote
+            while let Some(c) = chars.next() {
+                if c == '\'' && chars.next_if_eq(&'\'').is_none() {
+                    break;
+                }
+            }
+            out.push('?');
+            in_word = false;
+        } else if c.is_ascii_digit() && !in_word {
+            while chars.next_if(|c| c.is_ascii_digit() || *c == '.').is_some() {}
+            out.push('?');
+        } else if c.is_whitespace() {
+            while chars.next_if(|c| c.is_whitespace()).is_some() {}
+            if !out.is_empty() {
+                out.push(' ');
+            }
+            in_word = false;
+        } else {
+            out.push(c);
+            in_word = c.is_alphanumeric() || c == '_' || c == '$';
+        }
+        if out.len() > MAX_SQL_LEN {
+            let mut end = MAX_SQL_LEN;
+            while !out.is_char_boundary(end) {
+                end -= 1;
+            }
+            out.truncate(end);
+            out.push('…');
+            return out;
+        }
+    }
+    out.truncate(out.trim_end().len());
+    out
+}
+
+/// Collects the `sqlx::query` events of the request being handled by
+/// [`latency_middleware`]. Its filter passes them only inside a request with
+/// slow logging on; the `sqlx` log level of the output is unaffected.
+pub fn query_capture_layer<S>() -> impl Layer<S>
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+{
+    let filter = dynamic_filter_fn(|metadata, _| {
+        metadata.target() == "sqlx::query" && CAPTURED_QUERIES.try_with(|_| ()).is_ok()
+    })
+    .with_callsite_filter(|metadata| {
+        if metadata.target() == "sqlx::query" {
+            Interest::sometimes()
+        } else {
+            Interest::never()
+        }
+    });
+    QueryCaptureLayer.with_filter(filter)
+}
+
+struct QueryCaptureLayer;
+
+impl<S: Subscriber> Layer<S> for QueryCaptureLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut visitor = QueryVisitor::default();
+        event.record(&mut visitor);
+        // sqlx sets `db.statement` only when `summary` is shortened.
+        let sql = if visitor.statement.trim().is_empty() {
+            visitor.summary
+        } else {
+            visitor.statement
+        };
+        let query = CapturedQuery {
+            sql,
+            duration: Duration::from_secs_f64(visitor.elapsed_secs.max(0.0)),
+            rows: visitor.rows_returned.max(visitor.rows_affected),
+        };
+        let _ = CAPTURED_QUERIES.try_with(|queries| queries.borrow_mut().push(query));
+    }
+}
+
+#[derive(Default)]
+struct QueryVisitor {
+    summary: String,
+    statement: String,
+    elapsed_secs: f64,
+    rows_returned: u64,
+    rows_affected: u64,
+}
+
+impl Visit for QueryVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        match field.name() {
+            "summary" => self.summary = value.to_string(),
+            "db.statement" => self.statement = value.to_string(),
+            _ => {}
+        }
+    }
+
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        if field.name() == "elapsed_secs" {
+            self.elapsed_secs = value;
+        }
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        match field.name() {
+            "rows_returned" => self.rows_returned = value,
+            "rows_affected" => self.rows_affected = value,
+            _ => {}
+        }
+    }
+
+    fn record_debug(&mut self, _field: &Field, _value: &dyn std::fmt::Debug) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    #[test]
+    fn sanitize_sql_masks_literals_and_keeps_placeholders() {
+        assert_eq!(
+            sanitize_sql(
+                "SELECT * FROM erc20_transfers\n   WHERE address = 'it''s 0xabc' AND block_number > 42.5\n  AND value = $1 LIMIT 10"
+            ),
+            "SELECT * FROM erc20_transfers WHERE address = ? AND block_number > ? AND value = $1 LIMIT ?"
+        );
+        assert_eq!(
+            sanitize_sql("  SELECT t1.a FROM t1  "),
+            "SELECT t1.a FROM t1"
+        );
+        let long = sanitize_sql(&format!("SELECT {}", "x".repeat(MAX_SQL_LEN * 2)));
+        assert!(long.ends_with('…'));
+        assert!(long.len() <= MAX_SQL_LEN + '…'.len_utf8());
+    }
+
+    #[test]
+    fn percentiles_use_nearest_rank() {
+        let samples: Vec<u64> = (1..=100).map(|ms| ms * 1000).collect();
+        assert_eq!(percentile_ms(&samples, 50), 50.0);
+        assert_eq!(percentile_ms(&samples, 95), 95.0);
+        assert_eq!(percentile_ms(&samples, 99), 99.0);
+        assert_eq!(percentile_ms(&[7000], 99), 7.0);
+        assert_eq!(percentile_ms(&[], 50), 0.0);
+    }
+
+    #[test]
+    fn tracker_keeps_a_window_and_ranks_routes_by_p95() {
+        let tracker = LatencyTracker::new(Duration::from_millis(100));
+        for _ in 0..LATENCY_WINDOW {
+            tracker.record("GET", "/api/slow", Duration::from_millis(500));
+        }
+        for _ in 0..LATENCY_WINDOW {
+            tracker.record("GET", "/api/slow", Duration::from_millis(5));
+        }
+        tracker.record("GET", "/api/fast", Duration::from_millis(1));
+
+        let endpoints = tracker.endpoints(10);
+        assert_eq!(endpoints[0].route, "/api/slow");
+        assert_eq!(endpoints[0].requests, 2 * LATENCY_WINDOW as u64);
+        assert_eq!(endpoints[0].slow_requests, LATENCY_WINDOW as u64);
+        assert_eq!(endpoints[0].window, LATENCY_WINDOW);
+        // The 500ms requests have left the window.
+        assert_eq!(endpoints[0].max_ms, 5.0);
+        assert_eq!(endpoints[1].route, "/api/fast");
+        assert_eq!(tracker.endpoints(1).len(), 1);
+
+        for i in 0..=SLOW_REQUEST_HISTORY {
+            tracker.record_slow(slow_request(
+                None,
+                "GET".into(),
+                format!("/api/{i}"),
+                200,
+                Duration::from_secs(1),
+                Vec::new(),
+            ));
+        }
+        let slow = tracker.slow_requests();
+        assert_eq!(slow.len(), SLOW_REQUEST_HISTORY);
+        assert_eq!(slow[0].route, format!("/api/{SLOW_REQUEST_HISTORY}"));
+    }
+
+    #[tokio::test]
+    async fn captures_sqlx_events_only_inside_a_request() {
+        let subscriber = tracing_subscriber::registry().with(query_capture_layer());
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        // Not part of any request.
+        tracing::debug!(target: "sqlx::query", summary = "select 0", elapsed_secs = 0.1);
+
+        let captured = CAPTURED_QUERIES
+            .scope(RefCell::new(Vec::new()), async {
+                assert!(tracing::enabled!(target: "sqlx::query", tracing::Level::DEBUG));
+                tracing::debug!(
+                    target: "sqlx::query",
+                    summary = "select 1",
+                    db.statement = "",
+                    rows_affected = 0_u64,
+                    rows_returned = 1_u64,
+                    elapsed_secs = 0.25,
+                );
+                tracing::debug!(
+                    target: "sqlx::query",
+                    summary = "select * from …",
+                    db.statement = "\n\nselect * from blocks where number = 7\n",
+                    rows_affected = 0_u64,
+                    rows_returned = 3_u64,
+                    elapsed_secs = 0.5,
+                );
+                tracing::debug!(target: "atlas_server", "not a query");
+                CAPTURED_QUERIES.with(|queries| queries.take())
+            })
+            .await;
+
+        assert_eq!(captured.len(), 2);
+        assert_eq!(captured[0].sql, "select 1");
+        assert_eq!(captured[0].rows, 1);
+        assert_eq!(captured[0].duration, Duration::from_millis(250));
+        let slow = slow_request(
+            Some("abc".into()),
+            "GET".into(),
+            "/api/blocks/{number}".into(),
+            200,
+            Duration::from_secs(2),
+            captured,
+        );
+        assert_eq!(slow.query_count, 2);
+        assert_eq!(slow.queries[1].sql, "select * from blocks where number = ?");
+        assert_eq!(slow.queries[1].duration_ms, 500.0);
+    }
+}
@@ -0,0 +1,124 @@
+use axum::{
+    error_handling::HandleErrorLayer,
+    response::{IntoResponse, Response},
+    BoxError, Router,
+};
+use tower::limit::GlobalConcurrencyLimitLayer;
+use tower::ServiceBuilder;
+
+use crate::api::error::ApiError;
+use atlas_common::AtlasError;
+
+/// Seconds a shed client is asked to wait before retrying.
+const SHED_RETRY_AFTER_SECS: u64 = 1;
+
+/// In-flight request limits per route group. 0 disables a group's limit.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ConcurrencyLimits {
+    /// Every API route not in a dedicated group (SSE and contract
+    /// verification are never limited).
+    pub api: usize,
+    /// `/api/search`
+    pub search: usize,
+    /// The Etherscan-compatible `/api` endpoint.
+    pub etherscan: usize,
+}
+
+/// Cap concurrent requests across all routes of `router` at `max_in_flight`.
+///
+/// Requests arriving while the group is saturated are rejected immediately
+/// with 429 and `Retry-After` rather than queued, so a single crawler cannot
+/// pile up work on the database pool.
+pub fn limit_group<S>(router: Router<S>, group: &'static str, max_in_flight: usize) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    if max_in_flight == 0 {
+        return router;
+    }
+    // Router::layer wraps each route separately; the global variant shares one
+    // semaphore between them so the limit applies to the group as a whole.
+    router.layer(
+        ServiceBuilder::new()
+            .layer(HandleErrorLayer::new(move |_: BoxError| async move {
+                shed_response(group)
+            }))
+            .load_shed()
+            .layer(GlobalConcurrencyLimitLayer::new(max_in_flight)),
+    )
+}
+
+fn shed_response(group: &'static str) -> Response {
+    metrics::counter!("atlas_http_requests_shed_total", "group" => group).increment(1);
+    ApiError(AtlasError::TooManyRequests {
+        message: format!("Too many concurrent {group} requests, retry shortly"),
+        retry_after_seconds: SHED_RETRY_AFTER_SECS,
+    })
+    .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        body::Body,
+        http::{header::RETRY_AFTER, Request, StatusCode},
+        routing::get,
+    };
+    use std::sync::Arc;
+    use tokio::sync::Notify;
+    use tower::ServiceExt;
+
+    /// Router whose `/slow` handler blocks until `release` is notified.
+    fn app(limit: usize, release: Arc<Notify>) -> Router {
+        let slow = Router::new()
+            .route(
+                "/slow",
+                get(move || {
+                    let release = release.clone();
+                    async move { release.notified().await }
+                }),
+            )
+            .route("/fast", get(|| async {}));
+        limit_group(slow, "search", limit)
+    }
+
+    fn get_request(uri: &str) -> Request<Body> {
+        Request::builder().uri(uri).body(Body::empty()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn saturated_group_sheds_with_429_across_routes() {
+        let release = Arc::new(Notify::new());
+        let app = app(1, release.clone());
+
+        let in_flight = tokio::spawn(app.clone().oneshot(get_request("/slow")));
+        tokio::task::yield_now().await;
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+
+        let shed = app.clone().oneshot(get_request("/fast")).await.unwrap();
+        assert_eq!(shed.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(shed.headers()[RETRY_AFTER], "1");
+
+        release.notify_one();
+        assert_eq!(in_flight.await.unwrap().unwrap().status(), StatusCode::OK);
+
+        let after = app.oneshot(get_request("/fast")).await.unwrap();
+        assert_eq!(after.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn zero_limit_disables_shedding() {
+        let release = Arc::new(Notify::new());
+        let app = app(0, release.clone());
+
+        let in_flight = tokio::spawn(app.clone().oneshot(get_request("/slow")));
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+
+        let response = app.oneshot(get_request("/fast")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        release.notify_one();
+        in_flight.await.unwrap().unwrap();
+    }
+}
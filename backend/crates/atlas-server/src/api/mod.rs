@@ -1,5 +1,14 @@
+pub mod auth;
+pub mod body_limits;
+pub mod circuit_breaker;
 pub mod error;
 pub mod handlers;
+pub mod latency;
+pub mod load_shed;
+pub mod ndjson;
+pub mod request_id;
+pub mod row_limits;
+pub mod versioning;
 
 use axum::{extract::DefaultBodyLimit, middleware, routing::get, Router};
 use metrics_exporter_prometheus::PrometheusHandle;
@@ -7,14 +16,25 @@ use sqlx::PgPool;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::broadcast;
+use tower_http::compression::CompressionLayer;
 use tower_http::cors::{AllowOrigin, Any, CorsLayer};
 use tower_http::timeout::TimeoutLayer;
 use tower_http::trace::TraceLayer;
 
+use crate::api::body_limits::BodyLimits;
+use crate::api::circuit_breaker::CircuitBreaker;
+use crate::api::handlers::display::NativeCurrency;
+use crate::api::handlers::rpc_logs::LogLimits;
+use crate::api::latency::LatencyTracker;
+use crate::api::load_shed::ConcurrencyLimits;
+use crate::api::row_limits::RowLimits;
+use crate::db_pools::DbPools;
 use crate::faucet::SharedFaucetBackend;
-use crate::head::HeadTracker;
+use crate::head::{ConfirmationsHead, HeadTracker};
 use crate::indexer::DaSseUpdate;
 use crate::metrics::Metrics;
+use crate::runtime_settings::RuntimeSettings;
+use atlas_common::storage::TieredStorage;
 
 pub struct AppState {
     pub pool: PgPool,
@@ -28,6 +48,7 @@ pub struct AppState {
     pub faucet_cooldown_minutes: Option<u64>,
     pub chain_id: u64,
     pub chain_name: String,
+    pub native_currency: NativeCurrency,
     pub chain_logo_url: Option<String>,
     pub chain_logo_url_light: Option<String>,
     pub chain_logo_url_dark: Option<String>,
@@ -39,6 +60,62 @@ pub struct AppState {
     pub metrics: Metrics,
     pub prometheus_handle: PrometheusHandle,
     pub solc_cache_dir: String,
+    /// Time budget for one contract verification request
+    pub verify_timeout: Duration,
+    /// Largest `source_code` or `standard_json_input` accepted for verification
+    pub verify_max_source_bytes: usize,
+    /// Bulk export directory; `/api/export` routes exist only when set
+    pub export_dir: Option<String>,
+    /// Daily dataset dump directory; `/api/datasets` routes exist only when set
+    pub dataset_dir: Option<String>,
+    pub db_circuit_breaker: CircuitBreaker,
+    /// Per-route latencies and recent slow requests
+    pub latency: LatencyTracker,
+    pub admin_api_key: Option<String>,
+    pub db_pools: Arc<DbPools>,
+    pub concurrency_limits: ConcurrencyLimits,
+    pub body_limits: BodyLimits,
+    pub log_limits: LogLimits,
+    pub row_limits: RowLimits,
+    /// Where old event logs and ERC-20 transfers are read from
+    pub storage: Arc<dyn TieredStorage>,
+    /// Head Etherscan-compatible `confirmations` are counted from
+    pub confirmations_head: ConfirmationsHead,
+    /// Default stall threshold for `/api/stats/block-times`; 0 = none
+    pub block_stall_secs: u64,
+    /// Settings the admin API can change without a restart
+    pub runtime_settings: RuntimeSettings,
+}
+
+impl AppState {
+    /// Latest indexed block height, served from the in-memory head tracker.
+    ///
+    /// Falls back to `MAX(number)` only before the background refresher has
+    /// observed a height (e.g. the first second after startup).
+    pub async fn latest_block_height(&self) -> Result<Option<i64>, sqlx::Error> {
+        if let Some(height) = self.head_tracker.latest_height().await {
+            return Ok(Some(height));
+        }
+
+        let (height,): (Option<i64>,) = sqlx::query_as("SELECT MAX(number) FROM blocks")
+            .fetch_one(&self.pool)
+            .await?;
+        self.head_tracker.set_indexed_height(height);
+        Ok(height)
+    }
+
+    /// Height Etherscan-compatible `confirmations` are counted from, per
+    /// [`ConfirmationsHead`]. `None` when that head is not known yet.
+    pub async fn confirmations_height(&self) -> Result<Option<i64>, sqlx::Error> {
+        match self.confirmations_head {
+            ConfirmationsHead::Indexed => self.latest_block_height().await,
+            ConfirmationsHead::Chain => match self.head_tracker.chain_height() {
+                Some(height) => Ok(Some(height)),
+                None => self.latest_block_height().await,
+            },
+            ConfirmationsHead::Finalized => Ok(self.head_tracker.finalized_height()),
+        }
+    }
 }
 
 /// Build the Axum router.
@@ -51,16 +128,37 @@ pub fn build_router(state: Arc<AppState>, cors_origin: Option<String>) -> Router
         .route("/api/events", get(handlers::sse::block_events))
         .with_state(state.clone());
 
-    // Verify route — excluded from 10s TimeoutLayer; solc compilation can take longer
+    // Verify route — excluded from 10s TimeoutLayer; solc compilation can take
+    // longer, so it gets its own budget. The handler reads and limits its body.
     let verify_routes = Router::new()
         .route(
             "/api/contracts/{address}/verify",
             axum::routing::post(handlers::contracts::verify_contract),
         )
-        // Verification payloads can include full standard-json compiler inputs.
-        .layer(DefaultBodyLimit::max(50 * 1024 * 1024))
+        .route(
+            "/api/contracts/pending-verifications",
+            axum::routing::post(handlers::contracts::register_pending_verification),
+        )
+        .layer(TimeoutLayer::with_status_code(
+            axum::http::StatusCode::REQUEST_TIMEOUT,
+            state.verify_timeout,
+        ))
         .with_state(state.clone());
 
+    // Abuse-prone routes get their own concurrency budget
+    let limits = state.concurrency_limits;
+    let search_routes = load_shed::limit_group(
+        Router::new().route("/api/search", get(handlers::search::search)),
+        "search",
+        limits.search,
+    );
+    let etherscan_routes = load_shed::limit_group(
+        // Etherscan-compatible API
+        Router::new().route("/api", get(handlers::etherscan::etherscan_api)),
+        "etherscan",
+        limits.etherscan,
+    );
+
     let mut router = Router::new()
         // Blocks
         .route("/api/blocks", get(handlers::blocks::list_blocks))
@@ -69,6 +167,26 @@ pub fn build_router(state: Arc<AppState>, cors_origin: Option<String>) -> Router
             "/api/blocks/{number}/transactions",
             get(handlers::blocks::get_block_transactions),
         )
+        .route(
+            "/api/blocks/{number}/transactions/{index}",
+            get(handlers::blocks::get_block_transaction_by_index),
+        )
+        .route(
+            "/api/rpc/logs",
+            axum::routing::post(handlers::rpc_logs::get_logs),
+        )
+        .route(
+            "/api/blocks/{number}/logs",
+            get(handlers::logs::get_block_logs),
+        )
+        .route(
+            "/api/blocks/{number}/bloom",
+            get(handlers::bloom::check_block),
+        )
+        .route(
+            "/api/blocks/{number}/raw",
+            get(handlers::raw::get_raw_block),
+        )
         // Transactions
         .route(
             "/api/transactions",
@@ -94,12 +212,24 @@ pub fn build_router(state: Arc<AppState>, cors_origin: Option<String>) -> Router
             "/api/transactions/{hash}/nft-transfers",
             get(handlers::transactions::get_transaction_nft_transfers),
         )
+        .route(
+            "/api/transactions/{hash}/bloom",
+            get(handlers::bloom::check_transaction),
+        )
+        .route(
+            "/api/transactions/{hash}/raw",
+            get(handlers::raw::get_raw_transaction),
+        )
         // Addresses
         .route("/api/addresses", get(handlers::addresses::list_addresses))
         .route(
             "/api/addresses/{address}",
             get(handlers::addresses::get_address),
         )
+        .route(
+            "/api/addresses/{address}/meta",
+            get(handlers::addresses::get_address_meta),
+        )
         .route(
             "/api/addresses/{address}/transactions",
             get(handlers::addresses::get_address_transactions),
@@ -108,6 +238,18 @@ pub fn build_router(state: Arc<AppState>, cors_origin: Option<String>) -> Router
             "/api/addresses/{address}/transfers",
             get(handlers::addresses::get_address_transfers),
         )
+        .route(
+            "/api/addresses/{address}/token-flows",
+            get(handlers::addresses::get_address_token_flows),
+        )
+        .route(
+            "/api/addresses/{address}/deployed-contracts",
+            get(handlers::addresses::get_address_deployed_contracts),
+        )
+        .route(
+            "/api/addresses/{address}/nonces",
+            get(handlers::addresses::get_address_nonces),
+        )
         .route(
             "/api/addresses/{address}/nfts",
             get(handlers::addresses::get_address_nfts),
@@ -120,6 +262,10 @@ pub fn build_router(state: Arc<AppState>, cors_origin: Option<String>) -> Router
             "/api/addresses/{address}/logs",
             get(handlers::logs::get_address_logs),
         )
+        .route(
+            "/api/addresses/{address}/approval-risks",
+            get(handlers::approvals::get_approval_risks),
+        )
         // NFTs
         .route(
             "/api/nfts/collections",
@@ -133,10 +279,18 @@ pub fn build_router(state: Arc<AppState>, cors_origin: Option<String>) -> Router
             "/api/nfts/collections/{address}/tokens",
             get(handlers::nfts::list_collection_tokens),
         )
+        .route(
+            "/api/nfts/collections/{address}/traits",
+            get(handlers::nfts::get_collection_traits),
+        )
         .route(
             "/api/nfts/collections/{address}/transfers",
             get(handlers::nfts::get_collection_transfers),
         )
+        .route(
+            "/api/nfts/collections/{address}/sales",
+            get(handlers::nfts::get_collection_sales),
+        )
         .route(
             "/api/nfts/collections/{address}/tokens/{token_id}",
             get(handlers::nfts::get_token),
@@ -145,6 +299,7 @@ pub fn build_router(state: Arc<AppState>, cors_origin: Option<String>) -> Router
             "/api/nfts/collections/{address}/tokens/{token_id}/transfers",
             get(handlers::nfts::get_token_transfers),
         )
+        .route("/api/logos/{address}", get(handlers::logos::get_logo))
         // ERC-20 Tokens
         .route("/api/tokens", get(handlers::tokens::list_tokens))
         .route("/api/tokens/{address}", get(handlers::tokens::get_token))
@@ -170,30 +325,161 @@ pub fn build_router(state: Arc<AppState>, cors_origin: Option<String>) -> Router
             "/api/contracts/{address}/combined-abi",
             get(handlers::proxy::get_combined_abi),
         )
+        .route(
+            "/api/abis/bundle",
+            axum::routing::post(handlers::proxy::get_abi_bundle),
+        )
+        .route(
+            "/api/contracts/{address}/events",
+            get(handlers::contract_events::get_contract_events),
+        )
         // Contract verification
+        .route(
+            "/api/contracts/pending-verifications/{code_hash}",
+            get(handlers::contracts::get_pending_verification),
+        )
         .route(
             "/api/contracts/{address}",
             get(handlers::contracts::get_contract),
         )
-        // Etherscan-compatible API
-        .route("/api", get(handlers::etherscan::etherscan_api))
-        // Search
-        .route("/api/search", get(handlers::search::search))
+        .route(
+            "/api/contracts/{address}/ast",
+            get(handlers::contracts::get_contract_ast),
+        )
+        .route(
+            "/api/contracts/{address}/files",
+            get(handlers::contracts::list_contract_files),
+        )
+        .route(
+            "/api/contracts/{address}/files/{*path}",
+            get(handlers::contracts::get_contract_file),
+        )
+        // Decoding
+        .route(
+            "/api/decode/log",
+            axum::routing::post(handlers::decode::decode_log),
+        )
         // Stats (charts)
         .route(
             "/api/stats/blocks-chart",
             get(handlers::stats::get_blocks_chart),
         )
         .route("/api/stats/daily-txs", get(handlers::stats::get_daily_txs))
+        .route("/api/stats/daily", get(handlers::stats::get_daily_stats))
+        .route("/api/stats/fees", get(handlers::stats::get_fee_stats))
+        .route(
+            "/api/stats/fees/blocks",
+            get(handlers::stats::get_block_fees),
+        )
+        .route("/api/stats/aa", get(handlers::stats::get_aa_stats))
+        .route(
+            "/api/stats/block-times",
+            get(handlers::stats::get_block_times),
+        )
         .route(
             "/api/stats/gas-price",
             get(handlers::stats::get_gas_price_chart),
         )
+        .route(
+            "/api/stats/gas-oracle",
+            get(handlers::gas_oracle::get_gas_oracle),
+        )
+        // DEX swaps
+        .route("/api/dex/swaps", get(handlers::dex::list_swaps))
+        .route("/api/dex/pairs", get(handlers::dex::get_pair_stats))
+        // Safe multisigs
+        .route("/api/safes/{address}", get(handlers::safes::get_safe))
+        .route(
+            "/api/safes/{address}/executions",
+            get(handlers::safes::list_executions),
+        )
+        // Validators and sequencers
+        .route(
+            "/api/validators",
+            get(handlers::validators::list_validators),
+        )
+        .route(
+            "/api/validators/{address}",
+            get(handlers::validators::get_validator),
+        )
+        .route(
+            "/api/validators/{address}/events",
+            get(handlers::validators::list_validator_events),
+        )
         // Status
         .route("/api/height", get(handlers::status::get_height))
         .route("/api/status", get(handlers::status::get_status))
+        .route("/api/overview", get(handlers::overview::get_overview))
         // Config (white-label branding)
         .route("/api/config", get(handlers::config::get_config))
+        // Labels
+        .route(
+            "/api/labels/suggest",
+            axum::routing::post(handlers::labels::suggest_label),
+        )
+        .route("/api/labels/export", get(handlers::labels::export_labels))
+        // Label editing: admin key or a tag-scoped curator key
+        .route(
+            "/api/labels/suggestions",
+            get(handlers::labels::list_label_suggestions),
+        )
+        .route(
+            "/api/labels/suggestions/{id}/approve",
+            axum::routing::post(handlers::labels::approve_label_suggestion),
+        )
+        .route(
+            "/api/labels/suggestions/{id}/reject",
+            axum::routing::post(handlers::labels::reject_label_suggestion),
+        )
+        .route(
+            "/api/labels/import",
+            // Label dumps (e.g. etherscan exports) exceed the 2 MB default.
+            axum::routing::post(handlers::labels::import_labels)
+                .layer(DefaultBodyLimit::max(20 * 1024 * 1024)),
+        )
+        .route(
+            "/api/labels",
+            axum::routing::post(handlers::labels::save_label),
+        )
+        .route(
+            "/api/labels/{address}",
+            axum::routing::delete(handlers::labels::delete_label),
+        )
+        // Private address notes of the calling API key
+        .route(
+            "/api/me/notes",
+            get(handlers::notes::list_notes).post(handlers::notes::save_note),
+        )
+        .route(
+            "/api/me/notes/{address}",
+            get(handlers::notes::get_note).delete(handlers::notes::delete_note),
+        )
+        // Saved filters of the calling API key
+        .route(
+            "/api/me/filters",
+            get(handlers::filters::list_filters).post(handlers::filters::create_filter),
+        )
+        .route(
+            "/api/me/filters/{id}",
+            get(handlers::filters::get_filter)
+                .put(handlers::filters::update_filter)
+                .delete(handlers::filters::delete_filter),
+        )
+        // Alert rules of the calling API key
+        .route(
+            "/api/alerts",
+            get(handlers::alerts::list_alerts).post(handlers::alerts::create_alert),
+        )
+        .route(
+            "/api/alerts/{id}",
+            get(handlers::alerts::get_alert)
+                .put(handlers::alerts::update_alert)
+                .delete(handlers::alerts::delete_alert),
+        )
+        .route(
+            "/api/alerts/{id}/notifications",
+            get(handlers::alerts::list_alert_notifications),
+        )
         // Metrics
         .route("/metrics", get(handlers::metrics::metrics))
         // Health
@@ -210,21 +496,161 @@ pub fn build_router(state: Arc<AppState>, cors_origin: Option<String>) -> Router
             );
     }
 
-    router
+    // Export and dataset downloads are streamed, so they are merged below without TimeoutLayer
+    let mut download_routes = Router::new();
+    if state.export_dir.is_some() {
+        router = router
+            .route(
+                "/api/export",
+                axum::routing::post(handlers::export::create_export),
+            )
+            .route("/api/export/{id}", get(handlers::export::get_export));
+        download_routes = download_routes.route(
+            "/api/export/{id}/download",
+            get(handlers::export::download_export),
+        );
+    }
+    if state.dataset_dir.is_some() {
+        router = router.route("/api/datasets", get(handlers::datasets::list_datasets));
+        download_routes = download_routes.route(
+            "/api/datasets/{day}/{file}",
+            get(handlers::datasets::download_dataset),
+        );
+    }
+
+    if state.admin_api_key.is_some() {
+        router = router
+            .route(
+                "/api/labels/keys",
+                get(handlers::labels::list_label_api_keys)
+                    .post(handlers::labels::create_label_api_key),
+            )
+            .route(
+                "/api/labels/keys/{id}",
+                axum::routing::delete(handlers::labels::revoke_label_api_key),
+            )
+            .route("/api/admin/db-stats", get(handlers::admin::get_db_stats))
+            .route(
+                "/api/admin/runtime-settings",
+                get(handlers::admin::get_runtime_settings)
+                    .patch(handlers::admin::update_runtime_settings),
+            )
+            .route(
+                "/api/admin/data-quality",
+                get(handlers::admin::get_data_quality),
+            )
+            .route(
+                "/api/admin/contracts/{address}/unverify",
+                axum::routing::post(handlers::contracts::unverify_contract),
+            )
+            .route(
+                "/api/admin/contracts/{address}/reverify",
+                axum::routing::post(handlers::contracts::reverify_contract),
+            )
+            .route(
+                "/api/admin/contracts/{address}/verification-history",
+                get(handlers::contracts::get_verification_history),
+            )
+            .route(
+                "/api/admin/contracts/{address}/reindex",
+                get(handlers::admin::get_contract_reindex).post(handlers::admin::reindex_contract),
+            )
+            .route(
+                "/api/admin/tokens/curation",
+                get(handlers::tokens::list_token_curation),
+            )
+            .route(
+                "/api/admin/tokens/{address}/curation",
+                axum::routing::put(handlers::tokens::set_token_curation)
+                    .delete(handlers::tokens::delete_token_curation),
+            )
+            .route(
+                "/api/admin/logos/{address}",
+                axum::routing::put(handlers::logos::set_logo_url)
+                    .delete(handlers::logos::delete_logo),
+            )
+            .route(
+                "/api/admin/slow-queries",
+                get(handlers::admin::get_slow_queries),
+            )
+            .route(
+                "/api/admin/logos/{address}/image",
+                axum::routing::put(handlers::logos::upload_logo),
+            );
+    }
+
+    let api_routes = load_shed::limit_group(router, "api", limits.api)
+        .merge(search_routes)
+        .merge(etherscan_routes)
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            row_limits::row_limits_middleware,
+        ));
+
+    // Batched calls are dispatched through the routes above, behind the
+    // circuit breaker, and count against their own group's limit.
+    let batch_api = api_routes
+        .clone()
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            circuit_breaker::circuit_breaker_middleware,
+        ))
+        .with_state(state.clone());
+    let batch_routes = Router::new().route(
+        "/api/batch",
+        axum::routing::post(
+            move |axum::Json(calls): axum::Json<Vec<handlers::batch::BatchCall>>| {
+                handlers::batch::execute(batch_api.clone(), calls)
+            },
+        ),
+    );
+
+    let api = api_routes
+        .merge(batch_routes)
+        .layer(DefaultBodyLimit::max(state.body_limits.api))
         .layer(TimeoutLayer::with_status_code(
             axum::http::StatusCode::REQUEST_TIMEOUT,
             Duration::from_secs(10),
         ))
+        // DB circuit breaker — counts only database failures, not 408s
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            circuit_breaker::circuit_breaker_middleware,
+        ))
         // HTTP metrics middleware — placed after routing so MatchedPath is available
         .layer(middleware::from_fn(crate::metrics::http_metrics_middleware))
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            latency::latency_middleware,
+        ))
         // Merge SSE routes without TimeoutLayer so connections stay alive
         .merge(sse_routes)
         // Merge verify route without TimeoutLayer so solc compilation is not cut off
         .merge(verify_routes)
-        // Shared layers applied to all routes
+        .merge(download_routes.with_state(state.clone()))
+        // Shared layers applied to all routes. Compression skips SSE and
+        // responses too small to benefit.
+        .layer(CompressionLayer::new())
         .layer(build_cors_layer(cors_origin))
-        .layer(TraceLayer::new_for_http())
-        .with_state(state)
+        .layer(
+            TraceLayer::new_for_http()
+                .make_span_with(request_id::make_request_span)
+                .on_response(request_id::on_response),
+        )
+        .layer(middleware::from_fn(versioning::deprecation_headers))
+        // Outside TraceLayer so the request span can read the assigned ID
+        .layer(middleware::from_fn(request_id::request_id_middleware))
+        .with_state(state);
+
+    // `/api/v1` re-dispatches through all of the above, layers included.
+    let unversioned = api.clone();
+    api.route(
+        "/api/v1/{*path}",
+        axum::routing::any(move |request: axum::extract::Request| {
+            versioning::serve_v1(unversioned.clone(), request)
+        })
+        .layer(CompressionLayer::new()),
+    )
 }
 /// Construct the CORS layer.
 ///
@@ -298,7 +724,7 @@ mod tests {
             .build_recorder()
             .handle();
         Arc::new(AppState {
-            pool,
+            pool: pool.clone(),
             block_events_tx: tx,
             da_events_tx: da_tx,
             head_tracker,
@@ -309,6 +735,7 @@ mod tests {
             faucet_cooldown_minutes: None,
             chain_id: 1,
             chain_name: "Test Chain".to_string(),
+            native_currency: Default::default(),
             chain_logo_url: None,
             chain_logo_url_light: None,
             chain_logo_url_dark: None,
@@ -320,6 +747,22 @@ mod tests {
             metrics: Metrics::new(),
             prometheus_handle,
             solc_cache_dir: "/tmp/solc-cache".to_string(),
+            verify_timeout: std::time::Duration::from_secs(300),
+            verify_max_source_bytes: 20 * 1024 * 1024,
+            export_dir: None,
+            dataset_dir: None,
+            db_circuit_breaker: crate::api::circuit_breaker::CircuitBreaker::default(),
+            latency: Default::default(),
+            admin_api_key: None,
+            db_pools: Arc::new(crate::db_pools::DbPools::new(Vec::new())),
+            concurrency_limits: Default::default(),
+            body_limits: Default::default(),
+            log_limits: Default::default(),
+            row_limits: Default::default(),
+            storage: Arc::new(atlas_common::storage::HotStorage::new(pool)),
+            confirmations_head: Default::default(),
+            block_stall_secs: 0,
+            runtime_settings: Default::default(),
         })
     }
 
@@ -365,4 +808,50 @@ mod tests {
         let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
         assert_eq!(value["tx_hash"], "0xdeadbeef");
     }
+
+    #[tokio::test]
+    async fn responses_are_compressed_when_the_client_accepts_it() {
+        let faucet: SharedFaucetBackend = Arc::new(FakeFaucet);
+        let app = build_router(test_state(Some(faucet)), None);
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/faucet/info")
+                    .header("accept-encoding", "gzip")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()["content-encoding"], "gzip");
+    }
+
+    #[tokio::test]
+    async fn verify_route_rejects_bodies_over_its_limit() {
+        let mut state = Arc::into_inner(test_state(None)).unwrap();
+        state.body_limits.verify = 16;
+        let app = build_router(Arc::new(state), None);
+
+        let body = r#"{"source_code":"contract A {}"}"#;
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/api/contracts/0x0000000000000000000000000000000000000001/verify")
+                    .header("content-type", "application/json")
+                    .header("content-length", body.len())
+                    .body(Body::from(body))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(value["error"], "request body exceeds the 16 byte limit");
+    }
 }
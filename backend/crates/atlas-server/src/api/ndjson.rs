@@ -0,0 +1,133 @@
+//! Newline-delimited JSON for list endpoints that clients sync in bulk.
+//!
+//! A request with `Accept: application/x-ndjson` gets every matching row,
+//! one JSON object per line, instead of a page. Rows come from a server-side
+//! cursor read [`FETCH_SIZE`] at a time inside a read-only transaction, so
+//! memory stays flat however large the result is, and each `FETCH` is its own
+//! statement that stays under the pool's 10s `statement_timeout`.
+
+use axum::{
+    body::{Body, Bytes},
+    http::{header, HeaderMap},
+    response::{IntoResponse, Response},
+};
+use serde::Serialize;
+use sqlx::postgres::PgRow;
+use sqlx::{FromRow, PgPool, Postgres, Transaction};
+
+pub const CONTENT_TYPE: &str = "application/x-ndjson";
+
+/// Cursor every NDJSON response reads from; see [`declare`].
+const CURSOR: &str = "ndjson_rows";
+/// Rows fetched from the cursor per round trip.
+const FETCH_SIZE: usize = 500;
+
+/// Whether the client asked for NDJSON in its `Accept` header.
+pub fn requested(headers: &HeaderMap) -> bool {
+    headers
+        .get_all(header::ACCEPT)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .any(|media| {
+            media
+                .split(';')
+                .next()
+                .is_some_and(|media| media.trim().eq_ignore_ascii_case(CONTENT_TYPE))
+        })
+}
+
+/// `DECLARE` statement for the cursor over `select`. Bind the select's
+/// parameters to it and run it in the transaction passed to [`stream`].
+pub fn declare(select: &str) -> String {
+    format!("DECLARE {CURSOR} NO SCROLL CURSOR FOR {select}")
+}
+
+/// Begin the read-only transaction a cursor lives in.
+pub async fn begin(pool: &PgPool) -> Result<Transaction<'static, Postgres>, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    sqlx::query("SET TRANSACTION READ ONLY")
+        .execute(&mut *tx)
+        .await?;
+    Ok(tx)
+}
+
+/// Stream the declared cursor's rows as NDJSON. The transaction is rolled
+/// back once the cursor is drained or the client goes away.
+pub fn stream<T>(mut tx: Transaction<'static, Postgres>) -> Response
+where
+    T: for<'r> FromRow<'r, PgRow> + Serialize + Send + Unpin + 'static,
+{
+    // The body is written after the handler's task has returned, so carry
+    // over how it wants numbers written.
+    let as_strings = atlas_common::numeric::numbers_as_strings();
+    let body = async_stream::try_stream! {
+        loop {
+            // Not cached: the statement text is the same for every cursor, but
+            // its columns are not, and a cached statement keeps the first ones.
+            let rows: Vec<T> = sqlx::query_as(&format!("FETCH {FETCH_SIZE} FROM {CURSOR}"))
+                .persistent(false)
+                .fetch_all(&mut *tx)
+                .await
+                .inspect_err(|e| tracing::warn!(error = %e, "NDJSON stream aborted"))
+                .map_err(std::io::Error::other)?;
+            let done = rows.len() < FETCH_SIZE;
+            let chunk = atlas_common::numeric::with_mode(as_strings, || lines(rows))?;
+            if !chunk.is_empty() {
+                yield Bytes::from(chunk);
+            }
+            if done {
+                break;
+            }
+        }
+        tx.rollback().await.map_err(std::io::Error::other)?;
+    };
+    let body: std::pin::Pin<Box<dyn futures::Stream<Item = std::io::Result<Bytes>> + Send>> =
+        Box::pin(body);
+
+    (
+        [(header::CONTENT_TYPE, CONTENT_TYPE)],
+        Body::from_stream(body),
+    )
+        .into_response()
+}
+
+fn lines<T: Serialize>(rows: Vec<T>) -> std::io::Result<Vec<u8>> {
+    let mut out = Vec::new();
+    for row in rows {
+        serde_json::to_writer(&mut out, &row)?;
+        out.push(b'\n');
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    fn accept(value: &'static str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::ACCEPT, HeaderValue::from_static(value));
+        headers
+    }
+
+    #[test]
+    fn requested_matches_the_ndjson_media_type() {
+        assert!(requested(&accept("application/x-ndjson")));
+        assert!(requested(&accept(
+            "application/json, Application/X-NDJSON; q=0.9"
+        )));
+        assert!(!requested(&accept("application/json")));
+        assert!(!requested(&accept("*/*")));
+        assert!(!requested(&HeaderMap::new()));
+    }
+
+    #[test]
+    fn declare_wraps_the_select_in_the_cursor() {
+        assert_eq!(
+            declare("SELECT 1"),
+            "DECLARE ndjson_rows NO SCROLL CURSOR FOR SELECT 1"
+        );
+    }
+}
@@ -0,0 +1,149 @@
+use axum::{
+    body::Body,
+    extract::{MatchedPath, Request},
+    http::{HeaderName, HeaderValue},
+    middleware::Next,
+    response::Response,
+};
+use std::time::Duration;
+use tower_http::trace::{DefaultOnResponse, OnResponse};
+use tracing::Span;
+
+pub const REQUEST_ID_HEADER: HeaderName = HeaderName::from_static("x-request-id");
+
+/// Longest client-supplied request ID that is propagated as-is.
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+/// Request extension holding the ID assigned by [`request_id_middleware`].
+#[derive(Clone, Debug)]
+pub struct RequestId(pub String);
+
+/// Tag every request with an ID and echo it in the `x-request-id` response
+/// header. A well-formed ID sent by the client (or a proxy in front of us) is
+/// reused so one ID follows the request across services; otherwise a random
+/// one is generated.
+pub async fn request_id_middleware(mut request: Request, next: Next) -> Response {
+    let id = request
+        .headers()
+        .get(&REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .filter(|value| is_valid_request_id(value))
+        .map(str::to_string)
+        .unwrap_or_else(generate_request_id);
+
+    request.extensions_mut().insert(RequestId(id.clone()));
+    let mut response = next.run(request).await;
+    if let Ok(value) = HeaderValue::from_str(&id) {
+        response.headers_mut().insert(REQUEST_ID_HEADER, value);
+    }
+    response
+}
+
+/// Span for `TraceLayer`, carrying the request ID so every log line emitted
+/// while handling the request (handler, sqlx, errors) can be correlated.
+/// The `otel.*` fields name the span by route when traces are exported.
+pub fn make_request_span(request: &Request<Body>) -> Span {
+    let request_id = request
+        .extensions()
+        .get::<RequestId>()
+        .map(|id| id.0.as_str())
+        .unwrap_or_default();
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(MatchedPath::as_str)
+        .unwrap_or("unknown");
+    let span = tracing::info_span!(
+        "request",
+        method = %request.method(),
+        uri = %request.uri(),
+        request_id,
+        otel.name = %format_args!("{} {}", request.method(), route),
+        otel.kind = "server",
+        http.route = route,
+        http.status_code = tracing::field::Empty,
+    );
+    crate::telemetry::set_remote_parent(&span, request.headers());
+    span
+}
+
+/// `TraceLayer` response hook: record the status on the request span, then
+/// log as tower-http does by default.
+pub fn on_response(response: &Response, latency: Duration, span: &Span) {
+    span.record("http.status_code", response.status().as_u16());
+    DefaultOnResponse::default().on_response(response, latency, span);
+}
+
+fn is_valid_request_id(value: &str) -> bool {
+    !value.is_empty()
+        && value.len() <= MAX_REQUEST_ID_LEN
+        && value
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_' | b'.' | b':'))
+}
+
+fn generate_request_id() -> String {
+    hex::encode(rand::random::<[u8; 16]>())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{middleware, routing::get, Extension, Router};
+    use tower::ServiceExt;
+
+    fn app() -> Router {
+        Router::new()
+            .route(
+                "/",
+                get(|Extension(id): Extension<RequestId>| async move { id.0 }),
+            )
+            .layer(middleware::from_fn(request_id_middleware))
+    }
+
+    async fn call(header: Option<&str>) -> (String, String) {
+        let mut builder = Request::builder().uri("/");
+        if let Some(value) = header {
+            builder = builder.header(REQUEST_ID_HEADER, value);
+        }
+        let response = app()
+            .oneshot(builder.body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let echoed = response.headers()[REQUEST_ID_HEADER]
+            .to_str()
+            .unwrap()
+            .to_string();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (echoed, String::from_utf8(body.to_vec()).unwrap())
+    }
+
+    #[tokio::test]
+    async fn propagates_client_request_id() {
+        let (echoed, seen) = call(Some("lb-1234.abc")).await;
+        assert_eq!(echoed, "lb-1234.abc");
+        assert_eq!(seen, "lb-1234.abc");
+    }
+
+    #[tokio::test]
+    async fn generates_request_id_when_missing_or_invalid() {
+        let (echoed, seen) = call(None).await;
+        assert_eq!(echoed.len(), 32);
+        assert_eq!(echoed, seen);
+
+        let (echoed, _) = call(Some("has spaces")).await;
+        assert_eq!(echoed.len(), 32);
+        assert_ne!(echoed, "has spaces");
+    }
+
+    #[test]
+    fn request_id_validation() {
+        assert!(is_valid_request_id("0f3e9a"));
+        assert!(is_valid_request_id("trace:abc_def-1.2"));
+        assert!(!is_valid_request_id(""));
+        assert!(!is_valid_request_id("a\"b"));
+        assert!(!is_valid_request_id(&"a".repeat(MAX_REQUEST_ID_LEN + 1)));
+    }
+}
@@ -0,0 +1,132 @@
+//! Cap on how deep a paginated request may read into a result set.
+//!
+//! Pages are served with `LIMIT ... OFFSET`, so Postgres reads and discards
+//! every row before the page: page 50 000 of a large table is a full scan no
+//! matter how small the page. [`row_limits_middleware`] checks the page
+//! parameters of every API request before it reaches a handler and refuses
+//! pages past [`RowLimits::max_result_window`] with a 422 that names the
+//! limit. Etherscan-compatible requests get Etherscan's own `NOTOK` answer
+//! instead, as its clients expect. Endpoints that page with cursors are not
+//! affected.
+
+use axum::{
+    extract::{Query, Request, State},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::api::error::ApiError;
+use crate::api::handlers::etherscan::conformance::notok;
+use crate::api::AppState;
+use atlas_common::AtlasError;
+
+/// Largest page size any endpoint serves; bigger `limit`s are clamped.
+const MAX_PAGE_SIZE: u64 = 100;
+
+/// Result row caps for paginated endpoints.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RowLimits {
+    /// Deepest row a request may page to, `page × limit`.
+    pub max_result_window: u64,
+}
+
+impl Default for RowLimits {
+    fn default() -> Self {
+        Self {
+            max_result_window: 10_000,
+        }
+    }
+}
+
+/// Page parameters of both the native API (`page`, `limit`) and the
+/// Etherscan-compatible one (`page`, `offset` as the page size).
+#[derive(Deserialize)]
+struct PageParams {
+    page: Option<u64>,
+    limit: Option<u64>,
+    offset: Option<u64>,
+    module: Option<String>,
+}
+
+/// Refuse requests paging past `max_result_window` rows.
+pub async fn row_limits_middleware(
+    State(state): State<Arc<AppState>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    // Malformed parameters are left to the handler's own validation.
+    if let Ok(Query(params)) = Query::<PageParams>::try_from_uri(request.uri()) {
+        let max_result_window = state.row_limits.max_result_window;
+        if let Err(err) = check_result_window(&params, max_result_window) {
+            if params.module.is_some() {
+                return Json(notok(&format!(
+                    "Result window is too large, PageNo x Offset size must be less than or \
+                     equal to {max_result_window}"
+                )))
+                .into_response();
+            }
+            return ApiError(err).into_response();
+        }
+    }
+    next.run(request).await
+}
+
+fn check_result_window(params: &PageParams, max_result_window: u64) -> Result<(), AtlasError> {
+    let Some(page) = params.page.filter(|page| *page > 1) else {
+        return Ok(());
+    };
+    let size = if params.module.is_some() {
+        params.offset.unwrap_or(10)
+    } else {
+        params.limit.unwrap_or(20)
+    }
+    .clamp(1, MAX_PAGE_SIZE);
+
+    let last_row = page.saturating_mul(size);
+    if last_row > max_result_window {
+        return Err(AtlasError::LimitExceeded(format!(
+            "page {page} of {size} rows reaches row {last_row}, past the limit of \
+             {max_result_window} rows; narrow the query with filters or a block range \
+             instead of paging deeper"
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params(page: u64, limit: Option<u64>, offset: Option<u64>, module: bool) -> PageParams {
+        PageParams {
+            page: Some(page),
+            limit,
+            offset,
+            module: module.then(|| "account".to_string()),
+        }
+    }
+
+    #[test]
+    fn allows_pages_within_the_window() {
+        assert!(check_result_window(&params(100, Some(100), None, false), 10_000).is_ok());
+        assert!(check_result_window(&params(500, None, None, false), 10_000).is_ok());
+        // Etherscan pages are sized by `offset`, defaulting to 10
+        assert!(check_result_window(&params(1_000, None, None, true), 10_000).is_ok());
+    }
+
+    #[test]
+    fn refuses_pages_past_the_window_with_422() {
+        let err = check_result_window(&params(101, Some(100), None, false), 10_000).unwrap_err();
+        assert_eq!(err.status_code(), 422);
+        assert!(err.to_string().contains("reaches row 10100"));
+        assert!(err.to_string().contains("limit of 10000 rows"));
+
+        // Oversized limits count as the clamped page size
+        assert!(check_result_window(&params(101, Some(1_000), None, false), 10_000).is_err());
+        assert!(check_result_window(&params(100, Some(1_000), None, false), 10_000).is_ok());
+        assert!(check_result_window(&params(101, None, Some(100), true), 10_000).is_err());
+    }
+}
@@ -0,0 +1,300 @@
+//! Versioned API under `/api/v1`.
+//!
+//! `/api/v1/<path>` is served by the same handlers as `/api/<path>`: the
+//! request is dispatched through the API router with the version prefix
+//! removed, and the JSON response is then put in the v1 envelope.
+//!
+//! - Success: `{"data": ...}`. Paginated lists carry their page fields in
+//!   `pagination`: `{"data": [...], "pagination": {"page": 1, ...}}`.
+//! - Error: `{"error": {"code": "not_found", "message": "..."}}`, plus
+//!   `retry_after_seconds` when the request may be retried.
+//!
+//! Other successful responses (NDJSON, SSE, images, empty bodies) pass
+//! through as is.
+//! Shape changes go into a new version; the unversioned paths keep today's
+//! shapes and point to their successor with `Deprecation` and `Link` headers.
+
+use axum::{
+    body::Body,
+    extract::Request,
+    http::{header, HeaderValue, StatusCode, Uri},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Router,
+};
+use serde_json::{json, Map, Value};
+use tower::ServiceExt;
+
+use atlas_common::numeric::with_numbers_as_strings;
+
+/// Prefix of the current API version.
+pub const V1_PREFIX: &str = "/api/v1";
+
+/// Request extension marking a call dispatched from `/api/v1`.
+#[derive(Clone, Copy, Debug)]
+pub struct Versioned;
+
+/// `/api/v1/<path>` — run `/api/<path>` on `api` and wrap its response.
+pub async fn serve_v1(api: Router, request: Request) -> Response {
+    let Some(uri) = unversioned_uri(request.uri()) else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+    // A fresh request, so the `{*path}` match of this route does not reach
+    // the handler's path extractor.
+    let (parts, body) = request.into_parts();
+    let mut request = Request::new(body);
+    *request.method_mut() = parts.method;
+    *request.uri_mut() = uri;
+    *request.version_mut() = parts.version;
+    *request.headers_mut() = parts.headers;
+    request.extensions_mut().insert(Versioned);
+    // The body is rewritten below, so it must arrive uncompressed; the v1
+    // route compresses the result itself.
+    request.headers_mut().remove(header::ACCEPT_ENCODING);
+
+    let response = match with_numbers_as_strings(api.oneshot(request)).await {
+        Ok(response) => response,
+        Err(never) => match never {},
+    };
+    envelope(response).await
+}
+
+/// `/api/v1/x?q` as `/api/x?q`; `None` for anything that is not a v1 path.
+fn unversioned_uri(uri: &Uri) -> Option<Uri> {
+    let rest = uri.path().strip_prefix(V1_PREFIX)?;
+    if !rest.starts_with('/') || rest.len() == 1 {
+        return None;
+    }
+    let path_and_query = match uri.query() {
+        Some(query) => format!("/api{rest}?{query}"),
+        None => format!("/api{rest}"),
+    };
+    path_and_query.parse().ok()
+}
+
+async fn envelope(response: Response) -> Response {
+    let status = response.status();
+    let content_type = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default();
+    let is_json = content_type.starts_with("application/json");
+    // Errors from the router or extractors come as plain text or empty.
+    let is_plain_error = (status.is_client_error() || status.is_server_error())
+        && (content_type.is_empty() || content_type.starts_with("text/plain"));
+    if !is_json && !is_plain_error {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let Ok(bytes) = axum::body::to_bytes(body, usize::MAX).await else {
+        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+    };
+    let value = if is_json {
+        serde_json::from_slice(&bytes).unwrap_or(Value::Null)
+    } else {
+        Value::String(String::from_utf8_lossy(&bytes).trim().to_string())
+    };
+    let wrapped = if status.is_success() {
+        success_envelope(value)
+    } else {
+        error_envelope(status, value)
+    };
+
+    parts.headers.remove(header::CONTENT_LENGTH);
+    parts.headers.insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static("application/json"),
+    );
+    Response::from_parts(parts, Body::from(wrapped.to_string()))
+}
+
+fn success_envelope(value: Value) -> Value {
+    match value {
+        Value::Object(mut object) if is_paginated(&object) => {
+            let data = object.remove("data").unwrap_or_default();
+            json!({ "data": data, "pagination": object })
+        }
+        value => json!({ "data": value }),
+    }
+}
+
+/// A `PaginatedResponse`: a `data` array with `page` and `limit`.
+fn is_paginated(object: &Map<String, Value>) -> bool {
+    object.get("data").is_some_and(Value::is_array)
+        && object.get("page").is_some_and(Value::is_u64)
+        && object.get("limit").is_some_and(Value::is_u64)
+}
+
+fn error_envelope(status: StatusCode, value: Value) -> Value {
+    let mut error = Map::new();
+    let (message, code, retry_after) = match value {
+        Value::Object(mut object) => (
+            object.remove("error"),
+            object.remove("code"),
+            object.remove("retry_after_seconds"),
+        ),
+        Value::String(message) if !message.is_empty() => (Some(Value::String(message)), None, None),
+        _ => (None, None, None),
+    };
+    error.insert(
+        "code".to_string(),
+        code.unwrap_or_else(|| Value::String(error_code(status).to_string())),
+    );
+    error.insert(
+        "message".to_string(),
+        message.unwrap_or_else(|| {
+            Value::String(status.canonical_reason().unwrap_or("Error").to_string())
+        }),
+    );
+    if let Some(retry_after) = retry_after {
+        error.insert("retry_after_seconds".to_string(), retry_after);
+    }
+    json!({ "error": error })
+}
+
+/// Machine-readable code of an error status.
+fn error_code(status: StatusCode) -> &'static str {
+    match status {
+        StatusCode::BAD_REQUEST => "invalid_input",
+        StatusCode::UNAUTHORIZED => "unauthorized",
+        StatusCode::FORBIDDEN => "forbidden",
+        StatusCode::NOT_FOUND => "not_found",
+        StatusCode::METHOD_NOT_ALLOWED => "method_not_allowed",
+        StatusCode::REQUEST_TIMEOUT => "timeout",
+        StatusCode::CONFLICT => "conflict",
+        StatusCode::PAYLOAD_TOO_LARGE => "payload_too_large",
+        StatusCode::UNSUPPORTED_MEDIA_TYPE => "unsupported_media_type",
+        StatusCode::UNPROCESSABLE_ENTITY => "limit_exceeded",
+        StatusCode::TOO_MANY_REQUESTS => "too_many_requests",
+        StatusCode::SERVICE_UNAVAILABLE => "unavailable",
+        status if status.is_client_error() => "bad_request",
+        _ => "internal",
+    }
+}
+
+/// Mark unversioned `/api/...` responses as superseded by `/api/v1/...`.
+/// The Etherscan-compatible `/api` keeps Etherscan's format and is not marked.
+pub async fn deprecation_headers(request: Request, next: Next) -> Response {
+    let successor = (request.extensions().get::<Versioned>().is_none())
+        .then(|| request.uri().path().strip_prefix("/api/"))
+        .flatten()
+        .filter(|rest| !rest.is_empty() && !rest.starts_with("v1/"))
+        .map(|rest| format!("<{V1_PREFIX}/{rest}>; rel=\"successor-version\""));
+    let mut response = next.run(request).await;
+    if let Some(link) = successor.and_then(|link| HeaderValue::from_str(&link).ok()) {
+        let headers = response.headers_mut();
+        headers.insert("deprecation", HeaderValue::from_static("true"));
+        headers.insert(header::LINK, link);
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{http::Request, middleware, routing::get, Json};
+
+    fn api() -> Router {
+        Router::new()
+            .route(
+                "/api/items",
+                get(|| async {
+                    Json(json!({ "data": [1, 2], "page": 1, "limit": 2, "total": 9 }))
+                }),
+            )
+            .route(
+                "/api/items/{id}",
+                get(|| async { Json(json!({ "id": 7 })) }),
+            )
+            .route(
+                "/api/busy",
+                get(|| async {
+                    (
+                        StatusCode::TOO_MANY_REQUESTS,
+                        Json(json!({ "error": "slow down", "retry_after_seconds": 2 })),
+                    )
+                }),
+            )
+            .route("/api/raw", get(|| async { "plain" }))
+            .layer(middleware::from_fn(deprecation_headers))
+    }
+
+    async fn call(path: &str) -> (StatusCode, axum::http::HeaderMap, Value) {
+        let request = Request::builder().uri(path).body(Body::empty()).unwrap();
+        let response = serve_v1(api(), request).await;
+        let status = response.status();
+        let headers = response.headers().clone();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let value = serde_json::from_slice(&bytes)
+            .unwrap_or_else(|_| Value::String(String::from_utf8_lossy(&bytes).into_owned()));
+        (status, headers, value)
+    }
+
+    #[tokio::test]
+    async fn v1_wraps_objects_lists_and_errors() {
+        let (status, headers, body) = call("/api/v1/items?page=1").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(
+            body,
+            json!({ "data": [1, 2], "pagination": { "page": 1, "limit": 2, "total": 9 } })
+        );
+        assert!(headers.get("deprecation").is_none());
+
+        let (_, _, body) = call("/api/v1/items/7").await;
+        assert_eq!(body, json!({ "data": { "id": 7 } }));
+
+        let (status, _, body) = call("/api/v1/busy").await;
+        assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(
+            body,
+            json!({ "error": {
+                "code": "too_many_requests", "message": "slow down", "retry_after_seconds": 2
+            } })
+        );
+
+        let (status, _, body) = call("/api/v1/missing").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(
+            body,
+            json!({ "error": { "code": "not_found", "message": "Not Found" } })
+        );
+
+        // Non-JSON responses are passed through.
+        let (_, _, body) = call("/api/v1/raw").await;
+        assert_eq!(body, "plain");
+    }
+
+    #[test]
+    fn unversioned_uri_keeps_the_query() {
+        let uri = |path: &str| unversioned_uri(&path.parse().unwrap()).map(|uri| uri.to_string());
+        assert_eq!(
+            uri("/api/v1/blocks?page=2"),
+            Some("/api/blocks?page=2".into())
+        );
+        assert_eq!(uri("/api/v1/"), None);
+        assert_eq!(uri("/api/v10/blocks"), None);
+        assert_eq!(uri("/api/blocks"), None);
+    }
+
+    #[tokio::test]
+    async fn unversioned_paths_name_their_successor() {
+        let response = api()
+            .oneshot(
+                Request::builder()
+                    .uri("/api/items/7?x=1")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.headers()["deprecation"], "true");
+        assert_eq!(
+            response.headers()[header::LINK],
+            "</api/v1/items/7>; rel=\"successor-version\""
+        );
+    }
+}
@@ -0,0 +1,105 @@
+//! Logs bloom storage format and membership checks.
+//!
+//! Blocks and transactions keep the 2048-bit logs bloom from the header and
+//! receipt in a `logs_bloom BYTEA` column. An empty value means "no logs" (an
+//! all-zero bloom), which is the common case for transactions and saves
+//! 256 bytes per row; `NULL` means the row was indexed before blooms were
+//! stored, and callers must fall back to `event_logs`.
+//!
+//! A bloom answers "definitely not present" or "possibly present", so it can
+//! rule out blocks and transactions (and, for range queries, whole
+//! partitions) before any `event_logs` lookup.
+
+use alloy::primitives::{Address, Bloom, BloomInput, B256};
+
+/// Storage form of a bloom: empty when no bits are set.
+pub fn compact(bloom: &Bloom) -> Vec<u8> {
+    if bloom.is_zero() {
+        Vec::new()
+    } else {
+        bloom.as_slice().to_vec()
+    }
+}
+
+/// Decode a stored bloom; `None` for a malformed value.
+pub fn expand(stored: &[u8]) -> Option<Bloom> {
+    match stored.len() {
+        0 => Some(Bloom::ZERO),
+        256 => Some(Bloom::from_slice(stored)),
+        _ => None,
+    }
+}
+
+/// Log filter checked against a bloom. All set criteria must match.
+#[derive(Debug, Clone, Default)]
+pub struct BloomFilter {
+    /// Matches a log emitted by the address or carrying it as an indexed
+    /// (left-padded) topic, e.g. the `from`/`to` of a Transfer.
+    pub address: Option<Address>,
+    pub topic: Option<B256>,
+}
+
+impl BloomFilter {
+    pub fn is_empty(&self) -> bool {
+        self.address.is_none() && self.topic.is_none()
+    }
+
+    /// `false` means no log can match; `true` means one may.
+    pub fn may_match(&self, bloom: &Bloom) -> bool {
+        let address_ok = self.address.is_none_or(|address| {
+            bloom.contains_input(BloomInput::Raw(address.as_slice()))
+                || bloom.contains_input(BloomInput::Raw(address.into_word().as_slice()))
+        });
+        let topic_ok = self
+            .topic
+            .is_none_or(|topic| bloom.contains_input(BloomInput::Raw(topic.as_slice())));
+        address_ok && topic_ok
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::{address, b256};
+
+    const EMITTER: Address = address!("0x1111111111111111111111111111111111111111");
+    const HOLDER: Address = address!("0x2222222222222222222222222222222222222222");
+    const TRANSFER: B256 =
+        b256!("0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef");
+
+    fn transfer_bloom() -> Bloom {
+        let mut bloom = Bloom::ZERO;
+        bloom.accrue(BloomInput::Raw(EMITTER.as_slice()));
+        bloom.accrue(BloomInput::Raw(TRANSFER.as_slice()));
+        bloom.accrue(BloomInput::Raw(HOLDER.into_word().as_slice()));
+        bloom
+    }
+
+    #[test]
+    fn compact_round_trips_and_elides_empty_blooms() {
+        assert!(compact(&Bloom::ZERO).is_empty());
+        assert_eq!(expand(&[]), Some(Bloom::ZERO));
+
+        let bloom = transfer_bloom();
+        assert_eq!(expand(&compact(&bloom)), Some(bloom));
+        assert_eq!(expand(&[0u8; 12]), None);
+    }
+
+    #[test]
+    fn filter_matches_emitter_and_indexed_address() {
+        let bloom = transfer_bloom();
+        let matches = |address: Option<Address>, topic: Option<B256>| {
+            BloomFilter { address, topic }.may_match(&bloom)
+        };
+
+        assert!(matches(Some(EMITTER), None));
+        assert!(matches(Some(HOLDER), Some(TRANSFER)));
+        assert!(!matches(Some(Address::repeat_byte(0x33)), None));
+        assert!(!matches(None, Some(B256::repeat_byte(0x44))));
+        assert!(!BloomFilter {
+            address: Some(EMITTER),
+            topic: None,
+        }
+        .may_match(&Bloom::ZERO));
+    }
+}
@@ -0,0 +1,82 @@
+//! Chain ID guard: an index belongs to exactly one chain.
+//!
+//! The first process to start against an empty database records the RPC's
+//! chain ID in `indexer_state`; every later start of the API or the indexer
+//! compares the RPC against it and refuses to run on a mismatch, so a
+//! misconfigured `--atlas.rpc.url` cannot mix two chains in one index.
+
+use anyhow::{bail, Context, Result};
+use sqlx::PgPool;
+
+use crate::state_keys::CHAIN_ID_KEY;
+
+/// The chain ID this database was indexed from, if one has been recorded.
+pub async fn stored_chain_id(pool: &PgPool) -> Result<Option<u64>> {
+    let value: Option<String> =
+        sqlx::query_scalar("SELECT value FROM indexer_state WHERE key = $1")
+            .bind(CHAIN_ID_KEY)
+            .fetch_optional(pool)
+            .await?;
+    value
+        .map(|v| {
+            v.parse()
+                .with_context(|| format!("invalid chain ID {v:?} in indexer_state"))
+        })
+        .transpose()
+}
+
+/// Record `rpc_chain_id` if the database has none yet, otherwise fail unless
+/// it matches the stored one.
+pub async fn ensure_chain_id(pool: &PgPool, rpc_chain_id: u64) -> Result<()> {
+    // Insert-if-absent first so concurrent starters agree on a single value.
+    let inserted = sqlx::query(
+        "INSERT INTO indexer_state (key, value, updated_at) VALUES ($1, $2, NOW())
+         ON CONFLICT (key) DO NOTHING",
+    )
+    .bind(CHAIN_ID_KEY)
+    .bind(rpc_chain_id.to_string())
+    .execute(pool)
+    .await?
+    .rows_affected();
+    if inserted > 0 {
+        tracing::info!(
+            chain_id = rpc_chain_id,
+            "recorded chain ID for this database"
+        );
+        return Ok(());
+    }
+
+    let stored = stored_chain_id(pool)
+        .await?
+        .context("chain ID disappeared from indexer_state")?;
+    check_matches(stored, rpc_chain_id)
+}
+
+/// Error unless the stored and RPC chain IDs agree.
+pub fn check_matches(stored: u64, rpc_chain_id: u64) -> Result<()> {
+    if stored != rpc_chain_id {
+        bail!(
+            "RPC reports chain ID {rpc_chain_id} but this database was indexed from chain ID \
+             {stored}; point --atlas.rpc.url at the right node, or run `atlas-server db reset` \
+             (or start with --atlas.indexer.reindex) to index the new chain"
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_matches_accepts_equal_ids() {
+        assert!(check_matches(42161, 42161).is_ok());
+    }
+
+    #[test]
+    fn check_matches_names_both_ids_on_mismatch() {
+        let err = check_matches(1, 42161).unwrap_err().to_string();
+        assert!(err.contains("chain ID 42161"), "{err}");
+        assert!(err.contains("chain ID 1;"), "{err}");
+    }
+}
@@ -4,6 +4,16 @@ fn database_url_from_env() -> String {
     std::env::var("DATABASE_URL").unwrap_or_default()
 }
 
+/// The `run` subcommand, whose `--atlas.*` flags define the config file keys.
+pub fn run_command() -> clap::Command {
+    use clap::CommandFactory;
+
+    Cli::command()
+        .find_subcommand("run")
+        .expect("run subcommand is defined")
+        .clone()
+}
+
 /// Atlas — EVM blockchain explorer
 #[derive(Parser)]
 #[command(
@@ -12,6 +22,15 @@ fn database_url_from_env() -> String {
     about = "EVM blockchain explorer — indexer + API + DA tracking"
 )]
 pub struct Cli {
+    #[arg(
+        long = "config",
+        env = "ATLAS_CONFIG",
+        global = true,
+        value_name = "FILE",
+        help = "TOML file with settings for any --atlas.* flag; flags and env vars take precedence"
+    )]
+    pub config: Option<std::path::PathBuf>,
+
     #[command(subcommand)]
     pub command: Command,
 }
@@ -46,9 +65,17 @@ pub struct RunArgs {
     #[command(flatten)]
     pub faucet: FaucetArgs,
     #[command(flatten)]
+    pub labels: LabelArgs,
+    #[command(flatten)]
     pub branding: BrandingArgs,
     #[command(flatten)]
     pub log: LogArgs,
+
+    #[arg(
+        long = "print-config",
+        help = "Validate and print the resolved configuration as TOML, then exit"
+    )]
+    pub print_config: bool,
 }
 
 #[derive(Args, Clone)]
@@ -84,6 +111,122 @@ pub struct DatabaseArgs {
         help = "Max connections for the API pool"
     )]
     pub api_max_connections: u32,
+
+    #[arg(
+        long = "atlas.db.min-connections",
+        env = "DB_MIN_CONNECTIONS",
+        default_value = "0",
+        value_name = "N",
+        help = "Connections the indexer pool keeps open when idle"
+    )]
+    pub min_connections: u32,
+
+    #[arg(
+        long = "atlas.db.acquire-timeout-secs",
+        env = "DB_ACQUIRE_TIMEOUT_SECS",
+        default_value = "30",
+        value_name = "SECS",
+        help = "How long indexer queries wait for a free pooled connection"
+    )]
+    pub acquire_timeout_secs: u64,
+
+    #[arg(
+        long = "atlas.db.idle-timeout-secs",
+        env = "DB_IDLE_TIMEOUT_SECS",
+        default_value = "600",
+        value_name = "SECS",
+        help = "Close idle indexer pool connections after this long (0 = never)"
+    )]
+    pub idle_timeout_secs: u64,
+
+    #[arg(
+        long = "atlas.db.statement-cache-capacity",
+        env = "DB_STATEMENT_CACHE_CAPACITY",
+        default_value = "100",
+        value_name = "N",
+        help = "Prepared statements cached per indexer connection (0 = disabled)"
+    )]
+    pub statement_cache_capacity: usize,
+
+    #[arg(
+        long = "atlas.db.api-min-connections",
+        env = "API_DB_MIN_CONNECTIONS",
+        default_value = "0",
+        value_name = "N",
+        help = "Connections the API pool keeps open when idle"
+    )]
+    pub api_min_connections: u32,
+
+    #[arg(
+        long = "atlas.db.api-acquire-timeout-secs",
+        env = "API_DB_ACQUIRE_TIMEOUT_SECS",
+        default_value = "30",
+        value_name = "SECS",
+        help = "How long API queries wait for a free pooled connection"
+    )]
+    pub api_acquire_timeout_secs: u64,
+
+    #[arg(
+        long = "atlas.db.api-idle-timeout-secs",
+        env = "API_DB_IDLE_TIMEOUT_SECS",
+        default_value = "600",
+        value_name = "SECS",
+        help = "Close idle API pool connections after this long (0 = never)"
+    )]
+    pub api_idle_timeout_secs: u64,
+
+    #[arg(
+        long = "atlas.db.api-statement-cache-capacity",
+        env = "API_DB_STATEMENT_CACHE_CAPACITY",
+        default_value = "100",
+        value_name = "N",
+        help = "Prepared statements cached per API connection (0 = disabled)"
+    )]
+    pub api_statement_cache_capacity: usize,
+
+    #[arg(
+        long = "atlas.db.circuit-breaker-threshold",
+        env = "DB_CIRCUIT_BREAKER_THRESHOLD",
+        default_value = "5",
+        value_name = "N",
+        help = "Consecutive API database failures before the circuit breaker opens (0 = disabled)"
+    )]
+    pub circuit_breaker_threshold: u32,
+
+    #[arg(
+        long = "atlas.db.circuit-breaker-cooldown-secs",
+        env = "DB_CIRCUIT_BREAKER_COOLDOWN_SECS",
+        default_value = "30",
+        value_name = "SECS",
+        help = "How long the open circuit breaker rejects API requests with 503"
+    )]
+    pub circuit_breaker_cooldown_secs: u64,
+
+    #[arg(
+        long = "atlas.db.cold-before-block",
+        env = "COLD_STORAGE_BEFORE_BLOCK",
+        default_value = "0",
+        value_name = "BLOCK",
+        help = "Read event logs and ERC-20 transfers below this block from the cold tier (0 = disabled)"
+    )]
+    pub cold_before_block: u64,
+
+    #[arg(
+        long = "atlas.db.cold-url",
+        env = "COLD_STORAGE_URL",
+        value_name = "URL",
+        help = "Postgres database holding the cold tier (defaults to DATABASE_URL)"
+    )]
+    pub cold_url: Option<String>,
+
+    #[arg(
+        long = "atlas.db.cold-schema",
+        env = "COLD_STORAGE_SCHEMA",
+        default_value = "archive",
+        value_name = "SCHEMA",
+        help = "Schema of the cold tier's event_logs and erc20_transfers tables"
+    )]
+    pub cold_schema: String,
 }
 
 #[derive(Args, Clone)]
@@ -164,6 +307,139 @@ pub struct ApiArgs {
         help = "Directory to cache downloaded solc compiler binaries"
     )]
     pub solc_cache_dir: String,
+
+    #[arg(
+        long = "atlas.api.verify-timeout-secs",
+        env = "API_VERIFY_TIMEOUT_SECS",
+        default_value = "300",
+        value_name = "SECS",
+        help = "Time budget for one contract verification request, including compiler download and compilation"
+    )]
+    pub verify_timeout_secs: u64,
+
+    #[arg(
+        long = "atlas.api.verify-max-source-bytes",
+        env = "API_VERIFY_MAX_SOURCE_BYTES",
+        default_value = "20971520",
+        value_name = "BYTES",
+        help = "Max size of the source_code or standard_json_input submitted for verification"
+    )]
+    pub verify_max_source_bytes: usize,
+
+    #[arg(
+        long = "atlas.api.max-concurrency",
+        env = "API_MAX_CONCURRENCY",
+        default_value = "0",
+        value_name = "N",
+        help = "Max in-flight requests across API routes without their own limit; excess get 429 (0 = unlimited)"
+    )]
+    pub max_concurrency: usize,
+
+    #[arg(
+        long = "atlas.api.search-max-concurrency",
+        env = "API_SEARCH_MAX_CONCURRENCY",
+        default_value = "8",
+        value_name = "N",
+        help = "Max in-flight /api/search requests; excess get 429 (0 = unlimited)"
+    )]
+    pub search_max_concurrency: usize,
+
+    #[arg(
+        long = "atlas.api.etherscan-max-concurrency",
+        env = "API_ETHERSCAN_MAX_CONCURRENCY",
+        default_value = "8",
+        value_name = "N",
+        help = "Max in-flight Etherscan-compatible /api requests; excess get 429 (0 = unlimited)"
+    )]
+    pub etherscan_max_concurrency: usize,
+
+    #[arg(
+        long = "atlas.api.etherscan-confirmations-head",
+        env = "ETHERSCAN_CONFIRMATIONS_HEAD",
+        default_value = "indexed",
+        value_name = "HEAD",
+        help = "Head Etherscan-compatible confirmations count from: indexed, chain (node head) or finalized"
+    )]
+    pub etherscan_confirmations_head: String,
+
+    #[arg(
+        long = "atlas.api.max-body-bytes",
+        env = "API_MAX_BODY_BYTES",
+        default_value = "2097152",
+        value_name = "BYTES",
+        help = "Max request body size for API routes without their own limit; larger bodies get 413"
+    )]
+    pub max_body_bytes: usize,
+
+    #[arg(
+        long = "atlas.api.verify-max-body-bytes",
+        env = "API_VERIFY_MAX_BODY_BYTES",
+        default_value = "52428800",
+        value_name = "BYTES",
+        help = "Max request body size for contract verification; larger bodies get 413"
+    )]
+    pub verify_max_body_bytes: usize,
+
+    #[arg(
+        long = "atlas.api.logs-max-block-range",
+        env = "API_LOGS_MAX_BLOCK_RANGE",
+        default_value = "10000",
+        value_name = "BLOCKS",
+        help = "Max blocks one /api/rpc/logs request may span; wider ranges get 422"
+    )]
+    pub logs_max_block_range: u64,
+
+    #[arg(
+        long = "atlas.api.logs-max-results",
+        env = "API_LOGS_MAX_RESULTS",
+        default_value = "10000",
+        value_name = "N",
+        help = "Max logs one /api/rpc/logs request may return; larger result sets get 422"
+    )]
+    pub logs_max_results: usize,
+
+    #[arg(
+        long = "atlas.api.max-result-window",
+        env = "API_MAX_RESULT_WINDOW",
+        default_value = "10000",
+        value_name = "ROWS",
+        help = "Deepest row (page × limit) paginated API requests may reach; deeper pages get 422"
+    )]
+    pub max_result_window: u64,
+
+    #[arg(
+        long = "atlas.api.slow-request-ms",
+        env = "API_SLOW_REQUEST_MS",
+        default_value = "1000",
+        value_name = "MS",
+        help = "API requests taking at least this long are logged with their SQL (0 = off)"
+    )]
+    pub slow_request_ms: u64,
+
+    #[arg(
+        long = "atlas.api.export-dir",
+        env = "EXPORT_DIR",
+        value_name = "DIR",
+        help = "Directory for bulk export files; enables /api/export (unset = disabled)"
+    )]
+    pub export_dir: Option<String>,
+
+    #[arg(
+        long = "atlas.api.export-retention-hours",
+        env = "EXPORT_RETENTION_HOURS",
+        default_value = "24",
+        value_name = "HOURS",
+        help = "Hours a finished export (and its file) is kept"
+    )]
+    pub export_retention_hours: u64,
+
+    #[arg(
+        long = "atlas.api.dataset-dir",
+        env = "DATASET_DIR",
+        value_name = "DIR",
+        help = "Directory for daily public dataset dumps; enables /api/datasets (unset = disabled)"
+    )]
+    pub dataset_dir: Option<String>,
 }
 
 #[derive(Args, Clone)]
@@ -231,6 +507,271 @@ pub struct IndexerArgs {
         help = "Max retry attempts for metadata fetches"
     )]
     pub metadata_retry_attempts: u32,
+
+    #[arg(
+        long = "atlas.indexer.metadata-priority-window-blocks",
+        env = "METADATA_PRIORITY_WINDOW_BLOCKS",
+        default_value = "10000",
+        value_name = "BLOCKS",
+        help = "Fetch contract metadata busiest first, by transfers in this many recent blocks plus holders (0 = unordered)"
+    )]
+    pub metadata_priority_window_blocks: u64,
+
+    #[arg(
+        long = "atlas.indexer.metadata-nft-contract-workers",
+        env = "METADATA_NFT_CONTRACT_WORKERS",
+        default_value = "0",
+        value_name = "N",
+        help = "Concurrent NFT contract metadata fetches (0 = metadata-fetch-workers)"
+    )]
+    pub metadata_nft_contract_workers: u32,
+
+    #[arg(
+        long = "atlas.indexer.metadata-erc20-contract-workers",
+        env = "METADATA_ERC20_CONTRACT_WORKERS",
+        default_value = "0",
+        value_name = "N",
+        help = "Concurrent ERC-20 contract metadata fetches (0 = metadata-fetch-workers)"
+    )]
+    pub metadata_erc20_contract_workers: u32,
+
+    #[arg(
+        long = "atlas.indexer.metadata-nft-token-workers",
+        env = "METADATA_NFT_TOKEN_WORKERS",
+        default_value = "0",
+        value_name = "N",
+        help = "Concurrent NFT token metadata fetches (0 = metadata-fetch-workers)"
+    )]
+    pub metadata_nft_token_workers: u32,
+
+    #[arg(
+        long = "atlas.indexer.metadata-external",
+        env = "METADATA_EXTERNAL",
+        default_value_t = false,
+        help = "Leave token metadata to a separate atlas-metadata process instead of fetching it here"
+    )]
+    pub metadata_external: bool,
+
+    #[arg(
+        long = "atlas.indexer.raw-archive",
+        env = "RAW_ARCHIVE_ENABLED",
+        default_value_t = false,
+        help = "Archive compressed raw block and receipt JSON during ingestion"
+    )]
+    pub raw_archive: bool,
+
+    #[arg(
+        long = "atlas.indexer.replay-archive",
+        env = "REPLAY_ARCHIVE",
+        default_value_t = false,
+        help = "Index blocks from the raw archive before fetching from RPC"
+    )]
+    pub replay_archive: bool,
+
+    #[arg(
+        long = "atlas.indexer.defer-partition-indexes",
+        env = "DEFER_PARTITION_INDEXES",
+        default_value_t = false,
+        help = "Fill partitions entirely below the chain head without secondary indexes and build them once each partition is complete"
+    )]
+    pub defer_partition_indexes: bool,
+
+    #[arg(
+        long = "atlas.indexer.calldata-prefix-index",
+        env = "CALLDATA_PREFIX_INDEX",
+        default_value_t = false,
+        help = "Index the first 36 bytes of transaction calldata so /api/transactions can filter by input_prefix; built in the background without blocking indexing"
+    )]
+    pub calldata_prefix_index: bool,
+
+    #[arg(
+        long = "atlas.indexer.backfill-shards",
+        env = "BACKFILL_SHARDS",
+        default_value = "0",
+        value_name = "COUNT",
+        help = "Backfill workers indexing leased historical ranges in parallel; on a fresh index the head indexer splits the history into ranges and follows the head (0 = disabled)"
+    )]
+    pub backfill_shards: u32,
+
+    #[arg(
+        long = "atlas.indexer.backfill-range-blocks",
+        env = "BACKFILL_RANGE_BLOCKS",
+        default_value = "100000",
+        value_name = "BLOCKS",
+        help = "Blocks per leased backfill range"
+    )]
+    pub backfill_range_blocks: u64,
+
+    #[arg(
+        long = "atlas.indexer.backfill-only",
+        env = "BACKFILL_ONLY",
+        default_value_t = false,
+        help = "Run only the backfill workers, leaving the chain head to another instance"
+    )]
+    pub backfill_only: bool,
+
+    #[arg(
+        long = "atlas.indexer.genesis-file",
+        env = "GENESIS_FILE",
+        value_name = "PATH",
+        help = "Chain genesis JSON whose alloc accounts (balances, predeploys) are imported at block 0"
+    )]
+    pub genesis_file: Option<std::path::PathBuf>,
+
+    #[arg(
+        long = "atlas.indexer.daily-stats-interval-secs",
+        env = "DAILY_STATS_INTERVAL_SECS",
+        default_value = "300",
+        value_name = "SECONDS",
+        help = "Seconds between daily chain summary aggregation cycles (0 = disabled)"
+    )]
+    pub daily_stats_interval_secs: u64,
+
+    #[arg(
+        long = "atlas.indexer.holder-ranks-interval-secs",
+        env = "HOLDER_RANKS_INTERVAL_SECS",
+        default_value = "300",
+        value_name = "SECONDS",
+        help = "Seconds between ERC-20 holder ranking refreshes for token holder pages (0 = disabled)"
+    )]
+    pub holder_ranks_interval_secs: u64,
+
+    #[arg(
+        long = "atlas.indexer.data-quality-interval-secs",
+        env = "DATA_QUALITY_INTERVAL_SECS",
+        default_value = "600",
+        value_name = "SECONDS",
+        help = "Seconds between data-quality checks for negative balances, missing receipts and transfer/balance mismatches (0 = disabled)"
+    )]
+    pub data_quality_interval_secs: u64,
+
+    #[arg(
+        long = "atlas.indexer.transfer-retention-blocks",
+        env = "TRANSFER_RETENTION_BLOCKS",
+        default_value = "0",
+        value_name = "BLOCKS",
+        help = "Fold ERC-20 transfers older than this many blocks into daily per-pair summaries and delete the raw rows (0 = keep every transfer)"
+    )]
+    pub transfer_retention_blocks: u64,
+
+    #[arg(
+        long = "atlas.indexer.canonical-tokens-interval-secs",
+        env = "CANONICAL_TOKENS_INTERVAL_SECS",
+        default_value = "600",
+        value_name = "SECONDS",
+        help = "Seconds between canonical token resolution cycles for shared ERC-20 symbols (0 = disabled)"
+    )]
+    pub canonical_tokens_interval_secs: u64,
+
+    #[arg(
+        long = "atlas.indexer.pending-verification-interval-secs",
+        env = "PENDING_VERIFICATION_INTERVAL_SECS",
+        default_value = "30",
+        value_name = "SECONDS",
+        help = "Seconds between checks of newly created contracts against pre-registered verifications (0 = disabled)"
+    )]
+    pub pending_verification_interval_secs: u64,
+
+    #[arg(
+        long = "atlas.indexer.dex-pool-interval-secs",
+        env = "DEX_POOL_INTERVAL_SECS",
+        default_value = "30",
+        value_name = "SECONDS",
+        help = "Seconds between reads of the tokens of new DEX pools, when the dex_swaps processor runs (0 = disabled)"
+    )]
+    pub dex_pool_interval_secs: u64,
+
+    #[arg(
+        long = "atlas.indexer.alerts-interval-secs",
+        env = "ALERTS_INTERVAL_SECS",
+        default_value = "10",
+        value_name = "SECONDS",
+        help = "Longest wait between alert rule evaluation cycles; cycles also run after each indexed batch (0 = disabled)"
+    )]
+    pub alerts_interval_secs: u64,
+
+    #[arg(
+        long = "atlas.indexer.alerts-allow-private-webhooks",
+        env = "ALERTS_ALLOW_PRIVATE_WEBHOOKS",
+        default_value_t = false,
+        help = "Allow alert webhooks on loopback and private network addresses"
+    )]
+    pub alerts_allow_private_webhooks: bool,
+
+    #[arg(
+        long = "atlas.indexer.ops-alert-slack-webhook-url",
+        env = "OPS_ALERT_SLACK_WEBHOOK_URL",
+        value_name = "URL",
+        help = "Slack incoming webhook notified of failed blocks and indexer restarts"
+    )]
+    pub ops_alert_slack_webhook_url: Option<String>,
+
+    #[arg(
+        long = "atlas.indexer.ops-alert-pagerduty-routing-key",
+        env = "OPS_ALERT_PAGERDUTY_ROUTING_KEY",
+        value_name = "KEY",
+        help = "PagerDuty Events API v2 routing key notified of failed blocks and indexer restarts"
+    )]
+    pub ops_alert_pagerduty_routing_key: Option<String>,
+
+    #[arg(
+        long = "atlas.indexer.ops-alert-pagerduty-url",
+        env = "OPS_ALERT_PAGERDUTY_URL",
+        default_value = crate::ops_alerts::PAGERDUTY_EVENTS_URL,
+        value_name = "URL",
+        help = "PagerDuty-compatible events endpoint"
+    )]
+    pub ops_alert_pagerduty_url: String,
+
+    #[arg(
+        long = "atlas.indexer.ops-alert-cooldown-secs",
+        env = "OPS_ALERT_COOLDOWN_SECS",
+        default_value = "300",
+        value_name = "SECONDS",
+        help = "Minimum time between two ops alerts of the same kind (0 = send every alert)"
+    )]
+    pub ops_alert_cooldown_secs: u64,
+
+    #[arg(
+        long = "atlas.indexer.block-stall-secs",
+        env = "BLOCK_STALL_SECS",
+        default_value = "0",
+        value_name = "SECONDS",
+        help = "Send an ops alert when the chain head stays on one block this long, and count gaps this long as stalls in block time stats (0 = disabled)"
+    )]
+    pub block_stall_secs: u64,
+
+    #[arg(
+        long = "atlas.indexer.processors",
+        env = "BLOCK_PROCESSORS",
+        value_name = "NAMES",
+        help = "Comma-separated block processors to run on every indexed block, in order (unset = none)"
+    )]
+    pub processors: Option<String>,
+
+    #[arg(
+        long = "atlas.indexer.wrapped-native-contracts",
+        env = "WRAPPED_NATIVE_CONTRACTS",
+        value_name = "ADDRESSES",
+        help = "Comma-separated wrapped native token contracts (WETH-style) whose Deposit/Withdrawal events are indexed as mints and burns (unset = none)"
+    )]
+    pub wrapped_native_contracts: Option<String>,
+
+    #[arg(
+        long = "atlas.indexer.validator-registry-address",
+        env = "VALIDATOR_REGISTRY_ADDRESS",
+        value_name = "ADDRESS",
+        help = "Staking/sequencer registry contract indexed by the validators processor"
+    )]
+    pub validator_registry_address: Option<String>,
+
+    #[arg(
+        long = "atlas.indexer.validator-registry-abi",
+        env = "VALIDATOR_REGISTRY_ABI",
+        value_name = "PATH",
+        help = "JSON ABI (or compiler artifact) of the validator registry contract"
+    )]
+    pub validator_registry_abi: Option<std::path::PathBuf>,
 }
 
 #[derive(Args, Clone)]
@@ -268,6 +809,24 @@ pub struct ChainArgs {
         help = "URL to the chain logo image used in dark theme"
     )]
     pub logo_url_dark: Option<String>,
+
+    #[arg(
+        long = "atlas.chain.currency-symbol",
+        env = "CHAIN_CURRENCY_SYMBOL",
+        default_value = "ETH",
+        value_name = "SYMBOL",
+        help = "Symbol of the chain's native currency"
+    )]
+    pub currency_symbol: String,
+
+    #[arg(
+        long = "atlas.chain.currency-decimals",
+        env = "CHAIN_CURRENCY_DECIMALS",
+        default_value_t = 18,
+        value_name = "N",
+        help = "Decimals of the chain's native currency (0-36)"
+    )]
+    pub currency_decimals: u8,
 }
 
 #[derive(Args, Clone)]
@@ -339,6 +898,36 @@ pub struct FaucetArgs {
     // FAUCET_PRIVATE_KEY is intentionally env-only (security: never pass secrets as CLI flags)
 }
 
+#[derive(Args, Clone)]
+#[command(next_help_heading = "Labels")]
+pub struct LabelArgs {
+    #[arg(
+        long = "atlas.labels.propagation-enabled",
+        env = "LABEL_PROPAGATION_ENABLED",
+        default_value_t = false,
+        help = "Derive labels for contracts deployed and addresses funded by labeled addresses"
+    )]
+    pub propagation_enabled: bool,
+
+    #[arg(
+        long = "atlas.labels.propagation-interval-secs",
+        env = "LABEL_PROPAGATION_INTERVAL_SECS",
+        default_value = "300",
+        value_name = "SECONDS",
+        help = "Seconds between label propagation cycles"
+    )]
+    pub propagation_interval_secs: u64,
+
+    #[arg(
+        long = "atlas.labels.builtin",
+        env = "BUILTIN_LABELS",
+        default_value = "auto",
+        value_name = "SETS",
+        help = "Built-in precompile/predeploy labels: auto (by chain id), none, or a list of precompiles, op-stack, common"
+    )]
+    pub builtin: String,
+}
+
 #[derive(Args, Clone)]
 #[command(next_help_heading = "Branding")]
 pub struct BrandingArgs {
@@ -399,10 +988,28 @@ pub struct LogArgs {
         long = "atlas.log.format",
         env = "LOG_FORMAT",
         default_value = "text",
+        value_parser = ["text", "json"],
         value_name = "FORMAT",
-        help = "Log output format: text or json"
+        help = "Log output format: text or json (one object per line, with span fields such as request_id)"
     )]
     pub format: String,
+
+    #[arg(
+        long = "atlas.log.otlp-endpoint",
+        env = "OTEL_EXPORTER_OTLP_ENDPOINT",
+        value_name = "URL",
+        help = "OTLP/HTTP collector base URL (e.g. http://localhost:4318); enables trace export"
+    )]
+    pub otlp_endpoint: Option<String>,
+
+    #[arg(
+        long = "atlas.log.otel-service-name",
+        env = "OTEL_SERVICE_NAME",
+        default_value = "atlas-server",
+        value_name = "NAME",
+        help = "Service name attached to exported traces"
+    )]
+    pub otel_service_name: String,
 }
 
 #[cfg(test)]
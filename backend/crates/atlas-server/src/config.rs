@@ -1,7 +1,17 @@
-use alloy::primitives::U256;
+use crate::api::body_limits::BodyLimits;
+use crate::api::handlers::display::NativeCurrency;
+use crate::api::handlers::rpc_logs::LogLimits;
+use crate::api::load_shed::ConcurrencyLimits;
+use crate::api::row_limits::RowLimits;
+use crate::head::ConfirmationsHead;
+use crate::labels::builtin::BuiltinLabels;
+use alloy::primitives::{Address, U256};
 use alloy::signers::local::PrivateKeySigner;
 use anyhow::{bail, Context, Result};
+use atlas_common::db::PoolSettings;
+use atlas_common::encoding::address_to_hex;
 use chrono::NaiveTime;
+use std::time::Duration;
 use std::{env, str::FromStr};
 
 #[cfg(test)]
@@ -16,10 +26,20 @@ pub struct Config {
     pub rpc_url: String,
 
     // Indexer pool
-    pub indexer_db_max_connections: u32,
+    pub indexer_db_pool: PoolSettings,
 
     // API pool
-    pub api_db_max_connections: u32,
+    pub api_db_pool: PoolSettings,
+    pub db_circuit_breaker_threshold: u32,
+    pub db_circuit_breaker_cooldown_secs: u64,
+
+    // Cold storage tier (optional)
+    /// Event logs and ERC-20 transfers below this block are read from the
+    /// cold tier; 0 disables it
+    pub cold_storage_before_block: u64,
+    /// Database of the cold tier; `None` uses `database_url`
+    pub cold_storage_url: Option<String>,
+    pub cold_storage_schema: String,
 
     // Indexer-specific
     pub rpc_requests_per_second: u32,
@@ -29,8 +49,59 @@ pub struct Config {
     pub ipfs_gateway: String,
     pub metadata_fetch_workers: u32,
     pub metadata_retry_attempts: u32,
+    /// Recent blocks whose transfers rank pending contracts (0 = unordered)
+    pub metadata_priority_window_blocks: u64,
+    /// Per-phase fetch concurrency; 0 uses `metadata_fetch_workers`
+    pub metadata_nft_contract_workers: u32,
+    pub metadata_erc20_contract_workers: u32,
+    pub metadata_nft_token_workers: u32,
+    /// Token metadata is fetched by a separate `atlas-metadata` process
+    pub metadata_external: bool,
     pub fetch_workers: u32,
     pub rpc_batch_size: u32,
+    pub raw_archive_enabled: bool,
+    pub replay_archive: bool,
+    pub defer_partition_indexes: bool,
+    pub calldata_prefix_index: bool,
+    /// 0 disables parallel backfill
+    pub backfill_shards: u32,
+    pub backfill_range_blocks: u64,
+    pub backfill_only: bool,
+    pub genesis_file: Option<std::path::PathBuf>,
+    /// 0 disables the daily stats aggregation job
+    pub daily_stats_interval_secs: u64,
+    /// 0 disables the ERC-20 holder ranking job
+    pub holder_ranks_interval_secs: u64,
+    /// 0 disables the data-quality checks
+    pub data_quality_interval_secs: u64,
+    /// 0 keeps every raw ERC-20 transfer
+    pub transfer_retention_blocks: u64,
+    /// 0 disables the canonical token resolution job
+    pub canonical_tokens_interval_secs: u64,
+    /// 0 disables matching new contracts to pending verifications
+    pub pending_verification_interval_secs: u64,
+    pub dex_pool_interval_secs: u64,
+    /// 0 disables the alert engine
+    pub alerts_interval_secs: u64,
+    /// Deliver alert webhooks to loopback and private network addresses
+    pub alerts_allow_private_webhooks: bool,
+    /// Slack incoming webhook for indexing gap alerts
+    pub ops_alert_slack_webhook_url: Option<String>,
+    /// PagerDuty Events API v2 routing key for indexing gap alerts
+    pub ops_alert_pagerduty_routing_key: Option<String>,
+    pub ops_alert_pagerduty_url: String,
+    /// 0 sends every alert
+    pub ops_alert_cooldown_secs: u64,
+    /// Seconds without a new chain head before a stall alert; 0 = disabled
+    pub block_stall_secs: u64,
+    /// Names of the block processors to run, see `indexer::processors`
+    pub block_processors: Vec<String>,
+    /// Lowercase addresses of WETH-style contracts whose Deposit/Withdrawal
+    /// events are indexed as ERC-20 mints and burns
+    pub wrapped_native_contracts: Vec<String>,
+    /// Registry contract and ABI for the `validators` block processor
+    pub validator_registry_address: Option<String>,
+    pub validator_registry_abi: Option<std::path::PathBuf>,
 
     // DA tracking (optional)
     pub da_tracking_enabled: bool,
@@ -38,6 +109,11 @@ pub struct Config {
     pub da_worker_concurrency: u32,
     pub da_rpc_requests_per_second: u32,
 
+    // Label propagation (optional)
+    pub label_propagation_enabled: bool,
+    pub label_propagation_interval_secs: u64,
+    pub builtin_labels: BuiltinLabels,
+
     // API-specific
     pub api_host: String,
     pub api_port: u16,
@@ -45,7 +121,18 @@ pub struct Config {
     /// (backwards-compatible default for development / self-hosted deployments).
     pub cors_origin: Option<String>,
     pub sse_replay_buffer_blocks: usize,
+    pub concurrency_limits: ConcurrencyLimits,
+    pub body_limits: BodyLimits,
+    /// Range and result caps for `/api/rpc/logs`
+    pub log_limits: LogLimits,
+    /// Deepest row paginated API requests may reach
+    pub row_limits: RowLimits,
+    /// API requests taking at least this long are logged with their SQL; 0 = off
+    pub slow_request_ms: u64,
+    /// Head Etherscan-compatible `confirmations` are counted from
+    pub confirmations_head: ConfirmationsHead,
     pub chain_name: String,
+    pub native_currency: NativeCurrency,
 
     // Branding / white-label
     pub chain_logo_url: Option<String>,
@@ -59,6 +146,18 @@ pub struct Config {
 
     // Contract verification
     pub solc_cache_dir: String,
+    pub verify_timeout_secs: u64,
+    pub verify_max_source_bytes: usize,
+
+    // Bulk exports; disabled when `export_dir` is unset
+    pub export_dir: Option<String>,
+    pub export_retention_hours: u64,
+
+    // Daily dataset dumps; disabled when `dataset_dir` is unset
+    pub dataset_dir: Option<String>,
+
+    // Admin endpoints (label moderation); disabled when unset
+    pub admin_api_key: Option<String>,
 }
 
 #[derive(Clone)]
@@ -138,18 +237,36 @@ impl Config {
             DEFAULT_DA_RPC_REQUESTS_PER_SECOND
         };
 
+        let label_propagation_enabled: bool = env::var("LABEL_PROPAGATION_ENABLED")
+            .unwrap_or_else(|_| "false".to_string())
+            .parse()
+            .context("Invalid LABEL_PROPAGATION_ENABLED")?;
+        let label_propagation_interval_secs: u64 = env::var("LABEL_PROPAGATION_INTERVAL_SECS")
+            .unwrap_or_else(|_| "300".to_string())
+            .parse()
+            .context("Invalid LABEL_PROPAGATION_INTERVAL_SECS")?;
+        if label_propagation_enabled && label_propagation_interval_secs == 0 {
+            bail!("LABEL_PROPAGATION_INTERVAL_SECS must be greater than 0");
+        }
+
         Ok(Self {
             database_url: env::var("DATABASE_URL").context("DATABASE_URL must be set")?,
             rpc_url: env::var("RPC_URL").context("RPC_URL must be set")?,
 
-            indexer_db_max_connections: env::var("DB_MAX_CONNECTIONS")
-                .unwrap_or_else(|_| "20".to_string())
+            indexer_db_pool: pool_settings_from_env("DB_")?,
+            api_db_pool: pool_settings_from_env("API_DB_")?,
+            db_circuit_breaker_threshold: env::var("DB_CIRCUIT_BREAKER_THRESHOLD")
+                .unwrap_or_else(|_| "5".to_string())
                 .parse()
-                .context("Invalid DB_MAX_CONNECTIONS")?,
-            api_db_max_connections: env::var("API_DB_MAX_CONNECTIONS")
-                .unwrap_or_else(|_| "20".to_string())
+                .context("Invalid DB_CIRCUIT_BREAKER_THRESHOLD")?,
+            db_circuit_breaker_cooldown_secs: env::var("DB_CIRCUIT_BREAKER_COOLDOWN_SECS")
+                .unwrap_or_else(|_| "30".to_string())
                 .parse()
-                .context("Invalid API_DB_MAX_CONNECTIONS")?,
+                .context("Invalid DB_CIRCUIT_BREAKER_COOLDOWN_SECS")?,
+            cold_storage_before_block: parse_env_or("COLD_STORAGE_BEFORE_BLOCK", 0)?,
+            cold_storage_url: parse_optional_env(env::var("COLD_STORAGE_URL").ok()),
+            cold_storage_schema: env::var("COLD_STORAGE_SCHEMA")
+                .unwrap_or_else(|_| "archive".to_string()),
 
             rpc_requests_per_second: env::var("RPC_REQUESTS_PER_SECOND")
                 .unwrap_or_else(|_| "100".to_string())
@@ -177,6 +294,26 @@ impl Config {
                 .unwrap_or_else(|_| "3".to_string())
                 .parse()
                 .context("Invalid METADATA_RETRY_ATTEMPTS")?,
+            metadata_priority_window_blocks: env::var("METADATA_PRIORITY_WINDOW_BLOCKS")
+                .unwrap_or_else(|_| "10000".to_string())
+                .parse()
+                .context("Invalid METADATA_PRIORITY_WINDOW_BLOCKS")?,
+            metadata_nft_contract_workers: env::var("METADATA_NFT_CONTRACT_WORKERS")
+                .unwrap_or_else(|_| "0".to_string())
+                .parse()
+                .context("Invalid METADATA_NFT_CONTRACT_WORKERS")?,
+            metadata_erc20_contract_workers: env::var("METADATA_ERC20_CONTRACT_WORKERS")
+                .unwrap_or_else(|_| "0".to_string())
+                .parse()
+                .context("Invalid METADATA_ERC20_CONTRACT_WORKERS")?,
+            metadata_nft_token_workers: env::var("METADATA_NFT_TOKEN_WORKERS")
+                .unwrap_or_else(|_| "0".to_string())
+                .parse()
+                .context("Invalid METADATA_NFT_TOKEN_WORKERS")?,
+            metadata_external: env::var("METADATA_EXTERNAL")
+                .unwrap_or_else(|_| "false".to_string())
+                .parse()
+                .context("Invalid METADATA_EXTERNAL")?,
             fetch_workers: env::var("FETCH_WORKERS")
                 .unwrap_or_else(|_| "10".to_string())
                 .parse()
@@ -185,12 +322,91 @@ impl Config {
                 .unwrap_or_else(|_| "20".to_string())
                 .parse()
                 .context("Invalid RPC_BATCH_SIZE")?,
+            raw_archive_enabled: env::var("RAW_ARCHIVE_ENABLED")
+                .unwrap_or_else(|_| "false".to_string())
+                .parse()
+                .context("Invalid RAW_ARCHIVE_ENABLED")?,
+            replay_archive: env::var("REPLAY_ARCHIVE")
+                .unwrap_or_else(|_| "false".to_string())
+                .parse()
+                .context("Invalid REPLAY_ARCHIVE")?,
+            defer_partition_indexes: env::var("DEFER_PARTITION_INDEXES")
+                .unwrap_or_else(|_| "false".to_string())
+                .parse()
+                .context("Invalid DEFER_PARTITION_INDEXES")?,
+            calldata_prefix_index: env::var("CALLDATA_PREFIX_INDEX")
+                .unwrap_or_else(|_| "false".to_string())
+                .parse()
+                .context("Invalid CALLDATA_PREFIX_INDEX")?,
+            backfill_shards: parse_env_or("BACKFILL_SHARDS", 0)?,
+            backfill_range_blocks: parse_env_or("BACKFILL_RANGE_BLOCKS", 100_000)?,
+            backfill_only: parse_env_or("BACKFILL_ONLY", false)?,
+            genesis_file: env::var("GENESIS_FILE").ok().map(Into::into),
+            daily_stats_interval_secs: env::var("DAILY_STATS_INTERVAL_SECS")
+                .unwrap_or_else(|_| "300".to_string())
+                .parse()
+                .context("Invalid DAILY_STATS_INTERVAL_SECS")?,
+            holder_ranks_interval_secs: env::var("HOLDER_RANKS_INTERVAL_SECS")
+                .unwrap_or_else(|_| "300".to_string())
+                .parse()
+                .context("Invalid HOLDER_RANKS_INTERVAL_SECS")?,
+            data_quality_interval_secs: env::var("DATA_QUALITY_INTERVAL_SECS")
+                .unwrap_or_else(|_| "600".to_string())
+                .parse()
+                .context("Invalid DATA_QUALITY_INTERVAL_SECS")?,
+            transfer_retention_blocks: env::var("TRANSFER_RETENTION_BLOCKS")
+                .unwrap_or_else(|_| "0".to_string())
+                .parse()
+                .context("Invalid TRANSFER_RETENTION_BLOCKS")?,
+            canonical_tokens_interval_secs: env::var("CANONICAL_TOKENS_INTERVAL_SECS")
+                .unwrap_or_else(|_| "600".to_string())
+                .parse()
+                .context("Invalid CANONICAL_TOKENS_INTERVAL_SECS")?,
+            pending_verification_interval_secs: parse_env_or(
+                "PENDING_VERIFICATION_INTERVAL_SECS",
+                30,
+            )?,
+            dex_pool_interval_secs: parse_env_or("DEX_POOL_INTERVAL_SECS", 30)?,
+            alerts_interval_secs: env::var("ALERTS_INTERVAL_SECS")
+                .unwrap_or_else(|_| "10".to_string())
+                .parse()
+                .context("Invalid ALERTS_INTERVAL_SECS")?,
+            alerts_allow_private_webhooks: env::var("ALERTS_ALLOW_PRIVATE_WEBHOOKS")
+                .unwrap_or_else(|_| "false".to_string())
+                .parse()
+                .context("Invalid ALERTS_ALLOW_PRIVATE_WEBHOOKS")?,
+            ops_alert_slack_webhook_url: parse_optional_env(
+                env::var("OPS_ALERT_SLACK_WEBHOOK_URL").ok(),
+            ),
+            ops_alert_pagerduty_routing_key: parse_optional_env(
+                env::var("OPS_ALERT_PAGERDUTY_ROUTING_KEY").ok(),
+            ),
+            ops_alert_pagerduty_url: env::var("OPS_ALERT_PAGERDUTY_URL")
+                .unwrap_or_else(|_| crate::ops_alerts::PAGERDUTY_EVENTS_URL.to_string()),
+            ops_alert_cooldown_secs: parse_env_or("OPS_ALERT_COOLDOWN_SECS", 300)?,
+            block_stall_secs: parse_env_or("BLOCK_STALL_SECS", 0)?,
+            block_processors: parse_name_list(env::var("BLOCK_PROCESSORS").ok().as_deref()),
+            wrapped_native_contracts: parse_address_list(
+                "WRAPPED_NATIVE_CONTRACTS",
+                env::var("WRAPPED_NATIVE_CONTRACTS").ok().as_deref(),
+            )?,
+            validator_registry_address: parse_optional_env(
+                env::var("VALIDATOR_REGISTRY_ADDRESS").ok(),
+            ),
+            validator_registry_abi: env::var("VALIDATOR_REGISTRY_ABI").ok().map(Into::into),
 
             da_tracking_enabled,
             evnode_url,
             da_worker_concurrency,
             da_rpc_requests_per_second,
 
+            label_propagation_enabled,
+            label_propagation_interval_secs,
+            builtin_labels: BuiltinLabels::parse(
+                &env::var("BUILTIN_LABELS").unwrap_or_else(|_| "auto".to_string()),
+            )
+            .context("Invalid BUILTIN_LABELS")?,
+
             api_host: env::var("API_HOST").unwrap_or_else(|_| "127.0.0.1".to_string()),
             api_port: env::var("API_PORT")
                 .unwrap_or_else(|_| "3000".to_string())
@@ -198,11 +414,48 @@ impl Config {
                 .context("Invalid API_PORT")?,
             cors_origin: env::var("CORS_ORIGIN").ok(),
             sse_replay_buffer_blocks,
+            concurrency_limits: ConcurrencyLimits {
+                api: parse_env_or("API_MAX_CONCURRENCY", 0)?,
+                search: parse_env_or("API_SEARCH_MAX_CONCURRENCY", 8)?,
+                etherscan: parse_env_or("API_ETHERSCAN_MAX_CONCURRENCY", 8)?,
+            },
+            body_limits: BodyLimits {
+                api: parse_env_or("API_MAX_BODY_BYTES", BodyLimits::default().api)?,
+                verify: parse_env_or("API_VERIFY_MAX_BODY_BYTES", BodyLimits::default().verify)?,
+            },
+            log_limits: LogLimits {
+                max_block_range: parse_env_or(
+                    "API_LOGS_MAX_BLOCK_RANGE",
+                    LogLimits::default().max_block_range,
+                )?,
+                max_results: parse_env_or(
+                    "API_LOGS_MAX_RESULTS",
+                    LogLimits::default().max_results,
+                )?,
+            },
+            row_limits: RowLimits {
+                max_result_window: parse_env_or(
+                    "API_MAX_RESULT_WINDOW",
+                    RowLimits::default().max_result_window,
+                )?,
+            },
+            slow_request_ms: parse_env_or(
+                "API_SLOW_REQUEST_MS",
+                crate::api::latency::DEFAULT_SLOW_REQUEST_MS,
+            )?,
+            confirmations_head: ConfirmationsHead::parse(
+                &env::var("ETHERSCAN_CONFIRMATIONS_HEAD").unwrap_or_else(|_| "indexed".to_string()),
+            )
+            .context("Invalid ETHERSCAN_CONFIRMATIONS_HEAD")?,
             chain_name: env::var("CHAIN_NAME")
                 .ok()
                 .map(|s| s.trim().to_string())
                 .filter(|s| !s.is_empty())
                 .unwrap_or_else(|| "Unknown".to_string()),
+            native_currency: native_currency(
+                &env::var("CHAIN_CURRENCY_SYMBOL").unwrap_or_else(|_| "ETH".to_string()),
+                parse_env_or("CHAIN_CURRENCY_DECIMALS", 18)?,
+            )?,
             chain_logo_url: parse_optional_env(env::var("CHAIN_LOGO_URL").ok()),
             chain_logo_url_light: parse_optional_env(env::var("CHAIN_LOGO_URL_LIGHT").ok()),
             chain_logo_url_dark: parse_optional_env(env::var("CHAIN_LOGO_URL_DARK").ok()),
@@ -213,6 +466,15 @@ impl Config {
             error_color: parse_optional_env(env::var("ERROR_COLOR").ok()),
             solc_cache_dir: env::var("SOLC_CACHE_DIR")
                 .unwrap_or_else(|_| "/tmp/solc-cache".to_string()),
+            verify_timeout_secs: parse_env_or("API_VERIFY_TIMEOUT_SECS", 300)?,
+            verify_max_source_bytes: parse_env_or("API_VERIFY_MAX_SOURCE_BYTES", 20 * 1024 * 1024)?,
+            export_dir: parse_optional_env(env::var("EXPORT_DIR").ok()),
+            export_retention_hours: env::var("EXPORT_RETENTION_HOURS")
+                .unwrap_or_else(|_| "24".to_string())
+                .parse()
+                .context("Invalid EXPORT_RETENTION_HOURS")?,
+            dataset_dir: parse_optional_env(env::var("DATASET_DIR").ok()),
+            admin_api_key: parse_optional_env(env::var("ADMIN_API_KEY").ok()),
         })
     }
 }
@@ -265,6 +527,57 @@ impl FaucetConfig {
     }
 }
 
+/// Validate one service's pool flags; `flag_prefix` names them in errors.
+fn pool_settings(
+    flag_prefix: &str,
+    max_connections: u32,
+    min_connections: u32,
+    acquire_timeout_secs: u64,
+    idle_timeout_secs: u64,
+    statement_cache_capacity: usize,
+) -> Result<PoolSettings> {
+    if max_connections == 0 {
+        bail!("{flag_prefix}max-connections must be greater than 0");
+    }
+    if min_connections > max_connections {
+        bail!("{flag_prefix}min-connections must not exceed {flag_prefix}max-connections");
+    }
+    if acquire_timeout_secs == 0 {
+        bail!("{flag_prefix}acquire-timeout-secs must be greater than 0");
+    }
+    Ok(PoolSettings {
+        max_connections,
+        min_connections,
+        acquire_timeout: Duration::from_secs(acquire_timeout_secs),
+        idle_timeout: (idle_timeout_secs > 0).then(|| Duration::from_secs(idle_timeout_secs)),
+        statement_cache_capacity,
+    })
+}
+
+#[cfg(test)]
+fn parse_env_or<T: FromStr + ToString>(name: &str, default: T) -> Result<T>
+where
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    env::var(name)
+        .unwrap_or_else(|_| default.to_string())
+        .parse()
+        .with_context(|| format!("Invalid {name}"))
+}
+
+#[cfg(test)]
+fn pool_settings_from_env(env_prefix: &str) -> Result<PoolSettings> {
+    let var = |name: &str| format!("{env_prefix}{name}");
+    pool_settings(
+        env_prefix,
+        parse_env_or(&var("MAX_CONNECTIONS"), 20)?,
+        parse_env_or(&var("MIN_CONNECTIONS"), 0)?,
+        parse_env_or(&var("ACQUIRE_TIMEOUT_SECS"), 30)?,
+        parse_env_or(&var("IDLE_TIMEOUT_SECS"), 600)?,
+        parse_env_or(&var("STATEMENT_CACHE_CAPACITY"), 100)?,
+    )
+}
+
 // ── CLI → Config conversion ───────────────────────────────────────────────────
 
 impl Config {
@@ -279,6 +592,51 @@ impl Config {
             bail!("--atlas.api.sse-replay-buffer-blocks must be between 1 and 100000");
         }
 
+        let indexer_db_pool = pool_settings(
+            "--atlas.db.",
+            args.db.max_connections,
+            args.db.min_connections,
+            args.db.acquire_timeout_secs,
+            args.db.idle_timeout_secs,
+            args.db.statement_cache_capacity,
+        )?;
+        let api_db_pool = pool_settings(
+            "--atlas.db.api-",
+            args.db.api_max_connections,
+            args.db.api_min_connections,
+            args.db.api_acquire_timeout_secs,
+            args.db.api_idle_timeout_secs,
+            args.db.api_statement_cache_capacity,
+        )?;
+
+        if args.db.circuit_breaker_threshold > 0 && args.db.circuit_breaker_cooldown_secs == 0 {
+            bail!("--atlas.db.circuit-breaker-cooldown-secs must be greater than 0 when the circuit breaker is enabled");
+        }
+
+        let cold_storage_url = parse_optional_env(args.db.cold_url);
+        if cold_storage_url.is_some() && args.db.cold_before_block == 0 {
+            bail!("--atlas.db.cold-url requires --atlas.db.cold-before-block to be set");
+        }
+        let cold_storage_schema = args.db.cold_schema.trim().to_string();
+        if args.db.cold_before_block > 0 && cold_storage_schema.is_empty() {
+            bail!("--atlas.db.cold-schema must not be empty when cold storage is enabled");
+        }
+
+        if args.indexer.transfer_retention_blocks > 0 && args.db.cold_before_block > 0 {
+            bail!("--atlas.indexer.transfer-retention-blocks cannot be combined with --atlas.db.cold-before-block");
+        }
+
+        if args.indexer.backfill_shards > 0 {
+            if args.indexer.backfill_range_blocks == 0 {
+                bail!("--atlas.indexer.backfill-range-blocks must be greater than 0");
+            }
+            if args.indexer.replay_archive {
+                bail!("--atlas.indexer.backfill-shards cannot be combined with --atlas.indexer.replay-archive");
+            }
+        } else if args.indexer.backfill_only {
+            bail!("--atlas.indexer.backfill-only requires --atlas.indexer.backfill-shards");
+        }
+
         let da_tracking_enabled = args.da.enabled;
 
         if da_tracking_enabled && args.da.worker_concurrency == 0 {
@@ -288,6 +646,41 @@ impl Config {
             bail!("--atlas.da.rpc-requests-per-second must be greater than 0");
         }
 
+        if args.labels.propagation_enabled && args.labels.propagation_interval_secs == 0 {
+            bail!("--atlas.labels.propagation-interval-secs must be greater than 0");
+        }
+        let builtin_labels =
+            BuiltinLabels::parse(&args.labels.builtin).context("Invalid --atlas.labels.builtin")?;
+        let confirmations_head = ConfirmationsHead::parse(&args.api.etherscan_confirmations_head)
+            .context("Invalid --atlas.api.etherscan-confirmations-head")?;
+
+        if args.api.max_body_bytes == 0 {
+            bail!("--atlas.api.max-body-bytes must be greater than 0");
+        }
+        if args.api.verify_max_body_bytes == 0 {
+            bail!("--atlas.api.verify-max-body-bytes must be greater than 0");
+        }
+        if args.api.logs_max_block_range == 0 {
+            bail!("--atlas.api.logs-max-block-range must be greater than 0");
+        }
+        if args.api.logs_max_results == 0 {
+            bail!("--atlas.api.logs-max-results must be greater than 0");
+        }
+        if args.api.max_result_window == 0 {
+            bail!("--atlas.api.max-result-window must be greater than 0");
+        }
+        if args.api.verify_timeout_secs == 0 {
+            bail!("--atlas.api.verify-timeout-secs must be greater than 0");
+        }
+        if args.api.verify_max_source_bytes == 0 {
+            bail!("--atlas.api.verify-max-source-bytes must be greater than 0");
+        }
+
+        let export_dir = parse_optional_env(args.api.export_dir);
+        if export_dir.is_some() && args.api.export_retention_hours == 0 {
+            bail!("--atlas.api.export-retention-hours must be greater than 0");
+        }
+
         let evnode_url = if da_tracking_enabled {
             let url = args
                 .da
@@ -309,12 +702,19 @@ impl Config {
         } else {
             chain_name
         };
+        let native_currency =
+            native_currency(&args.chain.currency_symbol, args.chain.currency_decimals)?;
 
         Ok(Self {
             database_url,
             rpc_url: args.rpc.url,
-            indexer_db_max_connections: args.db.max_connections,
-            api_db_max_connections: args.db.api_max_connections,
+            indexer_db_pool,
+            api_db_pool,
+            db_circuit_breaker_threshold: args.db.circuit_breaker_threshold,
+            db_circuit_breaker_cooldown_secs: args.db.circuit_breaker_cooldown_secs,
+            cold_storage_before_block: args.db.cold_before_block,
+            cold_storage_url,
+            cold_storage_schema,
             rpc_requests_per_second: args.rpc.requests_per_second,
             start_block: args.indexer.start_block,
             batch_size: args.indexer.batch_size,
@@ -322,17 +722,77 @@ impl Config {
             ipfs_gateway: args.indexer.ipfs_gateway,
             metadata_fetch_workers: args.indexer.metadata_fetch_workers,
             metadata_retry_attempts: args.indexer.metadata_retry_attempts,
+            metadata_priority_window_blocks: args.indexer.metadata_priority_window_blocks,
+            metadata_nft_contract_workers: args.indexer.metadata_nft_contract_workers,
+            metadata_erc20_contract_workers: args.indexer.metadata_erc20_contract_workers,
+            metadata_nft_token_workers: args.indexer.metadata_nft_token_workers,
+            metadata_external: args.indexer.metadata_external,
             fetch_workers: args.indexer.fetch_workers,
             rpc_batch_size: args.rpc.batch_size,
+            raw_archive_enabled: args.indexer.raw_archive,
+            replay_archive: args.indexer.replay_archive,
+            defer_partition_indexes: args.indexer.defer_partition_indexes,
+            calldata_prefix_index: args.indexer.calldata_prefix_index,
+            backfill_shards: args.indexer.backfill_shards,
+            backfill_range_blocks: args.indexer.backfill_range_blocks,
+            backfill_only: args.indexer.backfill_only,
+            genesis_file: args.indexer.genesis_file,
+            daily_stats_interval_secs: args.indexer.daily_stats_interval_secs,
+            holder_ranks_interval_secs: args.indexer.holder_ranks_interval_secs,
+            data_quality_interval_secs: args.indexer.data_quality_interval_secs,
+            transfer_retention_blocks: args.indexer.transfer_retention_blocks,
+            canonical_tokens_interval_secs: args.indexer.canonical_tokens_interval_secs,
+            pending_verification_interval_secs: args.indexer.pending_verification_interval_secs,
+            dex_pool_interval_secs: args.indexer.dex_pool_interval_secs,
+            alerts_interval_secs: args.indexer.alerts_interval_secs,
+            alerts_allow_private_webhooks: args.indexer.alerts_allow_private_webhooks,
+            ops_alert_slack_webhook_url: parse_optional_env(
+                args.indexer.ops_alert_slack_webhook_url,
+            ),
+            ops_alert_pagerduty_routing_key: parse_optional_env(
+                args.indexer.ops_alert_pagerduty_routing_key,
+            ),
+            ops_alert_pagerduty_url: args.indexer.ops_alert_pagerduty_url,
+            ops_alert_cooldown_secs: args.indexer.ops_alert_cooldown_secs,
+            block_stall_secs: args.indexer.block_stall_secs,
+            block_processors: parse_name_list(args.indexer.processors.as_deref()),
+            wrapped_native_contracts: parse_address_list(
+                "WRAPPED_NATIVE_CONTRACTS",
+                args.indexer.wrapped_native_contracts.as_deref(),
+            )?,
+            validator_registry_address: parse_optional_env(args.indexer.validator_registry_address),
+            validator_registry_abi: args.indexer.validator_registry_abi,
             da_tracking_enabled,
             evnode_url,
             da_worker_concurrency: args.da.worker_concurrency,
             da_rpc_requests_per_second: args.da.rpc_requests_per_second,
+            label_propagation_enabled: args.labels.propagation_enabled,
+            label_propagation_interval_secs: args.labels.propagation_interval_secs,
+            builtin_labels,
             api_host: args.api.host,
             api_port: args.api.port,
             cors_origin: parse_optional_env(args.api.cors_origin),
             sse_replay_buffer_blocks,
+            concurrency_limits: ConcurrencyLimits {
+                api: args.api.max_concurrency,
+                search: args.api.search_max_concurrency,
+                etherscan: args.api.etherscan_max_concurrency,
+            },
+            body_limits: BodyLimits {
+                api: args.api.max_body_bytes,
+                verify: args.api.verify_max_body_bytes,
+            },
+            log_limits: LogLimits {
+                max_block_range: args.api.logs_max_block_range,
+                max_results: args.api.logs_max_results,
+            },
+            row_limits: RowLimits {
+                max_result_window: args.api.max_result_window,
+            },
+            slow_request_ms: args.api.slow_request_ms,
+            confirmations_head,
             chain_name,
+            native_currency,
             chain_logo_url: parse_optional_env(args.chain.logo_url),
             chain_logo_url_light: parse_optional_env(args.chain.logo_url_light),
             chain_logo_url_dark: parse_optional_env(args.chain.logo_url_dark),
@@ -342,6 +802,12 @@ impl Config {
             success_color: parse_optional_env(args.branding.success_color),
             error_color: parse_optional_env(args.branding.error_color),
             solc_cache_dir: args.api.solc_cache_dir,
+            verify_timeout_secs: args.api.verify_timeout_secs,
+            verify_max_source_bytes: args.api.verify_max_source_bytes,
+            export_dir,
+            export_retention_hours: args.api.export_retention_hours,
+            dataset_dir: parse_optional_env(args.api.dataset_dir),
+            admin_api_key: parse_optional_env(env::var("ADMIN_API_KEY").ok()),
         })
     }
 }
@@ -465,6 +931,42 @@ fn parse_optional_env(val: Option<String>) -> Option<String> {
     val.map(|s| s.trim().to_string()).filter(|s| !s.is_empty())
 }
 
+/// Native currency from its configured symbol and decimals.
+fn native_currency(symbol: &str, decimals: u8) -> Result<NativeCurrency> {
+    let symbol = symbol.trim();
+    if symbol.is_empty() {
+        bail!("CHAIN_CURRENCY_SYMBOL must not be empty");
+    }
+    if decimals > 36 {
+        bail!("CHAIN_CURRENCY_DECIMALS must be at most 36, got {decimals}");
+    }
+    Ok(NativeCurrency {
+        symbol: symbol.to_string(),
+        decimals,
+    })
+}
+
+fn parse_name_list(val: Option<&str>) -> Vec<String> {
+    val.unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Comma-separated addresses, validated and lowercased.
+fn parse_address_list(name: &str, val: Option<&str>) -> Result<Vec<String>> {
+    parse_name_list(val)
+        .iter()
+        .map(|value| {
+            let address = Address::from_str(value)
+                .with_context(|| format!("Invalid address '{value}' in {name}"))?;
+            Ok(address_to_hex(&address))
+        })
+        .collect()
+}
+
 fn parse_faucet_amount_to_wei(amount: &str) -> Result<U256> {
     let trimmed = amount.trim();
     if trimmed.is_empty() {
@@ -519,6 +1021,19 @@ mod tests_from_run_args {
                 url: "postgres://test@localhost/test".to_string(),
                 max_connections: 20,
                 api_max_connections: 20,
+                min_connections: 0,
+                acquire_timeout_secs: 30,
+                idle_timeout_secs: 600,
+                statement_cache_capacity: 100,
+                api_min_connections: 0,
+                api_acquire_timeout_secs: 30,
+                api_idle_timeout_secs: 600,
+                api_statement_cache_capacity: 100,
+                circuit_breaker_threshold: 5,
+                circuit_breaker_cooldown_secs: 30,
+                cold_before_block: 0,
+                cold_url: None,
+                cold_schema: "archive".to_string(),
             },
             rpc: cli::RpcArgs {
                 url: "http://localhost:8545".to_string(),
@@ -531,6 +1046,21 @@ mod tests_from_run_args {
                 cors_origin: None,
                 sse_replay_buffer_blocks: 4096,
                 solc_cache_dir: "/tmp/solc-cache".to_string(),
+                verify_timeout_secs: 300,
+                verify_max_source_bytes: 20 * 1024 * 1024,
+                max_concurrency: 0,
+                search_max_concurrency: 8,
+                etherscan_max_concurrency: 8,
+                etherscan_confirmations_head: "indexed".to_string(),
+                max_body_bytes: 2 * 1024 * 1024,
+                verify_max_body_bytes: 50 * 1024 * 1024,
+                logs_max_block_range: 10_000,
+                logs_max_results: 10_000,
+                max_result_window: 10_000,
+                slow_request_ms: 1000,
+                export_dir: None,
+                export_retention_hours: 24,
+                dataset_dir: None,
             },
             indexer: cli::IndexerArgs {
                 start_block: 0,
@@ -540,12 +1070,45 @@ mod tests_from_run_args {
                 ipfs_gateway: "https://ipfs.io/ipfs/".to_string(),
                 metadata_fetch_workers: 4,
                 metadata_retry_attempts: 3,
+                metadata_priority_window_blocks: 10000,
+                metadata_nft_contract_workers: 0,
+                metadata_erc20_contract_workers: 0,
+                metadata_nft_token_workers: 0,
+                metadata_external: false,
+                raw_archive: false,
+                replay_archive: false,
+                defer_partition_indexes: false,
+                calldata_prefix_index: false,
+                backfill_shards: 0,
+                backfill_range_blocks: 100_000,
+                backfill_only: false,
+                genesis_file: None,
+                daily_stats_interval_secs: 300,
+                holder_ranks_interval_secs: 300,
+                data_quality_interval_secs: 600,
+                transfer_retention_blocks: 0,
+                canonical_tokens_interval_secs: 600,
+                pending_verification_interval_secs: 30,
+                dex_pool_interval_secs: 30,
+                alerts_interval_secs: 10,
+                alerts_allow_private_webhooks: false,
+                ops_alert_slack_webhook_url: None,
+                ops_alert_pagerduty_routing_key: None,
+                ops_alert_pagerduty_url: crate::ops_alerts::PAGERDUTY_EVENTS_URL.to_string(),
+                ops_alert_cooldown_secs: 300,
+                block_stall_secs: 0,
+                processors: None,
+                wrapped_native_contracts: None,
+                validator_registry_address: None,
+                validator_registry_abi: None,
             },
             chain: cli::ChainArgs {
                 name: "TestChain".to_string(),
                 logo_url: None,
                 logo_url_light: None,
                 logo_url_dark: None,
+                currency_symbol: "ETH".to_string(),
+                currency_decimals: 18,
             },
             da: cli::DaArgs {
                 enabled: false,
@@ -558,6 +1121,11 @@ mod tests_from_run_args {
                 amount: None,
                 cooldown_minutes: None,
             },
+            labels: cli::LabelArgs {
+                propagation_enabled: false,
+                propagation_interval_secs: 300,
+                builtin: "auto".to_string(),
+            },
             branding: cli::BrandingArgs {
                 accent_color: None,
                 background_dark: None,
@@ -568,7 +1136,10 @@ mod tests_from_run_args {
             log: cli::LogArgs {
                 level: "info".to_string(),
                 format: "text".to_string(),
+                otlp_endpoint: None,
+                otel_service_name: "atlas-server".to_string(),
             },
+            print_config: false,
         }
     }
 
@@ -588,6 +1159,31 @@ mod tests_from_run_args {
         assert_eq!(Config::from_run_args(args).unwrap().chain_name, "Unknown");
     }
 
+    #[test]
+    fn native_currency_defaults_to_eth_and_validates_overrides() {
+        let config = Config::from_run_args(minimal_run_args()).unwrap();
+        assert_eq!(config.native_currency, NativeCurrency::default());
+
+        let mut args = minimal_run_args();
+        args.chain.currency_symbol = " TIA ".to_string();
+        args.chain.currency_decimals = 6;
+        assert_eq!(
+            Config::from_run_args(args).unwrap().native_currency,
+            NativeCurrency {
+                symbol: "TIA".to_string(),
+                decimals: 6,
+            }
+        );
+
+        let mut args = minimal_run_args();
+        args.chain.currency_symbol = " ".to_string();
+        assert!(Config::from_run_args(args).is_err());
+
+        let mut args = minimal_run_args();
+        args.chain.currency_decimals = 37;
+        assert!(Config::from_run_args(args).is_err());
+    }
+
     #[test]
     fn chain_name_surrounding_whitespace_is_trimmed() {
         let mut args = minimal_run_args();
@@ -595,6 +1191,163 @@ mod tests_from_run_args {
         assert_eq!(Config::from_run_args(args).unwrap().chain_name, "MyChain");
     }
 
+    #[test]
+    fn block_processors_are_split_and_trimmed() {
+        let mut args = minimal_run_args();
+        args.indexer.processors = Some(" swaps, ,lending ".to_string());
+        assert_eq!(
+            Config::from_run_args(args).unwrap().block_processors,
+            ["swaps", "lending"]
+        );
+        let config = Config::from_run_args(minimal_run_args()).unwrap();
+        assert!(config.block_processors.is_empty());
+    }
+
+    #[test]
+    fn wrapped_native_contracts_are_validated_and_lowercased() {
+        let mut args = minimal_run_args();
+        args.indexer.wrapped_native_contracts =
+            Some(" 0x4200000000000000000000000000000000000006, 0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2".to_string());
+        assert_eq!(
+            Config::from_run_args(args)
+                .unwrap()
+                .wrapped_native_contracts,
+            [
+                "0x4200000000000000000000000000000000000006",
+                "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2"
+            ]
+        );
+
+        let mut args = minimal_run_args();
+        args.indexer.wrapped_native_contracts = Some("0x42".to_string());
+        let err = Config::from_run_args(args).unwrap_err();
+        assert!(err.to_string().contains("WRAPPED_NATIVE_CONTRACTS"));
+    }
+
+    #[test]
+    fn db_pools_are_tuned_per_service() {
+        let mut args = minimal_run_args();
+        args.db.min_connections = 2;
+        args.db.idle_timeout_secs = 0;
+        args.db.api_acquire_timeout_secs = 5;
+        args.db.api_statement_cache_capacity = 0;
+        let config = Config::from_run_args(args).unwrap();
+
+        assert_eq!(config.indexer_db_pool.min_connections, 2);
+        assert_eq!(config.indexer_db_pool.idle_timeout, None);
+        assert_eq!(config.api_db_pool.min_connections, 0);
+        assert_eq!(config.api_db_pool.acquire_timeout, Duration::from_secs(5));
+        assert_eq!(config.api_db_pool.statement_cache_capacity, 0);
+        assert_eq!(
+            config.api_db_pool.idle_timeout,
+            Some(Duration::from_secs(600))
+        );
+    }
+
+    #[test]
+    fn concurrency_limits_come_from_api_args() {
+        let mut args = minimal_run_args();
+        args.api.max_concurrency = 64;
+        args.api.search_max_concurrency = 0;
+        let config = Config::from_run_args(args).unwrap();
+        assert_eq!(
+            config.concurrency_limits,
+            ConcurrencyLimits {
+                api: 64,
+                search: 0,
+                etherscan: 8,
+            }
+        );
+    }
+
+    #[test]
+    fn body_limits_come_from_api_args() {
+        let mut args = minimal_run_args();
+        args.api.verify_max_body_bytes = 10 * 1024 * 1024;
+        let config = Config::from_run_args(args).unwrap();
+        assert_eq!(
+            config.body_limits,
+            BodyLimits {
+                api: 2 * 1024 * 1024,
+                verify: 10 * 1024 * 1024,
+            }
+        );
+
+        let mut args = minimal_run_args();
+        args.api.max_body_bytes = 0;
+        assert!(Config::from_run_args(args)
+            .unwrap_err()
+            .to_string()
+            .contains("--atlas.api.max-body-bytes must be greater than 0"));
+    }
+
+    #[test]
+    fn log_limits_come_from_api_args() {
+        let mut args = minimal_run_args();
+        args.api.logs_max_block_range = 500;
+        let config = Config::from_run_args(args).unwrap();
+        assert_eq!(
+            config.log_limits,
+            LogLimits {
+                max_block_range: 500,
+                max_results: 10_000,
+            }
+        );
+
+        let mut args = minimal_run_args();
+        args.api.logs_max_results = 0;
+        assert!(Config::from_run_args(args)
+            .unwrap_err()
+            .to_string()
+            .contains("--atlas.api.logs-max-results must be greater than 0"));
+    }
+
+    #[test]
+    fn row_limits_come_from_api_args() {
+        let mut args = minimal_run_args();
+        args.api.max_result_window = 5_000;
+        let config = Config::from_run_args(args).unwrap();
+        assert_eq!(config.row_limits.max_result_window, 5_000);
+
+        let mut args = minimal_run_args();
+        args.api.max_result_window = 0;
+        assert!(Config::from_run_args(args)
+            .unwrap_err()
+            .to_string()
+            .contains("--atlas.api.max-result-window must be greater than 0"));
+    }
+
+    #[test]
+    fn verify_limits_are_validated() {
+        let config = Config::from_run_args(minimal_run_args()).unwrap();
+        assert_eq!(config.verify_timeout_secs, 300);
+        assert_eq!(config.verify_max_source_bytes, 20 * 1024 * 1024);
+
+        let mut args = minimal_run_args();
+        args.api.verify_timeout_secs = 0;
+        assert!(Config::from_run_args(args)
+            .unwrap_err()
+            .to_string()
+            .contains("--atlas.api.verify-timeout-secs must be greater than 0"));
+    }
+
+    #[test]
+    fn db_pool_settings_are_validated() {
+        let mut args = minimal_run_args();
+        args.db.api_min_connections = args.db.api_max_connections + 1;
+        assert!(Config::from_run_args(args)
+            .unwrap_err()
+            .to_string()
+            .contains("--atlas.db.api-min-connections must not exceed"));
+
+        let mut args = minimal_run_args();
+        args.db.acquire_timeout_secs = 0;
+        assert!(Config::from_run_args(args)
+            .unwrap_err()
+            .to_string()
+            .contains("--atlas.db.acquire-timeout-secs must be greater than 0"));
+    }
+
     #[test]
     fn sse_replay_buffer_zero_is_rejected() {
         let mut args = minimal_run_args();
@@ -605,6 +1358,46 @@ mod tests_from_run_args {
             .contains("must be between 1 and 100000"));
     }
 
+    #[test]
+    fn export_retention_zero_is_rejected_when_exports_enabled() {
+        let mut args = minimal_run_args();
+        args.api.export_retention_hours = 0;
+        assert!(Config::from_run_args(args.clone()).is_ok());
+
+        args.api.export_dir = Some("/exports".to_string());
+        assert!(Config::from_run_args(args)
+            .unwrap_err()
+            .to_string()
+            .contains("export-retention-hours must be greater than 0"));
+    }
+
+    #[test]
+    fn label_propagation_interval_zero_is_rejected_when_enabled() {
+        let mut args = minimal_run_args();
+        args.labels.propagation_interval_secs = 0;
+        assert!(Config::from_run_args(args.clone()).is_ok());
+
+        args.labels.propagation_enabled = true;
+        assert!(Config::from_run_args(args)
+            .unwrap_err()
+            .to_string()
+            .contains("--atlas.labels.propagation-interval-secs must be greater than 0"));
+    }
+
+    #[test]
+    fn zero_job_intervals_are_accepted_as_disabled() {
+        let mut args = minimal_run_args();
+        args.indexer.daily_stats_interval_secs = 0;
+        args.indexer.holder_ranks_interval_secs = 0;
+        args.indexer.data_quality_interval_secs = 0;
+        args.indexer.canonical_tokens_interval_secs = 0;
+        args.indexer.pending_verification_interval_secs = 0;
+        args.indexer.dex_pool_interval_secs = 0;
+        let config = Config::from_run_args(args).expect("zero intervals are valid");
+        assert_eq!(config.daily_stats_interval_secs, 0);
+        assert_eq!(config.dex_pool_interval_secs, 0);
+    }
+
     #[test]
     fn sse_replay_buffer_above_max_is_rejected() {
         let mut args = minimal_run_args();
@@ -615,6 +1408,140 @@ mod tests_from_run_args {
             .contains("must be between 1 and 100000"));
     }
 
+    #[test]
+    fn circuit_breaker_requires_cooldown_when_enabled() {
+        let mut args = minimal_run_args();
+        args.db.circuit_breaker_cooldown_secs = 0;
+        assert!(Config::from_run_args(args)
+            .unwrap_err()
+            .to_string()
+            .contains("circuit-breaker-cooldown-secs"));
+
+        let mut args = minimal_run_args();
+        args.db.circuit_breaker_threshold = 0;
+        args.db.circuit_breaker_cooldown_secs = 0;
+        assert!(Config::from_run_args(args).is_ok());
+    }
+
+    #[test]
+    fn cold_storage_url_requires_a_boundary_block() {
+        let mut args = minimal_run_args();
+        args.db.cold_url = Some("postgres://archive@localhost/atlas".to_string());
+        assert!(Config::from_run_args(args)
+            .unwrap_err()
+            .to_string()
+            .contains("cold-before-block"));
+
+        let mut args = minimal_run_args();
+        args.db.cold_url = Some("postgres://archive@localhost/atlas".to_string());
+        args.db.cold_before_block = 1_000_000;
+        let config = Config::from_run_args(args).unwrap();
+        assert_eq!(config.cold_storage_before_block, 1_000_000);
+        assert_eq!(config.cold_storage_schema, "archive");
+    }
+
+    #[test]
+    fn transfer_retention_excludes_cold_storage() {
+        let mut args = minimal_run_args();
+        args.indexer.transfer_retention_blocks = 100_000;
+        args.db.cold_before_block = 1_000_000;
+        assert!(Config::from_run_args(args)
+            .unwrap_err()
+            .to_string()
+            .contains("transfer-retention-blocks"));
+
+        let mut args = minimal_run_args();
+        args.indexer.transfer_retention_blocks = 100_000;
+        let config = Config::from_run_args(args).unwrap();
+        assert_eq!(config.transfer_retention_blocks, 100_000);
+    }
+
+    #[test]
+    fn backfill_shards_validation() {
+        let mut args = minimal_run_args();
+        args.indexer.backfill_only = true;
+        assert!(Config::from_run_args(args)
+            .unwrap_err()
+            .to_string()
+            .contains("backfill-only"));
+
+        let mut args = minimal_run_args();
+        args.indexer.backfill_shards = 4;
+        args.indexer.backfill_range_blocks = 0;
+        assert!(Config::from_run_args(args)
+            .unwrap_err()
+            .to_string()
+            .contains("backfill-range-blocks"));
+
+        let mut args = minimal_run_args();
+        args.indexer.backfill_shards = 4;
+        args.indexer.replay_archive = true;
+        assert!(Config::from_run_args(args)
+            .unwrap_err()
+            .to_string()
+            .contains("replay-archive"));
+
+        let mut args = minimal_run_args();
+        args.indexer.backfill_shards = 4;
+        args.indexer.backfill_only = true;
+        let config = Config::from_run_args(args).unwrap();
+        assert_eq!(config.backfill_shards, 4);
+        assert!(config.backfill_only);
+    }
+
+    #[test]
+    fn label_propagation_requires_interval_when_enabled() {
+        let mut args = minimal_run_args();
+        args.labels.propagation_enabled = true;
+        args.labels.propagation_interval_secs = 0;
+        assert!(Config::from_run_args(args)
+            .unwrap_err()
+            .to_string()
+            .contains("propagation-interval-secs"));
+
+        let mut args = minimal_run_args();
+        args.labels.propagation_interval_secs = 0;
+        assert!(Config::from_run_args(args).is_ok());
+    }
+
+    #[test]
+    fn confirmations_head_setting_is_validated() {
+        assert_eq!(
+            Config::from_run_args(minimal_run_args())
+                .unwrap()
+                .confirmations_head,
+            ConfirmationsHead::Indexed
+        );
+
+        let mut args = minimal_run_args();
+        args.api.etherscan_confirmations_head = "Finalized".to_string();
+        assert_eq!(
+            Config::from_run_args(args).unwrap().confirmations_head,
+            ConfirmationsHead::Finalized
+        );
+
+        let mut args = minimal_run_args();
+        args.api.etherscan_confirmations_head = "safe".to_string();
+        assert!(format!("{:#}", Config::from_run_args(args).unwrap_err()).contains("safe"));
+    }
+
+    #[test]
+    fn builtin_labels_setting_is_validated() {
+        let mut args = minimal_run_args();
+        args.labels.builtin = "precompiles,common".to_string();
+        assert_eq!(
+            Config::from_run_args(args).unwrap().builtin_labels,
+            BuiltinLabels::Sets(vec![
+                crate::labels::builtin::LabelSet::Precompiles,
+                crate::labels::builtin::LabelSet::Common
+            ])
+        );
+
+        let mut args = minimal_run_args();
+        args.labels.builtin = "arbitrum".to_string();
+        assert!(format!("{:#}", Config::from_run_args(args).unwrap_err()).contains("arbitrum"));
+    }
+
     #[test]
     fn da_tracking_requires_evnode_url() {
         let mut args = minimal_run_args();
@@ -0,0 +1,415 @@
+//! Public daily dataset dumps (`GET /api/datasets`), enabled with `DATASET_DIR`.
+//!
+//! ## Design
+//!
+//! - Days are UTC calendar days of block timestamps. For each day the
+//!   [`DatasetWorker`] writes one CSV per [`Dataset`] to
+//!   `<DATASET_DIR>/<day>/<dataset>.csv` and records the files in
+//!   `dataset_dumps`, which `/api/datasets` serves as the manifest.
+//! - A day is dumped once it is over and all of its blocks are indexed: the
+//!   block after the day's last one is indexed, and there is no gap inside
+//!   the day or right before it. Days are dumped oldest first, from the day
+//!   of the first indexed block; days without blocks are skipped.
+//! - A day still missing blocks once the following day is over too is
+//!   recorded in `dataset_skipped_days`, with a warning, and later days go
+//!   ahead. Skipped days are checked again every cycle and dumped once
+//!   gap-fill has completed them.
+//! - Raw ERC-20 transfers that transfer retention has already folded into
+//!   daily summaries are gone, so `erc20_transfers` is left out of days
+//!   below the retention cutoff rather than dumped empty.
+//! - Files are written like exports (see [`crate::export`]): streamed from a
+//!   connection without `statement_timeout` into a `.tmp` file that is
+//!   renamed once complete. Rows are selected by the day's block range, so
+//!   every query uses the `block_number` indexes.
+//! - Dumps are kept until removed by hand; `db reset` forgets them but
+//!   leaves the files.
+
+use anyhow::Result;
+use chrono::{Days, NaiveDate};
+use sqlx::PgPool;
+use std::path::PathBuf;
+use std::time::Duration;
+use tokio_postgres::types::ToSql;
+
+use crate::export::{write_rows, ExportEntity, ExportFormat, ExportSpec};
+use crate::indexer::daily_stats::{day_of, day_start};
+use crate::state_keys::TRANSFER_AGGREGATION_BEFORE_BLOCK_KEY;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(60);
+const SECONDS_PER_DAY: i64 = 86_400;
+
+const BLOCK_COLUMNS: &[&str] = &[
+    "number",
+    "hash",
+    "parent_hash",
+    "timestamp",
+    "gas_used",
+    "gas_limit",
+    "base_fee_per_gas",
+    "transaction_count",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dataset {
+    Blocks,
+    Transactions,
+    Erc20Transfers,
+    NftTransfers,
+}
+
+impl Dataset {
+    pub const ALL: [Self; 4] = [
+        Self::Blocks,
+        Self::Transactions,
+        Self::Erc20Transfers,
+        Self::NftTransfers,
+    ];
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|dataset| dataset.as_str() == value)
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Blocks => "blocks",
+            Self::Transactions => "transactions",
+            Self::Erc20Transfers => "erc20_transfers",
+            Self::NftTransfers => "nft_transfers",
+        }
+    }
+
+    /// `<dataset>.csv`, the name of the dump file within its day's directory.
+    pub fn file_name(self) -> String {
+        format!("{}.csv", self.as_str())
+    }
+
+    /// Columns, in CSV order; the same as in bulk exports.
+    fn columns(self) -> &'static [&'static str] {
+        match self.entity() {
+            Some(entity) => entity.columns(),
+            None => BLOCK_COLUMNS,
+        }
+    }
+
+    fn entity(self) -> Option<ExportEntity> {
+        match self {
+            Self::Blocks => None,
+            Self::Transactions => Some(ExportEntity::Transactions),
+            Self::Erc20Transfers => Some(ExportEntity::Erc20Transfers),
+            Self::NftTransfers => Some(ExportEntity::NftTransfers),
+        }
+    }
+
+    /// Whether rows of blocks from `from_block` on may already have been
+    /// folded into daily summaries and deleted, given the transfer retention
+    /// cutoff.
+    fn is_aggregated(self, from_block: i64, aggregated_before: Option<i64>) -> bool {
+        self == Self::Erc20Transfers && aggregated_before.is_some_and(|before| from_block < before)
+    }
+
+    /// SQL producing one text column per CSV column for the rows of blocks
+    /// `from_block..=to_block`, in chain order.
+    fn query(self, from_block: i64, to_block: i64) -> (String, Vec<Box<dyn ToSql + Sync + Send>>) {
+        match self.entity() {
+            Some(entity) => ExportSpec {
+                entity,
+                format: ExportFormat::Csv,
+                address: None,
+                from_block: Some(from_block),
+                to_block: Some(to_block),
+            }
+            .query(),
+            None => {
+                let output = BLOCK_COLUMNS
+                    .iter()
+                    .map(|c| format!("{c}::text"))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                (
+                    format!(
+                        "SELECT {output} FROM blocks
+                         WHERE number BETWEEN $1 AND $2 ORDER BY number"
+                    ),
+                    vec![Box::new(from_block), Box::new(to_block)],
+                )
+            }
+        }
+    }
+}
+
+/// A row of `dataset_dumps`.
+#[derive(Debug, Clone, serde::Serialize, sqlx::FromRow)]
+pub struct DatasetDump {
+    pub day: NaiveDate,
+    pub dataset: String,
+    pub from_block: i64,
+    pub to_block: i64,
+    pub row_count: i64,
+    /// File size in bytes
+    pub file_size: i64,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+pub const DATASET_DUMP_COLUMNS: &str =
+    "day, dataset, from_block, to_block, row_count, file_size, created_at";
+
+pub struct DatasetWorker {
+    pool: PgPool,
+    database_url: String,
+    dir: PathBuf,
+}
+
+impl DatasetWorker {
+    pub fn new(pool: PgPool, database_url: &str, dir: &str) -> Self {
+        Self {
+            pool,
+            database_url: database_url.to_string(),
+            dir: PathBuf::from(dir),
+        }
+    }
+
+    pub async fn run(&self) -> Result<()> {
+        tokio::fs::create_dir_all(&self.dir).await?;
+        tracing::info!(dir = %self.dir.display(), "Dataset worker started");
+
+        loop {
+            if !self.run_cycle().await? {
+                tokio::time::sleep(POLL_INTERVAL).await;
+            }
+        }
+    }
+
+    /// Dump a skipped day that has been completed since, or else the day
+    /// after the last dumped or skipped one, skipping it if it has stalled.
+    /// Returns whether a day was dumped or skipped.
+    pub async fn run_cycle(&self) -> Result<bool> {
+        let skipped: Vec<(NaiveDate,)> =
+            sqlx::query_as("SELECT day FROM dataset_skipped_days ORDER BY day")
+                .fetch_all(&self.pool)
+                .await?;
+        for (day,) in skipped {
+            if self.dump_day(day).await? {
+                return Ok(true);
+            }
+        }
+
+        let (last_day,): (Option<NaiveDate>,) = sqlx::query_as(
+            "SELECT GREATEST((SELECT MAX(day) FROM dataset_dumps),
+                             (SELECT MAX(day) FROM dataset_skipped_days))",
+        )
+        .fetch_one(&self.pool)
+        .await?;
+        let after = match last_day {
+            Some(day) => day_start(day + Days::new(1)),
+            None => i64::MIN,
+        };
+        let (next_ts, newest_ts): (Option<i64>, Option<i64>) = sqlx::query_as(
+            "SELECT (SELECT MIN(timestamp) FROM blocks WHERE timestamp >= $1),
+                    (SELECT MAX(timestamp) FROM blocks)",
+        )
+        .bind(after)
+        .fetch_one(&self.pool)
+        .await?;
+        let Some(next_ts) = next_ts else {
+            return Ok(false);
+        };
+        let day = day_of(next_ts)?;
+        if self.dump_day(day).await? {
+            return Ok(true);
+        }
+        if !is_stalled(day, newest_ts) {
+            return Ok(false);
+        }
+
+        sqlx::query(
+            "INSERT INTO dataset_skipped_days (day) VALUES ($1) ON CONFLICT (day) DO NOTHING",
+        )
+        .bind(day)
+        .execute(&self.pool)
+        .await?;
+        metrics::counter!("atlas_dataset_days_skipped_total").increment(1);
+        tracing::warn!(
+            %day,
+            "day still missing blocks a day after it ended, dataset dump skipped until gap-fill completes it"
+        );
+        Ok(true)
+    }
+
+    /// Write and record every dataset of `day`, replacing an earlier dump of
+    /// it. Returns `false`, writing nothing, while the day is incomplete.
+    pub async fn dump_day(&self, day: NaiveDate) -> Result<bool> {
+        let Some((from_block, to_block)) = self.complete_range(day).await? else {
+            tracing::debug!(%day, "day not complete yet, dataset dump deferred");
+            return Ok(false);
+        };
+
+        let (aggregated_before,): (Option<i64>,) =
+            sqlx::query_as("SELECT MAX(value::bigint) FROM indexer_state WHERE key = $1")
+                .bind(TRANSFER_AGGREGATION_BEFORE_BLOCK_KEY)
+                .fetch_one(&self.pool)
+                .await?;
+
+        let day_dir = self.dir.join(day.to_string());
+        tokio::fs::create_dir_all(&day_dir).await?;
+        let mut written = Vec::with_capacity(Dataset::ALL.len());
+        for dataset in Dataset::ALL {
+            if dataset.is_aggregated(from_block, aggregated_before) {
+                tracing::info!(
+                    %day,
+                    "raw erc20 transfers of the day already aggregated, not dumped"
+                );
+                continue;
+            }
+            let path = day_dir.join(dataset.file_name());
+            let tmp_path = day_dir.join(format!("{}.tmp", dataset.file_name()));
+            let (sql, params) = dataset.query(from_block, to_block);
+            let rows = match write_rows(
+                &self.database_url,
+                &sql,
+                &params,
+                ExportFormat::Csv,
+                dataset.columns(),
+                &tmp_path,
+            )
+            .await
+            {
+                Ok(rows) => rows,
+                Err(e) => {
+                    let _ = tokio::fs::remove_file(&tmp_path).await;
+                    return Err(e);
+                }
+            };
+            tokio::fs::rename(&tmp_path, &path).await?;
+            let size = tokio::fs::metadata(&path).await?.len();
+            written.push((dataset, rows, size as i64));
+        }
+
+        let mut tx = self.pool.begin().await?;
+        for (dataset, rows, size) in &written {
+            sqlx::query(
+                "INSERT INTO dataset_dumps (day, dataset, from_block, to_block, row_count, file_size)
+                 VALUES ($1, $2, $3, $4, $5, $6)
+                 ON CONFLICT (day, dataset) DO UPDATE SET
+                     from_block = EXCLUDED.from_block,
+                     to_block = EXCLUDED.to_block,
+                     row_count = EXCLUDED.row_count,
+                     file_size = EXCLUDED.file_size,
+                     created_at = NOW()",
+            )
+            .bind(day)
+            .bind(dataset.as_str())
+            .bind(from_block)
+            .bind(to_block)
+            .bind(rows)
+            .bind(size)
+            .execute(&mut *tx)
+            .await?;
+        }
+        sqlx::query("DELETE FROM dataset_skipped_days WHERE day = $1")
+            .bind(day)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        tracing::info!(%day, from_block, to_block, "dataset dump complete");
+        Ok(true)
+    }
+
+    /// Block range of `day`, or `None` while the day has no blocks, is not
+    /// over, or may still be missing some.
+    async fn complete_range(&self, day: NaiveDate) -> Result<Option<(i64, i64)>> {
+        let row: (Option<i64>, Option<i64>, i64, Option<i64>, Option<i64>) = sqlx::query_as(
+            "WITH range AS (
+                 SELECT MIN(number) AS lo, MAX(number) AS hi, COUNT(*) AS blocks
+                 FROM blocks
+                 WHERE timestamp >= $1 AND timestamp < $1 + 86400
+             )
+             SELECT r.lo, r.hi, r.blocks,
+                    (SELECT MAX(number) FROM blocks WHERE number < r.lo),
+                    (SELECT MIN(number) FROM blocks WHERE number > r.hi)
+             FROM range r",
+        )
+        .bind(day_start(day))
+        .fetch_one(&self.pool)
+        .await?;
+        let (Some(lo), Some(hi), blocks, before, after) = row else {
+            return Ok(None);
+        };
+        let complete = is_complete(lo, hi, blocks, before, after);
+        Ok(complete.then_some((lo, hi)))
+    }
+}
+
+/// Whether an incomplete `day` has been waited on long enough: the newest
+/// indexed block is from after the following day.
+fn is_stalled(day: NaiveDate, newest_ts: Option<i64>) -> bool {
+    newest_ts.is_some_and(|ts| ts >= day_start(day) + 2 * SECONDS_PER_DAY)
+}
+
+/// Whether a day spanning blocks `lo..=hi` with `blocks` indexed blocks is
+/// complete, given the closest indexed blocks before and after it.
+fn is_complete(lo: i64, hi: i64, blocks: i64, before: Option<i64>, after: Option<i64>) -> bool {
+    blocks == hi - lo + 1 && before.is_none_or(|b| b == lo - 1) && after == Some(hi + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn datasets_round_trip() {
+        for dataset in Dataset::ALL {
+            assert_eq!(Dataset::parse(dataset.as_str()), Some(dataset));
+        }
+        assert_eq!(Dataset::parse("logs"), None);
+        assert_eq!(Dataset::Erc20Transfers.file_name(), "erc20_transfers.csv");
+    }
+
+    #[test]
+    fn queries_select_the_day_by_block_range() {
+        let (sql, params) = Dataset::Blocks.query(10, 20);
+        assert_eq!(params.len(), 2);
+        assert!(sql.starts_with("SELECT number::text, hash::text,"));
+        assert!(sql.ends_with("WHERE number BETWEEN $1 AND $2 ORDER BY number"));
+        assert!(!sql.contains("address"));
+
+        let (sql, params) = Dataset::Transactions.query(10, 20);
+        assert_eq!(params.len(), 2);
+        assert!(sql.contains("block_number >= $1 AND block_number <= $2"));
+        assert_eq!(
+            Dataset::Transactions.columns(),
+            ExportEntity::Transactions.columns()
+        );
+    }
+
+    #[test]
+    fn days_wait_for_the_next_block_and_for_gaps() {
+        assert!(is_complete(10, 20, 11, Some(9), Some(21)));
+        // The first indexed day has nothing before it.
+        assert!(is_complete(10, 20, 11, None, Some(21)));
+        // The day may still be running.
+        assert!(!is_complete(10, 20, 11, Some(9), None));
+        // Blocks missing inside, before or after the day.
+        assert!(!is_complete(10, 20, 10, Some(9), Some(21)));
+        assert!(!is_complete(10, 20, 11, Some(8), Some(21)));
+        assert!(!is_complete(10, 20, 11, Some(9), Some(22)));
+    }
+
+    #[test]
+    fn only_transfers_below_the_retention_cutoff_are_aggregated() {
+        assert!(!Dataset::Erc20Transfers.is_aggregated(10, None));
+        assert!(!Dataset::Erc20Transfers.is_aggregated(10, Some(10)));
+        assert!(Dataset::Erc20Transfers.is_aggregated(10, Some(11)));
+        assert!(!Dataset::NftTransfers.is_aggregated(10, Some(11)));
+    }
+
+    #[test]
+    fn incomplete_days_stall_once_the_next_day_is_over() {
+        let day = NaiveDate::from_ymd_opt(2026, 1, 1).unwrap();
+        let next_day_end = day_start(day) + 2 * SECONDS_PER_DAY;
+        assert!(!is_stalled(day, None));
+        assert!(!is_stalled(day, Some(next_day_end - 1)));
+        assert!(is_stalled(day, Some(next_day_end)));
+    }
+}
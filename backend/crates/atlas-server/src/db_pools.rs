@@ -0,0 +1,149 @@
+//! Connection pool statistics for `/metrics` and `/api/admin/db-stats`.
+//!
+//! sqlx does not report how long queries queue for a connection, so the
+//! sampler times its own `acquire()` on each pool every interval. That probe
+//! waits in the same queue as real queries, so it tracks pool contention
+//! without instrumenting every call site.
+
+use atlas_common::db::PoolSettings;
+use serde::Serialize;
+use sqlx::PgPool;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use crate::metrics::Metrics;
+
+/// How often pool gauges are refreshed and the acquire probe runs.
+pub const SAMPLE_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Sentinel for "no probe has completed yet".
+const NO_SAMPLE: u64 = u64::MAX;
+
+pub struct MonitoredPool {
+    name: &'static str,
+    pool: PgPool,
+    settings: PoolSettings,
+    last_acquire_wait_micros: AtomicU64,
+}
+
+impl MonitoredPool {
+    pub fn new(name: &'static str, pool: PgPool, settings: PoolSettings) -> Self {
+        Self {
+            name,
+            pool,
+            settings,
+            last_acquire_wait_micros: AtomicU64::new(NO_SAMPLE),
+        }
+    }
+
+    pub fn stats(&self) -> PoolStats {
+        let size = self.pool.size();
+        let idle = self.pool.num_idle() as u32;
+        PoolStats {
+            name: self.name,
+            size,
+            idle,
+            in_use: size.saturating_sub(idle),
+            max_connections: self.settings.max_connections,
+            min_connections: self.settings.min_connections,
+            acquire_timeout_secs: self.settings.acquire_timeout.as_secs(),
+            idle_timeout_secs: self.settings.idle_timeout.map(|d| d.as_secs()),
+            statement_cache_capacity: self.settings.statement_cache_capacity,
+            last_acquire_wait_ms: match self.last_acquire_wait_micros.load(Ordering::Relaxed) {
+                NO_SAMPLE => None,
+                micros => Some(micros as f64 / 1000.0),
+            },
+        }
+    }
+
+    async fn sample(&self, metrics: &Metrics) {
+        let stats = self.stats();
+        metrics.set_db_pool_size(self.name, stats.size as f64);
+        metrics.set_db_pool_idle(self.name, stats.idle as f64);
+
+        let started = Instant::now();
+        match self.pool.acquire().await {
+            Ok(conn) => {
+                let wait = started.elapsed();
+                drop(conn);
+                self.last_acquire_wait_micros
+                    .store(wait.as_micros() as u64, Ordering::Relaxed);
+                metrics.record_db_pool_acquire_wait(self.name, wait.as_secs_f64());
+            }
+            Err(e) => {
+                tracing::warn!(pool = self.name, error = %e, "pool acquire probe failed");
+                metrics.error("db_pool", "acquire");
+            }
+        }
+    }
+}
+
+/// Snapshot of one pool, as served by `/api/admin/db-stats`.
+#[derive(Debug, Clone, Serialize)]
+pub struct PoolStats {
+    pub name: &'static str,
+    pub size: u32,
+    pub idle: u32,
+    pub in_use: u32,
+    pub max_connections: u32,
+    pub min_connections: u32,
+    pub acquire_timeout_secs: u64,
+    pub idle_timeout_secs: Option<u64>,
+    pub statement_cache_capacity: usize,
+    /// Time the latest sampler probe waited for a connection.
+    pub last_acquire_wait_ms: Option<f64>,
+}
+
+/// The process's database pools.
+pub struct DbPools {
+    pools: Vec<MonitoredPool>,
+}
+
+impl DbPools {
+    pub fn new(pools: Vec<MonitoredPool>) -> Self {
+        Self { pools }
+    }
+
+    pub fn stats(&self) -> Vec<PoolStats> {
+        self.pools.iter().map(MonitoredPool::stats).collect()
+    }
+
+    /// Publish the configured maximums, then sample every pool forever.
+    pub async fn run_sampler(&self, metrics: Metrics, interval: Duration) {
+        for pool in &self.pools {
+            metrics.set_db_pool_max(pool.name, pool.settings.max_connections as f64);
+        }
+        loop {
+            for pool in &self.pools {
+                pool.sample(&metrics).await;
+            }
+            tokio::time::sleep(interval).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn stats_report_settings_before_any_sample() {
+        let pool = sqlx::postgres::PgPoolOptions::new()
+            .connect_lazy("postgres://test@localhost:5432/test")
+            .expect("lazy pool");
+        let settings = PoolSettings {
+            idle_timeout: None,
+            ..PoolSettings::with_max_connections(7)
+        };
+        let pools = DbPools::new(vec![MonitoredPool::new("api", pool, settings)]);
+
+        let stats = pools.stats();
+        assert_eq!(stats.len(), 1);
+        assert_eq!(stats[0].name, "api");
+        assert_eq!(stats[0].size, 0);
+        assert_eq!(stats[0].in_use, 0);
+        assert_eq!(stats[0].max_connections, 7);
+        assert_eq!(stats[0].idle_timeout_secs, None);
+        assert_eq!(stats[0].last_acquire_wait_ms, None);
+    }
+}
@@ -0,0 +1,565 @@
+//! Bulk historical exports (`POST /api/export`), enabled with `EXPORT_DIR`.
+//!
+//! ## Design
+//!
+//! - A request is validated into an [`ExportSpec`] and queued as a row in
+//!   `export_jobs`; the [`ExportWorker`] runs queued jobs one at a time, so
+//!   an export never holds more than one extra database connection.
+//! - Rows are streamed from a dedicated tokio-postgres connection, which
+//!   (unlike the sqlx pools) has no 10s `statement_timeout`, straight into
+//!   `<id>.<ext>.tmp` in `EXPORT_DIR`. The file is renamed to `<id>.<ext>`
+//!   only once complete, so a download never sees a partial file.
+//! - `EXPORT_DIR` is a plain directory. Point it at a mounted bucket (s3fs,
+//!   gcsfuse, ...) to keep exports in object storage.
+//! - Finished jobs expire after `EXPORT_RETENTION_HOURS`; the worker deletes
+//!   expired rows and their files. A job left `running` by a restart is
+//!   queued again when the worker starts.
+
+use anyhow::{bail, Result};
+use atlas_common::storage::TieredTable;
+use chrono::{DateTime, TimeDelta, Utc};
+use futures::TryStreamExt;
+use serde::Serialize;
+use sqlx::PgPool;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::io::{AsyncWriteExt, BufWriter};
+use tokio_postgres::types::ToSql;
+
+use crate::indexer::Indexer;
+use crate::ordering::{ChainRow, Direction};
+
+/// Jobs queued or running at once; further requests get 429.
+pub const MAX_PENDING_EXPORTS: i64 = 16;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportEntity {
+    Transactions,
+    Erc20Transfers,
+    NftTransfers,
+    Logs,
+}
+
+impl ExportEntity {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "transactions" => Some(Self::Transactions),
+            "erc20_transfers" => Some(Self::Erc20Transfers),
+            "nft_transfers" => Some(Self::NftTransfers),
+            "logs" => Some(Self::Logs),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Transactions => "transactions",
+            Self::Erc20Transfers => "erc20_transfers",
+            Self::NftTransfers => "nft_transfers",
+            Self::Logs => "logs",
+        }
+    }
+
+    /// Table the rows are read from, when its old blocks may be in the
+    /// cold tier, which exports do not read.
+    pub fn tiered_table(self) -> Option<TieredTable> {
+        match self {
+            Self::Erc20Transfers => Some(TieredTable::Erc20Transfers),
+            Self::Logs => Some(TieredTable::EventLogs),
+            Self::Transactions | Self::NftTransfers => None,
+        }
+    }
+
+    /// Exported columns, in CSV order.
+    pub(crate) fn columns(self) -> &'static [&'static str] {
+        match self {
+            Self::Transactions => &[
+                "hash",
+                "block_number",
+                "block_index",
+                "from_address",
+                "to_address",
+                "value",
+                "gas_price",
+                "gas_used",
+                "input",
+                "status",
+                "contract_created",
+                "timestamp",
+            ],
+            Self::Erc20Transfers => &[
+                "tx_hash",
+                "log_index",
+                "contract_address",
+                "from_address",
+                "to_address",
+                "value",
+                "block_number",
+                "timestamp",
+            ],
+            Self::NftTransfers => &[
+                "tx_hash",
+                "log_index",
+                "contract_address",
+                "token_id",
+                "from_address",
+                "to_address",
+                "block_number",
+                "timestamp",
+            ],
+            Self::Logs => &[
+                "tx_hash",
+                "log_index",
+                "address",
+                "topic0",
+                "topic1",
+                "topic2",
+                "topic3",
+                "data",
+                "block_number",
+            ],
+        }
+    }
+
+    /// Select list and table. Wei amounts and token IDs are cast to text so
+    /// JSON consumers do not lose precision.
+    fn source(self) -> (&'static str, &'static str) {
+        match self {
+            Self::Transactions => (
+                "hash, block_number, block_index, from_address, to_address, value::text AS value,
+                 gas_price::text AS gas_price, gas_used, '0x' || encode(input_data, 'hex') AS input,
+                 status, contract_created, timestamp",
+                "transactions",
+            ),
+            Self::Erc20Transfers => (
+                "tx_hash, log_index, contract_address, from_address, to_address,
+                 value::text AS value, block_number, timestamp",
+                "erc20_transfers",
+            ),
+            Self::NftTransfers => (
+                "tx_hash, log_index, contract_address, token_id::text AS token_id,
+                 from_address, to_address, block_number, timestamp",
+                "nft_transfers",
+            ),
+            Self::Logs => (
+                "'0x' || encode(tx_hash, 'hex') AS tx_hash, log_index,
+                 '0x' || encode(address, 'hex') AS address,
+                 '0x' || encode(topic0, 'hex') AS topic0, '0x' || encode(topic1, 'hex') AS topic1,
+                 '0x' || encode(topic2, 'hex') AS topic2, '0x' || encode(topic3, 'hex') AS topic3,
+                 '0x' || encode(data, 'hex') AS data, block_number",
+                "event_logs",
+            ),
+        }
+    }
+
+    /// Rows involving an address: sender, recipient or created contract of a
+    /// transaction; sender, recipient or token of a transfer; emitter of a log.
+    fn address_filter(self) -> &'static str {
+        match self {
+            Self::Transactions => "(from_address = $1 OR to_address = $1 OR contract_created = $1)",
+            Self::Erc20Transfers | Self::NftTransfers => {
+                "(from_address = $1 OR to_address = $1 OR contract_address = $1)"
+            }
+            Self::Logs => "address = decode(substr($1, 3), 'hex')",
+        }
+    }
+
+    fn chain_row(self) -> ChainRow {
+        match self {
+            Self::Transactions => ChainRow::Transaction,
+            _ => ChainRow::Log,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Jsonl,
+}
+
+impl ExportFormat {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "csv" => Some(Self::Csv),
+            "jsonl" => Some(Self::Jsonl),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Jsonl => "jsonl",
+        }
+    }
+
+    pub fn content_type(self) -> &'static str {
+        match self {
+            Self::Csv => "text/csv; charset=utf-8",
+            Self::Jsonl => "application/x-ndjson",
+        }
+    }
+}
+
+/// What to export. Block bounds are inclusive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExportSpec {
+    pub entity: ExportEntity,
+    pub format: ExportFormat,
+    pub address: Option<String>,
+    pub from_block: Option<i64>,
+    pub to_block: Option<i64>,
+}
+
+impl ExportSpec {
+    /// SQL producing one text column per exported column (CSV) or one JSON
+    /// object per row (JSON Lines), ordered by block and position.
+    pub(crate) fn query(&self) -> (String, Vec<Box<dyn ToSql + Sync + Send>>) {
+        let (select, table) = self.entity.source();
+        let mut params: Vec<Box<dyn ToSql + Sync + Send>> = Vec::new();
+        let mut conditions = Vec::new();
+        if let Some(address) = &self.address {
+            params.push(Box::new(address.clone()));
+            conditions.push(self.entity.address_filter().to_string());
+        }
+        if let Some(from_block) = self.from_block {
+            params.push(Box::new(from_block));
+            conditions.push(format!("block_number >= ${}", params.len()));
+        }
+        if let Some(to_block) = self.to_block {
+            params.push(Box::new(to_block));
+            conditions.push(format!("block_number <= ${}", params.len()));
+        }
+        let filter = if conditions.is_empty() {
+            String::new()
+        } else {
+            format!(" WHERE {}", conditions.join(" AND "))
+        };
+
+        let output = match self.format {
+            ExportFormat::Csv => self
+                .entity
+                .columns()
+                .iter()
+                .map(|c| format!("e.{c}::text"))
+                .collect::<Vec<_>>()
+                .join(", "),
+            ExportFormat::Jsonl => "row_to_json(e)::text".to_string(),
+        };
+        let sql = format!(
+            "SELECT {output} FROM (SELECT {select} FROM {table}{filter}) e
+             ORDER BY {}",
+            self.entity.chain_row().order_by("e", Direction::Asc)
+        );
+        (sql, params)
+    }
+}
+
+/// A row of `export_jobs`.
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct ExportJob {
+    pub id: String,
+    /// `queued`, `running`, `completed` or `failed`
+    pub status: String,
+    pub entity: String,
+    pub format: String,
+    pub address: Option<String>,
+    pub from_block: Option<i64>,
+    pub to_block: Option<i64>,
+    pub row_count: Option<i64>,
+    /// File size in bytes
+    pub file_size: Option<i64>,
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+pub const EXPORT_JOB_COLUMNS: &str = "id, status, entity, format, address, from_block, to_block, row_count, file_size, error, created_at, started_at, completed_at, expires_at";
+
+impl ExportJob {
+    /// `<id>.<format>`, the name of the export file.
+    pub fn file_name(&self) -> String {
+        format!("{}.{}", self.id, self.format)
+    }
+
+    pub fn spec(&self) -> Result<ExportSpec> {
+        let (Some(entity), Some(format)) = (
+            ExportEntity::parse(&self.entity),
+            ExportFormat::parse(&self.format),
+        ) else {
+            bail!("export job {} has an unknown entity or format", self.id);
+        };
+        Ok(ExportSpec {
+            entity,
+            format,
+            address: self.address.clone(),
+            from_block: self.from_block,
+            to_block: self.to_block,
+        })
+    }
+}
+
+/// Random job ID; it doubles as the download credential.
+pub fn new_job_id() -> String {
+    hex::encode(rand::random::<[u8; 16]>())
+}
+
+pub struct ExportWorker {
+    pool: PgPool,
+    database_url: String,
+    dir: PathBuf,
+    retention: Duration,
+}
+
+impl ExportWorker {
+    pub fn new(pool: PgPool, database_url: &str, dir: &str, retention: Duration) -> Result<Self> {
+        if retention.is_zero() {
+            bail!("export retention must be greater than 0");
+        }
+        Ok(Self {
+            pool,
+            database_url: database_url.to_string(),
+            dir: PathBuf::from(dir),
+            retention,
+        })
+    }
+
+    pub async fn run(&self) -> Result<()> {
+        tokio::fs::create_dir_all(&self.dir).await?;
+        let requeued = sqlx::query(
+            "UPDATE export_jobs SET status = 'queued', started_at = NULL WHERE status = 'running'",
+        )
+        .execute(&self.pool)
+        .await?
+        .rows_affected();
+        tracing::info!(dir = %self.dir.display(), requeued, "Export worker started");
+
+        loop {
+            if !self.run_cycle().await? {
+                tokio::time::sleep(POLL_INTERVAL).await;
+            }
+        }
+    }
+
+    /// Delete expired exports, then run the oldest queued job. Returns
+    /// whether a job was run.
+    pub async fn run_cycle(&self) -> Result<bool> {
+        self.delete_expired().await?;
+
+        let job: Option<ExportJob> = sqlx::query_as(&format!(
+            "UPDATE export_jobs SET status = 'running', started_at = NOW()
+             WHERE id = (
+                 SELECT id FROM export_jobs WHERE status = 'queued'
+                 ORDER BY created_at LIMIT 1 FOR UPDATE SKIP LOCKED
+             )
+             RETURNING {EXPORT_JOB_COLUMNS}"
+        ))
+        .fetch_optional(&self.pool)
+        .await?;
+        let Some(job) = job else {
+            return Ok(false);
+        };
+
+        let path = self.dir.join(job.file_name());
+        let tmp_path = self.dir.join(format!("{}.tmp", job.file_name()));
+        let result = match job.spec() {
+            Ok(spec) => self.write(&spec, &tmp_path).await,
+            Err(e) => Err(e),
+        };
+        let expires_at = Utc::now() + TimeDelta::from_std(self.retention)?;
+
+        match result {
+            Ok(rows) => {
+                tokio::fs::rename(&tmp_path, &path).await?;
+                let size = tokio::fs::metadata(&path).await?.len();
+                sqlx::query(
+                    "UPDATE export_jobs
+                     SET status = 'completed', row_count = $2, file_size = $3,
+                         completed_at = NOW(), expires_at = $4
+                     WHERE id = $1",
+                )
+                .bind(&job.id)
+                .bind(rows)
+                .bind(size as i64)
+                .bind(expires_at)
+                .execute(&self.pool)
+                .await?;
+                tracing::info!(id = %job.id, entity = %job.entity, rows, size, "export complete");
+            }
+            Err(e) => {
+                let _ = tokio::fs::remove_file(&tmp_path).await;
+                tracing::warn!(id = %job.id, error = %e, "export failed");
+                sqlx::query(
+                    "UPDATE export_jobs
+                     SET status = 'failed', error = $2, completed_at = NOW(), expires_at = $3
+                     WHERE id = $1",
+                )
+                .bind(&job.id)
+                .bind(e.to_string())
+                .bind(expires_at)
+                .execute(&self.pool)
+                .await?;
+            }
+        }
+        Ok(true)
+    }
+
+    /// Stream the export into `path` and return the number of rows written.
+    async fn write(&self, spec: &ExportSpec, path: &Path) -> Result<i64> {
+        let (sql, params) = spec.query();
+        write_rows(
+            &self.database_url,
+            &sql,
+            &params,
+            spec.format,
+            spec.entity.columns(),
+            path,
+        )
+        .await
+    }
+
+    async fn delete_expired(&self) -> Result<()> {
+        let expired: Vec<ExportJob> = sqlx::query_as(&format!(
+            "DELETE FROM export_jobs WHERE expires_at < NOW() RETURNING {EXPORT_JOB_COLUMNS}"
+        ))
+        .fetch_all(&self.pool)
+        .await?;
+        for job in expired {
+            match tokio::fs::remove_file(self.dir.join(job.file_name())).await {
+                Ok(()) => {}
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => tracing::warn!(id = %job.id, error = %e, "failed to delete export"),
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Run `sql` on a connection without `statement_timeout` and stream its rows
+/// into `path`, returning how many were written. For CSV the query returns
+/// one text column per entry of `columns`, which also make up the header
+/// line; for JSON Lines it returns one JSON object per row.
+pub(crate) async fn write_rows(
+    database_url: &str,
+    sql: &str,
+    params: &[Box<dyn ToSql + Sync + Send>],
+    format: ExportFormat,
+    columns: &[&str],
+    path: &Path,
+) -> Result<i64> {
+    let client = Indexer::connect_copy_client(database_url).await?;
+    let rows = client
+        .query_raw(
+            sql,
+            params.iter().map(|p| p.as_ref() as &(dyn ToSql + Sync)),
+        )
+        .await?;
+    futures::pin_mut!(rows);
+
+    let mut out = BufWriter::new(tokio::fs::File::create(path).await?);
+    if format == ExportFormat::Csv {
+        out.write_all(columns.join(",").as_bytes()).await?;
+        out.write_all(b"\n").await?;
+    }
+
+    let mut count = 0i64;
+    let mut line = String::new();
+    while let Some(row) = rows.try_next().await? {
+        line.clear();
+        match format {
+            ExportFormat::Csv => {
+                for i in 0..columns.len() {
+                    if i > 0 {
+                        line.push(',');
+                    }
+                    push_csv_field(&mut line, row.get::<_, Option<&str>>(i).unwrap_or(""));
+                }
+            }
+            ExportFormat::Jsonl => line.push_str(row.get::<_, &str>(0)),
+        }
+        line.push('\n');
+        out.write_all(line.as_bytes()).await?;
+        count += 1;
+    }
+    out.flush().await?;
+    out.into_inner().sync_all().await?;
+    Ok(count)
+}
+
+/// Append a CSV field, quoting it when it contains a delimiter, quote or newline.
+fn push_csv_field(line: &mut String, value: &str) {
+    if value.contains([',', '"', '\n', '\r']) {
+        line.push('"');
+        line.push_str(&value.replace('"', "\"\""));
+        line.push('"');
+    } else {
+        line.push_str(value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec(format: ExportFormat) -> ExportSpec {
+        ExportSpec {
+            entity: ExportEntity::Erc20Transfers,
+            format,
+            address: Some("0x00000000000000000000000000000000000000aa".to_string()),
+            from_block: None,
+            to_block: Some(100),
+        }
+    }
+
+    #[test]
+    fn query_numbers_filters_in_order() {
+        let (sql, params) = spec(ExportFormat::Csv).query();
+        assert_eq!(params.len(), 2);
+        assert!(sql.contains("contract_address = $1"));
+        assert!(sql.contains("block_number <= $2"));
+        assert!(!sql.contains("block_number >="));
+        assert!(sql.starts_with("SELECT e.tx_hash::text, e.log_index::text,"));
+        assert!(sql.contains("ORDER BY e.block_number ASC, e.log_index ASC, e.tx_hash ASC"));
+
+        let (sql, params) = ExportSpec {
+            entity: ExportEntity::Transactions,
+            format: ExportFormat::Jsonl,
+            address: None,
+            from_block: Some(5),
+            to_block: None,
+        }
+        .query();
+        assert_eq!(params.len(), 1);
+        assert!(sql.starts_with("SELECT row_to_json(e)::text"));
+        assert!(sql.contains("WHERE block_number >= $1"));
+        assert!(sql.contains("ORDER BY e.block_number ASC, e.block_index ASC, e.hash ASC"));
+    }
+
+    #[test]
+    fn csv_fields_are_quoted_when_needed() {
+        let mut line = String::new();
+        push_csv_field(&mut line, "0xabc");
+        line.push(',');
+        push_csv_field(&mut line, "a,\"b\"");
+        assert_eq!(line, "0xabc,\"a,\"\"b\"\"\"");
+    }
+
+    #[test]
+    fn entities_and_formats_round_trip() {
+        for entity in [
+            ExportEntity::Transactions,
+            ExportEntity::Erc20Transfers,
+            ExportEntity::NftTransfers,
+            ExportEntity::Logs,
+        ] {
+            assert_eq!(ExportEntity::parse(entity.as_str()), Some(entity));
+        }
+        assert_eq!(ExportFormat::parse("jsonl"), Some(ExportFormat::Jsonl));
+        assert_eq!(ExportFormat::parse("xml"), None);
+    }
+}
@@ -2,6 +2,7 @@ use alloy::network::Ethereum;
 use alloy::primitives::{Address, U256};
 use alloy::providers::{Provider, WalletProvider};
 use alloy::rpc::types::TransactionRequest;
+use atlas_common::encoding::b256_to_hex;
 use atlas_common::AtlasError;
 use futures::future::{BoxFuture, FutureExt};
 use std::collections::{BTreeMap, HashMap, HashSet};
@@ -105,7 +106,7 @@ where
                 .value(amount_wei);
             match provider.send_transaction(tx).await {
                 Ok(pending) => Ok(FaucetTxResponse {
-                    tx_hash: pending.tx_hash().to_string(),
+                    tx_hash: b256_to_hex(pending.tx_hash()),
                 }),
                 Err(err) => {
                     let mut cooldowns = cooldowns.lock().await;
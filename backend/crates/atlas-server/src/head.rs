@@ -1,12 +1,62 @@
+use alloy::eips::BlockNumberOrTag;
+use alloy::providers::{Provider, ProviderBuilder};
+use anyhow::{bail, Result};
 use atlas_common::{Block, BLOCK_COLUMNS};
 use sqlx::PgPool;
 use std::collections::VecDeque;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::time::Duration;
 use tokio::sync::RwLock;
 use tracing::{info, warn};
 
+/// How often the background refresher re-reads the indexed height from the DB.
+pub const HEIGHT_REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How often [`HeadTracker::run_finalized_refresh`] polls the node.
+pub const FINALIZED_REFRESH_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Sentinel stored in `indexed_height` while no height is known.
+const UNKNOWN_HEIGHT: i64 = -1;
+
+/// Head that Etherscan-compatible `confirmations` are counted from
+/// (`ETHERSCAN_CONFIRMATIONS_HEAD`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ConfirmationsHead {
+    /// Latest indexed block.
+    #[default]
+    Indexed,
+    /// Chain head last reported by the indexer; falls back to the indexed
+    /// head until one has been seen.
+    Chain,
+    /// Node's `finalized` block; nothing is confirmed until one has been seen.
+    Finalized,
+}
+
+impl ConfirmationsHead {
+    pub fn parse(value: &str) -> Result<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "indexed" => Ok(Self::Indexed),
+            "chain" => Ok(Self::Chain),
+            "finalized" => Ok(Self::Finalized),
+            other => {
+                bail!("unknown confirmations head '{other}', expected indexed, chain or finalized")
+            }
+        }
+    }
+}
+
 pub struct HeadTracker {
     replay_capacity: usize,
     state: RwLock<HeadState>,
+    /// Highest block number present in the `blocks` table, as last observed by
+    /// [`HeadTracker::run_height_refresh`]. Covers blocks written by the gap-fill
+    /// worker or another indexer process that never pass through the replay buffer.
+    indexed_height: AtomicI64,
+    /// Chain head last reported by the indexer in this process, for lag
+    /// reporting. Stays unknown when the indexer runs elsewhere.
+    chain_height: AtomicI64,
+    /// Node's `finalized` block, kept by [`HeadTracker::run_finalized_refresh`].
+    finalized_height: AtomicI64,
 }
 
 #[derive(Default)]
@@ -33,6 +83,7 @@ impl HeadTracker {
         blocks.reverse();
 
         let latest = blocks.last().cloned();
+        let indexed_height = latest.as_ref().map_or(UNKNOWN_HEIGHT, |block| block.number);
         info!(
             loaded = blocks.len(),
             head = latest.as_ref().map(|b| b.number),
@@ -43,6 +94,9 @@ impl HeadTracker {
         Ok(Self {
             replay_capacity,
             state: RwLock::new(HeadState { latest, replay }),
+            indexed_height: AtomicI64::new(indexed_height),
+            chain_height: AtomicI64::new(UNKNOWN_HEIGHT),
+            finalized_height: AtomicI64::new(UNKNOWN_HEIGHT),
         })
     }
 
@@ -50,12 +104,16 @@ impl HeadTracker {
         Self {
             replay_capacity,
             state: RwLock::new(HeadState::default()),
+            indexed_height: AtomicI64::new(UNKNOWN_HEIGHT),
+            chain_height: AtomicI64::new(UNKNOWN_HEIGHT),
+            finalized_height: AtomicI64::new(UNKNOWN_HEIGHT),
         }
     }
 
     pub async fn clear(&self) {
         let mut state = self.state.write().await;
         *state = HeadState::default();
+        self.indexed_height.store(UNKNOWN_HEIGHT, Ordering::Relaxed);
     }
 
     pub async fn publish_committed_batch(&self, blocks: Vec<Block>) {
@@ -90,6 +148,90 @@ impl HeadTracker {
         self.state.read().await.latest.clone()
     }
 
+    /// Latest known block height without touching the database.
+    ///
+    /// Returns the higher of the replay head and the background-refreshed
+    /// indexed height, or `None` when neither has been observed yet.
+    pub async fn latest_height(&self) -> Option<i64> {
+        let replay_head = self.state.read().await.latest.as_ref().map(|b| b.number);
+        replay_head.max(self.indexed_height())
+    }
+
+    /// Height last observed by the background refresher.
+    pub fn indexed_height(&self) -> Option<i64> {
+        match self.indexed_height.load(Ordering::Relaxed) {
+            UNKNOWN_HEIGHT => None,
+            height => Some(height),
+        }
+    }
+
+    pub fn set_indexed_height(&self, height: Option<i64>) {
+        self.indexed_height
+            .store(height.unwrap_or(UNKNOWN_HEIGHT), Ordering::Relaxed);
+    }
+
+    /// Chain head height last reported by the indexer.
+    pub fn chain_height(&self) -> Option<i64> {
+        match self.chain_height.load(Ordering::Relaxed) {
+            UNKNOWN_HEIGHT => None,
+            height => Some(height),
+        }
+    }
+
+    pub fn set_chain_height(&self, height: i64) {
+        self.chain_height.store(height, Ordering::Relaxed);
+    }
+
+    /// Finalized block height last reported by the node.
+    pub fn finalized_height(&self) -> Option<i64> {
+        match self.finalized_height.load(Ordering::Relaxed) {
+            UNKNOWN_HEIGHT => None,
+            height => Some(height),
+        }
+    }
+
+    pub fn set_finalized_height(&self, height: Option<i64>) {
+        self.finalized_height
+            .store(height.unwrap_or(UNKNOWN_HEIGHT), Ordering::Relaxed);
+    }
+
+    /// Periodically poll the node for its `finalized` block. Runs forever;
+    /// RPC failures are logged and the previous value is kept. Nodes without
+    /// a `finalized` tag leave the finalized height unknown.
+    pub async fn run_finalized_refresh(&self, rpc_url: &str, interval: Duration) -> Result<()> {
+        let provider = ProviderBuilder::new().connect_http(rpc_url.parse()?);
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            ticker.tick().await;
+            match provider
+                .get_block_by_number(BlockNumberOrTag::Finalized)
+                .await
+            {
+                Ok(block) => self.set_finalized_height(block.map(|b| b.header.number as i64)),
+                Err(e) => warn!(error = %e, "failed to refresh finalized height"),
+            }
+        }
+    }
+
+    /// Periodically refresh the indexed height from the `blocks` table so hot API
+    /// paths (confirmations, block list totals) never need to run `MAX(number)`.
+    /// Runs forever; query failures are logged and the previous value is kept.
+    pub async fn run_height_refresh(&self, pool: PgPool, interval: Duration) {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            ticker.tick().await;
+            match sqlx::query_as::<_, (Option<i64>,)>("SELECT MAX(number) FROM blocks")
+                .fetch_one(&pool)
+                .await
+            {
+                Ok((height,)) => self.set_indexed_height(height),
+                Err(e) => warn!(error = %e, "failed to refresh indexed height"),
+            }
+        }
+    }
+
     pub async fn replay_after(&self, after_block: Option<i64>) -> ReplaySnapshot {
         let state = self.state.read().await;
 
@@ -210,4 +352,32 @@ mod tests {
         assert!(snapshot.blocks_after_cursor.is_empty());
         assert!(snapshot.buffer_start.is_none());
     }
+
+    #[tokio::test]
+    async fn latest_height_prefers_highest_known_source() {
+        let tracker = HeadTracker::empty(3);
+        assert_eq!(tracker.latest_height().await, None);
+
+        tracker.set_indexed_height(Some(7));
+        assert_eq!(tracker.latest_height().await, Some(7));
+
+        tracker
+            .publish_committed_batch(vec![sample_block(10)])
+            .await;
+        assert_eq!(tracker.latest_height().await, Some(10));
+
+        tracker.set_indexed_height(Some(12));
+        assert_eq!(tracker.latest_height().await, Some(12));
+    }
+
+    #[tokio::test]
+    async fn clear_resets_indexed_height() {
+        let tracker = HeadTracker::empty(3);
+        tracker.set_indexed_height(Some(5));
+
+        tracker.clear().await;
+
+        assert_eq!(tracker.indexed_height(), None);
+        assert_eq!(tracker.latest_height().await, None);
+    }
 }
@@ -0,0 +1,57 @@
+//! Refresh of `address_directory`, the table behind the addresses list.
+//!
+//! A directory row merges an address's `addresses` row with its ERC-20 or
+//! NFT contract row: type, token name and symbol, transaction count and
+//! first-seen block. Rather than joining the three tables on every request,
+//! each writer of those tables re-derives the rows of the addresses it
+//! touched with [`REFRESH_SQL`], in the same transaction: the indexer's batch
+//! write, the metadata fetcher, the genesis import and the builtin labels.
+
+use sqlx::PgExecutor;
+
+/// Re-derive the directory rows of addresses `$1` (`text[]`) from
+/// `addresses`, `erc20_contracts` and `nft_contracts`. Rows are written in
+/// address order so concurrent batches lock them in the same order.
+pub(crate) const REFRESH_SQL: &str = "
+    INSERT INTO address_directory (address, is_contract, first_seen_block, tx_count,
+                                   address_type, name, symbol)
+    SELECT s.address,
+           COALESCE(a.is_contract, true),
+           COALESCE(a.first_seen_block, e.first_seen_block, n.first_seen_block),
+           COALESCE(a.tx_count, 0),
+           CASE
+               WHEN e.address IS NOT NULL THEN 'erc20'
+               WHEN n.address IS NOT NULL THEN 'nft'
+               WHEN a.is_contract THEN 'contract'
+               ELSE 'eoa'
+           END,
+           COALESCE(e.name, n.name),
+           COALESCE(e.symbol, n.symbol)
+    FROM (SELECT DISTINCT unnest($1::text[]) AS address) s
+    LEFT JOIN addresses a ON a.address = s.address
+    LEFT JOIN erc20_contracts e ON e.address = s.address
+    LEFT JOIN nft_contracts n ON n.address = s.address
+    WHERE a.address IS NOT NULL OR e.address IS NOT NULL OR n.address IS NOT NULL
+    ORDER BY s.address
+    ON CONFLICT (address) DO UPDATE SET
+        is_contract = EXCLUDED.is_contract,
+        first_seen_block = EXCLUDED.first_seen_block,
+        tx_count = EXCLUDED.tx_count,
+        address_type = EXCLUDED.address_type,
+        name = EXCLUDED.name,
+        symbol = EXCLUDED.symbol";
+
+/// Refresh the directory rows of `addresses`.
+pub async fn refresh<'e>(
+    executor: impl PgExecutor<'e>,
+    addresses: &[&str],
+) -> Result<(), sqlx::Error> {
+    if addresses.is_empty() {
+        return Ok(());
+    }
+    sqlx::query(REFRESH_SQL)
+        .bind(addresses)
+        .execute(executor)
+        .await?;
+    Ok(())
+}
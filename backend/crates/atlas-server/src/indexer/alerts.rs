@@ -0,0 +1,321 @@
+//! Alert rules evaluated against newly indexed transfers and logs.
+//!
+//! ## Design
+//!
+//! Rules live in `alert_rules` (managed under `/api/alerts`) and are owned by
+//! an issued API key. Each rule carries its own block cursor, `last_block`,
+//! which starts at the indexed head when the rule is created, so a rule only
+//! fires for blocks indexed after it. Every cycle matches all enabled rules
+//! against the blocks past their cursors in one set-based statement per table
+//! and queues each match in `alert_notifications`. Cursors of every rule then
+//! move forward, including disabled ones, so re-enabling a rule does not
+//! replay the blocks it missed.
+//!
+//! Cycles run after each committed indexer batch (via the block event
+//! channel) and at least every `interval`. Queued notifications are POSTed to
+//! the rule's webhook; failed deliveries are retried with exponential backoff
+//! and marked `failed` after [`MAX_ATTEMPTS`]. Notifications are not
+//! retracted when a reorg removes the block they came from.
+
+use anyhow::{bail, Result};
+use futures::stream::{self, StreamExt};
+use sqlx::PgPool;
+use std::net::IpAddr;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::broadcast;
+
+use crate::nft_metadata::{is_non_public_ip, SsrfSafeResolver};
+
+/// Most blocks one rule is evaluated against per cycle.
+const CHUNK_SIZE: i64 = 1_000;
+/// Notifications taken from the queue per cycle.
+const DELIVERY_BATCH: i64 = 200;
+const DELIVERY_CONCURRENCY: usize = 8;
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+/// Deliveries attempted before a notification is marked `failed`.
+pub const MAX_ATTEMPTS: i32 = 8;
+
+/// Queues matches of `transfer` rules. `$1` is the indexed head, `$2` the
+/// chunk size.
+const MATCH_TRANSFERS_SQL: &str = "
+    INSERT INTO alert_notifications (rule_id, block_number, tx_hash, log_index, payload)
+    SELECT r.id, t.block_number, t.tx_hash, t.log_index,
+           jsonb_build_object(
+               'type', 'transfer',
+               'contract', t.contract_address,
+               'from', t.from_address,
+               'to', t.to_address,
+               'value', t.value::text,
+               'block_number', t.block_number,
+               'tx_hash', t.tx_hash,
+               'log_index', t.log_index,
+               'timestamp', t.timestamp)
+    FROM alert_rules r
+    JOIN label_api_keys k ON k.id = r.api_key_id AND k.revoked_at IS NULL
+    JOIN erc20_transfers t
+      ON t.block_number > r.last_block AND t.block_number <= LEAST(r.last_block + $2, $1)
+    LEFT JOIN transactions x
+      ON r.method IS NOT NULL AND x.hash = t.tx_hash AND x.block_number = t.block_number
+    WHERE r.enabled AND r.kind = 'transfer'
+      AND (r.contract IS NULL OR t.contract_address = r.contract)
+      AND (r.counterparty IS NULL OR r.counterparty IN (t.from_address, t.to_address))
+      AND (r.min_value IS NULL OR t.value >= r.min_value)
+      AND (r.method IS NULL OR '0x' || encode(substring(x.input_data FROM 1 FOR 4), 'hex') = r.method)
+    ON CONFLICT (rule_id, tx_hash, log_index, block_number) DO NOTHING";
+
+/// Queues matches of `log` rules. Parameters match [`MATCH_TRANSFERS_SQL`].
+const MATCH_LOGS_SQL: &str = "
+    INSERT INTO alert_notifications (rule_id, block_number, tx_hash, log_index, payload)
+    SELECT r.id, l.block_number, l.tx_hash, l.log_index,
+           jsonb_build_object(
+               'type', 'log',
+               'address', l.address,
+               'topics', to_jsonb(array_remove(ARRAY[l.topic0, l.topic1, l.topic2, l.topic3], NULL)),
+               'data', '0x' || encode(l.data, 'hex'),
+               'block_number', l.block_number,
+               'tx_hash', l.tx_hash,
+               'log_index', l.log_index)
+    FROM alert_rules r
+    JOIN label_api_keys k ON k.id = r.api_key_id AND k.revoked_at IS NULL
+    CROSS JOIN LATERAL (
+        SELECT block_number, '0x' || encode(tx_hash, 'hex') AS tx_hash, log_index,
+               '0x' || encode(address, 'hex') AS address,
+               '0x' || encode(topic0, 'hex') AS topic0, '0x' || encode(topic1, 'hex') AS topic1,
+               '0x' || encode(topic2, 'hex') AS topic2, '0x' || encode(topic3, 'hex') AS topic3,
+               data
+        FROM event_logs
+        WHERE block_number > r.last_block AND block_number <= LEAST(r.last_block + $2, $1)
+          AND (r.contract IS NULL OR address = decode(substr(r.contract, 3), 'hex'))
+          AND (r.event IS NULL OR topic0 = decode(substr(r.event, 3), 'hex'))
+    ) l
+    LEFT JOIN transactions x
+      ON (r.method IS NOT NULL OR r.counterparty IS NOT NULL)
+     AND x.hash = l.tx_hash AND x.block_number = l.block_number
+    WHERE r.enabled AND r.kind = 'log'
+      AND (r.counterparty IS NULL OR r.counterparty IN (x.from_address, x.to_address))
+      AND (r.method IS NULL OR '0x' || encode(substring(x.input_data FROM 1 FOR 4), 'hex') = r.method)
+    ON CONFLICT (rule_id, tx_hash, log_index, block_number) DO NOTHING";
+
+/// Moves every rule's cursor past the evaluated chunk.
+const ADVANCE_SQL: &str = "
+    UPDATE alert_rules SET last_block = LEAST(last_block + $2, $1) WHERE last_block < $1";
+
+#[derive(sqlx::FromRow)]
+struct PendingDelivery {
+    id: i64,
+    payload: serde_json::Value,
+    attempts: i32,
+    rule_id: i64,
+    rule_name: String,
+    webhook_url: String,
+}
+
+pub struct AlertEngine {
+    pool: PgPool,
+    client: reqwest::Client,
+    block_events: broadcast::Sender<()>,
+    interval: Duration,
+    allow_private_webhooks: bool,
+}
+
+impl AlertEngine {
+    /// `allow_private_webhooks` permits webhooks on loopback and private
+    /// networks, which are refused by default.
+    pub fn new(
+        pool: PgPool,
+        block_events: broadcast::Sender<()>,
+        interval: Duration,
+        allow_private_webhooks: bool,
+    ) -> Result<Self> {
+        if interval.is_zero() {
+            bail!("alert interval must be greater than 0");
+        }
+        let mut client = reqwest::Client::builder()
+            .timeout(WEBHOOK_TIMEOUT)
+            .redirect(reqwest::redirect::Policy::none())
+            .user_agent("atlas-server/0.1.0");
+        if !allow_private_webhooks {
+            client = client.dns_resolver(Arc::new(SsrfSafeResolver));
+        }
+        Ok(Self {
+            pool,
+            client: client.build()?,
+            block_events,
+            interval,
+            allow_private_webhooks,
+        })
+    }
+
+    pub async fn run(&self) -> Result<()> {
+        tracing::info!(
+            interval_secs = self.interval.as_secs(),
+            "Alert engine started"
+        );
+        let mut events = self.block_events.subscribe();
+        loop {
+            let (matched, delivered) = self.run_cycle().await?;
+            if matched > 0 || delivered > 0 {
+                tracing::info!(matched, delivered, "alert cycle complete");
+            }
+            // Wake on the next committed batch; lagging just means more to do.
+            tokio::select! {
+                result = events.recv() => {
+                    if let Err(broadcast::error::RecvError::Closed) = result {
+                        tokio::time::sleep(self.interval).await;
+                    }
+                }
+                _ = tokio::time::sleep(self.interval) => {}
+            }
+        }
+    }
+
+    /// Evaluate rules against new blocks, then deliver due notifications.
+    /// Returns the number of new matches and of successful deliveries.
+    pub async fn run_cycle(&self) -> Result<(u64, u64)> {
+        let matched = self.evaluate().await?;
+        let delivered = self.deliver().await?;
+        Ok((matched, delivered))
+    }
+
+    async fn evaluate(&self) -> Result<u64> {
+        let (head,): (Option<i64>,) = sqlx::query_as("SELECT MAX(number) FROM blocks")
+            .fetch_one(&self.pool)
+            .await?;
+        let Some(head) = head else {
+            return Ok(0);
+        };
+
+        let mut tx = self.pool.begin().await?;
+        let mut matched = 0;
+        for sql in [MATCH_TRANSFERS_SQL, MATCH_LOGS_SQL] {
+            matched += sqlx::query(sql)
+                .bind(head)
+                .bind(CHUNK_SIZE)
+                .execute(&mut *tx)
+                .await?
+                .rows_affected();
+        }
+        sqlx::query(ADVANCE_SQL)
+            .bind(head)
+            .bind(CHUNK_SIZE)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        Ok(matched)
+    }
+
+    async fn deliver(&self) -> Result<u64> {
+        let due: Vec<PendingDelivery> = sqlx::query_as(
+            "SELECT n.id, n.payload, n.attempts, r.id AS rule_id, r.name AS rule_name, r.webhook_url
+             FROM alert_notifications n
+             JOIN alert_rules r ON r.id = n.rule_id
+             WHERE n.status = 'pending' AND n.next_attempt_at <= NOW()
+             ORDER BY n.id
+             LIMIT $1",
+        )
+        .bind(DELIVERY_BATCH)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let outcomes: Vec<(i64, i32, Result<(), String>)> = stream::iter(due)
+            .map(|delivery| async move {
+                let outcome = self.post_webhook(&delivery).await;
+                (delivery.id, delivery.attempts, outcome)
+            })
+            .buffer_unordered(DELIVERY_CONCURRENCY)
+            .collect()
+            .await;
+
+        let mut delivered = 0;
+        for (id, attempts, outcome) in outcomes {
+            match outcome {
+                Ok(()) => {
+                    delivered += 1;
+                    sqlx::query(
+                        "UPDATE alert_notifications
+                         SET status = 'delivered', attempts = attempts + 1,
+                             last_error = NULL, delivered_at = NOW()
+                         WHERE id = $1",
+                    )
+                    .bind(id)
+                    .execute(&self.pool)
+                    .await?;
+                }
+                Err(error) => {
+                    let attempts = attempts + 1;
+                    sqlx::query(
+                        "UPDATE alert_notifications
+                         SET attempts = $2, last_error = $3,
+                             status = CASE WHEN $2 >= $4 THEN 'failed' ELSE 'pending' END,
+                             next_attempt_at = NOW() + make_interval(secs => $5)
+                         WHERE id = $1",
+                    )
+                    .bind(id)
+                    .bind(attempts)
+                    .bind(&error)
+                    .bind(MAX_ATTEMPTS)
+                    .bind(retry_delay(attempts).as_secs_f64())
+                    .execute(&self.pool)
+                    .await?;
+                }
+            }
+        }
+        Ok(delivered)
+    }
+
+    async fn post_webhook(&self, delivery: &PendingDelivery) -> Result<(), String> {
+        let url = reqwest::Url::parse(&delivery.webhook_url)
+            .map_err(|_| "invalid webhook URL".to_string())?;
+        // The SSRF-safe resolver does not see IP-literal hosts.
+        if !self.allow_private_webhooks {
+            if let Some(ip) = url.host_str().and_then(|h| {
+                h.trim_start_matches('[')
+                    .trim_end_matches(']')
+                    .parse::<IpAddr>()
+                    .ok()
+            }) {
+                if is_non_public_ip(&ip) {
+                    return Err("webhook host is not public".to_string());
+                }
+            }
+        }
+
+        let body = serde_json::json!({
+            "notification_id": delivery.id,
+            "rule": { "id": delivery.rule_id, "name": delivery.rule_name },
+            "event": delivery.payload,
+        });
+        let response = self
+            .client
+            .post(url)
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| format!("webhook request failed: {e}"))?;
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            Err(format!("webhook returned {}", response.status()))
+        }
+    }
+}
+
+/// Wait before the next delivery attempt: 30s doubling up to an hour.
+fn retry_delay(attempts: i32) -> Duration {
+    let exponent = attempts.saturating_sub(1).clamp(0, 7) as u32;
+    Duration::from_secs((30u64 << exponent).min(3600))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retry_delay_doubles_up_to_an_hour() {
+        assert_eq!(retry_delay(1), Duration::from_secs(30));
+        assert_eq!(retry_delay(2), Duration::from_secs(60));
+        assert_eq!(retry_delay(4), Duration::from_secs(240));
+        assert_eq!(retry_delay(20), Duration::from_secs(3600));
+    }
+}
@@ -0,0 +1,126 @@
+//! Raw block archive (`raw_block_archive`), enabled with `RAW_ARCHIVE_ENABLED`.
+//!
+//! Each archived block is the node's `eth_getBlockByNumber` (with full
+//! transactions) and `eth_getBlockReceipts` results, stored verbatim as one
+//! JSON document so any column added later can be derived from history
+//! without re-fetching it from the RPC node.
+//!
+//! ## Design
+//!
+//! - The payload is kept as `BYTEA` rather than `JSONB`: it is only ever read
+//!   back whole, and `JSONB` would re-encode the node's JSON. The migration
+//!   switches the column to lz4 TOAST compression where the server supports
+//!   it (pglz otherwise), which shrinks hex-heavy RPC JSON to a fraction of
+//!   its size without a compression dependency in the indexer.
+//! - Rows are written by `write_batch` in the same transaction as the
+//!   indexed data, so the archive never runs ahead of or behind the index.
+//!   A re-fetched block (reorg, gap fill) replaces its archived payload.
+//! - `--atlas.indexer.reindex` keeps the archive: it exists to survive
+//!   re-indexing. With `--atlas.indexer.replay-archive` the indexer decodes
+//!   archived payloads instead of fetching them (see `Indexer::replay_archive`),
+//!   so a schema-change backfill does not depend on the node.
+
+use alloy::rpc::types::{Block, TransactionReceipt};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::fetcher::FetchedBlock;
+use super::l1_fee;
+
+#[derive(Serialize)]
+struct RawBlockPayload<'a> {
+    block: &'a Value,
+    receipts: &'a Value,
+}
+
+#[derive(Deserialize)]
+struct ArchivedBlock {
+    block: Block,
+    #[serde(default)]
+    receipts: Value,
+}
+
+/// Encode the node's block and receipts results as an archive payload.
+pub(crate) fn encode(block: &Value, receipts: &Value) -> Vec<u8> {
+    serde_json::to_vec(&RawBlockPayload { block, receipts })
+        .expect("serializing JSON values cannot fail")
+}
+
+/// Decode an archive payload the same way the fetcher decodes RPC results:
+/// `null` receipts (a node without `eth_getBlockReceipts` data) mean none.
+///
+/// `raw` is left empty so replaying a block does not rewrite its archive row.
+pub(crate) fn decode(number: u64, payload: &[u8]) -> serde_json::Result<FetchedBlock> {
+    let archived: ArchivedBlock = serde_json::from_slice(payload)?;
+    let l1_fees = l1_fee::from_receipts(&archived.receipts);
+    let receipts: Option<Vec<TransactionReceipt>> = serde_json::from_value(archived.receipts)?;
+    Ok(FetchedBlock {
+        number,
+        block: archived.block,
+        receipts: receipts.unwrap_or_default(),
+        l1_fees,
+        raw: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_keeps_node_fields_verbatim() {
+        let block = serde_json::json!({"number": "0x1", "nodeOnlyField": "0xabc"});
+        let receipts = serde_json::json!([{"status": "0x1", "l1Fee": "0x5"}]);
+
+        let payload: Value = serde_json::from_slice(&encode(&block, &receipts)).unwrap();
+        assert_eq!(payload["block"], block);
+        assert_eq!(payload["receipts"], receipts);
+    }
+
+    fn block_json(number: u64) -> Value {
+        serde_json::json!({
+            "hash": format!("0x{:064x}", number),
+            "parentHash": format!("0x{:064x}", number - 1),
+            "sha3Uncles": "0x1dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347",
+            "miner": "0x0000000000000000000000000000000000000000",
+            "stateRoot": format!("0x{:064x}", 0),
+            "transactionsRoot": "0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421",
+            "receiptsRoot": "0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421",
+            "logsBloom": format!("0x{}", "00".repeat(256)),
+            "difficulty": "0x0",
+            "number": format!("0x{:x}", number),
+            "gasLimit": "0x1c9c380",
+            "gasUsed": "0x0",
+            "timestamp": "0x6123456",
+            "extraData": "0x",
+            "mixHash": format!("0x{:064x}", 0),
+            "nonce": "0x0000000000000000",
+            "baseFeePerGas": "0x1",
+            "transactions": [],
+            "uncles": [],
+            "size": "0x1f4"
+        })
+    }
+
+    #[test]
+    fn decode_round_trips_encoded_payload() {
+        let payload = encode(&block_json(42), &serde_json::json!([]));
+
+        let fetched = decode(42, &payload).unwrap();
+        assert_eq!(fetched.number, 42);
+        assert_eq!(fetched.block.header.number, 42);
+        assert!(fetched.receipts.is_empty());
+        assert!(fetched.raw.is_none());
+    }
+
+    #[test]
+    fn decode_treats_null_receipts_as_empty() {
+        let payload = encode(&block_json(7), &Value::Null);
+        assert!(decode(7, &payload).unwrap().receipts.is_empty());
+    }
+
+    #[test]
+    fn decode_rejects_malformed_payload() {
+        assert!(decode(1, b"{\"block\": null, \"receipts\": []}").is_err());
+    }
+}
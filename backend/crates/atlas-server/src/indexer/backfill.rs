@@ -0,0 +1,350 @@
+//! Parallel backfill of historical block ranges leased from `range_leases`.
+//!
+//! ## Design
+//!
+//! On a fresh index with `BACKFILL_SHARDS` set, the head-following indexer
+//! splits the history below the current chain head into ranges of
+//! `BACKFILL_RANGE_BLOCKS`, records them in `range_leases` and starts
+//! following the head from there. Backfill workers, in this instance or in
+//! others started with `BACKFILL_ONLY`, then index the ranges concurrently.
+//!
+//! A worker claims the lowest unleased (or expired) range with
+//! `FOR UPDATE SKIP LOCKED`, indexes it in order through the same
+//! `collect_block`/`write_batch` path as the main indexer, and after each
+//! write records its progress in `next_block` and renews the lease. A worker
+//! that dies leaves its range to be taken over from `next_block` once the
+//! lease expires; a block written twice is harmless since writes are upserts
+//! and per-block deltas are claimed in `processed_blocks`. Blocks that fail
+//! to fetch go to `failed_blocks` for the gap-fill worker.
+//!
+//! Once every range is complete, the ERC-20 supply history is marked complete
+//! if the backfill started at block 0, and the workers exit.
+
+use anyhow::Result;
+use governor::{Quota, RateLimiter};
+use sqlx::PgPool;
+use std::collections::HashSet;
+use std::num::NonZeroU32;
+use std::sync::atomic::AtomicU64;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio_postgres::Client;
+
+use super::batch::BlockBatch;
+use super::fetcher::{fetch_blocks_batch, FetchResult, SharedRateLimiter};
+use super::indexer::{ensure_partitions_exist, Indexer, PARTITION_SIZE, UNKNOWN_MAX_PARTITION};
+use super::partitions;
+use super::processors::Processors;
+use crate::metrics::Metrics;
+use crate::ops_alerts::{OpsAlert, OpsAlerter};
+use crate::state_keys::{BACKFILL_SPLIT_BLOCK_KEY, ERC20_SUPPLY_HISTORY_COMPLETE_KEY};
+
+/// Blocks per JSON-RPC batch request.
+const FETCH_BLOCKS: u64 = 20;
+
+/// Blocks per database write; the lease is renewed after each.
+const WRITE_BLOCKS: u64 = 100;
+
+/// How long a lease stays valid without progress.
+const LEASE_SECS: i64 = 300;
+
+/// Sleep while the plan does not exist yet or every open range is leased.
+const IDLE_SLEEP: Duration = Duration::from_secs(30);
+
+const CLAIM_SQL: &str = "
+    WITH candidate AS (
+        SELECT start_block FROM range_leases
+        WHERE completed_at IS NULL AND (leased_until IS NULL OR leased_until < NOW())
+        ORDER BY start_block
+        LIMIT 1
+        FOR UPDATE SKIP LOCKED
+    )
+    UPDATE range_leases r
+    SET holder = $1, leased_until = NOW() + make_interval(secs => $2)
+    FROM candidate
+    WHERE r.start_block = candidate.start_block
+    RETURNING r.start_block, r.next_block, r.end_block";
+
+const FAILED_BLOCK_SQL: &str = "
+    INSERT INTO failed_blocks (block_number, error_message, retry_count, last_failed_at)
+    VALUES ($1, $2, 0, NOW())
+    ON CONFLICT (block_number) DO UPDATE SET
+        error_message = EXCLUDED.error_message,
+        last_failed_at = NOW()";
+
+pub struct BackfillWorker {
+    pool: PgPool,
+    database_url: String,
+    rpc_url: String,
+    rpc_requests_per_second: u32,
+    metrics: Metrics,
+    raw_archive_enabled: bool,
+    ops_alerts: OpsAlerter,
+    holder: String,
+    current_max_partition: AtomicU64,
+    processors: Processors,
+    wrapped_native: Arc<HashSet<String>>,
+}
+
+impl BackfillWorker {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        pool: PgPool,
+        database_url: &str,
+        rpc_url: &str,
+        rpc_requests_per_second: u32,
+        metrics: Metrics,
+        raw_archive_enabled: bool,
+        ops_alerts: OpsAlerter,
+        shard: u32,
+    ) -> Result<Self> {
+        if rpc_requests_per_second == 0 {
+            anyhow::bail!("rpc_requests_per_second must be greater than 0");
+        }
+        let host = std::env::var("HOSTNAME").unwrap_or_else(|_| "atlas".to_string());
+        Ok(Self {
+            pool,
+            database_url: database_url.to_string(),
+            rpc_url: rpc_url.to_string(),
+            rpc_requests_per_second,
+            metrics,
+            raw_archive_enabled,
+            ops_alerts,
+            holder: format!("{host}:{}:{shard}", std::process::id()),
+            current_max_partition: AtomicU64::new(UNKNOWN_MAX_PARTITION),
+            processors: Processors::default(),
+            wrapped_native: Arc::default(),
+        })
+    }
+
+    /// Run `processors` on every block this worker writes.
+    pub fn with_processors(mut self, processors: Processors) -> Self {
+        self.processors = processors;
+        self
+    }
+
+    /// Index Deposit/Withdrawal events of these WETH-style contracts as
+    /// mints and burns.
+    pub fn with_wrapped_native(mut self, contracts: &[String]) -> Self {
+        self.wrapped_native = Arc::new(contracts.iter().cloned().collect());
+        self
+    }
+
+    /// Index leased ranges until every range is complete.
+    pub async fn run(&self) -> Result<()> {
+        tracing::info!(holder = %self.holder, "Backfill worker started");
+        loop {
+            if self.process_next_lease().await? {
+                continue;
+            }
+            if self.finished().await? {
+                tracing::info!(holder = %self.holder, "backfill complete");
+                return Ok(());
+            }
+            tokio::time::sleep(IDLE_SLEEP).await;
+        }
+    }
+
+    /// Claim one range and index the rest of it. Returns false when no range
+    /// was available.
+    pub async fn process_next_lease(&self) -> Result<bool> {
+        let lease: Option<(i64, i64, i64)> = sqlx::query_as(CLAIM_SQL)
+            .bind(&self.holder)
+            .bind(LEASE_SECS as f64)
+            .fetch_optional(&self.pool)
+            .await?;
+        let Some((start, next, end)) = lease else {
+            return Ok(false);
+        };
+        tracing::info!(holder = %self.holder, start, next, end, "leased backfill range");
+
+        let rps = NonZeroU32::new(self.rpc_requests_per_second).unwrap();
+        let rate_limiter: SharedRateLimiter = Arc::new(RateLimiter::direct(Quota::per_second(rps)));
+        let http_client = reqwest::Client::new();
+        let mut copy_client = Indexer::connect_copy_client(&self.database_url).await?;
+        let mut known_erc20: HashSet<String> = HashSet::new();
+        let mut known_nft: HashSet<String> = HashSet::new();
+
+        let mut block = next as u64;
+        while block < end as u64 {
+            // One partition per write: it may be staged (see `partitions`).
+            let to = (block + WRITE_BLOCKS)
+                .min(end as u64)
+                .min((block / PARTITION_SIZE + 1) * PARTITION_SIZE);
+            self.write_range(
+                &mut copy_client,
+                &http_client,
+                &rate_limiter,
+                block,
+                to,
+                &mut known_erc20,
+                &mut known_nft,
+            )
+            .await?;
+            block = to;
+
+            let renewed = sqlx::query(
+                "UPDATE range_leases
+                 SET next_block = $3, leased_until = NOW() + make_interval(secs => $4),
+                     completed_at = CASE WHEN $3 >= end_block THEN NOW() END
+                 WHERE start_block = $1 AND holder = $2",
+            )
+            .bind(start)
+            .bind(&self.holder)
+            .bind(block as i64)
+            .bind(LEASE_SECS as f64)
+            .execute(&self.pool)
+            .await?
+            .rows_affected();
+            if renewed == 0 {
+                tracing::warn!(holder = %self.holder, start, "backfill lease lost to another worker");
+                return Ok(true);
+            }
+        }
+
+        let (remaining,): (i64,) =
+            sqlx::query_as("SELECT COUNT(*) FROM range_leases WHERE completed_at IS NULL")
+                .fetch_one(&self.pool)
+                .await?;
+        metrics::gauge!("atlas_backfill_ranges_remaining").set(remaining as f64);
+        tracing::info!(holder = %self.holder, start, end, remaining, "backfill range complete");
+        Ok(true)
+    }
+
+    /// Fetch and write blocks `[from, to)` in one transaction.
+    #[allow(clippy::too_many_arguments)]
+    async fn write_range(
+        &self,
+        copy_client: &mut Client,
+        http_client: &reqwest::Client,
+        rate_limiter: &SharedRateLimiter,
+        from: u64,
+        to: u64,
+        known_erc20: &mut HashSet<String>,
+        known_nft: &mut HashSet<String>,
+    ) -> Result<()> {
+        let mut fetched = Vec::new();
+        let mut failed = Vec::new();
+        let mut block = from;
+        while block < to {
+            let count = FETCH_BLOCKS.min(to - block);
+            for result in fetch_blocks_batch(
+                http_client,
+                &self.rpc_url,
+                block,
+                count as usize,
+                rate_limiter,
+                &self.metrics,
+                self.raw_archive_enabled,
+            )
+            .await
+            {
+                match result {
+                    FetchResult::Success(data) => fetched.push(data),
+                    FetchResult::Error { block_num, error } => failed.push((block_num, error)),
+                }
+            }
+            block += count;
+        }
+        fetched.sort_by_key(|data| data.number);
+
+        let mut batch = BlockBatch::new();
+        batch.wrapped_native = self.wrapped_native.clone();
+        let written = fetched.len() as u64;
+        for data in fetched {
+            self.processors.collect(&data, &mut batch);
+            Indexer::collect_block(&mut batch, known_erc20, known_nft, *data);
+        }
+        if !batch.b_numbers.is_empty() {
+            ensure_partitions_exist(&self.pool, &self.current_max_partition, to - 1, false).await?;
+            batch.staged_partition = partitions::staged_partition_of(&self.pool, from).await?;
+            let new_erc20 = std::mem::take(&mut batch.new_erc20);
+            let new_nft = std::mem::take(&mut batch.new_nft);
+            Indexer::write_batch(copy_client, batch, false).await?;
+            known_erc20.extend(new_erc20);
+            known_nft.extend(new_nft);
+        }
+
+        for (block_num, error) in &failed {
+            tracing::warn!(block = block_num, error = %error, "backfill: block failed to fetch");
+            sqlx::query(FAILED_BLOCK_SQL)
+                .bind(*block_num as i64)
+                .bind(error)
+                .execute(&self.pool)
+                .await?;
+        }
+        if !failed.is_empty() {
+            self.metrics.record_failed_blocks(failed.len() as u64);
+            self.ops_alerts.fire(OpsAlert::FailedBlocks {
+                blocks: failed.iter().map(|(n, _)| *n).collect(),
+                error: failed[0].1.clone(),
+            });
+        }
+        metrics::counter!("atlas_backfill_blocks_total").increment(written);
+        Ok(())
+    }
+
+    /// Whether a backfill was planned and every range is complete, or none
+    /// will be planned. Marks the ERC-20 supply history complete on success.
+    async fn finished(&self) -> Result<bool> {
+        let split: Option<(String,)> =
+            sqlx::query_as("SELECT value FROM indexer_state WHERE key = $1")
+                .bind(BACKFILL_SPLIT_BLOCK_KEY)
+                .fetch_optional(&self.pool)
+                .await?;
+        if split.is_none() {
+            // The plan and the head indexer's watermark are written together,
+            // so an index with a watermark but no plan is not backfilled.
+            let (indexing,): (bool,) = sqlx::query_as(
+                "SELECT EXISTS (SELECT 1 FROM indexer_state WHERE key = 'last_indexed_block')",
+            )
+            .fetch_one(&self.pool)
+            .await?;
+            return Ok(indexing);
+        }
+
+        let (remaining,): (i64,) =
+            sqlx::query_as("SELECT COUNT(*) FROM range_leases WHERE completed_at IS NULL")
+                .fetch_one(&self.pool)
+                .await?;
+        metrics::gauge!("atlas_backfill_ranges_remaining").set(remaining as f64);
+        if remaining > 0 {
+            return Ok(false);
+        }
+
+        sqlx::query(
+            "UPDATE indexer_state SET value = 'true', updated_at = NOW()
+             WHERE key = $1 AND value = 'false'
+               AND EXISTS (SELECT 1 FROM range_leases WHERE start_block = 0)",
+        )
+        .bind(ERC20_SUPPLY_HISTORY_COMPLETE_KEY)
+        .execute(&self.pool)
+        .await?;
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn new_rejects_zero_rps() {
+        let pool = sqlx::postgres::PgPoolOptions::new()
+            .connect_lazy("postgres://test@localhost:5432/test")
+            .expect("lazy pool creation should not fail");
+        let err = BackfillWorker::new(
+            pool,
+            "postgres://test@localhost:5432/test",
+            "http://localhost:8545",
+            0,
+            Metrics::new(),
+            false,
+            OpsAlerter::default(),
+            0,
+        )
+        .err()
+        .expect("zero rps should fail");
+        assert!(err.to_string().contains("rpc_requests_per_second"));
+    }
+}
@@ -1,9 +1,15 @@
 use bigdecimal::BigDecimal;
+use std::collections::hash_map::Entry;
 use std::collections::{HashMap, HashSet};
+use std::ops::AddAssign;
+use std::sync::Arc;
 
 use atlas_common::Block;
 use chrono::{DateTime, Utc};
 
+use super::l1_fee::L1Fee;
+use super::processors::ProcessorWrites;
+
 // ---------------------------------------------------------------------------
 // Batch accumulator - collects data from multiple blocks before writing to DB
 // ---------------------------------------------------------------------------
@@ -12,6 +18,31 @@ pub(crate) struct AddrState {
     pub(crate) first_seen_block: i64,
     pub(crate) is_contract: bool,
     pub(crate) tx_count_delta: i64,
+    /// Per-block shares of `tx_count_delta`, see [`BlockBatch::discard_deltas`]
+    pub(crate) tx_count_shares: Vec<(i64, i64)>,
+    /// Earliest successful transaction sending this address native value
+    pub(crate) funding: Option<Provenance>,
+    /// Transaction that created this contract
+    pub(crate) creation: Option<Provenance>,
+}
+
+/// Where an address came from: the counterparty (funder or deployer) and
+/// the transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Provenance {
+    pub(crate) from: String,
+    pub(crate) block: i64,
+    pub(crate) tx_hash: String,
+}
+
+impl Provenance {
+    /// Keep the earliest of `slot` and `candidate`. Transactions are collected
+    /// in block order, so an equal block keeps the one seen first.
+    fn keep_earliest(slot: &mut Option<Provenance>, candidate: Provenance) {
+        if slot.as_ref().is_none_or(|p| candidate.block < p.block) {
+            *slot = Some(candidate);
+        }
+    }
 }
 
 pub(crate) struct NftTokenState {
@@ -19,9 +50,50 @@ pub(crate) struct NftTokenState {
     pub(crate) last_transfer_block: i64,
 }
 
+pub(crate) struct ApprovalState {
+    pub(crate) value: String, // BigDecimal as string
+    pub(crate) tx_hash: String,
+    pub(crate) log_index: i32,
+    pub(crate) block: i64,
+}
+
 pub(crate) struct BalanceDelta {
     pub(crate) delta: BigDecimal,
     pub(crate) last_block: i64,
+    pub(crate) shares: Vec<(i64, BigDecimal)>,
+}
+
+pub(crate) struct SupplyDelta {
+    pub(crate) delta: BigDecimal,
+    pub(crate) shares: Vec<(i64, BigDecimal)>,
+}
+
+/// Record `block`'s share of an aggregated delta. Blocks are collected in
+/// order, so consecutive shares of the same block are merged.
+fn add_share<T: AddAssign + Clone>(shares: &mut Vec<(i64, T)>, block: i64, value: T) {
+    match shares.last_mut() {
+        Some((last, share)) if *last == block => *share += value,
+        _ => shares.push((block, value)),
+    }
+}
+
+/// Remove the shares of `blocks`, returning their sum.
+fn take_shares<T: AddAssign + Default>(shares: &mut Vec<(i64, T)>, blocks: &HashSet<i64>) -> T {
+    let mut taken = T::default();
+    let (drop, keep): (Vec<_>, Vec<_>) = std::mem::take(shares)
+        .into_iter()
+        .partition(|(block, _)| blocks.contains(block));
+    for (_, share) in drop {
+        taken += share;
+    }
+    *shares = keep;
+    taken
+}
+
+/// Keep the rows of one column whose flag in `keep` is set.
+fn retain_rows<T>(column: &mut Vec<T>, keep: &[bool]) {
+    let mut flags = keep.iter();
+    column.retain(|_| *flags.next().unwrap_or(&true));
 }
 
 /// Holds all data collected across a batch of blocks, ready for bulk insert.
@@ -29,6 +101,13 @@ pub(crate) struct BalanceDelta {
 /// PostgreSQL UNNEST without any further transformation.
 #[derive(Default)]
 pub(crate) struct BlockBatch {
+    /// Staged partition holding every block of the batch, written directly
+    /// instead of through the parent tables (see `partitions`).
+    pub(crate) staged_partition: Option<u64>,
+    /// WETH-style contracts whose Deposit/Withdrawal events are collected
+    /// as ERC-20 mints and burns.
+    pub(crate) wrapped_native: Arc<HashSet<String>>,
+
     // blocks
     pub(crate) b_numbers: Vec<i64>,
     pub(crate) b_hashes: Vec<String>,
@@ -38,12 +117,14 @@ pub(crate) struct BlockBatch {
     pub(crate) b_gas_limits: Vec<i64>,
     pub(crate) b_base_fee_per_gas: Vec<Option<String>>,
     pub(crate) b_tx_counts: Vec<i32>,
+    pub(crate) b_logs_blooms: Vec<Vec<u8>>, // compacted, see crate::bloom
 
     // transactions (receipt data merged in at collection time)
     pub(crate) t_hashes: Vec<String>,
     pub(crate) t_block_numbers: Vec<i64>,
     pub(crate) t_block_indices: Vec<i32>,
     pub(crate) t_froms: Vec<String>,
+    pub(crate) t_nonces: Vec<i64>,
     pub(crate) t_tos: Vec<Option<String>>,
     pub(crate) t_values: Vec<String>, // BigDecimal as string → cast to numeric in SQL
     pub(crate) t_gas_prices: Vec<String>, // BigDecimal as string → cast to numeric in SQL
@@ -52,22 +133,30 @@ pub(crate) struct BlockBatch {
     pub(crate) t_statuses: Vec<bool>,
     pub(crate) t_timestamps: Vec<i64>,
     pub(crate) t_contracts_created: Vec<Option<String>>,
+    pub(crate) t_logs_blooms: Vec<Option<Vec<u8>>>, // None when the receipt is missing
+    pub(crate) t_l1_fees: Vec<Option<L1Fee>>,       // rollup L1 data fee, see l1_fee
+
+    // raw_block_archive — only populated when raw archiving is enabled
+    pub(crate) ra_block_numbers: Vec<i64>,
+    pub(crate) ra_block_hashes: Vec<String>,
+    pub(crate) ra_payloads: Vec<Vec<u8>>,
 
     // tx_hash_lookup
     pub(crate) tl_hashes: Vec<String>,
     pub(crate) tl_block_numbers: Vec<i64>,
+    pub(crate) tl_block_hashes: Vec<String>,
 
     // addresses — deduplicated by address in Rust
     pub(crate) addr_map: HashMap<String, AddrState>,
 
-    // event_logs
-    pub(crate) el_tx_hashes: Vec<String>,
+    // event_logs — hashes, addresses and topics as raw bytes
+    pub(crate) el_tx_hashes: Vec<Vec<u8>>,
     pub(crate) el_log_indices: Vec<i32>,
-    pub(crate) el_addresses: Vec<String>,
-    pub(crate) el_topic0s: Vec<String>,
-    pub(crate) el_topic1s: Vec<Option<String>>,
-    pub(crate) el_topic2s: Vec<Option<String>>,
-    pub(crate) el_topic3s: Vec<Option<String>>,
+    pub(crate) el_addresses: Vec<Vec<u8>>,
+    pub(crate) el_topic0s: Vec<Vec<u8>>,
+    pub(crate) el_topic1s: Vec<Option<Vec<u8>>>,
+    pub(crate) el_topic2s: Vec<Option<Vec<u8>>>,
+    pub(crate) el_topic3s: Vec<Option<Vec<u8>>>,
     pub(crate) el_datas: Vec<Vec<u8>>,
     pub(crate) el_block_numbers: Vec<i64>,
 
@@ -105,8 +194,11 @@ pub(crate) struct BlockBatch {
     // erc20_balances — aggregated deltas per (address, contract)
     pub(crate) balance_map: HashMap<(String, String), BalanceDelta>,
 
+    // erc20_approvals — only the latest Approval per (owner, contract, spender)
+    pub(crate) approval_map: HashMap<(String, String, String), ApprovalState>,
+
     // erc20 total supply deltas — aggregated per contract from mint/burn events
-    pub(crate) supply_map: HashMap<String, BigDecimal>,
+    pub(crate) supply_map: HashMap<String, SupplyDelta>,
 
     // Contracts newly discovered in this batch.
     // These are NOT merged into the persistent known_* sets until after a
@@ -115,6 +207,9 @@ pub(crate) struct BlockBatch {
     pub(crate) new_erc20: HashSet<String>,
     pub(crate) new_nft: HashSet<String>,
 
+    // Statements staged by block processors, run after the rows above
+    pub(crate) processor_writes: ProcessorWrites,
+
     pub(crate) last_block: u64,
 }
 
@@ -123,6 +218,15 @@ impl BlockBatch {
         Self::default()
     }
 
+    /// Target of an insert into partitioned `table`: the parent, or the
+    /// batch's staged partition aliased to the parent's name.
+    pub(crate) fn target(&self, table: &str) -> String {
+        match self.staged_partition {
+            Some(p) => format!("{table}_p{p} AS {table}"),
+            None => table.to_string(),
+        }
+    }
+
     /// Upsert an address into the in-memory deduplication map.
     /// tx_count_delta is added to whatever was already accumulated for this address.
     pub(crate) fn touch_addr(
@@ -132,14 +236,36 @@ impl BlockBatch {
         is_contract: bool,
         tx_count_delta: i64,
     ) {
-        let entry = self.addr_map.entry(address).or_insert(AddrState {
-            first_seen_block: block_num,
-            is_contract: false,
-            tx_count_delta: 0,
-        });
+        let entry = self.addr_state(address, block_num);
         entry.first_seen_block = entry.first_seen_block.min(block_num);
         entry.is_contract |= is_contract;
         entry.tx_count_delta += tx_count_delta;
+        if tx_count_delta != 0 {
+            add_share(&mut entry.tx_count_shares, block_num, tx_count_delta);
+        }
+    }
+
+    /// Record a successful native value transfer to `address`.
+    pub(crate) fn record_funding(&mut self, address: String, provenance: Provenance) {
+        let entry = self.addr_state(address, provenance.block);
+        Provenance::keep_earliest(&mut entry.funding, provenance);
+    }
+
+    /// Record the transaction that created the contract at `address`.
+    pub(crate) fn record_creation(&mut self, address: String, provenance: Provenance) {
+        let entry = self.addr_state(address, provenance.block);
+        Provenance::keep_earliest(&mut entry.creation, provenance);
+    }
+
+    fn addr_state(&mut self, address: String, block_num: i64) -> &mut AddrState {
+        self.addr_map.entry(address).or_insert(AddrState {
+            first_seen_block: block_num,
+            is_contract: false,
+            tx_count_delta: 0,
+            tx_count_shares: Vec::new(),
+            funding: None,
+            creation: None,
+        })
     }
 
     /// Add a balance delta for (address, contract).
@@ -157,19 +283,85 @@ impl BlockBatch {
             .or_insert(BalanceDelta {
                 delta: BigDecimal::from(0),
                 last_block: block,
+                shares: Vec::new(),
             });
-        entry.delta += delta;
+        entry.delta += delta.clone();
         entry.last_block = entry.last_block.max(block);
+        add_share(&mut entry.shares, block, delta);
+    }
+
+    /// Record an ERC-20 Approval; a later (block, log index) replaces an
+    /// earlier one for the same (owner, contract, spender).
+    pub(crate) fn record_approval(
+        &mut self,
+        owner: String,
+        contract: String,
+        spender: String,
+        approval: ApprovalState,
+    ) {
+        match self.approval_map.entry((owner, contract, spender)) {
+            Entry::Occupied(mut entry) => {
+                let current = entry.get();
+                if (approval.block, approval.log_index) >= (current.block, current.log_index) {
+                    entry.insert(approval);
+                }
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(approval);
+            }
+        }
     }
 
     /// Add a total supply delta for a contract.
     /// Only mint and burn transfers should touch this accumulator.
-    pub(crate) fn apply_supply_delta(&mut self, contract: String, delta: BigDecimal) {
-        let entry = self
-            .supply_map
-            .entry(contract)
-            .or_insert(BigDecimal::from(0));
-        *entry += delta;
+    pub(crate) fn apply_supply_delta(&mut self, contract: String, delta: BigDecimal, block: i64) {
+        let entry = self.supply_map.entry(contract).or_insert(SupplyDelta {
+            delta: BigDecimal::from(0),
+            shares: Vec::new(),
+        });
+        entry.delta += delta.clone();
+        add_share(&mut entry.shares, block, delta);
+    }
+
+    /// Take the given blocks' contributions back out of the aggregated
+    /// tx_count, balance and supply deltas. Used when some blocks of the batch
+    /// were already written, so their deltas must not be applied twice.
+    pub(crate) fn discard_deltas(&mut self, blocks: &HashSet<i64>) {
+        for state in self.addr_map.values_mut() {
+            state.tx_count_delta -= take_shares(&mut state.tx_count_shares, blocks);
+        }
+        self.balance_map.retain(|_, entry| {
+            entry.delta -= take_shares(&mut entry.shares, blocks);
+            match entry.shares.iter().map(|(block, _)| *block).max() {
+                Some(last_block) => {
+                    entry.last_block = last_block;
+                    true
+                }
+                None => false,
+            }
+        });
+        self.supply_map.retain(|_, entry| {
+            entry.delta -= take_shares(&mut entry.shares, blocks);
+            !entry.shares.is_empty()
+        });
+    }
+
+    /// Drop the ERC-20 transfer rows of the given blocks that lie below
+    /// `before`: they were already folded into `erc20_transfer_summaries`.
+    pub(crate) fn discard_aggregated_transfers(&mut self, blocks: &HashSet<i64>, before: i64) {
+        let keep: Vec<bool> = self
+            .et_block_numbers
+            .iter()
+            .map(|block| *block >= before || !blocks.contains(block))
+            .collect();
+        retain_rows(&mut self.et_tx_hashes, &keep);
+        retain_rows(&mut self.et_log_indices, &keep);
+        retain_rows(&mut self.et_contracts, &keep);
+        retain_rows(&mut self.et_froms, &keep);
+        retain_rows(&mut self.et_tos, &keep);
+        retain_rows(&mut self.et_values, &keep);
+        retain_rows(&mut self.et_block_numbers, &keep);
+        retain_rows(&mut self.et_timestamps, &keep);
     }
 
     pub(crate) fn materialize_blocks(&self, indexed_at: DateTime<Utc>) -> Vec<Block> {
@@ -218,6 +410,22 @@ mod tests {
         assert_eq!(batch.addr_map["0xabc"].first_seen_block, 100);
     }
 
+    #[test]
+    fn record_funding_keeps_earliest_transfer() {
+        let mut batch = BlockBatch::new();
+        let funding = |from: &str, block| Provenance {
+            from: from.to_string(),
+            block,
+            tx_hash: format!("0x{block}"),
+        };
+        batch.record_funding("0xabc".to_string(), funding("0xb", 200));
+        batch.record_funding("0xabc".to_string(), funding("0xa", 100));
+        batch.record_funding("0xabc".to_string(), funding("0xc", 100));
+
+        assert_eq!(batch.addr_map["0xabc"].funding, Some(funding("0xa", 100)));
+        assert!(batch.addr_map["0xabc"].creation.is_none());
+    }
+
     #[test]
     fn touch_addr_is_contract_latches_true() {
         let mut batch = BlockBatch::new();
@@ -282,10 +490,60 @@ mod tests {
         let mut batch = BlockBatch::new();
         let contract = "0xtoken".to_string();
 
-        batch.apply_supply_delta(contract.clone(), BigDecimal::from(100));
-        batch.apply_supply_delta(contract.clone(), BigDecimal::from(-25));
+        batch.apply_supply_delta(contract.clone(), BigDecimal::from(100), 10);
+        batch.apply_supply_delta(contract.clone(), BigDecimal::from(-25), 11);
+
+        assert_eq!(batch.supply_map[&contract].delta, BigDecimal::from(75));
+    }
+
+    #[test]
+    fn discard_deltas_removes_only_the_given_blocks() {
+        let mut batch = BlockBatch::new();
+        let key = ("0xaddr".to_string(), "0xtoken".to_string());
+        let only_replayed = ("0xother".to_string(), "0xtoken".to_string());
+        batch.touch_addr("0xaddr".to_string(), 10, false, 1);
+        batch.touch_addr("0xaddr".to_string(), 10, false, 1);
+        batch.touch_addr("0xaddr".to_string(), 11, false, 1);
+        batch.apply_balance_delta(key.0.clone(), key.1.clone(), BigDecimal::from(5), 10);
+        batch.apply_balance_delta(key.0.clone(), key.1.clone(), BigDecimal::from(7), 11);
+        batch.apply_balance_delta(
+            only_replayed.0.clone(),
+            only_replayed.1.clone(),
+            BigDecimal::from(3),
+            11,
+        );
+        batch.apply_supply_delta("0xtoken".to_string(), BigDecimal::from(12), 11);
+
+        batch.discard_deltas(&HashSet::from([11]));
 
-        assert_eq!(batch.supply_map[&contract], BigDecimal::from(75));
+        assert_eq!(batch.addr_map["0xaddr"].tx_count_delta, 2);
+        assert_eq!(batch.balance_map[&key].delta, BigDecimal::from(5));
+        assert_eq!(batch.balance_map[&key].last_block, 10);
+        assert!(!batch.balance_map.contains_key(&only_replayed));
+        assert!(batch.supply_map.is_empty());
+    }
+
+    #[test]
+    fn discard_aggregated_transfers_keeps_recent_and_new_blocks() {
+        let mut batch = BlockBatch::new();
+        for (block, log_index) in [(10, 0), (10, 1), (11, 0), (20, 0)] {
+            batch.et_tx_hashes.push(format!("0x{block}{log_index}"));
+            batch.et_log_indices.push(log_index);
+            batch.et_contracts.push("0xtoken".to_string());
+            batch.et_froms.push("0xfrom".to_string());
+            batch.et_tos.push("0xto".to_string());
+            batch.et_values.push("1".to_string());
+            batch.et_block_numbers.push(block);
+            batch.et_timestamps.push(1_700_000_000 + block);
+        }
+
+        // Block 10 was written before and aggregated; 11 is new (gap fill);
+        // 20 is a replay above the cutoff.
+        batch.discard_aggregated_transfers(&HashSet::from([10, 20]), 15);
+
+        assert_eq!(batch.et_block_numbers, vec![11, 20]);
+        assert_eq!(batch.et_tx_hashes, vec!["0x110", "0x200"]);
+        assert_eq!(batch.et_timestamps, vec![1_700_000_011, 1_700_000_020]);
     }
 
     #[test]
@@ -0,0 +1,128 @@
+//! Opt-in index over the start of transaction calldata.
+//!
+//! ## Design
+//!
+//! `/api/transactions?input_prefix=` finds transactions whose input starts
+//! with given bytes: a method selector, optionally followed by the start of
+//! its arguments (e.g. `transfer` to one recipient). A btree over the first
+//! [`PREFIX_BYTES`] bytes (the selector and the first argument word) answers
+//! that as a range scan: every input starting with `p` sorts between `p` and
+//! `p` padded with `0xff` bytes.
+//!
+//! The index costs write throughput and disk on every transaction, so it is
+//! only built with `CALLDATA_PREFIX_INDEX`. It is built without blocking the
+//! indexer: an invalid `ON ONLY` index on the partitioned parent, one
+//! `CREATE INDEX CONCURRENTLY` per attached partition, each attached to the
+//! parent. Postgres marks the parent valid once every partition is attached,
+//! which is what [`is_ready`] checks, and gives it to partitions created
+//! later. Until then the API refuses the filter rather than scanning every
+//! transaction.
+
+use anyhow::Result;
+use sqlx::PgPool;
+use std::time::Instant;
+
+/// Leading calldata bytes covered: the selector and one argument word.
+pub const PREFIX_BYTES: usize = 36;
+
+const INDEX_NAME: &str = "idx_transactions_input_prefix";
+
+/// Indexed expression; queries must use it verbatim to match the index.
+pub const PREFIX_EXPRESSION: &str = "substring(input_data FROM 1 FOR 36)";
+
+/// Build the index on `transactions` and every attached partition. Safe to
+/// call again after an interruption: finished partitions are skipped.
+pub async fn ensure(pool: &PgPool) -> Result<()> {
+    if is_ready(pool).await? {
+        return Ok(());
+    }
+    let started = Instant::now();
+
+    sqlx::query(&format!(
+        "CREATE INDEX IF NOT EXISTS {INDEX_NAME} ON ONLY transactions ({PREFIX_EXPRESSION})"
+    ))
+    .execute(pool)
+    .await?;
+
+    let partitions: Vec<(String,)> = sqlx::query_as(
+        "SELECT c.relname::text FROM pg_inherits i
+         JOIN pg_class c ON c.oid = i.inhrelid
+         WHERE i.inhparent = 'transactions'::regclass
+         ORDER BY c.relname",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    for (partition,) in partitions {
+        let name = format!(
+            "{INDEX_NAME}_{}",
+            partition.trim_start_matches("transactions_")
+        );
+        // An interrupted concurrent build leaves an invalid index behind.
+        let (invalid,): (bool,) = sqlx::query_as(
+            "SELECT EXISTS (SELECT 1 FROM pg_index x JOIN pg_class i ON i.oid = x.indexrelid
+                            WHERE i.relname = $1 AND NOT x.indisvalid)",
+        )
+        .bind(&name)
+        .fetch_one(pool)
+        .await?;
+        if invalid {
+            sqlx::query(&format!("DROP INDEX CONCURRENTLY {name}"))
+                .execute(pool)
+                .await?;
+        }
+        sqlx::query(&format!(
+            "CREATE INDEX CONCURRENTLY IF NOT EXISTS {name} ON {partition} ({PREFIX_EXPRESSION})"
+        ))
+        .execute(pool)
+        .await?;
+        sqlx::query(&format!("ALTER INDEX {INDEX_NAME} ATTACH PARTITION {name}"))
+            .execute(pool)
+            .await?;
+    }
+
+    tracing::info!(
+        duration_secs = started.elapsed().as_secs(),
+        "built calldata prefix index"
+    );
+    Ok(())
+}
+
+/// Whether the index exists and covers every partition.
+pub async fn is_ready(pool: &PgPool) -> Result<bool, sqlx::Error> {
+    let valid: Option<(bool,)> = sqlx::query_as(
+        "SELECT x.indisvalid FROM pg_index x JOIN pg_class i ON i.oid = x.indexrelid
+         WHERE i.relname = $1",
+    )
+    .bind(INDEX_NAME)
+    .fetch_optional(pool)
+    .await?;
+    Ok(valid.is_some_and(|(valid,)| valid))
+}
+
+/// Inclusive bounds of [`PREFIX_EXPRESSION`] for inputs starting with
+/// `prefix` (at most [`PREFIX_BYTES`] long).
+pub fn prefix_range(prefix: &[u8]) -> (Vec<u8>, Vec<u8>) {
+    let mut upper = prefix.to_vec();
+    upper.resize(PREFIX_BYTES.max(prefix.len()), 0xff);
+    (prefix.to_vec(), upper)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prefix_range_pads_the_upper_bound_with_ff() {
+        let (lower, upper) = prefix_range(&[0xa9, 0x05, 0x9c, 0xbb]);
+        assert_eq!(lower, [0xa9, 0x05, 0x9c, 0xbb]);
+        assert_eq!(upper.len(), PREFIX_BYTES);
+        assert_eq!(&upper[..4], &[0xa9, 0x05, 0x9c, 0xbb]);
+        assert!(upper[4..].iter().all(|byte| *byte == 0xff));
+
+        // A full-width prefix is an exact match
+        let full = [0x11; PREFIX_BYTES];
+        assert_eq!(prefix_range(&full), (full.to_vec(), full.to_vec()));
+        assert!(PREFIX_EXPRESSION.ends_with(&format!("FOR {PREFIX_BYTES})")));
+    }
+}
@@ -0,0 +1,125 @@
+//! Scheduled resolution of ERC-20 symbols shared by several tokens.
+//!
+//! ## Design
+//!
+//! Anyone can deploy a token called USDC, so a symbol alone does not identify
+//! a token. For every symbol (compared case-insensitively) used by more than
+//! one contract, this job picks the one the explorer should treat as the real
+//! token and records it in `canonical_tokens`:
+//!
+//! 1. a token an admin marked `verified` in `token_curation` wins;
+//! 2. otherwise the token with the most holders (positive balances), then
+//!    the most transfers, then the earliest first-seen block.
+//!
+//! Tokens marked `flagged` are never chosen. API responses join against both
+//! tables to set `verified`, `flagged` and `duplicate_of`, and lists and search
+//! rank the canonical token above its look-alikes.
+//!
+//! Admin changes re-resolve the affected symbol right away through
+//! [`resolve_symbols`]; the periodic cycle catches up with new tokens and
+//! shifting holder counts. A reindex truncates `canonical_tokens` but keeps
+//! the admin verdicts.
+
+use anyhow::{bail, Result};
+use sqlx::PgPool;
+use std::time::Duration;
+
+/// Ranks the tokens of every shared symbol (or just `$1` when it is set) and
+/// keeps the best one per symbol.
+const RANKED_SQL: &str = r#"
+    WITH shared AS (
+        SELECT lower(symbol) AS symbol_key
+        FROM erc20_contracts
+        WHERE symbol IS NOT NULL AND symbol <> ''
+          AND ($1::text IS NULL OR lower(symbol) = $1)
+        GROUP BY 1
+        HAVING COUNT(*) > 1
+    ),
+    candidates AS (
+        SELECT s.symbol_key, c.address, c.first_seen_block,
+               COALESCE(cur.status = 'verified', false) AS verified,
+               (SELECT COUNT(*) FROM erc20_balances b
+                WHERE b.contract_address = c.address AND b.balance > 0) AS holder_count,
+               (SELECT COUNT(*) FROM erc20_transfers t
+                WHERE t.contract_address = c.address)
+             + (SELECT COALESCE(SUM(s.transfer_count), 0) FROM erc20_transfer_summaries s
+                WHERE s.contract_address = c.address)::bigint AS transfer_count
+        FROM shared s
+        JOIN erc20_contracts c ON lower(c.symbol) = s.symbol_key
+        LEFT JOIN token_curation cur ON cur.address = c.address
+        WHERE cur.status IS DISTINCT FROM 'flagged'
+    ),
+    ranked AS (
+        SELECT DISTINCT ON (symbol_key)
+               symbol_key, address, verified, holder_count, transfer_count
+        FROM candidates
+        ORDER BY symbol_key, verified DESC, holder_count DESC, transfer_count DESC,
+                 first_seen_block ASC, address ASC
+    )
+"#;
+
+pub struct CanonicalTokenResolver {
+    pool: PgPool,
+    interval: Duration,
+}
+
+impl CanonicalTokenResolver {
+    pub fn new(pool: PgPool, interval: Duration) -> Result<Self> {
+        if interval.is_zero() {
+            bail!("canonical token interval must be greater than 0");
+        }
+        Ok(Self { pool, interval })
+    }
+
+    pub async fn run(&self) -> Result<()> {
+        tracing::info!(
+            interval_secs = self.interval.as_secs(),
+            "Canonical token resolution started"
+        );
+        loop {
+            let symbols = resolve_symbols(&self.pool, None).await?;
+            tracing::debug!(symbols, "canonical token cycle complete");
+            tokio::time::sleep(self.interval).await;
+        }
+    }
+}
+
+/// Re-resolve every shared symbol, or only `symbol` (any case), and return
+/// how many symbols now have a canonical token.
+pub async fn resolve_symbols(pool: &PgPool, symbol: Option<&str>) -> Result<u64, sqlx::Error> {
+    let symbol_key = symbol.map(str::to_lowercase);
+    let mut tx = pool.begin().await?;
+
+    sqlx::query(&format!(
+        "{RANKED_SQL}
+         DELETE FROM canonical_tokens
+         WHERE ($1::text IS NULL OR symbol_key = $1)
+           AND symbol_key NOT IN (SELECT symbol_key FROM ranked)"
+    ))
+    .bind(&symbol_key)
+    .execute(&mut *tx)
+    .await?;
+
+    let resolved = sqlx::query(&format!(
+        "{RANKED_SQL}
+         INSERT INTO canonical_tokens
+             (symbol_key, address, source, holder_count, transfer_count, updated_at)
+         SELECT symbol_key, address,
+                CASE WHEN verified THEN 'admin' ELSE 'heuristic' END,
+                holder_count, transfer_count, NOW()
+         FROM ranked
+         ON CONFLICT (symbol_key) DO UPDATE SET
+             address = EXCLUDED.address,
+             source = EXCLUDED.source,
+             holder_count = EXCLUDED.holder_count,
+             transfer_count = EXCLUDED.transfer_count,
+             updated_at = EXCLUDED.updated_at"
+    ))
+    .bind(&symbol_key)
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+
+    tx.commit().await?;
+    Ok(resolved)
+}
@@ -0,0 +1,387 @@
+//! Per-contract reindexing (`POST /api/admin/contracts/{address}/reindex`),
+//! for when a decoder bug affected one token and a global reindex is too
+//! expensive.
+//!
+//! ## Design
+//!
+//! - A request queues a row in `contract_reindex_jobs`; at most one job per
+//!   contract is open at a time. The [`ContractReindexer`] runs queued jobs
+//!   one at a time and queues a job left `running` by a restart again.
+//! - A job rescans the contract's `Transfer` (and, for wrapped native
+//!   tokens, `Deposit`/`Withdrawal`) logs in `event_logs`, decodes them with
+//!   the indexer's own [`decode_transfer_log`], and replaces the contract's
+//!   transfers. Balances, total supply and NFT owners are then recomputed
+//!   from the rebuilt transfers rather than adjusted by deltas.
+//! - Everything runs in one transaction on a dedicated tokio-postgres
+//!   connection (the sqlx pools have a 10s `statement_timeout`). It locks
+//!   `processed_blocks` first, which every indexer batch writes to, so live
+//!   indexing pauses until the job commits and no delta is applied twice.
+//! - Transfers below the aggregation cutoff are folded back into
+//!   `erc20_transfer_summaries`. With cold storage the hot `event_logs` no
+//!   longer hold the full history, so jobs fail instead of dropping it.
+
+use alloy::primitives::B256;
+use anyhow::{bail, Result};
+use chrono::{DateTime, Utc};
+use futures::TryStreamExt;
+use serde::Serialize;
+use sqlx::PgPool;
+use std::collections::HashSet;
+use std::time::Duration;
+use tokio_postgres::types::ToSql;
+
+use super::address_directory;
+use super::batch::BlockBatch;
+use super::copy::{copy_erc20_transfers, copy_nft_transfers};
+use super::holder_ranks;
+use super::indexer::{decode_transfer_log, Indexer, TransferLog, TRANSFER_EVENT_TOPICS};
+use crate::state_keys::TRANSFER_AGGREGATION_BEFORE_BLOCK_KEY;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+const ZERO_ADDRESS: &str = "0x0000000000000000000000000000000000000000";
+
+/// A row of `contract_reindex_jobs`.
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct ContractReindexJob {
+    pub id: i64,
+    pub address: String,
+    /// `queued`, `running`, `completed` or `failed`
+    pub status: String,
+    /// ERC-20 transfers rebuilt, set once completed
+    pub erc20_transfers: Option<i64>,
+    /// NFT transfers rebuilt, set once completed
+    pub nft_transfers: Option<i64>,
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+pub const CONTRACT_REINDEX_JOB_COLUMNS: &str = "id, address, status, erc20_transfers, nft_transfers, error, created_at, started_at, completed_at";
+
+const SELECT_LOGS_SQL: &str = "
+    SELECT '0x' || encode(l.tx_hash, 'hex'), l.log_index, l.topic0, l.topic1, l.topic2, l.topic3,
+           l.data, l.block_number, b.timestamp
+    FROM event_logs l
+    JOIN blocks b ON b.number = l.block_number
+    WHERE l.address = $1 AND l.topic0 = ANY($2)
+    ORDER BY l.block_number, l.log_index";
+
+/// Move the contract's transfers below the cutoff into the daily summaries,
+/// as `transfer_aggregation` does for every token.
+const AGGREGATE_CONTRACT_SQL: &str = "
+    WITH moved AS (
+        DELETE FROM erc20_transfers
+        WHERE contract_address = $1 AND block_number < $2
+        RETURNING contract_address, from_address, to_address, value, block_number, timestamp
+    )
+    INSERT INTO erc20_transfer_summaries
+        (day, contract_address, from_address, to_address, transfer_count, value, first_block, last_block)
+    SELECT DATE '1970-01-01' + (timestamp / 86400)::int, contract_address, from_address, to_address,
+           COUNT(*), SUM(value), MIN(block_number), MAX(block_number)
+    FROM moved
+    GROUP BY 1, 2, 3, 4";
+
+/// Balances summed over raw transfers and summaries; the zero address has
+/// none and a zero balance is stored as a missing row.
+const REBUILD_BALANCES_SQL: &str = "
+    INSERT INTO erc20_balances (address, contract_address, balance, last_updated_block)
+    SELECT address, $1, SUM(delta), MAX(block_number)
+    FROM (
+        SELECT to_address AS address, value AS delta, block_number
+        FROM erc20_transfers WHERE contract_address = $1
+        UNION ALL
+        SELECT from_address, -value, block_number
+        FROM erc20_transfers WHERE contract_address = $1
+        UNION ALL
+        SELECT to_address, value, last_block
+        FROM erc20_transfer_summaries WHERE contract_address = $1
+        UNION ALL
+        SELECT from_address, -value, last_block
+        FROM erc20_transfer_summaries WHERE contract_address = $1
+    ) d
+    WHERE address <> $2
+    GROUP BY address
+    HAVING SUM(delta) <> 0";
+
+/// Total supply is mints minus burns, as the indexer accumulates it.
+const REBUILD_SUPPLY_SQL: &str = "
+    UPDATE erc20_contracts SET total_supply = (
+        SELECT COALESCE(SUM(CASE WHEN from_address = $2 THEN value ELSE 0 END), 0)
+             - COALESCE(SUM(CASE WHEN to_address = $2 THEN value ELSE 0 END), 0)
+        FROM (
+            SELECT from_address, to_address, value
+            FROM erc20_transfers WHERE contract_address = $1
+            UNION ALL
+            SELECT from_address, to_address, value
+            FROM erc20_transfer_summaries WHERE contract_address = $1
+        ) t
+    )
+    WHERE address = $1";
+
+/// Owner of each token from its latest transfer; metadata state is kept.
+const REBUILD_NFT_TOKENS_SQL: &str = "
+    INSERT INTO nft_tokens (
+        contract_address, token_id, owner, metadata_status, metadata_retry_count,
+        next_retry_at, last_transfer_block
+    )
+    SELECT DISTINCT ON (token_id) contract_address, token_id, to_address, 'pending', 0, NOW(), block_number
+    FROM nft_transfers
+    WHERE contract_address = $1
+    ORDER BY token_id, block_number DESC, log_index DESC
+    ON CONFLICT (contract_address, token_id) DO UPDATE SET
+        owner = EXCLUDED.owner,
+        last_transfer_block = EXCLUDED.last_transfer_block";
+
+pub struct ContractReindexer {
+    pool: PgPool,
+    database_url: String,
+    wrapped_native: HashSet<String>,
+    cold_storage: bool,
+}
+
+impl ContractReindexer {
+    pub fn new(pool: PgPool, database_url: &str, cold_storage: bool) -> Self {
+        Self {
+            pool,
+            database_url: database_url.to_string(),
+            wrapped_native: HashSet::new(),
+            cold_storage,
+        }
+    }
+
+    /// Decode Deposit/Withdrawal events of these WETH-style contracts as
+    /// mints and burns, as the indexer does.
+    pub fn with_wrapped_native(mut self, contracts: &[String]) -> Self {
+        self.wrapped_native = contracts.iter().cloned().collect();
+        self
+    }
+
+    pub async fn run(&self) -> Result<()> {
+        let requeued = sqlx::query(
+            "UPDATE contract_reindex_jobs SET status = 'queued', started_at = NULL
+             WHERE status = 'running'",
+        )
+        .execute(&self.pool)
+        .await?
+        .rows_affected();
+        tracing::info!(requeued, "Contract reindex worker started");
+
+        loop {
+            if !self.run_cycle().await? {
+                tokio::time::sleep(POLL_INTERVAL).await;
+            }
+        }
+    }
+
+    /// Run the oldest queued job. Returns whether a job was run.
+    pub async fn run_cycle(&self) -> Result<bool> {
+        let job: Option<ContractReindexJob> = sqlx::query_as(&format!(
+            "UPDATE contract_reindex_jobs SET status = 'running', started_at = NOW()
+             WHERE id = (
+                 SELECT id FROM contract_reindex_jobs WHERE status = 'queued'
+                 ORDER BY id LIMIT 1 FOR UPDATE SKIP LOCKED
+             )
+             RETURNING {CONTRACT_REINDEX_JOB_COLUMNS}"
+        ))
+        .fetch_optional(&self.pool)
+        .await?;
+        let Some(job) = job else {
+            return Ok(false);
+        };
+
+        match self.reindex(&job).await {
+            Ok((erc20, nft)) => {
+                tracing::info!(id = job.id, address = %job.address, erc20, nft, "contract reindex complete");
+            }
+            Err(e) => {
+                tracing::warn!(id = job.id, address = %job.address, error = %e, "contract reindex failed");
+                sqlx::query(
+                    "UPDATE contract_reindex_jobs
+                     SET status = 'failed', error = $2, completed_at = NOW()
+                     WHERE id = $1",
+                )
+                .bind(job.id)
+                .bind(format!("{e:#}"))
+                .execute(&self.pool)
+                .await?;
+            }
+        }
+        Ok(true)
+    }
+
+    /// Rebuild the contract's transfers and derived state, mark the job
+    /// completed in the same transaction, and return the number of ERC-20
+    /// and NFT transfers rebuilt.
+    async fn reindex(&self, job: &ContractReindexJob) -> Result<(i64, i64)> {
+        if self.cold_storage {
+            bail!("contract reindexing is unavailable with cold storage enabled");
+        }
+        let address = job.address.as_str();
+        let address_bytes = hex::decode(address.trim_start_matches("0x"))?;
+        let topics = TRANSFER_EVENT_TOPICS
+            .iter()
+            .map(|topic| hex::decode(topic.trim_start_matches("0x")))
+            .collect::<Result<Vec<_>, _>>()?;
+        let wrapped_native = self.wrapped_native.contains(address);
+
+        let mut client = Indexer::connect_copy_client(&self.database_url).await?;
+        let mut tx = client.transaction().await?;
+        tx.batch_execute("LOCK TABLE processed_blocks IN SHARE ROW EXCLUSIVE MODE")
+            .await?;
+
+        let mut batch = BlockBatch::new();
+        let params: [&(dyn ToSql + Sync); 2] = [&address_bytes, &topics];
+        let rows = tx.query_raw(SELECT_LOGS_SQL, params).await?;
+        futures::pin_mut!(rows);
+        while let Some(row) = rows.try_next().await? {
+            let log_topics: Vec<B256> = (2..6)
+                .filter_map(|i| row.get::<_, Option<&[u8]>>(i))
+                .filter_map(|topic| B256::try_from(topic).ok())
+                .collect();
+            let Some(transfer) = decode_transfer_log(&log_topics, row.get(6), wrapped_native)
+            else {
+                continue;
+            };
+            let tx_hash: String = row.get(0);
+            let log_index: i32 = row.get(1);
+            let block_number: i64 = row.get(7);
+            let timestamp: i64 = row.get(8);
+            match transfer {
+                TransferLog::Erc20 { from, to, value } => {
+                    batch.et_tx_hashes.push(tx_hash);
+                    batch.et_log_indices.push(log_index);
+                    batch.et_contracts.push(address.to_string());
+                    batch.et_froms.push(from);
+                    batch.et_tos.push(to);
+                    batch.et_values.push(value.to_string());
+                    batch.et_block_numbers.push(block_number);
+                    batch.et_timestamps.push(timestamp);
+                }
+                TransferLog::Nft { from, to, token_id } => {
+                    batch.nt_tx_hashes.push(tx_hash);
+                    batch.nt_log_indices.push(log_index);
+                    batch.nt_contracts.push(address.to_string());
+                    batch.nt_token_ids.push(token_id.to_string());
+                    batch.nt_froms.push(from);
+                    batch.nt_tos.push(to);
+                    batch.nt_block_numbers.push(block_number);
+                    batch.nt_timestamps.push(timestamp);
+                }
+            }
+        }
+        let erc20 = batch.et_tx_hashes.len() as i64;
+        let nft = batch.nt_tx_hashes.len() as i64;
+
+        for table in [
+            "erc20_transfers",
+            "erc20_transfer_summaries",
+            "erc20_balances",
+            "nft_transfers",
+        ] {
+            tx.execute(
+                &format!("DELETE FROM {table} WHERE contract_address = $1"),
+                &[&address],
+            )
+            .await?;
+        }
+        copy_erc20_transfers(&mut tx, &batch).await?;
+        copy_nft_transfers(&mut tx, &batch).await?;
+
+        let aggregated_before = tx
+            .query_opt(
+                "SELECT value::bigint FROM indexer_state WHERE key = $1",
+                &[&TRANSFER_AGGREGATION_BEFORE_BLOCK_KEY],
+            )
+            .await?
+            .map(|row| row.get::<_, i64>(0));
+        if let Some(before) = aggregated_before {
+            tx.execute(AGGREGATE_CONTRACT_SQL, &[&address, &before])
+                .await?;
+        }
+
+        if erc20 > 0 {
+            let first_block = batch.et_block_numbers[0];
+            tx.execute(
+                "INSERT INTO erc20_contracts (address, decimals, first_seen_block)
+                 VALUES ($1, 18, $2)
+                 ON CONFLICT (address) DO NOTHING",
+                &[&address, &first_block],
+            )
+            .await?;
+        }
+        tx.execute(REBUILD_BALANCES_SQL, &[&address, &ZERO_ADDRESS])
+            .await?;
+        tx.execute(REBUILD_SUPPLY_SQL, &[&address, &ZERO_ADDRESS])
+            .await?;
+
+        if nft > 0 {
+            let first_block = batch.nt_block_numbers[0];
+            tx.execute(
+                "INSERT INTO nft_contracts (address, first_seen_block)
+                 VALUES ($1, $2)
+                 ON CONFLICT (address) DO NOTHING",
+                &[&address, &first_block],
+            )
+            .await?;
+        }
+        tx.execute(
+            "DELETE FROM nft_tokens t
+             WHERE t.contract_address = $1
+               AND NOT EXISTS (
+                   SELECT 1 FROM nft_transfers n
+                   WHERE n.contract_address = t.contract_address AND n.token_id = t.token_id
+               )",
+            &[&address],
+        )
+        .await?;
+        tx.execute(REBUILD_NFT_TOKENS_SQL, &[&address]).await?;
+
+        let mut accounts: Vec<String> = batch
+            .et_froms
+            .iter()
+            .chain(&batch.et_tos)
+            .chain(&batch.nt_froms)
+            .chain(&batch.nt_tos)
+            .filter(|account| *account != ZERO_ADDRESS)
+            .cloned()
+            .collect();
+        accounts.sort_unstable();
+        accounts.dedup();
+        accounts.push(address.to_string());
+        tx.execute(address_directory::REFRESH_SQL, &[&accounts])
+            .await?;
+
+        tx.execute(
+            "UPDATE contract_reindex_jobs
+             SET status = 'completed', erc20_transfers = $2, nft_transfers = $3, completed_at = NOW()
+             WHERE id = $1",
+            &[&job.id, &erc20, &nft],
+        )
+        .await?;
+        tx.commit().await?;
+
+        // The job has completed; a stale ranking is fixed by the next ranker
+        // cycle that sees a transfer of this token.
+        if let Err(e) = self.rerank(address).await {
+            tracing::warn!(address, error = %e, "failed to re-rank holders after reindex");
+        }
+
+        Ok((erc20, nft))
+    }
+
+    /// Re-rank the contract's holders if the holder ranker has ranked it.
+    async fn rerank(&self, address: &str) -> Result<()> {
+        let ranked_block: Option<i64> = sqlx::query_scalar(
+            "SELECT ranked_block FROM erc20_holder_rank_state WHERE contract_address = $1",
+        )
+        .bind(address)
+        .fetch_optional(&self.pool)
+        .await?;
+        if let Some(ranked_block) = ranked_block {
+            holder_ranks::rank_token(&self.pool, address, ranked_block).await?;
+        }
+        Ok(())
+    }
+}
@@ -9,6 +9,7 @@ use tokio_postgres::{
 
 use super::batch::BlockBatch;
 
+#[tracing::instrument(name = "db.copy", skip_all, fields(db.table = "blocks"))]
 pub async fn copy_blocks(
     tx: &mut Transaction<'_>,
     batch: &BlockBatch,
@@ -28,7 +29,8 @@ pub async fn copy_blocks(
             gas_limit BIGINT,
             base_fee_per_gas TEXT,
             transaction_count INT,
-            indexed_at TIMESTAMPTZ
+            indexed_at TIMESTAMPTZ,
+            logs_bloom BYTEA
         ) ON COMMIT DELETE ROWS;
         TRUNCATE tmp_blocks;",
     )
@@ -36,7 +38,7 @@ pub async fn copy_blocks(
 
     let sink = tx
         .copy_in(
-            "COPY tmp_blocks (number, hash, parent_hash, timestamp, gas_used, gas_limit, base_fee_per_gas, transaction_count, indexed_at) FROM STDIN BINARY",
+            "COPY tmp_blocks (number, hash, parent_hash, timestamp, gas_used, gas_limit, base_fee_per_gas, transaction_count, indexed_at, logs_bloom) FROM STDIN BINARY",
         )
         .await?;
     let writer = BinaryCopyInWriter::new(
@@ -51,12 +53,13 @@ pub async fn copy_blocks(
             Type::TEXT,
             Type::INT4,
             Type::TIMESTAMPTZ,
+            Type::BYTEA,
         ],
     );
     pin!(writer);
 
     for i in 0..batch.b_numbers.len() {
-        let row: [&(dyn ToSql + Sync); 9] = [
+        let row: [&(dyn ToSql + Sync); 10] = [
             &batch.b_numbers[i],
             &batch.b_hashes[i],
             &batch.b_parent_hashes[i],
@@ -66,6 +69,7 @@ pub async fn copy_blocks(
             &batch.b_base_fee_per_gas[i],
             &batch.b_tx_counts[i],
             &indexed_at,
+            &batch.b_logs_blooms[i],
         ];
         writer.as_mut().write(&row).await?;
     }
@@ -73,8 +77,9 @@ pub async fn copy_blocks(
     writer.finish().await?;
 
     tx.execute(
-        "INSERT INTO blocks (number, hash, parent_hash, timestamp, gas_used, gas_limit, base_fee_per_gas, transaction_count, indexed_at)
-         SELECT number, hash, parent_hash, timestamp, gas_used, gas_limit, base_fee_per_gas::numeric, transaction_count, indexed_at
+        &format!(
+            "INSERT INTO {} (number, hash, parent_hash, timestamp, gas_used, gas_limit, base_fee_per_gas, transaction_count, indexed_at, logs_bloom)
+         SELECT number, hash, parent_hash, timestamp, gas_used, gas_limit, base_fee_per_gas::numeric, transaction_count, indexed_at, logs_bloom
          FROM tmp_blocks
          ON CONFLICT (number) DO UPDATE SET
             hash = EXCLUDED.hash,
@@ -84,7 +89,10 @@ pub async fn copy_blocks(
             gas_limit = EXCLUDED.gas_limit,
             base_fee_per_gas = EXCLUDED.base_fee_per_gas,
             transaction_count = EXCLUDED.transaction_count,
-            indexed_at = EXCLUDED.indexed_at",
+            indexed_at = EXCLUDED.indexed_at,
+            logs_bloom = EXCLUDED.logs_bloom",
+            batch.target("blocks")
+        ),
         &[],
     )
     .await?;
@@ -92,6 +100,7 @@ pub async fn copy_blocks(
     Ok(())
 }
 
+#[tracing::instrument(name = "db.copy", skip_all, fields(db.table = "transactions"))]
 pub async fn copy_transactions(tx: &mut Transaction<'_>, batch: &BlockBatch) -> Result<()> {
     if batch.t_hashes.is_empty() {
         return Ok(());
@@ -103,6 +112,7 @@ pub async fn copy_transactions(tx: &mut Transaction<'_>, batch: &BlockBatch) ->
             block_number BIGINT,
             block_index INT,
             from_address TEXT,
+            nonce BIGINT,
             to_address TEXT,
             value TEXT,
             gas_price TEXT,
@@ -110,7 +120,15 @@ pub async fn copy_transactions(tx: &mut Transaction<'_>, batch: &BlockBatch) ->
             input_data BYTEA,
             status BOOLEAN,
             contract_created TEXT,
-            timestamp BIGINT
+            timestamp BIGINT,
+            logs_bloom BYTEA,
+            l1_fee TEXT,
+            l1_gas_used BIGINT,
+            l1_gas_price TEXT,
+            l1_blob_base_fee TEXT,
+            l1_fee_scalar TEXT,
+            l1_base_fee_scalar BIGINT,
+            l1_blob_base_fee_scalar BIGINT
         ) ON COMMIT DELETE ROWS;
         TRUNCATE tmp_transactions;",
     )
@@ -118,7 +136,8 @@ pub async fn copy_transactions(tx: &mut Transaction<'_>, batch: &BlockBatch) ->
 
     let sink = tx
         .copy_in(
-            "COPY tmp_transactions (hash, block_number, block_index, from_address, to_address, value, gas_price, gas_used, input_data, status, contract_created, timestamp)
+            "COPY tmp_transactions (hash, block_number, block_index, from_address, nonce, to_address, value, gas_price, gas_used, input_data, status, contract_created, timestamp, logs_bloom,
+                                    l1_fee, l1_gas_used, l1_gas_price, l1_blob_base_fee, l1_fee_scalar, l1_base_fee_scalar, l1_blob_base_fee_scalar)
              FROM STDIN BINARY",
         )
         .await?;
@@ -129,6 +148,7 @@ pub async fn copy_transactions(tx: &mut Transaction<'_>, batch: &BlockBatch) ->
             Type::INT8,
             Type::INT4,
             Type::TEXT,
+            Type::INT8,
             Type::TEXT,
             Type::TEXT,
             Type::TEXT,
@@ -137,6 +157,14 @@ pub async fn copy_transactions(tx: &mut Transaction<'_>, batch: &BlockBatch) ->
             Type::BOOL,
             Type::TEXT,
             Type::INT8,
+            Type::BYTEA,
+            Type::TEXT,
+            Type::INT8,
+            Type::TEXT,
+            Type::TEXT,
+            Type::TEXT,
+            Type::INT8,
+            Type::INT8,
         ],
     );
     pin!(writer);
@@ -144,12 +172,21 @@ pub async fn copy_transactions(tx: &mut Transaction<'_>, batch: &BlockBatch) ->
     for i in 0..batch.t_hashes.len() {
         let to_addr = &batch.t_tos[i];
         let contract_created = &batch.t_contracts_created[i];
-
-        let row: [&(dyn ToSql + Sync); 12] = [
+        let l1 = batch.t_l1_fees[i].as_ref();
+        let l1_fee = l1.map(|f| f.fee.as_str());
+        let l1_gas_used = l1.and_then(|f| f.gas_used);
+        let l1_gas_price = l1.and_then(|f| f.gas_price.as_deref());
+        let l1_blob_base_fee = l1.and_then(|f| f.blob_base_fee.as_deref());
+        let l1_fee_scalar = l1.and_then(|f| f.fee_scalar.as_deref());
+        let l1_base_fee_scalar = l1.and_then(|f| f.base_fee_scalar);
+        let l1_blob_base_fee_scalar = l1.and_then(|f| f.blob_base_fee_scalar);
+
+        let row: [&(dyn ToSql + Sync); 21] = [
             &batch.t_hashes[i],
             &batch.t_block_numbers[i],
             &batch.t_block_indices[i],
             &batch.t_froms[i],
+            &batch.t_nonces[i],
             to_addr,
             &batch.t_values[i],
             &batch.t_gas_prices[i],
@@ -158,6 +195,14 @@ pub async fn copy_transactions(tx: &mut Transaction<'_>, batch: &BlockBatch) ->
             &batch.t_statuses[i],
             contract_created,
             &batch.t_timestamps[i],
+            &batch.t_logs_blooms[i],
+            &l1_fee,
+            &l1_gas_used,
+            &l1_gas_price,
+            &l1_blob_base_fee,
+            &l1_fee_scalar,
+            &l1_base_fee_scalar,
+            &l1_blob_base_fee_scalar,
         ];
         writer.as_mut().write(&row).await?;
     }
@@ -165,13 +210,31 @@ pub async fn copy_transactions(tx: &mut Transaction<'_>, batch: &BlockBatch) ->
     writer.finish().await?;
 
     tx.execute(
-        "INSERT INTO transactions
-            (hash, block_number, block_index, from_address, to_address,
-             value, gas_price, gas_used, input_data, status, contract_created, timestamp)
-         SELECT hash, block_number, block_index, from_address, to_address,
-                value::numeric, gas_price::numeric, gas_used, input_data, status, contract_created, timestamp
+        &format!(
+            "INSERT INTO {}
+            (hash, block_number, block_index, from_address, nonce, to_address,
+             value, gas_price, gas_used, input_data, status, contract_created, timestamp, logs_bloom,
+             l1_fee, l1_gas_used, l1_gas_price, l1_blob_base_fee, l1_fee_scalar, l1_base_fee_scalar, l1_blob_base_fee_scalar)
+         SELECT hash, block_number, block_index, from_address, nonce, to_address,
+                value::numeric, gas_price::numeric, gas_used, input_data, status, contract_created, timestamp,
+                logs_bloom, l1_fee::numeric, l1_gas_used, l1_gas_price::numeric, l1_blob_base_fee::numeric,
+                l1_fee_scalar, l1_base_fee_scalar, l1_blob_base_fee_scalar
          FROM tmp_transactions
-         ON CONFLICT (hash, block_number) DO NOTHING",
+         ON CONFLICT (hash, block_number) DO UPDATE SET
+            nonce = COALESCE(transactions.nonce, EXCLUDED.nonce),
+            logs_bloom = COALESCE(transactions.logs_bloom, EXCLUDED.logs_bloom),
+            l1_fee = COALESCE(transactions.l1_fee, EXCLUDED.l1_fee),
+            l1_gas_used = COALESCE(transactions.l1_gas_used, EXCLUDED.l1_gas_used),
+            l1_gas_price = COALESCE(transactions.l1_gas_price, EXCLUDED.l1_gas_price),
+            l1_blob_base_fee = COALESCE(transactions.l1_blob_base_fee, EXCLUDED.l1_blob_base_fee),
+            l1_fee_scalar = COALESCE(transactions.l1_fee_scalar, EXCLUDED.l1_fee_scalar),
+            l1_base_fee_scalar = COALESCE(transactions.l1_base_fee_scalar, EXCLUDED.l1_base_fee_scalar),
+            l1_blob_base_fee_scalar = COALESCE(transactions.l1_blob_base_fee_scalar, EXCLUDED.l1_blob_base_fee_scalar)
+         WHERE (transactions.logs_bloom IS NULL AND EXCLUDED.logs_bloom IS NOT NULL)
+            OR (transactions.l1_fee IS NULL AND EXCLUDED.l1_fee IS NOT NULL)
+            OR (transactions.nonce IS NULL AND EXCLUDED.nonce IS NOT NULL)",
+            batch.target("transactions")
+        ),
         &[],
     )
     .await?;
@@ -179,6 +242,7 @@ pub async fn copy_transactions(tx: &mut Transaction<'_>, batch: &BlockBatch) ->
     Ok(())
 }
 
+#[tracing::instrument(name = "db.copy", skip_all, fields(db.table = "event_logs"))]
 pub async fn copy_event_logs(tx: &mut Transaction<'_>, batch: &BlockBatch) -> Result<()> {
     if batch.el_tx_hashes.is_empty() {
         return Ok(());
@@ -186,13 +250,13 @@ pub async fn copy_event_logs(tx: &mut Transaction<'_>, batch: &BlockBatch) -> Re
 
     tx.batch_execute(
         "CREATE TEMP TABLE IF NOT EXISTS tmp_event_logs (
-            tx_hash TEXT,
+            tx_hash BYTEA,
             log_index INT,
-            address TEXT,
-            topic0 TEXT,
-            topic1 TEXT,
-            topic2 TEXT,
-            topic3 TEXT,
+            address BYTEA,
+            topic0 BYTEA,
+            topic1 BYTEA,
+            topic2 BYTEA,
+            topic3 BYTEA,
             data BYTEA,
             block_number BIGINT
         ) ON COMMIT DELETE ROWS;
@@ -209,13 +273,13 @@ pub async fn copy_event_logs(tx: &mut Transaction<'_>, batch: &BlockBatch) -> Re
     let writer = BinaryCopyInWriter::new(
         sink,
         &[
-            Type::TEXT,
+            Type::BYTEA,
             Type::INT4,
-            Type::TEXT,
-            Type::TEXT,
-            Type::TEXT,
-            Type::TEXT,
-            Type::TEXT,
+            Type::BYTEA,
+            Type::BYTEA,
+            Type::BYTEA,
+            Type::BYTEA,
+            Type::BYTEA,
             Type::BYTEA,
             Type::INT8,
         ],
@@ -240,11 +304,14 @@ pub async fn copy_event_logs(tx: &mut Transaction<'_>, batch: &BlockBatch) -> Re
     writer.finish().await?;
 
     tx.execute(
-        "INSERT INTO event_logs
+        &format!(
+            "INSERT INTO {}
             (tx_hash, log_index, address, topic0, topic1, topic2, topic3, data, block_number)
          SELECT tx_hash, log_index, address, topic0, topic1, topic2, topic3, data, block_number
          FROM tmp_event_logs
          ON CONFLICT (tx_hash, log_index, block_number) DO NOTHING",
+            batch.target("event_logs")
+        ),
         &[],
     )
     .await?;
@@ -252,6 +319,7 @@ pub async fn copy_event_logs(tx: &mut Transaction<'_>, batch: &BlockBatch) -> Re
     Ok(())
 }
 
+#[tracing::instrument(name = "db.copy", skip_all, fields(db.table = "nft_transfers"))]
 pub async fn copy_nft_transfers(tx: &mut Transaction<'_>, batch: &BlockBatch) -> Result<()> {
     if batch.nt_tx_hashes.is_empty() {
         return Ok(());
@@ -310,11 +378,14 @@ pub async fn copy_nft_transfers(tx: &mut Transaction<'_>, batch: &BlockBatch) ->
     writer.finish().await?;
 
     tx.execute(
-        "INSERT INTO nft_transfers
+        &format!(
+            "INSERT INTO {}
             (tx_hash, log_index, contract_address, token_id, from_address, to_address, block_number, timestamp)
          SELECT tx_hash, log_index, contract_address, token_id::numeric, from_address, to_address, block_number, timestamp
          FROM tmp_nft_transfers
          ON CONFLICT (tx_hash, log_index, block_number) DO NOTHING",
+            batch.target("nft_transfers")
+        ),
         &[],
     )
     .await?;
@@ -322,6 +393,7 @@ pub async fn copy_nft_transfers(tx: &mut Transaction<'_>, batch: &BlockBatch) ->
     Ok(())
 }
 
+#[tracing::instrument(name = "db.copy", skip_all, fields(db.table = "erc20_transfers"))]
 pub async fn copy_erc20_transfers(tx: &mut Transaction<'_>, batch: &BlockBatch) -> Result<()> {
     if batch.et_tx_hashes.is_empty() {
         return Ok(());
@@ -380,11 +452,62 @@ pub async fn copy_erc20_transfers(tx: &mut Transaction<'_>, batch: &BlockBatch)
     writer.finish().await?;
 
     tx.execute(
-        "INSERT INTO erc20_transfers
+        &format!(
+            "INSERT INTO {}
             (tx_hash, log_index, contract_address, from_address, to_address, value, block_number, timestamp)
          SELECT tx_hash, log_index, contract_address, from_address, to_address, value::numeric, block_number, timestamp
          FROM tmp_erc20_transfers
          ON CONFLICT (tx_hash, log_index, block_number) DO NOTHING",
+            batch.target("erc20_transfers")
+        ),
+        &[],
+    )
+    .await?;
+
+    Ok(())
+}
+
+#[tracing::instrument(name = "db.copy", skip_all, fields(db.table = "raw_block_archive"))]
+pub async fn copy_raw_archive(tx: &mut Transaction<'_>, batch: &BlockBatch) -> Result<()> {
+    if batch.ra_block_numbers.is_empty() {
+        return Ok(());
+    }
+
+    tx.batch_execute(
+        "CREATE TEMP TABLE IF NOT EXISTS tmp_raw_block_archive (
+            block_number BIGINT,
+            block_hash TEXT,
+            payload BYTEA
+        ) ON COMMIT DELETE ROWS;
+        TRUNCATE tmp_raw_block_archive;",
+    )
+    .await?;
+
+    let sink = tx
+        .copy_in("COPY tmp_raw_block_archive (block_number, block_hash, payload) FROM STDIN BINARY")
+        .await?;
+    let writer = BinaryCopyInWriter::new(sink, &[Type::INT8, Type::TEXT, Type::BYTEA]);
+    pin!(writer);
+
+    for i in 0..batch.ra_block_numbers.len() {
+        let row: [&(dyn ToSql + Sync); 3] = [
+            &batch.ra_block_numbers[i],
+            &batch.ra_block_hashes[i],
+            &batch.ra_payloads[i],
+        ];
+        writer.as_mut().write(&row).await?;
+    }
+
+    writer.finish().await?;
+
+    tx.execute(
+        "INSERT INTO raw_block_archive (block_number, block_hash, payload)
+         SELECT block_number, block_hash, payload
+         FROM tmp_raw_block_archive
+         ON CONFLICT (block_number) DO UPDATE SET
+            block_hash = EXCLUDED.block_hash,
+            payload = EXCLUDED.payload,
+            archived_at = NOW()",
         &[],
     )
     .await?;
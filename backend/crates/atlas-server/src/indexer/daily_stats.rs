@@ -0,0 +1,199 @@
+//! Scheduled aggregation of per-day chain summaries into `daily_stats`.
+//!
+//! ## Design
+//!
+//! Days are UTC calendar days of block timestamps. Each day's row is computed
+//! from the block range the day covers, so every query below uses the
+//! `block_number` indexes instead of scanning by timestamp:
+//!
+//! - `tx_count`, `new_contracts` (top-level creations), `avg_gas_price`
+//!   (rounded, zero-priced transactions excluded), `active_addresses`
+//!   (distinct senders and recipients), `execution_fees` (gas used times
+//!   the effective gas price) and `l1_fees` from `transactions`;
+//! - `block_count`, `gas_used` and `base_fees` (gas used times the base fee)
+//!   from `blocks`;
+//! - `new_tokens` from ERC-20 and NFT contracts first seen that day;
+//! - `nft_mints` from NFT transfers out of the zero address.
+//!
+//! A cursor in `indexer_state` holds the first day that may still change.
+//! Every cycle recomputes the days from the cursor through the day of the
+//! latest indexed block, then moves the cursor to that latest day, which
+//! stays open until a later block closes it. A reindex truncates both.
+
+use anyhow::{bail, Result};
+use chrono::{DateTime, Days, NaiveDate, NaiveTime};
+use sqlx::PgPool;
+use std::time::Duration;
+
+use crate::state_keys::DAILY_STATS_NEXT_DAY_KEY;
+
+const AGGREGATE_DAY_SQL: &str = r#"
+    WITH range AS (
+        SELECT MIN(number) AS lo, MAX(number) AS hi,
+               COUNT(*) AS block_count, COALESCE(SUM(gas_used), 0) AS gas_used,
+               COALESCE(SUM(gas_used * base_fee_per_gas), 0) AS base_fees
+        FROM blocks
+        WHERE timestamp >= $2 AND timestamp < $2 + 86400
+    ),
+    txs AS (
+        SELECT COUNT(*) AS tx_count,
+               COUNT(t.contract_created) AS new_contracts,
+               ROUND(AVG(t.gas_price) FILTER (WHERE t.gas_price > 0)) AS avg_gas_price,
+               COALESCE(SUM(t.gas_used * t.gas_price), 0) AS execution_fees,
+               COALESCE(SUM(t.l1_fee), 0) AS l1_fees
+        FROM range r
+        JOIN transactions t ON t.block_number BETWEEN r.lo AND r.hi
+    ),
+    active AS (
+        SELECT COUNT(DISTINCT a.address) AS active_addresses
+        FROM range r
+        JOIN transactions t ON t.block_number BETWEEN r.lo AND r.hi
+        CROSS JOIN LATERAL (VALUES (t.from_address), (t.to_address)) AS a(address)
+        WHERE a.address IS NOT NULL
+    ),
+    tokens AS (
+        SELECT (SELECT COUNT(*) FROM erc20_contracts, range r
+                WHERE first_seen_block BETWEEN r.lo AND r.hi)
+             + (SELECT COUNT(*) FROM nft_contracts, range r
+                WHERE first_seen_block BETWEEN r.lo AND r.hi) AS new_tokens
+    ),
+    mints AS (
+        SELECT COUNT(*) AS nft_mints
+        FROM range r
+        JOIN nft_transfers n ON n.block_number BETWEEN r.lo AND r.hi
+        WHERE n.from_address = '0x0000000000000000000000000000000000000000'
+    )
+    INSERT INTO daily_stats
+        (day, block_count, tx_count, active_addresses, new_contracts,
+         gas_used, avg_gas_price, new_tokens, nft_mints,
+         execution_fees, base_fees, l1_fees, updated_at)
+    SELECT $1, range.block_count, txs.tx_count, active.active_addresses, txs.new_contracts,
+           range.gas_used, txs.avg_gas_price, tokens.new_tokens, mints.nft_mints,
+           txs.execution_fees, range.base_fees, txs.l1_fees, NOW()
+    FROM range, txs, active, tokens, mints
+    ON CONFLICT (day) DO UPDATE SET
+        block_count = EXCLUDED.block_count,
+        tx_count = EXCLUDED.tx_count,
+        active_addresses = EXCLUDED.active_addresses,
+        new_contracts = EXCLUDED.new_contracts,
+        gas_used = EXCLUDED.gas_used,
+        avg_gas_price = EXCLUDED.avg_gas_price,
+        new_tokens = EXCLUDED.new_tokens,
+        nft_mints = EXCLUDED.nft_mints,
+        execution_fees = EXCLUDED.execution_fees,
+        base_fees = EXCLUDED.base_fees,
+        l1_fees = EXCLUDED.l1_fees,
+        updated_at = EXCLUDED.updated_at
+"#;
+
+pub struct DailyStatsAggregator {
+    pool: PgPool,
+    interval: Duration,
+}
+
+impl DailyStatsAggregator {
+    pub fn new(pool: PgPool, interval: Duration) -> Result<Self> {
+        if interval.is_zero() {
+            bail!("daily stats interval must be greater than 0");
+        }
+        Ok(Self { pool, interval })
+    }
+
+    pub async fn run(&self) -> Result<()> {
+        tracing::info!(
+            interval_secs = self.interval.as_secs(),
+            "Daily stats aggregation started"
+        );
+        loop {
+            let days = self.run_cycle().await?;
+            tracing::debug!(days, "daily stats cycle complete");
+            tokio::time::sleep(self.interval).await;
+        }
+    }
+
+    /// Recompute every open day and return how many were written.
+    pub async fn run_cycle(&self) -> Result<u64> {
+        let (first_ts, last_ts): (Option<i64>, Option<i64>) =
+            sqlx::query_as("SELECT MIN(timestamp), MAX(timestamp) FROM blocks")
+                .fetch_one(&self.pool)
+                .await?;
+        let (Some(first_ts), Some(last_ts)) = (first_ts, last_ts) else {
+            return Ok(0);
+        };
+
+        let last_day = day_of(last_ts)?;
+        let mut day = match self.cursor().await? {
+            Some(day) => day,
+            None => day_of(first_ts)?,
+        };
+
+        let mut written = 0;
+        while day <= last_day {
+            aggregate_day(&self.pool, day).await?;
+            written += 1;
+            day = day + Days::new(1);
+        }
+
+        sqlx::query(
+            "INSERT INTO indexer_state (key, value, updated_at)
+             VALUES ($1, $2, NOW())
+             ON CONFLICT (key) DO UPDATE SET value = EXCLUDED.value, updated_at = EXCLUDED.updated_at",
+        )
+        .bind(DAILY_STATS_NEXT_DAY_KEY)
+        .bind(last_day.to_string())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(written)
+    }
+
+    async fn cursor(&self) -> Result<Option<NaiveDate>> {
+        let row: Option<(String,)> =
+            sqlx::query_as("SELECT value FROM indexer_state WHERE key = $1")
+                .bind(DAILY_STATS_NEXT_DAY_KEY)
+                .fetch_optional(&self.pool)
+                .await?;
+        row.map(|(value,)| value.parse::<NaiveDate>().map_err(Into::into))
+            .transpose()
+    }
+}
+
+/// Compute (or recompute) the `daily_stats` row of one UTC day.
+pub async fn aggregate_day(pool: &PgPool, day: NaiveDate) -> Result<()> {
+    sqlx::query(AGGREGATE_DAY_SQL)
+        .bind(day)
+        .bind(day_start(day))
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+/// UTC day of a block timestamp.
+pub(crate) fn day_of(timestamp: i64) -> Result<NaiveDate> {
+    match DateTime::from_timestamp(timestamp, 0) {
+        Some(t) => Ok(t.date_naive()),
+        None => bail!("block timestamp {timestamp} out of range"),
+    }
+}
+
+/// Unix timestamp of a day's midnight UTC.
+pub(crate) fn day_start(day: NaiveDate) -> i64 {
+    day.and_time(NaiveTime::MIN).and_utc().timestamp()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn days_are_utc_calendar_days() {
+        let day = NaiveDate::from_ymd_opt(2030, 1, 1).unwrap();
+        assert_eq!(day_start(day), 1_893_456_000);
+        assert_eq!(day_of(1_893_456_000).unwrap(), day);
+        assert_eq!(day_of(1_893_456_000 + 86_399).unwrap(), day);
+        assert_eq!(
+            day_of(1_893_456_000 - 1).unwrap(),
+            NaiveDate::from_ymd_opt(2029, 12, 31).unwrap()
+        );
+    }
+}
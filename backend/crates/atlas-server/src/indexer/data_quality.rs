@@ -0,0 +1,245 @@
+//! Scheduled data-quality checks recorded in `data_quality_issues`.
+//!
+//! ## Design
+//!
+//! ERC-20 balances are kept as running sums of transfer deltas, so a missed
+//! mint or a non-standard token (rebasing, fee-on-transfer) shows up as a
+//! balance that no transfer history explains. Each cycle looks for:
+//!
+//! - `negative_balance`: a balance below zero, over the whole table (a
+//!   partial index keeps this cheap);
+//! - `missing_receipt`: blocks with transactions stored without a receipt
+//!   (no gas used and no logs bloom), which also means their logs are missing;
+//! - `transfer_balance_mismatch`: a balance that differs from its indexed
+//!   incoming minus outgoing transfers, for pairs with transfers in new blocks.
+//!   Transfers folded into `erc20_transfer_summaries` count too. Skipped
+//!   while old transfers live in cold storage, since the hot table no longer
+//!   holds the full history.
+//!
+//! A cursor in `indexer_state` holds the last block checked; new blocks are
+//! checked in chunks of [`CHUNK_BLOCKS`]. Every cycle also re-checks the open
+//! issues and marks the ones that no longer hold as resolved. A reindex
+//! truncates the table and the cursor.
+
+use anyhow::{bail, Result};
+use sqlx::PgPool;
+use std::time::Duration;
+
+use crate::state_keys::DATA_QUALITY_LAST_BLOCK_KEY;
+
+/// Blocks checked per statement when catching up.
+const CHUNK_BLOCKS: i64 = 10_000;
+
+/// Issue kinds, as stored in `data_quality_issues.kind`.
+pub const ISSUE_KINDS: [&str; 3] = [
+    "negative_balance",
+    "missing_receipt",
+    "transfer_balance_mismatch",
+];
+
+const UPSERT_CONFLICT: &str = "
+    ON CONFLICT (kind, subject) DO UPDATE SET
+        block_number = EXCLUDED.block_number,
+        details = EXCLUDED.details,
+        last_seen_at = NOW(),
+        resolved_at = NULL";
+
+const NEGATIVE_BALANCES_SQL: &str = "
+    INSERT INTO data_quality_issues (kind, subject, block_number, address, contract_address, details)
+    SELECT 'negative_balance', address || '/' || contract_address, last_updated_block,
+           address, contract_address, jsonb_build_object('balance', balance::text)
+    FROM erc20_balances
+    WHERE balance < 0";
+
+const RESOLVE_NEGATIVE_BALANCES_SQL: &str = "
+    UPDATE data_quality_issues i SET resolved_at = NOW()
+    WHERE i.kind = 'negative_balance' AND i.resolved_at IS NULL
+      AND NOT EXISTS (
+          SELECT 1 FROM erc20_balances b
+          WHERE b.address = i.address AND b.contract_address = i.contract_address
+            AND b.balance < 0
+      )";
+
+const MISSING_RECEIPTS_SQL: &str = "
+    INSERT INTO data_quality_issues (kind, subject, block_number, details)
+    SELECT 'missing_receipt', block_number::text, block_number,
+           jsonb_build_object('transactions', COUNT(*))
+    FROM transactions
+    WHERE block_number > $1 AND block_number <= $2
+      AND gas_used = 0 AND logs_bloom IS NULL
+    GROUP BY block_number";
+
+const RESOLVE_MISSING_RECEIPTS_SQL: &str = "
+    UPDATE data_quality_issues i SET resolved_at = NOW()
+    WHERE i.kind = 'missing_receipt' AND i.resolved_at IS NULL
+      AND NOT EXISTS (
+          SELECT 1 FROM transactions t
+          WHERE t.block_number = i.block_number AND t.gas_used = 0 AND t.logs_bloom IS NULL
+      )";
+
+/// Net indexed transfers of `p.address` in `p.contract_address`, raw and
+/// aggregated.
+const EXPECTED_BALANCE: &str = "
+    COALESCE((SELECT SUM(value) FROM erc20_transfers t
+              WHERE t.contract_address = p.contract_address AND t.to_address = p.address), 0)
+  + COALESCE((SELECT SUM(value) FROM erc20_transfer_summaries s
+              WHERE s.contract_address = p.contract_address AND s.to_address = p.address), 0)
+  - COALESCE((SELECT SUM(value) FROM erc20_transfers t
+              WHERE t.contract_address = p.contract_address AND t.from_address = p.address), 0)
+  - COALESCE((SELECT SUM(value) FROM erc20_transfer_summaries s
+              WHERE s.contract_address = p.contract_address AND s.from_address = p.address), 0)";
+
+const ZERO_ADDRESS: &str = "0x0000000000000000000000000000000000000000";
+
+pub struct DataQualityChecker {
+    pool: PgPool,
+    interval: Duration,
+    check_transfer_sums: bool,
+}
+
+impl DataQualityChecker {
+    /// `check_transfer_sums` must be false when old transfers have been moved
+    /// to cold storage.
+    pub fn new(pool: PgPool, interval: Duration, check_transfer_sums: bool) -> Result<Self> {
+        if interval.is_zero() {
+            bail!("data quality interval must be greater than 0");
+        }
+        Ok(Self {
+            pool,
+            interval,
+            check_transfer_sums,
+        })
+    }
+
+    pub async fn run(&self) -> Result<()> {
+        tracing::info!(
+            interval_secs = self.interval.as_secs(),
+            transfer_sums = self.check_transfer_sums,
+            "Data quality checks started"
+        );
+        loop {
+            let open = self.run_cycle().await?;
+            tracing::debug!(open, "data quality cycle complete");
+            tokio::time::sleep(self.interval).await;
+        }
+    }
+
+    /// Check new blocks, re-check open issues and return how many are open.
+    pub async fn run_cycle(&self) -> Result<i64> {
+        let (head,): (Option<i64>,) = sqlx::query_as("SELECT MAX(number) FROM blocks")
+            .fetch_one(&self.pool)
+            .await?;
+
+        if let Some(head) = head {
+            let mut cursor = self.cursor().await?.unwrap_or(-1);
+            while cursor < head {
+                let to = head.min(cursor + CHUNK_BLOCKS);
+                self.check_blocks(cursor, to).await?;
+                self.set_cursor(to).await?;
+                cursor = to;
+            }
+        }
+
+        sqlx::query(&format!("{NEGATIVE_BALANCES_SQL} {UPSERT_CONFLICT}"))
+            .execute(&self.pool)
+            .await?;
+        sqlx::query(RESOLVE_NEGATIVE_BALANCES_SQL)
+            .execute(&self.pool)
+            .await?;
+        sqlx::query(RESOLVE_MISSING_RECEIPTS_SQL)
+            .execute(&self.pool)
+            .await?;
+        if self.check_transfer_sums {
+            sqlx::query(&format!(
+                "UPDATE data_quality_issues p SET resolved_at = NOW()
+                 WHERE p.kind = 'transfer_balance_mismatch' AND p.resolved_at IS NULL
+                   AND COALESCE((SELECT balance FROM erc20_balances b
+                                 WHERE b.address = p.address
+                                   AND b.contract_address = p.contract_address), 0)
+                       = {EXPECTED_BALANCE}"
+            ))
+            .execute(&self.pool)
+            .await?;
+        }
+
+        let counts: Vec<(String, i64)> = sqlx::query_as(
+            "SELECT kind, COUNT(*) FROM data_quality_issues
+             WHERE resolved_at IS NULL GROUP BY kind",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        for kind in ISSUE_KINDS {
+            let open = counts
+                .iter()
+                .find(|(k, _)| k == kind)
+                .map_or(0, |(_, count)| *count);
+            metrics::gauge!("atlas_data_quality_open_issues", "kind" => kind).set(open as f64);
+        }
+        Ok(counts.iter().map(|(_, count)| count).sum())
+    }
+
+    /// Record findings for blocks in `(from, to]`.
+    async fn check_blocks(&self, from: i64, to: i64) -> Result<()> {
+        sqlx::query(&format!("{MISSING_RECEIPTS_SQL} {UPSERT_CONFLICT}"))
+            .bind(from)
+            .bind(to)
+            .execute(&self.pool)
+            .await?;
+
+        if self.check_transfer_sums {
+            sqlx::query(&format!(
+                "WITH touched AS (
+                     SELECT DISTINCT t.contract_address, a.address
+                     FROM erc20_transfers t
+                     CROSS JOIN LATERAL (VALUES (t.from_address), (t.to_address)) AS a(address)
+                     WHERE t.block_number > $1 AND t.block_number <= $2 AND a.address <> $3
+                 ),
+                 compared AS (
+                     SELECT p.contract_address, p.address,
+                            COALESCE(b.balance, 0) AS balance, b.last_updated_block,
+                            {EXPECTED_BALANCE} AS expected
+                     FROM touched p
+                     LEFT JOIN erc20_balances b
+                       ON b.address = p.address AND b.contract_address = p.contract_address
+                 )
+                 INSERT INTO data_quality_issues
+                     (kind, subject, block_number, address, contract_address, details)
+                 SELECT 'transfer_balance_mismatch', address || '/' || contract_address,
+                        last_updated_block, address, contract_address,
+                        jsonb_build_object('balance', balance::text, 'expected', expected::text)
+                 FROM compared
+                 WHERE balance <> expected
+                 {UPSERT_CONFLICT}"
+            ))
+            .bind(from)
+            .bind(to)
+            .bind(ZERO_ADDRESS)
+            .execute(&self.pool)
+            .await?;
+        }
+        Ok(())
+    }
+
+    async fn cursor(&self) -> Result<Option<i64>> {
+        let row: Option<(String,)> =
+            sqlx::query_as("SELECT value FROM indexer_state WHERE key = $1")
+                .bind(DATA_QUALITY_LAST_BLOCK_KEY)
+                .fetch_optional(&self.pool)
+                .await?;
+        row.map(|(value,)| value.parse::<i64>().map_err(Into::into))
+            .transpose()
+    }
+
+    async fn set_cursor(&self, block: i64) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO indexer_state (key, value, updated_at)
+             VALUES ($1, $2, NOW())
+             ON CONFLICT (key) DO UPDATE SET value = EXCLUDED.value, updated_at = EXCLUDED.updated_at",
+        )
+        .bind(DATA_QUALITY_LAST_BLOCK_KEY)
+        .bind(block.to_string())
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+}
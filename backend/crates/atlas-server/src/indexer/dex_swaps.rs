@@ -0,0 +1,392 @@
+//! Uniswap V2 and V3 style swaps, decoded into `dex_swaps`.
+//!
+//! ## Design
+//!
+//! [`DexSwapProcessor`] is the built-in `dex_swaps` block processor. It
+//! decodes the `Swap` events of V2 pairs and V3 pools, and of the many forks
+//! that emit the same events, into one row per swap. Amounts are signed
+//! changes of the pool's balances: positive for the token paid in, negative
+//! for the token paid out. Each block's swaps are replaced as a whole, so a
+//! reorged block leaves no stale rows.
+//!
+//! Swap events do not name the pool's tokens. New pools are recorded in
+//! `dex_pools`, and [`DexPoolResolver`] reads their `token0()` and
+//! `token1()` in the background. A contract that only imitates the event
+//! has no tokens and stays out of the pair statistics.
+
+use alloy::primitives::{b256, B256, I256, U256};
+use anyhow::{bail, Context, Result};
+use sqlx::PgPool;
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use super::fetcher::FetchedBlock;
+use super::processors::{BlockProcessor, ProcessorWrites};
+use atlas_common::encoding::{address_to_hex, b256_to_hex, topic_to_address_hex};
+
+/// Name the processor is registered and enabled under.
+pub const DEX_SWAPS_PROCESSOR: &str = "dex_swaps";
+
+/// `Swap(address,uint256,uint256,uint256,uint256,address)`
+pub(crate) const UNISWAP_V2_SWAP_TOPIC: B256 =
+    b256!("d78ad95fa46c994b6551d0da85fc275fe613ce37657fb8d5e3d130840159d822");
+/// `Swap(address,address,int256,int256,uint160,uint128,int24)`
+pub(crate) const UNISWAP_V3_SWAP_TOPIC: B256 =
+    b256!("c42079f94a6350d7e6235f29174924f928cc2ac818eb64fed8004e115fbcca67");
+
+/// Pools resolved per step.
+const POOLS_PER_STEP: i64 = 100;
+
+/// `token0()` and `token1()` selectors.
+const TOKEN0_CALL: &str = "0x0dfe1681";
+const TOKEN1_CALL: &str = "0xd21220a7";
+
+const DELETE_SWAPS_SQL: &str = "DELETE FROM dex_swaps WHERE block_number = $1";
+
+const INSERT_POOLS_SQL: &str = "
+    INSERT INTO dex_pools (address, protocol, first_swap_block)
+    SELECT * FROM unnest($1::text[], $2::text[], $3::bigint[])
+    ON CONFLICT (address) DO UPDATE
+        SET first_swap_block = LEAST(dex_pools.first_swap_block, EXCLUDED.first_swap_block)
+        WHERE EXCLUDED.first_swap_block < dex_pools.first_swap_block";
+
+const INSERT_SWAPS_SQL: &str = "
+    INSERT INTO dex_swaps (block_number, log_index, tx_hash, pool, protocol,
+                           sender, recipient, amount0, amount1, timestamp)
+    SELECT $1::bigint, log_index, tx_hash, pool, protocol, sender, recipient,
+           amount0::numeric, amount1::numeric, $2::bigint
+    FROM unnest($3::int[], $4::text[], $5::text[], $6::text[], $7::text[], $8::text[],
+                $9::text[], $10::text[])
+        AS t(log_index, tx_hash, pool, protocol, sender, recipient, amount0, amount1)";
+
+/// A decoded swap.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Swap {
+    pub(crate) log_index: i32,
+    pub(crate) tx_hash: String,
+    pub(crate) pool: String,
+    pub(crate) protocol: &'static str,
+    pub(crate) sender: String,
+    pub(crate) recipient: String,
+    pub(crate) amount0: I256,
+    pub(crate) amount1: I256,
+}
+
+/// Decode a log into a swap, if it is a V2 or V3 `Swap` event.
+pub(crate) fn decode_swap(log: &alloy::rpc::types::Log) -> Option<Swap> {
+    let topics = log.topics();
+    let data = &log.data().data;
+    if topics.len() != 3 {
+        return None;
+    }
+    let word = |i: usize| U256::from_be_slice(&data[i * 32..(i + 1) * 32]);
+
+    let (protocol, amount0, amount1) = if topics[0] == UNISWAP_V2_SWAP_TOPIC && data.len() == 128 {
+        // amount0In, amount1In, amount0Out, amount1Out
+        let delta = |paid_in: U256, paid_out: U256| {
+            Some(I256::try_from(paid_in).ok()? - I256::try_from(paid_out).ok()?)
+        };
+        (
+            "uniswap_v2",
+            delta(word(0), word(2))?,
+            delta(word(1), word(3))?,
+        )
+    } else if topics[0] == UNISWAP_V3_SWAP_TOPIC && data.len() == 160 {
+        // amount0, amount1, sqrtPriceX96, liquidity, tick
+        (
+            "uniswap_v3",
+            I256::from_raw(word(0)),
+            I256::from_raw(word(1)),
+        )
+    } else {
+        return None;
+    };
+
+    Some(Swap {
+        log_index: log.log_index.unwrap_or(0) as i32,
+        tx_hash: log
+            .transaction_hash
+            .as_ref()
+            .map(b256_to_hex)
+            .unwrap_or_default(),
+        pool: address_to_hex(&log.address()),
+        protocol,
+        sender: topic_to_address_hex(&topics[1]),
+        // V2 indexes `to` last, V3 `recipient` second; both are topic 2
+        recipient: topic_to_address_hex(&topics[2]),
+        amount0,
+        amount1,
+    })
+}
+
+/// The `dex_swaps` block processor.
+pub struct DexSwapProcessor;
+
+impl BlockProcessor for DexSwapProcessor {
+    fn process(&self, block: &FetchedBlock, writes: &mut ProcessorWrites) {
+        let number = block.number as i64;
+        writes.execute(DELETE_SWAPS_SQL, vec![Box::new(number)]);
+
+        let swaps: Vec<Swap> = block
+            .receipts
+            .iter()
+            .flat_map(|receipt| receipt.inner.logs())
+            .filter_map(decode_swap)
+            .collect();
+        if swaps.is_empty() {
+            return;
+        }
+
+        let pools: BTreeMap<&str, &str> = swaps
+            .iter()
+            .map(|swap| (swap.pool.as_str(), swap.protocol))
+            .collect();
+        writes.execute(
+            INSERT_POOLS_SQL,
+            vec![
+                Box::new(pools.keys().map(|p| p.to_string()).collect::<Vec<_>>()),
+                Box::new(pools.values().map(|p| p.to_string()).collect::<Vec<_>>()),
+                Box::new(vec![number; pools.len()]),
+            ],
+        );
+
+        let timestamp = block.block.header.timestamp as i64;
+        let column = |f: fn(&Swap) -> String| swaps.iter().map(f).collect::<Vec<_>>();
+        writes.execute(
+            INSERT_SWAPS_SQL,
+            vec![
+                Box::new(number),
+                Box::new(timestamp),
+                Box::new(swaps.iter().map(|s| s.log_index).collect::<Vec<_>>()),
+                Box::new(column(|s| s.tx_hash.clone())),
+                Box::new(column(|s| s.pool.clone())),
+                Box::new(column(|s| s.protocol.to_string())),
+                Box::new(column(|s| s.sender.clone())),
+                Box::new(column(|s| s.recipient.clone())),
+                Box::new(column(|s| s.amount0.to_string())),
+                Box::new(column(|s| s.amount1.to_string())),
+            ],
+        );
+    }
+}
+
+/// Reads the tokens of pools seen in swaps.
+pub struct DexPoolResolver {
+    pool: PgPool,
+    rpc_url: String,
+    client: reqwest::Client,
+    interval: Duration,
+}
+
+impl DexPoolResolver {
+    pub fn new(pool: PgPool, rpc_url: &str, interval: Duration) -> Result<Self> {
+        if interval.is_zero() {
+            bail!("dex pool interval must be greater than 0");
+        }
+        Ok(Self {
+            pool,
+            rpc_url: rpc_url.to_string(),
+            client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(30))
+                .build()?,
+            interval,
+        })
+    }
+
+    pub async fn run(&self) -> Result<()> {
+        tracing::info!(
+            interval_secs = self.interval.as_secs(),
+            "DEX pool resolver started"
+        );
+        loop {
+            let resolved = self.resolve_pools().await?;
+            if resolved > 0 {
+                tracing::info!(resolved, "resolved DEX pool tokens");
+            }
+            tokio::time::sleep(self.interval).await;
+        }
+    }
+
+    /// Read the tokens of every unresolved pool. Returns how many pools were
+    /// checked.
+    pub async fn resolve_pools(&self) -> Result<u64> {
+        let mut resolved = 0;
+        loop {
+            let pools: Vec<(String,)> = sqlx::query_as(
+                "SELECT address FROM dex_pools WHERE resolved_at IS NULL
+                 ORDER BY first_swap_block, address
+                 LIMIT $1",
+            )
+            .bind(POOLS_PER_STEP)
+            .fetch_all(&self.pool)
+            .await?;
+            if pools.is_empty() {
+                return Ok(resolved);
+            }
+
+            let addresses: Vec<&str> = pools.iter().map(|(address,)| address.as_str()).collect();
+            let tokens = self.fetch_tokens(&addresses).await?;
+            for (address, (token0, token1)) in addresses.iter().zip(tokens) {
+                sqlx::query(
+                    "UPDATE dex_pools SET token0 = $2, token1 = $3, resolved_at = NOW()
+                     WHERE address = $1",
+                )
+                .bind(address)
+                .bind(token0)
+                .bind(token1)
+                .execute(&self.pool)
+                .await?;
+            }
+            resolved += addresses.len() as u64;
+        }
+    }
+
+    /// `token0()` and `token1()` of each pool, `None` when a call fails or
+    /// does not return an address.
+    async fn fetch_tokens(&self, pools: &[&str]) -> Result<Vec<(Option<String>, Option<String>)>> {
+        let batch: Vec<serde_json::Value> = pools
+            .iter()
+            .flat_map(|pool| [(pool, TOKEN0_CALL), (pool, TOKEN1_CALL)])
+            .enumerate()
+            .map(|(id, (pool, data))| {
+                serde_json::json!({
+                    "jsonrpc": "2.0",
+                    "method": "eth_call",
+                    "params": [{"to": pool, "data": data}, "latest"],
+                    "id": id,
+                })
+            })
+            .collect();
+        let responses: Vec<serde_json::Value> = self
+            .client
+            .post(&self.rpc_url)
+            .json(&batch)
+            .send()
+            .await
+            .context("eth_call batch failed")?
+            .error_for_status()?
+            .json()
+            .await
+            .context("invalid eth_call batch response")?;
+
+        let mut results = vec![None; pools.len() * 2];
+        for response in responses {
+            let Some(slot) = response
+                .get("id")
+                .and_then(|id| id.as_u64())
+                .and_then(|id| results.get_mut(id as usize))
+            else {
+                bail!("eth_call batch response has an unknown id");
+            };
+            *slot = response
+                .get("result")
+                .and_then(|result| result.as_str())
+                .and_then(returned_address);
+        }
+        let mut results = results.into_iter();
+        Ok(std::iter::from_fn(|| Some((results.next()?, results.next()?))).collect())
+    }
+}
+
+/// The address an `address`-returning call encoded, if it is one.
+fn returned_address(result: &str) -> Option<String> {
+    let bytes = alloy::hex::decode(result).ok()?;
+    if bytes.len() != 32 || bytes[..12].iter().any(|b| *b != 0) || bytes[12..] == [0; 20] {
+        return None;
+    }
+    Some(format!("0x{}", alloy::hex::encode(&bytes[12..])))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::{keccak256, Address, Bytes, LogData};
+
+    fn swap_log(topic0: B256, words: &[U256]) -> alloy::rpc::types::Log {
+        let data: Vec<u8> = words.iter().flat_map(|w| w.to_be_bytes::<32>()).collect();
+        alloy::rpc::types::Log {
+            inner: alloy::primitives::Log {
+                address: Address::repeat_byte(0xaa),
+                data: LogData::new_unchecked(
+                    vec![
+                        topic0,
+                        Address::repeat_byte(0x01).into_word(),
+                        Address::repeat_byte(0x02).into_word(),
+                    ],
+                    Bytes::from(data),
+                ),
+            },
+            transaction_hash: Some(B256::repeat_byte(0x11)),
+            log_index: Some(4),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn swap_topics_match_event_signatures() {
+        assert_eq!(
+            keccak256("Swap(address,uint256,uint256,uint256,uint256,address)"),
+            UNISWAP_V2_SWAP_TOPIC
+        );
+        assert_eq!(
+            keccak256("Swap(address,address,int256,int256,uint160,uint128,int24)"),
+            UNISWAP_V3_SWAP_TOPIC
+        );
+    }
+
+    #[test]
+    fn decodes_v2_swap_as_pool_deltas() {
+        let log = swap_log(
+            UNISWAP_V2_SWAP_TOPIC,
+            &[U256::from(1_000), U256::ZERO, U256::ZERO, U256::from(2_500)],
+        );
+        let swap = decode_swap(&log).expect("v2 swap");
+        assert_eq!(swap.protocol, "uniswap_v2");
+        assert_eq!(swap.pool, format!("0x{}", "aa".repeat(20)));
+        assert_eq!(swap.sender, format!("0x{}", "01".repeat(20)));
+        assert_eq!(swap.recipient, format!("0x{}", "02".repeat(20)));
+        assert_eq!(swap.amount0.to_string(), "1000");
+        assert_eq!(swap.amount1.to_string(), "-2500");
+        assert_eq!(swap.log_index, 4);
+    }
+
+    #[test]
+    fn decodes_v3_swap_with_signed_amounts() {
+        let minus = I256::try_from(-42i64).unwrap().into_raw();
+        let log = swap_log(
+            UNISWAP_V3_SWAP_TOPIC,
+            &[
+                minus,
+                U256::from(7),
+                U256::from(1) << 96,
+                U256::from(10),
+                U256::ZERO,
+            ],
+        );
+        let swap = decode_swap(&log).expect("v3 swap");
+        assert_eq!(swap.protocol, "uniswap_v3");
+        assert_eq!(swap.amount0.to_string(), "-42");
+        assert_eq!(swap.amount1.to_string(), "7");
+    }
+
+    #[test]
+    fn ignores_other_logs_and_malformed_swaps() {
+        // V2 topic with V3-sized data
+        let log = swap_log(UNISWAP_V2_SWAP_TOPIC, &[U256::ZERO; 5]);
+        assert!(decode_swap(&log).is_none());
+        let log = swap_log(B256::repeat_byte(0x33), &[U256::ZERO; 4]);
+        assert!(decode_swap(&log).is_none());
+    }
+
+    #[test]
+    fn returned_address_requires_an_abi_encoded_address() {
+        let word = format!("0x{}{}", "00".repeat(12), "ab".repeat(20));
+        assert_eq!(
+            returned_address(&word),
+            Some(format!("0x{}", "ab".repeat(20)))
+        );
+        assert_eq!(returned_address("0x"), None);
+        assert_eq!(returned_address(&format!("0x{}", "00".repeat(32))), None);
+        assert_eq!(returned_address(&format!("0x{}", "ff".repeat(32))), None);
+    }
+}
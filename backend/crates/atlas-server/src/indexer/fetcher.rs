@@ -3,10 +3,12 @@ use alloy::providers::{Provider, RootProvider};
 use alloy::rpc::types::{Block, TransactionReceipt};
 use anyhow::Result;
 use governor::RateLimiter;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 use std::sync::Arc;
 use std::time::Duration;
 
+use super::archive;
+use super::l1_fee::{self, L1Fee};
 use crate::metrics::Metrics;
 
 /// Retry delays for RPC calls (in seconds)
@@ -18,6 +20,9 @@ const RPC_MAX_RETRIES: usize = 10;
 pub(crate) struct WorkItem {
     pub(crate) start_block: u64,
     pub(crate) count: usize,
+    /// Limiter current when the batch was planned, so a rate change applies
+    /// from the next batch
+    pub(crate) rate_limiter: SharedRateLimiter,
 }
 
 pub(crate) type HttpProvider = RootProvider<Ethereum>;
@@ -36,12 +41,19 @@ pub(crate) enum FetchResult {
 }
 
 /// Data fetched from RPC for a single block
-pub(crate) struct FetchedBlock {
-    pub(crate) number: u64,
-    pub(crate) block: Block,
-    pub(crate) receipts: Vec<TransactionReceipt>,
+pub struct FetchedBlock {
+    pub number: u64,
+    /// Block with full transactions
+    pub block: Block,
+    /// Receipts in transaction order
+    pub receipts: Vec<TransactionReceipt>,
+    /// Rollup L1 data fees by transaction hash, empty on L1 chains.
+    pub(crate) l1_fees: HashMap<String, L1Fee>,
+    /// Archive payload, present when raw archiving is enabled.
+    pub(crate) raw: Option<Vec<u8>>,
 }
 
+#[tracing::instrument(name = "rpc.fetch_batch", skip_all, fields(start_block, count))]
 pub(crate) async fn fetch_blocks_batch(
     client: &reqwest::Client,
     rpc_url: &str,
@@ -49,6 +61,7 @@ pub(crate) async fn fetch_blocks_batch(
     count: usize,
     rate_limiter: &SharedRateLimiter,
     metrics: &Metrics,
+    archive_raw: bool,
 ) -> Vec<FetchResult> {
     tracing::debug!(
         start_block,
@@ -225,10 +238,19 @@ pub(crate) async fn fetch_blocks_batch(
                     receipts = receipts.len(),
                     "block complete"
                 );
+                let raw = archive_raw.then(|| {
+                    archive::encode(
+                        &response_map[&block_id]["result"],
+                        &response_map[&receipts_id]["result"],
+                    )
+                });
+                let l1_fees = l1_fee::from_receipts(&response_map[&receipts_id]["result"]);
                 results.push(FetchResult::Success(Box::new(FetchedBlock {
                     number: block_num,
                     block,
                     receipts,
+                    l1_fees,
+                    raw,
                 })));
             }
             (Err(e), _) => {
@@ -25,6 +25,8 @@ use tokio::sync::broadcast;
 use super::batch::BlockBatch;
 use super::fetcher::{fetch_blocks_batch, FetchResult, SharedRateLimiter};
 use super::indexer::{ensure_partitions_exist, Indexer};
+use super::partitions;
+use super::processors::Processors;
 use crate::metrics::Metrics;
 
 /// Maximum blocks processed per cycle.
@@ -62,7 +64,10 @@ pub struct GapFillWorker {
     rpc_requests_per_second: u32,
     block_events_tx: broadcast::Sender<()>,
     metrics: Metrics,
+    raw_archive_enabled: bool,
     current_max_partition: AtomicU64,
+    processors: Processors,
+    wrapped_native: Arc<HashSet<String>>,
 }
 
 impl GapFillWorker {
@@ -73,6 +78,7 @@ impl GapFillWorker {
         rpc_requests_per_second: u32,
         block_events_tx: broadcast::Sender<()>,
         metrics: Metrics,
+        raw_archive_enabled: bool,
     ) -> Result<Self> {
         if rpc_requests_per_second == 0 {
             anyhow::bail!("rpc_requests_per_second must be greater than 0");
@@ -84,10 +90,26 @@ impl GapFillWorker {
             rpc_requests_per_second,
             block_events_tx,
             metrics,
+            raw_archive_enabled,
             current_max_partition: AtomicU64::new(super::indexer::UNKNOWN_MAX_PARTITION),
+            processors: Processors::default(),
+            wrapped_native: Arc::default(),
         })
     }
 
+    /// Run `processors` on every block this worker writes.
+    pub fn with_processors(mut self, processors: Processors) -> Self {
+        self.processors = processors;
+        self
+    }
+
+    /// Index Deposit/Withdrawal events of these WETH-style contracts as
+    /// mints and burns.
+    pub fn with_wrapped_native(mut self, contracts: &[String]) -> Self {
+        self.wrapped_native = Arc::new(contracts.iter().cloned().collect());
+        self
+    }
+
     pub async fn run(&self) -> Result<()> {
         tracing::info!("Gap-fill worker started");
         loop {
@@ -134,22 +156,36 @@ impl GapFillWorker {
                 1,
                 &rate_limiter,
                 &self.metrics,
+                self.raw_archive_enabled,
             )
             .await;
 
             match results.into_iter().next() {
                 Some(FetchResult::Success(fetched)) => {
                     let mut batch = BlockBatch::new();
+                    batch.wrapped_native = self.wrapped_native.clone();
+                    self.processors.collect(&fetched, &mut batch);
                     Indexer::collect_block(&mut batch, &known_erc20, &known_nft, *fetched);
 
-                    if let Err(e) =
-                        ensure_partitions_exist(&self.pool, &self.current_max_partition, block_num)
-                            .await
-                    {
-                        tracing::warn!(block = block_num, error = %e, "gap-fill: partition check failed");
-                        self.increment_retry(block_number).await?;
-                        failed += 1;
-                        continue;
+                    let staged = async {
+                        ensure_partitions_exist(
+                            &self.pool,
+                            &self.current_max_partition,
+                            block_num,
+                            false,
+                        )
+                        .await?;
+                        partitions::staged_partition_of(&self.pool, block_num).await
+                    }
+                    .await;
+                    match staged {
+                        Ok(staged) => batch.staged_partition = staged,
+                        Err(e) => {
+                            tracing::warn!(block = block_num, error = %e, "gap-fill: partition check failed");
+                            self.increment_retry(block_number).await?;
+                            failed += 1;
+                            continue;
+                        }
                     }
 
                     if let Err(e) = Indexer::write_batch_and_clear_failed_block(
@@ -241,6 +277,7 @@ mod tests {
             0,
             tx,
             Metrics::new(),
+            false,
         )
         .err()
         .expect("zero rps should fail");
@@ -0,0 +1,274 @@
+//! Genesis state import (`GENESIS_FILE`).
+//!
+//! Pre-funded accounts and predeploys in the genesis `alloc` have no
+//! transactions, so block indexing never discovers them. The importer reads
+//! the chain's genesis JSON (geth format, also used by reth and ev-reth) and
+//! seeds them at block 0:
+//!
+//! - `addresses` rows with `first_seen_block = 0`, flagged as contracts when
+//!   the account has code;
+//! - `genesis_accounts` with the genesis balance, nonce, code size and
+//!   storage slot count.
+//!
+//! The import runs once per index: a marker in `indexer_state` records it,
+//! and a reindex (which truncates both) imports again.
+
+use alloy::primitives::U256;
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+use serde_json::Value;
+use sqlx::PgPool;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use super::address_directory;
+use crate::state_keys::GENESIS_IMPORTED_KEY;
+
+/// Accounts written per statement.
+const IMPORT_CHUNK_SIZE: usize = 1000;
+
+#[derive(Deserialize)]
+struct GenesisFile {
+    alloc: BTreeMap<String, GenesisAllocEntry>,
+}
+
+#[derive(Deserialize)]
+struct GenesisAllocEntry {
+    #[serde(default)]
+    balance: Option<Value>,
+    #[serde(default)]
+    nonce: Option<Value>,
+    #[serde(default)]
+    code: Option<String>,
+    #[serde(default)]
+    storage: Option<BTreeMap<String, Value>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GenesisAccount {
+    /// Lowercase `0x`-prefixed address.
+    pub address: String,
+    /// Wei, in decimal.
+    pub balance: String,
+    pub nonce: i64,
+    /// Bytes of deployed code; non-zero for predeploys.
+    pub code_size: i32,
+    pub storage_slots: i32,
+}
+
+/// Parse the `alloc` section of a genesis JSON document.
+pub fn parse_genesis(json: &[u8]) -> Result<Vec<GenesisAccount>> {
+    let genesis: GenesisFile =
+        serde_json::from_slice(json).context("genesis JSON has no valid `alloc` object")?;
+
+    genesis
+        .alloc
+        .into_iter()
+        .map(|(address, entry)| {
+            parse_account(&address, entry).with_context(|| format!("genesis account {address}"))
+        })
+        .collect()
+}
+
+fn parse_account(address: &str, entry: GenesisAllocEntry) -> Result<GenesisAccount> {
+    let hex_address = address.strip_prefix("0x").unwrap_or(address);
+    if hex_address.len() != 40 || !hex_address.chars().all(|c| c.is_ascii_hexdigit()) {
+        bail!("invalid address");
+    }
+
+    let balance = entry
+        .balance
+        .as_ref()
+        .map(parse_quantity)
+        .transpose()
+        .context("invalid balance")?
+        .unwrap_or_default();
+    let nonce = entry
+        .nonce
+        .as_ref()
+        .map(parse_quantity)
+        .transpose()
+        .context("invalid nonce")?
+        .unwrap_or_default();
+    let code = entry.code.as_deref().unwrap_or_default();
+    let code = hex::decode(code.strip_prefix("0x").unwrap_or(code)).context("invalid code")?;
+
+    Ok(GenesisAccount {
+        address: format!("0x{}", hex_address.to_lowercase()),
+        balance: balance.to_string(),
+        nonce: i64::try_from(nonce).context("nonce out of range")?,
+        code_size: i32::try_from(code.len()).context("code too large")?,
+        storage_slots: entry
+            .storage
+            .map_or(0, |storage| storage.len())
+            .try_into()
+            .context("too many storage slots")?,
+    })
+}
+
+/// A genesis quantity: a `0x` hex string, a decimal string or a JSON number.
+fn parse_quantity(value: &Value) -> Result<U256> {
+    match value {
+        Value::String(s) => {
+            let s = s.trim();
+            match s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+                Some("") => Ok(U256::ZERO),
+                Some(hex) => Ok(U256::from_str_radix(hex, 16)?),
+                None => Ok(U256::from_str_radix(s, 10)?),
+            }
+        }
+        Value::Number(n) => n
+            .as_u64()
+            .map(U256::from)
+            .context("expected a non-negative integer"),
+        _ => bail!("expected a string or number"),
+    }
+}
+
+/// Import the genesis file unless this index already has it.
+pub async fn import_if_needed(pool: &PgPool, path: &Path) -> Result<()> {
+    let imported: Option<String> =
+        sqlx::query_scalar("SELECT value FROM indexer_state WHERE key = $1")
+            .bind(GENESIS_IMPORTED_KEY)
+            .fetch_optional(pool)
+            .await?;
+    if let Some(count) = imported {
+        tracing::debug!(accounts = %count, "genesis state already imported");
+        return Ok(());
+    }
+
+    let json = tokio::fs::read(path)
+        .await
+        .with_context(|| format!("Failed to read genesis file {}", path.display()))?;
+    let accounts = parse_genesis(&json)
+        .with_context(|| format!("Failed to parse genesis file {}", path.display()))?;
+    import_accounts(pool, &accounts).await?;
+
+    tracing::info!(
+        accounts = accounts.len(),
+        contracts = accounts.iter().filter(|a| a.code_size > 0).count(),
+        "imported genesis state"
+    );
+    Ok(())
+}
+
+/// Seed addresses and genesis balances, and record the import, atomically.
+pub async fn import_accounts(pool: &PgPool, accounts: &[GenesisAccount]) -> Result<()> {
+    let mut tx = pool.begin().await?;
+
+    for chunk in accounts.chunks(IMPORT_CHUNK_SIZE) {
+        let addresses: Vec<&str> = chunk.iter().map(|a| a.address.as_str()).collect();
+        let balances: Vec<&str> = chunk.iter().map(|a| a.balance.as_str()).collect();
+        let nonces: Vec<i64> = chunk.iter().map(|a| a.nonce).collect();
+        let code_sizes: Vec<i32> = chunk.iter().map(|a| a.code_size).collect();
+        let storage_slots: Vec<i32> = chunk.iter().map(|a| a.storage_slots).collect();
+
+        // An address already seen in a later block still existed at genesis.
+        sqlx::query(
+            "INSERT INTO addresses (address, is_contract, first_seen_block, tx_count)
+             SELECT address, code_size > 0, 0, 0
+             FROM unnest($1::text[], $2::int[]) AS g(address, code_size)
+             ON CONFLICT (address) DO UPDATE SET
+                is_contract = addresses.is_contract OR EXCLUDED.is_contract,
+                first_seen_block = 0",
+        )
+        .bind(&addresses)
+        .bind(&code_sizes)
+        .execute(&mut *tx)
+        .await?;
+        address_directory::refresh(&mut *tx, &addresses).await?;
+
+        sqlx::query(
+            "INSERT INTO genesis_accounts (address, balance, nonce, code_size, storage_slots)
+             SELECT address, balance::numeric, nonce, code_size, storage_slots
+             FROM unnest($1::text[], $2::text[], $3::bigint[], $4::int[], $5::int[])
+                AS g(address, balance, nonce, code_size, storage_slots)
+             ON CONFLICT (address) DO UPDATE SET
+                balance = EXCLUDED.balance,
+                nonce = EXCLUDED.nonce,
+                code_size = EXCLUDED.code_size,
+                storage_slots = EXCLUDED.storage_slots",
+        )
+        .bind(&addresses)
+        .bind(&balances)
+        .bind(&nonces)
+        .bind(&code_sizes)
+        .bind(&storage_slots)
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    sqlx::query(
+        "INSERT INTO indexer_state (key, value, updated_at)
+         VALUES ($1, $2, NOW())
+         ON CONFLICT (key) DO UPDATE SET value = EXCLUDED.value, updated_at = EXCLUDED.updated_at",
+    )
+    .bind(GENESIS_IMPORTED_KEY)
+    .bind(accounts.len().to_string())
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_alloc_balances_and_predeploys() {
+        let genesis = br#"{
+            "config": {"chainId": 1234},
+            "alloc": {
+                "f39Fd6e51aad88F6F4ce6aB8827279cffFb92266": {"balance": "0xde0b6b3a7640000"},
+                "0x4200000000000000000000000000000000000016": {
+                    "balance": "0",
+                    "nonce": "0x1",
+                    "code": "0x6080604052",
+                    "storage": {"0x00": "0x01", "0x01": "0x02"}
+                },
+                "0x0000000000000000000000000000000000000001": {"balance": 1000, "nonce": 2}
+            }
+        }"#;
+
+        let accounts = parse_genesis(genesis).unwrap();
+        assert_eq!(accounts.len(), 3);
+
+        let funded = accounts
+            .iter()
+            .find(|a| a.address == "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266")
+            .unwrap();
+        assert_eq!(funded.balance, "1000000000000000000");
+        assert_eq!(funded.code_size, 0);
+
+        let predeploy = accounts
+            .iter()
+            .find(|a| a.address == "0x4200000000000000000000000000000000000016")
+            .unwrap();
+        assert_eq!(predeploy.nonce, 1);
+        assert_eq!(predeploy.code_size, 5);
+        assert_eq!(predeploy.storage_slots, 2);
+
+        let numeric = accounts
+            .iter()
+            .find(|a| a.address == "0x0000000000000000000000000000000000000001")
+            .unwrap();
+        assert_eq!(numeric.balance, "1000");
+        assert_eq!(numeric.nonce, 2);
+    }
+
+    #[test]
+    fn rejects_malformed_accounts() {
+        assert!(parse_genesis(br#"{"config": {}}"#).is_err());
+        assert!(parse_genesis(br#"{"alloc": {"0x1234": {"balance": "1"}}}"#).is_err());
+        assert!(parse_genesis(
+            br#"{"alloc": {"0x0000000000000000000000000000000000000001": {"balance": "0xzz"}}}"#
+        )
+        .is_err());
+        assert!(parse_genesis(
+            br#"{"alloc": {"0x0000000000000000000000000000000000000001": {"code": "0xabc"}}}"#
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,138 @@
+//! Scheduled ranking of ERC-20 holders into `erc20_holder_ranks`.
+//!
+//! ## Design
+//!
+//! Sorting every balance of a token on each holders request is slow for tokens
+//! with millions of holders. Instead each token's positive balances are
+//! numbered by `balance DESC, address` into a snapshot table keyed by
+//! `(contract_address, rank)`, so a page is a primary-key range read at any
+//! depth, and `erc20_holder_rank_state` keeps the holder count.
+//!
+//! A cursor in `indexer_state` holds the last block whose transfers have been
+//! ranked. Every cycle re-ranks only the tokens with transfers after the
+//! cursor (all tokens on the first cycle), then moves the cursor to the
+//! latest indexed block. Rankings therefore lag live balances by up to one
+//! interval. A reindex truncates the tables and the cursor.
+
+use anyhow::{bail, Result};
+use sqlx::PgPool;
+use std::time::Duration;
+
+use crate::state_keys::HOLDER_RANKS_LAST_BLOCK_KEY;
+
+const RANK_TOKEN_SQL: &str = "
+    INSERT INTO erc20_holder_ranks (contract_address, rank, address, balance)
+    SELECT contract_address, ROW_NUMBER() OVER (ORDER BY balance DESC, address), address, balance
+    FROM erc20_balances
+    WHERE contract_address = $1 AND balance > 0";
+
+pub struct HolderRanker {
+    pool: PgPool,
+    interval: Duration,
+}
+
+impl HolderRanker {
+    pub fn new(pool: PgPool, interval: Duration) -> Result<Self> {
+        if interval.is_zero() {
+            bail!("holder ranks interval must be greater than 0");
+        }
+        Ok(Self { pool, interval })
+    }
+
+    pub async fn run(&self) -> Result<()> {
+        tracing::info!(
+            interval_secs = self.interval.as_secs(),
+            "Holder ranking started"
+        );
+        loop {
+            let tokens = self.run_cycle().await?;
+            tracing::debug!(tokens, "holder ranking cycle complete");
+            tokio::time::sleep(self.interval).await;
+        }
+    }
+
+    /// Re-rank every token with transfers since the last cycle and return how
+    /// many were ranked.
+    pub async fn run_cycle(&self) -> Result<u64> {
+        let (head,): (Option<i64>,) = sqlx::query_as("SELECT MAX(number) FROM blocks")
+            .fetch_one(&self.pool)
+            .await?;
+        let Some(head) = head else {
+            return Ok(0);
+        };
+
+        let tokens: Vec<String> = match self.cursor().await? {
+            Some(cursor) if cursor >= head => return Ok(0),
+            Some(cursor) => {
+                sqlx::query_scalar(
+                    "SELECT DISTINCT contract_address FROM erc20_transfers
+                     WHERE block_number > $1 AND block_number <= $2",
+                )
+                .bind(cursor)
+                .bind(head)
+                .fetch_all(&self.pool)
+                .await?
+            }
+            None => {
+                sqlx::query_scalar("SELECT DISTINCT contract_address FROM erc20_balances")
+                    .fetch_all(&self.pool)
+                    .await?
+            }
+        };
+
+        for token in &tokens {
+            rank_token(&self.pool, token, head).await?;
+        }
+
+        sqlx::query(
+            "INSERT INTO indexer_state (key, value, updated_at)
+             VALUES ($1, $2, NOW())
+             ON CONFLICT (key) DO UPDATE SET value = EXCLUDED.value, updated_at = EXCLUDED.updated_at",
+        )
+        .bind(HOLDER_RANKS_LAST_BLOCK_KEY)
+        .bind(head.to_string())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(tokens.len() as u64)
+    }
+
+    async fn cursor(&self) -> Result<Option<i64>> {
+        let row: Option<(String,)> =
+            sqlx::query_as("SELECT value FROM indexer_state WHERE key = $1")
+                .bind(HOLDER_RANKS_LAST_BLOCK_KEY)
+                .fetch_optional(&self.pool)
+                .await?;
+        row.map(|(value,)| value.parse::<i64>().map_err(Into::into))
+            .transpose()
+    }
+}
+
+/// Replace one token's ranking with its current balances, atomically.
+pub async fn rank_token(pool: &PgPool, contract_address: &str, ranked_block: i64) -> Result<()> {
+    let mut tx = pool.begin().await?;
+    sqlx::query("DELETE FROM erc20_holder_ranks WHERE contract_address = $1")
+        .bind(contract_address)
+        .execute(&mut *tx)
+        .await?;
+    let holders = sqlx::query(RANK_TOKEN_SQL)
+        .bind(contract_address)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+    sqlx::query(
+        "INSERT INTO erc20_holder_rank_state (contract_address, holders, ranked_block, ranked_at)
+         VALUES ($1, $2, $3, NOW())
+         ON CONFLICT (contract_address) DO UPDATE SET
+             holders = EXCLUDED.holders,
+             ranked_block = EXCLUDED.ranked_block,
+             ranked_at = EXCLUDED.ranked_at",
+    )
+    .bind(contract_address)
+    .bind(holders as i64)
+    .bind(ranked_block)
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await?;
+    Ok(())
+}
@@ -1,7 +1,8 @@
-use alloy::primitives::U256;
-use alloy::providers::RootProvider;
+use alloy::primitives::{B256, U256};
+use alloy::providers::{Provider, RootProvider};
 use alloy::rpc::types::TransactionReceipt;
-use anyhow::Result;
+use anyhow::{Context, Result};
+use atlas_common::encoding::{address_to_hex, b256_to_hex, topic_to_address_hex};
 use bigdecimal::BigDecimal;
 use chrono::{DateTime, Utc};
 use governor::{Quota, RateLimiter};
@@ -14,28 +15,124 @@ use std::time::Duration;
 use tokio::sync::{broadcast, mpsc};
 use tokio_postgres::{types::ToSql, Client, NoTls};
 use tokio_postgres_rustls::MakeRustlsConnect;
+use tracing::Instrument;
 
-use super::batch::{BlockBatch, NftTokenState};
+use super::address_directory;
+use super::archive;
+use super::batch::{ApprovalState, BlockBatch, NftTokenState, Provenance};
 use super::copy::{
-    copy_blocks, copy_erc20_transfers, copy_event_logs, copy_nft_transfers, copy_transactions,
+    copy_blocks, copy_erc20_transfers, copy_event_logs, copy_nft_transfers, copy_raw_archive,
+    copy_transactions,
 };
 use super::fetcher::{
     fetch_blocks_batch, get_block_number_with_retry, FetchResult, FetchedBlock, SharedRateLimiter,
     WorkItem,
 };
+use super::genesis;
+use super::partitions::{self, PARTITIONED_TABLES};
+use super::processors::Processors;
+use crate::bloom;
 use crate::config::Config;
 use crate::head::HeadTracker;
 use crate::metrics::Metrics;
-use crate::state_keys::ERC20_SUPPLY_HISTORY_COMPLETE_KEY;
+use crate::ops_alerts::{OpsAlert, OpsAlerter};
+use crate::runtime_settings::RuntimeSettings;
+use crate::state_keys::{
+    BACKFILL_SPLIT_BLOCK_KEY, ERC20_SUPPLY_HISTORY_COMPLETE_KEY,
+    TRANSFER_AGGREGATION_BEFORE_BLOCK_KEY,
+};
 
 /// Partition size: 10 million blocks per partition
-const PARTITION_SIZE: u64 = 10_000_000;
+pub(crate) const PARTITION_SIZE: u64 = 10_000_000;
 
 /// ERC-20/721 Transfer event signature: Transfer(address,address,uint256)
 const TRANSFER_TOPIC: &str = "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef";
 
+/// ERC-20/721 Approval event signature: Approval(address,address,uint256)
+const APPROVAL_TOPIC: &str = "0x8c5be1e5ebec7d5bd14f71427d1e84f3dd0314c0f7b2291e5b200ac8c7c3b925";
+
+/// WETH9 Deposit event signature: Deposit(address,uint256)
+const DEPOSIT_TOPIC: &str = "0xe1fffcc4923d04b559f4d29a8bfc6cda04eb5b0d3c460751c2402c5c5cc9109c";
+
+/// WETH9 Withdrawal event signature: Withdrawal(address,uint256)
+const WITHDRAWAL_TOPIC: &str = "0x7fcf532c15f0a6db0bd6d0e038bea71d30d808c7d98cb3bf7268a95bf5081b65";
+
 const ZERO_ADDRESS: &str = "0x0000000000000000000000000000000000000000";
 
+/// Topics of the events [`decode_transfer_log`] decodes.
+pub(crate) const TRANSFER_EVENT_TOPICS: [&str; 3] =
+    [TRANSFER_TOPIC, DEPOSIT_TOPIC, WITHDRAWAL_TOPIC];
+
+/// A token transfer decoded from a log.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum TransferLog {
+    Erc20 {
+        from: String,
+        to: String,
+        value: U256,
+    },
+    Nft {
+        from: String,
+        to: String,
+        token_id: U256,
+    },
+}
+
+/// Decode the token transfer a log records, if any:
+///
+/// - ERC-20 `Transfer(address indexed from, address indexed to, uint256 value)`;
+/// - ERC-721 `Transfer(address indexed from, address indexed to, uint256 indexed tokenId)`;
+/// - for a wrapped native token (`wrapped_native`), `Deposit(address indexed dst,
+///   uint256 wad)` and `Withdrawal(address indexed src, uint256 wad)`, which move
+///   balances without a `Transfer` and count as a mint and a burn.
+///
+/// Shared by block collection and per-contract reindexing so both decode
+/// history the same way.
+pub(crate) fn decode_transfer_log(
+    topics: &[B256],
+    data: &[u8],
+    wrapped_native: bool,
+) -> Option<TransferLog> {
+    let topic0 = b256_to_hex(topics.first()?);
+    let amount = || (data.len() >= 32).then(|| U256::from_be_slice(&data[..32]));
+
+    if wrapped_native
+        && topics.len() == 2
+        && (topic0 == DEPOSIT_TOPIC || topic0 == WITHDRAWAL_TOPIC)
+    {
+        let account = topic_to_address_hex(&topics[1]);
+        let (from, to) = if topic0 == DEPOSIT_TOPIC {
+            (ZERO_ADDRESS.to_string(), account)
+        } else {
+            (account, ZERO_ADDRESS.to_string())
+        };
+        return Some(TransferLog::Erc20 {
+            from,
+            to,
+            value: amount()?,
+        });
+    }
+
+    if topic0 != TRANSFER_TOPIC {
+        return None;
+    }
+    let from = topic_to_address_hex(topics.get(1)?);
+    let to = topic_to_address_hex(topics.get(2)?);
+    match topics.len() {
+        4 => Some(TransferLog::Nft {
+            from,
+            to,
+            token_id: U256::from_be_slice(topics[3].as_slice()),
+        }),
+        3 => Some(TransferLog::Erc20 {
+            from,
+            to,
+            value: amount()?,
+        }),
+        _ => None,
+    }
+}
+
 pub struct Indexer {
     pool: PgPool,
     config: Config,
@@ -47,6 +144,10 @@ pub struct Indexer {
     /// Shared in-memory tracker for the latest committed head and replay tail
     head_tracker: Arc<HeadTracker>,
     metrics: Metrics,
+    ops_alerts: OpsAlerter,
+    settings: RuntimeSettings,
+    processors: Processors,
+    wrapped_native: Arc<HashSet<String>>,
 }
 
 impl Indexer {
@@ -56,7 +157,10 @@ impl Indexer {
         block_events_tx: broadcast::Sender<()>,
         head_tracker: Arc<HeadTracker>,
         metrics: Metrics,
+        ops_alerts: OpsAlerter,
+        settings: RuntimeSettings,
     ) -> Self {
+        let wrapped_native = Arc::new(config.wrapped_native_contracts.iter().cloned().collect());
         Self {
             pool,
             config,
@@ -65,9 +169,19 @@ impl Indexer {
             block_events_tx,
             head_tracker,
             metrics,
+            ops_alerts,
+            settings,
+            processors: Processors::default(),
+            wrapped_native,
         }
     }
 
+    /// Run `processors` on every block this indexer writes.
+    pub fn with_processors(mut self, processors: Processors) -> Self {
+        self.processors = processors;
+        self
+    }
+
     /// Open a tokio-postgres connection for binary COPY, using TLS when sslmode
     /// requires it (require / verify-ca / verify-full) and plain TCP otherwise.
     pub(crate) async fn connect_copy_client(database_url: &str) -> Result<Client> {
@@ -108,11 +222,10 @@ impl Indexer {
         // TLS is used when sslmode=require/verify-ca/verify-full is set in DATABASE_URL.
         let mut copy_client = Self::connect_copy_client(&self.config.database_url).await?;
 
-        // Create rate limiter for RPC requests
-        let rps = NonZeroU32::new(self.config.rpc_requests_per_second)
-            .unwrap_or(NonZeroU32::new(100).unwrap());
-        let rate_limiter: SharedRateLimiter = Arc::new(RateLimiter::direct(Quota::per_second(rps)));
-        tracing::info!(rps = %rps, "rate limiting RPC requests");
+        // Create rate limiter for RPC requests; rebuilt when the rate changes
+        let mut rps = self.settings.current().rpc_requests_per_second;
+        let mut rate_limiter = build_rate_limiter(rps);
+        tracing::info!(rps, "rate limiting RPC requests");
 
         // Handle reindex flag
         if self.config.reindex {
@@ -121,14 +234,27 @@ impl Indexer {
             self.truncate_tables().await?;
         }
 
+        // Re-checked here as well as at process start so a retry after the
+        // RPC endpoint has been swapped to another chain cannot write to this index.
+        let chain_id = provider.get_chain_id().await?;
+        crate::chain_guard::ensure_chain_id(&self.pool, chain_id).await?;
+
+        if let Some(genesis_file) = &self.config.genesis_file {
+            genesis::import_if_needed(&self.pool, genesis_file).await?;
+        }
+
         // Get starting block
-        let start_block = self.get_start_block().await?;
+        let first_block = self.get_start_block().await?;
         let erc20_supply_history_status = self.get_erc20_supply_history_status().await?;
-        let mut erc20_supply_backfill_pending = matches!(erc20_supply_history_status, Some(false))
-            || (erc20_supply_history_status.is_none() && start_block == 0);
-        if erc20_supply_history_status.is_none() && start_block == 0 {
+        if erc20_supply_history_status.is_none() && first_block == 0 {
             self.set_erc20_supply_history_complete(false).await?;
         }
+        // With a backfill planned the workers mark the supply history
+        // complete once every range is done.
+        let (start_block, backfill_planned) = self.plan_backfill(&provider, first_block).await?;
+        let mut erc20_supply_backfill_pending = !backfill_planned
+            && (matches!(erc20_supply_history_status, Some(false))
+                || (erc20_supply_history_status.is_none() && first_block == 0));
         tracing::info!(start_block, "starting indexing");
 
         let latest_indexed_block = self.head_tracker.latest().await;
@@ -145,6 +271,29 @@ impl Indexer {
         self.metrics
             .set_indexer_missing_blocks(known_missing_blocks);
 
+        // Finish partitions staged by an earlier run. Only the one indexing
+        // resumes in stays staged, and only while deferral is on.
+        let mut staged_partition = None;
+        for p in partitions::staged(&self.pool).await? {
+            if self.config.defer_partition_indexes
+                && !self.config.replay_archive
+                && start_block / PARTITION_SIZE == p
+            {
+                staged_partition = Some(p);
+            } else {
+                partitions::complete(&self.pool, p).await?;
+            }
+        }
+
+        let resume_block = if self.config.replay_archive {
+            self.replay_archive(start_block).await?
+        } else {
+            start_block
+        };
+        if resume_block > start_block {
+            indexed_head = Some(resume_block - 1);
+        }
+
         // Load known contracts into memory to avoid a SELECT per transfer
         let mut known_erc20: HashSet<String> = self.load_known_erc20().await?;
         tracing::info!(count = known_erc20.len(), "loaded known ERC-20 contracts");
@@ -169,12 +318,12 @@ impl Indexer {
         // Create HTTP client for batch requests
         let http_client = reqwest::Client::new();
         let rpc_url = self.config.rpc_url.clone();
+        let archive_raw = self.config.raw_archive_enabled;
 
         // Spawn long-lived workers
         for worker_id in 0..num_workers {
             let work_rx = work_rx.clone();
             let result_tx = result_tx.clone();
-            let limiter = Arc::clone(&rate_limiter);
             let client = http_client.clone();
             let url = rpc_url.clone();
             let worker_metrics = self.metrics.clone();
@@ -188,8 +337,9 @@ impl Indexer {
                         &url,
                         work_item.start_block,
                         work_item.count,
-                        &limiter,
+                        &work_item.rate_limiter,
                         &worker_metrics,
+                        archive_raw,
                     )
                     .await;
 
@@ -208,8 +358,9 @@ impl Indexer {
         drop(result_tx);
 
         // Main indexing loop
-        let mut current_block = start_block;
+        let mut current_block = resume_block;
         let mut last_log_time = std::time::Instant::now();
+        let mut head_stall = HeadStall::new(std::time::Instant::now());
 
         loop {
             // Get chain head with retry
@@ -222,10 +373,26 @@ impl Indexer {
                 }
             };
             self.metrics.set_chain_head_block(head);
+            self.head_tracker.set_chain_height(head as i64);
             self.metrics
                 .set_indexer_lag_blocks(lag_blocks(head, indexed_head, start_block));
             tracing::debug!(chain_head = head, current = current_block, "chain head");
 
+            let stalled_secs = head_stall
+                .observe(head, std::time::Instant::now())
+                .as_secs();
+            if self.config.block_stall_secs > 0 && stalled_secs >= self.config.block_stall_secs {
+                tracing::warn!(
+                    chain_head = head,
+                    stalled_secs,
+                    "chain head is not advancing"
+                );
+                self.ops_alerts.fire(OpsAlert::BlockProductionStalled {
+                    block: head,
+                    stalled_secs,
+                });
+            }
+
             if current_block > head {
                 if erc20_supply_backfill_pending {
                     self.set_erc20_supply_history_complete(true).await?;
@@ -238,8 +405,20 @@ impl Indexer {
 
             let processing_start = std::time::Instant::now();
 
+            let runtime = self.settings.current();
+            if runtime.rpc_requests_per_second != rps {
+                rps = runtime.rpc_requests_per_second;
+                rate_limiter = build_rate_limiter(rps);
+                tracing::info!(rps, "RPC rate limit changed");
+            }
+
             // Calculate batch end
-            let end_block = (current_block + self.config.batch_size - 1).min(head);
+            let mut end_block = (current_block + runtime.batch_size - 1).min(head);
+            if self.config.defer_partition_indexes {
+                // One partition per batch, so a staged partition is written alone.
+                end_block =
+                    end_block.min((current_block / PARTITION_SIZE + 1) * PARTITION_SIZE - 1);
+            }
             let batch_size = (end_block - current_block + 1) as usize;
             tracing::debug!(
                 start = current_block,
@@ -248,12 +427,20 @@ impl Indexer {
                 "fetching batch"
             );
 
-            // Ensure partitions exist for this batch range
-            self.ensure_partitions_exist(end_block).await?;
+            // Ensure partitions exist for this batch range. A new partition
+            // that the chain head is already past is staged.
+            let partition = end_block / PARTITION_SIZE;
+            let stage =
+                self.config.defer_partition_indexes && (partition + 1) * PARTITION_SIZE <= head + 1;
+            if self.ensure_partitions_exist(end_block, stage).await? {
+                staged_partition = Some(partition);
+            }
+            let batch_staged = staged_partition.filter(|p| *p == current_block / PARTITION_SIZE);
 
             // Spawn a task to send work (avoids deadlock with bounded channels)
             let work_tx_clone = work_tx.clone();
-            let blocks_per_batch = rpc_batch_size;
+            let batch_limiter = Arc::clone(&rate_limiter);
+            let blocks_per_batch = runtime.rpc_batch_size as usize;
             let send_task = tokio::spawn(async move {
                 let mut block = current_block;
                 while block <= end_block {
@@ -261,6 +448,7 @@ impl Indexer {
                     let work_item = WorkItem {
                         start_block: block,
                         count,
+                        rate_limiter: Arc::clone(&batch_limiter),
                     };
                     if work_tx_clone.send(work_item).await.is_err() {
                         break;
@@ -281,6 +469,8 @@ impl Indexer {
             let mut blocks_received = 0;
             let mut failed_blocks: Vec<(u64, String)> = Vec::new();
             let mut batch = BlockBatch::new();
+            batch.staged_partition = batch_staged;
+            batch.wrapped_native = self.wrapped_native.clone();
 
             // Receive all blocks for this batch
             while blocks_received < batch_size {
@@ -291,6 +481,7 @@ impl Indexer {
 
                         // Collect consecutive blocks in order (sync, no await)
                         while let Some(data) = buffer.remove(&next_to_process) {
+                            self.processors.collect(&data, &mut batch);
                             Self::collect_block(&mut batch, &known_erc20, &known_nft, data);
                             next_to_process += 1;
                         }
@@ -380,6 +571,7 @@ impl Indexer {
                             1,
                             &rate_limiter,
                             &self.metrics,
+                            self.config.raw_archive_enabled,
                         )
                         .await;
 
@@ -387,6 +579,9 @@ impl Indexer {
                             Some(FetchResult::Success(fetched)) => {
                                 // Write retried block immediately
                                 let mut mini_batch = BlockBatch::new();
+                                mini_batch.staged_partition = batch_staged;
+                                mini_batch.wrapped_native = self.wrapped_native.clone();
+                                self.processors.collect(&fetched, &mut mini_batch);
                                 Self::collect_block(
                                     &mut mini_batch,
                                     &known_erc20,
@@ -440,12 +635,35 @@ impl Indexer {
                         .await?;
                     }
 
+                    self.ops_alerts.fire(OpsAlert::FailedBlocks {
+                        blocks: failed_blocks.iter().map(|(n, _)| *n).collect(),
+                        error: failed_blocks[0].1.clone(),
+                    });
+
                     known_missing_blocks += failed_blocks.len() as u64;
                     self.metrics
                         .set_indexer_missing_blocks(known_missing_blocks);
                 }
             }
 
+            // The staged partition is complete: build its indexes and attach
+            // it in the background while the next partition fills.
+            if let Some(p) = batch_staged {
+                if end_block + 1 == (p + 1) * PARTITION_SIZE {
+                    staged_partition = None;
+                    let pool = self.pool.clone();
+                    tokio::spawn(async move {
+                        if let Err(e) = partitions::complete(&pool, p).await {
+                            tracing::error!(
+                                partition = p,
+                                error = %e,
+                                "building deferred partition indexes failed; retried on restart"
+                            );
+                        }
+                    });
+                }
+            }
+
             current_block = end_block + 1;
             indexed_head = Some(actual_head_block);
 
@@ -482,7 +700,7 @@ impl Indexer {
             last_log_time = std::time::Instant::now();
 
             // If we hit the head (batch smaller than configured), sleep to avoid tight loop
-            if (batch_size as u64) < self.config.batch_size {
+            if (batch_size as u64) < runtime.batch_size {
                 tracing::debug!("at chain head, sleeping");
                 tokio::time::sleep(Duration::from_secs(1)).await;
             }
@@ -505,22 +723,29 @@ impl Indexer {
         let block = fetched.block;
         let block_num = fetched.number;
 
+        if let Some(raw) = fetched.raw {
+            batch.ra_block_numbers.push(block_num as i64);
+            batch.ra_block_hashes.push(b256_to_hex(&block.header.hash));
+            batch.ra_payloads.push(raw);
+        }
+
         // Build a receipt map keyed by tx hash for O(1) lookup.
         // This lets us merge receipt data (status, gas_used, contract_created)
         // directly into the transaction row, eliminating the UPDATE after INSERT.
         let receipt_map: HashMap<String, &TransactionReceipt> = fetched
             .receipts
             .iter()
-            .map(|r| (format!("{:?}", r.transaction_hash), r))
+            .map(|r| (b256_to_hex(&r.transaction_hash), r))
             .collect();
 
         // --- Block ---
         let tx_count = block.transactions.len() as i32;
+        let block_hash = b256_to_hex(&block.header.hash);
         batch.b_numbers.push(block_num as i64);
-        batch.b_hashes.push(format!("{:?}", block.header.hash));
+        batch.b_hashes.push(block_hash.clone());
         batch
             .b_parent_hashes
-            .push(format!("{:?}", block.header.parent_hash));
+            .push(b256_to_hex(&block.header.parent_hash));
         batch.b_timestamps.push(block.header.timestamp as i64);
         batch.b_gas_used.push(block.header.gas_used as i64);
         batch.b_gas_limits.push(block.header.gas_limit as i64);
@@ -531,14 +756,17 @@ impl Indexer {
                 .map(|base_fee| base_fee.to_string()),
         );
         batch.b_tx_counts.push(tx_count);
+        batch
+            .b_logs_blooms
+            .push(bloom::compact(&block.header.logs_bloom));
 
         // --- Transactions ---
         if let Some(txs) = block.transactions.as_transactions() {
             for (idx, transaction) in txs.iter().enumerate() {
                 let inner = &transaction.inner;
-                let tx_hash_str = format!("{:?}", inner.tx_hash());
-                let from_str = format!("{:?}", transaction.inner.signer());
-                let to_opt = inner.to().map(|a| format!("{:?}", a));
+                let tx_hash_str = b256_to_hex(inner.tx_hash());
+                let from_str = address_to_hex(&transaction.inner.signer());
+                let to_opt = inner.to().map(|a| address_to_hex(&a));
                 let value_str = inner.value().to_string();
                 let gas_price_str = transaction
                     .effective_gas_price
@@ -547,21 +775,23 @@ impl Indexer {
                 let input = inner.input().to_vec();
 
                 // Merge receipt data — no separate UPDATE needed
-                let (status, gas_used, contract_created) = receipt_map
+                let (status, gas_used, contract_created, logs_bloom) = receipt_map
                     .get(&tx_hash_str)
                     .map(|r| {
                         (
                             r.inner.status(),
                             r.gas_used as i64,
-                            r.contract_address.map(|a| format!("{:?}", a)),
+                            r.contract_address.as_ref().map(address_to_hex),
+                            Some(bloom::compact(r.inner.logs_bloom())),
                         )
                     })
-                    .unwrap_or((false, 0, None));
+                    .unwrap_or((false, 0, None, None));
 
                 batch.t_hashes.push(tx_hash_str.clone());
                 batch.t_block_numbers.push(block_num as i64);
                 batch.t_block_indices.push(idx as i32);
                 batch.t_froms.push(from_str.clone());
+                batch.t_nonces.push(inner.nonce() as i64);
                 batch.t_tos.push(to_opt.clone());
                 batch.t_values.push(value_str);
                 batch.t_gas_prices.push(gas_price_str);
@@ -570,9 +800,30 @@ impl Indexer {
                 batch.t_statuses.push(status);
                 batch.t_timestamps.push(block.header.timestamp as i64);
                 batch.t_contracts_created.push(contract_created.clone());
+                batch.t_logs_blooms.push(logs_bloom);
+                batch
+                    .t_l1_fees
+                    .push(fetched.l1_fees.get(&tx_hash_str).cloned());
 
-                batch.tl_hashes.push(tx_hash_str);
+                batch.tl_hashes.push(tx_hash_str.clone());
                 batch.tl_block_numbers.push(block_num as i64);
+                batch.tl_block_hashes.push(block_hash.clone());
+
+                // First funder and deployer; failed transactions moved no value
+                // and created nothing.
+                let provenance = || Provenance {
+                    from: from_str.clone(),
+                    block: block_num as i64,
+                    tx_hash: tx_hash_str.clone(),
+                };
+                if status {
+                    if let Some(to) = to_opt.as_ref().filter(|_| !inner.value().is_zero()) {
+                        batch.record_funding(to.clone(), provenance());
+                    }
+                    if let Some(addr) = &contract_created {
+                        batch.record_creation(addr.clone(), provenance());
+                    }
+                }
 
                 // Sender and receiver each get +1 tx_count.
                 // Newly created contracts are registered as contracts but don't get a tx_count increment.
@@ -590,46 +841,65 @@ impl Indexer {
         for receipt in &fetched.receipts {
             for log in receipt.inner.logs() {
                 let topics = log.topics();
-                let topic0 = match topics.first().map(|t| format!("{:?}", t)) {
+                let topic0 = match topics.first().map(b256_to_hex) {
                     Some(t) => t,
                     None => continue, // skip logs with no topic0
                 };
-                let emitter = format!("{:?}", log.address());
+                let emitter = address_to_hex(&log.address());
 
-                batch.el_tx_hashes.push(
-                    log.transaction_hash
-                        .map(|h| format!("{:?}", h))
-                        .unwrap_or_default(),
-                );
-                batch.el_log_indices.push(log.log_index.unwrap_or(0) as i32);
-                batch.el_addresses.push(emitter.clone());
-                batch.el_topic0s.push(topic0.clone());
-                batch
-                    .el_topic1s
-                    .push(topics.get(1).map(|t| format!("{:?}", t)));
                 batch
-                    .el_topic2s
-                    .push(topics.get(2).map(|t| format!("{:?}", t)));
-                batch
-                    .el_topic3s
-                    .push(topics.get(3).map(|t| format!("{:?}", t)));
+                    .el_tx_hashes
+                    .push(log.transaction_hash.map(|h| h.to_vec()).unwrap_or_default());
+                batch.el_log_indices.push(log.log_index.unwrap_or(0) as i32);
+                batch.el_addresses.push(log.address().to_vec());
+                batch.el_topic0s.push(topics[0].to_vec());
+                batch.el_topic1s.push(topics.get(1).map(|t| t.to_vec()));
+                batch.el_topic2s.push(topics.get(2).map(|t| t.to_vec()));
+                batch.el_topic3s.push(topics.get(3).map(|t| t.to_vec()));
                 batch.el_datas.push(log.data().data.to_vec());
                 batch.el_block_numbers.push(block_num as i64);
 
                 // Any address that emits logs is a contract
                 batch.touch_addr(emitter.clone(), block_num as i64, true, 0);
 
-                if topic0 != TRANSFER_TOPIC {
+                // ERC-20: Approval(address indexed owner, address indexed spender, uint256 value).
+                // ERC-721 indexes the token id as a fourth topic and is skipped.
+                if topic0 == APPROVAL_TOPIC && topics.len() == 3 && log.data().data.len() >= 32 {
+                    batch.record_approval(
+                        topic_to_address_hex(&topics[1]),
+                        emitter.clone(),
+                        topic_to_address_hex(&topics[2]),
+                        ApprovalState {
+                            value: U256::from_be_slice(&log.data().data[..32]).to_string(),
+                            tx_hash: log
+                                .transaction_hash
+                                .as_ref()
+                                .map(b256_to_hex)
+                                .unwrap_or_default(),
+                            log_index: log.log_index.unwrap_or(0) as i32,
+                            block: block_num as i64,
+                        },
+                    );
                     continue;
                 }
 
-                match topics.len() {
-                    // ERC-721: Transfer(address indexed from, address indexed to, uint256 indexed tokenId)
-                    4 => {
+                let wrapped_native = batch.wrapped_native.contains(&emitter);
+                match decode_transfer_log(topics, &log.data().data, wrapped_native) {
+                    Some(TransferLog::Erc20 { from, to, value }) => {
+                        Self::collect_erc20_transfer(
+                            batch,
+                            known_erc20,
+                            log,
+                            from,
+                            to,
+                            value,
+                            block_num,
+                            block.header.timestamp,
+                        );
+                    }
+                    Some(TransferLog::Nft { from, to, token_id }) => {
                         let contract = emitter.clone();
-                        let from = format!("0x{}", hex::encode(&topics[1].as_slice()[12..]));
-                        let to = format!("0x{}", hex::encode(&topics[2].as_slice()[12..]));
-                        let token_id_str = U256::from_be_slice(topics[3].as_slice()).to_string();
+                        let token_id_str = token_id.to_string();
 
                         if !known_nft.contains(&contract) && batch.new_nft.insert(contract.clone())
                         {
@@ -640,7 +910,8 @@ impl Indexer {
 
                         batch.nt_tx_hashes.push(
                             log.transaction_hash
-                                .map(|h| format!("{:?}", h))
+                                .as_ref()
+                                .map(b256_to_hex)
                                 .unwrap_or_default(),
                         );
                         batch.nt_log_indices.push(log.log_index.unwrap_or(0) as i32);
@@ -660,64 +931,7 @@ impl Indexer {
                             },
                         );
                     }
-                    // ERC-20: Transfer(address indexed from, address indexed to, uint256 value)
-                    3 if log.data().data.len() >= 32 => {
-                        let contract = emitter.clone();
-                        let from = format!("0x{}", hex::encode(&topics[1].as_slice()[12..]));
-                        let to = format!("0x{}", hex::encode(&topics[2].as_slice()[12..]));
-                        let value = BigDecimal::from_str(
-                            &U256::from_be_slice(&log.data().data[..32]).to_string(),
-                        )
-                        .unwrap_or_default();
-
-                        // Register new contract without blocking RPC calls —
-                        // the metadata fetcher will fill in name/symbol/decimals.
-                        if !known_erc20.contains(&contract)
-                            && batch.new_erc20.insert(contract.clone())
-                        {
-                            batch.ec_addresses.push(contract.clone());
-                            batch.ec_first_seen_blocks.push(block_num as i64);
-                            batch.touch_addr(contract.clone(), block_num as i64, true, 0);
-                        }
-
-                        batch.et_tx_hashes.push(
-                            log.transaction_hash
-                                .map(|h| format!("{:?}", h))
-                                .unwrap_or_default(),
-                        );
-                        batch.et_log_indices.push(log.log_index.unwrap_or(0) as i32);
-                        batch.et_contracts.push(contract.clone());
-                        batch.et_froms.push(from.clone());
-                        batch.et_tos.push(to.clone());
-                        batch.et_values.push(value.to_string());
-                        batch.et_block_numbers.push(block_num as i64);
-                        batch.et_timestamps.push(block.header.timestamp as i64);
-
-                        // Aggregate balance deltas — multiple transfers in the same batch
-                        // for the same (address, contract) pair are summed in Rust,
-                        // so we only need one DB upsert per unique pair.
-                        if from == ZERO_ADDRESS {
-                            batch.apply_supply_delta(contract.clone(), value.clone());
-                        } else {
-                            batch.apply_balance_delta(
-                                from,
-                                contract.clone(),
-                                -value.clone(),
-                                block_num as i64,
-                            );
-                        }
-                        if to == ZERO_ADDRESS {
-                            batch.apply_supply_delta(contract.clone(), -value);
-                        } else {
-                            batch.apply_balance_delta(
-                                to,
-                                contract.clone(),
-                                value,
-                                block_num as i64,
-                            );
-                        }
-                    }
-                    _ => {}
+                    None => {}
                 }
             }
         }
@@ -725,9 +939,66 @@ impl Indexer {
         batch.last_block = block_num;
     }
 
+    /// Record an ERC-20 transfer emitted by `log`, with its balance deltas.
+    /// Mints come from and burns go to the zero address, which has no
+    /// balance but changes the total supply.
+    #[allow(clippy::too_many_arguments)]
+    fn collect_erc20_transfer(
+        batch: &mut BlockBatch,
+        known_erc20: &HashSet<String>,
+        log: &alloy::rpc::types::Log,
+        from: String,
+        to: String,
+        value: U256,
+        block_num: u64,
+        timestamp: u64,
+    ) {
+        let contract = address_to_hex(&log.address());
+        let value = BigDecimal::from_str(&value.to_string()).unwrap_or_default();
+
+        // Register new contract without blocking RPC calls —
+        // the metadata fetcher will fill in name/symbol/decimals.
+        if !known_erc20.contains(&contract) && batch.new_erc20.insert(contract.clone()) {
+            batch.ec_addresses.push(contract.clone());
+            batch.ec_first_seen_blocks.push(block_num as i64);
+            batch.touch_addr(contract.clone(), block_num as i64, true, 0);
+        }
+
+        batch.et_tx_hashes.push(
+            log.transaction_hash
+                .as_ref()
+                .map(b256_to_hex)
+                .unwrap_or_default(),
+        );
+        batch.et_log_indices.push(log.log_index.unwrap_or(0) as i32);
+        batch.et_contracts.push(contract.clone());
+        batch.et_froms.push(from.clone());
+        batch.et_tos.push(to.clone());
+        batch.et_values.push(value.to_string());
+        batch.et_block_numbers.push(block_num as i64);
+        batch.et_timestamps.push(timestamp as i64);
+
+        // Aggregate balance deltas — multiple transfers in the same batch
+        // for the same (address, contract) pair are summed in Rust,
+        // so we only need one DB upsert per unique pair.
+        if from == ZERO_ADDRESS {
+            batch.apply_supply_delta(contract.clone(), value.clone(), block_num as i64);
+        } else {
+            batch.apply_balance_delta(from, contract.clone(), -value.clone(), block_num as i64);
+        }
+        if to == ZERO_ADDRESS {
+            batch.apply_supply_delta(contract, -value, block_num as i64);
+        } else {
+            batch.apply_balance_delta(to, contract, value, block_num as i64);
+        }
+    }
+
     // -----------------------------------------------------------------------
     // write_batch — one DB transaction, one UNNEST query per table.
     // For a batch of N blocks this is ~11 round-trips regardless of N.
+    // Writing a block again is safe: rows are upserted and the deltas of
+    // blocks already in processed_blocks with the same hash are skipped; a
+    // block number rewritten with another hash replaces the old block.
     // -----------------------------------------------------------------------
 
     pub(crate) async fn write_batch(
@@ -746,9 +1017,14 @@ impl Indexer {
         Self::write_batch_internal(copy_client, batch, false, Some(failed_block_number)).await
     }
 
+    #[tracing::instrument(
+        name = "db.write_batch",
+        skip_all,
+        fields(blocks = batch.b_numbers.len(), last_block = batch.last_block)
+    )]
     async fn write_batch_internal(
         copy_client: &mut Client,
-        batch: BlockBatch,
+        mut batch: BlockBatch,
         update_watermark: bool,
         clear_failed_block_number: Option<i64>,
     ) -> Result<()> {
@@ -759,15 +1035,107 @@ impl Indexer {
         let mut pg_tx = copy_client.transaction().await?;
         let indexed_at: DateTime<Utc> = Utc::now();
 
+        // Claim the blocks before anything else. A block that is already in
+        // processed_blocks with the same hash was written by an earlier (or
+        // concurrent) batch: its rows are upserted again harmlessly, but its
+        // tx_count, balance and supply deltas must not be added a second
+        // time. A block number written before with another hash is replaced:
+        // the old block's deltas are reversed and its rows removed. Claiming
+        // in block order also serializes overlapping writers on the same rows.
+        let mut blocks: Vec<(i64, String)> = batch
+            .b_numbers
+            .iter()
+            .copied()
+            .zip(batch.b_hashes.iter().cloned())
+            .collect();
+        blocks.sort_unstable();
+        let (numbers, hashes): (Vec<i64>, Vec<String>) = blocks.into_iter().unzip();
+        let claims: Vec<(i64, Claim)> = pg_tx
+            .query(
+                "WITH input AS (
+                     SELECT * FROM unnest($1::bigint[], $2::text[]) AS t(number, hash)
+                 ),
+                 previous AS (
+                     SELECT p.number, p.hash FROM processed_blocks p JOIN input USING (number)
+                 ),
+                 claimed AS (
+                     INSERT INTO processed_blocks (number, hash)
+                     SELECT number, hash FROM input ORDER BY number
+                     ON CONFLICT (number) DO UPDATE SET hash = EXCLUDED.hash
+                     WHERE processed_blocks.hash IS DISTINCT FROM EXCLUDED.hash
+                     RETURNING number
+                 )
+                 SELECT c.number, p.number IS NOT NULL, p.hash
+                 FROM claimed c LEFT JOIN previous p USING (number)",
+                &[&numbers, &hashes],
+            )
+            .instrument(upsert_span("processed_blocks"))
+            .await?
+            .iter()
+            .map(|row| {
+                let claim = match (row.get::<_, bool>(1), row.get::<_, Option<String>>(2)) {
+                    (false, _) => Claim::New,
+                    (true, Some(_)) => Claim::Replaced,
+                    // Claimed before hashes were recorded: only the hash is new.
+                    (true, None) => Claim::Replayed,
+                };
+                (row.get(0), claim)
+            })
+            .collect();
+
+        let replaced: Vec<i64> = claims
+            .iter()
+            .filter(|(_, claim)| *claim == Claim::Replaced)
+            .map(|(number, _)| *number)
+            .collect();
+        if !replaced.is_empty() {
+            tracing::info!(blocks = ?replaced, "replacing blocks rewritten with another hash");
+            reverse_replaced_blocks(&mut pg_tx, &batch, &replaced).await?;
+        }
+
+        let claimed: HashSet<i64> = claims
+            .iter()
+            .filter(|(_, claim)| *claim != Claim::Replayed)
+            .map(|(number, _)| *number)
+            .collect();
+        if claimed.len() < numbers.len() {
+            let replayed: HashSet<i64> = numbers
+                .into_iter()
+                .filter(|number| !claimed.contains(number))
+                .collect();
+            tracing::debug!(
+                replayed = replayed.len(),
+                "skipping deltas of blocks that were already written"
+            );
+            batch.discard_deltas(&replayed);
+
+            // Transfers of replayed blocks below the retention cutoff are
+            // already counted in erc20_transfer_summaries.
+            let aggregated_before = pg_tx
+                .query_opt(
+                    "SELECT value::bigint FROM indexer_state WHERE key = $1",
+                    &[&TRANSFER_AGGREGATION_BEFORE_BLOCK_KEY],
+                )
+                .await?
+                .map(|row| row.get::<_, i64>(0));
+            if let Some(before) = aggregated_before {
+                batch.discard_aggregated_transfers(&replayed, before);
+            }
+        }
+
         copy_blocks(&mut pg_tx, &batch, indexed_at).await?;
         copy_transactions(&mut pg_tx, &batch).await?;
         copy_event_logs(&mut pg_tx, &batch).await?;
         copy_nft_transfers(&mut pg_tx, &batch).await?;
         copy_erc20_transfers(&mut pg_tx, &batch).await?;
+        copy_raw_archive(&mut pg_tx, &batch).await?;
 
         let BlockBatch {
+            b_numbers,
+            b_hashes,
             tl_hashes,
             tl_block_numbers,
+            tl_block_hashes,
             addr_map,
             nft_contract_addrs,
             nft_contract_first_seen,
@@ -775,20 +1143,60 @@ impl Indexer {
             ec_addresses,
             ec_first_seen_blocks,
             balance_map,
+            approval_map,
             supply_map,
+            processor_writes,
             last_block,
             ..
         } = batch;
 
+        // Every address whose `addresses` or token contract row this batch
+        // writes; their directory rows are re-derived once those are written.
+        let directory_addrs: Vec<String> = addr_map
+            .keys()
+            .chain(&nft_contract_addrs)
+            .chain(&ec_addresses)
+            .cloned()
+            .collect();
+
+        // A hash is canonical in the block it was last written with: lookups
+        // of a block number rewritten with another hash (a reorg) and other
+        // blocks holding the same hash lose the flag.
+        pg_tx
+            .execute(
+                "UPDATE tx_hash_lookup l SET canonical = false
+                 FROM unnest($1::bigint[], $2::text[]) AS b(number, hash)
+                 WHERE l.block_number = b.number AND l.canonical
+                   AND l.block_hash IS DISTINCT FROM b.hash",
+                &[&b_numbers, &b_hashes],
+            )
+            .instrument(upsert_span("tx_hash_lookup"))
+            .await?;
         if !tl_hashes.is_empty() {
-            let params: [&(dyn ToSql + Sync); 2] = [&tl_hashes, &tl_block_numbers];
             pg_tx
                 .execute(
-                    "INSERT INTO tx_hash_lookup (hash, block_number)
-                 SELECT * FROM unnest($1::text[], $2::bigint[]) AS t(hash, block_number)
-                 ON CONFLICT (hash) DO NOTHING",
+                    "UPDATE tx_hash_lookup l SET canonical = false
+                     FROM unnest($1::text[], $2::bigint[]) AS t(hash, block_number)
+                     WHERE l.hash = t.hash AND l.block_number <> t.block_number AND l.canonical",
+                    &[&tl_hashes, &tl_block_numbers],
+                )
+                .instrument(upsert_span("tx_hash_lookup"))
+                .await?;
+            let params: [&(dyn ToSql + Sync); 3] =
+                [&tl_hashes, &tl_block_numbers, &tl_block_hashes];
+            pg_tx
+                .execute(
+                    "INSERT INTO tx_hash_lookup (hash, block_number, block_hash, canonical)
+                 SELECT hash, block_number, block_hash, true
+                 FROM unnest($1::text[], $2::bigint[], $3::text[]) AS t(hash, block_number, block_hash)
+                 ON CONFLICT (hash, block_number) DO UPDATE SET
+                    block_hash = EXCLUDED.block_hash,
+                    canonical = true
+                 WHERE tx_hash_lookup.block_hash IS DISTINCT FROM EXCLUDED.block_hash
+                    OR NOT tx_hash_lookup.canonical",
                     &params,
                 )
+                .instrument(upsert_span("tx_hash_lookup"))
                 .await?;
         }
 
@@ -797,26 +1205,77 @@ impl Indexer {
             let mut a_contracts = Vec::with_capacity(addr_map.len());
             let mut a_first_seen = Vec::with_capacity(addr_map.len());
             let mut a_tx_counts = Vec::with_capacity(addr_map.len());
+            let mut a_funded_by = Vec::with_capacity(addr_map.len());
+            let mut a_funded_blocks = Vec::with_capacity(addr_map.len());
+            let mut a_funding_txs = Vec::with_capacity(addr_map.len());
+            let mut a_deployers = Vec::with_capacity(addr_map.len());
+            let mut a_creation_blocks = Vec::with_capacity(addr_map.len());
+            let mut a_creation_txs = Vec::with_capacity(addr_map.len());
             for (addr, state) in addr_map {
                 a_addrs.push(addr);
                 a_contracts.push(state.is_contract);
                 a_first_seen.push(state.first_seen_block);
                 a_tx_counts.push(state.tx_count_delta);
+                let (funded_by, funded_block, funding_tx) =
+                    state.funding.map_or((None, None, None), |p| {
+                        (Some(p.from), Some(p.block), Some(p.tx_hash))
+                    });
+                a_funded_by.push(funded_by);
+                a_funded_blocks.push(funded_block);
+                a_funding_txs.push(funding_tx);
+                let (deployer, creation_block, creation_tx) =
+                    state.creation.map_or((None, None, None), |p| {
+                        (Some(p.from), Some(p.block), Some(p.tx_hash))
+                    });
+                a_deployers.push(deployer);
+                a_creation_blocks.push(creation_block);
+                a_creation_txs.push(creation_tx);
             }
 
-            let params: [&(dyn ToSql + Sync); 4] =
-                [&a_addrs, &a_contracts, &a_first_seen, &a_tx_counts];
+            // Provenance columns keep the earliest evidence: a gap fill can
+            // index an older block after a newer one.
+            let params: [&(dyn ToSql + Sync); 10] = [
+                &a_addrs,
+                &a_contracts,
+                &a_first_seen,
+                &a_tx_counts,
+                &a_funded_by,
+                &a_funded_blocks,
+                &a_funding_txs,
+                &a_deployers,
+                &a_creation_blocks,
+                &a_creation_txs,
+            ];
             pg_tx.execute(
-                "INSERT INTO addresses (address, is_contract, first_seen_block, tx_count)
-                 SELECT * FROM unnest($1::text[], $2::bool[], $3::bigint[], $4::bigint[])
-                    AS t(address, is_contract, first_seen_block, tx_count)
+                "INSERT INTO addresses (address, is_contract, first_seen_block, tx_count,
+                                        funded_by, funded_block, funding_tx_hash,
+                                        deployer, creation_block, creation_tx_hash)
+                 SELECT * FROM unnest($1::text[], $2::bool[], $3::bigint[], $4::bigint[],
+                                      $5::text[], $6::bigint[], $7::text[],
+                                      $8::text[], $9::bigint[], $10::text[])
+                    AS t(address, is_contract, first_seen_block, tx_count,
+                         funded_by, funded_block, funding_tx_hash,
+                         deployer, creation_block, creation_tx_hash)
                  ON CONFLICT (address) DO UPDATE SET
                     tx_count = addresses.tx_count + EXCLUDED.tx_count,
                     is_contract = addresses.is_contract OR EXCLUDED.is_contract,
-                    first_seen_block = LEAST(addresses.first_seen_block, EXCLUDED.first_seen_block)",
+                    first_seen_block = LEAST(addresses.first_seen_block, EXCLUDED.first_seen_block),
+                    funded_by = CASE WHEN addresses.funded_block IS NULL OR EXCLUDED.funded_block < addresses.funded_block
+                        THEN EXCLUDED.funded_by ELSE addresses.funded_by END,
+                    funding_tx_hash = CASE WHEN addresses.funded_block IS NULL OR EXCLUDED.funded_block < addresses.funded_block
+                        THEN EXCLUDED.funding_tx_hash ELSE addresses.funding_tx_hash END,
+                    funded_block = CASE WHEN addresses.funded_block IS NULL OR EXCLUDED.funded_block < addresses.funded_block
+                        THEN EXCLUDED.funded_block ELSE addresses.funded_block END,
+                    deployer = CASE WHEN addresses.creation_block IS NULL OR EXCLUDED.creation_block < addresses.creation_block
+                        THEN EXCLUDED.deployer ELSE addresses.deployer END,
+                    creation_tx_hash = CASE WHEN addresses.creation_block IS NULL OR EXCLUDED.creation_block < addresses.creation_block
+                        THEN EXCLUDED.creation_tx_hash ELSE addresses.creation_tx_hash END,
+                    creation_block = CASE WHEN addresses.creation_block IS NULL OR EXCLUDED.creation_block < addresses.creation_block
+                        THEN EXCLUDED.creation_block ELSE addresses.creation_block END",
                 &params,
             )
-            .await?;
+            .instrument(upsert_span("addresses"))
+                .await?;
         }
 
         if !nft_contract_addrs.is_empty() {
@@ -828,6 +1287,7 @@ impl Indexer {
                  ON CONFLICT (address) DO NOTHING",
                     &params,
                 )
+                .instrument(upsert_span("nft_contracts"))
                 .await?;
         }
 
@@ -867,7 +1327,8 @@ impl Indexer {
                     last_transfer_block = GREATEST(nft_tokens.last_transfer_block, EXCLUDED.last_transfer_block)",
                 &params,
             )
-            .await?;
+            .instrument(upsert_span("nft_tokens"))
+                .await?;
         }
 
         if !ec_addresses.is_empty() {
@@ -880,6 +1341,7 @@ impl Indexer {
                  ON CONFLICT (address) DO NOTHING",
                     &params,
                 )
+                .instrument(upsert_span("erc20_contracts"))
                 .await?;
         }
 
@@ -908,7 +1370,71 @@ impl Indexer {
                     last_updated_block = GREATEST(erc20_balances.last_updated_block, EXCLUDED.last_updated_block)",
                 &params,
             )
-            .await?;
+            .instrument(upsert_span("erc20_balances"))
+                .await?;
+
+            // A missing row means a zero balance, so drop the rows this batch
+            // brought to exactly zero instead of keeping them forever.
+            let params: [&(dyn ToSql + Sync); 2] = [&bal_addrs, &bal_contracts];
+            let pruned = pg_tx
+                .execute(
+                    "DELETE FROM erc20_balances b
+                     USING unnest($1::text[], $2::text[]) AS t(address, contract_address)
+                     WHERE b.address = t.address AND b.contract_address = t.contract_address
+                       AND b.balance = 0",
+                    &params,
+                )
+                .instrument(tracing::info_span!(
+                    "db.delete",
+                    db.table = "erc20_balances"
+                ))
+                .await?;
+            metrics::counter!("atlas_indexer_erc20_zero_balances_pruned_total").increment(pruned);
+        }
+
+        if !approval_map.is_empty() {
+            let mut ap_owners = Vec::with_capacity(approval_map.len());
+            let mut ap_contracts = Vec::with_capacity(approval_map.len());
+            let mut ap_spenders = Vec::with_capacity(approval_map.len());
+            let mut ap_values = Vec::with_capacity(approval_map.len());
+            let mut ap_tx_hashes = Vec::with_capacity(approval_map.len());
+            let mut ap_log_indices = Vec::with_capacity(approval_map.len());
+            let mut ap_blocks = Vec::with_capacity(approval_map.len());
+            for ((owner, contract, spender), approval) in approval_map {
+                ap_owners.push(owner);
+                ap_contracts.push(contract);
+                ap_spenders.push(spender);
+                ap_values.push(approval.value);
+                ap_tx_hashes.push(approval.tx_hash);
+                ap_log_indices.push(approval.log_index);
+                ap_blocks.push(approval.block);
+            }
+
+            let params: [&(dyn ToSql + Sync); 7] = [
+                &ap_owners,
+                &ap_contracts,
+                &ap_spenders,
+                &ap_values,
+                &ap_tx_hashes,
+                &ap_log_indices,
+                &ap_blocks,
+            ];
+            pg_tx.execute(
+                "INSERT INTO erc20_approvals (owner, contract_address, spender, value, tx_hash, log_index, block_number)
+                 SELECT owner, contract_address, spender, value::numeric, tx_hash, log_index, block_number
+                 FROM unnest($1::text[], $2::text[], $3::text[], $4::text[], $5::text[], $6::int4[], $7::bigint[])
+                    AS t(owner, contract_address, spender, value, tx_hash, log_index, block_number)
+                 ON CONFLICT (owner, contract_address, spender) DO UPDATE SET
+                    value = EXCLUDED.value,
+                    tx_hash = EXCLUDED.tx_hash,
+                    log_index = EXCLUDED.log_index,
+                    block_number = EXCLUDED.block_number
+                 WHERE (EXCLUDED.block_number, EXCLUDED.log_index)
+                    >= (erc20_approvals.block_number, erc20_approvals.log_index)",
+                &params,
+            )
+            .instrument(upsert_span("erc20_approvals"))
+                .await?;
         }
 
         if !supply_map.is_empty() {
@@ -916,7 +1442,7 @@ impl Indexer {
             let mut supply_deltas = Vec::with_capacity(supply_map.len());
             for (contract, delta) in supply_map {
                 supply_contracts.push(contract);
-                supply_deltas.push(delta.to_string());
+                supply_deltas.push(delta.delta.to_string());
             }
 
             let params: [&(dyn ToSql + Sync); 2] = [&supply_contracts, &supply_deltas];
@@ -928,9 +1454,33 @@ impl Indexer {
                  WHERE c.address = s.contract_address",
                     &params,
                 )
+                .instrument(upsert_span("erc20_contracts"))
+                .await?;
+        }
+
+        if !directory_addrs.is_empty() {
+            pg_tx
+                .execute(address_directory::REFRESH_SQL, &[&directory_addrs])
+                .instrument(upsert_span("address_directory"))
                 .await?;
         }
 
+        for statement in processor_writes.statements {
+            let params: Vec<&(dyn ToSql + Sync)> = statement
+                .params
+                .iter()
+                .map(|param| param.as_ref() as &(dyn ToSql + Sync))
+                .collect();
+            pg_tx
+                .execute(statement.sql.as_str(), &params)
+                .instrument(tracing::info_span!(
+                    "db.processor",
+                    processor = statement.processor
+                ))
+                .await
+                .with_context(|| format!("block processor '{}' failed", statement.processor))?;
+        }
+
         if update_watermark {
             let last_value = last_block.to_string();
             pg_tx
@@ -940,6 +1490,7 @@ impl Indexer {
                  ON CONFLICT (key) DO UPDATE SET value = $1, updated_at = $2",
                     &[&last_value, &indexed_at],
                 )
+                .instrument(upsert_span("indexer_state"))
                 .await?;
         }
 
@@ -949,6 +1500,7 @@ impl Indexer {
                     "DELETE FROM failed_blocks WHERE block_number = $1",
                     &[&block_number],
                 )
+                .instrument(upsert_span("failed_blocks"))
                 .await?;
             anyhow::ensure!(
                 deleted == 1,
@@ -993,6 +1545,78 @@ impl Indexer {
         }
     }
 
+    /// On a fresh index with backfill shards configured, split the blocks
+    /// below the chain head into `range_leases` for the backfill workers and
+    /// continue from the head. Returns the block to index from and whether a
+    /// backfill has been planned, now or by an earlier run.
+    async fn plan_backfill(
+        &self,
+        provider: &RootProvider,
+        start_block: u64,
+    ) -> Result<(u64, bool)> {
+        let split: Option<(String,)> =
+            sqlx::query_as("SELECT value FROM indexer_state WHERE key = $1")
+                .bind(BACKFILL_SPLIT_BLOCK_KEY)
+                .fetch_optional(&self.pool)
+                .await?;
+        if split.is_some() {
+            return Ok((start_block, true));
+        }
+        let (indexed,): (bool,) = sqlx::query_as(
+            "SELECT EXISTS (SELECT 1 FROM indexer_state WHERE key = 'last_indexed_block')",
+        )
+        .fetch_one(&self.pool)
+        .await?;
+        if self.config.backfill_shards == 0 || indexed {
+            return Ok((start_block, false));
+        }
+
+        let head = get_block_number_with_retry(provider, &self.metrics).await?;
+        let range = self.config.backfill_range_blocks;
+        if head <= start_block + range {
+            return Ok((start_block, false));
+        }
+
+        let mut tx = self.pool.begin().await?;
+        let (ranges,): (i64,) = sqlx::query_as(
+            "WITH planned AS (
+                 INSERT INTO range_leases (start_block, end_block, next_block)
+                 SELECT s, LEAST(s + $3, $2), s FROM generate_series($1::bigint, $2 - 1, $3) AS s
+                 ON CONFLICT (start_block) DO NOTHING
+                 RETURNING 1
+             )
+             SELECT COUNT(*) FROM planned",
+        )
+        .bind(start_block as i64)
+        .bind(head as i64)
+        .bind(range as i64)
+        .fetch_one(&mut *tx)
+        .await?;
+        for (key, value) in [
+            (BACKFILL_SPLIT_BLOCK_KEY, head),
+            ("last_indexed_block", head - 1),
+        ] {
+            sqlx::query(
+                "INSERT INTO indexer_state (key, value, updated_at)
+                 VALUES ($1, $2, NOW())
+                 ON CONFLICT (key) DO UPDATE SET value = EXCLUDED.value, updated_at = EXCLUDED.updated_at",
+            )
+            .bind(key)
+            .bind(value.to_string())
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+
+        tracing::info!(
+            start_block,
+            split_block = head,
+            ranges,
+            "planned parallel backfill; following the head from the split block"
+        );
+        Ok((head, true))
+    }
+
     async fn get_missing_block_count(&self) -> Result<u64> {
         let count: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM failed_blocks")
             .fetch_one(&self.pool)
@@ -1000,15 +1624,108 @@ impl Indexer {
         Ok(count.0.max(0) as u64)
     }
 
-    async fn ensure_partitions_exist(&self, block_number: u64) -> Result<()> {
-        ensure_partitions_exist(&self.pool, &self.current_max_partition, block_number).await
+    async fn ensure_partitions_exist(&self, block_number: u64, stage: bool) -> Result<bool> {
+        ensure_partitions_exist(&self.pool, &self.current_max_partition, block_number, stage).await
+    }
+
+    /// Index the contiguous run of `raw_block_archive` rows starting at
+    /// `start_block` through the same `collect_block`/`write_batch` path as
+    /// fetched blocks. Stops at the first block missing from the archive (or
+    /// whose payload no longer decodes) and returns it, so the RPC loop picks
+    /// up from there.
+    pub async fn replay_archive(&self, start_block: u64) -> Result<u64> {
+        tracing::info!(start_block, "replaying raw block archive");
+        let mut copy_client = Self::connect_copy_client(&self.config.database_url).await?;
+        let mut known_erc20 = self.load_known_erc20().await?;
+        let mut known_nft = self.load_known_nft().await?;
+        let mut next_block = start_block;
+
+        loop {
+            // One partition per batch: it may be staged (see `partitions`).
+            let limit = self
+                .config
+                .batch_size
+                .min(PARTITION_SIZE - next_block % PARTITION_SIZE);
+            let rows: Vec<(i64, Vec<u8>)> = sqlx::query_as(
+                "SELECT block_number, payload FROM raw_block_archive
+                 WHERE block_number >= $1
+                 ORDER BY block_number
+                 LIMIT $2",
+            )
+            .bind(next_block as i64)
+            .bind(limit as i64)
+            .fetch_all(&self.pool)
+            .await?;
+
+            let mut batch = BlockBatch::new();
+            batch.wrapped_native = self.wrapped_native.clone();
+            let mut stop = (rows.len() as u64) < limit;
+            for (block_number, payload) in rows {
+                if block_number as u64 != next_block {
+                    stop = true;
+                    break;
+                }
+                match archive::decode(next_block, &payload) {
+                    Ok(fetched) => {
+                        self.processors.collect(&fetched, &mut batch);
+                        Self::collect_block(&mut batch, &known_erc20, &known_nft, fetched);
+                        next_block += 1;
+                    }
+                    Err(e) => {
+                        tracing::warn!(block = next_block, error = %e, "archived block does not decode");
+                        stop = true;
+                        break;
+                    }
+                }
+            }
+
+            if !batch.b_numbers.is_empty() {
+                self.ensure_partitions_exist(next_block - 1, false).await?;
+                batch.staged_partition =
+                    partitions::staged_partition_of(&self.pool, next_block - 1).await?;
+                let new_erc20 = std::mem::take(&mut batch.new_erc20);
+                let new_nft = std::mem::take(&mut batch.new_nft);
+                let blocks = batch.b_numbers.len() as u64;
+                let head_block_timestamp = batch.last_block_timestamp();
+                let committed_blocks = batch.materialize_blocks(Utc::now());
+                self.head_tracker
+                    .publish_committed_batch(committed_blocks)
+                    .await;
+                let _ = self.block_events_tx.send(());
+
+                Self::write_batch(&mut copy_client, batch, true).await?;
+                known_erc20.extend(new_erc20);
+                known_nft.extend(new_nft);
+
+                self.metrics.record_blocks_indexed(blocks);
+                self.metrics.set_indexer_head_block(next_block - 1);
+                if let Some(timestamp) = head_block_timestamp {
+                    self.metrics.set_indexer_head_block_timestamp(timestamp);
+                }
+                tracing::debug!(up_to = next_block - 1, "replayed archived blocks");
+            }
+
+            if stop {
+                break;
+            }
+        }
+
+        tracing::info!(
+            replayed = next_block - start_block,
+            resume_block = next_block,
+            "raw block archive replay complete"
+        );
+        Ok(next_block)
     }
 
     async fn truncate_tables(&self) -> Result<()> {
+        partitions::drop_staged(&self.pool).await?;
         sqlx::query(
             "TRUNCATE blocks, transactions, addresses, nft_contracts, nft_tokens, nft_transfers,
-             erc20_contracts, erc20_transfers, erc20_balances, event_logs, proxy_contracts,
-             indexer_state, failed_blocks CASCADE",
+             erc20_contracts, erc20_transfers, erc20_transfer_summaries, erc20_balances, erc20_approvals, erc20_holder_ranks, erc20_holder_rank_state, event_logs, proxy_contracts,
+             indexer_state, failed_blocks, derived_address_labels, raw_rpc_cache,
+             genesis_accounts, daily_stats, processed_blocks, canonical_tokens, data_quality_issues, range_leases,
+             address_directory CASCADE",
         )
         .execute(&self.pool)
         .await?;
@@ -1045,11 +1762,15 @@ impl Indexer {
 /// valid partition index (blocks 0–9 999 999 live in `blocks_p0`).
 pub(crate) const UNKNOWN_MAX_PARTITION: u64 = u64::MAX;
 
+/// Create the partitions up to the one holding `block_number`. With `stage`,
+/// that partition is created staged if it is new (see [`partitions`]); the
+/// return value tells whether it was.
 pub(crate) async fn ensure_partitions_exist(
     pool: &sqlx::PgPool,
     current_max: &std::sync::atomic::AtomicU64,
     block_number: u64,
-) -> Result<()> {
+    stage: bool,
+) -> Result<bool> {
     use std::sync::atomic::Ordering;
 
     let partition_num = block_number / PARTITION_SIZE;
@@ -1057,7 +1778,7 @@ pub(crate) async fn ensure_partitions_exist(
 
     // Fast path: cache is initialised and the required partition already exists.
     if current_max_val != UNKNOWN_MAX_PARTITION && partition_num <= current_max_val {
-        return Ok(());
+        return Ok(false);
     }
 
     // First run (cache uninitialized) or crossing a partition boundary — need to
@@ -1077,7 +1798,7 @@ pub(crate) async fn ensure_partitions_exist(
             Some(max) => {
                 current_max.store(max as u64, Ordering::Relaxed);
                 if partition_num <= max as u64 {
-                    return Ok(());
+                    return Ok(false);
                 }
                 max as u64 + 1
             }
@@ -1099,15 +1820,12 @@ pub(crate) async fn ensure_partitions_exist(
             "creating partitions"
         );
 
-        let tables = [
-            "blocks",
-            "transactions",
-            "event_logs",
-            "nft_transfers",
-            "erc20_transfers",
-        ];
+        if stage && p == partition_num {
+            partitions::create_staged(pool, p).await?;
+            continue;
+        }
 
-        for table in tables {
+        for table in PARTITIONED_TABLES {
             let create_sql = format!(
                 "CREATE TABLE IF NOT EXISTS {}_p{} PARTITION OF {} FOR VALUES FROM ({}) TO ({})",
                 table, p, table, partition_start, partition_end
@@ -1118,9 +1836,161 @@ pub(crate) async fn ensure_partitions_exist(
 
     current_max.store(partition_num, Ordering::Relaxed);
     tracing::info!(max_partition = partition_num, "partitions ready");
+    Ok(stage)
+}
+
+/// How `write_batch` claimed a block in `processed_blocks`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Claim {
+    /// Never written before: its deltas are applied.
+    New,
+    /// Written before with the same hash: its deltas are skipped.
+    Replayed,
+    /// Written before with another hash: the old block is reversed first.
+    Replaced,
+}
+
+/// Take the tx_count, balance and supply deltas of the blocks now stored
+/// under `numbers` back out, then delete their rows so the replacing blocks
+/// are written in their place. Deltas are derived from the stored rows the
+/// same way block collection derives them. Rows are read from the tables
+/// `batch` writes to, so blocks of a staged partition are found.
+async fn reverse_replaced_blocks(
+    pg_tx: &mut tokio_postgres::Transaction<'_>,
+    batch: &BlockBatch,
+    numbers: &[i64],
+) -> Result<()> {
+    let numbers = numbers.to_vec();
+    let transfers = batch.target("erc20_transfers");
+    pg_tx
+        .execute(
+            &format!(
+                "UPDATE addresses a SET tx_count = a.tx_count - d.count
+                 FROM (
+                     SELECT v.address, COUNT(*) AS count
+                     FROM {}
+                     CROSS JOIN LATERAL (VALUES (transactions.from_address), (transactions.to_address)) AS v(address)
+                     WHERE transactions.block_number = ANY($1) AND v.address IS NOT NULL
+                     GROUP BY v.address
+                 ) d
+                 WHERE a.address = d.address",
+                batch.target("transactions")
+            ),
+            &[&numbers],
+        )
+        .instrument(upsert_span("addresses"))
+        .await?;
+    pg_tx
+        .execute(
+            &format!(
+                "UPDATE erc20_balances b SET balance = b.balance - d.delta
+                 FROM (
+                     SELECT address, contract_address, SUM(delta) AS delta
+                     FROM (
+                         SELECT to_address AS address, contract_address, value AS delta
+                         FROM {transfers}
+                         WHERE block_number = ANY($1) AND to_address <> $2
+                         UNION ALL
+                         SELECT from_address, contract_address, -value
+                         FROM {transfers}
+                         WHERE block_number = ANY($1) AND from_address <> $2
+                     ) moves
+                     GROUP BY address, contract_address
+                 ) d
+                 WHERE b.address = d.address AND b.contract_address = d.contract_address"
+            ),
+            &[&numbers, &ZERO_ADDRESS],
+        )
+        .instrument(upsert_span("erc20_balances"))
+        .await?;
+    pg_tx
+        .execute(
+            &format!(
+                "DELETE FROM erc20_balances b
+                 USING {transfers}
+                 WHERE erc20_transfers.block_number = ANY($1)
+                   AND b.contract_address = erc20_transfers.contract_address
+                   AND b.address IN (erc20_transfers.from_address, erc20_transfers.to_address)
+                   AND b.balance = 0"
+            ),
+            &[&numbers],
+        )
+        .instrument(tracing::info_span!(
+            "db.delete",
+            db.table = "erc20_balances"
+        ))
+        .await?;
+    pg_tx
+        .execute(
+            &format!(
+                "UPDATE erc20_contracts c SET total_supply = COALESCE(c.total_supply, 0) - d.delta
+                 FROM (
+                     SELECT contract_address,
+                            SUM(CASE WHEN from_address = $2 THEN value ELSE -value END) AS delta
+                     FROM {transfers}
+                     WHERE block_number = ANY($1) AND (from_address = $2) <> (to_address = $2)
+                     GROUP BY contract_address
+                 ) d
+                 WHERE c.address = d.contract_address"
+            ),
+            &[&numbers, &ZERO_ADDRESS],
+        )
+        .instrument(upsert_span("erc20_contracts"))
+        .await?;
+    for table in [
+        "transactions",
+        "event_logs",
+        "erc20_transfers",
+        "nft_transfers",
+    ] {
+        pg_tx
+            .execute(
+                &format!(
+                    "DELETE FROM {} WHERE block_number = ANY($1)",
+                    batch.target(table)
+                ),
+                &[&numbers],
+            )
+            .instrument(tracing::info_span!("db.delete", db.table = table))
+            .await?;
+    }
     Ok(())
 }
 
+/// Span for one upsert statement in `write_batch`.
+fn upsert_span(table: &'static str) -> tracing::Span {
+    tracing::info_span!("db.upsert", db.table = table)
+}
+
+fn build_rate_limiter(rps: u32) -> SharedRateLimiter {
+    let rps = NonZeroU32::new(rps).unwrap_or(NonZeroU32::new(100).unwrap());
+    Arc::new(RateLimiter::direct(Quota::per_second(rps)))
+}
+
+/// How long the node has reported the same chain head.
+struct HeadStall {
+    head: Option<u64>,
+    since: std::time::Instant,
+}
+
+impl HeadStall {
+    fn new(now: std::time::Instant) -> Self {
+        Self {
+            head: None,
+            since: now,
+        }
+    }
+
+    /// Record the head seen at `now`; returns how long it has been unchanged.
+    fn observe(&mut self, head: u64, now: std::time::Instant) -> Duration {
+        if self.head != Some(head) {
+            self.head = Some(head);
+            self.since = now;
+        }
+        now.duration_since(self.since)
+    }
+}
+
 fn lag_blocks(chain_head: u64, indexed_head: Option<u64>, start_block: u64) -> u64 {
     match indexed_head {
         Some(indexed_head) => chain_head.saturating_sub(indexed_head),
@@ -1138,6 +2008,8 @@ mod tests {
             number,
             block: alloy::rpc::types::Block::default(),
             receipts: vec![],
+            l1_fees: HashMap::new(),
+            raw: None,
         }
     }
 
@@ -1211,6 +2083,61 @@ mod tests {
         assert!(receiver_delta.delta > 0);
     }
 
+    #[test]
+    fn collect_erc20_approval_keeps_latest_per_spender() {
+        let mut batch = BlockBatch::new();
+        let approval = |value: &str, log_index: &str, topics_extra: Option<&str>| {
+            let mut topics = vec![
+                "0x8c5be1e5ebec7d5bd14f71427d1e84f3dd0314c0f7b2291e5b200ac8c7c3b925",
+                "0x0000000000000000000000001111111111111111111111111111111111111111",
+                "0x0000000000000000000000002222222222222222222222222222222222222222",
+            ];
+            topics.extend(topics_extra);
+            serde_json::json!({
+                "address": "0x3333333333333333333333333333333333333333",
+                "topics": topics,
+                "data": value,
+                "blockNumber": "0x1",
+                "transactionHash": "0x0000000000000000000000000000000000000000000000000000000000000001",
+                "transactionIndex": "0x0",
+                "blockHash": "0x0000000000000000000000000000000000000000000000000000000000000001",
+                "logIndex": log_index,
+                "removed": false
+            })
+        };
+        let unlimited = format!("0x{}", "ff".repeat(32));
+        let logs = serde_json::json!([
+            approval(&unlimited, "0x0", None),
+            approval(
+                "0x00000000000000000000000000000000000000000000000000000000000003e8",
+                "0x1",
+                None
+            ),
+            // ERC-721 Approval: token id as a fourth topic
+            approval(
+                "0x",
+                "0x2",
+                Some("0x0000000000000000000000000000000000000000000000000000000000000007")
+            ),
+        ]);
+
+        let mut fb = empty_fetched_block(1);
+        fb.receipts = vec![make_receipt(logs)];
+        Indexer::collect_block(&mut batch, &HashSet::new(), &HashSet::new(), fb);
+
+        assert_eq!(batch.approval_map.len(), 1);
+        let key = (
+            "0x1111111111111111111111111111111111111111".to_string(),
+            "0x3333333333333333333333333333333333333333".to_string(),
+            "0x2222222222222222222222222222222222222222".to_string(),
+        );
+        assert_eq!(batch.approval_map[&key].value, "1000");
+        assert_eq!(batch.approval_map[&key].log_index, 1);
+        // Approvals are not transfers
+        assert!(batch.et_contracts.is_empty());
+        assert_eq!(batch.el_topic0s.len(), 3);
+    }
+
     #[test]
     fn collect_erc20_mint_skips_zero_address_balance_delta() {
         let mut batch = BlockBatch::new();
@@ -1244,7 +2171,7 @@ mod tests {
         let to = "0x2222222222222222222222222222222222222222";
         assert!(batch.balance_map.contains_key(&(to.to_string(), contract)));
         assert_eq!(
-            batch.supply_map["0x3333333333333333333333333333333333333333"],
+            batch.supply_map["0x3333333333333333333333333333333333333333"].delta,
             BigDecimal::from(1000)
         );
     }
@@ -1277,7 +2204,7 @@ mod tests {
 
         assert_eq!(batch.balance_map.len(), 1);
         assert_eq!(
-            batch.supply_map["0x3333333333333333333333333333333333333333"],
+            batch.supply_map["0x3333333333333333333333333333333333333333"].delta,
             BigDecimal::from(-1000)
         );
     }
@@ -1395,6 +2322,99 @@ mod tests {
         assert!(batch.balance_map.is_empty());
     }
 
+    #[test]
+    fn wrapped_native_topics_match_event_signatures() {
+        use alloy::primitives::keccak256;
+        assert_eq!(
+            b256_to_hex(&keccak256("Deposit(address,uint256)")),
+            DEPOSIT_TOPIC
+        );
+        assert_eq!(
+            b256_to_hex(&keccak256("Withdrawal(address,uint256)")),
+            WITHDRAWAL_TOPIC
+        );
+    }
+
+    #[test]
+    fn decode_transfer_log_requires_a_known_shape() {
+        let transfer = B256::from_str(TRANSFER_TOPIC).unwrap();
+        let deposit = B256::from_str(DEPOSIT_TOPIC).unwrap();
+        let account = B256::left_padding_from(&[0x11; 20]);
+        let amount = U256::from(5).to_be_bytes::<32>();
+
+        assert_eq!(
+            decode_transfer_log(&[transfer, account, account], &amount, false),
+            Some(TransferLog::Erc20 {
+                from: topic_to_address_hex(&account),
+                to: topic_to_address_hex(&account),
+                value: U256::from(5),
+            })
+        );
+        // An ERC-20 transfer without its amount word is not decoded.
+        assert_eq!(
+            decode_transfer_log(&[transfer, account, account], &[], false),
+            None
+        );
+        // Deposits only count for wrapped native tokens.
+        assert_eq!(
+            decode_transfer_log(&[deposit, account], &amount, false),
+            None
+        );
+        assert!(matches!(
+            decode_transfer_log(&[deposit, account], &amount, true),
+            Some(TransferLog::Erc20 { ref from, .. }) if from == ZERO_ADDRESS
+        ));
+    }
+
+    #[test]
+    fn collect_wrapped_native_deposit_and_withdrawal_as_mint_and_burn() {
+        let weth = "0x4200000000000000000000000000000000000006";
+        let other = "0x3333333333333333333333333333333333333333";
+        let account = "0x1111111111111111111111111111111111111111";
+        let event = |address: &str, topic0: &str, value: &str, log_index: &str| {
+            serde_json::json!({
+                "address": address,
+                "topics": [
+                    topic0,
+                    "0x0000000000000000000000001111111111111111111111111111111111111111"
+                ],
+                "data": value,
+                "blockNumber": "0x1",
+                "transactionHash": "0x0000000000000000000000000000000000000000000000000000000000000001",
+                "transactionIndex": "0x0",
+                "blockHash": "0x0000000000000000000000000000000000000000000000000000000000000001",
+                "logIndex": log_index,
+                "removed": false
+            })
+        };
+        let thousand = "0x00000000000000000000000000000000000000000000000000000000000003e8";
+        let four_hundred = "0x0000000000000000000000000000000000000000000000000000000000000190";
+        let logs = serde_json::json!([
+            event(weth, DEPOSIT_TOPIC, thousand, "0x0"),
+            event(weth, WITHDRAWAL_TOPIC, four_hundred, "0x1"),
+            // Same events from a contract that is not configured
+            event(other, DEPOSIT_TOPIC, thousand, "0x2"),
+        ]);
+
+        let mut batch = BlockBatch::new();
+        batch.wrapped_native = Arc::new(HashSet::from([weth.to_string()]));
+        let mut fb = empty_fetched_block(1);
+        fb.receipts = vec![make_receipt(logs)];
+        Indexer::collect_block(&mut batch, &HashSet::new(), &HashSet::new(), fb);
+
+        assert_eq!(batch.et_contracts, [weth, weth]);
+        assert_eq!(batch.et_froms, [ZERO_ADDRESS, account]);
+        assert_eq!(batch.et_tos, [account, ZERO_ADDRESS]);
+        assert_eq!(batch.et_values, ["1000", "400"]);
+        assert_eq!(batch.et_log_indices, [0, 1]);
+        assert_eq!(batch.ec_addresses, [weth]);
+
+        assert_eq!(batch.balance_map.len(), 1);
+        let balance = &batch.balance_map[&(account.to_string(), weth.to_string())];
+        assert_eq!(balance.delta, BigDecimal::from(600));
+        assert_eq!(batch.supply_map[weth].delta, BigDecimal::from(600));
+    }
+
     #[test]
     fn collect_erc20_two_transfers_in_same_block_aggregate_balance_deltas() {
         let mut batch = BlockBatch::new();
@@ -1481,6 +2501,95 @@ mod tests {
         assert!(batch.addr_map[emitter].is_contract);
     }
 
+    #[test]
+    fn collect_block_stores_compacted_logs_bloom() {
+        let mut batch = BlockBatch::new();
+        let known = HashSet::new();
+
+        Indexer::collect_block(&mut batch, &known, &known, empty_fetched_block(1));
+        let mut fb = empty_fetched_block(2);
+        fb.block.header.inner.logs_bloom = alloy::primitives::Bloom::repeat_byte(0x01);
+        Indexer::collect_block(&mut batch, &known, &known, fb);
+
+        assert!(batch.b_logs_blooms[0].is_empty());
+        assert_eq!(batch.b_logs_blooms[1], vec![0x01; 256]);
+    }
+
+    fn make_legacy_tx(
+        hash: u8,
+        from: &str,
+        to: Option<&str>,
+        value: &str,
+    ) -> alloy::rpc::types::Transaction {
+        serde_json::from_value(serde_json::json!({
+            "hash": format!("0x{:064x}", hash),
+            "blockHash": format!("0x{:064x}", 1),
+            "blockNumber": "0x1",
+            "transactionIndex": format!("0x{:x}", hash),
+            "from": from,
+            "to": to,
+            "value": value,
+            "nonce": "0x0",
+            "gas": "0x5208",
+            "gasPrice": "0x1",
+            "input": "0x",
+            "type": "0x0",
+            "v": "0x1b",
+            "r": "0x1",
+            "s": "0x1"
+        }))
+        .expect("valid transaction JSON")
+    }
+
+    fn make_tx_receipt(
+        hash: u8,
+        status: bool,
+        contract: Option<&str>,
+    ) -> alloy::rpc::types::TransactionReceipt {
+        let mut receipt = make_receipt(serde_json::json!([]));
+        receipt.transaction_hash = alloy::primitives::B256::with_last_byte(hash);
+        receipt.contract_address = contract.map(|c| c.parse().unwrap());
+        if !status {
+            let mut json = serde_json::to_value(&receipt).unwrap();
+            json["status"] = serde_json::json!("0x0");
+            receipt = serde_json::from_value(json).unwrap();
+        }
+        receipt
+    }
+
+    #[test]
+    fn collect_block_records_first_funder_and_deployer() {
+        let funder = "0x00000000000000000000000000000000000000f1";
+        let eoa = "0x00000000000000000000000000000000000000e1";
+        let contract = "0x00000000000000000000000000000000000000c1";
+        let mut fb = empty_fetched_block(1);
+        fb.block.transactions = alloy::rpc::types::BlockTransactions::Full(vec![
+            // Failed and zero-value transfers are not funding.
+            make_legacy_tx(1, funder, Some(eoa), "0x5"),
+            make_legacy_tx(2, funder, Some(eoa), "0x0"),
+            make_legacy_tx(3, funder, Some(eoa), "0x5"),
+            make_legacy_tx(4, eoa, None, "0x0"),
+        ]);
+        fb.receipts = vec![
+            make_tx_receipt(1, false, None),
+            make_tx_receipt(2, true, None),
+            make_tx_receipt(3, true, None),
+            make_tx_receipt(4, true, Some(contract)),
+        ];
+
+        let mut batch = BlockBatch::new();
+        let known = HashSet::new();
+        Indexer::collect_block(&mut batch, &known, &known, fb);
+
+        let funding = batch.addr_map[eoa].funding.as_ref().unwrap();
+        assert_eq!(funding.from, funder);
+        assert_eq!(funding.tx_hash, format!("0x{:064x}", 3));
+        let creation = batch.addr_map[contract].creation.as_ref().unwrap();
+        assert_eq!(creation.from, eoa);
+        assert_eq!(creation.block, 1);
+        assert!(batch.addr_map[funder].funding.is_none());
+    }
+
     #[test]
     fn collect_erc721_known_contract_not_added_to_nft_contract_addrs() {
         let mut batch = BlockBatch::new();
@@ -1583,6 +2692,20 @@ mod tests {
         assert_eq!(lag_blocks(100, None, 0), 101);
     }
 
+    #[test]
+    fn head_stall_restarts_when_the_head_advances() {
+        let start = std::time::Instant::now();
+        let mut stall = HeadStall::new(start);
+        assert_eq!(stall.observe(10, start), Duration::ZERO);
+        let later = start + Duration::from_secs(45);
+        assert_eq!(stall.observe(10, later), Duration::from_secs(45));
+        assert_eq!(stall.observe(11, later), Duration::ZERO);
+        assert_eq!(
+            stall.observe(11, later + Duration::from_secs(5)),
+            Duration::from_secs(5)
+        );
+    }
+
     #[test]
     fn lag_blocks_clamps_to_zero_when_chain_head_is_before_start_block() {
         assert_eq!(lag_blocks(50, None, 100), 0);
@@ -1609,9 +2732,10 @@ mod tests {
 
         let current_max = std::sync::atomic::AtomicU64::new(0); // partition 0 known
                                                                 // block 999 → partition 0; cache says partition 0 exists → fast path
-        ensure_partitions_exist(&pool, &current_max, 999)
+        let staged = ensure_partitions_exist(&pool, &current_max, 999, true)
             .await
             .expect("fast path must not need a DB connection");
+        assert!(!staged, "an existing partition is never staged");
     }
 
     /// On a fresh DB (sentinel), the fast path must NOT be taken for block 0
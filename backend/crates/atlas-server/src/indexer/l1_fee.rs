@@ -0,0 +1,143 @@
+//! L1 data fee fields of rollup receipts.
+//!
+//! OP Stack receipts (and those of chains built on its fee model) add the
+//! fee paid for posting the transaction's data to L1 on top of the L2
+//! execution fee (`gasUsed * effectiveGasPrice`):
+//!
+//! - `l1Fee`, `l1GasUsed`, `l1GasPrice` on every fork since Bedrock;
+//! - `l1FeeScalar`, a decimal string, before Ecotone;
+//! - `l1BaseFeeScalar`, `l1BlobBaseFee`, `l1BlobBaseFeeScalar` since Ecotone.
+//!
+//! alloy's `TransactionReceipt` drops unknown fields, so they are read from
+//! the raw `eth_getBlockReceipts` result alongside it. Receipts without
+//! `l1Fee` (L1 chains, deposit transactions) have no entry.
+
+use alloy::primitives::{B256, U256};
+use atlas_common::encoding::b256_to_hex;
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// L1 data fee of one transaction. Wei amounts are decimal strings.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct L1Fee {
+    pub(crate) fee: String,
+    pub(crate) gas_used: Option<i64>,
+    pub(crate) gas_price: Option<String>,
+    pub(crate) blob_base_fee: Option<String>,
+    /// Pre-Ecotone fee scalar, e.g. `"0.684"`.
+    pub(crate) fee_scalar: Option<String>,
+    pub(crate) base_fee_scalar: Option<i64>,
+    pub(crate) blob_base_fee_scalar: Option<i64>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ReceiptL1Fields {
+    transaction_hash: B256,
+    #[serde(default)]
+    l1_fee: Option<U256>,
+    #[serde(default)]
+    l1_gas_used: Option<U256>,
+    #[serde(default)]
+    l1_gas_price: Option<U256>,
+    #[serde(default)]
+    l1_blob_base_fee: Option<U256>,
+    #[serde(default)]
+    l1_fee_scalar: Option<String>,
+    #[serde(default)]
+    l1_base_fee_scalar: Option<U256>,
+    #[serde(default)]
+    l1_blob_base_fee_scalar: Option<U256>,
+}
+
+/// L1 fees of an `eth_getBlockReceipts` result, keyed by transaction hash.
+pub(crate) fn from_receipts(receipts: &Value) -> HashMap<String, L1Fee> {
+    let Some(receipts) = receipts.as_array() else {
+        return HashMap::new();
+    };
+
+    receipts
+        .iter()
+        .filter_map(|receipt| ReceiptL1Fields::deserialize(receipt).ok())
+        .filter_map(|fields| {
+            let fee = fields.l1_fee?;
+            Some((
+                b256_to_hex(&fields.transaction_hash),
+                L1Fee {
+                    fee: fee.to_string(),
+                    gas_used: fields.l1_gas_used.and_then(to_i64),
+                    gas_price: fields.l1_gas_price.map(|v| v.to_string()),
+                    blob_base_fee: fields.l1_blob_base_fee.map(|v| v.to_string()),
+                    fee_scalar: fields.l1_fee_scalar,
+                    base_fee_scalar: fields.l1_base_fee_scalar.and_then(to_i64),
+                    blob_base_fee_scalar: fields.l1_blob_base_fee_scalar.and_then(to_i64),
+                },
+            ))
+        })
+        .collect()
+}
+
+fn to_i64(value: U256) -> Option<i64> {
+    i64::try_from(u64::try_from(value).ok()?).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TX_A: &str = "0x00000000000000000000000000000000000000000000000000000000000000aa";
+    const TX_B: &str = "0x00000000000000000000000000000000000000000000000000000000000000bb";
+    const TX_C: &str = "0x00000000000000000000000000000000000000000000000000000000000000cc";
+
+    #[test]
+    fn reads_ecotone_and_bedrock_fields() {
+        let receipts = serde_json::json!([
+            {
+                "transactionHash": TX_A,
+                "l1Fee": "0x3e8",
+                "l1GasUsed": "0x640",
+                "l1GasPrice": "0x3b9aca00",
+                "l1BlobBaseFee": "0x1",
+                "l1BaseFeeScalar": "0x558",
+                "l1BlobBaseFeeScalar": "0xc5fc5"
+            },
+            {
+                "transactionHash": TX_B,
+                "l1Fee": "0x64",
+                "l1GasUsed": "0x10",
+                "l1GasPrice": "0x2",
+                "l1FeeScalar": "0.684"
+            },
+            {"transactionHash": TX_C, "gasUsed": "0x5208"}
+        ]);
+
+        let fees = from_receipts(&receipts);
+        assert_eq!(fees.len(), 2);
+        assert_eq!(
+            fees[TX_A],
+            L1Fee {
+                fee: "1000".to_string(),
+                gas_used: Some(1600),
+                gas_price: Some("1000000000".to_string()),
+                blob_base_fee: Some("1".to_string()),
+                fee_scalar: None,
+                base_fee_scalar: Some(1368),
+                blob_base_fee_scalar: Some(810949),
+            }
+        );
+        assert_eq!(fees[TX_B].fee, "100");
+        assert_eq!(fees[TX_B].fee_scalar.as_deref(), Some("0.684"));
+        assert_eq!(fees[TX_B].base_fee_scalar, None);
+    }
+
+    #[test]
+    fn ignores_missing_or_malformed_results() {
+        assert!(from_receipts(&Value::Null).is_empty());
+        assert!(from_receipts(&serde_json::json!([{"l1Fee": "0x1"}])).is_empty());
+        assert!(
+            from_receipts(&serde_json::json!([{"transactionHash": TX_A, "l1Fee": "0xzz"}]))
+                .is_empty()
+        );
+    }
+}
@@ -0,0 +1,218 @@
+//! Optional analytics job that derives address labels from curated ones.
+//!
+//! ## Design
+//!
+//! Curated labels live in `address_labels`. This job walks indexed
+//! transactions and copies each curated tag onto related addresses, writing
+//! the result to `derived_address_labels` with a `provenance` column:
+//!
+//! - `deployer`: contracts created by a labeled address get `deployed-by:<tag>`.
+//! - `funder`: addresses that received native value from a labeled address
+//!   get `funded-by:<tag>`.
+//!
+//! Only one hop is followed and addresses that already carry a curated label
+//! are skipped, so derived labels never feed back into further propagation.
+//! Built-in labels (`labels::builtin`, tagged `builtin`) are not sources:
+//! factories and utilities such as Multicall3 would tag half the chain.
+//! The first (lowest block) piece of evidence is kept for each tag.
+//!
+//! New blocks are processed incrementally behind a cursor in `indexer_state`.
+//! Curated labels edited since the previous cycle are re-applied to the
+//! already-processed range, and derived tags whose source tag was removed are
+//! pruned, so the derived set follows the curated set without a full rebuild.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use sqlx::{PgPool, Postgres, Transaction};
+use std::time::Duration;
+
+use crate::state_keys::{LABEL_PROPAGATION_LAST_BLOCK_KEY, LABEL_PROPAGATION_SYNCED_AT_KEY};
+
+/// Maximum blocks scanned per transaction when catching up.
+const CHUNK_SIZE: i64 = 10_000;
+
+/// Tags contracts created by a labeled deployer.
+/// `$1..$2` is the inclusive block range; `$3` restricts sources to labels
+/// updated after that instant (NULL = all labels).
+const DEPLOYER_SQL: &str = "
+    INSERT INTO derived_address_labels
+        (address, tag, provenance, source_address, source_tag, evidence_tx_hash, evidence_block)
+    SELECT t.contract_created, 'deployed-by:' || s.tag, 'deployer', l.address, s.tag, t.hash, t.block_number
+    FROM transactions t
+    JOIN address_labels l ON l.address = t.from_address
+    CROSS JOIN LATERAL unnest(l.tags) AS s(tag)
+    WHERE t.block_number BETWEEN $1 AND $2
+      AND ($3::timestamptz IS NULL OR l.updated_at > $3)
+      AND NOT 'builtin' = ANY(l.tags)
+      AND t.status
+      AND t.contract_created IS NOT NULL
+      AND NOT EXISTS (SELECT 1 FROM address_labels c WHERE c.address = t.contract_created)
+    ORDER BY t.block_number, t.block_index
+    ON CONFLICT (address, tag) DO NOTHING";
+
+/// Tags recipients of native value sent by a labeled address.
+/// Parameters match [`DEPLOYER_SQL`].
+const FUNDER_SQL: &str = "
+    INSERT INTO derived_address_labels
+        (address, tag, provenance, source_address, source_tag, evidence_tx_hash, evidence_block)
+    SELECT t.to_address, 'funded-by:' || s.tag, 'funder', l.address, s.tag, t.hash, t.block_number
+    FROM transactions t
+    JOIN address_labels l ON l.address = t.from_address
+    CROSS JOIN LATERAL unnest(l.tags) AS s(tag)
+    WHERE t.block_number BETWEEN $1 AND $2
+      AND ($3::timestamptz IS NULL OR l.updated_at > $3)
+      AND NOT 'builtin' = ANY(l.tags)
+      AND t.status
+      AND t.value > 0
+      AND t.to_address IS NOT NULL
+      AND t.to_address <> t.from_address
+      AND NOT EXISTS (SELECT 1 FROM address_labels c WHERE c.address = t.to_address)
+    ORDER BY t.block_number, t.block_index
+    ON CONFLICT (address, tag) DO NOTHING";
+
+/// Drops derived tags whose source address lost the tag (or its label),
+/// and derived rows for addresses that have since been curated.
+const PRUNE_SQL: &str = "
+    DELETE FROM derived_address_labels d
+    WHERE NOT EXISTS (
+            SELECT 1 FROM address_labels l
+            WHERE l.address = d.source_address AND d.source_tag = ANY(l.tags)
+        )
+       OR EXISTS (SELECT 1 FROM address_labels c WHERE c.address = d.address)";
+
+pub struct LabelPropagator {
+    pool: PgPool,
+    interval: Duration,
+}
+
+impl LabelPropagator {
+    pub fn new(pool: PgPool, interval: Duration) -> Result<Self> {
+        if interval.is_zero() {
+            anyhow::bail!("label propagation interval must be greater than 0");
+        }
+        Ok(Self { pool, interval })
+    }
+
+    pub async fn run(&self) -> Result<()> {
+        tracing::info!(
+            interval_secs = self.interval.as_secs(),
+            "Label propagation job started"
+        );
+        loop {
+            let derived = self.run_cycle().await?;
+            if derived > 0 {
+                tracing::info!(derived, "label propagation cycle complete");
+            }
+            tokio::time::sleep(self.interval).await;
+        }
+    }
+
+    /// Run one propagation cycle and return the number of derived labels added.
+    pub async fn run_cycle(&self) -> Result<u64> {
+        let (cycle_started_at,): (DateTime<Utc>,) =
+            sqlx::query_as("SELECT NOW()").fetch_one(&self.pool).await?;
+        let synced_at = self.synced_at().await?;
+        let cursor = self.cursor().await?;
+        let mut derived = 0u64;
+
+        // Labels edited since the last cycle: re-apply over blocks already scanned.
+        if let (Some(cursor), Some(synced_at)) = (cursor, synced_at) {
+            let mut tx = self.pool.begin().await?;
+            derived += propagate(&mut tx, 0, cursor, Some(synced_at)).await?;
+            tx.commit().await?;
+        }
+
+        let pruned = sqlx::query(PRUNE_SQL)
+            .execute(&self.pool)
+            .await?
+            .rows_affected();
+        if pruned > 0 {
+            tracing::info!(pruned, "label propagation pruned stale derived labels");
+        }
+
+        let (head,): (Option<i64>,) = sqlx::query_as("SELECT MAX(number) FROM blocks")
+            .fetch_one(&self.pool)
+            .await?;
+        if let Some(head) = head {
+            let mut from = cursor.map_or(0, |c| c + 1);
+            while from <= head {
+                let to = (from + CHUNK_SIZE - 1).min(head);
+                let mut tx = self.pool.begin().await?;
+                derived += propagate(&mut tx, from, to, None).await?;
+                set_state(&mut tx, LABEL_PROPAGATION_LAST_BLOCK_KEY, &to.to_string()).await?;
+                tx.commit().await?;
+                from = to + 1;
+            }
+        }
+
+        let mut tx = self.pool.begin().await?;
+        set_state(
+            &mut tx,
+            LABEL_PROPAGATION_SYNCED_AT_KEY,
+            &cycle_started_at.to_rfc3339(),
+        )
+        .await?;
+        tx.commit().await?;
+
+        Ok(derived)
+    }
+
+    async fn cursor(&self) -> Result<Option<i64>> {
+        self.state(LABEL_PROPAGATION_LAST_BLOCK_KEY)
+            .await?
+            .map(|v| v.parse::<i64>().map_err(Into::into))
+            .transpose()
+    }
+
+    async fn synced_at(&self) -> Result<Option<DateTime<Utc>>> {
+        self.state(LABEL_PROPAGATION_SYNCED_AT_KEY)
+            .await?
+            .map(|v| {
+                DateTime::parse_from_rfc3339(&v)
+                    .map(|t| t.with_timezone(&Utc))
+                    .map_err(Into::into)
+            })
+            .transpose()
+    }
+
+    async fn state(&self, key: &str) -> Result<Option<String>> {
+        let row: Option<(String,)> =
+            sqlx::query_as("SELECT value FROM indexer_state WHERE key = $1")
+                .bind(key)
+                .fetch_optional(&self.pool)
+                .await?;
+        Ok(row.map(|(value,)| value))
+    }
+}
+
+async fn propagate(
+    tx: &mut Transaction<'_, Postgres>,
+    from_block: i64,
+    to_block: i64,
+    labels_updated_since: Option<DateTime<Utc>>,
+) -> Result<u64> {
+    let mut inserted = 0;
+    for sql in [DEPLOYER_SQL, FUNDER_SQL] {
+        inserted += sqlx::query(sql)
+            .bind(from_block)
+            .bind(to_block)
+            .bind(labels_updated_since)
+            .execute(&mut **tx)
+            .await?
+            .rows_affected();
+    }
+    Ok(inserted)
+}
+
+async fn set_state(tx: &mut Transaction<'_, Postgres>, key: &str, value: &str) -> Result<()> {
+    sqlx::query(
+        "INSERT INTO indexer_state (key, value, updated_at)
+         VALUES ($1, $2, NOW())
+         ON CONFLICT (key) DO UPDATE SET value = EXCLUDED.value, updated_at = EXCLUDED.updated_at",
+    )
+    .bind(key)
+    .bind(value)
+    .execute(&mut **tx)
+    .await?;
+    Ok(())
+}
@@ -5,16 +5,19 @@ use alloy::{
     sol,
 };
 use anyhow::Result;
+use atlas_common::encoding::address_to_hex;
 use chrono::Utc;
 use sqlx::PgPool;
 use std::{str::FromStr, sync::Arc, time::Duration};
 
+use super::address_directory;
 use crate::config::Config;
 use crate::metrics::Metrics;
 use crate::nft_metadata::{
     self, FetchErrorKind, FetchedMetadata, RetryDecision, SsrfSafeResolver, NFT_METADATA_FETCHED,
     NFT_METADATA_PENDING, NFT_METADATA_PERMANENT_ERROR, NFT_METADATA_RETRYABLE_ERROR,
 };
+use crate::runtime_settings::RuntimeSettings;
 
 // ERC-721 interface
 sol! {
@@ -24,9 +27,17 @@ sol! {
         function name() external view returns (string memory);
         function symbol() external view returns (string memory);
         function totalSupply() external view returns (uint256);
+        function supportsInterface(bytes4 interfaceId) external view returns (bool);
+        function royaltyInfo(uint256 tokenId, uint256 salePrice) external view returns (address receiver, uint256 royaltyAmount);
     }
 }
 
+/// ERC-165 interface id of EIP-2981 royalties.
+const EIP2981_INTERFACE_ID: [u8; 4] = [0x2a, 0x55, 0x20, 0x5a];
+
+/// Sale price royalties are read at, so the amount is in basis points.
+const ROYALTY_SALE_PRICE: u64 = 10_000;
+
 // ERC-20 interface
 sol! {
     #[sol(rpc)]
@@ -39,16 +50,150 @@ sol! {
 
 type HttpProvider = RootProvider<Ethereum>;
 
+/// Settings the metadata fetcher reads once; the default worker count comes
+/// from [`RuntimeSettings`].
+#[derive(Debug, Clone)]
+pub struct MetadataConfig {
+    pub rpc_url: String,
+    pub ipfs_gateway: String,
+    pub retry_attempts: u32,
+    /// Pending contracts are fetched busiest first: transfers in this many
+    /// recent blocks plus current holders. 0 leaves them unordered.
+    pub priority_window_blocks: u64,
+    /// Per-phase concurrency; 0 uses the runtime `metadata_fetch_workers`.
+    pub nft_contract_workers: u32,
+    pub erc20_contract_workers: u32,
+    pub nft_token_workers: u32,
+}
+
+impl From<&Config> for MetadataConfig {
+    fn from(config: &Config) -> Self {
+        Self {
+            rpc_url: config.rpc_url.clone(),
+            ipfs_gateway: config.ipfs_gateway.clone(),
+            retry_attempts: config.metadata_retry_attempts,
+            priority_window_blocks: config.metadata_priority_window_blocks,
+            nft_contract_workers: config.metadata_nft_contract_workers,
+            erc20_contract_workers: config.metadata_erc20_contract_workers,
+            nft_token_workers: config.metadata_nft_token_workers,
+        }
+    }
+}
+
+/// Pending ERC-20 contracts, busiest first when `$2` (the priority window)
+/// is set.
+const PENDING_ERC20_CONTRACTS_SQL: &str = "
+    WITH pending AS (
+        SELECT address FROM erc20_contracts WHERE metadata_fetched = false
+    ),
+    recent AS (
+        SELECT contract_address, COUNT(*) AS transfers
+        FROM erc20_transfers
+        WHERE $2 > 0
+          AND block_number >= (SELECT COALESCE(MAX(number), 0) FROM blocks) - $2
+          AND contract_address IN (SELECT address FROM pending)
+        GROUP BY contract_address
+    ),
+    holders AS (
+        SELECT contract_address, COUNT(*) AS holders
+        FROM erc20_balances
+        WHERE $2 > 0 AND balance > 0 AND contract_address IN (SELECT address FROM pending)
+        GROUP BY contract_address
+    )
+    SELECT p.address
+    FROM pending p
+    LEFT JOIN recent r ON r.contract_address = p.address
+    LEFT JOIN holders h ON h.contract_address = p.address
+    ORDER BY COALESCE(r.transfers, 0) + COALESCE(h.holders, 0) DESC, p.address
+    LIMIT $1";
+
+/// Pending NFT contracts, busiest first when `$2` (the priority window) is
+/// set.
+const PENDING_NFT_CONTRACTS_SQL: &str = "
+    WITH pending AS (
+        SELECT address FROM nft_contracts WHERE metadata_fetched = false
+    ),
+    recent AS (
+        SELECT contract_address, COUNT(*) AS transfers
+        FROM nft_transfers
+        WHERE $2 > 0
+          AND block_number >= (SELECT COALESCE(MAX(number), 0) FROM blocks) - $2
+          AND contract_address IN (SELECT address FROM pending)
+        GROUP BY contract_address
+    ),
+    holders AS (
+        SELECT contract_address, COUNT(DISTINCT owner) AS holders
+        FROM nft_tokens
+        WHERE $2 > 0 AND contract_address IN (SELECT address FROM pending)
+        GROUP BY contract_address
+    )
+    SELECT p.address
+    FROM pending p
+    LEFT JOIN recent r ON r.contract_address = p.address
+    LEFT JOIN holders h ON h.contract_address = p.address
+    ORDER BY COALESCE(r.transfers, 0) + COALESCE(h.holders, 0) DESC, p.address
+    LIMIT $1";
+
+/// Up to `limit` ERC-20 contracts awaiting metadata, in fetch order.
+pub async fn pending_erc20_contracts(
+    pool: &PgPool,
+    limit: i64,
+    priority_window_blocks: u64,
+) -> Result<Vec<String>> {
+    pending_contracts(
+        pool,
+        PENDING_ERC20_CONTRACTS_SQL,
+        limit,
+        priority_window_blocks,
+    )
+    .await
+}
+
+/// Up to `limit` NFT contracts awaiting metadata, in fetch order.
+pub async fn pending_nft_contracts(
+    pool: &PgPool,
+    limit: i64,
+    priority_window_blocks: u64,
+) -> Result<Vec<String>> {
+    pending_contracts(
+        pool,
+        PENDING_NFT_CONTRACTS_SQL,
+        limit,
+        priority_window_blocks,
+    )
+    .await
+}
+
+async fn pending_contracts(
+    pool: &PgPool,
+    sql: &str,
+    limit: i64,
+    priority_window_blocks: u64,
+) -> Result<Vec<String>> {
+    let rows: Vec<(String,)> = sqlx::query_as(sql)
+        .bind(limit)
+        .bind(priority_window_blocks.min(i64::MAX as u64) as i64)
+        .fetch_all(pool)
+        .await?;
+    Ok(rows.into_iter().map(|(address,)| address).collect())
+}
+
 pub struct MetadataFetcher {
     pool: PgPool,
-    config: Config,
+    config: MetadataConfig,
     client: reqwest::Client,
     provider: Arc<HttpProvider>,
     metrics: Metrics,
+    settings: RuntimeSettings,
 }
 
 impl MetadataFetcher {
-    pub fn new(pool: PgPool, config: Config, metrics: Metrics) -> Result<Self> {
+    pub fn new(
+        pool: PgPool,
+        config: MetadataConfig,
+        metrics: Metrics,
+        settings: RuntimeSettings,
+    ) -> Result<Self> {
         let client = build_metadata_client()?;
 
         let provider = Arc::new(RootProvider::new_http(config.rpc_url.parse()?));
@@ -59,14 +204,12 @@ impl MetadataFetcher {
             client,
             provider,
             metrics,
+            settings,
         })
     }
 
     pub async fn run(&self) -> Result<()> {
-        tracing::info!(
-            workers = self.config.metadata_fetch_workers,
-            "starting metadata fetcher"
-        );
+        tracing::info!(workers = self.workers(), "starting metadata fetcher");
 
         loop {
             let mut did_work = false;
@@ -87,13 +230,28 @@ impl MetadataFetcher {
         }
     }
 
+    /// Concurrent fetches, read per pass so runtime changes apply.
+    fn workers(&self) -> u32 {
+        self.settings.current().metadata_fetch_workers
+    }
+
+    /// Concurrent fetches for one phase: its own setting, if any.
+    fn phase_workers(&self, configured: u32) -> u32 {
+        if configured > 0 {
+            configured
+        } else {
+            self.workers()
+        }
+    }
+
     /// Fetch metadata for NFT contracts (name, symbol, totalSupply)
     async fn fetch_nft_contract_metadata(&self) -> Result<bool> {
-        let contracts: Vec<(String,)> = sqlx::query_as(
-            "SELECT address FROM nft_contracts WHERE metadata_fetched = false LIMIT $1",
+        let workers = self.phase_workers(self.config.nft_contract_workers);
+        let contracts = pending_nft_contracts(
+            &self.pool,
+            i64::from(workers) * 5,
+            self.config.priority_window_blocks,
         )
-        .bind(self.config.metadata_fetch_workers as i32 * 5)
-        .fetch_all(&self.pool)
         .await?;
 
         if contracts.is_empty() {
@@ -103,7 +261,7 @@ impl MetadataFetcher {
         tracing::debug!(count = contracts.len(), "fetching NFT contract metadata");
 
         let mut handles = Vec::new();
-        for (address,) in contracts {
+        for address in contracts {
             let pool = self.pool.clone();
             let provider = self.provider.clone();
             let m = self.metrics.clone();
@@ -132,7 +290,7 @@ impl MetadataFetcher {
                 }
             }));
 
-            if handles.len() >= self.config.metadata_fetch_workers as usize {
+            if handles.len() >= workers as usize {
                 for handle in handles.drain(..) {
                     let _ = handle.await;
                 }
@@ -148,11 +306,12 @@ impl MetadataFetcher {
 
     /// Fetch metadata for ERC-20 contracts (name, symbol, decimals, totalSupply)
     async fn fetch_erc20_contract_metadata(&self) -> Result<bool> {
-        let contracts: Vec<(String,)> = sqlx::query_as(
-            "SELECT address FROM erc20_contracts WHERE metadata_fetched = false LIMIT $1",
+        let workers = self.phase_workers(self.config.erc20_contract_workers);
+        let contracts = pending_erc20_contracts(
+            &self.pool,
+            i64::from(workers) * 5,
+            self.config.priority_window_blocks,
         )
-        .bind(self.config.metadata_fetch_workers as i32 * 5)
-        .fetch_all(&self.pool)
         .await?;
 
         if contracts.is_empty() {
@@ -162,7 +321,7 @@ impl MetadataFetcher {
         tracing::debug!(count = contracts.len(), "fetching ERC-20 contract metadata");
 
         let mut handles = Vec::new();
-        for (address,) in contracts {
+        for address in contracts {
             let pool = self.pool.clone();
             let provider = self.provider.clone();
             let m = self.metrics.clone();
@@ -191,7 +350,7 @@ impl MetadataFetcher {
                 }
             }));
 
-            if handles.len() >= self.config.metadata_fetch_workers as usize {
+            if handles.len() >= workers as usize {
                 for handle in handles.drain(..) {
                     let _ = handle.await;
                 }
@@ -207,6 +366,7 @@ impl MetadataFetcher {
 
     /// Fetch metadata for individual NFT tokens
     async fn fetch_nft_token_metadata(&self) -> Result<bool> {
+        let workers = self.phase_workers(self.config.nft_token_workers);
         let tokens: Vec<(String, String, Option<String>, i32)> = sqlx::query_as(
             "SELECT contract_address, token_id::text, token_uri, metadata_retry_count
              FROM nft_tokens
@@ -220,7 +380,7 @@ impl MetadataFetcher {
         )
         .bind(NFT_METADATA_PENDING)
         .bind(NFT_METADATA_RETRYABLE_ERROR)
-        .bind(self.config.metadata_fetch_workers as i32 * 10)
+        .bind(workers as i32 * 10)
         .fetch_all(&self.pool)
         .await?;
 
@@ -236,7 +396,7 @@ impl MetadataFetcher {
             let client = self.client.clone();
             let provider = self.provider.clone();
             let ipfs_gateway = self.config.ipfs_gateway.clone();
-            let retry_attempts = self.config.metadata_retry_attempts;
+            let retry_attempts = self.config.retry_attempts;
             let m = self.metrics.clone();
 
             handles.push(tokio::spawn(async move {
@@ -262,7 +422,7 @@ impl MetadataFetcher {
                 }
             }));
 
-            if handles.len() >= self.config.metadata_fetch_workers as usize {
+            if handles.len() >= workers as usize {
                 for handle in handles.drain(..) {
                     let _ = handle.await;
                 }
@@ -309,11 +469,33 @@ async fn fetch_nft_contract_metadata(
         .ok()
         .map(|r| r.try_into().unwrap_or(0i64));
 
+    // Default royalty (EIP-2981): what token 0 pays on a sale. Collections
+    // that set royalties per token have no default and return their token
+    // 0 terms or revert.
+    let supports_royalties = contract
+        .supportsInterface(EIP2981_INTERFACE_ID.into())
+        .call()
+        .await
+        .unwrap_or(false);
+    let royalty = if supports_royalties {
+        contract
+            .royaltyInfo(U256::ZERO, U256::from(ROYALTY_SALE_PRICE))
+            .call()
+            .await
+            .ok()
+            .map(|info| royalty_terms(info.receiver, info.royaltyAmount))
+    } else {
+        None
+    };
+    let (royalty_receiver, royalty_bps) = royalty.unzip();
+
     sqlx::query(
         "UPDATE nft_contracts SET
             name = COALESCE($2, name),
             symbol = COALESCE($3, symbol),
             total_supply = COALESCE($4, total_supply),
+            royalty_receiver = $5,
+            royalty_bps = $6,
             metadata_fetched = true
          WHERE address = $1",
     )
@@ -321,13 +503,25 @@ async fn fetch_nft_contract_metadata(
     .bind(name)
     .bind(symbol)
     .bind(total_supply)
+    .bind(royalty_receiver.flatten())
+    .bind(royalty_bps)
     .execute(pool)
     .await?;
+    address_directory::refresh(pool, &[contract_address]).await?;
 
     tracing::debug!(address = %contract_address, "fetched NFT contract metadata");
     Ok(())
 }
 
+/// Receiver and basis points of a `royaltyInfo` answer at
+/// [`ROYALTY_SALE_PRICE`]. A zero receiver has no address; an amount above
+/// the sale price is recorded as 100%.
+fn royalty_terms(receiver: Address, amount: U256) -> (Option<String>, i32) {
+    let receiver = (!receiver.is_zero()).then(|| address_to_hex(&receiver));
+    let bps = amount.min(U256::from(ROYALTY_SALE_PRICE)).to::<i32>();
+    (receiver, bps)
+}
+
 /// Fetch ERC-20 contract metadata (name, symbol, decimals)
 async fn fetch_erc20_contract_metadata(
     pool: &PgPool,
@@ -360,6 +554,7 @@ async fn fetch_erc20_contract_metadata(
     .bind(decimals)
     .execute(pool)
     .await?;
+    address_directory::refresh(pool, &[contract_address]).await?;
 
     tracing::debug!(address = %contract_address, "fetched ERC-20 contract metadata");
     Ok(())
@@ -441,6 +636,7 @@ async fn fetch_and_store_token_metadata(
 
     match nft_metadata::fetch_metadata(client, &uri, ipfs_gateway).await {
         Ok(FetchedMetadata::DirectImage { image_url }) => {
+            let mut tx = pool.begin().await?;
             sqlx::query(
                 "UPDATE nft_tokens SET
                     metadata_status = $3,
@@ -457,8 +653,10 @@ async fn fetch_and_store_token_metadata(
             .bind(NFT_METADATA_FETCHED)
             .bind(image_url)
             .bind(now)
-            .execute(pool)
+            .execute(&mut *tx)
             .await?;
+            replace_token_attributes(&mut tx, contract_address, token_id, &[]).await?;
+            tx.commit().await?;
 
             Ok(true)
         }
@@ -466,6 +664,7 @@ async fn fetch_and_store_token_metadata(
             metadata,
             extracted,
         }) => {
+            let mut tx = pool.begin().await?;
             sqlx::query(
                 "UPDATE nft_tokens SET
                     metadata_status = $3,
@@ -485,8 +684,11 @@ async fn fetch_and_store_token_metadata(
             .bind(extracted.name)
             .bind(extracted.image_url)
             .bind(now)
-            .execute(pool)
+            .execute(&mut *tx)
             .await?;
+            replace_token_attributes(&mut tx, contract_address, token_id, &extracted.attributes)
+                .await?;
+            tx.commit().await?;
 
             Ok(true)
         }
@@ -544,6 +746,42 @@ async fn fetch_token_uri(
     Ok(uri)
 }
 
+/// Replace a token's indexed trait attributes with those of its latest metadata.
+async fn replace_token_attributes(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    contract_address: &str,
+    token_id: &str,
+    attributes: &[(String, String)],
+) -> Result<()> {
+    sqlx::query(
+        "DELETE FROM nft_token_attributes WHERE contract_address = $1 AND token_id = $2::numeric",
+    )
+    .bind(contract_address)
+    .bind(token_id)
+    .execute(&mut **tx)
+    .await?;
+
+    if attributes.is_empty() {
+        return Ok(());
+    }
+    let (trait_types, values): (Vec<&str>, Vec<&str>) = attributes
+        .iter()
+        .map(|(trait_type, value)| (trait_type.as_str(), value.as_str()))
+        .unzip();
+    sqlx::query(
+        "INSERT INTO nft_token_attributes (contract_address, token_id, trait_type, value)
+         SELECT $1, $2::numeric, trait_type, value FROM unnest($3::text[], $4::text[]) AS a(trait_type, value)
+         ON CONFLICT DO NOTHING",
+    )
+    .bind(contract_address)
+    .bind(token_id)
+    .bind(trait_types)
+    .bind(values)
+    .execute(&mut **tx)
+    .await?;
+    Ok(())
+}
+
 async fn persist_retryable_failure(
     pool: &PgPool,
     contract_address: &str,
@@ -619,10 +857,22 @@ async fn persist_permanent_failure(
 
 #[cfg(test)]
 mod tests {
-    use super::build_metadata_client;
+    use super::{build_metadata_client, royalty_terms};
+    use alloy::primitives::{Address, U256};
     use tokio::io::{AsyncReadExt, AsyncWriteExt};
     use tokio::net::TcpListener;
 
+    #[test]
+    fn royalty_terms_are_basis_points_of_the_sale_price() {
+        let receiver = Address::repeat_byte(0xab);
+        assert_eq!(
+            royalty_terms(receiver, U256::from(250)),
+            (Some(atlas_common::encoding::address_to_hex(&receiver)), 250)
+        );
+        assert_eq!(royalty_terms(Address::ZERO, U256::ZERO), (None, 0));
+        assert_eq!(royalty_terms(receiver, U256::MAX).1, 10_000);
+    }
+
     #[tokio::test]
     async fn metadata_client_does_not_follow_redirects() {
         let listener = TcpListener::bind("127.0.0.1:0")
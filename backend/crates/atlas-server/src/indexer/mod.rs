@@ -1,14 +1,47 @@
+pub mod address_directory;
+pub mod alerts;
+pub(crate) mod archive;
+pub mod backfill;
 pub(crate) mod batch;
+pub mod calldata_index;
+pub mod canonical_tokens;
+pub mod contract_reindex;
 pub(crate) mod copy;
 pub mod da_worker;
+pub mod daily_stats;
+pub mod data_quality;
+pub mod dex_swaps;
 pub(crate) mod evnode;
 pub(crate) mod fetcher;
 pub mod gap_fill_worker;
+pub mod genesis;
+pub mod holder_ranks;
 #[allow(clippy::module_inception)]
 pub mod indexer;
+pub(crate) mod l1_fee;
+pub mod label_propagation;
 pub mod metadata;
+pub mod nft_sales;
+pub mod partitions;
+pub mod pending_verifications;
+pub mod processors;
+pub mod safes;
+pub mod transfer_aggregation;
+pub mod validators;
 
+pub use alerts::AlertEngine;
+pub use backfill::BackfillWorker;
+pub use canonical_tokens::CanonicalTokenResolver;
+pub use contract_reindex::ContractReindexer;
 pub use da_worker::{DaSseUpdate, DaWorker};
+pub use daily_stats::DailyStatsAggregator;
+pub use data_quality::DataQualityChecker;
+pub use dex_swaps::DexPoolResolver;
 pub use gap_fill_worker::GapFillWorker;
+pub use holder_ranks::HolderRanker;
 pub use indexer::Indexer;
-pub use metadata::MetadataFetcher;
+pub use label_propagation::LabelPropagator;
+pub use metadata::{MetadataConfig, MetadataFetcher};
+pub use pending_verifications::PendingVerificationMatcher;
+pub use processors::ProcessorRegistry;
+pub use transfer_aggregation::TransferAggregator;
@@ -0,0 +1,431 @@
+//! NFT marketplace sales, decoded into `nft_sales`.
+//!
+//! ## Design
+//!
+//! [`NftSaleProcessor`] is the built-in `nft_sales` block processor. It reads
+//! the `OrderFulfilled` events of Seaport 1.1 to 1.6, the protocol behind
+//! OpenSea and most marketplaces built since. An order trades NFTs on one
+//! side for native coin or ERC-20 payments on the other: a listing offers
+//! the NFT and asks for payments, an accepted bid offers the payment and
+//! asks for the NFT. The price is the sum of the payment side, fees and
+//! royalties included, split evenly across the NFTs of a bundle. Orders
+//! trading NFTs for NFTs, paying nothing or mixing currencies are not sales.
+//!
+//! An order names the tokens but not who parted with them, so each ERC-721
+//! item is joined with the `Transfer` of that token in the same transaction,
+//! which gives the seller and the buyer. Items without such a transfer are
+//! skipped. Each block's sales are replaced as a whole.
+
+use alloy::primitives::{b256, Address, B256, U256};
+
+use super::fetcher::FetchedBlock;
+use super::processors::{BlockProcessor, ProcessorWrites};
+use atlas_common::encoding::{address_to_hex, b256_to_hex, topic_to_address_hex};
+
+/// Name the processor is registered and enabled under.
+pub const NFT_SALES_PROCESSOR: &str = "nft_sales";
+
+/// `OrderFulfilled(bytes32,address,address,address,(uint8,address,uint256,uint256)[],(uint8,address,uint256,uint256,address)[])`
+pub(crate) const SEAPORT_ORDER_FULFILLED_TOPIC: B256 =
+    b256!("9d9af8e38d66c62e2c12f0225249fd9d721c54b83f48d9352c97c6cacdcb6f31");
+/// `Transfer(address,address,uint256)`, with the token id indexed for ERC-721
+const TRANSFER_TOPIC: B256 =
+    b256!("ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef");
+
+/// Seaport item types.
+const ITEM_NATIVE: u8 = 0;
+const ITEM_ERC20: u8 = 1;
+const ITEM_ERC721: u8 = 2;
+const ITEM_ERC1155: u8 = 3;
+const ITEM_ERC721_WITH_CRITERIA: u8 = 4;
+const ITEM_ERC1155_WITH_CRITERIA: u8 = 5;
+
+const DELETE_SALES_SQL: &str = "DELETE FROM nft_sales WHERE block_number = $1";
+
+const INSERT_SALES_SQL: &str = "
+    INSERT INTO nft_sales (block_number, log_index, tx_hash, marketplace, exchange,
+                           contract_address, token_id, seller, buyer, currency, price,
+                           timestamp)
+    SELECT $1::bigint, log_index, tx_hash, 'seaport', exchange, contract_address,
+           token_id::numeric, seller, buyer, currency, price::numeric, $2::bigint
+    FROM unnest($3::int[], $4::text[], $5::text[], $6::text[], $7::text[], $8::text[],
+                $9::text[], $10::text[], $11::text[])
+        AS t(log_index, tx_hash, exchange, contract_address, token_id, seller, buyer,
+             currency, price)
+    ON CONFLICT (block_number, log_index, contract_address, token_id) DO NOTHING";
+
+/// An offer or consideration item of a Seaport order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Item {
+    item_type: u8,
+    token: Address,
+    identifier: U256,
+    amount: U256,
+}
+
+impl Item {
+    fn is_nft(&self) -> bool {
+        matches!(
+            self.item_type,
+            ITEM_ERC721 | ITEM_ERC1155 | ITEM_ERC721_WITH_CRITERIA | ITEM_ERC1155_WITH_CRITERIA
+        )
+    }
+
+    fn is_payment(&self) -> bool {
+        matches!(self.item_type, ITEM_NATIVE | ITEM_ERC20)
+    }
+}
+
+/// A sale of one ERC-721 token.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct NftSale {
+    pub(crate) log_index: i32,
+    pub(crate) tx_hash: String,
+    pub(crate) exchange: String,
+    pub(crate) contract_address: String,
+    pub(crate) token_id: U256,
+    pub(crate) seller: String,
+    pub(crate) buyer: String,
+    /// `None` for the native coin
+    pub(crate) currency: Option<String>,
+    pub(crate) price: U256,
+}
+
+/// `OrderFulfilled` data: orderHash, recipient, offer (dynamic) and
+/// consideration (dynamic). Returns the offer and consideration items.
+fn decode_order_fulfilled(log: &alloy::rpc::types::Log) -> Option<(Vec<Item>, Vec<Item>)> {
+    let topics = log.topics();
+    if topics.len() != 3 || topics[0] != SEAPORT_ORDER_FULFILLED_TOPIC {
+        return None;
+    }
+    let data = &log.data().data[..];
+    let word = |i: usize| data.get(i * 32..(i + 1) * 32).map(U256::from_be_slice);
+    let offer = decode_items(data, word(2)?, 4)?;
+    let consideration = decode_items(data, word(3)?, 5)?;
+    Some((offer, consideration))
+}
+
+/// An array of `words`-word item tuples at `offset`.
+fn decode_items(data: &[u8], offset: U256, words: usize) -> Option<Vec<Item>> {
+    let offset = usize::try_from(offset).ok()?;
+    let count = usize::try_from(U256::from_be_slice(data.get(offset..offset + 32)?)).ok()?;
+    let items = data.get(offset + 32..)?;
+    if count > items.len() / (words * 32) {
+        return None;
+    }
+    items
+        .chunks_exact(words * 32)
+        .take(count)
+        .map(|item| {
+            let word = |i: usize| &item[i * 32..(i + 1) * 32];
+            Some(Item {
+                item_type: u8::try_from(U256::from_be_slice(word(0))).ok()?,
+                token: Address::from_slice(&word(1)[12..]),
+                identifier: U256::from_be_slice(word(2)),
+                amount: U256::from_be_slice(word(3)),
+            })
+        })
+        .collect()
+}
+
+/// The ERC-721 items an order sells, its currency and the price of each.
+fn price_order<'a>(
+    offer: &'a [Item],
+    consideration: &'a [Item],
+) -> Option<(Vec<&'a Item>, Option<Address>, U256)> {
+    let (nfts, payments) = if offer.iter().any(Item::is_nft) {
+        (offer, consideration)
+    } else {
+        (consideration, offer)
+    };
+    // Payments on the NFT side (a bid's fees) come out of the other side.
+    let nft_count = nfts.iter().filter(|item| item.is_nft()).count();
+    if nft_count == 0 || payments.iter().any(Item::is_nft) {
+        return None;
+    }
+
+    let mut currency = None;
+    let mut total = U256::ZERO;
+    for item in payments.iter().filter(|item| item.is_payment()) {
+        let token = (item.item_type == ITEM_ERC20).then_some(item.token);
+        if *currency.get_or_insert(token) != token {
+            return None;
+        }
+        total = total.saturating_add(item.amount);
+    }
+    if total.is_zero() {
+        return None;
+    }
+
+    let sold = nfts
+        .iter()
+        .filter(|item| matches!(item.item_type, ITEM_ERC721 | ITEM_ERC721_WITH_CRITERIA))
+        .collect();
+    Some((sold, currency?, total / U256::from(nft_count)))
+}
+
+/// The sales in one transaction's logs.
+pub(crate) fn decode_sales(logs: &[alloy::rpc::types::Log]) -> Vec<NftSale> {
+    let mut sales = Vec::new();
+    for log in logs {
+        let Some((offer, consideration)) = decode_order_fulfilled(log) else {
+            continue;
+        };
+        let Some((sold, currency, price)) = price_order(&offer, &consideration) else {
+            continue;
+        };
+        let log_index = log.log_index.unwrap_or(0);
+        for item in sold {
+            // The token's transfer closest to the order event.
+            let transfer = logs
+                .iter()
+                .filter(|transfer| {
+                    let topics = transfer.topics();
+                    topics.len() == 4
+                        && topics[0] == TRANSFER_TOPIC
+                        && transfer.address() == item.token
+                        && U256::from_be_bytes(topics[3].0) == item.identifier
+                })
+                .min_by_key(|transfer| transfer.log_index.unwrap_or(0).abs_diff(log_index));
+            let Some(transfer) = transfer else {
+                continue;
+            };
+            sales.push(NftSale {
+                log_index: log_index as i32,
+                tx_hash: log
+                    .transaction_hash
+                    .as_ref()
+                    .map(b256_to_hex)
+                    .unwrap_or_default(),
+                exchange: address_to_hex(&log.address()),
+                contract_address: address_to_hex(&item.token),
+                token_id: item.identifier,
+                seller: topic_to_address_hex(&transfer.topics()[1]),
+                buyer: topic_to_address_hex(&transfer.topics()[2]),
+                currency: currency.as_ref().map(address_to_hex),
+                price,
+            });
+        }
+    }
+    sales
+}
+
+/// The `nft_sales` block processor.
+pub struct NftSaleProcessor;
+
+impl BlockProcessor for NftSaleProcessor {
+    fn process(&self, block: &FetchedBlock, writes: &mut ProcessorWrites) {
+        let number = block.number as i64;
+        writes.execute(DELETE_SALES_SQL, vec![Box::new(number)]);
+
+        let sales: Vec<NftSale> = block
+            .receipts
+            .iter()
+            .flat_map(|receipt| decode_sales(receipt.inner.logs()))
+            .collect();
+        if sales.is_empty() {
+            return;
+        }
+
+        writes.execute(
+            INSERT_SALES_SQL,
+            vec![
+                Box::new(number),
+                Box::new(block.block.header.timestamp as i64),
+                Box::new(sales.iter().map(|s| s.log_index).collect::<Vec<_>>()),
+                Box::new(sales.iter().map(|s| s.tx_hash.clone()).collect::<Vec<_>>()),
+                Box::new(sales.iter().map(|s| s.exchange.clone()).collect::<Vec<_>>()),
+                Box::new(
+                    sales
+                        .iter()
+                        .map(|s| s.contract_address.clone())
+                        .collect::<Vec<_>>(),
+                ),
+                Box::new(
+                    sales
+                        .iter()
+                        .map(|s| s.token_id.to_string())
+                        .collect::<Vec<_>>(),
+                ),
+                Box::new(sales.iter().map(|s| s.seller.clone()).collect::<Vec<_>>()),
+                Box::new(sales.iter().map(|s| s.buyer.clone()).collect::<Vec<_>>()),
+                Box::new(sales.iter().map(|s| s.currency.clone()).collect::<Vec<_>>()),
+                Box::new(
+                    sales
+                        .iter()
+                        .map(|s| s.price.to_string())
+                        .collect::<Vec<_>>(),
+                ),
+            ],
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::{keccak256, Bytes, LogData};
+
+    const SEAPORT: Address = Address::repeat_byte(0x5e);
+    const NFT: Address = Address::repeat_byte(0x71);
+    const WETH: Address = Address::repeat_byte(0x4e);
+
+    fn log(
+        address: Address,
+        topics: Vec<B256>,
+        data: Vec<u8>,
+        index: u64,
+    ) -> alloy::rpc::types::Log {
+        alloy::rpc::types::Log {
+            inner: alloy::primitives::Log {
+                address,
+                data: LogData::new_unchecked(topics, Bytes::from(data)),
+            },
+            transaction_hash: Some(B256::repeat_byte(0x11)),
+            log_index: Some(index),
+            ..Default::default()
+        }
+    }
+
+    fn number(n: u64) -> B256 {
+        U256::from(n).into()
+    }
+
+    fn account(byte: u8) -> B256 {
+        Address::repeat_byte(byte).into_word()
+    }
+
+    fn item(item_type: u8, token: Address, identifier: u64, amount: u64) -> Item {
+        Item {
+            item_type,
+            token,
+            identifier: U256::from(identifier),
+            amount: U256::from(amount),
+        }
+    }
+
+    fn order_fulfilled(
+        offer: &[Item],
+        consideration: &[Item],
+        index: u64,
+    ) -> alloy::rpc::types::Log {
+        let encode = |items: &[Item], recipient: bool| {
+            let mut words = vec![number(items.len() as u64)];
+            for item in items {
+                words.extend([
+                    number(item.item_type as u64),
+                    item.token.into_word(),
+                    item.identifier.into(),
+                    item.amount.into(),
+                ]);
+                if recipient {
+                    words.push(account(0xee));
+                }
+            }
+            words
+        };
+        let offer = encode(offer, false);
+        let consideration = encode(consideration, true);
+        let mut words = vec![
+            B256::repeat_byte(0xab),
+            account(0xcc),
+            number(128),
+            number(128 + offer.len() as u64 * 32),
+        ];
+        words.extend(offer);
+        words.extend(consideration);
+        log(
+            SEAPORT,
+            vec![SEAPORT_ORDER_FULFILLED_TOPIC, account(0xaa), B256::ZERO],
+            words.iter().flat_map(|w| w.0).collect(),
+            index,
+        )
+    }
+
+    fn nft_transfer(from: u8, to: u8, token_id: u64, index: u64) -> alloy::rpc::types::Log {
+        log(
+            NFT,
+            vec![TRANSFER_TOPIC, account(from), account(to), number(token_id)],
+            Vec::new(),
+            index,
+        )
+    }
+
+    #[test]
+    fn sale_topics_match_event_signatures() {
+        assert_eq!(
+            keccak256(
+                "OrderFulfilled(bytes32,address,address,address,(uint8,address,uint256,uint256)[],(uint8,address,uint256,uint256,address)[])"
+            ),
+            SEAPORT_ORDER_FULFILLED_TOPIC
+        );
+        assert_eq!(
+            keccak256("Transfer(address,address,uint256)"),
+            TRANSFER_TOPIC
+        );
+    }
+
+    #[test]
+    fn decodes_listing_joined_with_its_transfer() {
+        // Listing: the NFT for native coin, 2.5% of it to a fee recipient
+        let logs = [
+            nft_transfer(0xaa, 0xbb, 7, 0),
+            order_fulfilled(
+                &[item(ITEM_ERC721, NFT, 7, 1)],
+                &[
+                    item(ITEM_NATIVE, Address::ZERO, 0, 975),
+                    item(ITEM_NATIVE, Address::ZERO, 0, 25),
+                ],
+                1,
+            ),
+        ];
+        assert_eq!(
+            decode_sales(&logs),
+            vec![NftSale {
+                log_index: 1,
+                tx_hash: b256_to_hex(&B256::repeat_byte(0x11)),
+                exchange: address_to_hex(&SEAPORT),
+                contract_address: address_to_hex(&NFT),
+                token_id: U256::from(7),
+                seller: address_to_hex(&Address::repeat_byte(0xaa)),
+                buyer: address_to_hex(&Address::repeat_byte(0xbb)),
+                currency: None,
+                price: U256::from(1000),
+            }]
+        );
+
+        // Without the token's transfer there is no sale
+        assert!(decode_sales(&logs[1..]).is_empty());
+    }
+
+    #[test]
+    fn prices_accepted_bids_and_bundles() {
+        // Accepted bid: WETH offered, fees paid out of it
+        let offer = [item(ITEM_ERC20, WETH, 0, 1000)];
+        let consideration = [item(ITEM_ERC721, NFT, 7, 1), item(ITEM_ERC20, WETH, 0, 25)];
+        let (sold, currency, price) = price_order(&offer, &consideration).unwrap();
+        assert_eq!(sold, vec![&consideration[0]]);
+        assert_eq!(currency, Some(WETH));
+        assert_eq!(price, U256::from(1000));
+
+        // Bundle: the price is split across the NFTs
+        let offer = [item(ITEM_ERC721, NFT, 1, 1), item(ITEM_ERC721, NFT, 2, 1)];
+        let consideration = [item(ITEM_NATIVE, Address::ZERO, 0, 900)];
+        let (sold, _, price) = price_order(&offer, &consideration).unwrap();
+        assert_eq!(sold.len(), 2);
+        assert_eq!(price, U256::from(450));
+
+        // NFT swaps, mixed currencies and free transfers are not sales
+        let nft = [item(ITEM_ERC721, NFT, 1, 1)];
+        assert!(price_order(&nft, &[item(ITEM_ERC721, NFT, 2, 1)]).is_none());
+        assert!(price_order(
+            &nft,
+            &[
+                item(ITEM_NATIVE, Address::ZERO, 0, 1),
+                item(ITEM_ERC20, WETH, 0, 1)
+            ]
+        )
+        .is_none());
+        assert!(price_order(&nft, &[]).is_none());
+    }
+}
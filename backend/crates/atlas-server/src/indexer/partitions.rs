@@ -0,0 +1,251 @@
+//! Partitions filled without secondary indexes during backfill.
+//!
+//! ## Design
+//!
+//! Maintaining every secondary index row by row dominates backfill time.
+//! PostgreSQL gives a new partition all of its parent's indexes, so with
+//! `DEFER_PARTITION_INDEXES` a partition that lies entirely below the chain
+//! head is instead *staged*: created as a standalone table (`LIKE` the
+//! parent) with only the primary key and unique constraints that the writes'
+//! `ON CONFLICT` clauses need. The indexer writes a staged partition's blocks
+//! straight into it, one partition per batch.
+//!
+//! Once the indexer has passed the partition's last block, the remaining
+//! indexes are built with `CREATE INDEX CONCURRENTLY` and the table is
+//! attached. Attaching reuses indexes that match the parent's, and a range
+//! `CHECK` constraint added first spares it the validation scan.
+//!
+//! Until then the staged blocks are invisible through the parent tables, so
+//! the API and background jobs see them all at once when the partition is
+//! attached. A staged partition is recognised by its `blocks_p<N>` table not
+//! being attached, so an interrupted build is resumed on the next start.
+//!
+//! Backfill workers, gap-fill and archive replay may write into a staged
+//! range too: they look up [`staged_partition_of`] their blocks and write
+//! through the staged tables, one partition per write.
+
+use anyhow::Result;
+use sqlx::PgPool;
+use std::time::Instant;
+
+use super::indexer::PARTITION_SIZE;
+
+/// Tables partitioned by block number, in the order partitions are created.
+pub const PARTITIONED_TABLES: [&str; 5] = [
+    "blocks",
+    "transactions",
+    "event_logs",
+    "nft_transfers",
+    "erc20_transfers",
+];
+
+/// An index of a partitioned parent table.
+#[derive(Debug, sqlx::FromRow)]
+struct ParentIndex {
+    name: String,
+    is_unique: bool,
+    /// `p` (primary key) or `u` (unique) when the index backs a constraint.
+    constraint_type: Option<String>,
+    /// The definition from ` USING` onwards: method, columns and predicate.
+    definition: String,
+}
+
+fn partition_key(table: &str) -> &'static str {
+    if table == "blocks" {
+        "number"
+    } else {
+        "block_number"
+    }
+}
+
+async fn parent_indexes(pool: &PgPool, table: &str) -> Result<Vec<ParentIndex>> {
+    Ok(sqlx::query_as(
+        "SELECT i.relname AS name, x.indisunique AS is_unique,
+                c.contype::text AS constraint_type,
+                substring(pg_get_indexdef(i.oid) FROM ' USING .*$') AS definition
+         FROM pg_index x
+         JOIN pg_class i ON i.oid = x.indexrelid
+         LEFT JOIN pg_constraint c ON c.conindid = i.oid AND c.contype IN ('p', 'u')
+         WHERE x.indrelid = $1::regclass
+         ORDER BY i.relname",
+    )
+    .bind(table)
+    .fetch_all(pool)
+    .await?)
+}
+
+/// Create partition `p` of every partitioned table as a standalone table with
+/// only the parent's unique indexes.
+pub async fn create_staged(pool: &PgPool, p: u64) -> Result<()> {
+    for table in PARTITIONED_TABLES {
+        let child = format!("{table}_p{p}");
+        sqlx::query(&format!(
+            "CREATE TABLE IF NOT EXISTS {child} (LIKE {table} INCLUDING DEFAULTS INCLUDING CONSTRAINTS)"
+        ))
+        .execute(pool)
+        .await?;
+
+        for index in parent_indexes(pool, table).await? {
+            if !index.is_unique {
+                continue;
+            }
+            let name = format!("{}_p{p}", index.name);
+            sqlx::query(&format!(
+                "CREATE UNIQUE INDEX IF NOT EXISTS {name} ON {child}{}",
+                index.definition
+            ))
+            .execute(pool)
+            .await?;
+
+            let constraint = match index.constraint_type.as_deref() {
+                Some("p") => "PRIMARY KEY",
+                Some("u") => "UNIQUE",
+                _ => continue,
+            };
+            let (exists,): (bool,) = sqlx::query_as(
+                "SELECT EXISTS (SELECT 1 FROM pg_constraint
+                                WHERE conrelid = $1::regclass AND conname = $2)",
+            )
+            .bind(&child)
+            .bind(&name)
+            .fetch_one(pool)
+            .await?;
+            if !exists {
+                sqlx::query(&format!(
+                    "ALTER TABLE {child} ADD CONSTRAINT {name} {constraint} USING INDEX {name}"
+                ))
+                .execute(pool)
+                .await?;
+            }
+        }
+    }
+    tracing::info!(
+        partition = p,
+        "created staged partition without secondary indexes"
+    );
+    Ok(())
+}
+
+/// Partition numbers whose `blocks_p<N>` table exists but is not attached.
+pub async fn staged(pool: &PgPool) -> Result<Vec<u64>> {
+    let rows: Vec<(i64,)> = sqlx::query_as(
+        "SELECT CAST(SUBSTRING(c.relname FROM 'blocks_p(\\d+)') AS BIGINT)
+         FROM pg_class c
+         WHERE c.relname ~ '^blocks_p\\d+$' AND c.relkind = 'r'
+           AND c.relnamespace = current_schema()::regnamespace
+           AND NOT EXISTS (SELECT 1 FROM pg_inherits i WHERE i.inhrelid = c.oid)
+         ORDER BY 1",
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(rows.into_iter().map(|(p,)| p as u64).collect())
+}
+
+/// The partition holding `block`, if it is staged. Writes to its blocks go
+/// to the staged tables: the parent has no partition for them yet.
+pub async fn staged_partition_of(pool: &PgPool, block: u64) -> Result<Option<u64>> {
+    let p = block / PARTITION_SIZE;
+    let (staged,): (bool,) = sqlx::query_as(
+        "SELECT EXISTS (SELECT 1 FROM pg_class c
+                        WHERE c.oid = to_regclass($1) AND c.relkind = 'r'
+                          AND NOT EXISTS (SELECT 1 FROM pg_inherits i WHERE i.inhrelid = c.oid))",
+    )
+    .bind(format!("blocks_p{p}"))
+    .fetch_one(pool)
+    .await?;
+    Ok(staged.then_some(p))
+}
+
+/// Build the secondary indexes of staged partition `p` and attach it. Safe
+/// to call again after an interruption: finished tables are skipped.
+pub async fn complete(pool: &PgPool, p: u64) -> Result<()> {
+    let started = Instant::now();
+    let start = p * PARTITION_SIZE;
+    let end = start + PARTITION_SIZE;
+
+    // blocks last: its attachment marks the partition as complete.
+    for table in PARTITIONED_TABLES.iter().rev() {
+        let child = format!("{table}_p{p}");
+        let (attached,): (bool,) = sqlx::query_as(
+            "SELECT EXISTS (SELECT 1 FROM pg_inherits WHERE inhrelid = $1::regclass)",
+        )
+        .bind(&child)
+        .fetch_one(pool)
+        .await?;
+        if attached {
+            continue;
+        }
+
+        for index in parent_indexes(pool, table).await? {
+            if index.is_unique {
+                continue;
+            }
+            // An interrupted concurrent build leaves an invalid index behind.
+            let name = format!("{}_p{p}", index.name);
+            let (invalid,): (bool,) = sqlx::query_as(
+                "SELECT EXISTS (SELECT 1 FROM pg_index x JOIN pg_class i ON i.oid = x.indexrelid
+                                WHERE i.relname = $1 AND NOT x.indisvalid)",
+            )
+            .bind(&name)
+            .fetch_one(pool)
+            .await?;
+            if invalid {
+                sqlx::query(&format!("DROP INDEX CONCURRENTLY {name}"))
+                    .execute(pool)
+                    .await?;
+            }
+            sqlx::query(&format!(
+                "CREATE INDEX CONCURRENTLY IF NOT EXISTS {name} ON {child}{}",
+                index.definition
+            ))
+            .execute(pool)
+            .await?;
+        }
+
+        let key = partition_key(table);
+        let check = format!("{child}_range");
+        sqlx::query(&format!(
+            "ALTER TABLE {child} DROP CONSTRAINT IF EXISTS {check},
+                 ADD CONSTRAINT {check} CHECK ({key} IS NOT NULL AND {key} >= {start} AND {key} < {end})"
+        ))
+        .execute(pool)
+        .await?;
+        sqlx::query(&format!(
+            "ALTER TABLE {table} ATTACH PARTITION {child} FOR VALUES FROM ({start}) TO ({end})"
+        ))
+        .execute(pool)
+        .await?;
+        sqlx::query(&format!("ALTER TABLE {child} DROP CONSTRAINT {check}"))
+            .execute(pool)
+            .await?;
+    }
+
+    tracing::info!(
+        partition = p,
+        duration_secs = started.elapsed().as_secs(),
+        "built deferred indexes and attached partition"
+    );
+    Ok(())
+}
+
+/// Drop every staged partition; used when the index is reset, since
+/// truncating the parent tables does not reach them.
+pub async fn drop_staged(pool: &PgPool) -> Result<()> {
+    for p in staged(pool).await? {
+        for table in PARTITIONED_TABLES {
+            let child = format!("{table}_p{p}");
+            let (attached,): (bool,) = sqlx::query_as(
+                "SELECT EXISTS (SELECT 1 FROM pg_inherits WHERE inhrelid = to_regclass($1))",
+            )
+            .bind(&child)
+            .fetch_one(pool)
+            .await?;
+            if !attached {
+                sqlx::query(&format!("DROP TABLE IF EXISTS {child}"))
+                    .execute(pool)
+                    .await?;
+            }
+        }
+    }
+    Ok(())
+}
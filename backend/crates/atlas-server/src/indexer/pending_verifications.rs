@@ -0,0 +1,218 @@
+//! Verification of newly created contracts against verifications registered
+//! before deployment.
+//!
+//! ## Design
+//!
+//! `POST /api/contracts/pending-verifications` compiles a contract and stores
+//! it in `pending_verifications` under the keccak256 of its runtime bytecode.
+//! This job walks contracts by `addresses.creation_block` from a watermark in
+//! `indexer_state`, reads their code with `eth_getCode`, and copies the
+//! registration into `contract_abis` for every contract whose code hash
+//! matches, as an `exact` match.
+//!
+//! Only contracts created by a transaction are seen; contracts created by
+//! other contracts have no `creation_block`. While nothing is registered the
+//! watermark follows the indexed head without calling the node, so a
+//! registration covers contracts created from then on.
+
+use alloy::primitives::keccak256;
+use anyhow::{bail, Context, Result};
+use sqlx::PgPool;
+use std::time::Duration;
+
+use crate::state_keys::PENDING_VERIFICATION_LAST_BLOCK_KEY;
+
+/// Blocks of contract creations checked per step.
+const BLOCKS_PER_STEP: i64 = 1_000;
+
+/// Addresses per `eth_getCode` batch.
+const CODE_BATCH: usize = 100;
+
+const COPY_SQL: &str = "
+    INSERT INTO contract_abis
+        (address, abi, source_code, compiler_version, optimization_used, runs,
+         contract_name, evm_version, license_type, is_multi_file, source_files,
+         match_strategy, deployed_source_map, source_asts, verified_at)
+    SELECT $1, abi, source_code, compiler_version, optimization_used, runs,
+           contract_name, evm_version, license_type, is_multi_file, source_files,
+           'exact', deployed_source_map, source_asts, NOW()
+    FROM pending_verifications
+    WHERE code_hash = $2
+    ON CONFLICT (address) DO NOTHING";
+
+pub struct PendingVerificationMatcher {
+    pool: PgPool,
+    rpc_url: String,
+    client: reqwest::Client,
+    interval: Duration,
+}
+
+impl PendingVerificationMatcher {
+    pub fn new(pool: PgPool, rpc_url: &str, interval: Duration) -> Result<Self> {
+        if interval.is_zero() {
+            bail!("pending verification interval must be greater than 0");
+        }
+        Ok(Self {
+            pool,
+            rpc_url: rpc_url.to_string(),
+            client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(30))
+                .build()?,
+            interval,
+        })
+    }
+
+    pub async fn run(&self) -> Result<()> {
+        tracing::info!(
+            interval_secs = self.interval.as_secs(),
+            "Pending verification matcher started"
+        );
+        loop {
+            let verified = self.match_new_contracts().await?;
+            if verified > 0 {
+                tracing::info!(verified, "verified contracts from pending verifications");
+            }
+            tokio::time::sleep(self.interval).await;
+        }
+    }
+
+    /// Check contracts created since the watermark, up to the indexed head.
+    /// Returns how many were verified.
+    pub async fn match_new_contracts(&self) -> Result<u64> {
+        let head: Option<(String,)> =
+            sqlx::query_as("SELECT value FROM indexer_state WHERE key = 'last_indexed_block'")
+                .fetch_optional(&self.pool)
+                .await?;
+        let Some(head) = head.and_then(|(value,)| value.parse::<i64>().ok()) else {
+            return Ok(0);
+        };
+
+        let (registered,): (bool,) =
+            sqlx::query_as("SELECT EXISTS (SELECT 1 FROM pending_verifications)")
+                .fetch_one(&self.pool)
+                .await?;
+        let watermark: Option<(String,)> =
+            sqlx::query_as("SELECT value FROM indexer_state WHERE key = $1")
+                .bind(PENDING_VERIFICATION_LAST_BLOCK_KEY)
+                .fetch_optional(&self.pool)
+                .await?;
+        let watermark = watermark.and_then(|(value,)| value.parse::<i64>().ok());
+        let Some(mut from) = watermark.filter(|_| registered) else {
+            self.set_watermark(head).await?;
+            return Ok(0);
+        };
+
+        let mut verified = 0;
+        while from < head {
+            let to = (from + BLOCKS_PER_STEP).min(head);
+            verified += self.match_range(from, to).await?;
+            self.set_watermark(to).await?;
+            from = to;
+        }
+        Ok(verified)
+    }
+
+    /// Verify matching contracts created in blocks `(from, to]`.
+    async fn match_range(&self, from: i64, to: i64) -> Result<u64> {
+        let candidates: Vec<(String,)> = sqlx::query_as(
+            "SELECT a.address FROM addresses a
+             WHERE a.creation_block > $1 AND a.creation_block <= $2
+               AND NOT EXISTS (SELECT 1 FROM contract_abis c WHERE c.address = a.address)
+             ORDER BY a.creation_block, a.address",
+        )
+        .bind(from)
+        .bind(to)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut verified = 0;
+        for chunk in candidates.chunks(CODE_BATCH) {
+            let addresses: Vec<&str> = chunk.iter().map(|(address,)| address.as_str()).collect();
+            let hashes = self.fetch_code_hashes(&addresses).await?;
+            for (address, code_hash) in addresses.iter().zip(hashes) {
+                let Some(code_hash) = code_hash else {
+                    continue;
+                };
+                let inserted = sqlx::query(COPY_SQL)
+                    .bind(address)
+                    .bind(&code_hash)
+                    .execute(&self.pool)
+                    .await?
+                    .rows_affected();
+                if inserted == 0 {
+                    continue;
+                }
+                sqlx::query(
+                    "UPDATE pending_verifications
+                     SET matched_contracts = matched_contracts + 1, last_matched_at = NOW()
+                     WHERE code_hash = $1",
+                )
+                .bind(&code_hash)
+                .execute(&self.pool)
+                .await?;
+                tracing::debug!(address, code_hash, "verified from pending verification");
+                verified += 1;
+            }
+        }
+        Ok(verified)
+    }
+
+    /// keccak256 of each address's code as `0x` hex, `None` without code.
+    async fn fetch_code_hashes(&self, addresses: &[&str]) -> Result<Vec<Option<String>>> {
+        let batch: Vec<serde_json::Value> = addresses
+            .iter()
+            .enumerate()
+            .map(|(id, address)| {
+                serde_json::json!({
+                    "jsonrpc": "2.0",
+                    "method": "eth_getCode",
+                    "params": [address, "latest"],
+                    "id": id,
+                })
+            })
+            .collect();
+        let responses: Vec<serde_json::Value> = self
+            .client
+            .post(&self.rpc_url)
+            .json(&batch)
+            .send()
+            .await
+            .context("eth_getCode batch failed")?
+            .error_for_status()?
+            .json()
+            .await
+            .context("invalid eth_getCode batch response")?;
+
+        let mut hashes = vec![None; addresses.len()];
+        for response in responses {
+            let Some(slot) = response
+                .get("id")
+                .and_then(|id| id.as_u64())
+                .and_then(|id| hashes.get_mut(id as usize))
+            else {
+                bail!("eth_getCode batch response has an unknown id");
+            };
+            let code = response
+                .get("result")
+                .and_then(|result| result.as_str())
+                .unwrap_or_default();
+            let code = alloy::hex::decode(code).unwrap_or_default();
+            if !code.is_empty() {
+                *slot = Some(format!("{:#x}", keccak256(&code)));
+            }
+        }
+        Ok(hashes)
+    }
+
+    async fn set_watermark(&self, block: i64) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO indexer_state (key, value, updated_at) VALUES ($1, $2, NOW())
+             ON CONFLICT (key) DO UPDATE SET value = $2, updated_at = NOW()",
+        )
+        .bind(PENDING_VERIFICATION_LAST_BLOCK_KEY)
+        .bind(block.to_string())
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,238 @@
+//! Extension point for custom block decoders.
+//!
+//! ## Design
+//!
+//! A [`BlockProcessor`] sees every block the indexer collects (head
+//! following, backfill, gap fill and archive replay) and stages SQL
+//! statements in a [`ProcessorWrites`]. The statements run in the block
+//! batch's write transaction, after Atlas' own rows, so a deployment can
+//! decode protocol events such as DEX swaps or lending positions into its
+//! own tables from a separate crate without changing `collect_block`.
+//!
+//! Processors are registered by name in a [`ProcessorRegistry`], and
+//! `BLOCK_PROCESSORS` picks which of them run, in the order given. A failing
+//! statement fails the whole batch, which is retried like any other write
+//! error.
+//!
+//! Statements must be idempotent: a block is written again after a reorg,
+//! when a backfill lease is taken over and when the gap-fill worker retries
+//! it. Key rows by block and log index and upsert them.
+
+use anyhow::{bail, Result};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use tokio_postgres::types::ToSql;
+
+use super::batch::BlockBatch;
+use super::dex_swaps::{DexSwapProcessor, DEX_SWAPS_PROCESSOR};
+pub use super::fetcher::FetchedBlock;
+use super::nft_sales::{NftSaleProcessor, NFT_SALES_PROCESSOR};
+use super::safes::{SafeProcessor, SAFES_PROCESSOR};
+use super::validators::{ValidatorProcessor, VALIDATORS_PROCESSOR};
+use crate::config::Config;
+
+/// Bind parameter of a staged statement.
+pub type ProcessorParam = Box<dyn ToSql + Send + Sync>;
+
+/// Decodes blocks into rows of its own tables.
+pub trait BlockProcessor: Send + Sync {
+    /// Stage the writes for one block. Called in block order, without I/O.
+    fn process(&self, block: &FetchedBlock, writes: &mut ProcessorWrites);
+}
+
+/// Builds a processor when it is enabled.
+pub type ProcessorFactory = Box<dyn Fn() -> Result<Box<dyn BlockProcessor>> + Send + Sync>;
+
+pub(crate) struct ProcessorStatement {
+    pub(crate) processor: &'static str,
+    pub(crate) sql: String,
+    pub(crate) params: Vec<ProcessorParam>,
+}
+
+/// Statements staged by processors for the batch being collected.
+#[derive(Default)]
+pub struct ProcessorWrites {
+    processor: &'static str,
+    pub(crate) statements: Vec<ProcessorStatement>,
+}
+
+impl ProcessorWrites {
+    /// Stage `sql` with `params` ($1, $2, ...) for the batch transaction.
+    pub fn execute(&mut self, sql: impl Into<String>, params: Vec<ProcessorParam>) {
+        self.statements.push(ProcessorStatement {
+            processor: self.processor,
+            sql: sql.into(),
+            params,
+        });
+    }
+}
+
+/// Processors known to this build, by name.
+#[derive(Default)]
+pub struct ProcessorRegistry {
+    factories: BTreeMap<&'static str, ProcessorFactory>,
+}
+
+impl ProcessorRegistry {
+    /// Processors shipped with `atlas-server`. Deployments add theirs with
+    /// [`ProcessorRegistry::register`].
+    pub fn builtin() -> Self {
+        let mut registry = Self::default();
+        registry.register(DEX_SWAPS_PROCESSOR, || Ok(Box::new(DexSwapProcessor)));
+        registry.register(NFT_SALES_PROCESSOR, || Ok(Box::new(NftSaleProcessor)));
+        registry.register(SAFES_PROCESSOR, || Ok(Box::new(SafeProcessor)));
+        registry
+    }
+
+    /// [`ProcessorRegistry::builtin`] plus the processors that take their
+    /// settings from `config`.
+    pub fn for_config(config: &Config) -> Self {
+        let mut registry = Self::builtin();
+        let address = config.validator_registry_address.clone();
+        let abi = config.validator_registry_abi.clone();
+        registry.register(VALIDATORS_PROCESSOR, move || {
+            let (Some(address), Some(abi)) = (&address, &abi) else {
+                bail!("the validators processor needs VALIDATOR_REGISTRY_ADDRESS and VALIDATOR_REGISTRY_ABI");
+            };
+            Ok(Box::new(ValidatorProcessor::load(address, abi)?))
+        });
+        registry
+    }
+
+    /// Make `factory` available as `name`, replacing an earlier registration.
+    pub fn register<F>(&mut self, name: &'static str, factory: F)
+    where
+        F: Fn() -> Result<Box<dyn BlockProcessor>> + Send + Sync + 'static,
+    {
+        self.factories.insert(name, Box::new(factory));
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.factories.keys().copied()
+    }
+
+    /// Build the processors enabled by `names`, in order.
+    pub fn build(&self, names: &[String]) -> Result<Processors> {
+        let mut enabled: Vec<(&'static str, Box<dyn BlockProcessor>)> = Vec::new();
+        for name in names {
+            let Some((&key, factory)) = self.factories.get_key_value(name.as_str()) else {
+                let known: Vec<_> = self.names().collect();
+                bail!(
+                    "unknown block processor '{name}' (registered: {})",
+                    if known.is_empty() {
+                        "none".to_string()
+                    } else {
+                        known.join(", ")
+                    }
+                );
+            };
+            if enabled.iter().any(|(enabled, _)| *enabled == key) {
+                bail!("block processor '{name}' is enabled twice");
+            }
+            enabled.push((key, factory()?));
+        }
+        Ok(Processors(enabled.into()))
+    }
+}
+
+/// The processors an indexing worker runs on each block.
+#[derive(Clone)]
+pub struct Processors(Arc<[(&'static str, Box<dyn BlockProcessor>)]>);
+
+impl Default for Processors {
+    fn default() -> Self {
+        Self(Arc::new([]))
+    }
+}
+
+impl Processors {
+    pub fn names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.0.iter().map(|(name, _)| *name)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Run every processor on `block`, staging its writes in `batch`.
+    pub(crate) fn collect(&self, block: &FetchedBlock, batch: &mut BlockBatch) {
+        for (name, processor) in self.0.iter() {
+            batch.processor_writes.processor = name;
+            processor.process(block, &mut batch.processor_writes);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn empty_fetched_block(number: u64) -> FetchedBlock {
+        FetchedBlock {
+            number,
+            block: alloy::rpc::types::Block::default(),
+            receipts: vec![],
+            l1_fees: HashMap::new(),
+            raw: None,
+        }
+    }
+
+    struct BlockNumbers;
+
+    impl BlockProcessor for BlockNumbers {
+        fn process(&self, block: &FetchedBlock, writes: &mut ProcessorWrites) {
+            writes.execute(
+                "INSERT INTO seen_blocks (number) VALUES ($1) ON CONFLICT DO NOTHING",
+                vec![Box::new(block.number as i64)],
+            );
+        }
+    }
+
+    fn registry() -> ProcessorRegistry {
+        let mut registry = ProcessorRegistry::builtin();
+        registry.register("block_numbers", || Ok(Box::new(BlockNumbers)));
+        registry
+    }
+
+    #[test]
+    fn build_enables_registered_processors() {
+        let processors = registry()
+            .build(&["block_numbers".to_string()])
+            .expect("build");
+        assert_eq!(processors.names().collect::<Vec<_>>(), ["block_numbers"]);
+        assert!(registry().build(&[]).expect("build").is_empty());
+    }
+
+    #[test]
+    fn build_rejects_unknown_and_duplicate_names() {
+        let err = registry()
+            .build(&["swaps".to_string()])
+            .err()
+            .expect("unknown processor");
+        assert!(err
+            .to_string()
+            .contains("registered: block_numbers, dex_swaps, nft_sales, safes"));
+
+        let err = registry()
+            .build(&["block_numbers".to_string(), "block_numbers".to_string()])
+            .err()
+            .expect("duplicate processor");
+        assert!(err.to_string().contains("enabled twice"));
+    }
+
+    #[test]
+    fn collect_stages_statements_per_block() {
+        let processors = registry()
+            .build(&["block_numbers".to_string()])
+            .expect("build");
+        let mut batch = BlockBatch::new();
+        processors.collect(&empty_fetched_block(7), &mut batch);
+        processors.collect(&empty_fetched_block(8), &mut batch);
+
+        let statements = &batch.processor_writes.statements;
+        assert_eq!(statements.len(), 2);
+        assert!(statements.iter().all(|s| s.processor == "block_numbers"));
+        assert_eq!(statements[1].params.len(), 1);
+    }
+}
@@ -0,0 +1,381 @@
+//! Safe (formerly Gnosis Safe) multisigs, decoded into `safe_events` and
+//! `safe_executions`.
+//!
+//! ## Design
+//!
+//! [`SafeProcessor`] is the built-in `safes` block processor. Safe proxies
+//! are recognised by the events their singleton emits: `SafeSetup` when the
+//! proxy is initialised, `AddedOwner`, `RemovedOwner` and `ChangedThreshold`
+//! for owner management, and `ExecutionSuccess`/`ExecutionFailure` for each
+//! `execTransaction`. Safe 1.4 indexes the first argument of the owner and
+//! execution events, earlier versions keep it in the data; both are decoded.
+//!
+//! Owner changes are stored as events rather than applied to an owner table,
+//! so blocks can be written in any order (backfill runs behind the head) and
+//! replaced after a reorg. The API replays them to get the current owners
+//! and threshold. Each block's rows are replaced as a whole.
+
+use alloy::primitives::{b256, B256, U256};
+
+use super::fetcher::FetchedBlock;
+use super::processors::{BlockProcessor, ProcessorWrites};
+use atlas_common::encoding::{address_to_hex, b256_to_hex, topic_to_address_hex};
+
+/// Name the processor is registered and enabled under.
+pub const SAFES_PROCESSOR: &str = "safes";
+
+/// `SafeSetup(address,address[],uint256,address,address)`
+pub(crate) const SAFE_SETUP_TOPIC: B256 =
+    b256!("141df868a6331af528e38c83b7aa03edc19be66e37ae67f9285bf4f8e3c6a1a8");
+/// `AddedOwner(address)`
+pub(crate) const ADDED_OWNER_TOPIC: B256 =
+    b256!("9465fa0c962cc76958e6373a993326400c1c94f8be2fe3a952adfa7f60b2ea26");
+/// `RemovedOwner(address)`
+pub(crate) const REMOVED_OWNER_TOPIC: B256 =
+    b256!("f8d49fc529812e9a7c5c50e69c20f0dccc0db8fa95c98bc58cc9a4f1c1299eaf");
+/// `ChangedThreshold(uint256)`
+pub(crate) const CHANGED_THRESHOLD_TOPIC: B256 =
+    b256!("610f7ff2b304ae8903c3de74c60c6ab1f7d6226b3f52c5161905bb5ad4039c93");
+/// `ExecutionSuccess(bytes32,uint256)`
+pub(crate) const EXECUTION_SUCCESS_TOPIC: B256 =
+    b256!("442e715f626346e8c54381002da614f62bee8d27386535b2521ec8540898556e");
+/// `ExecutionFailure(bytes32,uint256)`
+pub(crate) const EXECUTION_FAILURE_TOPIC: B256 =
+    b256!("23428b18acfb3ea64b08dc0c1d296ea9c09702c09083ca5272e64d115b687d23");
+
+const DELETE_EVENTS_SQL: &str = "DELETE FROM safe_events WHERE block_number = $1";
+const DELETE_EXECUTIONS_SQL: &str = "DELETE FROM safe_executions WHERE block_number = $1";
+
+const INSERT_EVENT_SQL: &str = "
+    INSERT INTO safe_events (block_number, log_index, tx_hash, safe, kind, owners,
+                             threshold, timestamp)
+    VALUES ($1, $2, $3, $4, $5, $6, $7, $8)";
+
+const INSERT_EXECUTIONS_SQL: &str = "
+    INSERT INTO safe_executions (block_number, log_index, tx_hash, safe, safe_tx_hash,
+                                 success, payment, timestamp)
+    SELECT $1::bigint, log_index, tx_hash, safe, safe_tx_hash, success, payment::numeric,
+           $2::bigint
+    FROM unnest($3::int[], $4::text[], $5::text[], $6::text[], $7::bool[], $8::text[])
+        AS t(log_index, tx_hash, safe, safe_tx_hash, success, payment)";
+
+/// What a Safe event says.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum SafeEventKind {
+    Setup {
+        owners: Vec<String>,
+        threshold: i64,
+    },
+    AddedOwner(String),
+    RemovedOwner(String),
+    ChangedThreshold(i64),
+    Execution {
+        safe_tx_hash: String,
+        success: bool,
+        payment: U256,
+    },
+}
+
+/// A decoded Safe event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SafeEvent {
+    pub(crate) log_index: i32,
+    pub(crate) tx_hash: String,
+    pub(crate) safe: String,
+    pub(crate) kind: SafeEventKind,
+}
+
+/// Decode a log into a Safe event, if it is one.
+pub(crate) fn decode_safe_event(log: &alloy::rpc::types::Log) -> Option<SafeEvent> {
+    let topics = log.topics();
+    let data = &log.data().data[..];
+    let topic0 = *topics.first()?;
+
+    let kind = if topic0 == SAFE_SETUP_TOPIC && topics.len() == 2 {
+        decode_setup(data)?
+    } else {
+        // The first argument is indexed since Safe 1.4.
+        let (first, rest) = match topics.len() {
+            1 if data.len() >= 32 => (B256::from_slice(&data[..32]), &data[32..]),
+            2 => (topics[1], data),
+            _ => return None,
+        };
+        match topic0 {
+            t if t == ADDED_OWNER_TOPIC && rest.is_empty() => {
+                SafeEventKind::AddedOwner(topic_to_address_hex(&first))
+            }
+            t if t == REMOVED_OWNER_TOPIC && rest.is_empty() => {
+                SafeEventKind::RemovedOwner(topic_to_address_hex(&first))
+            }
+            t if t == CHANGED_THRESHOLD_TOPIC && topics.len() == 1 && rest.is_empty() => {
+                SafeEventKind::ChangedThreshold(threshold(&first)?)
+            }
+            t if (t == EXECUTION_SUCCESS_TOPIC || t == EXECUTION_FAILURE_TOPIC)
+                && rest.len() == 32 =>
+            {
+                SafeEventKind::Execution {
+                    safe_tx_hash: b256_to_hex(&first),
+                    success: t == EXECUTION_SUCCESS_TOPIC,
+                    payment: U256::from_be_slice(rest),
+                }
+            }
+            _ => return None,
+        }
+    };
+
+    Some(SafeEvent {
+        log_index: log.log_index.unwrap_or(0) as i32,
+        tx_hash: log
+            .transaction_hash
+            .as_ref()
+            .map(b256_to_hex)
+            .unwrap_or_default(),
+        safe: address_to_hex(&log.address()),
+        kind,
+    })
+}
+
+/// `SafeSetup` data: owners (dynamic), threshold, initializer, fallbackHandler.
+fn decode_setup(data: &[u8]) -> Option<SafeEventKind> {
+    if data.len() < 128 {
+        return None;
+    }
+    let word = |i: usize| B256::from_slice(&data[i * 32..(i + 1) * 32]);
+    let offset = usize::try_from(U256::from_be_bytes(word(0).0)).ok()?;
+    let threshold = threshold(&word(1))?;
+
+    let owners = data.get(offset..)?;
+    if owners.len() < 32 {
+        return None;
+    }
+    let (count, owners) = owners.split_at(32);
+    let count = usize::try_from(U256::from_be_slice(count)).ok()?;
+    if count > owners.len() / 32 {
+        return None;
+    }
+    let owners = owners
+        .chunks_exact(32)
+        .take(count)
+        .map(|owner| topic_to_address_hex(&B256::from_slice(owner)))
+        .collect();
+    Some(SafeEventKind::Setup { owners, threshold })
+}
+
+fn threshold(word: &B256) -> Option<i64> {
+    i64::try_from(U256::from_be_bytes(word.0)).ok()
+}
+
+/// The `safes` block processor.
+pub struct SafeProcessor;
+
+impl BlockProcessor for SafeProcessor {
+    fn process(&self, block: &FetchedBlock, writes: &mut ProcessorWrites) {
+        let number = block.number as i64;
+        let timestamp = block.block.header.timestamp as i64;
+        writes.execute(DELETE_EVENTS_SQL, vec![Box::new(number)]);
+        writes.execute(DELETE_EXECUTIONS_SQL, vec![Box::new(number)]);
+
+        let mut executions = Vec::new();
+        for event in block
+            .receipts
+            .iter()
+            .flat_map(|receipt| receipt.inner.logs())
+            .filter_map(decode_safe_event)
+        {
+            // Owner changes are rare, executions are not: those are batched.
+            let (kind, owners, threshold) = match event.kind {
+                SafeEventKind::Setup { owners, threshold } => ("setup", owners, Some(threshold)),
+                SafeEventKind::AddedOwner(owner) => ("added_owner", vec![owner], None),
+                SafeEventKind::RemovedOwner(owner) => ("removed_owner", vec![owner], None),
+                SafeEventKind::ChangedThreshold(threshold) => {
+                    ("changed_threshold", Vec::new(), Some(threshold))
+                }
+                SafeEventKind::Execution {
+                    safe_tx_hash,
+                    success,
+                    payment,
+                } => {
+                    executions.push((
+                        event.log_index,
+                        event.tx_hash,
+                        event.safe,
+                        safe_tx_hash,
+                        success,
+                        payment,
+                    ));
+                    continue;
+                }
+            };
+            writes.execute(
+                INSERT_EVENT_SQL,
+                vec![
+                    Box::new(number),
+                    Box::new(event.log_index),
+                    Box::new(event.tx_hash),
+                    Box::new(event.safe),
+                    Box::new(kind),
+                    Box::new(owners),
+                    Box::new(threshold),
+                    Box::new(timestamp),
+                ],
+            );
+        }
+        if executions.is_empty() {
+            return;
+        }
+
+        writes.execute(
+            INSERT_EXECUTIONS_SQL,
+            vec![
+                Box::new(number),
+                Box::new(timestamp),
+                Box::new(executions.iter().map(|e| e.0).collect::<Vec<_>>()),
+                Box::new(executions.iter().map(|e| e.1.clone()).collect::<Vec<_>>()),
+                Box::new(executions.iter().map(|e| e.2.clone()).collect::<Vec<_>>()),
+                Box::new(executions.iter().map(|e| e.3.clone()).collect::<Vec<_>>()),
+                Box::new(executions.iter().map(|e| e.4).collect::<Vec<_>>()),
+                Box::new(
+                    executions
+                        .iter()
+                        .map(|e| e.5.to_string())
+                        .collect::<Vec<_>>(),
+                ),
+            ],
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::{keccak256, Address, Bytes, LogData};
+
+    fn safe_log(topics: Vec<B256>, data: Vec<u8>) -> alloy::rpc::types::Log {
+        alloy::rpc::types::Log {
+            inner: alloy::primitives::Log {
+                address: Address::repeat_byte(0x5a),
+                data: LogData::new_unchecked(topics, Bytes::from(data)),
+            },
+            transaction_hash: Some(B256::repeat_byte(0x11)),
+            log_index: Some(2),
+            ..Default::default()
+        }
+    }
+
+    fn words(words: &[B256]) -> Vec<u8> {
+        words.iter().flat_map(|w| w.0).collect()
+    }
+
+    fn number(n: u64) -> B256 {
+        U256::from(n).into()
+    }
+
+    fn owner(byte: u8) -> B256 {
+        Address::repeat_byte(byte).into_word()
+    }
+
+    #[test]
+    fn safe_topics_match_event_signatures() {
+        for (signature, topic) in [
+            (
+                "SafeSetup(address,address[],uint256,address,address)",
+                SAFE_SETUP_TOPIC,
+            ),
+            ("AddedOwner(address)", ADDED_OWNER_TOPIC),
+            ("RemovedOwner(address)", REMOVED_OWNER_TOPIC),
+            ("ChangedThreshold(uint256)", CHANGED_THRESHOLD_TOPIC),
+            ("ExecutionSuccess(bytes32,uint256)", EXECUTION_SUCCESS_TOPIC),
+            ("ExecutionFailure(bytes32,uint256)", EXECUTION_FAILURE_TOPIC),
+        ] {
+            assert_eq!(keccak256(signature), topic, "{signature}");
+        }
+    }
+
+    #[test]
+    fn decodes_setup_owners_and_threshold() {
+        let data = words(&[
+            number(128),
+            number(2),
+            B256::ZERO,
+            owner(0xfb),
+            number(3),
+            owner(0x01),
+            owner(0x02),
+            owner(0x03),
+        ]);
+        let event =
+            decode_safe_event(&safe_log(vec![SAFE_SETUP_TOPIC, owner(0xf0)], data)).unwrap();
+        assert_eq!(event.safe, address_to_hex(&Address::repeat_byte(0x5a)));
+        assert_eq!(event.log_index, 2);
+        assert_eq!(
+            event.kind,
+            SafeEventKind::Setup {
+                owners: vec![
+                    address_to_hex(&Address::repeat_byte(0x01)),
+                    address_to_hex(&Address::repeat_byte(0x02)),
+                    address_to_hex(&Address::repeat_byte(0x03)),
+                ],
+                threshold: 2,
+            }
+        );
+
+        // An owner count running past the data is not a setup
+        let data = words(&[number(128), number(2), B256::ZERO, B256::ZERO, number(9)]);
+        assert!(decode_safe_event(&safe_log(vec![SAFE_SETUP_TOPIC, owner(0xf0)], data)).is_none());
+    }
+
+    #[test]
+    fn decodes_indexed_and_unindexed_owner_events() {
+        let added = SafeEventKind::AddedOwner(address_to_hex(&Address::repeat_byte(0x07)));
+        // Safe 1.3: the owner is in the data
+        let legacy = safe_log(vec![ADDED_OWNER_TOPIC], words(&[owner(0x07)]));
+        assert_eq!(decode_safe_event(&legacy).unwrap().kind, added);
+        // Safe 1.4: the owner is indexed
+        let indexed = safe_log(vec![ADDED_OWNER_TOPIC, owner(0x07)], Vec::new());
+        assert_eq!(decode_safe_event(&indexed).unwrap().kind, added);
+
+        let removed = safe_log(vec![REMOVED_OWNER_TOPIC, owner(0x08)], Vec::new());
+        assert_eq!(
+            decode_safe_event(&removed).unwrap().kind,
+            SafeEventKind::RemovedOwner(address_to_hex(&Address::repeat_byte(0x08)))
+        );
+        let threshold = safe_log(vec![CHANGED_THRESHOLD_TOPIC], words(&[number(3)]));
+        assert_eq!(
+            decode_safe_event(&threshold).unwrap().kind,
+            SafeEventKind::ChangedThreshold(3)
+        );
+    }
+
+    #[test]
+    fn decodes_executions() {
+        let safe_tx_hash = B256::repeat_byte(0xcd);
+        let legacy = safe_log(
+            vec![EXECUTION_SUCCESS_TOPIC],
+            words(&[safe_tx_hash, number(5)]),
+        );
+        assert_eq!(
+            decode_safe_event(&legacy).unwrap().kind,
+            SafeEventKind::Execution {
+                safe_tx_hash: b256_to_hex(&safe_tx_hash),
+                success: true,
+                payment: U256::from(5),
+            }
+        );
+        let indexed = safe_log(
+            vec![EXECUTION_FAILURE_TOPIC, safe_tx_hash],
+            words(&[number(0)]),
+        );
+        assert_eq!(
+            decode_safe_event(&indexed).unwrap().kind,
+            SafeEventKind::Execution {
+                safe_tx_hash: b256_to_hex(&safe_tx_hash),
+                success: false,
+                payment: U256::ZERO,
+            }
+        );
+
+        // Same topic with a different layout is another contract's event
+        let other = safe_log(vec![EXECUTION_SUCCESS_TOPIC], words(&[safe_tx_hash]));
+        assert!(decode_safe_event(&other).is_none());
+    }
+}
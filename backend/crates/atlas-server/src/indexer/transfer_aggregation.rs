@@ -0,0 +1,147 @@
+//! Retention of raw ERC-20 transfers for app-chains that only need recent
+//! history.
+//!
+//! ## Design
+//!
+//! Transfers in blocks older than `TRANSFER_RETENTION_BLOCKS` behind the head
+//! are folded into `erc20_transfer_summaries` (one row per UTC day, token,
+//! sender and recipient) and deleted from `erc20_transfers`, in one statement
+//! per chunk of [`CHUNK_BLOCKS`], so a row is never both summarized and kept.
+//!
+//! Balances and supply are running sums and do not read transfers, so they
+//! stay correct. Token transfer counts, address token flows and the
+//! transfer/balance data-quality check add the summaries to the raw rows;
+//! transfer lists and token charts only cover the retained blocks.
+//!
+//! Each cycle aggregates every raw row below the cutoff, so a block filled in
+//! late by the gap-fill worker is picked up on the next cycle. The cutoff is
+//! recorded in `indexer_state`; the indexer drops the transfers of a block
+//! written again below it (an archive replay), as they are already counted.
+
+use anyhow::{bail, Result};
+use sqlx::PgPool;
+use std::time::Duration;
+
+use crate::state_keys::TRANSFER_AGGREGATION_BEFORE_BLOCK_KEY;
+
+const INTERVAL: Duration = Duration::from_secs(300);
+
+/// Blocks aggregated per statement when catching up.
+const CHUNK_BLOCKS: i64 = 10_000;
+
+const AGGREGATE_SQL: &str = "
+    WITH moved AS (
+        DELETE FROM erc20_transfers
+        WHERE block_number >= $1 AND block_number < $2
+        RETURNING contract_address, from_address, to_address, value, block_number, timestamp
+    ),
+    summed AS (
+        INSERT INTO erc20_transfer_summaries AS s
+            (day, contract_address, from_address, to_address, transfer_count, value, first_block, last_block)
+        SELECT DATE '1970-01-01' + (timestamp / 86400)::int, contract_address, from_address, to_address,
+               COUNT(*), SUM(value), MIN(block_number), MAX(block_number)
+        FROM moved
+        GROUP BY 1, 2, 3, 4
+        ON CONFLICT (day, contract_address, from_address, to_address) DO UPDATE SET
+            transfer_count = s.transfer_count + EXCLUDED.transfer_count,
+            value = s.value + EXCLUDED.value,
+            first_block = LEAST(s.first_block, EXCLUDED.first_block),
+            last_block = GREATEST(s.last_block, EXCLUDED.last_block)
+    )
+    SELECT COUNT(*) FROM moved";
+
+pub struct TransferAggregator {
+    pool: PgPool,
+    retention_blocks: i64,
+}
+
+impl TransferAggregator {
+    pub fn new(pool: PgPool, retention_blocks: u64) -> Result<Self> {
+        if retention_blocks == 0 {
+            bail!("transfer retention must be greater than 0 blocks");
+        }
+        Ok(Self {
+            pool,
+            retention_blocks: i64::try_from(retention_blocks)?,
+        })
+    }
+
+    pub async fn run(&self) -> Result<()> {
+        tracing::info!(
+            retention_blocks = self.retention_blocks,
+            "ERC-20 transfer aggregation started"
+        );
+        loop {
+            let aggregated = self.run_cycle().await?;
+            if aggregated > 0 {
+                tracing::info!(aggregated, "aggregated old ERC-20 transfers");
+            }
+            tokio::time::sleep(INTERVAL).await;
+        }
+    }
+
+    /// Aggregate the transfers below the retention cutoff and return how many
+    /// raw rows were removed.
+    pub async fn run_cycle(&self) -> Result<u64> {
+        let (head,): (Option<i64>,) = sqlx::query_as("SELECT MAX(number) FROM blocks")
+            .fetch_one(&self.pool)
+            .await?;
+        let before = match head {
+            Some(head) if head > self.retention_blocks => head - self.retention_blocks,
+            _ => return Ok(0),
+        };
+
+        let mut aggregated = 0;
+        loop {
+            let (lowest,): (Option<i64>,) = sqlx::query_as(
+                "SELECT MIN(block_number) FROM erc20_transfers WHERE block_number < $1",
+            )
+            .bind(before)
+            .fetch_one(&self.pool)
+            .await?;
+            let Some(from) = lowest else { break };
+            aggregated += self
+                .aggregate_range(from, before.min(from + CHUNK_BLOCKS))
+                .await?;
+        }
+
+        sqlx::query(
+            "INSERT INTO indexer_state (key, value, updated_at)
+             VALUES ($1, $2, NOW())
+             ON CONFLICT (key) DO UPDATE SET
+                 value = GREATEST(indexer_state.value::bigint, EXCLUDED.value::bigint)::text,
+                 updated_at = EXCLUDED.updated_at",
+        )
+        .bind(TRANSFER_AGGREGATION_BEFORE_BLOCK_KEY)
+        .bind(before.to_string())
+        .execute(&self.pool)
+        .await?;
+
+        metrics::counter!("atlas_transfer_aggregation_rows_total").increment(aggregated);
+        Ok(aggregated)
+    }
+
+    /// Fold the transfers of blocks in `[from, to)` into the daily summaries
+    /// and delete them, returning how many were moved.
+    pub async fn aggregate_range(&self, from: i64, to: i64) -> Result<u64> {
+        let (moved,): (i64,) = sqlx::query_as(AGGREGATE_SQL)
+            .bind(from)
+            .bind(to)
+            .fetch_one(&self.pool)
+            .await?;
+        Ok(moved as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn new_rejects_zero_retention() {
+        let pool = sqlx::postgres::PgPoolOptions::new()
+            .connect_lazy("postgres://test@localhost:5432/test")
+            .expect("lazy pool creation should not fail");
+        assert!(TransferAggregator::new(pool, 0).is_err());
+    }
+}
@@ -0,0 +1,369 @@
+//! Staking and sequencer registry events, decoded into `validator_events`.
+//!
+//! ## Design
+//!
+//! [`ValidatorProcessor`] is the built-in `validators` block processor. The
+//! registry contract differs between chains, so it is configured with the
+//! contract's address and JSON ABI (`VALIDATOR_REGISTRY_ADDRESS`,
+//! `VALIDATOR_REGISTRY_ABI`). Each event in the ABI is classified by its name
+//! (see [`event_kind`]); the validator is its first address input, preferring
+//! one named like `validator` or `sequencer`, and the amount its stake-like
+//! uint input. Events without an address input or a recognised name are not
+//! indexed.
+//!
+//! Events are stored as they happen rather than applied to a validator table,
+//! so blocks can be written in any order and replaced after a reorg. The API
+//! sums stakes and takes the latest status change when read.
+
+use alloy::dyn_abi::{DynSolValue, EventExt};
+use alloy::json_abi::{Event, JsonAbi};
+use alloy::primitives::{Address, B256};
+use anyhow::{bail, Context, Result};
+use std::collections::HashMap;
+use std::path::Path;
+
+use super::fetcher::FetchedBlock;
+use super::processors::{BlockProcessor, ProcessorWrites};
+use atlas_common::encoding::{address_to_hex, b256_to_hex};
+
+/// Name the processor is registered and enabled under.
+pub const VALIDATORS_PROCESSOR: &str = "validators";
+
+const DELETE_EVENTS_SQL: &str = "DELETE FROM validator_events WHERE block_number = $1";
+
+const INSERT_EVENTS_SQL: &str = "
+    INSERT INTO validator_events (block_number, log_index, tx_hash, validator, event, kind,
+                                  amount, timestamp)
+    SELECT $1::bigint, log_index, tx_hash, validator, event, kind, amount::numeric, $2::bigint
+    FROM unnest($3::int[], $4::text[], $5::text[], $6::text[], $7::text[], $8::text[])
+        AS t(log_index, tx_hash, validator, event, kind, amount)";
+
+/// Input names preferred for the validator and the amount.
+const VALIDATOR_NAMES: &[&str] = &["validator", "sequencer", "operator", "proposer", "signer"];
+const AMOUNT_NAMES: &[&str] = &["amount", "stake", "value", "wad", "bond"];
+
+/// What a registry event does to a validator, from the event's name:
+///
+/// | Kind | Name contains |
+/// |------|---------------|
+/// | `unjail` | `unjail` |
+/// | `slash` | `slash` |
+/// | `jail` | `jail` |
+/// | `unstake` | `unstake`, `undelegate`, `unbond`, `withdraw`, or a stake word with `remove`/`decrease`/`reduce` |
+/// | `stake` | `stake`, `deposit`, `delegat`, `bond` |
+/// | `exit` | `deregister`, `unregister`, `remove`, `exit`, `leave`, `deactivate` |
+/// | `register` | `register`, `add`, `join`, `activate` |
+///
+/// Checked top to bottom, case-insensitively.
+pub(crate) fn event_kind(name: &str) -> Option<&'static str> {
+    let name = name.to_ascii_lowercase();
+    let has = |words: &[&str]| words.iter().any(|word| name.contains(word));
+    let stake_word = has(&["stake", "deposit", "delegat", "bond"]);
+    Some(if has(&["unjail"]) {
+        "unjail"
+    } else if has(&["slash"]) {
+        "slash"
+    } else if has(&["jail"]) {
+        "jail"
+    } else if has(&["unstake", "undelegate", "unbond", "withdraw"])
+        || (stake_word && has(&["remove", "decrease", "reduce"]))
+    {
+        "unstake"
+    } else if stake_word {
+        "stake"
+    } else if has(&[
+        "deregister",
+        "unregister",
+        "remove",
+        "exit",
+        "leave",
+        "deactivate",
+    ]) {
+        "exit"
+    } else if has(&["register", "add", "join", "activate"]) {
+        "register"
+    } else {
+        return None;
+    })
+}
+
+/// A registry event that is indexed, with the positions of its inputs.
+struct RegistryEvent {
+    event: Event,
+    kind: &'static str,
+    validator: usize,
+    amount: Option<usize>,
+}
+
+impl RegistryEvent {
+    fn new(event: &Event) -> Option<Self> {
+        let kind = event_kind(&event.name)?;
+        let named = |ty: &str, names: &[&str]| {
+            let inputs: Vec<usize> = (0..event.inputs.len())
+                .filter(|&i| {
+                    event.inputs[i].ty.starts_with(ty) && !event.inputs[i].ty.ends_with(']')
+                })
+                .collect();
+            let by_name = inputs.iter().copied().find(|&i| {
+                let name = event.inputs[i].name.to_ascii_lowercase();
+                names.iter().any(|n| name.contains(n))
+            });
+            (by_name, inputs)
+        };
+        let (validator, addresses) = named("address", VALIDATOR_NAMES);
+        let validator = validator.or_else(|| addresses.first().copied())?;
+        let (amount, uints) = named("uint", AMOUNT_NAMES);
+        let amount = amount.or_else(|| (uints.len() == 1).then(|| uints[0]));
+        Some(Self {
+            event: event.clone(),
+            kind,
+            validator,
+            amount,
+        })
+    }
+}
+
+/// A decoded registry event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ValidatorEvent {
+    pub(crate) log_index: i32,
+    pub(crate) tx_hash: String,
+    pub(crate) validator: String,
+    pub(crate) event: String,
+    pub(crate) kind: &'static str,
+    pub(crate) amount: Option<String>,
+}
+
+/// The `validators` block processor.
+pub struct ValidatorProcessor {
+    registry: Address,
+    events: HashMap<B256, RegistryEvent>,
+}
+
+impl ValidatorProcessor {
+    /// Index the events of the registry at `address` described by the ABI
+    /// file at `abi_path`, a JSON ABI array or a compiler artifact with an
+    /// `abi` field.
+    pub fn load(address: &str, abi_path: &Path) -> Result<Self> {
+        let abi = std::fs::read_to_string(abi_path).with_context(|| {
+            format!("cannot read validator registry ABI {}", abi_path.display())
+        })?;
+        Self::from_abi(address, &abi)
+    }
+
+    pub fn from_abi(address: &str, abi: &str) -> Result<Self> {
+        let registry: Address = address
+            .parse()
+            .with_context(|| format!("invalid validator registry address '{address}'"))?;
+        let mut json: serde_json::Value =
+            serde_json::from_str(abi).context("validator registry ABI is not JSON")?;
+        if let Some(artifact_abi) = json.get_mut("abi") {
+            json = artifact_abi.take();
+        }
+        let abi: JsonAbi =
+            serde_json::from_value(json).context("validator registry ABI is not a JSON ABI")?;
+
+        let events: HashMap<B256, RegistryEvent> = abi
+            .events()
+            .filter(|event| !event.anonymous)
+            .filter_map(|event| Some((event.selector(), RegistryEvent::new(event)?)))
+            .collect();
+        if events.is_empty() {
+            bail!("validator registry ABI has no event naming a validator and a stake or status change");
+        }
+        let mut names: Vec<_> = events
+            .values()
+            .map(|e| format!("{} ({})", e.event.name, e.kind))
+            .collect();
+        names.sort();
+        tracing::info!(registry = %address_to_hex(&registry), events = ?names, "validator registry events");
+        Ok(Self { registry, events })
+    }
+
+    /// Decode a log into a registry event, if the registry emitted a known one.
+    pub(crate) fn decode(&self, log: &alloy::rpc::types::Log) -> Option<ValidatorEvent> {
+        if log.address() != self.registry {
+            return None;
+        }
+        let registry_event = self.events.get(log.topics().first()?)?;
+        let event = &registry_event.event;
+        let decoded = event
+            .decode_log_parts(log.topics().iter().copied(), &log.data().data)
+            .ok()?;
+
+        // Inputs in declaration order, from the indexed and body values.
+        let (mut indexed, mut body) = (decoded.indexed.into_iter(), decoded.body.into_iter());
+        let values: Vec<DynSolValue> = event
+            .inputs
+            .iter()
+            .map(|input| {
+                if input.indexed {
+                    indexed.next()
+                } else {
+                    body.next()
+                }
+            })
+            .collect::<Option<_>>()?;
+
+        let DynSolValue::Address(validator) = values[registry_event.validator] else {
+            return None;
+        };
+        let amount = match registry_event.amount.map(|i| &values[i]) {
+            Some(DynSolValue::Uint(amount, _)) => Some(amount.to_string()),
+            _ => None,
+        };
+        Some(ValidatorEvent {
+            log_index: log.log_index.unwrap_or(0) as i32,
+            tx_hash: log
+                .transaction_hash
+                .as_ref()
+                .map(b256_to_hex)
+                .unwrap_or_default(),
+            validator: address_to_hex(&validator),
+            event: event.name.clone(),
+            kind: registry_event.kind,
+            amount,
+        })
+    }
+}
+
+impl BlockProcessor for ValidatorProcessor {
+    fn process(&self, block: &FetchedBlock, writes: &mut ProcessorWrites) {
+        let number = block.number as i64;
+        writes.execute(DELETE_EVENTS_SQL, vec![Box::new(number)]);
+
+        let events: Vec<ValidatorEvent> = block
+            .receipts
+            .iter()
+            .flat_map(|receipt| receipt.inner.logs())
+            .filter_map(|log| self.decode(log))
+            .collect();
+        if events.is_empty() {
+            return;
+        }
+
+        let column = |f: fn(&ValidatorEvent) -> String| events.iter().map(f).collect::<Vec<_>>();
+        writes.execute(
+            INSERT_EVENTS_SQL,
+            vec![
+                Box::new(number),
+                Box::new(block.block.header.timestamp as i64),
+                Box::new(events.iter().map(|e| e.log_index).collect::<Vec<_>>()),
+                Box::new(column(|e| e.tx_hash.clone())),
+                Box::new(column(|e| e.validator.clone())),
+                Box::new(column(|e| e.event.clone())),
+                Box::new(column(|e| e.kind.to_string())),
+                Box::new(events.iter().map(|e| e.amount.clone()).collect::<Vec<_>>()),
+            ],
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::{Bytes, LogData, U256};
+
+    const REGISTRY: &str = "0x00000000000000000000000000000000000000e5";
+
+    const ABI: &str = r#"{"abi": [
+        {"type": "event", "name": "SequencerRegistered", "anonymous": false, "inputs": [
+            {"name": "caller", "type": "address", "indexed": true},
+            {"name": "sequencer", "type": "address", "indexed": true},
+            {"name": "stake", "type": "uint256", "indexed": false}]},
+        {"type": "event", "name": "Unstaked", "anonymous": false, "inputs": [
+            {"name": "validator", "type": "address", "indexed": true},
+            {"name": "amount", "type": "uint256", "indexed": false},
+            {"name": "unlockAt", "type": "uint64", "indexed": false}]},
+        {"type": "event", "name": "ParamsChanged", "anonymous": false, "inputs": [
+            {"name": "admin", "type": "address", "indexed": false}]}
+    ]}"#;
+
+    fn log(topics: Vec<B256>, words: &[U256]) -> alloy::rpc::types::Log {
+        let data: Vec<u8> = words.iter().flat_map(|w| w.to_be_bytes::<32>()).collect();
+        alloy::rpc::types::Log {
+            inner: alloy::primitives::Log {
+                address: REGISTRY.parse().unwrap(),
+                data: LogData::new_unchecked(topics, Bytes::from(data)),
+            },
+            transaction_hash: Some(B256::repeat_byte(0x11)),
+            log_index: Some(3),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn event_kinds_follow_names() {
+        for (name, kind) in [
+            ("ValidatorRegistered", Some("register")),
+            ("SequencerAdded", Some("register")),
+            ("ValidatorDeactivated", Some("exit")),
+            ("ValidatorRemoved", Some("exit")),
+            ("StakeAdded", Some("stake")),
+            ("Delegated", Some("stake")),
+            ("StakeRemoved", Some("unstake")),
+            ("Undelegated", Some("unstake")),
+            ("Slashed", Some("slash")),
+            ("ValidatorJailed", Some("jail")),
+            ("ValidatorUnjailed", Some("unjail")),
+            ("OwnershipTransferred", None),
+        ] {
+            assert_eq!(event_kind(name), kind, "{name}");
+        }
+    }
+
+    #[test]
+    fn decodes_configured_registry_events() {
+        let processor = ValidatorProcessor::from_abi(REGISTRY, ABI).unwrap();
+        assert_eq!(processor.events.len(), 2, "ParamsChanged is not indexed");
+
+        let sequencer = Address::repeat_byte(0x5e);
+        let registered = processor
+            .events
+            .values()
+            .find(|e| e.kind == "register")
+            .unwrap();
+        let event = processor
+            .decode(&log(
+                vec![
+                    registered.event.selector(),
+                    Address::repeat_byte(0xca).into_word(),
+                    sequencer.into_word(),
+                ],
+                &[U256::from(1000)],
+            ))
+            .unwrap();
+        assert_eq!(event.validator, address_to_hex(&sequencer));
+        assert_eq!(event.event, "SequencerRegistered");
+        assert_eq!(event.amount.as_deref(), Some("1000"));
+
+        // The amount is the input named like one, not the unlock time
+        let unstaked = processor
+            .events
+            .values()
+            .find(|e| e.kind == "unstake")
+            .unwrap();
+        let event = processor
+            .decode(&log(
+                vec![unstaked.event.selector(), sequencer.into_word()],
+                &[U256::from(400), U256::from(99)],
+            ))
+            .unwrap();
+        assert_eq!(event.amount.as_deref(), Some("400"));
+
+        // Another contract emitting the same event is ignored
+        let mut other = log(
+            vec![unstaked.event.selector(), sequencer.into_word()],
+            &[U256::from(400), U256::from(99)],
+        );
+        other.inner.address = Address::repeat_byte(0x01);
+        assert!(processor.decode(&other).is_none());
+    }
+
+    #[test]
+    fn rejects_abis_without_validator_events() {
+        let err = ValidatorProcessor::from_abi(REGISTRY, "[]").err().unwrap();
+        assert!(err.to_string().contains("no event naming a validator"));
+        assert!(ValidatorProcessor::from_abi("0x12", ABI).is_err());
+    }
+}
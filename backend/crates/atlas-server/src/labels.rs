@@ -0,0 +1,708 @@
+//! Address label validation and bulk import/export formats.
+//!
+//! Curated labels enter the database through suggestions, bulk imports and
+//! direct edits; all of them go through the validators here so the
+//! `address_labels` table has one canonical shape: lowercase `0x` addresses,
+//! trimmed names and `[a-z0-9_-]` tags.
+//!
+//! Supported import formats:
+//!
+//! - `csv` / `json`: Atlas' own export (`address,name,tags` with `;`-separated
+//!   tags, or an array of `{address, name, tags}`), so exports round-trip.
+//! - `etherscan`: community etherscan label dumps, an object keyed by address
+//!   with `{name, labels[]}` values.
+//! - `ethereum-lists`: the ethereum-lists address lists, an array of
+//!   `{address, comment}`; `comment` becomes the label name.
+
+pub mod builtin;
+
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+use atlas_common::encoding::{address_to_hex, parse_address};
+use atlas_common::AtlasError;
+
+pub const MAX_NAME_LEN: usize = 100;
+pub const MAX_TAGS: usize = 10;
+pub const MAX_TAG_LEN: usize = 32;
+
+/// Upper bound on rows accepted by a single import.
+pub const MAX_IMPORT_ROWS: usize = 50_000;
+
+/// Separator for the `tags` column in CSV import/export.
+const CSV_TAG_SEPARATOR: char = ';';
+
+pub fn normalize_label_address(address: &str) -> Result<String, AtlasError> {
+    Ok(address_to_hex(&parse_address(address)?))
+}
+
+pub fn validate_label_name(name: &str) -> Result<String, AtlasError> {
+    let name = name.trim();
+    if name.is_empty() || name.chars().count() > MAX_NAME_LEN {
+        return Err(AtlasError::InvalidInput(format!(
+            "Name must be between 1 and {MAX_NAME_LEN} characters"
+        )));
+    }
+    Ok(name.to_string())
+}
+
+/// Lowercase, trim and de-duplicate tags. Tags are restricted to
+/// `[a-z0-9_-]` so they can never collide with the `prefix:` form used by
+/// derived labels.
+pub fn normalize_tags(tags: &[String]) -> Result<Vec<String>, AtlasError> {
+    let mut normalized: Vec<String> = Vec::with_capacity(tags.len());
+    for tag in tags {
+        let tag = tag.trim().to_lowercase();
+        if tag.is_empty()
+            || tag.len() > MAX_TAG_LEN
+            || !tag
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_')
+        {
+            return Err(AtlasError::InvalidInput(format!(
+                "Invalid tag '{tag}': use up to {MAX_TAG_LEN} characters of a-z, 0-9, '-' or '_'"
+            )));
+        }
+        if !normalized.contains(&tag) {
+            normalized.push(tag);
+        }
+    }
+    if normalized.len() > MAX_TAGS {
+        return Err(AtlasError::InvalidInput(format!(
+            "At most {MAX_TAGS} tags are allowed"
+        )));
+    }
+    Ok(normalized)
+}
+
+/// Turn a free-form source label ("Exchange Hot Wallet") into tag form
+/// ("exchange-hot-wallet").
+fn slugify_tag(raw: &str) -> String {
+    let mut slug = String::with_capacity(raw.len());
+    for c in raw.trim().to_lowercase().chars() {
+        if c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' {
+            slug.push(c);
+        } else if !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    slug.trim_matches('-').to_string()
+}
+
+/// Tags a curator key may manage.
+///
+/// A scoped editor may create or change a label only if it carries at least
+/// one in-scope tag, and may never add, remove or keep-but-rename tags outside
+/// its scope: out-of-scope tags on an existing label must survive unchanged.
+/// Deleting requires every tag on the label to be in scope.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TagScope(Vec<String>);
+
+impl TagScope {
+    pub fn new(tags: Vec<String>) -> Self {
+        Self(tags)
+    }
+
+    pub fn tags(&self) -> &[String] {
+        &self.0
+    }
+
+    pub fn covers_any(&self, tags: &[String]) -> bool {
+        tags.iter().any(|t| self.0.contains(t))
+    }
+
+    pub fn check_write(
+        &self,
+        existing: Option<&[String]>,
+        new_tags: &[String],
+    ) -> Result<(), String> {
+        if !self.covers_any(new_tags) {
+            return Err(format!(
+                "Label must carry at least one of the key's tags: {}",
+                self.0.join(", ")
+            ));
+        }
+        let Some(existing) = existing else {
+            return Ok(());
+        };
+        if !self.covers_any(existing) {
+            return Err("Existing label has none of the key's tags".to_string());
+        }
+        let foreign = |tags: &[String]| -> HashSet<String> {
+            tags.iter()
+                .filter(|t| !self.0.contains(t))
+                .cloned()
+                .collect()
+        };
+        if foreign(existing) != foreign(new_tags) {
+            return Err("Tags outside the key's scope cannot be added or removed".to_string());
+        }
+        Ok(())
+    }
+
+    pub fn check_delete(&self, existing: &[String]) -> Result<(), String> {
+        if existing.is_empty() || !existing.iter().all(|t| self.0.contains(t)) {
+            return Err(
+                "Only labels whose tags are all within the key's scope can be deleted".to_string(),
+            );
+        }
+        Ok(())
+    }
+}
+
+/// A validated label ready to be written to `address_labels`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LabelRecord {
+    pub address: String,
+    pub name: String,
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ImportFormat {
+    Csv,
+    Json,
+    Etherscan,
+    EthereumLists,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    Csv,
+    #[default]
+    Json,
+}
+
+/// A source row that could not be imported.
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportRowError {
+    /// 1-based data row (CSV excludes the header line).
+    pub row: usize,
+    pub address: String,
+    pub error: String,
+}
+
+/// Source-tag rewrites applied while importing, e.g. `exchange:cex,spam:`.
+/// Keys are matched after slugifying the source tag; an empty target drops
+/// the tag. `extra` tags are appended to every imported row.
+#[derive(Debug, Clone, Default)]
+pub struct TagMapping {
+    rewrites: HashMap<String, Option<String>>,
+    extra: Vec<String>,
+}
+
+impl TagMapping {
+    pub fn parse(tag_map: Option<&str>, add_tags: Option<&str>) -> Result<Self, AtlasError> {
+        let mut rewrites = HashMap::new();
+        for pair in tag_map
+            .unwrap_or("")
+            .split(',')
+            .filter(|p| !p.trim().is_empty())
+        {
+            let (from, to) = pair.split_once(':').ok_or_else(|| {
+                AtlasError::InvalidInput(format!(
+                    "Invalid tag_map entry '{pair}': expected 'source:target'"
+                ))
+            })?;
+            let to = slugify_tag(to);
+            rewrites.insert(slugify_tag(from), (!to.is_empty()).then_some(to));
+        }
+
+        let extra: Vec<String> = add_tags
+            .unwrap_or("")
+            .split(',')
+            .map(slugify_tag)
+            .filter(|t| !t.is_empty())
+            .collect();
+        normalize_tags(&extra)?;
+
+        Ok(Self { rewrites, extra })
+    }
+
+    fn apply(&self, raw_tags: &[String]) -> Vec<String> {
+        raw_tags
+            .iter()
+            .map(|raw| slugify_tag(raw))
+            .filter(|tag| !tag.is_empty())
+            .filter_map(|tag| match self.rewrites.get(&tag) {
+                Some(mapped) => mapped.clone(),
+                None => Some(tag),
+            })
+            .chain(self.extra.iter().cloned())
+            .collect()
+    }
+}
+
+/// Result of parsing an import payload: valid records plus per-row errors.
+#[derive(Debug, Default)]
+pub struct ParsedImport {
+    pub total_rows: usize,
+    pub records: Vec<LabelRecord>,
+    pub errors: Vec<ImportRowError>,
+    /// Source row of each entry in `records`.
+    record_rows: Vec<usize>,
+}
+
+impl ParsedImport {
+    /// Turn records failing `check` into row errors, keeping errors in row order.
+    pub fn reject_records(&mut self, mut check: impl FnMut(&LabelRecord) -> Result<(), String>) {
+        let records = std::mem::take(&mut self.records);
+        let rows = std::mem::take(&mut self.record_rows);
+        let mut rejected = false;
+        for (record, row) in records.into_iter().zip(rows) {
+            match check(&record) {
+                Ok(()) => {
+                    self.records.push(record);
+                    self.record_rows.push(row);
+                }
+                Err(error) => {
+                    rejected = true;
+                    self.errors.push(ImportRowError {
+                        row,
+                        address: record.address,
+                        error,
+                    });
+                }
+            }
+        }
+        if rejected {
+            self.errors.sort_by_key(|e| e.row);
+        }
+    }
+}
+
+struct RawLabel {
+    address: String,
+    name: String,
+    tags: Vec<String>,
+}
+
+/// Parse and validate an import payload. Structural problems (malformed JSON,
+/// missing CSV columns, too many rows) fail the whole import; bad rows are
+/// reported individually and skipped.
+pub fn parse_import(
+    format: ImportFormat,
+    body: &str,
+    mapping: &TagMapping,
+) -> Result<ParsedImport, AtlasError> {
+    let rows = match format {
+        ImportFormat::Csv => parse_atlas_csv(body)?,
+        ImportFormat::Json => parse_atlas_json(body)?,
+        ImportFormat::Etherscan => parse_etherscan(body)?,
+        ImportFormat::EthereumLists => parse_ethereum_lists(body)?,
+    };
+    if rows.len() > MAX_IMPORT_ROWS {
+        return Err(AtlasError::InvalidInput(format!(
+            "Import exceeds {MAX_IMPORT_ROWS} rows"
+        )));
+    }
+
+    let mut parsed = ParsedImport {
+        total_rows: rows.len(),
+        ..Default::default()
+    };
+    let mut seen = HashSet::new();
+    for (idx, raw) in rows.into_iter().enumerate() {
+        let row = idx + 1;
+        let reject = |address: String, error: String| ImportRowError {
+            row,
+            address,
+            error,
+        };
+
+        let address = match normalize_label_address(&raw.address) {
+            Ok(address) => address,
+            Err(e) => {
+                parsed
+                    .errors
+                    .push(reject(raw.address, row_error_message(e)));
+                continue;
+            }
+        };
+        if !seen.insert(address.clone()) {
+            parsed
+                .errors
+                .push(reject(address, "Duplicate address in import".to_string()));
+            continue;
+        }
+        let record = validate_label_name(&raw.name).and_then(|name| {
+            Ok(LabelRecord {
+                address: address.clone(),
+                name,
+                tags: normalize_tags(&mapping.apply(&raw.tags))?,
+            })
+        });
+        match record {
+            Ok(record) => {
+                parsed.records.push(record);
+                parsed.record_rows.push(row);
+            }
+            Err(e) => parsed.errors.push(reject(address, row_error_message(e))),
+        }
+    }
+    Ok(parsed)
+}
+
+/// Per-row message without the `Invalid input:` display prefix.
+fn row_error_message(err: AtlasError) -> String {
+    match err {
+        AtlasError::InvalidInput(msg) | AtlasError::Validation(msg) => msg,
+        other => other.to_string(),
+    }
+}
+
+fn invalid_payload(format: &str, err: impl std::fmt::Display) -> AtlasError {
+    AtlasError::InvalidInput(format!("Invalid {format} payload: {err}"))
+}
+
+fn parse_atlas_json(body: &str) -> Result<Vec<RawLabel>, AtlasError> {
+    #[derive(Deserialize)]
+    struct Row {
+        address: String,
+        name: String,
+        #[serde(default)]
+        tags: Vec<String>,
+    }
+
+    let rows: Vec<Row> = serde_json::from_str(body).map_err(|e| invalid_payload("json", e))?;
+    Ok(rows
+        .into_iter()
+        .map(|r| RawLabel {
+            address: r.address,
+            name: r.name,
+            tags: r.tags,
+        })
+        .collect())
+}
+
+fn parse_etherscan(body: &str) -> Result<Vec<RawLabel>, AtlasError> {
+    #[derive(Deserialize)]
+    struct Entry {
+        #[serde(default)]
+        name: String,
+        #[serde(default)]
+        labels: Vec<String>,
+    }
+
+    /// Address-keyed object read in file order, so row numbers in the
+    /// report match the source (a `serde_json::Map` would sort the keys).
+    struct Entries(Vec<(String, Entry)>);
+
+    impl<'de> Deserialize<'de> for Entries {
+        fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            struct Visitor;
+
+            impl<'de> serde::de::Visitor<'de> for Visitor {
+                type Value = Entries;
+
+                fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                    f.write_str("an object keyed by address")
+                }
+
+                fn visit_map<A: serde::de::MapAccess<'de>>(
+                    self,
+                    mut map: A,
+                ) -> Result<Entries, A::Error> {
+                    let mut entries = Vec::with_capacity(map.size_hint().unwrap_or(0));
+                    while let Some(entry) = map.next_entry()? {
+                        entries.push(entry);
+                    }
+                    Ok(Entries(entries))
+                }
+            }
+
+            deserializer.deserialize_map(Visitor)
+        }
+    }
+
+    let Entries(entries) =
+        serde_json::from_str(body).map_err(|e| invalid_payload("etherscan", e))?;
+    Ok(entries
+        .into_iter()
+        .map(|(address, entry)| RawLabel {
+            address,
+            name: entry.name,
+            tags: entry.labels,
+        })
+        .collect())
+}
+
+fn parse_ethereum_lists(body: &str) -> Result<Vec<RawLabel>, AtlasError> {
+    #[derive(Deserialize)]
+    struct Entry {
+        address: String,
+        #[serde(default)]
+        comment: String,
+    }
+
+    let entries: Vec<Entry> =
+        serde_json::from_str(body).map_err(|e| invalid_payload("ethereum-lists", e))?;
+    Ok(entries
+        .into_iter()
+        .map(|e| RawLabel {
+            address: e.address,
+            name: e.comment,
+            tags: Vec::new(),
+        })
+        .collect())
+}
+
+fn parse_atlas_csv(body: &str) -> Result<Vec<RawLabel>, AtlasError> {
+    let mut rows = parse_csv(body)?.into_iter();
+    let header = rows
+        .next()
+        .ok_or_else(|| invalid_payload("csv", "missing header row"))?;
+    let column = |name: &str| {
+        header
+            .iter()
+            .position(|h| h.trim().eq_ignore_ascii_case(name))
+            .ok_or_else(|| invalid_payload("csv", format!("missing '{name}' column")))
+    };
+    let (address_col, name_col) = (column("address")?, column("name")?);
+    let tags_col = column("tags").ok();
+
+    Ok(rows
+        .filter(|fields| fields.iter().any(|f| !f.trim().is_empty()))
+        .map(|fields| {
+            let field = |i: usize| fields.get(i).cloned().unwrap_or_default();
+            RawLabel {
+                address: field(address_col),
+                name: field(name_col),
+                tags: tags_col
+                    .map(|i| {
+                        field(i)
+                            .split(CSV_TAG_SEPARATOR)
+                            .filter(|t| !t.trim().is_empty())
+                            .map(str::to_string)
+                            .collect()
+                    })
+                    .unwrap_or_default(),
+            }
+        })
+        .collect())
+}
+
+/// Minimal RFC 4180 reader: quoted fields, doubled quotes, CRLF or LF.
+fn parse_csv(body: &str) -> Result<Vec<Vec<String>>, AtlasError> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = body.trim_start_matches('\u{feff}').chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match (in_quotes, c) {
+            (true, '"') if chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            (true, '"') => in_quotes = false,
+            (true, c) => field.push(c),
+            (false, '"') if field.is_empty() => in_quotes = true,
+            (false, ',') => row.push(std::mem::take(&mut field)),
+            (false, '\r') if chars.peek() == Some(&'\n') => {}
+            (false, '\n') => {
+                row.push(std::mem::take(&mut field));
+                rows.push(std::mem::take(&mut row));
+            }
+            (false, c) => field.push(c),
+        }
+    }
+    if in_quotes {
+        return Err(invalid_payload("csv", "unterminated quoted field"));
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+    Ok(rows)
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Render labels in the `csv` import format.
+pub fn write_csv(labels: &[LabelRecord]) -> String {
+    let mut out = String::from("address,name,tags\n");
+    for label in labels {
+        let tags = label.tags.join(&CSV_TAG_SEPARATOR.to_string());
+        out.push_str(&format!(
+            "{},{},{}\n",
+            csv_field(&label.address),
+            csv_field(&label.name),
+            csv_field(&tags)
+        ));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ADDR_A: &str = "0xdac17f958d2ee523a2206206994597c13d831ec7";
+    const ADDR_B: &str = "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48";
+
+    #[test]
+    fn normalize_label_address_lowercases_checksummed_input() {
+        assert_eq!(
+            normalize_label_address("0xdAC17F958D2ee523a2206206994597C13D831ec7").unwrap(),
+            ADDR_A
+        );
+        assert!(normalize_label_address("0x1234").is_err());
+    }
+
+    #[test]
+    fn normalize_tags_dedupes_and_lowercases() {
+        let tags = normalize_tags(&[
+            " Bridge ".to_string(),
+            "bridge".to_string(),
+            "layer-2".to_string(),
+        ])
+        .unwrap();
+        assert_eq!(tags, vec!["bridge", "layer-2"]);
+    }
+
+    #[test]
+    fn normalize_tags_rejects_derived_prefix_and_oversized_lists() {
+        assert!(normalize_tags(&["deployed-by:acme".to_string()]).is_err());
+        assert!(normalize_tags(&[String::new()]).is_err());
+
+        let many: Vec<String> = (0..=MAX_TAGS).map(|i| format!("t{i}")).collect();
+        assert!(normalize_tags(&many).is_err());
+    }
+
+    #[test]
+    fn validate_label_name_trims_and_bounds_length() {
+        assert_eq!(validate_label_name("  Acme  ").unwrap(), "Acme");
+        assert!(validate_label_name("   ").is_err());
+        assert!(validate_label_name(&"x".repeat(MAX_NAME_LEN + 1)).is_err());
+    }
+
+    #[test]
+    fn csv_export_round_trips_through_import() {
+        let labels = vec![
+            LabelRecord {
+                address: ADDR_A.to_string(),
+                name: "Tether: \"USDT\", Treasury".to_string(),
+                tags: vec!["stablecoin".to_string(), "token".to_string()],
+            },
+            LabelRecord {
+                address: ADDR_B.to_string(),
+                name: "USDC".to_string(),
+                tags: vec![],
+            },
+        ];
+
+        let parsed = parse_import(
+            ImportFormat::Csv,
+            &write_csv(&labels),
+            &TagMapping::default(),
+        )
+        .unwrap();
+        assert!(parsed.errors.is_empty());
+        assert_eq!(parsed.records, labels);
+    }
+
+    #[test]
+    fn etherscan_import_maps_and_slugifies_tags() {
+        let body = format!(
+            r#"{{"{ADDR_A}": {{"name": "Binance 14", "labels": ["Exchange", "Binance Hot Wallet", "Spam"]}}}}"#
+        );
+        let mapping = TagMapping::parse(Some("exchange:cex,spam:"), Some("imported")).unwrap();
+
+        let parsed = parse_import(ImportFormat::Etherscan, &body, &mapping).unwrap();
+        assert_eq!(parsed.records.len(), 1);
+        assert_eq!(
+            parsed.records[0].tags,
+            vec!["cex", "binance-hot-wallet", "imported"]
+        );
+    }
+
+    #[test]
+    fn ethereum_lists_import_reports_bad_rows() {
+        let body = format!(
+            r#"[
+                {{"address": "{ADDR_A}", "comment": "Fake airdrop", "date": "2018-01-01"}},
+                {{"address": "0xnotanaddress", "comment": "Broken"}},
+                {{"address": "{ADDR_A}", "comment": "Duplicate"}},
+                {{"address": "{ADDR_B}", "comment": ""}}
+            ]"#
+        );
+        let mapping = TagMapping::parse(None, Some("phishing")).unwrap();
+
+        let parsed = parse_import(ImportFormat::EthereumLists, &body, &mapping).unwrap();
+        assert_eq!(parsed.total_rows, 4);
+        assert_eq!(parsed.records.len(), 1);
+        assert_eq!(parsed.records[0].tags, vec!["phishing"]);
+        let rows: Vec<usize> = parsed.errors.iter().map(|e| e.row).collect();
+        assert_eq!(rows, vec![2, 3, 4]);
+        assert_eq!(parsed.errors[1].error, "Duplicate address in import");
+
+        let mut parsed = parse_import(ImportFormat::EthereumLists, &body, &mapping).unwrap();
+        parsed.reject_records(|_| Err("Out of scope".to_string()));
+        assert!(parsed.records.is_empty());
+        let rows: Vec<usize> = parsed.errors.iter().map(|e| e.row).collect();
+        assert_eq!(rows, vec![1, 2, 3, 4]);
+        assert_eq!(parsed.errors[0].error, "Out of scope");
+    }
+
+    #[test]
+    fn csv_import_requires_address_and_name_columns() {
+        let err =
+            parse_import(ImportFormat::Csv, "address,tags\n", &TagMapping::default()).unwrap_err();
+        assert!(err.to_string().contains("missing 'name' column"));
+    }
+
+    #[test]
+    fn parse_csv_rejects_unterminated_quotes() {
+        assert!(parse_csv("address,name\n\"0x1,oops\n").is_err());
+    }
+
+    fn tags(tags: &[&str]) -> Vec<String> {
+        tags.iter().map(|t| t.to_string()).collect()
+    }
+
+    #[test]
+    fn tag_scope_allows_edits_within_scope() {
+        let scope = TagScope::new(tags(&["bridge"]));
+        assert!(scope.check_write(None, &tags(&["bridge"])).is_ok());
+        assert!(scope
+            .check_write(Some(&tags(&["bridge", "defi"])), &tags(&["defi", "bridge"]))
+            .is_ok());
+        assert!(scope.check_delete(&tags(&["bridge"])).is_ok());
+    }
+
+    #[test]
+    fn tag_scope_rejects_foreign_labels_and_tags() {
+        let scope = TagScope::new(tags(&["bridge"]));
+        // New label without an in-scope tag.
+        assert!(scope.check_write(None, &tags(&["cex"])).is_err());
+        // Existing label owned by someone else.
+        assert!(scope
+            .check_write(Some(&tags(&["cex"])), &tags(&["cex", "bridge"]))
+            .is_err());
+        // Dropping or adding an out-of-scope tag.
+        assert!(scope
+            .check_write(Some(&tags(&["bridge", "defi"])), &tags(&["bridge"]))
+            .is_err());
+        assert!(scope
+            .check_write(Some(&tags(&["bridge"])), &tags(&["bridge", "defi"]))
+            .is_err());
+        // Deleting a shared label.
+        assert!(scope.check_delete(&tags(&["bridge", "defi"])).is_err());
+        assert!(scope.check_delete(&[]).is_err());
+    }
+
+    #[test]
+    fn tag_mapping_rejects_malformed_pairs() {
+        assert!(TagMapping::parse(Some("exchange"), None).is_err());
+    }
+}
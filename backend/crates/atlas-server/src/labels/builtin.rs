@@ -0,0 +1,566 @@
+//! Built-in labels for standard precompiles and predeploys (`BUILTIN_LABELS`).
+//!
+//! Seeded into `address_labels` at startup so a fresh explorer already shows
+//! "L2 Standard Bridge" or "Sequencer Fee Vault" instead of bare addresses.
+//! Curated labels always win: a built-in label is only inserted where the
+//! address has none.
+//!
+//! Which sets apply is chosen per chain id (see [`sets_for_chain`]) unless
+//! configured explicitly. Predeploys and well-known deployments are only
+//! labeled once `eth_getCode` confirms they exist on this chain; precompiles
+//! have no code and are labeled unconditionally.
+
+use anyhow::{bail, Context, Result};
+use sqlx::PgPool;
+use std::time::Duration;
+
+use crate::indexer::address_directory;
+
+/// Tag carried by every built-in label, so they can be told apart from
+/// curated ones.
+pub const BUILTIN_TAG: &str = "builtin";
+
+/// A group of registry entries that applies to a family of chains.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LabelSet {
+    /// EVM precompiles (`0x01`–`0x11`, RIP-7212 `P256VERIFY`).
+    Precompiles,
+    /// OP Stack L2 predeploys at `0x4200…`.
+    OpStack,
+    /// System contracts and deterministic deployments found on most EVM chains.
+    Common,
+}
+
+impl LabelSet {
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "precompiles" => Some(LabelSet::Precompiles),
+            "op-stack" => Some(LabelSet::OpStack),
+            "common" => Some(LabelSet::Common),
+            _ => None,
+        }
+    }
+}
+
+/// `BUILTIN_LABELS` setting.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BuiltinLabels {
+    /// Pick sets from the chain id.
+    Auto,
+    /// Exactly these sets; empty disables built-in labels.
+    Sets(Vec<LabelSet>),
+}
+
+impl BuiltinLabels {
+    /// Parse `auto`, `none`, or a comma-separated list of set names.
+    pub fn parse(value: &str) -> Result<Self> {
+        match value.trim() {
+            "auto" => Ok(BuiltinLabels::Auto),
+            "none" | "" => Ok(BuiltinLabels::Sets(Vec::new())),
+            list => list
+                .split(',')
+                .map(|name| {
+                    LabelSet::parse(name.trim()).with_context(|| {
+                        format!(
+                            "unknown built-in label set '{}', expected auto, none or a list of \
+                             precompiles, op-stack, common",
+                            name.trim()
+                        )
+                    })
+                })
+                .collect::<Result<Vec<_>>>()
+                .map(BuiltinLabels::Sets),
+        }
+    }
+
+    fn sets(&self, chain_id: u64) -> Vec<LabelSet> {
+        match self {
+            BuiltinLabels::Auto => sets_for_chain(chain_id),
+            BuiltinLabels::Sets(sets) => sets.clone(),
+        }
+    }
+}
+
+/// Ethereum L1 networks, which have no L2 predeploys.
+const ETHEREUM_CHAIN_IDS: &[u64] = &[1, 11155111, 17000, 560048];
+
+/// Sets applied with `BUILTIN_LABELS=auto`.
+///
+/// Ethereum L1 networks get no L2 predeploys. Every other chain — OP Stack
+/// chains and ev-node rollups, which often reuse the OP Stack genesis — gets
+/// every set; the code check drops predeploys the chain does not have.
+pub fn sets_for_chain(chain_id: u64) -> Vec<LabelSet> {
+    if ETHEREUM_CHAIN_IDS.contains(&chain_id) {
+        vec![LabelSet::Precompiles, LabelSet::Common]
+    } else {
+        vec![LabelSet::Precompiles, LabelSet::OpStack, LabelSet::Common]
+    }
+}
+
+/// How an entry's presence on the chain is established.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntryKind {
+    /// Native code without bytecode; present on every chain of its set.
+    Precompile,
+    /// Code placed in the genesis state; also recorded as a contract seen at block 0.
+    Predeploy,
+    /// Deployed by a transaction at a deterministic address.
+    Deployment,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct BuiltinLabel {
+    pub address: &'static str,
+    pub name: &'static str,
+    pub tags: &'static [&'static str],
+    pub kind: EntryKind,
+}
+
+const fn precompile(address: &'static str, name: &'static str) -> BuiltinLabel {
+    BuiltinLabel {
+        address,
+        name,
+        tags: &["precompile"],
+        kind: EntryKind::Precompile,
+    }
+}
+
+const fn op_predeploy(
+    address: &'static str,
+    name: &'static str,
+    tags: &'static [&'static str],
+) -> BuiltinLabel {
+    BuiltinLabel {
+        address,
+        name,
+        tags,
+        kind: EntryKind::Predeploy,
+    }
+}
+
+const PRECOMPILES: &[BuiltinLabel] = &[
+    precompile("0x0000000000000000000000000000000000000001", "ecRecover"),
+    precompile("0x0000000000000000000000000000000000000002", "SHA-256"),
+    precompile("0x0000000000000000000000000000000000000003", "RIPEMD-160"),
+    precompile("0x0000000000000000000000000000000000000004", "Identity"),
+    precompile("0x0000000000000000000000000000000000000005", "ModExp"),
+    precompile("0x0000000000000000000000000000000000000006", "BN254 Add"),
+    precompile("0x0000000000000000000000000000000000000007", "BN254 Mul"),
+    precompile(
+        "0x0000000000000000000000000000000000000008",
+        "BN254 Pairing",
+    ),
+    precompile("0x0000000000000000000000000000000000000009", "BLAKE2f"),
+    precompile(
+        "0x000000000000000000000000000000000000000a",
+        "KZG Point Evaluation",
+    ),
+    precompile(
+        "0x000000000000000000000000000000000000000b",
+        "BLS12-381 G1 Add",
+    ),
+    precompile(
+        "0x000000000000000000000000000000000000000c",
+        "BLS12-381 G1 MSM",
+    ),
+    precompile(
+        "0x000000000000000000000000000000000000000d",
+        "BLS12-381 G2 Add",
+    ),
+    precompile(
+        "0x000000000000000000000000000000000000000e",
+        "BLS12-381 G2 MSM",
+    ),
+    precompile(
+        "0x000000000000000000000000000000000000000f",
+        "BLS12-381 Pairing Check",
+    ),
+    precompile(
+        "0x0000000000000000000000000000000000000010",
+        "BLS12-381 Map Fp to G1",
+    ),
+    precompile(
+        "0x0000000000000000000000000000000000000011",
+        "BLS12-381 Map Fp2 to G2",
+    ),
+    precompile("0x0000000000000000000000000000000000000100", "P256 Verify"),
+];
+
+const OP_STACK_PREDEPLOYS: &[BuiltinLabel] = &[
+    op_predeploy(
+        "0x4200000000000000000000000000000000000000",
+        "Legacy Message Passer",
+        &["predeploy", "op-stack"],
+    ),
+    op_predeploy(
+        "0x4200000000000000000000000000000000000002",
+        "Deployer Whitelist",
+        &["predeploy", "op-stack"],
+    ),
+    op_predeploy(
+        "0x4200000000000000000000000000000000000006",
+        "Wrapped Ether",
+        &["predeploy", "op-stack", "token"],
+    ),
+    op_predeploy(
+        "0x4200000000000000000000000000000000000007",
+        "L2 Cross Domain Messenger",
+        &["predeploy", "op-stack", "bridge"],
+    ),
+    op_predeploy(
+        "0x420000000000000000000000000000000000000f",
+        "Gas Price Oracle",
+        &["predeploy", "op-stack"],
+    ),
+    op_predeploy(
+        "0x4200000000000000000000000000000000000010",
+        "L2 Standard Bridge",
+        &["predeploy", "op-stack", "bridge"],
+    ),
+    op_predeploy(
+        "0x4200000000000000000000000000000000000011",
+        "Sequencer Fee Vault",
+        &["predeploy", "op-stack", "fee-vault"],
+    ),
+    op_predeploy(
+        "0x4200000000000000000000000000000000000012",
+        "Optimism Mintable ERC20 Factory",
+        &["predeploy", "op-stack", "factory"],
+    ),
+    op_predeploy(
+        "0x4200000000000000000000000000000000000013",
+        "L1 Block Number",
+        &["predeploy", "op-stack"],
+    ),
+    op_predeploy(
+        "0x4200000000000000000000000000000000000014",
+        "L2 ERC721 Bridge",
+        &["predeploy", "op-stack", "bridge"],
+    ),
+    op_predeploy(
+        "0x4200000000000000000000000000000000000015",
+        "L1 Block Attributes",
+        &["predeploy", "op-stack"],
+    ),
+    op_predeploy(
+        "0x4200000000000000000000000000000000000016",
+        "L2 To L1 Message Passer",
+        &["predeploy", "op-stack", "bridge"],
+    ),
+    op_predeploy(
+        "0x4200000000000000000000000000000000000017",
+        "Optimism Mintable ERC721 Factory",
+        &["predeploy", "op-stack", "factory"],
+    ),
+    op_predeploy(
+        "0x4200000000000000000000000000000000000018",
+        "Proxy Admin",
+        &["predeploy", "op-stack"],
+    ),
+    op_predeploy(
+        "0x4200000000000000000000000000000000000019",
+        "Base Fee Vault",
+        &["predeploy", "op-stack", "fee-vault"],
+    ),
+    op_predeploy(
+        "0x420000000000000000000000000000000000001a",
+        "L1 Fee Vault",
+        &["predeploy", "op-stack", "fee-vault"],
+    ),
+    op_predeploy(
+        "0x420000000000000000000000000000000000001b",
+        "Operator Fee Vault",
+        &["predeploy", "op-stack", "fee-vault"],
+    ),
+    op_predeploy(
+        "0x4200000000000000000000000000000000000020",
+        "Schema Registry",
+        &["predeploy", "op-stack", "attestation"],
+    ),
+    op_predeploy(
+        "0x4200000000000000000000000000000000000021",
+        "Ethereum Attestation Service",
+        &["predeploy", "op-stack", "attestation"],
+    ),
+    op_predeploy(
+        "0x4200000000000000000000000000000000000042",
+        "Governance Token",
+        &["predeploy", "op-stack", "token"],
+    ),
+];
+
+const COMMON_DEPLOYMENTS: &[BuiltinLabel] = &[
+    BuiltinLabel {
+        address: "0x000f3df6d732807ef1319fb7b8bb8522d0beac02",
+        name: "Beacon Roots (EIP-4788)",
+        tags: &["system"],
+        kind: EntryKind::Deployment,
+    },
+    BuiltinLabel {
+        address: "0x0000f90827f1c53a10cb7a02335b175320002935",
+        name: "Block Hash History (EIP-2935)",
+        tags: &["system"],
+        kind: EntryKind::Deployment,
+    },
+    BuiltinLabel {
+        address: "0x00000961ef480eb55e80d19ad83579a64c007002",
+        name: "Withdrawal Requests (EIP-7002)",
+        tags: &["system"],
+        kind: EntryKind::Deployment,
+    },
+    BuiltinLabel {
+        address: "0x0000bbddc7ce488642fb579f8b00f3a590007251",
+        name: "Consolidation Requests (EIP-7251)",
+        tags: &["system"],
+        kind: EntryKind::Deployment,
+    },
+    BuiltinLabel {
+        address: "0xca11bde05977b3631167028862be2a173976ca11",
+        name: "Multicall3",
+        tags: &["utility"],
+        kind: EntryKind::Deployment,
+    },
+    BuiltinLabel {
+        address: "0x4e59b44847b379578588920ca78fbf26c0b4956c",
+        name: "Deterministic Deployment Proxy",
+        tags: &["factory"],
+        kind: EntryKind::Deployment,
+    },
+    BuiltinLabel {
+        address: "0x13b0d85ccb8bf860b6b79af3029fca081ae9bef2",
+        name: "Create2 Deployer",
+        tags: &["factory"],
+        kind: EntryKind::Deployment,
+    },
+    BuiltinLabel {
+        address: "0x914d7fec6aac8cd542e72bca78b30650d45643d7",
+        name: "Safe Singleton Factory",
+        tags: &["factory"],
+        kind: EntryKind::Deployment,
+    },
+    BuiltinLabel {
+        address: "0x000000000022d473030f116ddee9f6b43ac78ba3",
+        name: "Permit2",
+        tags: &["utility"],
+        kind: EntryKind::Deployment,
+    },
+    BuiltinLabel {
+        address: "0x5ff137d4b0fdcd49dca30c7cf57e578a026d2789",
+        name: "ERC-4337 EntryPoint v0.6",
+        tags: &["account-abstraction"],
+        kind: EntryKind::Deployment,
+    },
+    BuiltinLabel {
+        address: "0x0000000071727de22e5e9d8baf0edac6f37da032",
+        name: "ERC-4337 EntryPoint v0.7",
+        tags: &["account-abstraction"],
+        kind: EntryKind::Deployment,
+    },
+];
+
+/// Registry entries for the configured sets on `chain_id`.
+pub fn entries(setting: &BuiltinLabels, chain_id: u64) -> Vec<BuiltinLabel> {
+    setting
+        .sets(chain_id)
+        .into_iter()
+        .flat_map(|set| match set {
+            LabelSet::Precompiles => PRECOMPILES,
+            LabelSet::OpStack => OP_STACK_PREDEPLOYS,
+            LabelSet::Common => COMMON_DEPLOYMENTS,
+        })
+        .copied()
+        .collect()
+}
+
+/// Seed built-in labels for this chain. Entries with code are checked
+/// against the node first; the rest of the explorer works without them, so
+/// callers treat failures as non-fatal.
+pub async fn seed(
+    pool: &PgPool,
+    rpc_url: &str,
+    setting: &BuiltinLabels,
+    chain_id: u64,
+) -> Result<usize> {
+    let candidates = entries(setting, chain_id);
+    if candidates.is_empty() {
+        return Ok(0);
+    }
+
+    let with_code: Vec<&str> = candidates
+        .iter()
+        .filter(|entry| entry.kind != EntryKind::Precompile)
+        .map(|entry| entry.address)
+        .collect();
+    let deployed = fetch_has_code(rpc_url, &with_code).await?;
+    let present: Vec<&BuiltinLabel> = candidates
+        .iter()
+        .filter(|entry| entry.kind == EntryKind::Precompile || deployed.contains(&entry.address))
+        .collect();
+
+    write_labels(pool, &present).await?;
+    Ok(present.len())
+}
+
+/// Insert labels where none exist and flag genesis predeploys as contracts.
+pub async fn write_labels(pool: &PgPool, entries: &[&BuiltinLabel]) -> Result<()> {
+    let mut tx = pool.begin().await?;
+
+    for entry in entries {
+        let tags: Vec<&str> = entry
+            .tags
+            .iter()
+            .copied()
+            .chain(std::iter::once(BUILTIN_TAG))
+            .collect();
+        sqlx::query(
+            "INSERT INTO address_labels (address, name, tags)
+             VALUES ($1, $2, $3)
+             ON CONFLICT (address) DO NOTHING",
+        )
+        .bind(entry.address)
+        .bind(entry.name)
+        .bind(&tags)
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    let predeploys: Vec<&str> = entries
+        .iter()
+        .filter(|entry| entry.kind == EntryKind::Predeploy)
+        .map(|entry| entry.address)
+        .collect();
+    sqlx::query(
+        "INSERT INTO addresses (address, is_contract, first_seen_block, tx_count)
+         SELECT address, TRUE, 0, 0 FROM unnest($1::text[]) AS p(address)
+         ON CONFLICT (address) DO UPDATE SET is_contract = TRUE, first_seen_block = 0",
+    )
+    .bind(&predeploys)
+    .execute(&mut *tx)
+    .await?;
+    address_directory::refresh(&mut *tx, &predeploys).await?;
+
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Addresses among `addresses` with non-empty code, in one JSON-RPC batch.
+async fn fetch_has_code<'a>(rpc_url: &str, addresses: &[&'a str]) -> Result<Vec<&'a str>> {
+    if addresses.is_empty() {
+        return Ok(Vec::new());
+    }
+    let batch: Vec<serde_json::Value> = addresses
+        .iter()
+        .enumerate()
+        .map(|(id, address)| {
+            serde_json::json!({
+                "jsonrpc": "2.0",
+                "method": "eth_getCode",
+                "params": [address, "latest"],
+                "id": id,
+            })
+        })
+        .collect();
+
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(30))
+        .build()?;
+    let responses: Vec<serde_json::Value> = client
+        .post(rpc_url)
+        .json(&batch)
+        .send()
+        .await
+        .context("eth_getCode batch failed")?
+        .error_for_status()?
+        .json()
+        .await
+        .context("invalid eth_getCode batch response")?;
+
+    let mut deployed = Vec::new();
+    for response in responses {
+        let Some(address) = response
+            .get("id")
+            .and_then(|id| id.as_u64())
+            .and_then(|id| addresses.get(id as usize))
+        else {
+            bail!("eth_getCode batch response has an unknown id");
+        };
+        let code = response
+            .get("result")
+            .and_then(|result| result.as_str())
+            .unwrap_or_default();
+        if !matches!(code, "" | "0x" | "0x0") {
+            deployed.push(*address);
+        }
+    }
+    Ok(deployed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::labels::{normalize_label_address, normalize_tags, validate_label_name};
+
+    #[test]
+    fn registry_entries_pass_label_validation() {
+        let all = PRECOMPILES
+            .iter()
+            .chain(OP_STACK_PREDEPLOYS)
+            .chain(COMMON_DEPLOYMENTS);
+        let mut seen = std::collections::HashSet::new();
+        for entry in all {
+            assert_eq!(
+                normalize_label_address(entry.address).unwrap(),
+                entry.address
+            );
+            assert!(seen.insert(entry.address), "duplicate {}", entry.address);
+            validate_label_name(entry.name).unwrap();
+            let tags: Vec<String> = entry.tags.iter().map(|t| t.to_string()).collect();
+            assert_eq!(normalize_tags(&tags).unwrap(), tags);
+        }
+    }
+
+    #[test]
+    fn parses_setting() {
+        assert_eq!(BuiltinLabels::parse("auto").unwrap(), BuiltinLabels::Auto);
+        assert_eq!(
+            BuiltinLabels::parse("none").unwrap(),
+            BuiltinLabels::Sets(Vec::new())
+        );
+        assert_eq!(
+            BuiltinLabels::parse("precompiles, op-stack").unwrap(),
+            BuiltinLabels::Sets(vec![LabelSet::Precompiles, LabelSet::OpStack])
+        );
+        assert!(BuiltinLabels::parse("precompiles,arbitrum").is_err());
+    }
+
+    #[test]
+    fn auto_skips_l2_predeploys_on_ethereum() {
+        let mainnet = entries(&BuiltinLabels::Auto, 1);
+        assert!(mainnet.iter().all(|e| !e.address.starts_with("0x4200")));
+        assert!(mainnet.iter().any(|e| e.name == "Multicall3"));
+
+        let rollup = entries(&BuiltinLabels::Auto, 1234);
+        assert!(rollup.iter().any(|e| e.name == "Sequencer Fee Vault"));
+        assert!(entries(&BuiltinLabels::Sets(Vec::new()), 10).is_empty());
+    }
+
+    #[tokio::test]
+    async fn code_check_keeps_deployed_addresses() {
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                {"jsonrpc": "2.0", "id": 1, "result": "0x"},
+                {"jsonrpc": "2.0", "id": 0, "result": "0x6080"},
+            ])))
+            .mount(&server)
+            .await;
+
+        let deployed = fetch_has_code(&server.uri(), &["0xaa", "0xbb"])
+            .await
+            .unwrap();
+        assert_eq!(deployed, vec!["0xaa"]);
+    }
+}
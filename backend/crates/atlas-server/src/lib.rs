@@ -1,9 +1,20 @@
 pub mod api;
+pub mod bloom;
+pub mod chain_guard;
 pub mod cli;
 pub mod config;
+pub mod config_file;
+pub mod datasets;
+pub mod db_pools;
+pub mod export;
 pub mod faucet;
 pub mod head;
 pub mod indexer;
+pub mod labels;
 pub mod metrics;
 pub mod nft_metadata;
+pub mod ops_alerts;
+pub mod ordering;
+pub mod runtime_settings;
 pub mod state_keys;
+pub mod telemetry;
@@ -1,23 +1,34 @@
 use anyhow::{bail, Context, Result};
-use clap::Parser;
+use clap::{CommandFactory, FromArgMatches};
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::broadcast;
-use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 use alloy::providers::ProviderBuilder;
 use alloy::signers::local::PrivateKeySigner;
+use atlas_common::storage::{ColdBelowBlock, HotStorage, TieredStorage};
 
 mod api;
+mod bloom;
+mod chain_guard;
 mod cli;
 mod config;
+mod config_file;
+mod datasets;
+mod db_pools;
+mod export;
 mod faucet;
 mod head;
 mod indexer;
+mod labels;
 mod metrics;
 mod nft_metadata;
+mod ops_alerts;
+mod ordering;
+mod runtime_settings;
 mod snapshot;
 mod state_keys;
+mod telemetry;
 
 /// Retry delays for exponential backoff (in seconds)
 const RETRY_DELAYS: &[u64] = &[5, 10, 20, 30, 60];
@@ -31,24 +42,6 @@ const PORTABLE_PG_RESTORE_FLAGS: &[&str] = &[
 ];
 const RESET_DB_FOR_RESTORE_SQL: &str = "DROP SCHEMA public CASCADE; CREATE SCHEMA public;";
 
-fn init_tracing(filter: &str, format: &str) {
-    let env_filter = tracing_subscriber::EnvFilter::new(filter);
-    match format {
-        "json" => {
-            tracing_subscriber::registry()
-                .with(env_filter)
-                .with(tracing_subscriber::fmt::layer().json())
-                .init();
-        }
-        _ => {
-            tracing_subscriber::registry()
-                .with(env_filter)
-                .with(tracing_subscriber::fmt::layer())
-                .init();
-        }
-    }
-}
-
 fn required_db_url(db_url: &str) -> Result<&str> {
     let db_url = db_url.trim();
     if db_url.is_empty() {
@@ -204,15 +197,6 @@ pub(crate) fn postgres_command_async(
     command
 }
 
-fn portable_pg_dump_command(
-    program: &str,
-    config: &PostgresConnectionConfig,
-) -> std::process::Command {
-    let mut command = postgres_command(program, config);
-    command.args(PORTABLE_PG_DUMP_FLAGS);
-    command
-}
-
 pub(crate) fn portable_pg_dump_command_async(
     program: &str,
     config: &PostgresConnectionConfig,
@@ -253,12 +237,23 @@ async fn main() -> Result<()> {
     // Load .env before clap so env vars are available for clap's `env = "..."` fallback
     dotenvy::dotenv().ok();
 
-    let cli = cli::Cli::parse();
+    // A config file only fills in settings the environment leaves unset
+    let args: Vec<_> = std::env::args_os().collect();
+    let loaded = config_file::config_path(&args)
+        .map(|path| config_file::load(&path, &cli::run_command()))
+        .transpose()?;
+
+    let matches = cli::Cli::command().get_matches_from(args);
+    let cli = cli::Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
 
     match cli.command {
-        cli::Command::Run(args) => run(*args).await,
+        cli::Command::Run(args) | cli::Command::Check(args) if args.print_config => {
+            let (_, matches) = matches.subcommand().expect("subcommand was parsed");
+            print_config(*args, matches, loaded.as_ref())
+        }
+        cli::Command::Run(args) => run(*args, loaded.map(|loaded| loaded.path)).await,
         cli::Command::Migrate(args) => {
-            init_tracing(&args.log.level, &args.log.format);
+            let _telemetry = telemetry::init(&args.log)?;
             tracing::info!("Running database migrations");
             let database_url = required_db_url(&args.db.url)?;
             atlas_common::db::run_migrations(database_url).await?;
@@ -267,15 +262,30 @@ async fn main() -> Result<()> {
         }
         cli::Command::Check(args) => check(*args).await,
         cli::Command::Db(db_cmd) => match db_cmd.command {
-            cli::DbSubcommand::Dump { output, db_url } => cmd_db_dump(&db_url, &output),
-            cli::DbSubcommand::Restore { input, db_url } => cmd_db_restore(&db_url, &input),
+            cli::DbSubcommand::Dump { output, db_url } => cmd_db_dump(&db_url, &output).await,
+            cli::DbSubcommand::Restore { input, db_url } => cmd_db_restore(&db_url, &input).await,
             cli::DbSubcommand::Reset { confirm, db_url } => cmd_db_reset(&db_url, confirm).await,
         },
     }
 }
 
-async fn run(args: cli::RunArgs) -> Result<()> {
-    init_tracing(&args.log.level, &args.log.format);
+/// Validate `args` as `run` would, then print the resolved settings.
+fn print_config(
+    args: cli::RunArgs,
+    matches: &clap::ArgMatches,
+    loaded: Option<&config_file::LoadedConfig>,
+) -> Result<()> {
+    config::Config::from_run_args(args.clone())?;
+    config::FaucetConfig::from_faucet_args(&args.faucet)?;
+    print!(
+        "{}",
+        config_file::render(&cli::run_command(), matches, loaded)
+    );
+    Ok(())
+}
+
+async fn run(args: cli::RunArgs, config_file: Option<std::path::PathBuf>) -> Result<()> {
+    let telemetry = telemetry::init(&args.log)?;
     tracing::info!("Starting Atlas Server");
 
     // Install Prometheus metrics recorder
@@ -285,6 +295,23 @@ async fn run(args: cli::RunArgs) -> Result<()> {
     let config = config::Config::from_run_args(args.clone())?;
     let faucet_config = config::FaucetConfig::from_faucet_args(&args.faucet)?;
     let snapshot_config = config::SnapshotConfig::from_env(&config.database_url)?;
+    let settings = runtime_settings::RuntimeSettings::new(
+        runtime_settings::RuntimeValues::from_config(&config, &args.log.level),
+        Some(telemetry.log_filter()),
+    );
+    #[cfg(unix)]
+    {
+        let settings = settings.clone();
+        tokio::spawn(async move {
+            if let Err(e) =
+                runtime_settings::reload_on_sighup(settings, config_file, cli::run_command()).await
+            {
+                tracing::error!("SIGHUP handler terminated with error: {}", e);
+            }
+        });
+    }
+    #[cfg(not(unix))]
+    let _ = config_file;
     let faucet_amount_wei = faucet_config.amount_wei.as_ref().map(ToString::to_string);
     let faucet_cooldown_minutes = faucet_config.cooldown_minutes;
 
@@ -319,10 +346,53 @@ async fn run(args: cli::RunArgs) -> Result<()> {
     atlas_common::db::run_migrations(&config.database_url).await?;
 
     let indexer_pool =
-        atlas_common::db::create_pool(&config.database_url, config.indexer_db_max_connections)
-            .await?;
+        atlas_common::db::create_pool_with(&config.database_url, &config.indexer_db_pool).await?;
+    if config.reindex {
+        // The indexer truncates indexer_state and records the new chain ID itself.
+        tracing::warn!(chain_id, "reindex flag set, skipping stored chain ID check");
+    } else {
+        chain_guard::ensure_chain_id(&indexer_pool, chain_id).await?;
+    }
     let api_pool =
-        atlas_common::db::create_pool(&config.database_url, config.api_db_max_connections).await?;
+        atlas_common::db::create_pool_with(&config.database_url, &config.api_db_pool).await?;
+    let mut monitored_pools = vec![
+        db_pools::MonitoredPool::new("api", api_pool.clone(), config.api_db_pool.clone()),
+        db_pools::MonitoredPool::new(
+            "indexer",
+            indexer_pool.clone(),
+            config.indexer_db_pool.clone(),
+        ),
+    ];
+
+    let storage: Arc<dyn TieredStorage> = if config.cold_storage_before_block > 0 {
+        let cold_pool = match &config.cold_storage_url {
+            Some(url) => {
+                let pool = atlas_common::db::create_pool_with(url, &config.api_db_pool).await?;
+                monitored_pools.push(db_pools::MonitoredPool::new(
+                    "cold",
+                    pool.clone(),
+                    config.api_db_pool.clone(),
+                ));
+                pool
+            }
+            None => api_pool.clone(),
+        };
+        tracing::info!(
+            before_block = config.cold_storage_before_block,
+            schema = %config.cold_storage_schema,
+            "Serving old event logs and transfers from cold storage"
+        );
+        Arc::new(ColdBelowBlock::new(
+            api_pool.clone(),
+            cold_pool,
+            &config.cold_storage_schema,
+            i64::try_from(config.cold_storage_before_block)
+                .context("cold storage boundary block out of range")?,
+        )?)
+    } else {
+        Arc::new(HotStorage::new(api_pool.clone()))
+    };
+    let db_pools = Arc::new(db_pools::DbPools::new(monitored_pools));
 
     let (block_events_tx, _) = broadcast::channel(1024);
     let (da_events_tx, _) = broadcast::channel::<Vec<indexer::DaSseUpdate>>(256);
@@ -332,26 +402,40 @@ async fn run(args: cli::RunArgs) -> Result<()> {
         head::HeadTracker::bootstrap(&api_pool, config.sse_replay_buffer_blocks).await?
     });
 
-    // Set max pool size gauges
-    metrics.set_db_pool_max("api", config.api_db_max_connections as f64);
-    metrics.set_db_pool_max("indexer", config.indexer_db_max_connections as f64);
-
-    // Spawn pool stats sampler
+    // Keep the cached indexed height fresh for confirmation counts and block totals
     {
-        let api_pool_ref = api_pool.clone();
-        let indexer_pool_ref = indexer_pool.clone();
-        let metrics_ref = metrics.clone();
+        let head_tracker = head_tracker.clone();
+        let pool = api_pool.clone();
+        tokio::spawn(async move {
+            head_tracker
+                .run_height_refresh(pool, head::HEIGHT_REFRESH_INTERVAL)
+                .await;
+        });
+    }
+    if config.confirmations_head == head::ConfirmationsHead::Finalized {
+        let head_tracker = head_tracker.clone();
+        let rpc_url = config.rpc_url.clone();
         tokio::spawn(async move {
-            loop {
-                metrics_ref.set_db_pool_size("api", api_pool_ref.size() as f64);
-                metrics_ref.set_db_pool_idle("api", api_pool_ref.num_idle() as f64);
-                metrics_ref.set_db_pool_size("indexer", indexer_pool_ref.size() as f64);
-                metrics_ref.set_db_pool_idle("indexer", indexer_pool_ref.num_idle() as f64);
-                tokio::time::sleep(Duration::from_secs(5)).await;
+            if let Err(e) = head_tracker
+                .run_finalized_refresh(&rpc_url, head::FINALIZED_REFRESH_INTERVAL)
+                .await
+            {
+                tracing::error!("Finalized height refresh terminated with error: {}", e);
             }
         });
     }
 
+    // Pool size gauges and acquire-wait probe
+    {
+        let db_pools = db_pools.clone();
+        let metrics = metrics.clone();
+        tokio::spawn(async move {
+            db_pools
+                .run_sampler(metrics, db_pools::SAMPLE_INTERVAL)
+                .await;
+        });
+    }
+
     let state = Arc::new(api::AppState {
         pool: api_pool,
         block_events_tx: block_events_tx.clone(),
@@ -364,6 +448,7 @@ async fn run(args: cli::RunArgs) -> Result<()> {
         faucet_cooldown_minutes,
         chain_id,
         chain_name: config.chain_name.clone(),
+        native_currency: config.native_currency.clone(),
         chain_logo_url: config.chain_logo_url.clone(),
         chain_logo_url_light: config.chain_logo_url_light.clone(),
         chain_logo_url_dark: config.chain_logo_url_dark.clone(),
@@ -375,22 +460,88 @@ async fn run(args: cli::RunArgs) -> Result<()> {
         metrics: metrics.clone(),
         prometheus_handle,
         solc_cache_dir: config.solc_cache_dir.clone(),
+        verify_timeout: Duration::from_secs(config.verify_timeout_secs),
+        verify_max_source_bytes: config.verify_max_source_bytes,
+        export_dir: config.export_dir.clone(),
+        dataset_dir: config.dataset_dir.clone(),
+        db_circuit_breaker: api::circuit_breaker::CircuitBreaker::new(
+            config.db_circuit_breaker_threshold,
+            Duration::from_secs(config.db_circuit_breaker_cooldown_secs),
+        ),
+        admin_api_key: config.admin_api_key.clone(),
+        db_pools,
+        concurrency_limits: config.concurrency_limits,
+        body_limits: config.body_limits,
+        log_limits: config.log_limits,
+        row_limits: config.row_limits,
+        latency: api::latency::LatencyTracker::new(Duration::from_millis(config.slow_request_ms)),
+        storage,
+        confirmations_head: config.confirmations_head,
+        block_stall_secs: config.block_stall_secs,
+        runtime_settings: settings.clone(),
     });
 
+    let alerter = ops_alerts::OpsAlerter::from_config(&config)?;
+    let processors =
+        indexer::ProcessorRegistry::for_config(&config).build(&config.block_processors)?;
+    if !processors.is_empty() {
+        tracing::info!(
+            processors = ?processors.names().collect::<Vec<_>>(),
+            "block processors enabled"
+        );
+    }
     let da_pool = indexer_pool.clone();
     let gap_fill_events_tx = block_events_tx.clone();
-    let indexer = indexer::Indexer::new(
-        indexer_pool.clone(),
-        config.clone(),
-        block_events_tx,
-        head_tracker,
-        metrics.clone(),
-    );
-    tokio::spawn(async move {
-        if let Err(e) = run_with_retry(|| indexer.run()).await {
-            tracing::error!("Indexer terminated with error: {}", e);
-        }
-    });
+    let alert_events_tx = block_events_tx.clone();
+    if config.backfill_only {
+        tracing::info!("backfill-only mode, not following the chain head");
+    } else {
+        let indexer = indexer::Indexer::new(
+            indexer_pool.clone(),
+            config.clone(),
+            block_events_tx,
+            head_tracker,
+            metrics.clone(),
+            alerter.clone(),
+            settings.clone(),
+        )
+        .with_processors(processors.clone());
+        let alerter = alerter.clone();
+        tokio::spawn(async move {
+            let run = || async {
+                let result = indexer.run().await;
+                if let Err(e) = &result {
+                    alerter.fire(ops_alerts::OpsAlert::IndexerRestarting {
+                        error: format!("{e:#}"),
+                    });
+                }
+                result
+            };
+            if let Err(e) = run_with_retry(run).await {
+                tracing::error!("Indexer terminated with error: {}", e);
+            }
+        });
+    }
+
+    for shard in 0..config.backfill_shards {
+        let backfill = indexer::BackfillWorker::new(
+            indexer_pool.clone(),
+            &config.database_url,
+            &config.rpc_url,
+            config.rpc_requests_per_second,
+            metrics.clone(),
+            config.raw_archive_enabled,
+            alerter.clone(),
+            shard,
+        )?
+        .with_processors(processors.clone())
+        .with_wrapped_native(&config.wrapped_native_contracts);
+        tokio::spawn(async move {
+            if let Err(e) = run_until_done(|| backfill.run()).await {
+                tracing::error!("Backfill worker terminated with error: {}", e);
+            }
+        });
+    }
 
     let gap_fill_worker = indexer::GapFillWorker::new(
         indexer_pool.clone(),
@@ -399,7 +550,10 @@ async fn run(args: cli::RunArgs) -> Result<()> {
         config.rpc_requests_per_second,
         gap_fill_events_tx,
         metrics.clone(),
-    )?;
+        config.raw_archive_enabled,
+    )?
+    .with_processors(processors)
+    .with_wrapped_native(&config.wrapped_native_contracts);
     tokio::spawn(async move {
         if let Err(e) = run_with_retry(|| gap_fill_worker.run()).await {
             tracing::error!("Gap-fill worker terminated with error: {}", e);
@@ -431,23 +585,210 @@ async fn run(args: cli::RunArgs) -> Result<()> {
         });
     }
 
-    let metadata_pool = indexer_pool;
-    let metadata_config = config.clone();
-    let metadata_metrics = metrics.clone();
-    tokio::spawn(async move {
-        if let Err(e) = run_with_retry(|| async {
-            let fetcher = indexer::MetadataFetcher::new(
-                metadata_pool.clone(),
-                metadata_config.clone(),
-                metadata_metrics.clone(),
-            )?;
-            fetcher.run().await
-        })
-        .await
-        {
-            tracing::error!("Metadata fetcher terminated with error: {}", e);
-        }
-    });
+    // Built-in precompile/predeploy labels; best-effort, the explorer works without them
+    {
+        let pool = indexer_pool.clone();
+        let rpc_url = config.rpc_url.clone();
+        let builtin_labels = config.builtin_labels.clone();
+        tokio::spawn(async move {
+            match labels::builtin::seed(&pool, &rpc_url, &builtin_labels, chain_id).await {
+                Ok(0) => {}
+                Ok(count) => tracing::info!(count, "seeded built-in address labels"),
+                Err(e) => tracing::warn!(error = %e, "failed to seed built-in address labels"),
+            }
+        });
+    }
+
+    if config.calldata_prefix_index {
+        let pool = indexer_pool.clone();
+        tokio::spawn(async move {
+            if let Err(e) = run_until_done(|| indexer::calldata_index::ensure(&pool)).await {
+                tracing::error!("Calldata prefix index build terminated with error: {}", e);
+            }
+        });
+    }
+
+    if config.label_propagation_enabled {
+        let label_propagator = indexer::LabelPropagator::new(
+            indexer_pool.clone(),
+            Duration::from_secs(config.label_propagation_interval_secs),
+        )?;
+        tokio::spawn(async move {
+            if let Err(e) = run_with_retry(|| label_propagator.run()).await {
+                tracing::error!("Label propagation job terminated with error: {}", e);
+            }
+        });
+    }
+
+    if config.daily_stats_interval_secs > 0 {
+        let daily_stats = indexer::DailyStatsAggregator::new(
+            indexer_pool.clone(),
+            Duration::from_secs(config.daily_stats_interval_secs),
+        )?;
+        tokio::spawn(async move {
+            if let Err(e) = run_with_retry(|| daily_stats.run()).await {
+                tracing::error!("Daily stats aggregation terminated with error: {}", e);
+            }
+        });
+    }
+
+    if config.holder_ranks_interval_secs > 0 {
+        let holder_ranks = indexer::HolderRanker::new(
+            indexer_pool.clone(),
+            Duration::from_secs(config.holder_ranks_interval_secs),
+        )?;
+        tokio::spawn(async move {
+            if let Err(e) = run_with_retry(|| holder_ranks.run()).await {
+                tracing::error!("Holder ranking terminated with error: {}", e);
+            }
+        });
+    }
+
+    if config.data_quality_interval_secs > 0 {
+        // Archived transfers are no longer in the hot table, so their sums
+        // cannot be compared with balances.
+        let data_quality = indexer::DataQualityChecker::new(
+            indexer_pool.clone(),
+            Duration::from_secs(config.data_quality_interval_secs),
+            config.cold_storage_before_block == 0,
+        )?;
+        tokio::spawn(async move {
+            if let Err(e) = run_with_retry(|| data_quality.run()).await {
+                tracing::error!("Data quality checks terminated with error: {}", e);
+            }
+        });
+    }
+
+    if config.transfer_retention_blocks > 0 {
+        let transfer_aggregation = indexer::TransferAggregator::new(
+            indexer_pool.clone(),
+            config.transfer_retention_blocks,
+        )?;
+        tokio::spawn(async move {
+            if let Err(e) = run_with_retry(|| transfer_aggregation.run()).await {
+                tracing::error!("Transfer aggregation terminated with error: {}", e);
+            }
+        });
+    }
+
+    if config.canonical_tokens_interval_secs > 0 {
+        let canonical_tokens = indexer::CanonicalTokenResolver::new(
+            indexer_pool.clone(),
+            Duration::from_secs(config.canonical_tokens_interval_secs),
+        )?;
+        tokio::spawn(async move {
+            if let Err(e) = run_with_retry(|| canonical_tokens.run()).await {
+                tracing::error!("Canonical token resolution terminated with error: {}", e);
+            }
+        });
+    }
+
+    if config.pending_verification_interval_secs > 0 {
+        let matcher = indexer::PendingVerificationMatcher::new(
+            indexer_pool.clone(),
+            &config.rpc_url,
+            Duration::from_secs(config.pending_verification_interval_secs),
+        )?;
+        tokio::spawn(async move {
+            if let Err(e) = run_with_retry(|| matcher.run()).await {
+                tracing::error!("Pending verification matcher terminated with error: {}", e);
+            }
+        });
+    }
+
+    let dex_swaps_enabled = config
+        .block_processors
+        .iter()
+        .any(|name| name == indexer::dex_swaps::DEX_SWAPS_PROCESSOR);
+    if dex_swaps_enabled && config.dex_pool_interval_secs > 0 {
+        let resolver = indexer::DexPoolResolver::new(
+            indexer_pool.clone(),
+            &config.rpc_url,
+            Duration::from_secs(config.dex_pool_interval_secs),
+        )?;
+        tokio::spawn(async move {
+            if let Err(e) = run_with_retry(|| resolver.run()).await {
+                tracing::error!("DEX pool resolver terminated with error: {}", e);
+            }
+        });
+    }
+
+    if config.alerts_interval_secs > 0 {
+        let alert_engine = indexer::AlertEngine::new(
+            indexer_pool.clone(),
+            alert_events_tx,
+            Duration::from_secs(config.alerts_interval_secs),
+            config.alerts_allow_private_webhooks,
+        )?;
+        tokio::spawn(async move {
+            if let Err(e) = run_with_retry(|| alert_engine.run()).await {
+                tracing::error!("Alert engine terminated with error: {}", e);
+            }
+        });
+    }
+
+    if let Some(export_dir) = &config.export_dir {
+        let export_worker = export::ExportWorker::new(
+            indexer_pool.clone(),
+            &config.database_url,
+            export_dir,
+            Duration::from_secs(config.export_retention_hours * 3600),
+        )?;
+        tokio::spawn(async move {
+            if let Err(e) = run_with_retry(|| export_worker.run()).await {
+                tracing::error!("Export worker terminated with error: {}", e);
+            }
+        });
+    }
+
+    if let Some(dataset_dir) = &config.dataset_dir {
+        let dataset_worker =
+            datasets::DatasetWorker::new(indexer_pool.clone(), &config.database_url, dataset_dir);
+        tokio::spawn(async move {
+            if let Err(e) = run_with_retry(|| dataset_worker.run()).await {
+                tracing::error!("Dataset worker terminated with error: {}", e);
+            }
+        });
+    }
+
+    // Reindex jobs are queued through the admin API.
+    if config.admin_api_key.is_some() {
+        let contract_reindexer = indexer::ContractReindexer::new(
+            indexer_pool.clone(),
+            &config.database_url,
+            config.cold_storage_before_block > 0,
+        )
+        .with_wrapped_native(&config.wrapped_native_contracts);
+        tokio::spawn(async move {
+            if let Err(e) = run_with_retry(|| contract_reindexer.run()).await {
+                tracing::error!("Contract reindex worker terminated with error: {}", e);
+            }
+        });
+    }
+
+    if config.metadata_external {
+        tracing::info!("token metadata is left to atlas-metadata");
+    } else {
+        let metadata_pool = indexer_pool;
+        let metadata_config = indexer::MetadataConfig::from(&config);
+        let metadata_metrics = metrics.clone();
+        let metadata_settings = settings.clone();
+        tokio::spawn(async move {
+            if let Err(e) = run_with_retry(|| async {
+                let fetcher = indexer::MetadataFetcher::new(
+                    metadata_pool.clone(),
+                    metadata_config.clone(),
+                    metadata_metrics.clone(),
+                    metadata_settings.clone(),
+                )?;
+                fetcher.run().await
+            })
+            .await
+            {
+                tracing::error!("Metadata fetcher terminated with error: {}", e);
+            }
+        });
+    }
 
     // Spawn snapshot scheduler if enabled
     if snapshot_config.enabled {
@@ -474,10 +815,11 @@ async fn run(args: cli::RunArgs) -> Result<()> {
 }
 
 async fn check(args: cli::RunArgs) -> Result<()> {
-    init_tracing(&args.log.level, &args.log.format);
+    let _telemetry = telemetry::init(&args.log)?;
 
     let config = config::Config::from_run_args(args.clone())?;
     config::FaucetConfig::from_faucet_args(&args.faucet)?;
+    indexer::ProcessorRegistry::for_config(&config).build(&config.block_processors)?;
 
     // Test DB connectivity
     tracing::info!("testing database connectivity");
@@ -490,27 +832,52 @@ async fn check(args: cli::RunArgs) -> Result<()> {
     let chain_id = fetch_chain_id(&config.rpc_url).await?;
     tracing::info!(chain_id, "RPC OK");
 
+    let state_exists: bool = sqlx::query_scalar("SELECT to_regclass('indexer_state') IS NOT NULL")
+        .fetch_one(&pool)
+        .await?;
+    if !state_exists {
+        tracing::info!("database not migrated yet, skipping chain ID check");
+    } else if let Some(stored) = chain_guard::stored_chain_id(&pool).await? {
+        chain_guard::check_matches(stored, chain_id)?;
+        tracing::info!(chain_id, "chain ID matches database");
+    } else {
+        tracing::info!("no chain ID recorded in database yet");
+    }
+
     tracing::info!("configuration is valid");
     Ok(())
 }
 
-fn cmd_db_dump(db_url: &str, output: &str) -> Result<()> {
-    let config = postgres_connection_config(db_url)?;
-    let status = portable_pg_dump_command("pg_dump", &config)
-        .args(["--file", output])
-        .status()
-        .map_err(|e| anyhow::anyhow!("Failed to run pg_dump (is it installed?): {e}"))?;
-
-    if !status.success() {
-        anyhow::bail!("pg_dump exited with status {status}");
+async fn cmd_db_dump(db_url: &str, output: &str) -> Result<()> {
+    let manifest = snapshot::dump(required_db_url(db_url)?, output).await?;
+    if output != "-" {
+        snapshot::write_manifest(output, &manifest).await?;
+        eprintln!(
+            "Dump written to {output}, manifest to {}",
+            snapshot::manifest_path(output)
+        );
     }
-    eprintln!("Dump written to {output}");
     Ok(())
 }
 
-fn cmd_db_restore(db_url: &str, input: &str) -> Result<()> {
+async fn cmd_db_restore(db_url: &str, input: &str) -> Result<()> {
+    let db_url = required_db_url(db_url)?;
     let config = postgres_connection_config(db_url)?;
 
+    let manifest = if input == "-" {
+        None
+    } else {
+        snapshot::read_manifest_file(input).await?
+    };
+    if let Some(manifest) = &manifest {
+        let pool = atlas_common::db::create_pool(db_url, 1).await?;
+        let expected_version = atlas_common::db::migration_info(&pool)
+            .await?
+            .expected_version;
+        pool.close().await;
+        snapshot::check_restorable(manifest, expected_version)?;
+    }
+
     let reset_status = postgres_command("psql", &config)
         .arg("--dbname")
         .arg(&config.database_name)
@@ -537,7 +904,17 @@ fn cmd_db_restore(db_url: &str, input: &str) -> Result<()> {
     if !status.success() {
         anyhow::bail!("pg_restore exited with status {status}");
     }
+    snapshot::finish_restore(db_url).await?;
     eprintln!("Restore complete from {input}");
+    if let Some(manifest) = manifest {
+        eprintln!(
+            "Snapshot taken {} at block {}; the indexer resumes from there",
+            manifest.created_at.to_rfc3339(),
+            manifest
+                .last_indexed_block
+                .map_or_else(|| "none".to_string(), |b| b.to_string())
+        );
+    }
     Ok(())
 }
 
@@ -548,11 +925,15 @@ async fn cmd_db_reset(db_url: &str, confirm: bool) -> Result<()> {
     }
 
     let pool = atlas_common::db::create_pool(required_db_url(db_url)?, 1).await?;
+    indexer::partitions::drop_staged(&pool).await?;
     sqlx::query(
         "TRUNCATE blocks, transactions, event_logs, addresses, nft_contracts, nft_tokens,
-         nft_transfers, indexer_state, erc20_contracts, erc20_transfers, erc20_balances,
-         event_signatures, address_labels, proxy_contracts, contract_abis, failed_blocks,
-         tx_hash_lookup, block_da_status CASCADE",
+         nft_token_attributes, nft_transfers, indexer_state, erc20_contracts, erc20_transfers, erc20_transfer_summaries, erc20_balances, erc20_approvals,
+         erc20_holder_ranks, erc20_holder_rank_state, event_signatures, address_labels, proxy_contracts, contract_abis, failed_blocks,
+         tx_hash_lookup, block_da_status, derived_address_labels, label_suggestions,
+         label_api_keys, address_notes, saved_filters, alert_rules, alert_notifications, raw_rpc_cache, raw_block_archive, genesis_accounts, daily_stats, export_jobs,
+         processed_blocks, token_curation, canonical_tokens, data_quality_issues, range_leases,
+         pending_verifications, compilation_cache, dex_swaps, dex_pools, safe_events, safe_executions, validator_events, nft_sales, address_directory, contract_reindex_jobs, verification_history, contract_logos, dataset_dumps, dataset_skipped_days CASCADE",
     )
     .execute(&pool)
     .await?;
@@ -639,6 +1020,31 @@ where
     }
 }
 
+/// Like [`run_with_retry`], for jobs that finish: returns once `f` succeeds.
+async fn run_until_done<F, Fut>(f: F) -> Result<()>
+where
+    F: Fn() -> Fut,
+    Fut: std::future::Future<Output = Result<()>>,
+{
+    let mut retry_count = 0;
+
+    while let Err(e) = f().await {
+        let delay = RETRY_DELAYS
+            .get(retry_count)
+            .copied()
+            .unwrap_or(MAX_RETRY_DELAY);
+        tracing::error!(
+            error = %e,
+            restart_in_secs = delay,
+            attempt = retry_count + 1,
+            "job failed, restarting"
+        );
+        tokio::time::sleep(Duration::from_secs(delay)).await;
+        retry_count += 1;
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
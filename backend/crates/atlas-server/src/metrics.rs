@@ -56,6 +56,10 @@ impl Metrics {
             "atlas_http_request_duration_seconds",
             "HTTP request latency in seconds"
         );
+        describe_counter!(
+            "atlas_http_requests_shed_total",
+            "Requests rejected with 429 because their route group was at its concurrency limit"
+        );
 
         // -- Indexer --
         describe_counter!(
@@ -99,6 +103,10 @@ impl Metrics {
             "atlas_indexer_rpc_requests_total",
             "RPC batch requests by status"
         );
+        describe_counter!(
+            "atlas_indexer_erc20_zero_balances_pruned_total",
+            "ERC-20 balance rows deleted after reaching zero"
+        );
 
         // -- DA Worker --
         describe_counter!(
@@ -121,6 +129,40 @@ impl Metrics {
             "Failed metadata fetches by type"
         );
 
+        // -- Data quality --
+        describe_gauge!(
+            "atlas_data_quality_open_issues",
+            "Unresolved data-quality issues by kind"
+        );
+
+        // -- Transfer retention --
+        describe_counter!(
+            "atlas_transfer_aggregation_rows_total",
+            "Raw ERC-20 transfers folded into daily summaries and deleted"
+        );
+
+        // -- Dataset dumps --
+        describe_counter!(
+            "atlas_dataset_days_skipped_total",
+            "Days the dataset worker skipped for missing blocks"
+        );
+
+        // -- Parallel backfill --
+        describe_counter!(
+            "atlas_backfill_blocks_total",
+            "Blocks written by backfill workers"
+        );
+        describe_gauge!(
+            "atlas_backfill_ranges_remaining",
+            "Backfill ranges not yet complete"
+        );
+
+        // -- Ops alerts --
+        describe_counter!(
+            "atlas_ops_alerts_failed_total",
+            "Ops alerts the Slack or PagerDuty receiver did not accept"
+        );
+
         // -- SSE --
         describe_gauge!(
             "atlas_sse_active_connections",
@@ -131,6 +173,10 @@ impl Metrics {
         describe_gauge!("atlas_db_pool_size", "Total connections in pool");
         describe_gauge!("atlas_db_pool_idle", "Idle connections in pool");
         describe_gauge!("atlas_db_pool_max", "Max configured connections for pool");
+        describe_histogram!(
+            "atlas_db_pool_acquire_wait_seconds",
+            "Time the pool sampler waited to acquire a connection"
+        );
 
         // -- Errors --
         describe_counter!(
@@ -236,6 +282,11 @@ impl Metrics {
         gauge!("atlas_db_pool_max", "pool" => pool_name.to_string()).set(max);
     }
 
+    pub fn record_db_pool_acquire_wait(&self, pool_name: &str, seconds: f64) {
+        histogram!("atlas_db_pool_acquire_wait_seconds", "pool" => pool_name.to_string())
+            .record(seconds);
+    }
+
     // -- Error helper --
 
     /// Increment the error counter with component and error_type labels.
@@ -11,6 +11,24 @@ use reqwest::StatusCode;
 /// Maximum size of an NFT metadata payload (HTTP response body or data: URI).
 const MAX_METADATA_BYTES: usize = 2 * 1024 * 1024; // 2 MB
 
+/// Attributes beyond this many per token are not indexed.
+const MAX_ATTRIBUTES_PER_TOKEN: usize = 100;
+
+/// Longer trait types or values are not indexed.
+const MAX_ATTRIBUTE_LEN: usize = 256;
+
+/// Maximum size of metadata as stored, after normalization.
+const MAX_STORED_METADATA_BYTES: usize = 256 * 1024;
+
+/// `name` is cut to the width of `nft_tokens.name`.
+const MAX_NAME_CHARS: usize = 255;
+
+/// Other text fields are cut to this many characters.
+const MAX_TEXT_CHARS: usize = 10_000;
+
+/// Objects and arrays nested deeper than this are dropped.
+const MAX_METADATA_DEPTH: usize = 8;
+
 pub const NFT_METADATA_PENDING: &str = "pending";
 pub const NFT_METADATA_FETCHED: &str = "fetched";
 pub const NFT_METADATA_RETRYABLE_ERROR: &str = "retryable_error";
@@ -32,6 +50,8 @@ pub struct FetchError {
 pub struct ExtractedMetadata {
     pub name: Option<String>,
     pub image_url: Option<String>,
+    /// `(trait_type, value)` pairs from the `attributes` array.
+    pub attributes: Vec<(String, String)>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -112,7 +132,247 @@ pub fn extract_metadata_fields(
         .and_then(|value| value.as_str())
         .map(|image| resolve_uri(image, ipfs_gateway));
 
-    ExtractedMetadata { name, image_url }
+    ExtractedMetadata {
+        name,
+        image_url,
+        attributes: extract_attributes(metadata),
+    }
+}
+
+/// Trait pairs from the OpenSea-style `attributes` array.
+///
+/// Entries need a non-empty string `trait_type` and a string, number or
+/// boolean `value`; anything else (nested objects, value-only entries) is
+/// skipped. Runs of whitespace become one space and duplicate pairs are
+/// collapsed.
+pub fn extract_attributes(metadata: &serde_json::Value) -> Vec<(String, String)> {
+    let mut attributes: Vec<(String, String)> = Vec::new();
+    let entries = metadata
+        .get("attributes")
+        .and_then(|value| value.as_array())
+        .into_iter()
+        .flatten();
+
+    for entry in entries {
+        let Some(trait_type) = entry
+            .get("trait_type")
+            .and_then(|value| value.as_str())
+            .map(collapse_whitespace)
+        else {
+            continue;
+        };
+        let value = match entry.get("value") {
+            Some(serde_json::Value::String(value)) => collapse_whitespace(value),
+            Some(serde_json::Value::Number(value)) => value.to_string(),
+            Some(serde_json::Value::Bool(value)) => value.to_string(),
+            _ => continue,
+        };
+        if trait_type.is_empty()
+            || value.is_empty()
+            || trait_type.len() > MAX_ATTRIBUTE_LEN
+            || value.len() > MAX_ATTRIBUTE_LEN
+        {
+            continue;
+        }
+
+        let pair = (trait_type, value);
+        if !attributes.contains(&pair) {
+            attributes.push(pair);
+        }
+        if attributes.len() == MAX_ATTRIBUTES_PER_TOKEN {
+            break;
+        }
+    }
+
+    attributes
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Validate metadata against the OpenSea metadata standard and sanitize it
+/// for storage.
+///
+/// The document must be a JSON object. Standard fields of the wrong type are
+/// dropped, as are URLs outside http(s), `ipfs://`, `ar://` and media `data:`
+/// URIs. HTML tags, `<script>`/`<style>` blocks and control characters are
+/// stripped from every text value, which is also cut to length. An
+/// `attributes` object map becomes the standard array of
+/// `{trait_type, value}` entries, and raw SVG `image_data` becomes a
+/// `data:image/svg+xml` URI.
+pub fn normalize_metadata(metadata: serde_json::Value) -> Result<serde_json::Value, FetchError> {
+    let serde_json::Value::Object(fields) = metadata else {
+        return Err(permanent_error("metadata_not_object"));
+    };
+
+    let mut normalized = serde_json::Map::new();
+    for (key, value) in fields {
+        let value = match key.as_str() {
+            "name" | "title" => text_field(&value, MAX_NAME_CHARS),
+            "description" => text_field(&value, MAX_TEXT_CHARS),
+            "image" | "image_url" | "imageUrl" | "external_url" | "animation_url"
+            | "youtube_url" => url_field(&value),
+            "image_data" => image_data_field(&value),
+            "background_color" => color_field(&value),
+            "attributes" => attributes_field(value),
+            _ => sanitize_value(value, 1),
+        };
+        if let Some(value) = value {
+            normalized.insert(sanitize_text(&key, MAX_ATTRIBUTE_LEN), value);
+        }
+    }
+
+    let normalized = serde_json::Value::Object(normalized);
+    let size = serde_json::to_vec(&normalized).map_or(usize::MAX, |bytes| bytes.len());
+    if size > MAX_STORED_METADATA_BYTES {
+        return Err(permanent_error("metadata_too_large"));
+    }
+    Ok(normalized)
+}
+
+fn text_field(value: &serde_json::Value, max_chars: usize) -> Option<serde_json::Value> {
+    let text = sanitize_text(value.as_str()?, max_chars);
+    (!text.is_empty()).then_some(serde_json::Value::String(text))
+}
+
+fn url_field(value: &serde_json::Value) -> Option<serde_json::Value> {
+    let url = value.as_str()?.trim();
+    let lower = url.to_ascii_lowercase();
+    let allowed = ["http://", "https://", "ipfs://", "ar://"]
+        .iter()
+        .any(|scheme| lower.starts_with(scheme))
+        || ["data:image/", "data:video/", "data:audio/", "data:model/"]
+            .iter()
+            .any(|media| lower.starts_with(media));
+    (allowed && !url.chars().any(char::is_control))
+        .then(|| serde_json::Value::String(url.to_string()))
+}
+
+fn image_data_field(value: &serde_json::Value) -> Option<serde_json::Value> {
+    let data = value.as_str()?.trim();
+    if data.to_ascii_lowercase().starts_with("data:image/") {
+        return url_field(value);
+    }
+    if data.starts_with("<svg") || data.starts_with("<?xml") {
+        // Rendered through <img>, where SVG scripts don't run.
+        let encoded = base64::engine::general_purpose::STANDARD.encode(data);
+        return Some(serde_json::Value::String(format!(
+            "data:image/svg+xml;base64,{encoded}"
+        )));
+    }
+    None
+}
+
+fn color_field(value: &serde_json::Value) -> Option<serde_json::Value> {
+    let color = value.as_str()?.trim();
+    let color = color.strip_prefix('#').unwrap_or(color);
+    (color.len() == 6 && color.chars().all(|c| c.is_ascii_hexdigit()))
+        .then(|| serde_json::Value::String(color.to_ascii_lowercase()))
+}
+
+fn attributes_field(value: serde_json::Value) -> Option<serde_json::Value> {
+    let entries: Vec<serde_json::Value> = match value {
+        serde_json::Value::Array(entries) => entries
+            .into_iter()
+            .filter(serde_json::Value::is_object)
+            .collect(),
+        // {"Background": "Gold"} → [{"trait_type": "Background", "value": "Gold"}]
+        serde_json::Value::Object(traits) => traits
+            .into_iter()
+            .map(
+                |(trait_type, value)| serde_json::json!({"trait_type": trait_type, "value": value}),
+            )
+            .collect(),
+        _ => return None,
+    };
+    let entries = entries
+        .into_iter()
+        .take(MAX_ATTRIBUTES_PER_TOKEN)
+        .filter_map(|entry| sanitize_value(entry, 2))
+        .collect();
+    Some(serde_json::Value::Array(entries))
+}
+
+fn sanitize_value(value: serde_json::Value, depth: usize) -> Option<serde_json::Value> {
+    match value {
+        serde_json::Value::String(text) => Some(serde_json::Value::String(sanitize_text(
+            &text,
+            MAX_TEXT_CHARS,
+        ))),
+        serde_json::Value::Array(items) => {
+            if depth >= MAX_METADATA_DEPTH {
+                return None;
+            }
+            Some(serde_json::Value::Array(
+                items
+                    .into_iter()
+                    .filter_map(|item| sanitize_value(item, depth + 1))
+                    .collect(),
+            ))
+        }
+        serde_json::Value::Object(fields) => {
+            if depth >= MAX_METADATA_DEPTH {
+                return None;
+            }
+            Some(serde_json::Value::Object(
+                fields
+                    .into_iter()
+                    .filter_map(|(key, value)| {
+                        let value = sanitize_value(value, depth + 1)?;
+                        Some((sanitize_text(&key, MAX_ATTRIBUTE_LEN), value))
+                    })
+                    .collect(),
+            ))
+        }
+        other => Some(other),
+    }
+}
+
+/// Plain text: `<script>`/`<style>` blocks and HTML tags removed, control
+/// characters other than newlines and tabs dropped, trimmed and cut to
+/// `max_chars`.
+pub fn sanitize_text(text: &str, max_chars: usize) -> String {
+    let mut out = String::with_capacity(text.len().min(max_chars));
+    let mut rest = text;
+    while let Some(start) = rest.find('<') {
+        out.push_str(&rest[..start]);
+        let tag = &rest[start..];
+        let is_tag = tag[1..]
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '/' || c == '!' || c == '?');
+        if !is_tag {
+            out.push('<');
+            rest = &tag[1..];
+            continue;
+        }
+        let lower = tag.to_ascii_lowercase();
+        let block_end = ["script", "style"].iter().find_map(|name| {
+            let opens = lower[1..].starts_with(name)
+                && !lower[1 + name.len()..].starts_with(|c: char| c.is_ascii_alphanumeric());
+            opens.then(|| {
+                let close = format!("</{name}");
+                lower
+                    .find(&close)
+                    .and_then(|at| lower[at..].find('>').map(|end| at + end + 1))
+                    .unwrap_or(tag.len())
+            })
+        });
+        let end = block_end
+            .or_else(|| tag.find('>').map(|end| end + 1))
+            .unwrap_or(tag.len());
+        rest = &tag[end..];
+    }
+    out.push_str(rest);
+
+    out.chars()
+        .filter(|c| !c.is_control() || *c == '\n' || *c == '\t')
+        .collect::<String>()
+        .trim()
+        .chars()
+        .take(max_chars)
+        .collect()
 }
 
 pub async fn fetch_metadata(
@@ -125,7 +385,7 @@ pub async fn fetch_metadata(
         return Ok(FetchedMetadata::DirectImage { image_url: url });
     }
     if url.starts_with("data:") {
-        let metadata = parse_data_json_uri(&url)?;
+        let metadata = normalize_metadata(parse_data_json_uri(&url)?)?;
         let extracted = extract_metadata_fields(&metadata, ipfs_gateway);
 
         return Ok(FetchedMetadata::Json {
@@ -137,7 +397,7 @@ pub async fn fetch_metadata(
     validate_metadata_url_scheme(&url)?;
     // DNS rebinding protection is enforced by SsrfSafeResolver in the client.
 
-    let response = client
+    let mut response = client
         .get(&url)
         .send()
         .await
@@ -164,16 +424,23 @@ pub async fn fetch_metadata(
         return Err(permanent_error("response_too_large"));
     }
 
-    let bytes = response
-        .bytes()
+    // Chunked responses carry no Content-Length: stop reading once the body
+    // outgrows the limit instead of buffering all of it.
+    let mut bytes = Vec::new();
+    while let Some(chunk) = response
+        .chunk()
         .await
-        .map_err(|_| retryable_error("response_read_error"))?;
-    if bytes.len() > MAX_METADATA_BYTES {
-        return Err(permanent_error("response_too_large"));
+        .map_err(|_| retryable_error("response_read_error"))?
+    {
+        if bytes.len() + chunk.len() > MAX_METADATA_BYTES {
+            return Err(permanent_error("response_too_large"));
+        }
+        bytes.extend_from_slice(&chunk);
     }
 
     let metadata: serde_json::Value =
         serde_json::from_slice(&bytes).map_err(|_| permanent_error("json_parse_error"))?;
+    let metadata = normalize_metadata(metadata)?;
     let extracted = extract_metadata_fields(&metadata, ipfs_gateway);
 
     Ok(FetchedMetadata::Json {
@@ -295,7 +562,7 @@ fn validate_metadata_url_scheme(url: &str) -> Result<(), FetchError> {
     Ok(())
 }
 
-fn is_non_public_ip(ip: &IpAddr) -> bool {
+pub(crate) fn is_non_public_ip(ip: &IpAddr) -> bool {
     match ip {
         IpAddr::V4(v4) => {
             v4.is_loopback()
@@ -353,6 +620,107 @@ mod tests {
         );
     }
 
+    #[test]
+    fn extracts_trait_attributes() {
+        let metadata = serde_json::json!({
+            "attributes": [
+                {"trait_type": "Background", "value": " Gold "},
+                {"trait_type": "Level", "value": 5},
+                {"trait_type": "Shiny", "value": true},
+                {"trait_type": "Background", "value": "Gold"},
+                {"value": "no trait type"},
+                {"trait_type": "", "value": "empty"},
+                {"trait_type": "Nested", "value": {"a": 1}},
+                "not-an-object"
+            ]
+        });
+
+        assert_eq!(
+            extract_attributes(&metadata),
+            vec![
+                ("Background".to_string(), "Gold".to_string()),
+                ("Level".to_string(), "5".to_string()),
+                ("Shiny".to_string(), "true".to_string()),
+            ]
+        );
+        assert!(extract_attributes(&serde_json::json!({"attributes": "x"})).is_empty());
+    }
+
+    #[test]
+    fn normalize_rejects_non_object_metadata() {
+        for metadata in [serde_json::json!([1, 2]), serde_json::json!("name")] {
+            assert_eq!(
+                normalize_metadata(metadata).unwrap_err().code,
+                "metadata_not_object"
+            );
+        }
+    }
+
+    #[test]
+    fn normalize_sanitizes_standard_fields() {
+        let metadata = serde_json::json!({
+            "name": "<b>Ape</b> #1<script>alert(1)</script>",
+            "description": "I <3 NFTs\u{0007}<style>p{}</style><img src=x onerror=alert(1)>!",
+            "image": "javascript:alert(1)",
+            "external_url": "https://example.com/1",
+            "animation_url": 42,
+            "background_color": "#FFAA00",
+            "image_data": "<svg xmlns='http://www.w3.org/2000/svg'/>",
+            "properties": {"artist": "<i>Someone</i>"}
+        });
+
+        let normalized = normalize_metadata(metadata).unwrap();
+        assert_eq!(normalized["name"], "Ape #1");
+        assert_eq!(normalized["description"], "I <3 NFTs!");
+        assert!(normalized.get("image").is_none());
+        assert_eq!(normalized["external_url"], "https://example.com/1");
+        assert!(normalized.get("animation_url").is_none());
+        assert_eq!(normalized["background_color"], "ffaa00");
+        assert!(normalized["image_data"]
+            .as_str()
+            .unwrap()
+            .starts_with("data:image/svg+xml;base64,"));
+        assert_eq!(normalized["properties"]["artist"], "Someone");
+    }
+
+    #[test]
+    fn normalize_converts_attribute_maps_and_caps_size() {
+        let metadata = serde_json::json!({
+            "attributes": {"Background": "  Deep   Blue ", "Level": 3}
+        });
+        let normalized = normalize_metadata(metadata).unwrap();
+        assert_eq!(
+            extract_attributes(&normalized),
+            vec![
+                ("Background".to_string(), "Deep Blue".to_string()),
+                ("Level".to_string(), "3".to_string()),
+            ]
+        );
+
+        let junk: Vec<String> = (0..100)
+            .map(|i| format!("{i}{}", "x".repeat(5000)))
+            .collect();
+        let err = normalize_metadata(serde_json::json!({ "junk": junk })).unwrap_err();
+        assert_eq!(err.code, "metadata_too_large");
+        assert_eq!(err.kind, FetchErrorKind::Permanent);
+    }
+
+    #[test]
+    fn normalize_drops_deeply_nested_values() {
+        let mut nested = serde_json::json!("leaf");
+        for _ in 0..MAX_METADATA_DEPTH + 2 {
+            nested = serde_json::json!({ "inner": nested });
+        }
+        let normalized = normalize_metadata(serde_json::json!({ "deep": nested })).unwrap();
+        let mut depth = 0;
+        let mut value = &normalized["deep"];
+        while let Some(inner) = value.get("inner") {
+            depth += 1;
+            value = inner;
+        }
+        assert!(depth < MAX_METADATA_DEPTH);
+    }
+
     #[test]
     fn capped_retries_become_permanent_errors() {
         let now = Utc.with_ymd_and_hms(2026, 4, 22, 18, 0, 0).unwrap();
@@ -412,4 +780,47 @@ mod tests {
             other => panic!("expected json metadata, got {other:?}"),
         }
     }
+
+    #[tokio::test]
+    async fn chunked_response_over_the_limit_is_cut_off() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind test listener");
+        let port = listener.local_addr().expect("listener addr").port();
+
+        // Streams 64 KiB chunks without a Content-Length until the client
+        // hangs up, which it must do long before the stream ends.
+        let server = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.expect("accept connection");
+            let mut buf = [0u8; 1024];
+            let _ = stream.read(&mut buf).await.expect("read request");
+            stream
+                .write_all(
+                    b"HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nTransfer-Encoding: chunked\r\n\r\n",
+                )
+                .await
+                .expect("write headers");
+            let chunk = format!("10000\r\n{}\r\n", " ".repeat(0x10000));
+            while stream.write_all(chunk.as_bytes()).await.is_ok() {}
+        });
+
+        let client = reqwest::Client::new();
+        let error = tokio::time::timeout(
+            std::time::Duration::from_secs(10),
+            fetch_metadata(
+                &client,
+                &format!("http://localhost:{port}/token/1"),
+                "https://ipfs.io/ipfs/",
+            ),
+        )
+        .await
+        .expect("fetch stops reading")
+        .expect_err("oversized body is rejected");
+
+        assert_eq!(error.kind, FetchErrorKind::Permanent);
+        assert_eq!(error.code, "response_too_large");
+        server.await.expect("server sees the client hang up");
+    }
 }
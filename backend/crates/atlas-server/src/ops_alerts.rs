@@ -0,0 +1,388 @@
+//! Operator alerts for indexing gaps, posted to Slack and PagerDuty.
+//!
+//! ## Design
+//!
+//! The indexer and backfill workers fire an [`OpsAlert`] when blocks end up
+//! in `failed_blocks`, and the head indexer's supervisor fires one each time
+//! the indexer fails and is restarted from the outer retry loop. The head
+//! indexer also fires one when the node's head has not advanced for
+//! `BLOCK_STALL_SECS`, which usually means the sequencer stalled. Alerts go to
+//! a Slack incoming webhook (`OPS_ALERT_SLACK_WEBHOOK_URL`) and/or the
+//! PagerDuty Events API v2 (`OPS_ALERT_PAGERDUTY_ROUTING_KEY`), or any
+//! receiver accepting either payload.
+//!
+//! Delivery runs in a spawned task so a slow or unreachable receiver never
+//! holds up indexing, and is not retried: the next occurrence alerts again.
+//! An RPC outage fails every batch, so each kind of alert is sent at most
+//! once per `OPS_ALERT_COOLDOWN_SECS`; PagerDuty events also share a dedup
+//! key per chain and kind, so repeats fold into one incident.
+
+use anyhow::{bail, Result};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::config::Config;
+
+pub const PAGERDUTY_EVENTS_URL: &str = "https://events.pagerduty.com/v2/enqueue";
+
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+/// Failed block numbers listed in one alert.
+const MAX_LISTED_BLOCKS: usize = 20;
+
+/// Something operators should hear about before users notice missing data.
+#[derive(Debug, Clone)]
+pub enum OpsAlert {
+    /// Blocks that could not be fetched were stored in `failed_blocks`.
+    FailedBlocks { blocks: Vec<u64>, error: String },
+    /// The head indexer failed and is restarting from the outer retry loop.
+    IndexerRestarting { error: String },
+    /// The chain head has stayed on `block` for `stalled_secs`.
+    BlockProductionStalled { block: u64, stalled_secs: u64 },
+}
+
+impl OpsAlert {
+    fn kind(&self) -> &'static str {
+        match self {
+            OpsAlert::FailedBlocks { .. } => "failed_blocks",
+            OpsAlert::IndexerRestarting { .. } => "indexer_restarting",
+            OpsAlert::BlockProductionStalled { .. } => "block_production_stalled",
+        }
+    }
+
+    fn summary(&self, chain: &str) -> String {
+        match self {
+            OpsAlert::FailedBlocks { blocks, .. } => {
+                let mut listed = blocks
+                    .iter()
+                    .take(MAX_LISTED_BLOCKS)
+                    .map(u64::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                if blocks.len() > MAX_LISTED_BLOCKS {
+                    listed.push_str(", ...");
+                }
+                format!(
+                    "{chain}: {} block(s) stored in failed_blocks ({listed})",
+                    blocks.len()
+                )
+            }
+            OpsAlert::IndexerRestarting { .. } => {
+                format!("{chain}: indexer failed and is restarting")
+            }
+            OpsAlert::BlockProductionStalled {
+                block,
+                stalled_secs,
+            } => format!("{chain}: no new block for {stalled_secs}s (head is block {block})"),
+        }
+    }
+
+    fn error(&self) -> Option<&str> {
+        match self {
+            OpsAlert::FailedBlocks { error, .. } | OpsAlert::IndexerRestarting { error } => {
+                Some(error)
+            }
+            OpsAlert::BlockProductionStalled { .. } => None,
+        }
+    }
+}
+
+struct Inner {
+    client: reqwest::Client,
+    chain: String,
+    slack_webhook_url: Option<String>,
+    pagerduty: Option<(String, String)>,
+    cooldown: Duration,
+    last_sent: Mutex<HashMap<&'static str, Instant>>,
+}
+
+/// Sends [`OpsAlert`]s to the configured receivers; does nothing when none
+/// is configured. Cheap to clone.
+#[derive(Clone, Default)]
+pub struct OpsAlerter {
+    inner: Option<Arc<Inner>>,
+}
+
+impl OpsAlerter {
+    pub fn new(
+        chain: &str,
+        slack_webhook_url: Option<String>,
+        pagerduty_routing_key: Option<String>,
+        pagerduty_url: &str,
+        cooldown_secs: u64,
+    ) -> Result<Self> {
+        let urls = slack_webhook_url
+            .as_deref()
+            .into_iter()
+            .chain(pagerduty_routing_key.as_ref().map(|_| pagerduty_url));
+        for url in urls {
+            match reqwest::Url::parse(url) {
+                Ok(parsed) if matches!(parsed.scheme(), "http" | "https") => {}
+                _ => bail!("ops alert URL must be an http(s) URL: {url}"),
+            }
+        }
+        if slack_webhook_url.is_none() && pagerduty_routing_key.is_none() {
+            return Ok(Self::default());
+        }
+        Ok(Self {
+            inner: Some(Arc::new(Inner {
+                client: reqwest::Client::builder()
+                    .timeout(WEBHOOK_TIMEOUT)
+                    .build()?,
+                chain: chain.to_string(),
+                slack_webhook_url,
+                pagerduty: pagerduty_routing_key.map(|key| (pagerduty_url.to_string(), key)),
+                cooldown: Duration::from_secs(cooldown_secs),
+                last_sent: Mutex::new(HashMap::new()),
+            })),
+        })
+    }
+
+    pub fn from_config(config: &Config) -> Result<Self> {
+        Self::new(
+            &config.chain_name,
+            config.ops_alert_slack_webhook_url.clone(),
+            config.ops_alert_pagerduty_routing_key.clone(),
+            &config.ops_alert_pagerduty_url,
+            config.ops_alert_cooldown_secs,
+        )
+    }
+
+    /// Send `alert` in the background unless one of its kind was sent within
+    /// the cooldown.
+    pub fn fire(&self, alert: OpsAlert) {
+        let Some(inner) = self.inner.clone() else {
+            return;
+        };
+        if !inner.claim(alert.kind()) {
+            return;
+        }
+        tokio::spawn(async move { inner.deliver(&alert).await });
+    }
+}
+
+impl Inner {
+    fn claim(&self, kind: &'static str) -> bool {
+        let mut last_sent = self.last_sent.lock().unwrap();
+        let now = Instant::now();
+        if let Some(sent) = last_sent.get(kind) {
+            if now.duration_since(*sent) < self.cooldown {
+                return false;
+            }
+        }
+        last_sent.insert(kind, now);
+        true
+    }
+
+    async fn deliver(&self, alert: &OpsAlert) {
+        let mut requests = Vec::new();
+        if let Some(url) = &self.slack_webhook_url {
+            requests.push(("slack", url, slack_payload(&self.chain, alert)));
+        }
+        if let Some((url, routing_key)) = &self.pagerduty {
+            requests.push((
+                "pagerduty",
+                url,
+                pagerduty_payload(routing_key, &self.chain, alert),
+            ));
+        }
+        for (receiver, url, payload) in requests {
+            let result = self
+                .client
+                .post(url)
+                .json(&payload)
+                .send()
+                .await
+                .and_then(|response| response.error_for_status());
+            match result {
+                Ok(_) => tracing::info!(receiver, kind = alert.kind(), "sent ops alert"),
+                Err(e) => {
+                    metrics::counter!("atlas_ops_alerts_failed_total", "receiver" => receiver)
+                        .increment(1);
+                    tracing::warn!(receiver, kind = alert.kind(), error = %e, "ops alert delivery failed");
+                }
+            }
+        }
+    }
+}
+
+/// Slack incoming webhook message.
+fn slack_payload(chain: &str, alert: &OpsAlert) -> Value {
+    let text = match alert.error() {
+        Some(error) => format!(":warning: {}\n```{error}```", alert.summary(chain)),
+        None => format!(":warning: {}", alert.summary(chain)),
+    };
+    json!({ "text": text })
+}
+
+/// PagerDuty Events API v2 trigger event.
+fn pagerduty_payload(routing_key: &str, chain: &str, alert: &OpsAlert) -> Value {
+    let details = match alert {
+        OpsAlert::FailedBlocks { blocks, error } => json!({ "error": error, "blocks": blocks }),
+        OpsAlert::IndexerRestarting { error } => json!({ "error": error }),
+        OpsAlert::BlockProductionStalled {
+            block,
+            stalled_secs,
+        } => json!({ "block": block, "stalled_secs": stalled_secs }),
+    };
+    json!({
+        "routing_key": routing_key,
+        "event_action": "trigger",
+        "dedup_key": format!("atlas/{chain}/{}", alert.kind()),
+        "payload": {
+            "summary": alert.summary(chain),
+            "source": "atlas",
+            "severity": "error",
+            "component": "indexer",
+            "group": chain,
+            "class": alert.kind(),
+            "custom_details": details,
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{body_partial_json, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn failed_blocks(count: u64) -> OpsAlert {
+        OpsAlert::FailedBlocks {
+            blocks: (100..100 + count).collect(),
+            error: "rpc timeout".to_string(),
+        }
+    }
+
+    #[test]
+    fn summary_lists_a_bounded_number_of_blocks() {
+        assert_eq!(
+            failed_blocks(2).summary("Testnet"),
+            "Testnet: 2 block(s) stored in failed_blocks (100, 101)"
+        );
+        let summary = failed_blocks(50).summary("Testnet");
+        assert!(summary.starts_with("Testnet: 50 block(s)"));
+        assert!(summary.ends_with("119, ...)"));
+    }
+
+    #[test]
+    fn pagerduty_payload_dedups_per_chain_and_kind() {
+        let payload = pagerduty_payload("key", "Testnet", &failed_blocks(2));
+        assert_eq!(payload["routing_key"], "key");
+        assert_eq!(payload["event_action"], "trigger");
+        assert_eq!(payload["dedup_key"], "atlas/Testnet/failed_blocks");
+        assert_eq!(
+            payload["payload"]["custom_details"]["blocks"],
+            json!([100, 101])
+        );
+
+        let restart = OpsAlert::IndexerRestarting {
+            error: "db down".to_string(),
+        };
+        let payload = pagerduty_payload("key", "Testnet", &restart);
+        assert_eq!(payload["dedup_key"], "atlas/Testnet/indexer_restarting");
+        assert!(payload["payload"]["custom_details"]["blocks"].is_null());
+    }
+
+    #[test]
+    fn stall_alert_reports_the_stuck_head() {
+        let stall = OpsAlert::BlockProductionStalled {
+            block: 42,
+            stalled_secs: 90,
+        };
+        assert_eq!(
+            slack_payload("Testnet", &stall)["text"],
+            ":warning: Testnet: no new block for 90s (head is block 42)"
+        );
+        let payload = pagerduty_payload("key", "Testnet", &stall);
+        assert_eq!(
+            payload["dedup_key"],
+            "atlas/Testnet/block_production_stalled"
+        );
+        assert_eq!(
+            payload["payload"]["custom_details"],
+            json!({"block": 42, "stalled_secs": 90})
+        );
+    }
+
+    #[test]
+    fn new_without_receivers_is_disabled() {
+        let alerter = OpsAlerter::new("Testnet", None, None, PAGERDUTY_EVENTS_URL, 300).unwrap();
+        assert!(alerter.inner.is_none());
+    }
+
+    #[test]
+    fn new_rejects_non_http_urls() {
+        assert!(OpsAlerter::new(
+            "Testnet",
+            Some("ftp://hooks.example.com".to_string()),
+            None,
+            PAGERDUTY_EVENTS_URL,
+            300
+        )
+        .is_err());
+        assert!(
+            OpsAlerter::new("Testnet", None, Some("key".to_string()), "not a url", 300).is_err()
+        );
+    }
+
+    #[test]
+    fn claim_allows_each_kind_once_per_cooldown() {
+        let alerter = OpsAlerter::new(
+            "Testnet",
+            Some("https://hooks.example.com/slack".to_string()),
+            None,
+            PAGERDUTY_EVENTS_URL,
+            300,
+        )
+        .unwrap();
+        let inner = alerter.inner.unwrap();
+        assert!(inner.claim("failed_blocks"));
+        assert!(!inner.claim("failed_blocks"));
+        assert!(inner.claim("indexer_restarting"));
+
+        let alerter = OpsAlerter::new(
+            "Testnet",
+            Some("https://hooks.example.com/slack".to_string()),
+            None,
+            PAGERDUTY_EVENTS_URL,
+            0,
+        )
+        .unwrap();
+        let inner = alerter.inner.unwrap();
+        assert!(inner.claim("failed_blocks"));
+        assert!(inner.claim("failed_blocks"));
+    }
+
+    #[tokio::test]
+    async fn deliver_posts_to_every_receiver() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/slack"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/pagerduty"))
+            .and(body_partial_json(json!({
+                "routing_key": "key",
+                "dedup_key": "atlas/Testnet/failed_blocks",
+            })))
+            .respond_with(ResponseTemplate::new(202))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let alerter = OpsAlerter::new(
+            "Testnet",
+            Some(format!("{}/slack", server.uri())),
+            Some("key".to_string()),
+            &format!("{}/pagerduty", server.uri()),
+            300,
+        )
+        .unwrap();
+        alerter.inner.unwrap().deliver(&failed_blocks(1)).await;
+    }
+}
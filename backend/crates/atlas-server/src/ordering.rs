@@ -0,0 +1,82 @@
+//! `ORDER BY` terms that give chain rows a total order.
+//!
+//! Lists of transactions, logs and rows decoded from logs are ordered by
+//! block, then position in the block, then transaction hash. Transactions are
+//! keyed by `(hash, block_number)` and log-derived tables are unique on
+//! `(tx_hash, log_index, block_number)`, so no two rows tie: a row on a page
+//! boundary cannot swap places between requests, and `LIMIT ... OFFSET` pages
+//! neither repeat nor skip rows.
+
+/// Sort direction of a list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Asc,
+    Desc,
+}
+
+impl Direction {
+    pub fn as_sql(self) -> &'static str {
+        match self {
+            Self::Asc => "ASC",
+            Self::Desc => "DESC",
+        }
+    }
+}
+
+/// Kind of row being ordered, which names its position and hash columns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChainRow {
+    /// `transactions`: `block_index`, `hash`
+    Transaction,
+    /// `event_logs`, transfers and decoded events: `log_index`, `tx_hash`
+    Log,
+}
+
+impl ChainRow {
+    pub fn position_column(self) -> &'static str {
+        match self {
+            Self::Transaction => "block_index",
+            Self::Log => "log_index",
+        }
+    }
+
+    pub fn hash_column(self) -> &'static str {
+        match self {
+            Self::Transaction => "hash",
+            Self::Log => "tx_hash",
+        }
+    }
+
+    /// `ORDER BY` terms over the columns of table alias `alias` (unqualified
+    /// when empty), all sorted in `direction`.
+    pub fn order_by(self, alias: &str, direction: Direction) -> String {
+        let prefix = if alias.is_empty() {
+            String::new()
+        } else {
+            format!("{alias}.")
+        };
+        let dir = direction.as_sql();
+        format!(
+            "{prefix}block_number {dir}, {prefix}{} {dir}, {prefix}{} {dir}",
+            self.position_column(),
+            self.hash_column()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn order_by_ends_with_the_hash_tiebreaker() {
+        assert_eq!(
+            ChainRow::Transaction.order_by("", Direction::Desc),
+            "block_number DESC, block_index DESC, hash DESC"
+        );
+        assert_eq!(
+            ChainRow::Log.order_by("t", Direction::Asc),
+            "t.block_number ASC, t.log_index ASC, t.tx_hash ASC"
+        );
+    }
+}
@@ -0,0 +1,291 @@
+//! Settings that can change while the server runs.
+//!
+//! ## Design
+//!
+//! The RPC rate limit, the indexer and RPC batch sizes, the metadata worker
+//! count and the log filter are read from a shared [`RuntimeSettings`]
+//! instead of `Config`. The indexer picks them up at the start of every
+//! batch and the metadata fetcher at the start of every pass, so a change
+//! never interrupts work in flight.
+//!
+//! Changes come from `PATCH /api/admin/runtime-settings`, or from SIGHUP,
+//! which re-reads the `--config` file and applies the values it sets for
+//! these keys, whether or not the environment overrode them at startup.
+//! Either way, a change lasts until the next restart, which reads the
+//! configuration afresh. Every other setting still needs a restart.
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use std::str::FromStr;
+use std::sync::{Arc, RwLock};
+use tracing_subscriber::EnvFilter;
+
+use crate::config::Config;
+use crate::telemetry::LogFilterHandle;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RuntimeValues {
+    pub rpc_requests_per_second: u32,
+    /// Blocks per indexer batch (one DB transaction)
+    pub batch_size: u64,
+    /// Blocks per JSON-RPC batch request
+    pub rpc_batch_size: u32,
+    pub metadata_fetch_workers: u32,
+    /// `RUST_LOG`-style filter directives
+    pub log_level: String,
+}
+
+impl RuntimeValues {
+    pub fn from_config(config: &Config, log_level: &str) -> Self {
+        Self {
+            rpc_requests_per_second: config.rpc_requests_per_second,
+            batch_size: config.batch_size,
+            rpc_batch_size: config.rpc_batch_size,
+            metadata_fetch_workers: config.metadata_fetch_workers,
+            log_level: log_level.to_string(),
+        }
+    }
+
+    fn validate(&self) -> Result<()> {
+        let counts = [
+            (
+                "rpc_requests_per_second",
+                u64::from(self.rpc_requests_per_second),
+            ),
+            ("batch_size", self.batch_size),
+            ("rpc_batch_size", u64::from(self.rpc_batch_size)),
+            (
+                "metadata_fetch_workers",
+                u64::from(self.metadata_fetch_workers),
+            ),
+        ];
+        for (name, value) in counts {
+            if value == 0 {
+                bail!("{name} must be greater than 0");
+            }
+        }
+        EnvFilter::try_new(&self.log_level)
+            .with_context(|| format!("invalid log_level {:?}", self.log_level))?;
+        Ok(())
+    }
+}
+
+impl Default for RuntimeValues {
+    /// The CLI defaults.
+    fn default() -> Self {
+        Self {
+            rpc_requests_per_second: 100,
+            batch_size: 100,
+            rpc_batch_size: 20,
+            metadata_fetch_workers: 4,
+            log_level: "atlas_server=info,tower_http=debug,sqlx=warn".to_string(),
+        }
+    }
+}
+
+/// A partial change; omitted fields keep their current value.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RuntimeUpdate {
+    pub rpc_requests_per_second: Option<u32>,
+    pub batch_size: Option<u64>,
+    pub rpc_batch_size: Option<u32>,
+    pub metadata_fetch_workers: Option<u32>,
+    pub log_level: Option<String>,
+}
+
+impl RuntimeUpdate {
+    /// The runtime settings among a config file's (env var, value) pairs.
+    pub fn from_env_settings(settings: &[(String, String)]) -> Result<Self> {
+        let mut update = Self::default();
+        for (var, value) in settings {
+            match var.as_str() {
+                "RPC_REQUESTS_PER_SECOND" => {
+                    update.rpc_requests_per_second = Some(parse(var, value)?)
+                }
+                "BATCH_SIZE" => update.batch_size = Some(parse(var, value)?),
+                "RPC_BATCH_SIZE" => update.rpc_batch_size = Some(parse(var, value)?),
+                "METADATA_FETCH_WORKERS" => {
+                    update.metadata_fetch_workers = Some(parse(var, value)?)
+                }
+                "RUST_LOG" => update.log_level = Some(value.clone()),
+                _ => {}
+            }
+        }
+        Ok(update)
+    }
+}
+
+fn parse<T>(var: &str, value: &str) -> Result<T>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    value
+        .trim()
+        .parse()
+        .map_err(|e| anyhow::anyhow!("invalid {var} {value:?}: {e}"))
+}
+
+/// Shared handle to the current [`RuntimeValues`]. Cheap to clone.
+#[derive(Clone)]
+pub struct RuntimeSettings {
+    values: Arc<RwLock<RuntimeValues>>,
+    log_filter: Option<LogFilterHandle>,
+}
+
+impl RuntimeSettings {
+    pub fn new(values: RuntimeValues, log_filter: Option<LogFilterHandle>) -> Self {
+        Self {
+            values: Arc::new(RwLock::new(values)),
+            log_filter,
+        }
+    }
+
+    pub fn current(&self) -> RuntimeValues {
+        self.values.read().unwrap().clone()
+    }
+
+    /// Validate and apply `update`, returning the new values. Nothing changes
+    /// when any field is invalid.
+    pub fn update(&self, update: RuntimeUpdate) -> Result<RuntimeValues> {
+        let mut values = self.values.write().unwrap();
+        let mut next = values.clone();
+        if let Some(rps) = update.rpc_requests_per_second {
+            next.rpc_requests_per_second = rps;
+        }
+        if let Some(batch_size) = update.batch_size {
+            next.batch_size = batch_size;
+        }
+        if let Some(rpc_batch_size) = update.rpc_batch_size {
+            next.rpc_batch_size = rpc_batch_size;
+        }
+        if let Some(workers) = update.metadata_fetch_workers {
+            next.metadata_fetch_workers = workers;
+        }
+        if let Some(log_level) = update.log_level {
+            next.log_level = log_level;
+        }
+        next.validate()?;
+
+        if next.log_level != values.log_level {
+            if let Some(log_filter) = &self.log_filter {
+                log_filter
+                    .reload(EnvFilter::new(&next.log_level))
+                    .context("failed to replace the log filter")?;
+            }
+        }
+        if next != *values {
+            tracing::info!(
+                rpc_requests_per_second = next.rpc_requests_per_second,
+                batch_size = next.batch_size,
+                rpc_batch_size = next.rpc_batch_size,
+                metadata_fetch_workers = next.metadata_fetch_workers,
+                log_level = %next.log_level,
+                "runtime settings updated"
+            );
+        }
+        *values = next.clone();
+        Ok(next)
+    }
+}
+
+impl Default for RuntimeSettings {
+    /// The CLI defaults, without a log filter to reload.
+    fn default() -> Self {
+        Self::new(RuntimeValues::default(), None)
+    }
+}
+
+/// Re-read `config_file`, whose keys are `command`'s flags, on every SIGHUP
+/// and apply its runtime settings.
+#[cfg(unix)]
+pub async fn reload_on_sighup(
+    settings: RuntimeSettings,
+    config_file: Option<std::path::PathBuf>,
+    command: clap::Command,
+) -> Result<()> {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    let mut hangup = signal(SignalKind::hangup())?;
+    while hangup.recv().await.is_some() {
+        let Some(path) = &config_file else {
+            tracing::warn!("SIGHUP received without --config; nothing to reload");
+            continue;
+        };
+        let reloaded = crate::config_file::read(path, &command)
+            .and_then(|file| RuntimeUpdate::from_env_settings(&file))
+            .and_then(|update| settings.update(update));
+        match reloaded {
+            Ok(_) => tracing::info!(path = %path.display(), "reloaded runtime settings"),
+            Err(e) => tracing::error!(
+                path = %path.display(),
+                error = format!("{e:#}"),
+                "config reload failed, keeping current settings"
+            ),
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn update_changes_only_given_fields() {
+        let settings = RuntimeSettings::default();
+        let values = settings
+            .update(RuntimeUpdate {
+                batch_size: Some(500),
+                log_level: Some("debug".to_string()),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(values.batch_size, 500);
+        assert_eq!(values.log_level, "debug");
+        assert_eq!(values.rpc_batch_size, 20);
+        assert_eq!(settings.current(), values);
+    }
+
+    #[test]
+    fn invalid_update_changes_nothing() {
+        let settings = RuntimeSettings::default();
+        let err = settings
+            .update(RuntimeUpdate {
+                batch_size: Some(500),
+                metadata_fetch_workers: Some(0),
+                ..Default::default()
+            })
+            .unwrap_err();
+        assert!(err.to_string().contains("metadata_fetch_workers"));
+        assert!(settings
+            .update(RuntimeUpdate {
+                log_level: Some("atlas_server=loud".to_string()),
+                ..Default::default()
+            })
+            .is_err());
+        assert_eq!(settings.current().batch_size, 100);
+    }
+
+    #[test]
+    fn env_settings_map_to_runtime_fields() {
+        let settings = [
+            ("BATCH_SIZE", "250"),
+            ("RPC_REQUESTS_PER_SECOND", "40"),
+            ("RUST_LOG", "info"),
+            ("CHAIN_NAME", "Testnet"),
+        ]
+        .map(|(var, value)| (var.to_string(), value.to_string()));
+        let update = RuntimeUpdate::from_env_settings(&settings).unwrap();
+        assert_eq!(update.batch_size, Some(250));
+        assert_eq!(update.rpc_requests_per_second, Some(40));
+        assert_eq!(update.log_level.as_deref(), Some("info"));
+        assert!(update.rpc_batch_size.is_none());
+
+        let bad = [("BATCH_SIZE".to_string(), "lots".to_string())];
+        let err = RuntimeUpdate::from_env_settings(&bad).unwrap_err();
+        assert!(err.to_string().contains("BATCH_SIZE"));
+    }
+}
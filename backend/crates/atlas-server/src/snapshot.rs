@@ -1,8 +1,10 @@
 use anyhow::{bail, Result};
 use chrono::{DateTime, NaiveTime, Utc};
+use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
 use crate::config::SnapshotConfig;
+use crate::state_keys::CHAIN_ID_KEY;
 
 const SNAPSHOT_RETRY_DELAYS: &[u64] = &[5, 10, 20, 30, 60];
 const SNAPSHOT_MAX_RETRY_DELAY: u64 = 60;
@@ -34,6 +36,128 @@ fn sleep_duration(target: NaiveTime, now: DateTime<Utc>, retry_attempt: Option<u
         .unwrap_or_else(|| duration_until_next(target, now))
 }
 
+/// What a dump contains, written next to it as `<dump>.json`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct SnapshotManifest {
+    pub(crate) created_at: DateTime<Utc>,
+    pub(crate) atlas_version: String,
+    /// Newest applied migration; `None` for an unmigrated database
+    pub(crate) schema_version: Option<i64>,
+    pub(crate) chain_id: Option<u64>,
+    pub(crate) last_indexed_block: Option<u64>,
+}
+
+pub(crate) fn manifest_path(dump_path: &str) -> String {
+    format!("{dump_path}.json")
+}
+
+async fn read_manifest(conn: &mut sqlx::PgConnection) -> Result<SnapshotManifest> {
+    let (chain_id, last_indexed_block): (Option<String>, Option<String>) = sqlx::query_as(
+        "SELECT (SELECT value FROM indexer_state WHERE key = $1),
+                (SELECT value FROM indexer_state WHERE key = 'last_indexed_block')",
+    )
+    .bind(CHAIN_ID_KEY)
+    .fetch_one(&mut *conn)
+    .await?;
+    let (tracked,): (bool,) = sqlx::query_as("SELECT to_regclass('_sqlx_migrations') IS NOT NULL")
+        .fetch_one(&mut *conn)
+        .await?;
+    let schema_version = if tracked {
+        sqlx::query_as::<_, (Option<i64>,)>(
+            "SELECT MAX(version) FROM _sqlx_migrations WHERE success",
+        )
+        .fetch_one(&mut *conn)
+        .await?
+        .0
+    } else {
+        None
+    };
+
+    Ok(SnapshotManifest {
+        created_at: Utc::now(),
+        atlas_version: env!("CARGO_PKG_VERSION").to_string(),
+        schema_version,
+        chain_id: chain_id.map(|v| v.parse()).transpose()?,
+        last_indexed_block: last_indexed_block.map(|v| v.parse()).transpose()?,
+    })
+}
+
+/// Dump the database to `output` with pg_dump. The dump and the returned
+/// manifest are read from one exported transaction snapshot, so they agree
+/// even while the indexer keeps writing.
+pub(crate) async fn dump(database_url: &str, output: &str) -> Result<SnapshotManifest> {
+    let pool = atlas_common::db::create_pool(database_url, 1).await?;
+    let mut tx = pool.begin().await?;
+    sqlx::query("SET TRANSACTION ISOLATION LEVEL REPEATABLE READ, READ ONLY")
+        .execute(&mut *tx)
+        .await?;
+    let (snapshot_id,): (String,) = sqlx::query_as("SELECT pg_export_snapshot()")
+        .fetch_one(&mut *tx)
+        .await?;
+    let manifest = read_manifest(&mut tx).await?;
+
+    let pg_config = crate::postgres_connection_config(database_url)?;
+    let status = crate::portable_pg_dump_command_async("pg_dump", &pg_config)
+        .arg(format!("--snapshot={snapshot_id}"))
+        .args(["--file", output])
+        .status()
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to run pg_dump (is it installed?): {e}"))?;
+    // The snapshot must stay exported until pg_dump has finished.
+    tx.rollback().await?;
+    pool.close().await;
+
+    if !status.success() {
+        bail!("pg_dump exited with status {status}");
+    }
+    Ok(manifest)
+}
+
+pub(crate) async fn write_manifest(dump_path: &str, manifest: &SnapshotManifest) -> Result<()> {
+    tokio::fs::write(
+        manifest_path(dump_path),
+        serde_json::to_vec_pretty(manifest)?,
+    )
+    .await?;
+    Ok(())
+}
+
+/// The manifest next to `dump_path`, if there is one.
+pub(crate) async fn read_manifest_file(dump_path: &str) -> Result<Option<SnapshotManifest>> {
+    match tokio::fs::read(manifest_path(dump_path)).await {
+        Ok(bytes) => Ok(Some(serde_json::from_slice(&bytes)?)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Refuse a snapshot whose schema is newer than the migrations built into
+/// this binary; an older one is brought up to date after the restore.
+pub(crate) fn check_restorable(manifest: &SnapshotManifest, expected_version: i64) -> Result<()> {
+    match manifest.schema_version {
+        Some(version) if version > expected_version => bail!(
+            "snapshot schema version {version} is newer than this build's {expected_version}; \
+             restore it with atlas-server {} or later",
+            manifest.atlas_version
+        ),
+        _ => Ok(()),
+    }
+}
+
+/// Bring a restored database up to this build's schema and free the
+/// backfill ranges the source deployment's workers were holding.
+pub(crate) async fn finish_restore(database_url: &str) -> Result<()> {
+    atlas_common::db::run_migrations(database_url).await?;
+    let pool = atlas_common::db::create_pool(database_url, 1).await?;
+    sqlx::query(
+        "UPDATE range_leases SET holder = NULL, leased_until = NULL WHERE completed_at IS NULL",
+    )
+    .execute(&pool)
+    .await?;
+    pool.close().await;
+    Ok(())
+}
+
 async fn attempt_snapshot(config: &SnapshotConfig) -> Result<()> {
     tokio::fs::create_dir_all(&config.dir).await?;
 
@@ -44,20 +168,22 @@ async fn attempt_snapshot(config: &SnapshotConfig) -> Result<()> {
 
     tracing::info!(%filename, "Starting database snapshot");
 
-    let pg_config = crate::postgres_connection_config(&config.database_url)?;
-    let status = crate::portable_pg_dump_command_async("pg_dump", &pg_config)
-        .args(["--file", tmp_path.as_str()])
-        .status()
-        .await?;
-
-    if status.success() {
-        tokio::fs::rename(&tmp_path, &final_path).await?;
-        tracing::info!(%filename, "Snapshot complete");
-        cleanup_old_snapshots(&config.dir, config.retention).await;
-        Ok(())
-    } else {
-        let _ = tokio::fs::remove_file(&tmp_path).await;
-        bail!("pg_dump failed with status: {status}");
+    match dump(&config.database_url, &tmp_path).await {
+        Ok(manifest) => {
+            write_manifest(&final_path, &manifest).await?;
+            tokio::fs::rename(&tmp_path, &final_path).await?;
+            tracing::info!(
+                %filename,
+                last_indexed_block = ?manifest.last_indexed_block,
+                "Snapshot complete"
+            );
+            cleanup_old_snapshots(&config.dir, config.retention).await;
+            Ok(())
+        }
+        Err(e) => {
+            let _ = tokio::fs::remove_file(&tmp_path).await;
+            Err(e)
+        }
     }
 }
 
@@ -130,6 +256,7 @@ async fn cleanup_old_snapshots(dir: &str, retention: u32) {
         if let Err(e) = tokio::fs::remove_file(&old).await {
             tracing::warn!(path = %old.display(), error = %e, "Failed to remove old snapshot");
         }
+        let _ = tokio::fs::remove_file(manifest_path(&old.to_string_lossy())).await;
     }
 }
 
@@ -214,6 +341,55 @@ mod tests {
         assert!(remaining.contains(&"atlas_snapshot_2026-03-06T03-00-00.dump.tmp".to_string()));
     }
 
+    fn manifest(schema_version: Option<i64>) -> SnapshotManifest {
+        SnapshotManifest {
+            created_at: Utc.with_ymd_and_hms(2026, 3, 19, 3, 0, 0).unwrap(),
+            atlas_version: "0.2.0".to_string(),
+            schema_version,
+            chain_id: Some(1234),
+            last_indexed_block: Some(5_000_000),
+        }
+    }
+
+    #[test]
+    fn check_restorable_rejects_newer_schema() {
+        assert!(check_restorable(&manifest(Some(20260101000000)), 20260101000000).is_ok());
+        assert!(check_restorable(&manifest(Some(20250101000000)), 20260101000000).is_ok());
+        assert!(check_restorable(&manifest(None), 20260101000000).is_ok());
+
+        let err = check_restorable(&manifest(Some(20270101000000)), 20260101000000).unwrap_err();
+        assert!(err.to_string().contains("0.2.0 or later"));
+    }
+
+    #[tokio::test]
+    async fn manifest_file_round_trips_and_cleanup_removes_it() {
+        let dir = tempfile::tempdir().unwrap();
+        let dir_path = dir.path().to_str().unwrap();
+
+        let mut dumps = Vec::new();
+        for i in 1..=2 {
+            let path = format!("{dir_path}/atlas_snapshot_2026-03-{i:02}T03-00-00.dump");
+            tokio::fs::write(&path, b"test").await.unwrap();
+            write_manifest(&path, &manifest(Some(i))).await.unwrap();
+            dumps.push(path);
+        }
+
+        assert_eq!(
+            read_manifest_file(&dumps[1]).await.unwrap(),
+            Some(manifest(Some(2)))
+        );
+        assert_eq!(
+            read_manifest_file(&format!("{dir_path}/missing.dump"))
+                .await
+                .unwrap(),
+            None
+        );
+
+        cleanup_old_snapshots(dir_path, 1).await;
+        assert!(read_manifest_file(&dumps[0]).await.unwrap().is_none());
+        assert!(read_manifest_file(&dumps[1]).await.unwrap().is_some());
+    }
+
     #[tokio::test]
     async fn cleanup_noop_when_under_retention() {
         let dir = tempfile::tempdir().unwrap();
@@ -1 +1,11 @@
+pub const CHAIN_ID_KEY: &str = "chain_id";
+pub const GENESIS_IMPORTED_KEY: &str = "genesis_imported_accounts";
 pub const ERC20_SUPPLY_HISTORY_COMPLETE_KEY: &str = "erc20_supply_history_complete";
+pub const LABEL_PROPAGATION_LAST_BLOCK_KEY: &str = "label_propagation_last_block";
+pub const LABEL_PROPAGATION_SYNCED_AT_KEY: &str = "label_propagation_synced_at";
+pub const DAILY_STATS_NEXT_DAY_KEY: &str = "daily_stats_next_day";
+pub const HOLDER_RANKS_LAST_BLOCK_KEY: &str = "holder_ranks_last_block";
+pub const DATA_QUALITY_LAST_BLOCK_KEY: &str = "data_quality_last_block";
+pub const TRANSFER_AGGREGATION_BEFORE_BLOCK_KEY: &str = "transfer_aggregation_before_block";
+pub const BACKFILL_SPLIT_BLOCK_KEY: &str = "backfill_split_block";
+pub const PENDING_VERIFICATION_LAST_BLOCK_KEY: &str = "pending_verification_last_block";
@@ -0,0 +1,146 @@
+//! Log and trace subscriber setup.
+//!
+//! Logs go to stdout as text or JSON. When an OTLP endpoint is configured,
+//! spans (HTTP requests, RPC batch fetches, DB writes) are also exported to a
+//! collector such as Tempo or Jaeger. Sampling and exporter headers follow the
+//! standard `OTEL_TRACES_SAMPLER*` / `OTEL_EXPORTER_OTLP_*HEADERS` variables.
+
+use anyhow::{Context, Result};
+use opentelemetry::propagation::Extractor;
+use opentelemetry::trace::TracerProvider as _;
+use opentelemetry_sdk::propagation::TraceContextPropagator;
+use opentelemetry_sdk::trace::SdkTracerProvider;
+use opentelemetry_sdk::Resource;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+use tracing_subscriber::{
+    layer::SubscriberExt, reload, util::SubscriberInitExt, EnvFilter, Layer, Registry,
+};
+
+use crate::cli::LogArgs;
+
+/// Swaps the active log filter without restarting.
+pub type LogFilterHandle = reload::Handle<EnvFilter, Registry>;
+
+/// Keeps the trace exporter alive; dropping it flushes pending spans.
+pub struct Telemetry {
+    provider: Option<SdkTracerProvider>,
+    log_filter: LogFilterHandle,
+}
+
+impl Telemetry {
+    pub fn log_filter(&self) -> LogFilterHandle {
+        self.log_filter.clone()
+    }
+}
+
+impl Drop for Telemetry {
+    fn drop(&mut self) {
+        if let Some(provider) = self.provider.take() {
+            if let Err(e) = provider.shutdown() {
+                eprintln!("failed to flush traces: {e}");
+            }
+        }
+    }
+}
+
+/// Install the global subscriber. Hold the returned guard until exit.
+pub fn init(args: &LogArgs) -> Result<Telemetry> {
+    let (env_filter, log_filter) = reload::Layer::new(EnvFilter::new(&args.level));
+    let fmt_layer = match args.format.as_str() {
+        "json" => tracing_subscriber::fmt::layer().json().boxed(),
+        _ => tracing_subscriber::fmt::layer().boxed(),
+    };
+
+    let provider = args
+        .otlp_endpoint
+        .as_deref()
+        .map(|endpoint| tracer_provider(endpoint, &args.otel_service_name))
+        .transpose()?;
+    let otel_layer = provider.as_ref().map(|provider| {
+        opentelemetry::global::set_text_map_propagator(TraceContextPropagator::new());
+        tracing_opentelemetry::layer().with_tracer(provider.tracer("atlas-server"))
+    });
+
+    // The log filter applies to output only, so the capture layer still sees
+    // the sqlx statements of slow requests when `sqlx` logs are off.
+    tracing_subscriber::registry()
+        .with(fmt_layer.and_then(otel_layer).with_filter(env_filter))
+        .with(crate::api::latency::query_capture_layer())
+        .init();
+
+    Ok(Telemetry {
+        provider,
+        log_filter,
+    })
+}
+
+fn tracer_provider(endpoint: &str, service_name: &str) -> Result<SdkTracerProvider> {
+    use opentelemetry_otlp::WithExportConfig;
+
+    let exporter = opentelemetry_otlp::SpanExporter::builder()
+        .with_http()
+        .with_endpoint(traces_endpoint(endpoint))
+        .build()
+        .context("Failed to build OTLP span exporter")?;
+    Ok(SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_resource(
+            Resource::builder()
+                .with_service_name(service_name.to_string())
+                .build(),
+        )
+        .build())
+}
+
+/// OTLP/HTTP traces URL for a collector base URL, following the spec's
+/// `OTEL_EXPORTER_OTLP_ENDPOINT` rule of appending `/v1/traces`.
+fn traces_endpoint(endpoint: &str) -> String {
+    let endpoint = endpoint.trim_end_matches('/');
+    if endpoint.ends_with("/v1/traces") {
+        endpoint.to_string()
+    } else {
+        format!("{endpoint}/v1/traces")
+    }
+}
+
+/// Continue the caller's trace when the request carries a W3C `traceparent`.
+/// A no-op unless trace export is enabled.
+pub fn set_remote_parent(span: &tracing::Span, headers: &axum::http::HeaderMap) {
+    let parent = opentelemetry::global::get_text_map_propagator(|propagator| {
+        propagator.extract(&HeaderExtractor(headers))
+    });
+    let _ = span.set_parent(parent);
+}
+
+struct HeaderExtractor<'a>(&'a axum::http::HeaderMap);
+
+impl Extractor for HeaderExtractor<'_> {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).and_then(|value| value.to_str().ok())
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        self.0.keys().map(|key| key.as_str()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn traces_endpoint_appends_signal_path_once() {
+        assert_eq!(
+            traces_endpoint("http://tempo:4318"),
+            "http://tempo:4318/v1/traces"
+        );
+        assert_eq!(
+            traces_endpoint("http://tempo:4318/"),
+            "http://tempo:4318/v1/traces"
+        );
+        assert_eq!(
+            traces_endpoint("http://tempo:4318/v1/traces"),
+            "http://tempo:4318/v1/traces"
+        );
+    }
+}
@@ -168,6 +168,349 @@ fn get_address_detail() {
     });
 }
 
+#[test]
+fn address_detail_includes_funder_and_deployer() {
+    common::run(async {
+        let pool = common::pool();
+        let funded = "0x50000000000000000000000000000000000000f1";
+        let deployed = "0x50000000000000000000000000000000000000f2";
+        let funding_tx = format!("0x{:064x}", 0x50f1);
+        let creation_tx = format!("0x{:064x}", 0x50f2);
+        sqlx::query(
+            "INSERT INTO addresses (address, is_contract, first_seen_block, tx_count,
+                                    funded_by, funded_block, funding_tx_hash,
+                                    deployer, creation_block, creation_tx_hash)
+             VALUES ($1, FALSE, 5010, 1, $3, 5010, $4, NULL, NULL, NULL),
+                    ($2, TRUE, 5011, 0, NULL, NULL, NULL, $1, 5011, $5)
+             ON CONFLICT (address) DO NOTHING",
+        )
+        .bind(funded)
+        .bind(deployed)
+        .bind(ADDR)
+        .bind(&funding_tx)
+        .bind(&creation_tx)
+        .execute(&pool)
+        .await
+        .expect("seed provenance");
+
+        let app = common::test_router();
+        let body = common::json_body(
+            app.clone()
+                .oneshot(
+                    Request::builder()
+                        .uri(format!("/api/addresses/{}", funded))
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(body["funded_by"]["address"], ADDR);
+        assert_eq!(body["funded_by"]["block_number"], 5010);
+        assert_eq!(body["funded_by"]["tx_hash"], funding_tx.as_str());
+        assert!(body.get("created_by").is_none());
+
+        let body = common::json_body(
+            app.oneshot(
+                Request::builder()
+                    .uri(format!("/api/addresses/{}", deployed))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap(),
+        )
+        .await;
+        assert_eq!(body["created_by"]["address"], funded);
+        assert_eq!(body["created_by"]["tx_hash"], creation_tx.as_str());
+        assert!(body.get("funded_by").is_none());
+    });
+}
+
+#[test]
+fn deployed_contracts_lists_creations_with_verification() {
+    common::run(async {
+        let pool = common::pool();
+        let deployer = "0x50000000000000000000000000000000000000d1";
+        let verified = "0x50000000000000000000000000000000000000d2";
+        let unverified = "0x50000000000000000000000000000000000000d3";
+        sqlx::query(
+            "INSERT INTO addresses (address, is_contract, first_seen_block, tx_count,
+                                    deployer, creation_block, creation_tx_hash)
+             VALUES ($2, TRUE, 5020, 0, $1, 5020, $4),
+                    ($3, TRUE, 5021, 0, $1, 5021, $5)
+             ON CONFLICT (address) DO NOTHING",
+        )
+        .bind(deployer)
+        .bind(verified)
+        .bind(unverified)
+        .bind(format!("0x{:064x}", 0x50d2))
+        .bind(format!("0x{:064x}", 0x50d3))
+        .execute(&pool)
+        .await
+        .expect("seed deployed contracts");
+        sqlx::query(
+            "INSERT INTO contract_abis (address, abi, contract_name) VALUES ($1, '[]', 'Vault')
+             ON CONFLICT (address) DO NOTHING",
+        )
+        .bind(verified)
+        .execute(&pool)
+        .await
+        .expect("seed verified contract");
+
+        let app = common::test_router();
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri(format!("/api/addresses/{}/deployed-contracts", deployer))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = common::json_body(response).await;
+        assert_eq!(body["total"], 2);
+        let data = body["data"].as_array().unwrap();
+        assert_eq!(data[0]["address"], unverified);
+        assert_eq!(data[0]["verified"], false);
+        assert_eq!(data[1]["address"], verified);
+        assert_eq!(data[1]["block_number"], 5020);
+        assert_eq!(data[1]["verified"], true);
+        assert_eq!(data[1]["contract_name"], "Vault");
+    });
+}
+
+#[test]
+fn address_nonces_report_sequence_and_gaps() {
+    common::run(async {
+        let pool = common::pool();
+        let sender = "0x50000000000000000000000000000000000000e1";
+        for (idx, nonce) in [Some(0i64), Some(1), Some(4), None].into_iter().enumerate() {
+            sqlx::query(
+                "INSERT INTO transactions (hash, block_number, block_index, from_address, nonce, to_address, value, gas_price, gas_used, input_data, status, timestamp)
+                 VALUES ($1, 5030, $2, $3, $4, $5, 0, 1, 21000, ''::bytea, TRUE, 1700005030)
+                 ON CONFLICT (hash, block_number) DO NOTHING",
+            )
+            .bind(format!("0x{:064x}", 0x50e100 + idx))
+            .bind(idx as i32)
+            .bind(sender)
+            .bind(nonce)
+            .bind(ADDR_TO)
+            .execute(&pool)
+            .await
+            .expect("seed transaction");
+        }
+
+        let app = common::test_router();
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri(format!("/api/addresses/{}/nonces?limit=2", sender))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = common::json_body(response).await;
+        assert_eq!(body["nonce_count"], 3);
+        assert_eq!(body["unknown_nonce_count"], 1);
+        assert_eq!(body["lowest_nonce"], 0);
+        assert_eq!(body["highest_nonce"], 4);
+        assert_eq!(body["next_nonce"], 5);
+        assert_eq!(body["gaps"], serde_json::json!([{ "from": 2, "to": 3 }]));
+        let page = &body["transactions"];
+        assert_eq!(page["total"], 3);
+        let data = page["data"].as_array().unwrap();
+        assert_eq!(data.len(), 2);
+        assert_eq!(data[0]["nonce"], 4);
+        assert_eq!(data[1]["nonce"], 1);
+    });
+}
+
+#[test]
+fn address_token_flows_sum_volume_per_token_and_direction() {
+    common::run(async {
+        let pool = common::pool();
+        let treasury = "0x50000000000000000000000000000000000000f1";
+        let counterparty = "0x50000000000000000000000000000000000000f2";
+        let token = "0x50000000000000000000000000000000000000f3";
+        let unknown_token = "0x50000000000000000000000000000000000000f4";
+
+        sqlx::query(
+            "INSERT INTO blocks (number, hash, parent_hash, timestamp, gas_used, gas_limit, transaction_count, indexed_at)
+             VALUES (5040, $1, $2, 1700005040, 0, 30000000, 0, NOW())
+             ON CONFLICT (number) DO NOTHING",
+        )
+        .bind(format!("0x{:064x}", 5040))
+        .bind(format!("0x{:064x}", 5039))
+        .execute(&pool)
+        .await
+        .expect("seed block");
+        sqlx::query(
+            "INSERT INTO erc20_contracts (address, name, symbol, decimals, first_seen_block)
+             VALUES ($1, 'Flow Token', 'FLOW', 6, 5040)
+             ON CONFLICT (address) DO NOTHING",
+        )
+        .bind(token)
+        .execute(&pool)
+        .await
+        .expect("seed token");
+
+        // Recent transfers are stamped after any indexed block so they fall in
+        // every period; the one at timestamp 0 only counts for `all`.
+        for (log_index, contract, from, to, value, timestamp) in [
+            (0, token, counterparty, treasury, 100i64, 5_000_000_000i64),
+            (1, token, treasury, counterparty, 30, 5_000_000_000),
+            (2, token, treasury, treasury, 5, 5_000_000_000),
+            (3, unknown_token, counterparty, treasury, 7, 5_000_000_000),
+            (4, token, treasury, counterparty, 1_000, 0),
+        ] {
+            sqlx::query(
+                "INSERT INTO erc20_transfers (tx_hash, log_index, block_number, contract_address, from_address, to_address, value, timestamp)
+                 VALUES ($1, $2, 5040, $3, $4, $5, $6, $7)
+                 ON CONFLICT DO NOTHING",
+            )
+            .bind(format!("0x{:064x}", 0x50f100))
+            .bind(log_index)
+            .bind(contract)
+            .bind(from)
+            .bind(to)
+            .bind(bigdecimal::BigDecimal::from(value))
+            .bind(timestamp)
+            .execute(&pool)
+            .await
+            .expect("seed transfer");
+        }
+
+        let app = common::test_router();
+        let get = |uri: String| {
+            let app = app.clone();
+            async move {
+                app.oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+                    .await
+                    .unwrap()
+            }
+        };
+
+        let response = get(format!("/api/addresses/{}/token-flows", treasury)).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = common::json_body(response).await;
+        assert_eq!(body["period"], "30d");
+        assert_eq!(
+            body["to_timestamp"].as_i64().unwrap() - body["from_timestamp"].as_i64().unwrap(),
+            30 * 86_400
+        );
+        let tokens = body["tokens"].as_array().unwrap();
+        assert_eq!(tokens.len(), 2);
+        assert_eq!(tokens[0]["contract_address"], token);
+        assert_eq!(tokens[0]["symbol"], "FLOW");
+        assert_eq!(tokens[0]["decimals"], 6);
+        assert_eq!(tokens[0]["inbound"], "105");
+        assert_eq!(tokens[0]["outbound"], "35");
+        assert_eq!(tokens[0]["net"], "70");
+        assert_eq!(tokens[0]["inbound_transfers"], 2);
+        assert_eq!(tokens[0]["outbound_transfers"], 2);
+        assert_eq!(tokens[1]["contract_address"], unknown_token);
+        assert!(tokens[1]["symbol"].is_null());
+        assert_eq!(tokens[1]["net"], "7");
+
+        let response = get(format!(
+            "/api/addresses/{}/token-flows?period=all",
+            treasury.to_uppercase().replace("0X", "0x")
+        ))
+        .await;
+        let body = common::json_body(response).await;
+        assert!(body["from_timestamp"].is_null());
+        let tokens = body["tokens"].as_array().unwrap();
+        assert_eq!(tokens[0]["outbound"], "1035");
+        assert_eq!(tokens[0]["net"], "-930");
+
+        let response = get(format!("/api/addresses/{}/token-flows?period=2w", treasury)).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    });
+}
+
+#[test]
+fn address_history_streams_as_ndjson() {
+    common::run(async {
+        let pool = common::pool();
+        let holder = "0x50000000000000000000000000000000000000d1";
+        let token = "0x50000000000000000000000000000000000000d2";
+        for (idx, block) in [5060i64, 5061, 5062].into_iter().enumerate() {
+            sqlx::query(
+                "INSERT INTO transactions (hash, block_number, block_index, from_address, to_address, value, gas_price, gas_used, input_data, status, timestamp)
+                 VALUES ($1, $2, 0, $3, $4, 0, 1, 21000, ''::bytea, TRUE, 1700005060)
+                 ON CONFLICT (hash, block_number) DO NOTHING",
+            )
+            .bind(format!("0x{:064x}", 0x50d100 + idx))
+            .bind(block)
+            .bind(holder)
+            .bind(ADDR_TO)
+            .execute(&pool)
+            .await
+            .expect("seed transaction");
+            sqlx::query(
+                "INSERT INTO erc20_transfers (tx_hash, log_index, block_number, contract_address, from_address, to_address, value, timestamp)
+                 VALUES ($1, 0, $2, $3, $4, $5, 1, 1700005060)
+                 ON CONFLICT DO NOTHING",
+            )
+            .bind(format!("0x{:064x}", 0x50d100 + idx))
+            .bind(block)
+            .bind(token)
+            .bind(ADDR_TO)
+            .bind(holder)
+            .execute(&pool)
+            .await
+            .expect("seed transfer");
+        }
+
+        let app = common::test_router();
+        let stream = |uri: String| {
+            let app = app.clone();
+            async move {
+                let response = app
+                    .oneshot(
+                        Request::builder()
+                            .uri(uri)
+                            .header("accept", "application/x-ndjson")
+                            .body(Body::empty())
+                            .unwrap(),
+                    )
+                    .await
+                    .unwrap();
+                assert_eq!(response.status(), StatusCode::OK);
+                common::ndjson_body(response).await
+            }
+        };
+
+        let rows = stream(format!("/api/addresses/{}/transactions?limit=1", holder)).await;
+        let blocks: Vec<i64> = rows
+            .iter()
+            .map(|row| row["block_number"].as_i64().unwrap())
+            .collect();
+        assert_eq!(blocks, vec![5062, 5061, 5060]);
+
+        // as_of_block still bounds the stream
+        let rows = stream(format!(
+            "/api/addresses/{}/transfers?transfer_type=erc20&as_of_block=5061",
+            holder
+        ))
+        .await;
+        let blocks: Vec<i64> = rows
+            .iter()
+            .map(|row| row["block_number"].as_i64().unwrap())
+            .collect();
+        assert_eq!(blocks, vec![5061, 5060]);
+        assert_eq!(rows[0]["transfer_type"], "erc20");
+        assert_eq!(rows[0]["contract_address"], token);
+    });
+}
+
 #[test]
 fn get_address_transactions() {
     common::run(async {
@@ -189,6 +532,37 @@ fn get_address_transactions() {
         let body = common::json_body(response).await;
         let data = body["data"].as_array().unwrap();
         assert_eq!(data.len(), 2);
+        assert_eq!(body["total"].as_i64().unwrap(), 2);
+        assert_eq!(body["has_more"], false);
+    });
+}
+
+#[test]
+fn get_address_transactions_count_none_pages_with_has_more() {
+    common::run(async {
+        let pool = common::pool();
+        seed_address_data(&pool).await;
+
+        let app = common::test_router();
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri(format!(
+                        "/api/addresses/{}/transactions?limit=1&count=none",
+                        ADDR
+                    ))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = common::json_body(response).await;
+        assert_eq!(body["data"].as_array().unwrap().len(), 1);
+        assert_eq!(body["has_more"], true);
+        assert!(body.get("total").is_none());
+        assert!(body.get("total_pages").is_none());
     });
 }
 
@@ -217,3 +591,412 @@ fn get_erc20_address_detail_prefers_indexed_supply() {
         assert_eq!(body["total_supply"].as_str().unwrap(), "1000000");
     });
 }
+
+#[test]
+fn address_transactions_page_a_stable_snapshot_with_as_of_block() {
+    const SNAP_ADDR: &str = "0x5000000000000000000000000000000000000020";
+
+    common::run(async {
+        let pool = common::pool();
+        for (block, hash) in [
+            (
+                5100i64,
+                "0x5000000000000000000000000000000000000000000000000000000000000100",
+            ),
+            (
+                5101i64,
+                "0x5000000000000000000000000000000000000000000000000000000000000101",
+            ),
+        ] {
+            sqlx::query(
+                "INSERT INTO blocks (number, hash, parent_hash, timestamp, gas_used, gas_limit, transaction_count, indexed_at)
+                 VALUES ($1, $2, $3, $4, 21000, 30000000, 1, NOW())
+                 ON CONFLICT (number) DO NOTHING",
+            )
+            .bind(block)
+            .bind(format!("0x{:064x}", block))
+            .bind(format!("0x{:064x}", block - 1))
+            .bind(1_700_005_000i64 + block)
+            .execute(&pool)
+            .await
+            .expect("seed block");
+
+            sqlx::query(
+                "INSERT INTO transactions (hash, block_number, block_index, from_address, to_address, value, gas_price, gas_used, input_data, status, timestamp)
+                 VALUES ($1, $2, 0, $3, $4, 0, 1, 21000, ''::bytea, true, $5)
+                 ON CONFLICT (hash, block_number) DO NOTHING",
+            )
+            .bind(hash)
+            .bind(block)
+            .bind(SNAP_ADDR)
+            .bind(ADDR_TO)
+            .bind(1_700_005_000i64 + block)
+            .execute(&pool)
+            .await
+            .expect("seed transaction");
+        }
+
+        let get = |uri: String| async move {
+            let response = common::test_router()
+                .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+            let status = response.status();
+            (status, common::json_body(response).await)
+        };
+
+        let (status, latest) = get(format!("/api/addresses/{}/transactions", SNAP_ADDR)).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(latest["data"].as_array().unwrap().len(), 2);
+        assert!(latest["as_of_block"].as_i64().unwrap() >= 5101);
+
+        let (status, snapshot) = get(format!(
+            "/api/addresses/{}/transactions?as_of_block=5100",
+            SNAP_ADDR
+        ))
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        let data = snapshot["data"].as_array().unwrap();
+        assert_eq!(data.len(), 1);
+        assert_eq!(data[0]["block_number"], 5100);
+        assert_eq!(snapshot["total"], 1);
+        assert_eq!(snapshot["as_of_block"], 5100);
+
+        let (status, _) = get(format!(
+            "/api/addresses/{}/transactions?as_of_block=-1",
+            SNAP_ADDR
+        ))
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    });
+}
+
+#[test]
+fn genesis_import_seeds_accounts_at_block_zero() {
+    const FUNDED: &str = "0x50000000000000000000000000000000000000e1";
+    const PREDEPLOY: &str = "0x50000000000000000000000000000000000000e2";
+
+    common::run(async {
+        let pool = common::pool();
+        // Already indexed from a later block; genesis moves it to block 0.
+        sqlx::query(
+            "INSERT INTO addresses (address, is_contract, first_seen_block, tx_count)
+             VALUES ($1, false, 5000, 1)
+             ON CONFLICT (address) DO NOTHING",
+        )
+        .bind(FUNDED)
+        .execute(&pool)
+        .await
+        .expect("seed address");
+
+        let genesis = format!(
+            r#"{{"alloc": {{
+                "{}": {{"balance": "0x3635c9adc5dea00000"}},
+                "{}": {{"balance": "0", "code": "0x60806040", "storage": {{"0x0": "0x1"}}}}
+            }}}}"#,
+            FUNDED.to_uppercase().replace("0X", "0x"),
+            PREDEPLOY
+        );
+        let accounts = atlas_server::indexer::genesis::parse_genesis(genesis.as_bytes()).unwrap();
+        atlas_server::indexer::genesis::import_accounts(&pool, &accounts)
+            .await
+            .expect("import genesis");
+
+        let get = |address: &'static str| async move {
+            let response = common::test_router()
+                .oneshot(
+                    Request::builder()
+                        .uri(format!("/api/addresses/{address}"))
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            common::json_body(response).await
+        };
+
+        let funded = get(FUNDED).await;
+        assert_eq!(funded["first_seen_block"], 0);
+        assert_eq!(funded["tx_count"], 1);
+        assert_eq!(funded["address_type"], "eoa");
+        assert_eq!(funded["genesis"]["balance"], "1000000000000000000000");
+
+        let predeploy = get(PREDEPLOY).await;
+        assert_eq!(predeploy["first_seen_block"], 0);
+        assert_eq!(predeploy["address_type"], "contract");
+        assert_eq!(predeploy["genesis"]["code_size"], 4);
+        assert_eq!(predeploy["genesis"]["storage_slots"], 1);
+    });
+}
+
+#[test]
+fn approval_risks_group_active_allowances_by_spender() {
+    common::run(async {
+        let pool = common::pool();
+        const OWNER: &str = "0x50000000000000000000000000000000000000a1";
+        const DEX: &str = "0x50000000000000000000000000000000000000a2";
+        const DRAINER: &str = "0x50000000000000000000000000000000000000a3";
+        const TOKEN: &str = "0x50000000000000000000000000000000000000a4";
+        const UNLIMITED: &str =
+            "115792089237316195423570985008687907853269984665640564039457584007913129639935";
+        seed_address_data(&pool).await;
+
+        for (address, is_contract) in [(DEX, true), (DRAINER, false)] {
+            sqlx::query(
+                "INSERT INTO addresses (address, is_contract, first_seen_block, tx_count)
+                 VALUES ($1, $2, 5000, 1)
+                 ON CONFLICT (address) DO NOTHING",
+            )
+            .bind(address)
+            .bind(is_contract)
+            .execute(&pool)
+            .await
+            .expect("seed spender");
+        }
+        sqlx::query(
+            "INSERT INTO address_labels (address, name, tags) VALUES ($1, 'Fake airdrop', '{phishing}')
+             ON CONFLICT (address) DO UPDATE SET tags = EXCLUDED.tags",
+        )
+        .bind(DRAINER)
+        .execute(&pool)
+        .await
+        .expect("seed label");
+        sqlx::query(
+            "INSERT INTO transactions (hash, block_number, block_index, from_address, to_address, value, gas_price, gas_used, input_data, status, timestamp)
+             VALUES ($1, 5000, 9, $2, $3, 0, 1, 21000, '\\x', true, 1700005000)
+             ON CONFLICT (hash, block_number) DO NOTHING",
+        )
+        .bind("0x50000000000000000000000000000000000000000000000000000000000000a1")
+        .bind(OWNER)
+        .bind(DEX)
+        .execute(&pool)
+        .await
+        .expect("seed call to spender");
+
+        for (spender, value, log_index) in [
+            (DEX, UNLIMITED, 0),
+            (DRAINER, "500", 1),
+            (ERC20_ADDR, "0", 2),
+        ] {
+            let contract = if spender == ERC20_ADDR {
+                ERC20_ADDR
+            } else {
+                TOKEN
+            };
+            sqlx::query(
+                "INSERT INTO erc20_approvals (owner, contract_address, spender, value, tx_hash, log_index, block_number)
+                 VALUES ($1, $2, $3, $4::numeric, $5, $6, 5000)
+                 ON CONFLICT (owner, contract_address, spender) DO UPDATE SET value = EXCLUDED.value",
+            )
+            .bind(OWNER)
+            .bind(contract)
+            .bind(spender)
+            .bind(value)
+            .bind(TX_HASH_A)
+            .bind(log_index)
+            .execute(&pool)
+            .await
+            .expect("seed approval");
+        }
+
+        let app = common::test_router();
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri(format!("/api/addresses/{OWNER}/approval-risks"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = common::json_body(response).await;
+        assert_eq!(body["truncated"], false);
+
+        // Revoked (zero) allowances are left out; the phishing spender leads.
+        let spenders = body["spenders"].as_array().unwrap();
+        assert_eq!(spenders.len(), 2);
+        assert_eq!(spenders[0]["spender"], DRAINER);
+        assert_eq!(spenders[0]["risk"], "high");
+        assert_eq!(spenders[0]["label"], "Fake airdrop");
+        assert_eq!(spenders[0]["flags"], serde_json::json!(["phishing", "eoa"]));
+        assert_eq!(spenders[0]["allowances"][0]["value"], "500");
+
+        assert_eq!(spenders[1]["spender"], DEX);
+        assert_eq!(spenders[1]["risk"], "medium");
+        assert_eq!(spenders[1]["flags"], serde_json::json!(["unlimited"]));
+        assert_eq!(spenders[1]["is_contract"], true);
+        assert_eq!(spenders[1]["last_active_block"], 5000);
+        assert_eq!(spenders[1]["allowances"][0]["unlimited"], true);
+        assert_eq!(spenders[1]["allowances"][0]["contract_address"], TOKEN);
+    });
+}
+
+#[test]
+fn meta_combines_label_verification_proxy_and_token() {
+    common::run(async {
+        let pool = common::pool();
+        const PROXY: &str = "0x50000000000000000000000000000000000000b1";
+        const IMPLEMENTATION: &str = "0x50000000000000000000000000000000000000b2";
+        const UNKNOWN: &str = "0x50000000000000000000000000000000000000b3";
+
+        sqlx::query(
+            "INSERT INTO erc20_contracts (address, name, symbol, decimals, first_seen_block)
+             VALUES ($1, 'Meta Token', 'MTAK', 6, 5000)
+             ON CONFLICT (address) DO NOTHING",
+        )
+        .bind(PROXY)
+        .execute(&pool)
+        .await
+        .expect("seed token");
+        sqlx::query(
+            "INSERT INTO address_labels (address, name, tags) VALUES ($1, 'Meta Bridge', '{bridge}')
+             ON CONFLICT (address) DO UPDATE SET name = EXCLUDED.name, tags = EXCLUDED.tags",
+        )
+        .bind(PROXY)
+        .execute(&pool)
+        .await
+        .expect("seed label");
+        sqlx::query(
+            "INSERT INTO proxy_contracts (proxy_address, implementation_address, proxy_type, detected_at_block, last_checked_block)
+             VALUES ($1, $2, 'eip1967', 5000, 5000)
+             ON CONFLICT (proxy_address) DO NOTHING",
+        )
+        .bind(PROXY)
+        .bind(IMPLEMENTATION)
+        .execute(&pool)
+        .await
+        .expect("seed proxy");
+        sqlx::query(
+            "INSERT INTO contract_abis (address, abi, contract_name, match_strategy) VALUES ($1, '[]', 'MetaToken', 'exact')
+             ON CONFLICT (address) DO NOTHING",
+        )
+        .bind(IMPLEMENTATION)
+        .execute(&pool)
+        .await
+        .expect("seed implementation abi");
+
+        let get = |address: String| async move {
+            let response = common::test_router()
+                .oneshot(
+                    Request::builder()
+                        .uri(format!("/api/addresses/{address}/meta"))
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            let status = response.status();
+            (status, common::json_body(response).await)
+        };
+
+        let (status, body) = get(PROXY.to_uppercase().replace("0X", "0x")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["address"], PROXY);
+        assert_eq!(body["address_type"], "erc20");
+        assert_eq!(body["is_contract"], true);
+        assert_eq!(body["label"]["name"], "Meta Bridge");
+        assert_eq!(body["label"]["tags"], serde_json::json!(["bridge"]));
+        assert!(body["verification"].is_null());
+        assert_eq!(body["proxy"]["proxy_type"], "eip1967");
+        assert_eq!(body["proxy"]["implementation_address"], IMPLEMENTATION);
+        assert_eq!(body["proxy"]["implementation_verified"], true);
+        assert_eq!(body["token"]["symbol"], "MTAK");
+        assert_eq!(body["token"]["decimals"], 6);
+        assert_eq!(body["token"]["verified"], false);
+
+        let (status, body) = get(IMPLEMENTATION.to_string()).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["address_type"], "contract");
+        assert_eq!(body["verification"]["contract_name"], "MetaToken");
+        assert_eq!(body["verification"]["match_strategy"], "exact");
+        assert!(body["token"].is_null());
+
+        // Addresses the index has never seen are plain EOAs.
+        let (status, body) = get(UNKNOWN.to_string()).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["address_type"], "eoa");
+        assert_eq!(body["is_contract"], false);
+        assert!(body["label"].is_null());
+        assert!(body["proxy"].is_null());
+
+        let (status, _) = get("0x1234".to_string()).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    });
+}
+
+#[test]
+fn address_list_reads_the_refreshed_directory() {
+    const ACTIVE: &str = "0x50000000000000000000000000000000000000c1";
+    const TOKEN: &str = "0x50000000000000000000000000000000000000c2";
+
+    common::run(async {
+        let pool = common::pool();
+        sqlx::query(
+            "INSERT INTO addresses (address, is_contract, first_seen_block, tx_count)
+             VALUES ($1, false, 5990, 7)
+             ON CONFLICT (address) DO NOTHING",
+        )
+        .bind(ACTIVE)
+        .execute(&pool)
+        .await
+        .expect("seed address");
+        // A token contract with no `addresses` row
+        sqlx::query(
+            "INSERT INTO erc20_contracts (address, name, symbol, decimals, first_seen_block)
+             VALUES ($1, NULL, NULL, 18, 5991)
+             ON CONFLICT (address) DO NOTHING",
+        )
+        .bind(TOKEN)
+        .execute(&pool)
+        .await
+        .expect("seed token");
+        atlas_server::indexer::address_directory::refresh(&pool, &[ACTIVE, TOKEN])
+            .await
+            .expect("refresh directory");
+
+        // Metadata arriving later is picked up by the next refresh.
+        sqlx::query(
+            "UPDATE erc20_contracts SET name = 'Directory', symbol = 'DIR' WHERE address = $1",
+        )
+        .bind(TOKEN)
+        .execute(&pool)
+        .await
+        .expect("set metadata");
+        atlas_server::indexer::address_directory::refresh(&pool, &[TOKEN])
+            .await
+            .expect("refresh directory");
+
+        let list = |query: &'static str| async move {
+            let response = common::test_router()
+                .oneshot(
+                    Request::builder()
+                        .uri(format!(
+                            "/api/addresses?from_block=5990&to_block=5999{query}"
+                        ))
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            common::json_body(response).await
+        };
+
+        let body = list("").await;
+        assert_eq!(body["total"], 2);
+        let data = body["data"].as_array().unwrap();
+        assert_eq!(data[0]["address"], ACTIVE);
+        assert_eq!(data[0]["address_type"], "eoa");
+        assert_eq!(data[0]["tx_count"], 7);
+        assert_eq!(data[1]["address"], TOKEN);
+        assert_eq!(data[1]["address_type"], "erc20");
+        assert_eq!(data[1]["name"], "Directory");
+        assert_eq!(data[1]["symbol"], "DIR");
+
+        let body = list("&address_type=erc20").await;
+        assert_eq!(body["total"], 1);
+        assert_eq!(body["data"][0]["address"], TOKEN);
+    });
+}
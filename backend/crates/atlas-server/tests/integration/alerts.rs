@@ -0,0 +1,284 @@
+use axum::{
+    body::Body,
+    extract::State,
+    http::{Request, StatusCode},
+    routing::post,
+    Json, Router,
+};
+use serde_json::{json, Value};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tower::ServiceExt;
+
+use atlas_server::indexer::AlertEngine;
+
+use crate::common;
+
+// Block range: 15000-15999
+
+const TOKEN: &str = "0x1500000000000000000000000000000000000001";
+const SENDER: &str = "0x1500000000000000000000000000000000000010";
+const RECIPIENT: &str = "0x1500000000000000000000000000000000000011";
+const TX_HASH: &str = "0x1500000000000000000000000000000000000000000000000000000000000001";
+const TRANSFER: &str = "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef";
+
+async fn seed_block(pool: &sqlx::PgPool) {
+    sqlx::query(
+        "INSERT INTO blocks (number, hash, parent_hash, timestamp, gas_used, gas_limit, transaction_count, indexed_at)
+         VALUES (15000, $1, $2, 1700015000, 100000, 30000000, 1, NOW())
+         ON CONFLICT (number) DO NOTHING",
+    )
+    .bind(format!("0x{:064x}", 15000))
+    .bind(format!("0x{:064x}", 14999))
+    .execute(pool)
+    .await
+    .expect("seed block");
+
+    // transfer(address,uint256)
+    let mut input = vec![0xa9, 0x05, 0x9c, 0xbb];
+    input.extend([0u8; 64]);
+    sqlx::query(
+        "INSERT INTO transactions (hash, block_number, block_index, from_address, to_address, value, gas_price, gas_used, input_data, status, timestamp)
+         VALUES ($1, 15000, 0, $2, $3, 0, 1, 60000, $4, true, 1700015000)
+         ON CONFLICT (hash, block_number) DO NOTHING",
+    )
+    .bind(TX_HASH)
+    .bind(SENDER)
+    .bind(TOKEN)
+    .bind(input)
+    .execute(pool)
+    .await
+    .expect("seed transaction");
+
+    for (log_index, value) in [(0i32, 5_000i64), (1, 10)] {
+        sqlx::query(
+            "INSERT INTO erc20_transfers (tx_hash, log_index, contract_address, from_address, to_address, value, block_number, timestamp)
+             VALUES ($1, $2, $3, $4, $5, $6, 15000, 1700015000)
+             ON CONFLICT (tx_hash, log_index, block_number) DO NOTHING",
+        )
+        .bind(TX_HASH)
+        .bind(log_index)
+        .bind(TOKEN)
+        .bind(SENDER)
+        .bind(RECIPIENT)
+        .bind(bigdecimal::BigDecimal::from(value))
+        .execute(pool)
+        .await
+        .expect("seed transfer");
+
+        sqlx::query(
+            "INSERT INTO event_logs (tx_hash, log_index, address, topic0, data, block_number)
+             VALUES (decode(substr($1, 3), 'hex'), $2, decode(substr($3, 3), 'hex'), decode(substr($4, 3), 'hex'), $5, 15000)
+             ON CONFLICT (tx_hash, log_index, block_number) DO NOTHING",
+        )
+        .bind(TX_HASH)
+        .bind(log_index)
+        .bind(TOKEN)
+        .bind(TRANSFER)
+        .bind(value.to_be_bytes().to_vec())
+        .execute(pool)
+        .await
+        .expect("seed log");
+    }
+}
+
+/// Serve a webhook on a local port that records bodies POSTed to `/ok` and
+/// answers 500 on `/fail`.
+async fn webhook_receiver() -> (String, Arc<Mutex<Vec<Value>>>) {
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let app = Router::new()
+        .route(
+            "/ok",
+            post(
+                |State(received): State<Arc<Mutex<Vec<Value>>>>, Json(body): Json<Value>| async move {
+                    received.lock().unwrap().push(body);
+                    StatusCode::OK
+                },
+            ),
+        )
+        .route("/fail", post(|| async { StatusCode::INTERNAL_SERVER_ERROR }))
+        .with_state(received.clone());
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    (url, received)
+}
+
+fn request(method: &str, uri: &str, key: &str, body: Option<Value>) -> Request<Body> {
+    Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("content-type", "application/json")
+        .header("authorization", format!("Bearer {key}"))
+        .body(body.map_or_else(Body::empty, |b| Body::from(b.to_string())))
+        .unwrap()
+}
+
+async fn issue_key(app: &Router, name: &str) -> String {
+    let response = app
+        .clone()
+        .oneshot(request(
+            "POST",
+            "/api/labels/keys",
+            common::ADMIN_API_KEY,
+            Some(json!({ "name": name, "tags": ["alerts"] })),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    common::json_body(response).await["key"]
+        .as_str()
+        .unwrap()
+        .to_string()
+}
+
+#[test]
+fn alert_rules_match_new_blocks_and_post_webhooks() {
+    common::run(async {
+        let pool = common::pool();
+        sqlx::query("DELETE FROM alert_rules WHERE contract = $1")
+            .bind(TOKEN)
+            .execute(&pool)
+            .await
+            .expect("clear rules");
+        seed_block(&pool).await;
+        let (webhook, received) = webhook_receiver().await;
+        let app = common::test_router();
+        let key = issue_key(&app, "alerts owner").await;
+        let other_key = issue_key(&app, "alerts other").await;
+
+        let mut ids = Vec::new();
+        for rule in [
+            json!({
+                "name": "Large transfers",
+                "kind": "transfer",
+                "contract": TOKEN,
+                "min_value": "1000",
+                "method": "0xa9059cbb",
+                "webhook_url": format!("{webhook}/ok"),
+            }),
+            json!({
+                "name": "Transfer logs",
+                "kind": "log",
+                "contract": TOKEN,
+                "event": TRANSFER,
+                "counterparty": SENDER,
+                "webhook_url": format!("{webhook}/fail"),
+            }),
+        ] {
+            let response = app
+                .clone()
+                .oneshot(request("POST", "/api/alerts", &key, Some(rule)))
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::CREATED);
+            ids.push(common::json_body(response).await["id"].as_i64().unwrap());
+        }
+        let (transfer_rule, log_rule) = (ids[0], ids[1]);
+
+        // Rules are private to their key and validated.
+        let response = app
+            .clone()
+            .oneshot(request(
+                "GET",
+                &format!("/api/alerts/{transfer_rule}"),
+                &other_key,
+                None,
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        let response = app
+            .clone()
+            .oneshot(request(
+                "POST",
+                "/api/alerts",
+                &key,
+                Some(json!({
+                    "name": "No target",
+                    "kind": "transfer",
+                    "webhook_url": format!("{webhook}/ok"),
+                })),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        // New rules start at the indexed head; rewind them to cover the seed.
+        sqlx::query("UPDATE alert_rules SET last_block = 14999 WHERE id = ANY($1)")
+            .bind(&ids)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let (events, _) = broadcast::channel(1);
+        let engine = AlertEngine::new(pool.clone(), events, Duration::from_secs(1), true).unwrap();
+        let (matched, delivered) = engine.run_cycle().await.unwrap();
+        assert_eq!((matched, delivered), (3, 1));
+
+        // Only the transfer above the threshold reached the webhook.
+        let bodies: Vec<Value> = received.lock().unwrap().clone();
+        assert_eq!(bodies.len(), 1);
+        assert_eq!(bodies[0]["rule"]["id"], transfer_rule);
+        assert_eq!(bodies[0]["event"]["value"], "5000");
+        assert_eq!(bodies[0]["event"]["from"], SENDER);
+
+        let response = app
+            .clone()
+            .oneshot(request(
+                "GET",
+                &format!("/api/alerts/{transfer_rule}/notifications"),
+                &key,
+                None,
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = common::json_body(response).await;
+        assert_eq!(body["total"], 1);
+        assert_eq!(body["data"][0]["status"], "delivered");
+
+        // Failed deliveries stay queued for a retry.
+        let response = app
+            .clone()
+            .oneshot(request(
+                "GET",
+                &format!("/api/alerts/{log_rule}/notifications"),
+                &key,
+                None,
+            ))
+            .await
+            .unwrap();
+        let body = common::json_body(response).await;
+        assert_eq!(body["total"], 2);
+        for notification in body["data"].as_array().unwrap() {
+            assert_eq!(notification["status"], "pending");
+            assert_eq!(notification["attempts"], 1);
+            assert!(notification["last_error"].as_str().unwrap().contains("500"));
+        }
+
+        // Evaluated blocks are not matched again.
+        let (matched, _) = engine.run_cycle().await.unwrap();
+        assert_eq!(matched, 0);
+
+        let response = app
+            .clone()
+            .oneshot(request(
+                "DELETE",
+                &format!("/api/alerts/{log_rule}"),
+                &key,
+                None,
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        let (remaining,): (i64,) =
+            sqlx::query_as("SELECT COUNT(*) FROM alert_notifications WHERE rule_id = $1")
+                .bind(log_rule)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(remaining, 0);
+    });
+}
@@ -0,0 +1,222 @@
+use serde_json::json;
+use std::sync::Arc;
+use tokio::sync::broadcast;
+
+use atlas_server::cli::{Cli, Command};
+use atlas_server::config::Config;
+use atlas_server::head::HeadTracker;
+use atlas_server::indexer::Indexer;
+use atlas_server::metrics::Metrics;
+use atlas_server::ops_alerts::OpsAlerter;
+use atlas_server::runtime_settings::RuntimeSettings;
+use clap::Parser;
+
+use super::common;
+use super::gap_fill::empty_block_response;
+
+// Block range: 28000-28999
+
+const SENDER: &str = "0x2800000000000000000000000000000000000001";
+const TOKEN: &str = "0x2800000000000000000000000000000000000002";
+const RECIPIENT: &str = "0x2800000000000000000000000000000000000003";
+
+/// Archive payload of block `number`, with one transaction moving 500 TOKEN
+/// from SENDER to RECIPIENT when `transfer` is set.
+fn archived_block(number: u64, transfer: bool) -> Vec<u8> {
+    let mut response = empty_block_response(number);
+    if transfer {
+        let block_hash = format!("0x{:064x}", number);
+        let tx_hash = format!("0x{:064x}", number + 1);
+        let topic = |address: &str| format!("0x000000000000000000000000{}", &address[2..]);
+        response[0]["result"]["transactions"] = json!([{
+            "hash": tx_hash,
+            "nonce": "0x0",
+            "blockHash": block_hash,
+            "blockNumber": format!("0x{:x}", number),
+            "transactionIndex": "0x0",
+            "from": SENDER,
+            "to": TOKEN,
+            "value": "0x0",
+            "gasPrice": "0x1",
+            "gas": "0x10000",
+            "input": "0x",
+            "v": "0x1b",
+            "r": "0x1",
+            "s": "0x1",
+            "type": "0x0"
+        }]);
+        response[1]["result"] = json!([{
+            "transactionHash": tx_hash,
+            "transactionIndex": "0x0",
+            "blockHash": block_hash,
+            "blockNumber": format!("0x{:x}", number),
+            "from": SENDER,
+            "to": TOKEN,
+            "cumulativeGasUsed": "0x5208",
+            "gasUsed": "0x5208",
+            "contractAddress": null,
+            "logs": [{
+                "address": TOKEN,
+                "topics": [
+                    "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef",
+                    topic(SENDER),
+                    topic(RECIPIENT)
+                ],
+                "data": format!("0x{:064x}", 500),
+                "blockNumber": format!("0x{:x}", number),
+                "transactionHash": tx_hash,
+                "transactionIndex": "0x0",
+                "blockHash": block_hash,
+                "logIndex": "0x0",
+                "removed": false
+            }],
+            "logsBloom": format!("0x{}", "00".repeat(256)),
+            "type": "0x0",
+            "effectiveGasPrice": "0x1",
+            "status": "0x1"
+        }]);
+    }
+    serde_json::to_vec(&json!({
+        "block": response[0]["result"],
+        "receipts": response[1]["result"],
+    }))
+    .unwrap()
+}
+
+fn replay_indexer() -> Indexer {
+    let cli = Cli::try_parse_from([
+        "atlas-server",
+        "run",
+        "--atlas.rpc.url",
+        "http://127.0.0.1:1",
+        "--atlas.indexer.replay-archive",
+    ])
+    .expect("parse cli");
+    let Command::Run(mut args) = cli.command else {
+        panic!("expected run command");
+    };
+    args.db.url = common::database_url().to_string();
+    let config = Config::from_run_args(*args).expect("config");
+    let (tx, _) = broadcast::channel(16);
+    Indexer::new(
+        common::pool(),
+        config,
+        tx,
+        Arc::new(HeadTracker::empty(10)),
+        Metrics::new(),
+        OpsAlerter::default(),
+        RuntimeSettings::default(),
+    )
+}
+
+async fn clear(pool: &sqlx::PgPool) {
+    for sql in [
+        "DELETE FROM raw_block_archive WHERE block_number BETWEEN 28000 AND 28999",
+        "DELETE FROM blocks WHERE number BETWEEN 28000 AND 28999",
+        "DELETE FROM processed_blocks WHERE number BETWEEN 28000 AND 28999",
+        "DELETE FROM transactions WHERE block_number BETWEEN 28000 AND 28999",
+        "DELETE FROM tx_hash_lookup WHERE block_number BETWEEN 28000 AND 28999",
+        "DELETE FROM event_logs WHERE block_number BETWEEN 28000 AND 28999",
+        "DELETE FROM erc20_transfers WHERE block_number BETWEEN 28000 AND 28999",
+    ] {
+        sqlx::query(sql)
+            .execute(pool)
+            .await
+            .expect("clear archive blocks");
+    }
+    sqlx::query("DELETE FROM erc20_balances WHERE contract_address = $1")
+        .bind(TOKEN)
+        .execute(pool)
+        .await
+        .expect("clear balances");
+}
+
+#[test]
+fn replay_writes_archived_blocks_and_resumes_at_the_first_missing_one() {
+    common::run(async {
+        let _head = common::INDEXED_HEAD.lock().await;
+        let pool = common::pool();
+        clear(&pool).await;
+        let head: Option<String> =
+            sqlx::query_scalar("SELECT value FROM indexer_state WHERE key = 'last_indexed_block'")
+                .fetch_optional(&pool)
+                .await
+                .expect("read head");
+
+        // 28002 was never archived, so replay stops before it.
+        for (number, transfer) in [(28000, false), (28001, true), (28003, true)] {
+            sqlx::query(
+                "INSERT INTO raw_block_archive (block_number, block_hash, payload) VALUES ($1, $2, $3)",
+            )
+            .bind(number as i64)
+            .bind(format!("0x{:064x}", number))
+            .bind(archived_block(number, transfer))
+            .execute(&pool)
+            .await
+            .expect("archive block");
+        }
+
+        let resume_block = replay_indexer().replay_archive(28000).await;
+        let replayed_head: Option<String> =
+            sqlx::query_scalar("SELECT value FROM indexer_state WHERE key = 'last_indexed_block'")
+                .fetch_optional(&pool)
+                .await
+                .expect("read replayed head");
+        match &head {
+            Some(head) => {
+                sqlx::query("UPDATE indexer_state SET value = $1 WHERE key = 'last_indexed_block'")
+                    .bind(head)
+                    .execute(&pool)
+                    .await
+                    .expect("restore head");
+            }
+            None => {
+                sqlx::query("DELETE FROM indexer_state WHERE key = 'last_indexed_block'")
+                    .execute(&pool)
+                    .await
+                    .expect("restore head");
+            }
+        }
+        assert_eq!(resume_block.expect("replay archive"), 28002);
+        assert_eq!(replayed_head.as_deref(), Some("28001"));
+
+        let blocks: Vec<i64> = sqlx::query_scalar(
+            "SELECT number FROM blocks WHERE number BETWEEN 28000 AND 28999 ORDER BY number",
+        )
+        .fetch_all(&pool)
+        .await
+        .expect("replayed blocks");
+        assert_eq!(blocks, [28000, 28001]);
+        let transfers: Vec<(i64, String, String)> = sqlx::query_as(
+            "SELECT block_number, to_address, value::text FROM erc20_transfers
+             WHERE block_number BETWEEN 28000 AND 28999",
+        )
+        .fetch_all(&pool)
+        .await
+        .expect("replayed transfers");
+        assert_eq!(
+            transfers,
+            [(28001, RECIPIENT.to_string(), "500".to_string())]
+        );
+        let balance: String = sqlx::query_scalar(
+            "SELECT balance::text FROM erc20_balances WHERE address = $1 AND contract_address = $2",
+        )
+        .bind(RECIPIENT)
+        .bind(TOKEN)
+        .fetch_one(&pool)
+        .await
+        .expect("recipient balance");
+        assert_eq!(balance, "500");
+
+        // Replay leaves the archive itself untouched.
+        let archived: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM raw_block_archive WHERE block_number BETWEEN 28000 AND 28999",
+        )
+        .fetch_one(&pool)
+        .await
+        .expect("archived blocks");
+        assert_eq!(archived, 3);
+
+        clear(&pool).await;
+    });
+}
@@ -0,0 +1,109 @@
+use wiremock::matchers::method;
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use atlas_server::indexer::processors::{
+    BlockProcessor, FetchedBlock, ProcessorRegistry, ProcessorWrites,
+};
+use atlas_server::indexer::BackfillWorker;
+use atlas_server::metrics::Metrics;
+use atlas_server::ops_alerts::OpsAlerter;
+
+use super::common;
+use super::gap_fill::empty_block_response;
+
+// Block range: 19000-19999
+
+const BLOCK: u64 = 19_000;
+
+/// Records each block's hash in `indexer_state`.
+struct BlockHashes;
+
+impl BlockProcessor for BlockHashes {
+    fn process(&self, block: &FetchedBlock, writes: &mut ProcessorWrites) {
+        writes.execute(
+            "INSERT INTO indexer_state (key, value, updated_at) VALUES ($1, $2, NOW())
+             ON CONFLICT (key) DO UPDATE SET value = EXCLUDED.value",
+            vec![
+                Box::new(format!("processor_test_{}", block.number)),
+                Box::new(format!("{:#x}", block.block.header.hash)),
+            ],
+        );
+    }
+}
+
+#[test]
+fn worker_indexes_leased_range_and_completes_it() {
+    common::run(async {
+        let _leases = common::LEASES.lock().await;
+        let pool = common::pool();
+        sqlx::query("DELETE FROM range_leases WHERE start_block = $1")
+            .bind(BLOCK as i64)
+            .execute(&pool)
+            .await
+            .expect("clear lease");
+        sqlx::query("DELETE FROM blocks WHERE number = $1")
+            .bind(BLOCK as i64)
+            .execute(&pool)
+            .await
+            .expect("clear block");
+        sqlx::query(
+            "INSERT INTO range_leases (start_block, end_block, next_block) VALUES ($1, $1 + 1, $1)",
+        )
+        .bind(BLOCK as i64)
+        .execute(&pool)
+        .await
+        .expect("insert lease");
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(empty_block_response(BLOCK)))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let mut registry = ProcessorRegistry::builtin();
+        registry.register("block_hashes", || Ok(Box::new(BlockHashes)));
+        let processors = registry
+            .build(&["block_hashes".to_string()])
+            .expect("build processors");
+        let worker = BackfillWorker::new(
+            pool.clone(),
+            common::database_url(),
+            &mock_server.uri(),
+            10,
+            Metrics::new(),
+            false,
+            OpsAlerter::default(),
+            0,
+        )
+        .unwrap()
+        .with_processors(processors);
+        assert!(worker.process_next_lease().await.expect("process lease"));
+
+        let (hash,): (String,) = sqlx::query_as("SELECT hash FROM blocks WHERE number = $1")
+            .bind(BLOCK as i64)
+            .fetch_one(&pool)
+            .await
+            .expect("backfilled block");
+        assert_eq!(hash, format!("0x{:064x}", BLOCK));
+
+        let (next, completed): (i64, bool) = sqlx::query_as(
+            "SELECT next_block, completed_at IS NOT NULL FROM range_leases WHERE start_block = $1",
+        )
+        .bind(BLOCK as i64)
+        .fetch_one(&pool)
+        .await
+        .expect("lease row");
+        assert_eq!(next, BLOCK as i64 + 1);
+        assert!(completed);
+
+        // The processor's row was written with the block
+        let processed: String =
+            sqlx::query_scalar("DELETE FROM indexer_state WHERE key = $1 RETURNING value")
+                .bind(format!("processor_test_{BLOCK}"))
+                .fetch_one(&pool)
+                .await
+                .expect("processor row");
+        assert_eq!(processed, hash);
+    });
+}
@@ -99,3 +99,410 @@ fn get_block_not_found() {
         assert_eq!(response.status(), StatusCode::NOT_FOUND);
     });
 }
+
+#[test]
+fn v1_serves_blocks_in_the_envelope() {
+    common::run(async {
+        let pool = common::pool();
+        seed_blocks(&pool).await;
+
+        let get = |uri: &'static str| async move {
+            common::test_router()
+                .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+                .await
+                .unwrap()
+        };
+
+        let response = get("/api/v1/blocks?page=1&limit=2").await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(response.headers().get("deprecation").is_none());
+        let body = common::json_body(response).await;
+        assert_eq!(body["data"].as_array().unwrap().len(), 2);
+        assert_eq!(body["pagination"]["page"], 1);
+        assert_eq!(body["pagination"]["limit"], 2);
+
+        let body = common::json_body(get("/api/v1/blocks/1002").await).await;
+        assert_eq!(body["data"]["number"], 1002);
+        // v1 writes gas amounts as strings; heights stay numbers.
+        assert!(body["data"]["gas_used"].is_string());
+        assert!(body["data"]["gas_limit"].is_string());
+
+        let response = get("/api/v1/blocks/999999").await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        let body = common::json_body(response).await;
+        assert_eq!(body["error"]["code"], "not_found");
+        assert!(body["error"]["message"].is_string());
+
+        let response = get("/api/blocks/1002").await;
+        assert_eq!(response.headers()["deprecation"], "true");
+        assert_eq!(
+            response.headers()["link"],
+            "</api/v1/blocks/1002>; rel=\"successor-version\""
+        );
+        let body = common::json_body(response).await;
+        assert_eq!(body["number"], 1002);
+        assert!(body["gas_used"].is_number());
+    });
+}
+
+#[test]
+fn list_blocks_refuses_pages_past_the_result_window() {
+    common::run(async {
+        for (uri, status) in [
+            ("/api/blocks?page=100&limit=100", StatusCode::OK),
+            (
+                "/api/blocks?page=101&limit=100",
+                StatusCode::UNPROCESSABLE_ENTITY,
+            ),
+            ("/api/blocks?page=501", StatusCode::UNPROCESSABLE_ENTITY),
+        ] {
+            let response = common::test_router()
+                .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+            assert_eq!(response.status(), status, "{uri}");
+            if status != StatusCode::OK {
+                let body = common::json_body(response).await;
+                assert!(body["error"]
+                    .as_str()
+                    .unwrap()
+                    .contains("past the limit of 10000 rows"));
+            }
+        }
+    });
+}
+
+#[test]
+fn batch_runs_each_call_and_keeps_the_order() {
+    common::run(async {
+        let pool = common::pool();
+        seed_blocks(&pool).await;
+
+        let calls = serde_json::json!([
+            {"id": 1, "method": "/api/blocks/1003"},
+            {"id": "missing", "method": "/api/blocks/999999"},
+            {"id": 3, "method": "/api/blocks", "params": {"page": 1, "limit": 2}},
+            {"id": 4, "method": "/api/events"},
+        ]);
+        let response = common::test_router()
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/api/batch")
+                    .header("content-type", "application/json")
+                    .body(Body::from(calls.to_string()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = common::json_body(response).await;
+        let results = body.as_array().unwrap();
+        assert_eq!(results.len(), 4);
+        assert_eq!(results[0]["id"], 1);
+        assert_eq!(results[0]["status"], 200);
+        assert_eq!(results[0]["result"]["number"], 1003);
+        assert_eq!(results[1]["id"], "missing");
+        assert_eq!(results[1]["status"], 404);
+        assert!(results[1]["error"].is_string());
+        assert_eq!(results[2]["result"]["data"].as_array().unwrap().len(), 2);
+        // Streaming routes are not reachable through a batch.
+        assert_eq!(results[3]["status"], 404);
+    });
+}
+
+#[test]
+fn block_transactions_stream_as_ndjson() {
+    common::run(async {
+        let pool = common::pool();
+        // More rows than one page so the stream has to ignore pagination.
+        for index in 0..30i32 {
+            sqlx::query(
+                "INSERT INTO transactions (hash, block_number, block_index, from_address, to_address, value, gas_price, gas_used, input_data, status, timestamp)
+                 VALUES ($1, 1300, $2, '0x1300000000000000000000000000000000000001', NULL, 0, 1, 21000, '\\x', true, 1700001300)
+                 ON CONFLICT (hash, block_number) DO NOTHING",
+            )
+            .bind(format!("0x{:064x}", 0x130000 + index))
+            .bind(index)
+            .execute(&pool)
+            .await
+            .expect("seed transaction");
+        }
+
+        let response = common::test_router()
+            .oneshot(
+                Request::builder()
+                    .uri("/api/blocks/1300/transactions?limit=5")
+                    .header("accept", "application/x-ndjson")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()["content-type"], "application/x-ndjson");
+        let rows = common::ndjson_body(response).await;
+        let indexes: Vec<i64> = rows
+            .iter()
+            .map(|row| row["block_index"].as_i64().unwrap())
+            .collect();
+        assert_eq!(indexes, (0..30).collect::<Vec<i64>>());
+        assert_eq!(rows[0]["block_number"], 1300);
+    });
+}
+
+#[test]
+fn block_logs_list_every_log_in_order_with_filters() {
+    const TRANSFER: &str = "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef";
+    const APPROVAL: &str = "0x8c5be1e5ebec7d5bd14f71427d1e84f3dd0314c0f7b2291e5b200ac8c7c3b925";
+    const TOKEN_A: &str = "0x1200000000000000000000000000000000000001";
+    const TOKEN_B: &str = "0x1200000000000000000000000000000000000002";
+
+    common::run(async {
+        let pool = common::pool();
+        // Two transactions in block 1200; block 1201 must not leak in.
+        for (tx, log_index, address, topic0, block) in [
+            (0x1200, 0, TOKEN_A, TRANSFER, 1200i64),
+            (0x1200, 1, TOKEN_A, APPROVAL, 1200),
+            (0x1201, 2, TOKEN_B, TRANSFER, 1200),
+            (0x1202, 0, TOKEN_A, TRANSFER, 1201),
+        ] {
+            sqlx::query(
+                "INSERT INTO event_logs (tx_hash, log_index, address, topic0, data, block_number)
+                 VALUES (decode(substr($1, 3), 'hex'), $2, decode(substr($3, 3), 'hex'), decode(substr($4, 3), 'hex'), '\\x', $5)
+                 ON CONFLICT (tx_hash, log_index, block_number) DO NOTHING",
+            )
+            .bind(format!("0x{:064x}", tx))
+            .bind(log_index)
+            .bind(address)
+            .bind(topic0)
+            .bind(block)
+            .execute(&pool)
+            .await
+            .expect("seed event log");
+        }
+
+        let app = common::test_router();
+        let get = |uri: String| {
+            let app = app.clone();
+            async move {
+                let response = app
+                    .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+                    .await
+                    .unwrap();
+                assert_eq!(response.status(), StatusCode::OK);
+                common::json_body(response).await
+            }
+        };
+
+        let body = get("/api/blocks/1200/logs".to_string()).await;
+        assert_eq!(body["total"], 3);
+        let logs = body["data"].as_array().unwrap();
+        let indexes: Vec<i64> = logs
+            .iter()
+            .map(|l| l["log_index"].as_i64().unwrap())
+            .collect();
+        assert_eq!(indexes, vec![0, 1, 2]);
+        assert_eq!(logs[0]["event_name"], "Transfer");
+        assert_eq!(logs[1]["event_name"], "Approval");
+
+        let body = get(format!("/api/blocks/1200/logs?topic0={TRANSFER}")).await;
+        assert_eq!(body["total"], 2);
+
+        let body = get(format!(
+            "/api/blocks/1200/logs?topic0={TRANSFER}&address={}",
+            TOKEN_A.to_uppercase().replace("0X", "0x")
+        ))
+        .await;
+        assert_eq!(body["total"], 1);
+        assert_eq!(body["data"][0]["log_index"], 0);
+
+        let body = get("/api/blocks/1200/logs?limit=2&page=2".to_string()).await;
+        assert_eq!(body["data"].as_array().unwrap().len(), 1);
+        assert_eq!(body["data"][0]["log_index"], 2);
+    });
+}
+
+#[test]
+fn bloom_checks_answer_involvement() {
+    use alloy::primitives::{Address, Bloom, BloomInput};
+
+    const EMITTER: &str = "0x1100000000000000000000000000000000000001";
+    const SENDER: &str = "0x1100000000000000000000000000000000000002";
+    const OTHER: &str = "0x1100000000000000000000000000000000000003";
+    const RECEIVER: &str = "0x1100000000000000000000000000000000000004";
+    let tx_hash = format!("0x{:064x}", 1100);
+
+    common::run(async {
+        let pool = common::pool();
+        let mut bloom = Bloom::ZERO;
+        bloom.accrue(BloomInput::Raw(
+            EMITTER.parse::<Address>().unwrap().as_slice(),
+        ));
+        let stored = atlas_server::bloom::compact(&bloom);
+
+        // Block 1100 carries blooms; block 1101 predates them (NULL) and is
+        // answered from event_logs.
+        for (number, logs_bloom) in [(1100i64, Some(stored.clone())), (1101, None)] {
+            sqlx::query(
+                "INSERT INTO blocks (number, hash, parent_hash, timestamp, gas_used, gas_limit, transaction_count, indexed_at, logs_bloom)
+                 VALUES ($1, $2, $3, 1700001100, 21000, 30000000, 1, NOW(), $4)
+                 ON CONFLICT (number) DO UPDATE SET logs_bloom = EXCLUDED.logs_bloom",
+            )
+            .bind(number)
+            .bind(format!("0x{:064x}", number))
+            .bind(format!("0x{:064x}", number - 1))
+            .bind(logs_bloom)
+            .execute(&pool)
+            .await
+            .expect("seed block");
+        }
+        sqlx::query(
+            "INSERT INTO transactions (hash, block_number, block_index, from_address, to_address, value, gas_price, gas_used, input_data, status, timestamp, logs_bloom)
+             VALUES ($1, 1100, 0, $2, $3, 0, 1, 21000, '\\x', true, 1700001100, $4)
+             ON CONFLICT (hash, block_number) DO NOTHING",
+        )
+        .bind(&tx_hash)
+        .bind(SENDER)
+        .bind(RECEIVER)
+        .bind(&stored)
+        .execute(&pool)
+        .await
+        .expect("seed transaction");
+        sqlx::query(
+            "INSERT INTO event_logs (tx_hash, log_index, address, topic0, data, block_number)
+             VALUES (decode(substr($1, 3), 'hex'), 0, decode(substr($2, 3), 'hex'), decode(substr($3, 3), 'hex'), '\\x', 1101)
+             ON CONFLICT (tx_hash, log_index, block_number) DO NOTHING",
+        )
+        .bind(format!("0x{:064x}", 1101))
+        .bind(EMITTER)
+        .bind(format!("0x{:064x}", 1))
+        .execute(&pool)
+        .await
+        .expect("seed event log");
+
+        let app = common::test_router();
+        let check = |uri: String| {
+            let app = app.clone();
+            async move {
+                let response = app
+                    .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+                    .await
+                    .unwrap();
+                let status = response.status();
+                (status, common::json_body(response).await["result"].clone())
+            }
+        };
+
+        for (uri, expected) in [
+            (
+                format!("/api/blocks/1100/bloom?address={SENDER}"),
+                "confirmed",
+            ),
+            (
+                format!("/api/blocks/1100/bloom?address={EMITTER}"),
+                "possible",
+            ),
+            (format!("/api/blocks/1100/bloom?address={OTHER}"), "none"),
+            (
+                format!("/api/transactions/{tx_hash}/bloom?address={RECEIVER}"),
+                "confirmed",
+            ),
+            (
+                format!("/api/transactions/{tx_hash}/bloom?address={EMITTER}"),
+                "possible",
+            ),
+            (
+                format!("/api/transactions/{tx_hash}/bloom?topic={tx_hash}"),
+                "none",
+            ),
+            (
+                format!("/api/blocks/1101/bloom?address={EMITTER}"),
+                "confirmed",
+            ),
+            (format!("/api/blocks/1101/bloom?address={OTHER}"), "none"),
+        ] {
+            let (status, result) = check(uri.clone()).await;
+            assert_eq!(status, StatusCode::OK, "{uri}");
+            assert_eq!(result, expected, "{uri}");
+        }
+
+        let (status, _) = check("/api/blocks/1100/bloom".to_string()).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    });
+}
+
+#[test]
+fn raw_block_is_fetched_once_then_cached() {
+    use wiremock::matchers::{body_partial_json, method};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    const BLOCK: i64 = 1200;
+    let hash = format!("0x{:064x}", BLOCK);
+
+    common::run(async {
+        let pool = common::pool();
+        sqlx::query(
+            "INSERT INTO blocks (number, hash, parent_hash, timestamp, gas_used, gas_limit, transaction_count, indexed_at)
+             VALUES ($1, $2, $3, 1700001200, 0, 30000000, 0, NOW())
+             ON CONFLICT (number) DO NOTHING",
+        )
+        .bind(BLOCK)
+        .bind(&hash)
+        .bind(format!("0x{:064x}", BLOCK - 1))
+        .execute(&pool)
+        .await
+        .expect("seed block");
+        sqlx::query("DELETE FROM raw_rpc_cache WHERE kind = 'block' AND key = $1")
+            .bind(BLOCK.to_string())
+            .execute(&pool)
+            .await
+            .expect("reset raw cache");
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(serde_json::json!({
+                "method": "eth_getBlockByNumber",
+                "params": [format!("0x{:x}", BLOCK), false],
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "result": { "hash": hash, "number": "0x4b0", "nodeOnlyField": "kept" },
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let app = common::test_router_with_rpc(mock_server.uri());
+        for expected_cache in ["MISS", "HIT"] {
+            let response = app
+                .clone()
+                .oneshot(
+                    Request::builder()
+                        .uri(format!("/api/blocks/{BLOCK}/raw"))
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            assert_eq!(response.headers()["x-atlas-cache"], expected_cache);
+            let body = common::json_body(response).await;
+            assert_eq!(body["nodeOnlyField"], "kept");
+        }
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/blocks/1999999/raw")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    });
+}
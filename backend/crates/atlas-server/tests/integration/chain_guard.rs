@@ -0,0 +1,53 @@
+use atlas_server::chain_guard;
+use atlas_server::state_keys::CHAIN_ID_KEY;
+
+use crate::common;
+
+#[test]
+fn chain_id_is_recorded_once_and_enforced() {
+    common::run(async {
+        let pool = &common::pool();
+        let previous: Option<String> =
+            sqlx::query_scalar("DELETE FROM indexer_state WHERE key = $1 RETURNING value")
+                .bind(CHAIN_ID_KEY)
+                .fetch_optional(pool)
+                .await
+                .expect("clear chain id");
+
+        chain_guard::ensure_chain_id(pool, 42161)
+            .await
+            .expect("first start records the chain id");
+        assert_eq!(
+            chain_guard::stored_chain_id(pool).await.unwrap(),
+            Some(42161)
+        );
+        chain_guard::ensure_chain_id(pool, 42161)
+            .await
+            .expect("same chain is accepted");
+
+        let err = chain_guard::ensure_chain_id(pool, 1)
+            .await
+            .expect_err("other chain is refused")
+            .to_string();
+        assert!(err.contains("chain ID 1 "), "{err}");
+        assert!(err.contains("chain ID 42161"), "{err}");
+        assert_eq!(
+            chain_guard::stored_chain_id(pool).await.unwrap(),
+            Some(42161)
+        );
+
+        sqlx::query("DELETE FROM indexer_state WHERE key = $1")
+            .bind(CHAIN_ID_KEY)
+            .execute(pool)
+            .await
+            .expect("clear chain id");
+        if let Some(value) = previous {
+            sqlx::query("INSERT INTO indexer_state (key, value) VALUES ($1, $2)")
+                .bind(CHAIN_ID_KEY)
+                .bind(value)
+                .execute(pool)
+                .await
+                .expect("restore chain id");
+        }
+    });
+}
@@ -0,0 +1,398 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    Router,
+};
+use serde_json::{json, Value};
+use std::sync::Arc;
+use tower::ServiceExt;
+
+use atlas_common::storage::ColdBelowBlock;
+use atlas_common::Block;
+use atlas_server::head::HeadTracker;
+
+use crate::common;
+
+// Block range: 14000-14999
+
+/// Schema standing in for the cold tier; blocks below [`BOUNDARY`] are read
+/// from its tables.
+const COLD_SCHEMA: &str = "atlas_cold_test";
+const BOUNDARY: i64 = 14100;
+const HEAD: i64 = 14200;
+const TOKEN: &str = "0x1400000000000000000000000000000000000001";
+const TRANSFER: &str = "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef";
+const SENDER: &str = "0x1400000000000000000000000000000000000002";
+
+fn hash(value: i64) -> String {
+    format!("0x{:064x}", value)
+}
+
+/// Create the cold tier's tables and run `seed` on it, serialized across
+/// tests so concurrent `CREATE SCHEMA`s don't collide.
+async fn with_cold_tables(pool: &sqlx::PgPool, seed: &[(&str, Vec<String>)]) {
+    let mut tx = pool.begin().await.expect("begin");
+    sqlx::query("SELECT pg_advisory_xact_lock(14000)")
+        .execute(&mut *tx)
+        .await
+        .expect("lock");
+    sqlx::query(&format!("CREATE SCHEMA IF NOT EXISTS {COLD_SCHEMA}"))
+        .execute(&mut *tx)
+        .await
+        .expect("create cold schema");
+    for table in ["event_logs", "erc20_transfers"] {
+        sqlx::query(&format!(
+            "CREATE TABLE IF NOT EXISTS {COLD_SCHEMA}.{table} (LIKE public.{table} INCLUDING DEFAULTS)"
+        ))
+        .execute(&mut *tx)
+        .await
+        .expect("create cold table");
+    }
+    for (sql, binds) in seed {
+        let mut query = sqlx::query(sql);
+        for value in binds {
+            query = query.bind(value);
+        }
+        query.execute(&mut *tx).await.expect("seed cold table");
+    }
+    tx.commit().await.expect("commit");
+}
+
+async fn seed_blocks(pool: &sqlx::PgPool, numbers: &[i64]) {
+    for &number in numbers {
+        sqlx::query(
+            "INSERT INTO blocks (number, hash, parent_hash, timestamp, gas_used, gas_limit, transaction_count, indexed_at)
+             VALUES ($1, $2, $3, $4, 21000, 30000000, 1, NOW())
+             ON CONFLICT (number) DO NOTHING",
+        )
+        .bind(number)
+        .bind(hash(number))
+        .bind(hash(number - 1))
+        .bind(1_700_014_000i64 + number)
+        .execute(pool)
+        .await
+        .expect("seed block");
+    }
+}
+
+async fn router(pool: &sqlx::PgPool) -> Router {
+    let head_tracker = Arc::new(HeadTracker::empty(10));
+    head_tracker
+        .publish_committed_batch(vec![Block {
+            number: HEAD,
+            hash: hash(HEAD),
+            parent_hash: hash(HEAD - 1),
+            timestamp: 1_700_014_000 + HEAD,
+            gas_used: 21_000,
+            gas_limit: 30_000_000,
+            base_fee_per_gas: None,
+            transaction_count: 0,
+            indexed_at: chrono::Utc::now(),
+        }])
+        .await;
+    let storage = ColdBelowBlock::new(pool.clone(), pool.clone(), COLD_SCHEMA, BOUNDARY)
+        .expect("cold storage");
+    common::test_router_with_storage(head_tracker, Arc::new(storage))
+}
+
+async fn send(app: Router, request: Request<Body>) -> Value {
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    common::json_body(response).await
+}
+
+async fn status(app: Router, request: Request<Body>) -> StatusCode {
+    app.oneshot(request).await.unwrap().status()
+}
+
+fn get(uri: &str) -> Request<Body> {
+    Request::builder().uri(uri).body(Body::empty()).unwrap()
+}
+
+#[test]
+fn event_logs_below_the_boundary_are_read_from_the_cold_tier() {
+    common::run(async {
+        let pool = common::pool();
+        seed_blocks(&pool, &[14001, 14100]).await;
+        for (tx, block) in [(0x14001, 14001i64), (0x14100, 14100)] {
+            sqlx::query(
+                "INSERT INTO transactions (hash, block_number, block_index, from_address, to_address, value, gas_price, gas_used, input_data, status, timestamp)
+                 VALUES ($1, $2, 2, '0x1400000000000000000000000000000000000009', NULL, 0, 1, 21000, '\\x', true, 1700014000)
+                 ON CONFLICT (hash, block_number) DO NOTHING",
+            )
+            .bind(hash(tx))
+            .bind(block)
+            .execute(&pool)
+            .await
+            .expect("seed transaction");
+        }
+
+        let insert_log = |table: &str| {
+            format!(
+                "INSERT INTO {table} (tx_hash, log_index, address, topic0, data, block_number)
+                 VALUES (decode(substr($1, 3), 'hex'), $2::int, decode(substr('{TOKEN}', 3), 'hex'),
+                         decode(substr('{TRANSFER}', 3), 'hex'), '\\x01', $3::bigint)
+                 ON CONFLICT DO NOTHING"
+            )
+        };
+        // The hot copy of an archived block must not be served.
+        sqlx::query(&insert_log("event_logs"))
+            .bind(hash(0x14001))
+            .bind("7")
+            .bind("14001")
+            .execute(&pool)
+            .await
+            .expect("seed stale hot log");
+        sqlx::query(&insert_log("event_logs"))
+            .bind(hash(0x14100))
+            .bind("0")
+            .bind("14100")
+            .execute(&pool)
+            .await
+            .expect("seed hot log");
+        let cold_table = format!("{COLD_SCHEMA}.event_logs");
+        with_cold_tables(
+            &pool,
+            &[
+                (
+                    &format!("DELETE FROM {cold_table} WHERE block_number BETWEEN 14000 AND 14999"),
+                    vec![],
+                ),
+                (
+                    &insert_log(&cold_table),
+                    vec![hash(0x14001), "0".into(), "14001".into()],
+                ),
+                (
+                    &insert_log(&cold_table),
+                    vec![hash(0x14001), "1".into(), "14001".into()],
+                ),
+            ],
+        )
+        .await;
+
+        let logs = send(
+            router(&pool).await,
+            Request::builder()
+                .method("POST")
+                .uri("/api/rpc/logs")
+                .header("content-type", "application/json")
+                .body(Body::from(
+                    json!({ "fromBlock": "0x36b0", "toBlock": "0x3714", "address": TOKEN })
+                        .to_string(),
+                ))
+                .unwrap(),
+        )
+        .await;
+        let logs: Vec<(&str, &str, Option<&str>, Option<&str>)> = logs
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|log| {
+                (
+                    log["blockNumber"].as_str().unwrap(),
+                    log["logIndex"].as_str().unwrap(),
+                    log["blockHash"].as_str(),
+                    log["transactionIndex"].as_str(),
+                )
+            })
+            .collect();
+        let (cold_block, hot_block) = (hash(14001), hash(14100));
+        assert_eq!(
+            logs,
+            [
+                ("0x36b1", "0x0", Some(cold_block.as_str()), Some("0x2")),
+                ("0x36b1", "0x1", Some(cold_block.as_str()), Some("0x2")),
+                ("0x3714", "0x0", Some(hot_block.as_str()), Some("0x2")),
+            ]
+        );
+
+        let body = send(router(&pool).await, get("/api/blocks/14001/logs")).await;
+        assert_eq!(body["total"], 2);
+        let body = send(
+            router(&pool).await,
+            get(&format!("/api/transactions/{}/logs", hash(0x14001))),
+        )
+        .await;
+        assert_eq!(body["total"], 2);
+        assert_eq!(body["data"][1]["log_index"], 1);
+
+        let uri = format!("/api/addresses/{TOKEN}/logs?topic0={TRANSFER}");
+        let body = send(router(&pool).await, get(&uri)).await;
+        assert_eq!(body["total"], 3);
+        let logs: Vec<(i64, i64)> = body["data"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|log| {
+                (
+                    log["block_number"].as_i64().unwrap(),
+                    log["log_index"].as_i64().unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(logs, [(14100, 0), (14001, 1), (14001, 0)]);
+
+        // The bundler of a user operation is read from `transactions`, which
+        // the cold tier lacks.
+        assert_eq!(
+            status(
+                router(&pool).await,
+                get("/api/stats/aa?from_block=14000&to_block=14200")
+            )
+            .await,
+            StatusCode::BAD_REQUEST
+        );
+    });
+}
+
+#[test]
+fn erc20_transfers_are_read_across_both_tiers() {
+    common::run(async {
+        let pool = common::pool();
+        sqlx::query(
+            "INSERT INTO erc20_contracts (address, name, symbol, decimals, total_supply, first_seen_block)
+             VALUES ($1, 'Cold Token', 'COLD', 0, 1000, 14000)
+             ON CONFLICT (address) DO NOTHING",
+        )
+        .bind(TOKEN)
+        .execute(&pool)
+        .await
+        .expect("seed token");
+        sqlx::query(
+            "INSERT INTO transactions (hash, block_number, block_index, from_address, to_address, value, gas_price, gas_used, input_data, status, timestamp)
+             VALUES ($1, 14011, 0, $2, NULL, 0, 1, 21000, '\\x', true, 1700014000)
+             ON CONFLICT (hash, block_number) DO NOTHING",
+        )
+        .bind(hash(14011))
+        .bind(SENDER)
+        .execute(&pool)
+        .await
+        .expect("seed transaction");
+        // NFT transfers stay in the hot tables below the boundary too.
+        sqlx::query("DELETE FROM nft_transfers WHERE block_number BETWEEN 14000 AND 14999")
+            .execute(&pool)
+            .await
+            .expect("clear nft transfers");
+        sqlx::query(
+            "INSERT INTO nft_transfers (tx_hash, log_index, contract_address, token_id, from_address, to_address, block_number, timestamp)
+             VALUES ($1, 0, '0x1400000000000000000000000000000000000004', 1, $2, '0x1400000000000000000000000000000000000003', 14050, 1700014000)",
+        )
+        .bind(hash(14050))
+        .bind(SENDER)
+        .execute(&pool)
+        .await
+        .expect("seed nft transfer");
+        let insert_transfer = |table: &str| {
+            format!(
+                "INSERT INTO {table} (tx_hash, log_index, contract_address, from_address, to_address, value, block_number, timestamp)
+                 VALUES ($1, 0, '{TOKEN}', '{SENDER}', '0x1400000000000000000000000000000000000003', 5, $2::bigint, 1700014000)
+                 ON CONFLICT DO NOTHING"
+            )
+        };
+        for block in [14110i64, 14111] {
+            sqlx::query(&insert_transfer("erc20_transfers"))
+                .bind(hash(block))
+                .bind(block.to_string())
+                .execute(&pool)
+                .await
+                .expect("seed hot transfer");
+        }
+        let cold_table = format!("{COLD_SCHEMA}.erc20_transfers");
+        let mut seed = vec![(
+            format!("DELETE FROM {cold_table} WHERE block_number BETWEEN 14000 AND 14999"),
+            vec![],
+        )];
+        for block in [14011i64, 14012] {
+            seed.push((
+                insert_transfer(&cold_table),
+                vec![hash(block), block.to_string()],
+            ));
+        }
+        let seed: Vec<(&str, Vec<String>)> = seed
+            .iter()
+            .map(|(sql, binds)| (sql.as_str(), binds.clone()))
+            .collect();
+        with_cold_tables(&pool, &seed).await;
+
+        let blocks = |body: &Value| -> Vec<i64> {
+            body["data"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|t| t["block_number"].as_i64().unwrap())
+                .collect()
+        };
+        let uri = format!("/api/tokens/{TOKEN}/transfers?limit=3");
+        let body = send(router(&pool).await, get(&uri)).await;
+        assert_eq!(body["total"], 4);
+        assert_eq!(blocks(&body), [14111, 14110, 14012]);
+
+        let body = send(router(&pool).await, get(&format!("{uri}&page=2"))).await;
+        assert_eq!(blocks(&body), [14011]);
+
+        let body = send(router(&pool).await, get(&format!("/api/tokens/{TOKEN}"))).await;
+        assert_eq!(body["transfer_count"], 4);
+
+        let uri =
+            format!("/api/addresses/{SENDER}/transfers?transfer_type=erc20&limit=3&count=exact");
+        let body = send(router(&pool).await, get(&uri)).await;
+        assert_eq!(body["total"], 4);
+        assert_eq!(blocks(&body), [14111, 14110, 14012]);
+        assert_eq!(body["data"][2]["token_symbol"], "COLD");
+        let body = send(router(&pool).await, get(&format!("{uri}&page=2"))).await;
+        assert_eq!(blocks(&body), [14011]);
+
+        // Both kinds: the cold segment's ERC-20 transfers are merged with the
+        // hot NFT transfer in the same blocks.
+        let uri = format!("/api/addresses/{SENDER}/transfers?limit=3&count=exact");
+        let body = send(router(&pool).await, get(&uri)).await;
+        assert_eq!(body["total"], 5);
+        assert_eq!(blocks(&body), [14111, 14110, 14050]);
+        let body = send(router(&pool).await, get(&format!("{uri}&page=2"))).await;
+        assert_eq!(blocks(&body), [14012, 14011]);
+        assert_eq!(
+            status(
+                router(&pool).await,
+                Request::builder()
+                    .uri(format!("/api/addresses/{SENDER}/transfers"))
+                    .header("accept", "application/x-ndjson")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await,
+            StatusCode::BAD_REQUEST
+        );
+
+        let body = send(
+            router(&pool).await,
+            get(&format!(
+                "/api?module=account&action=tokentx&address={SENDER}&page=2&offset=3"
+            )),
+        )
+        .await;
+        assert_eq!(body["result"][0]["blockNumber"], "14011");
+        assert_eq!(body["result"][0]["tokenSymbol"], "COLD");
+
+        let body = send(
+            router(&pool).await,
+            get(&format!(
+                "/api/transactions/{}/erc20-transfers",
+                hash(14011)
+            )),
+        )
+        .await;
+        assert_eq!(body["total"], 1);
+        assert_eq!(blocks(&body), [14011]);
+
+        let body = send(
+            router(&pool).await,
+            get(&format!("/api/addresses/{SENDER}/token-flows?period=all")),
+        )
+        .await;
+        let flow = &body["tokens"][0];
+        assert_eq!(flow["contract_address"], TOKEN);
+        assert_eq!(flow["symbol"], "COLD");
+        assert_eq!(flow["outbound"], "20");
+        assert_eq!(flow["outbound_transfers"], 4);
+    });
+}
@@ -1,3 +1,5 @@
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
 use axum::Router;
 use sqlx::postgres::PgPoolOptions;
 use sqlx::PgPool;
@@ -8,9 +10,29 @@ use testcontainers::runners::SyncRunner;
 use testcontainers::{Container, ImageExt};
 use testcontainers_modules::postgres::Postgres;
 use tokio::sync::broadcast;
+use tower::ServiceExt;
+use wiremock::matchers::method;
+use wiremock::{Mock, MockServer, ResponseTemplate};
 
+use atlas_common::db::PoolSettings;
+use atlas_common::storage::{HotStorage, TieredStorage};
 use atlas_server::api::{build_router, AppState};
+use atlas_server::db_pools::{DbPools, MonitoredPool};
 use atlas_server::head::HeadTracker;
+use atlas_server::indexer::processors::Processors;
+use atlas_server::indexer::BackfillWorker;
+use atlas_server::metrics::Metrics;
+use atlas_server::ops_alerts::OpsAlerter;
+
+/// Admin key configured on the router returned by [`test_router`].
+pub const ADMIN_API_KEY: &str = "test-admin-key";
+
+/// Held by tests that run a backfill worker: workers claim the lowest open
+/// lease, whichever test inserted it.
+pub static LEASES: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+/// Held by tests that set or advance `last_indexed_block`.
+pub static INDEXED_HEAD: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
 
 struct TestEnv {
     database_url: String,
@@ -56,7 +78,7 @@ fn init_env() -> Result<TestEnv, String> {
                 .await
                 .map_err(|error| format!("failed to create test pool: {error}"))?;
 
-            sqlx::migrate!("../../migrations")
+            atlas_migrations::MIGRATOR
                 .run(&pool)
                 .await
                 .map_err(|error| format!("failed to run test migrations: {error}"))?;
@@ -88,8 +110,64 @@ pub fn database_url() -> &'static str {
 }
 
 pub fn test_router() -> Router {
+    test_router_with_rpc(String::new())
+}
+
+/// [`test_router`] pointed at a (mock) JSON-RPC endpoint.
+pub fn test_router_with_rpc(rpc_url: String) -> Router {
+    router_with(rpc_url, None, None, Arc::new(HeadTracker::empty(10)), None)
+}
+
+/// [`test_router_with_rpc`] with `head_tracker` as the indexed and chain head.
+pub fn test_router_with_rpc_and_head(rpc_url: String, head_tracker: Arc<HeadTracker>) -> Router {
+    router_with(rpc_url, None, None, head_tracker, None)
+}
+
+/// [`test_router`] with bulk exports enabled, writing to `export_dir`.
+pub fn test_router_with_export_dir(export_dir: String) -> Router {
+    router_with(
+        String::new(),
+        Some(export_dir),
+        None,
+        Arc::new(HeadTracker::empty(10)),
+        None,
+    )
+}
+
+/// [`test_router`] with daily dataset dumps served from `dataset_dir`.
+pub fn test_router_with_dataset_dir(dataset_dir: String) -> Router {
+    router_with(
+        String::new(),
+        None,
+        Some(dataset_dir),
+        Arc::new(HeadTracker::empty(10)),
+        None,
+    )
+}
+
+/// [`test_router`] whose indexed head is `head_tracker`'s latest block.
+pub fn test_router_with_head(head_tracker: Arc<HeadTracker>) -> Router {
+    router_with(String::new(), None, None, head_tracker, None)
+}
+
+/// [`test_router_with_head`] reading event logs and ERC-20 transfers through
+/// `storage`.
+pub fn test_router_with_storage(
+    head_tracker: Arc<HeadTracker>,
+    storage: Arc<dyn TieredStorage>,
+) -> Router {
+    router_with(String::new(), None, None, head_tracker, Some(storage))
+}
+
+fn router_with(
+    rpc_url: String,
+    export_dir: Option<String>,
+    dataset_dir: Option<String>,
+    head_tracker: Arc<HeadTracker>,
+    storage: Option<Arc<dyn TieredStorage>>,
+) -> Router {
     let pool = pool();
-    let head_tracker = Arc::new(HeadTracker::empty(10));
+    let storage = storage.unwrap_or_else(|| Arc::new(HotStorage::new(pool.clone())));
     let (tx, _) = broadcast::channel(1);
     let (da_tx, _) = broadcast::channel(1);
 
@@ -97,17 +175,18 @@ pub fn test_router() -> Router {
         .build_recorder()
         .handle();
     let state = Arc::new(AppState {
-        pool,
+        pool: pool.clone(),
         block_events_tx: tx,
         da_events_tx: da_tx,
         head_tracker,
-        rpc_url: String::new(),
+        rpc_url,
         da_tracking_enabled: false,
         faucet: None,
         faucet_amount_wei: None,
         faucet_cooldown_minutes: None,
         chain_id: 42,
         chain_name: "Test Chain".to_string(),
+        native_currency: Default::default(),
         chain_logo_url: None,
         chain_logo_url_light: None,
         chain_logo_url_dark: None,
@@ -119,6 +198,26 @@ pub fn test_router() -> Router {
         metrics: atlas_server::metrics::Metrics::new(),
         prometheus_handle,
         solc_cache_dir: "/tmp/solc-cache".to_string(),
+        verify_timeout: std::time::Duration::from_secs(300),
+        verify_max_source_bytes: 20 * 1024 * 1024,
+        export_dir,
+        dataset_dir,
+        db_circuit_breaker: atlas_server::api::circuit_breaker::CircuitBreaker::default(),
+        latency: Default::default(),
+        admin_api_key: Some(ADMIN_API_KEY.to_string()),
+        db_pools: Arc::new(DbPools::new(vec![MonitoredPool::new(
+            "api",
+            pool,
+            PoolSettings::with_max_connections(10),
+        )])),
+        concurrency_limits: Default::default(),
+        body_limits: Default::default(),
+        log_limits: Default::default(),
+        row_limits: Default::default(),
+        storage,
+        confirmations_head: Default::default(),
+        block_stall_secs: 0,
+        runtime_settings: Default::default(),
     });
 
     build_router(state, None)
@@ -136,6 +235,20 @@ pub fn run<F: std::future::Future<Output = ()>>(f: F) {
         .block_on(f);
 }
 
+/// Helper to parse an NDJSON response body into one value per line.
+pub async fn ndjson_body(
+    response: axum::http::Response<axum::body::Body>,
+) -> Vec<serde_json::Value> {
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("read body");
+    String::from_utf8(bytes.to_vec())
+        .expect("utf-8 body")
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("parse JSON line"))
+        .collect()
+}
+
 /// Helper to parse a JSON response body.
 pub async fn json_body(response: axum::http::Response<axum::body::Body>) -> serde_json::Value {
     let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
@@ -143,3 +256,67 @@ pub async fn json_body(response: axum::http::Response<axum::body::Body>) -> serd
         .expect("read body");
     serde_json::from_slice(&bytes).expect("parse JSON")
 }
+
+/// GET `uri` from [`test_router`], returning the status and the JSON body.
+/// Error bodies are `Null`: extractor rejections answer in plain text.
+pub async fn get_json(uri: &str) -> (StatusCode, serde_json::Value) {
+    let response = test_router()
+        .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let status = response.status();
+    if !status.is_success() {
+        return (status, serde_json::Value::Null);
+    }
+    (status, json_body(response).await)
+}
+
+/// Index `block` with a backfill worker running `processors`, against an RPC
+/// endpoint answering every request with `rpc_response`.
+///
+/// Holds [`LEASES`] while the block's lease is claimed and processed.
+pub async fn index_block_via_backfill(
+    pool: &PgPool,
+    block: u64,
+    rpc_response: serde_json::Value,
+    processors: Processors,
+) {
+    let _leases = LEASES.lock().await;
+    sqlx::query("DELETE FROM range_leases WHERE start_block = $1")
+        .bind(block as i64)
+        .execute(pool)
+        .await
+        .expect("clear lease");
+    sqlx::query(
+        "INSERT INTO range_leases (start_block, end_block, next_block) VALUES ($1, $1 + 1, $1)",
+    )
+    .bind(block as i64)
+    .execute(pool)
+    .await
+    .expect("insert lease");
+
+    let rpc = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(rpc_response))
+        .mount(&rpc)
+        .await;
+    let worker = BackfillWorker::new(
+        pool.clone(),
+        database_url(),
+        &rpc.uri(),
+        10,
+        Metrics::new(),
+        false,
+        OpsAlerter::default(),
+        0,
+    )
+    .unwrap()
+    .with_processors(processors);
+    assert!(worker.process_next_lease().await.expect("process lease"));
+
+    sqlx::query("DELETE FROM range_leases WHERE start_block = $1")
+        .bind(block as i64)
+        .execute(pool)
+        .await
+        .expect("clear lease");
+}
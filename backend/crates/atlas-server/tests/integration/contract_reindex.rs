@@ -0,0 +1,212 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+};
+use serde_json::Value;
+use tower::ServiceExt;
+
+use atlas_server::indexer::ContractReindexer;
+
+use crate::common;
+
+// Block range: 25000-25999
+
+const TOKEN: &str = "0x250000000000000000000000000000000000000a";
+const NFT: &str = "0x250000000000000000000000000000000000000b";
+const ALICE: &str = "0x2500000000000000000000000000000000000001";
+const BOB: &str = "0x2500000000000000000000000000000000000002";
+const CAROL: &str = "0x2500000000000000000000000000000000000003";
+const TRANSFER_TOPIC: &str = "ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef";
+
+fn address_bytes(address: &str) -> Vec<u8> {
+    hex::decode(address.trim_start_matches("0x")).unwrap()
+}
+
+fn address_topic(address: &str) -> Vec<u8> {
+    let mut topic = vec![0u8; 12];
+    topic.extend(address_bytes(address));
+    topic
+}
+
+fn word(value: u64) -> Vec<u8> {
+    let mut word = vec![0u8; 24];
+    word.extend(value.to_be_bytes());
+    word
+}
+
+async fn seed(pool: &sqlx::PgPool) {
+    for block in [25000_i64, 25001] {
+        sqlx::query(
+            "INSERT INTO blocks (number, hash, parent_hash, timestamp, gas_used, gas_limit, transaction_count, indexed_at)
+             VALUES ($1, $2, $3, $4, 0, 30000000, 0, NOW())
+             ON CONFLICT (number) DO NOTHING",
+        )
+        .bind(block)
+        .bind(format!("0x{:064x}", block))
+        .bind(format!("0x{:064x}", block - 1))
+        .bind(1_700_000_000 + block)
+        .execute(pool)
+        .await
+        .expect("seed block");
+    }
+
+    let zero = "0x0000000000000000000000000000000000000000";
+    // ERC-20: mint 100 to Alice, Alice sends 30 to Bob. NFT: token 7 minted
+    // to Alice, then sent to Bob.
+    let logs = [
+        (25000_i64, 0, TOKEN, zero, ALICE, None, word(100)),
+        (25000, 1, NFT, zero, ALICE, Some(word(7)), Vec::new()),
+        (25001, 0, TOKEN, ALICE, BOB, None, word(30)),
+        (25001, 1, NFT, ALICE, BOB, Some(word(7)), Vec::new()),
+    ];
+    for (block, log_index, address, from, to, topic3, data) in logs {
+        sqlx::query(
+            "INSERT INTO event_logs (tx_hash, log_index, address, topic0, topic1, topic2, topic3, data, block_number)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+             ON CONFLICT (tx_hash, log_index, block_number) DO NOTHING",
+        )
+        .bind(hex::decode(format!("{:064x}", block)).unwrap())
+        .bind(log_index)
+        .bind(address_bytes(address))
+        .bind(hex::decode(TRANSFER_TOPIC).unwrap())
+        .bind(address_topic(from))
+        .bind(address_topic(to))
+        .bind(topic3)
+        .bind(data)
+        .bind(block)
+        .execute(pool)
+        .await
+        .expect("seed log");
+    }
+
+    // State left behind by a decoder bug: a phantom transfer to Carol.
+    sqlx::query(
+        "INSERT INTO erc20_contracts (address, name, symbol, decimals, first_seen_block, total_supply)
+         VALUES ($1, 'Rebuilt', 'RBT', 18, 25000, 5)
+         ON CONFLICT (address) DO UPDATE SET total_supply = 5",
+    )
+    .bind(TOKEN)
+    .execute(pool)
+    .await
+    .expect("seed token");
+    sqlx::query(
+        "INSERT INTO erc20_transfers (tx_hash, log_index, contract_address, from_address, to_address, value, block_number, timestamp)
+         VALUES ($1, 9, $2, $3, $4, 999, 25001, 1700025001)
+         ON CONFLICT (tx_hash, log_index, block_number) DO NOTHING",
+    )
+    .bind(format!("0x{:064x}", 25001))
+    .bind(TOKEN)
+    .bind(ALICE)
+    .bind(CAROL)
+    .execute(pool)
+    .await
+    .expect("seed phantom transfer");
+    sqlx::query(
+        "INSERT INTO erc20_balances (address, contract_address, balance, last_updated_block)
+         VALUES ($1, $2, 999, 25001)
+         ON CONFLICT (address, contract_address) DO UPDATE SET balance = EXCLUDED.balance",
+    )
+    .bind(CAROL)
+    .bind(TOKEN)
+    .execute(pool)
+    .await
+    .expect("seed phantom balance");
+}
+
+async fn request(method: &str, address: &str) -> (StatusCode, Value) {
+    let response = common::test_router()
+        .oneshot(
+            Request::builder()
+                .method(method)
+                .uri(format!("/api/admin/contracts/{address}/reindex"))
+                .header("authorization", format!("Bearer {}", common::ADMIN_API_KEY))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let status = response.status();
+    (status, common::json_body(response).await)
+}
+
+#[test]
+fn contract_reindex_rebuilds_transfers_and_balances_from_logs() {
+    common::run(async {
+        let pool = common::pool();
+        seed(&pool).await;
+
+        let (status, job) = request("POST", TOKEN).await;
+        assert_eq!(status, StatusCode::ACCEPTED);
+        assert_eq!(job["status"], "queued");
+        assert_eq!(job["address"], TOKEN);
+        // A second request returns the open job.
+        let (_, again) = request("POST", TOKEN).await;
+        assert_eq!(again["id"], job["id"]);
+        request("POST", NFT).await;
+
+        let worker = ContractReindexer::new(pool.clone(), common::database_url(), false);
+        assert!(worker.run_cycle().await.unwrap());
+        assert!(worker.run_cycle().await.unwrap());
+
+        let (status, job) = request("GET", TOKEN).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(job["status"], "completed", "{job}");
+        assert_eq!(job["erc20_transfers"], 2);
+        assert_eq!(job["nft_transfers"], 0);
+
+        let transfers: Vec<(String, String, String)> = sqlx::query_as(
+            "SELECT from_address, to_address, value::text FROM erc20_transfers
+             WHERE contract_address = $1 ORDER BY block_number",
+        )
+        .bind(TOKEN)
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+        assert_eq!(transfers.len(), 2);
+        assert_eq!(transfers[1], (ALICE.into(), BOB.into(), "30".into()));
+
+        let balances: Vec<(String, String)> = sqlx::query_as(
+            "SELECT address, balance::text FROM erc20_balances
+             WHERE contract_address = $1 ORDER BY address",
+        )
+        .bind(TOKEN)
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+        assert_eq!(
+            balances,
+            vec![(ALICE.into(), "70".into()), (BOB.into(), "30".into())]
+        );
+
+        let (supply,): (String,) =
+            sqlx::query_as("SELECT total_supply::text FROM erc20_contracts WHERE address = $1")
+                .bind(TOKEN)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(supply, "100");
+
+        let (_, job) = request("GET", NFT).await;
+        assert_eq!(job["status"], "completed", "{job}");
+        assert_eq!(job["nft_transfers"], 2);
+        let (owner, last_block): (String, i64) = sqlx::query_as(
+            "SELECT owner, last_transfer_block FROM nft_tokens
+             WHERE contract_address = $1 AND token_id = 7",
+        )
+        .bind(NFT)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!((owner.as_str(), last_block), (BOB, 25001));
+    });
+}
+
+#[test]
+fn contract_reindex_status_is_not_found_before_a_request() {
+    common::run(async {
+        let (status, _) = request("GET", CAROL).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        let (status, _) = request("POST", "not-an-address").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    });
+}
@@ -0,0 +1,625 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+};
+use serde_json::json;
+use tower::ServiceExt;
+use wiremock::matchers::{body_partial_json, method};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use crate::common;
+
+// Contract addresses: 0x...c0de suffix
+
+const PROXY: &str = "0x000000000000000000000000000000000000c0de";
+const IMPLEMENTATION: &str = "0x000000000000000000000000000000000001c0de";
+const UNVERIFIED: &str = "0x000000000000000000000000000000000002c0de";
+
+async fn seed_abi(pool: &sqlx::PgPool, address: &str, abi: serde_json::Value) {
+    sqlx::query(
+        "INSERT INTO contract_abis (address, abi) VALUES ($1, $2)
+         ON CONFLICT (address) DO UPDATE SET abi = EXCLUDED.abi",
+    )
+    .bind(address)
+    .bind(abi)
+    .execute(pool)
+    .await
+    .expect("seed abi");
+}
+
+async fn get(app: axum::Router, uri: &str) -> axum::response::Response {
+    app.oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap()
+}
+
+#[test]
+fn contract_events_include_proxy_implementation_events() {
+    common::run(async {
+        let pool = common::pool();
+        let upgraded = json!({"type": "event", "name": "Upgraded", "anonymous": false, "inputs": [
+            {"name": "implementation", "type": "address", "indexed": true}
+        ]});
+        seed_abi(&pool, PROXY, json!([upgraded.clone()])).await;
+        seed_abi(
+            &pool,
+            IMPLEMENTATION,
+            json!([
+                upgraded,
+                {"type": "event", "name": "Transfer", "anonymous": false, "inputs": [
+                    {"name": "from", "type": "address", "indexed": true},
+                    {"name": "to", "type": "address", "indexed": true},
+                    {"name": "value", "type": "uint256", "indexed": false}
+                ]}
+            ]),
+        )
+        .await;
+        sqlx::query(
+            "INSERT INTO proxy_contracts (proxy_address, implementation_address, proxy_type, detected_at_block, last_checked_block)
+             VALUES ($1, $2, 'eip1967', 0, 0)
+             ON CONFLICT (proxy_address) DO UPDATE SET implementation_address = EXCLUDED.implementation_address",
+        )
+        .bind(PROXY)
+        .bind(IMPLEMENTATION)
+        .execute(&pool)
+        .await
+        .expect("seed proxy");
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({"method": "eth_getStorageAt"})))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "result": format!("0x{:0>64}", IMPLEMENTATION.trim_start_matches("0x")),
+            })))
+            .mount(&mock_server)
+            .await;
+        let app = common::test_router_with_rpc(mock_server.uri());
+
+        let response = get(app, &format!("/api/contracts/{PROXY}/events")).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = common::json_body(response).await;
+        assert_eq!(body["implementation_address"], IMPLEMENTATION);
+
+        let events = body["events"].as_array().unwrap();
+        let names: Vec<&str> = events.iter().map(|e| e["name"].as_str().unwrap()).collect();
+        assert_eq!(names, vec!["Upgraded", "Transfer"]);
+        assert!(events.iter().all(|e| e["source"] == "implementation"));
+
+        let transfer = &events[1];
+        let topic0 = "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef";
+        assert_eq!(transfer["topic0"], topic0);
+        assert_eq!(
+            transfer["filter"],
+            json!({"address": PROXY, "topics": [topic0]})
+        );
+        assert_eq!(transfer["abi"]["inputs"][2]["name"], "value");
+
+        // Not a proxy (empty implementation slot) and no ABI of its own.
+        sqlx::query("DELETE FROM proxy_contracts WHERE proxy_address = $1")
+            .bind(UNVERIFIED)
+            .execute(&pool)
+            .await
+            .expect("reset proxy");
+        let empty_slot = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "result": format!("0x{:064x}", 0),
+            })))
+            .mount(&empty_slot)
+            .await;
+        let app = common::test_router_with_rpc(empty_slot.uri());
+        let response = get(app, &format!("/api/contracts/{UNVERIFIED}/events")).await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    });
+}
+
+#[test]
+fn contract_files_list_and_serve_verified_sources() {
+    const MULTI_FILE: &str = "0x000000000000000000000000000000000003c0de";
+    const SINGLE_FILE: &str = "0x000000000000000000000000000000000004c0de";
+
+    common::run(async {
+        let pool = common::pool();
+        for (address, source_code, is_multi_file, source_files) in [
+            (
+                MULTI_FILE,
+                "{}",
+                true,
+                Some(json!({
+                    "src/Token.sol": "import \"@openzeppelin/contracts/token/ERC20/ERC20.sol\";",
+                    "@openzeppelin/contracts/token/ERC20/ERC20.sol": "contract ERC20 {}",
+                })),
+            ),
+            (SINGLE_FILE, "contract Counter {}", false, None),
+        ] {
+            sqlx::query(
+                "INSERT INTO contract_abis (address, abi, source_code, contract_name, is_multi_file, source_files)
+                 VALUES ($1, '[]', $2, 'Counter', $3, $4)
+                 ON CONFLICT (address) DO UPDATE SET source_code = EXCLUDED.source_code,
+                     is_multi_file = EXCLUDED.is_multi_file, source_files = EXCLUDED.source_files",
+            )
+            .bind(address)
+            .bind(source_code)
+            .bind(is_multi_file)
+            .bind(source_files)
+            .execute(&pool)
+            .await
+            .expect("seed verified contract");
+        }
+        let app = common::test_router();
+
+        let response = get(app.clone(), &format!("/api/contracts/{MULTI_FILE}/files")).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = common::json_body(response).await;
+        assert_eq!(body["is_multi_file"], true);
+        assert_eq!(
+            body["files"],
+            json!([
+                {"path": "@openzeppelin/contracts/token/ERC20/ERC20.sol", "size": 17},
+                {"path": "src/Token.sol", "size": 55},
+            ])
+        );
+
+        let response = get(
+            app.clone(),
+            &format!(
+                "/api/contracts/{MULTI_FILE}/files/@openzeppelin/contracts/token/ERC20/ERC20.sol"
+            ),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers()["content-type"],
+            "text/plain; charset=utf-8"
+        );
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(&bytes[..], b"contract ERC20 {}");
+
+        let response = get(app.clone(), &format!("/api/contracts/{SINGLE_FILE}/files")).await;
+        let body = common::json_body(response).await;
+        assert_eq!(body["files"], json!([{"path": "Counter.sol", "size": 19}]));
+
+        let response = get(
+            app.clone(),
+            &format!("/api/contracts/{MULTI_FILE}/files/src/Missing.sol"),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let response = get(app, &format!("/api/contracts/{UNVERIFIED}/files")).await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    });
+}
+
+#[test]
+fn contract_ast_returns_recorded_source_map_and_asts() {
+    const WITH_AST: &str = "0x000000000000000000000000000000000005c0de";
+    const WITHOUT_AST: &str = "0x000000000000000000000000000000000006c0de";
+
+    common::run(async {
+        let pool = common::pool();
+        let sources = json!({
+            "Counter.sol": {"id": 0, "ast": {"nodeType": "SourceUnit", "id": 7, "nodes": []}},
+        });
+        for (address, source_map, source_asts) in [
+            (WITH_AST, Some("0:120:0:-:0;;;"), Some(sources.clone())),
+            (WITHOUT_AST, None, None),
+        ] {
+            sqlx::query(
+                "INSERT INTO contract_abis (address, abi, contract_name, deployed_source_map, source_asts)
+                 VALUES ($1, '[]', 'Counter', $2, $3)
+                 ON CONFLICT (address) DO UPDATE SET deployed_source_map = EXCLUDED.deployed_source_map,
+                     source_asts = EXCLUDED.source_asts",
+            )
+            .bind(address)
+            .bind(source_map)
+            .bind(source_asts)
+            .execute(&pool)
+            .await
+            .expect("seed verified contract");
+        }
+        let app = common::test_router();
+
+        let response = get(app.clone(), &format!("/api/contracts/{WITH_AST}/ast")).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = common::json_body(response).await;
+        assert_eq!(
+            body,
+            json!({
+                "address": WITH_AST,
+                "contract_name": "Counter",
+                "source_map": "0:120:0:-:0;;;",
+                "sources": sources,
+            })
+        );
+
+        let response = get(app.clone(), &format!("/api/contracts/{WITHOUT_AST}/ast")).await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let response = get(app, &format!("/api/contracts/{UNVERIFIED}/ast")).await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    });
+}
+
+#[test]
+fn verify_reuses_cached_compilation_output() {
+    const CLONE: &str = "0x000000000000000000000000000000000005c0de";
+    // Not a real solc build: a cache miss would fail to download it.
+    const COMPILER: &str = "v0.8.24+commit.39640000";
+    const SOURCE: &str =
+        "contract Answer { fallback() external { assembly { mstore(0, 42) return(0, 32) } } }";
+    const RUNTIME: &str = "602a60005260206000f3";
+
+    common::run(async {
+        let pool = common::pool();
+        sqlx::query("DELETE FROM contract_abis WHERE address = $1")
+            .bind(CLONE)
+            .execute(&pool)
+            .await
+            .expect("reset abi");
+        sqlx::query(
+            "INSERT INTO addresses (address, is_contract, first_seen_block) VALUES ($1, true, 0)
+             ON CONFLICT (address) DO UPDATE SET is_contract = true",
+        )
+        .bind(CLONE)
+        .execute(&pool)
+        .await
+        .expect("seed contract address");
+
+        // The standard-JSON input the server builds for a single-file source.
+        let input = json!({
+            "language": "Solidity",
+            "sources": { "contract.sol": { "content": SOURCE } },
+            "settings": {
+                "optimizer": { "enabled": false, "runs": 200 },
+                "outputSelection": { "*": { "": ["ast"], "*": ["abi", "evm.deployedBytecode"] } },
+            },
+        });
+        let input_hash = format!(
+            "{:#x}",
+            alloy::primitives::keccak256(serde_json::to_string(&input).unwrap().as_bytes())
+        );
+        let output = json!({
+            "contracts": { "contract.sol": { "Answer": {
+                "abi": [{"type": "fallback", "stateMutability": "nonpayable"}],
+                "evm": { "deployedBytecode": { "object": RUNTIME } }
+            } } }
+        });
+        sqlx::query(
+            "INSERT INTO compilation_cache (compiler_version, input_hash, output) VALUES ($1, $2, $3)
+             ON CONFLICT (compiler_version, input_hash) DO UPDATE SET output = EXCLUDED.output, hits = 0",
+        )
+        .bind(COMPILER)
+        .bind(&input_hash)
+        .bind(&output)
+        .execute(&pool)
+        .await
+        .expect("seed compilation cache");
+
+        let rpc = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({"method": "eth_getCode"})))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "result": format!("0x{RUNTIME}"),
+            })))
+            .mount(&rpc)
+            .await;
+        let app = common::test_router_with_rpc(rpc.uri());
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri(format!("/api/contracts/{CLONE}/verify"))
+                    .header("content-type", "application/json")
+                    .body(Body::from(
+                        json!({
+                            "source_code": SOURCE,
+                            "contract_name": "Answer",
+                            "compiler_version": COMPILER,
+                        })
+                        .to_string(),
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let status = response.status();
+        let body = common::json_body(response).await;
+        assert_eq!(status, StatusCode::OK, "{body}");
+        assert_eq!(body["match_strategy"], "exact");
+
+        let hits: i64 = sqlx::query_scalar(
+            "SELECT hits FROM compilation_cache WHERE compiler_version = $1 AND input_hash = $2",
+        )
+        .bind(COMPILER)
+        .bind(&input_hash)
+        .fetch_one(&pool)
+        .await
+        .expect("cache entry");
+        assert_eq!(hits, 1);
+
+        sqlx::query("DELETE FROM compilation_cache WHERE compiler_version = $1")
+            .bind(COMPILER)
+            .execute(&pool)
+            .await
+            .expect("clear cache");
+        sqlx::query("DELETE FROM contract_abis WHERE address = $1")
+            .bind(CLONE)
+            .execute(&pool)
+            .await
+            .expect("clear abi");
+    });
+}
+
+#[test]
+fn abi_bundle_maps_addresses_to_combined_abis() {
+    const BUNDLE_PROXY: &str = "0x0000000000000000000000000000000000a0c0de";
+    const BUNDLE_IMPLEMENTATION: &str = "0x0000000000000000000000000000000000a1c0de";
+    const BUNDLE_PLAIN: &str = "0x0000000000000000000000000000000000a2c0de";
+    const BUNDLE_UNVERIFIED: &str = "0x0000000000000000000000000000000000a3c0de";
+
+    common::run(async {
+        let pool = common::pool();
+        let upgrade_to =
+            json!({"type": "function", "name": "upgradeTo", "inputs": [], "outputs": []});
+        let transfer = json!({"type": "function", "name": "transfer", "inputs": [], "outputs": []});
+        let approve = json!({"type": "function", "name": "approve", "inputs": [], "outputs": []});
+        seed_abi(&pool, BUNDLE_PROXY, json!([upgrade_to.clone()])).await;
+        seed_abi(&pool, BUNDLE_IMPLEMENTATION, json!([transfer.clone()])).await;
+        seed_abi(&pool, BUNDLE_PLAIN, json!([approve.clone()])).await;
+
+        // Only the proxy has an implementation in its EIP-1967 slot.
+        let rpc = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({
+                "method": "eth_getStorageAt",
+                "params": [
+                    BUNDLE_PROXY,
+                    "0x360894a13ba1a3210667c828492db98dca3e2076cc3735a920a3ca505d382bbc",
+                    "latest"
+                ],
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "result": format!("0x{:0>64}", BUNDLE_IMPLEMENTATION.trim_start_matches("0x")),
+            })))
+            .with_priority(1)
+            .mount(&rpc)
+            .await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "result": format!("0x{:064x}", 0),
+            })))
+            .mount(&rpc)
+            .await;
+
+        let bundle = |addresses: serde_json::Value| {
+            let app = common::test_router_with_rpc(rpc.uri());
+            async move {
+                app.oneshot(
+                    Request::builder()
+                        .method("POST")
+                        .uri("/api/abis/bundle")
+                        .header("content-type", "application/json")
+                        .body(Body::from(json!({"addresses": addresses}).to_string()))
+                        .unwrap(),
+                )
+                .await
+                .unwrap()
+            }
+        };
+
+        let response = bundle(json!([
+            BUNDLE_PROXY.to_uppercase().replacen("0X", "0x", 1),
+            BUNDLE_PLAIN,
+            BUNDLE_UNVERIFIED,
+            BUNDLE_PLAIN,
+        ]))
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            common::json_body(response).await,
+            json!({
+                BUNDLE_PROXY: [transfer, upgrade_to],
+                BUNDLE_PLAIN: [approve],
+                BUNDLE_UNVERIFIED: null,
+            })
+        );
+
+        let response = bundle(json!([])).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let response = bundle(json!(["0x1234"])).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    });
+}
+
+#[test]
+fn verification_can_be_replaced_removed_and_restored() {
+    const CONTRACT: &str = "0x0000000000000000000000000000000000b0c0de";
+    // Not a real solc build: a cache miss would fail to download it.
+    const COMPILER: &str = "v0.8.24+commit.39640001";
+    const SOURCE: &str =
+        "contract Answer { fallback() external { assembly { mstore(0, 42) return(0, 32) } } }";
+    const RUNTIME: &str = "602a60005260206000f3";
+
+    async fn send(
+        app: &axum::Router,
+        method: &str,
+        uri: String,
+        body: Option<serde_json::Value>,
+    ) -> (StatusCode, serde_json::Value) {
+        let mut request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("authorization", format!("Bearer {}", common::ADMIN_API_KEY));
+        if body.is_some() {
+            request = request.header("content-type", "application/json");
+        }
+        let body = body.map_or_else(Body::empty, |body| Body::from(body.to_string()));
+        let response = app
+            .clone()
+            .oneshot(request.body(body).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        (status, common::json_body(response).await)
+    }
+
+    common::run(async {
+        let pool = common::pool();
+        for table in ["contract_abis", "verification_history"] {
+            sqlx::query(&format!("DELETE FROM {table} WHERE address = $1"))
+                .bind(CONTRACT)
+                .execute(&pool)
+                .await
+                .expect("reset verification");
+        }
+        sqlx::query(
+            "INSERT INTO addresses (address, is_contract, first_seen_block) VALUES ($1, true, 0)
+             ON CONFLICT (address) DO UPDATE SET is_contract = true",
+        )
+        .bind(CONTRACT)
+        .execute(&pool)
+        .await
+        .expect("seed contract address");
+        // A wrong earlier verification.
+        sqlx::query(
+            "INSERT INTO contract_abis (address, abi, contract_name, match_strategy)
+             VALUES ($1, '[]', 'Wrong', 'metadata')",
+        )
+        .bind(CONTRACT)
+        .execute(&pool)
+        .await
+        .expect("seed verification");
+
+        let input = json!({
+            "language": "Solidity",
+            "sources": { "contract.sol": { "content": SOURCE } },
+            "settings": {
+                "optimizer": { "enabled": false, "runs": 200 },
+                "outputSelection": { "*": { "": ["ast"], "*": ["abi", "evm.deployedBytecode"] } },
+            },
+        });
+        let input_hash = format!(
+            "{:#x}",
+            alloy::primitives::keccak256(serde_json::to_string(&input).unwrap().as_bytes())
+        );
+        let output = json!({
+            "contracts": { "contract.sol": { "Answer": {
+                "abi": [{"type": "fallback", "stateMutability": "nonpayable"}],
+                "evm": { "deployedBytecode": { "object": RUNTIME } }
+            } } }
+        });
+        sqlx::query(
+            "INSERT INTO compilation_cache (compiler_version, input_hash, output) VALUES ($1, $2, $3)
+             ON CONFLICT (compiler_version, input_hash) DO UPDATE SET output = EXCLUDED.output, hits = 0",
+        )
+        .bind(COMPILER)
+        .bind(&input_hash)
+        .bind(&output)
+        .execute(&pool)
+        .await
+        .expect("seed compilation cache");
+
+        let rpc = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({"method": "eth_getCode"})))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "result": format!("0x{RUNTIME}"),
+            })))
+            .mount(&rpc)
+            .await;
+        let app = common::test_router_with_rpc(rpc.uri());
+        let verify = |strategies: Option<&[&str]>| {
+            let mut body = json!({
+                "source_code": SOURCE,
+                "contract_name": "Answer",
+                "compiler_version": COMPILER,
+            });
+            if let Some(strategies) = strategies {
+                body["match_strategies"] = json!(strategies);
+            }
+            Some(body)
+        };
+        let verify_uri = format!("/api/contracts/{CONTRACT}/verify");
+
+        // Re-verification must be able to match exactly.
+        let (status, body) = send(
+            &app,
+            "POST",
+            verify_uri.clone(),
+            verify(Some(&["metadata"])),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{body}");
+        assert!(
+            body["error"].as_str().unwrap().contains("exact match"),
+            "{body}"
+        );
+
+        let (status, body) = send(&app, "POST", verify_uri, verify(None)).await;
+        assert_eq!(status, StatusCode::OK, "{body}");
+        assert_eq!(body["match_strategy"], "exact");
+        let (_, contract) = send(&app, "GET", format!("/api/contracts/{CONTRACT}"), None).await;
+        assert_eq!(contract["contract_name"], "Answer");
+
+        let admin = format!("/api/admin/contracts/{CONTRACT}");
+        let (status, entry) = send(
+            &app,
+            "POST",
+            format!("{admin}/unverify"),
+            Some(json!({"reason": "wrong source"})),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK, "{entry}");
+        assert_eq!(entry["action"], "unverified");
+        assert_eq!(entry["reason"], "wrong source");
+        assert_eq!(entry["contract_name"], "Answer");
+        let (_, contract) = send(&app, "GET", format!("/api/contracts/{CONTRACT}"), None).await;
+        assert_eq!(contract["verified"], false);
+        let (status, _) = send(&app, "POST", format!("{admin}/unverify"), None).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        let (status, entry) = send(&app, "POST", format!("{admin}/reverify"), None).await;
+        assert_eq!(status, StatusCode::OK, "{entry}");
+        assert_eq!(entry["action"], "restored");
+        let (_, contract) = send(&app, "GET", format!("/api/contracts/{CONTRACT}"), None).await;
+        assert_eq!(contract["verified"], true);
+        assert_eq!(contract["contract_name"], "Answer");
+        assert_eq!(contract["match_strategy"], "exact");
+        let (status, _) = send(&app, "POST", format!("{admin}/reverify"), None).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let (status, history) =
+            send(&app, "GET", format!("{admin}/verification-history"), None).await;
+        assert_eq!(status, StatusCode::OK);
+        let actions: Vec<&str> = history
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|entry| entry["action"].as_str().unwrap())
+            .collect();
+        assert_eq!(actions, ["restored", "unverified", "replaced"]);
+        assert_eq!(history[2]["contract_name"], "Wrong");
+
+        sqlx::query("DELETE FROM compilation_cache WHERE compiler_version = $1")
+            .bind(COMPILER)
+            .execute(&pool)
+            .await
+            .expect("clear cache");
+    });
+}
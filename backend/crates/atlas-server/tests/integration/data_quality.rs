@@ -0,0 +1,196 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+};
+use serde_json::Value;
+use std::time::Duration;
+use tower::ServiceExt;
+
+use atlas_server::indexer::DataQualityChecker;
+use atlas_server::state_keys::DATA_QUALITY_LAST_BLOCK_KEY;
+
+use crate::common;
+
+// Block range: 17000-17999
+
+const BLOCK: i64 = 17000;
+const TOKEN: &str = "0x170000000000000000000000000000000000000a";
+const OVERDRAWN: &str = "0x1700000000000000000000000000000000000001";
+const SENDER: &str = "0x1700000000000000000000000000000000000002";
+const RECIPIENT: &str = "0x1700000000000000000000000000000000000003";
+const TX_NO_RECEIPT: &str = "0x1700000000000000000000000000000000000000000000000000000000000001";
+
+async fn seed(pool: &sqlx::PgPool) {
+    sqlx::query(
+        "INSERT INTO blocks (number, hash, parent_hash, timestamp, gas_used, gas_limit, transaction_count, indexed_at)
+         VALUES ($1, $2, $3, 1700017000, 0, 30000000, 1, NOW())
+         ON CONFLICT (number) DO NOTHING",
+    )
+    .bind(BLOCK)
+    .bind(format!("0x{:064x}", BLOCK))
+    .bind(format!("0x{:064x}", BLOCK - 1))
+    .execute(pool)
+    .await
+    .expect("seed block");
+
+    // What the indexer stores when a block's receipts could not be fetched.
+    sqlx::query(
+        "INSERT INTO transactions (hash, block_number, block_index, from_address, to_address, value, gas_price, gas_used, input_data, status, timestamp)
+         VALUES ($1, $2, 0, $3, $4, 0, 1000000000, 0, '\\x', false, 1700017000)
+         ON CONFLICT (hash, block_number) DO NOTHING",
+    )
+    .bind(TX_NO_RECEIPT)
+    .bind(BLOCK)
+    .bind(SENDER)
+    .bind(RECIPIENT)
+    .execute(pool)
+    .await
+    .expect("seed transaction");
+
+    sqlx::query(
+        "INSERT INTO erc20_contracts (address, name, symbol, decimals, first_seen_block)
+         VALUES ($1, 'Quality', 'QLT', 18, $2)
+         ON CONFLICT (address) DO NOTHING",
+    )
+    .bind(TOKEN)
+    .bind(BLOCK)
+    .execute(pool)
+    .await
+    .expect("seed token");
+    sqlx::query(
+        "INSERT INTO erc20_transfers (tx_hash, log_index, contract_address, from_address, to_address, value, block_number, timestamp)
+         VALUES ($1, 0, $2, '0x0000000000000000000000000000000000000000', $3, 100, $4, 1700017000)
+         ON CONFLICT (tx_hash, log_index, block_number) DO NOTHING",
+    )
+    .bind(TX_NO_RECEIPT)
+    .bind(TOKEN)
+    .bind(RECIPIENT)
+    .bind(BLOCK)
+    .execute(pool)
+    .await
+    .expect("seed transfer");
+
+    for (address, balance) in [(OVERDRAWN, "-5"), (RECIPIENT, "70")] {
+        sqlx::query(
+            "INSERT INTO erc20_balances (address, contract_address, balance, last_updated_block)
+             VALUES ($1, $2, $3::numeric, $4)
+             ON CONFLICT (address, contract_address) DO UPDATE SET balance = EXCLUDED.balance",
+        )
+        .bind(address)
+        .bind(TOKEN)
+        .bind(balance)
+        .bind(BLOCK)
+        .execute(pool)
+        .await
+        .expect("seed balance");
+    }
+}
+
+async fn report(query: &str) -> Value {
+    let response = common::test_router()
+        .oneshot(
+            Request::builder()
+                .uri(format!("/api/admin/data-quality?{query}"))
+                .header("authorization", format!("Bearer {}", common::ADMIN_API_KEY))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    common::json_body(response).await
+}
+
+fn subjects(body: &Value) -> Vec<String> {
+    body["issues"]["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|issue| issue["subject"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[test]
+fn data_quality_checks_record_and_resolve_issues() {
+    common::run(async {
+        let pool = common::pool();
+        seed(&pool).await;
+        sqlx::query("DELETE FROM indexer_state WHERE key = $1")
+            .bind(DATA_QUALITY_LAST_BLOCK_KEY)
+            .execute(&pool)
+            .await
+            .expect("reset cursor");
+
+        let checker = DataQualityChecker::new(pool.clone(), Duration::from_secs(60), true).unwrap();
+        checker.run_cycle().await.expect("first cycle");
+
+        let negative = format!("{OVERDRAWN}/{TOKEN}");
+        let mismatch = format!("{RECIPIENT}/{TOKEN}");
+        let block = BLOCK.to_string();
+
+        let body = report("kind=negative_balance&limit=100").await;
+        assert!(body["checked_through_block"].as_i64().unwrap() >= BLOCK);
+        assert!(body["open"]["negative_balance"].as_i64().unwrap() >= 1);
+        assert!(subjects(&body).contains(&negative));
+
+        let body = report("kind=missing_receipt&limit=100").await;
+        let issue = body["issues"]["data"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|issue| issue["subject"] == block.as_str())
+            .expect("missing receipt recorded");
+        assert_eq!(issue["details"]["transactions"], 1);
+
+        let body = report("kind=transfer_balance_mismatch&limit=100").await;
+        let issue = body["issues"]["data"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|issue| issue["subject"] == mismatch)
+            .expect("mismatch recorded");
+        assert_eq!(issue["details"]["balance"], "70");
+        assert_eq!(issue["details"]["expected"], "100");
+
+        // Fix the balances; the next cycle resolves both issues.
+        for (address, balance) in [(OVERDRAWN, "0"), (RECIPIENT, "100")] {
+            sqlx::query(
+                "UPDATE erc20_balances SET balance = $3::numeric WHERE address = $1 AND contract_address = $2",
+            )
+            .bind(address)
+            .bind(TOKEN)
+            .bind(balance)
+            .execute(&pool)
+            .await
+            .expect("fix balance");
+        }
+        checker.run_cycle().await.expect("second cycle");
+
+        for (kind, subject) in [
+            ("negative_balance", &negative),
+            ("transfer_balance_mismatch", &mismatch),
+        ] {
+            let open = report(&format!("kind={kind}&limit=100")).await;
+            assert!(!subjects(&open).contains(subject), "{kind} still open");
+            let resolved = report(&format!("kind={kind}&status=resolved&limit=100")).await;
+            assert!(subjects(&resolved).contains(subject), "{kind} not resolved");
+        }
+    });
+}
+
+#[test]
+fn data_quality_rejects_unknown_filters() {
+    common::run(async {
+        let response = common::test_router()
+            .oneshot(
+                Request::builder()
+                    .uri("/api/admin/data-quality?status=closed")
+                    .header("authorization", format!("Bearer {}", common::ADMIN_API_KEY))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    });
+}
@@ -0,0 +1,272 @@
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+    Router,
+};
+use chrono::NaiveDate;
+use tower::ServiceExt;
+
+use crate::common;
+use atlas_server::datasets::DatasetWorker;
+
+// Block range: 27000-27999
+
+const FROM: &str = "0x2700000000000000000000000000000000000001";
+const TO: &str = "0x2700000000000000000000000000000000000002";
+/// 1995-05-05T00:00:00Z, a day no other test has blocks on.
+const DAY_START: i64 = 799_632_000;
+
+fn day() -> NaiveDate {
+    NaiveDate::from_ymd_opt(1995, 5, 5).unwrap()
+}
+
+/// Block 27000 ends the day before, 27001-27003 make up `day()` and 27004
+/// starts the day after.
+async fn seed(pool: &sqlx::PgPool, blocks: &[i64]) {
+    for &number in blocks {
+        let timestamp = match number {
+            27000 => DAY_START - 10,
+            27004 => DAY_START + 86_400,
+            _ => DAY_START + (number - 27000) * 3600,
+        };
+        sqlx::query(
+            "INSERT INTO blocks (number, hash, parent_hash, timestamp, gas_used, gas_limit, transaction_count, indexed_at)
+             VALUES ($1, $2, $3, $4, 21000, 30000000, $5, NOW())
+             ON CONFLICT (number) DO NOTHING",
+        )
+        .bind(number)
+        .bind(format!("0x{:064x}", number))
+        .bind(format!("0x{:064x}", number - 1))
+        .bind(timestamp)
+        .bind(i32::from(number == 27002))
+        .execute(pool)
+        .await
+        .expect("seed block");
+    }
+    sqlx::query(
+        "INSERT INTO transactions (hash, block_number, block_index, from_address, to_address, value, gas_price, gas_used, input_data, status, timestamp)
+         VALUES ($1, 27002, 0, $2, $3, 1000000000000000000000, 1, 21000, ''::bytea, TRUE, $4)
+         ON CONFLICT (hash, block_number) DO NOTHING",
+    )
+    .bind(format!("0x{:064x}", 27002))
+    .bind(FROM)
+    .bind(TO)
+    .bind(DAY_START + 7200)
+    .execute(pool)
+    .await
+    .expect("seed transaction");
+}
+
+async fn get(app: &Router, uri: &str) -> axum::http::Response<Body> {
+    app.clone()
+        .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap()
+}
+
+#[test]
+fn complete_days_are_dumped_and_listed() {
+    common::run(async {
+        let pool = common::pool();
+        sqlx::query("DELETE FROM dataset_dumps WHERE day = $1")
+            .bind(day())
+            .execute(&pool)
+            .await
+            .expect("clear dumps");
+        sqlx::query("DELETE FROM blocks WHERE number = 27004")
+            .execute(&pool)
+            .await
+            .expect("clear next day");
+        seed(&pool, &[27000, 27001, 27002, 27003]).await;
+
+        let dir = tempfile::tempdir().unwrap();
+        let dataset_dir = dir.path().to_str().unwrap().to_string();
+        let worker = DatasetWorker::new(common::pool(), common::database_url(), &dataset_dir);
+
+        // No block of the next day yet, so the day may still be running.
+        assert!(!worker.dump_day(day()).await.unwrap());
+        seed(&pool, &[27004]).await;
+        assert!(worker.dump_day(day()).await.unwrap());
+
+        let app = common::test_router_with_dataset_dir(dataset_dir);
+        let response = get(&app, "/api/datasets").await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let manifest = common::json_body(response).await;
+        assert_eq!(manifest["format"], "csv");
+        let entry = manifest["days"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|entry| entry["day"] == "1995-05-05")
+            .expect("day in manifest")
+            .clone();
+        assert_eq!(entry["from_block"], 27001);
+        assert_eq!(entry["to_block"], 27003);
+        let files = entry["files"].as_array().unwrap();
+        let datasets: Vec<&str> = files
+            .iter()
+            .map(|file| file["dataset"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            datasets,
+            ["blocks", "erc20_transfers", "nft_transfers", "transactions"]
+        );
+        let blocks = &files[0];
+        assert_eq!(blocks["row_count"], 3);
+        assert_eq!(blocks["url"], "/api/datasets/1995-05-05/blocks.csv");
+        assert_eq!(files[3]["row_count"], 1);
+
+        let response = get(&app, "/api/datasets/1995-05-05/blocks.csv").await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers()[header::CONTENT_TYPE],
+            "text/csv; charset=utf-8"
+        );
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let csv = String::from_utf8(body.to_vec()).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(
+            lines[0],
+            "number,hash,parent_hash,timestamp,gas_used,gas_limit,base_fee_per_gas,transaction_count"
+        );
+        assert_eq!(lines.len(), 4);
+        assert!(lines[1].starts_with("27001,"));
+        assert!(lines[3].starts_with("27003,"));
+
+        let response = get(&app, "/api/datasets/1995-05-05/transactions.csv").await;
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert!(String::from_utf8(body.to_vec())
+            .unwrap()
+            .contains(&format!(",{FROM},{TO},1000000000000000000000,")));
+
+        for uri in [
+            "/api/datasets/1995-05-06/blocks.csv",
+            "/api/datasets/1995-05-05/logs.csv",
+            "/api/datasets/..%2F..%2Fetc/passwd",
+        ] {
+            assert_eq!(get(&app, uri).await.status(), StatusCode::NOT_FOUND);
+        }
+    });
+}
+
+#[test]
+fn datasets_are_disabled_without_a_directory() {
+    common::run(async {
+        let response = get(&common::test_router(), "/api/datasets").await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    });
+}
+
+/// 2100-01-01T00:00:00Z, after every other test's blocks.
+const STALLED_DAY_START: i64 = 4_102_444_800;
+
+async fn seed_stalled(pool: &sqlx::PgPool, blocks: &[(i64, i64)]) {
+    for &(number, timestamp) in blocks {
+        sqlx::query(
+            "INSERT INTO blocks (number, hash, parent_hash, timestamp, gas_used, gas_limit, transaction_count, indexed_at)
+             VALUES ($1, $2, $3, $4, 0, 30000000, 0, NOW())
+             ON CONFLICT (number) DO NOTHING",
+        )
+        .bind(number)
+        .bind(format!("0x{:064x}", number))
+        .bind(format!("0x{:064x}", number - 1))
+        .bind(timestamp)
+        .execute(pool)
+        .await
+        .expect("seed block");
+    }
+}
+
+async fn clear_stalled(pool: &sqlx::PgPool) {
+    for sql in [
+        "DELETE FROM dataset_dumps WHERE day >= '2099-12-31'",
+        "DELETE FROM dataset_skipped_days WHERE day >= '2099-12-31'",
+        "DELETE FROM blocks WHERE number BETWEEN 27100 AND 27199",
+    ] {
+        sqlx::query(sql)
+            .execute(pool)
+            .await
+            .expect("clear stalled days");
+    }
+}
+
+async fn dumped_days(pool: &sqlx::PgPool) -> Vec<String> {
+    sqlx::query_scalar(
+        "SELECT DISTINCT day::text FROM dataset_dumps WHERE day >= '2100-01-01' ORDER BY 1",
+    )
+    .fetch_all(pool)
+    .await
+    .expect("dumped days")
+}
+
+#[test]
+fn days_with_a_gap_are_skipped_and_dumped_once_filled() {
+    common::run(async {
+        let pool = common::pool();
+        clear_stalled(&pool).await;
+        // Everything up to 2099-12-31 counts as dumped already.
+        sqlx::query(
+            "INSERT INTO dataset_dumps (day, dataset, from_block, to_block, row_count, file_size)
+             VALUES ('2099-12-31', 'blocks', 27100, 27100, 1, 1)",
+        )
+        .execute(&pool)
+        .await
+        .expect("seed earlier dump");
+        // 2100-01-01 misses block 27102; 2100-01-02 and 2100-01-03 follow.
+        seed_stalled(
+            &pool,
+            &[
+                (27100, STALLED_DAY_START - 10),
+                (27101, STALLED_DAY_START),
+                (27103, STALLED_DAY_START + 7200),
+                (27104, STALLED_DAY_START + 86_400),
+                (27105, STALLED_DAY_START + 2 * 86_400),
+            ],
+        )
+        .await;
+
+        let dir = tempfile::tempdir().unwrap();
+        let worker = DatasetWorker::new(
+            common::pool(),
+            common::database_url(),
+            dir.path().to_str().unwrap(),
+        );
+
+        // The gapped day is skipped rather than holding back the next one.
+        assert!(worker.run_cycle().await.unwrap());
+        let skipped: Vec<String> = sqlx::query_scalar(
+            "SELECT day::text FROM dataset_skipped_days WHERE day >= '2100-01-01'",
+        )
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+        assert_eq!(skipped, ["2100-01-01"]);
+        assert!(worker.run_cycle().await.unwrap());
+        assert_eq!(dumped_days(&pool).await, ["2100-01-02"]);
+
+        // Once gap-fill has indexed the missing block, the day is dumped.
+        seed_stalled(&pool, &[(27102, STALLED_DAY_START + 3600)]).await;
+        assert!(worker.run_cycle().await.unwrap());
+        assert_eq!(dumped_days(&pool).await, ["2100-01-01", "2100-01-02"]);
+        let (from_block, to_block): (i64, i64) = sqlx::query_as(
+            "SELECT from_block, to_block FROM dataset_dumps WHERE day = '2100-01-01' AND dataset = 'blocks'",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!((from_block, to_block), (27101, 27103));
+        let skipped: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM dataset_skipped_days WHERE day >= '2100-01-01'",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(skipped, 0);
+
+        clear_stalled(&pool).await;
+    });
+}
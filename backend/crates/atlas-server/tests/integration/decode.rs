@@ -0,0 +1,124 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+};
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+use crate::common;
+
+// No blocks: contract addresses 0x2100...
+
+const TRANSFER: &str = "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef";
+const TOKEN: &str = "0x2100000000000000000000000000000000000001";
+const FROM: &str = "0x2100000000000000000000000000000000000002";
+const TO: &str = "0x2100000000000000000000000000000000000003";
+
+fn address_topic(address: &str) -> String {
+    format!("0x{:0>64}", address.trim_start_matches("0x"))
+}
+
+fn transfer_log() -> Value {
+    json!({
+        "topics": [TRANSFER, address_topic(FROM), address_topic(TO)],
+        "data": format!("0x{:064x}", 1000),
+    })
+}
+
+async fn decode(body: Value) -> (StatusCode, Option<Value>) {
+    let response = common::test_router()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/api/decode/log")
+                .header("content-type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let status = response.status();
+    let body = if status == StatusCode::OK {
+        Some(common::json_body(response).await)
+    } else {
+        None
+    };
+    (status, body)
+}
+
+#[test]
+fn decodes_with_the_contract_abi_when_an_address_is_given() {
+    common::run(async {
+        let pool = common::pool();
+        let abi = json!([{
+            "type": "event",
+            "name": "Transfer",
+            "anonymous": false,
+            "inputs": [
+                {"name": "src", "type": "address", "indexed": true},
+                {"name": "dst", "type": "address", "indexed": true},
+                {"name": "wad", "type": "uint256", "indexed": false}
+            ]
+        }]);
+        sqlx::query(
+            "INSERT INTO contract_abis (address, abi) VALUES ($1, $2)
+             ON CONFLICT (address) DO UPDATE SET abi = EXCLUDED.abi",
+        )
+        .bind(TOKEN)
+        .bind(&abi)
+        .execute(&pool)
+        .await
+        .expect("seed contract abi");
+
+        let mut log = transfer_log();
+        log["address"] = json!(TOKEN);
+        let (status, body) = decode(log).await;
+        assert_eq!(status, StatusCode::OK);
+        let body = body.unwrap();
+        assert_eq!(body["source"], "abi");
+        assert_eq!(body["signature"], "Transfer(address,address,uint256)");
+        let params = body["params"].as_array().unwrap();
+        assert_eq!(params[0]["name"], "src");
+        assert_eq!(params[0]["value"], FROM);
+        assert_eq!(params[1]["name"], "dst");
+        assert_eq!(params[2]["name"], "wad");
+        assert_eq!(params[2]["indexed"], false);
+        assert_eq!(params[2]["value"], "1000");
+    });
+}
+
+#[test]
+fn falls_back_to_known_event_signatures() {
+    common::run(async {
+        let (status, body) = decode(transfer_log()).await;
+        assert_eq!(status, StatusCode::OK);
+        let body = body.unwrap();
+        assert_eq!(body["source"], "signature");
+        assert_eq!(body["name"], "Transfer");
+        let params = body["params"].as_array().unwrap();
+        assert_eq!(params.len(), 3);
+        assert!(params[0]["name"].is_null());
+        assert_eq!(params[0]["indexed"], true);
+        assert_eq!(params[1]["value"], TO);
+        assert_eq!(params[2]["indexed"], false);
+        assert_eq!(params[2]["value"], "1000");
+    });
+}
+
+#[test]
+fn unknown_event_is_not_found_and_bad_hex_is_rejected() {
+    common::run(async {
+        let (status, _) = decode(json!({
+            "topics": [format!("0x{:064x}", 0x2100)],
+            "data": "0x",
+        }))
+        .await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        let (status, _) = decode(json!({"topics": ["0xzz"], "data": "0x"})).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let (status, _) = decode(json!({"topics": [], "data": "0x"})).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    });
+}
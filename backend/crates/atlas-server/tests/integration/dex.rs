@@ -0,0 +1,181 @@
+use axum::http::StatusCode;
+use serde_json::json;
+use std::time::Duration;
+use wiremock::matchers::method;
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use atlas_server::indexer::{DexPoolResolver, ProcessorRegistry};
+
+use super::common;
+use super::gap_fill::empty_block_response;
+
+// Block range: 21000-21999
+
+const BLOCK: u64 = 21_000;
+const V2_POOL: &str = "0x00000000000000000000000000000000000021aa";
+const V3_POOL: &str = "0x00000000000000000000000000000000000021bb";
+const TOKEN0: &str = "0x0000000000000000000000000000000000002101";
+const TOKEN1: &str = "0x0000000000000000000000000000000000002102";
+const V2_SWAP_TOPIC: &str = "0xd78ad95fa46c994b6551d0da85fc275fe613ce37657fb8d5e3d130840159d822";
+const V3_SWAP_TOPIC: &str = "0xc42079f94a6350d7e6235f29174924f928cc2ac818eb64fed8004e115fbcca67";
+
+fn word(value: i128) -> String {
+    if value < 0 {
+        format!("{}{:032x}", "f".repeat(32), value as u128)
+    } else {
+        format!("{:064x}", value)
+    }
+}
+
+fn swap_log(pool: &str, topic0: &str, words: &[i128], log_index: u64) -> serde_json::Value {
+    json!({
+        "address": pool,
+        "topics": [
+            topic0,
+            format!("0x{:064x}", 0x2111),
+            format!("0x{:064x}", 0x2112),
+        ],
+        "data": format!("0x{}", words.iter().map(|w| word(*w)).collect::<String>()),
+        "blockNumber": format!("0x{:x}", BLOCK),
+        "transactionHash": format!("0x{:064x}", 0x2100),
+        "transactionIndex": "0x0",
+        "blockHash": format!("0x{:064x}", BLOCK),
+        "logIndex": format!("0x{:x}", log_index),
+        "removed": false
+    })
+}
+
+/// The block with one transaction's receipt holding a V2 and a V3 swap.
+fn block_with_swaps() -> serde_json::Value {
+    let mut response = empty_block_response(BLOCK);
+    response[1]["result"] = json!([{
+        "transactionHash": format!("0x{:064x}", 0x2100),
+        "transactionIndex": "0x0",
+        "blockHash": format!("0x{:064x}", BLOCK),
+        "blockNumber": format!("0x{:x}", BLOCK),
+        "from": "0x0000000000000000000000000000000000002111",
+        "to": "0x0000000000000000000000000000000000002112",
+        "cumulativeGasUsed": "0x5208",
+        "gasUsed": "0x5208",
+        "contractAddress": null,
+        "logs": [
+            // 1000 token0 in, 2500 token1 out
+            swap_log(V2_POOL, V2_SWAP_TOPIC, &[1000, 0, 0, 2500], 0),
+            // 42 token0 out, 7 token1 in
+            swap_log(V3_POOL, V3_SWAP_TOPIC, &[-42, 7, 1 << 96, 10, 0], 1),
+        ],
+        "logsBloom": format!("0x{}", "00".repeat(256)),
+        "type": "0x2",
+        "effectiveGasPrice": "0x1",
+        "status": "0x1"
+    }]);
+    response
+}
+
+async fn clear(pool: &sqlx::PgPool) {
+    for sql in [
+        "DELETE FROM blocks WHERE number = 21000",
+        "DELETE FROM dex_swaps WHERE block_number BETWEEN 21000 AND 21999",
+        "DELETE FROM dex_pools",
+    ] {
+        sqlx::query(sql)
+            .execute(pool)
+            .await
+            .expect("clear dex data");
+    }
+}
+
+#[test]
+fn swaps_are_decoded_resolved_and_aggregated() {
+    common::run(async {
+        let pool = common::pool();
+        clear(&pool).await;
+        let processors = ProcessorRegistry::builtin()
+            .build(&["dex_swaps".to_string()])
+            .expect("build processors");
+        common::index_block_via_backfill(&pool, BLOCK, block_with_swaps(), processors).await;
+
+        let swaps: Vec<(String, String, String, String)> = sqlx::query_as(
+            "SELECT pool, protocol, amount0::text, amount1::text FROM dex_swaps
+             WHERE block_number = $1 ORDER BY log_index",
+        )
+        .bind(BLOCK as i64)
+        .fetch_all(&pool)
+        .await
+        .expect("decoded swaps");
+        assert_eq!(
+            swaps,
+            [
+                (
+                    V2_POOL.into(),
+                    "uniswap_v2".into(),
+                    "1000".into(),
+                    "-2500".into()
+                ),
+                (
+                    V3_POOL.into(),
+                    "uniswap_v3".into(),
+                    "-42".into(),
+                    "7".into()
+                ),
+            ]
+        );
+
+        // Pools are read in address order: the V2 pool answers, the V3
+        // address does not implement token0()/token1()
+        let token = |address: &str| format!("0x{:0>64}", address.trim_start_matches("0x"));
+        let node = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+                {"jsonrpc": "2.0", "id": 0, "result": token(TOKEN0)},
+                {"jsonrpc": "2.0", "id": 1, "result": token(TOKEN1)},
+                {"jsonrpc": "2.0", "id": 2, "error": {"code": 3, "message": "execution reverted"}},
+                {"jsonrpc": "2.0", "id": 3, "result": "0x"},
+            ])))
+            .mount(&node)
+            .await;
+        let resolver =
+            DexPoolResolver::new(pool.clone(), &node.uri(), Duration::from_secs(1)).unwrap();
+        assert_eq!(resolver.resolve_pools().await.expect("resolve pools"), 2);
+
+        let (status, page) = common::get_json(&format!("/api/dex/swaps?token={TOKEN1}")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(page["data"].as_array().unwrap().len(), 1);
+        assert_eq!(page["data"][0]["pool"], V2_POOL);
+        assert_eq!(page["data"][0]["token0"], TOKEN0);
+        assert_eq!(page["data"][0]["amount1"], "-2500");
+
+        let (_, page) = common::get_json(&format!("/api/dex/swaps?pool={V3_POOL}")).await;
+        assert_eq!(page["data"][0]["protocol"], "uniswap_v3");
+        assert!(page["data"][0]["token0"].is_null());
+
+        let (status, stats) =
+            common::get_json("/api/dex/pairs?from_block=21000&to_block=21999").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(
+            stats["pairs"],
+            json!([{
+                "token0": TOKEN0,
+                "token1": TOKEN1,
+                "token0_symbol": null,
+                "token1_symbol": null,
+                "token0_decimals": null,
+                "token1_decimals": null,
+                "pools": 1,
+                "swaps": 1,
+                "volume0": "1000",
+                "volume1": "2500"
+            }])
+        );
+
+        clear(&pool).await;
+    });
+}
+
+#[test]
+fn swaps_reject_invalid_filters() {
+    common::run(async {
+        let (status, _) = common::get_json("/api/dex/swaps?pool=0x1234").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    });
+}
@@ -0,0 +1,228 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+};
+use serde_json::{json, Value};
+use tower::ServiceExt;
+use wiremock::matchers::{body_partial_json, method};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use crate::common;
+
+// Block range: 16000-16999
+//
+// Every supported module/action is compared against a golden response in
+// tests/golden/etherscan. Run with ATLAS_UPDATE_GOLDEN=1 to rewrite them after
+// an intentional format change, and review the diff against Etherscan.
+
+const GOLDEN_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/etherscan");
+/// Fields that depend on the rest of the shared database (the indexed head).
+const MASKED_FIELDS: &[&str] = &["confirmations"];
+
+const BLOCK: i64 = 16000;
+const TIMESTAMP: i64 = 1_700_016_000;
+const SENDER: &str = "0x1600000000000000000000000000000000000001";
+const RECIPIENT: &str = "0x1600000000000000000000000000000000000002";
+const IDLE: &str = "0x1600000000000000000000000000000000000003";
+const TOKEN: &str = "0x160000000000000000000000000000000000000a";
+const VERIFIED: &str = "0x160000000000000000000000000000000000000b";
+const UNVERIFIED: &str = "0x160000000000000000000000000000000000000c";
+const TX_OK: &str = "0x1600000000000000000000000000000000000000000000000000000000000001";
+const TX_FAILED: &str = "0x1600000000000000000000000000000000000000000000000000000000000002";
+const TX_UNKNOWN: &str = "0x16000000000000000000000000000000000000000000000000000000000000ff";
+
+async fn seed(pool: &sqlx::PgPool) {
+    sqlx::query(
+        "INSERT INTO blocks (number, hash, parent_hash, timestamp, gas_used, gas_limit, transaction_count, indexed_at)
+         VALUES ($1, $2, $3, $4, 42000, 30000000, 2, NOW())
+         ON CONFLICT (number) DO NOTHING",
+    )
+    .bind(BLOCK)
+    .bind(format!("0x{:064x}", BLOCK))
+    .bind(format!("0x{:064x}", BLOCK - 1))
+    .bind(TIMESTAMP)
+    .execute(pool)
+    .await
+    .expect("seed block");
+
+    for (index, (hash, status)) in [(TX_OK, true), (TX_FAILED, false)].into_iter().enumerate() {
+        sqlx::query(
+            "INSERT INTO transactions (hash, block_number, block_index, from_address, to_address, value, gas_price, gas_used, input_data, status, timestamp)
+             VALUES ($1, $2, $3, $4, $5, 1000000000000000000, 1000000000, 21000, $6, $7, $8)
+             ON CONFLICT (hash, block_number) DO NOTHING",
+        )
+        .bind(hash)
+        .bind(BLOCK)
+        .bind(index as i32)
+        .bind(SENDER)
+        .bind(RECIPIENT)
+        .bind(vec![0xa9u8, 0x05, 0x9c, 0xbb])
+        .bind(status)
+        .bind(TIMESTAMP)
+        .execute(pool)
+        .await
+        .expect("seed transaction");
+    }
+
+    sqlx::query(
+        "INSERT INTO erc20_contracts (address, name, symbol, decimals, first_seen_block)
+         VALUES ($1, 'Golden', 'GLD', 6, $2)
+         ON CONFLICT (address) DO NOTHING",
+    )
+    .bind(TOKEN)
+    .bind(BLOCK)
+    .execute(pool)
+    .await
+    .expect("seed token");
+    sqlx::query(
+        "INSERT INTO erc20_transfers (tx_hash, log_index, contract_address, from_address, to_address, value, block_number, timestamp)
+         VALUES ($1, 3, $2, $3, $4, 2500000, $5, $6)
+         ON CONFLICT (tx_hash, log_index, block_number) DO NOTHING",
+    )
+    .bind(TX_OK)
+    .bind(TOKEN)
+    .bind(SENDER)
+    .bind(RECIPIENT)
+    .bind(BLOCK)
+    .bind(TIMESTAMP)
+    .execute(pool)
+    .await
+    .expect("seed transfer");
+    sqlx::query(
+        "INSERT INTO erc20_balances (address, contract_address, balance, last_updated_block)
+         VALUES ($1, $2, 2500000, $3)
+         ON CONFLICT (address, contract_address) DO NOTHING",
+    )
+    .bind(RECIPIENT)
+    .bind(TOKEN)
+    .bind(BLOCK)
+    .execute(pool)
+    .await
+    .expect("seed balance");
+
+    sqlx::query(
+        "INSERT INTO contract_abis (address, abi, source_code, compiler_version, optimization_used, runs)
+         VALUES ($1, $2, 'contract Golden {}', 'v0.8.24+commit.e11b9ed9', true, 1000)
+         ON CONFLICT (address) DO NOTHING",
+    )
+    .bind(VERIFIED)
+    .bind(json!([{"type": "function", "name": "ping", "inputs": [], "outputs": []}]))
+    .execute(pool)
+    .await
+    .expect("seed abi");
+    sqlx::query(
+        "INSERT INTO addresses (address, is_contract, first_seen_block, deployer, creation_block, creation_tx_hash)
+         VALUES ($1, true, $2, $3, $2, $4)
+         ON CONFLICT (address) DO NOTHING",
+    )
+    .bind(VERIFIED)
+    .bind(BLOCK)
+    .bind(SENDER)
+    .bind(TX_OK)
+    .execute(pool)
+    .await
+    .expect("seed contract creation");
+}
+
+async fn mock_rpc() -> MockServer {
+    let server = MockServer::start().await;
+    for (rpc_method, result) in [
+        ("eth_getBalance", "0xde0b6b3a7640000"),
+        ("eth_blockNumber", "0x3e80"),
+    ] {
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({"method": rpc_method})))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "result": result,
+            })))
+            .mount(&server)
+            .await;
+    }
+    server
+}
+
+fn mask(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, field) in map.iter_mut() {
+                if MASKED_FIELDS.contains(&key.as_str()) {
+                    *field = json!("<masked>");
+                } else {
+                    mask(field);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(mask),
+        _ => {}
+    }
+}
+
+fn assert_golden(name: &str, mut body: Value) {
+    mask(&mut body);
+    let path = format!("{GOLDEN_DIR}/{name}.json");
+    if std::env::var_os("ATLAS_UPDATE_GOLDEN").is_some() {
+        let pretty = serde_json::to_string_pretty(&body).unwrap();
+        std::fs::write(&path, pretty + "\n").expect("write golden file");
+        return;
+    }
+    let golden = std::fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("{path}: {e} (run with ATLAS_UPDATE_GOLDEN=1 to create it)"));
+    let expected: Value = serde_json::from_str(&golden).expect("golden file is JSON");
+    assert_eq!(body, expected, "response drifted from {path}");
+}
+
+#[test]
+fn etherscan_responses_match_golden_files() {
+    common::run(async {
+        let pool = common::pool();
+        seed(&pool).await;
+        let rpc = mock_rpc().await;
+        let app = common::test_router_with_rpc(rpc.uri());
+
+        let cases = [
+            ("account_balance", format!("module=account&action=balance&address={SENDER}")),
+            ("account_balance_invalid_address", "module=account&action=balance&address=0x1234".to_string()),
+            ("account_balancemulti", format!("module=account&action=balancemulti&address={SENDER},{RECIPIENT}")),
+            ("account_txlist", format!("module=account&action=txlist&address={SENDER}&sort=asc")),
+            ("account_txlist_empty", format!("module=account&action=txlist&address={IDLE}")),
+            ("account_txlist_result_window", format!("module=account&action=txlist&address={SENDER}&page=1001&offset=10")),
+            ("account_txlistinternal", format!("module=account&action=txlistinternal&address={SENDER}")),
+            ("account_tokentx", format!("module=account&action=tokentx&address={SENDER}")),
+            ("account_tokentx_empty", format!("module=account&action=tokentx&address={IDLE}")),
+            ("account_tokenbalance", format!("module=account&action=tokenbalance&address={RECIPIENT}&contractaddress={TOKEN}")),
+            ("contract_getabi", format!("module=contract&action=getabi&address={VERIFIED}")),
+            ("contract_getabi_unverified", format!("module=contract&action=getabi&address={UNVERIFIED}")),
+            ("contract_getsourcecode", format!("module=contract&action=getsourcecode&address={VERIFIED}")),
+            ("contract_getsourcecode_unverified", format!("module=contract&action=getsourcecode&address={UNVERIFIED}")),
+            ("contract_getcontractcreation", format!("module=contract&action=getcontractcreation&contractaddresses={VERIFIED},{UNVERIFIED}")),
+            ("contract_getcontractcreation_unknown", format!("module=contract&action=getcontractcreation&contractaddresses={UNVERIFIED}")),
+            ("contract_getcontractcreation_too_many", format!("module=contract&action=getcontractcreation&contractaddresses={}", [IDLE; 6].join(","))),
+            ("transaction_gettxreceiptstatus", format!("module=transaction&action=gettxreceiptstatus&txhash={TX_FAILED}")),
+            ("transaction_gettxreceiptstatus_unknown", format!("module=transaction&action=gettxreceiptstatus&txhash={TX_UNKNOWN}")),
+            ("block_getblockreward", format!("module=block&action=getblockreward&blockno={BLOCK}")),
+            ("block_getblockreward_unknown", "module=block&action=getblockreward&blockno=16999".to_string()),
+            ("proxy_eth_blockNumber", "module=proxy&action=eth_blockNumber".to_string()),
+            ("proxy_eth_getTransactionByBlockNumberAndIndex", "module=proxy&action=eth_getTransactionByBlockNumberAndIndex&tag=0x3e80&index=0x0".to_string()),
+            ("proxy_invalid_tag", "module=proxy&action=eth_getTransactionByBlockNumberAndIndex&tag=latest&index=0x0".to_string()),
+            ("unknown_module", "module=stats&action=ethprice".to_string()),
+            ("unknown_action", "module=account&action=txlistall".to_string()),
+        ];
+
+        for (name, query) in cases {
+            let response = app
+                .clone()
+                .oneshot(
+                    Request::builder()
+                        .uri(format!("/api?{query}"))
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK, "{name}");
+            assert_golden(name, common::json_body(response).await);
+        }
+    });
+}
@@ -0,0 +1,184 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    Router,
+};
+use std::time::Duration;
+use tower::ServiceExt;
+
+use crate::common;
+use atlas_server::export::ExportWorker;
+
+// Block range: 11000-11999
+
+const HOLDER: &str = "0xb000000000000000000000000000000000000001";
+const OTHER: &str = "0xb000000000000000000000000000000000000002";
+const TOKEN: &str = "0xb000000000000000000000000000000000000003";
+
+async fn seed_transfers(pool: &sqlx::PgPool) {
+    // Two transfers involving HOLDER (one in range) and one that does not.
+    for (block, log_index, from, to, value) in [
+        (11000i64, 0i32, HOLDER, OTHER, "1000000000000000000000"),
+        (11001, 0, OTHER, HOLDER, "5"),
+        (11001, 1, OTHER, TOKEN, "7"),
+        (11002, 0, OTHER, HOLDER, "9"),
+    ] {
+        sqlx::query(
+            "INSERT INTO erc20_transfers (tx_hash, log_index, contract_address, from_address, to_address, value, block_number, timestamp)
+             VALUES ($1, $2, $3, $4, $5, $6::numeric, $7, $8)
+             ON CONFLICT (tx_hash, log_index, block_number) DO NOTHING",
+        )
+        .bind(format!("0x{:064x}", block))
+        .bind(log_index)
+        .bind(if to == TOKEN { OTHER } else { TOKEN })
+        .bind(from)
+        .bind(to)
+        .bind(value)
+        .bind(block)
+        .bind(1_700_011_000i64 + block - 11000)
+        .execute(pool)
+        .await
+        .expect("seed transfer");
+    }
+}
+
+async fn send(app: &Router, request: Request<Body>) -> (StatusCode, axum::body::Bytes) {
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, body)
+}
+
+fn post_export(body: serde_json::Value) -> Request<Body> {
+    Request::builder()
+        .method("POST")
+        .uri("/api/export")
+        .header("content-type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
+
+fn get(uri: &str) -> Request<Body> {
+    Request::builder().uri(uri).body(Body::empty()).unwrap()
+}
+
+#[test]
+fn export_jobs_write_downloadable_files() {
+    common::run(async {
+        let pool = common::pool();
+        seed_transfers(&pool).await;
+        let dir = tempfile::tempdir().unwrap();
+        let export_dir = dir.path().to_str().unwrap().to_string();
+        let app = common::test_router_with_export_dir(export_dir.clone());
+
+        let mut ids = Vec::new();
+        for format in ["csv", "jsonl"] {
+            let (status, body) = send(
+                &app,
+                post_export(serde_json::json!({
+                    "entity": "erc20_transfers",
+                    "format": format,
+                    "address": HOLDER.to_uppercase().replacen("0X", "0x", 1),
+                    "from_block": 11000,
+                    "to_block": 11001,
+                })),
+            )
+            .await;
+            assert_eq!(status, StatusCode::ACCEPTED);
+            let job: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            assert_eq!(job["status"], "queued");
+            assert_eq!(job["address"], HOLDER);
+            assert!(job["download_url"].is_null());
+            ids.push(job["id"].as_str().unwrap().to_string());
+        }
+
+        let worker = ExportWorker::new(
+            common::pool(),
+            common::database_url(),
+            &export_dir,
+            Duration::from_secs(3600),
+        )
+        .unwrap();
+        while worker.run_cycle().await.unwrap() {}
+
+        let (status, body) = send(&app, get(&format!("/api/export/{}", ids[0]))).await;
+        assert_eq!(status, StatusCode::OK);
+        let job: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(job["status"], "completed");
+        assert_eq!(job["row_count"], 2);
+        assert_eq!(
+            job["download_url"],
+            format!("/api/export/{}/download", ids[0])
+        );
+
+        let (status, csv) = send(&app, get(&format!("/api/export/{}/download", ids[0]))).await;
+        assert_eq!(status, StatusCode::OK);
+        let csv = String::from_utf8(csv.to_vec()).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(
+            lines[0],
+            "tx_hash,log_index,contract_address,from_address,to_address,value,block_number,timestamp"
+        );
+        assert_eq!(lines.len(), 3);
+        assert!(lines[1].ends_with(&format!(
+            "{HOLDER},{OTHER},1000000000000000000000,11000,1700011000"
+        )));
+        assert!(lines[2].contains(",11001,"));
+
+        let (status, jsonl) = send(&app, get(&format!("/api/export/{}/download", ids[1]))).await;
+        assert_eq!(status, StatusCode::OK);
+        let rows: Vec<serde_json::Value> = String::from_utf8(jsonl.to_vec())
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0]["value"], "1000000000000000000000");
+        assert_eq!(rows[0]["block_number"], 11000);
+        assert_eq!(rows[1]["to_address"], HOLDER);
+
+        // Expired exports are deleted with their files.
+        sqlx::query(
+            "UPDATE export_jobs SET expires_at = NOW() - INTERVAL '1 second' WHERE id = $1",
+        )
+        .bind(&ids[0])
+        .execute(&pool)
+        .await
+        .unwrap();
+        worker.run_cycle().await.unwrap();
+        let (status, _) = send(&app, get(&format!("/api/export/{}", ids[0]))).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert!(!dir.path().join(format!("{}.csv", ids[0])).exists());
+    });
+}
+
+#[test]
+fn export_requests_are_validated() {
+    common::run(async {
+        let dir = tempfile::tempdir().unwrap();
+        let app = common::test_router_with_export_dir(dir.path().to_str().unwrap().to_string());
+
+        for body in [
+            serde_json::json!({"entity": "balances"}),
+            serde_json::json!({"entity": "logs", "format": "xml"}),
+            serde_json::json!({"entity": "logs", "address": "0x1234"}),
+            serde_json::json!({"entity": "logs", "from_block": 10, "to_block": 9}),
+        ] {
+            let (status, _) = send(&app, post_export(body)).await;
+            assert_eq!(status, StatusCode::BAD_REQUEST);
+        }
+
+        let (status, _) = send(&app, get("/api/export/unknown")).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        // Routes do not exist when exports are disabled.
+        let (status, _) = send(
+            &common::test_router(),
+            post_export(serde_json::json!({"entity": "logs"})),
+        )
+        .await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    });
+}
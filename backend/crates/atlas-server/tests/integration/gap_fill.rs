@@ -1,5 +1,10 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+};
 use std::sync::{LazyLock, Mutex};
 use tokio::sync::broadcast;
+use tower::ServiceExt;
 use wiremock::matchers::method;
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
@@ -14,7 +19,7 @@ static SERIALIZER: LazyLock<Mutex<()>> = LazyLock::new(|| Mutex::new(()));
 
 /// Minimal valid JSON-RPC batch response for a block with no transactions.
 /// IDs match what fetch_blocks_batch sends: block=i*2, receipts=i*2+1 (i=0).
-fn empty_block_response(block_number: u64) -> serde_json::Value {
+pub fn empty_block_response(block_number: u64) -> serde_json::Value {
     serde_json::json!([
         {
             "jsonrpc": "2.0",
@@ -84,10 +89,27 @@ fn make_worker(database_url: &str, rpc_url: &str) -> GapFillWorker {
 }
 
 fn make_worker_with_metrics(database_url: &str, rpc_url: &str, metrics: Metrics) -> GapFillWorker {
+    build_worker(database_url, rpc_url, metrics, false)
+}
+
+fn build_worker(
+    database_url: &str,
+    rpc_url: &str,
+    metrics: Metrics,
+    raw_archive_enabled: bool,
+) -> GapFillWorker {
     let pool = common::pool();
     let (tx, _) = broadcast::channel(16);
-    GapFillWorker::new(pool, database_url, rpc_url, 10, tx, metrics)
-        .expect("worker construction should succeed")
+    GapFillWorker::new(
+        pool,
+        database_url,
+        rpc_url,
+        10,
+        tx,
+        metrics,
+        raw_archive_enabled,
+    )
+    .expect("worker construction should succeed")
 }
 
 fn read_gauge(body: &str, name: &str) -> Option<f64> {
@@ -192,6 +214,57 @@ fn gap_fill_updates_missing_blocks_metric_after_recovery() {
     });
 }
 
+#[test]
+fn gap_fill_archives_raw_block_when_enabled() {
+    const BLOCK: u64 = 990_005;
+    let _guard = SERIALIZER.lock().unwrap();
+
+    common::run(async {
+        let pool = common::pool();
+        let database_url = common::database_url();
+        reset_failed_blocks(&pool, BLOCK).await;
+        sqlx::query("DELETE FROM raw_block_archive WHERE block_number = $1")
+            .bind(BLOCK as i64)
+            .execute(&pool)
+            .await
+            .expect("delete archived block");
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(empty_block_response(BLOCK)))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        sqlx::query(
+            "INSERT INTO failed_blocks (block_number, error_message, retry_count, last_failed_at)
+             VALUES ($1, 'test error', 0, NOW() - INTERVAL '1 hour')",
+        )
+        .bind(BLOCK as i64)
+        .execute(&pool)
+        .await
+        .expect("insert test row");
+
+        let worker = build_worker(database_url, &mock_server.uri(), Metrics::new(), true);
+        let (_, recovered) = worker.process_batch().await.expect("process_batch");
+        assert_eq!(recovered, 1, "expected 1 block recovered");
+
+        let (block_hash, payload): (String, Vec<u8>) = sqlx::query_as(
+            "SELECT block_hash, payload FROM raw_block_archive WHERE block_number = $1",
+        )
+        .bind(BLOCK as i64)
+        .fetch_one(&pool)
+        .await
+        .expect("archived block");
+        assert_eq!(block_hash, format!("0x{:064x}", BLOCK));
+
+        let payload: serde_json::Value = serde_json::from_slice(&payload).unwrap();
+        let response = empty_block_response(BLOCK);
+        assert_eq!(payload["block"], response[0]["result"]);
+        assert_eq!(payload["receipts"], response[1]["result"]);
+    });
+}
+
 // ---------------------------------------------------------------------------
 // Test 2: failure path — RPC returns a block-level error
 // ---------------------------------------------------------------------------
@@ -279,3 +352,356 @@ fn gap_fill_skips_recently_failed_block() {
         // mock_server Drop verifies expect(0) was satisfied
     });
 }
+
+// ---------------------------------------------------------------------------
+// Test 4: replay — writing a block again does not re-apply its deltas
+// ---------------------------------------------------------------------------
+
+const REPLAY_SENDER: &str = "0x9900000000000000000000000000000000000001";
+const REPLAY_TOKEN: &str = "0x9900000000000000000000000000000000000002";
+const REPLAY_RECIPIENT: &str = "0x9900000000000000000000000000000000000003";
+
+/// Block with one transaction from REPLAY_SENDER to REPLAY_TOKEN whose receipt
+/// transfers 1000 tokens from REPLAY_SENDER to REPLAY_RECIPIENT.
+fn transfer_block_response(block_number: u64) -> serde_json::Value {
+    let mut response = empty_block_response(block_number);
+    let block_hash = format!("0x{:064x}", block_number);
+    let tx_hash = format!("0x{:064x}", block_number + 1);
+    let topic = |address: &str| format!("0x000000000000000000000000{}", &address[2..]);
+
+    response[0]["result"]["transactions"] = serde_json::json!([{
+        "hash": tx_hash,
+        "nonce": "0x0",
+        "blockHash": block_hash,
+        "blockNumber": format!("0x{:x}", block_number),
+        "transactionIndex": "0x0",
+        "from": REPLAY_SENDER,
+        "to": REPLAY_TOKEN,
+        "value": "0x0",
+        "gasPrice": "0x1",
+        "gas": "0x10000",
+        "input": "0x",
+        "v": "0x1b",
+        "r": "0x1",
+        "s": "0x1",
+        "type": "0x0"
+    }]);
+    response[1]["result"] = serde_json::json!([{
+        "transactionHash": tx_hash,
+        "transactionIndex": "0x0",
+        "blockHash": block_hash,
+        "blockNumber": format!("0x{:x}", block_number),
+        "from": REPLAY_SENDER,
+        "to": REPLAY_TOKEN,
+        "cumulativeGasUsed": "0x5208",
+        "gasUsed": "0x5208",
+        "contractAddress": null,
+        "logs": [{
+            "address": REPLAY_TOKEN,
+            "topics": [
+                "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef",
+                topic(REPLAY_SENDER),
+                topic(REPLAY_RECIPIENT)
+            ],
+            "data": "0x00000000000000000000000000000000000000000000000000000000000003e8",
+            "blockNumber": format!("0x{:x}", block_number),
+            "transactionHash": tx_hash,
+            "transactionIndex": "0x0",
+            "blockHash": block_hash,
+            "logIndex": "0x0",
+            "removed": false
+        }],
+        "logsBloom": response[0]["result"]["logsBloom"],
+        "type": "0x0",
+        "effectiveGasPrice": "0x1",
+        "status": "0x1"
+    }]);
+    response
+}
+
+#[test]
+fn gap_fill_replaying_block_does_not_reapply_deltas() {
+    const BLOCK: u64 = 990_010;
+    let _guard = SERIALIZER.lock().unwrap();
+
+    common::run(async {
+        let pool = common::pool();
+        let database_url = common::database_url();
+        reset_failed_blocks(&pool, BLOCK).await;
+        for sql in [
+            "DELETE FROM processed_blocks WHERE number = $1",
+            "DELETE FROM transactions WHERE block_number = $1",
+            "DELETE FROM tx_hash_lookup WHERE block_number = $1",
+            "DELETE FROM event_logs WHERE block_number = $1",
+            "DELETE FROM erc20_transfers WHERE block_number = $1",
+        ] {
+            sqlx::query(sql)
+                .bind(BLOCK as i64)
+                .execute(&pool)
+                .await
+                .expect("clear block rows");
+        }
+        sqlx::query("DELETE FROM addresses WHERE address = ANY($1)")
+            .bind(vec![REPLAY_SENDER, REPLAY_TOKEN, REPLAY_RECIPIENT])
+            .execute(&pool)
+            .await
+            .expect("clear addresses");
+        sqlx::query("DELETE FROM erc20_balances WHERE contract_address = $1")
+            .bind(REPLAY_TOKEN)
+            .execute(&pool)
+            .await
+            .expect("clear balances");
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(transfer_block_response(BLOCK)))
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+
+        let worker = make_worker(database_url, &mock_server.uri());
+        for _ in 0..2 {
+            sqlx::query(
+                "INSERT INTO failed_blocks (block_number, error_message, retry_count, last_failed_at)
+                 VALUES ($1, 'test error', 0, NOW() - INTERVAL '1 hour')",
+            )
+            .bind(BLOCK as i64)
+            .execute(&pool)
+            .await
+            .expect("insert test row");
+
+            let (_, recovered) = worker.process_batch().await.expect("process_batch");
+            assert_eq!(recovered, 1, "expected 1 block recovered");
+        }
+
+        let (tx_count,): (i32,) =
+            sqlx::query_as("SELECT tx_count FROM addresses WHERE address = $1")
+                .bind(REPLAY_SENDER)
+                .fetch_one(&pool)
+                .await
+                .expect("sender address");
+        assert_eq!(tx_count, 1, "tx_count must count the replayed block once");
+
+        let (balance,): (String,) = sqlx::query_as(
+            "SELECT balance::text FROM erc20_balances
+             WHERE address = $1 AND contract_address = $2",
+        )
+        .bind(REPLAY_RECIPIENT)
+        .bind(REPLAY_TOKEN)
+        .fetch_one(&pool)
+        .await
+        .expect("recipient balance");
+        assert_eq!(
+            balance, "1000",
+            "balance must count the replayed block once"
+        );
+    });
+}
+
+#[test]
+fn gap_fill_rewritten_block_replaces_deltas() {
+    const BLOCK: u64 = 990_030;
+    let _guard = SERIALIZER.lock().unwrap();
+
+    common::run(async {
+        let pool = common::pool();
+        let database_url = common::database_url();
+        reset_failed_blocks(&pool, BLOCK).await;
+        for sql in [
+            "DELETE FROM processed_blocks WHERE number = $1",
+            "DELETE FROM transactions WHERE block_number = $1",
+            "DELETE FROM tx_hash_lookup WHERE block_number = $1",
+            "DELETE FROM event_logs WHERE block_number = $1",
+            "DELETE FROM erc20_transfers WHERE block_number = $1",
+        ] {
+            sqlx::query(sql)
+                .bind(BLOCK as i64)
+                .execute(&pool)
+                .await
+                .expect("clear block rows");
+        }
+        sqlx::query("DELETE FROM addresses WHERE address = ANY($1)")
+            .bind(vec![REPLAY_SENDER, REPLAY_TOKEN, REPLAY_RECIPIENT])
+            .execute(&pool)
+            .await
+            .expect("clear addresses");
+        sqlx::query("DELETE FROM erc20_balances WHERE contract_address = $1")
+            .bind(REPLAY_TOKEN)
+            .execute(&pool)
+            .await
+            .expect("clear balances");
+
+        // The same block number three times: as first indexed, rewritten
+        // with another hash moving 2000 tokens instead of 1000, and
+        // rewritten again as an empty block.
+        let mut doubled = transfer_block_response(BLOCK);
+        doubled[0]["result"]["hash"] = serde_json::json!(format!("0x{:064x}", 0xbeef));
+        doubled[1]["result"][0]["logs"][0]["data"] = serde_json::json!(format!("0x{:064x}", 2000));
+        let mut emptied = empty_block_response(BLOCK);
+        emptied[0]["result"]["hash"] = serde_json::json!(format!("0x{:064x}", 0xdead));
+
+        let tx_count = |address: &'static str| {
+            let pool = pool.clone();
+            async move {
+                sqlx::query_scalar::<_, i32>("SELECT tx_count FROM addresses WHERE address = $1")
+                    .bind(address)
+                    .fetch_one(&pool)
+                    .await
+                    .expect("address row")
+            }
+        };
+        let balance = |address: &'static str| {
+            let pool = pool.clone();
+            async move {
+                sqlx::query_scalar::<_, String>(
+                    "SELECT balance::text FROM erc20_balances
+                     WHERE address = $1 AND contract_address = $2",
+                )
+                .bind(address)
+                .bind(REPLAY_TOKEN)
+                .fetch_optional(&pool)
+                .await
+                .expect("balance row")
+            }
+        };
+
+        for (response, sender_txs, recipient_balance) in [
+            (transfer_block_response(BLOCK), 1, Some("1000")),
+            (doubled, 1, Some("2000")),
+            (emptied, 0, None),
+        ] {
+            let mock_server = MockServer::start().await;
+            Mock::given(method("POST"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(response))
+                .mount(&mock_server)
+                .await;
+            sqlx::query(
+                "INSERT INTO failed_blocks (block_number, error_message, retry_count, last_failed_at)
+                 VALUES ($1, 'test error', 0, NOW() - INTERVAL '1 hour')",
+            )
+            .bind(BLOCK as i64)
+            .execute(&pool)
+            .await
+            .expect("insert test row");
+            let worker = make_worker(database_url, &mock_server.uri());
+            let (_, recovered) = worker.process_batch().await.expect("process_batch");
+            assert_eq!(recovered, 1);
+
+            assert_eq!(tx_count(REPLAY_SENDER).await, sender_txs);
+            assert_eq!(tx_count(REPLAY_TOKEN).await, sender_txs);
+            assert_eq!(
+                balance(REPLAY_RECIPIENT).await.as_deref(),
+                recipient_balance
+            );
+            let transfers: i64 =
+                sqlx::query_scalar("SELECT COUNT(*) FROM erc20_transfers WHERE block_number = $1")
+                    .bind(BLOCK as i64)
+                    .fetch_one(&pool)
+                    .await
+                    .expect("transfer count");
+            assert_eq!(transfers, i64::from(recipient_balance.is_some()));
+        }
+    });
+}
+
+#[test]
+fn gap_fill_rewritten_block_moves_tx_hash_lookup() {
+    const BLOCK: u64 = 990_020;
+    let _guard = SERIALIZER.lock().unwrap();
+    let tx_hash = format!("0x{:064x}", BLOCK + 1);
+
+    common::run(async {
+        let pool = common::pool();
+        let database_url = common::database_url();
+        reset_failed_blocks(&pool, BLOCK).await;
+        for sql in [
+            "DELETE FROM processed_blocks WHERE number IN ($1, $1 + 1)",
+            "DELETE FROM blocks WHERE number IN ($1, $1 + 1)",
+            "DELETE FROM transactions WHERE block_number IN ($1, $1 + 1)",
+            "DELETE FROM tx_hash_lookup WHERE block_number IN ($1, $1 + 1)",
+            "DELETE FROM event_logs WHERE block_number IN ($1, $1 + 1)",
+            "DELETE FROM erc20_transfers WHERE block_number IN ($1, $1 + 1)",
+        ] {
+            sqlx::query(sql)
+                .bind(BLOCK as i64)
+                .execute(&pool)
+                .await
+                .expect("clear block rows");
+        }
+
+        // The transaction is first indexed in BLOCK; a reorg then replaces
+        // BLOCK with an empty block and includes the transaction in BLOCK + 1.
+        let mut reorged = empty_block_response(BLOCK);
+        reorged[0]["result"]["hash"] = serde_json::json!(format!("0x{:064x}", 0xdead));
+        let moved = serde_json::from_str(
+            &transfer_block_response(BLOCK)
+                .to_string()
+                .replace(
+                    &format!("0x{:064x}", BLOCK),
+                    &format!("0x{:064x}", BLOCK + 2),
+                )
+                .replace(
+                    &format!("\"0x{:x}\"", BLOCK),
+                    &format!("\"0x{:x}\"", BLOCK + 1),
+                ),
+        )
+        .unwrap();
+
+        let get_transaction = || async {
+            let response = common::test_router()
+                .oneshot(
+                    Request::builder()
+                        .uri(format!("/api/transactions/{tx_hash}"))
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            let status = response.status();
+            (status, common::json_body(response).await)
+        };
+
+        // Block to write, its RPC response, and the block the API then serves
+        // the transaction from (none once it was reorged out).
+        for (block, response, served_from) in [
+            (BLOCK, transfer_block_response(BLOCK), Some(BLOCK)),
+            (BLOCK, reorged, None),
+            (BLOCK + 1, moved, Some(BLOCK + 1)),
+        ] {
+            let mock_server = MockServer::start().await;
+            Mock::given(method("POST"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(response))
+                .mount(&mock_server)
+                .await;
+            sqlx::query(
+                "INSERT INTO failed_blocks (block_number, error_message, retry_count, last_failed_at)
+                 VALUES ($1, 'test error', 0, NOW() - INTERVAL '1 hour')",
+            )
+            .bind(block as i64)
+            .execute(&pool)
+            .await
+            .expect("insert test row");
+            let worker = make_worker(database_url, &mock_server.uri());
+            let (_, recovered) = worker.process_batch().await.expect("process_batch");
+            assert_eq!(recovered, 1);
+
+            let (status, body) = get_transaction().await;
+            match served_from {
+                Some(served_from) => {
+                    assert_eq!(status, StatusCode::OK);
+                    assert_eq!(body["block_number"], served_from);
+                }
+                None => assert_eq!(status, StatusCode::NOT_FOUND),
+            }
+        }
+
+        let rows: Vec<(i64, bool)> = sqlx::query_as(
+            "SELECT block_number, canonical FROM tx_hash_lookup WHERE hash = $1 ORDER BY block_number",
+        )
+        .bind(&tx_hash)
+        .fetch_all(&pool)
+        .await
+        .expect("lookup rows");
+        assert_eq!(rows, vec![(BLOCK as i64, false), (BLOCK as i64 + 1, true)]);
+    });
+}
@@ -0,0 +1,138 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+};
+use serde_json::Value;
+use std::sync::Arc;
+use tower::ServiceExt;
+use wiremock::matchers::{body_partial_json, method};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use atlas_server::head::HeadTracker;
+
+use crate::common;
+
+// Block range: 20000-20999
+
+const HEAD: i64 = 20_002;
+
+async fn seed(pool: &sqlx::PgPool) {
+    for number in 20_000..=HEAD {
+        sqlx::query(
+            "INSERT INTO blocks (number, hash, parent_hash, timestamp, gas_used, gas_limit, transaction_count, base_fee_per_gas, indexed_at)
+             VALUES ($1, $2, $3, 1900000000 + $1, 21000, 30000000, 1, 100, NOW())
+             ON CONFLICT (number) DO NOTHING",
+        )
+        .bind(number)
+        .bind(format!("0x{:064x}", number))
+        .bind(format!("0x{:064x}", number - 1))
+        .execute(pool)
+        .await
+        .expect("seed block");
+
+        // Tips of 1, 5 and 10 wei over the base fee.
+        let tip = [1, 5, 10][(number - 20_000) as usize];
+        sqlx::query(
+            "INSERT INTO transactions (hash, block_number, block_index, from_address, to_address, value, gas_price, gas_used, input_data, status, timestamp)
+             VALUES ($1, $2, 0, $3, $3, 0, $4, 21000, ''::bytea, TRUE, 1900000000 + $2)
+             ON CONFLICT (hash, block_number) DO NOTHING",
+        )
+        .bind(format!("0x{:064x}", 0x20000 + number))
+        .bind(number)
+        .bind("0x2000000000000000000000000000000000000001")
+        .bind(bigdecimal::BigDecimal::from(100 + tip))
+        .execute(pool)
+        .await
+        .expect("seed transaction");
+    }
+}
+
+async fn oracle(rpc_url: String, chain_head: i64) -> Value {
+    let head_tracker = Arc::new(HeadTracker::empty(10));
+    head_tracker.set_indexed_height(Some(HEAD));
+    head_tracker.set_chain_height(chain_head);
+
+    let response = common::test_router_with_rpc_and_head(rpc_url, head_tracker)
+        .oneshot(
+            Request::builder()
+                .uri("/api/stats/gas-oracle")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    common::json_body(response).await
+}
+
+#[test]
+fn gas_oracle_uses_indexed_blocks_near_the_head() {
+    common::run(async {
+        seed(&common::pool()).await;
+
+        let body = oracle(String::new(), HEAD + 3).await;
+        assert_eq!(body["source"], "indexed");
+        assert_eq!(body["block_number"], HEAD);
+        assert_eq!(body["lag_blocks"], 3);
+        assert_eq!(body["base_fee_per_gas"], "100");
+        assert_eq!(body["priority_fee_per_gas"]["slow"], "1");
+        assert_eq!(body["priority_fee_per_gas"]["standard"], "5");
+        assert_eq!(body["priority_fee_per_gas"]["fast"], "10");
+    });
+}
+
+#[test]
+fn gas_oracle_falls_back_to_fee_history_when_far_behind() {
+    common::run(async {
+        seed(&common::pool()).await;
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(
+                serde_json::json!({"method": "eth_feeHistory"}),
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "result": {
+                    "oldestBlock": "0x7517",
+                    "baseFeePerGas": ["0x7", "0x8"],
+                    "gasUsedRatio": [0.5],
+                    "reward": [["0x2", "0x3", "0x4"]]
+                }
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let body = oracle(mock_server.uri(), 30_000).await;
+        assert_eq!(body["source"], "rpc");
+        assert_eq!(body["block_number"], 29_975);
+        assert_eq!(body["indexed_block"], HEAD);
+        assert_eq!(body["lag_blocks"], 30_000 - HEAD);
+        assert_eq!(body["base_fee_per_gas"], "8");
+        assert_eq!(body["priority_fee_per_gas"]["standard"], "3");
+    });
+}
+
+#[test]
+fn gas_oracle_serves_indexed_estimate_when_fee_history_fails() {
+    common::run(async {
+        seed(&common::pool()).await;
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "error": {"code": -32601, "message": "method not found"}
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let body = oracle(mock_server.uri(), 30_000).await;
+        assert_eq!(body["source"], "indexed");
+        assert_eq!(body["block_number"], HEAD);
+        assert_eq!(body["priority_fee_per_gas"]["standard"], "5");
+    });
+}
@@ -0,0 +1,807 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+};
+use std::time::Duration;
+use tower::ServiceExt;
+
+use crate::common;
+use atlas_server::indexer::LabelPropagator;
+use atlas_server::state_keys::{LABEL_PROPAGATION_LAST_BLOCK_KEY, LABEL_PROPAGATION_SYNCED_AT_KEY};
+
+// Block range: 8000-8999
+
+const DEPLOYER: &str = "0x8000000000000000000000000000000000000001";
+const CONTRACT: &str = "0x8000000000000000000000000000000000000002";
+const FUNDED: &str = "0x8000000000000000000000000000000000000003";
+const CURATED: &str = "0x8000000000000000000000000000000000000004";
+const SUGGESTED: &str = "0x8000000000000000000000000000000000000005";
+const SUGGESTER_IP: &str = "198.51.100.80";
+
+async fn seed_tx(
+    pool: &sqlx::PgPool,
+    block_index: i32,
+    to: Option<&str>,
+    value: i64,
+    contract_created: Option<&str>,
+) {
+    sqlx::query(
+        "INSERT INTO transactions (hash, block_number, block_index, from_address, to_address, value, gas_price, gas_used, input_data, status, contract_created, timestamp)
+         VALUES ($1, 8000, $2, $3, $4, $5, 1, 21000, '\\x', true, $6, 1700008000)
+         ON CONFLICT (hash, block_number) DO NOTHING",
+    )
+    .bind(format!("0x{:064x}", 0x8000 + block_index))
+    .bind(block_index)
+    .bind(DEPLOYER)
+    .bind(to)
+    .bind(value)
+    .bind(contract_created)
+    .execute(pool)
+    .await
+    .expect("seed transaction");
+}
+
+async fn seed(pool: &sqlx::PgPool) {
+    sqlx::query(
+        "INSERT INTO blocks (number, hash, parent_hash, timestamp, gas_used, gas_limit, transaction_count, indexed_at)
+         VALUES (8000, $1, $2, 1700008000, 84000, 30000000, 4, NOW())
+         ON CONFLICT (number) DO NOTHING",
+    )
+    .bind(format!("0x{:064x}", 8000))
+    .bind(format!("0x{:064x}", 7999))
+    .execute(pool)
+    .await
+    .expect("seed block");
+
+    seed_tx(pool, 0, None, 0, Some(CONTRACT)).await;
+    seed_tx(pool, 1, Some(FUNDED), 1_000, None).await;
+    seed_tx(pool, 2, Some(CURATED), 1_000, None).await;
+    // Funds FUNDED again; the earlier transaction stays its evidence.
+    seed_tx(pool, 3, Some(FUNDED), 5, None).await;
+
+    for (address, name, tags) in [
+        (DEPLOYER, "Acme Deployer", vec!["acme"]),
+        (CURATED, "Acme Treasury", vec!["treasury"]),
+    ] {
+        sqlx::query(
+            "INSERT INTO address_labels (address, name, tags) VALUES ($1, $2, $3)
+             ON CONFLICT (address) DO UPDATE SET name = EXCLUDED.name, tags = EXCLUDED.tags, updated_at = NOW()",
+        )
+        .bind(address)
+        .bind(name)
+        .bind(tags)
+        .execute(pool)
+        .await
+        .expect("seed label");
+    }
+
+    sqlx::query("DELETE FROM indexer_state WHERE key = ANY($1)")
+        .bind(vec![
+            LABEL_PROPAGATION_LAST_BLOCK_KEY,
+            LABEL_PROPAGATION_SYNCED_AT_KEY,
+        ])
+        .execute(pool)
+        .await
+        .expect("reset propagation state");
+}
+
+async fn derived(pool: &sqlx::PgPool) -> Vec<(String, String, String)> {
+    sqlx::query_as(
+        "SELECT address, tag, provenance FROM derived_address_labels
+         WHERE source_address = $1 ORDER BY address, tag",
+    )
+    .bind(DEPLOYER)
+    .fetch_all(pool)
+    .await
+    .expect("query derived labels")
+}
+
+#[test]
+fn propagates_deployer_and_funder_labels() {
+    common::run(async {
+        let pool = common::pool();
+        seed(&pool).await;
+
+        let propagator =
+            LabelPropagator::new(pool.clone(), Duration::from_secs(60)).expect("propagator");
+        propagator.run_cycle().await.expect("first cycle");
+
+        assert_eq!(
+            derived(&pool).await,
+            vec![
+                (
+                    CONTRACT.to_string(),
+                    "deployed-by:acme".to_string(),
+                    "deployer".to_string()
+                ),
+                (
+                    FUNDED.to_string(),
+                    "funded-by:acme".to_string(),
+                    "funder".to_string()
+                ),
+            ],
+            "curated addresses must not receive derived labels"
+        );
+        let evidence: (String, i64) = sqlx::query_as(
+            "SELECT evidence_tx_hash, evidence_block FROM derived_address_labels
+             WHERE address = $1 AND tag = 'funded-by:acme'",
+        )
+        .bind(FUNDED)
+        .fetch_one(&pool)
+        .await
+        .expect("funder evidence");
+        assert_eq!(evidence, (format!("0x{:064x}", 0x8001), 8000));
+        let curated: (String, Vec<String>) =
+            sqlx::query_as("SELECT name, tags FROM address_labels WHERE address = $1")
+                .bind(CURATED)
+                .fetch_one(&pool)
+                .await
+                .expect("curated label");
+        assert_eq!(
+            curated,
+            ("Acme Treasury".to_string(), vec!["treasury".to_string()])
+        );
+
+        // Retagging the source re-applies history and prunes the old tag.
+        sqlx::query("UPDATE address_labels SET tags = $2, updated_at = NOW() WHERE address = $1")
+            .bind(DEPLOYER)
+            .bind(vec!["acme-labs"])
+            .execute(&pool)
+            .await
+            .expect("retag deployer");
+        propagator.run_cycle().await.expect("second cycle");
+
+        let tags: Vec<String> = derived(&pool)
+            .await
+            .into_iter()
+            .map(|(_, t, _)| t)
+            .collect();
+        assert_eq!(tags, vec!["deployed-by:acme-labs", "funded-by:acme-labs"]);
+    });
+}
+
+fn post(uri: &str, admin: bool, body: serde_json::Value) -> Request<Body> {
+    let mut builder = Request::builder()
+        .method("POST")
+        .uri(uri)
+        .header("content-type", "application/json")
+        .header("x-real-ip", SUGGESTER_IP);
+    if admin {
+        builder = builder.header("authorization", format!("Bearer {}", common::ADMIN_API_KEY));
+    }
+    builder.body(Body::from(body.to_string())).unwrap()
+}
+
+#[test]
+fn label_suggestion_moderation_flow() {
+    common::run(async {
+        let pool = common::pool();
+        for sql in [
+            "DELETE FROM label_suggestions WHERE address = $1",
+            "DELETE FROM address_labels WHERE address = $1",
+        ] {
+            sqlx::query(sql)
+                .bind(SUGGESTED)
+                .execute(&pool)
+                .await
+                .expect("reset suggestion fixtures");
+        }
+
+        let app = common::test_router();
+        let suggestion = serde_json::json!({
+            "address": SUGGESTED.to_uppercase().replacen("0X", "0x", 1),
+            "name": "Acme Bridge",
+            "tags": ["Bridge", "bridge"],
+        });
+
+        let response = app
+            .clone()
+            .oneshot(post("/api/labels/suggest", false, suggestion.clone()))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let body = common::json_body(response).await;
+        assert_eq!(body["status"], "pending");
+        let approve_id = body["id"].as_i64().unwrap();
+
+        let response = app
+            .clone()
+            .oneshot(post("/api/labels/suggest", false, suggestion))
+            .await
+            .unwrap();
+        let reject_id = common::json_body(response).await["id"].as_i64().unwrap();
+
+        // Moderation requires the admin key.
+        let response = app
+            .clone()
+            .oneshot(post(
+                &format!("/api/labels/suggestions/{approve_id}/approve"),
+                false,
+                serde_json::json!({}),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri("/api/labels/suggestions?status=pending&page=1&limit=100")
+                    .header("authorization", format!("Bearer {}", common::ADMIN_API_KEY))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = common::json_body(response).await;
+        let queued: Vec<i64> = body["data"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|s| s["id"].as_i64().unwrap())
+            .collect();
+        assert!(queued.contains(&approve_id) && queued.contains(&reject_id));
+
+        let response = app
+            .clone()
+            .oneshot(post(
+                &format!("/api/labels/suggestions/{approve_id}/approve"),
+                true,
+                serde_json::json!({ "note": "verified on bridge docs" }),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let label = common::json_body(response).await;
+        assert_eq!(label["address"], SUGGESTED);
+        assert_eq!(label["tags"], serde_json::json!(["bridge"]));
+
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri(format!("/api/labels/suggestions/{reject_id}/reject"))
+                    .header("authorization", format!("Bearer {}", common::ADMIN_API_KEY))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(common::json_body(response).await["status"], "rejected");
+
+        // Reviewed suggestions cannot be reviewed again.
+        let response = app
+            .oneshot(post(
+                &format!("/api/labels/suggestions/{approve_id}/reject"),
+                true,
+                serde_json::json!({}),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    });
+}
+
+#[test]
+fn label_import_dry_run_then_apply_and_export() {
+    const IMPORTED_A: &str = "0x8000000000000000000000000000000000000006";
+    const IMPORTED_B: &str = "0x8000000000000000000000000000000000000007";
+
+    common::run(async {
+        let pool = common::pool();
+        sqlx::query("DELETE FROM address_labels WHERE address = ANY($1)")
+            .bind(vec![IMPORTED_A, IMPORTED_B])
+            .execute(&pool)
+            .await
+            .expect("reset import fixtures");
+
+        let dump = serde_json::json!({
+            IMPORTED_A: { "name": "Acme Exchange 1", "labels": ["Exchange", "Hot Wallet"] },
+            IMPORTED_B: { "name": "", "labels": ["Exchange"] },
+        });
+        let import = |dry_run: bool| {
+            Request::builder()
+                .method("POST")
+                .uri(format!(
+                    "/api/labels/import?format=etherscan&dry_run={dry_run}&tag_map=exchange:cex"
+                ))
+                .header("authorization", format!("Bearer {}", common::ADMIN_API_KEY))
+                .body(Body::from(dump.to_string()))
+                .unwrap()
+        };
+        let app = common::test_router();
+
+        let response = app.clone().oneshot(import(true)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let report = common::json_body(response).await;
+        assert_eq!(report["dry_run"], true);
+        assert_eq!(report["total_rows"], 2);
+        assert_eq!(report["valid_rows"], 1);
+        assert_eq!(report["created"], 1);
+        assert_eq!(
+            report["tags"],
+            serde_json::json!({"cex": 1, "hot-wallet": 1})
+        );
+        assert_eq!(report["errors"][0]["address"], IMPORTED_B);
+
+        let (labeled,): (i64,) =
+            sqlx::query_as("SELECT COUNT(*) FROM address_labels WHERE address = $1")
+                .bind(IMPORTED_A)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(labeled, 0, "dry run must not write");
+
+        let response = app.clone().oneshot(import(false)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(common::json_body(response).await["created"], 1);
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/labels/export?format=csv&tag=hot-wallet")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers()["content-type"],
+            "text/csv; charset=utf-8"
+        );
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let csv = String::from_utf8(bytes.to_vec()).unwrap();
+        assert!(csv.starts_with("address,name,tags\n"));
+        assert!(csv.contains(&format!("{IMPORTED_A},Acme Exchange 1,cex;hot-wallet\n")));
+    });
+}
+
+#[test]
+fn curator_keys_are_limited_to_their_tags() {
+    const BRIDGE: &str = "0x8000000000000000000000000000000000000008";
+    const EXCHANGE: &str = "0x8000000000000000000000000000000000000009";
+    common::run(async {
+        let pool = common::pool();
+        sqlx::query("DELETE FROM address_labels WHERE address = ANY($1)")
+            .bind(vec![BRIDGE, EXCHANGE])
+            .execute(&pool)
+            .await
+            .expect("reset curator fixtures");
+        sqlx::query(
+            "INSERT INTO address_labels (address, name, tags) VALUES ($1, 'Acme Exchange', '{cex}')",
+        )
+        .bind(EXCHANGE)
+        .execute(&pool)
+        .await
+        .expect("seed exchange label");
+
+        let app = common::test_router();
+        let request = |method: &str, uri: &str, key: &str, body: serde_json::Value| {
+            Request::builder()
+                .method(method)
+                .uri(uri)
+                .header("content-type", "application/json")
+                .header("authorization", format!("Bearer {key}"))
+                .body(Body::from(body.to_string()))
+                .unwrap()
+        };
+
+        let response = app
+            .clone()
+            .oneshot(post(
+                "/api/labels/keys",
+                true,
+                serde_json::json!({ "name": "bridge team", "tags": ["bridge"] }),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let body = common::json_body(response).await;
+        let key = body["key"].as_str().unwrap().to_string();
+        let key_id = body["id"].as_i64().unwrap();
+
+        let put = |address: &str, tags: serde_json::Value| {
+            request(
+                "POST",
+                "/api/labels",
+                &key,
+                serde_json::json!({ "address": address, "name": "Acme Bridge", "tags": tags }),
+            )
+        };
+
+        let response = app
+            .clone()
+            .oneshot(put(BRIDGE, serde_json::json!(["bridge", "defi"])))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            common::json_body(response).await["tags"],
+            serde_json::json!(["bridge", "defi"])
+        );
+
+        // Labels owned by another team and foreign-only tags are off limits.
+        for (address, tags) in [
+            (EXCHANGE, serde_json::json!(["cex", "bridge"])),
+            (BRIDGE, serde_json::json!(["bridge"])),
+        ] {
+            let response = app.clone().oneshot(put(address, tags)).await.unwrap();
+            assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        }
+        let response = app
+            .clone()
+            .oneshot(request(
+                "DELETE",
+                &format!("/api/labels/{EXCHANGE}"),
+                &key,
+                serde_json::json!({}),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        // Curators cannot manage keys.
+        let response = app
+            .clone()
+            .oneshot(request(
+                "GET",
+                "/api/labels/keys",
+                &key,
+                serde_json::json!({}),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let response = app
+            .clone()
+            .oneshot(request(
+                "DELETE",
+                &format!("/api/labels/keys/{key_id}"),
+                common::ADMIN_API_KEY,
+                serde_json::json!({}),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(!common::json_body(response).await["revoked_at"].is_null());
+
+        let response = app
+            .oneshot(put(BRIDGE, serde_json::json!(["bridge", "defi"])))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    });
+}
+
+#[test]
+fn address_notes_are_private_to_their_key() {
+    const TRACKED: &str = "0x800000000000000000000000000000000000000a";
+    common::run(async {
+        let app = common::test_router();
+        let request = |method: &str, uri: &str, key: &str, body: serde_json::Value| {
+            Request::builder()
+                .method(method)
+                .uri(uri)
+                .header("content-type", "application/json")
+                .header("authorization", format!("Bearer {key}"))
+                .body(Body::from(body.to_string()))
+                .unwrap()
+        };
+        let mut keys = Vec::new();
+        for name in ["notes owner", "notes other"] {
+            let response = app
+                .clone()
+                .oneshot(post(
+                    "/api/labels/keys",
+                    true,
+                    serde_json::json!({ "name": name, "tags": ["watch"] }),
+                ))
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::CREATED);
+            keys.push(
+                common::json_body(response).await["key"]
+                    .as_str()
+                    .unwrap()
+                    .to_string(),
+            );
+        }
+        let (owner, other) = (&keys[0], &keys[1]);
+
+        let response = app
+            .clone()
+            .oneshot(request(
+                "POST",
+                "/api/me/notes",
+                owner,
+                serde_json::json!({
+                    "address": TRACKED.to_uppercase().replace("0X", "0x"),
+                    "note": " Treasury multisig ",
+                    "tags": ["Treasury"],
+                }),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = common::json_body(response).await;
+        assert_eq!(body["address"], TRACKED);
+        assert_eq!(body["note"], "Treasury multisig");
+        assert_eq!(body["tags"], serde_json::json!(["treasury"]));
+
+        let response = app
+            .clone()
+            .oneshot(request(
+                "GET",
+                "/api/me/notes?tag=treasury",
+                owner,
+                serde_json::json!({}),
+            ))
+            .await
+            .unwrap();
+        let body = common::json_body(response).await;
+        assert_eq!(body["total"], 1);
+        assert_eq!(body["data"][0]["address"], TRACKED);
+
+        // Another key sees none of it, and the note is not a public label.
+        let response = app
+            .clone()
+            .oneshot(request(
+                "GET",
+                &format!("/api/me/notes/{TRACKED}"),
+                other,
+                serde_json::json!({}),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        let response = app
+            .clone()
+            .oneshot(request(
+                "GET",
+                "/api/me/notes",
+                other,
+                serde_json::json!({}),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(common::json_body(response).await["total"], 0);
+        let response = app
+            .clone()
+            .oneshot(request(
+                "GET",
+                "/api/me/notes",
+                common::ADMIN_API_KEY,
+                serde_json::json!({}),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let response = app
+            .clone()
+            .oneshot(request(
+                "DELETE",
+                &format!("/api/me/notes/{TRACKED}"),
+                owner,
+                serde_json::json!({}),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        let response = app
+            .oneshot(request(
+                "GET",
+                &format!("/api/me/notes/{TRACKED}"),
+                owner,
+                serde_json::json!({}),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    });
+}
+
+#[test]
+fn saved_filters_are_kept_per_key_and_run_as_batch_calls() {
+    common::run(async {
+        let app = common::test_router();
+        let request = |method: &str, uri: &str, key: &str, body: serde_json::Value| {
+            Request::builder()
+                .method(method)
+                .uri(uri)
+                .header("content-type", "application/json")
+                .header("authorization", format!("Bearer {key}"))
+                .body(Body::from(body.to_string()))
+                .unwrap()
+        };
+        let response = app
+            .clone()
+            .oneshot(post(
+                "/api/labels/keys",
+                true,
+                serde_json::json!({ "name": "filters owner", "tags": ["watch"] }),
+            ))
+            .await
+            .unwrap();
+        let key = common::json_body(response).await["key"]
+            .as_str()
+            .unwrap()
+            .to_string();
+
+        let filter = serde_json::json!({
+            "name": "Latest blocks",
+            "method": "/api/blocks",
+            "params": { "limit": 2 },
+        });
+        let response = app
+            .clone()
+            .oneshot(request("POST", "/api/me/filters", &key, filter.clone()))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let saved = common::json_body(response).await;
+        let id = saved["id"].as_i64().unwrap();
+        assert_eq!(saved["params"], serde_json::json!({ "limit": 2 }));
+
+        // Names are unique per key; unrunnable queries are refused.
+        let response = app
+            .clone()
+            .oneshot(request("POST", "/api/me/filters", &key, filter))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let response = app
+            .clone()
+            .oneshot(request(
+                "POST",
+                "/api/me/filters",
+                &key,
+                serde_json::json!({ "name": "Health", "method": "/health" }),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let response = app
+            .clone()
+            .oneshot(request(
+                "PUT",
+                &format!("/api/me/filters/{id}"),
+                &key,
+                serde_json::json!({
+                    "name": "Latest block",
+                    "method": "/api/blocks",
+                    "params": { "limit": 1 },
+                }),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = app
+            .clone()
+            .oneshot(request(
+                "GET",
+                "/api/me/filters",
+                &key,
+                serde_json::json!({}),
+            ))
+            .await
+            .unwrap();
+        let filters = common::json_body(response).await;
+        assert_eq!(filters.as_array().unwrap().len(), 1);
+        let saved = &filters[0];
+        assert_eq!(saved["name"], "Latest block");
+
+        let response = app
+            .clone()
+            .oneshot(request(
+                "POST",
+                "/api/batch",
+                &key,
+                serde_json::json!([{
+                    "id": saved["id"],
+                    "method": saved["method"],
+                    "params": saved["params"],
+                }]),
+            ))
+            .await
+            .unwrap();
+        let results = common::json_body(response).await;
+        assert_eq!(results[0]["status"], 200);
+        assert_eq!(results[0]["result"]["limit"], 1);
+
+        // Other callers can't see the filter.
+        let response = app
+            .clone()
+            .oneshot(request(
+                "GET",
+                &format!("/api/me/filters/{id}"),
+                common::ADMIN_API_KEY,
+                serde_json::json!({}),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let response = app
+            .clone()
+            .oneshot(request(
+                "DELETE",
+                &format!("/api/me/filters/{id}"),
+                &key,
+                serde_json::json!({}),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        let response = app
+            .oneshot(request(
+                "GET",
+                &format!("/api/me/filters/{id}"),
+                &key,
+                serde_json::json!({}),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    });
+}
+
+#[test]
+fn builtin_labels_do_not_override_curated_labels() {
+    use atlas_server::labels::builtin::{self, BuiltinLabels, LabelSet};
+
+    const BRIDGE: &str = "0x4200000000000000000000000000000000000010";
+    const FEE_VAULT: &str = "0x4200000000000000000000000000000000000011";
+
+    common::run(async {
+        let pool = common::pool();
+        sqlx::query(
+            "INSERT INTO address_labels (address, name, tags) VALUES ($1, 'Our Bridge', '{}')
+             ON CONFLICT (address) DO UPDATE SET name = EXCLUDED.name",
+        )
+        .bind(BRIDGE)
+        .execute(&pool)
+        .await
+        .expect("seed curated label");
+
+        let entries = builtin::entries(&BuiltinLabels::Sets(vec![LabelSet::OpStack]), 1234);
+        let selected: Vec<_> = entries
+            .iter()
+            .filter(|entry| [BRIDGE, FEE_VAULT].contains(&entry.address))
+            .collect();
+        builtin::write_labels(&pool, &selected)
+            .await
+            .expect("write built-in labels");
+
+        let labels: Vec<(String, String, Vec<String>)> = sqlx::query_as(
+            "SELECT address, name, tags FROM address_labels WHERE address = ANY($1) ORDER BY address",
+        )
+        .bind(vec![BRIDGE, FEE_VAULT])
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+        assert_eq!(labels[0].1, "Our Bridge");
+        assert_eq!(labels[1].1, "Sequencer Fee Vault");
+        assert!(labels[1].2.contains(&builtin::BUILTIN_TAG.to_string()));
+
+        let (is_contract, first_seen_block): (bool, i64) = sqlx::query_as(
+            "SELECT is_contract, first_seen_block FROM addresses WHERE address = $1",
+        )
+        .bind(FEE_VAULT)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert!(is_contract);
+        assert_eq!(first_seen_block, 0);
+    });
+}
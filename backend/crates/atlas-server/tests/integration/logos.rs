@@ -0,0 +1,204 @@
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+};
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+use crate::common;
+
+// Block range: 26000-26999
+
+const TOKEN: &str = "0x260000000000000000000000000000000000000a";
+const COLLECTION: &str = "0x260000000000000000000000000000000000000b";
+const UNKNOWN: &str = "0x260000000000000000000000000000000000000c";
+const PNG: &[u8] = b"\x89PNG\r\n\x1a\nnot-really-a-png";
+
+async fn seed(pool: &sqlx::PgPool) {
+    sqlx::query(
+        "INSERT INTO erc20_contracts (address, name, symbol, decimals, total_supply, first_seen_block)
+         VALUES ($1, 'Logo Token', 'LOGO', 18, 1000, 26000)
+         ON CONFLICT (address) DO NOTHING",
+    )
+    .bind(TOKEN)
+    .execute(pool)
+    .await
+    .expect("seed token");
+    sqlx::query(
+        "INSERT INTO nft_contracts (address, name, symbol, total_supply, first_seen_block)
+         VALUES ($1, 'Logo Collection', 'LGC', 10, 26000)
+         ON CONFLICT (address) DO NOTHING",
+    )
+    .bind(COLLECTION)
+    .execute(pool)
+    .await
+    .expect("seed collection");
+    sqlx::query("DELETE FROM contract_logos WHERE address = ANY($1)")
+        .bind(vec![TOKEN, COLLECTION])
+        .execute(pool)
+        .await
+        .expect("clear logos");
+}
+
+async fn admin(
+    method: &str,
+    uri: &str,
+    content_type: &str,
+    body: Vec<u8>,
+) -> (StatusCode, Option<Value>) {
+    let mut request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("authorization", format!("Bearer {}", common::ADMIN_API_KEY));
+    if !body.is_empty() {
+        request = request.header(header::CONTENT_TYPE, content_type);
+    }
+    let response = common::test_router()
+        .oneshot(request.body(Body::from(body)).unwrap())
+        .await
+        .unwrap();
+    let status = response.status();
+    if status == StatusCode::NO_CONTENT {
+        return (status, None);
+    }
+    (status, Some(common::json_body(response).await))
+}
+
+#[test]
+fn logos_can_be_linked_uploaded_and_removed() {
+    common::run(async {
+        let pool = common::pool();
+        seed(&pool).await;
+
+        let url = "https://example.com/logo.png";
+        let (status, logo) = admin(
+            "PUT",
+            &format!("/api/admin/logos/{TOKEN}"),
+            "application/json",
+            json!({ "url": url }).to_string().into_bytes(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(logo.unwrap()["logo_url"], url);
+        let (status, token) = common::get_json(&format!("/api/tokens/{TOKEN}")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(token["logo_url"], url);
+
+        let (status, logo) = admin(
+            "PUT",
+            &format!("/api/admin/logos/{COLLECTION}/image"),
+            "image/png",
+            PNG.to_vec(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        let logo_url = logo.unwrap()["logo_url"].as_str().unwrap().to_string();
+        assert!(logo_url.starts_with(&format!("/api/logos/{COLLECTION}?v=")));
+        let (status, collection) =
+            common::get_json(&format!("/api/nfts/collections/{COLLECTION}")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(collection["logo_url"], logo_url);
+        assert_eq!(collection["name"], "Logo Collection");
+
+        let response = common::test_router()
+            .oneshot(
+                Request::builder()
+                    .uri(&logo_url)
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "image/png");
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(&bytes[..], PNG);
+
+        // A URL replaces the uploaded image.
+        admin(
+            "PUT",
+            &format!("/api/admin/logos/{COLLECTION}"),
+            "application/json",
+            json!({ "url": url }).to_string().into_bytes(),
+        )
+        .await;
+        let response = common::test_router()
+            .oneshot(
+                Request::builder()
+                    .uri(format!("/api/logos/{COLLECTION}"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let (status, _) = admin(
+            "DELETE",
+            &format!("/api/admin/logos/{TOKEN}"),
+            "",
+            Vec::new(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        let (status, token) = common::get_json(&format!("/api/tokens/{TOKEN}")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(token["logo_url"], Value::Null);
+        let (status, _) = admin(
+            "DELETE",
+            &format!("/api/admin/logos/{TOKEN}"),
+            "",
+            Vec::new(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    });
+}
+
+#[test]
+fn logo_uploads_are_validated() {
+    common::run(async {
+        let pool = common::pool();
+        seed(&pool).await;
+
+        let (status, _) = admin(
+            "PUT",
+            &format!("/api/admin/logos/{UNKNOWN}/image"),
+            "image/png",
+            PNG.to_vec(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        let (status, _) = admin(
+            "PUT",
+            &format!("/api/admin/logos/{TOKEN}/image"),
+            "text/html",
+            b"<script></script>".to_vec(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let (status, _) = admin(
+            "PUT",
+            &format!("/api/admin/logos/{TOKEN}"),
+            "application/json",
+            json!({ "url": "javascript:alert(1)" })
+                .to_string()
+                .into_bytes(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let (status, _) = admin(
+            "PUT",
+            &format!("/api/admin/logos/{TOKEN}/image"),
+            "image/png",
+            vec![0; 256 * 1024 + 1],
+        )
+        .await;
+        assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+    });
+}
@@ -1,12 +1,38 @@
 mod common;
 
 mod addresses;
+mod alerts;
+mod archive;
+mod backfill;
 mod blocks;
+mod chain_guard;
+mod cold_storage;
+mod contract_reindex;
+mod contracts;
+mod data_quality;
+mod datasets;
+mod decode;
+mod dex;
+mod etherscan;
+mod export;
 mod gap_fill;
+mod gas_oracle;
+mod labels;
+mod logos;
+mod metadata;
+mod nft_sales;
 mod nfts;
+mod partitions;
+mod pending_verifications;
+mod rpc_logs;
+mod runtime_settings;
+mod safes;
 mod schema;
 mod search;
 mod snapshots;
+mod stats;
 mod status;
 mod tokens;
 mod transactions;
+mod transfer_aggregation;
+mod validators;
@@ -0,0 +1,96 @@
+use atlas_server::indexer::metadata::{pending_erc20_contracts, pending_nft_contracts};
+
+use crate::common;
+
+const QUIET: &str = "0x3959000000000000000000000000000000000a01";
+const BUSY: &str = "0x3959000000000000000000000000000000000b02";
+
+async fn clear(pool: &sqlx::PgPool) {
+    for table in ["erc20_contracts", "nft_contracts"] {
+        sqlx::query(&format!("DELETE FROM {table} WHERE address IN ($1, $2)"))
+            .bind(QUIET)
+            .bind(BUSY)
+            .execute(pool)
+            .await
+            .expect("clear contracts");
+    }
+}
+
+fn position(addresses: &[String], address: &str) -> usize {
+    addresses
+        .iter()
+        .position(|a| a == address)
+        .unwrap_or_else(|| panic!("{address} missing from the backlog"))
+}
+
+#[test]
+fn erc20_backlog_puts_tokens_with_holders_first() {
+    common::run(async {
+        let pool = common::pool();
+        clear(&pool).await;
+        for address in [QUIET, BUSY] {
+            sqlx::query("INSERT INTO erc20_contracts (address, first_seen_block) VALUES ($1, 1)")
+                .bind(address)
+                .execute(&pool)
+                .await
+                .expect("insert contract");
+        }
+        for holder in 1..=3 {
+            sqlx::query(
+                "INSERT INTO erc20_balances (address, contract_address, balance, last_updated_block)
+                 VALUES ($1, $2, 100, 1)",
+            )
+            .bind(format!("0x{holder:040x}"))
+            .bind(BUSY)
+            .execute(&pool)
+            .await
+            .expect("insert balance");
+        }
+
+        let prioritized = pending_erc20_contracts(&pool, 10_000, 10_000)
+            .await
+            .expect("prioritized backlog");
+        assert!(position(&prioritized, BUSY) < position(&prioritized, QUIET));
+
+        let unordered = pending_erc20_contracts(&pool, 10_000, 0)
+            .await
+            .expect("unordered backlog");
+        assert!(position(&unordered, QUIET) < position(&unordered, BUSY));
+
+        clear(&pool).await;
+    });
+}
+
+#[test]
+fn nft_backlog_puts_collections_with_owners_first() {
+    common::run(async {
+        let pool = common::pool();
+        clear(&pool).await;
+        for address in [QUIET, BUSY] {
+            sqlx::query("INSERT INTO nft_contracts (address, first_seen_block) VALUES ($1, 1)")
+                .bind(address)
+                .execute(&pool)
+                .await
+                .expect("insert contract");
+        }
+        for token_id in 1..=3i64 {
+            sqlx::query(
+                "INSERT INTO nft_tokens (contract_address, token_id, owner, last_transfer_block)
+                 VALUES ($1, $2, $3, 1)",
+            )
+            .bind(BUSY)
+            .bind(bigdecimal::BigDecimal::from(token_id))
+            .bind(format!("0x{token_id:040x}"))
+            .execute(&pool)
+            .await
+            .expect("insert token");
+        }
+
+        let prioritized = pending_nft_contracts(&pool, 10_000, 10_000)
+            .await
+            .expect("prioritized backlog");
+        assert!(position(&prioritized, BUSY) < position(&prioritized, QUIET));
+
+        clear(&pool).await;
+    });
+}
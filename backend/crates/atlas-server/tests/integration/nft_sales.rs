@@ -0,0 +1,124 @@
+use axum::http::StatusCode;
+use serde_json::json;
+
+use atlas_server::indexer::ProcessorRegistry;
+
+use super::common;
+use super::gap_fill::empty_block_response;
+
+// Block range: 24000-24999
+
+const BLOCK: u64 = 24_000;
+const SEAPORT: &str = "0x00000000000000000000000000000000000024aa";
+const NFT: &str = "0x00000000000000000000000000000000000024bb";
+const SELLER: &str = "0x0000000000000000000000000000000000002401";
+const BUYER: &str = "0x0000000000000000000000000000000000002402";
+const FEE_RECIPIENT: &str = "0x0000000000000000000000000000000000002403";
+const TRANSFER_TOPIC: &str = "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef";
+const ORDER_FULFILLED_TOPIC: &str =
+    "0x9d9af8e38d66c62e2c12f0225249fd9d721c54b83f48d9352c97c6cacdcb6f31";
+
+fn word(hex: &str) -> String {
+    format!("{:0>64}", hex.trim_start_matches("0x"))
+}
+
+fn log(address: &str, topics: &[String], words: &[String], log_index: u64) -> serde_json::Value {
+    json!({
+        "address": address,
+        "topics": topics,
+        "data": format!("0x{}", words.concat()),
+        "blockNumber": format!("0x{:x}", BLOCK),
+        "transactionHash": format!("0x{:064x}", 0x2400),
+        "transactionIndex": "0x0",
+        "blockHash": format!("0x{:064x}", BLOCK),
+        "logIndex": format!("0x{:x}", log_index),
+        "removed": false
+    })
+}
+
+/// The block with one transaction filling a Seaport listing: token 7 for
+/// 0x3cf (975) wei to the seller and 0x19 (25) wei to a fee recipient.
+fn block_with_sale() -> serde_json::Value {
+    let topic = |hex: &str| format!("0x{}", word(hex));
+    let mut response = empty_block_response(BLOCK);
+    response[1]["result"] = json!([{
+        "transactionHash": format!("0x{:064x}", 0x2400),
+        "transactionIndex": "0x0",
+        "blockHash": format!("0x{:064x}", BLOCK),
+        "blockNumber": format!("0x{:x}", BLOCK),
+        "from": BUYER,
+        "to": SEAPORT,
+        "cumulativeGasUsed": "0x5208",
+        "gasUsed": "0x5208",
+        "contractAddress": null,
+        "logs": [
+            log(NFT, &[topic(TRANSFER_TOPIC), topic(SELLER), topic(BUYER), topic("7")], &[], 0),
+            log(
+                SEAPORT,
+                &[topic(ORDER_FULFILLED_TOPIC), topic(SELLER), topic("0")],
+                &[
+                    word("ab"), word(BUYER), word("80"), word("120"),
+                    word("1"), word("2"), word(NFT), word("7"), word("1"),
+                    word("2"),
+                    word("0"), word("0"), word("0"), word("3cf"), word(SELLER),
+                    word("0"), word("0"), word("0"), word("19"), word(FEE_RECIPIENT),
+                ],
+                1,
+            ),
+        ],
+        "logsBloom": format!("0x{}", "00".repeat(256)),
+        "type": "0x2",
+        "effectiveGasPrice": "0x1",
+        "status": "0x1"
+    }]);
+    response
+}
+
+async fn clear(pool: &sqlx::PgPool) {
+    for sql in [
+        "DELETE FROM blocks WHERE number = 24000",
+        "DELETE FROM event_logs WHERE block_number = 24000",
+        "DELETE FROM nft_transfers WHERE block_number BETWEEN 24000 AND 24999",
+        "DELETE FROM nft_contracts WHERE address = '0x00000000000000000000000000000000000024bb'",
+        "DELETE FROM nft_sales WHERE block_number BETWEEN 24000 AND 24999",
+    ] {
+        sqlx::query(sql)
+            .execute(pool)
+            .await
+            .expect("clear sale data");
+    }
+}
+
+#[test]
+fn seaport_sales_are_decoded_with_seller_buyer_and_price() {
+    common::run(async {
+        let pool = common::pool();
+        clear(&pool).await;
+        let processors = ProcessorRegistry::builtin()
+            .build(&["nft_sales".to_string()])
+            .expect("build processors");
+        common::index_block_via_backfill(&pool, BLOCK, block_with_sale(), processors).await;
+
+        let (status, page) = common::get_json(&format!("/api/nfts/collections/{NFT}/sales")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(page["total"], 1);
+        let sale = &page["data"][0];
+        assert_eq!(sale["block_number"], BLOCK);
+        assert_eq!(sale["log_index"], 1);
+        assert_eq!(sale["marketplace"], "seaport");
+        assert_eq!(sale["exchange"], SEAPORT);
+        assert_eq!(sale["token_id"], "7");
+        assert_eq!(sale["seller"], SELLER);
+        assert_eq!(sale["buyer"], BUYER);
+        assert_eq!(sale["currency"], serde_json::Value::Null);
+        assert_eq!(sale["price"], "1000");
+
+        let (status, token) =
+            common::get_json(&format!("/api/nfts/collections/{NFT}/tokens/7")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(token["owner"], BUYER);
+        assert_eq!(token["last_sale"]["price"], "1000");
+
+        clear(&pool).await;
+    });
+}
@@ -10,6 +10,7 @@ use crate::common;
 
 const NFT_A: &str = "0x7000000000000000000000000000000000000001";
 const NFT_B: &str = "0x7000000000000000000000000000000000000002";
+const NFT_TRAITS: &str = "0x7000000000000000000000000000000000000003";
 const OWNER: &str = "0x7000000000000000000000000000000000000010";
 const TX_HASH_NFT: &str = "0x7000000000000000000000000000000000000000000000000000000000000001";
 
@@ -114,6 +115,41 @@ fn list_nft_collections() {
     });
 }
 
+#[test]
+fn get_collection_includes_default_royalty() {
+    common::run(async {
+        let pool = common::pool();
+        seed_nft_data(&pool).await;
+        sqlx::query(
+            "UPDATE nft_contracts SET royalty_receiver = $2, royalty_bps = 500 WHERE address = $1",
+        )
+        .bind(NFT_B)
+        .bind(OWNER)
+        .execute(&pool)
+        .await
+        .expect("set royalty");
+
+        for (address, royalty) in [
+            (NFT_A, serde_json::Value::Null),
+            (NFT_B, serde_json::json!({"receiver": OWNER, "bps": 500})),
+        ] {
+            let response = common::test_router()
+                .oneshot(
+                    Request::builder()
+                        .uri(format!("/api/nfts/collections/{address}"))
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            let body = common::json_body(response).await;
+            assert_eq!(body["address"], address);
+            assert_eq!(body["royalty"], royalty);
+        }
+    });
+}
+
 #[test]
 fn list_collection_tokens() {
     common::run(async {
@@ -222,3 +258,142 @@ fn get_token_returns_metadata_state_and_raw_metadata() {
         assert_eq!(body["metadata"]["description"], "Example NFT");
     });
 }
+
+/// Four tokens: 1 Gold/Laser, 2 Gold/Normal, 3 Blue/Laser, 4 without attributes.
+async fn seed_trait_collection(pool: &sqlx::PgPool) {
+    seed_nft_data(pool).await;
+
+    sqlx::query(
+        "INSERT INTO nft_contracts (address, name, symbol, total_supply, first_seen_block)
+         VALUES ($1, 'Traits', 'TRT', 4, 7000)
+         ON CONFLICT (address) DO NOTHING",
+    )
+    .bind(NFT_TRAITS)
+    .execute(pool)
+    .await
+    .expect("seed trait collection");
+
+    let attributes: [&[(&str, &str)]; 4] = [
+        &[("Background", "Gold"), ("Eyes", "Laser")],
+        &[("Background", "Gold"), ("Eyes", "Normal")],
+        &[("Background", "Blue"), ("Eyes", "Laser")],
+        &[],
+    ];
+    for (token_id, traits) in (1..=4i64).zip(attributes) {
+        sqlx::query(
+            "INSERT INTO nft_tokens (contract_address, token_id, owner, metadata_status, last_transfer_block)
+             VALUES ($1, $2, $3, 'fetched', 7000)
+             ON CONFLICT (contract_address, token_id) DO NOTHING",
+        )
+        .bind(NFT_TRAITS)
+        .bind(bigdecimal::BigDecimal::from(token_id))
+        .bind(OWNER)
+        .execute(pool)
+        .await
+        .expect("seed trait token");
+
+        for (trait_type, value) in traits {
+            sqlx::query(
+                "INSERT INTO nft_token_attributes (contract_address, token_id, trait_type, value)
+                 VALUES ($1, $2, $3, $4)
+                 ON CONFLICT DO NOTHING",
+            )
+            .bind(NFT_TRAITS)
+            .bind(bigdecimal::BigDecimal::from(token_id))
+            .bind(trait_type)
+            .bind(value)
+            .execute(pool)
+            .await
+            .expect("seed token attribute");
+        }
+    }
+}
+
+async fn trait_filtered_token_ids(query: &str) -> (StatusCode, Vec<String>) {
+    let response = common::test_router()
+        .oneshot(
+            Request::builder()
+                .uri(format!("/api/nfts/collections/{NFT_TRAITS}/tokens?{query}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let status = response.status();
+    let body = common::json_body(response).await;
+    let ids = body["data"]
+        .as_array()
+        .map(|data| {
+            data.iter()
+                .map(|token| token["token_id"].to_string().replace('"', ""))
+                .collect()
+        })
+        .unwrap_or_default();
+    (status, ids)
+}
+
+#[test]
+fn collection_tokens_filter_by_traits() {
+    common::run(async {
+        seed_trait_collection(&common::pool()).await;
+
+        let (status, ids) = trait_filtered_token_ids("trait%5BBackground%5D=Gold").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(ids, vec!["1", "2"]);
+
+        // Different trait types must all match.
+        let (_, ids) = trait_filtered_token_ids("trait[Background]=Gold&trait[Eyes]=Laser").await;
+        assert_eq!(ids, vec!["1"]);
+
+        // Repeated trait types match any of the values.
+        let (_, ids) =
+            trait_filtered_token_ids("trait[Background]=Gold&trait[Background]=Blue&limit=2").await;
+        assert_eq!(ids, vec!["1", "2"]);
+
+        let (_, ids) = trait_filtered_token_ids("trait[Background]=Purple").await;
+        assert!(ids.is_empty());
+
+        let (status, _) = trait_filtered_token_ids("trait[]=Gold").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    });
+}
+
+#[test]
+fn collection_traits_report_rarity() {
+    common::run(async {
+        seed_trait_collection(&common::pool()).await;
+
+        let response = common::test_router()
+            .oneshot(
+                Request::builder()
+                    .uri(format!("/api/nfts/collections/{NFT_TRAITS}/traits"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = common::json_body(response).await;
+
+        assert_eq!(body["total_tokens"], 4);
+        let traits = body["traits"].as_array().unwrap();
+        assert_eq!(traits.len(), 2);
+        assert_eq!(traits[0]["trait_type"], "Background");
+        assert_eq!(traits[0]["value_count"], 2);
+        assert_eq!(traits[0]["values"][0]["value"], "Gold");
+        assert_eq!(traits[0]["values"][0]["count"], 2);
+        assert_eq!(traits[0]["values"][0]["frequency"], 0.5);
+        assert_eq!(traits[0]["values"][1]["frequency"], 0.25);
+
+        let missing = common::test_router()
+            .oneshot(
+                Request::builder()
+                    .uri("/api/nfts/collections/0x70000000000000000000000000000000000000ff/traits")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(missing.status(), StatusCode::NOT_FOUND);
+    });
+}
@@ -0,0 +1,168 @@
+use atlas_server::indexer::partitions::{self, PARTITIONED_TABLES};
+use atlas_server::indexer::processors::Processors;
+
+use crate::common;
+use crate::gap_fill::empty_block_response;
+
+// Partitions 900-901: blocks 9_000_000_000-9_019_999_999, far above other
+// tests.
+
+const PARTITION: u64 = 900;
+const BLOCK: i64 = 9_000_000_000;
+const BACKFILL_PARTITION: u64 = 901;
+const BACKFILL_BLOCK: u64 = 9_010_000_000;
+
+async fn drop_partition(pool: &sqlx::PgPool) {
+    drop_partition_tables(pool, PARTITION).await;
+}
+
+async fn drop_partition_tables(pool: &sqlx::PgPool, p: u64) {
+    for table in PARTITIONED_TABLES {
+        sqlx::query(&format!("DROP TABLE IF EXISTS {table}_p{p}"))
+            .execute(pool)
+            .await
+            .expect("drop partition");
+    }
+}
+
+async fn index_count(pool: &sqlx::PgPool, table: &str) -> i64 {
+    let (count,): (i64,) =
+        sqlx::query_as("SELECT COUNT(*) FROM pg_index WHERE indrelid = $1::regclass")
+            .bind(table)
+            .fetch_one(pool)
+            .await
+            .expect("count indexes");
+    count
+}
+
+#[test]
+fn staged_partition_gets_its_indexes_when_attached() {
+    common::run(async {
+        let pool = common::pool();
+        drop_partition(&pool).await;
+
+        partitions::create_staged(&pool, PARTITION).await.unwrap();
+        assert!(partitions::staged(&pool)
+            .await
+            .unwrap()
+            .contains(&PARTITION));
+        // Only the unique indexes behind ON CONFLICT exist while staged.
+        assert_eq!(index_count(&pool, "erc20_transfers_p900").await, 2);
+        assert!(index_count(&pool, "erc20_transfers").await > 2);
+
+        // Writes target the staged table under the parent's name.
+        for _ in 0..2 {
+            sqlx::query(
+                "INSERT INTO blocks_p900 AS blocks (number, hash, parent_hash, timestamp, gas_used, gas_limit, transaction_count, indexed_at)
+                 VALUES ($1, $2, $3, 1700000000, 0, 30000000, 0, NOW())
+                 ON CONFLICT (number) DO UPDATE SET hash = EXCLUDED.hash
+                 WHERE blocks.hash <> EXCLUDED.hash",
+            )
+            .bind(BLOCK)
+            .bind(format!("0x{:064x}", BLOCK))
+            .bind(format!("0x{:064x}", BLOCK - 1))
+            .execute(&pool)
+            .await
+            .expect("write staged block");
+        }
+        let (visible,): (bool,) =
+            sqlx::query_as("SELECT EXISTS (SELECT 1 FROM blocks WHERE number = $1)")
+                .bind(BLOCK)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert!(!visible, "staged rows stay hidden until attached");
+
+        partitions::complete(&pool, PARTITION).await.unwrap();
+        // Completing again is a no-op.
+        partitions::complete(&pool, PARTITION).await.unwrap();
+
+        assert!(!partitions::staged(&pool)
+            .await
+            .unwrap()
+            .contains(&PARTITION));
+        for table in PARTITIONED_TABLES {
+            assert_eq!(
+                index_count(&pool, &format!("{table}_p{PARTITION}")).await,
+                index_count(&pool, table).await,
+                "{table} partition has every parent index"
+            );
+        }
+        let (hash,): (String,) = sqlx::query_as("SELECT hash FROM blocks WHERE number = $1")
+            .bind(BLOCK)
+            .fetch_one(&pool)
+            .await
+            .expect("attached block");
+        assert_eq!(hash, format!("0x{:064x}", BLOCK));
+
+        drop_partition(&pool).await;
+    });
+}
+
+async fn block_hash(pool: &sqlx::PgPool, table: &str, number: u64) -> Option<String> {
+    sqlx::query_as::<_, (String,)>(&format!("SELECT hash FROM {table} WHERE number = $1"))
+        .bind(number as i64)
+        .fetch_optional(pool)
+        .await
+        .expect("read block")
+        .map(|(hash,)| hash)
+}
+
+#[test]
+fn backfill_writes_into_a_staged_partition() {
+    common::run(async {
+        let pool = common::pool();
+        drop_partition_tables(&pool, BACKFILL_PARTITION).await;
+        sqlx::query("DELETE FROM processed_blocks WHERE number = $1")
+            .bind(BACKFILL_BLOCK as i64)
+            .execute(&pool)
+            .await
+            .expect("clear processed block");
+
+        partitions::create_staged(&pool, BACKFILL_PARTITION)
+            .await
+            .unwrap();
+        assert_eq!(
+            partitions::staged_partition_of(&pool, BACKFILL_BLOCK)
+                .await
+                .unwrap(),
+            Some(BACKFILL_PARTITION)
+        );
+
+        common::index_block_via_backfill(
+            &pool,
+            BACKFILL_BLOCK,
+            empty_block_response(BACKFILL_BLOCK),
+            Processors::default(),
+        )
+        .await;
+
+        let hash = format!("0x{:064x}", BACKFILL_BLOCK);
+        assert_eq!(
+            block_hash(&pool, "blocks_p901", BACKFILL_BLOCK).await,
+            Some(hash.clone())
+        );
+        assert_eq!(block_hash(&pool, "blocks", BACKFILL_BLOCK).await, None);
+
+        partitions::complete(&pool, BACKFILL_PARTITION)
+            .await
+            .unwrap();
+        assert_eq!(
+            partitions::staged_partition_of(&pool, BACKFILL_BLOCK)
+                .await
+                .unwrap(),
+            None
+        );
+        assert_eq!(
+            block_hash(&pool, "blocks", BACKFILL_BLOCK).await,
+            Some(hash)
+        );
+
+        drop_partition_tables(&pool, BACKFILL_PARTITION).await;
+        sqlx::query("DELETE FROM processed_blocks WHERE number = $1")
+            .bind(BACKFILL_BLOCK as i64)
+            .execute(&pool)
+            .await
+            .expect("clear processed block");
+    });
+}
@@ -0,0 +1,171 @@
+use alloy::primitives::keccak256;
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+};
+use serde_json::json;
+use std::time::Duration;
+use tower::ServiceExt;
+use wiremock::matchers::method;
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use atlas_server::indexer::PendingVerificationMatcher;
+use atlas_server::state_keys::PENDING_VERIFICATION_LAST_BLOCK_KEY;
+
+use crate::common;
+
+// Blocks 3_963_000_000..: contracts created after registration
+
+const DEPLOYED: &str = "0x0000000000000000000000000000000000396300";
+const CODE: &str = "0x6080604052348015600e575f80fd5b50600436106026575f3560e01c";
+const FIRST_BLOCK: i64 = 3_963_000_000;
+
+async fn set_state(pool: &sqlx::PgPool, key: &str, value: &str) {
+    sqlx::query(
+        "INSERT INTO indexer_state (key, value, updated_at)
+         VALUES ($1, $2, NOW())
+         ON CONFLICT (key) DO UPDATE SET value = EXCLUDED.value, updated_at = EXCLUDED.updated_at",
+    )
+    .bind(key)
+    .bind(value)
+    .execute(pool)
+    .await
+    .expect("set indexer state");
+}
+
+async fn clear(pool: &sqlx::PgPool, code_hash: &str) {
+    for (sql, value) in [
+        ("DELETE FROM contract_abis WHERE address = $1", DEPLOYED),
+        ("DELETE FROM addresses WHERE address = $1", DEPLOYED),
+        (
+            "DELETE FROM pending_verifications WHERE code_hash = $1",
+            code_hash,
+        ),
+        (
+            "DELETE FROM indexer_state WHERE key = $1",
+            PENDING_VERIFICATION_LAST_BLOCK_KEY,
+        ),
+    ] {
+        sqlx::query(sql)
+            .bind(value)
+            .execute(pool)
+            .await
+            .expect("clear pending verification data");
+    }
+}
+
+#[test]
+fn matcher_verifies_contracts_deployed_with_registered_code() {
+    common::run(async {
+        let _head = common::INDEXED_HEAD.lock().await;
+        let pool = common::pool();
+        let code = alloy::hex::decode(CODE).unwrap();
+        let code_hash = format!("{:#x}", keccak256(&code));
+        clear(&pool, &code_hash).await;
+
+        let head: Option<String> =
+            sqlx::query_scalar("SELECT value FROM indexer_state WHERE key = 'last_indexed_block'")
+                .fetch_optional(&pool)
+                .await
+                .expect("read head");
+
+        sqlx::query(
+            "INSERT INTO pending_verifications (code_hash, abi, source_code, compiler_version, contract_name)
+             VALUES ($1, '[]', 'contract Counter {}', 'v0.8.24+commit.e11b9ed9', 'Counter')",
+        )
+        .bind(&code_hash)
+        .execute(&pool)
+        .await
+        .expect("register pending verification");
+        sqlx::query(
+            "INSERT INTO addresses (address, is_contract, first_seen_block, creation_block)
+             VALUES ($1, true, $2, $2)",
+        )
+        .bind(DEPLOYED)
+        .bind(FIRST_BLOCK + 10)
+        .execute(&pool)
+        .await
+        .expect("seed deployed contract");
+        set_state(
+            &pool,
+            PENDING_VERIFICATION_LAST_BLOCK_KEY,
+            &FIRST_BLOCK.to_string(),
+        )
+        .await;
+        set_state(&pool, "last_indexed_block", &(FIRST_BLOCK + 20).to_string()).await;
+
+        let rpc = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(json!([{"jsonrpc": "2.0", "id": 0, "result": CODE}])),
+            )
+            .mount(&rpc)
+            .await;
+
+        let matcher =
+            PendingVerificationMatcher::new(pool.clone(), &rpc.uri(), Duration::from_secs(1))
+                .unwrap();
+        let verified = matcher.match_new_contracts().await;
+
+        match &head {
+            Some(head) => set_state(&pool, "last_indexed_block", head).await,
+            None => {
+                sqlx::query("DELETE FROM indexer_state WHERE key = 'last_indexed_block'")
+                    .execute(&pool)
+                    .await
+                    .expect("restore head");
+            }
+        }
+        assert_eq!(verified.expect("match new contracts"), 1);
+
+        let (name, strategy): (String, String) = sqlx::query_as(
+            "SELECT contract_name, match_strategy FROM contract_abis WHERE address = $1",
+        )
+        .bind(DEPLOYED)
+        .fetch_one(&pool)
+        .await
+        .expect("verified contract");
+        assert_eq!(name, "Counter");
+        assert_eq!(strategy, "exact");
+
+        let matched: i64 = sqlx::query_scalar(
+            "SELECT matched_contracts FROM pending_verifications WHERE code_hash = $1",
+        )
+        .bind(&code_hash)
+        .fetch_one(&pool)
+        .await
+        .expect("registration");
+        assert_eq!(matched, 1);
+
+        let watermark: String =
+            sqlx::query_scalar("SELECT value FROM indexer_state WHERE key = $1")
+                .bind(PENDING_VERIFICATION_LAST_BLOCK_KEY)
+                .fetch_one(&pool)
+                .await
+                .expect("watermark");
+        assert_eq!(watermark, (FIRST_BLOCK + 20).to_string());
+
+        clear(&pool, &code_hash).await;
+    });
+}
+
+#[test]
+fn get_unknown_pending_verification_returns_404() {
+    common::run(async {
+        let app = common::test_router();
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri(format!(
+                        "/api/contracts/pending-verifications/0x{}",
+                        "39".repeat(32)
+                    ))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    });
+}
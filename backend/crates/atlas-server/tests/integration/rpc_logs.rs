@@ -0,0 +1,221 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    Router,
+};
+use serde_json::{json, Value};
+use std::sync::Arc;
+use tower::ServiceExt;
+
+use atlas_common::Block;
+use atlas_server::head::HeadTracker;
+
+use crate::common;
+
+// Block range: 13000-13999
+
+const TRANSFER: &str = "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef";
+const APPROVAL: &str = "0x8c5be1e5ebec7d5bd14f71427d1e84f3dd0314c0f7b2291e5b200ac8c7c3b925";
+const TOKEN_A: &str = "0x1300000000000000000000000000000000000001";
+const TOKEN_B: &str = "0x1300000000000000000000000000000000000002";
+/// Indexed head served by [`router`]; block 13006 is stored but not yet
+/// reported as indexed.
+const HEAD: i64 = 13005;
+
+fn block_hash(number: i64) -> String {
+    format!("0x{:064x}", number)
+}
+
+fn topic(value: u64) -> String {
+    format!("0x{:064x}", value)
+}
+
+async fn seed_logs(pool: &sqlx::PgPool) {
+    for number in 13000..=13006i64 {
+        sqlx::query(
+            "INSERT INTO blocks (number, hash, parent_hash, timestamp, gas_used, gas_limit, transaction_count, indexed_at)
+             VALUES ($1, $2, $3, $4, 21000, 30000000, 1, NOW())
+             ON CONFLICT (number) DO NOTHING",
+        )
+        .bind(number)
+        .bind(block_hash(number))
+        .bind(block_hash(number - 1))
+        .bind(1_700_013_000i64 + number)
+        .execute(pool)
+        .await
+        .expect("seed block");
+    }
+
+    for (tx, block) in [(0x13001, 13001i64), (0x13003, 13003), (0x13006, 13006)] {
+        sqlx::query(
+            "INSERT INTO transactions (hash, block_number, block_index, from_address, to_address, value, gas_price, gas_used, input_data, status, timestamp)
+             VALUES ($1, $2, 3, '0x1300000000000000000000000000000000000009', NULL, 0, 1, 21000, '\\x', true, 1700013000)
+             ON CONFLICT (hash, block_number) DO NOTHING",
+        )
+        .bind(topic(tx))
+        .bind(block)
+        .execute(pool)
+        .await
+        .expect("seed transaction");
+    }
+
+    for (tx, log_index, address, topic0, topic1, block) in [
+        (0x13001, 0, TOKEN_A, TRANSFER, Some(topic(0xa)), 13001i64),
+        (0x13001, 1, TOKEN_B, APPROVAL, None, 13001),
+        (0x13003, 0, TOKEN_A, TRANSFER, Some(topic(0xb)), 13003),
+        (0x13006, 0, TOKEN_A, TRANSFER, Some(topic(0xa)), 13006),
+    ] {
+        sqlx::query(
+            "INSERT INTO event_logs (tx_hash, log_index, address, topic0, topic1, data, block_number)
+             VALUES (decode(substr($1, 3), 'hex'), $2, decode(substr($3, 3), 'hex'), decode(substr($4, 3), 'hex'), decode(substr($5, 3), 'hex'), '\\x01', $6)
+             ON CONFLICT (tx_hash, log_index, block_number) DO NOTHING",
+        )
+        .bind(topic(tx))
+        .bind(log_index)
+        .bind(address)
+        .bind(topic0)
+        .bind(topic1)
+        .bind(block)
+        .execute(pool)
+        .await
+        .expect("seed event log");
+    }
+}
+
+async fn router() -> Router {
+    let head_tracker = Arc::new(HeadTracker::empty(10));
+    head_tracker
+        .publish_committed_batch(vec![Block {
+            number: HEAD,
+            hash: block_hash(HEAD),
+            parent_hash: block_hash(HEAD - 1),
+            timestamp: 1_700_013_000 + HEAD,
+            gas_used: 21_000,
+            gas_limit: 30_000_000,
+            base_fee_per_gas: None,
+            transaction_count: 0,
+            indexed_at: chrono::Utc::now(),
+        }])
+        .await;
+    common::test_router_with_head(head_tracker)
+}
+
+async fn get_logs(app: Router, filter: Value) -> (StatusCode, Option<Value>) {
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/api/rpc/logs")
+                .header("content-type", "application/json")
+                .body(Body::from(filter.to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    let status = response.status();
+    let body = if status == StatusCode::OK {
+        Some(common::json_body(response).await)
+    } else {
+        None
+    };
+    (status, body)
+}
+
+#[test]
+fn rpc_logs_return_eth_get_logs_format_up_to_the_indexed_head() {
+    common::run(async {
+        let pool = common::pool();
+        seed_logs(&pool).await;
+
+        let (status, body) = get_logs(
+            router().await,
+            json!({ "fromBlock": "0x32c9", "toBlock": "latest", "address": TOKEN_A }),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::OK);
+        let logs = body.unwrap();
+        let logs = logs.as_array().unwrap();
+        // The log in block 13006 is above the indexed head.
+        assert_eq!(logs.len(), 2);
+        assert_eq!(
+            logs[0],
+            json!({
+                "address": TOKEN_A,
+                "topics": [TRANSFER, topic(0xa)],
+                "data": "0x01",
+                "blockNumber": "0x32c9",
+                "blockHash": block_hash(13001),
+                "transactionHash": topic(0x13001),
+                "transactionIndex": "0x3",
+                "logIndex": "0x0",
+                "removed": false,
+            })
+        );
+        assert_eq!(logs[1]["blockNumber"], "0x32cb");
+    });
+}
+
+#[test]
+fn rpc_logs_filter_by_topics_and_block_hash() {
+    common::run(async {
+        let pool = common::pool();
+        seed_logs(&pool).await;
+
+        let (status, body) = get_logs(
+            router().await,
+            json!({
+                "fromBlock": "0x32c8",
+                "toBlock": "0x32cd",
+                "topics": [[TRANSFER, APPROVAL], [topic(0xa)]],
+            }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        let logs = body.unwrap();
+        let logs = logs.as_array().unwrap();
+        assert_eq!(logs.len(), 1);
+        assert_eq!(logs[0]["transactionHash"], topic(0x13001));
+        assert_eq!(logs[0]["logIndex"], "0x0");
+
+        let (status, body) =
+            get_logs(router().await, json!({ "blockHash": block_hash(13001) })).await;
+        assert_eq!(status, StatusCode::OK);
+        let logs = body.unwrap();
+        let addresses: Vec<&str> = logs
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|log| log["address"].as_str().unwrap())
+            .collect();
+        assert_eq!(addresses, [TOKEN_A, TOKEN_B]);
+    });
+}
+
+#[test]
+fn rpc_logs_reject_invalid_filters() {
+    common::run(async {
+        let pool = common::pool();
+        seed_logs(&pool).await;
+
+        // fromBlock after toBlock
+        let (status, _) = get_logs(
+            router().await,
+            json!({ "fromBlock": "0x32cb", "toBlock": "0x32c9" }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        // Wider than the default 10000-block limit
+        let (status, _) = get_logs(
+            router().await,
+            json!({ "fromBlock": "earliest", "toBlock": "latest" }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+
+        let (status, _) = get_logs(router().await, json!({ "blockHash": topic(0x13999) })).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    });
+}
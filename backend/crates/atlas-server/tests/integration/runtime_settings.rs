@@ -0,0 +1,82 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    Router,
+};
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+use crate::common;
+
+async fn send(
+    app: &Router,
+    method: &str,
+    body: Option<Value>,
+    key: Option<&str>,
+) -> (StatusCode, Value) {
+    let mut builder = Request::builder()
+        .method(method)
+        .uri("/api/admin/runtime-settings")
+        .header("content-type", "application/json");
+    if let Some(key) = key {
+        builder = builder.header("authorization", format!("Bearer {key}"));
+    }
+    let body = body.map_or_else(Body::empty, |body| Body::from(body.to_string()));
+    let response = app
+        .clone()
+        .oneshot(builder.body(body).unwrap())
+        .await
+        .unwrap();
+    let status = response.status();
+    let body = if status == StatusCode::OK {
+        common::json_body(response).await
+    } else {
+        Value::Null
+    };
+    (status, body)
+}
+
+#[test]
+fn admin_can_read_and_patch_runtime_settings() {
+    common::run(async {
+        let app = common::test_router();
+        let key = Some(common::ADMIN_API_KEY);
+
+        let (status, body) = send(&app, "GET", None, key).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["batch_size"], 100);
+        assert_eq!(body["rpc_requests_per_second"], 100);
+
+        let patch = json!({"batch_size": 500, "metadata_fetch_workers": 8});
+        let (status, body) = send(&app, "PATCH", Some(patch), key).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["batch_size"], 500);
+        assert_eq!(body["metadata_fetch_workers"], 8);
+        assert_eq!(body["rpc_batch_size"], 20);
+
+        let (_, body) = send(&app, "GET", None, key).await;
+        assert_eq!(body["batch_size"], 500);
+    });
+}
+
+#[test]
+fn runtime_settings_reject_invalid_values_and_missing_key() {
+    common::run(async {
+        let app = common::test_router();
+        let key = Some(common::ADMIN_API_KEY);
+
+        let (status, _) = send(&app, "PATCH", Some(json!({"batch_size": 0})), key).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        let patch = json!({"log_level": "atlas_server=loud"});
+        let (status, _) = send(&app, "PATCH", Some(patch), key).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let (status, _) = send(&app, "GET", None, None).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        let (status, _) = send(&app, "PATCH", Some(json!({"batch_size": 5})), None).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+        let (_, body) = send(&app, "GET", None, key).await;
+        assert_eq!(body["batch_size"], 100);
+    });
+}
@@ -0,0 +1,136 @@
+use axum::http::StatusCode;
+use serde_json::json;
+
+use atlas_server::indexer::ProcessorRegistry;
+
+use super::common;
+use super::gap_fill::empty_block_response;
+
+// Block range: 22000-22999
+
+const BLOCK: u64 = 22_000;
+const SAFE: &str = "0x00000000000000000000000000000000000022aa";
+const OWNER_A: &str = "0x0000000000000000000000000000000000002201";
+const OWNER_B: &str = "0x0000000000000000000000000000000000002202";
+const OWNER_C: &str = "0x0000000000000000000000000000000000002203";
+const SAFE_SETUP_TOPIC: &str = "0x141df868a6331af528e38c83b7aa03edc19be66e37ae67f9285bf4f8e3c6a1a8";
+const ADDED_OWNER_TOPIC: &str =
+    "0x9465fa0c962cc76958e6373a993326400c1c94f8be2fe3a952adfa7f60b2ea26";
+const REMOVED_OWNER_TOPIC: &str =
+    "0xf8d49fc529812e9a7c5c50e69c20f0dccc0db8fa95c98bc58cc9a4f1c1299eaf";
+const CHANGED_THRESHOLD_TOPIC: &str =
+    "0x610f7ff2b304ae8903c3de74c60c6ab1f7d6226b3f52c5161905bb5ad4039c93";
+const EXECUTION_SUCCESS_TOPIC: &str =
+    "0x442e715f626346e8c54381002da614f62bee8d27386535b2521ec8540898556e";
+const EXECUTION_FAILURE_TOPIC: &str =
+    "0x23428b18acfb3ea64b08dc0c1d296ea9c09702c09083ca5272e64d115b687d23";
+
+fn word(hex: &str) -> String {
+    format!("{:0>64}", hex.trim_start_matches("0x"))
+}
+
+fn safe_log(topics: &[String], words: &[String], log_index: u64) -> serde_json::Value {
+    json!({
+        "address": SAFE,
+        "topics": topics,
+        "data": format!("0x{}", words.concat()),
+        "blockNumber": format!("0x{:x}", BLOCK),
+        "transactionHash": format!("0x{:064x}", 0x2200),
+        "transactionIndex": "0x0",
+        "blockHash": format!("0x{:064x}", BLOCK),
+        "logIndex": format!("0x{:x}", log_index),
+        "removed": false
+    })
+}
+
+/// The block with one transaction setting up a Safe with owners A and B,
+/// adding C (Safe 1.3 layout), removing A (Safe 1.4 layout), raising the
+/// threshold to 2 and executing two Safe transactions.
+fn block_with_safe() -> serde_json::Value {
+    let topic = |t: &str| t.to_string();
+    let mut response = empty_block_response(BLOCK);
+    response[1]["result"] = json!([{
+        "transactionHash": format!("0x{:064x}", 0x2200),
+        "transactionIndex": "0x0",
+        "blockHash": format!("0x{:064x}", BLOCK),
+        "blockNumber": format!("0x{:x}", BLOCK),
+        "from": OWNER_A,
+        "to": SAFE,
+        "cumulativeGasUsed": "0x5208",
+        "gasUsed": "0x5208",
+        "contractAddress": null,
+        "logs": [
+            safe_log(
+                &[topic(SAFE_SETUP_TOPIC), format!("0x{}", word(OWNER_A))],
+                &[word("80"), word("1"), word("0"), word("0"), word("2"), word(OWNER_A), word(OWNER_B)],
+                0,
+            ),
+            safe_log(&[topic(ADDED_OWNER_TOPIC)], &[word(OWNER_C)], 1),
+            safe_log(&[topic(REMOVED_OWNER_TOPIC), format!("0x{}", word(OWNER_A))], &[], 2),
+            safe_log(&[topic(CHANGED_THRESHOLD_TOPIC)], &[word("2")], 3),
+            safe_log(&[topic(EXECUTION_SUCCESS_TOPIC)], &[word("2211"), word("5")], 4),
+            safe_log(
+                &[topic(EXECUTION_FAILURE_TOPIC), format!("0x{}", word("2212"))],
+                &[word("0")],
+                5,
+            ),
+        ],
+        "logsBloom": format!("0x{}", "00".repeat(256)),
+        "type": "0x2",
+        "effectiveGasPrice": "0x1",
+        "status": "0x1"
+    }]);
+    response
+}
+
+async fn clear(pool: &sqlx::PgPool) {
+    for sql in [
+        "DELETE FROM blocks WHERE number = 22000",
+        "DELETE FROM safe_events WHERE block_number BETWEEN 22000 AND 22999",
+        "DELETE FROM safe_executions WHERE block_number BETWEEN 22000 AND 22999",
+    ] {
+        sqlx::query(sql)
+            .execute(pool)
+            .await
+            .expect("clear safe data");
+    }
+}
+
+#[test]
+fn safe_owners_and_executions_are_decoded() {
+    common::run(async {
+        let pool = common::pool();
+        clear(&pool).await;
+        let processors = ProcessorRegistry::builtin()
+            .build(&["safes".to_string()])
+            .expect("build processors");
+        common::index_block_via_backfill(&pool, BLOCK, block_with_safe(), processors).await;
+
+        let (status, safe) = common::get_json(&format!("/api/safes/{SAFE}")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(safe["address"], SAFE);
+        assert_eq!(safe["setup_block"], BLOCK);
+        assert_eq!(safe["owners"], json!([OWNER_B, OWNER_C]));
+        assert_eq!(safe["threshold"], 2);
+        assert_eq!(safe["executions"], 2);
+        assert_eq!(safe["failed_executions"], 1);
+        let recent = safe["recent_executions"].as_array().unwrap();
+        assert_eq!(recent.len(), 2);
+        assert_eq!(recent[0]["safe_tx_hash"], format!("0x{}", word("2212")));
+        assert_eq!(recent[0]["success"], false);
+        assert_eq!(recent[1]["payment"], "5");
+
+        let (status, page) =
+            common::get_json(&format!("/api/safes/{SAFE}/executions?limit=1")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(page["data"].as_array().unwrap().len(), 1);
+        assert_eq!(page["has_more"], true);
+
+        let (status, _) = common::get_json(&format!("/api/safes/{OWNER_A}")).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        let (status, _) = common::get_json("/api/safes/0x1234/executions").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        clear(&pool).await;
+    });
+}
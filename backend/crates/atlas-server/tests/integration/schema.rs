@@ -8,13 +8,28 @@ fn migrations_are_idempotent() {
     // This verifies the tracking table is intact and no migration errors on repeat.
     common::run(async {
         let pool = common::pool();
-        sqlx::migrate!("../../migrations")
+        atlas_migrations::MIGRATOR
             .run(&pool)
             .await
             .expect("migrations should be idempotent");
     });
 }
 
+#[test]
+fn migration_info_matches_embedded_migrations() {
+    common::run(async {
+        let pool = common::pool();
+        let info = atlas_common::db::migration_info(&pool)
+            .await
+            .expect("migration info");
+
+        let embedded: Vec<i64> = atlas_migrations::embedded().map(|m| m.version).collect();
+        assert_eq!(info.applied, embedded);
+        assert!(info.compatible, "{info:?}");
+        assert!(info.checksum_mismatches.is_empty());
+    });
+}
+
 // ── Table presence ────────────────────────────────────────────────────────────
 
 #[test]
@@ -130,6 +145,26 @@ fn key_indexes_exist() {
     });
 }
 
+#[test]
+fn event_log_hashes_and_addresses_are_bytea() {
+    common::run(async {
+        let pool = common::pool();
+        let columns: Vec<(String, String)> = sqlx::query_as(
+            "SELECT column_name::text, data_type::text FROM information_schema.columns
+             WHERE table_schema = 'public' AND table_name = 'event_logs'
+               AND column_name IN ('tx_hash', 'address', 'topic0', 'topic1', 'topic2', 'topic3')",
+        )
+        .fetch_all(&pool)
+        .await
+        .expect("query columns");
+
+        assert_eq!(columns.len(), 6);
+        for (column, data_type) in columns {
+            assert_eq!(data_type, "bytea", "event_logs.{column}");
+        }
+    });
+}
+
 #[test]
 fn pg_trgm_extension_is_installed() {
     // Required for fuzzy search indexes on token names / symbols.
@@ -265,3 +300,60 @@ fn duplicate_erc20_transfer_is_rejected() {
         );
     });
 }
+
+// ── Data migrations ───────────────────────────────────────────────────────────
+
+#[test]
+fn legacy_mixed_case_addresses_are_lowercased() {
+    const MIXED: &str = "0xABCDEF0000000000000000000000000000039900";
+    const TAKEN: &str = "0xABCDEF0000000000000000000000000000039901";
+    common::run(async {
+        let pool = common::pool();
+        let lower = [MIXED.to_lowercase(), TAKEN.to_lowercase()];
+        sqlx::query("DELETE FROM address_labels WHERE lower(address) = ANY($1)")
+            .bind(&lower[..])
+            .execute(&pool)
+            .await
+            .expect("clear labels");
+        // TAKEN's lowercase form already has a label, so it cannot move.
+        for (address, name) in [
+            (MIXED, "Legacy"),
+            (TAKEN, "Legacy"),
+            (&*lower[1], "Current"),
+        ] {
+            sqlx::query("INSERT INTO address_labels (address, name) VALUES ($1, $2)")
+                .bind(address)
+                .bind(name)
+                .execute(&pool)
+                .await
+                .expect("seed label");
+        }
+
+        let _: i64 = sqlx::query_scalar("SELECT lowercase_legacy_addresses()")
+            .fetch_one(&pool)
+            .await
+            .expect("lowercase legacy addresses");
+
+        let labels: Vec<(String, String)> = sqlx::query_as(
+            "SELECT address, name FROM address_labels WHERE lower(address) = ANY($1) ORDER BY address COLLATE \"C\"",
+        )
+        .bind(&lower[..])
+        .fetch_all(&pool)
+        .await
+        .expect("labels");
+        assert_eq!(
+            labels,
+            [
+                (TAKEN.to_string(), "Legacy".to_string()),
+                (lower[0].clone(), "Legacy".to_string()),
+                (lower[1].clone(), "Current".to_string()),
+            ]
+        );
+
+        sqlx::query("DELETE FROM address_labels WHERE lower(address) = ANY($1)")
+            .bind(&lower[..])
+            .execute(&pool)
+            .await
+            .expect("clear labels");
+    });
+}
@@ -52,7 +52,7 @@ async fn seed_search_data(pool: &sqlx::PgPool) {
     sqlx::query(
         "INSERT INTO tx_hash_lookup (hash, block_number)
          VALUES ($1, $2)
-         ON CONFLICT (hash) DO NOTHING",
+         ON CONFLICT (hash, block_number) DO NOTHING",
     )
     .bind(SEARCH_TX_HASH)
     .bind(SEARCH_BLOCK)
@@ -162,3 +162,109 @@ fn search_by_address() {
         assert_eq!(results[0]["address"].as_str().unwrap(), SEARCH_ADDR);
     });
 }
+
+/// Three "Quokka" ERC-20 tokens and a "Quokka Treasury" label.
+async fn seed_text_search_data(pool: &sqlx::PgPool) {
+    for i in 1..=3 {
+        sqlx::query(
+            "INSERT INTO erc20_contracts (address, name, symbol, decimals, total_supply, first_seen_block)
+             VALUES ($1, $2, $3, 18, 0, $4)
+             ON CONFLICT (address) DO NOTHING",
+        )
+        .bind(format!("0x30000000000000000000000000000000000000a{i}"))
+        .bind(format!("Quokka Token {i}"))
+        .bind(format!("QKA{i}"))
+        .bind(3000i64 + i)
+        .execute(pool)
+        .await
+        .expect("seed erc20 contract");
+    }
+
+    sqlx::query(
+        "INSERT INTO address_labels (address, name, tags)
+         VALUES ($1, 'Quokka Treasury', ARRAY['treasury'])
+         ON CONFLICT (address) DO NOTHING",
+    )
+    .bind(SEARCH_ADDR)
+    .execute(pool)
+    .await
+    .expect("seed address label");
+}
+
+async fn search_json(query: &str) -> (StatusCode, serde_json::Value) {
+    let response = common::test_router()
+        .oneshot(
+            Request::builder()
+                .uri(format!("/api/search?{query}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let status = response.status();
+    (status, common::json_body(response).await)
+}
+
+fn result_types(body: &serde_json::Value) -> Vec<String> {
+    body["results"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|result| result["type"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[test]
+fn search_filters_by_type_and_paginates_per_type() {
+    common::run(async {
+        seed_text_search_data(&common::pool()).await;
+
+        let (status, body) = search_json("q=quokka").await;
+        assert_eq!(status, StatusCode::OK);
+        let types = result_types(&body);
+        assert_eq!(types.iter().filter(|t| *t == "erc20_token").count(), 3);
+        assert!(types.contains(&"label".to_string()));
+
+        let (_, body) = search_json("q=quokka&type=label").await;
+        assert_eq!(result_types(&body), vec!["label"]);
+        assert_eq!(body["results"][0]["address"], SEARCH_ADDR);
+        assert_eq!(body["results"][0]["tags"][0], "treasury");
+        assert_eq!(body["pages"][0]["type"], "label");
+
+        let (_, first) = search_json("q=quokka&type=token&limit=2").await;
+        assert_eq!(result_types(&first), vec!["erc20_token"; 2]);
+        assert_eq!(first["pages"][0]["has_more"], true);
+
+        let (_, second) = search_json("q=quokka&type=token&limit=2&page=2").await;
+        assert_eq!(result_types(&second), vec!["erc20_token"]);
+        assert_eq!(second["pages"][0]["has_more"], false);
+        assert_ne!(
+            first["results"][0]["address"],
+            second["results"][0]["address"]
+        );
+
+        // Exact-match types respect the filter too.
+        let (_, body) = search_json(&format!("q={SEARCH_ADDR}&type=token")).await;
+        assert!(result_types(&body).iter().all(|t| t == "erc20_token"));
+
+        let (status, _) = search_json("q=quokka&type=bogus").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    });
+}
+
+#[test]
+fn search_suggest_mode_caps_results_without_pages() {
+    common::run(async {
+        let pool = common::pool();
+        seed_search_data(&pool).await;
+        seed_text_search_data(&pool).await;
+
+        let (status, body) = search_json("q=quokka&suggest=true&type=token").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(result_types(&body).len(), 3);
+        assert!(body.get("pages").is_none());
+
+        let (_, body) = search_json(&format!("q={SEARCH_ADDR}&suggest=true")).await;
+        assert_eq!(result_types(&body), vec!["address"]);
+    });
+}
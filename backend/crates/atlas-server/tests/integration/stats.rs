@@ -0,0 +1,413 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+};
+use chrono::NaiveDate;
+use tower::ServiceExt;
+
+use crate::common;
+use atlas_server::indexer::daily_stats;
+
+// Block range: 10000-10999, all on 2030-01-01 (UTC)
+
+const DAY_START: i64 = 1_893_456_000;
+const SENDER: &str = "0xa000000000000000000000000000000000000001";
+const RECIPIENT: &str = "0xa000000000000000000000000000000000000002";
+const CREATED: &str = "0xa000000000000000000000000000000000000003";
+const TOKEN: &str = "0xa000000000000000000000000000000000000004";
+const COLLECTION: &str = "0xa000000000000000000000000000000000000005";
+const ZERO: &str = "0x0000000000000000000000000000000000000000";
+
+async fn seed_day(pool: &sqlx::PgPool) {
+    for (number, gas_used) in [(10000i64, 100_000i64), (10001, 50_000)] {
+        sqlx::query(
+            "INSERT INTO blocks (number, hash, parent_hash, timestamp, gas_used, gas_limit, transaction_count, indexed_at)
+             VALUES ($1, $2, $3, $4, $5, 30000000, 1, NOW())
+             ON CONFLICT (number) DO NOTHING",
+        )
+        .bind(number)
+        .bind(format!("0x{:064x}", number))
+        .bind(format!("0x{:064x}", number - 1))
+        .bind(DAY_START + (number - 10000) * 3600)
+        .bind(gas_used)
+        .execute(pool)
+        .await
+        .expect("seed block");
+    }
+
+    // A transfer at 10 gwei and a contract creation at 30 gwei.
+    for (number, to, created, gas_price) in [
+        (10000i64, Some(RECIPIENT), None, 10_000_000_000i64),
+        (10001, None, Some(CREATED), 30_000_000_000),
+    ] {
+        sqlx::query(
+            "INSERT INTO transactions (hash, block_number, block_index, from_address, to_address, value, gas_price, gas_used, input_data, status, contract_created, timestamp)
+             VALUES ($1, $2, 0, $3, $4, 0, $5, 21000, ''::bytea, TRUE, $6, $7)
+             ON CONFLICT (hash, block_number) DO NOTHING",
+        )
+        .bind(format!("0x{:064x}", 0xa0000 + number))
+        .bind(number)
+        .bind(SENDER)
+        .bind(to)
+        .bind(gas_price)
+        .bind(created)
+        .bind(DAY_START + (number - 10000) * 3600)
+        .execute(pool)
+        .await
+        .expect("seed transaction");
+    }
+
+    sqlx::query(
+        "INSERT INTO erc20_contracts (address, first_seen_block) VALUES ($1, 10000)
+         ON CONFLICT (address) DO NOTHING",
+    )
+    .bind(TOKEN)
+    .execute(pool)
+    .await
+    .expect("seed erc20 contract");
+    sqlx::query(
+        "INSERT INTO nft_contracts (address, first_seen_block) VALUES ($1, 10001)
+         ON CONFLICT (address) DO NOTHING",
+    )
+    .bind(COLLECTION)
+    .execute(pool)
+    .await
+    .expect("seed nft contract");
+
+    for (log_index, from) in [(0i32, ZERO), (1, RECIPIENT)] {
+        sqlx::query(
+            "INSERT INTO nft_transfers (tx_hash, log_index, contract_address, token_id, from_address, to_address, block_number, timestamp)
+             VALUES ($1, $2, $3, $2, $4, $5, 10001, $6)
+             ON CONFLICT DO NOTHING",
+        )
+        .bind(format!("0x{:064x}", 0xa0000 + 10001))
+        .bind(log_index)
+        .bind(COLLECTION)
+        .bind(from)
+        .bind(SENDER)
+        .bind(DAY_START + 3600)
+        .execute(pool)
+        .await
+        .expect("seed nft transfer");
+    }
+}
+
+#[test]
+fn daily_stats_summarize_aggregated_day() {
+    common::run(async {
+        let pool = common::pool();
+        seed_day(&pool).await;
+        daily_stats::aggregate_day(&pool, NaiveDate::from_ymd_opt(2030, 1, 1).unwrap())
+            .await
+            .expect("aggregate day");
+
+        let app = common::test_router();
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/stats/daily?from=2030-01-01&to=2030-01-02")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = common::json_body(response).await;
+        let days = body.as_array().unwrap();
+        assert_eq!(days.len(), 1);
+        let day = &days[0];
+        assert_eq!(day["day"], "2030-01-01");
+        assert_eq!(day["block_count"], 2);
+        assert_eq!(day["tx_count"], 2);
+        assert_eq!(day["active_addresses"], 2);
+        assert_eq!(day["new_contracts"], 1);
+        assert_eq!(day["gas_used"], "150000");
+        assert_eq!(day["avg_gas_price"], "20000000000");
+        assert_eq!(day["new_tokens"], 2);
+        assert_eq!(day["nft_mints"], 1);
+    });
+}
+
+#[test]
+fn daily_stats_reject_inverted_range() {
+    common::run(async {
+        let app = common::test_router();
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/stats/daily?from=2030-01-02&to=2030-01-01")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    });
+}
+
+// Account abstraction stats: block range 12000-12999
+
+const ENTRY_POINT_V06: &str = "0x5ff137d4b0fdcd49dca30c7cf57e578a026d2789";
+const ENTRY_POINT_V07: &str = "0x0000000071727de22e5e9d8baf0edac6f37da032";
+const USER_OPERATION_EVENT: &str =
+    "0x49628fd1471006c1482da88028e9ce4dbb080b815c9b0344d39e5a8e6ec1419f";
+const BUNDLER_A: &str = "0xc000000000000000000000000000000000000001";
+const BUNDLER_B: &str = "0xc000000000000000000000000000000000000002";
+const ACCOUNT_1: &str = "0xc000000000000000000000000000000000000011";
+const ACCOUNT_2: &str = "0xc000000000000000000000000000000000000012";
+const PAYMASTER: &str = "0xc000000000000000000000000000000000000021";
+const IMPOSTOR: &str = "0xc000000000000000000000000000000000000099";
+/// 2^64 + 2^63: exercises the unsigned handling of a full 64-bit chunk.
+const BIG_GAS_COST: u128 = (1 << 64) + (1 << 63);
+
+fn topic_for(address: &str) -> String {
+    format!("0x{:0>64}", address.trim_start_matches("0x"))
+}
+
+/// `UserOperationEvent` data: nonce, success, actualGasCost, actualGasUsed.
+fn user_op_data(success: bool, gas_cost: u128) -> Vec<u8> {
+    let mut data = vec![0u8; 128];
+    data[63] = success as u8;
+    data[80..96].copy_from_slice(&gas_cost.to_be_bytes());
+    data[127] = 1;
+    data
+}
+
+async fn seed_user_ops(pool: &sqlx::PgPool) {
+    for (number, bundler) in [(12000i64, BUNDLER_A), (12001, BUNDLER_B)] {
+        sqlx::query(
+            "INSERT INTO transactions (hash, block_number, block_index, from_address, to_address, value, gas_price, gas_used, input_data, status, timestamp)
+             VALUES ($1, $2, 0, $3, $4, 0, 1, 21000, ''::bytea, TRUE, $5)
+             ON CONFLICT (hash, block_number) DO NOTHING",
+        )
+        .bind(format!("0x{:064x}", 0xc0000 + number))
+        .bind(number)
+        .bind(bundler)
+        .bind(ENTRY_POINT_V07)
+        .bind(DAY_START + 86_400 * 40)
+        .execute(pool)
+        .await
+        .expect("seed handleOps transaction");
+    }
+
+    let ops = [
+        (
+            12000i64,
+            0,
+            ENTRY_POINT_V07,
+            ACCOUNT_1,
+            Some(PAYMASTER),
+            true,
+            BIG_GAS_COST,
+        ),
+        (12000, 1, ENTRY_POINT_V07, ACCOUNT_2, None, false, 1_000),
+        (
+            12001,
+            0,
+            ENTRY_POINT_V06,
+            ACCOUNT_1,
+            Some(PAYMASTER),
+            true,
+            500,
+        ),
+        // Same event from a contract that is not an EntryPoint
+        (12001, 1, IMPOSTOR, ACCOUNT_2, Some(PAYMASTER), true, 7),
+    ];
+    for (number, log_index, emitter, sender, paymaster, success, gas_cost) in ops {
+        sqlx::query(
+            "INSERT INTO event_logs (tx_hash, log_index, address, topic0, topic1, topic2, topic3, data, block_number)
+             VALUES (decode(substr($1, 3), 'hex'), $2, decode(substr($3, 3), 'hex'), decode(substr($4, 3), 'hex'), decode(substr($5, 3), 'hex'),
+                     decode(substr($6, 3), 'hex'), decode(substr($7, 3), 'hex'), $8, $9)
+             ON CONFLICT (tx_hash, log_index, block_number) DO NOTHING",
+        )
+        .bind(format!("0x{:064x}", 0xc0000 + number))
+        .bind(log_index)
+        .bind(emitter)
+        .bind(USER_OPERATION_EVENT)
+        .bind(format!("0x{:064x}", number * 10 + log_index as i64))
+        .bind(topic_for(sender))
+        .bind(topic_for(paymaster.unwrap_or(ZERO)))
+        .bind(user_op_data(success, gas_cost))
+        .bind(number)
+        .execute(pool)
+        .await
+        .expect("seed user operation event");
+    }
+}
+
+#[test]
+fn aa_stats_aggregate_paymasters_and_bundlers() {
+    common::run(async {
+        let pool = common::pool();
+        seed_user_ops(&pool).await;
+
+        let app = common::test_router();
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/stats/aa?from_block=12000&to_block=12001")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = common::json_body(response).await;
+        assert_eq!(body["from_block"], 12000);
+        assert_eq!(body["user_ops"], 3);
+        assert_eq!(body["successful_user_ops"], 2);
+        assert_eq!(body["sponsored_user_ops"], 2);
+        assert_eq!(body["senders"], 2);
+        assert_eq!(body["bundles"], 2);
+        assert_eq!(
+            body["total_gas_cost"],
+            (BIG_GAS_COST + 1_500).to_string().as_str()
+        );
+        assert_eq!(
+            body["sponsored_gas_cost"],
+            (BIG_GAS_COST + 500).to_string().as_str()
+        );
+
+        let paymasters = body["paymasters"].as_array().unwrap();
+        assert_eq!(paymasters.len(), 1);
+        assert_eq!(paymasters[0]["address"], PAYMASTER);
+        assert_eq!(paymasters[0]["user_ops"], 2);
+        assert_eq!(paymasters[0]["success_rate"], 1.0);
+
+        let bundlers = body["bundlers"].as_array().unwrap();
+        assert_eq!(bundlers.len(), 2);
+        assert_eq!(bundlers[0]["address"], BUNDLER_A);
+        assert_eq!(bundlers[0]["bundles"], 1);
+        assert_eq!(bundlers[0]["user_ops"], 2);
+        assert_eq!(bundlers[0]["success_rate"], 0.5);
+        assert_eq!(bundlers[1]["address"], BUNDLER_B);
+    });
+}
+
+#[test]
+fn aa_stats_reject_inverted_block_range() {
+    common::run(async {
+        let app = common::test_router();
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/stats/aa?from_block=12001&to_block=12000")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    });
+}
+
+#[test]
+fn block_times_report_percentiles_and_stalls() {
+    common::run(async {
+        let pool = common::pool();
+        // Blocks 10500-10505 two seconds apart, except a 30s stall before 10504.
+        let start = DAY_START - 10 * 86_400;
+        for (number, offset) in [
+            (10500i64, 0i64),
+            (10501, 2),
+            (10502, 4),
+            (10503, 6),
+            (10504, 36),
+            (10505, 38),
+        ] {
+            sqlx::query(
+                "INSERT INTO blocks (number, hash, parent_hash, timestamp, gas_used, gas_limit, transaction_count, indexed_at)
+                 VALUES ($1, $2, $3, $4, 0, 30000000, 0, NOW())
+                 ON CONFLICT (number) DO NOTHING",
+            )
+            .bind(number)
+            .bind(format!("0x{:064x}", number))
+            .bind(format!("0x{:064x}", number - 1))
+            .bind(start + offset)
+            .execute(&pool)
+            .await
+            .expect("seed block");
+        }
+
+        let app = common::test_router();
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/stats/block-times?from_block=10500&to_block=10505&stall_secs=10")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = common::json_body(response).await;
+
+        assert_eq!(body["blocks"], 6);
+        assert_eq!(body["p50_secs"], 2.0);
+        assert_eq!(body["max_secs"], 30);
+        assert_eq!(body["avg_secs"], 7.6);
+        assert_eq!(body["stall_threshold_secs"], 10);
+        assert_eq!(body["stalls"], 1);
+        let longest = &body["longest_gaps"][0];
+        assert_eq!(longest["block_number"], 10504);
+        assert_eq!(longest["gap_secs"], 30);
+        assert_eq!(body["longest_gaps"].as_array().unwrap().len(), 5);
+    });
+}
+
+#[test]
+fn fee_stats_sum_fees_per_day_and_block() {
+    common::run(async {
+        let pool = common::pool();
+        seed_day(&pool).await;
+        daily_stats::aggregate_day(&pool, NaiveDate::from_ymd_opt(2030, 1, 1).unwrap())
+            .await
+            .expect("aggregate day");
+        let app = common::test_router();
+
+        // 21000 gas at 10 gwei and at 30 gwei; no base fee or L1 fee.
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri("/api/stats/fees?from=2030-01-01&to=2030-01-02")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = common::json_body(response).await;
+        let days = body["days"].as_array().unwrap();
+        assert_eq!(days.len(), 1);
+        assert_eq!(days[0]["day"], "2030-01-01");
+        assert_eq!(days[0]["tx_count"], 2);
+        assert_eq!(days[0]["execution_fees"], "840000000000000");
+        assert_eq!(days[0]["base_fees"], "0");
+        assert_eq!(days[0]["l1_fees"], "0");
+        assert_eq!(days[0]["total_fees"], "840000000000000");
+        assert_eq!(body["totals"]["total_fees"], "840000000000000");
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/stats/fees/blocks?from_block=10000&to_block=10001")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = common::json_body(response).await;
+        let blocks = body.as_array().unwrap();
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0]["block_number"], 10001);
+        assert_eq!(blocks[0]["tx_count"], 1);
+        assert_eq!(blocks[0]["total_fees"], "630000000000000");
+        assert_eq!(blocks[1]["total_fees"], "210000000000000");
+    });
+}
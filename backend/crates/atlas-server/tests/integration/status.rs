@@ -2,7 +2,10 @@ use axum::{
     body::Body,
     http::{Request, StatusCode},
 };
+use serde_json::json;
 use tower::ServiceExt;
+use wiremock::matchers::{body_partial_json, method};
+use wiremock::{Mock, MockServer, ResponseTemplate};
 
 use crate::common;
 
@@ -80,7 +83,70 @@ fn status_returns_chain_info() {
         let body = common::json_body(response).await;
         assert_eq!(body["chain_id"].as_str().unwrap(), "42");
         assert_eq!(body["chain_name"].as_str().unwrap(), "Test Chain");
+        assert_eq!(
+            body["native_currency"],
+            serde_json::json!({"symbol": "ETH", "decimals": 18})
+        );
         assert!(body["block_height"].is_i64());
+        assert_eq!(body["version"], env!("CARGO_PKG_VERSION"));
+        assert_eq!(body["schema"]["compatible"], true);
+        assert_eq!(
+            body["schema"]["expected_version"],
+            atlas_migrations::latest_version()
+        );
+        assert_eq!(
+            body["schema"]["applied_version"],
+            atlas_migrations::latest_version()
+        );
+        assert_eq!(body["schema"]["pending"], serde_json::json!([]));
+        // The test router has no RPC to probe
+        assert_eq!(body["health"]["rpc"]["status"], "down");
+        assert_eq!(body["health"]["rpc"]["error"], "no RPC URL configured");
+        assert_eq!(body["health"]["status"], "down");
+        assert_eq!(body["health"]["api"]["status"], "ok");
+    });
+}
+
+#[test]
+fn status_reports_rpc_head_and_sync_state() {
+    common::run(async {
+        let rpc = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({"method": "eth_blockNumber"})))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(json!({"jsonrpc": "2.0", "id": 1, "result": "0x2a"})),
+            )
+            .mount(&rpc)
+            .await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({"method": "eth_syncing"})))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "jsonrpc": "2.0", "id": 1,
+                "result": {"startingBlock": "0x0", "currentBlock": "0x2a", "highestBlock": "0x64"}
+            })))
+            .mount(&rpc)
+            .await;
+
+        let response = common::test_router_with_rpc(rpc.uri())
+            .oneshot(
+                Request::builder()
+                    .uri("/api/status")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = common::json_body(response).await;
+        let rpc_health = &body["health"]["rpc"];
+        assert_eq!(rpc_health["status"], "degraded");
+        assert_eq!(rpc_health["head_block"], 42);
+        assert_eq!(rpc_health["syncing"], true);
+        assert!(rpc_health["latency_ms"].is_u64());
+        assert!(rpc_health.get("error").is_none());
+        assert_ne!(body["health"]["status"], "ok");
     });
 }
 
@@ -122,3 +188,118 @@ fn stats_charts_return_exact_bucket_count_for_non_aligned_window() {
         assert_eq!(gas_body.as_array().unwrap().len(), 12);
     });
 }
+
+#[test]
+fn overview_returns_homepage_summary() {
+    common::run(async {
+        let pool = common::pool();
+        seed_chart_data(&pool).await;
+
+        let app = common::test_router();
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/overview?limit=3")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = common::json_body(response).await;
+
+        let blocks = body["latest_blocks"].as_array().unwrap();
+        assert!(!blocks.is_empty() && blocks.len() <= 3);
+        let numbers: Vec<i64> = blocks
+            .iter()
+            .map(|b| b["number"].as_i64().unwrap())
+            .collect();
+        assert!(numbers.windows(2).all(|w| w[0] > w[1]));
+        assert_eq!(body["block_height"].as_i64(), Some(numbers[0]));
+
+        let txs = body["latest_transactions"].as_array().unwrap();
+        assert!(!txs.is_empty() && txs.len() <= 3);
+        assert!(body["total_transactions"].is_i64());
+        assert!(body["total_addresses"].is_i64());
+        // The test router runs without an indexer, so the chain head is unknown.
+        assert!(body["chain_height"].is_null());
+        assert!(body["indexer_lag_blocks"].is_null());
+    });
+}
+
+#[test]
+fn db_stats_requires_admin_and_reports_pools() {
+    common::run(async {
+        let app = common::test_router();
+        let request = |auth: Option<&str>| {
+            let mut builder = Request::builder().uri("/api/admin/db-stats");
+            if let Some(key) = auth {
+                builder = builder.header("authorization", format!("Bearer {key}"));
+            }
+            builder.body(Body::empty()).unwrap()
+        };
+
+        let response = app.clone().oneshot(request(None)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let response = app
+            .oneshot(request(Some(common::ADMIN_API_KEY)))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = common::json_body(response).await;
+
+        let pools = body["pools"].as_array().unwrap();
+        assert_eq!(pools.len(), 1);
+        assert_eq!(pools[0]["name"], "api");
+        assert_eq!(pools[0]["max_connections"], 10);
+        assert_eq!(pools[0]["acquire_timeout_secs"], 30);
+        assert!(pools[0]["in_use"].is_u64());
+    });
+}
+
+#[test]
+fn slow_queries_reports_latency_by_route() {
+    common::run(async {
+        let app = common::test_router();
+        for _ in 0..3 {
+            let response = app
+                .clone()
+                .oneshot(
+                    Request::builder()
+                        .uri("/api/height")
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+        }
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/admin/slow-queries?limit=5")
+                    .header("authorization", format!("Bearer {}", common::ADMIN_API_KEY))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = common::json_body(response).await;
+
+        assert_eq!(body["threshold_ms"], 1000);
+        let endpoints = body["endpoints"].as_array().unwrap();
+        assert!(endpoints.len() <= 5);
+        let height = endpoints
+            .iter()
+            .find(|endpoint| endpoint["route"] == "/api/height")
+            .expect("height route tracked");
+        assert_eq!(height["method"], "GET");
+        assert_eq!(height["requests"], 3);
+        assert!(height["p50_ms"].as_f64().unwrap() <= height["p99_ms"].as_f64().unwrap());
+        assert!(body["slow_requests"].is_array());
+    });
+}
@@ -2,11 +2,15 @@ use axum::{
     body::Body,
     http::{Request, StatusCode},
 };
+use serde_json::json;
 use tower::ServiceExt;
 
 use crate::common;
+use atlas_server::indexer::processors::Processors;
 use atlas_server::state_keys::ERC20_SUPPLY_HISTORY_COMPLETE_KEY;
 
+use super::gap_fill::empty_block_response;
+
 // Block range: 6000-6999
 
 const TOKEN_A: &str = "0x6000000000000000000000000000000000000001";
@@ -316,6 +320,57 @@ fn get_tx_erc20_transfers() {
         assert_eq!(data[0]["contract_address"].as_str().unwrap(), TOKEN_A);
         assert_eq!(data[0]["from_address"].as_str().unwrap(), HOLDER_1);
         assert_eq!(data[0]["to_address"].as_str().unwrap(), HOLDER_2);
+        assert!(data[0].get("display").is_none());
+    });
+}
+
+#[test]
+fn include_display_adds_decimal_adjusted_amounts() {
+    common::run(async {
+        let pool = common::pool();
+        seed_token_data(&pool).await;
+
+        let response = common::test_router()
+            .oneshot(
+                Request::builder()
+                    .uri(format!(
+                        "/api/transactions/{}/erc20-transfers?include=display",
+                        TX_HASH
+                    ))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = common::json_body(response).await;
+        let transfer = &body["data"][0];
+        assert_eq!(transfer["value"], "50000");
+        assert_eq!(transfer["display"]["value"], "0.00000000000005");
+        assert!(transfer["display"]["age_seconds"].as_i64().unwrap() > 0);
+
+        let response = common::test_router()
+            .oneshot(
+                Request::builder()
+                    .uri(format!(
+                        "/api/addresses/{}/tokens?include=display",
+                        HOLDER_1
+                    ))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = common::json_body(response).await;
+        let balance = body["data"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|b| b["contract_address"] == TOKEN_A)
+            .expect("token A balance");
+        assert_eq!(balance["display"]["value"], "0.0000000000007");
+        assert!(balance["display"].get("age_seconds").is_none());
     });
 }
 
@@ -342,3 +397,406 @@ fn get_token_chart_returns_exact_bucket_count_for_non_aligned_window() {
         assert_eq!(body.as_array().unwrap().len(), 12);
     });
 }
+
+const DUP_REAL: &str = "0x6000000000000000000000000000000000000101";
+const DUP_FAKE: &str = "0x6000000000000000000000000000000000000102";
+const DUP_SCAM: &str = "0x6000000000000000000000000000000000000103";
+
+/// Three tokens sharing the symbol DUPX; the real one has the most holders.
+async fn seed_duplicate_symbol_tokens(pool: &sqlx::PgPool) {
+    sqlx::query("DELETE FROM token_curation WHERE address = ANY($1)")
+        .bind(vec![DUP_REAL, DUP_FAKE, DUP_SCAM])
+        .execute(pool)
+        .await
+        .expect("reset token curation");
+
+    for (i, (address, symbol)) in [(DUP_REAL, "DUPX"), (DUP_FAKE, "dupx"), (DUP_SCAM, "DUPX")]
+        .into_iter()
+        .enumerate()
+    {
+        sqlx::query(
+            "INSERT INTO erc20_contracts (address, name, symbol, decimals, first_seen_block)
+             VALUES ($1, $2, $3, 18, $4)
+             ON CONFLICT (address) DO NOTHING",
+        )
+        .bind(address)
+        .bind(format!("Dup Token {i}"))
+        .bind(symbol)
+        .bind(6100i64 + i as i64)
+        .execute(pool)
+        .await
+        .expect("seed duplicate symbol token");
+    }
+
+    for (holder, token) in [
+        (HOLDER_1, DUP_REAL),
+        (HOLDER_2, DUP_REAL),
+        (HOLDER_1, DUP_FAKE),
+    ] {
+        sqlx::query(
+            "INSERT INTO erc20_balances (address, contract_address, balance, last_updated_block)
+             VALUES ($1, $2, 1, 6100)
+             ON CONFLICT (address, contract_address) DO NOTHING",
+        )
+        .bind(holder)
+        .bind(token)
+        .execute(pool)
+        .await
+        .expect("seed duplicate symbol balance");
+    }
+
+    atlas_server::indexer::canonical_tokens::resolve_symbols(pool, Some("DUPX"))
+        .await
+        .expect("resolve DUPX");
+}
+
+async fn token_json(app: &axum::Router, address: &str) -> serde_json::Value {
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri(format!("/api/tokens/{address}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    common::json_body(response).await
+}
+
+async fn searched_token_addresses(app: &axum::Router) -> Vec<String> {
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/api/search?q=dupx&type=token")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    common::json_body(response).await["results"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|r| r["address"].as_str().unwrap().to_string())
+        .collect()
+}
+
+async fn curate(app: &axum::Router, method: &str, address: &str, admin: bool) -> StatusCode {
+    let mut builder = Request::builder()
+        .method(method)
+        .uri(format!("/api/admin/tokens/{address}/curation"))
+        .header("content-type", "application/json");
+    if admin {
+        builder = builder.header("authorization", format!("Bearer {}", common::ADMIN_API_KEY));
+    }
+    let status = if address == DUP_SCAM {
+        "flagged"
+    } else {
+        "verified"
+    };
+    let body = serde_json::json!({ "status": status, "note": "checked with issuer" });
+    app.clone()
+        .oneshot(builder.body(Body::from(body.to_string())).unwrap())
+        .await
+        .unwrap()
+        .status()
+}
+
+#[test]
+fn duplicate_symbols_resolve_to_a_canonical_token() {
+    common::run(async {
+        let pool = common::pool();
+        seed_duplicate_symbol_tokens(&pool).await;
+        let app = common::test_router();
+
+        // Most holders wins; the others point at it
+        let real = token_json(&app, DUP_REAL).await;
+        assert_eq!(real["verified"], false);
+        assert!(real["duplicate_of"].is_null());
+        let fake = token_json(&app, DUP_FAKE).await;
+        assert_eq!(fake["duplicate_of"].as_str(), Some(DUP_REAL));
+        assert_eq!(searched_token_addresses(&app).await[0], DUP_REAL);
+
+        // Admin verdicts override the heuristic
+        assert_eq!(
+            curate(&app, "PUT", DUP_FAKE, false).await,
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(curate(&app, "PUT", DUP_FAKE, true).await, StatusCode::OK);
+        assert_eq!(curate(&app, "PUT", DUP_SCAM, true).await, StatusCode::OK);
+
+        let fake = token_json(&app, DUP_FAKE).await;
+        assert_eq!(fake["verified"], true);
+        assert!(fake["duplicate_of"].is_null());
+        let real = token_json(&app, DUP_REAL).await;
+        assert_eq!(real["duplicate_of"].as_str(), Some(DUP_FAKE));
+        let scam = token_json(&app, DUP_SCAM).await;
+        assert_eq!(scam["flagged"], true);
+        let found = searched_token_addresses(&app).await;
+        assert_eq!(found.first().map(String::as_str), Some(DUP_FAKE));
+
+        // Clearing the verdict falls back to the heuristic
+        assert_eq!(
+            curate(&app, "DELETE", DUP_FAKE, true).await,
+            StatusCode::NO_CONTENT
+        );
+        let fake = token_json(&app, DUP_FAKE).await;
+        assert_eq!(fake["duplicate_of"].as_str(), Some(DUP_REAL));
+        assert_eq!(
+            curate(&app, "DELETE", DUP_FAKE, true).await,
+            StatusCode::NOT_FOUND
+        );
+    });
+}
+
+const RANKED_TOKEN: &str = "0x6000000000000000000000000000000000000201";
+
+async fn holders_page(app: &axum::Router, query: &str) -> serde_json::Value {
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri(format!("/api/tokens/{RANKED_TOKEN}/holders?{query}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    common::json_body(response).await
+}
+
+fn ranks(page: &serde_json::Value) -> Vec<(i64, String)> {
+    page["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|h| {
+            (
+                h["rank"].as_i64().unwrap(),
+                h["address"].as_str().unwrap().to_string(),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn token_holders_are_served_from_rankings_once_built() {
+    common::run(async {
+        let pool = common::pool();
+        sqlx::query(
+            "INSERT INTO erc20_contracts (address, name, symbol, decimals, first_seen_block)
+             VALUES ($1, 'Ranked', 'RNK', 0, 6200)
+             ON CONFLICT (address) DO NOTHING",
+        )
+        .bind(RANKED_TOKEN)
+        .execute(&pool)
+        .await
+        .expect("seed token");
+        for table in ["erc20_holder_ranks", "erc20_holder_rank_state"] {
+            sqlx::query(&format!("DELETE FROM {table} WHERE contract_address = $1"))
+                .bind(RANKED_TOKEN)
+                .execute(&pool)
+                .await
+                .expect("clear ranking");
+        }
+        // Ties are broken by address; zero balances are not holders.
+        let balances = [("0201a", 50), ("0201b", 900), ("0201c", 50), ("0201d", 0)];
+        for (suffix, balance) in balances {
+            sqlx::query(
+                "INSERT INTO erc20_balances (address, contract_address, balance, last_updated_block)
+                 VALUES ($1, $2, $3, 6200)
+                 ON CONFLICT (address, contract_address) DO UPDATE SET balance = EXCLUDED.balance",
+            )
+            .bind(format!("0x{suffix:0>40}"))
+            .bind(RANKED_TOKEN)
+            .bind(bigdecimal::BigDecimal::from(balance))
+            .execute(&pool)
+            .await
+            .expect("seed balance");
+        }
+        let holder = |suffix: &str| format!("0x{suffix:0>40}");
+        let expected = vec![
+            (1, holder("0201b")),
+            (2, holder("0201a")),
+            (3, holder("0201c")),
+        ];
+
+        // Not ranked yet: sorted from live balances, ranks follow the page.
+        let app = common::test_router();
+        let live = holders_page(&app, "limit=2&page=2").await;
+        assert_eq!(live["total"], 3);
+        assert_eq!(ranks(&live), expected[2..]);
+
+        atlas_server::indexer::holder_ranks::rank_token(&pool, RANKED_TOKEN, 6200)
+            .await
+            .expect("rank token");
+        let ranked = holders_page(&app, "limit=10").await;
+        assert_eq!(ranked["total"], 3);
+        assert_eq!(ranks(&ranked), expected);
+        let deep = holders_page(&app, "limit=2&page=2").await;
+        assert_eq!(ranks(&deep), expected[2..]);
+
+        // Balances moved since the ranking show up after the next refresh.
+        sqlx::query(
+            "UPDATE erc20_balances SET balance = 1000 WHERE address = $1 AND contract_address = $2",
+        )
+        .bind(holder("0201d"))
+        .bind(RANKED_TOKEN)
+        .execute(&pool)
+        .await
+        .expect("move balance");
+        assert_eq!(ranks(&holders_page(&app, "limit=10").await), expected);
+        atlas_server::indexer::holder_ranks::rank_token(&pool, RANKED_TOKEN, 6201)
+            .await
+            .expect("re-rank token");
+        let refreshed = holders_page(&app, "limit=10").await;
+        assert_eq!(refreshed["total"], 4);
+        assert_eq!(ranks(&refreshed)[0], (1, holder("0201d")));
+    });
+}
+
+const DRAIN_TOKEN: &str = "0x6000000000000000000000000000000000000301";
+const DRAIN_HOLDER_1: &str = "0x6000000000000000000000000000000000000310";
+const DRAIN_HOLDER_2: &str = "0x6000000000000000000000000000000000000311";
+const ZERO_ADDRESS: &str = "0x0000000000000000000000000000000000000000";
+
+/// Block `number` with one transaction whose receipt moves `value` DRAIN_TOKEN
+/// from `from` to `to`; a transfer from the zero address is a mint.
+fn drain_transfer_block(number: u64, from: &str, to: &str, value: u64) -> serde_json::Value {
+    let topic = |address: &str| format!("0x000000000000000000000000{}", &address[2..]);
+    let tx_hash = format!("0x{:064x}", number);
+    let mut response = empty_block_response(number);
+    response[1]["result"] = json!([{
+        "transactionHash": tx_hash,
+        "transactionIndex": "0x0",
+        "blockHash": format!("0x{:064x}", number),
+        "blockNumber": format!("0x{:x}", number),
+        "from": DRAIN_HOLDER_1,
+        "to": DRAIN_TOKEN,
+        "cumulativeGasUsed": "0x5208",
+        "gasUsed": "0x5208",
+        "contractAddress": null,
+        "logs": [{
+            "address": DRAIN_TOKEN,
+            "topics": [
+                "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef",
+                topic(from),
+                topic(to)
+            ],
+            "data": format!("0x{:064x}", value),
+            "blockNumber": format!("0x{:x}", number),
+            "transactionHash": tx_hash,
+            "transactionIndex": "0x0",
+            "blockHash": format!("0x{:064x}", number),
+            "logIndex": "0x0",
+            "removed": false
+        }],
+        "logsBloom": format!("0x{}", "00".repeat(256)),
+        "type": "0x2",
+        "effectiveGasPrice": "0x1",
+        "status": "0x1"
+    }]);
+    response
+}
+
+async fn clear_drained_token(pool: &sqlx::PgPool) {
+    for sql in [
+        "DELETE FROM blocks WHERE number BETWEEN 6300 AND 6302",
+        "DELETE FROM processed_blocks WHERE number BETWEEN 6300 AND 6302",
+        "DELETE FROM transactions WHERE block_number BETWEEN 6300 AND 6302",
+        "DELETE FROM tx_hash_lookup WHERE block_number BETWEEN 6300 AND 6302",
+        "DELETE FROM event_logs WHERE block_number BETWEEN 6300 AND 6302",
+        "DELETE FROM erc20_transfers WHERE block_number BETWEEN 6300 AND 6302",
+    ] {
+        sqlx::query(sql)
+            .execute(pool)
+            .await
+            .expect("clear drained token blocks");
+    }
+    sqlx::query("DELETE FROM erc20_balances WHERE contract_address = $1")
+        .bind(DRAIN_TOKEN)
+        .execute(pool)
+        .await
+        .expect("clear drained token balances");
+}
+
+#[test]
+fn drained_balances_are_pruned_and_recreated() {
+    common::run(async {
+        let pool = common::pool();
+        clear_drained_token(&pool).await;
+        sqlx::query(
+            "INSERT INTO erc20_contracts (address, name, symbol, decimals, first_seen_block)
+             VALUES ($1, 'Drained', 'DRN', 0, 6300)
+             ON CONFLICT (address) DO NOTHING",
+        )
+        .bind(DRAIN_TOKEN)
+        .execute(&pool)
+        .await
+        .expect("seed token");
+
+        let app = common::test_router();
+        let holders = || async {
+            let balances: Vec<(String, String)> = sqlx::query_as(
+                "SELECT address, balance::text FROM erc20_balances
+                 WHERE contract_address = $1 ORDER BY address",
+            )
+            .bind(DRAIN_TOKEN)
+            .fetch_all(&pool)
+            .await
+            .expect("balances");
+            let token = token_json(&app, DRAIN_TOKEN).await;
+            (balances, token["holder_count"].clone())
+        };
+        let row = |address: &str, balance: &str| (address.to_string(), balance.to_string());
+
+        // Mint 1000 to holder 1.
+        common::index_block_via_backfill(
+            &pool,
+            6300,
+            drain_transfer_block(6300, ZERO_ADDRESS, DRAIN_HOLDER_1, 1000),
+            Processors::default(),
+        )
+        .await;
+        assert_eq!(
+            holders().await,
+            (vec![row(DRAIN_HOLDER_1, "1000")], json!(1))
+        );
+
+        // Holder 1 sends everything to holder 2: its row is deleted.
+        common::index_block_via_backfill(
+            &pool,
+            6301,
+            drain_transfer_block(6301, DRAIN_HOLDER_1, DRAIN_HOLDER_2, 1000),
+            Processors::default(),
+        )
+        .await;
+        assert_eq!(
+            holders().await,
+            (vec![row(DRAIN_HOLDER_2, "1000")], json!(1))
+        );
+
+        // Crediting holder 1 again inserts a fresh row.
+        common::index_block_via_backfill(
+            &pool,
+            6302,
+            drain_transfer_block(6302, DRAIN_HOLDER_2, DRAIN_HOLDER_1, 400),
+            Processors::default(),
+        )
+        .await;
+        assert_eq!(
+            holders().await,
+            (
+                vec![row(DRAIN_HOLDER_1, "400"), row(DRAIN_HOLDER_2, "600")],
+                json!(2)
+            )
+        );
+
+        clear_drained_token(&pool).await;
+    });
+}
@@ -11,6 +11,7 @@ use crate::common;
 const TX_HASH_1: &str = "0x2000000000000000000000000000000000000000000000000000000000000001";
 const TX_HASH_2: &str = "0x2000000000000000000000000000000000000000000000000000000000000002";
 const TX_HASH_3: &str = "0x2000000000000000000000000000000000000000000000000000000000000003";
+const L2_TX_HASH: &str = "0x2000000000000000000000000000000000000000000000000000000000000004";
 const FROM_ADDR: &str = "0x2000000000000000000000000000000000000001";
 const TO_ADDR: &str = "0x2000000000000000000000000000000000000002";
 
@@ -101,6 +102,88 @@ fn get_transaction_by_hash() {
         assert_eq!(body["hash"].as_str().unwrap(), TX_HASH_1);
         assert_eq!(body["block_number"].as_i64().unwrap(), 2000);
         assert!(body["status"].as_bool().unwrap());
+        assert_eq!(body["fee"]["execution_fee"], "420000000000000");
+        assert_eq!(body["fee"]["total_fee"], "420000000000000");
+        assert!(body["fee"]["l1_fee"].is_null());
+        assert!(body.get("display").is_none());
+    });
+}
+
+#[test]
+fn transactions_include_native_display_values() {
+    common::run(async {
+        let pool = common::pool();
+        seed_transactions(&pool).await;
+
+        let response = common::test_router()
+            .oneshot(
+                Request::builder()
+                    .uri(format!("/api/transactions/{TX_HASH_1}?include=display"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = common::json_body(response).await;
+        assert_eq!(body["display"]["value"], "1");
+        assert_eq!(body["display"]["fee"], "0.00042");
+        assert_eq!(body["display"]["symbol"], "ETH");
+
+        let response = common::test_router()
+            .oneshot(
+                Request::builder()
+                    .uri("/api/blocks/2000/transactions?include=display")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = common::json_body(response).await;
+        let first = &body["data"][0];
+        assert_eq!(first["display"]["value"], "1");
+        assert_eq!(first["display"]["fee"], "0.00042");
+    });
+}
+
+#[test]
+fn get_transaction_includes_l1_fee_breakdown() {
+    common::run(async {
+        let pool = common::pool();
+        sqlx::query(
+            "INSERT INTO transactions (hash, block_number, block_index, from_address, to_address, value, gas_price, gas_used, input_data, status, timestamp,
+                                       l1_fee, l1_gas_used, l1_gas_price, l1_blob_base_fee, l1_base_fee_scalar, l1_blob_base_fee_scalar)
+             VALUES ($1, 2001, 0, $2, $3, 0, 1000, 21000, ''::bytea, TRUE, 1700002001, 5000000, 1600, 30000000000, 1, 1368, 810949)
+             ON CONFLICT (hash, block_number) DO NOTHING",
+        )
+        .bind(L2_TX_HASH)
+        .bind(FROM_ADDR)
+        .bind(TO_ADDR)
+        .execute(&pool)
+        .await
+        .expect("seed L2 transaction");
+
+        let app = common::test_router();
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri(format!("/api/transactions/{}", L2_TX_HASH))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let fee = &common::json_body(response).await["fee"];
+        assert_eq!(fee["execution_fee"], "21000000");
+        assert_eq!(fee["l1_fee"], "5000000");
+        assert_eq!(fee["total_fee"], "26000000");
+        assert_eq!(fee["l1_gas_used"], 1600);
+        assert_eq!(fee["l1_gas_price"], "30000000000");
+        assert_eq!(fee["l1_base_fee_scalar"], 1368);
+        assert!(fee["l1_fee_scalar"].is_null());
     });
 }
 
@@ -151,3 +234,243 @@ fn get_block_transactions() {
         assert!(idx0 < idx1 && idx1 < idx2);
     });
 }
+
+#[test]
+fn get_transaction_by_block_and_index() {
+    common::run(async {
+        let pool = common::pool();
+        seed_transactions(&pool).await;
+
+        let app = common::test_router();
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri("/api/blocks/2000/transactions/1")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = common::json_body(response).await;
+        assert_eq!(body["hash"], TX_HASH_2);
+        assert_eq!(body["block_index"], 1);
+        assert_eq!(body["fee"]["total_fee"], "420000000000000");
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/blocks/2000/transactions/9")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    });
+}
+
+#[test]
+fn etherscan_proxy_serves_transaction_by_block_and_index() {
+    common::run(async {
+        let pool = common::pool();
+        seed_transactions(&pool).await;
+
+        // No RPC is configured: the proxy action is answered from the database.
+        let app = common::test_router();
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri("/api?module=proxy&action=eth_getTransactionByBlockNumberAndIndex&tag=0x7d0&index=0x2")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = common::json_body(response).await;
+        let tx = &body["result"];
+        assert_eq!(tx["hash"], TX_HASH_3);
+        assert_eq!(tx["blockHash"], format!("0x{:064x}", 2000));
+        assert_eq!(tx["blockNumber"], "0x7d0");
+        assert_eq!(tx["transactionIndex"], "0x2");
+        assert_eq!(tx["from"], FROM_ADDR);
+        assert_eq!(tx["to"], TO_ADDR);
+        assert_eq!(tx["value"], "0xde0b6b3a7640000");
+        assert_eq!(tx["gasPrice"], "0x4a817c800");
+        assert_eq!(tx["input"], "0x");
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api?module=proxy&action=eth_getTransactionByBlockNumberAndIndex&tag=2000&index=9")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let body = common::json_body(response).await;
+        assert!(body["result"].is_null());
+    });
+}
+
+#[test]
+fn transaction_transfers_page_in_a_stable_order() {
+    common::run(async {
+        let pool = common::pool();
+        // The same transaction indexed in two blocks (one later reorged out),
+        // with its transfer at the same log index in both.
+        const REORGED_HASH: &str =
+            "0x2000000000000000000000000000000000000000000000000000000000000005";
+        for block in [2003i64, 2002] {
+            sqlx::query(
+                "INSERT INTO erc20_transfers (tx_hash, log_index, contract_address, from_address, to_address, value, block_number, timestamp)
+                 VALUES ($1, 0, $2, $3, $2, 5, $4, 1700002000)
+                 ON CONFLICT (tx_hash, log_index, block_number) DO NOTHING",
+            )
+            .bind(REORGED_HASH)
+            .bind(TO_ADDR)
+            .bind(FROM_ADDR)
+            .bind(block)
+            .execute(&pool)
+            .await
+            .expect("seed transfer");
+        }
+
+        let mut blocks = Vec::new();
+        for page in 1..=2 {
+            let response = common::test_router()
+                .oneshot(
+                    Request::builder()
+                        .uri(format!(
+                            "/api/transactions/{REORGED_HASH}/erc20-transfers?page={page}&limit=1"
+                        ))
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            let body = common::json_body(response).await;
+            blocks.push(body["data"][0]["block_number"].as_i64().unwrap());
+        }
+        assert_eq!(blocks, [2002, 2003]);
+    });
+}
+
+#[test]
+fn list_transactions_by_input_prefix() {
+    common::run(async {
+        let pool = common::pool();
+        atlas_server::indexer::calldata_index::ensure(&pool)
+            .await
+            .expect("build calldata index");
+
+        // Two calls to the same selector with different first arguments, and
+        // one with a selector that only shares its first byte.
+        let selector = [0x20u8, 0x10, 0xab, 0xcd];
+        let mut to_a = selector.to_vec();
+        to_a.extend([0xaa; 32]);
+        to_a.extend([0x01; 32]);
+        let mut to_b = selector.to_vec();
+        to_b.extend([0xbb; 32]);
+        let other = vec![0x20u8, 0x10, 0xab, 0xce];
+        let seeds = [
+            (
+                "0x2000000000000000000000000000000000000000000000000000000000000010",
+                0,
+                to_a,
+            ),
+            (
+                "0x2000000000000000000000000000000000000000000000000000000000000011",
+                1,
+                to_b,
+            ),
+            (
+                "0x2000000000000000000000000000000000000000000000000000000000000012",
+                2,
+                other,
+            ),
+        ];
+        for (hash, index, input) in seeds {
+            sqlx::query(
+                "INSERT INTO transactions (hash, block_number, block_index, from_address, to_address, value, gas_price, gas_used, input_data, status, timestamp)
+                 VALUES ($1, 2010, $2, $3, $4, 0, 1, 21000, $5, TRUE, 1700002010)
+                 ON CONFLICT (hash, block_number) DO NOTHING",
+            )
+            .bind(hash)
+            .bind(index)
+            .bind(FROM_ADDR)
+            .bind(TO_ADDR)
+            .bind(input)
+            .execute(&pool)
+            .await
+            .expect("seed transaction");
+        }
+
+        let hashes = |body: serde_json::Value| -> Vec<String> {
+            body["data"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|tx| tx["hash"].as_str().unwrap().to_string())
+                .collect()
+        };
+
+        let response = common::test_router()
+            .oneshot(
+                Request::builder()
+                    .uri("/api/transactions?input_prefix=0x2010abcd")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = common::json_body(response).await;
+        assert_eq!(body["has_more"], false);
+        assert_eq!(
+            hashes(body),
+            [
+                "0x2000000000000000000000000000000000000000000000000000000000000011",
+                "0x2000000000000000000000000000000000000000000000000000000000000010",
+            ]
+        );
+
+        // Selector plus the first argument word narrows to one call.
+        let response = common::test_router()
+            .oneshot(
+                Request::builder()
+                    .uri(format!(
+                        "/api/transactions?input_prefix=0x2010abcd{}",
+                        "aa".repeat(32)
+                    ))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            hashes(common::json_body(response).await),
+            ["0x2000000000000000000000000000000000000000000000000000000000000010"]
+        );
+
+        for prefix in ["2010abcd", "0x2010ab", "0xzz10abcd"] {
+            let response = common::test_router()
+                .oneshot(
+                    Request::builder()
+                        .uri(format!("/api/transactions?input_prefix={prefix}"))
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{prefix}");
+        }
+    });
+}
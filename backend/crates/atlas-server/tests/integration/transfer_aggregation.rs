@@ -0,0 +1,190 @@
+use axum::http::StatusCode;
+use serde_json::Value;
+
+use atlas_server::indexer::TransferAggregator;
+
+use crate::common;
+
+// Block range: 18000-18999
+
+const TOKEN: &str = "0x180000000000000000000000000000000000000a";
+const ALICE: &str = "0x1800000000000000000000000000000000000001";
+const BOB: &str = "0x1800000000000000000000000000000000000002";
+const CAROL: &str = "0x1800000000000000000000000000000000000003";
+/// 2030-02-01 00:00:00 UTC
+const DAY_START: i64 = 1_896_134_400;
+
+async fn seed(pool: &sqlx::PgPool) {
+    sqlx::query(
+        "INSERT INTO erc20_contracts (address, name, symbol, decimals, first_seen_block)
+         VALUES ($1, 'Retained', 'RTN', 18, 18000)
+         ON CONFLICT (address) DO NOTHING",
+    )
+    .bind(TOKEN)
+    .execute(pool)
+    .await
+    .expect("seed token");
+
+    // Reruns start from the same raw rows.
+    sqlx::query("DELETE FROM erc20_transfer_summaries WHERE contract_address = $1")
+        .bind(TOKEN)
+        .execute(pool)
+        .await
+        .expect("clear summaries");
+
+    for (block, from, to, value) in [
+        (18000_i64, ALICE, BOB, 10),
+        (18001, ALICE, BOB, 20),
+        (18002, BOB, CAROL, 5),
+        (18010, BOB, CAROL, 1),
+    ] {
+        sqlx::query(
+            "INSERT INTO erc20_transfers (tx_hash, log_index, contract_address, from_address, to_address, value, block_number, timestamp)
+             VALUES ($1, 0, $2, $3, $4, $5, $6, $7)
+             ON CONFLICT (tx_hash, log_index, block_number) DO NOTHING",
+        )
+        .bind(format!("0x{:064x}", block))
+        .bind(TOKEN)
+        .bind(from)
+        .bind(to)
+        .bind(bigdecimal::BigDecimal::from(value))
+        .bind(block)
+        .bind(DAY_START + (block - 18000) * 60)
+        .execute(pool)
+        .await
+        .expect("seed transfer");
+    }
+}
+
+async fn get(uri: &str) -> Value {
+    let (status, body) = common::get_json(uri).await;
+    assert_eq!(status, StatusCode::OK);
+    body
+}
+
+#[test]
+fn old_transfers_are_folded_into_daily_summaries() {
+    common::run(async {
+        let pool = common::pool();
+        seed(&pool).await;
+
+        let aggregator = TransferAggregator::new(pool.clone(), 1_000).unwrap();
+        assert_eq!(aggregator.aggregate_range(18000, 18003).await.unwrap(), 3);
+        assert_eq!(aggregator.aggregate_range(18000, 18003).await.unwrap(), 0);
+
+        let (raw,): (i64,) =
+            sqlx::query_as("SELECT COUNT(*) FROM erc20_transfers WHERE contract_address = $1")
+                .bind(TOKEN)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(raw, 1, "only the block past the cutoff keeps its raw row");
+
+        let summaries: Vec<(String, String, i64, String, i64, i64)> = sqlx::query_as(
+            "SELECT day::text, from_address, transfer_count, value::text, first_block, last_block
+             FROM erc20_transfer_summaries
+             WHERE contract_address = $1
+             ORDER BY from_address",
+        )
+        .bind(TOKEN)
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+        assert_eq!(
+            summaries,
+            vec![
+                (
+                    "2030-02-01".to_string(),
+                    ALICE.to_string(),
+                    2,
+                    "30".to_string(),
+                    18000,
+                    18001
+                ),
+                (
+                    "2030-02-01".to_string(),
+                    BOB.to_string(),
+                    1,
+                    "5".to_string(),
+                    18002,
+                    18002
+                ),
+            ]
+        );
+
+        let token = get(&format!("/api/tokens/{TOKEN}")).await;
+        assert_eq!(token["transfer_count"], 4);
+
+        let flows = get(&format!("/api/addresses/{BOB}/token-flows?period=all")).await;
+        let flow = &flows["tokens"][0];
+        assert_eq!(flow["contract_address"], TOKEN);
+        assert_eq!(flow["inbound"], "30");
+        assert_eq!(flow["outbound"], "6");
+        assert_eq!(flow["inbound_transfers"], 2);
+        assert_eq!(flow["outbound_transfers"], 2);
+    });
+}
+
+const CHART_TOKEN: &str = "0x180000000000000000000000000000000000000b";
+/// 2099-11-15 00:00:00 UTC: within a year of the chart test blocks, which
+/// anchor the chart window.
+const CHART_DAY_START: i64 = 4_098_384_000;
+
+/// Transfer count and volume summed over all of the token's chart buckets.
+async fn chart_totals(window: &str) -> (i64, f64) {
+    let points = get(&format!("/api/tokens/{CHART_TOKEN}/chart?window={window}")).await;
+    points
+        .as_array()
+        .unwrap()
+        .iter()
+        .fold((0, 0.0), |(count, volume), point| {
+            (
+                count + point["transfer_count"].as_i64().unwrap(),
+                volume + point["volume"].as_f64().unwrap(),
+            )
+        })
+}
+
+#[test]
+fn token_chart_counts_aggregated_transfers() {
+    common::run(async {
+        let pool = common::pool();
+        sqlx::query(
+            "INSERT INTO erc20_contracts (address, name, symbol, decimals, first_seen_block)
+             VALUES ($1, 'Charted', 'CHT', 0, 18100)
+             ON CONFLICT (address) DO NOTHING",
+        )
+        .bind(CHART_TOKEN)
+        .execute(&pool)
+        .await
+        .expect("seed token");
+        sqlx::query("DELETE FROM erc20_transfer_summaries WHERE contract_address = $1")
+            .bind(CHART_TOKEN)
+            .execute(&pool)
+            .await
+            .expect("clear summaries");
+        for (block, value) in [(18100_i64, 7), (18101, 8), (18110, 9)] {
+            sqlx::query(
+                "INSERT INTO erc20_transfers (tx_hash, log_index, contract_address, from_address, to_address, value, block_number, timestamp)
+                 VALUES ($1, 0, $2, $3, $4, $5, $6, $7)
+                 ON CONFLICT (tx_hash, log_index, block_number) DO NOTHING",
+            )
+            .bind(format!("0x{:064x}", block))
+            .bind(CHART_TOKEN)
+            .bind(ALICE)
+            .bind(BOB)
+            .bind(bigdecimal::BigDecimal::from(value))
+            .bind(block)
+            .bind(CHART_DAY_START + (block - 18100) * 60)
+            .execute(&pool)
+            .await
+            .expect("seed transfer");
+        }
+
+        assert_eq!(chart_totals("1y").await, (3, 24.0));
+
+        let aggregator = TransferAggregator::new(pool.clone(), 1_000).unwrap();
+        assert_eq!(aggregator.aggregate_range(18100, 18102).await.unwrap(), 2);
+        assert_eq!(chart_totals("1y").await, (3, 24.0));
+    });
+}
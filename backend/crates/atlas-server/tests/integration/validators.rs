@@ -0,0 +1,156 @@
+use axum::http::StatusCode;
+use serde_json::json;
+
+use alloy::json_abi::Event;
+use atlas_server::indexer::validators::ValidatorProcessor;
+use atlas_server::indexer::ProcessorRegistry;
+
+use super::common;
+use super::gap_fill::empty_block_response;
+
+// Block range: 23000-23999
+
+const BLOCK: u64 = 23_000;
+const REGISTRY: &str = "0x00000000000000000000000000000000000023aa";
+const VALIDATOR_A: &str = "0x0000000000000000000000000000000000002301";
+const VALIDATOR_B: &str = "0x0000000000000000000000000000000000002302";
+
+fn events() -> Vec<Event> {
+    [
+        "event SequencerRegistered(address indexed sequencer, uint256 stake)",
+        "event Staked(address indexed validator, uint256 amount)",
+        "event Unstaked(address indexed validator, uint256 amount)",
+        "event Jailed(address indexed validator)",
+    ]
+    .into_iter()
+    .map(|event| Event::parse(event).unwrap())
+    .collect()
+}
+
+fn registry_log(
+    event: usize,
+    validator: &str,
+    amount: Option<u64>,
+    log_index: u64,
+) -> serde_json::Value {
+    json!({
+        "address": REGISTRY,
+        "topics": [
+            events()[event].selector().to_string(),
+            format!("0x{:0>64}", validator.trim_start_matches("0x")),
+        ],
+        "data": amount.map(|a| format!("0x{a:064x}")).unwrap_or_else(|| "0x".to_string()),
+        "blockNumber": format!("0x{:x}", BLOCK),
+        "transactionHash": format!("0x{:064x}", 0x2300),
+        "transactionIndex": "0x0",
+        "blockHash": format!("0x{:064x}", BLOCK),
+        "logIndex": format!("0x{:x}", log_index),
+        "removed": false
+    })
+}
+
+/// A registers with 1000 and stakes 200 more, B registers with 500 and is
+/// jailed, then A unstakes 300.
+fn block_with_registry_events() -> serde_json::Value {
+    let mut response = empty_block_response(BLOCK);
+    response[1]["result"] = json!([{
+        "transactionHash": format!("0x{:064x}", 0x2300),
+        "transactionIndex": "0x0",
+        "blockHash": format!("0x{:064x}", BLOCK),
+        "blockNumber": format!("0x{:x}", BLOCK),
+        "from": VALIDATOR_A,
+        "to": REGISTRY,
+        "cumulativeGasUsed": "0x5208",
+        "gasUsed": "0x5208",
+        "contractAddress": null,
+        "logs": [
+            registry_log(0, VALIDATOR_A, Some(1000), 0),
+            registry_log(1, VALIDATOR_A, Some(200), 1),
+            registry_log(0, VALIDATOR_B, Some(500), 2),
+            registry_log(3, VALIDATOR_B, None, 3),
+            registry_log(2, VALIDATOR_A, Some(300), 4),
+        ],
+        "logsBloom": format!("0x{}", "00".repeat(256)),
+        "type": "0x2",
+        "effectiveGasPrice": "0x1",
+        "status": "0x1"
+    }]);
+    response
+}
+
+async fn clear(pool: &sqlx::PgPool) {
+    for sql in [
+        "DELETE FROM blocks WHERE number = 23000",
+        "DELETE FROM validator_events WHERE block_number BETWEEN 23000 AND 23999",
+    ] {
+        sqlx::query(sql)
+            .execute(pool)
+            .await
+            .expect("clear validator data");
+    }
+}
+
+#[test]
+fn validator_stakes_and_status_follow_registry_events() {
+    common::run(async {
+        let pool = common::pool();
+        clear(&pool).await;
+        let abi = serde_json::to_string(&events()).unwrap();
+        let mut registry = ProcessorRegistry::builtin();
+        registry.register("validators", move || {
+            Ok(Box::new(ValidatorProcessor::from_abi(REGISTRY, &abi)?))
+        });
+        let processors = registry.build(&["validators".to_string()]).unwrap();
+        common::index_block_via_backfill(&pool, BLOCK, block_with_registry_events(), processors)
+            .await;
+
+        let (status, page) = common::get_json("/api/validators").await;
+        assert_eq!(status, StatusCode::OK);
+        let validators: Vec<_> = page["data"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|v| {
+                (
+                    v["address"].clone(),
+                    v["status"].clone(),
+                    v["stake"].clone(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            validators,
+            [
+                (json!(VALIDATOR_A), json!("active"), json!("900")),
+                (json!(VALIDATOR_B), json!("jailed"), json!("500")),
+            ]
+        );
+
+        let (_, page) = common::get_json("/api/validators?status=jailed").await;
+        assert_eq!(page["data"].as_array().unwrap().len(), 1);
+        assert_eq!(page["data"][0]["address"], VALIDATOR_B);
+        let (status, _) = common::get_json("/api/validators?status=retired").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let (status, validator) = common::get_json(&format!("/api/validators/{VALIDATOR_A}")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(validator["events"], 3);
+        assert_eq!(validator["first_block"], BLOCK);
+        let recent = validator["recent_events"].as_array().unwrap();
+        assert_eq!(recent[0]["event"], "Unstaked");
+        assert_eq!(recent[0]["kind"], "unstake");
+        assert_eq!(recent[2]["kind"], "register");
+
+        let (status, page) =
+            common::get_json(&format!("/api/validators/{VALIDATOR_B}/events?limit=1")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(page["data"][0]["kind"], "jail");
+        assert!(page["data"][0]["amount"].is_null());
+        assert_eq!(page["has_more"], true);
+
+        let (status, _) = common::get_json(&format!("/api/validators/{REGISTRY}")).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        clear(&pool).await;
+    });
+}
@@ -1,5 +1,5 @@
 import client from './client';
-import type { Address, PaginatedResponse, AddressTransfer } from '../types';
+import type { Address, PaginatedResponse, AddressTransfer, DeployedContract, AddressNonces, AddressTokenFlows, TokenFlowPeriod, ApprovalRisks } from '../types';
 
 export async function getAddress(address: string): Promise<Address> {
   return client.get<Address>(`/addresses/${address}`);
@@ -18,12 +18,14 @@ export async function getAddresses(params: GetAddressesParams = {}): Promise<Pag
 }
 
 // Etherscan-compatible endpoint for native balance: GET /api?module=account&action=balance&address=...
-interface EtherscanLikeResponse<T> { result: T }
+// Caller errors come back as 200 with status "0" and an "Error! ..." result.
+interface EtherscanLikeResponse<T> { status: string; result: T }
 
 export async function getEthBalance(address: string): Promise<string> {
   const data = await client.get<EtherscanLikeResponse<string>>('', {
     params: { module: 'account', action: 'balance', address },
   });
+  if (data.status !== '1') throw new Error(data.result);
   return data.result ?? '0';
 }
 
@@ -36,3 +38,19 @@ export interface GetAddressTransfersParams {
 export async function getAddressTransfers(address: string, params: GetAddressTransfersParams = {}): Promise<PaginatedResponse<AddressTransfer>> {
   return client.get<PaginatedResponse<AddressTransfer>>(`/addresses/${address}/transfers`, { params: params as Record<string, unknown> });
 }
+
+export async function getDeployedContracts(address: string, params: { page?: number; limit?: number } = {}): Promise<PaginatedResponse<DeployedContract>> {
+  return client.get<PaginatedResponse<DeployedContract>>(`/addresses/${address}/deployed-contracts`, { params: params as Record<string, unknown> });
+}
+
+export async function getAddressNonces(address: string, params: { page?: number; limit?: number } = {}): Promise<AddressNonces> {
+  return client.get<AddressNonces>(`/addresses/${address}/nonces`, { params: params as Record<string, unknown> });
+}
+
+export async function getAddressTokenFlows(address: string, period: TokenFlowPeriod = '30d'): Promise<AddressTokenFlows> {
+  return client.get<AddressTokenFlows>(`/addresses/${address}/token-flows`, { params: { period } });
+}
+
+export async function getApprovalRisks(address: string): Promise<ApprovalRisks> {
+  return client.get<ApprovalRisks>(`/addresses/${address}/approval-risks`);
+}
@@ -0,0 +1,20 @@
+import client from './client';
+
+export interface BatchCall {
+  id?: string | number;
+  /** API path, e.g. `/api/blocks/42` */
+  method: string;
+  params?: Record<string, string | number | boolean>;
+}
+
+export interface BatchResult<T = unknown> {
+  id: string | number | null;
+  status: number;
+  result?: T;
+  error?: string;
+}
+
+/** Run up to 20 GET calls in one request; results keep the call order. */
+export async function batch(calls: BatchCall[]): Promise<BatchResult[]> {
+  return client.post<BatchResult[]>('/batch', calls);
+}
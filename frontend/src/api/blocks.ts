@@ -29,3 +29,7 @@ export async function getBlockTransactions(
     { params: { page, limit } }
   );
 }
+
+export async function getBlockTransactionByIndex(blockNumber: number, index: number): Promise<Transaction> {
+  return client.get<Transaction>(`/blocks/${blockNumber}/transactions/${index}`);
+}
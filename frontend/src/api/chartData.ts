@@ -26,6 +26,63 @@ export function getDailyTxs(): Promise<DailyTxPoint[]> {
   return client.get<DailyTxPoint[]>('/stats/daily-txs');
 }
 
+// Precomputed per-day summary; wei and gas amounts are decimal strings
+export interface DailyStats {
+  day: string; // YYYY-MM-DD (UTC)
+  block_count: number;
+  tx_count: number;
+  active_addresses: number;
+  new_contracts: number;
+  gas_used: string;
+  avg_gas_price: string | null;
+  new_tokens: number;
+  nft_mints: number;
+}
+
+export function getDailyStats(params: { from?: string; to?: string } = {}): Promise<DailyStats[]> {
+  return client.get<DailyStats[]>('/stats/daily', { params });
+}
+
+// ERC-4337 activity; gas costs are wei decimal strings, rates fractions
+export interface AaPaymasterStats {
+  address: string;
+  user_ops: number;
+  successful_user_ops: number;
+  success_rate: number | null;
+  senders: number;
+  gas_cost: string;
+}
+
+export interface AaBundlerStats {
+  address: string;
+  bundles: number;
+  user_ops: number;
+  successful_user_ops: number;
+  success_rate: number | null;
+  gas_cost: string;
+}
+
+export interface AaStats {
+  from_block: number | null;
+  to_block: number | null;
+  user_ops: number;
+  successful_user_ops: number;
+  success_rate: number | null;
+  sponsored_user_ops: number;
+  senders: number;
+  bundles: number;
+  total_gas_cost: string;
+  sponsored_gas_cost: string;
+  paymasters: AaPaymasterStats[];
+  bundlers: AaBundlerStats[];
+}
+
+export function getAaStats(
+  params: { window?: ChartWindow; from_block?: number; to_block?: number } = {}
+): Promise<AaStats> {
+  return client.get<AaStats>('/stats/aa', { params });
+}
+
 export function getGasPriceChart(window: ChartWindow): Promise<GasPricePoint[]> {
   return client.get<GasPricePoint[]>('/stats/gas-price', { params: { window } });
 }
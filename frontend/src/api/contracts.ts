@@ -1,5 +1,13 @@
 import { API_BASE_URL } from './client';
-import type { ContractDetail, VerifyContractRequest, AbiItem } from '../types';
+import type {
+  ContractAst,
+  ContractDetail,
+  ContractFiles,
+  VerifyContractRequest,
+  AbiItem,
+  MatchStrategy,
+  ImmutableValue,
+} from '../types';
 
 export async function getContractDetail(address: string): Promise<ContractDetail> {
   const res = await fetch(`${API_BASE_URL}/contracts/${address}`);
@@ -10,9 +18,39 @@ export async function getContractDetail(address: string): Promise<ContractDetail
   return res.json();
 }
 
+export async function getContractFiles(address: string): Promise<ContractFiles> {
+  const res = await fetch(`${API_BASE_URL}/contracts/${address}/files`);
+  if (!res.ok) {
+    const data = await res.json().catch(() => ({}));
+    throw { error: data.error ?? res.statusText, status: res.status };
+  }
+  return res.json();
+}
+
+export async function getContractFile(address: string, path: string): Promise<string> {
+  const encoded = path.split('/').map(encodeURIComponent).join('/');
+  const res = await fetch(`${API_BASE_URL}/contracts/${address}/files/${encoded}`);
+  if (!res.ok) {
+    const data = await res.json().catch(() => ({}));
+    throw { error: data.error ?? res.statusText, status: res.status };
+  }
+  return res.text();
+}
+
+export async function getContractAst(address: string): Promise<ContractAst> {
+  const res = await fetch(`${API_BASE_URL}/contracts/${address}/ast`);
+  if (!res.ok) {
+    const data = await res.json().catch(() => ({}));
+    throw { error: data.error ?? res.statusText, status: res.status };
+  }
+  return res.json();
+}
+
 export interface VerifyContractResponse {
   verified: boolean;
   abi: AbiItem[];
+  match_strategy: MatchStrategy;
+  immutable_values: ImmutableValue[];
 }
 
 export async function verifyContract(
@@ -0,0 +1,10 @@
+import client from './client';
+import type { ExportJob, ExportRequest } from '../types';
+
+export async function createExport(request: ExportRequest): Promise<ExportJob> {
+  return client.post<ExportJob>('/export', request);
+}
+
+export async function getExport(id: string): Promise<ExportJob> {
+  return client.get<ExportJob>(`/export/${id}`);
+}
@@ -7,4 +7,6 @@ export * from './logs';
 export * from './search';
 export * from './faucet';
 export * from './contracts';
+export * from './export';
+export * from './batch';
 export { default as client } from './client';
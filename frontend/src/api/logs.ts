@@ -20,6 +20,20 @@ export async function getTransactionDecodedLogs(txHash: string, params: GetTrans
   });
 }
 
+export interface GetBlockLogsParams {
+  page?: number;
+  limit?: number;
+  topic0?: string;
+  address?: string;
+}
+
+export async function getBlockLogs(blockNumber: number, params: GetBlockLogsParams = {}): Promise<PaginatedResponse<DecodedEventLog>> {
+  const { page = 1, limit = 50, ...filters } = params;
+  return client.get<PaginatedResponse<DecodedEventLog>>(`/blocks/${blockNumber}/logs`, {
+    params: { page, limit, ...filters },
+  });
+}
+
 export interface GetAddressLogsParams {
   page?: number;
   limit?: number;
@@ -1,6 +1,26 @@
 import client from './client';
-import type { SearchResponse } from '../types';
+import type { SearchResponse, SearchType } from '../types';
 
-export async function search(query: string): Promise<SearchResponse> {
-  return client.get<SearchResponse>('/search', { params: { q: query } });
+export interface SearchOptions {
+  types?: SearchType[];
+  page?: number;
+  limit?: number;
+  /** Few, cheap results per type for the search-as-you-type dropdown. */
+  suggest?: boolean;
+}
+
+export async function search(
+  query: string,
+  options: SearchOptions = {},
+): Promise<SearchResponse> {
+  const { types, page, limit, suggest } = options;
+  return client.get<SearchResponse>('/search', {
+    params: {
+      q: query,
+      type: types?.length ? types.join(',') : undefined,
+      page,
+      limit,
+      suggest: suggest || undefined,
+    },
+  });
 }
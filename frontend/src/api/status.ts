@@ -5,6 +5,36 @@ export interface HeightResponse {
   indexed_at?: string; // ISO timestamp, absent when no blocks indexed
 }
 
+export interface SchemaStatus {
+  expected_version: number;
+  applied_version: number | null;
+  applied: number[];
+  pending: number[];
+  unknown: number[];
+  checksum_mismatches: number[];
+  failed: number[];
+  compatible: boolean;
+}
+
+export type HealthLevel = "ok" | "degraded" | "down";
+
+export interface ServiceHealth {
+  status: HealthLevel; // worst of the components
+  api: { status: HealthLevel; db_latency_ms: number };
+  indexer: {
+    status: HealthLevel;
+    lag_blocks: number | null;
+    last_indexed_secs_ago: number | null;
+  };
+  rpc: {
+    status: HealthLevel;
+    latency_ms: number | null;
+    head_block: number | null;
+    syncing: boolean | null;
+    error?: string;
+  };
+}
+
 export interface ChainStatusResponse {
   chain_id: string;
   chain_name: string;
@@ -12,6 +42,9 @@ export interface ChainStatusResponse {
   total_transactions: number;
   total_addresses: number;
   indexed_at: string; // ISO timestamp
+  version: string;
+  schema: SchemaStatus;
+  health: ServiceHealth;
 }
 
 export async function getHeight(): Promise<HeightResponse> {
@@ -689,6 +689,9 @@ function VerifiedView({ contract }: VerifiedViewProps) {
         {contract.license_type && (
           <span className="badge-chip">{contract.license_type}</span>
         )}
+        {contract.match_strategy && contract.match_strategy !== 'exact' && (
+          <span className="badge-chip">Match: {contract.match_strategy}</span>
+        )}
         {contract.verified_at && (
           <span className="text-gray-500 text-xs self-center">
             Verified {new Date(contract.verified_at).toLocaleDateString()}
@@ -727,6 +730,27 @@ function VerifiedView({ contract }: VerifiedViewProps) {
         </div>
       )}
 
+      {/* Immutable values */}
+      {contract.immutable_values && contract.immutable_values.length > 0 && (
+        <div>
+          <h3 className="text-sm font-semibold text-gray-300 uppercase tracking-wide mb-2">
+            Immutable Values
+          </h3>
+          <div className="border border-dark-500 divide-y divide-dark-500 text-sm">
+            {contract.immutable_values.map(item => (
+              <div
+                key={item.id}
+                className="px-4 py-2 font-mono text-xs text-gray-200 flex flex-wrap gap-x-2 items-baseline"
+              >
+                {item.type && <span className="text-blue-400 shrink-0">{item.type}</span>}
+                <span className="text-fg font-medium">{item.name ?? `#${item.id}`}</span>
+                <span className="text-gray-400 break-all">{item.decoded ?? item.value}</span>
+              </div>
+            ))}
+          </div>
+        </div>
+      )}
+
       {/* ABI viewer */}
       {contract.abi && contract.abi.length > 0 && (
         <div>
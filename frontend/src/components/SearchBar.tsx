@@ -34,6 +34,8 @@ function getPrimaryText(result: AnySearchResult): string {
       return `NFT ${result.contract_address} #${result.token_id}`;
     case 'nft_collection':
       return `NFT Collection ${result.name || ''}`;
+    case 'label':
+      return `Label ${result.name}`;
   }
 }
 
@@ -52,6 +54,8 @@ function getSecondaryText(result: AnySearchResult): string {
       return result.name || 'NFT';
     case 'nft_collection':
       return result.address;
+    case 'label':
+      return result.address;
   }
 }
 
@@ -137,6 +141,9 @@ export default function SearchBar({ variant = 'hero' }: SearchBarProps) {
       case 'nft_collection':
         navigate(`/nfts/${r.address}`);
         break;
+      case 'label':
+        navigate(`/address/${r.address}`);
+        break;
       default:
         break;
     }
@@ -165,7 +172,7 @@ export default function SearchBar({ variant = 'hero' }: SearchBarProps) {
         if (abortRef.current) abortRef.current.abort();
         const controller = new AbortController();
         abortRef.current = controller;
-        const res = await apiSearch(q);
+        const res = await apiSearch(q, { suggest: true });
         setSuggestions(res.results || []);
         setHighlight(-1);
       } catch {
@@ -11,7 +11,7 @@ import {
   Tooltip,
   ResponsiveContainer,
 } from 'recharts';
-import { getChainStatus, type ChainStatusResponse } from '../api/status';
+import { getChainStatus, type ChainStatusResponse, type HealthLevel } from '../api/status';
 import { type ChartWindow } from '../api/chartData';
 import { formatNumber } from '../utils';
 import { EntityHeroVisual, Loading, PageHero, SectionPanel, StatCard } from '../components';
@@ -106,6 +106,30 @@ export default function StatusPage() {
             <StatusStat label="Total Transactions" value={status ? formatNumber(status.total_transactions) : '—'} />
             <StatusStat label="Total Addresses" value={status ? formatNumber(status.total_addresses) : '—'} />
             <StatusStat label="Last Indexed" value={lastIndexed} />
+            {status?.health && (
+              <>
+                <StatusStat
+                  label="API"
+                  value={`${healthLabel(status.health.api.status)} · ${status.health.api.db_latency_ms} ms`}
+                />
+                <StatusStat
+                  label="Indexer"
+                  value={
+                    status.health.indexer.lag_blocks !== null
+                      ? `${healthLabel(status.health.indexer.status)} · ${formatNumber(status.health.indexer.lag_blocks)} blocks behind`
+                      : healthLabel(status.health.indexer.status)
+                  }
+                />
+                <StatusStat
+                  label="RPC Node"
+                  value={
+                    status.health.rpc.latency_ms !== null
+                      ? `${healthLabel(status.health.rpc.status)} · ${status.health.rpc.latency_ms} ms${status.health.rpc.syncing ? ' · syncing' : ''}`
+                      : healthLabel(status.health.rpc.status)
+                  }
+                />
+              </>
+            )}
           </div>
         )}
       </SectionPanel>
@@ -256,6 +280,17 @@ export default function StatusPage() {
 
 // ─── sub-components ───────────────────────────────────────────────────────────
 
+function healthLabel(level: HealthLevel): string {
+  switch (level) {
+    case 'ok':
+      return 'Operational';
+    case 'degraded':
+      return 'Degraded';
+    case 'down':
+      return 'Down';
+  }
+}
+
 interface StatusStatProps {
   label: string;
   value: string;
@@ -116,8 +116,23 @@ export default function TokenDetailPage() {
             {token.symbol}
           </span>
         )}
+        {token?.verified && (
+          <span className="badge-chip text-green-400">Verified</span>
+        )}
+        {token?.flagged && (
+          <span className="badge-chip text-red-400">Flagged</span>
+        )}
       </div>
 
+      {token?.duplicate_of && (
+        <div className="card mb-6 text-sm text-yellow-400">
+          Another token uses this symbol and is more likely the real one:{' '}
+          <Link to={`/tokens/${token.duplicate_of}`} className="address">
+            {truncateHash(token.duplicate_of, 8, 6)}
+          </Link>
+        </div>
+      )}
+
       {/* Overview Cards */}
       <div className="grid grid-cols-1 md:grid-cols-2 lg:grid-cols-4 gap-4 mb-8">
         <div className="card">
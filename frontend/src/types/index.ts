@@ -40,6 +40,21 @@ export interface Transaction {
   status: boolean;
   contract_created: string | null;
   timestamp: number;
+  // Only on GET /transactions/:hash
+  fee?: TransactionFee;
+}
+
+// Amounts in wei; l1_* fields are null outside rollups that report an L1 data fee
+export interface TransactionFee {
+  execution_fee: string;
+  l1_fee: string | null;
+  total_fee: string;
+  l1_gas_used: number | null;
+  l1_gas_price: string | null;
+  l1_blob_base_fee: string | null;
+  l1_fee_scalar: string | null;
+  l1_base_fee_scalar: number | null;
+  l1_blob_base_fee_scalar: number | null;
 }
 
 // Address types
@@ -53,6 +68,105 @@ export interface Address {
   symbol?: string | null;
   total_supply?: string | null;
   decimals?: number; // for erc20
+  // First native-value funder and deployer, when known (address detail only)
+  funded_by?: AddressProvenance;
+  created_by?: AddressProvenance;
+}
+
+export interface AddressProvenance {
+  address: string;
+  block_number: number;
+  tx_hash: string;
+}
+
+// Contract created directly by an address (GET /addresses/:address/deployed-contracts)
+export interface DeployedContract {
+  address: string;
+  block_number: number;
+  tx_hash: string;
+  verified: boolean;
+  contract_name: string | null;
+  verified_at: string | null;
+}
+
+export interface NonceGap {
+  from: number;
+  to: number;
+}
+
+export interface NonceTransaction {
+  nonce: number;
+  hash: string;
+  block_number: number;
+  status: boolean;
+  timestamp: number;
+}
+
+export interface AddressNonces {
+  address: string;
+  nonce_count: number;
+  unknown_nonce_count: number;
+  lowest_nonce: number | null;
+  highest_nonce: number | null;
+  next_nonce: number | null;
+  gaps: NonceGap[];
+  transactions: PaginatedResponse<NonceTransaction>;
+}
+
+export type TokenFlowPeriod = '24h' | '7d' | '30d' | '90d' | '1y' | 'all';
+
+// Raw token amounts; net is negative when more left than arrived
+export interface TokenFlow {
+  contract_address: string;
+  name: string | null;
+  symbol: string | null;
+  decimals: number | null;
+  inbound: string;
+  outbound: string;
+  net: string;
+  inbound_transfers: number;
+  outbound_transfers: number;
+}
+
+export interface AddressTokenFlows {
+  address: string;
+  period: TokenFlowPeriod;
+  from_timestamp: number | null;
+  to_timestamp: number | null;
+  tokens: TokenFlow[];
+}
+
+// Raw allowance as of the token's latest Approval event
+export interface ActiveAllowance {
+  contract_address: string;
+  name: string | null;
+  symbol: string | null;
+  decimals: number | null;
+  value: string;
+  unlimited: boolean;
+  token_flagged: boolean;
+  block_number: number;
+  tx_hash: string;
+  timestamp: number | null;
+}
+
+export type ApprovalRisk = 'high' | 'medium' | 'low';
+
+export interface SpenderRisk {
+  spender: string;
+  label?: string;
+  is_contract: boolean | null;
+  risk: ApprovalRisk;
+  flags: string[];
+  last_active_block: number | null;
+  last_active_timestamp: number | null;
+  allowances: ActiveAllowance[];
+}
+
+export interface ApprovalRisks {
+  owner: string;
+  spenders: SpenderRisk[];
+  truncated: boolean;
 }
 
 // NFT types
@@ -62,6 +176,8 @@ export interface NftContract {
   symbol: string | null;
   total_supply: number | null;
   first_seen_block: number;
+  // Admin-set logo; absent where the endpoint does not return it
+  logo_url?: string | null;
 }
 
 export interface NftToken {
@@ -117,10 +233,14 @@ export interface PaginatedResponse<T> {
   limit: number;
   total: number;
   total_pages: number;
+  /** Set when `total` is a planner estimate (e.g. address history). */
+  total_is_estimate?: boolean;
+  /** Set by endpoints that probe for a next page. */
+  has_more?: boolean;
 }
 
 export interface SearchResult {
-  type: "block" | "transaction" | "address" | "nft" | "nft_collection";
+  type: "block" | "transaction" | "address" | "nft" | "nft_collection" | "erc20_token" | "label";
 }
 
 export interface BlockSearchResult extends SearchResult {
@@ -177,16 +297,40 @@ export interface NftCollectionSearchResult extends SearchResult {
   image_url?: string | null;
 }
 
+export interface Erc20TokenSearchResult extends SearchResult, Token {
+  type: "erc20_token";
+}
+
+export interface LabelSearchResult extends SearchResult {
+  type: "label";
+  address: string;
+  name: string;
+  tags: string[];
+}
+
 export type AnySearchResult =
   | BlockSearchResult
   | TransactionSearchResult
   | AddressSearchResult
   | NftSearchResult
-  | NftCollectionSearchResult;
+  | NftCollectionSearchResult
+  | Erc20TokenSearchResult
+  | LabelSearchResult;
+
+export type SearchType = "block" | "tx" | "address" | "token" | "nft" | "label";
+
+export interface SearchTypePage {
+  type: SearchType;
+  page: number;
+  limit: number;
+  has_more: boolean;
+}
 
 export interface SearchResponse {
   results: AnySearchResult[];
   query: string;
+  /** Paging per text-searched type; absent in suggest mode. */
+  pages?: SearchTypePage[];
 }
 
 export interface ApiError {
@@ -206,17 +350,58 @@ export interface FaucetRequestResponse {
   tx_hash: string;
 }
 
+// Bulk export jobs (POST /export)
+export type ExportEntity = "transactions" | "erc20_transfers" | "nft_transfers" | "logs";
+
+export interface ExportRequest {
+  entity: ExportEntity;
+  format?: "csv" | "jsonl";
+  address?: string;
+  from_block?: number;
+  to_block?: number;
+}
+
+export interface ExportJob {
+  id: string;
+  status: "queued" | "running" | "completed" | "failed";
+  entity: ExportEntity;
+  format: "csv" | "jsonl";
+  address: string | null;
+  from_block: number | null;
+  to_block: number | null;
+  row_count: number | null;
+  file_size: number | null;
+  error: string | null;
+  created_at: string;
+  started_at: string | null;
+  completed_at: string | null;
+  expires_at: string | null;
+  // Relative to the site root; set once completed
+  download_url: string | null;
+}
+
 // ERC-20 Token types
-export interface Token {
+
+// How a token stands among tokens sharing its symbol
+export interface TokenStanding {
+  verified: boolean;
+  flagged: boolean;
+  duplicate_of: string | null;
+}
+
+export interface Token extends TokenStanding {
   address: string;
   name: string | null;
   symbol: string | null;
   decimals: number;
   total_supply: string | null;
   first_seen_block: number;
+  // Admin-set logo; absent where the endpoint does not return it
+  logo_url?: string | null;
 }
 
 export interface TokenHolder {
+  rank: number;
   address: string;
   balance: string;
   percentage: number;
@@ -234,7 +419,7 @@ export interface TokenTransfer {
   timestamp: number;
 }
 
-export interface AddressTokenBalance {
+export interface AddressTokenBalance extends TokenStanding {
   contract_address: string;
   name: string | null;
   symbol: string | null;
@@ -355,9 +540,48 @@ export interface ContractDetail {
   license_type?: string;
   is_multi_file?: boolean;
   source_files?: Record<string, string>;
+  match_strategy?: MatchStrategy;
+  immutable_values?: ImmutableValue[];
+  constructor_arguments?: ConstructorArgument[];
   verified_at?: string;
 }
 
+export interface ConstructorArgument {
+  name: string;
+  type: string;
+  /** Integers as decimal strings, addresses/bytes as hex, arrays and structs as arrays */
+  value: unknown;
+}
+
+export interface ContractFiles {
+  address: string;
+  is_multi_file: boolean;
+  files: ContractFileEntry[];
+}
+
+export interface ContractFileEntry {
+  path: string;
+  size: number;
+}
+
+export interface ContractAst {
+  address: string;
+  contract_name: string | null;
+  /** solc compressed source map of the runtime bytecode */
+  source_map: string | null;
+  sources: Record<string, { id: number; ast: Record<string, unknown> }>;
+}
+
+export type MatchStrategy = 'exact' | 'immutables' | 'metadata';
+
+export interface ImmutableValue {
+  id: number;
+  name: string | null;
+  type: string | null;
+  value: string;
+  decoded: string | null;
+}
+
 export interface VerifyContractRequest {
   source_code?: string;
   standard_json_input?: string;
@@ -368,4 +592,5 @@ export interface VerifyContractRequest {
   constructor_args?: string;
   evm_version?: string;
   license_type?: string;
+  match_strategies?: MatchStrategy[];
 }